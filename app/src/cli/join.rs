@@ -5,7 +5,10 @@ use ambient_native_std::{
 use ambient_network::native::client::ResolvedAddr;
 use clap::Parser;
 
-use crate::{client, server::QUIC_INTERFACE_PORT};
+use crate::{
+    client::{self, JoinTarget},
+    server::QUIC_INTERFACE_PORT,
+};
 
 use super::ClientCli;
 
@@ -16,9 +19,17 @@ pub struct Join {
     pub client: ClientCli,
     /// The server to connect to; defaults to localhost
     pub host: Option<String>,
+    /// Pick the server to connect to from those listed by the directory at this URL
+    #[arg(long, conflicts_with = "host")]
+    pub directory: Option<String>,
 }
 
 pub fn handle(args: &Join, rt: &tokio::runtime::Runtime, assets: AssetCache) -> anyhow::Result<()> {
+    if let Some(directory) = &args.directory {
+        let target = JoinTarget::Directory(directory.clone());
+        return client::run(rt, assets, target, &args.client, None);
+    }
+
     let assets_ref = &assets;
     let server_addr = rt.block_on(async move {
         let Some(mut host) = args.host.as_ref().cloned() else {
@@ -40,5 +51,5 @@ pub fn handle(args: &Join, rt: &tokio::runtime::Runtime, assets: AssetCache) ->
         }
         ResolvedAddr::lookup_host(&host).await
     })?;
-    client::run(rt, assets, server_addr, &args.client, None)
+    client::run(rt, assets, server_addr.into(), &args.client, None)
 }
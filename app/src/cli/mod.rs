@@ -6,6 +6,7 @@ pub mod assets;
 pub mod join;
 pub mod login;
 pub mod package;
+pub mod rcon;
 
 mod package_path;
 pub use package_path::*;
@@ -16,6 +17,7 @@ use self::{
     package::{
         build::Build, deploy::Deploy, new::New, run::Run, serve::Serve, Package, PackageArgs,
     },
+    rcon::Rcon,
 };
 
 #[derive(Parser, Clone)]
@@ -34,6 +36,7 @@ pub enum Commands {
     Deploy(Deploy),
     Serve(Serve),
     Join(Join),
+    Rcon(Rcon),
     Package {
         #[command(subcommand)]
         package: Package,
@@ -86,6 +89,10 @@ pub struct ClientCli {
     #[clap(short, long)]
     pub user_id: Option<String>,
 
+    /// A token vouching for the user ID, for servers which authenticate their players
+    #[arg(long)]
+    pub auth_token: Option<String>,
+
     /// Allows connecting to servers with a mismatched version. Only available in non-production builds.
     ///
     /// DO NOT USE THIS UNLESS YOU KNOW WHAT YOU ARE DOING.
@@ -125,6 +132,7 @@ impl Cli {
             Commands::Deploy(Deploy { package, .. }) => Some(package),
             Commands::Serve(Serve { package, .. }) => Some(package),
             Commands::Join(Join { .. }) => None,
+            Commands::Rcon(Rcon { .. }) => None,
             Commands::Assets { .. } => None,
             Commands::Login => None,
         }
@@ -139,7 +147,12 @@ impl Cli {
             C::Run(Run { package, .. }) | C::Build(Build { package, .. }) => {
                 package.is_release().unwrap_or(false)
             }
-            C::New(_) | C::Join(_) | C::Assets { .. } | C::Package { .. } | C::Login => false,
+            C::New(_)
+            | C::Join(_)
+            | C::Rcon(_)
+            | C::Assets { .. }
+            | C::Package { .. }
+            | C::Login => false,
         }
     }
 }
@@ -1,7 +1,11 @@
-use std::{collections::HashSet, future::Future, path::PathBuf};
+use std::{collections::HashSet, future::Future, path::PathBuf, sync::Arc};
 
 use ambient_build::BuildResult;
-use ambient_native_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl};
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    bundle::{parse_public_key, TrustedBundleKeysKey},
+};
 use ambient_package::BuildSettings;
 use ambient_package_semantic::RetrievableFile;
 
@@ -15,6 +19,14 @@ use super::PackageArgs;
 pub struct Build {
     #[command(flatten)]
     pub package: PackageArgs,
+
+    /// Pack the built packages into a single signed bundle, `build/<package id>.ambient-bundle`, which can be run instead of the package
+    #[arg(long)]
+    pub bundle: bool,
+
+    /// The Ed25519 key, as a PKCS#8 document, to sign the bundle with; defaults to a key generated the first time a bundle is built
+    #[arg(long, requires = "bundle")]
+    pub bundle_key: Option<PathBuf>,
 }
 
 pub struct BuildDirectories {
@@ -24,6 +36,10 @@ pub struct BuildDirectories {
     pub main_package_path: AbsAssetUrl,
     /// The name of the main package being built.
     pub main_package_name: String,
+    /// The local packages that were built, including the dependencies of the main package.
+    pub local_packages: Vec<LocalPackage>,
+    /// The bundle the packages were unpacked from, if they were.
+    pub bundle: Option<AbsAssetUrl>,
 }
 impl BuildDirectories {
     pub fn new_with_same_paths(path: AbsAssetUrl) -> Self {
@@ -31,16 +47,48 @@ impl BuildDirectories {
             build_root_path: path.clone(),
             main_package_name: "Remote package".to_string(),
             main_package_path: path,
+            local_packages: vec![],
+            bundle: None,
         }
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct LocalPackage {
+    /// The directory of the package, containing its `ambient.toml`.
+    pub path: PathBuf,
+    /// The directory the package was built to.
+    pub build_path: PathBuf,
+}
+
 pub async fn handle(
     build: &Build,
     assets: &AssetCache,
     release_build: bool,
 ) -> anyhow::Result<BuildDirectories> {
-    handle_inner(&build.package, assets, release_build).await
+    let dirs = handle_inner(&build.package, assets, release_build).await?;
+
+    if build.bundle {
+        let (Some(root_build_path), Some(main_package_build_path), false) = (
+            dirs.build_root_path.to_file_path()?,
+            dirs.main_package_path.to_file_path()?,
+            dirs.local_packages.is_empty(),
+        ) else {
+            anyhow::bail!("Only local packages that are built can be bundled");
+        };
+
+        ambient_build::bundle::write_bundle(
+            &root_build_path,
+            &main_package_build_path,
+            &dirs.main_package_name,
+            dirs.local_packages
+                .iter()
+                .map(|package| package.build_path.as_path()),
+            build.bundle_key.as_deref(),
+        )?;
+    }
+
+    Ok(dirs)
 }
 
 pub async fn handle_inner(
@@ -50,6 +98,33 @@ pub async fn handle_inner(
 ) -> anyhow::Result<BuildDirectories> {
     let main_package_path = package_cli.package_path()?;
 
+    if main_package_path.is_bundle() {
+        // The bundles signed with the default key were built here
+        let mut trusted_keys = package_cli
+            .trusted_bundle_keys
+            .iter()
+            .map(|key| parse_public_key(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        trusted_keys.extend(ambient_build::bundle::default_public_key()?);
+        if trusted_keys.is_empty() {
+            anyhow::bail!(
+                "No key to trust the bundle with; pass the public key it was signed with to --trusted-bundle-key"
+            );
+        }
+        TrustedBundleKeysKey.insert(assets, Arc::new(trusted_keys));
+
+        // Bundles are already built, and are unpacked so that the server can load their packages
+        let (bundle, path) =
+            ambient_build::bundle::unpack_bundle(assets, &main_package_path.url).await?;
+        return Ok(BuildDirectories {
+            build_root_path: AbsAssetUrl::from_directory_path(&path),
+            main_package_path: AbsAssetUrl::from_directory_path(path.join(bundle.main_package())),
+            main_package_name: bundle.main_package_name().to_string(),
+            local_packages: vec![],
+            bundle: Some(main_package_path.url),
+        });
+    }
+
     if package_cli.no_build {
         return Ok(BuildDirectories::new_with_same_paths(
             main_package_path.url.clone(),
@@ -62,6 +137,7 @@ pub async fn handle_inner(
         ));
     };
 
+    package_cli.apply_build_cache(assets);
     let build_wasm_only = package_cli.build_wasm_only;
     let clean_build = package_cli.clean_build;
 
@@ -154,6 +230,7 @@ pub async fn build<
     // the correct dependencies after they have been deployed (if necessary).
     let mut output_path = root_build_path.clone();
     let mut output_package_name = String::new();
+    let mut local_packages = vec![];
     while let Some(manifest_path) = queue.pop() {
         pre_build(manifest_path.clone()).await?;
 
@@ -171,6 +248,13 @@ pub async fn build<
 
         post_build(manifest_path.clone(), build_path.clone(), was_built).await?;
 
+        if let Some(path) = manifest_path.parent() {
+            local_packages.push(LocalPackage {
+                path: path.to_owned(),
+                build_path: build_path.clone(),
+            });
+        }
+
         if AbsAssetUrl::from_file_path(manifest_path) == main_manifest_url {
             output_path = build_path;
             output_package_name = package_name;
@@ -181,5 +265,7 @@ pub async fn build<
         build_root_path: AbsAssetUrl::from_file_path(root_build_path),
         main_package_path: AbsAssetUrl::from_file_path(output_path),
         main_package_name: output_package_name,
+        local_packages,
+        bundle: None,
     })
 }
@@ -109,6 +109,8 @@ pub async fn handle(args: &Deploy, assets: &AssetCache, release_build: bool) ->
         all_packages
     };
 
+    package.apply_build_cache(assets);
+
     let mut first_deployment_id = None;
     for package_path in all_package_paths {
         let skip_building = manifest_path_to_deployment_id
@@ -3,7 +3,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use ambient_native_std::asset_cache::AssetCache;
+use ambient_build::pipelines::cache::BuildCacheDirKey;
+use ambient_native_std::asset_cache::{AssetCache, SyncAssetKeyExt};
 use ambient_package::PackageId;
 use anyhow::Context;
 use clap::{Args, Subcommand};
@@ -58,6 +59,10 @@ pub struct PackageArgs {
     /// Perform a clean build
     pub clean_build: bool,
 
+    #[arg(long)]
+    /// Process all the assets, instead of copying the outputs of the pipelines whose inputs are unchanged from the build cache
+    pub no_build_cache: bool,
+
     #[arg(long)]
     /// Only build the WASM modules
     pub build_wasm_only: bool,
@@ -65,6 +70,10 @@ pub struct PackageArgs {
     #[arg(long)]
     /// Open docs after building
     pub open_docs: bool,
+
+    #[arg(long = "trusted-bundle-key")]
+    /// Run bundles signed with this public key, in hexadecimal, on top of the ones signed with the default key; can be given several times
+    pub trusted_bundle_keys: Vec<String>,
 }
 impl PackageArgs {
     pub fn is_release(&self) -> Option<bool> {
@@ -82,6 +91,13 @@ impl PackageArgs {
     pub fn package_path(&self) -> anyhow::Result<PackagePath> {
         self.path.clone().try_into()
     }
+
+    /// Disables the build cache for the builds with `assets` if `--no-build-cache` was passed
+    pub fn apply_build_cache(&self, assets: &AssetCache) {
+        if self.no_build_cache {
+            BuildCacheDirKey.insert(assets, None);
+        }
+    }
 }
 
 #[derive(Args, Clone, Debug)]
@@ -131,6 +147,93 @@ pub struct HostCli {
     /// Shutdown the server after the specified number of seconds of inactivity
     #[arg(long)]
     pub shutdown_after_inactivity_seconds: Option<u64>,
+
+    /// Simulate physics deterministically, stepping it exactly once per server tick
+    ///
+    /// This is needed for lockstep multiplayer and replay verification
+    #[arg(long)]
+    pub deterministic_physics: bool,
+
+    /// Record the replicated state and the networked messages of the server to a replay file
+    #[arg(long)]
+    pub record_replay: Option<PathBuf>,
+
+    /// Play a replay file back instead of running the package, for clients to spectate
+    #[arg(long, conflicts_with = "record_replay")]
+    pub replay: Option<PathBuf>,
+
+    /// Authenticate players with HS256 JSON web tokens signed with the secret in this file
+    #[arg(long)]
+    pub auth_jwt_secret: Option<PathBuf>,
+
+    /// Authenticate players with HMAC tokens signed with the secret in this file
+    #[arg(long, conflicts_with = "auth_jwt_secret")]
+    pub auth_hmac_secret: Option<PathBuf>,
+
+    /// Only accept authentication tokens issued by this issuer
+    #[arg(long)]
+    pub auth_issuer: Option<String>,
+
+    /// Only accept authentication tokens intended for this audience
+    #[arg(long)]
+    pub auth_audience: Option<String>,
+
+    /// Keep the players whose connection dropped for the specified number of seconds, during
+    /// which their clients can reconnect and resume where they left off
+    #[arg(long)]
+    pub session_grace_seconds: Option<u64>,
+
+    /// Broadcast the changes to the world to the players at this rate, in Hz, coalescing the
+    /// changes of the ticks in between. Lower rates save bandwidth and CPU, without changing the
+    /// tick rate of the simulation
+    ///
+    /// Defaults to the tick rate, 60 Hz
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub broadcast_rate: Option<u32>,
+
+    /// Register the server with the directory at this URL, for clients to discover it
+    #[arg(long)]
+    pub directory: Option<String>,
+
+    /// The name the server is listed under in the directory
+    ///
+    /// Defaults to the name of the main package
+    #[arg(long, requires("directory"))]
+    pub directory_name: Option<String>,
+
+    /// The map the server is listed as playing in the directory
+    #[arg(long, requires("directory"))]
+    pub directory_map: Option<String>,
+
+    /// Act as a directory, serving the listings of the servers registered with it on the HTTP
+    /// interface
+    #[arg(long)]
+    pub serve_directory: bool,
+
+    /// Serve the metrics of the server, such as its tick time, entity counts and players, in the
+    /// Prometheus text format on the `/metrics` route of the HTTP interface
+    #[arg(long)]
+    pub metrics: bool,
+
+    /// Hand off entities to, and accept them from, the servers sharing the secret in this file,
+    /// on the `/handoff` route of the HTTP interface
+    #[arg(long)]
+    pub handoff_secret: Option<PathBuf>,
+
+    /// Serve a remote console for admins presenting the secret in this file, on the `/rcon` route
+    /// of the HTTP interface. Requires `--use-https`, as the secret is sent with every request
+    #[arg(long, requires("use_https"))]
+    pub rcon_secret: Option<PathBuf>,
+
+    /// Save the players banned through the remote console to this JSON file, and refuse the ones
+    /// already in it
+    #[arg(long)]
+    pub bans: Option<PathBuf>,
+
+    /// Rebuild the assets of the local packages when their source files change, and reload them
+    /// in the running game without restarting it
+    #[arg(long)]
+    pub watch: bool,
 }
 
 pub fn handle(
@@ -33,7 +33,7 @@ pub fn handle(
     client::run(
         rt,
         assets,
-        server_handle.resolve_as_localhost(),
+        server_handle.resolve_as_localhost().into(),
         &args.run,
         package_path.fs_path,
     )
@@ -40,6 +40,8 @@ pub async fn handle_inner(
         build_root_path,
         main_package_path,
         main_package_name: _,
+        local_packages,
+        bundle,
     } = build::handle_inner(package, &assets, release_build).await?;
 
     let manifest = match main_package_path
@@ -58,17 +60,29 @@ pub async fn handle_inner(
         .to_file_path()?
         .unwrap_or(std::env::current_dir()?);
 
+    let build_root_fs_path = build_root_path.to_file_path()?;
+
     let server_handle = server::start(
-        assets,
+        assets.clone(),
         host,
         build_root_path,
         main_package_path,
+        bundle,
         working_directory,
         manifest,
         crypto,
     )
     .await;
 
+    if host.watch {
+        match build_root_fs_path {
+            Some(build_root_path) if !local_packages.is_empty() => server_handle
+                .watch_assets(assets, build_root_path, local_packages)
+                .context("Failed to watch the assets")?,
+            _ => tracing::warn!("Only the assets of local packages that are built can be watched"),
+        }
+    }
+
     Ok(server_handle)
 }
 
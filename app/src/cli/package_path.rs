@@ -1,4 +1,4 @@
-use ambient_native_std::asset_url::AbsAssetUrl;
+use ambient_native_std::{asset_url::AbsAssetUrl, bundle::BUNDLE_EXTENSION};
 use anyhow::Context;
 use std::{path::PathBuf, str::FromStr};
 
@@ -19,6 +19,15 @@ impl PackagePath {
             ambient_std::path::normalize(&current_dir.join(path))
         };
 
+        if path.is_file()
+            && path
+                .extension()
+                .map_or(false, |ext| ext == BUNDLE_EXTENSION)
+        {
+            // Bundles are run from their file, without being built
+            let url = AbsAssetUrl::from_file_path(path);
+            return Ok(Self { url, fs_path: None });
+        }
         if path.exists() && !path.is_dir() {
             anyhow::bail!("Package path {path:?} exists and is not a directory.");
         }
@@ -31,6 +40,10 @@ impl PackagePath {
     pub fn is_remote(&self) -> bool {
         self.fs_path.is_none()
     }
+
+    pub fn is_bundle(&self) -> bool {
+        self.url.extension_is(BUNDLE_EXTENSION)
+    }
 }
 
 impl TryFrom<Option<String>> for PackagePath {
@@ -44,6 +57,12 @@ impl TryFrom<Option<String>> for PackagePath {
                     || package_path.starts_with("file:/") =>
             {
                 let url = AbsAssetUrl::from_str(&package_path)?;
+                if url.extension_is(BUNDLE_EXTENSION) {
+                    if let Some(local) = url.to_file_path()? {
+                        return Self::new_local(local);
+                    }
+                    return Ok(Self { url, fs_path: None });
+                }
                 if url.extension().is_some() {
                     anyhow::bail!("Package path must be a directory");
                 }
@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use ambient_native_std::asset_cache::AssetCache;
+use ambient_network::rcon::{self, RconRequest, RconResponse};
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Clone, Debug)]
+/// Administer a running server through its remote console
+pub struct Rcon {
+    /// The URL of the HTTP interface of the server
+    pub endpoint: String,
+    /// The file holding the secret of the remote console of the server
+    #[arg(long)]
+    pub secret: PathBuf,
+    #[command(subcommand)]
+    pub command: RconCommand,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum RconCommand {
+    /// List the connected players
+    Players,
+    /// Disconnect a player
+    Kick {
+        user_id: String,
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// Disconnect a player, and refuse them until they are unbanned
+    Ban {
+        user_id: String,
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// Let a banned player connect again
+    Unban { user_id: String },
+    /// List the modules of the packages running on the server
+    Modules,
+    /// Enable a module of a package
+    Enable { package: String, module: String },
+    /// Disable a module of a package
+    Disable { package: String, module: String },
+    /// List the admin commands registered by the server modules
+    Commands,
+    /// Run an admin command registered by a server module
+    Run { name: String, args: Vec<String> },
+}
+
+impl RconCommand {
+    fn request(&self) -> RconRequest {
+        match self.clone() {
+            RconCommand::Players => RconRequest::ListPlayers,
+            RconCommand::Kick { user_id, reason } => RconRequest::Kick { user_id, reason },
+            RconCommand::Ban { user_id, reason } => RconRequest::Ban { user_id, reason },
+            RconCommand::Unban { user_id } => RconRequest::Unban { user_id },
+            RconCommand::Modules => RconRequest::ListModules,
+            RconCommand::Enable { package, module } => RconRequest::SetModuleEnabled {
+                package,
+                module,
+                enabled: true,
+            },
+            RconCommand::Disable { package, module } => RconRequest::SetModuleEnabled {
+                package,
+                module,
+                enabled: false,
+            },
+            RconCommand::Commands => RconRequest::ListCommands,
+            RconCommand::Run { name, args } => RconRequest::Run {
+                name,
+                args: args.join(" "),
+            },
+        }
+    }
+}
+
+pub async fn handle(args: &Rcon, assets: &AssetCache) -> anyhow::Result<()> {
+    let secret = std::fs::read_to_string(&args.secret).with_context(|| {
+        format!(
+            "Failed to read remote console secret {}",
+            args.secret.display()
+        )
+    })?;
+
+    let response = rcon::send(
+        assets,
+        &args.endpoint,
+        secret.trim(),
+        &args.command.request(),
+    )
+    .await?;
+
+    match response {
+        RconResponse::Done => {}
+        RconResponse::Players(players) => {
+            for player in players {
+                let entity = player.entity.map(|id| id.to_string()).unwrap_or_default();
+                println!("{}\t{}\t{entity}", player.user_id, player.instance);
+            }
+        }
+        RconResponse::Modules(modules) => {
+            for module in modules {
+                let side = if module.on_server { "server" } else { "client" };
+                let state = if module.enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                };
+                println!("{}/{}\t{side}\t{state}", module.package, module.name);
+            }
+        }
+        RconResponse::Commands(commands) => {
+            for (name, description) in commands {
+                println!("{name}\t{description}");
+            }
+        }
+        RconResponse::Output(output) => println!("{output}"),
+    }
+
+    Ok(())
+}
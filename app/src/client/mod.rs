@@ -1,7 +1,7 @@
 use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 
 use ambient_app::{fps_stats, window_title, AppBuilder};
-use ambient_audio::{AudioMixer, AudioStream};
+use ambient_audio::{AudioBuses, AudioStream, MASTER_BUS, MUSIC_BUS, SFX_BUS, VOICE_BUS};
 use ambient_cameras::UICamera;
 use ambient_client_shared::game_view::GameView;
 use ambient_core::{
@@ -19,12 +19,13 @@ use ambient_native_std::{
 };
 use ambient_network::{
     client::{client_network_stats, GameClientRenderTarget},
+    directory::ServerBrowser,
     hooks::use_remote_resource,
     native::client::{ClientView, ResolvedAddr},
 };
 use ambient_settings::SettingsKey;
 use ambient_sys::time::Instant;
-use ambient_ui_native::{Dock, WindowSized};
+use ambient_ui_native::{ui::notification::NotificationCenter, Dock, WindowSized};
 use glam::uvec2;
 
 use crate::{
@@ -34,14 +35,30 @@ use crate::{
 
 mod wasm;
 
+/// The server the client joins
+#[derive(Debug, Clone)]
+pub enum JoinTarget {
+    Server(ResolvedAddr),
+    /// Let the user pick one of the servers listed by the directory at this URL
+    Directory(String),
+}
+
+impl From<ResolvedAddr> for JoinTarget {
+    fn from(addr: ResolvedAddr) -> Self {
+        Self::Server(addr)
+    }
+}
+
 /// Construct an app and enter the main client view
 pub fn run(
     rt: &tokio::runtime::Runtime,
     assets: AssetCache,
-    server_addr: ResolvedAddr,
+    target: JoinTarget,
     args: &ClientCli,
     golden_image_output_dir: Option<PathBuf>,
 ) -> anyhow::Result<()> {
+    let settings = SettingsKey.get(&assets);
+
     let audio_stream = if !args.mute_audio {
         match AudioStream::new() {
             Ok(v) => Some(v),
@@ -53,8 +70,19 @@ pub fn run(
     } else {
         None
     };
-    let mixer = audio_stream.as_ref().map(|v| v.mixer().clone());
-    let settings = SettingsKey.get(&assets);
+    let buses = audio_stream.as_ref().map(|v| v.buses().clone());
+    if let Some(buses) = &buses {
+        let audio = &settings.audio;
+        for (name, volume) in [
+            (MASTER_BUS, audio.master_volume),
+            (MUSIC_BUS, audio.music_volume),
+            (SFX_BUS, audio.sfx_volume),
+            (VOICE_BUS, audio.voice_volume),
+        ] {
+            buses.get_or_create(name).set_gain(volume);
+        }
+        buses.master().set_muted(audio.muted);
+    }
 
     let user_id = match args.user_id.clone().or(settings.general.user_id) {
         Some(user_id) => user_id,
@@ -123,14 +151,15 @@ pub fn run(
     let fail_on_version_mismatch = !args.dev_allow_version_mismatch;
 
     MainApp {
-        server_addr,
+        target,
         user_id,
+        token: args.auth_token.clone(),
         fail_on_version_mismatch,
         show_debug: is_debug,
         golden_image_cmd: args.golden_image,
         golden_image_output_dir,
         cert,
-        mixer,
+        buses,
     }
     .el()
     .spawn_interactive(&mut app.world);
@@ -178,16 +207,47 @@ fn TitleUpdater(hooks: &mut Hooks) -> Element {
 #[element_component]
 fn MainApp(
     hooks: &mut Hooks,
-    server_addr: ResolvedAddr,
+    target: JoinTarget,
     golden_image_output_dir: Option<PathBuf>,
     user_id: String,
+    token: Option<String>,
     fail_on_version_mismatch: bool,
     show_debug: bool,
     golden_image_cmd: Option<GoldenImageCommand>,
     cert: Option<Vec<u8>>,
-    mixer: Option<AudioMixer>,
+    buses: Option<AudioBuses>,
 ) -> Element {
     let (loaded, set_loaded) = use_state(hooks, false);
+    let (server_addr, set_server_addr) = use_state_with(hooks, |_| match &target {
+        JoinTarget::Server(addr) => Some(addr.clone()),
+        JoinTarget::Directory(_) => None,
+    });
+
+    let Some(server_addr) = server_addr else {
+        let JoinTarget::Directory(endpoint) = target else {
+            unreachable!("only the server of a directory is picked");
+        };
+        let runtime = hooks.world.resource(runtime()).clone();
+
+        return Group::el([
+            UICamera.el(),
+            WindowSized::el([ServerBrowser {
+                endpoint,
+                on_join: cb(move |_, listing| {
+                    let set_server_addr = set_server_addr.clone();
+                    runtime.spawn(async move {
+                        match ResolvedAddr::lookup_host(listing.address.clone()).await {
+                            Ok(addr) => set_server_addr(Some(addr)),
+                            Err(err) => {
+                                tracing::error!("Failed to resolve {}: {err:?}", listing.address)
+                            }
+                        }
+                    });
+                }),
+            }
+            .el()]),
+        ]);
+    };
 
     Group::el([
         UICamera.el(),
@@ -195,6 +255,7 @@ fn MainApp(
         WindowSized::el([ClientView {
             server_addr,
             user_id,
+            token,
             fail_on_version_mismatch,
             // NOTE: client.game_state is **locked** and accesible through game_state.
             //
@@ -206,9 +267,10 @@ fn MainApp(
                 let world = &mut game_state.world;
                 let assets = world.resource(asset_cache()).clone();
 
-                wasm::initialize(world, &assets, mixer.clone()).unwrap();
+                wasm::initialize(world, &assets, buses.clone()).unwrap();
 
                 UICamera.el().spawn_static(world);
+                NotificationCenter.el().spawn_interactive(world);
                 set_loaded(true);
 
                 Ok(Box::new(|| {
@@ -230,7 +292,8 @@ fn MainApp(
                     unistream_handlers,
                 );
 
-                let dgram_handlers = HashMap::new();
+                let mut dgram_handlers = HashMap::new();
+                ambient_network::voice::register_client_datagram_handler(&mut dgram_handlers);
                 resources.set(ambient_network::client::datagram_handlers(), dgram_handlers);
 
                 (systems(), resources)
@@ -407,6 +470,7 @@ fn systems() -> SystemGroup {
             Box::new(ambient_primitives::systems()),
             Box::new(ambient_sky::systems()),
             Box::new(ambient_water::systems()),
+            Box::new(ambient_heightmap_terrain::client_systems()),
             Box::new(ambient_gizmos::client_systems()),
             Box::new(wasm::systems()),
             Box::new(ambient_client_shared::player::systems_final()),
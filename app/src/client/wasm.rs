@@ -1,18 +1,47 @@
-use ambient_audio::AudioMixer;
-use ambient_ecs::{EntityId, SystemGroup, World};
+use ambient_audio::AudioBuses;
+use ambient_ecs::{query, EntityId, SystemGroup, World};
 use ambient_native_std::asset_cache::AssetCache;
-use ambient_wasm::shared::{module_name, MessageType};
+use ambient_ui_native::ui::notification::Notification;
+use ambient_wasm::shared::{module_errors, module_name, MessageType};
 
 use std::sync::Arc;
 
+/// How many lines of an error are shown in its notification
+const ERROR_NOTIFICATION_LINES: usize = 4;
+
 pub fn systems() -> SystemGroup {
-    ambient_wasm::client::systems()
+    SystemGroup::new(
+        "client/wasm",
+        vec![
+            Box::new(ambient_wasm::client::systems()),
+            // Shows the latest error of each module, including the ones of the server, which are
+            // synchronized to the client
+            query(module_errors().changed()).to_system(|q, world, qs, _| {
+                for (id, errors) in q.collect_cloned(world, qs) {
+                    let Some(error) = errors.0.last() else {
+                        continue;
+                    };
+                    let module_name = world.get_cloned(id, module_name()).unwrap_or_default();
+                    let body = error
+                        .lines()
+                        .take(ERROR_NOTIFICATION_LINES)
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    Notification::error(format!("Error in {module_name}"))
+                        .id(format!("module_error:{id}"))
+                        .body(body)
+                        .timeout(0.)
+                        .show(world);
+                }
+            }),
+        ],
+    )
 }
 
 pub fn initialize(
     world: &mut World,
     assets: &AssetCache,
-    mixer: Option<AudioMixer>,
+    buses: Option<AudioBuses>,
 ) -> anyhow::Result<()> {
     let messenger = Arc::new(
         |world: &World, id: EntityId, ty: MessageType, message: &str| {
@@ -28,8 +57,8 @@ pub fn initialize(
         },
     );
 
-    if let Some(mixer) = mixer {
-        world.add_resource(ambient_world_audio::audio_mixer(), mixer);
+    if let Some(buses) = buses {
+        world.add_resource(ambient_world_audio::audio_buses(), buses);
     }
 
     ambient_wasm::client::initialize(world, assets, messenger)?;
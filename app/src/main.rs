@@ -65,8 +65,9 @@ fn main() -> anyhow::Result<()> {
     let use_release_build = cli.use_release_build();
     if let Some(package_path) = cli.package().map(|p| p.package_path()).transpose()? {
         if package_path.is_remote() {
-            // package path is a URL, so let's use it as the content base URL
-            ContentBaseUrlKey.insert(&assets, package_path.url.clone());
+            // package path is a URL, so let's use it as the content base URL;
+            // the files of bundles are read from below their URL
+            ContentBaseUrlKey.insert(&assets, package_path.url.as_directory());
         }
 
         // Store a flag that we are using local debug assets
@@ -104,6 +105,7 @@ fn main() -> anyhow::Result<()> {
         Commands::Assets { assets: command } => rt.block_on(cli::assets::handle(command, &assets)),
         Commands::Login => rt.block_on(cli::login::handle(&assets)),
         Commands::Join(join) => cli::join::handle(join, &rt, assets),
+        Commands::Rcon(args) => rt.block_on(cli::rcon::handle(args, &assets)),
     }
 }
 
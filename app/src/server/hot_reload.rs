@@ -0,0 +1,106 @@
+//! Watches the source assets of the local packages, rebuilds them when they change and pushes
+//! them to the running game, for `--watch`.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use ambient_core::hot_reload::changed_assets;
+use ambient_native_std::asset_cache::AssetCache;
+use ambient_network::{
+    server::{SharedServerState, MAIN_INSTANCE_ID},
+    ServerWorldExt,
+};
+use ambient_std::path::path_to_unix_string_lossy;
+use notify::{RecursiveMode, Watcher};
+use parking_lot::Mutex;
+
+use crate::cli::package::build::LocalPackage;
+
+/// How long to wait for more changes after a file changed, as tools often write several files,
+/// or a file in several steps
+const DEBOUNCE_TIME: Duration = Duration::from_millis(300);
+
+pub fn start_asset_watcher(
+    assets: AssetCache,
+    build_root_path: PathBuf,
+    packages: Vec<LocalPackage>,
+    server_state_holder: Arc<Mutex<Option<SharedServerState>>>,
+) -> anyhow::Result<()> {
+    let (tx, rx) = flume::unbounded();
+
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if event.kind.is_create() || event.kind.is_modify() {
+                let _ = tx.send(event.paths);
+            }
+        }
+    })?;
+    for package in &packages {
+        let assets_path = package.path.join("assets");
+        if assets_path.is_dir() {
+            tracing::info!("Watching the assets in {assets_path:?}");
+            watcher.watch(&assets_path, RecursiveMode::Recursive)?;
+        }
+    }
+
+    tokio::task::spawn(async move {
+        // The watcher stops watching when it's dropped
+        let _watcher = watcher;
+
+        while let Ok(mut paths) = rx.recv_async().await {
+            tokio::time::sleep(DEBOUNCE_TIME).await;
+            paths.extend(rx.drain().flatten());
+            paths.retain(|path| path.is_file());
+            paths.sort();
+            paths.dedup();
+
+            let mut changed = Vec::new();
+            for package in &packages {
+                let assets_path = package.path.join("assets");
+                let package_paths = paths
+                    .iter()
+                    .filter(|path| path.starts_with(&assets_path))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if package_paths.is_empty() {
+                    continue;
+                }
+
+                tracing::info!("Rebuilding the changed assets: {package_paths:?}");
+                match ambient_build::rebuild_assets(
+                    &assets,
+                    &assets_path,
+                    &package.build_path,
+                    &package_paths,
+                )
+                .await
+                {
+                    Ok(written) => changed.extend(
+                        written
+                            .iter()
+                            .filter_map(|path| path.strip_prefix(&build_root_path).ok())
+                            .map(path_to_unix_string_lossy),
+                    ),
+                    Err(err) => tracing::error!("Failed to rebuild the changed assets: {err:?}"),
+                }
+            }
+            if changed.is_empty() {
+                continue;
+            }
+
+            let Some(server_state) = server_state_holder.lock().clone() else {
+                continue;
+            };
+            let mut state = server_state.lock();
+            if let Some(instance) = state.instances.get_mut(MAIN_INSTANCE_ID) {
+                if let Some(id) = instance.world.synced_resource_entity() {
+                    instance
+                        .world
+                        .add_component(id, changed_assets(), changed)
+                        .ok();
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! The metrics of the server, in the Prometheus text format, for the `/metrics` route of the HTTP
+//! interface.
+
+use std::fmt::Write;
+
+use ambient_core::player::{is_player, user_id};
+use ambient_ecs::{
+    generated::network::components::{bandwidth_in, bandwidth_out},
+    query,
+};
+use ambient_network::{
+    server::{server_stats, ServerState, MAIN_INSTANCE_ID},
+    ServerWorldExt,
+};
+use ambient_wasm::shared::{module_cpu_time, module_name};
+
+/// Writes the metrics of `state`
+pub fn render(state: &ServerState) -> String {
+    let mut metrics = Metrics::default();
+
+    metrics.family(
+        "ambient_players_connected",
+        "gauge",
+        "The players connected to the server",
+    );
+    metrics.sample(
+        "ambient_players_connected",
+        &[],
+        state.player_count() as f64,
+    );
+
+    let stats = state.instances.get(MAIN_INSTANCE_ID).and_then(|instance| {
+        let id = instance.world.synced_resource_entity()?;
+        instance.world.get_ref(id, server_stats()).ok().cloned()
+    });
+    if let Some(stats) = stats.filter(|stats| stats.n_frames > 0) {
+        metrics.family(
+            "ambient_tick_seconds",
+            "gauge",
+            "The average time spent simulating a tick, over the last sample",
+        );
+        metrics.sample(
+            "ambient_tick_seconds",
+            &[],
+            stats.active_time.as_secs_f64() / stats.n_frames as f64,
+        );
+        metrics.family(
+            "ambient_tick_seconds_max",
+            "gauge",
+            "The slowest tick, over the last sample",
+        );
+        metrics.sample(
+            "ambient_tick_seconds_max",
+            &[],
+            stats.slowest_frame.as_secs_f64(),
+        );
+        metrics.family(
+            "ambient_ticks_per_second",
+            "gauge",
+            "The ticks simulated per second, over the last sample",
+        );
+        metrics.sample("ambient_ticks_per_second", &[], stats.fps() as f64);
+    }
+
+    let mut instances: Vec<_> = state.instances.iter().collect();
+    instances.sort_by(|a, b| a.0.cmp(b.0));
+
+    metrics.family(
+        "ambient_entities",
+        "gauge",
+        "The entities of each world instance",
+    );
+    for (instance_id, instance) in &instances {
+        metrics.sample(
+            "ambient_entities",
+            &[("instance", instance_id.as_str())],
+            instance.world.len() as f64,
+        );
+    }
+
+    metrics.family(
+        "ambient_module_cpu_seconds_total",
+        "counter",
+        "The time spent running each module",
+    );
+    for (instance_id, instance) in &instances {
+        for (id, (name, cpu_time)) in
+            query((module_name(), module_cpu_time())).iter(&instance.world, None)
+        {
+            // Several modules can have the same name, so the entity tells them apart
+            let id = id.to_string();
+            metrics.sample(
+                "ambient_module_cpu_seconds_total",
+                &[
+                    ("instance", instance_id.as_str()),
+                    ("module", name.as_str()),
+                    ("module_id", id.as_str()),
+                ],
+                cpu_time.as_secs_f64(),
+            );
+        }
+    }
+
+    for (metric, component, help) in [
+        (
+            "ambient_player_bandwidth_in_bytes_per_second",
+            bandwidth_in(),
+            "The bytes per second received from each player",
+        ),
+        (
+            "ambient_player_bandwidth_out_bytes_per_second",
+            bandwidth_out(),
+            "The bytes per second sent to each player",
+        ),
+    ] {
+        metrics.family(metric, "gauge", help);
+        for (_, instance) in &instances {
+            for (_, (player, bandwidth)) in query((user_id(), component))
+                .incl(is_player())
+                .iter(&instance.world, None)
+            {
+                metrics.sample(metric, &[("user_id", player.as_str())], *bandwidth as f64);
+            }
+        }
+    }
+
+    metrics.0
+}
+
+#[derive(Default)]
+struct Metrics(String);
+impl Metrics {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        writeln!(self.0, "# HELP {name} {help}").unwrap();
+        writeln!(self.0, "# TYPE {name} {kind}").unwrap();
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.0.push_str(name);
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(label, value)| format!("{label}=\"{}\"", escape(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            write!(self.0, "{{{labels}}}").unwrap();
+        }
+        writeln!(self.0, " {value}").unwrap();
+    }
+}
+
+/// Escapes a label value, as the text format requires
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -9,44 +9,69 @@ use std::{
 
 use ambient_core::{asset_cache, main_package_name, name, FIXED_SERVER_TICK_TIME};
 use ambient_ecs::{
-    dont_store, generated::network::components::no_sync, world_events, ComponentDesc, Entity,
-    Networked, SystemGroup, World, WorldContext, WorldEventsSystem, WorldStreamCompEvent,
+    dont_store, generated::network::components::no_sync, query, world_events, ComponentDesc,
+    Entity, Networked, SystemGroup, World, WorldContext, WorldEventsSystem, WorldStreamCompEvent,
 };
 use ambient_native_std::{
     ambient_version,
-    asset_cache::{AssetCache, SyncAssetKeyExt},
+    asset_cache::{AssetCache, AsyncAssetKeyExt, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, ContentBaseUrlKey, ServerBaseUrlKey},
+    bundle::{mount_bundle, BundleFromUrl},
     cb,
 };
 use ambient_network::{
+    auth::{Authenticator, AuthenticatorKey, HmacAuthenticator, JwtAuthenticator, Validation},
+    directory::{self, Directory, ServerListing, HEARTBEAT_INTERVAL},
+    handoff::{self, Handoff, HandoffSecretKey},
     is_persistent_resources, is_synced_resources,
+    mux::{MuxConnection, MuxSide, MAX_MUX_FRAME_SIZE},
     native::{
         client::ResolvedAddr,
         server::{Crypto, GameServer},
     },
-    server::{ForkingEvent, ProxySettings, SharedServerState, ShutdownEvent},
+    rcon::{self, RconRequest, RconSecretKey},
+    replay::{replay_player, replay_recorder, ReplayPlayer, ReplayRecorder},
+    server::{
+        BroadcastIntervalKey, ForkingEvent, ProxySettings, SharedServerState, ShutdownEvent,
+        MAIN_INSTANCE_ID,
+    },
+    session::SessionGraceKey,
 };
-use ambient_sys::task::RuntimeHandle;
+use ambient_physics::deterministic::DeterministicPhysicsKey;
+use ambient_sys::{task::RuntimeHandle, time::Instant};
 use anyhow::Context;
 use axum::{
-    extract::{Host, State},
-    http::{Method, StatusCode},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Host, State,
+    },
+    http::{header::AUTHORIZATION, HeaderMap, Method, StatusCode},
     response::IntoResponse,
-    routing::{get, get_service},
+    routing::{get, get_service, post},
     Router,
 };
 use axum_server::tls_rustls::RustlsConfig;
+use futures::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use rustls::{Certificate, PrivateKey, ServerConfig};
-use tower_http::{cors::CorsLayer, services::ServeDir};
+use tower_http::{
+    cors::CorsLayer,
+    services::{ServeDir, ServeFile},
+};
 
-use crate::{cli::package::HostCli, shared};
+use crate::{
+    cli::package::{build::LocalPackage, HostCli},
+    shared,
+};
 
+mod hot_reload;
+mod metrics;
 pub mod wasm;
 
 pub struct ServerHandle {
     addr: SocketAddr,
     join_handle: tokio::task::JoinHandle<()>,
+    server_state_holder: Arc<Mutex<Option<SharedServerState>>>,
 }
 impl ServerHandle {
     pub async fn join(self) -> Result<(), tokio::task::JoinError> {
@@ -56,6 +81,22 @@ impl ServerHandle {
     pub fn resolve_as_localhost(&self) -> ResolvedAddr {
         ResolvedAddr::localhost_with_port(self.addr.port())
     }
+
+    /// Rebuilds the assets of `packages` when their source files change, and reloads them in the
+    /// running game and its clients
+    pub fn watch_assets(
+        &self,
+        assets: AssetCache,
+        build_root_path: PathBuf,
+        packages: Vec<LocalPackage>,
+    ) -> anyhow::Result<()> {
+        hot_reload::start_asset_watcher(
+            assets,
+            build_root_path,
+            packages,
+            self.server_state_holder.clone(),
+        )
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -64,6 +105,7 @@ pub async fn start(
     host_cli: &HostCli,
     build_root_path: AbsAssetUrl,
     main_package_path: AbsAssetUrl,
+    bundle: Option<AbsAssetUrl>,
     working_directory: PathBuf,
     manifest: ambient_package::Manifest,
     crypto: Crypto,
@@ -133,9 +175,30 @@ pub async fn start(
 
     // here the key is inserted into the asset cache
     let server_state_holder = Arc::new(Mutex::new(None));
-    if let Ok(Some(build_path_fs)) = build_root_path.to_file_path() {
+    let directory = host_cli
+        .serve_directory
+        .then(|| Arc::new(Mutex::new(Directory::default())));
+    // Bundles are served as they are, and the packages are read from them
+    let content_root = match &bundle {
+        Some(bundle) => bundle.clone(),
+        None => build_root_path.clone(),
+    };
+    if let Ok(Some(build_path_fs)) = content_root.to_file_path() {
         let proto = if host_cli.use_https { "https" } else { "http" };
-        let key = format!("{proto}://{public_host}:{http_interface_port}/content/");
+        let mut key = format!("{proto}://{public_host}:{http_interface_port}/content/");
+        if let Some(bundle) = &bundle {
+            let name = build_path_fs.file_name().unwrap_or_default();
+            key.push_str(&format!("{}/", name.to_string_lossy()));
+
+            // The server reads the bundle from memory, instead of downloading it from itself
+            let loaded = BundleFromUrl(bundle.clone())
+                .get(&assets)
+                .await
+                .with_context(|| format!("Failed to load bundle {bundle}"))
+                .unwrap();
+            let url = AbsAssetUrl::from_str(&key).unwrap();
+            mount_bundle(&assets, url, loaded);
+        }
         let base_url = AbsAssetUrl::from_str(&key).unwrap();
         ServerBaseUrlKey.insert(&assets, base_url.clone());
         ContentBaseUrlKey.insert(&assets, base_url);
@@ -145,10 +208,15 @@ pub async fn start(
             http_interface_port,
             addr.port(),
             server_state_holder.clone(),
+            server.websocket_acceptor(),
             use_https,
+            directory,
+            host_cli.metrics,
+            host_cli.handoff_secret.is_some(),
+            host_cli.rcon_secret.is_some(),
         );
     } else {
-        let base_url = build_root_path.clone();
+        let base_url = content_root.as_directory();
 
         ServerBaseUrlKey.insert(&assets, base_url.clone());
         ContentBaseUrlKey.insert(&assets, base_url);
@@ -158,10 +226,66 @@ pub async fn start(
             http_interface_port,
             addr.port(),
             server_state_holder.clone(),
+            server.websocket_acceptor(),
             use_https,
+            directory,
+            host_cli.metrics,
+            host_cli.handoff_secret.is_some(),
+            host_cli.rcon_secret.is_some(),
+        );
+    }
+
+    if let Some(endpoint) = host_cli.directory.clone() {
+        let listing = ServerListing {
+            name: host_cli
+                .directory_name
+                .clone()
+                .unwrap_or_else(|| manifest.package.name.clone()),
+            address: format!("{public_host}:{}", addr.port()),
+            map: host_cli.directory_map.clone(),
+            player_count: 0,
+            packages: Vec::new(),
+            version: ambient_version().version.to_string(),
+        };
+        start_directory_registration(
+            assets.clone(),
+            endpoint,
+            listing,
+            server_state_holder.clone(),
         );
     }
 
+    DeterministicPhysicsKey.insert(&assets, host_cli.deterministic_physics);
+    if let Some(rate) = host_cli.broadcast_rate {
+        BroadcastIntervalKey.insert(&assets, Duration::from_secs_f64(1.0 / rate as f64));
+    }
+    if let Some(seconds) = host_cli.session_grace_seconds {
+        SessionGraceKey.insert(&assets, Duration::from_secs(seconds));
+    }
+
+    if let Some(authenticator) = create_authenticator(host_cli).unwrap() {
+        AuthenticatorKey.insert(&assets, authenticator);
+    }
+    if let Some(path) = &host_cli.handoff_secret {
+        let secret = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read handoff secret {}", path.display()))
+            .unwrap();
+        HandoffSecretKey.insert(&assets, secret.trim().into());
+    }
+    if let Some(path) = &host_cli.rcon_secret {
+        let secret = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read remote console secret {}", path.display()))
+            .unwrap();
+        RconSecretKey.insert(&assets, secret.trim().into());
+    }
+    if let Some(path) = &host_cli.bans {
+        rcon::load_bans(&assets, path).unwrap();
+    }
+
+    let record_replay = host_cli.record_replay.clone();
+    let replay = host_cli.replay.clone();
+
+    let server_state_holder_clone = server_state_holder.clone();
     let join_handle = tokio::task::spawn(async move {
         let mut server_world = World::new_with_config("server", WorldContext::Server, true);
         server_world.init_shape_change_tracking();
@@ -182,6 +306,26 @@ pub async fn start(
             )
             .unwrap();
 
+        if let Some(path) = replay {
+            // The replay brings its own resources and packages, and nothing else runs
+            let mut player = ReplayPlayer::open(&path).unwrap();
+            player.advance(&mut server_world).unwrap();
+            server_world.add_resource(replay_player(), Arc::new(Mutex::new(player)));
+
+            tracing::debug!("Starting server in replay mode");
+            server
+                .run(
+                    server_world,
+                    Arc::new(replay_systems),
+                    Arc::new(on_forking_systems),
+                    Arc::new(on_shutdown_systems),
+                    Arc::new(is_sync_component),
+                    Arc::new(move |state| *server_state_holder.lock() = Some(state)),
+                )
+                .await;
+            return;
+        }
+
         Entity::new()
             .with(ambient_core::name(), "Synced resources".to_string())
             .with(is_synced_resources(), ())
@@ -209,6 +353,11 @@ pub async fn start(
         .await
         .unwrap();
 
+        if let Some(path) = record_replay {
+            let recorder = ReplayRecorder::create(&path).unwrap();
+            server_world.add_resource(replay_recorder(), Arc::new(Mutex::new(recorder)));
+        }
+
         tracing::debug!("Starting server");
         server
             .run(
@@ -222,20 +371,23 @@ pub async fn start(
             .await;
     });
 
-    ServerHandle { addr, join_handle }
+    ServerHandle {
+        addr,
+        join_handle,
+        server_state_holder: server_state_holder_clone,
+    }
 }
 
 fn systems(_world: &mut World) -> SystemGroup {
     SystemGroup::new(
         "server",
         vec![
-            ambient_physics::run_simulation_system(),
-            // Can happen *during* the physics step
+            // Waits for the physics steps started at the end of the last frame
+            ambient_physics::fetch_simulation_system(),
             Box::new(ambient_core::async_ecs::async_ecs_systems()),
+            Box::new(ambient_core::hot_reload::systems()),
             Box::new(ambient_prefab::systems()),
             Box::new(ambient_core::hierarchy::systems()),
-            // Happens after the physics step
-            ambient_physics::fetch_simulation_system(),
             Box::new(ambient_animation::animation_systems()),
             Box::new(ambient_physics::physx::sync_ecs_physics()),
             Box::new(ambient_core::transform::TransformSystem::new()),
@@ -243,12 +395,29 @@ fn systems(_world: &mut World) -> SystemGroup {
             ambient_core::refcount_system(),
             Box::new(WorldEventsSystem),
             Box::new(ambient_core::camera::camera_systems()),
+            Box::new(ambient_heightmap_terrain::server_systems()),
             Box::new(ambient_physics::server_systems()),
             Box::new(ambient_package_semantic_native::server_systems()),
             Box::new(wasm::systems()),
+            Box::new(ambient_gizmos::server_systems()),
+            Box::new(ambient_network::bandwidth::server_systems()),
+            Box::new(ambient_network::handoff::server_systems()),
+            Box::new(ambient_network::rcon::server_systems()),
+            // Physics runs on its own thread until the start of the next frame
+            ambient_physics::run_simulation_system(),
+        ],
+    )
+}
+fn replay_systems(_world: &mut World) -> SystemGroup {
+    SystemGroup::new(
+        "server",
+        vec![
+            Box::new(ambient_network::replay::playback_systems()),
+            Box::new(WorldEventsSystem),
         ],
     )
 }
+
 fn on_forking_systems() -> SystemGroup<ForkingEvent> {
     SystemGroup::new(
         "on_forking_systems",
@@ -277,7 +446,8 @@ fn create_resources(assets: AssetCache) -> Entity {
         .with(name(), "Resources".to_string())
         .with(asset_cache(), assets.clone())
         .with(no_sync(), ())
-        .with(world_events(), Default::default());
+        .with(world_events(), Default::default())
+        .with(ambient_gizmos::debug_draw(), Default::default());
     ambient_physics::create_server_resources(&assets, &mut server_resources);
     server_resources.merge(ambient_core::async_ecs::async_ecs_resources());
     server_resources.set(ambient_core::runtime(), RuntimeHandle::current());
@@ -294,13 +464,15 @@ fn create_resources(assets: AssetCache) -> Entity {
         bistream_handlers,
     );
 
-    let unistream_handlers = HashMap::new();
+    let mut unistream_handlers = HashMap::new();
+    ambient_network::authority::register_server_uni_stream_handler(&mut unistream_handlers);
     server_resources.set(
         ambient_network::server::uni_stream_handlers(),
         unistream_handlers,
     );
 
-    let dgram_handlers = HashMap::new();
+    let mut dgram_handlers = HashMap::new();
+    ambient_network::voice::register_server_datagram_handler(&mut dgram_handlers);
     server_resources.set(ambient_network::server::datagram_handlers(), dgram_handlers);
 
     server_resources
@@ -332,7 +504,7 @@ const INDEX_TEMPLATE: &str = r#"<html>
         (async function () {
             const client = new AmbientClient("ambient-web-$VERSION$");
             await client.init();
-            await client.start(document.getElementById("ambient"), "https://$ENDPOINT$", { });
+            await client.start(document.getElementById("ambient"), "https://$ENDPOINT$", { websocketUrl: "$WEBSOCKET_URL$" });
         })()
     </script>
 </body>
@@ -343,8 +515,14 @@ fn start_http_interface(
     http_interface_port: u16,
     quic_interface_port: u16,
     server_state_holder: Arc<Mutex<Option<SharedServerState>>>,
+    websocket_acceptor: flume::Sender<MuxConnection>,
     use_https: Option<Crypto>,
+    directory: Option<Arc<Mutex<Directory>>>,
+    serve_metrics: bool,
+    accept_handoffs: bool,
+    serve_rcon: bool,
 ) {
+    let websocket_scheme = if use_https.is_some() { "wss" } else { "ws" };
     let mut router = Router::new()
         .route("/ping", get(|| async move { "ok" }))
         .route(
@@ -371,19 +549,115 @@ fn start_http_interface(
                     INDEX_TEMPLATE
                         .replace("$VERSION$", &version.version.to_string())
                         .replace("$ENDPOINT$", &format!("{hostname}:{quic_interface_port}"))
+                        .replace(
+                            "$WEBSOCKET_URL$",
+                            &format!("{websocket_scheme}://{hostname}/ws"),
+                        )
                 } else {
                     "<h1>Unreleased versions do not support the self-hosted web client</h1>"
                         .to_owned()
                 };
                 axum::response::Html(html)
             }),
+        )
+        .route(
+            "/ws",
+            get(move |ws: WebSocketUpgrade| async move {
+                ws.max_message_size(MAX_MUX_FRAME_SIZE)
+                    .max_frame_size(MAX_MUX_FRAME_SIZE)
+                    .on_upgrade(move |socket| handle_websocket(socket, websocket_acceptor))
+            }),
         );
 
-    if let Some(build_path) = build_path {
-        router = router.nest_service(
-            "/content",
-            get_service(ServeDir::new(build_path)).handle_error(handle_error),
+    if let Some(directory) = directory {
+        let listed = directory.clone();
+        router = router.route(
+            "/servers",
+            get(move || async move { axum::Json(listed.lock().listings(Instant::now())) }).post(
+                move |axum::Json(listing): axum::Json<ServerListing>| async move {
+                    directory.lock().register(listing, Instant::now());
+                },
+            ),
+        );
+    }
+
+    if serve_metrics {
+        router = router.route(
+            "/metrics",
+            get(
+                |State(holder): State<Arc<Mutex<Option<SharedServerState>>>>| async move {
+                    let server_state = holder.lock().clone();
+                    let metrics = server_state
+                        .map(|s| metrics::render(&s.lock()))
+                        .unwrap_or_default();
+                    (
+                        [(
+                            axum::http::header::CONTENT_TYPE,
+                            "text/plain; version=0.0.4",
+                        )],
+                        metrics,
+                    )
+                },
+            ),
+        );
+    }
+
+    if accept_handoffs {
+        router = router.route(
+            "/handoff",
+            post(
+                |State(holder): State<Arc<Mutex<Option<SharedServerState>>>>,
+                 headers: HeaderMap,
+                 axum::Json(handoff): axum::Json<Handoff>| async move {
+                    let secret = bearer_token(&headers);
+                    let server_state = holder.lock().clone().ok_or((
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "The server is starting".to_owned(),
+                    ))?;
+                    let receipt = handoff::accept(&mut server_state.lock(), secret, handoff)
+                        .map_err(|err| (StatusCode::FORBIDDEN, format!("{err:#}")))?;
+                    Ok::<_, (StatusCode, String)>(axum::Json(receipt))
+                },
+            ),
+        );
+    }
+
+    if serve_rcon {
+        router = router.route(
+            "/rcon",
+            post(
+                |State(holder): State<Arc<Mutex<Option<SharedServerState>>>>,
+                 headers: HeaderMap,
+                 axum::Json(request): axum::Json<RconRequest>| async move {
+                    let server_state = holder.lock().clone().ok_or((
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "The server is starting".to_owned(),
+                    ))?;
+                    let assets = server_state.lock().assets.clone();
+                    rcon::authorize(&assets, bearer_token(&headers))
+                        .map_err(|err| (StatusCode::FORBIDDEN, format!("{err:#}")))?;
+                    let response = rcon::execute(&server_state, request)
+                        .await
+                        .map_err(|err| (StatusCode::BAD_REQUEST, format!("{err:#}")))?;
+                    Ok::<_, (StatusCode, String)>(axum::Json(response))
+                },
+            ),
         );
+    }
+
+    if let Some(build_path) = build_path {
+        router = if build_path.is_file() {
+            let name = build_path.file_name().unwrap_or_default().to_string_lossy();
+            router.route(
+                &format!("/content/{name}"),
+                get_service(ServeFile::new(build_path)).handle_error(handle_error),
+            )
+        } else {
+            router.nest_service(
+                "/content",
+                get_service(ServeDir::new(build_path)).handle_error(handle_error),
+            )
+        };
     };
 
     let router = router.with_state(server_state_holder).layer(
@@ -424,6 +698,112 @@ fn start_http_interface(
     });
 }
 
+/// Keeps the listing of the server in the directory at `endpoint` up to date
+fn start_directory_registration(
+    assets: AssetCache,
+    endpoint: String,
+    mut listing: ServerListing,
+    server_state_holder: Arc<Mutex<Option<SharedServerState>>>,
+) {
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let server_state = server_state_holder.lock().clone();
+            if let Some(server_state) = server_state {
+                let state = server_state.lock();
+                listing.player_count = state.player_count();
+                if let Some(instance) = state.instances.get(MAIN_INSTANCE_ID) {
+                    listing.packages = query(ambient_package_semantic_native::name())
+                        .incl(ambient_package_semantic_native::is_package())
+                        .iter(&instance.world, None)
+                        .map(|(_, name)| name.clone())
+                        .collect();
+                }
+            }
+
+            if let Err(err) = directory::register(&assets, &endpoint, &listing).await {
+                tracing::warn!("{err:?}");
+            }
+        }
+    });
+}
+
+/// The token presented in the `Authorization` header of a request, if any
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Relays the frames of a client connected over the WebSocket fallback to and from the game server
+async fn handle_websocket(socket: WebSocket, acceptor: flume::Sender<MuxConnection>) {
+    let (conn, mut outgoing) = MuxConnection::new(MuxSide::Server);
+    if acceptor.send(conn.clone()).is_err() {
+        return;
+    }
+
+    let (mut sink, mut stream) = socket.split();
+
+    let send = async {
+        while let Some(frame) = outgoing.recv().await {
+            if sink.send(Message::Binary(frame.to_vec())).await.is_err() {
+                break;
+            }
+        }
+    };
+
+    let recv = async {
+        while let Some(Ok(message)) = stream.next().await {
+            match message {
+                Message::Binary(data) => {
+                    if let Err(err) = conn.handle_frame(data.into()) {
+                        tracing::warn!("Closing WebSocket connection: {err}");
+                        break;
+                    }
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = send => {}
+        _ = recv => {}
+    }
+
+    conn.close();
+}
+
+/// Creates the authenticator of the players chosen with `host_cli`, if any
+fn create_authenticator(host_cli: &HostCli) -> anyhow::Result<Option<Arc<dyn Authenticator>>> {
+    let read_secret = |path: &Path| {
+        std::fs::read(path)
+            .with_context(|| format!("Failed to read authentication secret {}", path.display()))
+    };
+    let validation = Validation {
+        issuer: host_cli.auth_issuer.clone(),
+        audience: host_cli.auth_audience.clone(),
+    };
+
+    let authenticator: Arc<dyn Authenticator> = if let Some(path) = &host_cli.auth_jwt_secret {
+        let mut authenticator = JwtAuthenticator::new(&read_secret(path)?);
+        authenticator.validation = validation;
+        Arc::new(authenticator)
+    } else if let Some(path) = &host_cli.auth_hmac_secret {
+        let mut authenticator = HmacAuthenticator::new(&read_secret(path)?);
+        authenticator.validation = validation;
+        Arc::new(authenticator)
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(authenticator))
+}
+
 async fn handle_error(_err: std::io::Error) -> impl IntoResponse {
     (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong...")
 }
@@ -0,0 +1,468 @@
+//! Makes the UI readable by assistive technology, like screen readers, with [AccessKit](accesskit).
+//!
+//! Every world with a UI builds a tree of the entities that have an `accessibility_role`, or
+//! `text`, in the order of the hierarchy, and publishes it to the [SharedAccessibilityTree]. The
+//! [Accessibility] adapter of the window merges the trees of all the worlds and hands them to the
+//! platform, and sends back the actions of assistive technology: focusing an entity moves the UI
+//! focus to it, and activating it sends `FocusActivated`, like the Enter key.
+use std::{collections::HashMap, sync::Arc};
+
+use accesskit::{
+    Action, ActionHandler, ActionRequest, Checked, Live, Node, NodeBuilder, NodeClassSet, NodeId,
+    Rect, Role, Tree, TreeUpdate,
+};
+use accesskit_winit::Adapter;
+use ambient_core::window::window_scale_factor;
+use ambient_ecs::{
+    components,
+    generated::{
+        app::components::ui_scene,
+        hierarchy::components::{children, parent},
+        layout::components::{height, width},
+        text::components::text,
+        transform::components::local_to_world,
+        ui::{
+            components::{
+                accessibility_description, accessibility_hidden, accessibility_label,
+                accessibility_live, accessibility_role, accessibility_value, focus, focusable,
+            },
+            messages::FocusActivated,
+        },
+    },
+    query, world_events, Debuggable, EntityId, FnSystem, Resource, SystemGroup, World,
+};
+use ambient_shared_types::accessibility::AccessibilityRole;
+use glam::vec2;
+use parking_lot::Mutex;
+use winit::{event::WindowEvent, window::Window};
+
+/// The trees of all the worlds, and the actions for them
+pub type SharedAccessibilityTree = Arc<Mutex<AccessibilityTree>>;
+
+components!("ui", {
+    @[Debuggable, Resource]
+    accessibility_tree: SharedAccessibilityTree,
+});
+
+/// The node of the window, which the trees of the worlds are under
+const ROOT: NodeId = NodeId(0);
+
+#[derive(Debug, Default)]
+pub struct AccessibilityTree {
+    /// Whether assistive technology is reading the tree; the worlds only build it while it is
+    active: bool,
+    /// The trees the worlds built this frame, by the name of the world
+    worlds: HashMap<&'static str, WorldTree>,
+    /// The world of each node that was sent, which its actions are run in
+    node_worlds: HashMap<NodeId, &'static str>,
+    /// The actions for each world, which are run on its next frame
+    actions: HashMap<&'static str, Vec<ActionRequest>>,
+}
+
+/// The tree of the UI of a world
+#[derive(Debug, Default)]
+struct WorldTree {
+    roots: Vec<NodeId>,
+    nodes: Vec<(NodeId, AccessNode)>,
+    focus: Option<NodeId>,
+}
+
+/// The properties of a node, which are compared to only send the nodes that changed
+#[derive(Debug, Clone, PartialEq)]
+struct AccessNode {
+    role: AccessibilityRole,
+    name: Option<String>,
+    description: Option<String>,
+    value: Option<String>,
+    bounds: Option<Rect>,
+    children: Vec<NodeId>,
+    focusable: bool,
+    live: bool,
+}
+impl AccessNode {
+    fn build(&self, classes: &mut NodeClassSet) -> Node {
+        let mut builder = NodeBuilder::new(role(self.role));
+        if let Some(name) = &self.name {
+            builder.set_name(name.as_str());
+        }
+        if let Some(description) = &self.description {
+            builder.set_description(description.as_str());
+        }
+        if let Some(value) = &self.value {
+            match self.role {
+                AccessibilityRole::Checkbox | AccessibilityRole::RadioButton => builder
+                    .set_checked(if value == "true" {
+                        Checked::True
+                    } else {
+                        Checked::False
+                    }),
+                _ => builder.set_value(value.as_str()),
+            }
+        }
+        if let Some(bounds) = self.bounds {
+            builder.set_bounds(bounds);
+        }
+        builder.set_children(self.children.clone());
+        if self.focusable {
+            builder.add_action(Action::Focus);
+            if self.role.is_activatable() {
+                builder.add_action(Action::Default);
+            }
+        }
+        if self.live {
+            builder.set_live(Live::Polite);
+        }
+        builder.build(classes)
+    }
+}
+
+fn role(role: AccessibilityRole) -> Role {
+    match role {
+        AccessibilityRole::Alert => Role::Alert,
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::Checkbox => Role::CheckBox,
+        AccessibilityRole::Dialog => Role::Dialog,
+        AccessibilityRole::Group => Role::Group,
+        AccessibilityRole::Heading => Role::Heading,
+        AccessibilityRole::Image => Role::Image,
+        AccessibilityRole::Link => Role::Link,
+        AccessibilityRole::List => Role::List,
+        AccessibilityRole::ListItem => Role::ListItem,
+        AccessibilityRole::Menu => Role::Menu,
+        AccessibilityRole::MenuItem => Role::MenuItem,
+        AccessibilityRole::ProgressBar => Role::ProgressIndicator,
+        AccessibilityRole::RadioButton => Role::RadioButton,
+        AccessibilityRole::Slider => Role::Slider,
+        AccessibilityRole::Status => Role::Status,
+        AccessibilityRole::Tab => Role::Tab,
+        AccessibilityRole::TabList => Role::TabList,
+        AccessibilityRole::Text => Role::StaticText,
+        AccessibilityRole::TextInput => Role::TextInput,
+    }
+}
+
+fn node_id(id: EntityId) -> NodeId {
+    // Entity ids are random, so their lower bits are as unique as all of them
+    NodeId(id.0 as u64)
+}
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "accessibility",
+        vec![Box::new(FnSystem::new(|world, _| {
+            let Some(tree) = world.resource_opt(accessibility_tree()).cloned() else {
+                return;
+            };
+            let actions = {
+                let mut tree = tree.lock();
+                if !tree.active {
+                    return;
+                }
+                tree.actions.remove(world.name()).unwrap_or_default()
+            };
+            for action in actions {
+                run_action(world, action);
+            }
+            let world_tree = build_tree(world);
+            tree.lock().worlds.insert(world.name(), world_tree);
+        }))],
+    )
+}
+
+/// Runs an action of assistive technology on the entity of its node
+fn run_action(world: &mut World, request: ActionRequest) {
+    let Some(focus_id) = query(focusable())
+        .iter(world, None)
+        .find(|(id, focus_id)| node_id(*id) == request.target && !focus_id.is_empty())
+        .map(|(_, focus_id)| focus_id.clone())
+    else {
+        return;
+    };
+    match request.action {
+        Action::Focus => ambient_focus::set_focus(world, focus_id, true),
+        Action::Default => {
+            ambient_focus::set_focus(world, focus_id.clone(), true);
+            world
+                .resource_mut(world_events())
+                .add_message(FocusActivated::new(focus_id));
+        }
+        _ => {}
+    }
+}
+
+fn build_tree(world: &World) -> WorldTree {
+    let mut builder = TreeBuilder {
+        world,
+        scale_factor: world
+            .resource_opt(window_scale_factor())
+            .copied()
+            .unwrap_or(1.) as f32,
+        focus_id: world.resource_opt(focus()).cloned().unwrap_or_default(),
+        tree: WorldTree::default(),
+    };
+    let mut roots = Vec::new();
+    for (id, _) in query(()).incl(ui_scene()).excl(parent()).iter(world, None) {
+        builder.visit(id, &mut roots);
+    }
+    builder.tree.roots = roots;
+    builder.tree
+}
+
+struct TreeBuilder<'a> {
+    world: &'a World,
+    scale_factor: f32,
+    focus_id: String,
+    tree: WorldTree,
+}
+impl<'a> TreeBuilder<'a> {
+    /// Adds the node of `id` to `siblings`, or the nodes of its children if it has none
+    fn visit(&mut self, id: EntityId, siblings: &mut Vec<NodeId>) {
+        let world = self.world;
+        if world.has_component(id, accessibility_hidden()) {
+            return;
+        }
+        let own_text = world
+            .get_ref(id, text())
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .cloned();
+        // Unknown roles are ignored, like they would be by assistive technology
+        let role = world
+            .get_ref(id, accessibility_role())
+            .ok()
+            .and_then(|role| role.parse::<AccessibilityRole>().ok())
+            .or(own_text.is_some().then_some(AccessibilityRole::Text));
+        let Some(role) = role else {
+            self.visit_children(id, siblings);
+            return;
+        };
+
+        let node = node_id(id);
+        // The children of buttons and the like are their label, and the glyphs of text inputs
+        // are their value, so they aren't read on their own
+        let is_leaf = role.is_named_by_content() || role == AccessibilityRole::TextInput;
+        let name = world
+            .get_cloned(id, accessibility_label())
+            .ok()
+            .or(own_text)
+            .or_else(|| {
+                role.is_named_by_content()
+                    .then(|| content_text(world, id))
+                    .filter(|text| !text.is_empty())
+            });
+        let mut children = Vec::new();
+        if !is_leaf {
+            self.visit_children(id, &mut children);
+        }
+        let focus_id = world.get_ref(id, focusable()).ok();
+        if focus_id.is_some_and(|focus_id| !focus_id.is_empty() && *focus_id == self.focus_id) {
+            self.tree.focus = Some(node);
+        }
+
+        self.tree.nodes.push((
+            node,
+            AccessNode {
+                role,
+                name,
+                description: world.get_cloned(id, accessibility_description()).ok(),
+                value: world.get_cloned(id, accessibility_value()).ok(),
+                bounds: self.bounds(id),
+                children,
+                focusable: focus_id.is_some_and(|focus_id| !focus_id.is_empty()),
+                live: world.has_component(id, accessibility_live()),
+            },
+        ));
+        siblings.push(node);
+    }
+
+    fn visit_children(&mut self, id: EntityId, siblings: &mut Vec<NodeId>) {
+        let world = self.world;
+        if let Ok(children) = world.get_ref(id, children()) {
+            for &child in children {
+                self.visit(child, siblings);
+            }
+        }
+    }
+
+    /// The rectangle of `id` in the window, in physical pixels
+    fn bounds(&self, id: EntityId) -> Option<Rect> {
+        let (_, _, position) = self
+            .world
+            .get(id, local_to_world())
+            .ok()?
+            .to_scale_rotation_translation();
+        let size = vec2(
+            self.world.get(id, width()).unwrap_or(0.),
+            self.world.get(id, height()).unwrap_or(0.),
+        );
+        let min = position.truncate() * self.scale_factor;
+        let max = (position.truncate() + size) * self.scale_factor;
+        Some(Rect::new(
+            min.x as f64,
+            min.y as f64,
+            max.x as f64,
+            max.y as f64,
+        ))
+    }
+}
+
+/// The text of the descendants of `id`, which names buttons and the like
+fn content_text(world: &World, id: EntityId) -> String {
+    let mut texts = Vec::new();
+    if let Ok(children) = world.get_ref(id, children()) {
+        for &child in children {
+            if world.has_component(child, accessibility_hidden()) {
+                continue;
+            }
+            if let Ok(label) = world.get_ref(child, accessibility_label()) {
+                texts.push(label.clone());
+                continue;
+            }
+            if let Ok(value) = world.get_ref(child, text()) {
+                texts.push(value.clone());
+            }
+            texts.push(content_text(world, child));
+        }
+    }
+    texts.retain(|text| !text.trim().is_empty());
+    texts.join(" ")
+}
+
+/// Forwards the actions of assistive technology to the worlds of their nodes
+struct ActionSender(SharedAccessibilityTree);
+impl ActionHandler for ActionSender {
+    fn do_action(&mut self, request: ActionRequest) {
+        let mut tree = self.0.lock();
+        if let Some(world) = tree.node_worlds.get(&request.target).copied() {
+            tree.actions.entry(world).or_default().push(request);
+        }
+    }
+}
+
+/// The AccessKit adapter of a window, which hands the UI to assistive technology.
+pub struct Accessibility {
+    adapter: Adapter,
+    window: Arc<Window>,
+    tree: SharedAccessibilityTree,
+    /// The nodes that were sent last, to only send the ones that changed
+    sent: HashMap<NodeId, AccessNode>,
+    sent_roots: Vec<NodeId>,
+    sent_focus: NodeId,
+}
+impl std::fmt::Debug for Accessibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Accessibility")
+            .field("nodes", &self.sent.len())
+            .field("focus", &self.sent_focus)
+            .finish()
+    }
+}
+impl Accessibility {
+    /// Creates the adapter of `window`, which must not be visible yet; show it afterwards.
+    pub fn new(window: Arc<Window>, tree: SharedAccessibilityTree) -> Self {
+        let adapter = Adapter::with_action_handler(
+            &window,
+            {
+                let tree = tree.clone();
+                move || {
+                    // Called when assistive technology starts reading the window
+                    tree.lock().active = true;
+                    TreeUpdate {
+                        nodes: vec![(
+                            ROOT,
+                            window_node(Vec::new(), &mut NodeClassSet::lock_global()),
+                        )],
+                        tree: Some(Tree::new(ROOT)),
+                        focus: ROOT,
+                    }
+                }
+            },
+            Box::new(ActionSender(tree.clone())),
+        );
+        Self {
+            adapter,
+            window,
+            tree,
+            sent: HashMap::new(),
+            sent_roots: Vec::new(),
+            sent_focus: ROOT,
+        }
+    }
+
+    /// Passes an event of the window to the adapter, which follows the focus and the size of the
+    /// window with them.
+    pub fn on_window_event(&mut self, event: &WindowEvent) {
+        self.adapter.on_event(&self.window, event);
+    }
+
+    /// Sends the changes of the trees that the worlds built this frame to assistive technology.
+    pub fn update(&mut self) {
+        let mut worlds = {
+            let mut tree = self.tree.lock();
+            if !tree.active {
+                return;
+            }
+            // The worlds that didn't build a tree this frame are gone, so their nodes are dropped
+            std::mem::take(&mut tree.worlds)
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        worlds.sort_by_key(|(name, _)| *name);
+
+        let roots = worlds
+            .iter()
+            .flat_map(|(_, world)| world.roots.iter().copied())
+            .collect::<Vec<_>>();
+        let focus = worlds
+            .iter()
+            .find_map(|(_, world)| world.focus)
+            .unwrap_or(ROOT);
+        let mut node_worlds = HashMap::new();
+        let mut nodes = HashMap::new();
+        for (name, world) in worlds {
+            for (id, node) in world.nodes {
+                node_worlds.insert(id, name);
+                nodes.insert(id, node);
+            }
+        }
+        self.tree.lock().node_worlds = node_worlds;
+
+        let changed = nodes
+            .iter()
+            .filter(|(id, node)| self.sent.get(id) != Some(node))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        let roots_changed = roots != self.sent_roots;
+        if changed.is_empty() && !roots_changed && focus == self.sent_focus {
+            return;
+        }
+
+        self.adapter.update_if_active(|| {
+            let mut classes = NodeClassSet::lock_global();
+            let mut update = TreeUpdate {
+                nodes: changed
+                    .iter()
+                    .map(|id| (*id, nodes[id].build(&mut classes)))
+                    .collect(),
+                tree: None,
+                focus,
+            };
+            if roots_changed {
+                update
+                    .nodes
+                    .push((ROOT, window_node(roots.clone(), &mut classes)));
+            }
+            update
+        });
+        self.sent = nodes;
+        self.sent_roots = roots;
+        self.sent_focus = focus;
+    }
+}
+
+/// The node of the window, with the roots of the UIs of the worlds as its children
+fn window_node(children: Vec<NodeId>, classes: &mut NodeClassSet) -> Node {
+    let mut builder = NodeBuilder::new(Role::Window);
+    builder.set_children(children);
+    builder.build(classes)
+}
@@ -0,0 +1,554 @@
+//! Animation graphs: layered state machines of clips.
+//!
+//! A graph is a JSON file, whose clip URLs are relative to it:
+//!
+//! ```json
+//! {
+//!     "parameters": { "speed": 0 },
+//!     "layers": [
+//!         {
+//!             "states": [
+//!                 { "name": "idle", "clip": "Idle.fbx/animations/mixamo.com.anim" },
+//!                 { "name": "run", "clip": "Run.fbx/animations/mixamo.com.anim",
+//!                   "events": [{ "time": 0.3, "name": "footstep" }] }
+//!             ],
+//!             "transitions": [
+//!                 { "from": "idle", "to": "run", "blend_time": 0.2,
+//!                   "conditions": [{ "parameter": "speed", "op": "greater", "value": 0.1 }] },
+//!                 { "from": "run", "to": "idle", "blend_time": 0.2,
+//!                   "conditions": [{ "parameter": "speed", "op": "less", "value": 0.1 }] }
+//!             ]
+//!         },
+//!         {
+//!             "mask": { "Spine": 1, "LeftArm": 1, "RightArm": 1 },
+//!             "states": [...]
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Each layer plays one state at a time, starting with its first one, and blends into the next one
+//! when a transition is taken. The layers are applied in order, each over the previous ones, with
+//! the weights of their mask.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    str::FromStr,
+    sync::Arc,
+};
+
+use ambient_core::{asset_cache, async_ecs::async_run, epoch_time, runtime};
+use ambient_ecs::{
+    components,
+    generated::animation::{
+        components::{
+            animation_graph_from_url, animation_graph_parameter_values, animation_graph_parameters,
+            animation_graph_states, clip_load_error,
+        },
+        messages::AnimationEvent,
+    },
+    query, world_events, Debuggable, EntityId, SystemGroup, World,
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::JsonFromUrl,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    player::{sample_clip, AnimationOutputKey},
+    AnimationClip, AnimationClipRetargetedFromModel, AnimationOutput, AnimationRetargeting,
+    AnimationTarget,
+};
+
+components!("animation::graph", {
+    animation_graph: Arc<AnimationGraph>,
+    @[Debuggable]
+    animation_graph_runtime: AnimationGraphRuntime,
+});
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationGraphDesc {
+    /// The default values of the parameters; the ones neither declared nor set are 0
+    #[serde(default)]
+    pub parameters: HashMap<String, f32>,
+    pub layers: Vec<AnimationLayerDesc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationLayerDesc {
+    /// The weight of the layer per bind id. Without a mask, the layer applies to every bone
+    pub mask: Option<HashMap<String, f32>>,
+    pub states: Vec<AnimationStateDesc>,
+    #[serde(default)]
+    pub transitions: Vec<AnimationTransitionDesc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationStateDesc {
+    pub name: String,
+    pub clip: String,
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    #[serde(default)]
+    pub events: Vec<AnimationEventDesc>,
+}
+
+fn default_looping() -> bool {
+    true
+}
+
+fn default_speed() -> f32 {
+    1.
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationEventDesc {
+    /// The time of the event in the clip, in seconds
+    pub time: f32,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationTransitionDesc {
+    /// The state the transition leaves. Without it, the transition is taken from any other state
+    pub from: Option<String>,
+    pub to: String,
+    /// The time, in seconds, to blend from the previous state into the next one
+    #[serde(default)]
+    pub blend_time: f32,
+    /// The fraction of the clip of the previous state to play before the transition is taken
+    pub exit_time: Option<f32>,
+    #[serde(default)]
+    pub conditions: Vec<AnimationCondition>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationCondition {
+    pub parameter: String,
+    pub op: AnimationConditionOp,
+    pub value: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationConditionOp {
+    Greater,
+    Less,
+    Equal,
+    NotEqual,
+}
+
+impl AnimationCondition {
+    fn holds(&self, value: f32) -> bool {
+        match self.op {
+            AnimationConditionOp::Greater => value > self.value,
+            AnimationConditionOp::Less => value < self.value,
+            AnimationConditionOp::Equal => value == self.value,
+            AnimationConditionOp::NotEqual => value != self.value,
+        }
+    }
+}
+
+/// A graph with its clips loaded
+#[derive(Debug)]
+pub struct AnimationGraph {
+    pub desc: AnimationGraphDesc,
+    /// The clip of each state of each layer
+    clips: Vec<Vec<Arc<AnimationClip>>>,
+}
+
+impl AnimationGraph {
+    pub async fn load(assets: &AssetCache, url: &AbsAssetUrl) -> anyhow::Result<Self> {
+        let desc = JsonFromUrl::<AnimationGraphDesc>::new(url.clone(), true)
+            .get(assets)
+            .await
+            .with_context(|| format!("Failed to load animation graph {url}"))?;
+        let desc = (*desc).clone();
+        desc.validate()?;
+
+        let mut clips = Vec::with_capacity(desc.layers.len());
+        for layer in &desc.layers {
+            let mut layer_clips = Vec::with_capacity(layer.states.len());
+            for state in &layer.states {
+                let clip = AnimationClipRetargetedFromModel {
+                    clip: url.resolve(&state.clip)?.into(),
+                    translation_retargeting: AnimationRetargeting::None,
+                    retarget_model: None,
+                }
+                .get(assets)
+                .await
+                .with_context(|| format!("Failed to load the clip of state {:?}", state.name))?;
+                layer_clips.push(clip);
+            }
+            clips.push(layer_clips);
+        }
+        Ok(Self { desc, clips })
+    }
+
+    fn clip_time(&self, layer: usize, state: usize, since_entered: f64) -> f64 {
+        let desc = &self.desc.layers[layer].states[state];
+        let duration = self.clips[layer][state].duration() as f64;
+        let time = since_entered * desc.speed as f64;
+        if desc.looping && duration > 0. {
+            time.rem_euclid(duration)
+        } else {
+            time.min(duration)
+        }
+    }
+}
+
+impl AnimationGraphDesc {
+    fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.layers.is_empty(), "The graph has no layers");
+        for (i, layer) in self.layers.iter().enumerate() {
+            anyhow::ensure!(!layer.states.is_empty(), "Layer {i} has no states");
+            for transition in &layer.transitions {
+                for name in transition.from.iter().chain([&transition.to]) {
+                    anyhow::ensure!(
+                        layer.state(name).is_some(),
+                        "Layer {i} has a transition with an unknown state {name:?}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AnimationLayerDesc {
+    fn state(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+}
+
+/// The playback of an animation graph
+#[derive(Debug, Clone, Default)]
+pub struct AnimationGraphRuntime {
+    layers: Vec<LayerRuntime>,
+}
+
+#[derive(Debug, Clone)]
+struct LayerRuntime {
+    state: usize,
+    /// The time the state was entered at, in seconds
+    entered_at: f64,
+    /// The state being blended out of
+    previous: Option<BlendOut>,
+    /// The time of the last update, in seconds
+    updated_at: f64,
+}
+
+#[derive(Debug, Clone)]
+struct BlendOut {
+    state: usize,
+    entered_at: f64,
+    started_at: f64,
+    blend_time: f64,
+}
+
+impl AnimationGraphRuntime {
+    pub fn new(graph: &AnimationGraph, now: f64) -> Self {
+        Self {
+            layers: graph
+                .desc
+                .layers
+                .iter()
+                .map(|_| LayerRuntime {
+                    state: 0,
+                    entered_at: now,
+                    previous: None,
+                    updated_at: now,
+                })
+                .collect(),
+        }
+    }
+
+    /// Takes the transitions whose conditions hold, and returns the events reached since the
+    /// last update, as pairs of state and event names
+    pub fn update(
+        &mut self,
+        graph: &AnimationGraph,
+        parameter: impl Fn(&str) -> f32,
+        now: f64,
+    ) -> Vec<(String, String)> {
+        let mut events = Vec::new();
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            let desc = &graph.desc.layers[i];
+
+            let state = &desc.states[layer.state];
+            let duration = graph.clips[i][layer.state].duration() as f64;
+            let played = (now - layer.entered_at) * state.speed as f64;
+            let transition = desc.transitions.iter().find(|transition| {
+                let from = match &transition.from {
+                    Some(from) => *from == state.name,
+                    None => transition.to != state.name,
+                };
+                from && transition
+                    .exit_time
+                    .map_or(true, |exit_time| played >= exit_time as f64 * duration)
+                    && transition
+                        .conditions
+                        .iter()
+                        .all(|condition| condition.holds(parameter(&condition.parameter)))
+            });
+            if let Some(transition) = transition {
+                layer.previous = Some(BlendOut {
+                    state: layer.state,
+                    entered_at: layer.entered_at,
+                    started_at: now,
+                    blend_time: transition.blend_time as f64,
+                });
+                layer.state = desc.state(&transition.to).unwrap();
+                layer.entered_at = now;
+                layer.updated_at = now;
+            }
+            if let Some(previous) = &layer.previous {
+                if now - previous.started_at >= previous.blend_time {
+                    layer.previous = None;
+                }
+            }
+
+            let state = &desc.states[layer.state];
+            let duration = graph.clips[i][layer.state].duration() as f64;
+            let speed = state.speed as f64;
+            let from = (layer.updated_at - layer.entered_at) * speed;
+            let to = (now - layer.entered_at) * speed;
+            for event in &state.events {
+                let time = event.time as f64;
+                // The number of times the event was passed in [from, to)
+                let reached = if state.looping && duration > 0. {
+                    ((to - time) / duration).ceil() - ((from - time) / duration).ceil()
+                } else if from <= time && time < to && time <= duration {
+                    1.
+                } else {
+                    0.
+                };
+                for _ in 0..reached.max(0.) as usize {
+                    events.push((state.name.clone(), event.name.clone()));
+                }
+            }
+            layer.updated_at = now;
+        }
+        events
+    }
+
+    pub fn states(&self, graph: &AnimationGraph) -> Vec<String> {
+        self.layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| graph.desc.layers[i].states[layer.state].name.clone())
+            .collect()
+    }
+
+    pub(crate) fn sample(
+        &self,
+        graph: &AnimationGraph,
+        now: f64,
+    ) -> HashMap<AnimationOutputKey, AnimationOutput> {
+        let mut output = HashMap::new();
+        for (i, layer) in self.layers.iter().enumerate() {
+            let sample_state = |state: usize, entered_at: f64| {
+                sample_clip(
+                    &graph.clips[i][state],
+                    graph.clip_time(i, state, now - entered_at),
+                )
+            };
+            let mut pose = sample_state(layer.state, layer.entered_at);
+            if let Some(previous) = &layer.previous {
+                let weight = ((now - previous.started_at) / previous.blend_time).clamp(0., 1.);
+                let mut blended = sample_state(previous.state, previous.entered_at);
+                mix_into(&mut blended, pose, |_| weight as f32);
+                pose = blended;
+            }
+
+            match &graph.desc.layers[i].mask {
+                _ if i == 0 => output = pose,
+                None => mix_into(&mut output, pose, |_| 1.),
+                Some(mask) => mix_into(&mut output, pose, |key| match &key.target {
+                    AnimationTarget::BinderId(bind_id) => mask.get(bind_id).copied().unwrap_or(0.),
+                    AnimationTarget::Entity(_) => 0.,
+                }),
+            }
+        }
+        output
+    }
+}
+
+/// Mixes `right` into `left`, with the weight of each output
+fn mix_into(
+    left: &mut HashMap<AnimationOutputKey, AnimationOutput>,
+    right: HashMap<AnimationOutputKey, AnimationOutput>,
+    weight: impl Fn(&AnimationOutputKey) -> f32,
+) {
+    for (key, value) in right {
+        let weight = weight(&key);
+        match left.entry(key) {
+            Entry::Occupied(mut o) => {
+                let left = o.get_mut();
+                *left = left.mix(value, weight);
+            }
+            Entry::Vacant(v) => {
+                if weight > 0. {
+                    v.insert(value);
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn sample_animation_graph(
+    world: &World,
+    node: EntityId,
+) -> HashMap<AnimationOutputKey, AnimationOutput> {
+    let (Ok(graph), Ok(runtime)) = (
+        world.get_ref(node, animation_graph()),
+        world.get_ref(node, animation_graph_runtime()),
+    ) else {
+        return Default::default();
+    };
+    runtime.sample(graph, world.resource(epoch_time()).as_secs_f64())
+}
+
+pub fn animation_graph_systems() -> SystemGroup {
+    SystemGroup::new(
+        "animation_graph_systems",
+        vec![
+            query(animation_graph_from_url().changed()).to_system(|q, world, qs, _| {
+                let runtime = world.resource(runtime()).clone();
+                for (id, url) in q.collect_cloned(world, qs) {
+                    world.remove_component(id, clip_load_error()).ok();
+                    let async_run = world.resource(async_run()).clone();
+                    let assets = world.resource(asset_cache()).clone();
+                    runtime.spawn(async move {
+                        let graph = match AbsAssetUrl::from_str(&url) {
+                            Ok(url) => AnimationGraph::load(&assets, &url).await,
+                            Err(err) => Err(err),
+                        };
+                        async_run.run(move |world| match graph {
+                            Ok(graph) => {
+                                let now = world.resource(epoch_time()).as_secs_f64();
+                                let graph_runtime = AnimationGraphRuntime::new(&graph, now);
+                                world
+                                    .add_component(
+                                        id,
+                                        animation_graph_states(),
+                                        graph_runtime.states(&graph),
+                                    )
+                                    .ok();
+                                world
+                                    .add_component(id, animation_graph_runtime(), graph_runtime)
+                                    .ok();
+                                world
+                                    .add_component(id, animation_graph(), Arc::new(graph))
+                                    .ok();
+                            }
+                            Err(err) => {
+                                tracing::warn!("Failed to load animation graph: {:?}", err);
+                                world
+                                    .add_component(id, clip_load_error(), format!("{:?}", err))
+                                    .ok();
+                            }
+                        });
+                    });
+                }
+            }),
+            query((animation_graph(), animation_graph_runtime())).to_system(|q, world, qs, _| {
+                let now = world.resource(epoch_time()).as_secs_f64();
+                for (id, (graph, mut graph_runtime)) in q.collect_cloned(world, qs) {
+                    let names = world
+                        .get_ref(id, animation_graph_parameters())
+                        .cloned()
+                        .unwrap_or_default();
+                    let values = world
+                        .get_ref(id, animation_graph_parameter_values())
+                        .cloned()
+                        .unwrap_or_default();
+                    let parameter = |name: &str| {
+                        names
+                            .iter()
+                            .position(|x| x == name)
+                            .and_then(|i| values.get(i).copied())
+                            .or_else(|| graph.desc.parameters.get(name).copied())
+                            .unwrap_or_default()
+                    };
+
+                    let events = graph_runtime.update(&graph, parameter, now);
+                    for (state, name) in events {
+                        world
+                            .resource_mut(world_events())
+                            .add_message(AnimationEvent {
+                                node: id,
+                                state,
+                                name,
+                            });
+                    }
+                    let states = graph_runtime.states(&graph);
+                    if world.get_ref(id, animation_graph_states()).ok() != Some(&states) {
+                        world.set(id, animation_graph_states(), states).ok();
+                    }
+                    world.set(id, animation_graph_runtime(), graph_runtime).ok();
+                }
+            }),
+        ],
+    )
+}
+
+#[test]
+fn test_animation_graph_transitions() {
+    ambient_core::transform::init_components();
+    ambient_ecs::init_components();
+
+    let graph: AnimationGraphDesc = serde_json::from_str(
+        r#"{
+            "layers": [{
+                "states": [
+                    { "name": "idle", "clip": "idle.anim" },
+                    { "name": "run", "clip": "run.anim", "events": [{ "time": 0.5, "name": "step" }] }
+                ],
+                "transitions": [
+                    { "from": "idle", "to": "run", "blend_time": 0.2,
+                      "conditions": [{ "parameter": "speed", "op": "greater", "value": 0.1 }] },
+                    { "from": "run", "to": "idle", "blend_time": 0.2,
+                      "conditions": [{ "parameter": "speed", "op": "less", "value": 0.1 }] }
+                ]
+            }]
+        }"#,
+    )
+    .unwrap();
+    graph.validate().unwrap();
+    let clip = |duration: f32| {
+        Arc::new(AnimationClip::from_tracks(vec![crate::AnimationTrack {
+            target: AnimationTarget::BinderId(String::new()),
+            inputs: vec![0., duration],
+            outputs: crate::AnimationOutputs::Vec3 {
+                component: ambient_core::transform::translation(),
+                data: vec![Default::default(), Default::default()],
+            },
+        }]))
+    };
+    let graph = AnimationGraph {
+        desc: graph,
+        clips: vec![vec![clip(1.), clip(1.)]],
+    };
+
+    let mut runtime = AnimationGraphRuntime::new(&graph, 0.);
+    assert!(runtime.update(&graph, |_| 0., 0.1).is_empty());
+    assert_eq!(runtime.states(&graph), vec!["idle"]);
+
+    assert!(runtime.update(&graph, |_| 1., 0.2).is_empty());
+    assert_eq!(runtime.states(&graph), vec!["run"]);
+    assert!(runtime.layers[0].previous.is_some());
+
+    // The step at 0.5s into the looping clip is passed twice
+    let events = runtime.update(&graph, |_| 1., 1.8);
+    assert_eq!(events.len(), 2);
+    assert!(runtime.layers[0].previous.is_none());
+
+    runtime.update(&graph, |_| 0., 1.9);
+    assert_eq!(runtime.states(&graph), vec!["idle"]);
+}
@@ -1,19 +1,32 @@
 use ambient_ecs::SystemGroup;
+use graph::animation_graph_systems;
 use player::animation_player_systems;
 
+mod graph;
 mod player;
 mod resources;
 mod retargeting;
+mod sockets;
 
+pub use graph::*;
 pub use resources::*;
 pub use retargeting::*;
+pub use sockets::*;
 
 pub fn init_all_components() {
     player::init_components();
+    graph::init_components();
+    sockets::init_components();
 }
 
 pub fn animation_systems() -> SystemGroup {
-    animation_player_systems()
+    SystemGroup::new(
+        "animation",
+        vec![
+            Box::new(animation_graph_systems()),
+            Box::new(animation_player_systems()),
+        ],
+    )
 }
 
 #[test]
@@ -9,10 +9,10 @@ use ambient_core::{asset_cache, async_ecs::async_run, epoch_time, runtime};
 use ambient_ecs::{
     components,
     generated::animation::components::{
-        animation_errors, apply_animation_player, apply_base_pose, bind_ids, blend, clip_duration,
-        clip_load_error, clip_loaded, freeze_at_percentage, freeze_at_time, is_animation_player,
-        looping, mask_bind_ids, mask_weights, play_clip_from_url, retarget_animation_scaled,
-        retarget_model_from_url, speed, start_time,
+        animation_errors, animation_graph_from_url, apply_animation_player, apply_base_pose,
+        bind_ids, blend, clip_duration, clip_load_error, clip_loaded, freeze_at_percentage,
+        freeze_at_time, is_animation_player, looping, mask_bind_ids, mask_weights,
+        play_clip_from_url, retarget_animation_scaled, retarget_model_from_url, speed, start_time,
     },
     generated::hierarchy::components::children,
     query, ComponentDesc, Debuggable, EntityId, SystemGroup, World,
@@ -28,8 +28,8 @@ use glam::{Quat, Vec3};
 use itertools::Itertools;
 
 use crate::{
-    AnimationClip, AnimationClipRetargetedFromModel, AnimationOutput, AnimationRetargeting,
-    AnimationTarget, AnimationTrackInterpolator, Vec3Field,
+    graph::sample_animation_graph, AnimationClip, AnimationClipRetargetedFromModel,
+    AnimationOutput, AnimationRetargeting, AnimationTarget, AnimationTrackInterpolator, Vec3Field,
 };
 
 components!("animation", {
@@ -55,7 +55,7 @@ impl std::fmt::Debug for AnimationOutputs {
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AnimationOutputKey {
-    target: AnimationTarget,
+    pub(crate) target: AnimationTarget,
     component: u32,
     field: Option<Vec3Field>,
 }
@@ -100,19 +100,7 @@ fn sample_animation_node_inner(
             }
             time * speed as f64
         };
-        let mut output: HashMap<AnimationOutputKey, AnimationOutput> = clip
-            .tracks
-            .iter()
-            .map(|track| {
-                let value = AnimationTrackInterpolator::new().value(track, time as f32);
-                let key = AnimationOutputKey {
-                    target: track.target.clone(),
-                    component: track.outputs.component().index(),
-                    field: track.outputs.field(),
-                };
-                (key, value)
-            })
-            .collect();
+        let mut output = sample_clip(&clip, time);
         if let Ok(base_pose) = world.get_ref(node, cached_base_pose()) {
             for (key, value) in base_pose.iter() {
                 if !output.contains_key(key) {
@@ -121,6 +109,8 @@ fn sample_animation_node_inner(
             }
         }
         Ok(output)
+    } else if world.get_ref(node, animation_graph_from_url()).is_ok() {
+        Ok(sample_animation_graph(world, node))
     } else if let Ok(blend_weight) = world.get(node, blend()) {
         let children = world.get_ref(node, children())?;
         if children.len() != 2 {
@@ -154,6 +144,24 @@ fn sample_animation_node_inner(
     }
 }
 
+pub(crate) fn sample_clip(
+    clip: &AnimationClip,
+    time: f64,
+) -> HashMap<AnimationOutputKey, AnimationOutput> {
+    clip.tracks
+        .iter()
+        .map(|track| {
+            let value = AnimationTrackInterpolator::new().value(track, time as f32);
+            let key = AnimationOutputKey {
+                target: track.target.clone(),
+                component: track.outputs.component().index(),
+                field: track.outputs.field(),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
 fn apply_animation_outputs_to_entity(
     world: &World,
     binder: &HashMap<String, EntityId>,
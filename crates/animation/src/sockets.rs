@@ -0,0 +1,104 @@
+//! Attachment of entities to the bones of models.
+//!
+//! The bones of a model only exist where the model is spawned, so each side resolves the bone
+//! of an attached entity on its own, and retries until the model has been loaded.
+
+use ambient_core::{
+    hierarchy::children,
+    name,
+    transform::{local_to_parent, local_to_world, update_transform_recursive},
+};
+use ambient_ecs::{
+    components,
+    generated::animation::components::{attach_to_bone, attach_to_model, bind_id},
+    query, Debuggable, EntityId, SystemGroup, World,
+};
+use ambient_model::animation_binder;
+use glam::Mat4;
+
+components!("animation::sockets", {
+    /// The bone an entity with `attach_to_model` and `attach_to_bone` currently follows
+    @[Debuggable]
+    attached_bone: EntityId,
+});
+
+/// Finds the bone of the model on `model` with the bind id or node name `bone`
+pub fn find_bone(world: &World, model: EntityId, bone: &str) -> Option<EntityId> {
+    if let Some(id) = world
+        .get_ref(model, animation_binder())
+        .ok()
+        .and_then(|binder| binder.get(bone))
+    {
+        if world.exists(*id) {
+            return Some(*id);
+        }
+    }
+    find_node(world, model, bone)
+}
+
+fn find_node(world: &World, id: EntityId, bone: &str) -> Option<EntityId> {
+    for child in world.get_ref(id, children()).into_iter().flatten() {
+        let matches = |value: Result<&String, _>| value.is_ok_and(|value| value == bone);
+        if matches(world.get_ref(*child, name())) || matches(world.get_ref(*child, bind_id())) {
+            return Some(*child);
+        }
+        if let Some(node) = find_node(world, *child, bone) {
+            return Some(node);
+        }
+    }
+    None
+}
+
+/// Makes the entities attached to bones follow them; must run after the transforms are updated
+pub fn socket_systems() -> SystemGroup {
+    SystemGroup::new(
+        "animation/sockets",
+        vec![
+            query((attach_to_model().changed(), attach_to_bone().changed())).to_system(
+                |q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        world.remove_component(id, attached_bone()).ok();
+                    }
+                },
+            ),
+            query(attach_to_model())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        world.remove_component(id, attached_bone()).ok();
+                    }
+                }),
+            query((attach_to_model(), attach_to_bone()))
+                .excl(attached_bone())
+                .to_system(|q, world, qs, _| {
+                    for (id, (model, bone)) in q.collect_cloned(world, qs) {
+                        let Some(bone) = find_bone(world, model, &bone) else {
+                            continue;
+                        };
+                        if !world.has_component(id, local_to_parent()) {
+                            world
+                                .add_component(id, local_to_parent(), Mat4::IDENTITY)
+                                .unwrap();
+                        }
+                        if !world.has_component(id, local_to_world()) {
+                            world
+                                .add_component(id, local_to_world(), Mat4::IDENTITY)
+                                .unwrap();
+                        }
+                        world.add_component(id, attached_bone(), bone).unwrap();
+                    }
+                }),
+            query(attached_bone()).to_system(|q, world, qs, _| {
+                for (id, bone) in q.collect_cloned(world, qs) {
+                    match world.get(bone, local_to_world()) {
+                        Ok(bone_to_world) => update_transform_recursive(world, id, bone_to_world),
+                        // The model was removed or reloaded, so the bone has to be found again
+                        Err(_) => {
+                            world.remove_component(id, attached_bone()).unwrap();
+                        }
+                    }
+                }
+            }),
+        ],
+    )
+}
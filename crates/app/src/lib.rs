@@ -1,6 +1,10 @@
 use std::{future::Future, sync::Arc, time::Duration};
 
-use ambient_cameras::assets_camera_systems;
+use ambient_accessibility::{accessibility_tree, Accessibility, SharedAccessibilityTree};
+use ambient_cameras::{
+    assets_camera_systems,
+    effects::{camera_effects_systems, camera_shake_systems},
+};
 pub use ambient_core::gpu;
 use ambient_core::{
     asset_cache,
@@ -18,11 +22,13 @@ use ambient_core::{
     ClientTimeResourcesSystem, PerformanceSample, RuntimeKey,
 };
 use ambient_ecs::{
-    components, generated::ui::components::focus, world_events, Debuggable, DynSystem, Entity,
-    FrameEvent, MakeDefault, MaybeResource, System, SystemGroup, World, WorldEventsSystem,
+    components,
+    generated::ui::components::{focus, focus_visible},
+    world_events, Debuggable, DynSystem, Entity, FrameEvent, MakeDefault, MaybeResource, System,
+    SystemGroup, World, WorldEventsSystem,
 };
 use ambient_element::ambient_system;
-use ambient_gizmos::{gizmos, Gizmos};
+use ambient_gizmos::{debug_draw, gizmos, Gizmos};
 use ambient_gpu::{
     gpu::{Gpu, GpuKey},
     mesh_buffer::MeshBufferKey,
@@ -41,7 +47,7 @@ use glam::{uvec2, vec2, IVec2, UVec2, Vec2};
 use parking_lot::Mutex;
 use renderers::{main_renderer, ui_renderer, MainRenderer, UiRenderer};
 use winit::{
-    dpi::PhysicalPosition,
+    dpi::{LogicalPosition, PhysicalPosition},
     event::{ElementState, Event, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     window::{CursorGrabMode, Fullscreen, Window, WindowBuilder},
@@ -75,6 +81,7 @@ pub fn init_all_components() {
     ambient_cameras::init_all_components();
     renderers::init_components();
     ambient_procedurals::init_components();
+    ambient_accessibility::init_components();
 }
 
 pub fn gpu_world_sync_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
@@ -98,11 +105,13 @@ pub fn world_instance_systems(full: bool) -> SystemGroup {
         vec![
             Box::new(ClientTimeResourcesSystem::new()),
             Box::new(async_ecs_systems()),
+            Box::new(ambient_core::hot_reload::systems()),
             remove_at_time_system(),
             refcount_system(),
             Box::new(ambient_core::hierarchy::systems()),
             Box::new(WorldEventsSystem),
             Box::new(ambient_focus::systems()),
+            Box::new(ambient_accessibility::systems()),
             if full {
                 Box::new(ambient_input::picking::frame_systems())
             } else {
@@ -118,10 +127,13 @@ pub fn world_instance_systems(full: bool) -> SystemGroup {
             },
             Box::new(ambient_model::model_systems()),
             Box::new(ambient_animation::animation_systems()),
+            Box::new(camera_effects_systems()),
             Box::new(TransformSystem::new()),
+            Box::new(ambient_animation::socket_systems()),
             Box::new(ambient_renderer::skinning::skinning_systems()),
             Box::new(bounding_systems()),
             Box::new(camera_systems()),
+            Box::new(camera_shake_systems()),
             Box::new(ambient_procedurals::client_systems()),
         ],
     )
@@ -132,6 +144,7 @@ pub struct AppResources {
     pub gpu: Arc<Gpu>,
     pub runtime: RuntimeHandle,
     pub ctl_tx: flume::Sender<WindowCtl>,
+    pub accessibility_tree: SharedAccessibilityTree,
     window_physical_size: UVec2,
     window_logical_size: UVec2,
     window_scale_factor: f64,
@@ -144,6 +157,7 @@ impl AppResources {
             gpu: world.resource(self::gpu()).clone(),
             runtime: world.resource(self::runtime()).clone(),
             ctl_tx: world.resource(ambient_core::window::window_ctl()).clone(),
+            accessibility_tree: world.resource(accessibility_tree()).clone(),
             window_physical_size: *world.resource(ambient_core::window::window_physical_size()),
             window_logical_size: *world.resource(ambient_core::window::window_logical_size()),
             window_scale_factor: *world.resource(ambient_core::window::window_scale_factor()),
@@ -156,6 +170,7 @@ pub fn world_instance_resources(resources: AppResources) -> Entity {
         .with(name(), "Resources".to_string())
         .with(self::gpu(), resources.gpu.clone())
         .with(gizmos(), Gizmos::new())
+        .with(debug_draw(), Default::default())
         .with(self::runtime(), resources.runtime)
         .with(self::window_title(), "".to_string())
         .with(self::fps_stats(), FpsSample::default())
@@ -185,8 +200,10 @@ pub fn world_instance_resources(resources: AppResources) -> Entity {
             resources.window_scale_factor,
         )
         .with(ambient_core::window::window_ctl(), resources.ctl_tx)
+        .with(accessibility_tree(), resources.accessibility_tree)
         .with(procedural_storage(), ProceduralStorage::new())
         .with(focus(), Default::default())
+        .with(focus_visible(), false)
 }
 
 pub struct AppBuilder {
@@ -346,7 +363,8 @@ impl AppBuilder {
             } else {
                 window
             };
-            let window = Arc::new(window.build(&event_loop).unwrap());
+            // The window is shown once its accessibility adapter is set up, as AccessKit requires
+            let window = Arc::new(window.with_visible(false).build(&event_loop).unwrap());
             (Some(window), Some(event_loop))
         };
 
@@ -506,11 +524,19 @@ impl AppBuilder {
                 (headless_size, headless_size, 1.)
             };
 
+        let accessibility_tree = SharedAccessibilityTree::default();
+        let accessibility = window.as_ref().map(|window| {
+            let accessibility = Accessibility::new(window.clone(), accessibility_tree.clone());
+            window.set_visible(true);
+            accessibility
+        });
+
         let app_resources = AppResources {
             gpu: gpu.clone(),
             runtime: runtime.clone(),
             assets,
             ctl_tx,
+            accessibility_tree,
             window_physical_size,
             window_logical_size,
             window_scale_factor,
@@ -568,6 +594,7 @@ impl AppBuilder {
             gpu_world_sync_systems: gpu_world_sync_systems(gpu.clone()),
             window_event_systems,
             event_loop,
+            accessibility,
 
             fps: FpsCounter::new(),
             #[cfg(feature = "profile")]
@@ -621,6 +648,7 @@ pub struct App {
     pub runtime: RuntimeHandle,
     pub window: Option<Arc<Window>>,
     event_loop: Option<EventLoop<()>>,
+    accessibility: Option<Accessibility>,
     fps: FpsCounter,
     #[cfg(feature = "profile")]
     _puffin: Option<puffin_http::Server>,
@@ -771,6 +799,11 @@ impl App {
         let gpu_world_sync_systems = &mut self.gpu_world_sync_systems;
         world.resource(gpu()).device.poll(wgpu::Maintain::Poll);
 
+        if let (Some(accessibility), Event::WindowEvent { event, .. }) =
+            (&mut self.accessibility, event)
+        {
+            accessibility.on_window_event(event);
+        }
         self.window_event_systems.run(world, event);
 
         match event {
@@ -834,6 +867,17 @@ impl App {
                                 });
                             }
                         }
+                        WindowCtl::SetImeAllowed(allowed) => {
+                            if let Some(window) = &self.window {
+                                window.set_ime_allowed(allowed);
+                            }
+                        }
+                        WindowCtl::SetImePosition(position) => {
+                            if let Some(window) = &self.window {
+                                window
+                                    .set_ime_position(LogicalPosition::new(position.x, position.y));
+                            }
+                        }
                         WindowCtl::ExitProcess(exit_status) => {
                             *control_flow = ControlFlow::Exit;
                             return exit_status;
@@ -866,6 +910,10 @@ impl App {
                     }
                 }
 
+                if let Some(accessibility) = &mut self.accessibility {
+                    accessibility.update();
+                }
+
                 if let Some(window) = &self.window {
                     window.request_redraw();
                 }
@@ -10,7 +10,10 @@ use ambient_gpu::{
     texture::{Texture, TextureView},
 };
 use ambient_native_std::{asset_cache::SyncAssetKeyExt, color::Color};
-use ambient_renderer::{renderer_stats, RenderTarget, Renderer, RendererConfig, RendererTarget};
+use ambient_renderer::{
+    renderer_stats, texture_streaming::texture_streaming_stats, RenderTarget, Renderer,
+    RendererConfig, RendererTarget, MATERIAL_FORMAT,
+};
 use ambient_ui_native::app_background_color;
 use glam::{uvec2, UVec2};
 use parking_lot::Mutex;
@@ -113,6 +116,8 @@ impl MainRenderer {
                     RendererConfig {
                         scene: main_scene(),
                         shadows: true,
+                        planar_reflections: true,
+                        frame_stats: true,
                         ..Default::default()
                     },
                 );
@@ -289,8 +294,12 @@ impl System for MainRenderer {
             action();
         }
 
+        let stats = match world.resource_opt(texture_streaming_stats()) {
+            Some(textures) => format!("{} {textures}", self.stats()),
+            None => self.stats(),
+        };
         world
-            .set(world.resource_entity(), renderer_stats(), self.stats())
+            .set(world.resource_entity(), renderer_stats(), stats)
             .unwrap();
     }
 }
@@ -299,6 +308,7 @@ pub struct UiRenderer {
     ui_renderer: Renderer,
     depth_buffer_view: Arc<TextureView>,
     normals_view: Arc<TextureView>,
+    material_view: Arc<TextureView>,
 }
 
 impl UiRenderer {
@@ -331,6 +341,8 @@ impl UiRenderer {
             },
         ));
 
+        let material = Self::create_material_buffer(&gpu, &PhysicalSize::new(size.x, size.y));
+
         let mut ui_renderer = Renderer::new(
             &gpu,
             assets,
@@ -345,9 +357,31 @@ impl UiRenderer {
             ui_renderer,
             depth_buffer_view: Arc::new(depth_buffer.create_view(&Default::default())),
             normals_view: Arc::new(normals.create_view(&Default::default())),
+            material_view: Arc::new(material.create_view(&Default::default())),
         }
     }
 
+    fn create_material_buffer(gpu: &Gpu, size: &PhysicalSize<u32>) -> Texture {
+        Texture::new(
+            gpu,
+            &wgpu::TextureDescriptor {
+                label: Some("UiRenderer.material_buffer"),
+                size: wgpu::Extent3d {
+                    width: size.width,
+                    height: size.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: MATERIAL_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        )
+    }
+
     fn create_depth_buffer(gpu: &Gpu, size: &PhysicalSize<u32>) -> Texture {
         Texture::new(
             gpu,
@@ -373,6 +407,8 @@ impl UiRenderer {
     fn resize(&mut self, gpu: &Gpu, size: &PhysicalSize<u32>) {
         let depth_buffer = Arc::new(Self::create_depth_buffer(gpu, size));
         self.depth_buffer_view = Arc::new(depth_buffer.create_view(&Default::default()));
+        let material = Self::create_material_buffer(gpu, size);
+        self.material_view = Arc::new(material.create_view(&Default::default()));
     }
 
     fn render(&mut self, gpu: &Gpu, world: &mut World) {
@@ -411,6 +447,7 @@ impl UiRenderer {
                     depth_or_array_layers: 1,
                 },
                 normals: &self.normals_view,
+                material: &self.material_view,
             },
             Some(app_background_color()),
         );
@@ -285,6 +285,21 @@ impl AssetCache {
         }
     }
 
+    /// Expires the loaded assets whose keys match `filter`, so that they're loaded again the next
+    /// time they're requested, like when their files have changed. Returns how many were expired.
+    pub fn invalidate(&self, filter: impl Fn(&str) -> bool) -> usize {
+        let mut async_ = self.async_cache.lock();
+        let mut count = 0;
+        for (key, asset) in &mut *async_ {
+            if matches!(asset.content, ContentState::Loaded { .. }) && filter(key) {
+                asset.content = ContentState::Expired;
+                asset.keepalive_task = None;
+                count += 1;
+            }
+        }
+        count
+    }
+
     /// Returns a snapshot of the current state of the asset
     pub(crate) fn content_state<
         T: 'static + Clone + Asset + Send + Sync,
@@ -959,6 +974,24 @@ mod test {
         assert!(matches!(state, Some(ContentState::Loaded { .. })));
     }
 
+    #[tokio::test]
+    async fn invalidate() {
+        let assets = AssetCache::new(runtime::Handle::current());
+
+        let foo = TestAssetKey { name: "foo".into() }.get(&assets).await;
+        TestAssetKey { name: "bar".into() }.get(&assets).await;
+
+        assert_eq!(assets.invalidate(|key| key.contains("foo")), 1);
+
+        let state = assets.content_state(&TestAssetKey { name: "foo".into() });
+        assert!(matches!(state, Some(ContentState::Expired)));
+        let state = assets.content_state(&TestAssetKey { name: "bar".into() });
+        assert!(matches!(state, Some(ContentState::Loaded { .. })));
+
+        let reloaded = TestAssetKey { name: "foo".into() }.get(&assets).await;
+        assert!(!Arc::ptr_eq(&foo, &reloaded));
+    }
+
     #[tokio::test]
     async fn test_weak_asset() {
         use std::sync::atomic::{AtomicU32, Ordering};
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+#[cfg(not(target_os = "unknown"))]
+use ambient_native_std::download_asset::BytesFromUrlCachedPath;
 use ambient_native_std::{
     asset_cache::{AsyncAssetKey, AsyncAssetKeyExt},
     asset_url::{AbsAssetUrl, AssetType, GetAssetType},
@@ -12,6 +14,18 @@ use crate::{
     vorbis::VorbisTrack,
     Error,
 };
+fn url_format(url: &AbsAssetUrl) -> Result<AudioFormat, Arc<Error>> {
+    match url.extension().as_ref().map(|x| x as &str) {
+        Some("wav") => Ok(AudioFormat::Wav),
+        Some("ogg") => Ok(AudioFormat::Vorbis),
+        Some("mp3") => Ok(AudioFormat::Mp3),
+        Some("flac") => Ok(AudioFormat::Flac),
+        v => Err(Arc::new(Error::UnsupportedFormat(
+            v.unwrap_or_default().to_string(),
+        ))),
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct AudioFromUrl {
     pub url: AbsAssetUrl,
@@ -26,15 +40,7 @@ impl AsyncAssetKey<Result<Arc<Track>, Arc<Error>>> for AudioFromUrl {
     where
         Self: 'async_trait,
     {
-        let format = match self.url.extension().as_ref().map(|x| x as &str) {
-            Some("wav") => AudioFormat::Wav,
-            Some("ogg") => AudioFormat::Vorbis,
-            v => {
-                return Err(Arc::new(Error::UnsupportedFormat(
-                    v.unwrap_or_default().to_string(),
-                )))
-            }
-        };
+        let format = url_format(&self.url)?;
         let bytes: Arc<[u8]> = BytesFromUrl::new(self.url.clone(), true)
             .get(&assets)
             .await
@@ -45,6 +51,47 @@ impl AsyncAssetKey<Result<Arc<Track>, Arc<Error>>> for AudioFromUrl {
     }
 }
 
+/// Loads a track which is decoded from the disk cache as it plays, rather than from memory.
+///
+/// On the web, the track is loaded into memory like [AudioFromUrl].
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct AudioStreamFromUrl {
+    pub url: AbsAssetUrl,
+}
+
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<Track>, Arc<Error>>> for AudioStreamFromUrl {
+    #[cfg(not(target_os = "unknown"))]
+    async fn load(
+        self,
+        assets: ambient_native_std::asset_cache::AssetCache,
+    ) -> Result<Arc<Track>, Arc<Error>>
+    where
+        Self: 'async_trait,
+    {
+        let format = url_format(&self.url)?;
+        let path = BytesFromUrlCachedPath {
+            url: self.url.clone(),
+        }
+        .get(&assets)
+        .await
+        .map_err(|e| Arc::new(e.into()))?;
+
+        Ok(Arc::new(Track::from_file(&*path, format)?))
+    }
+
+    #[cfg(target_os = "unknown")]
+    async fn load(
+        self,
+        assets: ambient_native_std::asset_cache::AssetCache,
+    ) -> Result<Arc<Track>, Arc<Error>>
+    where
+        Self: 'async_trait,
+    {
+        AudioFromUrl { url: self.url }.get(&assets).await
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct VorbisFromUrl {
     pub url: AbsAssetUrl,
@@ -0,0 +1,479 @@
+use std::{
+    collections::HashMap,
+    f32::consts::TAU,
+    mem::discriminant,
+    sync::{Arc, Weak},
+};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    reverb::Reverb,
+    AudioClock, AudioMixer, Frame, SampleRate, Sound, SoundId, Source,
+};
+
+pub const MASTER_BUS: &str = "master";
+pub const MUSIC_BUS: &str = "music";
+pub const SFX_BUS: &str = "sfx";
+pub const VOICE_BUS: &str = "voice";
+
+/// An effect applied to everything that plays on a bus
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BusEffect {
+    /// One pole low pass filter with the given cutoff frequency
+    LowPass(f32),
+    /// One pole high pass filter with the given cutoff frequency
+    HighPass(f32),
+    /// Reverb which mixes `wet` of the reverberated sound with the rest of the dry sound. See
+    /// [Reverb::process] for the other parameters.
+    Reverb {
+        room_size: f32,
+        damping: f32,
+        wet: f32,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum EffectState {
+    OnePole(Frame),
+    Reverb(Box<Reverb>),
+}
+
+/// An effect in a bus, along with its state
+#[derive(Debug, Clone)]
+struct EffectSlot {
+    effect: BusEffect,
+    state: EffectState,
+}
+
+impl EffectSlot {
+    fn new(effect: BusEffect, sample_rate: SampleRate) -> Self {
+        let state = match effect {
+            BusEffect::LowPass(_) | BusEffect::HighPass(_) => EffectState::OnePole(Frame::ZERO),
+            BusEffect::Reverb { .. } => EffectState::Reverb(Box::new(Reverb::new(sample_rate))),
+        };
+        Self { effect, state }
+    }
+
+    fn process(&mut self, sample: Frame, sample_rate: SampleRate) -> Frame {
+        let onepole = |y1: Frame, freq: f32| {
+            let b = (-TAU * freq / sample_rate as f32).exp();
+            sample * (1.0 - b) + y1 * b
+        };
+        match (self.effect, &mut self.state) {
+            (BusEffect::LowPass(freq), EffectState::OnePole(y1)) => {
+                *y1 = onepole(*y1, freq);
+                *y1
+            }
+            (BusEffect::HighPass(freq), EffectState::OnePole(y1)) => {
+                *y1 = onepole(*y1, freq);
+                sample - *y1
+            }
+            (
+                BusEffect::Reverb {
+                    room_size,
+                    damping,
+                    wet,
+                },
+                EffectState::Reverb(reverb),
+            ) => {
+                let wet = wet.clamp(0.0, 1.0);
+                sample * (1.0 - wet) + reverb.process(sample, room_size, damping) * wet
+            }
+            _ => unreachable!("effect state does not match the effect"),
+        }
+    }
+}
+
+/// Receives the output of a bus on the mixer thread, like a meter or a recorder
+pub trait BusTap: Send + Sync + std::fmt::Debug {
+    /// Called with every block of frames the bus outputs, after its effects and gain
+    fn process(&self, frames: &[Frame], sample_rate: SampleRate);
+}
+
+#[derive(Debug)]
+struct BusState {
+    gain: f32,
+    muted: bool,
+    effects: Vec<EffectSlot>,
+    children: Vec<AudioBus>,
+    taps: Vec<Arc<dyn BusTap>>,
+    buffer: Vec<Frame>,
+}
+
+#[derive(Debug)]
+struct AudioBusInner {
+    name: String,
+    mixer: AudioMixer,
+    state: Mutex<BusState>,
+}
+
+#[derive(Clone, Debug)]
+pub struct WeakAudioBus {
+    inner: Weak<AudioBusInner>,
+}
+
+impl WeakAudioBus {
+    pub fn upgrade(&self) -> Option<AudioBus> {
+        Some(AudioBus {
+            inner: self.inner.upgrade()?,
+        })
+    }
+}
+
+/// A named group of sounds which are mixed together with the child buses, passed through the
+/// effects of the bus, and scaled by its gain.
+#[derive(Clone, Debug)]
+pub struct AudioBus {
+    inner: Arc<AudioBusInner>,
+}
+
+impl AudioBus {
+    pub fn new(name: impl Into<String>, sample_rate: SampleRate) -> Self {
+        Self {
+            inner: Arc::new(AudioBusInner {
+                name: name.into(),
+                mixer: AudioMixer::new(sample_rate),
+                state: Mutex::new(BusState {
+                    gain: 1.0,
+                    muted: false,
+                    effects: Vec::new(),
+                    children: Vec::new(),
+                    taps: Vec::new(),
+                    buffer: Vec::new(),
+                }),
+            }),
+        }
+    }
+
+    pub fn downgrade(&self) -> WeakAudioBus {
+        WeakAudioBus {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.inner.name
+    }
+
+    /// The mixer of the sounds playing directly on this bus
+    pub fn mixer(&self) -> &AudioMixer {
+        &self.inner.mixer
+    }
+
+    /// Play a source on this bus
+    pub fn play<S: Source + 'static>(&self, source: S) -> Sound {
+        self.inner.mixer.play(source)
+    }
+
+    /// Play a source on this bus from `frame` of `clock`
+    pub fn play_at<S: Source + 'static>(&self, source: S, clock: &AudioClock, frame: u64) -> Sound {
+        self.inner.mixer.play_at(source, clock, frame)
+    }
+
+    pub fn stop(&self, id: SoundId) {
+        self.inner.mixer.stop(id)
+    }
+
+    pub fn gain(&self) -> f32 {
+        self.inner.state.lock().gain
+    }
+
+    pub fn set_gain(&self, gain: f32) {
+        self.inner.state.lock().gain = gain;
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.inner.state.lock().muted
+    }
+
+    /// A muted bus keeps playing its sounds, but outputs silence
+    pub fn set_muted(&self, muted: bool) {
+        self.inner.state.lock().muted = muted;
+    }
+
+    pub fn effects(&self) -> Vec<BusEffect> {
+        let state = self.inner.state.lock();
+        state.effects.iter().map(|slot| slot.effect).collect()
+    }
+
+    /// Replace the effects of the bus, which are applied in order. Effects of the same kind which
+    /// stay in the same slot keep their state, so that updating a bus doesn't click or cut off
+    /// its reverb tail.
+    pub fn set_effects(&self, effects: &[BusEffect]) {
+        let sample_rate = self.inner.mixer.sample_rate();
+        let mut state = self.inner.state.lock();
+        state.effects.truncate(effects.len());
+        for (i, &effect) in effects.iter().enumerate() {
+            match state.effects.get_mut(i) {
+                Some(slot) if discriminant(&slot.effect) == discriminant(&effect) => {
+                    slot.effect = effect
+                }
+                Some(slot) => *slot = EffectSlot::new(effect, sample_rate),
+                None => state.effects.push(EffectSlot::new(effect, sample_rate)),
+            }
+        }
+    }
+
+    pub fn children(&self) -> Vec<AudioBus> {
+        self.inner.state.lock().children.clone()
+    }
+
+    /// Adds a tap which receives everything the bus outputs
+    pub fn add_tap(&self, tap: Arc<dyn BusTap>) {
+        self.inner.state.lock().taps.push(tap);
+    }
+
+    pub fn remove_tap(&self, tap: &Arc<dyn BusTap>) {
+        // Compare the data pointers only, as the vtables of the same type may differ
+        let tap = Arc::as_ptr(tap) as *const ();
+        self.inner
+            .state
+            .lock()
+            .taps
+            .retain(|v| Arc::as_ptr(v) as *const () != tap);
+    }
+
+    fn add_child(&self, child: AudioBus) {
+        self.inner.state.lock().children.push(child);
+    }
+
+    fn remove_child(&self, child: &AudioBus) {
+        self.inner
+            .state
+            .lock()
+            .children
+            .retain(|v| !Arc::ptr_eq(&v.inner, &child.inner));
+    }
+
+    /// Returns true if `other` is this bus or one of its descendants
+    fn contains(&self, other: &AudioBus) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+            || self.children().iter().any(|child| child.contains(other))
+    }
+}
+
+impl Source for AudioBus {
+    fn next_sample(&mut self) -> Option<Frame> {
+        let mut state = self.inner.state.lock();
+        let sample_rate = self.inner.mixer.sample_rate();
+
+        let mut sample = self.inner.mixer.clone().next_sample().unwrap_or_default();
+        for child in &mut state.children {
+            sample += child.next_sample().unwrap_or_default();
+        }
+        for slot in &mut state.effects {
+            sample = slot.process(sample, sample_rate);
+        }
+
+        let sample = if state.muted {
+            Frame::ZERO
+        } else {
+            sample * state.gain
+        };
+        for tap in &state.taps {
+            tap.process(&[sample], sample_rate);
+        }
+
+        Some(sample)
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.inner.mixer.sample_rate()
+    }
+
+    fn sample_buffered(&mut self, output: &mut [Frame]) -> usize {
+        let mut state = self.inner.state.lock();
+        let sample_rate = self.inner.mixer.sample_rate();
+        let BusState {
+            gain,
+            muted,
+            effects,
+            children,
+            taps,
+            buffer,
+        } = &mut *state;
+
+        buffer.clear();
+        buffer.resize(output.len(), Frame::ZERO);
+        self.inner.mixer.clone().sample_buffered(buffer);
+        for child in children {
+            child.sample_buffered(buffer);
+        }
+
+        let gain = if *muted { 0.0 } else { *gain };
+        for sample in buffer.iter_mut() {
+            for slot in effects.iter_mut() {
+                *sample = slot.process(*sample, sample_rate);
+            }
+            *sample *= gain;
+        }
+
+        for (dst, src) in output.iter_mut().zip(buffer.iter()) {
+            *dst += *src;
+        }
+        for tap in taps.iter() {
+            tap.process(buffer, sample_rate);
+        }
+
+        output.len()
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The hierarchy of buses, rooted in the `master` bus which has the `music`, `sfx` and `voice`
+/// buses as children.
+#[derive(Clone, Debug)]
+pub struct AudioBuses {
+    master: AudioBus,
+    buses: Arc<Mutex<HashMap<String, AudioBus>>>,
+    clock: AudioClock,
+}
+
+impl AudioBuses {
+    pub fn new(sample_rate: SampleRate) -> Self {
+        let master = AudioBus::new(MASTER_BUS, sample_rate);
+        let mut buses = HashMap::new();
+        buses.insert(MASTER_BUS.to_string(), master.clone());
+        for name in [MUSIC_BUS, SFX_BUS, VOICE_BUS] {
+            let bus = AudioBus::new(name, sample_rate);
+            master.add_child(bus.clone());
+            buses.insert(name.to_string(), bus);
+        }
+
+        Self {
+            master,
+            buses: Arc::new(Mutex::new(buses)),
+            clock: AudioClock::new(sample_rate),
+        }
+    }
+
+    pub fn master(&self) -> &AudioBus {
+        &self.master
+    }
+
+    /// The clock of the stream which plays the master bus
+    pub fn clock(&self) -> &AudioClock {
+        &self.clock
+    }
+
+    pub fn get(&self, name: &str) -> Option<AudioBus> {
+        self.buses.lock().get(name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.buses.lock().keys().cloned().collect()
+    }
+
+    /// Returns the bus `name`, creating it as a child of the master bus if it doesn't exist
+    pub fn get_or_create(&self, name: &str) -> AudioBus {
+        let mut buses = self.buses.lock();
+        if let Some(bus) = buses.get(name) {
+            return bus.clone();
+        }
+
+        let bus = AudioBus::new(name, self.master.sample_rate());
+        self.master.add_child(bus.clone());
+        buses.insert(name.to_string(), bus.clone());
+        bus
+    }
+
+    /// Returns the parent of `bus`, or `None` for the master bus
+    pub fn parent(&self, bus: &AudioBus) -> Option<AudioBus> {
+        self.buses
+            .lock()
+            .values()
+            .find(|parent| {
+                parent
+                    .children()
+                    .iter()
+                    .any(|child| Arc::ptr_eq(&child.inner, &bus.inner))
+            })
+            .cloned()
+    }
+
+    /// Removes the bus `name` along with its children, which stop being played. The master bus
+    /// can not be removed.
+    pub fn remove(&self, name: &str) -> Option<AudioBus> {
+        if name == MASTER_BUS {
+            return None;
+        }
+        let bus = self.buses.lock().remove(name)?;
+        if let Some(parent) = self.parent(&bus) {
+            parent.remove_child(&bus);
+        }
+        Some(bus)
+    }
+
+    /// Moves `bus` under `parent`. Fails if that would create a cycle.
+    pub fn set_parent(&self, bus: &AudioBus, parent: &AudioBus) -> Result<()> {
+        if bus.contains(parent) {
+            return Err(Error::BusCycle(
+                bus.name().to_string(),
+                parent.name().to_string(),
+            ));
+        }
+
+        if let Some(old_parent) = self.parent(bus) {
+            if Arc::ptr_eq(&old_parent.inner, &parent.inner) {
+                return Ok(());
+            }
+            old_parent.remove_child(bus);
+        }
+        parent.add_child(bus.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BufferedSource;
+
+    fn source() -> BufferedSource<[f32; 4], f32> {
+        BufferedSource::new([1.0, 1.0, 1.0, 1.0], 2, 4)
+    }
+
+    #[test]
+    fn bus_gain() {
+        let buses = AudioBuses::new(4);
+        buses.get(SFX_BUS).unwrap().play(source());
+        buses.get(SFX_BUS).unwrap().set_gain(0.5);
+        buses.master().set_gain(0.5);
+
+        let mut master = buses.master().clone();
+        assert_eq!(master.next_sample(), Some(Frame::splat(0.25)));
+
+        buses.get(MUSIC_BUS).unwrap().play(source());
+        assert_eq!(master.next_sample(), Some(Frame::splat(0.75)));
+
+        buses.master().set_muted(true);
+        assert_eq!(master.next_sample(), Some(Frame::ZERO));
+    }
+
+    #[test]
+    fn bus_hierarchy() {
+        let buses = AudioBuses::new(4);
+        let ui = buses.get_or_create("ui");
+        let sfx = buses.get(SFX_BUS).unwrap();
+        buses.set_parent(&ui, &sfx).unwrap();
+        assert_eq!(buses.parent(&ui).unwrap().name(), SFX_BUS);
+        assert!(buses.set_parent(&sfx, &ui).is_err());
+
+        ui.play(source());
+        sfx.set_muted(true);
+        assert_eq!(buses.master().clone().next_sample(), Some(Frame::ZERO));
+
+        sfx.set_muted(false);
+        assert!(buses.remove(MASTER_BUS).is_none());
+        buses.remove("ui").unwrap();
+        assert!(buses.get("ui").is_none());
+        assert!(sfx.children().is_empty());
+        assert_eq!(buses.master().clone().next_sample(), Some(Frame::ZERO));
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    InputCallbackInfo, Sample,
+};
+use flume::{Receiver, Sender};
+
+use crate::{
+    error::{Error, Result},
+    Frame, SampleRate,
+};
+
+/// Captures audio from the default input device, like a microphone.
+///
+/// The cpal stream can not be moved across threads, so it lives on a thread of its own until the
+/// capture is dropped.
+#[derive(Debug)]
+pub struct AudioCapture {
+    receiver: Receiver<Frame>,
+    sample_rate: SampleRate,
+    running: Arc<AtomicBool>,
+}
+
+impl AudioCapture {
+    pub fn new() -> Result<Self> {
+        let (sender, receiver) = flume::unbounded();
+        let (init_tx, init_rx) = flume::bounded(1);
+        let running = Arc::new(AtomicBool::new(true));
+
+        thread::Builder::new()
+            .name("audio_capture".into())
+            .spawn({
+                let running = running.clone();
+                move || {
+                    let stream = match start_stream(sender) {
+                        Ok((stream, sample_rate)) => {
+                            let _ = init_tx.send(Ok(sample_rate));
+                            stream
+                        }
+                        Err(err) => {
+                            let _ = init_tx.send(Err(err));
+                            return;
+                        }
+                    };
+                    while running.load(Ordering::Relaxed) {
+                        thread::park_timeout(Duration::from_millis(100));
+                    }
+                    drop(stream);
+                }
+            })
+            .map_err(Error::CaptureThread)?;
+
+        let sample_rate = init_rx.recv().map_err(|_| Error::NoInputDevice)??;
+
+        Ok(Self {
+            receiver,
+            sample_rate,
+            running,
+        })
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    /// Returns the frames captured since the last call
+    pub fn drain(&self) -> impl Iterator<Item = Frame> + '_ {
+        self.receiver.try_iter()
+    }
+}
+
+impl Drop for AudioCapture {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+fn start_stream(sender: Sender<Frame>) -> Result<(cpal::Stream, SampleRate)> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or(Error::NoInputDevice)?;
+    let config = device.default_input_config()?;
+    let format = config.sample_format();
+    let config: cpal::StreamConfig = config.into();
+    tracing::debug!("Audio capture config: {config:?}");
+
+    let channels = config.channels as usize;
+    if channels == 0 {
+        return Err(Error::InvalidChannelCount(config.channels));
+    }
+
+    fn reader<T: Sample>(
+        sender: Sender<Frame>,
+        channels: usize,
+    ) -> impl FnMut(&[T], &InputCallbackInfo) {
+        move |data, _| {
+            for frame in data.chunks_exact(channels) {
+                let frame = match frame {
+                    [mono] => Frame::splat(mono.to_f32()),
+                    [l, r, ..] => Frame::new(l.to_f32(), r.to_f32()),
+                    [] => unreachable!(),
+                };
+                let _ = sender.send(frame);
+            }
+        }
+    }
+
+    let err_func = |err| tracing::error!("Audio capture error: {err}");
+    let stream = match format {
+        cpal::SampleFormat::I16 => {
+            device.build_input_stream(&config, reader::<i16>(sender, channels), err_func)
+        }
+        cpal::SampleFormat::U16 => {
+            device.build_input_stream(&config, reader::<u16>(sender, channels), err_func)
+        }
+        cpal::SampleFormat::F32 => {
+            device.build_input_stream(&config, reader::<f32>(sender, channels), err_func)
+        }
+    }?;
+    stream.play()?;
+
+    Ok((stream, config.sample_rate.0 as _))
+}
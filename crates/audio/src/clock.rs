@@ -0,0 +1,56 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use crate::SampleRate;
+
+#[derive(Debug)]
+struct ClockInner {
+    frames: AtomicU64,
+    sample_rate: SampleRate,
+}
+
+/// Counts the frames the audio stream has output, which all of its buses share.
+///
+/// Sounds can be scheduled to start on an exact frame of the clock with
+/// [AudioBus::play_at](crate::AudioBus::play_at), rather than whenever they happen to be played.
+#[derive(Debug, Clone)]
+pub struct AudioClock {
+    inner: Arc<ClockInner>,
+}
+
+impl AudioClock {
+    pub fn new(sample_rate: SampleRate) -> Self {
+        Self {
+            inner: Arc::new(ClockInner {
+                frames: AtomicU64::new(0),
+                sample_rate,
+            }),
+        }
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.inner.sample_rate
+    }
+
+    /// The frame that the next block of the stream starts at
+    pub fn frames(&self) -> u64 {
+        self.inner.frames.load(Ordering::Acquire)
+    }
+
+    /// The time of the next block of the stream, in seconds
+    pub fn time(&self) -> f64 {
+        self.frames() as f64 / self.inner.sample_rate as f64
+    }
+
+    /// Returns the frame at `time` seconds
+    pub fn frame_at(&self, time: f64) -> u64 {
+        (time * self.inner.sample_rate as f64).round().max(0.0) as u64
+    }
+
+    /// Moves the clock forward once the stream has output `frames`
+    pub fn advance(&self, frames: u64) {
+        self.inner.frames.fetch_add(frames, Ordering::AcqRel);
+    }
+}
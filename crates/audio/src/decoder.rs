@@ -0,0 +1,254 @@
+//! Decoding of mp3 and flac, through symphonia
+
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL},
+    errors::Error as SymphoniaError,
+    formats::{FormatOptions, FormatReader, SeekMode, SeekTo},
+    io::{MediaSource, MediaSourceStream},
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+use crate::{
+    track::{AudioFormat, TrackData, TrackReader},
+    Error, Frame, Result, SampleRate, Source,
+};
+
+impl MediaSource for TrackReader {
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        match self {
+            TrackReader::Bytes(v) => Some(v.get_ref().len() as u64),
+            TrackReader::File(v) => v.get_ref().metadata().ok().map(|v| v.len()),
+        }
+    }
+}
+
+fn extension(format: &AudioFormat) -> &'static str {
+    match format {
+        AudioFormat::Wav => "wav",
+        AudioFormat::Vorbis => "ogg",
+        AudioFormat::Mp3 => "mp3",
+        AudioFormat::Flac => "flac",
+    }
+}
+
+/// Opens the format reader and a decoder for the first audio track of `data`
+fn open(
+    data: &TrackData,
+    format: &AudioFormat,
+) -> Result<(Box<dyn FormatReader>, Box<dyn Decoder>, u32)> {
+    let mut hint = Hint::new();
+    hint.with_extension(extension(format));
+    let stream = MediaSourceStream::new(Box::new(data.reader()?), Default::default());
+
+    let reader = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )?
+        .format;
+    let track = reader
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| Error::UnsupportedFormat("no audio track".into()))?;
+    let decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+    let track_id = track.id;
+
+    Ok((reader, decoder, track_id))
+}
+
+/// A track in one of the formats decoded by symphonia, like mp3 or flac
+#[derive(Debug, Clone)]
+pub struct SymphoniaTrack {
+    data: TrackData,
+    format: AudioFormat,
+    sample_rate: SampleRate,
+    /// The number of frames in the fully decoded audio
+    decoded_len: u64,
+}
+
+impl SymphoniaTrack {
+    pub fn from_data(data: TrackData, format: AudioFormat) -> Result<Self> {
+        let (mut reader, decoder, track_id) = open(&data, &format)?;
+        let params = decoder.codec_params();
+        let sample_rate = params
+            .sample_rate
+            .ok_or_else(|| Error::UnsupportedFormat("audio without a sample rate".into()))?
+            as SampleRate;
+
+        // Formats without the length in their header, like mp3 without a Xing header, are
+        // scanned for it, which doesn't need any decoding
+        let decoded_len = match params.n_frames {
+            Some(len) => len,
+            None => {
+                let mut len = 0;
+                loop {
+                    match reader.next_packet() {
+                        Ok(packet) if packet.track_id() == track_id => len += packet.dur,
+                        Ok(_) => {}
+                        Err(SymphoniaError::IoError(_)) => break,
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                len
+            }
+        };
+
+        Ok(Self {
+            data,
+            format,
+            sample_rate,
+            decoded_len,
+        })
+    }
+
+    pub fn decode(&self) -> SymphoniaDecodeStream {
+        SymphoniaDecodeStream::new(self.clone())
+    }
+}
+
+/// Audio source which decodes a [SymphoniaTrack] as it plays
+pub struct SymphoniaDecodeStream {
+    track: SymphoniaTrack,
+    reader: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    packet: Vec<Frame>,
+    cursor: usize,
+}
+
+impl SymphoniaDecodeStream {
+    fn new(track: SymphoniaTrack) -> Self {
+        // Creation of the track ensures the stream can be opened
+        let (reader, decoder, track_id) = open(&track.data, &track.format).unwrap();
+        Self {
+            track,
+            reader,
+            decoder,
+            track_id,
+            packet: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Decodes the next packet of the track, returning its timestamp in frames and its frames,
+    /// or `None` at the end of the stream
+    fn read_packet(&mut self) -> Option<(u64, Vec<Frame>)> {
+        loop {
+            let packet = match self.reader.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(_)) => return None,
+                Err(err) => {
+                    tracing::error!("Failed to read audio packet: {err}");
+                    return None;
+                }
+            };
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+
+            let decoded = match self.decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                // Corrupt packets are skipped
+                Err(SymphoniaError::DecodeError(err)) => {
+                    tracing::warn!("Skipping corrupt audio packet: {err}");
+                    continue;
+                }
+                Err(err) => {
+                    tracing::error!("Failed to decode audio packet: {err}");
+                    return None;
+                }
+            };
+
+            let channels = decoded.spec().channels.count();
+            let mut samples = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+            samples.copy_interleaved_ref(decoded);
+            // Only the first two channels are played
+            let frames = match channels {
+                1 => samples
+                    .samples()
+                    .iter()
+                    .copied()
+                    .map(Frame::splat)
+                    .collect(),
+                _ => samples
+                    .samples()
+                    .chunks_exact(channels)
+                    .map(|v| Frame::new(v[0], v[1]))
+                    .collect(),
+            };
+            return Some((packet.ts(), frames));
+        }
+    }
+}
+
+impl Clone for SymphoniaDecodeStream {
+    fn clone(&self) -> Self {
+        Self::new(self.track.clone())
+    }
+}
+
+impl Source for SymphoniaDecodeStream {
+    #[inline]
+    fn next_sample(&mut self) -> Option<Frame> {
+        loop {
+            if let Some(&s) = self.packet.get(self.cursor) {
+                self.cursor += 1;
+                return Some(s);
+            }
+            self.packet = self.read_packet()?.1;
+            self.cursor = 0;
+        }
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.track.sample_rate
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        Some(self.track.decoded_len)
+    }
+
+    /// Seeks to the packet containing `position`, and skips ahead within it so that the seek is
+    /// sample accurate.
+    fn seek(&mut self, position: u64) -> bool {
+        let seeked = self.reader.seek(
+            SeekMode::Accurate,
+            SeekTo::TimeStamp {
+                ts: position,
+                track_id: self.track_id,
+            },
+        );
+        let required = match seeked {
+            Ok(seeked) => seeked.required_ts,
+            Err(err) => {
+                tracing::error!("Failed to seek in audio stream: {err}");
+                return false;
+            }
+        };
+        self.decoder.reset();
+
+        loop {
+            let Some((ts, packet)) = self.read_packet() else {
+                // Seeked past the end
+                self.packet = Vec::new();
+                self.cursor = 0;
+                return true;
+            };
+            if required < ts + packet.len() as u64 {
+                self.cursor = (required.saturating_sub(ts) as usize).min(packet.len());
+                self.packet = packet;
+                return true;
+            }
+        }
+    }
+}
@@ -11,6 +11,10 @@ use crate::hrtf;
 pub enum Error {
     #[error("Failed to find audio output device")]
     NoOutputDevice,
+    #[error("Failed to find audio input device")]
+    NoInputDevice,
+    #[error("Failed to start audio capture: {0}")]
+    CaptureThread(io::Error),
     #[error("Failed to find appropriate audio config")]
     NoOutputConfig,
     #[error("Default stream config error")]
@@ -21,6 +25,10 @@ pub enum Error {
     PlayStreamError(#[from] PlayStreamError),
     #[error("Failed to decode wav")]
     WavError(#[from] hound::Error),
+    #[error("Failed to write wav: {0}")]
+    WavWrite(hound::Error),
+    #[error("Failed to start audio recording: {0}")]
+    RecorderThread(io::Error),
     #[error("Unsupported file format: {0:?}")]
     UnsupportedFormat(String),
     #[error("Failed to open {1:?}: {0}")]
@@ -29,6 +37,8 @@ pub enum Error {
     ContentDownload(#[from] AssetError),
     #[error("Failed to decode vorbis")]
     Vorbis(#[from] VorbisError),
+    #[error("Failed to decode audio: {0}")]
+    Symphonia(#[from] symphonia::core::errors::Error),
     #[error("Invalid channel configuration: {0}")]
     InvalidChannelCount(u16),
     #[error("Failed to load IR sphere for spatial audio")]
@@ -36,6 +46,11 @@ pub enum Error {
 
     #[error("Too many channels in ogg stream. Expected a maximum of 2 channels, found {0}")]
     TooManyOggChannels(usize),
+    #[cfg(not(target_os = "unknown"))]
+    #[error("Failed to encode or decode voice: {0}")]
+    Opus(#[from] opus::Error),
+    #[error("Can not move bus {0:?} into its own descendant {1:?}")]
+    BusCycle(String, String),
 }
 
 pub(crate) type Result<T> = std::result::Result<T, Error>;
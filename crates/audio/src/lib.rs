@@ -1,25 +1,45 @@
 mod assets;
+mod bus;
+#[cfg(not(target_os = "unknown"))]
+mod capture;
+mod clock;
 mod error;
+mod loop_points;
+mod meter;
 mod mixer;
+#[cfg(not(target_os = "unknown"))]
+mod recorder;
 // mod sink;
 mod stream;
 
 mod barycentric;
 pub mod blt;
+pub mod decoder;
 /// Fast fourier transform
 pub mod hrtf;
+pub mod reverb;
 pub mod signal;
 pub mod source;
 mod spatial;
 pub mod track;
 pub mod utils;
 pub mod value;
+#[cfg(not(target_os = "unknown"))]
+pub mod voice;
 pub mod vorbis;
 pub mod wav;
 
 pub use assets::*;
+pub use bus::*;
+#[cfg(not(target_os = "unknown"))]
+pub use capture::*;
+pub use clock::*;
 pub use error::*;
+pub use loop_points::*;
+pub use meter::*;
 pub use mixer::*;
+#[cfg(not(target_os = "unknown"))]
+pub use recorder::*;
 // pub use sink::*;
 pub use source::*;
 pub use spatial::*;
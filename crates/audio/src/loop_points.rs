@@ -0,0 +1,172 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// The loop points stored in the metadata of a track, in frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopPoints {
+    /// The frame a looping track jumps back to
+    pub start: u64,
+    /// The frame at which a looping track jumps back to `start`, or the end of the track if `None`
+    pub end: Option<u64>,
+}
+
+impl LoopPoints {
+    /// The Vorbis comments holding the loop points, as written by most audio tools
+    pub const START_COMMENT: &'static str = "LOOPSTART";
+    pub const END_COMMENT: &'static str = "LOOPEND";
+    /// Holds the length of the loop rather than its end
+    pub const LENGTH_COMMENT: &'static str = "LOOPLENGTH";
+
+    /// Reads the loop points from the `LOOPSTART`, and `LOOPEND` or `LOOPLENGTH` comments of an
+    /// Ogg Vorbis or FLAC file. Returns `None` if there are none.
+    pub fn from_comments<K: AsRef<str>, V: AsRef<str>>(
+        comments: impl IntoIterator<Item = (K, V)>,
+    ) -> Option<Self> {
+        let (mut start, mut end, mut length) = (None, None, None);
+        for (key, value) in comments {
+            let (key, value) = (key.as_ref(), value.as_ref());
+            let Ok(value) = value.trim().parse::<u64>() else {
+                continue;
+            };
+            if key.eq_ignore_ascii_case(Self::START_COMMENT) {
+                start = Some(value);
+            } else if key.eq_ignore_ascii_case(Self::END_COMMENT) {
+                end = Some(value);
+            } else if key.eq_ignore_ascii_case(Self::LENGTH_COMMENT) {
+                length = Some(value);
+            }
+        }
+        if start.is_none() && end.is_none() && length.is_none() {
+            return None;
+        }
+
+        let start = start.unwrap_or(0);
+        Some(Self {
+            start,
+            end: end.or(length.map(|length| start + length)),
+        })
+    }
+
+    /// The comments holding these loop points, see [Self::from_comments]
+    pub fn to_comments(&self) -> Vec<(&'static str, String)> {
+        let mut comments = vec![(Self::START_COMMENT, self.start.to_string())];
+        if let Some(end) = self.end {
+            comments.push((Self::END_COMMENT, end.to_string()));
+        }
+        comments
+    }
+
+    /// Reads the first loop of the `smpl` chunk of a WAV file. Returns `None` if there is none.
+    pub fn from_wav(reader: &mut (impl Read + Seek)) -> io::Result<Option<Self>> {
+        let mut header = [0; 12];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+        if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+            return Ok(None);
+        }
+
+        loop {
+            let mut chunk = [0; 8];
+            match reader.read_exact(&mut chunk) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(err) => return Err(err),
+            }
+            let size = u32::from_le_bytes(chunk[4..8].try_into().unwrap()) as u64;
+            if &chunk[0..4] == b"smpl" {
+                let mut data = vec![0; size as usize];
+                reader.read_exact(&mut data)?;
+                return Ok(Self::from_smpl_chunk(&data));
+            }
+            // Chunks are padded to an even size
+            reader.seek(SeekFrom::Current((size + size % 2) as i64))?;
+        }
+    }
+
+    fn from_smpl_chunk(data: &[u8]) -> Option<Self> {
+        let u32_at = |offset: usize| {
+            Some(u32::from_le_bytes(
+                data.get(offset..offset + 4)?.try_into().unwrap(),
+            ))
+        };
+        if u32_at(28)? == 0 {
+            return None;
+        }
+        // The loops follow the 36 bytes of the header, and their ends are inclusive
+        let start = u32_at(36 + 8)? as u64;
+        let end = u32_at(36 + 12)? as u64 + 1;
+        Some(Self {
+            start,
+            end: Some(end),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn loop_points_from_comments() {
+        assert_eq!(
+            LoopPoints::from_comments([("LOOPSTART", "100"), ("LOOPEND", "200")]),
+            Some(LoopPoints {
+                start: 100,
+                end: Some(200)
+            })
+        );
+        assert_eq!(
+            LoopPoints::from_comments([("loopstart", "100"), ("LOOPLENGTH", "50")]),
+            Some(LoopPoints {
+                start: 100,
+                end: Some(150)
+            })
+        );
+        assert_eq!(
+            LoopPoints::from_comments([("TITLE", "Theme"), ("LOOPSTART", "nope")]),
+            None
+        );
+
+        let points = LoopPoints {
+            start: 10,
+            end: Some(20),
+        };
+        assert_eq!(
+            LoopPoints::from_comments(points.to_comments()),
+            Some(points)
+        );
+    }
+
+    #[test]
+    fn loop_points_from_wav() {
+        let mut smpl = vec![0u8; 36 + 24];
+        smpl[28..32].copy_from_slice(&1u32.to_le_bytes());
+        smpl[44..48].copy_from_slice(&100u32.to_le_bytes());
+        smpl[48..52].copy_from_slice(&199u32.to_le_bytes());
+
+        let mut wav = Vec::new();
+        wav.extend(b"RIFF");
+        wav.extend(0u32.to_le_bytes());
+        wav.extend(b"WAVE");
+        // An odd-sized chunk, which is padded
+        wav.extend(b"LIST");
+        wav.extend(3u32.to_le_bytes());
+        wav.extend([0, 0, 0, 0]);
+        wav.extend(b"smpl");
+        wav.extend((smpl.len() as u32).to_le_bytes());
+        wav.extend(&smpl);
+
+        assert_eq!(
+            LoopPoints::from_wav(&mut Cursor::new(&wav)).unwrap(),
+            Some(LoopPoints {
+                start: 100,
+                end: Some(200)
+            })
+        );
+        assert_eq!(
+            LoopPoints::from_wav(&mut Cursor::new(&wav[..24])).unwrap(),
+            None
+        );
+    }
+}
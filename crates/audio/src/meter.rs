@@ -0,0 +1,255 @@
+use std::{collections::VecDeque, f64::consts::PI};
+
+use parking_lot::Mutex;
+
+use crate::{BusTap, Frame, SampleRate};
+
+/// Length of the blocks loudness is measured in, in seconds
+const BLOCK_LENGTH: f64 = 0.1;
+/// The number of blocks in the 400 ms window of the momentary loudness
+const WINDOW_BLOCKS: usize = 4;
+/// Windows quieter than this are left out of the integrated loudness
+const ABSOLUTE_GATE: f64 = -70.0;
+/// Windows this much quieter than the ungated loudness are left out of the integrated loudness
+const RELATIVE_GATE: f64 = -10.0;
+
+/// The loudness of a bus, as measured by a [LoudnessMeter]. Silence measures as negative
+/// infinity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    /// The RMS level of the last 400 ms, averaged over both channels, in dBFS
+    pub rms: f32,
+    /// The highest sample of the last 400 ms, in dBFS
+    pub peak: f32,
+    /// The K-weighted loudness of the last 400 ms, in LUFS
+    pub momentary: f32,
+    /// The gated loudness since the meter was created or reset, in LUFS
+    pub integrated: f32,
+}
+
+impl Default for Loudness {
+    fn default() -> Self {
+        Self {
+            rms: f32::NEG_INFINITY,
+            peak: f32::NEG_INFINITY,
+            momentary: f32::NEG_INFINITY,
+            integrated: f32::NEG_INFINITY,
+        }
+    }
+}
+
+/// A biquad in transposed direct form II, with the state of both channels
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z: [[f64; 2]; 2],
+}
+
+impl Biquad {
+    fn process(&mut self, channel: usize, x: f64) -> f64 {
+        let z = &mut self.z[channel];
+        let y = self.b[0] * x + z[0];
+        z[0] = self.b[1] * x - self.a[0] * y + z[1];
+        z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// The K-weighting filter of ITU-R BS.1770: a high shelf modelling the head, followed by a high
+/// pass. The coefficients are derived for any sample rate, rather than using the ones for 48 kHz.
+fn k_weighting(sample_rate: SampleRate) -> [Biquad; 2] {
+    let rate = sample_rate as f64;
+
+    let (f0, gain, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+    let k = (PI * f0 / rate).tan();
+    let vh = 10f64.powf(gain / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        z: Default::default(),
+    };
+
+    let (f0, q) = (38.13547087602444, 0.5003270373238773);
+    let k = (PI * f0 / rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        z: Default::default(),
+    };
+
+    [shelf, high_pass]
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Block {
+    frames: usize,
+    squares: f64,
+    weighted: f64,
+    peak: f64,
+}
+
+#[derive(Debug, Default)]
+struct MeterState {
+    sample_rate: SampleRate,
+    filters: [Biquad; 2],
+    block: Block,
+    /// The last complete blocks, up to a window
+    blocks: VecDeque<Block>,
+    /// The mean K-weighted power of every window since the meter was reset
+    windows: Vec<f64>,
+    loudness: Loudness,
+}
+
+impl MeterState {
+    fn push(&mut self, frame: Frame) {
+        let mut block = self.block;
+        for (channel, x) in [frame.x as f64, frame.y as f64].into_iter().enumerate() {
+            let weighted = self
+                .filters
+                .iter_mut()
+                .fold(x, |x, filter| filter.process(channel, x));
+            block.squares += x * x / 2.0;
+            block.weighted += weighted * weighted;
+            block.peak = block.peak.max(x.abs());
+        }
+        block.frames += 1;
+        self.block = block;
+
+        if block.frames as f64 >= BLOCK_LENGTH * self.sample_rate as f64 {
+            self.end_block();
+        }
+    }
+
+    fn end_block(&mut self) {
+        if self.blocks.len() == WINDOW_BLOCKS {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back(std::mem::take(&mut self.block));
+
+        let frames = self.blocks.iter().map(|b| b.frames).sum::<usize>() as f64;
+        let squares = self.blocks.iter().map(|b| b.squares).sum::<f64>() / frames;
+        let weighted = self.blocks.iter().map(|b| b.weighted).sum::<f64>() / frames;
+        let peak = self.blocks.iter().map(|b| b.peak).fold(0.0, f64::max);
+
+        self.loudness.rms = (10.0 * squares.log10()) as f32;
+        self.loudness.peak = (20.0 * peak.log10()) as f32;
+        self.loudness.momentary = lufs(weighted) as f32;
+
+        if self.blocks.len() == WINDOW_BLOCKS {
+            self.windows.push(weighted);
+            self.loudness.integrated = self.integrated() as f32;
+        }
+    }
+
+    fn integrated(&self) -> f64 {
+        let gated_mean = |threshold: f64| {
+            let (sum, count) = self
+                .windows
+                .iter()
+                .filter(|&&power| lufs(power) > threshold)
+                .fold((0.0, 0), |(sum, count), power| (sum + power, count + 1));
+            sum / count as f64
+        };
+
+        let ungated = gated_mean(ABSOLUTE_GATE);
+        if ungated.is_nan() {
+            return f64::NEG_INFINITY;
+        }
+        lufs(gated_mean(lufs(ungated) + RELATIVE_GATE))
+    }
+}
+
+/// Converts the K-weighted power summed over the channels to LUFS
+fn lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+/// Measures the RMS level and the loudness in LUFS of the bus it taps, following ITU-R BS.1770.
+///
+/// The measurements are updated every 100 ms, over the last 400 ms of audio, which makes them
+/// useful both for level meters and for asserting in tests that sounds played.
+#[derive(Debug, Default)]
+pub struct LoudnessMeter {
+    state: Mutex<MeterState>,
+}
+
+impl LoudnessMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn loudness(&self) -> Loudness {
+        self.state.lock().loudness
+    }
+
+    /// Forgets everything measured so far, including the integrated loudness
+    pub fn reset(&self) {
+        *self.state.lock() = MeterState::default();
+    }
+}
+
+impl BusTap for LoudnessMeter {
+    fn process(&self, frames: &[Frame], sample_rate: SampleRate) {
+        let mut state = self.state.lock();
+        if state.sample_rate != sample_rate {
+            *state = MeterState {
+                sample_rate,
+                filters: k_weighting(sample_rate),
+                ..Default::default()
+            };
+        }
+
+        for &frame in frames {
+            state.push(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::TAU;
+
+    use approx::assert_abs_diff_eq;
+    use itertools::Itertools;
+
+    use super::*;
+
+    const SAMPLE_RATE: SampleRate = 48000;
+
+    fn sine(amplitude: f32, seconds: f32) -> Vec<Frame> {
+        (0..(seconds * SAMPLE_RATE as f32) as usize)
+            .map(|i| Frame::splat(amplitude * (TAU * 1000.0 * i as f32 / SAMPLE_RATE as f32).sin()))
+            .collect_vec()
+    }
+
+    #[test]
+    fn loudness_of_sine() {
+        let meter = LoudnessMeter::new();
+        meter.process(&sine(1.0, 1.0), SAMPLE_RATE);
+
+        // A full scale 1 kHz sine in both channels is 0 LUFS by definition
+        let loudness = meter.loudness();
+        assert_abs_diff_eq!(loudness.rms, -3.01, epsilon = 0.01);
+        assert_abs_diff_eq!(loudness.peak, 0.0, epsilon = 0.01);
+        assert_abs_diff_eq!(loudness.momentary, 0.0, epsilon = 0.05);
+        assert_abs_diff_eq!(loudness.integrated, 0.0, epsilon = 0.1);
+
+        // Silence is gated out of the integrated loudness
+        meter.process(&sine(0.0, 1.0), SAMPLE_RATE);
+        let loudness = meter.loudness();
+        assert_eq!(loudness.rms, f32::NEG_INFINITY);
+        assert!(loudness.momentary < -70.0);
+        assert_abs_diff_eq!(loudness.integrated, 0.0, epsilon = 0.1);
+
+        meter.reset();
+        assert_eq!(meter.loudness(), Loudness::default());
+    }
+}
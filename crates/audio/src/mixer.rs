@@ -11,7 +11,7 @@ use slotmap::{new_key_type, SlotMap};
 
 use crate::{
     signal::{AsyncSignal, BlockingSignal, Signal},
-    Frame, SampleConversion, SampleRate, Source,
+    AudioClock, Frame, SampleConversion, SampleRate, Scheduled, Source,
 };
 
 new_key_type! {
@@ -134,14 +134,27 @@ impl AudioMixer {
 
     /// Play a source on the mixer, returning a handle which can be used to control it
     pub fn play<S: Source + 'static>(&self, source: S) -> Sound {
-        let sample_rate = source.sample_rate();
+        let source = self.convert(source);
+        self.insert(source)
+    }
+
+    /// Play a source on the mixer from `frame` of `clock`, which must run at the sample rate of
+    /// the mixer. See [Scheduled].
+    pub fn play_at<S: Source + 'static>(&self, source: S, clock: &AudioClock, frame: u64) -> Sound {
+        let source = self.convert(source);
+        self.insert(Box::new(Scheduled::new(source, clock.clone(), frame)))
+    }
 
-        let source = if sample_rate == self.inner.sample_rate {
-            Box::new(source) as Box<dyn Source>
+    /// Converts `source` to the sample rate of the mixer
+    fn convert<S: Source + 'static>(&self, source: S) -> Box<dyn Source> {
+        if source.sample_rate() == self.inner.sample_rate {
+            Box::new(source)
         } else {
-            Box::new(SampleConversion::new(source, self.inner.sample_rate as _)) as Box<dyn Source>
-        };
+            Box::new(SampleConversion::new(source, self.inner.sample_rate as _))
+        }
+    }
 
+    fn insert(&self, source: Box<dyn Source>) -> Sound {
         let id = self
             .inner
             .sources
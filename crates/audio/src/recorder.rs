@@ -0,0 +1,123 @@
+use std::{
+    path::{Path, PathBuf},
+    thread::{self, JoinHandle},
+};
+
+use flume::Sender;
+use hound::{SampleFormat, WavSpec, WavWriter};
+use parking_lot::Mutex;
+
+use crate::{
+    error::{Error, Result},
+    BusTap, Frame, SampleRate,
+};
+
+/// Records everything the bus it taps outputs to a 32 bit float stereo wav file.
+///
+/// The file is written on a thread of its own, so that the mixer never waits on the disk. The
+/// recording stops once the recorder is finished or dropped.
+#[derive(Debug)]
+pub struct WavRecorder {
+    path: PathBuf,
+    sample_rate: SampleRate,
+    sender: Mutex<Option<Sender<Vec<Frame>>>>,
+    thread: Mutex<Option<JoinHandle<Result<()>>>>,
+}
+
+impl WavRecorder {
+    /// Creates the file at `path`, which is overwritten if it exists
+    pub fn new(path: impl Into<PathBuf>, sample_rate: SampleRate) -> Result<Self> {
+        let path = path.into();
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: sample_rate as u32,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let mut writer = WavWriter::create(&path, spec).map_err(Error::WavWrite)?;
+
+        let (sender, receiver) = flume::unbounded::<Vec<Frame>>();
+        let thread = thread::Builder::new()
+            .name("wav_recorder".into())
+            .spawn({
+                let path = path.clone();
+                move || {
+                    let result = receiver
+                        .into_iter()
+                        .flatten()
+                        .try_for_each(|frame| {
+                            writer.write_sample(frame.x)?;
+                            writer.write_sample(frame.y)
+                        })
+                        .and_then(|_| writer.finalize())
+                        .map_err(Error::WavWrite);
+                    if let Err(err) = &result {
+                        tracing::error!("Failed to record audio to {path:?}: {err}");
+                    }
+                    result
+                }
+            })
+            .map_err(Error::RecorderThread)?;
+
+        Ok(Self {
+            path,
+            sample_rate,
+            sender: Mutex::new(Some(sender)),
+            thread: Mutex::new(Some(thread)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stops recording, and waits for the file to be written
+    pub fn finish(&self) -> Result<()> {
+        self.sender.lock().take();
+        match self.thread.lock().take() {
+            Some(thread) => thread.join().expect("The wav recorder thread panicked"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl BusTap for WavRecorder {
+    fn process(&self, frames: &[Frame], sample_rate: SampleRate) {
+        debug_assert_eq!(sample_rate, self.sample_rate);
+        if let Some(sender) = &*self.sender.lock() {
+            let _ = sender.send(frames.to_vec());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hound::WavReader;
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::{AudioBus, BufferedSource, Source};
+
+    #[test]
+    fn record_bus() {
+        let path = std::env::temp_dir().join("ambient_audio_record_bus.wav");
+        let bus = AudioBus::new("test", 4);
+        bus.play(BufferedSource::new([1.0, -1.0, 0.5, -0.5], 2, 4));
+        bus.set_gain(0.5);
+
+        let recorder = std::sync::Arc::new(WavRecorder::new(&path, 4).unwrap());
+        bus.add_tap(recorder.clone());
+        let mut output = [Frame::ZERO; 3];
+        bus.clone().sample_buffered(&mut output);
+        recorder.finish().unwrap();
+
+        let reader = WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, 4);
+        let samples = reader
+            .into_samples::<f32>()
+            .map(|v| v.unwrap())
+            .collect_vec();
+        assert_eq!(samples, [0.5, -0.5, 0.25, -0.25, 0.0, 0.0]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{Frame, SampleRate};
+
+/// The delays of the comb filters, in samples at 44.1 kHz
+const COMB_TUNING: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+/// The delays of the allpass filters, in samples at 44.1 kHz
+const ALLPASS_TUNING: [usize; 4] = [556, 441, 341, 225];
+/// How many samples longer the delays of the right channel are, which widens the reverb
+const STEREO_SPREAD: usize = 23;
+const INPUT_GAIN: f32 = 0.015;
+const OUTPUT_GAIN: f32 = 3.0;
+
+#[derive(Debug, Clone)]
+struct Comb {
+    buffer: Vec<f32>,
+    index: usize,
+    filter_store: f32,
+}
+
+impl Comb {
+    fn new(len: usize) -> Self {
+        Self {
+            buffer: vec![0.0; len.max(1)],
+            index: 0,
+            filter_store: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.buffer[self.index];
+        self.filter_store = output * (1.0 - damping) + self.filter_store * damping;
+        self.buffer[self.index] = input + self.filter_store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AllPass {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl AllPass {
+    fn new(len: usize) -> Self {
+        Self {
+            buffer: vec![0.0; len.max(1)],
+            index: 0,
+        }
+    }
+
+    fn process(&mut self, input: f32) -> f32 {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = input + delayed * 0.5;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Channel {
+    combs: Vec<Comb>,
+    allpasses: Vec<AllPass>,
+}
+
+impl Channel {
+    fn new(sample_rate: SampleRate, spread: usize) -> Self {
+        let scale = |len: usize| (len + spread) * sample_rate as usize / 44100;
+        Self {
+            combs: COMB_TUNING
+                .iter()
+                .map(|&len| Comb::new(scale(len)))
+                .collect(),
+            allpasses: ALLPASS_TUNING
+                .iter()
+                .map(|&len| AllPass::new(scale(len)))
+                .collect(),
+        }
+    }
+
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self
+            .combs
+            .iter_mut()
+            .map(|comb| comb.process(input, feedback, damping))
+            .sum();
+        self.allpasses
+            .iter_mut()
+            .fold(output, |output, allpass| allpass.process(output))
+    }
+}
+
+/// A stereo reverb, made of parallel comb filters followed by allpass filters (Freeverb).
+#[derive(Debug, Clone)]
+pub struct Reverb {
+    left: Channel,
+    right: Channel,
+}
+
+impl Reverb {
+    pub fn new(sample_rate: SampleRate) -> Self {
+        Self {
+            left: Channel::new(sample_rate, 0),
+            right: Channel::new(sample_rate, STEREO_SPREAD),
+        }
+    }
+
+    /// Returns the reverberated `input`, without the input itself.
+    ///
+    /// `room_size` from 0 to 1 sets how long the reverb rings, and `damping` from 0 to 1 how
+    /// quickly the high frequencies die out.
+    pub fn process(&mut self, input: Frame, room_size: f32, damping: f32) -> Frame {
+        let feedback = 0.7 + 0.28 * room_size.clamp(0.0, 1.0);
+        let damping = 0.4 * damping.clamp(0.0, 1.0);
+        let input = (input.x + input.y) * INPUT_GAIN;
+        Frame::new(
+            self.left.process(input, feedback, damping),
+            self.right.process(input, feedback, damping),
+        ) * OUTPUT_GAIN
+    }
+}
@@ -0,0 +1,125 @@
+use std::{sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+
+use crate::{GainValue, Param, SampleRate};
+
+#[derive(Debug)]
+struct Ramp {
+    target: f32,
+    frames_left: u64,
+}
+
+#[derive(Debug)]
+struct AutomationState {
+    value: f32,
+    sample_rate: SampleRate,
+    ramp: Option<Ramp>,
+}
+
+/// A parameter of a playing sound, like its gain, which can be ramped to a new value over time.
+///
+/// The ramp runs on the mixer thread: every read of the value through [GainValue] or [Param]
+/// advances it by one frame, so it must be read once per frame of a source with `sample_rate`,
+/// like [Gain](crate::Gain) and [OnePole](crate::OnePole) do.
+#[derive(Debug, Clone)]
+pub struct AutomatedParam {
+    state: Arc<Mutex<AutomationState>>,
+}
+
+impl AutomatedParam {
+    pub fn new(value: f32, sample_rate: SampleRate) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(AutomationState {
+                value,
+                sample_rate,
+                ramp: None,
+            })),
+        }
+    }
+
+    /// Returns the current value, without advancing a ramp
+    pub fn value(&self) -> f32 {
+        self.state.lock().value
+    }
+
+    /// Sets the value immediately, cancelling any ramp
+    pub fn set(&self, value: f32) {
+        let mut state = self.state.lock();
+        state.value = value;
+        state.ramp = None;
+    }
+
+    /// Ramps linearly from the current value to `target` over `duration`, replacing any ramp in
+    /// progress
+    pub fn ramp_to(&self, target: f32, duration: Duration) {
+        let mut state = self.state.lock();
+        let frames_left = (duration.as_secs_f64() * state.sample_rate as f64).round() as u64;
+        if frames_left == 0 {
+            state.value = target;
+            state.ramp = None;
+        } else {
+            state.ramp = Some(Ramp {
+                target,
+                frames_left,
+            });
+        }
+    }
+
+    pub fn is_ramping(&self) -> bool {
+        self.state.lock().ramp.is_some()
+    }
+
+    /// Returns the current value, and advances the ramp by one frame
+    fn next_value(&self) -> f32 {
+        let mut state = self.state.lock();
+        let value = state.value;
+        if let Some(ramp) = &mut state.ramp {
+            let target = ramp.target;
+            let step = (target - value) / ramp.frames_left as f32;
+            ramp.frames_left -= 1;
+            if ramp.frames_left == 0 {
+                state.ramp = None;
+                state.value = target;
+            } else {
+                state.value = value + step;
+            }
+        }
+        value
+    }
+}
+
+impl GainValue for AutomatedParam {
+    fn get_value(&self) -> f32 {
+        self.next_value()
+    }
+}
+
+impl Param for AutomatedParam {
+    fn get_value(&self) -> f32 {
+        self.next_value()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn ramp() {
+        let param = AutomatedParam::new(0.0, 4);
+        param.ramp_to(1.0, Duration::from_secs(1));
+        assert!(param.is_ramping());
+        let values = (0..6).map(|_| GainValue::get_value(&param)).collect_vec();
+        assert_eq!(values, [0.0, 0.25, 0.5, 0.75, 1.0, 1.0]);
+        assert!(!param.is_ramping());
+
+        param.ramp_to(0.0, Duration::from_secs(1));
+        GainValue::get_value(&param);
+        param.set(0.5);
+        assert!(!param.is_ramping());
+        assert_eq!(GainValue::get_value(&param), 0.5);
+    }
+}
@@ -1,3 +1,4 @@
+mod automation;
 mod buffered;
 mod chain;
 mod crossfade;
@@ -10,13 +11,17 @@ mod oscilloscope;
 mod pad_to;
 mod pan;
 mod peek;
+mod playback;
 mod repeat;
 mod sample_bufferer;
 mod sample_rate;
+mod scheduled;
+mod send;
 mod slice;
 mod spatial;
 pub mod streaming_source;
 mod uniform;
+mod virtualize;
 use std::{
     self,
     f32::consts::TAU,
@@ -26,6 +31,7 @@ use std::{
     time::Duration,
 };
 
+pub use automation::*;
 pub use buffered::*;
 pub use chain::*;
 use circular_queue::CircularQueue;
@@ -36,11 +42,15 @@ pub use onepole::*;
 pub use pan::*;
 use parking_lot::Mutex;
 pub use peek::*;
+pub use playback::*;
 pub use repeat::*;
 pub use sample_rate::*;
+pub use scheduled::*;
+pub use send::*;
 pub use slice::*;
 pub use spatial::*;
 pub use uniform::*;
+pub use virtualize::*;
 
 use self::{history::History, oscilloscope::Oscilloscope, pad_to::PadTo};
 use crate::{
@@ -107,6 +117,14 @@ pub trait Source: Send {
         ))
     }
 
+    /// Jumps to the frame at `position` from the start of the source.
+    ///
+    /// Returns false if the source can not seek, in which case it is left unchanged, and the
+    /// caller has to restart the source and skip ahead instead.
+    fn seek(&mut self, _position: u64) -> bool {
+        false
+    }
+
     fn take(self, dur: Duration) -> Slice<Self>
     where
         Self: Sized,
@@ -221,6 +239,14 @@ pub trait Source: Send {
         Box::new(OnePole::new(self, freq))
     }
 
+    /// Sends the source to the channels of `levels`, see [EffectSend]
+    fn effect_send(self, levels: SendHandle) -> EffectSend<Self>
+    where
+        Self: Sized,
+    {
+        EffectSend::new(self, levels)
+    }
+
     fn blt<V, H>(self, transfer: V) -> BilinearTransform<Self, H, V>
     where
         Self: Sized,
@@ -263,6 +289,11 @@ where
     fn sample_count(&self) -> Option<u64> {
         self.deref().sample_count()
     }
+
+    #[inline]
+    fn seek(&mut self, position: u64) -> bool {
+        self.deref_mut().seek(position)
+    }
 }
 const DEFAULT_HZ: SampleRate = 44100;
 
@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{AutomatedParam, Frame, GainValue, Source};
+
+/// The shared state of a [Playback], through which a playing sound is controlled.
+#[derive(Debug, Clone)]
+pub struct PlaybackControl {
+    /// While paused, the source outputs silence without advancing.
+    pub paused: bool,
+    /// The playback speed, which also shifts the pitch. 1.0 is the original speed.
+    pub speed: f32,
+    /// Set to seek to this position, in seconds. Cleared once the seek has happened.
+    pub seek: Option<f32>,
+    /// The current position, in seconds.
+    pub position: f32,
+    /// Set once a non-looping source has played to the end.
+    pub finished: bool,
+    /// The frame a looping source jumps back to.
+    pub loop_start: u64,
+    /// The frame at which a looping source jumps back to `loop_start`. Loops at the end of the
+    /// source when `None`.
+    pub loop_end: Option<u64>,
+}
+
+impl Default for PlaybackControl {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            speed: 1.0,
+            seek: None,
+            position: 0.0,
+            finished: false,
+            loop_start: 0,
+            loop_end: None,
+        }
+    }
+}
+
+pub type PlaybackHandle = Arc<Mutex<PlaybackControl>>;
+
+/// Plays a source with pausing, seeking, looping and a variable playback speed, controlled
+/// through a [PlaybackHandle].
+///
+/// Seeking uses [Source::seek] when the source supports it. Otherwise the source is restarted and
+/// skipped ahead to the position, so seeking far into such a source is not free.
+#[derive(Debug, Clone)]
+pub struct Playback<S> {
+    orig: S,
+    source: S,
+    control: PlaybackHandle,
+    /// Multiplied with the speed of the control, so that the pitch can be ramped
+    pitch: Option<AutomatedParam>,
+    looping: bool,
+    loop_start: u64,
+    loop_end: Option<u64>,
+    /// The number of samples read from the source
+    cursor: u64,
+    /// The two samples being interpolated between, with their index in the source, and how far
+    /// between them the playback is
+    current: Option<(u64, Frame)>,
+    next: Option<(u64, Frame)>,
+    frac: f32,
+}
+
+impl<S> Playback<S>
+where
+    S: Source + Clone,
+{
+    pub fn new(source: S, control: PlaybackHandle, looping: bool) -> Self {
+        let mut res = Self {
+            orig: source.clone(),
+            source,
+            control,
+            pitch: None,
+            looping,
+            loop_start: 0,
+            loop_end: None,
+            cursor: 0,
+            current: None,
+            next: None,
+            frac: 0.0,
+        };
+        res.current = res.read();
+        res.next = res.read();
+        res
+    }
+
+    /// Multiplies the playback speed with `pitch`, which can be ramped while playing
+    pub fn with_pitch(mut self, pitch: AutomatedParam) -> Self {
+        self.pitch = Some(pitch);
+        self
+    }
+
+    /// Reads the next sample of the source, jumping back to the loop start if looping
+    fn read(&mut self) -> Option<(u64, Frame)> {
+        if self.looping
+            && self
+                .loop_end
+                .map_or(false, |end| end > self.loop_start && self.cursor >= end)
+        {
+            self.seek_frame(self.loop_start);
+        }
+        match self.source.next_sample() {
+            Some(v) => {
+                self.cursor += 1;
+                Some((self.cursor - 1, v))
+            }
+            None if self.looping && self.cursor > self.loop_start => {
+                self.seek_frame(self.loop_start);
+                self.read()
+            }
+            None => None,
+        }
+    }
+
+    fn restart(&mut self) {
+        self.source = self.orig.clone();
+        self.cursor = 0;
+    }
+
+    /// Moves the source to `target`, or as close as it gets if the source ends before it
+    fn seek_frame(&mut self, target: u64) {
+        if target == self.cursor {
+            return;
+        }
+        if self.source.seek(target) {
+            self.cursor = target;
+            return;
+        }
+
+        if target < self.cursor {
+            self.restart();
+        }
+        while self.cursor < target {
+            if self.source.next_sample().is_none() {
+                break;
+            }
+            self.cursor += 1;
+        }
+    }
+
+    fn seek_seconds(&mut self, position: f32) {
+        let target = (position.max(0.0) as f64 * self.sample_rate() as f64) as u64;
+        // Seeking past the end of a loop wraps around within it
+        let target = match self.loop_end.or(self.orig.sample_count()) {
+            Some(end) if self.looping && end > self.loop_start && target >= end => {
+                self.loop_start + (target - self.loop_start) % (end - self.loop_start)
+            }
+            _ => target,
+        };
+        self.seek_frame(target);
+        self.current = self.read();
+        self.next = self.read();
+        self.frac = 0.0;
+    }
+}
+
+impl<S> Source for Playback<S>
+where
+    S: Source + Clone,
+{
+    fn next_sample(&mut self) -> Option<Frame> {
+        let (paused, speed, seek) = {
+            let mut control = self.control.lock();
+            self.loop_start = control.loop_start;
+            self.loop_end = control.loop_end;
+            (control.paused, control.speed.max(0.0), control.seek.take())
+        };
+        if let Some(position) = seek {
+            self.seek_seconds(position);
+        }
+        if paused {
+            return Some(Frame::ZERO);
+        }
+        let speed = match &self.pitch {
+            Some(pitch) => speed * pitch.get_value().max(0.0),
+            None => speed,
+        };
+
+        // Resample linearly to play at `speed`
+        while self.frac >= 1.0 {
+            self.frac -= 1.0;
+            self.current = self.next;
+            self.next = self.read();
+        }
+        let Some((index, current)) = self.current else {
+            self.control.lock().finished = true;
+            return None;
+        };
+        let next = self.next.map_or(current, |(_, next)| next);
+        self.frac += speed;
+
+        self.control.lock().position = index as f32 / self.sample_rate() as f32;
+        Some(current.lerp(next, self.frac - speed))
+    }
+
+    fn sample_rate(&self) -> crate::SampleRate {
+        self.source.sample_rate()
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        if self.looping {
+            None
+        } else {
+            self.orig.sample_count()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::{BufferedSource, SampleIter};
+
+    fn source() -> BufferedSource<[f32; 8], f32> {
+        BufferedSource::new([0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0], 2, 4)
+    }
+
+    #[test]
+    fn playback() {
+        let control = PlaybackHandle::default();
+        let samples =
+            SampleIter::new(Playback::new(source(), control.clone(), false)).collect_vec();
+        assert_eq!(
+            samples,
+            [
+                Frame::new(0.0, 0.0),
+                Frame::new(1.0, 1.0),
+                Frame::new(2.0, 2.0),
+                Frame::new(3.0, 3.0),
+            ]
+        );
+        assert!(control.lock().finished);
+    }
+
+    #[test]
+    fn playback_half_speed() {
+        let control = PlaybackHandle::default();
+        control.lock().speed = 0.5;
+        let samples = SampleIter::new(Playback::new(source(), control, false)).collect_vec();
+        assert_eq!(
+            samples,
+            [
+                Frame::new(0.0, 0.0),
+                Frame::new(0.5, 0.5),
+                Frame::new(1.0, 1.0),
+                Frame::new(1.5, 1.5),
+                Frame::new(2.0, 2.0),
+                Frame::new(2.5, 2.5),
+                Frame::new(3.0, 3.0),
+                Frame::new(3.0, 3.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn playback_seek_and_loop() {
+        let control = PlaybackHandle::default();
+        let mut playback = Playback::new(source(), control.clone(), true);
+        control.lock().seek = Some(0.5);
+        let samples = (0..4)
+            .map(|_| playback.next_sample().unwrap())
+            .collect_vec();
+        assert_eq!(
+            samples,
+            [
+                Frame::new(2.0, 2.0),
+                Frame::new(3.0, 3.0),
+                Frame::new(0.0, 0.0),
+                Frame::new(1.0, 1.0),
+            ]
+        );
+        assert_eq!(control.lock().position, 0.25);
+    }
+
+    #[test]
+    fn playback_loop_points() {
+        let control = PlaybackHandle::default();
+        control.lock().loop_start = 1;
+        control.lock().loop_end = Some(3);
+        let mut playback = Playback::new(source(), control.clone(), true);
+        let samples = (0..6)
+            .map(|_| playback.next_sample().unwrap())
+            .collect_vec();
+        assert_eq!(
+            samples,
+            [
+                Frame::new(0.0, 0.0),
+                Frame::new(1.0, 1.0),
+                Frame::new(2.0, 2.0),
+                Frame::new(1.0, 1.0),
+                Frame::new(2.0, 2.0),
+                Frame::new(1.0, 1.0),
+            ]
+        );
+
+        control.lock().loop_end = None;
+        let samples = (0..4)
+            .map(|_| playback.next_sample().unwrap())
+            .collect_vec();
+        assert_eq!(
+            samples,
+            [
+                Frame::new(2.0, 2.0),
+                Frame::new(3.0, 3.0),
+                Frame::new(1.0, 1.0),
+                Frame::new(2.0, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn playback_paused() {
+        let control = PlaybackHandle::default();
+        let mut playback = Playback::new(source(), control.clone(), false);
+        control.lock().paused = true;
+        assert_eq!(playback.next_sample(), Some(Frame::ZERO));
+        control.lock().paused = false;
+        assert_eq!(playback.next_sample(), Some(Frame::new(0.0, 0.0)));
+        assert_eq!(playback.next_sample(), Some(Frame::new(1.0, 1.0)));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{AudioClock, Frame, SampleRate, Source};
+
+/// Starts playing a source on a frame of an [AudioClock], and plays silence until then.
+///
+/// The clock is read once, when the source is first mixed, and frames are counted from there, so
+/// that the source starts within the block it is scheduled in. A source scheduled in the past
+/// starts right away.
+#[derive(Debug)]
+pub struct Scheduled<S> {
+    source: S,
+    clock: AudioClock,
+    start: u64,
+    cursor: Option<u64>,
+}
+
+impl<S: Source> Scheduled<S> {
+    pub fn new(source: S, clock: AudioClock, start: u64) -> Self {
+        debug_assert_eq!(source.sample_rate(), clock.sample_rate());
+        Self {
+            source,
+            clock,
+            start,
+            cursor: None,
+        }
+    }
+
+    /// Returns the number of silent frames left before the source starts
+    fn frames_left(&mut self) -> u64 {
+        let cursor = *self.cursor.get_or_insert_with(|| self.clock.frames());
+        self.start.saturating_sub(cursor)
+    }
+}
+
+impl<S: Source> Source for Scheduled<S> {
+    fn next_sample(&mut self) -> Option<Frame> {
+        if self.frames_left() > 0 {
+            *self.cursor.as_mut().unwrap() += 1;
+            Some(Frame::ZERO)
+        } else {
+            self.source.next_sample()
+        }
+    }
+
+    fn sample_buffered(&mut self, output: &mut [Frame]) -> usize {
+        let silence = self.frames_left().min(output.len() as u64) as usize;
+        *self.cursor.as_mut().unwrap() += silence as u64;
+        silence + self.source.sample_buffered(&mut output[silence..])
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.source.sample_rate()
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        self.source.sample_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BufferedSource;
+
+    fn source() -> BufferedSource<[f32; 4], f32> {
+        BufferedSource::new([1.0, 1.0, 2.0, 2.0], 2, 4)
+    }
+
+    #[test]
+    fn scheduled_buffered() {
+        let clock = AudioClock::new(4);
+        clock.advance(4);
+        let mut source = Scheduled::new(source(), clock.clone(), 6);
+
+        let mut output = [Frame::ZERO; 3];
+        assert_eq!(source.sample_buffered(&mut output), 3);
+        assert_eq!(output, [Frame::ZERO, Frame::ZERO, Frame::splat(1.0)]);
+
+        let mut output = [Frame::ZERO; 3];
+        assert_eq!(source.sample_buffered(&mut output), 1);
+        assert_eq!(output[0], Frame::splat(2.0));
+    }
+
+    #[test]
+    fn scheduled_in_the_past() {
+        let clock = AudioClock::new(4);
+        clock.advance(8);
+        let mut source = Scheduled::new(source(), clock, 2);
+        assert_eq!(source.next_sample(), Some(Frame::splat(1.0)));
+    }
+}
@@ -0,0 +1,180 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::{Frame, SampleRate, Source};
+
+/// How far the senders of a channel may get ahead of its return before their samples are dropped
+const MAX_LATENCY: usize = 16384;
+
+#[derive(Debug, Default)]
+struct SendBuffer {
+    /// The number of samples read by the return
+    read: u64,
+    /// The samples from `read` onwards, mixed together from all the senders
+    samples: VecDeque<Frame>,
+}
+
+/// A shared input which any number of [EffectSend]s mix into, and which is played by a
+/// [SendReturn], usually on a bus with effects.
+#[derive(Debug, Clone, Default)]
+pub struct SendChannel {
+    buffer: Arc<Mutex<SendBuffer>>,
+}
+
+impl SendChannel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mixes `sample` in at `position`, which is advanced. A sender that has fallen behind the
+    /// return is moved up to it.
+    fn write(&self, position: &mut u64, sample: Frame) {
+        let mut buffer = self.buffer.lock();
+        *position = (*position).max(buffer.read);
+        let index = (*position - buffer.read) as usize;
+        *position += 1;
+        if index >= MAX_LATENCY {
+            return;
+        }
+        if index >= buffer.samples.len() {
+            buffer.samples.resize(index + 1, Frame::ZERO);
+        }
+        buffer.samples[index] += sample;
+    }
+
+    fn read(&self) -> Frame {
+        let mut buffer = self.buffer.lock();
+        buffer.read += 1;
+        buffer.samples.pop_front().unwrap_or(Frame::ZERO)
+    }
+
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.buffer, &other.buffer)
+    }
+}
+
+/// Plays what is sent to a [SendChannel]. Never ends.
+#[derive(Debug, Clone)]
+pub struct SendReturn {
+    channel: SendChannel,
+    sample_rate: SampleRate,
+}
+
+impl SendReturn {
+    pub fn new(channel: SendChannel, sample_rate: SampleRate) -> Self {
+        Self {
+            channel,
+            sample_rate,
+        }
+    }
+}
+
+impl Source for SendReturn {
+    fn next_sample(&mut self) -> Option<Frame> {
+        Some(self.channel.read())
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// How much of a source is sent to each [SendChannel], and how much is output directly
+#[derive(Debug, Clone)]
+pub struct SendLevels {
+    pub dry: f32,
+    pub sends: Vec<(SendChannel, f32)>,
+}
+
+impl Default for SendLevels {
+    fn default() -> Self {
+        Self {
+            dry: 1.0,
+            sends: Vec::new(),
+        }
+    }
+}
+
+pub type SendHandle = Arc<Mutex<SendLevels>>;
+
+/// Sends a source to the channels of a [SendHandle] while also outputting it directly
+#[derive(Debug)]
+pub struct EffectSend<S> {
+    source: S,
+    levels: SendHandle,
+    /// The write position of this source in each channel it has sent to
+    positions: Vec<(SendChannel, u64)>,
+}
+
+impl<S: Source> EffectSend<S> {
+    pub fn new(source: S, levels: SendHandle) -> Self {
+        Self {
+            source,
+            levels,
+            positions: Vec::new(),
+        }
+    }
+}
+
+impl<S: Source> Source for EffectSend<S> {
+    fn next_sample(&mut self) -> Option<Frame> {
+        let sample = self.source.next_sample()?;
+        let levels = self.levels.lock();
+        for (channel, level) in &levels.sends {
+            let index = match self.positions.iter().position(|(v, _)| v.ptr_eq(channel)) {
+                Some(index) => index,
+                None => {
+                    self.positions.push((channel.clone(), 0));
+                    self.positions.len() - 1
+                }
+            };
+            channel.write(&mut self.positions[index].1, sample * *level);
+        }
+        Some(sample * levels.dry)
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.source.sample_rate()
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        self.source.sample_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::{BufferedSource, SampleIter};
+
+    fn source() -> BufferedSource<[f32; 4], f32> {
+        BufferedSource::new([1.0, 1.0, 2.0, 2.0], 2, 4)
+    }
+
+    #[test]
+    fn send_mixes_senders() {
+        let channel = SendChannel::new();
+        let levels = SendHandle::default();
+        levels.lock().dry = 0.5;
+        levels.lock().sends.push((channel.clone(), 0.25));
+
+        let a = SampleIter::new(EffectSend::new(source(), levels.clone())).collect_vec();
+        let b = SampleIter::new(EffectSend::new(source(), levels)).collect_vec();
+        assert_eq!(a, [Frame::splat(0.5), Frame::splat(1.0)]);
+        assert_eq!(a, b);
+
+        let mut ret = SendReturn::new(channel, 4);
+        let returned = (0..3).map(|_| ret.next_sample().unwrap()).collect_vec();
+        assert_eq!(
+            returned,
+            [Frame::splat(0.5), Frame::splat(1.0), Frame::splat(0.0)]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{Frame, PlaybackHandle, SampleRate, Source};
+
+/// Set to make a [Virtualize]d source virtual
+pub type VirtualHandle = Arc<Mutex<bool>>;
+
+/// Stops processing a playing sound while it is virtual, and keeps advancing the position of its
+/// [Playback](crate::Playback) instead, so that it continues from where it would have been once it
+/// is made audible again.
+///
+/// Everything before this in the chain, like decoding and spatialization, is skipped while
+/// virtual, which makes sounds that can't be heard almost free.
+#[derive(Debug)]
+pub struct Virtualize<S> {
+    source: S,
+    handle: VirtualHandle,
+    control: PlaybackHandle,
+}
+
+impl<S: Source> Virtualize<S> {
+    pub fn new(source: S, handle: VirtualHandle, control: PlaybackHandle) -> Self {
+        Self {
+            source,
+            handle,
+            control,
+        }
+    }
+
+    /// Advances the playback by `frames` without playing them. Returns false once a
+    /// non-looping source has reached its end.
+    fn advance(&mut self, frames: usize) -> bool {
+        let sample_rate = self.source.sample_rate() as f32;
+        let mut control = self.control.lock();
+        let speed = if control.paused {
+            0.0
+        } else {
+            control.speed.max(0.0)
+        };
+        let mut position =
+            control.seek.unwrap_or(control.position) + frames as f32 * speed / sample_rate;
+
+        match self.source.sample_count() {
+            Some(count) if position >= count as f32 / sample_rate => {
+                control.finished = true;
+                return false;
+            }
+            Some(_) => {}
+            // Loops; wrap within the loop points if there are any, or leave it to the playback
+            None => {
+                let start = control.loop_start as f32 / sample_rate;
+                if let Some(end) = control.loop_end.map(|end| end as f32 / sample_rate) {
+                    if end > start && position >= end {
+                        position = start + (position - start) % (end - start);
+                    }
+                }
+            }
+        }
+
+        // The playback picks up the seek once the source is audible again
+        control.seek = Some(position);
+        control.position = position;
+        true
+    }
+}
+
+impl<S: Source> Source for Virtualize<S> {
+    fn next_sample(&mut self) -> Option<Frame> {
+        if *self.handle.lock() {
+            self.advance(1).then_some(Frame::ZERO)
+        } else {
+            self.source.next_sample()
+        }
+    }
+
+    fn sample_buffered(&mut self, output: &mut [Frame]) -> usize {
+        if *self.handle.lock() {
+            if self.advance(output.len()) {
+                output.len()
+            } else {
+                0
+            }
+        } else {
+            self.source.sample_buffered(output)
+        }
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.source.sample_rate()
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        self.source.sample_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::{BufferedSource, Playback, SampleIter};
+
+    fn source() -> BufferedSource<[f32; 8], f32> {
+        BufferedSource::new([0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0], 2, 4)
+    }
+
+    #[test]
+    fn virtualize_skips_ahead() {
+        let control = PlaybackHandle::default();
+        let handle = VirtualHandle::default();
+        let mut source = Virtualize::new(
+            Playback::new(source(), control.clone(), false),
+            handle.clone(),
+            control.clone(),
+        );
+
+        *handle.lock() = true;
+        assert_eq!(source.next_sample(), Some(Frame::ZERO));
+        assert_eq!(source.next_sample(), Some(Frame::ZERO));
+        assert_eq!(control.lock().position, 0.5);
+
+        *handle.lock() = false;
+        let samples = SampleIter::new(source).collect_vec();
+        assert_eq!(samples, [Frame::splat(2.0), Frame::splat(3.0)]);
+    }
+
+    #[test]
+    fn virtualize_finishes() {
+        let control = PlaybackHandle::default();
+        let handle = VirtualHandle::default();
+        *handle.lock() = true;
+        let source = Virtualize::new(
+            Playback::new(source(), control.clone(), false),
+            handle,
+            control.clone(),
+        );
+        assert_eq!(SampleIter::new(source).count(), 3);
+        assert!(control.lock().finished);
+    }
+}
@@ -27,6 +27,18 @@ pub struct AudioEmitter {
     pub attenuation: Attenuation,
 }
 
+impl AudioEmitter {
+    /// Returns how loud the emitter is at `listener`, after attenuation
+    pub fn gain_at(&self, listener: &AudioListener) -> f32 {
+        let distance = listener
+            .transform
+            .inverse()
+            .transform_point3(self.pos)
+            .length();
+        self.amplitude * self.attenuation.attenuate(distance)
+    }
+}
+
 impl Default for AudioEmitter {
     fn default() -> Self {
         Self {
@@ -9,7 +9,7 @@ use slotmap::new_key_type;
 
 use crate::{
     error::{Error, Result},
-    AudioMixer, ChannelCount, Frame, SampleRate, Source, WeakAudioMixer,
+    AudioBuses, AudioClock, AudioMixer, ChannelCount, Frame, SampleRate, Source, WeakAudioBus,
 };
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -44,7 +44,7 @@ impl From<cpal::StreamConfig> for StreamConfig {
 /// Wraps a cpal Stream. Can not be moved across threads.
 pub struct AudioStream {
     _stream: cpal::Stream,
-    mixer: AudioMixer,
+    buses: AudioBuses,
     _device: cpal::Device,
 }
 
@@ -69,16 +69,18 @@ impl AudioStream {
 
         let mixer_config: crate::StreamConfig = config.clone().into();
 
-        let mixer = AudioMixer::new(mixer_config.sample_rate);
+        let buses = AudioBuses::new(mixer_config.sample_rate);
 
-        let weak_mixer = mixer.downgrade();
+        let weak_master = buses.master().downgrade();
+        let clock = buses.clock().clone();
 
         let err_func = |err| tracing::error!("Audio error: {err}");
 
         let channels = mixer_config.channels;
 
         fn writer<T>(
-            mixer: WeakAudioMixer,
+            master: WeakAudioBus,
+            clock: AudioClock,
             channel_count: u16,
         ) -> impl FnMut(&mut [T], &OutputCallbackInfo)
         where
@@ -92,9 +94,10 @@ impl AudioStream {
                     *v = Frame::ZERO;
                 }
 
-                if let Some(mut mixer) = mixer.upgrade() {
-                    mixer.sample_buffered(&mut buf);
+                if let Some(mut master) = master.upgrade() {
+                    master.sample_buffered(&mut buf);
                 }
+                clock.advance(buf.len() as u64);
 
                 // Write to the concrete type buffer
                 if channel_count == 1 {
@@ -113,30 +116,42 @@ impl AudioStream {
         }
 
         let stream = match format {
-            cpal::SampleFormat::I16 => {
-                device.build_output_stream(&config, writer::<i16>(weak_mixer, channels), err_func)
-            }
-            cpal::SampleFormat::U16 => {
-                device.build_output_stream(&config, writer::<u16>(weak_mixer, channels), err_func)
-            }
-            cpal::SampleFormat::F32 => {
-                device.build_output_stream(&config, writer::<f32>(weak_mixer, channels), err_func)
-            }
+            cpal::SampleFormat::I16 => device.build_output_stream(
+                &config,
+                writer::<i16>(weak_master, clock, channels),
+                err_func,
+            ),
+            cpal::SampleFormat::U16 => device.build_output_stream(
+                &config,
+                writer::<u16>(weak_master, clock, channels),
+                err_func,
+            ),
+            cpal::SampleFormat::F32 => device.build_output_stream(
+                &config,
+                writer::<f32>(weak_master, clock, channels),
+                err_func,
+            ),
         }?;
 
         stream.play()?;
 
         Ok(Self {
-            mixer,
+            buses,
             _stream: stream,
             _device: device,
         })
     }
 
-    /// Get a reference to the audio stream's mixer.
+    /// Get a reference to the mixer of the master bus.
     #[must_use]
     pub fn mixer(&self) -> &AudioMixer {
-        &self.mixer
+        self.buses.master().mixer()
+    }
+
+    /// Get a reference to the audio stream's buses.
+    #[must_use]
+    pub fn buses(&self) -> &AudioBuses {
+        &self.buses
     }
 }
 
@@ -1,18 +1,70 @@
-use std::sync::Arc;
+use std::{
+    fs::File,
+    io::{self, BufReader, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+use derivative::Derivative;
 use derive_more::From;
 
 use crate::{
-    error::Result,
+    decoder::{SymphoniaDecodeStream, SymphoniaTrack},
+    error::{Error, Result},
     vorbis::{VorbisDecodeStream, VorbisTrack},
     wav::{WavDecodeStream, WavTrack},
-    Source,
+    LoopPoints, Source,
 };
 
+/// The encoded audio of a track, either in memory or streamed from a file
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub enum TrackData {
+    Bytes(#[derivative(Debug = "ignore")] Arc<[u8]>),
+    File(Arc<PathBuf>),
+}
+
+impl TrackData {
+    pub fn reader(&self) -> Result<TrackReader> {
+        match self {
+            TrackData::Bytes(bytes) => Ok(TrackReader::Bytes(Cursor::new(bytes.clone()))),
+            TrackData::File(path) => File::open(&**path)
+                .map(|file| TrackReader::File(BufReader::new(file)))
+                .map_err(|err| Error::Io(err, path.to_path_buf())),
+        }
+    }
+}
+
+/// Reads the encoded audio of a [TrackData]
+pub enum TrackReader {
+    Bytes(Cursor<Arc<[u8]>>),
+    File(BufReader<File>),
+}
+
+impl Read for TrackReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            TrackReader::Bytes(v) => v.read(buf),
+            TrackReader::File(v) => v.read(buf),
+        }
+    }
+}
+
+impl Seek for TrackReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            TrackReader::Bytes(v) => v.seek(pos),
+            TrackReader::File(v) => v.seek(pos),
+        }
+    }
+}
+
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
 pub enum AudioFormat {
     Wav,
     Vorbis,
+    Mp3,
+    Flac,
 }
 
 #[derive(From, Debug, Clone)]
@@ -22,6 +74,7 @@ pub enum AudioFormat {
 pub enum Track {
     Vorbis(VorbisTrack),
     Wav(WavTrack),
+    Symphonia(SymphoniaTrack),
 }
 
 impl Track {
@@ -34,9 +87,34 @@ impl Track {
     }
 
     pub fn from_format(bytes: impl Into<Arc<[u8]>>, format: AudioFormat) -> Result<Self> {
+        Self::from_data(TrackData::Bytes(bytes.into()), format)
+    }
+
+    /// Creates a track which is decoded from the file at `path` as it plays, rather than being
+    /// loaded into memory. Use this for long tracks like music.
+    pub fn from_file(path: impl AsRef<Path>, format: AudioFormat) -> Result<Self> {
+        Self::from_data(
+            TrackData::File(Arc::new(path.as_ref().to_path_buf())),
+            format,
+        )
+    }
+
+    pub fn from_data(data: TrackData, format: AudioFormat) -> Result<Self> {
         match format {
-            AudioFormat::Wav => Self::from_wav(bytes.into()),
-            AudioFormat::Vorbis => Self::from_vorbis(bytes.into()),
+            AudioFormat::Wav => Ok(Self::Wav(WavTrack::from_data(data)?)),
+            AudioFormat::Vorbis => Ok(Self::Vorbis(VorbisTrack::from_data(data)?)),
+            AudioFormat::Mp3 | AudioFormat::Flac => {
+                Ok(Self::Symphonia(SymphoniaTrack::from_data(data, format)?))
+            }
+        }
+    }
+
+    /// The loop points in the metadata of the track, if it has any
+    pub fn loop_points(&self) -> Option<LoopPoints> {
+        match self {
+            Track::Vorbis(v) => v.loop_points(),
+            Track::Wav(v) => v.loop_points(),
+            Track::Symphonia(_) => None,
         }
     }
 
@@ -44,6 +122,7 @@ impl Track {
         match self {
             Track::Vorbis(v) => TrackDecodeStream::Vorbis(Box::new(v.decode())),
             Track::Wav(v) => TrackDecodeStream::Wav(v.decode()),
+            Track::Symphonia(v) => TrackDecodeStream::Symphonia(Box::new(v.decode())),
         }
     }
 }
@@ -52,6 +131,7 @@ impl Track {
 pub enum TrackDecodeStream {
     Vorbis(Box<VorbisDecodeStream>),
     Wav(WavDecodeStream),
+    Symphonia(Box<SymphoniaDecodeStream>),
 }
 
 impl Source for TrackDecodeStream {
@@ -59,6 +139,7 @@ impl Source for TrackDecodeStream {
         match self {
             TrackDecodeStream::Vorbis(v) => v.next_sample(),
             TrackDecodeStream::Wav(v) => v.next_sample(),
+            TrackDecodeStream::Symphonia(v) => v.next_sample(),
         }
     }
 
@@ -66,6 +147,7 @@ impl Source for TrackDecodeStream {
         match self {
             TrackDecodeStream::Vorbis(v) => v.sample_buffered(output),
             TrackDecodeStream::Wav(v) => v.sample_buffered(output),
+            TrackDecodeStream::Symphonia(v) => v.sample_buffered(output),
         }
     }
 
@@ -73,6 +155,7 @@ impl Source for TrackDecodeStream {
         match self {
             TrackDecodeStream::Vorbis(v) => v.sample_rate(),
             TrackDecodeStream::Wav(v) => v.sample_rate(),
+            TrackDecodeStream::Symphonia(v) => v.sample_rate(),
         }
     }
 
@@ -80,6 +163,15 @@ impl Source for TrackDecodeStream {
         match self {
             TrackDecodeStream::Vorbis(v) => v.sample_count(),
             TrackDecodeStream::Wav(v) => v.sample_count(),
+            TrackDecodeStream::Symphonia(v) => v.sample_count(),
+        }
+    }
+
+    fn seek(&mut self, position: u64) -> bool {
+        match self {
+            TrackDecodeStream::Vorbis(v) => v.seek(position),
+            TrackDecodeStream::Wav(v) => v.seek(position),
+            TrackDecodeStream::Symphonia(v) => v.seek(position),
         }
     }
 }
@@ -0,0 +1,208 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use opus::{Application, Channels};
+use parking_lot::Mutex;
+
+use crate::{error::Result, Frame, SampleRate, Source};
+
+/// The sample rate voice is encoded at
+pub const VOICE_SAMPLE_RATE: SampleRate = 48000;
+/// The number of samples in each encoded voice packet, which is 20 ms
+pub const VOICE_FRAME_SIZE: usize = 960;
+/// The maximum size of an encoded voice packet
+const MAX_PACKET_SIZE: usize = 1276;
+/// Lost packets are concealed by the decoder, up to this many in a row
+const MAX_CONCEALED_PACKETS: u32 = 5;
+/// A voice stream waits until this many samples are buffered before it starts playing, so that
+/// packets arriving late don't cause gaps
+const JITTER_BUFFER: usize = 3 * VOICE_FRAME_SIZE;
+/// Samples buffered beyond this are dropped, so that the latency stays bounded
+const MAX_BUFFERED: usize = 25 * VOICE_FRAME_SIZE;
+
+/// Encodes captured audio into opus packets of mono voice
+pub struct VoiceEncoder {
+    encoder: opus::Encoder,
+    input_rate: SampleRate,
+    /// The position between the last two input samples that the next output sample is at
+    phase: f64,
+    last: f32,
+    pending: Vec<f32>,
+}
+
+impl VoiceEncoder {
+    pub fn new(input_rate: SampleRate) -> Result<Self> {
+        Ok(Self {
+            encoder: opus::Encoder::new(
+                VOICE_SAMPLE_RATE as u32,
+                Channels::Mono,
+                Application::Voip,
+            )?,
+            input_rate,
+            phase: 0.0,
+            last: 0.0,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Adds captured frames, and returns the packets that could be encoded
+    pub fn push(&mut self, frames: impl IntoIterator<Item = Frame>) -> Result<Vec<Vec<u8>>> {
+        // Downmix, and resample linearly to the voice sample rate
+        let step = self.input_rate as f64 / VOICE_SAMPLE_RATE as f64;
+        for frame in frames {
+            let sample = (frame.x + frame.y) * 0.5;
+            while self.phase < 1.0 {
+                self.pending
+                    .push(self.last + (sample - self.last) * self.phase as f32);
+                self.phase += step;
+            }
+            self.phase -= 1.0;
+            self.last = sample;
+        }
+
+        let mut packets = Vec::new();
+        let mut output = [0; MAX_PACKET_SIZE];
+        while self.pending.len() >= VOICE_FRAME_SIZE {
+            let len = self
+                .encoder
+                .encode_float(&self.pending[..VOICE_FRAME_SIZE], &mut output)?;
+            packets.push(output[..len].to_vec());
+            self.pending.drain(..VOICE_FRAME_SIZE);
+        }
+        Ok(packets)
+    }
+}
+
+/// Decodes the opus packets of a voice into a [VoiceStream]
+pub struct VoiceDecoder {
+    decoder: opus::Decoder,
+    buffer: VoiceBuffer,
+    last_sequence: Option<u32>,
+}
+
+impl VoiceDecoder {
+    pub fn new(buffer: VoiceBuffer) -> Result<Self> {
+        Ok(Self {
+            decoder: opus::Decoder::new(VOICE_SAMPLE_RATE as u32, Channels::Mono)?,
+            buffer,
+            last_sequence: None,
+        })
+    }
+
+    /// Decodes the packet with the given sequence number. Packets which arrive out of order are
+    /// dropped, and a few missing packets are concealed.
+    pub fn decode(&mut self, sequence: u32, packet: &[u8]) -> Result<()> {
+        let mut output = [0.0; VOICE_FRAME_SIZE];
+        if let Some(last) = self.last_sequence {
+            if sequence <= last {
+                return Ok(());
+            }
+            let lost = sequence - last - 1;
+            if lost <= MAX_CONCEALED_PACKETS {
+                for _ in 0..lost {
+                    let len = self.decoder.decode_float(&[], &mut output, false)?;
+                    self.buffer.push(&output[..len]);
+                }
+            }
+        }
+        self.last_sequence = Some(sequence);
+
+        let len = self.decoder.decode_float(packet, &mut output, false)?;
+        self.buffer.push(&output[..len]);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct VoiceBufferState {
+    samples: VecDeque<f32>,
+    playing: bool,
+}
+
+/// The decoded samples of a voice, waiting to be played
+#[derive(Debug, Clone, Default)]
+pub struct VoiceBuffer {
+    state: Arc<Mutex<VoiceBufferState>>,
+}
+
+impl VoiceBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&self, samples: &[f32]) {
+        let mut state = self.state.lock();
+        state.samples.extend(samples);
+        let excess = state.samples.len().saturating_sub(MAX_BUFFERED);
+        state.samples.drain(..excess);
+    }
+}
+
+/// Plays a [VoiceBuffer]. Outputs silence while waiting for more voice, and never ends.
+#[derive(Debug, Clone)]
+pub struct VoiceStream {
+    buffer: VoiceBuffer,
+}
+
+impl VoiceStream {
+    pub fn new(buffer: VoiceBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl Source for VoiceStream {
+    fn next_sample(&mut self) -> Option<Frame> {
+        let mut state = self.buffer.state.lock();
+        if !state.playing && state.samples.len() < JITTER_BUFFER {
+            return Some(Frame::ZERO);
+        }
+        match state.samples.pop_front() {
+            Some(sample) => {
+                state.playing = true;
+                Some(Frame::splat(sample))
+            }
+            None => {
+                // Ran dry, so wait for the jitter buffer to fill up again
+                state.playing = false;
+                Some(Frame::ZERO)
+            }
+        }
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        VOICE_SAMPLE_RATE
+    }
+
+    fn sample_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn voice_roundtrip() {
+        let mut encoder = VoiceEncoder::new(VOICE_SAMPLE_RATE).unwrap();
+        let frames = (0..VOICE_FRAME_SIZE * 4)
+            .map(|i| Frame::splat((i as f32 * 0.05).sin() * 0.5))
+            .collect::<Vec<_>>();
+        let packets = encoder.push(frames).unwrap();
+        assert_eq!(packets.len(), 4);
+
+        let buffer = VoiceBuffer::new();
+        let mut decoder = VoiceDecoder::new(buffer.clone()).unwrap();
+        decoder.decode(0, &packets[0]).unwrap();
+        // A lost packet is concealed, and a late one dropped
+        decoder.decode(2, &packets[2]).unwrap();
+        decoder.decode(1, &packets[1]).unwrap();
+        assert_eq!(buffer.state.lock().samples.len(), 3 * VOICE_FRAME_SIZE);
+
+        let mut stream = VoiceStream::new(buffer.clone());
+        for _ in 0..3 * VOICE_FRAME_SIZE {
+            stream.next_sample();
+        }
+        assert!(buffer.state.lock().samples.is_empty());
+        assert_eq!(stream.next_sample(), Some(Frame::ZERO));
+    }
+}
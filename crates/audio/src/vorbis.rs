@@ -1,10 +1,16 @@
-use std::{io::Cursor, sync::Arc};
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+    sync::Arc,
+};
 
-use derivative::Derivative;
 use itertools::Itertools;
 use lewton::inside_ogg::OggStreamReader;
 
-use crate::{ChannelCount, Error, Frame, Result, SampleRate, Source};
+use crate::{
+    track::{TrackData, TrackReader},
+    ChannelCount, Error, Frame, LoopPoints, Result, SampleRate, Source,
+};
 
 /// A packet of multi-channel interleaved samples
 struct FramedSamples {
@@ -15,7 +21,7 @@ struct FramedSamples {
 impl lewton::samples::Samples for FramedSamples {
     fn num_samples(&self) -> usize {
         if let Ok(samples) = &self.samples {
-            samples.len() / self.channel_count
+            samples.len()
         } else {
             0
         }
@@ -23,7 +29,7 @@ impl lewton::samples::Samples for FramedSamples {
 
     fn truncate(&mut self, limit: usize) {
         if let Ok(samples) = &mut self.samples {
-            samples.truncate(limit * self.channel_count);
+            samples.truncate(limit);
         }
     }
 
@@ -54,80 +60,131 @@ impl lewton::samples::Samples for FramedSamples {
     }
 }
 
-#[derive(Derivative, Clone)]
-#[derivative(Debug)]
+#[derive(Debug, Clone)]
 pub struct VorbisTrack {
-    #[derivative(Debug = "ignore")]
-    bytes: Arc<[u8]>,
+    data: TrackData,
     /// The number of frames in the fully decoded audio
-    decoded_len: usize,
+    decoded_len: u64,
+    loop_points: Option<LoopPoints>,
 }
 
 impl VorbisTrack {
     pub fn new(bytes: impl Into<Arc<[u8]>>) -> Result<Self> {
-        // Decode once to ensure there are no encoding errors, and acquire the duration
-
-        let bytes = bytes.into();
-        let mut streamer = OggStreamReader::new(Cursor::new(&bytes[..]))?;
-
-        let channels: ChannelCount = streamer.ident_hdr.audio_channels as _;
+        Self::from_data(TrackData::Bytes(bytes.into()))
+    }
 
-        let mut len = 0;
-        while let Some(s) = streamer.read_dec_packet_generic::<FramedSamples>()? {
-            let samples = s.samples?;
-            assert_eq!(
-                samples.len() % channels as usize,
-                0,
-                "Expected packet size to be evenly divisible by channel count"
-            );
-            debug_assert_eq!(channels as usize, s.channel_count);
-
-            len += samples.len() / channels as usize;
-        }
+    pub fn from_data(data: TrackData) -> Result<Self> {
+        let mut reader = data.reader()?;
+        // The granule position of the last page is the length of the stream in frames, so the
+        // stream doesn't have to be decoded up front
+        let decoded_len = last_granule_position(&mut reader)?;
+        reader
+            .rewind()
+            .map_err(|err| Error::Io(err, PathBuf::new()))?;
+        // Read the headers to ensure this is a vorbis stream
+        let streamer = OggStreamReader::new(reader)?;
+        let loop_points = LoopPoints::from_comments(
+            streamer
+                .comment_hdr
+                .comment_list
+                .iter()
+                .map(|(key, value)| (key, value)),
+        );
 
         Ok(Self {
-            bytes,
-            decoded_len: len,
+            data,
+            decoded_len,
+            loop_points,
         })
     }
 
-    pub fn decode(&self) -> VorbisDecodeStream {
-        let streamer = OggStreamReader::new(Cursor::new(self.bytes.clone())).unwrap();
-        let channels: ChannelCount = streamer.ident_hdr.audio_channels as _;
-        let _sample_rate: SampleRate = streamer.ident_hdr.audio_sample_rate as _;
+    /// The loop points in the comments of the stream, if it has any
+    pub fn loop_points(&self) -> Option<LoopPoints> {
+        self.loop_points
+    }
 
-        VorbisDecodeStream {
-            streamer,
-            decoded_len: self.decoded_len,
-            packet: Vec::new(),
-            cursor: 0,
-            channels,
-            bytes: self.bytes.clone(),
-        }
+    pub fn decode(&self) -> VorbisDecodeStream {
+        VorbisDecodeStream::new(self.data.clone(), self.decoded_len)
     }
 }
 
+/// Returns the granule position of the last ogg page in `reader`
+fn last_granule_position(reader: &mut TrackReader) -> Result<u64> {
+    const CAPTURE_PATTERN: &[u8] = b"OggS";
+    // Ogg pages are at most 65307 bytes long
+    const MAX_PAGE_SIZE: u64 = 65307;
+
+    let io_err = |err| Error::Io(err, PathBuf::new());
+    let len = reader.seek(SeekFrom::End(0)).map_err(io_err)?;
+    reader
+        .seek(SeekFrom::Start(len.saturating_sub(MAX_PAGE_SIZE)))
+        .map_err(io_err)?;
+    let mut tail = Vec::new();
+    reader.read_to_end(&mut tail).map_err(io_err)?;
+
+    let page = tail
+        .windows(CAPTURE_PATTERN.len())
+        .rposition(|v| v == CAPTURE_PATTERN)
+        .ok_or_else(|| Error::UnsupportedFormat("ogg stream without pages".into()))?;
+    let granule = tail
+        .get(page + 6..page + 14)
+        .ok_or_else(|| Error::UnsupportedFormat("truncated ogg page".into()))?;
+
+    Ok(u64::from_le_bytes(granule.try_into().unwrap()))
+}
+
 /// Audio source which decodes a compressed ogg stream
 pub struct VorbisDecodeStream {
-    bytes: Arc<[u8]>,
-    streamer: OggStreamReader<Cursor<Arc<[u8]>>>,
-    decoded_len: usize,
+    data: TrackData,
+    streamer: OggStreamReader<TrackReader>,
+    decoded_len: u64,
     packet: Vec<Frame>,
     channels: ChannelCount,
     cursor: usize,
 }
 
-impl Clone for VorbisDecodeStream {
-    fn clone(&self) -> Self {
+impl VorbisDecodeStream {
+    fn new(data: TrackData, decoded_len: u64) -> Self {
+        // Creation of the track ensures the stream can be opened
+        let streamer = OggStreamReader::new(data.reader().unwrap()).unwrap();
+        let channels: ChannelCount = streamer.ident_hdr.audio_channels as _;
+
         Self {
-            streamer: OggStreamReader::new(Cursor::new(self.bytes.clone())).unwrap(),
-            bytes: self.bytes.clone(),
-            decoded_len: self.decoded_len,
+            data,
+            streamer,
+            decoded_len,
             packet: Vec::new(),
-            channels: self.channels,
+            channels,
             cursor: 0,
         }
     }
+
+    /// Decodes the next packet, returning `None` at the end of the stream
+    fn read_packet(&mut self) -> Option<Vec<Frame>> {
+        match self.streamer.read_dec_packet_generic::<FramedSamples>() {
+            Ok(Some(packet)) => match packet.samples {
+                Ok(samples) => {
+                    debug_assert_eq!(packet.channel_count, self.channels as usize);
+                    Some(samples)
+                }
+                Err(err) => {
+                    tracing::error!("Failed to decode vorbis packet: {err}");
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(err) => {
+                tracing::error!("Failed to decode vorbis packet: {err}");
+                None
+            }
+        }
+    }
+}
+
+impl Clone for VorbisDecodeStream {
+    fn clone(&self) -> Self {
+        Self::new(self.data.clone(), self.decoded_len)
+    }
 }
 
 impl Source for VorbisDecodeStream {
@@ -138,16 +195,10 @@ impl Source for VorbisDecodeStream {
             Some(s)
         } else {
             // Read the next packet
-            // Creation of the Track ensures decoding works
             loop {
-                let pkt = self
-                    .streamer
-                    .read_dec_packet_generic::<FramedSamples>()
-                    .unwrap()?;
-
-                self.packet = pkt.samples.unwrap();
+                self.packet = self.read_packet()?;
 
-                if let Some(&s) = self.packet.get(0) {
+                if let Some(&s) = self.packet.first() {
                     self.cursor = 1;
                     return Some(s);
                 }
@@ -160,7 +211,38 @@ impl Source for VorbisDecodeStream {
     }
 
     fn sample_count(&self) -> Option<u64> {
-        // TODO: bug in lewton?
-        Some(self.decoded_len as u64 * self.channels as u64)
+        Some(self.decoded_len)
+    }
+
+    /// Seeks to the ogg page containing `position`, and decodes from there until the position of
+    /// the decoded frames is known, so that the seek is sample accurate.
+    fn seek(&mut self, position: u64) -> bool {
+        if let Err(err) = self.streamer.seek_absgp_pg(position) {
+            tracing::error!("Failed to seek in vorbis stream: {err}");
+            return false;
+        }
+
+        // The position is only known once a packet which ends a page has been decoded, after
+        // which it is tracked for every packet
+        let mut pending: Vec<Frame> = Vec::new();
+        loop {
+            let Some(packet) = self.read_packet() else {
+                // Seeked past the end
+                self.packet = Vec::new();
+                self.cursor = 0;
+                return true;
+            };
+            pending.extend(packet);
+
+            if let Some(end) = self.streamer.get_last_absgp() {
+                if position < end {
+                    let start = end.saturating_sub(pending.len() as u64);
+                    self.cursor = (position.saturating_sub(start) as usize).min(pending.len());
+                    self.packet = pending;
+                    return true;
+                }
+                pending.clear();
+            }
+        }
     }
 }
@@ -1,63 +1,86 @@
-use std::{io::Cursor, sync::Arc};
+use std::sync::Arc;
 
 use cpal::{ChannelCount, Sample};
-use derivative::Derivative;
 use glam::{vec2, Vec2};
 use hound::{SampleFormat, WavReader, WavSpec};
 use itertools::Itertools;
 
-use crate::{Frame, Result, SampleRate, Source};
+use crate::{
+    track::{TrackData, TrackReader},
+    Error, Frame, LoopPoints, Result, SampleRate, Source,
+};
 
-#[derive(Derivative, Clone)]
-#[derivative(Debug)]
+#[derive(Debug, Clone)]
 pub struct WavTrack {
-    #[derivative(Debug = "ignore")]
-    bytes: Arc<[u8]>,
+    data: TrackData,
     /// The number of frames in the fully decoded audio
-    decoded_len: usize,
+    decoded_len: u64,
+    loop_points: Option<LoopPoints>,
 }
 
 impl WavTrack {
     pub fn new(bytes: Arc<[u8]>) -> Result<Self> {
-        let mut reader = WavReader::new(Cursor::new(&bytes[..]))?;
+        Self::from_data(TrackData::Bytes(bytes))
+    }
+
+    pub fn from_data(data: TrackData) -> Result<Self> {
+        let reader = WavReader::new(data.reader()?)?;
 
         let WavSpec {
-            channels,
             bits_per_sample,
             sample_format,
             ..
         } = reader.spec();
+        if !matches!(
+            (sample_format, bits_per_sample),
+            (SampleFormat::Int, 16 | 24 | 32) | (SampleFormat::Float, 32)
+        ) {
+            return Err(Error::UnsupportedFormat(format!(
+                "{bits_per_sample} bit {sample_format:?} wav"
+            )));
+        }
 
-        // Convert samples as appropriate
-        let samples = match (sample_format, bits_per_sample) {
-            (SampleFormat::Int, 16) => reader
-                .samples::<i16>()
-                .map_ok(|_| ())
-                .collect::<std::result::Result<Arc<[_]>, _>>(),
-            (SampleFormat::Int, 24) => reader
-                .samples::<i32>()
-                .map_ok(|_| ())
-                .collect::<std::result::Result<Arc<_>, _>>(),
-            (SampleFormat::Int, 32) => reader
-                .samples::<i32>()
-                .map_ok(|_| ())
-                .collect::<std::result::Result<Arc<_>, _>>(),
-            (SampleFormat::Float, 32) => reader
-                .samples::<f32>()
-                .map_ok(|_| ())
-                .collect::<std::result::Result<Arc<_>, _>>(),
-            _ => {
-                panic!("Unsupported wav format")
-            }
-        }?;
+        // The duration is the number of frames, and is read from the header
+        let decoded_len = reader.duration() as u64;
+        // The loop points are optional, so a malformed `smpl` chunk doesn't fail the track
+        let loop_points = LoopPoints::from_wav(&mut data.reader()?).ok().flatten();
 
-        let decoded_len = samples.len() / channels as usize;
+        Ok(Self {
+            data,
+            decoded_len,
+            loop_points,
+        })
+    }
 
-        Ok(Self { bytes, decoded_len })
+    /// The loop points in the `smpl` chunk of the file, if it has any
+    pub fn loop_points(&self) -> Option<LoopPoints> {
+        self.loop_points
     }
 
     pub fn decode(&self) -> WavDecodeStream {
-        let streamer = WavReader::new(Cursor::new(self.bytes.clone())).unwrap();
+        WavDecodeStream::new(self.data.clone(), self.decoded_len)
+    }
+}
+
+pub const WAV_BLOCK_SIZE: usize = 512;
+
+pub struct WavDecodeStream {
+    data: TrackData,
+    channels: ChannelCount,
+    streamer: WavReader<TrackReader>,
+    format: SampleFormat,
+    bits_per_sample: u16,
+
+    sample_rate: SampleRate,
+    current_block: Vec<Frame>,
+    cursor: usize,
+    decoded_len: u64,
+}
+
+impl WavDecodeStream {
+    fn new(data: TrackData, decoded_len: u64) -> Self {
+        // Creation of the track ensures the stream can be opened
+        let streamer = WavReader::new(data.reader().unwrap()).unwrap();
 
         let WavSpec {
             channels,
@@ -66,48 +89,23 @@ impl WavTrack {
             sample_format,
         } = streamer.spec();
 
-        WavDecodeStream {
+        Self {
+            data,
             streamer,
-            decoded_len: self.decoded_len,
+            decoded_len,
             cursor: 0,
             channels,
             format: sample_format,
             bits_per_sample,
             current_block: Vec::new(),
             sample_rate: sample_rate as _,
-            bytes: self.bytes.clone(),
         }
     }
 }
 
-pub const WAV_BLOCK_SIZE: usize = 512;
-
-pub struct WavDecodeStream {
-    bytes: Arc<[u8]>,
-    channels: ChannelCount,
-    streamer: WavReader<Cursor<Arc<[u8]>>>,
-    format: SampleFormat,
-    bits_per_sample: u16,
-
-    sample_rate: SampleRate,
-    current_block: Vec<Frame>,
-    cursor: usize,
-    decoded_len: usize,
-}
-
 impl Clone for WavDecodeStream {
     fn clone(&self) -> Self {
-        Self {
-            bytes: self.bytes.clone(),
-            streamer: WavReader::new(Cursor::new(self.bytes.clone())).unwrap(),
-            channels: self.channels,
-            format: self.format,
-            bits_per_sample: self.bits_per_sample,
-            sample_rate: self.sample_rate,
-            current_block: Vec::new(),
-            cursor: 0,
-            decoded_len: self.decoded_len,
-        }
+        Self::new(self.data.clone(), self.decoded_len)
     }
 }
 
@@ -182,7 +180,14 @@ impl Source for WavDecodeStream {
             self.cursor += 1;
             Some(val)
         } else {
-            let &s = self.read_next_block().unwrap().get(1)?;
+            let block = match self.read_next_block() {
+                Ok(block) => block,
+                Err(err) => {
+                    tracing::error!("Failed to decode wav: {err}");
+                    return None;
+                }
+            };
+            let &s = block.first()?;
             self.cursor += 1;
             Some(s)
         }
@@ -193,6 +198,17 @@ impl Source for WavDecodeStream {
     }
 
     fn sample_count(&self) -> Option<u64> {
-        Some(self.decoded_len as u64)
+        Some(self.decoded_len)
+    }
+
+    fn seek(&mut self, position: u64) -> bool {
+        let position = position.min(self.decoded_len);
+        if let Err(err) = self.streamer.seek(position as u32) {
+            tracing::error!("Failed to seek in wav: {err}");
+            return false;
+        }
+        self.current_block.clear();
+        self.cursor = 0;
+        true
     }
 }
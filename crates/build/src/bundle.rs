@@ -0,0 +1,136 @@
+//! Packing of built packages into bundles, and unpacking of bundles so that the server can load
+//! their packages.
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ambient_asset_cache::{AssetCache, AsyncAssetKeyExt};
+use ambient_native_std::{
+    asset_url::AbsAssetUrl,
+    bundle::{Bundle, BundleFromUrl, BundleSigningKey, BUNDLE_EXTENSION},
+};
+use ambient_std::path::path_to_unix_string_lossy;
+use anyhow::Context;
+use walkdir::WalkDir;
+
+/// Packs the packages built to `package_build_paths` into a bundle next to them, in
+/// `root_build_path`, and returns its path.
+///
+/// The bundle is signed with the key at `key_path`, or with the default key, which is generated the
+/// first time it is used.
+pub fn write_bundle<'a>(
+    root_build_path: &Path,
+    main_package_build_path: &Path,
+    main_package_name: &str,
+    package_build_paths: impl IntoIterator<Item = &'a Path>,
+    key_path: Option<&Path>,
+) -> anyhow::Result<PathBuf> {
+    let key = load_signing_key(key_path)?;
+    let relative_path = |path: &Path| {
+        path.strip_prefix(root_build_path)
+            .map(path_to_unix_string_lossy)
+            .with_context(|| format!("{path:?} was not built to {root_build_path:?}"))
+    };
+
+    let main_package = relative_path(main_package_build_path)?;
+    let mut files = Vec::new();
+    for package_build_path in package_build_paths {
+        // The docs are only useful locally
+        let docs_path = package_build_path.join("docs");
+        for entry in WalkDir::new(package_build_path)
+            .into_iter()
+            .filter_entry(|entry| entry.path() != docs_path)
+        {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let content = std::fs::read(entry.path())
+                .with_context(|| format!("Failed to read {:?}", entry.path()))?;
+            files.push((relative_path(entry.path())?, content));
+        }
+    }
+
+    let file_count = files.len();
+    let data = Bundle::write(&main_package, main_package_name, files, &key)?;
+    let path = root_build_path.join(format!("{main_package}.{BUNDLE_EXTENSION}"));
+    std::fs::write(&path, data).with_context(|| format!("Failed to write bundle to {path:?}"))?;
+    tracing::info!(
+        "Bundled {file_count} files into {path:?}, signed with {}",
+        data_encoding::HEXLOWER.encode(key.public_key())
+    );
+    Ok(path)
+}
+
+fn load_signing_key(key_path: Option<&Path>) -> anyhow::Result<BundleSigningKey> {
+    let path = match key_path {
+        Some(path) => path.to_owned(),
+        None => {
+            let path = ambient_dirs::bundle_key_path();
+            if !path.exists() {
+                let (key, document) = BundleSigningKey::generate()?;
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                write_private_key(&path, &document)
+                    .with_context(|| format!("Failed to write signing key to {path:?}"))?;
+                tracing::info!("Generated a new key to sign bundles with at {path:?}");
+                return Ok(key);
+            }
+            path
+        }
+    };
+    let document =
+        std::fs::read(&path).with_context(|| format!("Failed to read signing key {path:?}"))?;
+    BundleSigningKey::from_pkcs8(&document).with_context(|| format!("Invalid signing key {path:?}"))
+}
+
+/// The public key of the default signing key, if it has been generated
+pub fn default_public_key() -> anyhow::Result<Option<Vec<u8>>> {
+    let path = ambient_dirs::bundle_key_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(load_signing_key(Some(&path))?.public_key().to_vec()))
+}
+
+/// Writes a private key, which only the current user may read
+fn write_private_key(path: &Path, document: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(document)
+}
+
+/// Downloads the bundle at `url`, and unpacks it to the cache, unless it already was. Returns the
+/// bundle and the directory it was unpacked to.
+pub async fn unpack_bundle(
+    assets: &AssetCache,
+    url: &AbsAssetUrl,
+) -> anyhow::Result<(Arc<Bundle>, PathBuf)> {
+    let bundle = BundleFromUrl(url.clone()).get(assets).await?;
+    let path = ambient_dirs::bundle_cache_path(bundle.hash());
+    if !path.exists() {
+        // Unpacked to a temporary directory first, so that an interrupted unpacking isn't reused
+        let tmp_path = path.with_extension("unpacking");
+        match tokio::fs::remove_dir_all(&tmp_path).await {
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err).context("Failed to remove unpacking directory"),
+        }
+        bundle
+            .unpack(&tmp_path)
+            .with_context(|| format!("Failed to unpack bundle {url} to {tmp_path:?}"))?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .with_context(|| format!("Failed to move unpacked bundle to {path:?}"))?;
+    }
+    Ok((bundle, path))
+}
@@ -23,6 +23,7 @@ use pipelines::{out_asset::OutAsset, FileCollection, ProcessCtx, ProcessCtxKey};
 use tokio::sync::Semaphore;
 use walkdir::WalkDir;
 
+pub mod bundle;
 pub mod migrate;
 pub mod pipelines;
 
@@ -269,13 +270,55 @@ pub async fn build_assets(
     build_path: &Path,
     for_import_only: bool,
 ) -> anyhow::Result<Vec<OutAsset>> {
+    let (assets, _) =
+        build_assets_inner(assets, assets_path, build_path, for_import_only, None).await?;
+    Ok(assets)
+}
+
+/// Builds the assets of a package again after the `changed` files in `assets_path` were modified,
+/// running only the pipelines affected by them. The build metadata isn't updated, so the next
+/// build of the package builds it fully.
+///
+/// Returns the files that were written.
+pub async fn rebuild_assets(
+    assets: &AssetCache,
+    assets_path: &Path,
+    build_path: &Path,
+    changed: &[PathBuf],
+) -> anyhow::Result<Vec<PathBuf>> {
+    let changed = changed
+        .iter()
+        .map(AbsAssetUrl::from_file_path)
+        .collect_vec();
+    // The pipelines may have loaded the changed files through the asset cache the last time
+    assets.invalidate(|key| changed.iter().any(|url| key.contains(url.0.as_str())));
+
+    let (_, written) =
+        build_assets_inner(assets, assets_path, build_path, false, Some(&changed)).await?;
+    Ok(written)
+}
+
+async fn build_assets_inner(
+    assets: &AssetCache,
+    assets_path: &Path,
+    build_path: &Path,
+    for_import_only: bool,
+    changed: Option<&[AbsAssetUrl]>,
+) -> anyhow::Result<(Vec<OutAsset>, Vec<PathBuf>)> {
     let files = get_files_in_path(assets_path).map(Into::into).collect_vec();
+    let asset_processors = pipelines::custom::package_asset_processors(
+        assets,
+        assets_path.parent().unwrap_or(Path::new("")),
+    )?;
 
     let has_errored = Arc::new(AtomicBool::new(false));
 
     let anim_files = Arc::new(parking_lot::Mutex::new(vec![]));
     let anim_files_clone = anim_files.clone();
 
+    let written_files = Arc::new(parking_lot::Mutex::new(vec![]));
+    let written_files_clone = written_files.clone();
+
     let file_write_semaphore = Arc::new(Semaphore::new(10));
 
     let ctx = ProcessCtx {
@@ -285,6 +328,7 @@ pub async fn build_assets(
         out_root: AbsAssetUrl::from_directory_path(build_path.join("assets")),
         input_file_filter: None,
         package_name: "".to_string(),
+        asset_processors,
         write_file: Arc::new({
             let build_path = build_path.to_owned();
             let file_write_semaphore = file_write_semaphore.clone();
@@ -304,6 +348,7 @@ pub async fn build_assets(
                         }
                     }
                 }
+                written_files_clone.lock().push(path.clone());
 
                 async move {
                     std::fs::create_dir_all(path.parent().unwrap()).unwrap();
@@ -330,9 +375,11 @@ pub async fn build_assets(
 
     ProcessCtxKey.insert(&ctx.assets, ctx.clone());
 
-    let assets = pipelines::process_pipelines(&ctx)
-        .await
-        .with_context(|| format!("Failed to process pipelines for {assets_path:?}"))?;
+    let assets = match changed {
+        Some(changed) => pipelines::process_changed_pipelines(&ctx, changed).await,
+        None => pipelines::process_pipelines(&ctx).await,
+    }
+    .with_context(|| format!("Failed to process pipelines for {assets_path:?}"))?;
 
     if !anim_files.lock().is_empty() {
         println!("🐆 Available animation files: {:?}", anim_files.lock());
@@ -352,7 +399,8 @@ pub async fn build_assets(
         anyhow::bail!("Failed to build assets");
     }
 
-    Ok(assets)
+    let written_files = std::mem::take(&mut *written_files.lock());
+    Ok((assets, written_files))
 }
 
 pub async fn build_rust_if_available(
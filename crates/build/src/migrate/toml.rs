@@ -235,6 +235,7 @@ impl From<json_pipeline::Collider> for Collider {
             } => Self::FromModel {
                 flip_normals,
                 reverse_indices,
+                max_convex_hulls: None,
             },
             json_pipeline::Collider::Character { radius, height } => {
                 Self::Character { radius, height }
@@ -256,6 +257,7 @@ impl From<json_pipeline::AudioPipeline> for AudioPipeline {
     fn from(value: json_pipeline::AudioPipeline) -> Self {
         Self {
             convert: value.convert,
+            loudness: None,
         }
     }
 }
@@ -287,6 +289,9 @@ impl From<json_pipeline::ModelsPipeline> for ModelsPipeline {
             prefab_components: None,
             material_overrides: value.material_overrides,
             transforms: value.transforms,
+            lods: None,
+            retarget: None,
+            generate_lods: None,
         }
     }
 }
@@ -308,6 +313,7 @@ impl From<json_pipeline::Pipeline> for Pipeline {
             sources: value.sources,
             tags: value.tags,
             categories: value.categories,
+            texture_compression: None,
         }
     }
 }
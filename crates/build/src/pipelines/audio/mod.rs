@@ -1,7 +1,16 @@
+use std::{
+    io::Cursor,
+    num::{NonZeroU32, NonZeroU8},
+};
+
 use ambient_native_std::asset_url::AssetType;
 use ambient_pipeline_types::audio::AudioPipeline;
-use ambient_world_audio::AudioNode;
+use ambient_world_audio::{
+    core::{BusTap, Frame, LoopPoints, LoudnessMeter, SampleRate},
+    AudioNode,
+};
 use anyhow::Context;
+use itertools::Itertools;
 use tracing::{info_span, Instrument};
 
 use super::{
@@ -12,11 +21,12 @@ use super::{
 pub const SOUND_GRAPH_EXTENSION: &str = "sgr";
 
 pub async fn pipeline(ctx: &PipelineCtx, config: AudioPipeline) -> Vec<OutAsset> {
+    let AudioPipeline { convert, loudness } = config;
     ctx.process_files(
         |file| {
             matches!(
                 file.extension().as_deref(),
-                Some("ogg") | Some("wav") | Some("mp3")
+                Some("ogg") | Some("wav") | Some("mp3") | Some("flac")
             )
         },
         move |ctx, file| async move {
@@ -26,30 +36,25 @@ pub async fn pipeline(ctx: &PipelineCtx, config: AudioPipeline) -> Vec<OutAsset>
 
             let rel_path = ctx.in_root().relative_path(file.decoded_path());
 
-            let content_url = match file.extension().as_deref() {
-                Some("wav") => {
-                    if config.convert {
-                        tracing::debug!("Processing wav file");
-                        let contents = symphonia_convert("wav", contents).await?;
-                        ctx.write_file(rel_path.with_extension("ogg"), contents)
-                            .await
-                    } else {
-                        ctx.write_file(&rel_path, contents).await
-                    }
-                }
-                Some("ogg") => ctx.write_file(&rel_path, contents).await,
-                Some(ext @ "mp3") => {
-                    tracing::debug!("Processing mp3 file");
-                    // Make sure to take the contents, to avoid having both the input and output in
-                    // memory at once
-                    let contents = symphonia_convert(ext, contents).await?;
-                    ctx.write_file(rel_path.with_extension("ogg"), contents)
-                        .await
-                }
-                other => anyhow::bail!(
-                    "Audio filetype {:?} is not yet supported",
-                    other.unwrap_or_default()
-                ),
+            let ext = file.extension().unwrap_or_default();
+            // mp3 and flac files are always converted, and the loudness can only be normalized
+            // by converting the audio
+            let convert = match ext.as_str() {
+                "wav" => convert || loudness.is_some(),
+                "ogg" => loudness.is_some(),
+                "mp3" | "flac" => true,
+                other => anyhow::bail!("Audio filetype {other:?} is not yet supported"),
+            };
+
+            let content_url = if convert {
+                tracing::debug!("Processing {ext} file");
+                // Make sure to take the contents, to avoid having both the input and output in
+                // memory at once
+                let contents = symphonia_convert(&ext, contents, loudness).await?;
+                ctx.write_file(rel_path.with_extension("ogg"), contents)
+                    .await
+            } else {
+                ctx.write_file(&rel_path, contents).await
             };
 
             let root_node = AudioNode::Vorbis {
@@ -98,30 +103,59 @@ fn save_audio_graph(root: AudioNode) -> anyhow::Result<Vec<u8>> {
         .into_bytes())
 }
 
+/// Converts the audio of a file to Ogg Vorbis, keeping its loop points, and normalizing it to an
+/// integrated loudness of `loudness` LUFS if set
 #[tracing::instrument(level = "debug", skip(input))]
-async fn symphonia_convert(ext: &str, input: Vec<u8>) -> anyhow::Result<Vec<u8>> {
-    use std::num::{NonZeroU32, NonZeroU8};
+async fn symphonia_convert(
+    ext: &str,
+    input: Vec<u8>,
+    loudness: Option<f32>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut audio = symphonia_decode(ext, input)?;
+    if let Some(target) = loudness {
+        normalize_loudness(&mut audio, target);
+    }
+    let output = vorbis_encode(&audio)?;
+    tracing::debug!("Encoded {} samples", output.len());
+    Ok(output)
+}
+
+/// Audio decoded to planar samples, with the loop points in its metadata
+struct DecodedAudio {
+    sample_rate: NonZeroU32,
+    /// The samples of each channel
+    channels: Vec<Vec<f32>>,
+    loop_points: Option<LoopPoints>,
+}
 
+fn symphonia_decode(ext: &str, input: Vec<u8>) -> anyhow::Result<DecodedAudio> {
     use symphonia::core::{
         codecs::{DecoderOptions, CODEC_TYPE_NULL},
         errors::Error,
         formats::FormatOptions,
         io::MediaSourceStream,
-        meta::MetadataOptions,
+        meta::{MetadataOptions, MetadataRevision},
         probe::Hint,
     };
 
-    use vorbis_rs::{VorbisBitrateManagementStrategy, VorbisEncoder};
-
     // this symphonia decoding code is largely based on symphonia's examples:
     // https://github.com/pdeljanov/Symphonia/blob/master/symphonia/examples
 
+    // the loop points of wav files are in their `smpl` chunk, which symphonia doesn't read
+    let wav_loop_points = if ext == "wav" {
+        LoopPoints::from_wav(&mut Cursor::new(&input))
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+
     // hint symphonia about what format this file might be
     let mut hint = Hint::new();
     hint.with_extension(ext);
 
     // create a media source stream with default options
-    let media_source = Box::new(std::io::Cursor::new(input));
+    let media_source = Box::new(Cursor::new(input));
     let mss = MediaSourceStream::new(media_source, Default::default());
 
     // use default metadata and format reader options
@@ -129,10 +163,28 @@ async fn symphonia_convert(ext: &str, input: Vec<u8>) -> anyhow::Result<Vec<u8>>
     let fmt_opts = FormatOptions::default();
 
     // probe the audio file for its params
-    let probed = symphonia::default::get_probe()
+    let mut probed = symphonia::default::get_probe()
         .format(&hint, mss, &fmt_opts, &meta_opts)
         .context("Failed to probe audio format")?;
+
+    // collect the tags of the file, which may hold its loop points
+    let mut tags = Vec::new();
+    let mut collect_tags = |revision: &MetadataRevision| {
+        tags.extend(
+            revision
+                .tags()
+                .iter()
+                .map(|tag| (tag.key.clone(), tag.value.to_string())),
+        )
+    };
+    if let Some(revision) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+        collect_tags(revision);
+    }
     let mut format = probed.format;
+    if let Some(revision) = format.metadata().current() {
+        collect_tags(revision);
+    }
+    let loop_points = wav_loop_points.or_else(|| LoopPoints::from_comments(tags));
 
     // find the default audio track for this file
     let track = format
@@ -147,11 +199,8 @@ async fn symphonia_convert(ext: &str, input: Vec<u8>) -> anyhow::Result<Vec<u8>>
         .make(&track.codec_params, &dec_opts)
         .context("Failed to create audio decoder")?;
 
-    // randomize an ogg stream serial number
-    let stream_serial: i32 = rand::random();
-
     // retrieve the sampling rate from the input file
-    let sampling_rate: NonZeroU32 = decoder
+    let sample_rate: NonZeroU32 = decoder
         .codec_params()
         .sample_rate
         .context("Expected audio to have sample rate")?
@@ -159,32 +208,14 @@ async fn symphonia_convert(ext: &str, input: Vec<u8>) -> anyhow::Result<Vec<u8>>
         .context("Audio must have >0 sampling rate")?;
 
     // retrieve the channel count from the input file
-    let channels = decoder
+    let channel_count = decoder
         .codec_params()
         .channels
         .context("Audio does not have any channels")?
         .count();
-    let channels: NonZeroU8 = (channels as u8)
-        .try_into()
-        .context("Audio must have >0 channels")?;
-
-    // select a bitrate
-    let bitrate = VorbisBitrateManagementStrategy::QualityVbr {
-        target_quality: 0.9,
-    };
+    let mut channels = vec![Vec::new(); channel_count];
 
-    // create the ogg Vorbis encoder
-    let mut encoder = VorbisEncoder::new(
-        stream_serial,
-        [("", ""); 0], // no tags
-        sampling_rate,
-        channels,
-        bitrate,
-        None,
-        Vec::new(),
-    )?;
-
-    // process all packets in the input file
+    // decode all packets in the input file
     let result = loop {
         // read the next packet
         let packet = match format.next_packet() {
@@ -202,12 +233,10 @@ async fn symphonia_convert(ext: &str, input: Vec<u8>) -> anyhow::Result<Vec<u8>>
         let mut block = decoded.make_equivalent::<f32>();
         decoded.convert(&mut block);
 
-        // get the samples as &[&[f32]]
-        let planes = block.planes();
-        let plane_samples = planes.planes();
-
-        // feed the samples into the encoder
-        encoder.encode_audio_block(plane_samples)?;
+        // append the samples of each channel
+        for (channel, samples) in channels.iter_mut().zip(block.planes().planes()) {
+            channel.extend_from_slice(samples);
+        }
     };
 
     // process the error returned by the loop
@@ -220,8 +249,99 @@ async fn symphonia_convert(ext: &str, input: Vec<u8>) -> anyhow::Result<Vec<u8>>
         err => return Err(err.into()),
     }
 
+    Ok(DecodedAudio {
+        sample_rate,
+        channels,
+        loop_points,
+    })
+}
+
+/// The highest peak of normalized audio, in dBFS, which leaves some headroom for the encoding
+const NORMALIZED_PEAK: f32 = -1.0;
+
+/// Scales `audio` to an integrated loudness of `target` LUFS, without raising its peak above
+/// [NORMALIZED_PEAK]
+fn normalize_loudness(audio: &mut DecodedAudio, target: f32) {
+    let len = audio.channels.first().map_or(0, |channel| channel.len());
+    if len == 0 {
+        return;
+    }
+
+    // The audio is played in stereo, with mono audio in both channels, so it's measured that way.
+    // Loudness is measured over windows of 400 ms, so shorter sounds are measured as if repeated.
+    let sample_rate = audio.sample_rate.get() as SampleRate;
+    let frames = (0..len.max(sample_rate as usize / 2))
+        .map(|i| match audio.channels.as_slice() {
+            [mono] => Frame::splat(mono[i % len]),
+            [left, right, ..] => Frame::new(left[i % len], right[i % len]),
+            [] => unreachable!(),
+        })
+        .collect_vec();
+    let meter = LoudnessMeter::new();
+    meter.process(&frames, sample_rate);
+    let loudness = meter.loudness().integrated;
+    if !loudness.is_finite() {
+        tracing::debug!("Not normalizing silent audio");
+        return;
+    }
+
+    let db_to_gain = |db: f32| 10f32.powf(db / 20.0);
+    let peak = audio
+        .channels
+        .iter()
+        .flatten()
+        .fold(0f32, |peak, sample| peak.max(sample.abs()));
+    let gain = db_to_gain(target - loudness).min(db_to_gain(NORMALIZED_PEAK) / peak);
+    tracing::debug!("Normalizing audio of {loudness} LUFS with a gain of {gain}");
+    for sample in audio.channels.iter_mut().flatten() {
+        *sample *= gain;
+    }
+}
+
+fn vorbis_encode(audio: &DecodedAudio) -> anyhow::Result<Vec<u8>> {
+    use vorbis_rs::{VorbisBitrateManagementStrategy, VorbisEncoder};
+
+    /// The number of frames encoded at once
+    const BLOCK_SIZE: usize = 4096;
+
+    let channels: NonZeroU8 = (audio.channels.len() as u8)
+        .try_into()
+        .context("Audio must have >0 channels")?;
+
+    // randomize an ogg stream serial number
+    let stream_serial: i32 = rand::random();
+
+    // select a bitrate
+    let bitrate = VorbisBitrateManagementStrategy::QualityVbr {
+        target_quality: 0.9,
+    };
+
+    // create the ogg Vorbis encoder, with the loop points as comments
+    let mut encoder = VorbisEncoder::new(
+        stream_serial,
+        audio
+            .loop_points
+            .map(|points| points.to_comments())
+            .unwrap_or_default(),
+        audio.sample_rate,
+        channels,
+        bitrate,
+        None,
+        Vec::new(),
+    )?;
+
+    // feed the samples into the encoder, a block at a time
+    let len = audio.channels[0].len();
+    for start in (0..len).step_by(BLOCK_SIZE) {
+        let end = (start + BLOCK_SIZE).min(len);
+        let block = audio
+            .channels
+            .iter()
+            .map(|channel| &channel[start..end])
+            .collect_vec();
+        encoder.encode_audio_block(&block)?;
+    }
+
     // finish encoding
-    let output = encoder.finish()?;
-    tracing::debug!("Encoded {} samples", output.len());
-    Ok(output)
+    Ok(encoder.finish()?)
 }
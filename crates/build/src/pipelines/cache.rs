@@ -0,0 +1,394 @@
+//! A content-addressed cache of the outputs of the pipelines, shared by all the packages built on
+//! this machine.
+//!
+//! The outputs of a pipeline are stored under a hash of everything that decides them: the version
+//! of the processors, the pipeline itself, its package and where it is in it, where the package is
+//! built to (as the ids of the assets are made from their urls), and the contents of the files in
+//! its folder, as well as of the other files it reads (see [pipeline_dependencies]). `Custom`
+//! pipelines are only cached if their processor has a
+//! [cache key](super::custom::AssetProcessor::cache_key).
+//! Running a pipeline again with the same inputs writes its outputs from the
+//! cache instead of processing its files again.
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    asset_url::{AbsAssetUrl, AssetType},
+    AmbientVersion,
+};
+use ambient_pipeline_types::PipelineProcessor;
+use itertools::Itertools;
+use parking_lot::Mutex;
+use relative_path::RelativePath;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{
+    context::PipelineCtx,
+    out_asset::{OutAsset, OutAssetContent, OutAssetPreview},
+    pipeline_dependencies, process_pipeline,
+};
+
+/// The version of the processors. Bump it when a pipeline writes different outputs for the same
+/// inputs, so that the outputs cached by earlier versions aren't used.
+const PROCESSORS_VERSION: u32 = 2;
+
+const ENTRY_FILE: &str = "entry.json";
+
+/// The directory of the build cache; `None` disables the cache
+#[derive(Debug, Clone)]
+pub struct BuildCacheDirKey;
+impl SyncAssetKey<Option<PathBuf>> for BuildCacheDirKey {
+    fn load(&self, _assets: AssetCache) -> Option<PathBuf> {
+        Some(ambient_dirs::build_cache_path())
+    }
+}
+
+/// The build cache for a single build, which remembers the hashes of the files it has read
+pub(crate) struct BuildCache {
+    dir: PathBuf,
+    file_hashes: Mutex<HashMap<AbsAssetUrl, Option<String>>>,
+}
+impl BuildCache {
+    pub fn new(assets: &AssetCache) -> Option<Self> {
+        Some(Self {
+            dir: BuildCacheDirKey.get(assets)?,
+            file_hashes: Default::default(),
+        })
+    }
+
+    /// Processes the pipeline of `ctx`, or writes its outputs from the cache if it was processed
+    /// with the same inputs before
+    pub async fn process(&self, ctx: PipelineCtx) -> Vec<OutAsset> {
+        let pipeline = ctx.pipeline.clone();
+        let Some(key) = self.key(&ctx).await else {
+            return process_pipeline(&pipeline, ctx).await;
+        };
+        let entry_path = self.dir.join(key);
+
+        match restore(&ctx, &entry_path).await {
+            Ok(Some(assets)) => {
+                tracing::debug!("Restored pipeline {} from the cache", ctx.pipeline_path());
+                return assets;
+            }
+            Ok(None) => {}
+            Err(err) => tracing::warn!(
+                "Failed to restore pipeline {} from the cache: {err:?}",
+                ctx.pipeline_path()
+            ),
+        }
+
+        let assets = process_pipeline(&pipeline, ctx.clone()).await;
+        if let Err(err) = store(&ctx, &entry_path, &assets).await {
+            tracing::warn!(
+                "Failed to store pipeline {} in the cache: {err:?}",
+                ctx.pipeline_path()
+            );
+        }
+        assets
+    }
+
+    /// The hash of the inputs of the pipeline of `ctx`; `None` if some can't be read
+    async fn key(&self, ctx: &PipelineCtx) -> Option<String> {
+        // The outputs of custom pipelines also depend on their processor, which isn't cached if it
+        // can't tell what decides them
+        let processor = match &ctx.pipeline.processor {
+            PipelineProcessor::Custom(config) => ctx
+                .process_ctx
+                .asset_processors
+                .get(&config.processor)?
+                .cache_key()?,
+            _ => String::new(),
+        };
+        let AmbientVersion { version, revision } = AmbientVersion::default();
+        let parts = [
+            PROCESSORS_VERSION.to_string(),
+            version.to_string(),
+            revision,
+            serde_json::to_string(&*ctx.pipeline).ok()?,
+            processor,
+            ctx.process_ctx.package_name.clone(),
+            ctx.process_ctx.in_root.to_string(),
+            ctx.process_ctx.out_root.to_string(),
+            ctx.root_path.to_string(),
+            ctx.process_ctx
+                .input_file_filter
+                .clone()
+                .unwrap_or_default(),
+        ];
+
+        let mut hasher = Sha256::new();
+        for part in &parts {
+            hash_part(&mut hasher, part.as_bytes());
+        }
+        let files = ctx
+            .files
+            .0
+            .iter()
+            .map(|file| {
+                (
+                    ctx.process_ctx.in_root.relative_path(file.decoded_path()),
+                    file,
+                )
+            })
+            .sorted_by(|(a, _), (b, _)| a.cmp(b));
+        for (path, file) in files {
+            hash_part(&mut hasher, path.as_str().as_bytes());
+            hash_part(&mut hasher, self.file_hash(file).await?.as_bytes());
+        }
+        // The other files it reads can be anywhere, so they are identified by their full url
+        let dependencies = pipeline_dependencies(&ctx.pipeline, ctx).ok()?;
+        for file in dependencies.iter().sorted_by_key(|file| file.to_string()) {
+            hash_part(&mut hasher, file.to_string().as_bytes());
+            hash_part(&mut hasher, self.file_hash(file).await?.as_bytes());
+        }
+        Some(data_encoding::HEXLOWER.encode(&hasher.finalize()))
+    }
+
+    async fn file_hash(&self, file: &AbsAssetUrl) -> Option<String> {
+        if let Some(hash) = self.file_hashes.lock().get(file) {
+            return hash.clone();
+        }
+        let hash = match file.to_file_path() {
+            Ok(Some(path)) => tokio::fs::read(path)
+                .await
+                .ok()
+                .map(|data| data_encoding::HEXLOWER.encode(&Sha256::digest(data))),
+            _ => None,
+        };
+        self.file_hashes.lock().insert(file.clone(), hash.clone());
+        hash
+    }
+}
+
+/// Hashes the length of `data` too, so that the parts can't run into each other
+pub(crate) fn hash_part(hasher: &mut Sha256, data: &[u8]) {
+    hasher.update((data.len() as u64).to_le_bytes());
+    hasher.update(data);
+}
+
+/// The outputs of a pipeline, with the urls relative to the roots of the package
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// The files written, relative to the output root
+    files: Vec<String>,
+    assets: Vec<CachedAsset>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedAsset {
+    id: String,
+    type_: AssetType,
+    hidden: bool,
+    name: String,
+    tags: Vec<String>,
+    categories: [HashSet<String>; 3],
+    /// The model the preview is rendered from; image previews aren't cached, as the build doesn't
+    /// use them
+    preview_model: Option<String>,
+    content: CachedContent,
+    source: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CachedContent {
+    Content(String),
+    Collection(Vec<String>),
+}
+
+impl CachedAsset {
+    fn new(asset: &OutAsset, in_root: &AbsAssetUrl, out_root: &AbsAssetUrl) -> Self {
+        Self {
+            id: asset.id.clone(),
+            type_: asset.type_,
+            hidden: asset.hidden,
+            name: asset.name.clone(),
+            tags: asset.tags.clone(),
+            categories: asset.categories.clone(),
+            preview_model: match &asset.preview {
+                OutAssetPreview::FromModel { url } => Some(relative_url(url, out_root)),
+                _ => None,
+            },
+            content: match &asset.content {
+                OutAssetContent::Content(url) => {
+                    CachedContent::Content(relative_url(url, out_root))
+                }
+                OutAssetContent::Collection(ids) => CachedContent::Collection(ids.clone()),
+            },
+            source: asset.source.as_ref().map(|url| relative_url(url, in_root)),
+        }
+    }
+
+    fn into_out_asset(
+        self,
+        in_root: &AbsAssetUrl,
+        out_root: &AbsAssetUrl,
+    ) -> anyhow::Result<OutAsset> {
+        Ok(OutAsset {
+            id: self.id,
+            type_: self.type_,
+            hidden: self.hidden,
+            name: self.name,
+            tags: self.tags,
+            categories: self.categories,
+            preview: match self.preview_model {
+                Some(url) => OutAssetPreview::FromModel {
+                    url: resolve_url(&url, out_root)?,
+                },
+                None => OutAssetPreview::None,
+            },
+            content: match self.content {
+                CachedContent::Content(url) => {
+                    OutAssetContent::Content(resolve_url(&url, out_root)?)
+                }
+                CachedContent::Collection(ids) => OutAssetContent::Collection(ids),
+            },
+            source: self
+                .source
+                .map(|url| resolve_url(&url, in_root))
+                .transpose()?,
+        })
+    }
+}
+
+/// `url` relative to `root` if it's in it, or else absolute
+fn relative_url(url: &AbsAssetUrl, root: &AbsAssetUrl) -> String {
+    match url.0.as_str().strip_prefix(root.0.as_str()) {
+        Some(path) => format!("./{path}"),
+        None => url.to_string(),
+    }
+}
+
+fn resolve_url(url: &str, root: &AbsAssetUrl) -> anyhow::Result<AbsAssetUrl> {
+    Ok(AbsAssetUrl(root.0.join(url)?))
+}
+
+/// Writes the outputs of the pipeline of `ctx` from the cache entry at `entry_path`, if there's one
+async fn restore(ctx: &PipelineCtx, entry_path: &Path) -> anyhow::Result<Option<Vec<OutAsset>>> {
+    let data = match tokio::fs::read(entry_path.join(ENTRY_FILE)).await {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let entry: CacheEntry = serde_json::from_slice(&data)?;
+
+    let files_path = entry_path.join("files");
+    for path in entry.files {
+        let content = tokio::fs::read(RelativePath::new(&path).to_path(&files_path)).await?;
+        (ctx.process_ctx.write_file)(path, content).await;
+    }
+
+    let process_ctx = &ctx.process_ctx;
+    entry
+        .assets
+        .into_iter()
+        .map(|asset| asset.into_out_asset(&process_ctx.in_root, &process_ctx.out_root))
+        .collect::<anyhow::Result<_>>()
+        .map(Some)
+}
+
+/// Stores the outputs of the pipeline of `ctx` in a cache entry at `entry_path`, unless it failed
+/// or wrote files outside of the output root
+async fn store(ctx: &PipelineCtx, entry_path: &Path, assets: &[OutAsset]) -> anyhow::Result<()> {
+    let record = ctx.record.lock().clone();
+    if record.failed || entry_path.exists() {
+        return Ok(());
+    }
+    let in_root = &ctx.process_ctx.in_root;
+    let out_root = &ctx.process_ctx.out_root;
+
+    let mut files = Vec::new();
+    for url in record.files.iter().unique() {
+        let Some(file_path) = url.to_file_path()? else {
+            return Ok(());
+        };
+        if !url.0.as_str().starts_with(out_root.0.as_str()) {
+            return Ok(());
+        }
+        files.push((file_path, out_root.relative_path(url.decoded_path())));
+    }
+    let entry = CacheEntry {
+        files: files.iter().map(|(_, path)| path.to_string()).collect(),
+        assets: assets
+            .iter()
+            .map(|asset| CachedAsset::new(asset, in_root, out_root))
+            .collect(),
+    };
+
+    // Written to a temporary directory first, so that other builds never see a partial entry
+    let temp_path = entry_path.with_extension(format!("tmp{}", rand::random::<u32>()));
+    let written = async {
+        let files_path = temp_path.join("files");
+        for (file_path, path) in &files {
+            let cached_path = path.to_path(&files_path);
+            if let Some(parent) = cached_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::copy(file_path, cached_path).await?;
+        }
+        tokio::fs::create_dir_all(&temp_path).await?;
+        tokio::fs::write(temp_path.join(ENTRY_FILE), serde_json::to_vec(&entry)?).await?;
+        anyhow::Ok(())
+    }
+    .await;
+    // Fails if another build stored the same outputs first
+    if written.is_err() || tokio::fs::rename(&temp_path, entry_path).await.is_err() {
+        tokio::fs::remove_dir_all(&temp_path).await.ok();
+    }
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline_ctx(assets: &AssetCache, root: &Path, out_dir: &str) -> PipelineCtx {
+        let pipeline = serde_json::json!({
+            "type": "Materials",
+            "importer": { "type": "Single", "base_color": "../shared/base_color.png" },
+        });
+        PipelineCtx::for_test(
+            assets,
+            AbsAssetUrl::from_directory_path(root.join("assets")),
+            AbsAssetUrl::from_directory_path(root.join(out_dir)),
+            "materials",
+            serde_json::from_value(pipeline).unwrap(),
+        )
+    }
+
+    async fn key(root: &Path, ctx: &PipelineCtx) -> String {
+        // A new cache for each key, as the hashes of the files are kept for the whole build
+        let cache = BuildCache {
+            dir: root.join("cache"),
+            file_hashes: Default::default(),
+        };
+        cache.key(ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn key_covers_textures_outside_of_the_folder_and_the_output_root() {
+        let root =
+            std::env::temp_dir().join(format!("ambient_build_cache_{}", rand::random::<u64>()));
+        std::fs::create_dir_all(root.join("assets/materials")).unwrap();
+        std::fs::create_dir_all(root.join("assets/shared")).unwrap();
+        std::fs::write(root.join("assets/materials/pipeline.toml"), "").unwrap();
+        std::fs::write(root.join("assets/shared/base_color.png"), "a").unwrap();
+
+        let assets = AssetCache::new(tokio::runtime::Handle::current());
+        let ctx = pipeline_ctx(&assets, &root, "build");
+        let before = key(&root, &ctx).await;
+        assert_eq!(key(&root, &ctx).await, before);
+
+        std::fs::write(root.join("assets/shared/base_color.png"), "b").unwrap();
+        let after = key(&root, &ctx).await;
+        assert_ne!(after, before);
+
+        let elsewhere = pipeline_ctx(&assets, &root, "other_build");
+        assert_ne!(key(&root, &elsewhere).await, after);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -5,9 +5,11 @@ use ambient_native_std::{
     asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, ModelCrateAssetType, TypedAssetUrl},
 };
+use ambient_pipeline_types::textures::TextureCompressionSettings;
 use anyhow::Context;
 use futures::{future::join_all, Future};
 use itertools::Itertools;
+use parking_lot::Mutex;
 use relative_path::{RelativePath, RelativePathBuf};
 use tokio::sync::Semaphore;
 
@@ -21,7 +23,19 @@ pub struct PipelineCtx {
     pub(crate) root_path: RelativePathBuf,
 
     pub(crate) pipeline: Arc<Pipeline>,
+
+    /// What this pipeline did, for the build cache
+    pub(crate) record: Arc<Mutex<PipelineRecord>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PipelineRecord {
+    /// The files this pipeline wrote
+    pub files: Vec<AbsAssetUrl>,
+    /// Whether processing any of the files failed
+    pub failed: bool,
 }
+
 impl PipelineCtx {
     pub fn assets(&self) -> &AssetCache {
         &self.process_ctx.assets
@@ -68,7 +82,39 @@ impl PipelineCtx {
         self.out_root().push(path).unwrap().as_directory().into()
     }
     pub async fn write_file(&self, path: impl AsRef<str>, content: Vec<u8>) -> AbsAssetUrl {
-        (self.process_ctx.write_file)(self.root_path.join(path.as_ref()).to_string(), content).await
+        let url =
+            (self.process_ctx.write_file)(self.root_path.join(path.as_ref()).to_string(), content)
+                .await;
+        self.record_output(&url);
+        url
+    }
+    /// Records a file written for this pipeline without [Self::write_file], like an image shared
+    /// with other pipelines through the asset cache, as one of its outputs
+    pub fn record_output(&self, url: &AbsAssetUrl) {
+        self.record.lock().files.push(url.clone());
+    }
+    /// How the texture used as the map `map` of a material is compressed, from the texture
+    /// compression of the pipeline; `None` if it isn't. `source` is the image the texture is
+    /// made from, if it's a file of the package.
+    pub fn texture_compression(
+        &self,
+        map: &str,
+        source: Option<&AbsAssetUrl>,
+    ) -> Option<TextureCompressionSettings> {
+        let compression = self.pipeline.texture_compression.as_ref()?;
+        let path = source.map(|source| self.in_root().relative_path(source.decoded_path()));
+        compression.settings(|pattern| {
+            glob::Pattern::new(pattern).is_ok_and(|pattern| {
+                pattern.matches(map)
+                    || path
+                        .as_ref()
+                        .is_some_and(|path| pattern.matches(path.as_str()))
+            })
+        })
+    }
+    async fn report_error(&self, err: anyhow::Error) {
+        self.record.lock().failed = true;
+        (self.process_ctx.on_error)(err).await;
     }
     pub async fn process_single<F: Future<Output = anyhow::Result<Vec<OutAsset>>> + Send>(
         &self,
@@ -97,14 +143,11 @@ impl PipelineCtx {
             Ok(Err(err)) => err,
             Err(err) => err,
         };
-        (self.process_ctx.on_error)(err).await;
+        self.report_error(err).await;
         Vec::new()
     }
-    pub async fn process_files<F: Future<Output = anyhow::Result<Vec<OutAsset>>> + Send>(
-        &self,
-        filter: impl Fn(&AbsAssetUrl) -> bool,
-        process_file: impl Fn(PipelineCtx, AbsAssetUrl) -> F + Sync + Send + 'static,
-    ) -> Vec<OutAsset> {
+    /// The files of this pipeline's sources which match `filter`
+    pub fn filter_files(&self, filter: impl Fn(&AbsAssetUrl) -> bool) -> Vec<AbsAssetUrl> {
         let sources_filter = self
             .pipeline
             .sources
@@ -147,8 +190,7 @@ impl PipelineCtx {
                 .map(|p| p.matches(path.as_str()))
                 .unwrap_or(true)
         };
-        let files = self
-            .files
+        self.files
             .0
             .iter()
             .filter(move |file| {
@@ -179,7 +221,14 @@ impl PipelineCtx {
                 true
             })
             .cloned()
-            .collect_vec();
+            .collect_vec()
+    }
+    pub async fn process_files<F: Future<Output = anyhow::Result<Vec<OutAsset>>> + Send>(
+        &self,
+        filter: impl Fn(&AbsAssetUrl) -> bool,
+        process_file: impl Fn(PipelineCtx, AbsAssetUrl) -> F + Sync + Send + 'static,
+    ) -> Vec<OutAsset> {
+        let files = self.filter_files(filter);
         let n_files = files.len();
         let process_file = Arc::new(process_file);
         let semaphore = PipelineFileSemaphore.get(&self.process_ctx.assets);
@@ -225,7 +274,7 @@ impl PipelineCtx {
                     Ok(Err(err)) => err,
                     Err(err) => err,
                 };
-                (self.process_ctx.on_error)(err).await;
+                self.report_error(err).await;
                 Vec::new()
             }
         }))
@@ -252,3 +301,50 @@ impl SyncAssetKey<Arc<Semaphore>> for PipelineFileSemaphore {
         Arc::new(Semaphore::new(20))
     }
 }
+
+#[cfg(test)]
+impl PipelineCtx {
+    /// A context for `pipeline`, in the folder `root_path` of the package at `in_root`, which
+    /// writes its files to `out_root`
+    pub(crate) fn for_test(
+        assets: &AssetCache,
+        in_root: AbsAssetUrl,
+        out_root: AbsAssetUrl,
+        root_path: &str,
+        pipeline: Pipeline,
+    ) -> Self {
+        use futures::FutureExt;
+
+        let pipeline_file = in_root.join(format!("{root_path}/pipeline.toml")).unwrap();
+        let files = FileCollection(Arc::new(vec![pipeline_file.clone()]));
+        let write_root = out_root.clone();
+        Self {
+            process_ctx: ProcessCtx {
+                assets: assets.clone(),
+                files: files.clone(),
+                input_file_filter: None,
+                package_name: "test".to_string(),
+                in_root,
+                out_root,
+                asset_processors: Default::default(),
+                write_file: Arc::new(move |path: String, contents: Vec<u8>| {
+                    let url = write_root.join(path).unwrap();
+                    async move {
+                        let path = url.to_file_path().unwrap().unwrap();
+                        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                        std::fs::write(path, contents).unwrap();
+                        url
+                    }
+                    .boxed()
+                }),
+                on_status: Arc::new(|_| async {}.boxed()),
+                on_error: Arc::new(|_| async {}.boxed()),
+            },
+            files,
+            pipeline_file,
+            root_path: root_path.into(),
+            pipeline: Arc::new(pipeline),
+            record: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! Asset processors supplied by the users of the asset pipeline, which import the formats the
+//! built-in pipelines don't support, like voxel files or tilemaps.
+//!
+//! A processor is either registered by the application building the packages, by implementing
+//! [AssetProcessor] and adding it to the [AssetProcessorsKey], or declared in the
+//! `[build.asset-processors]` section of the `ambient.toml` of a package as a command, which is
+//! run for each file. `Custom` pipelines import their files with a processor, by its name, and are
+//! only kept in the build cache if it has a [AssetProcessor::cache_key].
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    asset_url::{AbsAssetUrl, AssetType},
+};
+use ambient_package::Manifest as PackageManifest;
+use ambient_pipeline_types::CustomPipeline;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info_span, Instrument};
+use walkdir::WalkDir;
+
+use super::{
+    cache::hash_part,
+    context::PipelineCtx,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+/// Imports the files of the `Custom` pipelines which use it
+#[async_trait]
+pub trait AssetProcessor: Send + Sync {
+    /// Imports `file` with the `config` of its pipeline, writing the files of the assets imported
+    /// from it with [PipelineCtx::write_file]
+    async fn process(
+        &self,
+        ctx: &PipelineCtx,
+        config: &toml::Table,
+        file: &AbsAssetUrl,
+    ) -> anyhow::Result<Vec<OutAsset>>;
+
+    /// What decides the outputs of this processor besides the files and the config of the
+    /// pipeline, for the build cache. The pipelines using a processor without one, the default,
+    /// aren't cached.
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The asset processors the pipelines can use, by name
+#[derive(Clone, Default)]
+pub struct AssetProcessors(HashMap<String, Arc<dyn AssetProcessor>>);
+impl AssetProcessors {
+    /// Registers `processor` as `name`, replacing the processor registered as `name` before
+    pub fn register(&mut self, name: impl Into<String>, processor: impl AssetProcessor + 'static) {
+        self.0.insert(name.into(), Arc::new(processor));
+    }
+    pub fn get(&self, name: &str) -> Option<Arc<dyn AssetProcessor>> {
+        self.0.get(name).cloned()
+    }
+}
+impl std::fmt::Debug for AssetProcessors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// The asset processors registered by the application, which the pipelines of all the packages
+/// can use. Empty by default.
+#[derive(Debug, Clone)]
+pub struct AssetProcessorsKey;
+impl SyncAssetKey<AssetProcessors> for AssetProcessorsKey {
+    fn load(&self, _assets: AssetCache) -> AssetProcessors {
+        AssetProcessors::default()
+    }
+}
+
+/// Registers `processor` as `name` in the [AssetProcessorsKey] of `assets`
+pub fn register_asset_processor(
+    assets: &AssetCache,
+    name: impl Into<String>,
+    processor: impl AssetProcessor + 'static,
+) {
+    let mut processors = AssetProcessorsKey.get(assets);
+    processors.register(name, processor);
+    AssetProcessorsKey.insert(assets, processors);
+}
+
+/// The asset processors the pipelines of the package at `package_path` can use: the ones
+/// registered by the application, and the ones declared in its manifest, which take precedence
+pub(crate) fn package_asset_processors(
+    assets: &AssetCache,
+    package_path: &Path,
+) -> anyhow::Result<AssetProcessors> {
+    let mut processors = AssetProcessorsKey.get(assets);
+
+    let manifest_path = package_path.join("ambient.toml");
+    if !manifest_path.exists() {
+        return Ok(processors);
+    }
+    let manifest = PackageManifest::parse(
+        &std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {manifest_path:?}"))?,
+    )
+    .with_context(|| format!("Failed to parse {manifest_path:?}"))?;
+    for (name, processor) in manifest.build.asset_processors {
+        processors.register(
+            name.as_str(),
+            CommandAssetProcessor {
+                package_path: package_path.to_owned(),
+                command: processor.command,
+            },
+        );
+    }
+    Ok(processors)
+}
+
+pub async fn pipeline(ctx: &PipelineCtx, config: CustomPipeline) -> Vec<OutAsset> {
+    let Some(processor) = ctx.process_ctx.asset_processors.get(&config.processor) else {
+        let name = config.processor;
+        return ctx
+            .process_single(move |_| async move {
+                Err(anyhow::anyhow!(
+                    "There is no asset processor named \"{name}\"; declare it in the [build.asset-processors] section of ambient.toml"
+                ))
+            })
+            .await;
+    };
+    let config = Arc::new(config.config);
+    ctx.process_files(
+        |file| {
+            !file
+                .decoded_path()
+                .file_name()
+                .unwrap_or_default()
+                .ends_with("pipeline.toml")
+        },
+        move |ctx, file| {
+            let processor = processor.clone();
+            let config = config.clone();
+            async move { processor.process(&ctx, &config, &file).await }
+        },
+    )
+    .instrument(info_span!("custom_pipeline"))
+    .await
+}
+
+/// An asset processor declared in the manifest of a package, which runs a command for each file.
+///
+/// The command is sent a JSON request on its standard input:
+/// `{ "input": "<path of the file>", "output": "<directory to write the assets to>", "config": { ... } }`.
+/// It writes the files of the assets it imports to the output directory, then prints a JSON
+/// response listing the assets: `{ "assets": [{ "path": "castle.png", "type": "Image" }] }`,
+/// with their paths relative to the output directory.
+pub struct CommandAssetProcessor {
+    /// The directory the command is run in
+    pub package_path: PathBuf,
+    pub command: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CommandRequest<'a> {
+    input: &'a Path,
+    output: &'a Path,
+    config: &'a toml::Table,
+}
+
+#[derive(Deserialize)]
+struct CommandResponse {
+    assets: Vec<CommandAsset>,
+}
+
+#[derive(Deserialize)]
+struct CommandAsset {
+    path: String,
+    #[serde(rename = "type")]
+    type_: AssetType,
+    /// Defaults to the file name of `path`
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[async_trait]
+impl AssetProcessor for CommandAssetProcessor {
+    async fn process(
+        &self,
+        ctx: &PipelineCtx,
+        config: &toml::Table,
+        file: &AbsAssetUrl,
+    ) -> anyhow::Result<Vec<OutAsset>> {
+        let dir = std::env::temp_dir().join(format!(
+            "ambient_asset_processor_{:016x}",
+            rand::random::<u64>()
+        ));
+        let res = self.process_in(ctx, config, file, &dir).await;
+        std::fs::remove_dir_all(&dir).ok();
+        res
+    }
+
+    /// The command, and the contents of the files it names, like its script
+    fn cache_key(&self) -> Option<String> {
+        let mut hasher = Sha256::new();
+        for part in &self.command {
+            hash_part(&mut hasher, part.as_bytes());
+            let path = self.package_path.join(part);
+            if path.is_file() {
+                hash_part(&mut hasher, &Sha256::digest(std::fs::read(path).ok()?));
+            }
+        }
+        Some(data_encoding::HEXLOWER.encode(&hasher.finalize()))
+    }
+}
+impl CommandAssetProcessor {
+    /// Processes `file`, with the input and output files of the command in `dir`
+    async fn process_in(
+        &self,
+        ctx: &PipelineCtx,
+        config: &toml::Table,
+        file: &AbsAssetUrl,
+        dir: &Path,
+    ) -> anyhow::Result<Vec<OutAsset>> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .context("The command of the asset processor is empty")?;
+
+        let input = dir
+            .join("input")
+            .join(file.decoded_path().file_name().unwrap_or("input"));
+        let output = dir.join("output");
+        std::fs::create_dir_all(input.parent().unwrap())?;
+        std::fs::create_dir_all(&output)?;
+        std::fs::write(&input, &*file.download_bytes(ctx.assets()).await?)?;
+
+        let request = serde_json::to_vec(&CommandRequest {
+            input: &input,
+            output: &output,
+            config,
+        })?;
+        let mut command = std::process::Command::new(program);
+        command.args(args).current_dir(&self.package_path);
+        let result = tokio::task::spawn_blocking(move || -> std::io::Result<_> {
+            use std::io::Write;
+
+            let mut child = command
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()?;
+            child.stdin.take().unwrap().write_all(&request)?;
+            child.wait_with_output()
+        })
+        .await?
+        .with_context(|| format!("Failed to run the asset processor {:?}", self.command))?;
+        if !result.status.success() {
+            anyhow::bail!(
+                "The asset processor {:?} failed with {}:\n{}",
+                self.command,
+                result.status,
+                String::from_utf8_lossy(&result.stderr)
+            );
+        }
+        let response: CommandResponse =
+            serde_json::from_slice(&result.stdout).with_context(|| {
+                format!(
+                    "The asset processor {:?} printed an invalid response",
+                    self.command
+                )
+            })?;
+
+        // The assets are written to a directory named after the file, with all the files the
+        // processor wrote, which the assets may reference
+        let out_path = ctx.in_root().relative_path(file.decoded_path());
+        let mut urls = HashMap::new();
+        for entry in WalkDir::new(&output).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path =
+                ambient_std::path::path_to_unix_string_lossy(entry.path().strip_prefix(&output)?);
+            let url = ctx
+                .write_file(
+                    out_path.join(&path),
+                    std::fs::read(entry.path())
+                        .with_context(|| format!("Failed to read {:?}", entry.path()))?,
+                )
+                .await;
+            urls.insert(path, url);
+        }
+
+        response
+            .assets
+            .into_iter()
+            .map(|asset| {
+                let path = asset.path.trim_start_matches("./");
+                let url = urls.get(path).with_context(|| {
+                    format!(
+                        "The asset processor {:?} didn't write the asset {path}",
+                        self.command
+                    )
+                })?;
+                Ok(OutAsset {
+                    id: asset_id_from_url(url),
+                    type_: asset.type_,
+                    hidden: false,
+                    name: asset
+                        .name
+                        .unwrap_or_else(|| path.rsplit('/').next().unwrap_or_default().to_string()),
+                    tags: asset.tags,
+                    categories: Default::default(),
+                    preview: OutAssetPreview::None,
+                    content: OutAssetContent::Content(url.clone()),
+                    source: Some(file.clone()),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Runs `script` with `sh` as the processor of the file `voxels/castle.vox`, in a new package
+    /// in `root`
+    async fn process(root: &Path, script: &str) -> anyhow::Result<Vec<OutAsset>> {
+        std::fs::create_dir_all(root.join("assets/voxels")).unwrap();
+        std::fs::write(root.join("assets/voxels/castle.vox"), "voxels").unwrap();
+
+        let assets = AssetCache::new(tokio::runtime::Handle::current());
+        let in_root = AbsAssetUrl::from_directory_path(root.join("assets"));
+        let file = in_root.join("voxels/castle.vox").unwrap();
+        let pipeline = serde_json::json!({ "type": "Custom", "processor": "voxels" });
+        let ctx = PipelineCtx::for_test(
+            &assets,
+            in_root,
+            AbsAssetUrl::from_directory_path(root.join("build")),
+            "voxels",
+            serde_json::from_value(pipeline).unwrap(),
+        );
+        let processor = CommandAssetProcessor {
+            package_path: root.to_owned(),
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+        };
+        processor.process(&ctx, &Default::default(), &file).await
+    }
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "ambient_custom_processor_{:016x}",
+            rand::random::<u64>()
+        ))
+    }
+
+    #[tokio::test]
+    async fn command_writes_the_assets_it_lists() {
+        let root = temp_root();
+        let script = r#"
+            request=$(cat)
+            input=$(printf '%s' "$request" | sed 's/.*"input":"\([^"]*\)".*/\1/')
+            output=$(printf '%s' "$request" | sed 's/.*"output":"\([^"]*\)".*/\1/')
+            cp "$input" "$output/castle.png"
+            echo '{ "assets": [{ "path": "./castle.png", "type": "Image", "tags": ["voxel"] }] }'
+        "#;
+        let assets = process(&root, script).await.unwrap();
+
+        assert_eq!(assets.len(), 1);
+        let asset = &assets[0];
+        assert_eq!(asset.type_, AssetType::Image);
+        assert_eq!(asset.name, "castle.png");
+        assert_eq!(asset.tags, ["voxel"]);
+        let OutAssetContent::Content(url) = &asset.content else {
+            panic!("The asset has no content");
+        };
+        let path = url.to_file_path().unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "voxels");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn command_failures_are_reported() {
+        let root = temp_root();
+        let err = process(&root, "cat > /dev/null; echo broken >&2; exit 3")
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("broken"), "{err:#}");
+
+        let script =
+            r#"cat > /dev/null; echo '{ "assets": [{ "path": "missing.png", "type": "Image" }] }'"#;
+        let err = process(&root, script).await.unwrap_err();
+        assert!(
+            format!("{err:#}").contains("didn't write the asset missing.png"),
+            "{err:#}"
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn cache_key_covers_the_files_of_the_command() {
+        let root = temp_root();
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("voxels.sh"), "echo a").unwrap();
+        let processor = |script: &str| CommandAssetProcessor {
+            package_path: root.clone(),
+            command: vec!["sh".to_string(), script.to_string()],
+        };
+
+        let before = processor("voxels.sh").cache_key().unwrap();
+        assert_eq!(processor("voxels.sh").cache_key().unwrap(), before);
+        assert_ne!(processor("other.sh").cache_key().unwrap(), before);
+        std::fs::write(root.join("voxels.sh"), "echo b").unwrap();
+        assert_ne!(processor("voxels.sh").cache_key().unwrap(), before);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
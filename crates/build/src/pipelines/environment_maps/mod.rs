@@ -0,0 +1,279 @@
+use std::f32::consts::PI;
+
+use ambient_native_std::asset_url::AssetType;
+use ambient_pipeline_types::EnvironmentMapsPipeline;
+use ambient_renderer::{pack_rgb9e5, EnvironmentMapDesc, ENVIRONMENT_MAP_SH_COEFFICIENTS};
+use anyhow::Context;
+use glam::{vec3, Vec3};
+use image::{imageops::FilterType, Rgb32FImage};
+use tracing::{info_span, Instrument};
+
+use super::{
+    context::PipelineCtx,
+    download_image,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+/// The mip levels of the environment maps, prefiltered for a roughness of 0, 0.2, ..., 1
+const LEVELS: u32 = 6;
+/// The GGX samples taken for each texel of the prefiltered levels
+const SAMPLES: u32 = 64;
+/// The widest image the irradiance is integrated over
+const IRRADIANCE_WIDTH: u32 = 64;
+
+pub async fn pipeline(ctx: &PipelineCtx, config: EnvironmentMapsPipeline) -> Vec<OutAsset> {
+    // Every level needs to be at least one texel high
+    let size = config.size.unwrap_or(512).max(2 << LEVELS);
+    ctx.process_files(
+        |file| matches!(file.extension().as_deref(), Some("hdr") | Some("exr")),
+        move |ctx, file| async move {
+            let image = download_image(ctx.assets(), &file).await?.into_rgb32f();
+
+            let filename = file.decoded_path().file_name().unwrap().to_string();
+            let rel_path = ctx.in_root().relative_path(file.decoded_path());
+
+            let (levels, irradiance) = tokio::task::spawn_blocking(move || prefilter(&image, size))
+                .await
+                .context("Environment map prefiltering panicked")?;
+            let texels = levels
+                .iter()
+                .flat_map(|level| level.texels.iter())
+                .flat_map(|color| pack_rgb9e5(*color).to_le_bytes())
+                .collect::<Vec<_>>();
+
+            let texels_path = rel_path.with_extension("env.bin");
+            ctx.write_file(&texels_path, texels).await;
+            let desc = EnvironmentMapDesc {
+                width: levels[0].width,
+                height: levels[0].height,
+                levels: LEVELS,
+                irradiance,
+                texels: texels_path.file_name().unwrap().to_string(),
+            };
+            let desc_url = ctx
+                .write_file(
+                    rel_path.with_extension("env.json"),
+                    serde_json::to_vec_pretty(&desc)?,
+                )
+                .await;
+
+            Ok(vec![OutAsset {
+                id: asset_id_from_url(&file),
+                type_: AssetType::EnvironmentMap,
+                hidden: false,
+                name: filename,
+                tags: Vec::new(),
+                categories: Default::default(),
+                preview: OutAssetPreview::None,
+                content: OutAssetContent::Content(desc_url),
+                source: Some(file.clone()),
+            }])
+        },
+    )
+    .instrument(info_span!("environment_maps_pipeline"))
+    .await
+}
+
+/// An equirectangular image, with +Z at the top
+struct Equirect {
+    width: u32,
+    height: u32,
+    texels: Vec<Vec3>,
+}
+impl Equirect {
+    fn new(image: &Rgb32FImage, width: u32) -> Self {
+        let height = width / 2;
+        let image = image::imageops::resize(image, width, height, FilterType::Triangle);
+        Self {
+            width,
+            height,
+            texels: image.pixels().map(|p| Vec3::from(p.0)).collect(),
+        }
+    }
+
+    /// The direction at the texture coordinates `u`, `v`
+    fn direction(u: f32, v: f32) -> Vec3 {
+        let phi = (u - 0.5) * 2. * PI;
+        let theta = v * PI;
+        vec3(
+            theta.sin() * phi.cos(),
+            theta.sin() * phi.sin(),
+            theta.cos(),
+        )
+    }
+
+    fn texel_direction(&self, x: u32, y: u32) -> Vec3 {
+        Self::direction(
+            (x as f32 + 0.5) / self.width as f32,
+            (y as f32 + 0.5) / self.height as f32,
+        )
+    }
+
+    fn texel(&self, x: i32, y: i32) -> Vec3 {
+        let x = x.rem_euclid(self.width as i32) as u32;
+        let y = y.clamp(0, self.height as i32 - 1) as u32;
+        self.texels[(y * self.width + x) as usize]
+    }
+
+    /// Samples the image bilinearly, wrapping around horizontally
+    fn sample(&self, dir: Vec3) -> Vec3 {
+        let dir = dir.normalize();
+        let u = dir.y.atan2(dir.x) / (2. * PI) + 0.5;
+        let v = dir.z.clamp(-1., 1.).acos() / PI;
+        let x = u * self.width as f32 - 0.5;
+        let y = v * self.height as f32 - 0.5;
+        let (x0, y0) = (x.floor() as i32, y.floor() as i32);
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+        let top = self.texel(x0, y0).lerp(self.texel(x0 + 1, y0), fx);
+        let bottom = self.texel(x0, y0 + 1).lerp(self.texel(x0 + 1, y0 + 1), fx);
+        top.lerp(bottom, fy)
+    }
+
+    fn downsampled(&self) -> Self {
+        let (width, height) = (self.width / 2, self.height / 2);
+        let texels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x as i32 * 2, y as i32 * 2)))
+            .map(|(x, y)| {
+                (self.texel(x, y)
+                    + self.texel(x + 1, y)
+                    + self.texel(x, y + 1)
+                    + self.texel(x + 1, y + 1))
+                    / 4.
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            texels,
+        }
+    }
+}
+
+/// Prefilters the levels of an environment map, and integrates its diffuse irradiance
+fn prefilter(
+    image: &Rgb32FImage,
+    width: u32,
+) -> (Vec<Equirect>, [Vec3; ENVIRONMENT_MAP_SH_COEFFICIENTS]) {
+    let mut mips = vec![Equirect::new(image, width)];
+    while mips.last().unwrap().height > 1 {
+        let next = mips.last().unwrap().downsampled();
+        mips.push(next);
+    }
+
+    let irradiance = irradiance(
+        mips.iter()
+            .find(|mip| mip.width <= IRRADIANCE_WIDTH)
+            .unwrap_or_else(|| mips.last().unwrap()),
+    );
+    let levels = (0..LEVELS)
+        .map(|level| {
+            if level == 0 {
+                return Equirect {
+                    width: mips[0].width,
+                    height: mips[0].height,
+                    texels: mips[0].texels.clone(),
+                };
+            }
+            let roughness = level as f32 / (LEVELS - 1) as f32;
+            prefilter_level(&mips, mips[level as usize].width, roughness)
+        })
+        .collect();
+    (levels, irradiance)
+}
+
+/// Convolves the environment with the GGX lobe of `roughness`, seen from straight above the
+/// surface. The samples covering a wider part of the lobe read blurrier mips, to avoid aliasing.
+fn prefilter_level(mips: &[Equirect], width: u32, roughness: f32) -> Equirect {
+    let height = width / 2;
+    let a = roughness * roughness;
+    let texel_solid_angle = 4. * PI / (mips[0].width * mips[0].height) as f32;
+    let texels = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let normal = Equirect::direction(
+                (x as f32 + 0.5) / width as f32,
+                (y as f32 + 0.5) / height as f32,
+            );
+            let (tangent, bitangent) = normal.any_orthonormal_pair();
+            let mut sum = Vec3::ZERO;
+            let mut weight = 0f32;
+            for i in 0..SAMPLES {
+                let (u1, u2) = hammersley(i, SAMPLES);
+                let phi = 2. * PI * u1;
+                let cos_theta = ((1. - u2) / (1. + (a * a - 1.) * u2)).sqrt();
+                let sin_theta = (1. - cos_theta * cos_theta).sqrt();
+                let half = tangent * sin_theta * phi.cos()
+                    + bitangent * sin_theta * phi.sin()
+                    + normal * cos_theta;
+                let light = 2. * normal.dot(half) * half - normal;
+                let ndotl = normal.dot(light);
+                if ndotl <= 0. {
+                    continue;
+                }
+                let d = cos_theta * cos_theta * (a * a - 1.) + 1.;
+                let pdf = a * a / (PI * d * d) / 4.;
+                let sample_solid_angle = 1. / (SAMPLES as f32 * pdf + 0.0001);
+                let mip = (0.5 * (sample_solid_angle / texel_solid_angle).log2() + 1.)
+                    .clamp(0., (mips.len() - 1) as f32);
+                sum += sample_mips(mips, light, mip) * ndotl;
+                weight += ndotl;
+            }
+            sum / weight.max(0.0001)
+        })
+        .collect();
+    Equirect {
+        width,
+        height,
+        texels,
+    }
+}
+
+fn sample_mips(mips: &[Equirect], dir: Vec3, mip: f32) -> Vec3 {
+    let low = mip.floor() as usize;
+    let high = (low + 1).min(mips.len() - 1);
+    mips[low]
+        .sample(dir)
+        .lerp(mips[high].sample(dir), mip - mip.floor())
+}
+
+fn hammersley(i: u32, count: u32) -> (f32, f32) {
+    (
+        i as f32 / count as f32,
+        i.reverse_bits() as f32 * 2.328_306_4e-10,
+    )
+}
+
+/// The second order spherical harmonics of the diffuse irradiance of the environment, divided by
+/// pi, in the order the renderer evaluates them
+fn irradiance(image: &Equirect) -> [Vec3; ENVIRONMENT_MAP_SH_COEFFICIENTS] {
+    let mut sh = [Vec3::ZERO; ENVIRONMENT_MAP_SH_COEFFICIENTS];
+    for y in 0..image.height {
+        let theta = (y as f32 + 0.5) / image.height as f32 * PI;
+        let solid_angle = (2. * PI / image.width as f32) * (PI / image.height as f32) * theta.sin();
+        for x in 0..image.width {
+            let dir = image.texel_direction(x, y);
+            let color = image.texels[(y * image.width + x) as usize];
+            let basis = [
+                0.282095,
+                0.488603 * dir.y,
+                0.488603 * dir.z,
+                0.488603 * dir.x,
+                1.092548 * dir.x * dir.y,
+                1.092548 * dir.y * dir.z,
+                0.315392 * (3. * dir.z * dir.z - 1.),
+                1.092548 * dir.x * dir.z,
+                0.546274 * (dir.x * dir.x - dir.y * dir.y),
+            ];
+            for (coefficient, basis) in sh.iter_mut().zip(basis) {
+                *coefficient += color * basis * solid_angle;
+            }
+        }
+    }
+    // Convolving the radiance with a clamped cosine scales each band by pi, 2 pi / 3 and pi / 4
+    let bands: [f32; ENVIRONMENT_MAP_SH_COEFFICIENTS] =
+        [1., 2. / 3., 2. / 3., 2. / 3., 0.25, 0.25, 0.25, 0.25, 0.25];
+    for (coefficient, band) in sh.iter_mut().zip(bands) {
+        *coefficient *= band;
+    }
+    sh
+}
@@ -14,6 +14,7 @@ use ambient_physics::collider::{collider, collider_type};
 use ambient_pipeline_types::{
     materials::{MaterialsImporter, MaterialsPipeline, PipelinePbrMaterial},
     models::ModelTextureSize,
+    textures::TextureCompressionSettings,
 };
 use ambient_renderer::materials::pbr_material::PbrMaterialDesc;
 use anyhow::Context;
@@ -21,11 +22,12 @@ use async_trait::async_trait;
 use dyn_clonable::*;
 use futures::{future::BoxFuture, FutureExt};
 use glam::Vec3;
-use image::{ImageOutputFormat, RgbaImage};
+use image::{ImageOutputFormat, Pixel, RgbaImage};
 
 use super::{
     context::PipelineCtx,
     out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+    texture_compression::{self, compress_texture, is_srgb_map},
     ProcessCtxKey,
 };
 use crate::pipelines::download_image;
@@ -46,12 +48,18 @@ pub async fn pipeline(ctx: &PipelineCtx, config: MaterialsPipeline) -> Vec<OutAs
 
                 let mat_out_url = ctx.out_root().join(ctx.pipeline_path())?.as_directory();
                 let material = to_mat(&mat, &ctx, &ctx.in_root(), &mat_out_url).await?;
-                let base_color_url = material
+                let mut base_color_url = material
                     .base_color
                     .clone()
                     .unwrap()
                     .resolve(&mat_out_url)
                     .unwrap();
+                // Compressed textures can't be decoded here, so the preview is made from the
+                // source image instead
+                if base_color_url.extension_is(texture_compression::EXTENSION) {
+                    base_color_url = AssetUrl::from_str(mat.base_color.as_ref().unwrap())?
+                        .resolve(&ctx.in_root())?;
+                }
                 let base_color = ImageFromUrl {
                     url: base_color_url,
                 }
@@ -134,13 +142,44 @@ pub async fn pipeline(ctx: &PipelineCtx, config: MaterialsPipeline) -> Vec<OutAs
     }
 }
 
+/// The images the materials of `config` read, which can be outside of the folder of the pipeline
+pub fn dependencies(
+    ctx: &PipelineCtx,
+    config: &MaterialsPipeline,
+) -> anyhow::Result<Vec<AbsAssetUrl>> {
+    match &*config.importer {
+        MaterialsImporter::Single(mat) => texture_dependencies(mat, &ctx.in_root()),
+        MaterialsImporter::Quixel => Ok(vec![]),
+    }
+}
+
+/// The images [to_mat] reads for `pipeline`, resolved against `source_root`
+pub fn texture_dependencies(
+    pipeline: &PipelinePbrMaterial,
+    source_root: &AbsAssetUrl,
+) -> anyhow::Result<Vec<AbsAssetUrl>> {
+    [
+        &pipeline.base_color,
+        &pipeline.opacity,
+        &pipeline.normalmap,
+        &pipeline.metallic_roughness,
+        &pipeline.specular,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| Ok(AssetUrl::from_str(path)?.resolve(source_root)?))
+    .collect()
+}
+
 pub async fn to_mat(
     pipeline: &PipelinePbrMaterial,
     ctx: &PipelineCtx,
     source_root: &AbsAssetUrl,
     out_root: &AbsAssetUrl,
 ) -> anyhow::Result<PbrMaterialDesc> {
-    let pipe_image = |path: &Option<String>| -> BoxFuture<'_, anyhow::Result<Option<AssetUrl>>> {
+    let pipe_image = |path: &Option<String>,
+                      map: &'static str|
+     -> BoxFuture<'_, anyhow::Result<Option<AssetUrl>>> {
         let source_root = source_root.clone();
         let path = path.clone();
         let ctx = ctx.clone();
@@ -154,7 +193,8 @@ pub async fn to_mat(
                             .resolve(&source_root)
                             .unwrap(),
                     )
-                    .get(ctx.assets())
+                    .compress_as(&ctx, map)
+                    .write(&ctx)
                     .await?,
                 )))
             } else {
@@ -163,12 +203,51 @@ pub async fn to_mat(
         }
         .boxed()
     };
+    // The runtime can't put a separate opacity map into a compressed base color texture, so
+    // it is put into the base color before it's compressed
+    let base_color = pipeline.base_color.as_ref().map(|path| {
+        PipeImage::resolve(
+            ctx,
+            AssetUrl::from_str(path)
+                .unwrap()
+                .resolve(source_root)
+                .unwrap(),
+        )
+        .compress_as(ctx, "base_color")
+    });
+    let (base_color, opacity) = match (base_color, &pipeline.opacity) {
+        (Some(base_color), Some(opacity)) if base_color.compression.is_some() => {
+            let opacity = AssetUrl::from_str(opacity)
+                .unwrap()
+                .resolve(source_root)
+                .unwrap();
+            let base_color = base_color
+                .second_source(ctx.get_downloadable_url(&opacity)?.clone())
+                .transform("with_opacity", |image, opacity| {
+                    if let Some(opacity) = opacity {
+                        for (pixel, alpha) in image.pixels_mut().zip(opacity.pixels()) {
+                            pixel[3] = alpha.to_luma()[0];
+                        }
+                    }
+                })
+                .write(ctx)
+                .await?;
+            (Some(AssetUrl::from(base_color)), None)
+        }
+        (base_color, _) => (
+            match base_color {
+                Some(base_color) => Some(AssetUrl::from(base_color.write(ctx).await?)),
+                None => None,
+            },
+            pipe_image(&pipeline.opacity, "opacity").await?,
+        ),
+    };
     Ok(PbrMaterialDesc {
         name: pipeline.name.clone(),
         source: pipeline.source.clone(),
-        base_color: pipe_image(&pipeline.base_color).await?,
-        opacity: pipe_image(&pipeline.opacity).await?,
-        normalmap: pipe_image(&pipeline.normalmap).await?,
+        base_color,
+        opacity,
+        normalmap: pipe_image(&pipeline.normalmap, "normalmap").await?,
         metallic_roughness: if let Some(url) = &pipeline.metallic_roughness {
             Some(
                 PipeImage::resolve(
@@ -178,7 +257,8 @@ pub async fn to_mat(
                         .resolve(source_root)
                         .unwrap(),
                 )
-                .get(ctx.assets())
+                .compress_as(ctx, "metallic_roughness")
+                .write(ctx)
                 .await?
                 .into(),
             )
@@ -192,6 +272,7 @@ pub async fn to_mat(
                         .resolve(source_root)
                         .unwrap(),
                 )
+                .compress_as(ctx, "metallic_roughness")
                 .transform("mr_from_s", move |image, _| {
                     for p in image.pixels_mut() {
                         let specular = 1. - (1. - p[1] as f32 / 255.).powf(specular_exponent);
@@ -201,7 +282,7 @@ pub async fn to_mat(
                         p[3] = 255;
                     }
                 })
-                .get(ctx.assets())
+                .write(ctx)
                 .await?
                 .into(),
             )
@@ -216,6 +297,17 @@ pub async fn to_mat(
         double_sided: pipeline.double_sided,
         metallic_factor: pipeline.metallic_factor.unwrap_or(1.0),
         roughness_factor: pipeline.roughness_factor.unwrap_or(1.0),
+        reflectivity: pipeline.reflectivity,
+        wind_sway: pipeline.wind_sway,
+        wind_flutter: pipeline.wind_flutter,
+        wind_height: pipeline.wind_height,
+        transparency_group: pipeline.transparency_group,
+        transparency_sort_bias: pipeline.transparency_sort_bias,
+        clearcoat: None,
+        clearcoat_roughness: None,
+        transmission: None,
+        texcoord_transform: None,
+        imposter: None,
         sampler: pipeline.sampler.map(|sampler| SamplerKey {
             address_mode_u: sampler.address_mode_u,
             address_mode_v: sampler.address_mode_v,
@@ -283,6 +375,8 @@ pub struct PipeImage {
     second_source: Option<AbsAssetUrl>,
     transform: Option<Box<dyn ImageTransformer>>,
     cap_texture_sizes: Option<ModelTextureSize>,
+    compression: Option<TextureCompressionSettings>,
+    srgb: bool,
 }
 
 impl PipeImage {
@@ -295,8 +389,15 @@ impl PipeImage {
             second_source: None,
             transform: None,
             cap_texture_sizes: None,
+            compression: None,
+            srgb: false,
         }
     }
+    /// Sets the image the transform gets as its second image
+    pub fn second_source(mut self, second_source: AbsAssetUrl) -> Self {
+        self.second_source = Some(second_source);
+        self
+    }
     pub fn transform<F: Fn(&mut RgbaImage, Option<&RgbaImage>) + Sync + Send + 'static>(
         mut self,
         transform_name: &'static str,
@@ -309,6 +410,20 @@ impl PipeImage {
         self.cap_texture_sizes = cap_texture_sizes;
         self
     }
+    /// Compresses the image as the map `map` of a material, if the pipeline of `ctx` compresses
+    /// it
+    pub fn compress_as(mut self, ctx: &PipelineCtx, map: &str) -> Self {
+        self.compression = ctx.texture_compression(map, Some(&self.source));
+        self.srgb = is_srgb_map(map);
+        self
+    }
+    /// Writes the image once for all the pipelines using it, and records it as an output of the
+    /// pipeline of `ctx`
+    pub async fn write(self, ctx: &PipelineCtx) -> anyhow::Result<Arc<AbsAssetUrl>> {
+        let url = self.get(ctx.assets()).await?;
+        ctx.record_output(&url);
+        Ok(url)
+    }
 }
 #[async_trait]
 impl AsyncAssetKey<AssetResult<Arc<AbsAssetUrl>>> for PipeImage {
@@ -321,7 +436,6 @@ impl AsyncAssetKey<AssetResult<Arc<AbsAssetUrl>>> for PipeImage {
         .await
         .with_context(|| format!("Failed to download image \"{}\"", self.source))?)
         .clone();
-        let mut extension = "png".to_string();
         let second_image = if let Some(second_source) = &self.second_source {
             Some(
                 ImageFromUrl {
@@ -335,23 +449,34 @@ impl AsyncAssetKey<AssetResult<Arc<AbsAssetUrl>>> for PipeImage {
             None
         };
         let path = ctx.in_root.relative_path(self.source.decoded_path());
-        let mut data = Cursor::new(Vec::new());
-        tokio::task::block_in_place(|| {
+        let data = tokio::task::block_in_place(|| -> anyhow::Result<Vec<u8>> {
             if let Some(transform) = &self.transform {
                 transform.transform(&mut image, second_image.as_deref());
-                extension = format!("{}.png", transform.name());
             }
             if let Some(size) = self.cap_texture_sizes {
                 cap_texture_size(&mut image, size.size());
             }
-            image.write_to(&mut data, ImageOutputFormat::Png).unwrap();
-        });
+            match self.compression {
+                Some(settings) => compress_texture(&image, settings, self.srgb)
+                    .with_context(|| format!("Failed to compress image \"{}\"", self.source)),
+                None => {
+                    let mut data = Cursor::new(Vec::new());
+                    image.write_to(&mut data, ImageOutputFormat::Png).unwrap();
+                    Ok(data.into_inner())
+                }
+            }
+        })?;
+        let format = if self.compression.is_some() {
+            texture_compression::EXTENSION
+        } else {
+            "png"
+        };
+        let extension = match &self.transform {
+            Some(transform) => format!("{}.{format}", transform.name()),
+            None => format.to_string(),
+        };
         Ok(Arc::new(
-            (ctx.write_file)(
-                path.with_extension(extension).to_string(),
-                data.into_inner(),
-            )
-            .await,
+            (ctx.write_file)(path.with_extension(extension).to_string(), data).await,
         ))
     }
 }
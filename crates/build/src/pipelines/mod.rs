@@ -4,7 +4,9 @@ use ambient_asset_cache::SyncAssetKey;
 use ambient_native_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl};
 use ambient_pipeline_types::{models::ModelsPipeline, Pipeline, PipelineProcessor, PipelinesFile};
 use anyhow::Context;
+use cache::BuildCache;
 use context::PipelineCtx;
+use custom::AssetProcessors;
 use futures::{
     future::{ready, BoxFuture},
     stream, Stream, StreamExt, TryStreamExt,
@@ -13,20 +15,50 @@ use image::ImageFormat;
 use out_asset::{OutAsset, OutAssetContent, OutAssetPreview};
 
 pub mod audio;
+pub mod cache;
 pub mod context;
+pub mod custom;
+pub mod environment_maps;
 pub mod importer;
 pub mod materials;
 pub mod models;
+pub mod navmeshes;
 pub mod out_asset;
+pub mod string_tables;
+pub mod texture_atlases;
+pub mod texture_compression;
 
 pub use importer::*;
 
+/// The files a pipeline reads besides the ones in its folder, which its outputs depend on too
+pub fn pipeline_dependencies(
+    pipeline: &Pipeline,
+    ctx: &PipelineCtx,
+) -> anyhow::Result<Vec<AbsAssetUrl>> {
+    match &pipeline.processor {
+        PipelineProcessor::Models(config) => models::dependencies(ctx, config),
+        PipelineProcessor::Materials(config) => materials::dependencies(ctx, config),
+        _ => Ok(vec![]),
+    }
+}
+
 pub async fn process_pipeline(pipeline: &Pipeline, ctx: PipelineCtx) -> Vec<OutAsset> {
     tracing::debug!("Processing pipeline: {:?}", ctx.pipeline_path());
     let mut assets = match &pipeline.processor {
         PipelineProcessor::Models(config) => models::pipeline(&ctx, config.clone()).await,
         PipelineProcessor::Materials(config) => materials::pipeline(&ctx, config.clone()).await,
         PipelineProcessor::Audio(config) => audio::pipeline(&ctx, config.clone()).await,
+        PipelineProcessor::EnvironmentMaps(config) => {
+            environment_maps::pipeline(&ctx, config.clone()).await
+        }
+        PipelineProcessor::TextureAtlases(config) => {
+            texture_atlases::pipeline(&ctx, config.clone()).await
+        }
+        PipelineProcessor::StringTables(config) => {
+            string_tables::pipeline(&ctx, config.clone()).await
+        }
+        PipelineProcessor::Navmeshes(config) => navmeshes::pipeline(&ctx, config.clone()).await,
+        PipelineProcessor::Custom(config) => custom::pipeline(&ctx, config.clone()).await,
     };
 
     for asset in &mut assets {
@@ -67,6 +99,48 @@ fn get_pipelines(
 pub async fn process_pipelines(ctx: &ProcessCtx) -> anyhow::Result<Vec<OutAsset>> {
     tracing::debug!(?ctx.out_root, "Processing pipelines");
 
+    run_pipelines(ctx, get_pipeline_ctxs(ctx).await?).await
+}
+
+/// Processes only the pipelines affected by the `changed` files: the pipelines whose sources or
+/// `pipeline.toml` changed, and, for the files which aren't the sources of any pipeline, like the
+/// textures of a material, all the pipelines that can read them
+pub async fn process_changed_pipelines(
+    ctx: &ProcessCtx,
+    changed: &[AbsAssetUrl],
+) -> anyhow::Result<Vec<OutAsset>> {
+    tracing::debug!(?ctx.out_root, ?changed, "Processing changed pipelines");
+
+    let pipelines = get_pipeline_ctxs(ctx).await?;
+    let sources = pipelines
+        .iter()
+        .map(|pipeline| pipeline.filter_files(|file| changed.contains(file)))
+        .collect::<Vec<_>>();
+    let referenced = changed
+        .iter()
+        .filter(|file| !sources.iter().flatten().any(|source| source == *file))
+        .collect::<Vec<_>>();
+
+    let affected = pipelines
+        .into_iter()
+        .zip(sources)
+        .filter(|(pipeline, sources)| {
+            let pipeline_file = pipeline.pipeline_file.decoded_path();
+            !sources.is_empty()
+                || changed
+                    .iter()
+                    .any(|file| file.decoded_path() == pipeline_file)
+                || referenced
+                    .iter()
+                    .any(|file| pipeline.files.has_input_file(file))
+        })
+        .map(|(pipeline, _)| pipeline)
+        .collect::<Vec<_>>();
+
+    run_pipelines(ctx, affected).await
+}
+
+async fn get_pipeline_ctxs(ctx: &ProcessCtx) -> anyhow::Result<Vec<PipelineCtx>> {
     get_pipelines(ctx)
         .map_ok(|(file, pipelines)| {
             tracing::debug!(
@@ -85,21 +159,42 @@ pub async fn process_pipelines(ctx: &ProcessCtx) -> anyhow::Result<Vec<OutAsset>
         .try_flatten()
         .map_ok(|(pipeline_file, pipeline): (AbsAssetUrl, Pipeline)| {
             let root = pipeline_file.join(".").unwrap();
-            let ctx = PipelineCtx {
+            PipelineCtx {
                 files: ctx.files.sub_directory(root.decoded_path().as_str()),
                 process_ctx: ctx.clone(),
-                pipeline: Arc::new(pipeline.clone()),
+                pipeline: Arc::new(pipeline),
                 pipeline_file,
                 root_path: ctx.in_root.relative_path(root.decoded_path()),
-            };
+                record: Default::default(),
+            }
+        })
+        .try_collect()
+        .await
+}
 
+async fn run_pipelines(
+    ctx: &ProcessCtx,
+    pipelines: Vec<PipelineCtx>,
+) -> anyhow::Result<Vec<OutAsset>> {
+    let cache = BuildCache::new(&ctx.assets).map(Arc::new);
+    stream::iter(pipelines)
+        .map(|ctx| {
+            let cache = cache.clone();
             async move {
-                tokio::spawn(async move { process_pipeline(&pipeline, ctx).await })
-                    .await
-                    .context("Pipeline processing panicked")
+                tokio::spawn(async move {
+                    match cache {
+                        Some(cache) => cache.process(ctx).await,
+                        None => {
+                            let pipeline = ctx.pipeline.clone();
+                            process_pipeline(&pipeline, ctx).await
+                        }
+                    }
+                })
+                .await
+                .context("Pipeline processing panicked")
             }
         })
-        .try_buffered(30)
+        .buffered(30)
         .map_ok(|out_assets| futures::stream::iter(out_assets.into_iter().map(Ok)))
         .try_flatten()
         .try_collect::<Vec<_>>()
@@ -118,6 +213,7 @@ pub struct ProcessCtx {
     pub(crate) package_name: String,
     pub(crate) in_root: AbsAssetUrl,
     pub(crate) out_root: AbsAssetUrl,
+    pub(crate) asset_processors: AssetProcessors,
     pub(crate) write_file:
         Arc<dyn Fn(String, Vec<u8>) -> BoxFuture<'static, AbsAssetUrl> + Sync + Send>,
     pub(crate) on_status: Arc<dyn Fn(String) -> BoxFuture<'static, ()> + Sync + Send>,
@@ -133,6 +229,7 @@ impl std::fmt::Debug for ProcessCtx {
             .field("package_name", &self.package_name)
             .field("in_root", &self.in_root)
             .field("out_root", &self.out_root)
+            .field("asset_processors", &self.asset_processors)
             .finish_non_exhaustive()
     }
 }
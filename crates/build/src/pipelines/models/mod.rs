@@ -1,10 +1,16 @@
 use std::{path::PathBuf, sync::Arc};
 
 use ambient_core::hierarchy::children;
-use ambient_model_import::{apply_model_transform, model_crate::ModelCrate, TextureResolver};
-use ambient_native_std::asset_url::AssetType;
-use ambient_physics::collider::collider_type;
-use ambient_pipeline_types::models::{Collider, ModelImporter, ModelsPipeline};
+use ambient_model_import::{
+    apply_model_transform, lods::generate_lods, model_crate::ModelCrate,
+    retarget::retarget_animations, TextureResolver,
+};
+use ambient_native_std::asset_url::{AbsAssetUrl, AssetType};
+use ambient_physics::{collider::collider_type, convex_decomposition::ConvexDecompositionParams};
+use ambient_pipeline_types::models::{
+    Collider, ModelImporter, ModelRetarget, ModelsPipeline, SkeletonMap,
+};
+use anyhow::Context;
 use futures::FutureExt;
 use relative_path::RelativePath;
 
@@ -12,6 +18,7 @@ use super::{
     context::PipelineCtx,
     download_image,
     out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+    texture_compression::{compress_texture, is_srgb_map},
 };
 use ambient_ecs::Entity;
 
@@ -20,6 +27,9 @@ pub mod regular;
 pub mod unity;
 
 pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset> {
+    if config.retarget.is_some() && !config.importer.is_regular() {
+        tracing::warn!("Retargeting animations is only supported by the regular importer");
+    }
     let mut assets = match &config.importer {
         ModelImporter::Regular => regular::pipeline(ctx, config.clone()).await,
         ModelImporter::UnityModels { use_prefabs } => {
@@ -67,18 +77,46 @@ async fn apply(
         .await?;
         model_crate.override_material(&mat.filter, material);
     }
+    // After the material overrides, so that the imposters are baked with the final materials
+    if let Some(generate_lods) = &pipeline.generate_lods {
+        tokio::task::block_in_place(|| generate_lods(model_crate, generate_lods))
+            .context("Failed to generate the levels of detail")?;
+    }
     if let Some(max_size) = pipeline.cap_texture_sizes {
         model_crate.cap_texture_sizes(max_size.size());
     }
+    if ctx.pipeline.texture_compression.is_some() {
+        tokio::task::block_in_place(|| {
+            model_crate.compress_images(|map, image| {
+                ctx.texture_compression(map, None)
+                    .map(|settings| compress_texture(image, settings, is_srgb_map(map)))
+                    .transpose()
+            })
+        })?;
+    }
     model_crate.finalize_model();
     match pipeline.collider {
         Collider::None => {}
         Collider::FromModel {
             flip_normals,
             reverse_indices,
+            max_convex_hulls,
         } => {
+            // Only dynamic colliders use the convex version, so only decompose those
+            let convex_decomposition = (pipeline.collider_type
+                == ambient_pipeline_types::models::ColliderType::Dynamic)
+                .then(|| ConvexDecompositionParams {
+                    max_hulls: max_convex_hulls
+                        .unwrap_or(ConvexDecompositionParams::default().max_hulls),
+                    ..Default::default()
+                });
             model_crate
-                .create_collider_from_model(&ctx.process_ctx.assets, flip_normals, reverse_indices)
+                .create_collider_from_model(
+                    &ctx.process_ctx.assets,
+                    flip_normals,
+                    reverse_indices,
+                    convex_decomposition,
+                )
                 .unwrap();
         }
         Collider::Character { radius, height } => {
@@ -111,6 +149,62 @@ async fn apply(
     Ok(())
 }
 
+/// The files read by the pipeline besides the ones in its folder: the images of its material
+/// overrides, and the model and skeleton map of its `retarget`
+pub fn dependencies(
+    ctx: &PipelineCtx,
+    config: &ModelsPipeline,
+) -> anyhow::Result<Vec<AbsAssetUrl>> {
+    let in_root = ctx.in_root();
+    let mut urls = Vec::new();
+    for mat in &config.material_overrides {
+        urls.extend(super::materials::texture_dependencies(
+            &mat.material,
+            &in_root,
+        )?);
+    }
+    if let Some(retarget) = &config.retarget {
+        urls.push(in_root.resolve(&retarget.model)?);
+        if let Some(path) = &retarget.skeleton_map {
+            urls.push(in_root.resolve(path)?);
+        }
+    }
+    Ok(urls)
+}
+
+/// Retargets the animations of `model_crate` onto the skeleton of the model of `retarget`
+async fn retarget(
+    ctx: &PipelineCtx,
+    retarget: &ModelRetarget,
+    model_crate: &mut ModelCrate,
+) -> anyhow::Result<()> {
+    let in_root = ctx.in_root();
+    let target_url = in_root.resolve(&retarget.model)?;
+    let mut target = ModelCrate::new();
+    target
+        .import(
+            ctx.assets(),
+            &target_url,
+            true,
+            false,
+            // Only the skeleton of the model is used
+            Arc::new(|_| async { None }.boxed()),
+        )
+        .await
+        .with_context(|| format!("Failed to import model \"{target_url}\""))?;
+    let map = match &retarget.skeleton_map {
+        Some(path) => {
+            let url = in_root.resolve(path)?;
+            url.download_toml::<SkeletonMap>(ctx.assets())
+                .await
+                .with_context(|| format!("Failed to read skeleton map \"{url}\""))?
+        }
+        None => SkeletonMap::default(),
+    };
+    retarget_animations(model_crate, &mut target, &map)
+        .with_context(|| format!("Failed to retarget the animations onto \"{target_url}\""))
+}
+
 fn create_texture_resolver(ctx: &PipelineCtx) -> TextureResolver {
     let ctx = ctx.clone();
     Arc::new(move |path| {
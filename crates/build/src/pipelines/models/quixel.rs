@@ -154,7 +154,7 @@ pub async fn object_pipelines_from_quixel_json(
             Ok(AssetUrl::from(
                 PipeImage::new(file)
                     .cap_texture_size(config.cap_texture_sizes)
-                    .get(ctx.assets())
+                    .write(&ctx)
                     .await?,
             ))
         }
@@ -337,7 +337,7 @@ pub async fn object_pipelines_from_quixel_json(
                                 )
                                 .transform("mr", |img, _| rougness_to_mr(img))
                                 .cap_texture_size(config.cap_texture_sizes)
-                                .get(ctx.assets())
+                                .write(ctx)
                                 .await?,
                             )))
                         } else {
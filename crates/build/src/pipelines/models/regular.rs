@@ -8,7 +8,7 @@ use super::{
         out_asset::{OutAssetContent, OutAssetPreview},
         ModelsPipeline,
     },
-    apply, create_texture_resolver,
+    apply, create_texture_resolver, retarget,
 };
 use crate::pipelines::{out_asset::asset_id_from_url, OutAsset};
 
@@ -35,6 +35,18 @@ pub async fn pipeline(ctx: &PipelineCtx, config: ModelsPipeline) -> Vec<OutAsset
                     )
                     .await
                     .with_context(|| format!("Failed to import model \"{file}\""))?;
+                if let Some(target) = &config.retarget {
+                    retarget(&ctx, target, &mut model_crate).await?;
+                }
+                if let Some(lods) = &config.lods {
+                    let levels = model_crate.authored_mesh_lods();
+                    if levels.len() > 1 {
+                        model_crate =
+                            model_crate.merge_authored_mesh_lods(levels, lods.screen_sizes.clone());
+                    } else {
+                        tracing::warn!("Model \"{file}\" has no levels of detail to merge");
+                    }
+                }
                 model_crate
                     .model_mut()
                     .set_name(file.decoded_path().file_name().unwrap());
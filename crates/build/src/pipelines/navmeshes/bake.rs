@@ -0,0 +1,429 @@
+//! Bakes navmeshes the way Recast does: the triangles of the level are voxelized into columns of
+//! solid spans, the tops of the spans an agent fits on become walkable cells, which are eroded by
+//! the radius of the agent, and the walkable cells are merged into rectangles, which become the
+//! polygons of the navmesh.
+use std::collections::HashMap;
+
+use ambient_pipeline_types::navmeshes::NavmeshSettings;
+use ambient_shared_types::navmesh::{Navmesh, NavmeshPolygon};
+use glam::{vec3, Vec2, Vec3, Vec3Swizzles};
+
+/// The offsets to the neighbouring cells: east, north, west and south
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const EAST: usize = 0;
+const NORTH: usize = 1;
+const WEST: usize = 2;
+const SOUTH: usize = 3;
+
+/// Bakes a navmesh over `triangles`, which are counter-clockwise when seen from their front
+pub fn bake(triangles: &[[Vec3; 3]], settings: NavmeshSettings) -> Navmesh {
+    let Some(heightfield) = Heightfield::rasterize(triangles, &settings) else {
+        return Navmesh::default();
+    };
+    let mut cells = WalkableCells::new(&heightfield, &settings);
+    cells.erode(settings.agent_radius, settings.cell_size);
+    cells.to_navmesh(&heightfield, settings.max_climb)
+}
+
+/// A solid part of a column of the heightfield
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    min: f32,
+    max: f32,
+    /// Whether the top of the span is a walkable surface
+    walkable: bool,
+}
+
+/// The level voxelized into columns of spans, on a grid of square cells
+struct Heightfield {
+    origin: Vec2,
+    cell_size: f32,
+    width: i32,
+    height: i32,
+    /// The spans of each column, from the lowest
+    columns: Vec<Vec<Span>>,
+}
+impl Heightfield {
+    fn rasterize(triangles: &[[Vec3; 3]], settings: &NavmeshSettings) -> Option<Self> {
+        let min = triangles.iter().flatten().copied().reduce(Vec3::min)?;
+        let max = triangles.iter().flatten().copied().reduce(Vec3::max)?;
+        let cell_size = settings.cell_size;
+        let width = ((max.x - min.x) / cell_size).ceil().max(1.) as i32;
+        let height = ((max.y - min.y) / cell_size).ceil().max(1.) as i32;
+        let mut heightfield = Self {
+            origin: min.xy(),
+            cell_size,
+            width,
+            height,
+            columns: vec![Vec::new(); (width * height) as usize],
+        };
+
+        let min_normal_z = settings.max_slope.to_radians().cos();
+        for &[a, b, c] in triangles {
+            let normal = (b - a).cross(c - a);
+            if normal.length_squared() == 0. {
+                continue;
+            }
+            let walkable = normal.normalize().z >= min_normal_z;
+
+            let ((x0, y0), (x1, y1)) =
+                heightfield.cells_in(a.min(b).min(c).xy(), a.max(b).max(c).xy());
+            for y in y0..=y1 {
+                let row_min = heightfield.origin.y + y as f32 * cell_size;
+                let row = clip(&[a, b, c], 1, row_min, true);
+                let row = clip(&row, 1, row_min + cell_size, false);
+                if row.is_empty() {
+                    continue;
+                }
+                for x in x0..=x1 {
+                    let column_min = heightfield.origin.x + x as f32 * cell_size;
+                    let polygon = clip(&row, 0, column_min, true);
+                    let polygon = clip(&polygon, 0, column_min + cell_size, false);
+                    let Some(min) = polygon.iter().map(|p| p.z).reduce(f32::min) else {
+                        continue;
+                    };
+                    let max = polygon.iter().map(|p| p.z).fold(min, f32::max);
+                    let index = heightfield.index(x, y);
+                    add_span(
+                        &mut heightfield.columns[index],
+                        Span { min, max, walkable },
+                        settings.max_climb,
+                    );
+                }
+            }
+        }
+        Some(heightfield)
+    }
+
+    /// The first and last cells covered by the bounds from `min` to `max`
+    fn cells_in(&self, min: Vec2, max: Vec2) -> ((i32, i32), (i32, i32)) {
+        let first = ((min - self.origin) / self.cell_size).floor();
+        // Bounds which end on the edge of a cell don't cover it
+        let last = (((max - self.origin) / self.cell_size).ceil() - Vec2::ONE).max(first);
+        let x = |x: f32| (x as i32).clamp(0, self.width - 1);
+        let y = |y: f32| (y as i32).clamp(0, self.height - 1);
+        ((x(first.x), y(first.y)), (x(last.x), y(last.y)))
+    }
+    fn index(&self, x: i32, y: i32) -> usize {
+        (y * self.width + x) as usize
+    }
+    fn contains(&self, x: i32, y: i32) -> bool {
+        (0..self.width).contains(&x) && (0..self.height).contains(&y)
+    }
+}
+
+/// Adds `span` to `column`, merging it with the spans it overlaps. The merged span is walkable
+/// if its top is, or if the tops of both spans are within `max_climb` and either is walkable.
+fn add_span(column: &mut Vec<Span>, mut span: Span, max_climb: f32) {
+    let mut i = 0;
+    while i < column.len() {
+        let other = column[i];
+        if other.min > span.max {
+            break;
+        }
+        if other.max < span.min {
+            i += 1;
+            continue;
+        }
+        if (other.max - span.max).abs() <= max_climb {
+            span.walkable |= other.walkable;
+        } else if other.max > span.max {
+            span.walkable = other.walkable;
+        }
+        span.min = span.min.min(other.min);
+        span.max = span.max.max(other.max);
+        column.remove(i);
+    }
+    column.insert(i, span);
+}
+
+/// Clips `polygon` to the side of the plane `axis = value` where the values are greater, or
+/// smaller
+fn clip(polygon: &[Vec3], axis: usize, value: f32, keep_greater: bool) -> Vec<Vec3> {
+    let distance = |point: Vec3| {
+        if keep_greater {
+            point[axis] - value
+        } else {
+            value - point[axis]
+        }
+    };
+    let mut clipped = Vec::new();
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (da, db) = (distance(a), distance(b));
+        if da >= 0. {
+            clipped.push(a);
+        }
+        if (da >= 0.) != (db >= 0.) {
+            clipped.push(a + (b - a) * (da / (da - db)));
+        }
+    }
+    clipped
+}
+
+/// The top of a span an agent fits on
+#[derive(Debug, Clone)]
+struct WalkableCell {
+    x: i32,
+    y: i32,
+    top: f32,
+    /// The bottom of the span above, if any
+    ceiling: f32,
+    /// The cell the agent can walk to in each of the [DIRECTIONS]
+    links: [Option<u32>; 4],
+}
+
+struct WalkableCells {
+    cells: Vec<WalkableCell>,
+    /// The walkable cells of each column of the heightfield
+    columns: Vec<Vec<u32>>,
+}
+impl WalkableCells {
+    fn new(heightfield: &Heightfield, settings: &NavmeshSettings) -> Self {
+        let mut cells = Vec::new();
+        let mut columns = Vec::new();
+        for y in 0..heightfield.height {
+            for x in 0..heightfield.width {
+                let spans = &heightfield.columns[heightfield.index(x, y)];
+                let mut column = Vec::new();
+                for (i, span) in spans.iter().enumerate() {
+                    let ceiling = spans.get(i + 1).map_or(f32::INFINITY, |above| above.min);
+                    if span.walkable && ceiling - span.max >= settings.agent_height {
+                        column.push(cells.len() as u32);
+                        cells.push(WalkableCell {
+                            x,
+                            y,
+                            top: span.max,
+                            ceiling,
+                            links: [None; 4],
+                        });
+                    }
+                }
+                columns.push(column);
+            }
+        }
+        let mut walkable = Self { cells, columns };
+
+        // The agent can walk to a neighbouring cell if it can climb to it, and fits between the
+        // highest of their tops and the lowest of their ceilings
+        let can_walk = |a: &WalkableCell, b: &WalkableCell| {
+            (a.top - b.top).abs() <= settings.max_climb
+                && a.ceiling.min(b.ceiling) - a.top.max(b.top) >= settings.agent_height
+        };
+        let links = walkable
+            .cells
+            .iter()
+            .map(|cell| {
+                DIRECTIONS.map(|(dx, dy)| {
+                    let (x, y) = (cell.x + dx, cell.y + dy);
+                    if !heightfield.contains(x, y) {
+                        return None;
+                    }
+                    walkable.columns[heightfield.index(x, y)]
+                        .iter()
+                        .copied()
+                        .filter(|&other| can_walk(cell, &walkable.cells[other as usize]))
+                        .min_by(|&a, &b| {
+                            let climb =
+                                |other: u32| (walkable.cells[other as usize].top - cell.top).abs();
+                            climb(a).total_cmp(&climb(b))
+                        })
+                })
+            })
+            .collect::<Vec<_>>();
+        // Keep the links which go both ways, so that the polygons agree on their neighbours
+        for (index, cell) in walkable.cells.iter_mut().enumerate() {
+            for (direction, link) in cell.links.iter_mut().enumerate() {
+                *link = links[index][direction].filter(|&other| {
+                    links[other as usize][(direction + 2) % 4] == Some(index as u32)
+                });
+            }
+        }
+        walkable
+    }
+
+    /// Removes the cells closer than `radius` to the edges of the walkable area. Like Recast,
+    /// the distances to the edges are chamfer distances in half cells: 2 to the next cell, and 3
+    /// to the diagonal one.
+    fn erode(&mut self, radius: f32, cell_size: f32) {
+        let mut distances = self
+            .cells
+            .iter()
+            .map(|cell| {
+                if cell.links.contains(&None) {
+                    0
+                } else {
+                    u32::MAX
+                }
+            })
+            .collect::<Vec<_>>();
+        let cells = &self.cells;
+        let neighbours = |index: usize| {
+            let links = cells[index].links;
+            (0..4).flat_map(move |direction| {
+                let next = links[direction];
+                let diagonal =
+                    next.and_then(|next| cells[next as usize].links[(direction + 1) % 4]);
+                [
+                    next.map(|next| (next, 2)),
+                    diagonal.map(|diagonal| (diagonal, 3)),
+                ]
+                .into_iter()
+                .flatten()
+            })
+        };
+        // Sweeps over the cells forwards and backwards, until the distances stop changing
+        let mut changed = true;
+        let mut forward = true;
+        while changed {
+            changed = false;
+            for i in 0..cells.len() {
+                let index = if forward { i } else { cells.len() - 1 - i };
+                for (other, weight) in neighbours(index) {
+                    let distance = distances[other as usize].saturating_add(weight);
+                    if distance < distances[index] {
+                        distances[index] = distance;
+                        changed = true;
+                    }
+                }
+            }
+            forward = !forward;
+        }
+
+        // The center of a cell at the edge is half a cell from it
+        let removed = distances
+            .iter()
+            .map(|&distance| (distance + 1) as f32 * cell_size / 2. < radius)
+            .collect::<Vec<_>>();
+        for cell in &mut self.cells {
+            for link in &mut cell.links {
+                if link.map_or(false, |other| removed[other as usize]) {
+                    *link = None;
+                }
+            }
+        }
+        for column in &mut self.columns {
+            column.retain(|&cell| !removed[cell as usize]);
+        }
+    }
+
+    /// Merges the cells into rectangles, and turns them into the polygons of the navmesh
+    fn to_navmesh(&self, heightfield: &Heightfield, max_climb: f32) -> Navmesh {
+        let rectangles = self.rectangles(max_climb);
+        let mut rectangle_of = vec![None; self.cells.len()];
+        for (index, rows) in rectangles.iter().enumerate() {
+            for &cell in rows.iter().flatten() {
+                rectangle_of[cell as usize] = Some(index as u32);
+            }
+        }
+
+        let mut navmesh = Navmesh::default();
+        let mut vertices = HashMap::new();
+        for rows in &rectangles {
+            let (width, height) = (rows[0].len(), rows.len());
+            let origin = &self.cells[rows[0][0] as usize];
+            let (x, y) = (origin.x, origin.y);
+
+            // The edges of the cells around the rectangle, counter-clockwise from its south-west
+            // corner, as the corner they start at, the cell they belong to and the direction
+            // they face
+            let mut edges = Vec::new();
+            for (i, &cell) in rows[0].iter().enumerate() {
+                edges.push(((x + i as i32, y), cell, SOUTH));
+            }
+            for (j, row) in rows.iter().enumerate() {
+                edges.push(((x + width as i32, y + j as i32), row[width - 1], EAST));
+            }
+            for (i, &cell) in rows[height - 1].iter().enumerate().rev() {
+                edges.push(((x + i as i32 + 1, y + height as i32), cell, NORTH));
+            }
+            for (j, row) in rows.iter().enumerate().rev() {
+                edges.push(((x, y + j as i32 + 1), row[0], WEST));
+            }
+
+            // The edges of the polygon are the runs of cell edges with the same neighbour, so
+            // each of them is shared with a single polygon
+            let neighbour = |(_, cell, direction): (_, u32, usize)| {
+                self.cells[cell as usize].links[direction]
+                    .and_then(|other| rectangle_of[other as usize])
+            };
+            let corners = [0, width, width + height, 2 * width + height];
+            let mut polygon = NavmeshPolygon::default();
+            for (i, &edge) in edges.iter().enumerate() {
+                if !corners.contains(&i) && neighbour(edge) == neighbour(edges[i - 1]) {
+                    continue;
+                }
+                let ((corner_x, corner_y), cell, _) = edge;
+                let top = self.cells[cell as usize].top;
+                let vertex = *vertices
+                    .entry((corner_x, corner_y, top.to_bits()))
+                    .or_insert_with(|| {
+                        let position = heightfield.origin
+                            + Vec2::new(corner_x as f32, corner_y as f32) * heightfield.cell_size;
+                        navmesh.vertices.push(vec3(position.x, position.y, top));
+                        navmesh.vertices.len() as u32 - 1
+                    });
+                polygon.vertices.push(vertex);
+                polygon.neighbours.push(neighbour(edge));
+            }
+            navmesh.polygons.push(polygon);
+        }
+        navmesh
+    }
+
+    /// Greedily merges the cells into rectangles, as rows of cells from the south, whose tops are
+    /// within `max_climb` of each other
+    fn rectangles(&self, max_climb: f32) -> Vec<Vec<Vec<u32>>> {
+        let mut assigned = vec![false; self.cells.len()];
+        let mut rectangles = Vec::new();
+        for &start in self.columns.iter().flatten() {
+            if assigned[start as usize] {
+                continue;
+            }
+            let top = |cell: u32| self.cells[cell as usize].top;
+            let (mut min, mut max) = (top(start), top(start));
+
+            let mut row = vec![start];
+            while let Some(next) = self.cells[*row.last().unwrap() as usize].links[EAST] {
+                if assigned[next as usize] || max.max(top(next)) - min.min(top(next)) > max_climb {
+                    break;
+                }
+                min = min.min(top(next));
+                max = max.max(top(next));
+                row.push(next);
+            }
+
+            let mut rows = vec![row];
+            loop {
+                let Some(row) = rows
+                    .last()
+                    .unwrap()
+                    .iter()
+                    .map(|&cell| {
+                        self.cells[cell as usize].links[NORTH]
+                            .filter(|&next| !assigned[next as usize])
+                    })
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    break;
+                };
+                let is_connected = row
+                    .windows(2)
+                    .all(|pair| self.cells[pair[0] as usize].links[EAST] == Some(pair[1]));
+                let row_min = row.iter().map(|&cell| top(cell)).fold(min, f32::min);
+                let row_max = row.iter().map(|&cell| top(cell)).fold(max, f32::max);
+                if !is_connected || row_max - row_min > max_climb {
+                    break;
+                }
+                (min, max) = (row_min, row_max);
+                rows.push(row);
+            }
+
+            for &cell in rows.iter().flatten() {
+                assigned[cell as usize] = true;
+            }
+            rectangles.push(rows);
+        }
+        rectangles
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use ambient_model_import::{model_crate::ModelCrate, MODEL_EXTENSIONS};
+use ambient_native_std::asset_url::AssetType;
+use ambient_pipeline_types::NavmeshesPipeline;
+use ambient_shared_types::navmesh::Navmesh;
+use anyhow::Context;
+use futures::FutureExt;
+use tracing::{info_span, Instrument};
+
+use super::{
+    context::PipelineCtx,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+pub mod bake;
+
+pub async fn pipeline(ctx: &PipelineCtx, config: NavmeshesPipeline) -> Vec<OutAsset> {
+    let files = ctx.filter_files(|file| {
+        MODEL_EXTENSIONS
+            .iter()
+            .any(|x| x == &file.extension().unwrap_or_default().to_lowercase())
+    });
+    ctx.process_single(move |ctx| async move {
+        let name = config.name.clone().unwrap_or_else(|| "navmesh".to_string());
+
+        // All the models are baked together, as the parts of a single level
+        let mut triangles = Vec::new();
+        for file in &files {
+            let mut model_crate = ModelCrate::new();
+            model_crate
+                .import(
+                    ctx.assets(),
+                    file,
+                    true,
+                    false,
+                    // Only the geometry of the models is baked
+                    Arc::new(|_| async { None }.boxed()),
+                )
+                .await
+                .with_context(|| format!("Failed to import model \"{file}\""))?;
+            triangles.extend(model_crate.world_triangles());
+        }
+
+        let settings = config.settings();
+        let navmesh = tokio::task::spawn_blocking(move || bake::bake(&triangles, settings))
+            .await
+            .context("Navmesh baking panicked")?;
+        if navmesh.polygons.is_empty() {
+            tracing::warn!("The navmesh {name} has no surfaces an agent can walk on");
+        }
+
+        let url = ctx
+            .write_file(format!("{name}.{}", Navmesh::EXTENSION), navmesh.to_bytes())
+            .await;
+
+        Ok(vec![OutAsset {
+            id: asset_id_from_url(&url),
+            type_: AssetType::Navmesh,
+            hidden: false,
+            name,
+            tags: Vec::new(),
+            categories: Default::default(),
+            preview: OutAssetPreview::None,
+            content: OutAssetContent::Content(url),
+            source: None,
+        }])
+    })
+    .instrument(info_span!("navmeshes_pipeline"))
+    .await
+}
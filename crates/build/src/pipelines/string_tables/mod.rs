@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use ambient_native_std::asset_url::AssetType;
+use ambient_pipeline_types::StringTablesPipeline;
+use ambient_shared_types::localization::{
+    normalize_locale, PluralCategory, StringTables, DEFAULT_LOCALE,
+};
+use anyhow::Context;
+use tracing::{info_span, Instrument};
+
+use super::{
+    context::PipelineCtx,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+pub async fn pipeline(ctx: &PipelineCtx, config: StringTablesPipeline) -> Vec<OutAsset> {
+    let files = ctx.filter_files(|file| {
+        file.extension().as_deref() == Some("toml")
+            && !file
+                .decoded_path()
+                .file_name()
+                .unwrap_or_default()
+                .ends_with("pipeline.toml")
+    });
+    ctx.process_single(move |ctx| async move {
+        let name = config.name.clone().unwrap_or_else(|| "locales".to_string());
+
+        let mut tables = StringTables::default();
+        for file in &files {
+            let locale = normalize_locale(
+                file.file_stem()
+                    .with_context(|| format!("Invalid string table path {file}"))?,
+            );
+            let table = file
+                .download_toml::<toml::Table>(ctx.assets())
+                .await
+                .with_context(|| format!("Invalid string table {file}"))?;
+            let mut strings = BTreeMap::new();
+            flatten(&mut strings, "", &table)
+                .with_context(|| format!("Invalid string table {file}"))?;
+            if tables.locales.insert(locale.clone(), strings).is_some() {
+                anyhow::bail!("The string tables {name} have more than one table for {locale}");
+            }
+        }
+        if !tables.locales.contains_key(DEFAULT_LOCALE) {
+            tracing::warn!(
+                "The string tables {name} have no {DEFAULT_LOCALE} table, which is used for the strings other locales don't have"
+            );
+        }
+
+        let url = ctx
+            .write_file(
+                format!("{name}.strings.json"),
+                serde_json::to_vec_pretty(&tables)?,
+            )
+            .await;
+
+        Ok(vec![OutAsset {
+            id: asset_id_from_url(&url),
+            type_: AssetType::StringTables,
+            hidden: false,
+            name,
+            tags: Vec::new(),
+            categories: Default::default(),
+            preview: OutAssetPreview::None,
+            content: OutAssetContent::Content(url),
+            source: None,
+        }])
+    })
+    .instrument(info_span!("string_tables_pipeline"))
+    .await
+}
+
+/// Adds the strings of `table` to `strings`, with the keys of nested tables joined with dots, so
+/// the plural forms of `items`, like `items = { one = "...", other = "..." }`, become `items.one`
+/// and `items.other`.
+fn flatten(
+    strings: &mut BTreeMap<String, String>,
+    prefix: &str,
+    table: &toml::Table,
+) -> anyhow::Result<()> {
+    let is_plural = !prefix.is_empty()
+        && table.keys().all(|key| {
+            PluralCategory::ALL
+                .iter()
+                .any(|category| category.as_str() == key)
+        });
+    if is_plural && !table.contains_key(PluralCategory::Other.as_str()) {
+        anyhow::bail!("The plural forms of {prefix} have no `other` form");
+    }
+
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            toml::Value::String(value) => {
+                strings.insert(key, value.clone());
+            }
+            toml::Value::Table(table) => flatten(strings, &key, table)?,
+            _ => anyhow::bail!("The value of {key} isn't a string or a table"),
+        }
+    }
+    Ok(())
+}
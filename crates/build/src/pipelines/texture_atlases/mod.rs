@@ -0,0 +1,251 @@
+use std::{collections::BTreeMap, io::Cursor};
+
+use ambient_native_std::asset_url::AssetType;
+use ambient_pipeline_types::TextureAtlasesPipeline;
+use ambient_renderer::texture_atlas::{TextureAtlasDesc, TextureAtlasEntry, TextureAtlasPage};
+use anyhow::Context;
+use glam::{uvec2, vec4, UVec2};
+use image::{ImageOutputFormat, RgbaImage};
+use tracing::{info_span, Instrument};
+
+use super::{
+    context::PipelineCtx,
+    download_image,
+    out_asset::{asset_id_from_url, OutAsset, OutAssetContent, OutAssetPreview},
+};
+
+pub async fn pipeline(ctx: &PipelineCtx, config: TextureAtlasesPipeline) -> Vec<OutAsset> {
+    let files = ctx.filter_files(|file| {
+        matches!(
+            file.extension().as_deref(),
+            Some("png") | Some("jpg") | Some("jpeg") | Some("tga") | Some("bmp")
+        )
+    });
+    ctx.process_single(move |ctx| async move {
+        let name = config.name.clone().unwrap_or_else(|| "atlas".to_string());
+        let padding = config.padding.unwrap_or(2);
+        let max_size = config.max_size.unwrap_or(4096);
+
+        let mut images = BTreeMap::new();
+        for file in &files {
+            let image_name = file
+                .file_stem()
+                .with_context(|| format!("Invalid image path {file}"))?
+                .to_string();
+            let image = download_image(ctx.assets(), file).await?.into_rgba8();
+            if images.insert(image_name.clone(), image).is_some() {
+                anyhow::bail!("The atlas {name} has more than one image named {image_name}");
+            }
+        }
+        for image_name in config.slices.keys() {
+            if !images.contains_key(image_name) {
+                tracing::warn!(
+                    "The atlas {name} has a slice for {image_name}, which isn't one of its images"
+                );
+            }
+        }
+
+        let pages = tokio::task::spawn_blocking({
+            let name = name.clone();
+            move || {
+                let images = images.into_iter().collect::<Vec<_>>();
+                let sizes = images
+                    .iter()
+                    .map(|(_, image)| uvec2(image.width(), image.height()))
+                    .collect::<Vec<_>>();
+                let pages = pack_pages(&sizes, padding, max_size).with_context(|| {
+                    format!(
+                        "An image of the atlas {name} doesn't fit in {max_size}x{max_size} pixels"
+                    )
+                })?;
+                let pages = pages
+                    .into_iter()
+                    .map(|(size, placed)| {
+                        let mut atlas = RgbaImage::new(size.x, size.y);
+                        let mut regions = Vec::new();
+                        for (i, position) in placed {
+                            let (image_name, image) = &images[i];
+                            blit_extruded(&mut atlas, image, position, padding);
+                            regions.push((
+                                image_name.clone(),
+                                position,
+                                uvec2(image.width(), image.height()),
+                            ));
+                        }
+                        (atlas, regions)
+                    })
+                    .collect::<Vec<_>>();
+                anyhow::Ok(pages)
+            }
+        })
+        .await
+        .context("Texture atlas packing panicked")??;
+        if pages.len() > 1 {
+            tracing::info!(
+                "The images of the atlas {name} were spread over {} pages",
+                pages.len()
+            );
+        }
+
+        let mut desc = TextureAtlasDesc {
+            pages: Vec::new(),
+            entries: BTreeMap::new(),
+        };
+        for (page, (atlas, regions)) in pages.into_iter().enumerate() {
+            let mut data = Cursor::new(Vec::new());
+            atlas.write_to(&mut data, ImageOutputFormat::Png)?;
+            let image_path = if page == 0 {
+                format!("{name}.atlas.png")
+            } else {
+                format!("{name}.atlas.{page}.png")
+            };
+            ctx.write_file(&image_path, data.into_inner()).await;
+
+            let (width, height) = (atlas.width() as f32, atlas.height() as f32);
+            for (image_name, position, size) in regions {
+                let region = vec4(
+                    position.x as f32,
+                    position.y as f32,
+                    size.x as f32,
+                    size.y as f32,
+                );
+                let entry = TextureAtlasEntry {
+                    page,
+                    region,
+                    uv: region / vec4(width, height, width, height),
+                    slice: config.slices.get(&image_name).copied(),
+                };
+                desc.entries.insert(image_name, entry);
+            }
+            desc.pages.push(TextureAtlasPage {
+                image: image_path,
+                width: atlas.width(),
+                height: atlas.height(),
+            });
+        }
+        let desc_url = ctx
+            .write_file(
+                format!("{name}.atlas.json"),
+                serde_json::to_vec_pretty(&desc)?,
+            )
+            .await;
+
+        Ok(vec![OutAsset {
+            id: asset_id_from_url(&desc_url),
+            type_: AssetType::TextureAtlas,
+            hidden: false,
+            name,
+            tags: Vec::new(),
+            categories: Default::default(),
+            preview: OutAssetPreview::None,
+            content: OutAssetContent::Content(desc_url),
+            source: None,
+        }])
+    })
+    .instrument(info_span!("texture_atlases_pipeline"))
+    .await
+}
+
+/// Packs rectangles of `sizes` into as few pages as possible. If they don't all fit in one page, the
+/// pages are filled with shelves, tallest first, up to `max_size`, and the rectangles left over go
+/// to the next pages. Returns the size of each page along with the rectangles on it and their top
+/// left corners, or None if a rectangle is larger than a page.
+fn pack_pages(
+    sizes: &[UVec2],
+    padding: u32,
+    max_size: u32,
+) -> Option<Vec<(UVec2, Vec<(usize, UVec2)>)>> {
+    if sizes
+        .iter()
+        .any(|size| (*size + 2 * padding).max_element() > max_size)
+    {
+        return None;
+    }
+
+    let mut remaining = (0..sizes.len()).collect::<Vec<_>>();
+    remaining.sort_by_key(|&i| std::cmp::Reverse(sizes[i].y));
+    let mut pages = Vec::new();
+    loop {
+        let remaining_sizes = remaining.iter().map(|&i| sizes[i]).collect::<Vec<_>>();
+        if let Some((size, positions)) = pack(&remaining_sizes, padding, max_size) {
+            pages.push((size, remaining.into_iter().zip(positions).collect()));
+            return Some(pages);
+        }
+
+        let mut placed = Vec::new();
+        let mut left_over = Vec::new();
+        let mut cursor = UVec2::ZERO;
+        let mut shelf_height = 0;
+        for i in remaining {
+            let padded = sizes[i] + 2 * padding;
+            if cursor.x + padded.x > max_size {
+                // The shelf is at least as tall as the rectangle, as the tallest come first
+                if cursor.y + shelf_height + padded.y > max_size {
+                    left_over.push(i);
+                    continue;
+                }
+                cursor = uvec2(0, cursor.y + shelf_height);
+                shelf_height = 0;
+            }
+            placed.push((i, cursor + padding));
+            cursor.x += padded.x;
+            shelf_height = shelf_height.max(padded.y);
+        }
+        pages.push((uvec2(max_size, cursor.y + shelf_height), placed));
+        remaining = left_over;
+    }
+}
+
+/// Packs rectangles of `sizes` into shelves, tallest first, with `padding` pixels around each of
+/// them. Tries power of two widths up to `max_size`, and returns the size of the first atlas which
+/// is no taller than it is wide, along with the top left corner of each rectangle.
+fn pack(sizes: &[UVec2], padding: u32, max_size: u32) -> Option<(UVec2, Vec<UVec2>)> {
+    let mut order = (0..sizes.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| std::cmp::Reverse(sizes[i].y));
+
+    let mut width = 16;
+    while width <= max_size {
+        let mut positions = vec![UVec2::ZERO; sizes.len()];
+        let mut cursor = UVec2::ZERO;
+        let mut shelf_height = 0;
+        let mut fits = true;
+        for &i in &order {
+            let padded = sizes[i] + 2 * padding;
+            if padded.x > width {
+                fits = false;
+                break;
+            }
+            if cursor.x + padded.x > width {
+                cursor = uvec2(0, cursor.y + shelf_height);
+                shelf_height = 0;
+            }
+            positions[i] = cursor + padding;
+            cursor.x += padded.x;
+            shelf_height = shelf_height.max(padded.y);
+        }
+        let height = (cursor.y + shelf_height).max(1);
+        if fits && height <= width {
+            return Some((uvec2(width, height), positions));
+        }
+        width *= 2;
+    }
+    None
+}
+
+/// Copies `image` into `atlas` at `position`, and fills the `padding` around it with its edges.
+fn blit_extruded(atlas: &mut RgbaImage, image: &RgbaImage, position: UVec2, padding: u32) {
+    let padding = padding as i32;
+    for y in -padding..image.height() as i32 + padding {
+        for x in -padding..image.width() as i32 + padding {
+            let pixel = *image.get_pixel(
+                x.clamp(0, image.width() as i32 - 1) as u32,
+                y.clamp(0, image.height() as i32 - 1) as u32,
+            );
+            atlas.put_pixel(
+                (position.x as i32 + x) as u32,
+                (position.y as i32 + y) as u32,
+                pixel,
+            );
+        }
+    }
+}
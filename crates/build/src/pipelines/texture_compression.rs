@@ -0,0 +1,83 @@
+//! Compresses the textures of materials to the Basis Universal format, which the runtime
+//! transcodes to a compressed format the GPU supports.
+use std::borrow::Cow;
+
+use ambient_pipeline_types::textures::{TextureCodec, TextureCompressionSettings};
+use anyhow::Context;
+use basis_universal::{BasisTextureFormat, ColorSpace, Compressor, CompressorParams};
+use image::RgbaImage;
+
+/// The extension of the compressed textures
+pub const EXTENSION: &str = "basis";
+
+/// Whether the map `map` of a material holds colors, which are in the sRGB color space
+pub fn is_srgb_map(map: &str) -> bool {
+    map == "base_color"
+}
+
+/// Compresses `image`, with its mips, to a Basis Universal file
+pub fn compress_texture(
+    image: &RgbaImage,
+    settings: TextureCompressionSettings,
+    srgb: bool,
+) -> anyhow::Result<Vec<u8>> {
+    // The compressed formats are made of blocks of 4x4 pixels, so the GPU can only create
+    // textures of them whose sizes are multiples of 4
+    let width = (image.width().max(1) + 3) / 4 * 4;
+    let height = (image.height().max(1) + 3) / 4 * 4;
+    let image = if image.dimensions() == (width, height) {
+        Cow::Borrowed(image)
+    } else {
+        Cow::Owned(image::imageops::resize(
+            image,
+            width,
+            height,
+            image::imageops::FilterType::CatmullRom,
+        ))
+    };
+
+    basis_universal::encoder_init();
+    let mut params = CompressorParams::new();
+    params.set_generate_mipmaps(true);
+    params.set_color_space(if srgb {
+        ColorSpace::Srgb
+    } else {
+        ColorSpace::Linear
+    });
+    let quality = settings.quality.clamp(0., 1.);
+    let level = |min: u32, max: u32| min + ((max - min) as f32 * quality).round() as u32;
+    match settings.codec {
+        TextureCodec::Etc1s => {
+            params.set_basis_format(BasisTextureFormat::ETC1S);
+            params.set_etc1s_quality_level(level(
+                basis_universal::ETC1S_QUALITY_MIN,
+                basis_universal::ETC1S_QUALITY_MAX,
+            ));
+        }
+        TextureCodec::Uastc => {
+            params.set_basis_format(BasisTextureFormat::UASTC4x4);
+            params.set_uastc_quality_level(level(
+                basis_universal::UASTC_QUALITY_MIN,
+                basis_universal::UASTC_QUALITY_MAX,
+            ));
+        }
+    }
+    params
+        .source_image_mut(0)
+        .init(image.as_raw(), width, height, 4);
+
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let mut compressor = Compressor::new(threads as u32);
+    // Safety: the parameters have a single source image, which was initialized above
+    unsafe {
+        anyhow::ensure!(
+            compressor.init(&params),
+            "Failed to initialize the texture compressor"
+        );
+        compressor
+            .process()
+            .map_err(|err| anyhow::anyhow!("{err:?}"))
+            .context("Failed to compress texture")?;
+    }
+    Ok(compressor.basis_file().to_vec())
+}
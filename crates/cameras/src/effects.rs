@@ -0,0 +1,194 @@
+//! Camera effects driven by components: smooth following with look-ahead, field of view
+//! transitions and trauma-based shake. They run every frame on the client, so that games don't
+//! have to do it from their modules, where it jitters against the frame rate.
+
+use ambient_core::{
+    camera::{fovy, projection, projection_view},
+    delta_time, game_time,
+    hierarchy::parent,
+    transform::{get_world_position, inv_local_to_world, lookat_target, translation},
+};
+use ambient_ecs::{components, query, EntityId, SystemGroup, World};
+use glam::{EulerRot, Mat4, Quat, Vec3};
+
+pub use ambient_ecs::generated::camera::components::{
+    camera_follow, camera_follow_damping, camera_follow_look_ahead, camera_follow_offset,
+    camera_fovy_target, camera_fovy_transition_time, camera_shake_decay, camera_shake_frequency,
+    camera_shake_max_angle, camera_shake_max_offset, camera_shake_trauma,
+};
+
+/// How long the velocity of a followed entity is smoothed over, so that the look-ahead doesn't
+/// pick up the noise of its movement
+const FOLLOW_VELOCITY_SMOOTHING: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CameraFollowState {
+    target: EntityId,
+    target_position: Vec3,
+    velocity: Vec3,
+    /// The point the camera is centered on, catching up with the target
+    focus: Vec3,
+}
+
+components!("camera", {
+    camera_follow_state: CameraFollowState,
+});
+
+/// Moves the cameras which follow an entity, and eases their field of view towards its target.
+/// Runs before the transforms are updated.
+pub fn camera_effects_systems() -> SystemGroup {
+    SystemGroup::new(
+        "camera_effects",
+        vec![
+            query(camera_follow()).to_system(|q, world, qs, _| {
+                let dt = *world.resource(delta_time());
+                for (id, target) in q.collect_cloned(world, qs) {
+                    update_follow(world, id, target, dt);
+                }
+            }),
+            query(())
+                .incl(camera_follow_state())
+                .excl(camera_follow())
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        world.remove_component(id, camera_follow_state()).ok();
+                    }
+                }),
+            query((fovy(), camera_fovy_target())).to_system(|q, world, qs, _| {
+                let dt = *world.resource(delta_time());
+                for (id, (current, target)) in q.collect_cloned(world, qs) {
+                    let time = world.get(id, camera_fovy_transition_time()).unwrap_or(0.25);
+                    let value = current + (target - current) * smoothing(dt, time);
+                    world.set_if_changed(id, fovy(), value).unwrap();
+                }
+            }),
+        ],
+    )
+}
+
+/// Shakes the cameras with a `camera_shake_trauma`. Runs after their `projection_view` has been
+/// updated, and offsets it without touching their transform.
+pub fn camera_shake_systems() -> SystemGroup {
+    SystemGroup::new(
+        "camera_shake",
+        vec![
+            query((camera_shake_trauma(), projection(), inv_local_to_world())).to_system(
+                |q, world, qs, _| {
+                    let dt = *world.resource(delta_time());
+                    let time = world
+                        .resource_opt(game_time())
+                        .map(|time| time.as_secs_f32())
+                        .unwrap_or_default();
+                    for (id, (trauma, projection, view)) in q.collect_cloned(world, qs) {
+                        let decay = world.get(id, camera_shake_decay()).unwrap_or(1.);
+                        let max_angle = world.get(id, camera_shake_max_angle()).unwrap_or(0.1);
+                        let max_offset = world.get(id, camera_shake_max_offset()).unwrap_or(0.);
+                        let frequency = world.get(id, camera_shake_frequency()).unwrap_or(15.);
+
+                        let shake = trauma.clamp(0., 1.).powi(2);
+                        let t = time * frequency;
+                        let angles =
+                            Vec3::new(noise(t, 0), noise(t, 1), noise(t, 2)) * max_angle * shake;
+                        let offset =
+                            Vec3::new(noise(t, 3), noise(t, 4), noise(t, 5)) * max_offset * shake;
+                        let shaken = Mat4::from_rotation_translation(
+                            Quat::from_euler(EulerRot::XYZ, angles.x, angles.y, angles.z),
+                            offset,
+                        );
+                        world
+                            .set(id, projection_view(), projection * shaken.inverse() * view)
+                            .ok();
+                        world
+                            .set_if_changed(
+                                id,
+                                camera_shake_trauma(),
+                                (trauma - decay * dt).max(0.),
+                            )
+                            .unwrap();
+                    }
+                },
+            ),
+        ],
+    )
+}
+
+fn update_follow(world: &mut World, id: EntityId, target: EntityId, dt: f32) {
+    let Some(target_position) = get_target_position(world, target) else {
+        return;
+    };
+    let damping = world.get(id, camera_follow_damping()).unwrap_or(0.2);
+    let look_ahead = world.get(id, camera_follow_look_ahead()).unwrap_or(0.);
+    let offset = world.get(id, camera_follow_offset()).unwrap_or_default();
+
+    let state = match world.get(id, camera_follow_state()) {
+        Ok(state) if state.target == target && dt > 0. => {
+            let velocity = (target_position - state.target_position) / dt;
+            let velocity = state
+                .velocity
+                .lerp(velocity, smoothing(dt, FOLLOW_VELOCITY_SMOOTHING));
+            let focus = target_position + velocity * look_ahead;
+            CameraFollowState {
+                target,
+                target_position,
+                velocity,
+                focus: state.focus.lerp(focus, smoothing(dt, damping)),
+            }
+        }
+        Ok(state) if state.target == target => state,
+        // The camera jumps to a new target
+        _ => CameraFollowState {
+            target,
+            target_position,
+            velocity: Vec3::ZERO,
+            focus: target_position,
+        },
+    };
+    world
+        .add_component(id, camera_follow_state(), state)
+        .unwrap();
+    world
+        .add_component(id, translation(), state.focus + offset)
+        .unwrap();
+    if world.has_component(id, lookat_target()) {
+        world.set(id, lookat_target(), state.focus).unwrap();
+    }
+}
+
+/// The position of `target` this frame. Root entities are read from their `translation`, which
+/// is ahead of their `local_to_world` until the transforms are updated
+fn get_target_position(world: &World, target: EntityId) -> Option<Vec3> {
+    if world.has_component(target, parent()) {
+        get_world_position(world, target).ok()
+    } else {
+        world
+            .get(target, translation())
+            .or_else(|_| get_world_position(world, target))
+            .ok()
+    }
+}
+
+/// How much of the way to a target value to go in `dt` seconds, to get most of the way there in
+/// `time` seconds regardless of the frame rate
+fn smoothing(dt: f32, time: f32) -> f32 {
+    if time <= 0. {
+        1.
+    } else {
+        1. - (-dt / time).exp()
+    }
+}
+
+/// Smooth noise between -1 and 1, which changes direction about once per unit of `t`
+fn noise(t: f32, seed: u32) -> f32 {
+    fn hash(x: i32, seed: u32) -> f32 {
+        let mut h = (x as u32).wrapping_mul(0x9e37_79b1) ^ seed.wrapping_mul(0x85eb_ca77);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2c1b_3c6d);
+        h ^= h >> 12;
+        h as f32 / u32::MAX as f32 * 2. - 1.
+    }
+    let i = t.floor();
+    let f = t - i;
+    let a = hash(i as i32, seed);
+    let b = hash(i as i32 + 1, seed);
+    a + (b - a) * f * f * (3. - 2. * f)
+}
@@ -7,6 +7,7 @@ use winit::event::Event;
 
 use crate::{free::free_camera_system, spherical::spherical_camera_system};
 
+pub mod effects;
 pub mod free;
 pub mod spherical;
 
@@ -16,6 +17,7 @@ components!("camera", {
 });
 
 pub fn init_all_components() {
+    effects::init_components();
     free::init_components();
     init_components();
     spherical::init_components();
@@ -470,10 +470,13 @@ impl Camera {
             proj_view_inv.project_point3(vec3(s, s, s)),
         ]
     }
+    /// The corners of the part of the view covered by a shadow cascade. The cascades are spread
+    /// logarithmically for a `split` of 0, and linearly for a `split` of 1
     pub fn world_space_frustum_points_for_shadow_cascade(
         &self,
         cascade_index: u32,
         n_cascades: u32,
+        split: f32,
     ) -> Vec<Vec3> {
         // From: http://developer.download.nvidia.com/SDK/10.5/opengl/src/cascaded_shadow_maps/doc/cascaded_shadow_maps.pdf
         fn split_z(linear_factor: f32, near: f32, far: f32, i: u32, n: u32) -> f32 {
@@ -483,7 +486,7 @@ impl Camera {
         }
 
         let near = 1.;
-        let linear_factor = 0.0;
+        let linear_factor = split.clamp(0., 1.);
         let main_projection = self.projection.matrix();
         let main_projection_view_inv = self.projection_view().inverse();
         let far = self.projection.far().expect(
@@ -548,10 +551,14 @@ impl Camera {
         cascade_index: u32,
         n_cascades: u32,
         shadow_map_resolution: u32,
+        split: f32,
     ) -> Self {
         let main_camera = self.to_shadows_far_bound();
-        let frustum_world =
-            main_camera.world_space_frustum_points_for_shadow_cascade(cascade_index, n_cascades);
+        let frustum_world = main_camera.world_space_frustum_points_for_shadow_cascade(
+            cascade_index,
+            n_cascades,
+            split,
+        );
         let frustum_perspective = frustum_world
             .iter()
             .map(|x| main_camera.view.project_point3(*x))
@@ -679,6 +686,7 @@ pub fn shadow_cameras_from_world(
     world: &World,
     shadow_cascades: u32,
     shadow_map_resolution: u32,
+    shadow_cascade_split: f32,
     light_direction: Vec3,
     scene: Component<()>,
     user_id: Option<&String>,
@@ -691,6 +699,7 @@ pub fn shadow_cameras_from_world(
                 cascade,
                 shadow_cascades,
                 shadow_map_resolution,
+                shadow_cascade_split,
             )
         })
         .collect()
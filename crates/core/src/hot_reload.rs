@@ -0,0 +1,90 @@
+//! Reloads the assets which were built again while the game is running, like when the dev server
+//! sees that their source files have changed.
+//!
+//! The server publishes the paths of the rebuilt assets, relative to the build root, in the
+//! `changed_assets` synced resource. Each world then expires the cached assets loaded from them,
+//! and sets the url components which refer to them again, so that the systems loading them load
+//! them again.
+use ambient_ecs::{
+    components, query, Component, ComponentRegistry, Debuggable, Networked, Resource, SystemGroup,
+    World,
+};
+#[cfg(not(target_os = "unknown"))]
+use ambient_native_std::{asset_cache::AssetCache, asset_url::AbsAssetUrl};
+use itertools::Itertools;
+
+use crate::asset_cache;
+
+components!("app", {
+    @[Debuggable, Networked, Resource]
+    changed_assets: Vec<String>,
+});
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "hot_reload",
+        vec![
+            query(changed_assets().changed()).to_system(|q, world, qs, _| {
+                for (_, paths) in q.collect_cloned(world, qs) {
+                    reload_assets(world, &paths);
+                }
+            }),
+        ],
+    )
+}
+
+/// Expires the cached assets loaded from `paths`, relative to the build root, and sets the url
+/// components which refer to them again, so that they're loaded again
+pub fn reload_assets(world: &mut World, paths: &[String]) {
+    let assets = world.resource(asset_cache()).clone();
+    let expired = assets.invalidate(|key| paths.iter().any(|path| key.contains(path.as_str())));
+    #[cfg(not(target_os = "unknown"))]
+    for path in paths {
+        remove_from_disk_cache(&assets, path);
+    }
+
+    let url_components = ComponentRegistry::get()
+        .all()
+        .filter(|desc| desc.is::<String>() && desc.path_last().ends_with("url"))
+        .map(Component::<String>::new)
+        .collect_vec();
+    let mut reloaded = 0;
+    for component in url_components {
+        for (id, url) in query(component).collect_cloned(world, None) {
+            if paths.iter().any(|path| refers_to(&url, path)) {
+                world.set(id, component, url).ok();
+                reloaded += 1;
+            }
+        }
+    }
+
+    tracing::info!(
+        "Reloaded {} changed assets: expired {expired} cached assets, reloading {reloaded} entities",
+        paths.len()
+    );
+}
+
+/// Whether `url` refers to the asset at `path`, relative to the build root, or to the directory
+/// containing it, like a model crate
+fn refers_to(url: &str, path: &str) -> bool {
+    let Some(package) = path.split('/').next().filter(|package| !package.is_empty()) else {
+        return false;
+    };
+    let Some(start) = url.find(&format!("/{package}/")) else {
+        return false;
+    };
+    let tail = &url[start + 1..];
+    tail == path || (tail.ends_with('/') && path.starts_with(tail))
+}
+
+/// Removes the downloaded copies of the asset at `path` from the disk cache
+#[cfg(not(target_os = "unknown"))]
+fn remove_from_disk_cache(assets: &AssetCache, path: &str) {
+    let Ok(url) = AbsAssetUrl::from_asset_key(path) else {
+        return;
+    };
+    let download_url = url.to_download_url(assets).ok();
+    for url in std::iter::once(url).chain(download_url) {
+        std::fs::remove_file(url.absolute_cache_path(assets)).ok();
+    }
+}
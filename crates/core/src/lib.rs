@@ -21,6 +21,7 @@ pub mod bounding;
 pub mod camera;
 
 pub mod hierarchy;
+pub mod hot_reload;
 pub mod player;
 pub mod transform;
 pub mod window;
@@ -76,6 +77,7 @@ pub fn init_all_components() {
     init_components();
     window::init_components();
     async_ecs::init_components();
+    hot_reload::init_components();
     ambient_gpu_ecs::init_components();
     camera::init_components();
     transform::init_components();
@@ -502,7 +502,8 @@ pub fn transform_gpu_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
         ))],
     )
 }
-fn update_transform_recursive(world: &mut World, id: EntityId, mut parent_transform: Mat4) {
+/// Sets the `local_to_world` of `id` and its descendants from the transform of its parent
+pub fn update_transform_recursive(world: &mut World, id: EntityId, mut parent_transform: Mat4) {
     if world.has_component(id, reset_scale()) {
         let (_s, r, t) = parent_transform.to_scale_rotation_translation();
         parent_transform = Mat4::from_rotation_translation(r, t);
@@ -59,6 +59,11 @@ pub enum WindowCtl {
     ShowCursor(bool),
     SetTitle(String),
     SetFullscreen(bool),
+    /// Whether text can be composed with an input method editor, for text fields to turn on while
+    /// they are focused
+    SetImeAllowed(bool),
+    /// Where the input method editor's candidate window should be, in logical pixels
+    SetImePosition(Vec2),
     ExitProcess(ExitStatus),
 }
 
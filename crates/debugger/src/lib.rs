@@ -3,11 +3,8 @@ use std::{fmt::Debug, sync::Arc, time::Duration};
 use ambient_core::{
     asset_cache,
     bounding::world_bounding_sphere,
-    camera::shadow_cameras_from_world,
     hierarchy::{dump_world_hierarchy, dump_world_hierarchy_to_user},
-    main_scene, performance_samples,
-    player::local_user_id,
-    runtime,
+    performance_samples, runtime,
 };
 use ambient_ecs::{query, World};
 use ambient_element::{
@@ -15,15 +12,18 @@ use ambient_element::{
     ElementComponentExt, Hooks,
 };
 use ambient_gizmos::{gizmos, GizmoPrimitive};
-use ambient_native_std::{asset_cache::AssetCache, color::Color, Cb};
+use ambient_native_std::{asset_cache::AssetCache, cb, color::Color, Cb};
 use ambient_network::{client::ClientState, server::RpcArgs as ServerRpcArgs};
-use ambient_renderer::{RenderTarget, Renderer};
+use ambient_renderer::{
+    frame_stats, get_shadow_settings, shadow_settings, FrameStats, RenderTarget, Renderer,
+    ShadowSettings,
+};
 use ambient_rpc::RpcRegistry;
 use ambient_shared_types::{ModifiersState, VirtualKeyCode};
 use ambient_std::line_uid;
 use ambient_ui_native::{
     fit_horizontal, height, space_between_items, width, Button, ButtonStyle, Dropdown, Fit,
-    FlowColumn, FlowRow, Image, Text, UIExt,
+    FlowColumn, FlowRow, Image, Slider, Text, UIExt,
 };
 use glam::Vec3;
 
@@ -157,6 +157,7 @@ const DEBUGGER_BUTTONS: bool = true;
 #[element_component]
 pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
     let (show_shadows, set_show_shadows) = use_state(hooks, false);
+    let (show_frame_stats, set_show_frame_stats) = use_state(hooks, false);
     let (client_state, _) = consume_context::<ClientState>(hooks).unwrap();
 
     FlowColumn::el([
@@ -211,26 +212,19 @@ pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
                     Button::new("Show Shadow Frustums", {
                         let get_state = get_state.clone();
                         move |_| {
-                            get_state(&mut |_, _, world| {
+                            get_state(&mut |renderer, _, world| {
+                                let Some(shadows) = &renderer.shadows else {
+                                    return;
+                                };
+                                let cascades = shadows.cascade_cameras();
                                 let gizmos = world.resource(gizmos());
                                 let mut g = gizmos.scope(line_uid!());
-                                let cascades = 5;
-                                for (i, cam) in shadow_cameras_from_world(
-                                    world,
-                                    cascades,
-                                    1024,
-                                    Vec3::ONE.normalize(),
-                                    main_scene(),
-                                    world.resource_opt(local_user_id()),
-                                )
-                                .into_iter()
-                                .enumerate()
-                                {
+                                for (i, cam) in cascades.iter().enumerate() {
                                     for line in cam.world_space_frustum_lines() {
                                         g.draw(
                                             GizmoPrimitive::line(line.0, line.1, 1.).with_color(
                                                 Color::hsl(
-                                                    360. * i as f32 / cascades as f32,
+                                                    360. * i as f32 / cascades.len() as f32,
                                                     1.0,
                                                     0.5,
                                                 )
@@ -276,6 +270,19 @@ pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
                     .hotkey(VirtualKeyCode::F6)
                     .style(ButtonStyle::Flat)
                     .el(),
+                    Button::new("Show Frame Stats", {
+                        move |_| {
+                            set_show_frame_stats(!show_frame_stats);
+                        }
+                    })
+                    .hotkey_modifier(ModifiersState::SHIFT)
+                    .hotkey(VirtualKeyCode::F7)
+                    .style(ButtonStyle::Flat)
+                    .el(),
+                    ShadowSettingsEditor {
+                        get_state: get_state.clone(),
+                    }
+                    .el(),
                     ShaderDebug {
                         get_state: get_state.clone(),
                     }
@@ -294,6 +301,14 @@ pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
         ])
         .el()
         .with(space_between_items(), 5.),
+        if show_frame_stats {
+            FrameStatsView {
+                get_state: get_state.clone(),
+            }
+            .el()
+        } else {
+            Element::new()
+        },
         if show_shadows {
             ShadowMapsViz {
                 get_state: get_state.clone(),
@@ -307,6 +322,47 @@ pub fn Debugger(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
     .with(fit_horizontal(), Fit::Parent)
 }
 
+#[element_component]
+fn FrameStatsView(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
+    let (stats, set_stats) = use_state(hooks, FrameStats::default());
+
+    use_frame(hooks, move |_| {
+        let mut stats = FrameStats::default();
+        get_state(&mut |_, _, world| {
+            stats = world
+                .resource_opt(frame_stats())
+                .cloned()
+                .unwrap_or_default();
+        });
+        set_stats(stats);
+    });
+
+    const MIB: u64 = 1024 * 1024;
+    let mut lines = vec![
+        Text::el(format!(
+            "Draw calls {:<8} Triangles {}",
+            stats.draws.draw_calls, stats.draws.triangles
+        )),
+        Text::el(format!(
+            "Textures   {:<4} MiB Buffers   {} MiB",
+            stats.texture_bytes / MIB,
+            stats.buffer_bytes / MIB
+        )),
+    ];
+    if stats.passes.is_empty() {
+        lines.push(Text::el("GPU time   not measured by this GPU"));
+    } else {
+        lines.push(Text::el(format!("GPU time   {:.2?}", stats.gpu_time())));
+        lines.extend(
+            stats
+                .passes
+                .iter()
+                .map(|(pass, time)| Text::el(format!("  {pass:<18} {time:.2?}"))),
+        );
+    }
+    FlowColumn::el(lines)
+}
+
 #[element_component]
 fn ShadowMapsViz(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
     let (shadow_cascades, _) = use_state_with(hooks, |_| {
@@ -353,6 +409,127 @@ fn ShadowMapViz(hooks: &mut Hooks, get_state: GetDebuggerState, cascade: u32) ->
         .with(height(), 200.)
 }
 
+#[element_component]
+fn ShadowSettingsEditor(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
+    let (show, set_show) = use_state(hooks, false);
+    let (_, upd) = use_state(hooks, ());
+
+    let mut settings = ShadowSettings::default();
+    get_state(&mut |_, _, world| {
+        settings = get_shadow_settings(world);
+    });
+    // Changing the resource of the world is picked up by its renderer on the next frame
+    let set_settings = cb(move |settings: ShadowSettings| {
+        get_state(&mut |_, _, world| {
+            world.add_resource(shadow_settings(), settings.clone());
+        });
+        upd(());
+    });
+    let slider =
+        |value: f32, min: f32, max: f32, round: u32, edit: fn(&mut ShadowSettings, f32)| {
+            let settings = settings.clone();
+            let set_settings = set_settings.clone();
+            Slider {
+                value,
+                on_change: Some(cb(move |value| {
+                    let mut settings = settings.clone();
+                    edit(&mut settings, value);
+                    set_settings(settings);
+                })),
+                min,
+                max,
+                width: 150.,
+                logarithmic: min > 0.,
+                round: Some(round),
+                suffix: None,
+            }
+            .el()
+        };
+    let choice =
+        |label: String, selected: bool, edit: Box<dyn Fn(&mut ShadowSettings) + Sync + Send>| {
+            let settings = settings.clone();
+            let set_settings = set_settings.clone();
+            Button::new(label, move |_| {
+                let mut settings = settings.clone();
+                edit(&mut settings);
+                set_settings(settings);
+            })
+            .toggled(selected)
+            .style(ButtonStyle::Flat)
+            .el()
+        };
+    let row = |label: &str, mut items: Vec<Element>| {
+        items.insert(0, Text::el(label));
+        FlowRow::el(items).with(space_between_items(), 5.)
+    };
+
+    Dropdown {
+        content: Button::new("Shadow Settings", move |_| set_show(!show))
+            .toggled(show)
+            .el(),
+        dropdown: FlowColumn::el([
+            row(
+                "Resolution",
+                [512, 1024, 2048, 4096]
+                    .into_iter()
+                    .map(|resolution| {
+                        choice(
+                            resolution.to_string(),
+                            settings.resolution == resolution,
+                            Box::new(move |settings| settings.resolution = resolution),
+                        )
+                    })
+                    .collect(),
+            ),
+            row(
+                "Cascades",
+                (1..=6)
+                    .map(|cascades| {
+                        choice(
+                            cascades.to_string(),
+                            settings.cascades == cascades,
+                            Box::new(move |settings| settings.cascades = cascades),
+                        )
+                    })
+                    .collect(),
+            ),
+            row(
+                "Cascade split",
+                vec![slider(
+                    settings.cascade_split,
+                    0.,
+                    1.,
+                    2,
+                    |settings, value| settings.cascade_split = value,
+                )],
+            ),
+            row(
+                "Bias",
+                vec![slider(
+                    settings.bias,
+                    0.00001,
+                    0.01,
+                    5,
+                    |settings, value| settings.bias = value,
+                )],
+            ),
+            row(
+                "Distance",
+                vec![slider(
+                    settings.distance,
+                    10.,
+                    2000.,
+                    0,
+                    |settings, value| settings.distance = value,
+                )],
+            ),
+        ])
+        .with(space_between_items(), 5.),
+        show,
+    }
+    .el()
+}
+
 #[element_component]
 fn ShaderDebug(hooks: &mut Hooks, get_state: GetDebuggerState) -> Element {
     let (show, set_show) = use_state(hooks, false);
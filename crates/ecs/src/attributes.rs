@@ -3,6 +3,7 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     sync::Arc,
+    time::Duration,
 };
 
 use as_any::{AsAny, Downcast};
@@ -238,6 +239,55 @@ where
     }
 }
 
+/// How urgently changes to a [`Replicated`] component are sent to the clients
+#[derive(
+    Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum ReplicationPriority {
+    /// Changes are coalesced and sent at [`ReplicationPriority::LOW_RATE`], unless a rate is given
+    Low,
+    #[default]
+    Normal,
+    /// A change to the component flushes the held back changes of its entity along with it
+    High,
+}
+impl ReplicationPriority {
+    /// The rate, in hertz, low priority components are sent at if no rate is given
+    pub const LOW_RATE: f32 = 4.0;
+}
+
+/// Limits how often changes to a [`Networked`] component are sent to the clients, instead of
+/// every server tick.
+///
+/// Changes in between are held back, and only the latest value is sent once the interval has
+/// elapsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Replicated {
+    /// The maximum number of times per second the component of an entity is sent
+    pub rate: Option<f32>,
+    pub priority: ReplicationPriority,
+    /// The step floating point values of the component are rounded to when only their change
+    /// is sent, instead of being sent exactly
+    pub quantization: Option<f32>,
+}
+impl ComponentAttribute for Replicated {}
+impl<T: ComponentValue> AttributeConstructor<T, Replicated> for Replicated {
+    fn construct(store: &mut AttributeStore, value: Replicated) {
+        store.set(value)
+    }
+}
+impl Replicated {
+    /// The shortest time between two sends of the component, or `None` if it is sent every tick
+    pub fn interval(&self) -> Option<Duration> {
+        let rate = match (self.rate, self.priority) {
+            (Some(rate), _) => rate,
+            (None, ReplicationPriority::Low) => ReplicationPriority::LOW_RATE,
+            (None, _) => return None,
+        };
+        (rate > 0.0).then(|| Duration::from_secs_f32(1.0 / rate))
+    }
+}
+
 pub(crate) struct ComponentPath(pub String);
 impl ComponentAttribute for ComponentPath {}
 
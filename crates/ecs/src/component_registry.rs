@@ -67,12 +67,15 @@ macro_rules! define_external_component_attribute {
         pub struct ExternalComponentAttributes {
             $(pub $field_name: bool,)*
             $(pub $special_field_name: bool,)*
+            #[serde(default)]
+            pub replicated: Option<Replicated>,
         }
         impl ExternalComponentAttributes {
             pub fn from_existing_component(desc: ComponentDesc) -> Self {
                 Self {
                     $($field_name: desc.has_attribute::<$type_name>(),)*
                     $($special_field_name: desc.has_attribute::<$special_type_name>(),)*
+                    replicated: desc.attribute::<Replicated>().map(|r| *r),
                 }
             }
 
@@ -95,6 +98,10 @@ macro_rules! define_external_component_attribute {
                 if self.enum_ {
                     <Enum as AttributeConstructor<u32, _>>::construct(store, ());
                 }
+
+                if let Some(replicated) = self.replicated {
+                    <Replicated as AttributeConstructor<T, _>>::construct(store, replicated);
+                }
             }
         }
         impl<'a> FromIterator<&'a str> for ExternalComponentAttributes {
@@ -30,7 +30,58 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("animation" , { # [doc = "**Is animation player**: This entity is treated as an animation player. Attach an animation node as a child for it to play.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is animation player"] , Description ["This entity is treated as an animation player. Attach an animation node as a child for it to play."]] is_animation_player : () , # [doc = "**Animation errors**: A list of errors that were produced trying to play the animation.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Animation errors"] , Description ["A list of errors that were produced trying to play the animation."]] animation_errors : Vec :: < String > , # [doc = "**Apply animation player**: Apply the designated animation player to this entity and its sub-tree.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Apply animation player"] , Description ["Apply the designated animation player to this entity and its sub-tree."]] apply_animation_player : EntityId , # [doc = "**Play clip from URL**: Make this entity a 'play animation clip' node. The value is the URL to the clip we'd like to play.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Play clip from URL"] , Description ["Make this entity a 'play animation clip' node. The value is the URL to the clip we'd like to play."]] play_clip_from_url : String , # [doc = "**Looping**: When this is true, the animation clip will repeat infinitely.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Looping"] , Description ["When this is true, the animation clip will repeat infinitely."]] looping : bool , # [doc = "**Speed**: Animation playback speed. Default is 1, higher values speeds up the animation.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Speed"] , Description ["Animation playback speed. Default is 1, higher values speeds up the animation."]] speed : f32 , # [doc = "**Start time**: Start time of an animation node.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Start time"] , Description ["Start time of an animation node."]] start_time : Duration , # [doc = "**Freeze at percentage**: Sample the input animation at a certain percentage of the animation track length.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Freeze at percentage"] , Description ["Sample the input animation at a certain percentage of the animation track length."]] freeze_at_percentage : f32 , # [doc = "**Freeze at time**: Sample the input animation at a certain time (in seconds).\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Freeze at time"] , Description ["Sample the input animation at a certain time (in seconds)."]] freeze_at_time : f32 , # [doc = "**Clip duration**: The clip duration is loaded from the clip, and then applied to the entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Clip duration"] , Description ["The clip duration is loaded from the clip, and then applied to the entity."]] clip_duration : f32 , # [doc = "**Clip loaded**: The clip has been loaded.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Clip loaded"] , Description ["The clip has been loaded."]] clip_loaded : () , # [doc = "**Clip load error**: There was an error loading the clip.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Clip load error"] , Description ["There was an error loading the clip."]] clip_load_error : String , # [doc = "**Blend**: Blend two animations together. The values is the blend weight. Use `children` to set the animations. Blend 0 means we only sample from the first animation, 1 means only the second one, and values in between blend between them.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Blend"] , Description ["Blend two animations together. The values is the blend weight. Use `children` to set the animations. Blend 0 means we only sample from the first animation, 1 means only the second one, and values in between blend between them."]] blend : f32 , # [doc = "**Mask bind ids**: List of bind ids that will be masked.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Mask bind ids"] , Description ["List of bind ids that will be masked."]] mask_bind_ids : Vec :: < String > , # [doc = "**Mask weights**: Weights for each bind id in `mask_bind_ids`.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Mask weights"] , Description ["Weights for each bind id in `mask_bind_ids`."]] mask_weights : Vec :: < f32 > , # [doc = "**Retarget Model from URL**: Retarget the animation using the model at the given URL.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Retarget Model from URL"] , Description ["Retarget the animation using the model at the given URL."]] retarget_model_from_url : String , # [doc = "**Retarget animation scaled**: Retarget animation scaled. True means normalize hip.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Retarget animation scaled"] , Description ["Retarget animation scaled. True means normalize hip."]] retarget_animation_scaled : bool , # [doc = "**Apply base pose**: Apply the base pose to this clip.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Apply base pose"] , Description ["Apply the base pose to this clip."]] apply_base_pose : () , # [doc = "**Bind id**: Animation bind ID.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Bind id"] , Description ["Animation bind ID."]] bind_id : String , # [doc = "**Bind ids**: Animation bind IDs.\n\n*Attributes*: Debuggable, Store"] @ [Debuggable , Store , Name ["Bind ids"] , Description ["Animation bind IDs."]] bind_ids : Vec :: < String > , });
+                components ! ("animation" , { # [doc = "**Is animation player**: This entity is treated as an animation player. Attach an animation node as a child for it to play.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is animation player"] , Description ["This entity is treated as an animation player. Attach an animation node as a child for it to play."]] is_animation_player : () , # [doc = "**Animation errors**: A list of errors that were produced trying to play the animation.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Animation errors"] , Description ["A list of errors that were produced trying to play the animation."]] animation_errors : Vec :: < String > , # [doc = "**Apply animation player**: Apply the designated animation player to this entity and its sub-tree.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Apply animation player"] , Description ["Apply the designated animation player to this entity and its sub-tree."]] apply_animation_player : EntityId , # [doc = "**Play clip from URL**: Make this entity a 'play animation clip' node. The value is the URL to the clip we'd like to play.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Play clip from URL"] , Description ["Make this entity a 'play animation clip' node. The value is the URL to the clip we'd like to play."]] play_clip_from_url : String , # [doc = "**Looping**: When this is true, the animation clip will repeat infinitely.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Looping"] , Description ["When this is true, the animation clip will repeat infinitely."]] looping : bool , # [doc = "**Speed**: Animation playback speed. Default is 1, higher values speeds up the animation.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Speed"] , Description ["Animation playback speed. Default is 1, higher values speeds up the animation."]] speed : f32 , # [doc = "**Start time**: Start time of an animation node.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Start time"] , Description ["Start time of an animation node."]] start_time : Duration , # [doc = "**Freeze at percentage**: Sample the input animation at a certain percentage of the animation track length.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Freeze at percentage"] , Description ["Sample the input animation at a certain percentage of the animation track length."]] freeze_at_percentage : f32 , # [doc = "**Freeze at time**: Sample the input animation at a certain time (in seconds).\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Freeze at time"] , Description ["Sample the input animation at a certain time (in seconds)."]] freeze_at_time : f32 , # [doc = "**Clip duration**: The clip duration is loaded from the clip, and then applied to the entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Clip duration"] , Description ["The clip duration is loaded from the clip, and then applied to the entity."]] clip_duration : f32 , # [doc = "**Clip loaded**: The clip has been loaded.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Clip loaded"] , Description ["The clip has been loaded."]] clip_loaded : () , # [doc = "**Clip load error**: There was an error loading the clip.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Clip load error"] , Description ["There was an error loading the clip."]] clip_load_error : String , # [doc = "**Blend**: Blend two animations together. The values is the blend weight. Use `children` to set the animations. Blend 0 means we only sample from the first animation, 1 means only the second one, and values in between blend between them.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Blend"] , Description ["Blend two animations together. The values is the blend weight. Use `children` to set the animations. Blend 0 means we only sample from the first animation, 1 means only the second one, and values in between blend between them."]] blend : f32 , # [doc = "**Mask bind ids**: List of bind ids that will be masked.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Mask bind ids"] , Description ["List of bind ids that will be masked."]] mask_bind_ids : Vec :: < String > , # [doc = "**Mask weights**: Weights for each bind id in `mask_bind_ids`.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Mask weights"] , Description ["Weights for each bind id in `mask_bind_ids`."]] mask_weights : Vec :: < f32 > , # [doc = "**Retarget Model from URL**: Retarget the animation using the model at the given URL.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Retarget Model from URL"] , Description ["Retarget the animation using the model at the given URL."]] retarget_model_from_url : String , # [doc = "**Retarget animation scaled**: Retarget animation scaled. True means normalize hip.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Retarget animation scaled"] , Description ["Retarget animation scaled. True means normalize hip."]] retarget_animation_scaled : bool , # [doc = "**Apply base pose**: Apply the base pose to this clip.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Apply base pose"] , Description ["Apply the base pose to this clip."]] apply_base_pose : () , # [doc = "**Bind id**: Animation bind ID.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Bind id"] , Description ["Animation bind ID."]] bind_id : String , # [doc = "**Bind ids**: Animation bind IDs.\n\n*Attributes*: Debuggable, Store"] @ [Debuggable , Store , Name ["Bind ids"] , Description ["Animation bind IDs."]] bind_ids : Vec :: < String > , # [doc = "**Animation graph from URL**: Make this entity an 'animation graph' node. The value is the URL to the graph we'd like to play: a JSON file of layered state machines, whose states play clips and whose transitions blend between them.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Animation graph from URL"] , Description ["Make this entity an 'animation graph' node. The value is the URL to the graph we'd like to play: a JSON file of layered state machines, whose states play clips and whose transitions blend between them."]] animation_graph_from_url : String , # [doc = "**Animation graph parameters**: The names of the parameters set on this animation graph node, which drive the transitions of its state machines.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Animation graph parameters"] , Description ["The names of the parameters set on this animation graph node, which drive the transitions of its state machines."]] animation_graph_parameters : Vec :: < String > , # [doc = "**Animation graph parameter values**: Values for each parameter in `animation_graph_parameters`. Booleans are 0 or 1.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Animation graph parameter values"] , Description ["Values for each parameter in `animation_graph_parameters`. Booleans are 0 or 1."]] animation_graph_parameter_values : Vec :: < f32 > , # [doc = "**Animation graph states**: The current state of each layer of this animation graph node.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Animation graph states"] , Description ["The current state of each layer of this animation graph node."]] animation_graph_states : Vec :: < String > , # [doc = "**Attach to model**: Attaches this entity to a bone of the model of the given entity, such as a weapon to a hand or a hat to a head. The bone is picked with `attach_to_bone`.\n\nOnce the model is loaded, this entity follows the bone every frame, offset by its `local_to_parent`; add `reset_scale` to ignore the scale of the bone.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Attach to model"] , Description ["Attaches this entity to a bone of the model of the given entity, such as a weapon to a hand or a hat to a head. The bone is picked with `attach_to_bone`.\nOnce the model is loaded, this entity follows the bone every frame, offset by its `local_to_parent`; add `reset_scale` to ignore the scale of the bone."]] attach_to_model : EntityId , # [doc = "**Attach to bone**: The bone or socket of the model in `attach_to_model` to attach this entity to: either the bind id of a bone (for example `RightHand`), or the name of a node of the model.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Attach to bone"] , Description ["The bone or socket of the model in `attach_to_model` to attach this entity to: either the bind id of a bone (for example `RightHand`), or the name of a node of the model."]] attach_to_bone : String , });
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{Entity, EntityId};
+                use ambient_package_rt::message_serde::{
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
+                };
+                use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
+                #[derive(Clone, Debug)]
+                #[doc = "**AnimationEvent**: An event of a state of an animation graph was reached: `name` is the event, `state` the state playing it, and `node` the animation graph node."]
+                pub struct AnimationEvent {
+                    pub node: EntityId,
+                    pub state: String,
+                    pub name: String,
+                }
+                impl AnimationEvent {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        node: impl Into<EntityId>,
+                        state: impl Into<String>,
+                        name: impl Into<String>,
+                    ) -> Self {
+                        Self {
+                            node: node.into(),
+                            state: state.into(),
+                            name: name.into(),
+                        }
+                    }
+                }
+                impl Message for AnimationEvent {
+                    fn id() -> &'static str {
+                        "ambient_core::animation::AnimationEvent"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.node.serialize_message_part(&mut output)?;
+                        self.state.serialize_message_part(&mut output)?;
+                        self.name.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            node: EntityId::deserialize_message_part(&mut input)?,
+                            state: String::deserialize_message_part(&mut input)?,
+                            name: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for AnimationEvent {}
             }
         }
         pub mod app {
@@ -62,7 +113,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("audio" , { # [doc = "**Is audio player**: The entity is an audio player.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Is audio player"] , Description ["The entity is an audio player."]] is_audio_player : () , # [doc = "**Is spatial audio player**: The entity is a spatial audio player.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Is spatial audio player"] , Description ["The entity is a spatial audio player."]] is_spatial_audio_player : () , # [doc = "**Spatial audio emitter**: The entity is a spatial audio emitter.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Spatial audio emitter"] , Description ["The entity is a spatial audio emitter."]] spatial_audio_emitter : EntityId , # [doc = "**Spatial audio listener**: The entity is a spatial audio listener.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Spatial audio listener"] , Description ["The entity is a spatial audio listener."]] spatial_audio_listener : EntityId , # [doc = "**Looping**: Whether or not the audio should loop.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Looping"] , Description ["Whether or not the audio should loop.\n"]] looping : bool , # [doc = "**One pole low pass filter**: With this component, the audio will be filtered with a one pole low pass filter.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["One pole low pass filter"] , Description ["With this component, the audio will be filtered with a one pole low pass filter.\n"]] onepole_lpf : f32 , # [doc = "**Playing sound**: The entity with this comp is a playing sound.\n\nWe can attach other components to it to control the sound parameters.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Playing sound"] , Description ["The entity with this comp is a playing sound.\nWe can attach other components to it to control the sound parameters.\n"]] playing_sound : () , # [doc = "**Amplitude**: The amplitude of the audio.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Amplitude"] , Description ["The amplitude of the audio.\n"]] amplitude : f32 , # [doc = "**Panning**: The panning of the audio.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Panning"] , Description ["The panning of the audio.\n"]] panning : f32 , # [doc = "**Low_pass filter**: Low pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Low_pass filter"] , Description ["Low pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n"]] lpf : Vec2 , # [doc = "**High_pass filter**: High pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["High_pass filter"] , Description ["High pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n"]] hpf : Vec2 , # [doc = "**Audio URL**: The URL of the assets.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio URL"] , Description ["The URL of the assets.\n"]] audio_url : String , # [doc = "**Trigger at this frame**: The system will watch for this component and PLAY the audio at this frame,\n\nusing the other components as parameters.\n\nThen set it back to false.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Trigger at this frame"] , Description ["The system will watch for this component and PLAY the audio at this frame,\nusing the other components as parameters.\nThen set it back to false.\n"]] play_now : () , # [doc = "**Stop at this frame**: The system will watch for this component and STOP the audio at this frame,\n\nusing the other components as parameters.\n\nThen set it back to false.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Stop at this frame"] , Description ["The system will watch for this component and STOP the audio at this frame,\nusing the other components as parameters.\nThen set it back to false.\n"]] stop_now : () , });
+                components ! ("audio" , { # [doc = "**Is audio player**: The entity is an audio player.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Is audio player"] , Description ["The entity is an audio player."]] is_audio_player : () , # [doc = "**Is spatial audio player**: The entity is a spatial audio player.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Is spatial audio player"] , Description ["The entity is a spatial audio player."]] is_spatial_audio_player : () , # [doc = "**Spatial audio emitter**: The entity is a spatial audio emitter.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Spatial audio emitter"] , Description ["The entity is a spatial audio emitter."]] spatial_audio_emitter : EntityId , # [doc = "**Spatial audio listener**: The entity is a spatial audio listener.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Spatial audio listener"] , Description ["The entity is a spatial audio listener."]] spatial_audio_listener : EntityId , # [doc = "**Spatial audio attenuation**: How the volume of a spatial sound falls off with the distance `d` to the listener, as the `(constant, linear, quadratic)` coefficients of `1 / (constant + linear * d + quadratic * d²)`.\n\nCan be attached to a spatial audio player, or to one of its playing sounds to change it while it plays.\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Spatial audio attenuation"] , Description ["How the volume of a spatial sound falls off with the distance `d` to the listener, as the `(constant, linear, quadratic)` coefficients of `1 / (constant + linear * d + quadratic * d²)`.\nCan be attached to a spatial audio player, or to one of its playing sounds to change it while it plays."]] spatial_audio_attenuation : Vec3 , # [doc = "**Audio occlusion**: How much the path from the listener to a playing spatial sound is blocked by colliders, from 0.0 (clear) to 1.0 (fully blocked).\n\nOccluded sounds are muffled and quieter. Updated a few times per second, in worlds with physics.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio occlusion"] , Description ["How much the path from the listener to a playing spatial sound is blocked by colliders, from 0.0 (clear) to 1.0 (fully blocked).\nOccluded sounds are muffled and quieter. Updated a few times per second, in worlds with physics.\n"]] audio_occlusion : f32 , # [doc = "**Doppler factor**: How strongly the pitch of a spatial sound shifts as its emitter and listener move towards or away from each other. 1.0 is physically accurate, and 0.0 disables the doppler effect. Defaults to 1.0.\n\nCan be attached to a spatial audio player, or to one of its playing sounds to change it while it plays.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Doppler factor"] , Description ["How strongly the pitch of a spatial sound shifts as its emitter and listener move towards or away from each other. 1.0 is physically accurate, and 0.0 disables the doppler effect. Defaults to 1.0.\nCan be attached to a spatial audio player, or to one of its playing sounds to change it while it plays.\n"]] doppler_factor : f32 , # [doc = "**Doppler shift**: The pitch multiplier applied to a playing spatial sound by the doppler effect. Updated every frame.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Doppler shift"] , Description ["The pitch multiplier applied to a playing spatial sound by the doppler effect. Updated every frame.\n"]] doppler_shift : f32 , # [doc = "**Looping**: Whether or not the audio should loop.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Looping"] , Description ["Whether or not the audio should loop.\n"]] looping : bool , # [doc = "**Loop start**: The frame a looping sound jumps back to when it reaches `loop_end` or the end of the audio. Defaults to 0, or to the loop start in the metadata of the audio if the player has neither `loop_start` nor `loop_end`.\n\nCan be attached to an audio player, or to one of its playing sounds to change it while it plays.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Loop start"] , Description ["The frame a looping sound jumps back to when it reaches `loop_end` or the end of the audio. Defaults to 0, or to the loop start in the metadata of the audio if the player has neither `loop_start` nor `loop_end`.\nCan be attached to an audio player, or to one of its playing sounds to change it while it plays.\n"]] loop_start : u64 , # [doc = "**Loop end**: The frame at which a looping sound jumps back to `loop_start`. If not attached, defaults to the loop end in the metadata of the audio if the player has neither `loop_start` nor `loop_end`, or else loops at the end of the audio.\n\nCan be attached to an audio player, or to one of its playing sounds to change it while it plays.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Loop end"] , Description ["The frame at which a looping sound jumps back to `loop_start`. If not attached, defaults to the loop end in the metadata of the audio if the player has neither `loop_start` nor `loop_end`, or else loops at the end of the audio.\nCan be attached to an audio player, or to one of its playing sounds to change it while it plays.\n"]] loop_end : u64 , # [doc = "**Streaming**: If attached to an audio player, its sounds are decoded from disk as they play instead of being loaded into memory first.\n\nUse this for long audio like music. Streaming is not supported on the web, where this has no effect.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Streaming"] , Description ["If attached to an audio player, its sounds are decoded from disk as they play instead of being loaded into memory first.\nUse this for long audio like music. Streaming is not supported on the web, where this has no effect.\n"]] streaming : () , # [doc = "**One pole low pass filter**: With this component, the audio will be filtered with a one pole low pass filter.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["One pole low pass filter"] , Description ["With this component, the audio will be filtered with a one pole low pass filter.\n"]] onepole_lpf : f32 , # [doc = "**Playing sound**: The entity with this comp is a playing sound.\n\nWe can attach other components to it to control the sound parameters.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Playing sound"] , Description ["The entity with this comp is a playing sound.\nWe can attach other components to it to control the sound parameters.\n"]] playing_sound : () , # [doc = "**Paused**: While attached to a playing sound, the sound is paused.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Paused"] , Description ["While attached to a playing sound, the sound is paused.\n"]] paused : () , # [doc = "**Pitch**: The playback speed of a playing sound, which also shifts its pitch. 1.0 is the original speed, 2.0 is twice as fast and an octave higher.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Pitch"] , Description ["The playback speed of a playing sound, which also shifts its pitch. 1.0 is the original speed, 2.0 is twice as fast and an octave higher.\n"]] pitch : f32 , # [doc = "**Seek to**: Add this to a playing sound to jump to this position, in seconds.\n\nIt is removed once the sound has jumped.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Seek to"] , Description ["Add this to a playing sound to jump to this position, in seconds.\nIt is removed once the sound has jumped.\n"]] seek_to : f32 , # [doc = "**Playback position**: The position of a playing sound, in seconds. Updated every frame.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Playback position"] , Description ["The position of a playing sound, in seconds. Updated every frame.\n"]] playback_position : f32 , # [doc = "**Playback duration**: The duration of a playing sound, in seconds, once it has loaded.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Playback duration"] , Description ["The duration of a playing sound, in seconds, once it has loaded.\n"]] playback_duration : f32 , # [doc = "**Audio bus**: The name of the mixer bus an audio player plays its sounds on, like `music`, `sfx` or `voice`.\n\nDefaults to `sfx`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio bus"] , Description ["The name of the mixer bus an audio player plays its sounds on, like `music`, `sfx` or `voice`.\nDefaults to `sfx`.\n"]] audio_bus : String , # [doc = "**Bus name**: The entity configures the mixer bus with this name, creating it if it does not exist.\n\nThe built-in buses are `master`, `music`, `sfx` and `voice`; all other buses are children of `master` unless they have a `bus_parent`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Bus name"] , Description ["The entity configures the mixer bus with this name, creating it if it does not exist.\nThe built-in buses are `master`, `music`, `sfx` and `voice`; all other buses are children of `master` unless they have a `bus_parent`.\n"]] bus_name : String , # [doc = "**Bus parent**: The name of the bus that the bus configured by this entity is mixed into. Defaults to `master`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Bus parent"] , Description ["The name of the bus that the bus configured by this entity is mixed into. Defaults to `master`.\n"]] bus_parent : String , # [doc = "**Bus gain**: The gain of the bus configured by this entity. Defaults to 1.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Bus gain"] , Description ["The gain of the bus configured by this entity. Defaults to 1.0.\n"]] bus_gain : f32 , # [doc = "**Bus muted**: While attached, the bus configured by this entity is muted. Its sounds keep playing silently.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Bus muted"] , Description ["While attached, the bus configured by this entity is muted. Its sounds keep playing silently.\n"]] bus_muted : () , # [doc = "**Bus low-pass filter**: The cutoff frequency of a one pole low-pass filter applied to the bus configured by this entity.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Bus low-pass filter"] , Description ["The cutoff frequency of a one pole low-pass filter applied to the bus configured by this entity.\n"]] bus_low_pass : f32 , # [doc = "**Bus high-pass filter**: The cutoff frequency of a one pole high-pass filter applied to the bus configured by this entity.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Bus high-pass filter"] , Description ["The cutoff frequency of a one pole high-pass filter applied to the bus configured by this entity.\n"]] bus_high_pass : f32 , # [doc = "**Reverb zone**: If attached, this entity is a reverb zone. Spatial sounds whose emitter is inside it send some of their sound to its reverb.\n\nIts shape is set with `reverb_zone_radius` or `reverb_zone_extents`, around its `local_to_world`. The reverb plays on the bus in `audio_bus`, which defaults to `sfx`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb zone"] , Description ["If attached, this entity is a reverb zone. Spatial sounds whose emitter is inside it send some of their sound to its reverb.\nIts shape is set with `reverb_zone_radius` or `reverb_zone_extents`, around its `local_to_world`. The reverb plays on the bus in `audio_bus`, which defaults to `sfx`.\n"]] reverb_zone : () , # [doc = "**Reverb zone radius**: The radius of a spherical reverb zone.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb zone radius"] , Description ["The radius of a spherical reverb zone.\n"]] reverb_zone_radius : f32 , # [doc = "**Reverb zone extents**: The half extents of a box-shaped reverb zone, in its local space.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb zone extents"] , Description ["The half extents of a box-shaped reverb zone, in its local space.\n"]] reverb_zone_extents : Vec3 , # [doc = "**Reverb zone fade**: The distance outside of a reverb zone over which its reverb fades out, so that moving in and out of it is smooth. Defaults to 1.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb zone fade"] , Description ["The distance outside of a reverb zone over which its reverb fades out, so that moving in and out of it is smooth. Defaults to 1.0.\n"]] reverb_zone_fade : f32 , # [doc = "**Reverb room size**: How long the reverb of a reverb zone or bus rings, from 0 to 1. Defaults to 0.5.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb room size"] , Description ["How long the reverb of a reverb zone or bus rings, from 0 to 1. Defaults to 0.5.\n"]] reverb_room_size : f32 , # [doc = "**Reverb damping**: How quickly the high frequencies of the reverb of a reverb zone or bus die out, from 0 to 1. Defaults to 0.5.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb damping"] , Description ["How quickly the high frequencies of the reverb of a reverb zone or bus die out, from 0 to 1. Defaults to 0.5.\n"]] reverb_damping : f32 , # [doc = "**Reverb wet**: On a reverb zone, how much of the sound of the emitters inside it is sent to its reverb. Defaults to 0.5.\n\nOn a bus entity, adds a reverb to the bus which mixes in this much of the reverberated sound.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb wet"] , Description ["On a reverb zone, how much of the sound of the emitters inside it is sent to its reverb. Defaults to 0.5.\nOn a bus entity, adds a reverb to the bus which mixes in this much of the reverberated sound.\n"]] reverb_wet : f32 , # [doc = "**Reverb dry**: The gain of the direct sound of spatial sounds whose emitter is inside this reverb zone. Defaults to 1.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Reverb dry"] , Description ["The gain of the direct sound of spatial sounds whose emitter is inside this reverb zone. Defaults to 1.0.\n"]] reverb_dry : f32 , # [doc = "**Voice capture**: While this resource is attached on a client, the microphone is captured and sent to the other players as voice chat.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Voice capture"] , Description ["While this resource is attached on a client, the microphone is captured and sent to the other players as voice chat.\n"]] voice_capture : () , # [doc = "**Voice muted**: If attached to a player entity on the server, the voice of the player is not sent to anyone.\n\nIf attached to a player entity on a client, the voice of the player is not played for that client.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Voice muted"] , Description ["If attached to a player entity on the server, the voice of the player is not sent to anyone.\nIf attached to a player entity on a client, the voice of the player is not played for that client.\n"]] voice_muted : () , # [doc = "**Audio priority**: The priority of a spatial audio player, or of one of its playing sounds. When more spatial sounds play than `max_audible_sounds`, those with the lowest priority are virtualized first, and the quietest among sounds of equal priority. Defaults to 0.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio priority"] , Description ["The priority of a spatial audio player, or of one of its playing sounds. When more spatial sounds play than `max_audible_sounds`, those with the lowest priority are virtualized first, and the quietest among sounds of equal priority. Defaults to 0.0.\n"]] audio_priority : f32 , # [doc = "**Max audible sounds**: The maximum number of spatial sounds that are played at once on this client. The others are virtualized: they keep their position, but aren't processed until they are among the loudest again. Defaults to 32.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Max audible sounds"] , Description ["The maximum number of spatial sounds that are played at once on this client. The others are virtualized: they keep their position, but aren't processed until they are among the loudest again. Defaults to 32.\n"]] max_audible_sounds : u32 , # [doc = "**Virtualized**: Attached by the engine to spatial playing sounds which are currently virtualized, as they are too quiet or too many sounds are playing.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Virtualized"] , Description ["Attached by the engine to spatial playing sounds which are currently virtualized, as they are too quiet or too many sounds are playing.\n"]] virtualized : () , # [doc = "**Amplitude fade**: Attach to a playing sound to ramp its `amplitude` to `x` over `y` seconds. The ramp happens on the audio thread, and `amplitude` is set to `x` once it has started. Removed when the ramp starts.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Amplitude fade"] , Description ["Attach to a playing sound to ramp its `amplitude` to `x` over `y` seconds. The ramp happens on the audio thread, and `amplitude` is set to `x` once it has started. Removed when the ramp starts.\n"]] amplitude_fade : Vec2 , # [doc = "**Pitch fade**: Attach to a playing sound to ramp its `pitch` to `x` over `y` seconds, see `amplitude_fade`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Pitch fade"] , Description ["Attach to a playing sound to ramp its `pitch` to `x` over `y` seconds, see `amplitude_fade`.\n"]] pitch_fade : Vec2 , # [doc = "**One-pole low-pass filter fade**: Attach to a playing sound of an audio player to ramp the cutoff of its `onepole_lpf` to `x` Hz over `y` seconds, see `amplitude_fade`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["One-pole low-pass filter fade"] , Description ["Attach to a playing sound of an audio player to ramp the cutoff of its `onepole_lpf` to `x` Hz over `y` seconds, see `amplitude_fade`.\n"]] onepole_lpf_fade : Vec2 , # [doc = "**Active audio listener**: If attached as a resource, every spatial sound is heard from this entity instead of its `spatial_audio_listener`. Switch it at runtime to change the camera sounds are heard from, like in spectator modes.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Active audio listener"] , Description ["If attached as a resource, every spatial sound is heard from this entity instead of its `spatial_audio_listener`. Switch it at runtime to change the camera sounds are heard from, like in spectator modes.\n"]] active_audio_listener : EntityId , # [doc = "**Audio metering**: If attached as a resource, the loudness of the master bus is measured, and updated every 100 ms in `audio_rms`, `audio_peak`, `audio_momentary_loudness` and `audio_integrated_loudness`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio metering"] , Description ["If attached as a resource, the loudness of the master bus is measured, and updated every 100 ms in `audio_rms`, `audio_peak`, `audio_momentary_loudness` and `audio_integrated_loudness`.\n"]] audio_metering : () , # [doc = "**Audio RMS**: The RMS level of the last 400 ms of the master bus, in dBFS. Negative infinity for silence. Measured while `audio_metering` is attached.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio RMS"] , Description ["The RMS level of the last 400 ms of the master bus, in dBFS. Negative infinity for silence. Measured while `audio_metering` is attached.\n"]] audio_rms : f32 , # [doc = "**Audio peak**: The highest sample of the last 400 ms of the master bus, in dBFS. Negative infinity for silence. Measured while `audio_metering` is attached.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio peak"] , Description ["The highest sample of the last 400 ms of the master bus, in dBFS. Negative infinity for silence. Measured while `audio_metering` is attached.\n"]] audio_peak : f32 , # [doc = "**Audio momentary loudness**: The loudness of the last 400 ms of the master bus, in LUFS. Measured while `audio_metering` is attached.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio momentary loudness"] , Description ["The loudness of the last 400 ms of the master bus, in LUFS. Measured while `audio_metering` is attached.\n"]] audio_momentary_loudness : f32 , # [doc = "**Audio integrated loudness**: The loudness of the master bus since `audio_metering` was attached, in LUFS. Silent parts are left out.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio integrated loudness"] , Description ["The loudness of the master bus since `audio_metering` was attached, in LUFS. Silent parts are left out.\n"]] audio_integrated_loudness : f32 , # [doc = "**Audio recording**: If attached as a resource, the master bus is recorded to `recordings/<name>.wav` in the working directory until it is removed. Only the file name of the value is used. Not supported on the web.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio recording"] , Description ["If attached as a resource, the master bus is recorded to `recordings/<name>.wav` in the working directory until it is removed. Only the file name of the value is used. Not supported on the web.\n"]] audio_recording : String , # [doc = "**Audio time**: The time of the audio clock of this client, in seconds of audio output since it started. Updated every frame; sounds scheduled with `start_at_audio_time` should be a little ahead of it.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio time"] , Description ["The time of the audio clock of this client, in seconds of audio output since it started. Updated every frame; sounds scheduled with `start_at_audio_time` should be a little ahead of it.\n"]] audio_time : f64 , # [doc = "**Start at audio time**: Starts this playing sound on the exact sample at this `audio_time`, rather than as soon as it has loaded. Sounds scheduled in the past start right away. Must be attached when the sound is played.\n\n\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Start at audio time"] , Description ["Starts this playing sound on the exact sample at this `audio_time`, rather than as soon as it has loaded. Sounds scheduled in the past start right away. Must be attached when the sound is played.\n"]] start_at_audio_time : f64 , # [doc = "**Amplitude**: The amplitude of the audio.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Amplitude"] , Description ["The amplitude of the audio.\n"]] amplitude : f32 , # [doc = "**Panning**: The panning of the audio.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Panning"] , Description ["The panning of the audio.\n"]] panning : f32 , # [doc = "**Low_pass filter**: Low pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Low_pass filter"] , Description ["Low pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n"]] lpf : Vec2 , # [doc = "**High_pass filter**: High pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["High_pass filter"] , Description ["High pass filter. The first value is the cutoff frequency, the second is the bandwidth.\n"]] hpf : Vec2 , # [doc = "**Audio URL**: The URL of the assets.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Audio URL"] , Description ["The URL of the assets.\n"]] audio_url : String , # [doc = "**Trigger at this frame**: The system will watch for this component and PLAY the audio at this frame,\n\nusing the other components as parameters.\n\nThen set it back to false.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Trigger at this frame"] , Description ["The system will watch for this component and PLAY the audio at this frame,\nusing the other components as parameters.\nThen set it back to false.\n"]] play_now : () , # [doc = "**Stop at this frame**: The system will watch for this component and STOP the audio at this frame,\n\nusing the other components as parameters.\n\nThen set it back to false.\n\n\n\n*Attributes*: MaybeResource, Debuggable"] @ [MaybeResource , Debuggable , Name ["Stop at this frame"] , Description ["The system will watch for this component and STOP the audio at this frame,\nusing the other components as parameters.\nThen set it back to false.\n"]] stop_now : () , });
             }
         }
         pub mod camera {
@@ -78,7 +129,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("camera" , { # [doc = "**Active camera**: The camera with the highest `active_camera` value will be used for rendering. Cameras are also filtered by the `user_id`.\n\nIf there's no `user_id`, the camera is considered global and potentially applies to all users (if its `active_camera` value is high enough).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Active camera"] , Description ["The camera with the highest `active_camera` value will be used for rendering. Cameras are also filtered by the `user_id`.\nIf there's no `user_id`, the camera is considered global and potentially applies to all users (if its `active_camera` value is high enough)."]] active_camera : f32 , # [doc = "**Aspect ratio**: The aspect ratio of this camera.\n\nIf `aspect_ratio_from_window` is set, this will be automatically updated to match the window.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Aspect ratio"] , Description ["The aspect ratio of this camera.\nIf `aspect_ratio_from_window` is set, this will be automatically updated to match the window."]] aspect_ratio : f32 , # [doc = "**Aspect ratio from window**: If attached, the `aspect_ratio` component will be automatically updated to match the aspect ratio of the window. Should point to an entity with a `window_physical_size` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Aspect ratio from window"] , Description ["If attached, the `aspect_ratio` component will be automatically updated to match the aspect ratio of the window. Should point to an entity with a `window_physical_size` component."]] aspect_ratio_from_window : EntityId , # [doc = "**Far plane**: The far plane of this camera, measured in meters.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Far plane"] , Description ["The far plane of this camera, measured in meters."]] far : f32 , # [doc = "**Fog**: If attached, this camera will see/render fog.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog"] , Description ["If attached, this camera will see/render fog."]] fog : () , # [doc = "**Field of View Y**: The field of view of this camera in the Y/vertical direction, measured in radians.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Field of View Y"] , Description ["The field of view of this camera in the Y/vertical direction, measured in radians."]] fovy : f32 , # [doc = "**Near plane**: The near plane of this camera, measured in meters.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Near plane"] , Description ["The near plane of this camera, measured in meters."]] near : f32 , # [doc = "**Orthographic projection**: If attached, this camera will use a standard orthographic projection matrix.\n\nEnsure that the `orthographic_` components are set, including `left`, right`, `top` and `bottom`, as well as `near` and `far`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic projection"] , Description ["If attached, this camera will use a standard orthographic projection matrix.\nEnsure that the `orthographic_` components are set, including `left`, right`, `top` and `bottom`, as well as `near` and `far`."]] orthographic : () , # [doc = "**Orthographic bottom**: The bottom bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic bottom"] , Description ["The bottom bound for this `orthographic` camera."]] orthographic_bottom : f32 , # [doc = "**Orthographic from window**: The bounds of this orthographic camera will be updated to match the window automatically. Should point to an entity with a `window_logical_size` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic from window"] , Description ["The bounds of this orthographic camera will be updated to match the window automatically. Should point to an entity with a `window_logical_size` component."]] orthographic_from_window : EntityId , # [doc = "**Orthographic left**: The left bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic left"] , Description ["The left bound for this `orthographic` camera."]] orthographic_left : f32 , # [doc = "**Orthographic right**: The right bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic right"] , Description ["The right bound for this `orthographic` camera."]] orthographic_right : f32 , # [doc = "**Orthographic top**: The top bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic top"] , Description ["The top bound for this `orthographic` camera."]] orthographic_top : f32 , # [doc = "**Perspective projection**: If attached, this camera will use a standard perspective projection matrix.\n\nEnsure that `near` and `far` are set.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Perspective projection"] , Description ["If attached, this camera will use a standard perspective projection matrix.\nEnsure that `near` and `far` are set."]] perspective : () , # [doc = "**Perspective-infinite-reverse projection**: If attached, this camera will use a perspective-infinite-reverse projection matrix.\n\nThis is well-suited for rendering large worlds as it has no far plane. Ensure `near` is set.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Perspective-infinite-reverse projection"] , Description ["If attached, this camera will use a perspective-infinite-reverse projection matrix.\nThis is well-suited for rendering large worlds as it has no far plane. Ensure `near` is set."]] perspective_infinite_reverse : () , # [doc = "**Projection**: The projection matrix of this camera.\n\nThis can be driven by other components, including `perspective` and `perspective_infinite_reverse`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Projection"] , Description ["The projection matrix of this camera.\nThis can be driven by other components, including `perspective` and `perspective_infinite_reverse`."]] projection : Mat4 , # [doc = "**Projection-view**: The composition of the projection and view (inverse-local-to-world) matrices.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Projection-view"] , Description ["The composition of the projection and view (inverse-local-to-world) matrices."]] projection_view : Mat4 , # [doc = "**Shadows far plane**: The far plane for the shadow camera, measured in meters.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Shadows far plane"] , Description ["The far plane for the shadow camera, measured in meters."]] shadows_far : f32 , });
+                components ! ("camera" , { # [doc = "**Active camera**: The camera with the highest `active_camera` value will be used for rendering. Cameras are also filtered by the `user_id`.\n\nIf there's no `user_id`, the camera is considered global and potentially applies to all users (if its `active_camera` value is high enough).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Active camera"] , Description ["The camera with the highest `active_camera` value will be used for rendering. Cameras are also filtered by the `user_id`.\nIf there's no `user_id`, the camera is considered global and potentially applies to all users (if its `active_camera` value is high enough)."]] active_camera : f32 , # [doc = "**Aspect ratio**: The aspect ratio of this camera.\n\nIf `aspect_ratio_from_window` is set, this will be automatically updated to match the window.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Aspect ratio"] , Description ["The aspect ratio of this camera.\nIf `aspect_ratio_from_window` is set, this will be automatically updated to match the window."]] aspect_ratio : f32 , # [doc = "**Aspect ratio from window**: If attached, the `aspect_ratio` component will be automatically updated to match the aspect ratio of the window. Should point to an entity with a `window_physical_size` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Aspect ratio from window"] , Description ["If attached, the `aspect_ratio` component will be automatically updated to match the aspect ratio of the window. Should point to an entity with a `window_physical_size` component."]] aspect_ratio_from_window : EntityId , # [doc = "**Far plane**: The far plane of this camera, measured in meters.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Far plane"] , Description ["The far plane of this camera, measured in meters."]] far : f32 , # [doc = "**Fog**: If attached, this camera will see/render fog.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog"] , Description ["If attached, this camera will see/render fog."]] fog : () , # [doc = "**Field of View Y**: The field of view of this camera in the Y/vertical direction, measured in radians.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Field of View Y"] , Description ["The field of view of this camera in the Y/vertical direction, measured in radians."]] fovy : f32 , # [doc = "**Near plane**: The near plane of this camera, measured in meters.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Near plane"] , Description ["The near plane of this camera, measured in meters."]] near : f32 , # [doc = "**Orthographic projection**: If attached, this camera will use a standard orthographic projection matrix.\n\nEnsure that the `orthographic_` components are set, including `left`, right`, `top` and `bottom`, as well as `near` and `far`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic projection"] , Description ["If attached, this camera will use a standard orthographic projection matrix.\nEnsure that the `orthographic_` components are set, including `left`, right`, `top` and `bottom`, as well as `near` and `far`."]] orthographic : () , # [doc = "**Orthographic bottom**: The bottom bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic bottom"] , Description ["The bottom bound for this `orthographic` camera."]] orthographic_bottom : f32 , # [doc = "**Orthographic from window**: The bounds of this orthographic camera will be updated to match the window automatically. Should point to an entity with a `window_logical_size` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic from window"] , Description ["The bounds of this orthographic camera will be updated to match the window automatically. Should point to an entity with a `window_logical_size` component."]] orthographic_from_window : EntityId , # [doc = "**Orthographic left**: The left bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic left"] , Description ["The left bound for this `orthographic` camera."]] orthographic_left : f32 , # [doc = "**Orthographic right**: The right bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic right"] , Description ["The right bound for this `orthographic` camera."]] orthographic_right : f32 , # [doc = "**Orthographic top**: The top bound for this `orthographic` camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Orthographic top"] , Description ["The top bound for this `orthographic` camera."]] orthographic_top : f32 , # [doc = "**Perspective projection**: If attached, this camera will use a standard perspective projection matrix.\n\nEnsure that `near` and `far` are set.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Perspective projection"] , Description ["If attached, this camera will use a standard perspective projection matrix.\nEnsure that `near` and `far` are set."]] perspective : () , # [doc = "**Perspective-infinite-reverse projection**: If attached, this camera will use a perspective-infinite-reverse projection matrix.\n\nThis is well-suited for rendering large worlds as it has no far plane. Ensure `near` is set.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Perspective-infinite-reverse projection"] , Description ["If attached, this camera will use a perspective-infinite-reverse projection matrix.\nThis is well-suited for rendering large worlds as it has no far plane. Ensure `near` is set."]] perspective_infinite_reverse : () , # [doc = "**Projection**: The projection matrix of this camera.\n\nThis can be driven by other components, including `perspective` and `perspective_infinite_reverse`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Projection"] , Description ["The projection matrix of this camera.\nThis can be driven by other components, including `perspective` and `perspective_infinite_reverse`."]] projection : Mat4 , # [doc = "**Projection-view**: The composition of the projection and view (inverse-local-to-world) matrices.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Projection-view"] , Description ["The composition of the projection and view (inverse-local-to-world) matrices."]] projection_view : Mat4 , # [doc = "**Shadows far plane**: The far plane for the shadow camera, measured in meters.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Shadows far plane"] , Description ["The far plane for the shadow camera, measured in meters."]] shadows_far : f32 , # [doc = "**Camera follow**: If attached, this camera follows the given entity on the client: it stays at `camera_follow_offset` from it, and looks at it if the camera has a `lookat_target`. It catches up with the entity smoothly, see `camera_follow_damping` and `camera_follow_look_ahead`. This replaces the `translation` and `lookat_target` of the camera every frame.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Camera follow"] , Description ["If attached, this camera follows the given entity on the client: it stays at `camera_follow_offset` from it, and looks at it if the camera has a `lookat_target`. It catches up with the entity smoothly, see `camera_follow_damping` and `camera_follow_look_ahead`. This replaces the `translation` and `lookat_target` of the camera every frame."]] camera_follow : EntityId , # [doc = "**Camera follow offset**: The position of a camera with `camera_follow` relative to the entity it follows, in world space. Defaults to zero if not attached.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Camera follow offset"] , Description ["The position of a camera with `camera_follow` relative to the entity it follows, in world space. Defaults to zero if not attached."]] camera_follow_offset : Vec3 , # [doc = "**Camera follow damping**: Roughly how long, in seconds, a camera with `camera_follow` takes to catch up with the entity it follows. Zero keeps it exactly in place. Defaults to 0.2 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.2"] @ [Debuggable , Networked , Store , Name ["Camera follow damping"] , Description ["Roughly how long, in seconds, a camera with `camera_follow` takes to catch up with the entity it follows. Zero keeps it exactly in place. Defaults to 0.2 if not attached."]] camera_follow_damping : f32 , # [doc = "**Camera follow look-ahead**: How far ahead of the entity it follows a camera with `camera_follow` aims, in seconds of the entity's velocity, so that players see where they are going. Defaults to 0 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["Camera follow look-ahead"] , Description ["How far ahead of the entity it follows a camera with `camera_follow` aims, in seconds of the entity's velocity, so that players see where they are going. Defaults to 0 if not attached."]] camera_follow_look_ahead : f32 , # [doc = "**Camera field of view Y target**: If attached, the `fovy` of this camera moves smoothly towards this value, in radians, over `camera_fovy_transition_time`. For a FOV kick, set `fovy` directly and let it ease back to the target.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Camera field of view Y target"] , Description ["If attached, the `fovy` of this camera moves smoothly towards this value, in radians, over `camera_fovy_transition_time`. For a FOV kick, set `fovy` directly and let it ease back to the target."]] camera_fovy_target : f32 , # [doc = "**Camera field of view Y transition time**: Roughly how long, in seconds, the `fovy` of a camera takes to reach its `camera_fovy_target`. Defaults to 0.25 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.25"] @ [Debuggable , Networked , Store , Name ["Camera field of view Y transition time"] , Description ["Roughly how long, in seconds, the `fovy` of a camera takes to reach its `camera_fovy_target`. Defaults to 0.25 if not attached."]] camera_fovy_transition_time : f32 , # [doc = "**Camera shake trauma**: If attached, this camera shakes on the client. The trauma goes from 0 to 1, and the shake grows with its square; add to it on hits and explosions. It decays by `camera_shake_decay` every second.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Camera shake trauma"] , Description ["If attached, this camera shakes on the client. The trauma goes from 0 to 1, and the shake grows with its square; add to it on hits and explosions. It decays by `camera_shake_decay` every second."]] camera_shake_trauma : f32 , # [doc = "**Camera shake decay**: How much `camera_shake_trauma` the camera loses every second. Defaults to 1 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Camera shake decay"] , Description ["How much `camera_shake_trauma` the camera loses every second. Defaults to 1 if not attached."]] camera_shake_decay : f32 , # [doc = "**Camera shake max angle**: How far a camera with a `camera_shake_trauma` of 1 turns around each of its axes, in radians. Defaults to 0.1 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.1"] @ [Debuggable , Networked , Store , Name ["Camera shake max angle"] , Description ["How far a camera with a `camera_shake_trauma` of 1 turns around each of its axes, in radians. Defaults to 0.1 if not attached."]] camera_shake_max_angle : f32 , # [doc = "**Camera shake max offset**: How far a camera with a `camera_shake_trauma` of 1 moves along each of its axes, in meters. Defaults to 0 if not attached, as turning the camera is usually enough.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["Camera shake max offset"] , Description ["How far a camera with a `camera_shake_trauma` of 1 moves along each of its axes, in meters. Defaults to 0 if not attached, as turning the camera is usually enough."]] camera_shake_max_offset : f32 , # [doc = "**Camera shake frequency**: How fast a camera with a `camera_shake_trauma` shakes, in changes of direction per second. Defaults to 15 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 15.0"] @ [Debuggable , Networked , Store , Name ["Camera shake frequency"] , Description ["How fast a camera with a `camera_shake_trauma` shakes, in changes of direction per second. Defaults to 15 if not attached."]] camera_shake_frequency : f32 , });
             }
         }
         pub mod ecs {
@@ -133,7 +184,8 @@ mod raw {
             pub mod messages {
                 use crate::{Entity, EntityId};
                 use ambient_package_rt::message_serde::{
-                    Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 #[derive(Clone, Debug)]
@@ -221,7 +273,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("layout" , { # [doc = "**Align horizontal**: Layout alignment: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align horizontal"] , Description ["Layout alignment: horizontal."]] align_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Align vertical**: Layout alignment: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align vertical"] , Description ["Layout alignment: vertical."]] align_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Docking**: Layout docking.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Docking"] , Description ["Layout docking."]] docking : crate :: generated :: raw :: ambient_core :: layout :: types :: Docking , # [doc = "**Fit horizontal**: Layout fit: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit horizontal"] , Description ["Layout fit: horizontal."]] fit_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Fit vertical**: Layout fit: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit vertical"] , Description ["Layout fit: vertical."]] fit_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Layout**: Layout.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Layout"] , Description ["Layout."]] layout : crate :: generated :: raw :: ambient_core :: layout :: types :: Layout , # [doc = "**Orientation**: Layout orientation.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Orientation"] , Description ["Layout orientation."]] orientation : crate :: generated :: raw :: ambient_core :: layout :: types :: Orientation , # [doc = "**Is book file**: This is a file in a `layout_bookcase`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is book file"] , Description ["This is a file in a `layout_bookcase`."]] is_book_file : () , # [doc = "**Margin**: Layout margin: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Margin"] , Description ["Layout margin: [top, right, bottom, left]."]] margin : Vec4 , # [doc = "**Padding**: Layout padding: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Padding"] , Description ["Layout padding: [top, right, bottom, left]."]] padding : Vec4 , # [doc = "**Mesh to local from size**: Update the `mesh_to_local` based on the width and height of this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Mesh to local from size"] , Description ["Update the `mesh_to_local` based on the width and height of this entity."]] mesh_to_local_from_size : () , # [doc = "**Minimum height**: The minimum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum height"] , Description ["The minimum height of a UI element."]] min_height : f32 , # [doc = "**Minimum width**: The minimum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum width"] , Description ["The minimum width of a UI element."]] min_width : f32 , # [doc = "**Maximum height**: The maximum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum height"] , Description ["The maximum height of a UI element."]] max_height : f32 , # [doc = "**Maximum width**: The maximum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum width"] , Description ["The maximum width of a UI element."]] max_width : f32 , # [doc = "**Is screen**: This entity will be treated as a screen. Used by the Screen ui component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is screen"] , Description ["This entity will be treated as a screen. Used by the Screen ui component."]] is_screen : () , # [doc = "**Space between items**: Space between items in a layout.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Space between items"] , Description ["Space between items in a layout."]] space_between_items : f32 , # [doc = "**Width**: The width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Width"] , Description ["The width of a UI element."]] width : f32 , # [doc = "**Height**: The height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Height"] , Description ["The height of a UI element."]] height : f32 , # [doc = "**GPU UI size**: Upload the width and height of this UI element to the GPU.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["GPU UI size"] , Description ["Upload the width and height of this UI element to the GPU."]] gpu_ui_size : Vec4 , });
+                components ! ("layout" , { # [doc = "**Align horizontal**: Layout alignment: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align horizontal"] , Description ["Layout alignment: horizontal."]] align_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Align vertical**: Layout alignment: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align vertical"] , Description ["Layout alignment: vertical."]] align_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Align , # [doc = "**Docking**: Layout docking.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Docking"] , Description ["Layout docking."]] docking : crate :: generated :: raw :: ambient_core :: layout :: types :: Docking , # [doc = "**Fit horizontal**: Layout fit: horizontal.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit horizontal"] , Description ["Layout fit: horizontal."]] fit_horizontal : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Fit vertical**: Layout fit: vertical.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Fit vertical"] , Description ["Layout fit: vertical."]] fit_vertical : crate :: generated :: raw :: ambient_core :: layout :: types :: Fit , # [doc = "**Layout**: Layout.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Layout"] , Description ["Layout."]] layout : crate :: generated :: raw :: ambient_core :: layout :: types :: Layout , # [doc = "**Orientation**: Layout orientation.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Orientation"] , Description ["Layout orientation."]] orientation : crate :: generated :: raw :: ambient_core :: layout :: types :: Orientation , # [doc = "**Is book file**: This is a file in a `layout_bookcase`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is book file"] , Description ["This is a file in a `layout_bookcase`."]] is_book_file : () , # [doc = "**Margin**: Layout margin: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Margin"] , Description ["Layout margin: [top, right, bottom, left]."]] margin : Vec4 , # [doc = "**Padding**: Layout padding: [top, right, bottom, left].\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Padding"] , Description ["Layout padding: [top, right, bottom, left]."]] padding : Vec4 , # [doc = "**Mesh to local from size**: Update the `mesh_to_local` based on the width and height of this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Mesh to local from size"] , Description ["Update the `mesh_to_local` based on the width and height of this entity."]] mesh_to_local_from_size : () , # [doc = "**Minimum height**: The minimum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum height"] , Description ["The minimum height of a UI element."]] min_height : f32 , # [doc = "**Minimum width**: The minimum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Minimum width"] , Description ["The minimum width of a UI element."]] min_width : f32 , # [doc = "**Maximum height**: The maximum height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum height"] , Description ["The maximum height of a UI element."]] max_height : f32 , # [doc = "**Maximum width**: The maximum width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Maximum width"] , Description ["The maximum width of a UI element."]] max_width : f32 , # [doc = "**Is screen**: This entity will be treated as a screen. Used by the Screen ui component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is screen"] , Description ["This entity will be treated as a screen. Used by the Screen ui component."]] is_screen : () , # [doc = "**Space between items**: Space between items in a layout.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Space between items"] , Description ["Space between items in a layout."]] space_between_items : f32 , # [doc = "**Width**: The width of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Width"] , Description ["The width of a UI element."]] width : f32 , # [doc = "**Height**: The height of a UI element.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Height"] , Description ["The height of a UI element."]] height : f32 , # [doc = "**GPU UI size**: Upload the width and height of this UI element to the GPU.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["GPU UI size"] , Description ["Upload the width and height of this UI element to the GPU."]] gpu_ui_size : Vec4 , # [doc = "**Flex wrap**: Whether the children of a `Flex` layout wrap onto new lines when they don't fit along its `orientation`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: false"] @ [Debuggable , Networked , Store , Name ["Flex wrap"] , Description ["Whether the children of a `Flex` layout wrap onto new lines when they don't fit along its `orientation`."]] flex_wrap : bool , # [doc = "**Justify content**: How a `Flex` layout distributes the space left along its `orientation` between its children.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Justify content"] , Description ["How a `Flex` layout distributes the space left along its `orientation` between its children."]] justify_content : crate :: generated :: raw :: ambient_core :: layout :: types :: Justify , # [doc = "**Align items**: How a `Flex` layout aligns its children across its `orientation`, within their line.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align items"] , Description ["How a `Flex` layout aligns its children across its `orientation`, within their line."]] align_items : crate :: generated :: raw :: ambient_core :: layout :: types :: FlexAlign , # [doc = "**Align self**: How this child of a `Flex` layout is aligned across its `orientation`, overriding the layout's `align_items`.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Align self"] , Description ["How this child of a `Flex` layout is aligned across its `orientation`, overriding the layout's `align_items`."]] align_self : crate :: generated :: raw :: ambient_core :: layout :: types :: FlexAlign , # [doc = "**Flex grow**: How much of the space left along a `Flex` layout's `orientation` this child takes, relative to the other children.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["Flex grow"] , Description ["How much of the space left along a `Flex` layout's `orientation` this child takes, relative to the other children."]] flex_grow : f32 , # [doc = "**Flex shrink**: How much this child of a `Flex` layout shrinks, relative to the other children, when they don't fit along its `orientation`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["Flex shrink"] , Description ["How much this child of a `Flex` layout shrinks, relative to the other children, when they don't fit along its `orientation`."]] flex_shrink : f32 , # [doc = "**Flex basis**: The size of this child of a `Flex` layout along its `orientation`, before growing or shrinking.\n\nDefaults to the child's own size, like `auto` in CSS. The layout keeps the size a growing or shrinking child had before it was resized as its basis, until the child is resized by something else.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Flex basis"] , Description ["The size of this child of a `Flex` layout along its `orientation`, before growing or shrinking.\nDefaults to the child's own size, like `auto` in CSS. The layout keeps the size a growing or shrinking child had before it was resized as its basis, until the child is resized by something else."]] flex_basis : f32 , # [doc = "**Grid columns**: The sizes of the columns of a `Grid` layout.\n\nPositive sizes are in pixels, 0 fits the column to its children, and negative sizes are shares of the space left: `[200., -1., -2.]` is a 200 pixel column followed by two columns sharing the rest 1:2.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid columns"] , Description ["The sizes of the columns of a `Grid` layout.\nPositive sizes are in pixels, 0 fits the column to its children, and negative sizes are shares of the space left: `[200., -1., -2.]` is a 200 pixel column followed by two columns sharing the rest 1:2."]] grid_columns : Vec :: < f32 > , # [doc = "**Grid rows**: The sizes of the rows of a `Grid` layout, like `grid_columns`.\n\nRows past these are fitted to their children.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid rows"] , Description ["The sizes of the rows of a `Grid` layout, like `grid_columns`.\nRows past these are fitted to their children."]] grid_rows : Vec :: < f32 > , # [doc = "**Grid column**: The column of a `Grid` layout this child is placed in, from 0. Children without a `grid_column` and `grid_row` fill the next free cells, row by row.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid column"] , Description ["The column of a `Grid` layout this child is placed in, from 0. Children without a `grid_column` and `grid_row` fill the next free cells, row by row."]] grid_column : u32 , # [doc = "**Grid row**: The row of a `Grid` layout this child is placed in, from 0.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Grid row"] , Description ["The row of a `Grid` layout this child is placed in, from 0."]] grid_row : u32 , # [doc = "**Grid column span**: The number of columns of a `Grid` layout this child covers.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1"] @ [Debuggable , Networked , Store , Name ["Grid column span"] , Description ["The number of columns of a `Grid` layout this child covers."]] grid_column_span : u32 , # [doc = "**Grid row span**: The number of rows of a `Grid` layout this child covers.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1"] @ [Debuggable , Networked , Store , Name ["Grid row span"] , Description ["The number of rows of a `Grid` layout this child covers."]] grid_row_span : u32 , });
             }
             #[doc = r" Auto-generated type definitions."]
             pub mod types {
@@ -479,6 +531,10 @@ mod raw {
                     Bookcase,
                     #[doc = "Width to children."]
                     WidthToChildren,
+                    #[doc = "Flexbox layout: children grow, shrink, wrap and are aligned along its orientation."]
+                    Flex,
+                    #[doc = "Grid layout: children are placed in columns and rows."]
+                    Grid,
                 }
                 impl crate::EnumComponent for Layout {
                     fn to_u32(&self) -> u32 {
@@ -487,6 +543,8 @@ mod raw {
                             Self::Dock => Layout::Dock as u32,
                             Self::Bookcase => Layout::Bookcase as u32,
                             Self::WidthToChildren => Layout::WidthToChildren as u32,
+                            Self::Flex => Layout::Flex as u32,
+                            Self::Grid => Layout::Grid as u32,
                         }
                     }
                     fn from_u32(value: u32) -> Option<Self> {
@@ -502,6 +560,12 @@ mod raw {
                         if value == Layout::WidthToChildren as u32 {
                             return Some(Self::WidthToChildren);
                         }
+                        if value == Layout::Flex as u32 {
+                            return Some(Self::Flex);
+                        }
+                        if value == Layout::Grid as u32 {
+                            return Some(Self::Grid);
+                        }
                         None
                     }
                 }
@@ -519,6 +583,142 @@ mod raw {
                             .ok_or(MessageSerdeError::InvalidValue)
                     }
                 }
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**Justify**: How the space left along a layout is distributed."]
+                pub enum Justify {
+                    #[default]
+                    #[doc = "The children are packed at the beginning."]
+                    Begin,
+                    #[doc = "The children are packed in the center."]
+                    Center,
+                    #[doc = "The children are packed at the end."]
+                    End,
+                    #[doc = "The space is shared between the children, with none before the first or after the last."]
+                    SpaceBetween,
+                    #[doc = "The space is shared around each child, so the ends get half as much as between two children."]
+                    SpaceAround,
+                    #[doc = "The space is shared evenly between the children and the ends."]
+                    SpaceEvenly,
+                }
+                impl crate::EnumComponent for Justify {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Begin => Justify::Begin as u32,
+                            Self::Center => Justify::Center as u32,
+                            Self::End => Justify::End as u32,
+                            Self::SpaceBetween => Justify::SpaceBetween as u32,
+                            Self::SpaceAround => Justify::SpaceAround as u32,
+                            Self::SpaceEvenly => Justify::SpaceEvenly as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == Justify::Begin as u32 {
+                            return Some(Self::Begin);
+                        }
+                        if value == Justify::Center as u32 {
+                            return Some(Self::Center);
+                        }
+                        if value == Justify::End as u32 {
+                            return Some(Self::End);
+                        }
+                        if value == Justify::SpaceBetween as u32 {
+                            return Some(Self::SpaceBetween);
+                        }
+                        if value == Justify::SpaceAround as u32 {
+                            return Some(Self::SpaceAround);
+                        }
+                        if value == Justify::SpaceEvenly as u32 {
+                            return Some(Self::SpaceEvenly);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for Justify {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**FlexAlign**: How a child is aligned across a `Flex` layout."]
+                pub enum FlexAlign {
+                    #[default]
+                    #[doc = "Begin"]
+                    Begin,
+                    #[doc = "Center"]
+                    Center,
+                    #[doc = "End"]
+                    End,
+                    #[doc = "The child is stretched to the size of its line."]
+                    Stretch,
+                }
+                impl crate::EnumComponent for FlexAlign {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Begin => FlexAlign::Begin as u32,
+                            Self::Center => FlexAlign::Center as u32,
+                            Self::End => FlexAlign::End as u32,
+                            Self::Stretch => FlexAlign::Stretch as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == FlexAlign::Begin as u32 {
+                            return Some(Self::Begin);
+                        }
+                        if value == FlexAlign::Center as u32 {
+                            return Some(Self::Center);
+                        }
+                        if value == FlexAlign::End as u32 {
+                            return Some(Self::End);
+                        }
+                        if value == FlexAlign::Stretch as u32 {
+                            return Some(Self::Stretch);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for FlexAlign {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod model {
@@ -550,7 +750,236 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("network" , { # [doc = "**Is remote entity**: If attached, this entity was not spawned locally (e.g. if this is the client, it was spawned by the server).\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is remote entity"] , Description ["If attached, this entity was not spawned locally (e.g. if this is the client, it was spawned by the server)."]] is_remote_entity : () , # [doc = "**Is persistent resources**: If attached, this entity contains global resources that are persisted to disk and synchronized to clients.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is persistent resources"] , Description ["If attached, this entity contains global resources that are persisted to disk and synchronized to clients."]] is_persistent_resources : () , # [doc = "**Is synchronized resources**: If attached, this entity contains global resources that are synchronized to clients, but not persisted.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is synchronized resources"] , Description ["If attached, this entity contains global resources that are synchronized to clients, but not persisted."]] is_synced_resources : () , # [doc = "**No sync**: If attached, this entity will not be synchronized to clients.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["No sync"] , Description ["If attached, this entity will not be synchronized to clients."]] no_sync : () , });
+                components ! ("network" , { # [doc = "**Is remote entity**: If attached, this entity was not spawned locally (e.g. if this is the client, it was spawned by the server).\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is remote entity"] , Description ["If attached, this entity was not spawned locally (e.g. if this is the client, it was spawned by the server)."]] is_remote_entity : () , # [doc = "**Is persistent resources**: If attached, this entity contains global resources that are persisted to disk and synchronized to clients.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is persistent resources"] , Description ["If attached, this entity contains global resources that are persisted to disk and synchronized to clients."]] is_persistent_resources : () , # [doc = "**Is synchronized resources**: If attached, this entity contains global resources that are synchronized to clients, but not persisted.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Is synchronized resources"] , Description ["If attached, this entity contains global resources that are synchronized to clients, but not persisted."]] is_synced_resources : () , # [doc = "**No sync**: If attached, this entity will not be synchronized to clients.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["No sync"] , Description ["If attached, this entity will not be synchronized to clients."]] no_sync : () , # [doc = "**Replication radius**: On a player entity, or as a resource for every player: only entities within this distance of the player's `replication_center` are replicated to them, while those that aren't positioned are always replicated. Entities that are children are replicated along with their root.\n\nUntil the player has a `replication_center`, positioned entities are not replicated to them at all.\n\n\n\n*Attributes*: Debuggable, MaybeResource"] @ [Debuggable , MaybeResource , Name ["Replication radius"] , Description ["On a player entity, or as a resource for every player: only entities within this distance of the player's `replication_center` are replicated to them, while those that aren't positioned are always replicated. Entities that are children are replicated along with their root.\nUntil the player has a `replication_center`, positioned entities are not replicated to them at all.\n"]] replication_radius : f32 , # [doc = "**Replication center**: The position around which entities are replicated to this player, see `replication_radius`. Usually kept at the position of the player's character or camera.\n\n\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Replication center"] , Description ["The position around which entities are replicated to this player, see `replication_radius`. Usually kept at the position of the player's character or camera.\n"]] replication_center : Vec3 , # [doc = "**Always replicated**: If attached, this entity is replicated to every player regardless of their `replication_radius`.\n\n\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Always replicated"] , Description ["If attached, this entity is replicated to every player regardless of their `replication_radius`.\n"]] always_replicated : () , # [doc = "**Interest group**: If attached, this entity is only replicated to the players whose `interest_groups` contain this group, regardless of their `replication_radius`. Useful for team-only entities or per-room state.\n\n\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Interest group"] , Description ["If attached, this entity is only replicated to the players whose `interest_groups` contain this group, regardless of their `replication_radius`. Useful for team-only entities or per-room state.\n"]] interest_group : String , # [doc = "**Interest groups**: The `interest_group`s whose entities are replicated to this player.\n\n\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Interest groups"] , Description ["The `interest_group`s whose entities are replicated to this player.\n"]] interest_groups : Vec :: < String > , # [doc = "**Predicted**: If attached, clients simulate this entity ahead of the server with the inputs they send through a `Predictor`.\n\nWhenever the server's state of a predicted entity, or the player's `last_processed_input`, arrives, the client rewinds every predicted entity to the server's state, marks it with `prediction_rewound`, and replays the inputs the server hasn't processed yet.\n\n\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Predicted"] , Description ["If attached, clients simulate this entity ahead of the server with the inputs they send through a `Predictor`.\nWhenever the server's state of a predicted entity, or the player's `last_processed_input`, arrives, the client rewinds every predicted entity to the server's state, marks it with `prediction_rewound`, and replays the inputs the server hasn't processed yet.\n"]] predicted : () , # [doc = "**Last processed input**: On a player entity: the sequence number of the last predicted input of that player the server has simulated. Inputs up to it are no longer replayed by the client.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Last processed input"] , Description ["On a player entity: the sequence number of the last predicted input of that player the server has simulated. Inputs up to it are no longer replayed by the client.\n"]] last_processed_input : u32 , # [doc = "**Prediction rewound**: Added on the client to the `predicted` entities which have just been rewound to the server's state, until the pending inputs have been replayed on top of them.\n\n\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Prediction rewound"] , Description ["Added on the client to the `predicted` entities which have just been rewound to the server's state, until the pending inputs have been replayed on top of them.\n"]] prediction_rewound : () , # [doc = "**Interpolation delay**: On the client: how far behind the server the transforms of remote entities are shown, so that they can be interpolated between the snapshots the server sends instead of jumping on every update. Defaults to 100 ms if not set; zero disables interpolation.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Interpolation delay"] , Description ["On the client: how far behind the server the transforms of remote entities are shown, so that they can be interpolated between the snapshots the server sends instead of jumping on every update. Defaults to 100 ms if not set; zero disables interpolation.\n"]] interpolation_delay : Duration , # [doc = "**Max extrapolation**: On the client: how long remote entities keep moving with the velocity of their last snapshots when the next one is late, before they stop. Defaults to 250 ms if not set.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Max extrapolation"] , Description ["On the client: how long remote entities keep moving with the velocity of their last snapshots when the next one is late, before they stop. Defaults to 250 ms if not set.\n"]] max_extrapolation : Duration , # [doc = "**Server time**: On the synchronized resources: the server's `game_time` when it sent the latest update to the clients.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Server time"] , Description ["On the synchronized resources: the server's `game_time` when it sent the latest update to the clients.\n"]] server_time : Duration , # [doc = "**Shown server time**: On the client: the `server_time` the remote entities are currently shown at, taking `interpolation_delay` into account. Send it to the server along with a shot, so that the server can check what the player saw with `raycast_at`.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Shown server time"] , Description ["On the client: the `server_time` the remote entities are currently shown at, taking `interpolation_delay` into account. Send it to the server along with a shot, so that the server can check what the player saw with `raycast_at`.\n"]] shown_server_time : Duration , # [doc = "**Bandwidth budget**: On the server: the bytes per second the updates of the world sent to a player may take, on the player entity or as a resource for every player. Once the budget is spent, changes to low priority components are held back first, then those to normal priority components, and only the latest value is sent when there is room again. Zero or not set means no limit.\n\n\n\n*Attributes*: Debuggable, MaybeResource"] @ [Debuggable , MaybeResource , Name ["Bandwidth budget"] , Description ["On the server: the bytes per second the updates of the world sent to a player may take, on the player entity or as a resource for every player. Once the budget is spent, changes to low priority components are held back first, then those to normal priority components, and only the latest value is sent when there is room again. Zero or not set means no limit.\n"]] bandwidth_budget : u32 , # [doc = "**Bandwidth in**: On a player entity: the bytes per second the server has received from the player over the last second.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Bandwidth in"] , Description ["On a player entity: the bytes per second the server has received from the player over the last second.\n"]] bandwidth_in : f32 , # [doc = "**Bandwidth out**: On a player entity: the bytes per second the server has sent to the player over the last second.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Bandwidth out"] , Description ["On a player entity: the bytes per second the server has sent to the player over the last second.\n"]] bandwidth_out : f32 , # [doc = "**Round trip time**: On a player entity: the time it takes for a packet to reach the player and be acknowledged, as estimated by the connection.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Round trip time"] , Description ["On a player entity: the time it takes for a packet to reach the player and be acknowledged, as estimated by the connection.\n"]] round_trip_time : Duration , # [doc = "**Packet loss**: On a player entity: the fraction of the packets sent to the player over the last second which were lost, from 0 to 1.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Packet loss"] , Description ["On a player entity: the fraction of the packets sent to the player over the last second which were lost, from 0 to 1.\n"]] packet_loss : f32 , # [doc = "**Send queue depth**: On a player entity: the number of world updates waiting to be sent to the player, including the entities whose changes are held back by `bandwidth_budget`.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Send queue depth"] , Description ["On a player entity: the number of world updates waiting to be sent to the player, including the entities whose changes are held back by `bandwidth_budget`.\n"]] send_queue_depth : u32 , # [doc = "**Authority**: The user id of the player with authority over this entity. Their client simulates the entity and sends the changes it makes to its networked components to the server, which applies them and replicates them to the other players.\n\n\n\nChanges to the entity sent by any other client are ignored, and the owner ignores the changes the server sends it, so the server should take authority back (by removing this component) before changing the entity itself.\n\n\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Authority"] , Description ["The user id of the player with authority over this entity. Their client simulates the entity and sends the changes it makes to its networked components to the server, which applies them and replicates them to the other players.\n\nChanges to the entity sent by any other client are ignored, and the owner ignores the changes the server sends it, so the server should take authority back (by removing this component) before changing the entity itself.\n"]] authority : String , # [doc = "**Message rate limit**: On the server: the messages of each type per second a player may send to the server modules, on the player entity or as a resource for every player. Bursts of up to one second of messages are allowed. Messages over the limit are dropped before they reach the modules. Zero or not set means no limit.\n\n\n\n*Attributes*: Debuggable, MaybeResource"] @ [Debuggable , MaybeResource , Name ["Message rate limit"] , Description ["On the server: the messages of each type per second a player may send to the server modules, on the player entity or as a resource for every player. Bursts of up to one second of messages are allowed. Messages over the limit are dropped before they reach the modules. Zero or not set means no limit.\n"]] message_rate_limit : u32 , # [doc = "**Message flood limit**: On the server: the messages over `message_rate_limit` a player may send within a second before being kicked, on the player entity or as a resource for every player. Zero or not set means players are never kicked, only throttled.\n\n\n\n*Attributes*: Debuggable, MaybeResource"] @ [Debuggable , MaybeResource , Name ["Message flood limit"] , Description ["On the server: the messages over `message_rate_limit` a player may send within a second before being kicked, on the player entity or as a resource for every player. Zero or not set means players are never kicked, only throttled.\n"]] message_flood_limit : u32 , # [doc = "**Messages received**: On a player entity: the number of messages the player has sent to the server modules which were passed on to them.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Messages received"] , Description ["On a player entity: the number of messages the player has sent to the server modules which were passed on to them.\n"]] messages_received : u64 , # [doc = "**Messages dropped**: On a player entity: the number of messages the player has sent to the server modules which were dropped for going over `message_rate_limit`.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Messages dropped"] , Description ["On a player entity: the number of messages the player has sent to the server modules which were dropped for going over `message_rate_limit`.\n"]] messages_dropped : u64 , # [doc = "**Simulated latency**: For development: holds back what this side of the connection receives (messages, datagrams and, on the client, the updates of the world) by this long, to simulate a slow network. Not set means no simulated latency.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Simulated latency"] , Description ["For development: holds back what this side of the connection receives (messages, datagrams and, on the client, the updates of the world) by this long, to simulate a slow network. Not set means no simulated latency.\n"]] simulated_latency : Duration , # [doc = "**Simulated jitter**: For development: holds back what this side of the connection receives by up to this long on top of `simulated_latency`, at random. Datagrams and messages may arrive out of order as a result, while the updates of the world stay in order.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Simulated jitter"] , Description ["For development: holds back what this side of the connection receives by up to this long on top of `simulated_latency`, at random. Datagrams and messages may arrive out of order as a result, while the updates of the world stay in order.\n"]] simulated_jitter : Duration , # [doc = "**Simulated packet loss**: For development: the fraction of the datagrams this side of the connection receives which are dropped, from 0 to 1.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Simulated packet loss"] , Description ["For development: the fraction of the datagrams this side of the connection receives which are dropped, from 0 to 1.\n"]] simulated_packet_loss : f32 , # [doc = "**Simulated duplication**: For development: the fraction of the datagrams this side of the connection receives which arrive twice, from 0 to 1.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Simulated duplication"] , Description ["For development: the fraction of the datagrams this side of the connection receives which arrive twice, from 0 to 1.\n"]] simulated_duplication : f32 , });
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{Entity, EntityId};
+                use ambient_package_rt::message_serde::{
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
+                };
+                use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
+                #[derive(Clone, Debug)]
+                #[doc = "**HandoffRequest**: Sent by a server module: hands `root` and its descendants off to the server whose HTTP interface is at `endpoint`, to keep simulating them there. If `user_id` is not empty, that player comes along: the player entity moves with its components, and its client is redirected to `address`, the QUIC address of the other server."]
+                pub struct HandoffRequest {
+                    pub root: EntityId,
+                    pub user_id: String,
+                    pub endpoint: String,
+                    pub address: String,
+                }
+                impl HandoffRequest {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        root: impl Into<EntityId>,
+                        user_id: impl Into<String>,
+                        endpoint: impl Into<String>,
+                        address: impl Into<String>,
+                    ) -> Self {
+                        Self {
+                            root: root.into(),
+                            user_id: user_id.into(),
+                            endpoint: endpoint.into(),
+                            address: address.into(),
+                        }
+                    }
+                }
+                impl Message for HandoffRequest {
+                    fn id() -> &'static str {
+                        "ambient_core::network::HandoffRequest"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.root.serialize_message_part(&mut output)?;
+                        self.user_id.serialize_message_part(&mut output)?;
+                        self.endpoint.serialize_message_part(&mut output)?;
+                        self.address.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            root: EntityId::deserialize_message_part(&mut input)?,
+                            user_id: String::deserialize_message_part(&mut input)?,
+                            endpoint: String::deserialize_message_part(&mut input)?,
+                            address: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for HandoffRequest {}
+                #[derive(Clone, Debug)]
+                #[doc = "**HandoffArrived**: Entities were handed off to this server: `root` and its descendants, which keep their IDs. If `user_id` is not empty, that player is on its way, and joins once its client has been redirected."]
+                pub struct HandoffArrived {
+                    pub root: EntityId,
+                    pub user_id: String,
+                }
+                impl HandoffArrived {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(root: impl Into<EntityId>, user_id: impl Into<String>) -> Self {
+                        Self {
+                            root: root.into(),
+                            user_id: user_id.into(),
+                        }
+                    }
+                }
+                impl Message for HandoffArrived {
+                    fn id() -> &'static str {
+                        "ambient_core::network::HandoffArrived"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.root.serialize_message_part(&mut output)?;
+                        self.user_id.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            root: EntityId::deserialize_message_part(&mut input)?,
+                            user_id: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for HandoffArrived {}
+                #[derive(Clone, Debug)]
+                #[doc = "**HandoffFailed**: The handoff of `root` to another server failed, for the given reason. The entities are left as they were on this server."]
+                pub struct HandoffFailed {
+                    pub root: EntityId,
+                    pub reason: String,
+                }
+                impl HandoffFailed {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(root: impl Into<EntityId>, reason: impl Into<String>) -> Self {
+                        Self {
+                            root: root.into(),
+                            reason: reason.into(),
+                        }
+                    }
+                }
+                impl Message for HandoffFailed {
+                    fn id() -> &'static str {
+                        "ambient_core::network::HandoffFailed"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.root.serialize_message_part(&mut output)?;
+                        self.reason.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            root: EntityId::deserialize_message_part(&mut input)?,
+                            reason: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for HandoffFailed {}
+                #[derive(Clone, Debug)]
+                #[doc = "**RconRegisterCommand**: Sent by a server module: registers `name` as an admin command of the remote console, described by `description`. When an admin runs it, the module receives an `RconCommand`."]
+                pub struct RconRegisterCommand {
+                    pub name: String,
+                    pub description: String,
+                }
+                impl RconRegisterCommand {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+                        Self {
+                            name: name.into(),
+                            description: description.into(),
+                        }
+                    }
+                }
+                impl Message for RconRegisterCommand {
+                    fn id() -> &'static str {
+                        "ambient_core::network::RconRegisterCommand"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.name.serialize_message_part(&mut output)?;
+                        self.description.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            name: String::deserialize_message_part(&mut input)?,
+                            description: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for RconRegisterCommand {}
+                #[derive(Clone, Debug)]
+                #[doc = "**RconCommand**: An admin ran the remote console command `name` with `args`. The module which registered it answers with an `RconOutput` for the same `request`."]
+                pub struct RconCommand {
+                    pub request: u32,
+                    pub name: String,
+                    pub args: String,
+                }
+                impl RconCommand {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        request: impl Into<u32>,
+                        name: impl Into<String>,
+                        args: impl Into<String>,
+                    ) -> Self {
+                        Self {
+                            request: request.into(),
+                            name: name.into(),
+                            args: args.into(),
+                        }
+                    }
+                }
+                impl Message for RconCommand {
+                    fn id() -> &'static str {
+                        "ambient_core::network::RconCommand"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.request.serialize_message_part(&mut output)?;
+                        self.name.serialize_message_part(&mut output)?;
+                        self.args.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            request: u32::deserialize_message_part(&mut input)?,
+                            name: String::deserialize_message_part(&mut input)?,
+                            args: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for RconCommand {}
+                #[derive(Clone, Debug)]
+                #[doc = "**RconOutput**: Sent by a server module: the output of the remote console command it ran for `request`, which is shown to the admin."]
+                pub struct RconOutput {
+                    pub request: u32,
+                    pub output: String,
+                }
+                impl RconOutput {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(request: impl Into<u32>, output: impl Into<String>) -> Self {
+                        Self {
+                            request: request.into(),
+                            output: output.into(),
+                        }
+                    }
+                }
+                impl Message for RconOutput {
+                    fn id() -> &'static str {
+                        "ambient_core::network::RconOutput"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.request.serialize_message_part(&mut output)?;
+                        self.output.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            request: u32::deserialize_message_part(&mut input)?,
+                            output: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for RconOutput {}
             }
         }
         pub mod package {
@@ -573,7 +1002,8 @@ mod raw {
             pub mod messages {
                 use crate::{Entity, EntityId};
                 use ambient_package_rt::message_serde::{
-                    Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 #[derive(Clone, Debug)]
@@ -724,23 +1154,90 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("physics" , { # [doc = "**Angular velocity**: Angular velocity (radians/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's angular velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Angular velocity"] , Description ["Angular velocity (radians/second) of this entity in the physics scene.\nUpdating this component will update the entity's angular velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead."]] angular_velocity : Vec3 , # [doc = "**Cube collider**: If attached, this entity will have a cube physics collider.\n\n`x, y, z` is the size of the cube.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Cube collider"] , Description ["If attached, this entity will have a cube physics collider.\n`x, y, z` is the size of the cube."]] cube_collider : Vec3 , # [doc = "**Character controller height**: The height of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller height"] , Description ["The height of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider."]] character_controller_height : f32 , # [doc = "**Character controller radius**: The radius of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller radius"] , Description ["The radius of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider."]] character_controller_radius : f32 , # [doc = "**Collider from URL**: This entity will load its physics collider from the URL.\n\nThe value is the URL to load from.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider from URL"] , Description ["This entity will load its physics collider from the URL.\nThe value is the URL to load from."]] collider_from_url : String , # [doc = "**Collider loaded**: This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider loaded"] , Description ["This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`)."]] collider_loaded : () , # [doc = "**Collider loads**: Contains all colliders that were loaded in this physics tick.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Collider loads"] , Description ["Contains all colliders that were loaded in this physics tick."]] collider_loads : Vec :: < EntityId > , # [doc = "**Contact offset**: Contact offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Contact offset"] , Description ["Contact offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene."]] contact_offset : f32 , # [doc = "**Density**: The density of this entity.\n\nThis is used to update the `mass` when the entity is rescaled.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Density"] , Description ["The density of this entity.\nThis is used to update the `mass` when the entity is rescaled."]] density : f32 , # [doc = "**Dynamic**: If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Dynamic"] , Description ["If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static."]] dynamic : bool , # [doc = "**Kinematic**: If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Kinematic"] , Description ["If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally."]] kinematic : () , # [doc = "**Linear velocity**: Linear velocity (meters/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's linear velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Linear velocity"] , Description ["Linear velocity (meters/second) of this entity in the physics scene.\nUpdating this component will update the entity's linear velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead."]] linear_velocity : Vec3 , # [doc = "**Make physics static**: All physics objects will be made static when loaded.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Make physics static"] , Description ["All physics objects will be made static when loaded."]] make_physics_static : bool , # [doc = "**Mass**: The mass of this entity, measured in kilograms.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Mass"] , Description ["The mass of this entity, measured in kilograms."]] mass : f32 , # [doc = "**Physics controlled**: If attached, this entity will be controlled by physics.\n\nNote that this requires the entity to have a collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Physics controlled"] , Description ["If attached, this entity will be controlled by physics.\nNote that this requires the entity to have a collider."]] physics_controlled : () , # [doc = "**Plane collider**: If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Plane collider"] , Description ["If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead."]] plane_collider : () , # [doc = "**Rest offset**: Rest offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Rest offset"] , Description ["Rest offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene."]] rest_offset : f32 , # [doc = "**Sphere collider**: If attached, this entity will have a sphere physics collider.\n\nThe value corresponds to the radius of the sphere.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sphere collider"] , Description ["If attached, this entity will have a sphere physics collider.\nThe value corresponds to the radius of the sphere."]] sphere_collider : f32 , # [doc = "**Unit mass**: The mass of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit mass"] , Description ["The mass of a character/unit."]] unit_mass : f32 , # [doc = "**Unit velocity**: The velocity of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit velocity"] , Description ["The velocity of a character/unit."]] unit_velocity : Vec3 , # [doc = "**Unit yaw**: The yaw of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit yaw"] , Description ["The yaw of a character/unit."]] unit_yaw : f32 , # [doc = "**Visualize collider**: If attached, the collider will be rendered.\n\n\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Visualize collider"] , Description ["If attached, the collider will be rendered.\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n"]] visualize_collider : () , });
+                components ! ("physics" , { # [doc = "**Angular velocity**: Angular velocity (radians/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's angular velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Angular velocity"] , Description ["Angular velocity (radians/second) of this entity in the physics scene.\nUpdating this component will update the entity's angular velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like improper physics or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying an impulse using `physics` functions instead."]] angular_velocity : Vec3 , # [doc = "**Buoyancy density**: The density of the fluid in this `buoyancy_volume`, relative to the `density` of the bodies in it.\n\nBodies with a lower density than the fluid float, and bodies with a higher density sink.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Buoyancy density"] , Description ["The density of the fluid in this `buoyancy_volume`, relative to the `density` of the bodies in it.\nBodies with a lower density than the fluid float, and bodies with a higher density sink."]] buoyancy_density : f32 , # [doc = "**Buoyancy drag**: How strongly the fluid in this `buoyancy_volume` slows down the bodies moving through it, as the fraction of their velocity lost per second.\n\nThis is scaled by how much of each body is submerged.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Buoyancy drag"] , Description ["How strongly the fluid in this `buoyancy_volume` slows down the bodies moving through it, as the fraction of their velocity lost per second.\nThis is scaled by how much of each body is submerged."]] buoyancy_drag : f32 , # [doc = "**Buoyancy volume**: If attached, this entity is a volume of fluid that pushes dynamic bodies up, in proportion to how deep they are submerged.\n\n`x, y, z` is the size of the box, centered on this entity's position and aligned with the world axes; its top is the surface of the fluid.\n\nA `Splash` message is sent when a body enters or leaves the volume.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Buoyancy volume"] , Description ["If attached, this entity is a volume of fluid that pushes dynamic bodies up, in proportion to how deep they are submerged.\n`x, y, z` is the size of the box, centered on this entity's position and aligned with the world axes; its top is the surface of the fluid.\nA `Splash` message is sent when a body enters or leaves the volume."]] buoyancy_volume : Vec3 , # [doc = "**Cube collider**: If attached, this entity will have a cube physics collider.\n\n`x, y, z` is the size of the cube.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Cube collider"] , Description ["If attached, this entity will have a cube physics collider.\n`x, y, z` is the size of the cube."]] cube_collider : Vec3 , # [doc = "**CCD enabled**: Whether continuous collision detection (CCD) is enabled for this dynamic entity. CCD stops fast-moving entities, like projectiles and vehicles, from passing through thin colliders, at the cost of some performance.\n\nIf this component is not attached, CCD is enabled for dynamic entities and disabled for kinematic entities. Kinematic entities use speculative CCD when it is enabled.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["CCD enabled"] , Description ["Whether continuous collision detection (CCD) is enabled for this dynamic entity. CCD stops fast-moving entities, like projectiles and vehicles, from passing through thin colliders, at the cost of some performance.\nIf this component is not attached, CCD is enabled for dynamic entities and disabled for kinematic entities. Kinematic entities use speculative CCD when it is enabled."]] ccd_enabled : bool , # [doc = "**Character controller height**: The height of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller height"] , Description ["The height of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider."]] character_controller_height : f32 , # [doc = "**Character controller radius**: The radius of the physics character controller attached to this entity.\n\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Character controller radius"] , Description ["The radius of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider."]] character_controller_radius : f32 , # [doc = "**Collider convex hulls**: The number of convex hulls in the convex version of this entity's collider, which is used when the entity is dynamic. This is attached when the collider is loaded.\n\nConcave models are split into several convex hulls by the asset pipeline when their collider is dynamic; see `max_convex_hulls` in the pipeline's `FromModel` collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider convex hulls"] , Description ["The number of convex hulls in the convex version of this entity's collider, which is used when the entity is dynamic. This is attached when the collider is loaded.\nConcave models are split into several convex hulls by the asset pipeline when their collider is dynamic; see `max_convex_hulls` in the pipeline's `FromModel` collider."]] collider_convex_hulls : u32 , # [doc = "**Collider from URL**: This entity will load its physics collider from the URL.\n\nThe value is the URL to load from.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider from URL"] , Description ["This entity will load its physics collider from the URL.\nThe value is the URL to load from."]] collider_from_url : String , # [doc = "**Collider loaded**: This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider loaded"] , Description ["This component is automatically attached to an entity once the collider has been loaded (through e.g. `collider_from_url`)."]] collider_loaded : () , # [doc = "**Collider loads**: Contains all colliders that were loaded in this physics tick.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Collider loads"] , Description ["Contains all colliders that were loaded in this physics tick."]] collider_loads : Vec :: < EntityId > , # [doc = "**Collider max concavity**: The largest concavity left in the convex hulls of this entity's collider, relative to the size of each hull, when the asset pipeline split its model into convex hulls. It is part of the prefabs of the models with a dynamic `FromModel` collider, and can be read once the collider has loaded, along with `collider_convex_hulls`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collider max concavity"] , Description ["The largest concavity left in the convex hulls of this entity's collider, relative to the size of each hull, when the asset pipeline split its model into convex hulls. It is part of the prefabs of the models with a dynamic `FromModel` collider, and can be read once the collider has loaded, along with `collider_convex_hulls`."]] collider_max_concavity : f32 , # [doc = "**Collision event groups**: If attached to a package, its modules will only receive `Collision` messages for collisions involving at least one entity in one of these collision groups (a bitmask, see `collision_groups`).\n\nPackages without this component receive every `Collision` message.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collision event groups"] , Description ["If attached to a package, its modules will only receive `Collision` messages for collisions involving at least one entity in one of these collision groups (a bitmask, see `collision_groups`).\nPackages without this component receive every `Collision` message."]] collision_event_groups : u32 , # [doc = "**Collision groups**: A bitmask of the collision groups this entity belongs to. Entities don't collide with entities that ignore one of their groups (see `collision_ignored_groups`).\n\nIf not attached, the entity is not in any group.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collision groups"] , Description ["A bitmask of the collision groups this entity belongs to. Entities don't collide with entities that ignore one of their groups (see `collision_ignored_groups`).\nIf not attached, the entity is not in any group."]] collision_groups : u32 , # [doc = "**Collision ignored groups**: A bitmask of the collision groups this entity does not collide with (see `collision_groups`).\n\nThis can be used for team-based collisions, or for one-way platforms, by ignoring the platforms' group while moving up through them. If not attached, the entity collides with every group.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Collision ignored groups"] , Description ["A bitmask of the collision groups this entity does not collide with (see `collision_groups`).\nThis can be used for team-based collisions, or for one-way platforms, by ignoring the platforms' group while moving up through them. If not attached, the entity collides with every group."]] collision_ignored_groups : u32 , # [doc = "**Contact filter**: If attached to a package, its modules decide whether the entities with `contact_filtered` collide with the entities they touch.\n\nThey are sent a `ContactFilter` message for each new pair of touching entities, and answer with `physics::set_contact_filter`. The answers are cached, so each pair is only asked about once.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Contact filter"] , Description ["If attached to a package, its modules decide whether the entities with `contact_filtered` collide with the entities they touch.\nThey are sent a `ContactFilter` message for each new pair of touching entities, and answer with `physics::set_contact_filter`. The answers are cached, so each pair is only asked about once."]] contact_filter : () , # [doc = "**Contact filtered**: If attached, whether this entity collides with the entities it touches is decided by the packages with `contact_filter`.\n\nIt collides with an entity until they have answered for it.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Contact filtered"] , Description ["If attached, whether this entity collides with the entities it touches is decided by the packages with `contact_filter`.\nIt collides with an entity until they have answered for it."]] contact_filtered : () , # [doc = "**Contact offset**: Contact offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Contact offset"] , Description ["Contact offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene."]] contact_offset : f32 , # [doc = "**Density**: The density of this entity, in kg/m³.\n\nThe `mass` and `inertia` of dynamic entities are computed from the volume of their collider and this density, and are updated when the entity is rescaled.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Density"] , Description ["The density of this entity, in kg/m³.\nThe `mass` and `inertia` of dynamic entities are computed from the volume of their collider and this density, and are updated when the entity is rescaled."]] density : f32 , # [doc = "**Dynamic**: If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Dynamic"] , Description ["If this is true, the entity will be dynamic (i.e. be able to move). Otherwise, it will be static."]] dynamic : bool , # [doc = "**Force field attractor**: If attached along with `force_field_radius`, dynamic bodies within the radius are accelerated towards this entity's position by this amount (in m/s²) every physics step, regardless of their mass.\n\nNegative values push bodies away instead, which can be used for explosions and repulsors.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Force field attractor"] , Description ["If attached along with `force_field_radius`, dynamic bodies within the radius are accelerated towards this entity's position by this amount (in m/s²) every physics step, regardless of their mass.\nNegative values push bodies away instead, which can be used for explosions and repulsors."]] force_field_attractor : f32 , # [doc = "**Force field falloff**: If attached to a force field, its strength falls off linearly from the full strength at its center to zero at `force_field_radius`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Force field falloff"] , Description ["If attached to a force field, its strength falls off linearly from the full strength at its center to zero at `force_field_radius`."]] force_field_falloff : () , # [doc = "**Force field radius**: The radius of the sphere around this entity's position in which its force field affects dynamic bodies.\n\nThe force field itself is defined by `force_field_wind` and/or `force_field_attractor`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Force field radius"] , Description ["The radius of the sphere around this entity's position in which its force field affects dynamic bodies.\nThe force field itself is defined by `force_field_wind` and/or `force_field_attractor`."]] force_field_radius : f32 , # [doc = "**Force field wind**: If attached along with `force_field_radius`, dynamic bodies within the radius are accelerated by this vector (in world space, in m/s²) every physics step, regardless of their mass.\n\nThis can be used for wind, currents and conveyor-like volumes.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Force field wind"] , Description ["If attached along with `force_field_radius`, dynamic bodies within the radius are accelerated by this vector (in world space, in m/s²) every physics step, regardless of their mass.\nThis can be used for wind, currents and conveyor-like volumes."]] force_field_wind : Vec3 , # [doc = "**Friction**: The friction coefficient of this entity's collider, used for both static and dynamic friction.\n\nUpdating this component will update the physics material of each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Friction"] , Description ["The friction coefficient of this entity's collider, used for both static and dynamic friction.\nUpdating this component will update the physics material of each attached shape in the physics scene."]] friction : f32 , # [doc = "**Friction combine mode**: How the friction of this entity's collider is combined with the friction of the collider it is touching.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Friction combine mode"] , Description ["How the friction of this entity's collider is combined with the friction of the collider it is touching."]] friction_combine_mode : crate :: generated :: raw :: ambient_core :: physics :: types :: CombineMode , # [doc = "**Heightfield collider**: If attached along with `heightfield_collider_resolution`, this entity will have a heightfield physics collider. This is much cheaper than building large terrains out of many colliders or a triangle mesh.\n\nThe value contains the height of each sample, row by row: the sample at `(x, y)` is at index `y * resolution.x + x`, and is placed at `(x, y, height)` in the entity's local space. Use `scale` to change the spacing between samples and the height.\n\nHeightfield colliders can't be used with dynamic entities, unless they are also kinematic.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Heightfield collider"] , Description ["If attached along with `heightfield_collider_resolution`, this entity will have a heightfield physics collider. This is much cheaper than building large terrains out of many colliders or a triangle mesh.\nThe value contains the height of each sample, row by row: the sample at `(x, y)` is at index `y * resolution.x + x`, and is placed at `(x, y, height)` in the entity's local space. Use `scale` to change the spacing between samples and the height.\nHeightfield colliders can't be used with dynamic entities, unless they are also kinematic."]] heightfield_collider : Vec :: < f32 > , # [doc = "**Heightfield collider from URL**: If attached, this entity will have a heightfield physics collider loaded from the grayscale heightmap image at this URL.\n\nEach pixel becomes a sample, with black being a height of 0 and white being a height of 1; the image's top-left pixel is at the local origin, with rows going along +Y. Use `scale` to change the spacing between samples and the height.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Heightfield collider from URL"] , Description ["If attached, this entity will have a heightfield physics collider loaded from the grayscale heightmap image at this URL.\nEach pixel becomes a sample, with black being a height of 0 and white being a height of 1; the image's top-left pixel is at the local origin, with rows going along +Y. Use `scale` to change the spacing between samples and the height."]] heightfield_collider_from_url : String , # [doc = "**Heightfield collider resolution**: The number of samples along the X and Y axes of this entity's `heightfield_collider`. Each axis needs at least 2 samples.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Heightfield collider resolution"] , Description ["The number of samples along the X and Y axes of this entity's `heightfield_collider`. Each axis needs at least 2 samples."]] heightfield_collider_resolution : UVec2 , # [doc = "**Inertia**: The moment of inertia (in kg·m²) of this dynamic entity around each of its principal axes.\n\nLike `mass`, this is computed from the collider and `density` of the entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Inertia"] , Description ["The moment of inertia (in kg·m²) of this dynamic entity around each of its principal axes.\nLike `mass`, this is computed from the collider and `density` of the entity."]] inertia : Vec3 , # [doc = "**Joint break force**: The linear force (in newtons) above which the joints attached to this entity break.\n\nIf both entities of a joint have a break force, the lowest one is used. If not attached, joints are unbreakable. A `JointBroken` message is sent when joints break.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Joint break force"] , Description ["The linear force (in newtons) above which the joints attached to this entity break.\nIf both entities of a joint have a break force, the lowest one is used. If not attached, joints are unbreakable. A `JointBroken` message is sent when joints break."]] joint_break_force : f32 , # [doc = "**Joint break torque**: The torque (in newton-meters) above which the joints attached to this entity break.\n\nIf both entities of a joint have a break torque, the lowest one is used. If not attached, joints are unbreakable. A `JointBroken` message is sent when joints break.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Joint break torque"] , Description ["The torque (in newton-meters) above which the joints attached to this entity break.\nIf both entities of a joint have a break torque, the lowest one is used. If not attached, joints are unbreakable. A `JointBroken` message is sent when joints break."]] joint_break_torque : f32 , # [doc = "**Kinematic**: If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Kinematic"] , Description ["If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally."]] kinematic : () , # [doc = "**Kinematic target rotation**: If attached to a kinematic entity, it will be rotated to this rotation over the physics steps of the next frame, pushing dynamic bodies out of its way.\n\nUnlike setting `rotation` directly, the body gets an angular velocity while it moves, so the bodies it pushes (or carries) move along with it. Add `physics_controlled` to have `rotation` follow the body.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Kinematic target rotation"] , Description ["If attached to a kinematic entity, it will be rotated to this rotation over the physics steps of the next frame, pushing dynamic bodies out of its way.\nUnlike setting `rotation` directly, the body gets an angular velocity while it moves, so the bodies it pushes (or carries) move along with it. Add `physics_controlled` to have `rotation` follow the body."]] kinematic_target_rotation : Quat , # [doc = "**Kinematic target translation**: If attached to a kinematic entity, it will be moved to this position over the physics steps of the next frame, pushing dynamic bodies out of its way. This can be used for moving platforms, doors and elevators.\n\nUnlike setting `translation` directly, the body gets a velocity while it moves, so the bodies it pushes (or carries) move along with it. Add `physics_controlled` to have `translation` follow the body.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Kinematic target translation"] , Description ["If attached to a kinematic entity, it will be moved to this position over the physics steps of the next frame, pushing dynamic bodies out of its way. This can be used for moving platforms, doors and elevators.\nUnlike setting `translation` directly, the body gets a velocity while it moves, so the bodies it pushes (or carries) move along with it. Add `physics_controlled` to have `translation` follow the body."]] kinematic_target_translation : Vec3 , # [doc = "**Lag compensation history**: How long the server keeps the poses of the moving colliders for `raycast_at`, which limits how far back a player's shot can be rewound. Defaults to 1 second if not set.\n\n\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Lag compensation history"] , Description ["How long the server keeps the poses of the moving colliders for `raycast_at`, which limits how far back a player's shot can be rewound. Defaults to 1 second if not set.\n"]] lag_compensation_history : Duration , # [doc = "**Linear velocity**: Linear velocity (meters/second) of this entity in the physics scene.\n\nUpdating this component will update the entity's linear velocity in the physics scene.\n\n\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\n\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Linear velocity"] , Description ["Linear velocity (meters/second) of this entity in the physics scene.\nUpdating this component will update the entity's linear velocity in the physics scene.\n\nNote that changing this component will forcibly set the velocity; changing the velocity every frame may lead to unexpected behavior, like gravity not working or collisions failing.\n\nIf you need to adjust the velocity each frame, consider applying a force using `physics` functions instead."]] linear_velocity : Vec3 , # [doc = "**Make physics static**: All physics objects will be made static when loaded.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Make physics static"] , Description ["All physics objects will be made static when loaded."]] make_physics_static : bool , # [doc = "**Mass**: The mass of this entity, measured in kilograms.\n\nThis is computed from the collider and `density` of dynamic entities; to make an entity heavier or lighter, change its `density`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Mass"] , Description ["The mass of this entity, measured in kilograms.\nThis is computed from the collider and `density` of dynamic entities; to make an entity heavier or lighter, change its `density`."]] mass : f32 , # [doc = "**Physics controlled**: If attached, this entity will be controlled by physics.\n\nNote that this requires the entity to have a collider.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Physics controlled"] , Description ["If attached, this entity will be controlled by physics.\nNote that this requires the entity to have a collider."]] physics_controlled : () , # [doc = "**Physics timestep**: The fixed time step (in seconds) that the physics simulation is advanced by.\n\nPhysics is stepped as many times as needed to keep up with the server's frame time, independently of the frame rate, and the transforms of physics-controlled entities are interpolated between the last two steps. Forces added to an entity only act on the next step.\n\nIf not attached, the server tick time is used.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Physics timestep"] , Description ["The fixed time step (in seconds) that the physics simulation is advanced by.\nPhysics is stepped as many times as needed to keep up with the server's frame time, independently of the frame rate, and the transforms of physics-controlled entities are interpolated between the last two steps. Forces added to an entity only act on the next step.\nIf not attached, the server tick time is used."]] physics_timestep : f32 , # [doc = "**Plane collider**: If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Plane collider"] , Description ["If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead."]] plane_collider : () , # [doc = "**Ragdoll**: If attached to a model with an animation binder (a character), its bones will be simulated as a ragdoll: each bone becomes a link in a physics articulation, with a capsule shape reaching to its child bones and a limited spherical joint to its parent bone.\n\nRemoving this component releases the articulation and hands the bones back to the animation.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Ragdoll"] , Description ["If attached to a model with an animation binder (a character), its bones will be simulated as a ragdoll: each bone becomes a link in a physics articulation, with a capsule shape reaching to its child bones and a limited spherical joint to its parent bone.\nRemoving this component releases the articulation and hands the bones back to the animation."]] ragdoll : () , # [doc = "**Ragdoll blend**: How much of a ragdoll's pose comes from the physics simulation, between 0 and 1. At 0 the bones follow the animation, and at 1 they follow the simulation.\n\nIf not attached, the simulation is used.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Ragdoll blend"] , Description ["How much of a ragdoll's pose comes from the physics simulation, between 0 and 1. At 0 the bones follow the animation, and at 1 they follow the simulation.\nIf not attached, the simulation is used."]] ragdoll_blend : f32 , # [doc = "**Rest offset**: Rest offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Rest offset"] , Description ["Rest offset (in meters) of this entity in the physics scene.\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene."]] rest_offset : f32 , # [doc = "**Restitution**: The restitution (bounciness) of this entity's collider. 0 will not bounce at all, while 1 will bounce without losing energy.\n\nUpdating this component will update the physics material of each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.6"] @ [Debuggable , Networked , Store , Name ["Restitution"] , Description ["The restitution (bounciness) of this entity's collider. 0 will not bounce at all, while 1 will bounce without losing energy.\nUpdating this component will update the physics material of each attached shape in the physics scene."]] restitution : f32 , # [doc = "**Restitution combine mode**: How the restitution of this entity's collider is combined with the restitution of the collider it is touching.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Restitution combine mode"] , Description ["How the restitution of this entity's collider is combined with the restitution of the collider it is touching."]] restitution_combine_mode : crate :: generated :: raw :: ambient_core :: physics :: types :: CombineMode , # [doc = "**Sleep threshold**: The mass-normalized kinetic energy (in m²/s²) below which this dynamic entity may fall asleep. Sleeping entities are not simulated until something wakes them up, which saves a lot of time in scenes with many resting objects.\n\nIf not attached, the PhysX default is used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sleep threshold"] , Description ["The mass-normalized kinetic energy (in m²/s²) below which this dynamic entity may fall asleep. Sleeping entities are not simulated until something wakes them up, which saves a lot of time in scenes with many resting objects.\nIf not attached, the PhysX default is used."]] sleep_threshold : f32 , # [doc = "**Sleeping**: Attached to dynamic entities while they are asleep.\n\nA `BodySleep` message is sent when entities fall asleep, and a `BodyWake` message when they wake up.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sleeping"] , Description ["Attached to dynamic entities while they are asleep.\nA `BodySleep` message is sent when entities fall asleep, and a `BodyWake` message when they wake up."]] sleeping : () , # [doc = "**Sphere collider**: If attached, this entity will have a sphere physics collider.\n\nThe value corresponds to the radius of the sphere.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sphere collider"] , Description ["If attached, this entity will have a sphere physics collider.\nThe value corresponds to the radius of the sphere."]] sphere_collider : f32 , # [doc = "**Unit mass**: The mass of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit mass"] , Description ["The mass of a character/unit."]] unit_mass : f32 , # [doc = "**Unit velocity**: The velocity of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit velocity"] , Description ["The velocity of a character/unit."]] unit_velocity : Vec3 , # [doc = "**Unit yaw**: The yaw of a character/unit.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Unit yaw"] , Description ["The yaw of a character/unit."]] unit_yaw : f32 , # [doc = "**Vehicle brake**: The brake input of this vehicle, from 0 (no braking) to 1 (full braking).\n\nSet this from your gameplay code to control the vehicle.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle brake"] , Description ["The brake input of this vehicle, from 0 (no braking) to 1 (full braking).\nSet this from your gameplay code to control the vehicle."]] vehicle_brake : f32 , # [doc = "**Vehicle brake force**: The total force (in newtons) applied by the brakes of this vehicle at full braking, spread across all wheels.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 8000.0"] @ [Debuggable , Networked , Store , Name ["Vehicle brake force"] , Description ["The total force (in newtons) applied by the brakes of this vehicle at full braking, spread across all wheels."]] vehicle_brake_force : f32 , # [doc = "**Vehicle driven wheels**: The indices (into `vehicle_wheel_offsets`) of the wheels that are driven by the engine.\n\nIf not attached, all wheels are driven.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle driven wheels"] , Description ["The indices (into `vehicle_wheel_offsets`) of the wheels that are driven by the engine.\nIf not attached, all wheels are driven."]] vehicle_driven_wheels : Vec :: < u32 > , # [doc = "**Vehicle engine curve**: The engine curve of this vehicle. Each point is `(forward speed in meters/second, engine force multiplier)`, sorted by speed; the multiplier is linearly interpolated between points, and clamped to the first and last points.\n\nIf not attached, the full engine force is available at all speeds.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle engine curve"] , Description ["The engine curve of this vehicle. Each point is `(forward speed in meters/second, engine force multiplier)`, sorted by speed; the multiplier is linearly interpolated between points, and clamped to the first and last points.\nIf not attached, the full engine force is available at all speeds."]] vehicle_engine_curve : Vec :: < Vec2 > , # [doc = "**Vehicle engine force**: The total force (in newtons) the engine of this vehicle applies at full throttle, spread across the driven wheels.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 6000.0"] @ [Debuggable , Networked , Store , Name ["Vehicle engine force"] , Description ["The total force (in newtons) the engine of this vehicle applies at full throttle, spread across the driven wheels."]] vehicle_engine_force : f32 , # [doc = "**Vehicle max steer angle**: The maximum angle (in radians) that the steered wheels of this vehicle can turn.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.6"] @ [Debuggable , Networked , Store , Name ["Vehicle max steer angle"] , Description ["The maximum angle (in radians) that the steered wheels of this vehicle can turn."]] vehicle_max_steer_angle : f32 , # [doc = "**Vehicle steer**: The steering input of this vehicle, from -1 (full left) to 1 (full right).\n\nSet this from your gameplay code to control the vehicle.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle steer"] , Description ["The steering input of this vehicle, from -1 (full left) to 1 (full right).\nSet this from your gameplay code to control the vehicle."]] vehicle_steer : f32 , # [doc = "**Vehicle steered wheels**: The indices (into `vehicle_wheel_offsets`) of the wheels that turn when steering.\n\nIf not attached, the vehicle can't steer.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle steered wheels"] , Description ["The indices (into `vehicle_wheel_offsets`) of the wheels that turn when steering.\nIf not attached, the vehicle can't steer."]] vehicle_steered_wheels : Vec :: < u32 > , # [doc = "**Vehicle suspension damping**: The damping (in newton-seconds/meter) of each wheel's suspension on this vehicle.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 3000.0"] @ [Debuggable , Networked , Store , Name ["Vehicle suspension damping"] , Description ["The damping (in newton-seconds/meter) of each wheel's suspension on this vehicle."]] vehicle_suspension_damping : f32 , # [doc = "**Vehicle suspension length**: The length (in meters) of each wheel's suspension on this vehicle when fully extended.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Vehicle suspension length"] , Description ["The length (in meters) of each wheel's suspension on this vehicle when fully extended."]] vehicle_suspension_length : f32 , # [doc = "**Vehicle suspension stiffness**: The stiffness (in newtons/meter) of each wheel's suspension on this vehicle.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 30000.0"] @ [Debuggable , Networked , Store , Name ["Vehicle suspension stiffness"] , Description ["The stiffness (in newtons/meter) of each wheel's suspension on this vehicle."]] vehicle_suspension_stiffness : f32 , # [doc = "**Vehicle throttle**: The throttle input of this vehicle, from -1 (full reverse) to 1 (full forward).\n\nSet this from your gameplay code to control the vehicle.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle throttle"] , Description ["The throttle input of this vehicle, from -1 (full reverse) to 1 (full forward).\nSet this from your gameplay code to control the vehicle."]] vehicle_throttle : f32 , # [doc = "**Vehicle tire grip**: The friction coefficient between the tires of this vehicle and the ground. This limits how much force each wheel can apply sideways, and from the engine and brakes.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.5"] @ [Debuggable , Networked , Store , Name ["Vehicle tire grip"] , Description ["The friction coefficient between the tires of this vehicle and the ground. This limits how much force each wheel can apply sideways, and from the engine and brakes."]] vehicle_tire_grip : f32 , # [doc = "**Vehicle wheel compressions**: The current compression of each wheel's suspension on this vehicle, from 0 (fully extended, or not touching the ground) to 1 (fully compressed).\n\nThis is updated by the physics simulation, and can be used to position the wheel models.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle wheel compressions"] , Description ["The current compression of each wheel's suspension on this vehicle, from 0 (fully extended, or not touching the ground) to 1 (fully compressed).\nThis is updated by the physics simulation, and can be used to position the wheel models."]] vehicle_wheel_compressions : Vec :: < f32 > , # [doc = "**Vehicle wheel offsets**: If attached to a dynamic entity, it will be simulated as a raycast vehicle with a wheel at each offset. The offsets are in the entity's local space, and are where each wheel's suspension is mounted to the chassis.\n\nThe vehicle's forward direction is its local -Y axis, and its up direction is its local +Z axis.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Vehicle wheel offsets"] , Description ["If attached to a dynamic entity, it will be simulated as a raycast vehicle with a wheel at each offset. The offsets are in the entity's local space, and are where each wheel's suspension is mounted to the chassis.\nThe vehicle's forward direction is its local -Y axis, and its up direction is its local +Z axis."]] vehicle_wheel_offsets : Vec :: < Vec3 > , # [doc = "**Vehicle wheel radius**: The radius (in meters) of each wheel on this vehicle.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.4"] @ [Debuggable , Networked , Store , Name ["Vehicle wheel radius"] , Description ["The radius (in meters) of each wheel on this vehicle."]] vehicle_wheel_radius : f32 , # [doc = "**Visualize collider**: If attached, the collider will be rendered.\n\n\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n\n\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Visualize collider"] , Description ["If attached, the collider will be rendered.\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n"]] visualize_collider : () , });
             }
-        }
-        pub mod player {
-            #[doc = r" Auto-generated component definitions."]
-            pub mod components {
-                use crate::{
-                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
-                    Networked, Resource, Store,
-                };
-                use ambient_shared_types::{
-                    ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
-                    ProceduralTextureHandle,
+            #[doc = r" Auto-generated type definitions."]
+            pub mod types {
+                use ambient_package_rt::message_serde::*;
+                use serde;
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**CombineMode**: How a physics material property is combined between two touching colliders."]
+                pub enum CombineMode {
+                    #[default]
+                    #[doc = "The average of the two values."]
+                    Average,
+                    #[doc = "The smaller of the two values."]
+                    Min,
+                    #[doc = "The product of the two values."]
+                    Multiply,
+                    #[doc = "The larger of the two values."]
+                    Max,
+                }
+                impl crate::EnumComponent for CombineMode {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Average => CombineMode::Average as u32,
+                            Self::Min => CombineMode::Min as u32,
+                            Self::Multiply => CombineMode::Multiply as u32,
+                            Self::Max => CombineMode::Max as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == CombineMode::Average as u32 {
+                            return Some(Self::Average);
+                        }
+                        if value == CombineMode::Min as u32 {
+                            return Some(Self::Min);
+                        }
+                        if value == CombineMode::Multiply as u32 {
+                            return Some(Self::Multiply);
+                        }
+                        if value == CombineMode::Max as u32 {
+                            return Some(Self::Max);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for CombineMode {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
+            }
+        }
+        pub mod player {
+            #[doc = r" Auto-generated component definitions."]
+            pub mod components {
+                use crate::{
+                    components, Debuggable, Description, EntityId, Enum, MaybeResource, Name,
+                    Networked, Resource, Store,
+                };
+                use ambient_shared_types::{
+                    ProceduralMaterialHandle, ProceduralMeshHandle, ProceduralSamplerHandle,
+                    ProceduralTextureHandle,
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("player" , { # [doc = "**Local user ID**: The user ID of the local player.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Local user ID"] , Description ["The user ID of the local player."]] local_user_id : String , # [doc = "**Is player**: This entity is a player.\n\nNote that this is a logical construct; a player's body may be separate from the player itself.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is player"] , Description ["This entity is a player.\nNote that this is a logical construct; a player's body may be separate from the player itself."]] is_player : () , # [doc = "**User ID**: An identifier attached to all things owned by a user, and supplied by the user.\n\nThis can be attached to more than just the player; by convention, it is also attached to related entities, including their camera and body.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["User ID"] , Description ["An identifier attached to all things owned by a user, and supplied by the user.\nThis can be attached to more than just the player; by convention, it is also attached to related entities, including their camera and body."]] user_id : String , });
+                components ! ("player" , { # [doc = "**Local user ID**: The user ID of the local player.\n\n*Attributes*: Debuggable, Networked, Resource, Store"] @ [Debuggable , Networked , Resource , Store , Name ["Local user ID"] , Description ["The user ID of the local player."]] local_user_id : String , # [doc = "**Is player**: This entity is a player.\n\nNote that this is a logical construct; a player's body may be separate from the player itself.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Is player"] , Description ["This entity is a player.\nNote that this is a logical construct; a player's body may be separate from the player itself."]] is_player : () , # [doc = "**User ID**: An identifier attached to all things owned by a user, and supplied by the user.\n\nThis can be attached to more than just the player; by convention, it is also attached to related entities, including their camera and body.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["User ID"] , Description ["An identifier attached to all things owned by a user, and supplied by the user.\nThis can be attached to more than just the player; by convention, it is also attached to related entities, including their camera and body."]] user_id : String , # [doc = "**Identity subject**: On a player entity: who the player is, as verified by the server from the token the player's client presented when connecting. This is the same as their `user_id`.\n\nAbsent if the server does not authenticate its players.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Identity subject"] , Description ["On a player entity: who the player is, as verified by the server from the token the player's client presented when connecting. This is the same as their `user_id`.\nAbsent if the server does not authenticate its players."]] identity_subject : String , # [doc = "**Identity name**: On a player entity: the display name of the player, as verified by the server from the token the player's client presented when connecting.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Identity name"] , Description ["On a player entity: the display name of the player, as verified by the server from the token the player's client presented when connecting."]] identity_name : String , # [doc = "**Identity issuer**: On a player entity: who issued the token the player's client presented when connecting, and vouched for their identity.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Identity issuer"] , Description ["On a player entity: who issued the token the player's client presented when connecting, and vouched for their identity."]] identity_issuer : String , # [doc = "**Identity roles**: On a player entity: the roles of the player, as verified by the server from the token the player's client presented when connecting.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Identity roles"] , Description ["On a player entity: the roles of the player, as verified by the server from the token the player's client presented when connecting."]] identity_roles : Vec :: < String > , });
             }
         }
         pub mod prefab {
@@ -804,7 +1301,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("rect" , { # [doc = "**Background color**: Background color of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Background color"] , Description ["Background color of an entity with a `rect` component."]] background_color : Vec4 , # [doc = "**Background URL**: URL to an image asset.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Background URL"] , Description ["URL to an image asset."]] background_url : String , # [doc = "**Border color**: Border color of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Border color"] , Description ["Border color of an entity with a `rect` component."]] border_color : Vec4 , # [doc = "**Border radius**: Radius for each corner of an entity with a `rect` component.\n\n`x` = top-left, `y` = top-right, `z` = bottom-left, `w` = bottom-right.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Border radius"] , Description ["Radius for each corner of an entity with a `rect` component.\n`x` = top-left, `y` = top-right, `z` = bottom-left, `w` = bottom-right."]] border_radius : Vec4 , # [doc = "**Border thickness**: Border thickness of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Border thickness"] , Description ["Border thickness of an entity with a `rect` component."]] border_thickness : f32 , # [doc = "**Pixel Line from**: Start point of a pixel sized line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Pixel Line from"] , Description ["Start point of a pixel sized line."]] pixel_line_from : Vec3 , # [doc = "**Pixel Line to**: End point of a pixel sized line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Pixel Line to"] , Description ["End point of a pixel sized line."]] pixel_line_to : Vec3 , # [doc = "**Line from**: Start point of a line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Line from"] , Description ["Start point of a line."]] line_from : Vec3 , # [doc = "**Line to**: End point of a line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Line to"] , Description ["End point of a line."]] line_to : Vec3 , # [doc = "**Line width**: Width of line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Line width"] , Description ["Width of line."]] line_width : f32 , # [doc = "**Rect**: If attached to an entity, the entity will be converted to a UI rectangle, with optionally rounded corners and borders.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Rect"] , Description ["If attached to an entity, the entity will be converted to a UI rectangle, with optionally rounded corners and borders."]] rect : () , # [doc = "**Size from background image**: Resize this rect based on the size of the background image.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Size from background image"] , Description ["Resize this rect based on the size of the background image."]] size_from_background_image : () , });
+                components ! ("rect" , { # [doc = "**Background color**: Background color of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Background color"] , Description ["Background color of an entity with a `rect` component."]] background_color : Vec4 , # [doc = "**Background URL**: URL to an image asset.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Background URL"] , Description ["URL to an image asset."]] background_url : String , # [doc = "**Background region**: The region of the `background_url` image this rect shows, as the x and y of its top left corner followed by its width and height, in pixels.\n\nUse it to show one image out of an atlas. If not attached, the whole image is shown.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Background region"] , Description ["The region of the `background_url` image this rect shows, as the x and y of its top left corner followed by its width and height, in pixels.\nUse it to show one image out of an atlas. If not attached, the whole image is shown."]] background_region : Vec4 , # [doc = "**Background slice**: Nine-slice insets of the background image of this rect, in pixels: `x` = top, `y` = right, `z` = bottom, `w` = left.\n\nThe corners keep their size, the edges only stretch along their length, and the center stretches to fill the rect, so skinned buttons and panels can be resized without stretching their borders.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Background slice"] , Description ["Nine-slice insets of the background image of this rect, in pixels: `x` = top, `y` = right, `z` = bottom, `w` = left.\nThe corners keep their size, the edges only stretch along their length, and the center stretches to fill the rect, so skinned buttons and panels can be resized without stretching their borders."]] background_slice : Vec4 , # [doc = "**Border color**: Border color of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Border color"] , Description ["Border color of an entity with a `rect` component."]] border_color : Vec4 , # [doc = "**Border radius**: Radius for each corner of an entity with a `rect` component.\n\n`x` = top-left, `y` = top-right, `z` = bottom-left, `w` = bottom-right.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Border radius"] , Description ["Radius for each corner of an entity with a `rect` component.\n`x` = top-left, `y` = top-right, `z` = bottom-left, `w` = bottom-right."]] border_radius : Vec4 , # [doc = "**Border thickness**: Border thickness of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Border thickness"] , Description ["Border thickness of an entity with a `rect` component."]] border_thickness : f32 , # [doc = "**Pixel Line from**: Start point of a pixel sized line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Pixel Line from"] , Description ["Start point of a pixel sized line."]] pixel_line_from : Vec3 , # [doc = "**Pixel Line to**: End point of a pixel sized line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Pixel Line to"] , Description ["End point of a pixel sized line."]] pixel_line_to : Vec3 , # [doc = "**Line from**: Start point of a line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Line from"] , Description ["Start point of a line."]] line_from : Vec3 , # [doc = "**Line to**: End point of a line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Line to"] , Description ["End point of a line."]] line_to : Vec3 , # [doc = "**Line width**: Width of line.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Line width"] , Description ["Width of line."]] line_width : f32 , # [doc = "**Rect**: If attached to an entity, the entity will be converted to a UI rectangle, with optionally rounded corners and borders.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Rect"] , Description ["If attached to an entity, the entity will be converted to a UI rectangle, with optionally rounded corners and borders."]] rect : () , # [doc = "**Size from background image**: Resize this rect based on the size of the background image.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Size from background image"] , Description ["Resize this rect based on the size of the background image."]] size_from_background_image : () , });
             }
         }
         pub mod rendering {
@@ -820,7 +1317,167 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("rendering" , { # [doc = "**Cast shadows**: If attached, this entity will cast shadows.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Cast shadows"] , Description ["If attached, this entity will cast shadows."]] cast_shadows : () , # [doc = "**Color**: This entity will be tinted with the specified color if the color is not black.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Color"] , Description ["This entity will be tinted with the specified color if the color is not black."]] color : Vec4 , # [doc = "**Double-sided**: If attached, this controls whether or not the entity will be rendered with double-sided rendering. If not attached, the decision will fall back to the material.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Double-sided"] , Description ["If attached, this controls whether or not the entity will be rendered with double-sided rendering. If not attached, the decision will fall back to the material."]] double_sided : bool , # [doc = "**Fog color**: The color of the fog for this `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog color"] , Description ["The color of the fog for this `sun`."]] fog_color : Vec3 , # [doc = "**Fog density**: The density of the fog for this `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog density"] , Description ["The density of the fog for this `sun`."]] fog_density : f32 , # [doc = "**Fog height fall-off**: The height at which the fog will fall off (i.e. stop being visible) for this `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog height fall-off"] , Description ["The height at which the fog will fall off (i.e. stop being visible) for this `sun`."]] fog_height_falloff : f32 , # [doc = "**Joint Matrices**: Contains the matrices for each joint of this skinned mesh.\n\nThis should be used in combination with `joints`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Joint Matrices"] , Description ["Contains the matrices for each joint of this skinned mesh.\nThis should be used in combination with `joints`."]] joint_matrices : Vec :: < Mat4 > , # [doc = "**Joints**: Contains the joints that comprise this skinned mesh.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Joints"] , Description ["Contains the joints that comprise this skinned mesh."]] joints : Vec :: < EntityId > , # [doc = "**Light ambient**: The ambient light color of the `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Light ambient"] , Description ["The ambient light color of the `sun`."]] light_ambient : Vec3 , # [doc = "**Light diffuse**: The diffuse light color of the `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Light diffuse"] , Description ["The diffuse light color of the `sun`."]] light_diffuse : Vec3 , # [doc = "**Outline**: If attached, this entity will be rendered with an outline with the color specified.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Outline"] , Description ["If attached, this entity will be rendered with an outline with the color specified."]] outline : Vec4 , # [doc = "**Outline (recursive)**: If attached, this entity and all of its children will be rendered with an outline with the color specified.\n\nYou do not need to attach `outline` if you have attached `outline_recursive`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Outline (recursive)"] , Description ["If attached, this entity and all of its children will be rendered with an outline with the color specified.\nYou do not need to attach `outline` if you have attached `outline_recursive`."]] outline_recursive : Vec4 , # [doc = "**Overlay**: If attached, this entity will be rendered with an overlay.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Overlay"] , Description ["If attached, this entity will be rendered with an overlay."]] overlay : () , # [doc = "**PBR material from URL**: Load a PBR material from the URL and attach it to this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["PBR material from URL"] , Description ["Load a PBR material from the URL and attach it to this entity."]] pbr_material_from_url : String , # [doc = "**Sky**: Add a realistic skybox to the scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sky"] , Description ["Add a realistic skybox to the scene."]] sky : () , # [doc = "**Sun**: Marks this entity as a sun (i.e. its rotation will be used to control the global light direction).\n\nThe entity with the highest `sun` value takes precedence.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sun"] , Description ["Marks this entity as a sun (i.e. its rotation will be used to control the global light direction).\nThe entity with the highest `sun` value takes precedence."]] sun : f32 , # [doc = "**Transparency group**: Controls when this transparent object will be rendered. Transparent objects are sorted by `(transparency_group, z-depth)`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Transparency group"] , Description ["Controls when this transparent object will be rendered. Transparent objects are sorted by `(transparency_group, z-depth)`."]] transparency_group : i32 , # [doc = "**Water**: Add a realistic water plane to this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Water"] , Description ["Add a realistic water plane to this entity."]] water : () , # [doc = "**Decal material from URL**: Load a Decal material from the URL and attach it to this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Decal material from URL"] , Description ["Load a Decal material from the URL and attach it to this entity."]] decal_from_url : String , # [doc = "**Scissors**: Apply a scissors test to this entity (anything outside the rect will be hidden).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Scissors"] , Description ["Apply a scissors test to this entity (anything outside the rect will be hidden)."]] scissors : UVec4 , # [doc = "**Scissors (recursive)**: If attached, this entity and all of its children will be rendered with an scissor with the rect specified.\n\nYou do not need to attach `scissors` if you have attached `scissors_recursive`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Scissors (recursive)"] , Description ["If attached, this entity and all of its children will be rendered with an scissor with the rect specified.\nYou do not need to attach `scissors` if you have attached `scissors_recursive`."]] scissors_recursive : UVec4 , # [doc = "**Local bounding AABB min**: The minimum point of the local AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Local bounding AABB min"] , Description ["The minimum point of the local AABB of this entity."]] local_bounding_aabb_min : Vec3 , # [doc = "**Local bounding AABB max**: The maximum point of the local AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Local bounding AABB max"] , Description ["The maximum point of the local AABB of this entity."]] local_bounding_aabb_max : Vec3 , # [doc = "**World bounding AABB min**: The minimum point of the world AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding AABB min"] , Description ["The minimum point of the world AABB of this entity."]] world_bounding_aabb_min : Vec3 , # [doc = "**World bounding AABB max**: The maximum point of the world AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding AABB max"] , Description ["The maximum point of the world AABB of this entity."]] world_bounding_aabb_max : Vec3 , # [doc = "**World bounding sphere center**: The center of the world bounding sphere of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding sphere center"] , Description ["The center of the world bounding sphere of this entity."]] world_bounding_sphere_center : Vec3 , # [doc = "**World bounding sphere radius**: The radius of the world bounding sphere of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding sphere radius"] , Description ["The radius of the world bounding sphere of this entity."]] world_bounding_sphere_radius : f32 , });
+                components ! ("rendering" , { # [doc = "**Cast shadows**: If attached, this entity will cast shadows.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Cast shadows"] , Description ["If attached, this entity will cast shadows."]] cast_shadows : () , # [doc = "**Color**: This entity will be tinted with the specified color if the color is not black.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Color"] , Description ["This entity will be tinted with the specified color if the color is not black."]] color : Vec4 , # [doc = "**Double-sided**: If attached, this controls whether or not the entity will be rendered with double-sided rendering. If not attached, the decision will fall back to the material.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Double-sided"] , Description ["If attached, this controls whether or not the entity will be rendered with double-sided rendering. If not attached, the decision will fall back to the material."]] double_sided : bool , # [doc = "**Fog color**: The color of the fog for this `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog color"] , Description ["The color of the fog for this `sun`."]] fog_color : Vec3 , # [doc = "**Fog density**: The density of the fog for this `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog density"] , Description ["The density of the fog for this `sun`."]] fog_density : f32 , # [doc = "**Fog height fall-off**: The height at which the fog will fall off (i.e. stop being visible) for this `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Fog height fall-off"] , Description ["The height at which the fog will fall off (i.e. stop being visible) for this `sun`."]] fog_height_falloff : f32 , # [doc = "**Joint Matrices**: Contains the matrices for each joint of this skinned mesh.\n\nThis should be used in combination with `joints`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Joint Matrices"] , Description ["Contains the matrices for each joint of this skinned mesh.\nThis should be used in combination with `joints`."]] joint_matrices : Vec :: < Mat4 > , # [doc = "**Joints**: Contains the joints that comprise this skinned mesh.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Joints"] , Description ["Contains the joints that comprise this skinned mesh."]] joints : Vec :: < EntityId > , # [doc = "**Light ambient**: The ambient light color of the `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Light ambient"] , Description ["The ambient light color of the `sun`."]] light_ambient : Vec3 , # [doc = "**Light diffuse**: The diffuse light color of the `sun`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Light diffuse"] , Description ["The diffuse light color of the `sun`."]] light_diffuse : Vec3 , # [doc = "**Outline**: If attached, this entity will be rendered with an outline with the color specified, as thick as `outline_thickness`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Outline"] , Description ["If attached, this entity will be rendered with an outline with the color specified, as thick as `outline_thickness`."]] outline : Vec4 , # [doc = "**Outline (recursive)**: If attached, this entity and all of its children will be rendered with an outline with the color specified.\n\nYou do not need to attach `outline` if you have attached `outline_recursive`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Outline (recursive)"] , Description ["If attached, this entity and all of its children will be rendered with an outline with the color specified.\nYou do not need to attach `outline` if you have attached `outline_recursive`."]] outline_recursive : Vec4 , # [doc = "**Overlay**: If attached, this entity will be rendered with an overlay.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Overlay"] , Description ["If attached, this entity will be rendered with an overlay."]] overlay : () , # [doc = "**PBR material from URL**: Load a PBR material from the URL and attach it to this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["PBR material from URL"] , Description ["Load a PBR material from the URL and attach it to this entity."]] pbr_material_from_url : String , # [doc = "**Custom material from URL**: Load a custom material (a WGSL shader snippet with a typed parameter block) from the URL and attach it to this entity.\n\nThe material is reloaded whenever this component is set, or its package is reloaded.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Custom material from URL"] , Description ["Load a custom material (a WGSL shader snippet with a typed parameter block) from the URL and attach it to this entity.\nThe material is reloaded whenever this component is set, or its package is reloaded."]] custom_material_from_url : String , # [doc = "**Sky**: Add a realistic skybox to the scene.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sky"] , Description ["Add a realistic skybox to the scene."]] sky : () , # [doc = "**Sun**: Marks this entity as a sun (i.e. its rotation will be used to control the global light direction).\n\nThe entity with the highest `sun` value takes precedence.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sun"] , Description ["Marks this entity as a sun (i.e. its rotation will be used to control the global light direction).\nThe entity with the highest `sun` value takes precedence."]] sun : f32 , # [doc = "**Transparency group**: Controls when this transparent object will be rendered. Transparent objects are sorted by `(transparency_group, depth)`, drawing lower groups first and, within a group, the farthest objects first. The depth can be offset with `transparency_sort_bias`. If not attached, the group of its material is used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Transparency group"] , Description ["Controls when this transparent object will be rendered. Transparent objects are sorted by `(transparency_group, depth)`, drawing lower groups first and, within a group, the farthest objects first. The depth can be offset with `transparency_sort_bias`. If not attached, the group of its material is used."]] transparency_group : i32 , # [doc = "**Water**: Add a realistic water plane to this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Water"] , Description ["Add a realistic water plane to this entity."]] water : () , # [doc = "**Decal material from URL**: Load a Decal material from the URL and attach it to this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Decal material from URL"] , Description ["Load a Decal material from the URL and attach it to this entity."]] decal_from_url : String , # [doc = "**Scissors**: Apply a scissors test to this entity (anything outside the rect will be hidden).\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Scissors"] , Description ["Apply a scissors test to this entity (anything outside the rect will be hidden)."]] scissors : UVec4 , # [doc = "**Scissors (recursive)**: If attached, this entity and all of its children will be rendered with an scissor with the rect specified.\n\nYou do not need to attach `scissors` if you have attached `scissors_recursive`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Scissors (recursive)"] , Description ["If attached, this entity and all of its children will be rendered with an scissor with the rect specified.\nYou do not need to attach `scissors` if you have attached `scissors_recursive`."]] scissors_recursive : UVec4 , # [doc = "**Local bounding AABB min**: The minimum point of the local AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Local bounding AABB min"] , Description ["The minimum point of the local AABB of this entity."]] local_bounding_aabb_min : Vec3 , # [doc = "**Local bounding AABB max**: The maximum point of the local AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["Local bounding AABB max"] , Description ["The maximum point of the local AABB of this entity."]] local_bounding_aabb_max : Vec3 , # [doc = "**World bounding AABB min**: The minimum point of the world AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding AABB min"] , Description ["The minimum point of the world AABB of this entity."]] world_bounding_aabb_min : Vec3 , # [doc = "**World bounding AABB max**: The maximum point of the world AABB of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding AABB max"] , Description ["The maximum point of the world AABB of this entity."]] world_bounding_aabb_max : Vec3 , # [doc = "**World bounding sphere center**: The center of the world bounding sphere of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding sphere center"] , Description ["The center of the world bounding sphere of this entity."]] world_bounding_sphere_center : Vec3 , # [doc = "**World bounding sphere radius**: The radius of the world bounding sphere of this entity.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["World bounding sphere radius"] , Description ["The radius of the world bounding sphere of this entity."]] world_bounding_sphere_radius : f32 , # [doc = "**Post-processing**: The post-processing effects applied to the image of this camera, in order.\n\nThe supported effects are `ssao`, `ssr`, `bloom`, `tonemapping`, `color_grading` and `vignette`; `color_grading` expects colors between 0 and 1, so it should come after `tonemapping`. Their parameters are read from the other post-processing components of the camera.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Post-processing"] , Description ["The post-processing effects applied to the image of this camera, in order.\nThe supported effects are `ssao`, `bloom`, `tonemapping` and `vignette`. Their parameters are read from the other post-processing components of the camera."]] post_processing : Vec :: < String > , # [doc = "**SSAO radius**: The radius, in meters, within which geometry occludes ambient light when the `ssao` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["SSAO radius"] , Description ["The radius, in meters, within which geometry occludes ambient light when the `ssao` post-processing effect is applied."]] ssao_radius : f32 , # [doc = "**SSAO intensity**: How much the `ssao` post-processing effect darkens occluded areas, from 0 to 1.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["SSAO intensity"] , Description ["How much the `ssao` post-processing effect darkens occluded areas, from 0 to 1."]] ssao_intensity : f32 , # [doc = "**Bloom threshold**: The luminance above which pixels bleed into their surroundings when the `bloom` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.8"] @ [Debuggable , Networked , Store , Name ["Bloom threshold"] , Description ["The luminance above which pixels bleed into their surroundings when the `bloom` post-processing effect is applied."]] bloom_threshold : f32 , # [doc = "**Bloom intensity**: How strongly bright pixels bleed into their surroundings when the `bloom` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Bloom intensity"] , Description ["How strongly bright pixels bleed into their surroundings when the `bloom` post-processing effect is applied."]] bloom_intensity : f32 , # [doc = "**Tonemapping**: The operator used to map the colors of the image when the `tonemapping` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Tonemapping"] , Description ["The operator used to map the colors of the image when the `tonemapping` post-processing effect is applied."]] tonemapping : crate :: generated :: raw :: ambient_core :: rendering :: types :: Tonemapping , # [doc = "**Exposure**: The exposure, in stops, the image is brightened or darkened by before tonemapping when the `tonemapping` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["Exposure"] , Description ["The exposure, in stops, the image is brightened or darkened by before tonemapping when the `tonemapping` post-processing effect is applied."]] exposure : f32 , # [doc = "**Vignette intensity**: How much the `vignette` post-processing effect darkens the corners of the image, from 0 to 1.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.3"] @ [Debuggable , Networked , Store , Name ["Vignette intensity"] , Description ["How much the `vignette` post-processing effect darkens the corners of the image, from 0 to 1."]] vignette_intensity : f32 , # [doc = "**Vignette radius**: The distance from the center of the image, relative to its half diagonal, at which the `vignette` post-processing effect starts darkening it.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.75"] @ [Debuggable , Networked , Store , Name ["Vignette radius"] , Description ["The distance from the center of the image, relative to its half diagonal, at which the `vignette` post-processing effect starts darkening it."]] vignette_radius : f32 , # [doc = "**Particle emitter**: If attached, this entity emits particles from its position, simulated on the GPU.\n\nThe particles are spawned continuously at `particle_spawn_rate`, and in bursts with the `ParticleBurst` message.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Particle emitter"] , Description ["If attached, this entity emits particles from its position, simulated on the GPU.\nThe particles are spawned continuously at `particle_spawn_rate`, and in bursts with the `ParticleBurst` message."]] particle_emitter : () , # [doc = "**Particle spawn rate**: The number of particles spawned per second by this emitter.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 10.0"] @ [Debuggable , Networked , Store , Name ["Particle spawn rate"] , Description ["The number of particles spawned per second by this emitter."]] particle_spawn_rate : f32 , # [doc = "**Particle max count**: The maximum number of particles of this emitter alive at once. When it is reached, the oldest particles are replaced.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1000"] @ [Debuggable , Networked , Store , Name ["Particle max count"] , Description ["The maximum number of particles of this emitter alive at once. When it is reached, the oldest particles are replaced."]] particle_max_count : u32 , # [doc = "**Particle lifetime**: The time, in seconds, a particle of this emitter lives for.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 2.0"] @ [Debuggable , Networked , Store , Name ["Particle lifetime"] , Description ["The time, in seconds, a particle of this emitter lives for."]] particle_lifetime : f32 , # [doc = "**Particle velocity**: The velocity particles are spawned with, in the space of the emitter.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: [0.0, 0.0, 1.0]"] @ [Debuggable , Networked , Store , Name ["Particle velocity"] , Description ["The velocity particles are spawned with, in the space of the emitter."]] particle_velocity : Vec3 , # [doc = "**Particle velocity spread**: The maximum random velocity added to each spawned particle, in any direction.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Particle velocity spread"] , Description ["The maximum random velocity added to each spawned particle, in any direction."]] particle_velocity_spread : f32 , # [doc = "**Particle acceleration**: The acceleration applied to the particles, in world space. Use it for gravity or wind.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: [0.0, 0.0, 0.0]"] @ [Debuggable , Networked , Store , Name ["Particle acceleration"] , Description ["The acceleration applied to the particles, in world space. Use it for gravity or wind."]] particle_acceleration : Vec3 , # [doc = "**Particle speed over life**: A curve scaling the speed of the particles over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Particle speed over life"] , Description ["A curve scaling the speed of the particles over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used."]] particle_speed_over_life : Vec :: < f32 > , # [doc = "**Particle color over life**: A curve of the color of the particles over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Particle color over life"] , Description ["A curve of the color of the particles over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used."]] particle_color_over_life : Vec :: < Vec4 > , # [doc = "**Particle size over life**: A curve of the size of the particles, in meters, over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Particle size over life"] , Description ["A curve of the size of the particles, in meters, over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used."]] particle_size_over_life : Vec :: < f32 > , # [doc = "**Particle collision**: If true, the particles of this emitter bounce off the visible surfaces of the scene, using the depth buffer.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: false"] @ [Debuggable , Networked , Store , Name ["Particle collision"] , Description ["If true, the particles of this emitter bounce off the visible surfaces of the scene, using the depth buffer."]] particle_collision : bool , # [doc = "**Particle restitution**: The fraction of their velocity the particles keep when they bounce off a surface.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Particle restitution"] , Description ["The fraction of their velocity the particles keep when they bounce off a surface."]] particle_restitution : f32 , # [doc = "**Point light**: If attached, this entity is a point light, which lights its surroundings in all directions from its world position.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Point light"] , Description ["If attached, this entity is a point light, which lights its surroundings in all directions from its world position."]] point_light : () , # [doc = "**Spot light**: If attached, this entity is a spot light, which lights a cone along its local Z axis from its world position.\n\nThe angle of the cone is set with `light_cone_angle`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Spot light"] , Description ["If attached, this entity is a spot light, which lights a cone along its local Z axis from its world position.\nThe angle of the cone is set with `light_cone_angle`."]] spot_light : () , # [doc = "**Light color**: The color of a `point_light` or `spot_light`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: [1.0, 1.0, 1.0]"] @ [Debuggable , Networked , Store , Name ["Light color"] , Description ["The color of a `point_light` or `spot_light`."]] light_color : Vec3 , # [doc = "**Light intensity**: The intensity of a `point_light` or `spot_light`, which scales its `light_color`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Light intensity"] , Description ["The intensity of a `point_light` or `spot_light`, which scales its `light_color`."]] light_intensity : f32 , # [doc = "**Light range**: The distance, in meters, beyond which a `point_light` or `spot_light` has no effect. The light fades out smoothly towards it.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 10.0"] @ [Debuggable , Networked , Store , Name ["Light range"] , Description ["The distance, in meters, beyond which a `point_light` or `spot_light` has no effect. The light fades out smoothly towards it."]] light_range : f32 , # [doc = "**Light cone angle**: The angle, in radians, between the axis of a `spot_light` and the edge of its cone.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.7853982"] @ [Debuggable , Networked , Store , Name ["Light cone angle"] , Description ["The angle, in radians, between the axis of a `spot_light` and the edge of its cone."]] light_cone_angle : f32 , # [doc = "**Light shadows**: If attached to a `point_light` or `spot_light`, it will cast shadows.\n\nOnly the closest few lights to the camera with this component get shadow maps each frame.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Light shadows"] , Description ["If attached to a `point_light` or `spot_light`, it will cast shadows.\nOnly the closest few lights to the camera with this component get shadow maps each frame."]] light_shadows : () , # [doc = "**LOD group**: If attached, the children of this entity are its levels of detail, from the most detailed to the least.\n\nOnly the level matching the size of this entity on the screen is shown, as picked with `lod_screen_sizes`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["LOD group"] , Description ["If attached, the children of this entity are its levels of detail, from the most detailed to the least.\nOnly the level matching the size of this entity on the screen is shown, as picked with `lod_screen_sizes`."]] lod_group : () , # [doc = "**LOD screen sizes**: The minimum screen size of each level of a `lod_group`, from the most detailed level to the least.\n\nThe screen size is the fraction of the height of the screen covered by the bounding sphere of the group. Below the last size, no level is shown, which culls small objects.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["LOD screen sizes"] , Description ["The minimum screen size of each level of a `lod_group`, from the most detailed level to the least.\nThe screen size is the fraction of the height of the screen covered by the bounding sphere of the group. Below the last size, no level is shown, which culls small objects."]] lod_screen_sizes : Vec :: < f32 > , # [doc = "**LOD crossfade duration**: The time, in seconds, over which a `lod_group` crossfades between two levels of detail. If zero, the levels are switched instantly.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["LOD crossfade duration"] , Description ["The time, in seconds, over which a `lod_group` crossfades between two levels of detail. If zero, the levels are switched instantly."]] lod_crossfade_duration : f32 , # [doc = "**LOD cull screen size**: If attached, this entity is not rendered by the main camera when the bounding sphere covers less than this fraction of the height of the screen.\n\nThis is cheaper than a `lod_group` for culling small objects with a single level of detail.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["LOD cull screen size"] , Description ["If attached, this entity is not rendered by the main camera when the bounding sphere covers less than this fraction of the height of the screen.\nThis is cheaper than a `lod_group` for culling small objects with a single level of detail."]] lod_cull_screen_size : f32 , # [doc = "**Terrain**: If attached along with `terrain_heights` and `terrain_resolution`, this entity is rendered as a heightmap terrain, and gets a matching heightfield collider.\n\nThe terrain covers the samples from the local origin along +X and +Y, with more detail close to the camera. Use `scale` to change the spacing between samples and the height.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Terrain"] , Description ["If attached along with `terrain_heights` and `terrain_resolution`, this entity is rendered as a heightmap terrain, and gets a matching heightfield collider.\nThe terrain covers the samples from the local origin along +X and +Y, with more detail close to the camera. Use `scale` to change the spacing between samples and the height."]] terrain : () , # [doc = "**Terrain heights**: The height of each sample of a `terrain`, row by row: the sample at `(x, y)` is at index `y * resolution.x + x`, and is placed at `(x, y, height)` in the entity's local space.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Terrain heights"] , Description ["The height of each sample of a `terrain`, row by row: the sample at `(x, y)` is at index `y * resolution.x + x`, and is placed at `(x, y, height)` in the entity's local space."]] terrain_heights : Vec :: < f32 > , # [doc = "**Terrain resolution**: The number of samples along the X and Y axes of a `terrain`. Each axis needs at least 2 samples.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Terrain resolution"] , Description ["The number of samples along the X and Y axes of a `terrain`. Each axis needs at least 2 samples."]] terrain_resolution : UVec2 , # [doc = "**Terrain splatmap**: The weights of the four material layers of a `terrain` at each sample, in the same order as `terrain_heights`. Each weight is a byte, with the first layer in the lowest byte; the layers are blended in proportion to their weights.\n\nIf not attached, the first layer covers the whole terrain.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Terrain splatmap"] , Description ["The weights of the four material layers of a `terrain` at each sample, in the same order as `terrain_heights`. Each weight is a byte, with the first layer in the lowest byte; the layers are blended in proportion to their weights.\nIf not attached, the first layer covers the whole terrain."]] terrain_splatmap : Vec :: < u32 > , # [doc = "**Terrain layer colors**: The color of each of the four material layers of a `terrain`, multiplied with its texture. Layers without a color are white.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Terrain layer colors"] , Description ["The color of each of the four material layers of a `terrain`, multiplied with its texture. Layers without a color are white."]] terrain_layer_colors : Vec :: < Vec3 > , # [doc = "**Terrain layer textures**: The URL of the base color texture of each of the four material layers of a `terrain`. Layers without a texture, or with an empty URL, only use their color.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Terrain layer textures"] , Description ["The URL of the base color texture of each of the four material layers of a `terrain`. Layers without a texture, or with an empty URL, only use their color."]] terrain_layer_textures : Vec :: < String > , # [doc = "**Terrain layer scale**: The size, in meters, of one repetition of the layer textures of a `terrain`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 4.0"] @ [Debuggable , Networked , Store , Name ["Terrain layer scale"] , Description ["The size, in meters, of one repetition of the layer textures of a `terrain`."]] terrain_layer_scale : f32 , # [doc = "**Outline thickness**: The thickness, in pixels, of the `outline` of this entity, between 1 and 255. Defaults to 3.\n\nWhen attached along with `outline_recursive`, it also applies to all of the children of this entity.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 3.0"] @ [Debuggable , Networked , Store , Name ["Outline thickness"] , Description ["The thickness, in pixels, of the `outline` of this entity, between 1 and 255. Defaults to 3.\nWhen attached along with `outline_recursive`, it also applies to all of the children of this entity."]] outline_thickness : f32 , # [doc = "**SSR intensity**: How strongly surfaces reflect their surroundings when the `ssr` post-processing effect is applied, from 0 to 1.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["SSR intensity"] , Description ["How strongly surfaces reflect their surroundings when the `ssr` post-processing effect is applied, from 0 to 1."]] ssr_intensity : f32 , # [doc = "**SSR max distance**: How far, in meters, reflected rays are followed when the `ssr` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 20.0"] @ [Debuggable , Networked , Store , Name ["SSR max distance"] , Description ["How far, in meters, reflected rays are followed when the `ssr` post-processing effect is applied."]] ssr_max_distance : f32 , # [doc = "**Planar reflection**: Makes this entity a planar reflection probe: the scene is rendered mirrored across the XY plane of this entity, and reflective surfaces lying on that plane, like water and mirrors, reflect that image instead of what is visible on screen.\n\nOnly the closest probe facing the camera is rendered.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Planar reflection"] , Description ["Makes this entity a planar reflection probe: the scene is rendered mirrored across the XY plane of this entity, and reflective surfaces lying on that plane, like water and mirrors, reflect that image instead of what is visible on screen.\nOnly the closest probe facing the camera is rendered."]] planar_reflection : () , # [doc = "**Texture streaming priority**: How important the textures of this entity are when the streamed textures exceed their GPU memory budget: the textures of the entities with the highest priority, scaled by how large they are on the screen, are given the most detail first. Defaults to 1.\n\nAt 0, its textures stay at their lowest resolution.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Texture streaming priority"] , Description ["How important the textures of this entity are when the streamed textures exceed their GPU memory budget: the textures of the entities with the highest priority, scaled by how large they are on the screen, are given the most detail first. Defaults to 1.\nAt 0, its textures stay at their lowest resolution."]] texture_streaming_priority : f32 , # [doc = "**Sprite**: If attached, this entity is rendered as a sprite: a flat quad showing the texture at this URL, or the image of a texture atlas at this URL (like `sprites.atlas.json#coin`), blended with what is behind it.\n\nThe quad is centered on the entity, and sized from `sprite_region` and `sprite_pixels_per_unit`. It faces the camera as set by `sprite_billboard`, and is tinted by `color`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sprite"] , Description ["If attached, this entity is rendered as a sprite: a flat quad showing the texture at this URL, or the image of a texture atlas at this URL (like `sprites.atlas.json#coin`), blended with what is behind it.\nThe quad is centered on the entity, and sized from `sprite_region` and `sprite_pixels_per_unit`. It faces the camera as set by `sprite_billboard`, and is tinted by `color`."]] sprite : String , # [doc = "**Sprite region**: The region of the texture of the `sprite` this entity shows, as the x and y of its top left corner followed by its width and height, in pixels.\n\nUse it to pick one image out of an atlas. If not attached, the whole texture, or the image of the texture atlas, is shown.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sprite region"] , Description ["The region of the texture of the `sprite` this entity shows, as the x and y of its top left corner followed by its width and height, in pixels.\nUse it to pick one image out of an atlas. If not attached, the whole texture, or the image of the texture atlas, is shown."]] sprite_region : Vec4 , # [doc = "**Sprite pixels per unit**: How many pixels of the texture of the `sprite` cover one meter of its quad.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 100.0"] @ [Debuggable , Networked , Store , Name ["Sprite pixels per unit"] , Description ["How many pixels of the texture of the `sprite` cover one meter of its quad."]] sprite_pixels_per_unit : f32 , # [doc = "**Sprite billboard**: How the quad of the `sprite` of this entity turns to face the camera. If not attached, it fully faces the camera.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Sprite billboard"] , Description ["How the quad of the `sprite` of this entity turns to face the camera. If not attached, it fully faces the camera."]] sprite_billboard : crate :: generated :: raw :: ambient_core :: rendering :: types :: SpriteBillboard , # [doc = "**Sun shadows**: If attached to a `sun`, controls whether it casts shadows. Turning them off skips rendering the shadow cascades, which is cheaper. If not attached, the sun casts shadows.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Sun shadows"] , Description ["If attached to a `sun`, controls whether it casts shadows. Turning them off skips rendering the shadow cascades, which is cheaper. If not attached, the sun casts shadows."]] sun_shadows : bool , # [doc = "**Environment map from URL**: If attached, the scene is lit by the environment map at this URL: an HDR image of its surroundings, prefiltered by the `EnvironmentMaps` asset pipeline. It gives the scene diffuse and specular image-based lighting in place of the `light_ambient` of the sun, and is shown as the sky by the `sky` of the scene.\n\nIf several entities have one, the first found is used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Environment map from URL"] , Description ["If attached, the scene is lit by the environment map at this URL: an HDR image of its surroundings, prefiltered by the `EnvironmentMaps` asset pipeline. It gives the scene diffuse and specular image-based lighting in place of the `light_ambient` of the sun, and is shown as the sky by the `sky` of the scene.\nIf several entities have one, the first found is used."]] environment_map_from_url : String , # [doc = "**Environment map intensity**: How strongly the `environment_map_from_url` of this entity lights the scene, and how bright it is as the sky.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Environment map intensity"] , Description ["How strongly the `environment_map_from_url` of this entity lights the scene, and how bright it is as the sky."]] environment_map_intensity : f32 , # [doc = "**Wind velocity**: The direction and speed of the wind blowing over the scene of this entity, in meters per second. Materials with wind parameters, like foliage, sway with it. Only one wind is used per scene; without one, a gentle breeze of 1 m/s blows along +X.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Wind velocity"] , Description ["The direction and speed of the wind blowing over the scene of this entity, in meters per second. Materials with wind parameters, like foliage, sway with it. Only one wind is used per scene; without one, a gentle breeze of 1 m/s blows along +X."]] wind_velocity : Vec3 , # [doc = "**Wind gust strength**: If attached next to `wind_velocity`, how much the wind varies in gusts travelling across the scene, from 0 (a steady wind) to 1. Defaults to 0.5 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"] @ [Debuggable , Networked , Store , Name ["Wind gust strength"] , Description ["If attached next to `wind_velocity`, how much the wind varies in gusts travelling across the scene, from 0 (a steady wind) to 1. Defaults to 0.5 if not attached."]] wind_gust_strength : f32 , # [doc = "**Color grading LUT from URL**: The 3D lookup table the `color_grading` post-processing effect of this camera remaps colors with, as an image of N slices of N x N pixels side by side (N² pixels wide and N pixels high). Red increases to the right within each slice, green downwards, and blue from one slice to the next.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Color grading LUT from URL"] , Description ["The 3D lookup table the `color_grading` post-processing effect of this camera remaps colors with, as an image of N slices of N x N pixels side by side (N² pixels wide and N pixels high). Red increases to the right within each slice, green downwards, and blue from one slice to the next."]] color_grading_lut_from_url : String , # [doc = "**Color grading LUT blend from URL**: A second lookup table for the `color_grading` post-processing effect of this camera, laid out like `color_grading_lut_from_url`. The colors are remapped with a mix of both tables, set by `color_grading_lut_blend`.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Color grading LUT blend from URL"] , Description ["A second lookup table for the `color_grading` post-processing effect of this camera, laid out like `color_grading_lut_from_url`. The colors are remapped with a mix of both tables, set by `color_grading_lut_blend`."]] color_grading_lut_blend_from_url : String , # [doc = "**Color grading LUT blend**: How much of the `color_grading_lut_blend_from_url` table of this camera is used, from 0 (only `color_grading_lut_from_url`) to 1 (only `color_grading_lut_blend_from_url`). Animate it to transition between moods.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"] @ [Debuggable , Networked , Store , Name ["Color grading LUT blend"] , Description ["How much of the `color_grading_lut_blend_from_url` table of this camera is used, from 0 (only `color_grading_lut_from_url`) to 1 (only `color_grading_lut_blend_from_url`). Animate it to transition between moods."]] color_grading_lut_blend : f32 , # [doc = "**Color grading intensity**: How strongly the `color_grading` post-processing effect of this camera is applied, from 0 (the original colors) to 1 (the colors of the lookup tables).\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"] @ [Debuggable , Networked , Store , Name ["Color grading intensity"] , Description ["How strongly the `color_grading` post-processing effect of this camera is applied, from 0 (the original colors) to 1 (the colors of the lookup tables)."]] color_grading_intensity : f32 , # [doc = "**Transparency sort bias**: Moves this transparent object towards the camera by this many meters when sorting it against the other transparent objects of its `transparency_group`, so that it is drawn over them. Negative values move it away. Use it to keep layered effects, like particles inside glass, in a stable order from every angle. If not attached, the bias of its material is used.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Transparency sort bias"] , Description ["Moves this transparent object towards the camera by this many meters when sorting it against the other transparent objects of its `transparency_group`, so that it is drawn over them. Negative values move it away. Use it to keep layered effects, like particles inside glass, in a stable order from every angle. If not attached, the bias of its material is used."]] transparency_sort_bias : f32 , });
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{Entity, EntityId};
+                use ambient_package_rt::message_serde::{
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
+                };
+                use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
+                #[derive(Clone, Debug)]
+                #[doc = "**ParticleBurst**: Sent by a client module: spawns `count` particles at once from the particle emitter `emitter`, for explosions and impacts."]
+                pub struct ParticleBurst {
+                    pub emitter: EntityId,
+                    pub count: u32,
+                }
+                impl ParticleBurst {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(emitter: impl Into<EntityId>, count: impl Into<u32>) -> Self {
+                        Self {
+                            emitter: emitter.into(),
+                            count: count.into(),
+                        }
+                    }
+                }
+                impl Message for ParticleBurst {
+                    fn id() -> &'static str {
+                        "ambient_core::rendering::ParticleBurst"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.emitter.serialize_message_part(&mut output)?;
+                        self.count.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            emitter: EntityId::deserialize_message_part(&mut input)?,
+                            count: u32::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for ParticleBurst {}
+            }
+            #[doc = r" Auto-generated type definitions."]
+            pub mod types {
+                use ambient_package_rt::message_serde::*;
+                use serde;
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**Tonemapping**: An operator mapping the colors of the rendered image to the colors of the screen."]
+                pub enum Tonemapping {
+                    #[default]
+                    #[doc = "The ACES filmic curve, which keeps contrast and desaturates highlights."]
+                    Aces,
+                    #[doc = "The Reinhard curve, which compresses highlights smoothly."]
+                    Reinhard,
+                    #[doc = "Only applies the exposure, clamping the colors."]
+                    Linear,
+                }
+                impl crate::EnumComponent for Tonemapping {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Aces => Tonemapping::Aces as u32,
+                            Self::Reinhard => Tonemapping::Reinhard as u32,
+                            Self::Linear => Tonemapping::Linear as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == Tonemapping::Aces as u32 {
+                            return Some(Self::Aces);
+                        }
+                        if value == Tonemapping::Reinhard as u32 {
+                            return Some(Self::Reinhard);
+                        }
+                        if value == Tonemapping::Linear as u32 {
+                            return Some(Self::Linear);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for Tonemapping {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**SpriteBillboard**: How a sprite turns to face the camera."]
+                pub enum SpriteBillboard {
+                    #[default]
+                    #[doc = "The sprite always fully faces the camera, like a health bar."]
+                    Spherical,
+                    #[doc = "The sprite only turns around the world Z axis, staying upright, like a tree."]
+                    Cylindrical,
+                    #[doc = "The sprite does not turn, and lies on the XY plane of the entity."]
+                    Fixed,
+                }
+                impl crate::EnumComponent for SpriteBillboard {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Spherical => SpriteBillboard::Spherical as u32,
+                            Self::Cylindrical => SpriteBillboard::Cylindrical as u32,
+                            Self::Fixed => SpriteBillboard::Fixed as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == SpriteBillboard::Spherical as u32 {
+                            return Some(Self::Spherical);
+                        }
+                        if value == SpriteBillboard::Cylindrical as u32 {
+                            return Some(Self::Cylindrical);
+                        }
+                        if value == SpriteBillboard::Fixed as u32 {
+                            return Some(Self::Fixed);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for SpriteBillboard {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod text {
@@ -836,7 +1493,7 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("text" , { # [doc = "**Font family**: Font family to be used. Can either be 'Default', 'FontAwesome', 'FontAwesomeSolid', 'Code' or a url to a font.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Font family"] , Description ["Font family to be used. Can either be 'Default', 'FontAwesome', 'FontAwesomeSolid', 'Code' or a url to a font."]] font_family : String , # [doc = "**Font size**: Size of the font.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Font size"] , Description ["Size of the font."]] font_size : f32 , # [doc = "**Font style**: Style of the font.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Font style"] , Description ["Style of the font."]] font_style : crate :: generated :: raw :: ambient_core :: text :: types :: FontStyle , # [doc = "**Text**: Create a text mesh on this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Text"] , Description ["Create a text mesh on this entity."]] text : String , });
+                components ! ("text" , { # [doc = "**Font family**: Font family to be used. Can either be 'Default', 'FontAwesome', 'FontAwesomeSolid', 'Code' or a url to a font.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Font family"] , Description ["Font family to be used. Can either be 'Default', 'FontAwesome', 'FontAwesomeSolid', 'Code' or a url to a font."]] font_family : String , # [doc = "**Font size**: Size of the font.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Font size"] , Description ["Size of the font."]] font_size : f32 , # [doc = "**Font style**: Style of the font.\n\n*Attributes*: Debuggable, Networked, Store, Enum"] @ [Debuggable , Networked , Store , Enum , Name ["Font style"] , Description ["Style of the font."]] font_style : crate :: generated :: raw :: ambient_core :: text :: types :: FontStyle , # [doc = "**Text**: Create a text mesh on this entity.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Text"] , Description ["Create a text mesh on this entity."]] text : String , # [doc = "**Locale**: The locale that text is translated to, like `en` or `fr-CA`. The runtime sets it to the locale of the system; set it to switch the locale at runtime.\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Locale"] , Description ["The locale that text is translated to, like `en` or `fr-CA`. The runtime sets it to the locale of the system; set it to switch the locale at runtime."]] locale : String , # [doc = "**String tables URL**: Loads the string tables at this URL, as written by the `StringTables` asset pipeline, and uses their strings to translate text.\n\n*Attributes*: Debuggable"] @ [Debuggable , Name ["String tables URL"] , Description ["Loads the string tables at this URL, as written by the `StringTables` asset pipeline, and uses their strings to translate text."]] string_tables_url : String , # [doc = "**Translation arguments**: The arguments of the `translation_key` string, as `name=value` pairs. A `count` argument also picks the plural form of the string.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Translation arguments"] , Description ["The arguments of the `translation_key` string, as `name=value` pairs. A `count` argument also picks the plural form of the string."]] translation_args : Vec :: < String > , # [doc = "**Translation key**: Sets `text` to the string with this key in the current locale, and updates it when the locale changes.\n\n*Attributes*: Debuggable, Networked, Store"] @ [Debuggable , Networked , Store , Name ["Translation key"] , Description ["Sets `text` to the string with this key in the current locale, and updates it when the locale changes."]] translation_key : String , # [doc = "**Translation keys**: The keys of the strings of the current locale, from all the loaded string tables. The string of each key is at the same index of `translation_strings`.\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Translation keys"] , Description ["The keys of the strings of the current locale, from all the loaded string tables. The string of each key is at the same index of `translation_strings`."]] translation_keys : Vec :: < String > , # [doc = "**Translation revision**: Changes whenever the strings of the current locale do, so translations can be cached.\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Translation revision"] , Description ["Changes whenever the strings of the current locale do, so translations can be cached."]] translation_revision : u32 , # [doc = "**Translation strings**: The strings of the current locale, for each of the `translation_keys`.\n\n*Attributes*: Debuggable, Resource"] @ [Debuggable , Resource , Name ["Translation strings"] , Description ["The strings of the current locale, for each of the `translation_keys`."]] translation_strings : Vec :: < String > , });
             }
             #[doc = r" Auto-generated type definitions."]
             pub mod types {
@@ -959,14 +1616,15 @@ mod raw {
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 use std::time::Duration;
-                components ! ("ui" , { # [doc = "**Focus**: Currently focused object.\n\n*Attributes*: Debuggable, Networked, Resource"] @ [Debuggable , Networked , Resource , Name ["Focus"] , Description ["Currently focused object."]] focus : String , # [doc = "**Focus**: This entity can be focused. The value is the focus id.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Focus"] , Description ["This entity can be focused. The value is the focus id."]] focusable : String , });
+                components ! ("ui" , { # [doc = "**Focus**: Currently focused object.\n\n*Attributes*: Debuggable, Networked, Resource"] @ [Debuggable , Networked , Resource , Name ["Focus"] , Description ["Currently focused object."]] focus : String , # [doc = "**Focus**: This entity can be focused. The value is the focus id.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Focus"] , Description ["This entity can be focused. The value is the focus id."]] focusable : String , # [doc = "**Focus order**: The position of this focusable entity in the order the focus moves in with Tab; lower values come first.\n\nEntities with the same order, or without one, are ordered by their position on the screen: top to bottom, then left to right.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Focus order"] , Description ["The position of this focusable entity in the order the focus moves in with Tab; lower values come first.\nEntities with the same order, or without one, are ordered by their position on the screen: top to bottom, then left to right."]] focus_order : i32 , # [doc = "**Focus captures navigation**: While this entity is focused, directional `FocusNavigate` messages and the activation keys don't move the focus or send `FocusActivated`.\n\nThe entity can handle the `FocusNavigate` messages itself, for example to move a cursor. Tab still moves the focus.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Focus captures navigation"] , Description ["While this entity is focused, directional `FocusNavigate` messages and the activation keys don't move the focus or send `FocusActivated`.\nThe entity can handle the `FocusNavigate` messages itself, for example to move a cursor. Tab still moves the focus."]] focus_captures_navigation : () , # [doc = "**Focus visible**: Whether the focus was moved with `FocusNavigate`, rather than by clicking.\n\nFocused elements only show that they are focused, and are only activated by the activation keys, when it was.\n\n*Attributes*: Debuggable, Networked, Resource"] @ [Debuggable , Networked , Resource , Name ["Focus visible"] , Description ["Whether the focus was moved with `FocusNavigate`, rather than by clicking.\nFocused elements only show that they are focused, and are only activated by the activation keys, when it was."]] focus_visible : bool , # [doc = "**Accessibility role**: The role of this entity for assistive technology, like screen readers: `alert`, `button`, `checkbox`, `dialog`, `group`, `heading`, `image`, `link`, `list`, `list_item`, `menu`, `menu_item`, `progress_bar`, `radio_button`, `slider`, `status`, `tab`, `tab_list`, `text` or `text_input`. Entities with a role, and entities with `text`, are part of the accessibility tree.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Accessibility role"] , Description ["The role of this entity for assistive technology, like screen readers: `alert`, `button`, `checkbox`, `dialog`, `group`, `heading`, `image`, `link`, `list`, `list_item`, `menu`, `menu_item`, `progress_bar`, `radio_button`, `slider`, `status`, `tab`, `tab_list`, `text` or `text_input`. Entities with a role, and entities with `text`, are part of the accessibility tree."]] accessibility_role : String , # [doc = "**Accessibility label**: The name that assistive technology reads for this entity. Defaults to its `text`, or to the text of its children for buttons and other roles that are named by their content.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Accessibility label"] , Description ["The name that assistive technology reads for this entity. Defaults to its `text`, or to the text of its children for buttons and other roles that are named by their content."]] accessibility_label : String , # [doc = "**Accessibility description**: A longer description that assistive technology reads after the `accessibility_label` of this entity, like the tooltip of a button.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Accessibility description"] , Description ["A longer description that assistive technology reads after the `accessibility_label` of this entity, like the tooltip of a button."]] accessibility_description : String , # [doc = "**Accessibility value**: The value that assistive technology reads for this entity, like the text of a text input or the position of a slider. Checkboxes and radio buttons are checked when it is `true`.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Accessibility value"] , Description ["The value that assistive technology reads for this entity, like the text of a text input or the position of a slider. Checkboxes and radio buttons are checked when it is `true`."]] accessibility_value : String , # [doc = "**Accessibility hidden**: Hides this entity and its children from assistive technology, for decorations and other elements that aren't meaningful.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Accessibility hidden"] , Description ["Hides this entity and its children from assistive technology, for decorations and other elements that aren't meaningful."]] accessibility_hidden : () , # [doc = "**Accessibility live**: Assistive technology announces the changes of this entity and its children, without it being focused. Use it for status and HUD text, like a score or a timer.\n\n*Attributes*: Debuggable, Networked"] @ [Debuggable , Networked , Name ["Accessibility live"] , Description ["Assistive technology announces the changes of this entity and its children, without it being focused. Use it for status and HUD text, like a score or a timer."]] accessibility_live : () , });
             }
             #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
             #[doc = r" and with other modules."]
             pub mod messages {
                 use crate::{Entity, EntityId};
                 use ambient_package_rt::message_serde::{
-                    Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
                 };
                 use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
                 #[derive(Clone, Debug)]
@@ -1002,6 +1660,336 @@ mod raw {
                     }
                 }
                 impl ModuleMessage for FocusChanged {}
+                #[derive(Clone, Debug)]
+                #[doc = "**FocusNavigate**: Moves the focus: to the nearest focusable entity in `direction` on the screen, where +y is down, or by `step` entities in the focus order if `direction` is zero. Sent by the runtime for the Tab and arrow keys; packages can send it to navigate with other inputs, like gamepads."]
+                pub struct FocusNavigate {
+                    pub direction: Vec2,
+                    pub step: i32,
+                }
+                impl FocusNavigate {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(direction: impl Into<Vec2>, step: impl Into<i32>) -> Self {
+                        Self {
+                            direction: direction.into(),
+                            step: step.into(),
+                        }
+                    }
+                }
+                impl Message for FocusNavigate {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::FocusNavigate"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.direction.serialize_message_part(&mut output)?;
+                        self.step.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            direction: Vec2::deserialize_message_part(&mut input)?,
+                            step: i32::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for FocusNavigate {}
+                #[derive(Clone, Debug)]
+                #[doc = "**FocusActivated**: The focused entity was activated, like a click. Sent by the runtime for the Enter and Space keys; packages can send it to activate with other inputs, like gamepads."]
+                pub struct FocusActivated {
+                    pub focus: String,
+                }
+                impl FocusActivated {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(focus: impl Into<String>) -> Self {
+                        Self {
+                            focus: focus.into(),
+                        }
+                    }
+                }
+                impl Message for FocusActivated {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::FocusActivated"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.focus.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            focus: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for FocusActivated {}
+                #[derive(Clone, Debug)]
+                #[doc = "**NotificationShow**: Shows a notification in the corner of the screen. Notifications with a higher `priority` are shown first, and each is hidden after `timeout` seconds, or when dismissed if it's zero. A notification with the same non-empty `id` as a shown one replaces it. Each of the `actions` is a button, which sends `NotificationAction` and dismisses the notification. Sent by the runtime for errors of modules; packages can send it to notify the player."]
+                pub struct NotificationShow {
+                    pub id: String,
+                    pub title: String,
+                    pub body: String,
+                    pub level: crate::generated::raw::ambient_core::ui::types::NotificationLevel,
+                    pub priority: i32,
+                    pub timeout: f32,
+                    pub actions: Vec<String>,
+                }
+                impl NotificationShow {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        id: impl Into<String>,
+                        title: impl Into<String>,
+                        body: impl Into<String>,
+                        level: impl Into<
+                            crate::generated::raw::ambient_core::ui::types::NotificationLevel,
+                        >,
+                        priority: impl Into<i32>,
+                        timeout: impl Into<f32>,
+                        actions: impl Into<Vec<String>>,
+                    ) -> Self {
+                        Self {
+                            id: id.into(),
+                            title: title.into(),
+                            body: body.into(),
+                            level: level.into(),
+                            priority: priority.into(),
+                            timeout: timeout.into(),
+                            actions: actions.into(),
+                        }
+                    }
+                }
+                impl Message for NotificationShow {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::NotificationShow"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.title.serialize_message_part(&mut output)?;
+                        self.body.serialize_message_part(&mut output)?;
+                        self.level.serialize_message_part(&mut output)?;
+                        self.priority.serialize_message_part(&mut output)?;
+                        self.timeout.serialize_message_part(&mut output)?;
+                        self.actions.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            title: String::deserialize_message_part(&mut input)?,
+                            body: String::deserialize_message_part(&mut input)?,
+                            level: crate::generated::raw::ambient_core::ui::types::NotificationLevel::deserialize_message_part(&mut input)?,
+                            priority: i32::deserialize_message_part(&mut input)?,
+                            timeout: f32::deserialize_message_part(&mut input)?,
+                            actions: Vec::<String>::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for NotificationShow {}
+                #[derive(Clone, Debug)]
+                #[doc = "**NotificationDismiss**: Hides the notification with `id`."]
+                pub struct NotificationDismiss {
+                    pub id: String,
+                }
+                impl NotificationDismiss {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(id: impl Into<String>) -> Self {
+                        Self { id: id.into() }
+                    }
+                }
+                impl Message for NotificationDismiss {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::NotificationDismiss"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for NotificationDismiss {}
+                #[derive(Clone, Debug)]
+                #[doc = "**NotificationAction**: One of the `actions` of the notification with `id` was clicked. Sent by the runtime."]
+                pub struct NotificationAction {
+                    pub id: String,
+                    pub action: String,
+                }
+                impl NotificationAction {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(id: impl Into<String>, action: impl Into<String>) -> Self {
+                        Self {
+                            id: id.into(),
+                            action: action.into(),
+                        }
+                    }
+                }
+                impl Message for NotificationAction {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::NotificationAction"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.action.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            action: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for NotificationAction {}
+                #[derive(Clone, Debug)]
+                #[doc = "**ModalShow**: Shows a modal dialog, which blocks the UI until one of its `actions` is clicked, which sends `ModalResponse`. Dialogs are shown one at a time, in the order they were sent, and one without actions has an `Ok` action."]
+                pub struct ModalShow {
+                    pub id: String,
+                    pub title: String,
+                    pub body: String,
+                    pub actions: Vec<String>,
+                }
+                impl ModalShow {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        id: impl Into<String>,
+                        title: impl Into<String>,
+                        body: impl Into<String>,
+                        actions: impl Into<Vec<String>>,
+                    ) -> Self {
+                        Self {
+                            id: id.into(),
+                            title: title.into(),
+                            body: body.into(),
+                            actions: actions.into(),
+                        }
+                    }
+                }
+                impl Message for ModalShow {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::ModalShow"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.title.serialize_message_part(&mut output)?;
+                        self.body.serialize_message_part(&mut output)?;
+                        self.actions.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            title: String::deserialize_message_part(&mut input)?,
+                            body: String::deserialize_message_part(&mut input)?,
+                            actions: Vec::<String>::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for ModalShow {}
+                #[derive(Clone, Debug)]
+                #[doc = "**ModalResponse**: One of the `actions` of the modal dialog with `id` was clicked. Sent by the runtime."]
+                pub struct ModalResponse {
+                    pub id: String,
+                    pub action: String,
+                }
+                impl ModalResponse {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(id: impl Into<String>, action: impl Into<String>) -> Self {
+                        Self {
+                            id: id.into(),
+                            action: action.into(),
+                        }
+                    }
+                }
+                impl Message for ModalResponse {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::ModalResponse"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.action.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            action: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for ModalResponse {}
+            }
+            pub mod types {
+                use ambient_package_rt::message_serde::*;
+                use serde;
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**NotificationLevel**: How important a notification is, which determines its color."]
+                pub enum NotificationLevel {
+                    #[default]
+                    #[doc = "Information, like a hint or a status change."]
+                    Info,
+                    #[doc = "Something finished successfully."]
+                    Success,
+                    #[doc = "Something might be wrong."]
+                    Warning,
+                    #[doc = "Something failed."]
+                    Error,
+                }
+                impl crate::EnumComponent for NotificationLevel {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Info => NotificationLevel::Info as u32,
+                            Self::Success => NotificationLevel::Success as u32,
+                            Self::Warning => NotificationLevel::Warning as u32,
+                            Self::Error => NotificationLevel::Error as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == NotificationLevel::Info as u32 {
+                            return Some(Self::Info);
+                        }
+                        if value == NotificationLevel::Success as u32 {
+                            return Some(Self::Success);
+                        }
+                        if value == NotificationLevel::Warning as u32 {
+                            return Some(Self::Warning);
+                        }
+                        if value == NotificationLevel::Error as u32 {
+                            return Some(Self::Error);
+                        }
+                        None
+                    }
+                }
+                impl MessageSerde for NotificationLevel {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod wasm {
@@ -1025,7 +2013,8 @@ mod raw {
         pub mod messages {
             use crate::{Entity, EntityId};
             use ambient_package_rt::message_serde::{
-                Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                RuntimeMessage,
             };
             use glam::{Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4};
             #[derive(Clone, Debug)]
@@ -1096,6 +2085,170 @@ mod raw {
             }
             impl RuntimeMessage for Collision {}
             #[derive(Clone, Debug)]
+            #[doc = "**ContactFilter**: Sent to the packages with `contact_filter` for the pairs of touching entities that haven't been decided yet, where at least one has `contact_filtered`. `first[i]` and `second[i]` are a pair; answer with `physics::set_contact_filter`."]
+            pub struct ContactFilter {
+                pub first: Vec<EntityId>,
+                pub second: Vec<EntityId>,
+            }
+            impl ContactFilter {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    first: impl Into<Vec<EntityId>>,
+                    second: impl Into<Vec<EntityId>>,
+                ) -> Self {
+                    Self {
+                        first: first.into(),
+                        second: second.into(),
+                    }
+                }
+            }
+            impl Message for ContactFilter {
+                fn id() -> &'static str {
+                    "ambient_core::ContactFilter"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.first.serialize_message_part(&mut output)?;
+                    self.second.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        first: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                        second: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for ContactFilter {}
+            #[derive(Clone, Debug)]
+            #[doc = "**Splash**: Sent when a dynamic `body` enters or leaves the buoyancy `volume` (see `buoyancy_volume`)."]
+            pub struct Splash {
+                pub volume: EntityId,
+                pub body: EntityId,
+                pub entered: bool,
+            }
+            impl Splash {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    volume: impl Into<EntityId>,
+                    body: impl Into<EntityId>,
+                    entered: impl Into<bool>,
+                ) -> Self {
+                    Self {
+                        volume: volume.into(),
+                        body: body.into(),
+                        entered: entered.into(),
+                    }
+                }
+            }
+            impl Message for Splash {
+                fn id() -> &'static str {
+                    "ambient_core::Splash"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.volume.serialize_message_part(&mut output)?;
+                    self.body.serialize_message_part(&mut output)?;
+                    self.entered.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        volume: EntityId::deserialize_message_part(&mut input)?,
+                        body: EntityId::deserialize_message_part(&mut input)?,
+                        entered: bool::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for Splash {}
+            #[derive(Clone, Debug)]
+            #[doc = "**BodySleep**: Sent when dynamic entities fall asleep (see `sleeping`)."]
+            pub struct BodySleep {
+                pub ids: Vec<EntityId>,
+            }
+            impl BodySleep {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(ids: impl Into<Vec<EntityId>>) -> Self {
+                    Self { ids: ids.into() }
+                }
+            }
+            impl Message for BodySleep {
+                fn id() -> &'static str {
+                    "ambient_core::BodySleep"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.ids.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        ids: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for BodySleep {}
+            #[derive(Clone, Debug)]
+            #[doc = "**BodyWake**: Sent when sleeping dynamic entities wake up (see `sleeping`)."]
+            pub struct BodyWake {
+                pub ids: Vec<EntityId>,
+            }
+            impl BodyWake {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(ids: impl Into<Vec<EntityId>>) -> Self {
+                    Self { ids: ids.into() }
+                }
+            }
+            impl Message for BodyWake {
+                fn id() -> &'static str {
+                    "ambient_core::BodyWake"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.ids.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        ids: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for BodyWake {}
+            #[derive(Clone, Debug)]
+            #[doc = "**JointBroken**: Sent when the joint between `entity0` and `entity1` breaks (see `joint_break_force` and `joint_break_torque`). Either entity is null if the joint was attached to the world."]
+            pub struct JointBroken {
+                pub entity0: EntityId,
+                pub entity1: EntityId,
+            }
+            impl JointBroken {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(entity0: impl Into<EntityId>, entity1: impl Into<EntityId>) -> Self {
+                    Self {
+                        entity0: entity0.into(),
+                        entity1: entity1.into(),
+                    }
+                }
+            }
+            impl Message for JointBroken {
+                fn id() -> &'static str {
+                    "ambient_core::JointBroken"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.entity0.serialize_message_part(&mut output)?;
+                    self.entity1.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        entity0: EntityId::deserialize_message_part(&mut input)?,
+                        entity1: EntityId::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for JointBroken {}
+            #[derive(Clone, Debug)]
             #[doc = "**ColliderLoads**: Sent when colliders load."]
             pub struct ColliderLoads {
                 pub ids: Vec<EntityId>,
@@ -1259,6 +2412,33 @@ mod raw {
             }
             impl RuntimeMessage for WindowKeyboardCharacter {}
             #[derive(Clone, Debug)]
+            #[doc = "**WindowImeComposition**: Sent when the text being composed with an input method editor changes. It is empty once the composition is committed, as `WindowKeyboardCharacter` messages, or cancelled."]
+            pub struct WindowImeComposition {
+                pub text: String,
+            }
+            impl WindowImeComposition {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(text: impl Into<String>) -> Self {
+                    Self { text: text.into() }
+                }
+            }
+            impl Message for WindowImeComposition {
+                fn id() -> &'static str {
+                    "ambient_core::WindowImeComposition"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.text.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        text: String::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WindowImeComposition {}
+            #[derive(Clone, Debug)]
             #[doc = "**WindowKeyboardModifiersChange**: Sent when the window's keyboard modifiers change."]
             pub struct WindowKeyboardModifiersChange {
                 pub modifiers: u32,
@@ -1527,6 +2707,47 @@ mod raw {
                 }
             }
             impl RuntimeMessage for WasmRebuild {}
+            #[derive(Clone, Debug)]
+            #[doc = "**RelevancyChange**: Sent on the server when entities start or stop being replicated to `player` (see `replication_radius` and `interest_group`). Entities that were despawned are not in `left`."]
+            pub struct RelevancyChange {
+                pub player: EntityId,
+                pub entered: Vec<EntityId>,
+                pub left: Vec<EntityId>,
+            }
+            impl RelevancyChange {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    player: impl Into<EntityId>,
+                    entered: impl Into<Vec<EntityId>>,
+                    left: impl Into<Vec<EntityId>>,
+                ) -> Self {
+                    Self {
+                        player: player.into(),
+                        entered: entered.into(),
+                        left: left.into(),
+                    }
+                }
+            }
+            impl Message for RelevancyChange {
+                fn id() -> &'static str {
+                    "ambient_core::RelevancyChange"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.player.serialize_message_part(&mut output)?;
+                    self.entered.serialize_message_part(&mut output)?;
+                    self.left.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        player: EntityId::deserialize_message_part(&mut input)?,
+                        entered: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                        left: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for RelevancyChange {}
         }
         pub fn init() {
             crate::generated::raw::ambient_core::animation::components::init_components();
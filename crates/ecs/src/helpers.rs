@@ -60,7 +60,7 @@ pub fn ensure_has_component_with_make_default<
         )
 }
 
-fn add_component_recursive<T: ComponentValue + Clone + PartialEq + 'static>(
+pub fn add_component_recursive<T: ComponentValue + Clone + PartialEq + 'static>(
     world: &mut World,
     entity: EntityId,
     component: Component<T>,
@@ -254,7 +254,7 @@ impl WorldStreamFilter {
             .filter(|&comp| (self.component_filter)(comp, WorldStreamCompEvent::Init))
             .collect_vec()
     }
-    fn read_entity_components(&self, world: &World, id: EntityId) -> Vec<ComponentEntry> {
+    pub fn read_entity_components(&self, world: &World, id: EntityId) -> Vec<ComponentEntry> {
         self.get_entity_components(world, id)
             .into_iter()
             .map(|comp| world.get_entry(id, comp).unwrap())
@@ -52,6 +52,8 @@ fn spawn_entity(world: &mut World, mat: SharedMaterial) -> EntityId {
                     material_shader: GridShaderKey.get(assets),
                     lit: false,
                     shadow_cascades: config.shadow_cascades,
+                    wind: false,
+                    imposter: false,
                 }
                 .get(assets)
             }),
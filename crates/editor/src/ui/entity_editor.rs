@@ -216,6 +216,11 @@ fn EntityComponentsEditor(
         // reg_default_component!("Collider type", true, collider_type()),
         reg_default_component!("Mass", true, mass()),
         reg_default_component!("Audio Emitter", false, ambient_world_audio::audio_emitter()),
+        reg_default_component!(
+            "Audio attenuation",
+            false,
+            ambient_world_audio::audio_attenuation()
+        ),
         // reg_default_component!("Loop animation", true, loop_animation()),
         // reg_default_component!("Animation retargeting", true, animation_retargeting()),
         reg_default_component!("Snap to ground", true, snap_to_ground()),
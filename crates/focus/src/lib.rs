@@ -1,40 +1,204 @@
 use ambient_ecs::{
     generated::{
         input::components::mouse_over_entity,
-        messages::WindowMouseInput,
+        layout::components::{height, width},
+        messages::{WindowKeyboardInput, WindowMouseInput},
+        transform::components::local_to_world,
         ui::{
-            components::{focus, focusable},
-            messages::FocusChanged,
+            components::{focus, focus_captures_navigation, focus_order, focus_visible, focusable},
+            messages::{FocusActivated, FocusChanged, FocusNavigate},
         },
     },
-    read_messages, world_events, FnSystem, SystemGroup, WorldEventReader, WorldEventsExt,
+    query, read_messages, world_events, FnSystem, SystemGroup, World, WorldEventReader,
+    WorldEventsExt,
 };
+use ambient_shared_types::{ModifiersState, VirtualKeyCode};
+use glam::{vec2, Vec2};
+use itertools::Itertools;
 
 pub fn systems() -> SystemGroup {
-    let mut reader = WorldEventReader::new();
+    let mut mouse_reader = WorldEventReader::new();
+    let mut keyboard_reader = WorldEventReader::new();
+    let mut navigate_reader = WorldEventReader::new();
     SystemGroup::new(
         "focus",
-        vec![Box::new(FnSystem::new(move |world, _| {
-            for event in
-                read_messages::<WindowMouseInput>(&mut reader, world.resource(world_events()))
-            {
-                if event.button == 0 {
-                    let mouse_over = *world.resource(mouse_over_entity());
-                    let focus_id = world
-                        .get_cloned(mouse_over, focusable())
-                        .unwrap_or_default();
-                    let cur_focus = world.resource(focus()).clone();
-                    if cur_focus != focus_id {
-                        *world.resource_mut(focus()) = focus_id.clone();
-                        world
-                            .resource_mut(world_events())
-                            .add_message(FocusChanged {
-                                from_external: false,
-                                focus: focus_id,
-                            });
+        vec![
+            Box::new(FnSystem::new(move |world, _| {
+                for event in read_messages::<WindowMouseInput>(
+                    &mut mouse_reader,
+                    world.resource(world_events()),
+                ) {
+                    if event.button == 0 {
+                        let mouse_over = *world.resource(mouse_over_entity());
+                        let focus_id = world
+                            .get_cloned(mouse_over, focusable())
+                            .unwrap_or_default();
+                        set_focus(world, focus_id, false);
                     }
                 }
-            }
-        }))],
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                for event in read_messages::<WindowKeyboardInput>(
+                    &mut keyboard_reader,
+                    world.resource(world_events()),
+                ) {
+                    if !event.pressed {
+                        continue;
+                    }
+                    let Some(keycode) =
+                        event.keycode.and_then(|k| k.parse::<VirtualKeyCode>().ok())
+                    else {
+                        continue;
+                    };
+                    let modifiers = ModifiersState::from_bits_truncate(event.modifiers);
+                    let navigate = match keycode {
+                        VirtualKeyCode::Tab if modifiers.shift() => {
+                            FocusNavigate::new(Vec2::ZERO, -1)
+                        }
+                        VirtualKeyCode::Tab => FocusNavigate::new(Vec2::ZERO, 1),
+                        VirtualKeyCode::Left => FocusNavigate::new(-Vec2::X, 0),
+                        VirtualKeyCode::Right => FocusNavigate::new(Vec2::X, 0),
+                        VirtualKeyCode::Up => FocusNavigate::new(-Vec2::Y, 0),
+                        VirtualKeyCode::Down => FocusNavigate::new(Vec2::Y, 0),
+                        VirtualKeyCode::Return
+                        | VirtualKeyCode::NumpadEnter
+                        | VirtualKeyCode::Space => {
+                            // Only elements focused by navigation are activated, so that the
+                            // keys still reach the game after a button is clicked
+                            let focus_id = world.resource(focus()).clone();
+                            if !focus_id.is_empty()
+                                && *world.resource(focus_visible())
+                                && !is_captured(world, &focus_id)
+                            {
+                                world
+                                    .resource_mut(world_events())
+                                    .add_message(FocusActivated::new(focus_id));
+                            }
+                            continue;
+                        }
+                        _ => continue,
+                    };
+                    // Sent rather than applied, so that packages capturing the navigation see it
+                    world.resource_mut(world_events()).add_message(navigate);
+                }
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                for event in read_messages::<FocusNavigate>(
+                    &mut navigate_reader,
+                    world.resource(world_events()),
+                ) {
+                    navigate(world, event.direction, event.step);
+                }
+            })),
+        ],
     )
 }
+
+/// Focuses the entity with the focus id `focus_id`, showing that it's focused if `visible`
+pub fn set_focus(world: &mut World, focus_id: String, visible: bool) {
+    let cur_focus = world.resource(focus()).clone();
+    let visible = visible && !focus_id.is_empty();
+    if cur_focus != focus_id || *world.resource(focus_visible()) != visible {
+        *world.resource_mut(focus()) = focus_id.clone();
+        *world.resource_mut(focus_visible()) = visible;
+        world
+            .resource_mut(world_events())
+            .add_message(FocusChanged {
+                from_external: false,
+                focus: focus_id,
+            });
+    }
+}
+
+/// Whether the entity with the focus id `focus_id` handles the navigation itself
+fn is_captured(world: &World, focus_id: &str) -> bool {
+    query(focusable())
+        .incl(focus_captures_navigation())
+        .iter(world, None)
+        .any(|(_, id)| id == focus_id)
+}
+
+/// A focusable entity, and its rectangle on the screen
+struct Focusable {
+    focus_id: String,
+    order: i32,
+    position: Vec2,
+    size: Vec2,
+}
+impl Focusable {
+    fn center(&self) -> Vec2 {
+        self.position + self.size / 2.
+    }
+}
+
+/// The focusable entities, in the focus order
+fn focusables(world: &World) -> Vec<Focusable> {
+    query((focusable(), local_to_world()))
+        .iter(world, None)
+        .filter(|(_, (focus_id, _))| !focus_id.is_empty())
+        .map(|(id, (focus_id, local_to_world))| {
+            let (_, _, position) = local_to_world.to_scale_rotation_translation();
+            Focusable {
+                focus_id: focus_id.clone(),
+                order: world.get(id, focus_order()).unwrap_or(0),
+                position: position.truncate(),
+                size: vec2(
+                    world.get(id, width()).unwrap_or(0.),
+                    world.get(id, height()).unwrap_or(0.),
+                ),
+            }
+        })
+        // Several entities can share a focus id; the first one stands for all of them
+        .unique_by(|f| f.focus_id.clone())
+        .sorted_by(|a, b| {
+            a.order
+                .cmp(&b.order)
+                .then(a.position.y.total_cmp(&b.position.y))
+                .then(a.position.x.total_cmp(&b.position.x))
+        })
+        .collect()
+}
+
+/// Moves the focus in `direction`, or by `step` in the focus order if `direction` is zero
+fn navigate(world: &mut World, direction: Vec2, step: i32) {
+    let focusables = focusables(world);
+    if focusables.is_empty() {
+        return;
+    }
+    let focus_id = world.resource(focus()).clone();
+    let current = focusables.iter().position(|f| f.focus_id == focus_id);
+    if direction != Vec2::ZERO && current.is_some() && is_captured(world, &focus_id) {
+        return;
+    }
+
+    let next = match current {
+        // The arrow keys are often used by the game, so only Tab starts navigating
+        None if direction != Vec2::ZERO => return,
+        None if step < 0 => focusables.len() - 1,
+        None => 0,
+        Some(current) if direction == Vec2::ZERO => {
+            (current as i32 + step).rem_euclid(focusables.len() as i32) as usize
+        }
+        Some(current) => {
+            let direction = direction.normalize();
+            let from = focusables[current].center();
+            // The nearest entity in `direction`, preferring the ones in line with the current one
+            let nearest = focusables
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != current)
+                .filter_map(|(i, f)| {
+                    let delta = f.center() - from;
+                    let along = delta.dot(direction);
+                    let across = delta.perp_dot(direction).abs();
+                    (along > 0.).then_some((i, along + across * 2.))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b));
+            match nearest {
+                Some((i, _)) => i,
+                None => return,
+            }
+        }
+    };
+    set_focus(world, focusables[next].focus_id.clone(), true);
+}
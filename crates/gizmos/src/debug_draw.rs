@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use glam::{vec2, Quat, Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::{GizmoPrimitive, DEFAULT_WIDTH};
+
+/// A shape drawn through the debug draw API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DebugShape {
+    Line {
+        start: Vec3,
+        end: Vec3,
+        color: Vec3,
+    },
+    WireSphere {
+        center: Vec3,
+        radius: f32,
+        color: Vec3,
+    },
+    WireBox {
+        center: Vec3,
+        half_extents: Vec3,
+        rotation: Quat,
+        color: Vec3,
+    },
+    Text {
+        position: Vec3,
+        text: String,
+        /// The height of a line of text
+        size: f32,
+        color: Vec3,
+    },
+}
+
+impl DebugShape {
+    /// Turns the shape into gizmos; text is laid out facing a camera with `camera_rotation`
+    pub fn to_primitives(&self, camera_rotation: Quat, out: &mut Vec<GizmoPrimitive>) {
+        match self {
+            Self::Line { start, end, color } => {
+                // A line without a direction can't be oriented
+                if start != end {
+                    out.push(GizmoPrimitive::line(*start, *end, DEFAULT_WIDTH).with_color(*color));
+                }
+            }
+            Self::WireSphere {
+                center,
+                radius,
+                color,
+            } => {
+                out.push(GizmoPrimitive::torus(*center, *radius, DEFAULT_WIDTH).with_color(*color));
+            }
+            Self::WireBox {
+                center,
+                half_extents,
+                rotation,
+                color,
+            } => {
+                let corner = |i: usize| {
+                    let sign = Vec3::new(
+                        if i & 1 == 0 { -1. } else { 1. },
+                        if i & 2 == 0 { -1. } else { 1. },
+                        if i & 4 == 0 { -1. } else { 1. },
+                    );
+                    *center + *rotation * (sign * *half_extents)
+                };
+                // Every pair of corners which differ along a single axis is an edge
+                for i in 0..8 {
+                    for axis in [1, 2, 4] {
+                        if i & axis == 0 {
+                            out.push(
+                                GizmoPrimitive::line(corner(i), corner(i | axis), DEFAULT_WIDTH)
+                                    .with_color(*color),
+                            );
+                        }
+                    }
+                }
+            }
+            Self::Text {
+                position,
+                text,
+                size,
+                color,
+            } => {
+                let right = camera_rotation * Vec3::X;
+                let up = camera_rotation * Vec3::Y;
+                let unit = *size / GLYPH_HEIGHT;
+                let radius = *size * 0.04;
+
+                for (row, line) in text.lines().enumerate() {
+                    let width = line.chars().count() as f32 * GLYPH_ADVANCE - GLYPH_SPACING;
+                    let origin = vec2(-width / 2., -(row as f32) * LINE_ADVANCE);
+                    for (column, c) in line.chars().enumerate() {
+                        let offset = origin + vec2(column as f32 * GLYPH_ADVANCE, 0.);
+                        for (start, end) in glyph_segments(c) {
+                            let point = |p: Vec2| {
+                                let p = (offset + p) * unit;
+                                *position + right * p.x + up * p.y
+                            };
+                            out.push(
+                                GizmoPrimitive::line(point(start), point(end), radius)
+                                    .with_color(*color),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Shapes drawn by the modules on this side, each kept until its expiry time
+#[derive(Debug, Clone, Default)]
+pub struct DebugDraw {
+    shapes: Vec<(DebugShape, Duration)>,
+    changed: bool,
+}
+
+impl DebugDraw {
+    /// Draws `shape` for `duration` from `time`; a shape drawn for no time is kept for one frame
+    pub fn draw(&mut self, shape: DebugShape, time: Duration, duration: Duration) {
+        self.shapes.push((shape, time + duration));
+        self.changed = true;
+    }
+
+    /// Removes the shapes which expired before `time`
+    pub fn expire(&mut self, time: Duration) {
+        let count = self.shapes.len();
+        self.shapes.retain(|(_, expires)| *expires >= time);
+        self.changed |= self.shapes.len() != count;
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = &DebugShape> {
+        self.shapes.iter().map(|(shape, _)| shape)
+    }
+
+    /// The current shapes, if they changed since this was last called
+    pub(crate) fn take_changed(&mut self) -> Option<Vec<DebugShape>> {
+        if !std::mem::take(&mut self.changed) {
+            return None;
+        }
+        Some(self.shapes().cloned().collect())
+    }
+}
+
+const GLYPH_HEIGHT: f32 = 2.;
+const GLYPH_SPACING: f32 = 0.5;
+const GLYPH_ADVANCE: f32 = 1. + GLYPH_SPACING;
+const LINE_ADVANCE: f32 = GLYPH_HEIGHT + 1.;
+
+/// The segments of a glyph in a sixteen-segment display font, in a cell one unit wide and two high.
+///
+/// The segments are named as on a sixteen-segment display, where `a`/`A`, `d`/`D` and `g`/`G` are
+/// the left and right halves of the top, bottom and middle segments, and `p` is a dot.
+/// Lowercase letters are drawn as uppercase ones, and unknown characters as `?`.
+fn glyph_segments(c: char) -> impl Iterator<Item = (Vec2, Vec2)> {
+    let segments = match c.to_ascii_uppercase() {
+        ' ' => "",
+        'A' => "aAbcefgG",
+        'B' => "aAbcdDilG",
+        'C' => "aAfedD",
+        'D' => "aAbcdDil",
+        'E' => "aAfedDg",
+        'F' => "aAfeg",
+        'G' => "aAfedDcG",
+        'H' => "fegGbc",
+        'I' => "aAildD",
+        'J' => "bcdDe",
+        'K' => "fegjm",
+        'L' => "fedD",
+        'M' => "efbchj",
+        'N' => "efbchm",
+        'O' => "aAbcdDef",
+        'P' => "aAbefgG",
+        'Q' => "aAbcdDefm",
+        'R' => "aAbefgGm",
+        'S' => "aAfgGcdD",
+        'T' => "aAil",
+        'U' => "bcdDef",
+        'V' => "efkj",
+        'W' => "efbckm",
+        'X' => "hjkm",
+        'Y' => "hjl",
+        'Z' => "aAjkdD",
+        '0' => "aAbcdDefjk",
+        '1' => "bc",
+        '2' => "aAbgGedD",
+        '3' => "aAbGcdD",
+        '4' => "fgGbc",
+        '5' => "aAfgGcdD",
+        '6' => "aAfegGcdD",
+        '7' => "aAbc",
+        '8' => "aAbcdDefgG",
+        '9' => "aAbcdDfgG",
+        '-' => "gG",
+        '+' => "gGil",
+        '_' => "dD",
+        '=' => "gGdD",
+        '*' => "gGhijklm",
+        '/' => "jk",
+        '\\' => "hm",
+        '|' => "il",
+        '(' | '<' => "jm",
+        ')' | '>' => "hk",
+        '[' => "AilD",
+        ']' => "aild",
+        '\'' => "i",
+        '"' => "fi",
+        '.' | ',' => "p",
+        ':' => "pq",
+        '!' => "ip",
+        '$' => "aAfgGcdDil",
+        _ => "aAbGp",
+    };
+    segments.chars().map(|segment| {
+        let (start, end) = match segment {
+            'a' => ((0., 2.), (0.5, 2.)),
+            'A' => ((0.5, 2.), (1., 2.)),
+            'b' => ((1., 2.), (1., 1.)),
+            'c' => ((1., 1.), (1., 0.)),
+            'd' => ((0., 0.), (0.5, 0.)),
+            'D' => ((0.5, 0.), (1., 0.)),
+            'e' => ((0., 1.), (0., 0.)),
+            'f' => ((0., 2.), (0., 1.)),
+            'g' => ((0., 1.), (0.5, 1.)),
+            'G' => ((0.5, 1.), (1., 1.)),
+            'h' => ((0., 2.), (0.5, 1.)),
+            'i' => ((0.5, 2.), (0.5, 1.)),
+            'j' => ((1., 2.), (0.5, 1.)),
+            'k' => ((0.5, 1.), (0., 0.)),
+            'l' => ((0.5, 1.), (0.5, 0.)),
+            'm' => ((0.5, 1.), (1., 0.)),
+            'p' => ((0.5, 0.), (0.5, 0.2)),
+            'q' => ((0.5, 1.), (0.5, 1.2)),
+            _ => unreachable!("unknown glyph segment {segment}"),
+        };
+        (Vec2::from(start), Vec2::from(end))
+    })
+}
@@ -1,12 +1,17 @@
 use std::borrow::{Borrow, Cow};
 
-use ambient_ecs::{components, query, Debuggable, Networked, Resource, SystemGroup};
+use ambient_core::{game_time, name};
+use ambient_ecs::{
+    components, query, Debuggable, Entity, FnSystem, Networked, Resource, SystemGroup,
+};
 use glam::{Mat4, Vec2};
 
+mod debug_draw;
 pub mod render;
 mod traits;
 use ambient_native_std::{math::Line, CowStr};
 use dashmap::{mapref::one::RefMut, DashMap};
+pub use debug_draw::*;
 use glam::Vec3;
 pub use traits::*;
 
@@ -18,6 +23,13 @@ components!("gizmos", {
     /// Gizmos for an entity.
     @[Networked, Debuggable]
     local_gizmos: Vec<GizmoPrimitive>,
+
+    /// The shapes drawn by the modules on this side through the debug draw API
+    @[Resource]
+    debug_draw: DebugDraw,
+    /// The shapes drawn by the server modules through the debug draw API, replicated to the clients
+    @[Networked, Debuggable]
+    debug_draw_shapes: Vec<DebugShape>,
 });
 
 #[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -357,6 +369,50 @@ pub fn client_systems() -> SystemGroup {
                         world.resource(gizmos()).remove_scope(id.to_string());
                     }
                 }),
+            // Runs before the modules, so that the shapes they draw are rendered at least once
+            Box::new(FnSystem::new(|world, _| {
+                let time = *world.resource(game_time());
+                if let Some(debug_draw) = world.resource_mut_opt(debug_draw()) {
+                    debug_draw.expire(time);
+                }
+            })),
+        ],
+    )
+}
+
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "debug_draw/server",
+        vec![
+            // Runs after the modules, so that the shapes they draw are replicated at least once
+            Box::new(FnSystem::new(|world, _| {
+                profiling::scope!("debug_draw_sync");
+                let time = *world.resource(game_time());
+                let Some(debug_draw) = world.resource_mut_opt(debug_draw()) else {
+                    return;
+                };
+                debug_draw.expire(time);
+                let Some(shapes) = debug_draw.take_changed() else {
+                    return;
+                };
+
+                let entity = query(())
+                    .incl(debug_draw_shapes())
+                    .iter(world, None)
+                    .map(|(id, _)| id)
+                    .next();
+                match entity {
+                    Some(id) => {
+                        world.set(id, debug_draw_shapes(), shapes).unwrap();
+                    }
+                    None => {
+                        Entity::new()
+                            .with(name(), "Debug draw".to_string())
+                            .with(debug_draw_shapes(), shapes)
+                            .spawn(world);
+                    }
+                }
+            })),
         ],
     )
 }
@@ -1,7 +1,7 @@
 use std::{fmt::Debug, sync::Arc};
 
 use ambient_core::{asset_cache, camera::Camera, main_scene, player::local_user_id};
-use ambient_ecs::World;
+use ambient_ecs::{query, World};
 use ambient_gpu::{
     gpu::Gpu,
     mesh_buffer::{GpuMesh, MeshBuffer},
@@ -25,7 +25,7 @@ use wgpu::{
     ColorTargetState, ColorWrites, ShaderStages,
 };
 
-use super::{gizmos, GizmoPrimitive};
+use super::{debug_draw, debug_draw_shapes, gizmos, GizmoPrimitive};
 
 fn get_gizmos_layout() -> BindGroupDesc<'static> {
     BindGroupDesc {
@@ -60,6 +60,7 @@ pub struct GizmoRenderer {
     pipeline: OnceCell<GraphicsPipeline>,
     buffer: TypedBuffer<Gizmo>,
     primitives: Vec<Gizmo>,
+    debug_primitives: Vec<GizmoPrimitive>,
     layout: Arc<BindGroupLayout>,
 }
 impl Debug for GizmoRenderer {
@@ -84,6 +85,7 @@ impl GizmoRenderer {
             pipeline: OnceCell::new(),
             buffer,
             primitives: Vec::new(),
+            debug_primitives: Vec::new(),
             layout,
         }
     }
@@ -117,6 +119,26 @@ impl SubRenderer for GizmoRenderer {
             );
         });
 
+        // Debug shapes drawn locally, and replicated from the server
+        let debug_primitives = &mut self.debug_primitives;
+        debug_primitives.clear();
+        let camera_rotation = camera.view.inverse().to_scale_rotation_translation().1;
+        let local_shapes = world
+            .resource_opt(debug_draw())
+            .into_iter()
+            .flat_map(|debug_draw| debug_draw.shapes());
+        let remote_shapes = query(debug_draw_shapes())
+            .iter(world, None)
+            .flat_map(|(_, shapes)| shapes.iter());
+        for shape in local_shapes.chain(remote_shapes) {
+            shape.to_primitives(camera_rotation, debug_primitives);
+        }
+        primitives.extend(
+            debug_primitives
+                .iter()
+                .map(|v| Gizmo::from_primitive(v, camera.position())),
+        );
+
         if primitives.is_empty() {
             return;
         }
@@ -0,0 +1,177 @@
+//! Loads the textures compressed to the Basis Universal format by the asset pipeline, by
+//! transcoding them to a compressed format the GPU supports.
+//!
+//! Natively, the files are transcoded with the `basis-universal` crate. On the web, they are
+//! transcoded with the transcoder of Basis Universal for JavaScript, which the web client loads.
+use std::sync::Arc;
+
+use ambient_native_std::{
+    asset_cache::{AssetCache, AssetKeepalive, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::{AssetError, BytesFromUrl},
+};
+use ambient_sys::task;
+use anyhow::Context;
+use async_trait::async_trait;
+use glam::UVec2;
+
+use crate::{
+    gpu::{Gpu, GpuKey},
+    texture::Texture,
+};
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "unknown")] {
+        mod wasm;
+        use wasm as transcoder;
+    } else {
+        mod native;
+        use native as transcoder;
+    }
+}
+
+/// The extension of Basis Universal files
+pub const EXTENSION: &str = "basis";
+
+/// Whether `url` is a Basis Universal file
+pub fn is_basis_url(url: &AbsAssetUrl) -> bool {
+    url.extension_is(EXTENSION)
+}
+
+/// The formats the textures are transcoded to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscodeFormat {
+    Bc7,
+    Astc4x4,
+    Etc2,
+    /// Uncompressed, for the GPUs which support none of the compressed formats
+    Rgba8,
+}
+impl TranscodeFormat {
+    /// The best format `gpu` supports
+    pub fn for_gpu(gpu: &Gpu) -> Self {
+        let features = gpu.device.features();
+        if features.contains(wgpu::Features::TEXTURE_COMPRESSION_BC) {
+            Self::Bc7
+        } else if features.contains(wgpu::Features::TEXTURE_COMPRESSION_ASTC) {
+            Self::Astc4x4
+        } else if features.contains(wgpu::Features::TEXTURE_COMPRESSION_ETC2) {
+            Self::Etc2
+        } else {
+            Self::Rgba8
+        }
+    }
+    pub fn texture_format(self, srgb: bool) -> wgpu::TextureFormat {
+        let format = match self {
+            Self::Bc7 => wgpu::TextureFormat::Bc7RgbaUnorm,
+            Self::Astc4x4 => wgpu::TextureFormat::Astc {
+                block: wgpu::AstcBlock::B4x4,
+                channel: wgpu::AstcChannel::Unorm,
+            },
+            Self::Etc2 => wgpu::TextureFormat::Etc2Rgba8Unorm,
+            Self::Rgba8 => wgpu::TextureFormat::Rgba8Unorm,
+        };
+        if srgb {
+            format.add_srgb_suffix()
+        } else {
+            format
+        }
+    }
+    /// The memory used by a texel of a texture in this format
+    pub fn bytes_per_texel(self) -> u64 {
+        match self {
+            Self::Bc7 | Self::Astc4x4 | Self::Etc2 => 1,
+            Self::Rgba8 => 4,
+        }
+    }
+}
+
+/// A Basis Universal file, with the sizes of its mips
+#[derive(Debug)]
+pub struct BasisFile {
+    pub data: Arc<Vec<u8>>,
+    pub mips: Vec<UVec2>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BasisFileFromUrl {
+    pub url: AbsAssetUrl,
+}
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<BasisFile>, AssetError>> for BasisFileFromUrl {
+    async fn load(self, assets: AssetCache) -> Result<Arc<BasisFile>, AssetError> {
+        let data = BytesFromUrl::new(self.url.clone(), true)
+            .get(&assets)
+            .await?;
+        let mips = task::block_in_place(|| transcoder::mips(&data))
+            .with_context(|| format!("Failed to read Basis Universal file \"{}\"", self.url))?;
+        if mips.is_empty() {
+            return Err(anyhow::anyhow!("\"{}\" has no mips", self.url).into());
+        }
+        Ok(Arc::new(BasisFile { data, mips }))
+    }
+}
+
+/// The texture of a Basis Universal file without its `mip` largest mips, transcoded to the best
+/// format the GPU supports
+#[derive(Debug, Clone)]
+pub struct TextureMipFromBasis {
+    pub url: AbsAssetUrl,
+    /// Whether the texture holds colors, which are in the sRGB color space
+    pub srgb: bool,
+    pub mip: u32,
+}
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<Texture>, AssetError>> for TextureMipFromBasis {
+    fn gpu_size(&self, asset: &Result<Arc<Texture>, AssetError>) -> Option<u64> {
+        asset.as_ref().ok().map(|x| x.size_in_bytes)
+    }
+    /// The mips are streamed in and out, so their memory is freed as soon as they are unused
+    fn keepalive(&self) -> AssetKeepalive {
+        AssetKeepalive::None
+    }
+    async fn load(self, assets: AssetCache) -> Result<Arc<Texture>, AssetError> {
+        let gpu = GpuKey.get(&assets);
+        let file = BasisFileFromUrl {
+            url: self.url.clone(),
+        }
+        .get(&assets)
+        .await?;
+        let texture = task::block_in_place(|| -> anyhow::Result<Texture> {
+            let mips = file.mips.len() as u32;
+            let mip = self.mip.min(mips - 1);
+            let size = file.mips[mip as usize];
+            // The compressed formats are made of blocks of 4x4 texels, so the textures in them
+            // must have sizes which are multiples of 4
+            let format = if size.x % 4 == 0 && size.y % 4 == 0 {
+                TranscodeFormat::for_gpu(&gpu)
+            } else {
+                TranscodeFormat::Rgba8
+            };
+            let mut data = Vec::new();
+            for level in mip..mips {
+                data.extend(transcoder::transcode(&file.data, level, format)?);
+            }
+            Ok(Texture::new_with_data(
+                &gpu,
+                &wgpu::TextureDescriptor {
+                    label: Some(&format!("{} mip={mip}", self.url)),
+                    size: wgpu::Extent3d {
+                        width: size.x,
+                        height: size.y,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: mips - mip,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: format.texture_format(self.srgb),
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                },
+                &data,
+            ))
+        })
+        .with_context(|| format!("Failed to transcode \"{}\"", self.url))?;
+        Ok(Arc::new(texture))
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Once;
+
+use anyhow::Context;
+use basis_universal::{TranscodeParameters, Transcoder, TranscoderTextureFormat};
+use glam::{uvec2, UVec2};
+
+use super::TranscodeFormat;
+
+fn transcoder(data: &[u8]) -> anyhow::Result<Transcoder> {
+    static INIT: Once = Once::new();
+    INIT.call_once(basis_universal::transcoder_init);
+
+    let mut transcoder = Transcoder::new();
+    transcoder
+        .prepare_transcoding(data)
+        .map_err(|_| anyhow::anyhow!("Invalid Basis Universal file"))?;
+    Ok(transcoder)
+}
+
+/// The sizes of the mips of the first image in `data`
+pub(super) fn mips(data: &[u8]) -> anyhow::Result<Vec<UVec2>> {
+    let mut transcoder = transcoder(data)?;
+    let mips = (0..transcoder.image_level_count(data, 0))
+        .map(|level| {
+            let description = transcoder
+                .image_level_description(data, 0, level)
+                .with_context(|| format!("Missing mip {level}"))?;
+            Ok(uvec2(
+                description.original_width,
+                description.original_height,
+            ))
+        })
+        .collect();
+    transcoder.end_transcoding();
+    mips
+}
+
+/// Transcodes the mip `level` of the first image in `data` to `format`
+pub(super) fn transcode(
+    data: &[u8],
+    level: u32,
+    format: TranscodeFormat,
+) -> anyhow::Result<Vec<u8>> {
+    let mut transcoder = transcoder(data)?;
+    let transcoded = transcoder
+        .transcode_image_level(
+            data,
+            match format {
+                TranscodeFormat::Bc7 => TranscoderTextureFormat::BC7_RGBA,
+                TranscodeFormat::Astc4x4 => TranscoderTextureFormat::ASTC_4x4_RGBA,
+                TranscodeFormat::Etc2 => TranscoderTextureFormat::ETC2_RGBA,
+                TranscodeFormat::Rgba8 => TranscoderTextureFormat::RGBA32,
+            },
+            TranscodeParameters {
+                image_index: 0,
+                level_index: level,
+                decode_flags: None,
+                output_row_pitch_in_blocks_or_pixels: None,
+                output_rows_in_pixels: None,
+            },
+        )
+        .map_err(|err| anyhow::anyhow!("Failed to transcode mip {level}: {err:?}"));
+    transcoder.end_transcoding();
+    transcoded
+}
@@ -0,0 +1,97 @@
+use glam::{uvec2, UVec2};
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+use super::TranscodeFormat;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A file of the transcoder of Basis Universal for JavaScript, which the web client loads as
+    /// `ambientBasis`
+    #[wasm_bindgen(js_namespace = ambientBasis)]
+    type BasisFile;
+
+    #[wasm_bindgen(catch, constructor, js_namespace = ambientBasis)]
+    fn new(data: &Uint8Array) -> Result<BasisFile, JsValue>;
+    #[wasm_bindgen(method, js_name = getNumLevels)]
+    fn get_num_levels(this: &BasisFile, image: u32) -> u32;
+    #[wasm_bindgen(method, js_name = getImageWidth)]
+    fn get_image_width(this: &BasisFile, image: u32, level: u32) -> u32;
+    #[wasm_bindgen(method, js_name = getImageHeight)]
+    fn get_image_height(this: &BasisFile, image: u32, level: u32) -> u32;
+    #[wasm_bindgen(method, js_name = startTranscoding)]
+    fn start_transcoding(this: &BasisFile) -> u32;
+    #[wasm_bindgen(method, js_name = getImageTranscodedSizeInBytes)]
+    fn get_image_transcoded_size_in_bytes(
+        this: &BasisFile,
+        image: u32,
+        level: u32,
+        format: u32,
+    ) -> u32;
+    #[wasm_bindgen(method, js_name = transcodeImage)]
+    fn transcode_image(
+        this: &BasisFile,
+        dst: &Uint8Array,
+        image: u32,
+        level: u32,
+        format: u32,
+        unused: u32,
+        get_alpha_for_opaque_formats: u32,
+    ) -> u32;
+    #[wasm_bindgen(method)]
+    fn close(this: &BasisFile);
+    #[wasm_bindgen(method)]
+    fn delete(this: &BasisFile);
+}
+
+/// Opens `data` with the transcoder, and frees it once `f` is done with it
+fn with_file<R>(data: &[u8], f: impl FnOnce(&BasisFile) -> anyhow::Result<R>) -> anyhow::Result<R> {
+    let file = BasisFile::new(&Uint8Array::from(data))
+        .map_err(|err| anyhow::anyhow!("The Basis Universal transcoder isn't loaded: {err:?}"))?;
+    let result = f(&file);
+    file.close();
+    file.delete();
+    result
+}
+
+/// The sizes of the mips of the first image in `data`
+pub(super) fn mips(data: &[u8]) -> anyhow::Result<Vec<UVec2>> {
+    with_file(data, |file| {
+        Ok((0..file.get_num_levels(0))
+            .map(|level| {
+                uvec2(
+                    file.get_image_width(0, level),
+                    file.get_image_height(0, level),
+                )
+            })
+            .collect())
+    })
+}
+
+/// Transcodes the mip `level` of the first image in `data` to `format`
+pub(super) fn transcode(
+    data: &[u8],
+    level: u32,
+    format: TranscodeFormat,
+) -> anyhow::Result<Vec<u8>> {
+    // The `transcoder_texture_format` of Basis Universal
+    let format = match format {
+        TranscodeFormat::Bc7 => 6,
+        TranscodeFormat::Astc4x4 => 10,
+        TranscodeFormat::Etc2 => 1,
+        TranscodeFormat::Rgba8 => 13,
+    };
+    with_file(data, |file| {
+        anyhow::ensure!(
+            file.start_transcoding() != 0,
+            "Invalid Basis Universal file"
+        );
+        let dst =
+            Uint8Array::new_with_length(file.get_image_transcoded_size_in_bytes(0, level, format));
+        anyhow::ensure!(
+            file.transcode_image(&dst, 0, level, format, 0, 0) != 0,
+            "Failed to transcode mip {level}"
+        );
+        Ok(dst.to_vec())
+    })
+}
@@ -121,6 +121,15 @@ impl Gpu {
             }
         };
 
+        // The frame statistics of the renderer time its passes when the GPU can
+        let features = features | (adapter.features() & wgpu::Features::TIMESTAMP_QUERY);
+        // The compressed textures are transcoded to the best of these formats the GPU supports
+        let features = features
+            | (adapter.features()
+                & (wgpu::Features::TEXTURE_COMPRESSION_BC
+                    | wgpu::Features::TEXTURE_COMPRESSION_ASTC
+                    | wgpu::Features::TEXTURE_COMPRESSION_ETC2));
+
         tracing::info!("Using device features: {features:?}");
 
         let (device, queue) = adapter
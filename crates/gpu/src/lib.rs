@@ -1,3 +1,4 @@
+pub mod basis;
 pub mod blit;
 pub mod fill;
 pub mod gpu;
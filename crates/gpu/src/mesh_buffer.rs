@@ -111,6 +111,49 @@ pub struct BaseMesh {
     _padding: Vec2,
 }
 
+/// The number of vertices `mesh` takes up in the base buffer, which is its longest attribute
+fn base_vertex_count(mesh: &Mesh) -> usize {
+    [
+        mesh.positions().len(),
+        mesh.normals().len(),
+        mesh.tangents().len(),
+        mesh.texcoords(0).len(),
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0)
+}
+
+/// Pads all vertex attributes to match vertex positions buffer, for the vertices in `range`.
+fn base_mesh_data(mesh: &Mesh, range: Range<usize>) -> Vec<BaseMesh> {
+    let mut data = vec![BaseMesh::default(); range.len()];
+    let slice = |len: usize| range.start.min(len)..range.end.min(len);
+
+    let pos = mesh.positions();
+    let norm = mesh.normals();
+    let tan = mesh.tangents();
+    let uv = mesh.texcoords(0);
+
+    pos[slice(pos.len())]
+        .iter()
+        .zip(&mut data)
+        .for_each(|(src, dst)| dst.position = src.extend(0.0));
+    norm[slice(norm.len())]
+        .iter()
+        .zip(&mut data)
+        .for_each(|(src, dst)| dst.normal = src.extend(0.0));
+    tan[slice(tan.len())]
+        .iter()
+        .zip(&mut data)
+        .for_each(|(src, dst)| dst.tangent = src.extend(0.0));
+    uv[slice(uv.len())]
+        .iter()
+        .zip(&mut data)
+        .for_each(|(src, dst)| dst.texcoord0 = *src);
+
+    data
+}
+
 #[repr(C)]
 #[derive(Default, Clone, Copy, Pod, Zeroable)]
 pub struct SkinnedMesh {
@@ -191,32 +234,8 @@ impl MeshBuffer {
             ..Default::default()
         };
 
-        // Pad all vertex attributes to match vertex positions buffer.
         {
-            let pos = mesh.positions();
-            let norm = mesh.normals();
-            let tan = mesh.tangents();
-            let uv = mesh.texcoords(0);
-
-            let len = ([pos.len(), norm.len(), tan.len(), uv.len()])
-                .into_iter()
-                .max()
-                .unwrap_or(0);
-
-            let mut data = vec![BaseMesh::default(); len];
-
-            pos.iter()
-                .zip(&mut data)
-                .for_each(|(src, dst)| dst.position = src.extend(0.0));
-            norm.iter()
-                .zip(&mut data)
-                .for_each(|(src, dst)| dst.normal = src.extend(0.0));
-            tan.iter()
-                .zip(&mut data)
-                .for_each(|(src, dst)| dst.tangent = src.extend(0.0));
-            uv.iter()
-                .zip(&mut data)
-                .for_each(|(src, dst)| dst.texcoord0 = *src);
+            let data = base_mesh_data(mesh, 0..base_vertex_count(mesh));
 
             self.base_buffer
                 .front
@@ -293,6 +312,52 @@ impl MeshBuffer {
         })
     }
 
+    /// Writes the `vertices` and `indices` of `mesh` over the ones `gpu_mesh` was created with.
+    ///
+    /// This only works when the mesh still has as many vertices and indices as when it was
+    /// inserted; returns false if it doesn't, in which case it needs to be inserted again.
+    /// Skinning attributes are not written.
+    pub fn write(
+        &mut self,
+        gpu: &Gpu,
+        gpu_mesh: &GpuMesh,
+        mesh: &Mesh,
+        vertices: Range<usize>,
+        indices: Range<usize>,
+    ) -> bool {
+        let Some(internal_mesh) = self.meshes[gpu_mesh.index as usize].as_ref() else {
+            return false;
+        };
+        let base_count = base_vertex_count(mesh);
+        if internal_mesh.base_count != base_count as u64
+            || internal_mesh.index_count != mesh.index_count() as u64
+        {
+            return false;
+        }
+        let metadata = internal_mesh.metadata;
+
+        let vertices = vertices.start.min(base_count)..vertices.end.min(base_count);
+        if !vertices.is_empty() {
+            self.base_buffer.front.write(
+                gpu,
+                metadata.base_offset as usize + vertices.start,
+                &base_mesh_data(mesh, vertices),
+            );
+        }
+
+        let index_count = mesh.indices().len();
+        let indices = indices.start.min(index_count)..indices.end.min(index_count);
+        if !indices.is_empty() {
+            self.index_buffer.front.write(
+                gpu,
+                metadata.index_offset as usize + indices.start,
+                &mesh.indices()[indices],
+            );
+        }
+
+        true
+    }
+
     pub fn update(&mut self, gpu: &Gpu) {
         let to_remove = {
             let mut to_remove = self.to_remove.lock();
@@ -48,8 +48,10 @@ impl Texture {
 
     fn size_in_bytes_from_desc(descriptor: &wgpu::TextureDescriptor) -> u64 {
         tracing::debug!("descriptor: {:?}", descriptor);
-        let mut mip_size = (descriptor.size.width as u64
-            * descriptor.size.height as u64
+        // Compressed formats store blocks of several texels
+        let (block_width, block_height) = descriptor.format.block_dimensions();
+        let mut mip_size = (((descriptor.size.width + block_width - 1) / block_width) as u64
+            * ((descriptor.size.height + block_height - 1) / block_height) as u64
             * descriptor.size.depth_or_array_layers as u64)
             * descriptor.format.block_size(None).unwrap() as u64;
         let mut size_in_bytes = mip_size;
@@ -1,7 +1,7 @@
 use std::{borrow::Cow, fmt, io::Cursor, sync::Arc};
 
 use ambient_native_std::{
-    asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_cache::{AssetCache, AssetKeepalive, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKeyExt},
     asset_url::AbsAssetUrl,
     download_asset::{AssetError, AssetResult, BytesFromUrl},
     CowStr,
@@ -106,6 +106,51 @@ impl AsyncAssetKey<Result<Arc<Texture>, AssetError>> for TextureFromRgba8Image {
     }
 }
 
+/// The key of an image asset, however it is loaded
+pub type Rgba8ImageKey = Arc<dyn AsyncAssetKeyExt<Result<Arc<image::RgbaImage>, AssetError>>>;
+
+/// The texture of an image without its `mip` largest mips, so half its size for each mip dropped
+#[derive(Clone, Debug)]
+pub struct TextureMipFromRgba8Image {
+    pub image: Rgba8ImageKey,
+    pub format: wgpu::TextureFormat,
+    pub mip: u32,
+}
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<Texture>, AssetError>> for TextureMipFromRgba8Image {
+    fn gpu_size(&self, asset: &Result<Arc<Texture>, AssetError>) -> Option<u64> {
+        asset.as_ref().ok().map(|x| x.size_in_bytes)
+    }
+    /// The mips are streamed in and out, so their memory is freed as soon as they are unused
+    fn keepalive(&self) -> AssetKeepalive {
+        AssetKeepalive::None
+    }
+    async fn load(self, assets: AssetCache) -> Result<Arc<Texture>, AssetError> {
+        let gpu = GpuKey.get(&assets);
+        let img = self.image.get(&assets).await?;
+        task::block_in_place(|| {
+            let label = format!("{:?} mip={}", self.image, self.mip);
+            let img = if self.mip > 0 {
+                Cow::Owned(image::imageops::resize(
+                    &*img,
+                    (img.width() >> self.mip).max(1),
+                    (img.height() >> self.mip).max(1),
+                    image::imageops::FilterType::Triangle,
+                ))
+            } else {
+                Cow::Borrowed(&*img)
+            };
+            Ok(Arc::new(Texture::from_rgba8_image_mipmapped(
+                &gpu,
+                &assets,
+                &img,
+                self.format,
+                Some(&label),
+            )))
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TextureFromBytes {
     bytes: Cow<'static, [u8]>,
@@ -43,6 +43,11 @@ impl<T> Drop for TypedBuffer<T> {
 }
 
 impl<T> TypedBuffer<T> {
+    /// The bytes allocated by all the typed buffers, whatever their type
+    pub fn total_bytes_used() -> u64 {
+        TOTAL_ALLOCATED_BYTES.load(Ordering::SeqCst)
+    }
+
     pub fn new(
         gpu: &Gpu,
         label: Option<&'static str>,
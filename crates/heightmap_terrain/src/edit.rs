@@ -0,0 +1,162 @@
+use ambient_core::transform::get_world_transform;
+use ambient_ecs::{EntityId, World};
+use anyhow::Context;
+use glam::{Mat4, UVec2, Vec2, Vec3Swizzles};
+
+use crate::{terrain, terrain_heights, terrain_resolution, terrain_splatmap, LAYER_COUNT};
+
+/// The layout of the samples of a terrain, and where it is in the world
+struct Heightmap {
+    resolution: UVec2,
+    terrain_to_world: Mat4,
+}
+
+impl Heightmap {
+    fn get(world: &World, id: EntityId) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            world.has_component(id, terrain()),
+            "Entity {id} is not a terrain"
+        );
+        let resolution = world
+            .get(id, terrain_resolution())
+            .context("Terrain has no resolution")?;
+        let len = world
+            .get_ref(id, terrain_heights())
+            .context("Terrain has no heights")?
+            .len();
+        anyhow::ensure!(
+            resolution.x >= 2 && resolution.y >= 2 && len == (resolution.x * resolution.y) as usize,
+            "Terrain has {len} heights, which does not match its resolution {resolution}"
+        );
+        Ok(Self {
+            resolution,
+            terrain_to_world: get_world_transform(world, id)?,
+        })
+    }
+
+    /// Turns a position in the world into a position in samples
+    fn to_samples(&self, position: Vec2) -> Vec2 {
+        self.terrain_to_world
+            .inverse()
+            .transform_point3(position.extend(0.))
+            .xy()
+    }
+
+    /// The samples within `radius` meters of `center`, each with the strength of the brush there
+    fn brush(&self, center: Vec2, radius: f32) -> Vec<(usize, f32)> {
+        let center = self.to_samples(center);
+        let radius = radius / self.terrain_to_world.x_axis.truncate().length();
+        if radius.is_nan() || radius <= 0. {
+            return Vec::new();
+        }
+
+        let last = (self.resolution - 1).as_vec2();
+        let min = (center - radius).ceil().clamp(Vec2::ZERO, last).as_uvec2();
+        let max = (center + radius).floor().clamp(Vec2::ZERO, last).as_uvec2();
+        let mut samples = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let t = (UVec2::new(x, y).as_vec2().distance(center) / radius).min(1.);
+                let falloff = 1. - t * t * (3. - 2. * t);
+                if falloff > 0. {
+                    samples.push(((y * self.resolution.x + x) as usize, falloff));
+                }
+            }
+        }
+        samples
+    }
+}
+
+/// Raises the terrain by `amount` meters at `center`, smoothly falling off to nothing at `radius`
+/// meters from it. A negative amount lowers the terrain.
+pub fn raise(
+    world: &mut World,
+    id: EntityId,
+    center: Vec2,
+    radius: f32,
+    amount: f32,
+) -> anyhow::Result<()> {
+    let heightmap = Heightmap::get(world, id)?;
+    let samples = heightmap.brush(center, radius);
+    let amount = amount / heightmap.terrain_to_world.z_axis.truncate().length();
+
+    let heights = world.get_mut(id, terrain_heights())?;
+    for (index, falloff) in samples {
+        heights[index] += amount * falloff;
+    }
+    Ok(())
+}
+
+/// Lowers the terrain by `amount` meters at `center`; the opposite of [raise]
+pub fn lower(
+    world: &mut World,
+    id: EntityId,
+    center: Vec2,
+    radius: f32,
+    amount: f32,
+) -> anyhow::Result<()> {
+    raise(world, id, center, radius, -amount)
+}
+
+/// Paints `layer` over the other layers of the terrain at `center`, with `strength` between 0 and
+/// 1, smoothly falling off to nothing at `radius` meters from it
+pub fn paint(
+    world: &mut World,
+    id: EntityId,
+    center: Vec2,
+    radius: f32,
+    layer: u32,
+    strength: f32,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        layer < LAYER_COUNT,
+        "Terrains have {LAYER_COUNT} layers, so there is no layer {layer}"
+    );
+    let heightmap = Heightmap::get(world, id)?;
+    let samples = heightmap.brush(center, radius);
+    let len = (heightmap.resolution.x * heightmap.resolution.y) as usize;
+
+    // Without a splatmap, the first layer covers the whole terrain
+    if world
+        .get_ref(id, terrain_splatmap())
+        .map_or(true, |splatmap| splatmap.len() != len)
+    {
+        world.add_component(id, terrain_splatmap(), vec![0xff; len])?;
+    }
+    let splatmap = world.get_mut(id, terrain_splatmap())?;
+    let strength = strength.clamp(0., 1.);
+    for (index, falloff) in samples {
+        let blend = strength * falloff;
+        let weights = splatmap[index]
+            .to_le_bytes()
+            .map(|weight| weight as f32 / 255.);
+        let mut painted = [0; 4];
+        for (i, (out, weight)) in painted.iter_mut().zip(weights).enumerate() {
+            let target = if i as u32 == layer { 1. } else { 0. };
+            *out = ((weight + (target - weight) * blend) * 255.).round() as u8;
+        }
+        splatmap[index] = u32::from_le_bytes(painted);
+    }
+    Ok(())
+}
+
+/// The height, in meters, of the terrain at `position`. Positions outside of the terrain get the
+/// height of its closest edge.
+pub fn height(world: &World, id: EntityId, position: Vec2) -> anyhow::Result<f32> {
+    let heightmap = Heightmap::get(world, id)?;
+    let heights = world.get_ref(id, terrain_heights())?;
+
+    let last = (heightmap.resolution - 1).as_vec2();
+    let position = heightmap.to_samples(position).clamp(Vec2::ZERO, last);
+    let min = position.floor().min(last - 1.);
+    let t = position - min;
+    let min = min.as_uvec2();
+    let at = |x: u32, y: u32| heights[(y * heightmap.resolution.x + x) as usize];
+    let height = (at(min.x, min.y) * (1. - t.x) + at(min.x + 1, min.y) * t.x) * (1. - t.y)
+        + (at(min.x, min.y + 1) * (1. - t.x) + at(min.x + 1, min.y + 1) * t.x) * t.y;
+
+    Ok(heightmap
+        .terrain_to_world
+        .transform_point3(position.extend(height))
+        .z)
+}
@@ -0,0 +1,335 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use ambient_core::{
+    asset_cache,
+    async_ecs::async_run,
+    bounding::{local_bounding_aabb, world_bounding_aabb, world_bounding_sphere},
+    camera::get_active_camera,
+    main_scene, mesh,
+    player::local_user_id,
+    runtime,
+    transform::{get_world_position, get_world_transform, local_to_world, mesh_to_world},
+};
+use ambient_ecs::{components, query, Entity, EntityId, SystemGroup, World};
+use ambient_gpu::{gpu::GpuKey, texture::Texture, texture_loaders::TextureFromUrl};
+use ambient_meshes::{GridMesh, GridMeshKey};
+use ambient_native_std::{
+    asset_cache::{AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    cb,
+    shapes::AABB,
+};
+use ambient_physics::collider::{collider, ColliderDef};
+use ambient_renderer::{
+    cast_shadows, color, gpu_primitives_lod, gpu_primitives_mesh, primitives, renderer_shader,
+};
+use glam::{vec3, Mat4, Quat, UVec2, Vec3Swizzles, Vec4};
+
+pub use ambient_ecs::generated::rendering::components::{
+    terrain, terrain_heights, terrain_layer_colors, terrain_layer_scale, terrain_layer_textures,
+    terrain_resolution, terrain_splatmap,
+};
+pub use edit::*;
+pub use material::*;
+
+mod edit;
+mod lod;
+mod material;
+
+use lod::{select_patches, Patch, PATCH_QUADS};
+
+/// The number of material layers a terrain blends between
+pub const LAYER_COUNT: u32 = 4;
+
+components!("rendering", {
+    /// The base color textures of the layers of a terrain, once loaded
+    terrain_layer_maps: Vec<Option<Arc<Texture>>>,
+    terrain_material: Arc<TerrainMaterial>,
+    /// The entities drawing the patches of a terrain
+    terrain_patches: HashMap<Patch, EntityId>,
+});
+
+/// If a terrain has as many heights as its resolution needs
+fn valid_terrain(world: &World, id: EntityId) -> Option<UVec2> {
+    let resolution = world.get(id, terrain_resolution()).ok()?;
+    let heights = world.get_ref(id, terrain_heights()).ok()?;
+    if resolution.x < 2
+        || resolution.y < 2
+        || heights.len() != (resolution.x * resolution.y) as usize
+    {
+        tracing::warn!(
+            "Terrain {id} has {} heights, which does not match its resolution {resolution}",
+            heights.len()
+        );
+        return None;
+    }
+    Some(resolution)
+}
+
+/// Pairs terrains with a heightfield collider
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "heightmap_terrain/server",
+        vec![query((
+            terrain(),
+            terrain_heights().changed(),
+            terrain_resolution().changed(),
+        ))
+        .to_system(|q, world, qs, _| {
+            for (id, _) in q.collect_cloned(world, qs) {
+                let Some(resolution) = valid_terrain(world, id) else {
+                    continue;
+                };
+                let heights = world.get_ref(id, terrain_heights()).unwrap().clone();
+                world
+                    .add_component(
+                        id,
+                        collider(),
+                        ColliderDef::Heightfield {
+                            heights,
+                            resolution,
+                        },
+                    )
+                    .unwrap();
+            }
+        })],
+    )
+}
+
+pub fn client_systems() -> SystemGroup {
+    SystemGroup::new(
+        "heightmap_terrain/client",
+        vec![
+            query((terrain(), terrain_layer_textures().changed())).to_system(|q, world, qs, _| {
+                for (id, (_, urls)) in q.collect_cloned(world, qs) {
+                    let assets = world.resource(asset_cache()).clone();
+                    let async_run = world.resource(async_run()).clone();
+                    world.resource(runtime()).spawn(async move {
+                        let mut maps = Vec::new();
+                        for url in urls.iter().take(LAYER_COUNT as usize) {
+                            maps.push(load_layer_map(&assets, url).await);
+                        }
+                        async_run.run(move |world| {
+                            // The terrain may have been despawned while the textures loaded
+                            world.add_component(id, terrain_layer_maps(), maps).ok();
+                        });
+                    });
+                }
+            }),
+            query((
+                terrain(),
+                terrain_heights().changed(),
+                terrain_resolution().changed(),
+            ))
+            .optional_changed(terrain_splatmap())
+            .optional_changed(terrain_layer_maps())
+            .to_system(|q, world, qs, _| {
+                for (id, _) in q.collect_cloned(world, qs) {
+                    update_material(world, id);
+                }
+            }),
+            query((terrain(), terrain_material())).to_system(|q, world, qs, _| {
+                let Some(camera) =
+                    get_active_camera(world, main_scene(), world.resource_opt(local_user_id()))
+                        .and_then(|id| get_world_position(world, id).ok())
+                else {
+                    return;
+                };
+                for (id, _) in q.collect_cloned(world, qs) {
+                    update_patches(world, id, camera);
+                }
+            }),
+            query((terrain_patches(), local_to_world().changed())).to_system(|q, world, qs, _| {
+                for (_, (patches, terrain_to_world)) in q.collect_cloned(world, qs) {
+                    for (patch, id) in patches {
+                        world
+                            .set(
+                                id,
+                                local_to_world(),
+                                patch_to_world(terrain_to_world, patch),
+                            )
+                            .ok();
+                    }
+                }
+            }),
+            query(terrain_patches())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (_, patches) in q.collect_cloned(world, qs) {
+                        for id in patches.into_values() {
+                            world.despawn(id);
+                        }
+                    }
+                }),
+        ],
+    )
+}
+
+async fn load_layer_map(
+    assets: &ambient_native_std::asset_cache::AssetCache,
+    url: &str,
+) -> Option<Arc<Texture>> {
+    if url.is_empty() {
+        return None;
+    }
+    let url = match AbsAssetUrl::from_str(url) {
+        Ok(url) => url,
+        Err(err) => {
+            tracing::warn!("Failed to parse terrain layer texture url: {:?}", err);
+            return None;
+        }
+    };
+    match (TextureFromUrl {
+        url,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+    })
+    .get(assets)
+    .await
+    {
+        Ok(texture) => Some(texture),
+        Err(err) => {
+            tracing::warn!("Failed to load terrain layer texture: {:?}", err);
+            None
+        }
+    }
+}
+
+/// Uploads the heights and splatmap of a terrain, recreating its material when the textures it
+/// needs changed
+fn update_material(world: &mut World, id: EntityId) {
+    let Some(resolution) = valid_terrain(world, id) else {
+        return;
+    };
+    let assets = world.resource(asset_cache()).clone();
+    let gpu = GpuKey.get(&assets);
+    let layers = world
+        .get_ref(id, terrain_layer_maps())
+        .cloned()
+        .unwrap_or_default();
+
+    let material = match world.get_ref(id, terrain_material()) {
+        Ok(material) if material.matches(resolution, &layers) => material.clone(),
+        _ => {
+            let material = Arc::new(TerrainMaterial::new(&gpu, &assets, id, resolution, layers));
+            world
+                .add_component(id, terrain_material(), material.clone())
+                .unwrap();
+            let patches = world
+                .get_ref(id, terrain_patches())
+                .map(|patches| patches.values().copied().collect::<Vec<_>>())
+                .unwrap_or_default();
+            for patch in patches {
+                world
+                    .set(patch, ambient_renderer::material(), material.clone().into())
+                    .ok();
+            }
+            material
+        }
+    };
+
+    let heights = world.get_ref(id, terrain_heights()).unwrap();
+    material.write_heights(&gpu, heights);
+    let splatmap = world
+        .get_ref(id, terrain_splatmap())
+        .ok()
+        .filter(|splatmap| splatmap.len() == heights.len());
+    material.write_splatmap(&gpu, splatmap.map(|splatmap| splatmap.as_slice()));
+
+    // The heights of the patches may have changed too
+    let patches = world
+        .get_ref(id, terrain_patches())
+        .cloned()
+        .unwrap_or_default();
+    for (patch, patch_id) in patches {
+        let aabb = patch_bounds(world, id, resolution, patch);
+        world.set(patch_id, local_bounding_aabb(), aabb).ok();
+    }
+}
+
+/// Picks the patches a terrain is drawn with from `camera`, and spawns or despawns their entities
+fn update_patches(world: &mut World, id: EntityId, camera: glam::Vec3) {
+    let Some(resolution) = valid_terrain(world, id) else {
+        return;
+    };
+    let terrain_to_world = get_world_transform(world, id).unwrap_or_default();
+    let camera = terrain_to_world.inverse().transform_point3(camera).xy();
+    let selected = select_patches(resolution, camera);
+
+    let mut patches = world
+        .get_ref(id, terrain_patches())
+        .cloned()
+        .unwrap_or_default();
+    if patches.len() == selected.len() && selected.iter().all(|patch| patches.contains_key(patch)) {
+        return;
+    }
+
+    patches.retain(|patch, patch_id| {
+        let keep = selected.contains(patch);
+        if !keep {
+            world.despawn(*patch_id);
+        }
+        keep
+    });
+
+    let assets = world.resource(asset_cache()).clone();
+    let material = world.get_ref(id, terrain_material()).unwrap().clone();
+    let grid = GridMeshKey(GridMesh {
+        n_vertices_width: PATCH_QUADS as usize + 1,
+        n_vertices_height: PATCH_QUADS as usize + 1,
+        ..Default::default()
+    })
+    .get(&assets);
+    for patch in selected {
+        if patches.contains_key(&patch) {
+            continue;
+        }
+        let aabb = patch_bounds(world, id, resolution, patch);
+        let patch_to_world = patch_to_world(terrain_to_world, patch);
+        let world_box = aabb.transform(&patch_to_world);
+        let patch_id = Entity::new()
+            .with(mesh(), grid.clone())
+            .with(local_to_world(), patch_to_world)
+            .with(mesh_to_world(), patch_to_world)
+            .with(renderer_shader(), cb(get_terrain_shader))
+            .with(ambient_renderer::material(), material.clone().into())
+            .with(primitives(), vec![])
+            .with(gpu_primitives_mesh(), Default::default())
+            .with(gpu_primitives_lod(), Default::default())
+            .with(color(), Vec4::ONE)
+            .with(main_scene(), ())
+            .with(cast_shadows(), ())
+            .with(local_bounding_aabb(), aabb)
+            .with(world_bounding_aabb(), world_box.to_aabb())
+            .with(world_bounding_sphere(), world_box.to_sphere())
+            .spawn(world);
+        patches.insert(patch, patch_id);
+    }
+    world.add_component(id, terrain_patches(), patches).unwrap();
+}
+
+/// Places the unit grid of a patch on its samples
+fn patch_to_world(terrain_to_world: Mat4, patch: Patch) -> Mat4 {
+    terrain_to_world
+        * Mat4::from_scale_rotation_translation(
+            vec3(patch.size as f32, patch.size as f32, 1.),
+            Quat::IDENTITY,
+            patch.origin.as_vec2().extend(0.),
+        )
+}
+
+/// The bounds of the unit grid of a patch, from the heights of the samples it covers
+fn patch_bounds(world: &World, id: EntityId, resolution: UVec2, patch: Patch) -> AABB {
+    let heights = world.get_ref(id, terrain_heights()).unwrap();
+    let (min, max) = patch.samples(resolution);
+    let mut range = (f32::MAX, f32::MIN);
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            let height = heights[(y * resolution.x + x) as usize];
+            range = (range.0.min(height), range.1.max(height));
+        }
+    }
+    AABB {
+        min: vec3(0., 0., range.0),
+        max: vec3(1., 1., range.1),
+    }
+}
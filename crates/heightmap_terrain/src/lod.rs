@@ -0,0 +1,78 @@
+use glam::{uvec2, UVec2, Vec2};
+
+/// The number of quads along each side of a patch
+pub(crate) const PATCH_QUADS: u32 = 32;
+
+/// A patch is split in four when the camera is closer than this many times the size of the
+/// smaller patches, which then morph into the larger one over the last quarter of that distance.
+///
+/// This has to be above `2 * sqrt(2) / 0.5` for neighbouring patches to differ by at most one
+/// level, and to have matching edges.
+pub(crate) const LOD_DISTANCE: u32 = 8;
+
+/// A square part of a terrain drawn with the same detail, in samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Patch {
+    pub origin: UVec2,
+    pub size: u32,
+}
+
+impl Patch {
+    /// The distance on the XY plane between `point` and the closest point of the patch
+    fn distance(&self, point: Vec2) -> f32 {
+        let min = self.origin.as_vec2();
+        let max = min + self.size as f32;
+        point.clamp(min, max).distance(point)
+    }
+
+    fn children(&self) -> impl Iterator<Item = Patch> + '_ {
+        let size = self.size / 2;
+        [uvec2(0, 0), uvec2(1, 0), uvec2(0, 1), uvec2(1, 1)]
+            .into_iter()
+            .map(move |offset| Patch {
+                origin: self.origin + offset * size,
+                size,
+            })
+    }
+
+    /// The samples covered by the patch on a terrain of `resolution` samples, as an inclusive range
+    pub fn samples(&self, resolution: UVec2) -> (UVec2, UVec2) {
+        let last = resolution - 1;
+        (self.origin.min(last), (self.origin + self.size).min(last))
+    }
+}
+
+/// The patches covering a terrain of `resolution` samples, more detailed closer to `camera`, which
+/// is in the terrain's local space
+pub(crate) fn select_patches(resolution: UVec2, camera: Vec2) -> Vec<Patch> {
+    let extent = resolution.saturating_sub(UVec2::ONE);
+    let mut size = PATCH_QUADS;
+    while size < extent.max_element() {
+        size *= 2;
+    }
+
+    let mut patches = Vec::new();
+    select(
+        Patch {
+            origin: UVec2::ZERO,
+            size,
+        },
+        extent,
+        camera,
+        &mut patches,
+    );
+    patches
+}
+
+fn select(patch: Patch, extent: UVec2, camera: Vec2, out: &mut Vec<Patch>) {
+    if patch.origin.x >= extent.x || patch.origin.y >= extent.y {
+        return;
+    }
+    if patch.size > PATCH_QUADS && patch.distance(camera) < (LOD_DISTANCE * patch.size / 2) as f32 {
+        for child in patch.children() {
+            select(child, extent, camera, out);
+        }
+    } else {
+        out.push(patch);
+    }
+}
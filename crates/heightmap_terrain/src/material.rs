@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use ambient_core::transform::get_world_transform;
+use ambient_ecs::{EntityId, World};
+use ambient_gpu::{
+    gpu::Gpu,
+    sampler::SamplerKey,
+    shader_module::{BindGroupDesc, Shader, ShaderIdent, ShaderModule},
+    std_assets::PixelTextureViewKey,
+    texture::Texture,
+};
+use ambient_gpu_ecs::ENTITIES_BIND_GROUP;
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    friendly_id,
+};
+use ambient_renderer::{
+    get_forward_modules, Material, RendererConfig, RendererShader, GLOBALS_BIND_GROUP,
+    MATERIAL_BIND_GROUP, PRIMITIVES_BIND_GROUP,
+};
+use glam::{Mat4, UVec2, Vec2, Vec4};
+use wgpu::{util::DeviceExt, BindGroup};
+
+use crate::{
+    lod::{LOD_DISTANCE, PATCH_QUADS},
+    terrain_layer_colors, terrain_layer_scale, LAYER_COUNT,
+};
+
+fn get_terrain_layout() -> BindGroupDesc<'static> {
+    let texture = |binding, visibility, filterable| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility,
+        ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    };
+    let mut entries = vec![
+        // terrain_params
+        wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        // heightmap
+        texture(1, wgpu::ShaderStages::VERTEX_FRAGMENT, false),
+        // splatmap
+        texture(2, wgpu::ShaderStages::FRAGMENT, true),
+        // layer_sampler
+        wgpu::BindGroupLayoutEntry {
+            binding: 3,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        },
+    ];
+    // layer0..layer3
+    entries.extend((0..LAYER_COUNT).map(|i| texture(4 + i, wgpu::ShaderStages::FRAGMENT, true)));
+    BindGroupDesc {
+        entries,
+        label: MATERIAL_BIND_GROUP.into(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TerrainShaderKey {
+    pub shadow_cascades: u32,
+}
+impl SyncAssetKey<Arc<RendererShader>> for TerrainShaderKey {
+    fn load(&self, assets: AssetCache) -> Arc<RendererShader> {
+        let shader = Shader::new(
+            &assets,
+            "heightmap_terrain",
+            &[
+                GLOBALS_BIND_GROUP,
+                ENTITIES_BIND_GROUP,
+                PRIMITIVES_BIND_GROUP,
+                MATERIAL_BIND_GROUP,
+            ],
+            &ShaderModule::new("HeightmapTerrain", include_str!("terrain.wgsl"))
+                .with_binding_desc(get_terrain_layout())
+                .with_ident(ShaderIdent::constant("LAYER_COUNT", LAYER_COUNT))
+                .with_ident(ShaderIdent::constant("PATCH_QUADS", PATCH_QUADS))
+                .with_ident(ShaderIdent::constant("LOD_DISTANCE", LOD_DISTANCE))
+                .with_dependencies(get_forward_modules(&assets, self.shadow_cascades)),
+        )
+        .unwrap();
+
+        Arc::new(RendererShader {
+            shader,
+            id: "heightmap_terrain".to_string(),
+            vs_main: "vs_main".to_string(),
+            fs_forward_main: "fs_forward_main".to_string(),
+            fs_shadow_main: "fs_shadow_main".to_string(),
+            fs_outline_main: "fs_outlines_main".to_string(),
+            transparent: false,
+            double_sided: false,
+            depth_write_enabled: true,
+            transparency_group: 0,
+        })
+    }
+}
+
+pub fn get_terrain_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<RendererShader> {
+    TerrainShaderKey {
+        shadow_cascades: config.shadow_cascades,
+    }
+    .get(assets)
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct TerrainParams {
+    terrain_to_world: Mat4,
+    world_to_terrain: Mat4,
+    layer_colors: [Vec4; LAYER_COUNT as usize],
+    resolution: Vec2,
+    layer_scale: f32,
+    _padding: f32,
+}
+
+/// The material of the patches of a terrain, which holds its heights and splatmap on the gpu
+#[derive(Debug)]
+pub struct TerrainMaterial {
+    id: String,
+    terrain: EntityId,
+    resolution: UVec2,
+    layers: Vec<Option<Arc<Texture>>>,
+    heightmap: Arc<Texture>,
+    splatmap: Arc<Texture>,
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+impl TerrainMaterial {
+    pub fn new(
+        gpu: &Gpu,
+        assets: &AssetCache,
+        terrain: EntityId,
+        resolution: UVec2,
+        layers: Vec<Option<Arc<Texture>>>,
+    ) -> Self {
+        let layout = get_terrain_layout().get(assets);
+
+        let texture = |label, format| {
+            Arc::new(Texture::new(
+                gpu,
+                &wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width: resolution.x,
+                        height: resolution.y,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                },
+            ))
+        };
+        let heightmap = texture("TerrainMaterial.heightmap", wgpu::TextureFormat::R32Float);
+        let splatmap = texture("TerrainMaterial.splatmap", wgpu::TextureFormat::Rgba8Unorm);
+
+        let buffer = gpu
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("TerrainMaterial.buffer"),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                contents: bytemuck::cast_slice(&[TerrainParams::default()]),
+            });
+
+        let heightmap_view = heightmap.create_view(&Default::default());
+        let splatmap_view = splatmap.create_view(&Default::default());
+        let layer_views = (0..LAYER_COUNT as usize)
+            .map(|i| match layers.get(i) {
+                Some(Some(texture)) => Arc::new(texture.create_view(&Default::default())),
+                _ => PixelTextureViewKey::white().get(assets),
+            })
+            .collect::<Vec<_>>();
+        let layer_sampler = SamplerKey::LINEAR_REPEAT.get(assets);
+
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(buffer.as_entire_buffer_binding()),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&heightmap_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(&splatmap_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Sampler(&layer_sampler),
+            },
+        ];
+        entries.extend(
+            layer_views
+                .iter()
+                .enumerate()
+                .map(|(i, view)| wgpu::BindGroupEntry {
+                    binding: 4 + i as u32,
+                    resource: wgpu::BindingResource::TextureView(view),
+                }),
+        );
+
+        Self {
+            id: friendly_id(),
+            terrain,
+            resolution,
+            bind_group: gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &layout,
+                entries: &entries,
+                label: Some("TerrainMaterial.bind_group"),
+            }),
+            layers,
+            heightmap,
+            splatmap,
+            buffer,
+        }
+    }
+
+    /// If the material can be used for a terrain with these samples and layers
+    pub fn matches(&self, resolution: UVec2, layers: &[Option<Arc<Texture>>]) -> bool {
+        self.resolution == resolution
+            && self.layers.len() == layers.len()
+            && self.layers.iter().zip(layers).all(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            })
+    }
+
+    pub fn write_heights(&self, gpu: &Gpu, heights: &[f32]) {
+        self.heightmap.write(gpu, bytemuck::cast_slice(heights));
+    }
+
+    /// Writes the layer weights of each sample; without a splatmap, the first layer covers
+    /// everything
+    pub fn write_splatmap(&self, gpu: &Gpu, splatmap: Option<&[u32]>) {
+        match splatmap {
+            Some(splatmap) => self.splatmap.write(gpu, bytemuck::cast_slice(splatmap)),
+            None => {
+                let first_layer = vec![0xffu32; (self.resolution.x * self.resolution.y) as usize];
+                self.splatmap.write(gpu, bytemuck::cast_slice(&first_layer));
+            }
+        }
+    }
+}
+impl Material for TerrainMaterial {
+    fn update(&self, gpu: &Gpu, world: &World) {
+        let Ok(terrain_to_world) = get_world_transform(world, self.terrain) else {
+            return;
+        };
+        let mut layer_colors = [Vec4::ONE; LAYER_COUNT as usize];
+        if let Ok(colors) = world.get_ref(self.terrain, terrain_layer_colors()) {
+            for (layer, color) in layer_colors.iter_mut().zip(colors) {
+                *layer = color.extend(1.);
+            }
+        }
+        let params = TerrainParams {
+            terrain_to_world,
+            world_to_terrain: terrain_to_world.inverse(),
+            layer_colors,
+            resolution: self.resolution.as_vec2(),
+            layer_scale: world
+                .get(self.terrain, terrain_layer_scale())
+                .unwrap_or(4.)
+                .max(0.001),
+            _padding: 0.,
+        };
+        gpu.queue
+            .write_buffer(&self.buffer, 0, bytemuck::cast_slice(&[params]));
+    }
+
+    fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
@@ -0,0 +1,157 @@
+struct TerrainParams {
+    terrain_to_world: mat4x4<f32>,
+    world_to_terrain: mat4x4<f32>,
+    layer_colors: array<vec4<f32>, LAYER_COUNT>,
+    resolution: vec2<f32>,
+    layer_scale: f32,
+    padding: f32,
+};
+
+@group(MATERIAL_BIND_GROUP)
+@binding(0)
+var<uniform> terrain_params: TerrainParams;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(1)
+var heightmap: texture_2d<f32>;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(2)
+var splatmap: texture_2d<f32>;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(3)
+var layer_sampler: sampler;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(4)
+var layer0: texture_2d<f32>;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(5)
+var layer1: texture_2d<f32>;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(6)
+var layer2: texture_2d<f32>;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(7)
+var layer3: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) terrain_position: vec2<f32>,
+    @location(1) world_position: vec4<f32>,
+    @location(2) @interpolate(flat) instance_index: u32,
+};
+
+fn get_height(texel: vec2<i32>) -> f32 {
+    let last = vec2<i32>(terrain_params.resolution) - 1;
+    return textureLoad(heightmap, clamp(texel, vec2<i32>(0), last), 0).x;
+}
+
+// The heightmap can't be filtered, so it is interpolated here
+fn sample_height(position: vec2<f32>) -> f32 {
+    let base = floor(position);
+    let t = position - base;
+    let texel = vec2<i32>(base);
+    return mix(
+        mix(get_height(texel), get_height(texel + vec2<i32>(1, 0)), t.x),
+        mix(get_height(texel + vec2<i32>(0, 1)), get_height(texel + vec2<i32>(1, 1)), t.x),
+        t.y
+    );
+}
+
+fn get_world_normal(position: vec2<f32>) -> vec3<f32> {
+    let normal = vec3<f32>(
+        sample_height(position - vec2<f32>(1., 0.)) - sample_height(position + vec2<f32>(1., 0.)),
+        sample_height(position - vec2<f32>(0., 1.)) - sample_height(position + vec2<f32>(0., 1.)),
+        2.
+    );
+    // Normals are transformed by the inverse transpose
+    return normalize((vec4<f32>(normal, 0.) * terrain_params.world_to_terrain).xyz);
+}
+
+@vertex
+fn vs_main(@builtin(instance_index) instance_index: u32, @builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let primitive = primitives.data[instance_index];
+    let entity_loc = primitive.xy;
+    let mesh_index = get_entity_primitive_mesh(entity_loc, primitive.z);
+
+    // Patches are unit grids scaled to their size, and placed at their origin, in samples
+    let patch = terrain_params.world_to_terrain * get_entity_mesh_to_world(entity_loc);
+    let patch_size = round(patch[0].x);
+    let patch_origin = round(patch[3].xy);
+    let grid = round(get_mesh_base(mesh_index, vertex_index).position.xy * f32(PATCH_QUADS));
+    let step = patch_size / f32(PATCH_QUADS);
+
+    // The odd vertices slide onto their even neighbours as the camera gets far enough for the
+    // patch to be replaced by a larger one, which must use the camera of the main view for the
+    // shadows to match
+    let camera = (terrain_params.world_to_terrain * global_params.forward_camera_position).xy;
+    let range = f32(LOD_DISTANCE) * patch_size;
+    let camera_distance = length(camera - (patch_origin + grid * step));
+    let morph = clamp((camera_distance - range * 0.75) / (range * 0.25), 0., 1.);
+    let morphed = grid - fract(grid * 0.5) * 2. * morph;
+
+    let position = clamp(patch_origin + morphed * step, vec2<f32>(0.), terrain_params.resolution - 1.);
+    out.terrain_position = position;
+    out.world_position = terrain_params.terrain_to_world * vec4<f32>(position, sample_height(position), 1.);
+    out.instance_index = instance_index;
+    out.position = global_params.projection_view * out.world_position;
+    return out;
+}
+
+fn get_material(in: VertexOutput) -> MaterialOutput {
+    var out: MaterialOutput;
+
+    let uv = (in.terrain_position + 0.5) / terrain_params.resolution;
+    let splat = textureSample(splatmap, default_sampler, uv);
+    let weights = splat / max(dot(splat, vec4<f32>(1.)), 0.001);
+
+    let tc = in.world_position.xy / terrain_params.layer_scale;
+    let color = textureSample(layer0, layer_sampler, tc) * terrain_params.layer_colors[0] * weights.x
+        + textureSample(layer1, layer_sampler, tc) * terrain_params.layer_colors[1] * weights.y
+        + textureSample(layer2, layer_sampler, tc) * terrain_params.layer_colors[2] * weights.z
+        + textureSample(layer3, layer_sampler, tc) * terrain_params.layer_colors[3] * weights.w;
+
+    out.base_color = color.rgb;
+    out.emissive_factor = vec3<f32>(0., 0., 0.);
+    out.opacity = 1.;
+    out.alpha_cutoff = 0.;
+    out.shading = 1.;
+    out.normal = get_world_normal(in.terrain_position);
+    out.metallic = 0.;
+    out.roughness = 0.9;
+    return out;
+}
+
+fn get_normal_matrix(normal: vec3<f32>) -> mat3x3<f32> {
+    let tangent = normalize(vec3<f32>(1., 0., 0.) - normal * normal.x);
+    return mat3x3<f32>(tangent, cross(normal, tangent), normal);
+}
+
+@fragment
+fn fs_shadow_main(in: VertexOutput) {
+}
+
+@fragment
+fn fs_forward_main(in: VertexOutput) -> MainFsOut {
+    let material = get_material(in);
+
+    return forward_out(
+        material,
+        shading(material, in.world_position),
+        in.world_position,
+        quat_from_mat3(get_normal_matrix(material.normal))
+    );
+}
+
+@fragment
+fn fs_outlines_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let entity_loc = primitives.data[in.instance_index].xy;
+    return get_entity_outline_or(entity_loc, vec4<f32>(0., 0., 0., 0.));
+}
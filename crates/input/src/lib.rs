@@ -8,8 +8,8 @@ use glam::{vec2, Vec2};
 use serde::{Deserialize, Serialize};
 use winit::event::ModifiersState;
 pub use winit::event::{
-    DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, VirtualKeyCode,
-    WindowEvent,
+    DeviceEvent, ElementState, Event, Ime, KeyboardInput, MouseButton, MouseScrollDelta,
+    VirtualKeyCode, WindowEvent,
 };
 
 pub mod picking;
@@ -111,6 +111,27 @@ impl System<Event<'static, ()>> for InputSystem {
                         .add_message(messages::WindowKeyboardCharacter::new(c.to_string()));
                 }
 
+                WindowEvent::Ime(ime) => {
+                    let events = world.resource_mut(world_events());
+                    match ime {
+                        Ime::Preedit(text, _) => {
+                            events.add_message(messages::WindowImeComposition::new(text.clone()));
+                        }
+                        Ime::Commit(text) => {
+                            events.add_message(messages::WindowImeComposition::new(""));
+                            for c in text.chars() {
+                                events.add_message(messages::WindowKeyboardCharacter::new(
+                                    c.to_string(),
+                                ));
+                            }
+                        }
+                        Ime::Disabled => {
+                            events.add_message(messages::WindowImeComposition::new(""));
+                        }
+                        Ime::Enabled => {}
+                    }
+                }
+
                 WindowEvent::ModifiersChanged(mods) => {
                     self.modifiers = *mods;
                     world
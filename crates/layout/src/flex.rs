@@ -0,0 +1,352 @@
+//! The `Flex` layout: a subset of CSS flexbox.
+//!
+//! Children are laid out in lines along the layout's `orientation`, optionally wrapping onto new
+//! lines, and then grow or shrink to fill each line. Across the orientation, each child is aligned
+//! within its line.
+
+use ambient_core::transform::{local_to_parent, translation};
+use ambient_ecs::{components, Component, EntityId, World};
+use glam::{vec2, Vec2};
+use itertools::Itertools;
+
+use crate::{
+    align_items, align_self, fit_horizontal, fit_vertical, flex_basis, flex_grow, flex_shrink,
+    flex_wrap, height, invalidate_parent_layout, justify_content, margin, orientation, padding,
+    space_between_items, width, Borders, Fit, FlexAlign, Justify, Orientation, Z_DELTA,
+};
+
+components!("layout", {
+    /// The basis of a growing or shrinking child without a `flex_basis`, along with the size the
+    /// layout gave it. The child's own size is only its basis again once something else resizes it
+    flex_auto_basis: (f32, f32),
+});
+
+/// The axis along a layout's orientation, and the one across it
+#[derive(Clone, Copy)]
+struct Axes(Orientation);
+impl Axes {
+    fn main(self, value: Vec2) -> f32 {
+        match self.0 {
+            Orientation::Horizontal => value.x,
+            Orientation::Vertical => value.y,
+        }
+    }
+    fn cross(self, value: Vec2) -> f32 {
+        match self.0 {
+            Orientation::Horizontal => value.y,
+            Orientation::Vertical => value.x,
+        }
+    }
+    fn vec(self, main: f32, cross: f32) -> Vec2 {
+        match self.0 {
+            Orientation::Horizontal => vec2(main, cross),
+            Orientation::Vertical => vec2(cross, main),
+        }
+    }
+    fn main_size(self) -> Component<f32> {
+        match self.0 {
+            Orientation::Horizontal => width(),
+            Orientation::Vertical => height(),
+        }
+    }
+    fn cross_size(self) -> Component<f32> {
+        match self.0 {
+            Orientation::Horizontal => height(),
+            Orientation::Vertical => width(),
+        }
+    }
+    fn main_fit(self) -> Component<Fit> {
+        match self.0 {
+            Orientation::Horizontal => fit_horizontal(),
+            Orientation::Vertical => fit_vertical(),
+        }
+    }
+    fn cross_fit(self) -> Component<Fit> {
+        match self.0 {
+            Orientation::Horizontal => fit_vertical(),
+            Orientation::Vertical => fit_horizontal(),
+        }
+    }
+    fn cross_orientation(self) -> Orientation {
+        match self.0 {
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Horizontal,
+        }
+    }
+}
+
+struct Item {
+    id: EntityId,
+    margin: Borders,
+    /// Whether the layout sets the size along the orientation
+    flexible: bool,
+    /// Whether the basis is the child's own size, as it has no `flex_basis`
+    auto_basis: bool,
+    grow: f32,
+    shrink: f32,
+    basis: f32,
+    /// The size along the orientation, once grown or shrunk
+    size: f32,
+    cross: f32,
+    align: FlexAlign,
+}
+
+#[profiling::function]
+pub(crate) fn flex_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
+    let axes = Axes(
+        world
+            .get(id, orientation())
+            .unwrap_or(Orientation::Horizontal),
+    );
+    let self_padding: Borders = world
+        .get(id, padding())
+        .unwrap_or(Borders::ZERO.into())
+        .into();
+    let gap = world.get(id, space_between_items()).unwrap_or(0.);
+    let wrap = world.get(id, flex_wrap()).unwrap_or(false);
+    let justify = world.get(id, justify_content()).unwrap_or(Justify::Begin);
+    let self_align = world.get(id, align_items()).unwrap_or(FlexAlign::Begin);
+    let self_size = vec2(
+        world.get(id, width()).unwrap_or(0.),
+        world.get(id, height()).unwrap_or(0.),
+    );
+    let fit_main = world.get(id, axes.main_fit()).unwrap_or(Fit::None) == Fit::Children;
+    let fit_cross = world.get(id, axes.cross_fit()).unwrap_or(Fit::None) == Fit::Children;
+    let padding_main = axes.main(self_padding.border_size());
+    let padding_cross = axes.cross(self_padding.border_size());
+
+    let mut items = children
+        .iter()
+        .filter(|id| world.has_component(**id, local_to_parent()))
+        .map(|&c| {
+            let grow = world.get(c, flex_grow()).unwrap_or(0.);
+            let shrink = world.get(c, flex_shrink()).unwrap_or(0.);
+            let flexible = grow > 0. || shrink > 0.;
+            let size = vec2(
+                world.get(c, width()).unwrap_or(0.),
+                world.get(c, height()).unwrap_or(0.),
+            );
+            let explicit_basis = world.get(c, flex_basis()).ok();
+            // Like `auto` in CSS, the basis defaults to the child's own size. The layout resizes
+            // flexible children, so their size from before that is kept
+            let basis = explicit_basis.unwrap_or_else(|| match world.get(c, flex_auto_basis()) {
+                Ok((basis, laid_out)) if flexible && laid_out == axes.main(size) => basis,
+                _ => axes.main(size),
+            });
+            Item {
+                id: c,
+                margin: world
+                    .get(c, margin())
+                    .unwrap_or(Borders::ZERO.into())
+                    .into(),
+                flexible,
+                auto_basis: explicit_basis.is_none(),
+                grow,
+                shrink,
+                basis,
+                size: basis,
+                cross: axes.cross(size),
+                align: world.get(c, align_self()).unwrap_or(self_align),
+            }
+        })
+        .collect_vec();
+
+    // Break the children into lines
+    let available = if fit_main {
+        f32::INFINITY
+    } else {
+        axes.main(self_size) - padding_main
+    };
+    let mut lines: Vec<Vec<usize>> = vec![];
+    let mut line_length = 0.;
+    for (i, item) in items.iter().enumerate() {
+        let length = item.basis + axes.main(item.margin.border_size());
+        match lines.last_mut() {
+            Some(line) if !(wrap && line_length + gap + length > available) => {
+                line.push(i);
+                line_length += gap + length;
+            }
+            _ => {
+                lines.push(vec![i]);
+                line_length = length;
+            }
+        }
+    }
+    let outer_length = |items: &[Item], line: &[usize]| {
+        line.iter()
+            .map(|&i| items[i].size + axes.main(items[i].margin.border_size()))
+            .sum::<f32>()
+            + gap * line.len().saturating_sub(1) as f32
+    };
+
+    let new_main = if fit_main {
+        lines
+            .iter()
+            .map(|line| outer_length(&items, line))
+            .fold(0., f32::max)
+            + padding_main
+    } else {
+        axes.main(self_size)
+    };
+    let main_space = new_main - padding_main;
+
+    // Grow or shrink the children to fill their line, and find how thick each line is
+    let single_line = lines.len() == 1 && !fit_cross;
+    let line_thicknesses = lines
+        .iter()
+        .map(|line| {
+            let free = main_space - outer_length(&items, line);
+            if free > 0. {
+                let total_grow: f32 = line.iter().map(|&i| items[i].grow).sum();
+                if total_grow > 0. {
+                    for &i in line {
+                        items[i].size += free * items[i].grow / total_grow;
+                    }
+                }
+            } else if free < 0. {
+                let total_shrink: f32 =
+                    line.iter().map(|&i| items[i].shrink * items[i].basis).sum();
+                if total_shrink > 0. {
+                    for &i in line {
+                        let item = &mut items[i];
+                        item.size =
+                            (item.size + free * item.shrink * item.basis / total_shrink).max(0.);
+                    }
+                }
+            }
+            if single_line {
+                // Like in CSS, a line which doesn't wrap takes the whole layout
+                return axes.cross(self_size) - padding_cross;
+            }
+            line.iter()
+                .map(|&i| {
+                    let item = &items[i];
+                    // A stretched child gets its size from the line, so it doesn't count here
+                    let cross = if item.align == FlexAlign::Stretch {
+                        0.
+                    } else {
+                        item.cross
+                    };
+                    cross + axes.cross(item.margin.border_size())
+                })
+                .fold(0., f32::max)
+        })
+        .collect_vec();
+
+    let new_cross = if fit_cross {
+        line_thicknesses.iter().sum::<f32>()
+            + gap * line_thicknesses.len().saturating_sub(1) as f32
+            + padding_cross
+    } else {
+        axes.cross(self_size)
+    };
+
+    let mut cross_offset = axes.cross(self_padding.offset());
+    for (line, thickness) in lines.iter().zip(line_thicknesses) {
+        let free = (main_space - outer_length(&items, line)).max(0.);
+        let count = line.len() as f32;
+        let (start, between) = match justify {
+            Justify::Begin => (0., 0.),
+            Justify::Center => (free / 2., 0.),
+            Justify::End => (free, 0.),
+            Justify::SpaceBetween if count > 1. => (0., free / (count - 1.)),
+            Justify::SpaceBetween => (0., 0.),
+            Justify::SpaceAround => (free / count / 2., free / count),
+            Justify::SpaceEvenly => (free / (count + 1.), free / (count + 1.)),
+        };
+        let mut main_offset = axes.main(self_padding.offset()) + start;
+        for &i in line {
+            let item = &items[i];
+            let margin_cross = axes.cross(item.margin.border_size());
+            let cross = if item.align == FlexAlign::Stretch {
+                (thickness - margin_cross).max(0.)
+            } else {
+                item.cross
+            };
+            let cross_position = cross_offset
+                + axes.cross(item.margin.offset())
+                + match item.align {
+                    FlexAlign::Begin | FlexAlign::Stretch => 0.,
+                    FlexAlign::Center => (thickness - margin_cross - cross) / 2.,
+                    FlexAlign::End => thickness - margin_cross - cross,
+                };
+            let position = axes.vec(
+                main_offset + axes.main(item.margin.offset()),
+                cross_position,
+            );
+            world
+                .set_if_changed(item.id, translation(), position.floor().extend(Z_DELTA))
+                .ok();
+            if item.flexible {
+                world
+                    .set_if_changed(item.id, axes.main_size(), item.size)
+                    .ok();
+                let auto_basis = (item.basis, item.size);
+                if item.auto_basis && world.get(item.id, flex_auto_basis()).ok() != Some(auto_basis)
+                {
+                    world
+                        .add_component(item.id, flex_auto_basis(), auto_basis)
+                        .ok();
+                }
+            }
+            if item.align == FlexAlign::Stretch {
+                world.set_if_changed(item.id, axes.cross_size(), cross).ok();
+            }
+            main_offset += item.size + axes.main(item.margin.border_size()) + gap + between;
+        }
+        cross_offset += thickness + gap;
+    }
+
+    if fit_main && axes.main(self_size) != new_main {
+        world.set(id, axes.main_size(), new_main).ok();
+        invalidate_parent_layout(world, id, axes.0);
+    }
+    if fit_cross && axes.cross(self_size) != new_cross {
+        world.set(id, axes.cross_size(), new_cross).ok();
+        invalidate_parent_layout(world, id, axes.cross_orientation());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_ecs::{Entity, WorldContext};
+    use glam::Mat4;
+
+    use super::*;
+
+    #[test]
+    fn shrinking_children_start_from_their_own_size() {
+        ambient_ecs::init_components();
+        init_components();
+
+        let mut world = World::new("flex", WorldContext::Client);
+        let layout = Entity::new()
+            .with(width(), 100.)
+            .with(height(), 10.)
+            .spawn(&mut world);
+        let children = (0..2)
+            .map(|_| {
+                Entity::new()
+                    .with(local_to_parent(), Mat4::IDENTITY)
+                    .with(width(), 80.)
+                    .with(height(), 10.)
+                    .with(flex_shrink(), 1.)
+                    .spawn(&mut world)
+            })
+            .collect_vec();
+        let widths = |world: &World| {
+            children
+                .iter()
+                .map(|&c| world.get(c, width()).unwrap())
+                .collect_vec()
+        };
+
+        flex_layout(&mut world, layout, children.clone());
+        assert_eq!(widths(&world), [50., 50.]);
+
+        // Laying out again doesn't take the shrunk size as the basis
+        world.set(layout, width(), 200.).unwrap();
+        flex_layout(&mut world, layout, children.clone());
+        assert_eq!(widths(&world), [80., 80.]);
+    }
+}
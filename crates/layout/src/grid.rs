@@ -0,0 +1,264 @@
+//! The `Grid` layout: a subset of CSS grid.
+//!
+//! Children are placed in cells, either explicitly with `grid_column` and `grid_row` or in the
+//! next free cells, and can span several columns and rows. Columns and rows have a size in pixels,
+//! fit their children, or share the space left.
+
+use std::collections::HashSet;
+
+use ambient_core::transform::{local_to_parent, translation};
+use ambient_ecs::{EntityId, World};
+use glam::{vec2, Vec2};
+use itertools::Itertools;
+
+use crate::{
+    fit_horizontal, fit_vertical, grid_column, grid_column_span, grid_columns, grid_row,
+    grid_row_span, grid_rows, height, invalidate_parent_layout, margin, padding,
+    space_between_items, width, Borders, Fit, Orientation, Z_DELTA,
+};
+
+struct Cell {
+    id: EntityId,
+    column: usize,
+    row: usize,
+    column_span: usize,
+    row_span: usize,
+    margin: Borders,
+    size: Vec2,
+    fit_horizontal: Fit,
+    fit_vertical: Fit,
+}
+impl Cell {
+    fn new(
+        world: &World,
+        id: EntityId,
+        (column, row): (usize, usize),
+        (column_span, row_span): (usize, usize),
+    ) -> Self {
+        Self {
+            id,
+            column,
+            row,
+            column_span,
+            row_span,
+            margin: world
+                .get(id, margin())
+                .unwrap_or(Borders::ZERO.into())
+                .into(),
+            size: vec2(
+                world.get(id, width()).unwrap_or(0.),
+                world.get(id, height()).unwrap_or(0.),
+            ),
+            fit_horizontal: world.get(id, fit_horizontal()).unwrap_or(Fit::None),
+            fit_vertical: world.get(id, fit_vertical()).unwrap_or(Fit::None),
+        }
+    }
+}
+
+fn occupy(
+    occupied: &mut HashSet<(usize, usize)>,
+    (column, row): (usize, usize),
+    (column_span, row_span): (usize, usize),
+) {
+    for column in column..column + column_span {
+        for row in row..row + row_span {
+            occupied.insert((column, row));
+        }
+    }
+}
+
+/// The size of each track, from their sizes in `grid_columns` or `grid_rows` and the sizes of the
+/// children in only one of them. `available` is the space for the tracks, if it's not fitted to
+/// them
+fn track_sizes(
+    tracks: &[f32],
+    count: usize,
+    children: impl Iterator<Item = (usize, f32)> + Clone,
+    available: Option<f32>,
+    gap: f32,
+) -> Vec<f32> {
+    let track = |i: usize| tracks.get(i).copied().unwrap_or(0.);
+    let mut sizes = (0..count)
+        .map(|i| {
+            let track = track(i);
+            if track > 0. {
+                track
+            } else if track == 0. || available.is_none() {
+                // Fitted to its children; so are the shares if there's no space to share
+                children
+                    .clone()
+                    .filter(|(index, _)| *index == i)
+                    .map(|(_, size)| size)
+                    .fold(0., f32::max)
+            } else {
+                0.
+            }
+        })
+        .collect_vec();
+    if let Some(available) = available {
+        let shares: f32 = (0..count).map(|i| (-track(i)).max(0.)).sum();
+        if shares > 0. {
+            let left =
+                (available - sizes.iter().sum::<f32>() - gap * count.saturating_sub(1) as f32)
+                    .max(0.);
+            for (i, size) in sizes.iter_mut().enumerate() {
+                if track(i) < 0. {
+                    *size = left * -track(i) / shares;
+                }
+            }
+        }
+    }
+    sizes
+}
+
+/// Where each track starts
+fn track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+    sizes
+        .iter()
+        .scan(0., |offset, size| {
+            let start = *offset;
+            *offset += size + gap;
+            Some(start)
+        })
+        .collect()
+}
+
+fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+    sizes[start..start + span].iter().sum::<f32>() + gap * span.saturating_sub(1) as f32
+}
+
+#[profiling::function]
+pub(crate) fn grid_layout(world: &mut World, id: EntityId, children: Vec<EntityId>) {
+    let self_padding: Borders = world
+        .get(id, padding())
+        .unwrap_or(Borders::ZERO.into())
+        .into();
+    let gap = world.get(id, space_between_items()).unwrap_or(0.);
+    let columns = world
+        .get_cloned(id, grid_columns())
+        .ok()
+        .filter(|columns| !columns.is_empty())
+        .unwrap_or_else(|| vec![0.]);
+    let rows = world.get_cloned(id, grid_rows()).unwrap_or_default();
+    let self_size = vec2(
+        world.get(id, width()).unwrap_or(0.),
+        world.get(id, height()).unwrap_or(0.),
+    );
+    let self_fit_horizontal = world.get(id, fit_horizontal()).unwrap_or(Fit::None);
+    let self_fit_vertical = world.get(id, fit_vertical()).unwrap_or(Fit::None);
+
+    // Place the children with a cell first, then fill the free cells with the others
+    let children = children
+        .iter()
+        .filter(|id| world.has_component(**id, local_to_parent()))
+        .copied()
+        .collect_vec();
+    let (placed, unplaced): (Vec<_>, Vec<_>) = children.into_iter().partition(|&c| {
+        world.has_component(c, grid_column()) || world.has_component(c, grid_row())
+    });
+    let mut occupied = HashSet::new();
+    let mut cells = Vec::new();
+    let spans = |c: EntityId| {
+        (
+            (world.get(c, grid_column_span()).unwrap_or(1).max(1) as usize).min(columns.len()),
+            world.get(c, grid_row_span()).unwrap_or(1).max(1) as usize,
+        )
+    };
+    for c in placed {
+        let spans = spans(c);
+        let column =
+            (world.get(c, grid_column()).unwrap_or(0) as usize).min(columns.len() - spans.0);
+        let position = (column, world.get(c, grid_row()).unwrap_or(0) as usize);
+        occupy(&mut occupied, position, spans);
+        cells.push(Cell::new(world, c, position, spans));
+    }
+    let mut next = 0;
+    for c in unplaced {
+        let spans = spans(c);
+        let (column_span, row_span) = spans;
+        let index = (next..)
+            .find(|index| {
+                let (column, row) = (index % columns.len(), index / columns.len());
+                column + column_span <= columns.len()
+                    && (column..column + column_span).all(|column| {
+                        (row..row + row_span).all(|row| !occupied.contains(&(column, row)))
+                    })
+            })
+            .unwrap();
+        let position = (index % columns.len(), index / columns.len());
+        occupy(&mut occupied, position, spans);
+        cells.push(Cell::new(world, c, position, spans));
+        next = index + column_span;
+    }
+    let row_count = cells
+        .iter()
+        .map(|cell| cell.row + cell.row_span)
+        .max()
+        .unwrap_or(0)
+        .max(rows.len());
+
+    // Children sized by the layout don't count towards the size of their tracks
+    let column_widths = track_sizes(
+        &columns,
+        columns.len(),
+        cells
+            .iter()
+            .filter(|cell| cell.column_span == 1 && cell.fit_horizontal != Fit::Parent)
+            .map(|cell| (cell.column, cell.size.x + cell.margin.get_horizontal())),
+        (self_fit_horizontal != Fit::Children)
+            .then_some(self_size.x - self_padding.get_horizontal()),
+        gap,
+    );
+    let row_heights = track_sizes(
+        &rows,
+        row_count,
+        cells
+            .iter()
+            .filter(|cell| cell.row_span == 1 && cell.fit_vertical != Fit::Parent)
+            .map(|cell| (cell.row, cell.size.y + cell.margin.get_vertical())),
+        (self_fit_vertical != Fit::Children).then_some(self_size.y - self_padding.get_vertical()),
+        gap,
+    );
+    let column_offsets = track_offsets(&column_widths, gap);
+    let row_offsets = track_offsets(&row_heights, gap);
+
+    for cell in &cells {
+        let position = self_padding.offset()
+            + vec2(column_offsets[cell.column], row_offsets[cell.row])
+            + cell.margin.offset();
+        world
+            .set_if_changed(cell.id, translation(), position.floor().extend(Z_DELTA))
+            .ok();
+        if cell.fit_horizontal == Fit::Parent {
+            let cell_width = span_size(&column_widths, cell.column, cell.column_span, gap)
+                - cell.margin.get_horizontal();
+            world
+                .set_if_changed(cell.id, width(), cell_width.max(0.))
+                .ok();
+        }
+        if cell.fit_vertical == Fit::Parent {
+            let cell_height =
+                span_size(&row_heights, cell.row, cell.row_span, gap) - cell.margin.get_vertical();
+            world
+                .set_if_changed(cell.id, height(), cell_height.max(0.))
+                .ok();
+        }
+    }
+
+    if self_fit_horizontal == Fit::Children {
+        let new_width =
+            span_size(&column_widths, 0, column_widths.len(), gap) + self_padding.get_horizontal();
+        if new_width != self_size.x {
+            world.set(id, width(), new_width).ok();
+            invalidate_parent_layout(world, id, Orientation::Horizontal);
+        }
+    }
+    if self_fit_vertical == Fit::Children {
+        let new_height =
+            span_size(&row_heights, 0, row_heights.len(), gap) + self_padding.get_vertical();
+        if new_height != self_size.y {
+            world.set(id, height(), new_height).ok();
+            invalidate_parent_layout(world, id, Orientation::Vertical);
+        }
+    }
+}
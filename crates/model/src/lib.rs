@@ -24,10 +24,7 @@ use ambient_native_std::{
 };
 use ambient_renderer::{
     color, gpu_primitives_lod, gpu_primitives_mesh,
-    materials::{
-        flat_material::{get_flat_shader, FlatMaterialKey},
-        pbr_material::get_pbr_shader,
-    },
+    materials::flat_material::{get_flat_shader, FlatMaterialKey},
     pbr_material::PbrMaterialFromUrl,
     primitives, RenderPrimitive, StandardShaderKey,
 };
@@ -92,6 +89,8 @@ async fn internal_spawn_models_from_defs(
                         material_shader: LoadingShaderKey.get(assets),
                         lit: false,
                         shadow_cascades: config.shadow_cascades,
+                        wind: false,
+                        imposter: false,
                     }
                     .get(assets)
                 }),
@@ -344,8 +343,8 @@ impl AsyncAssetKey<Result<Arc<RenderPrimitive>, AssetError>> for PbrRenderPrimit
         if let Some(mat_url) = self.material {
             let mat = PbrMaterialFromUrl(mat_url).get(&assets).await?;
             Ok(Arc::new(RenderPrimitive {
+                shader: mat.shader(),
                 material: mat.into(),
-                shader: cb(get_pbr_shader),
                 mesh,
                 lod: self.lod,
             }))
@@ -191,6 +191,17 @@ impl FbxMaterial {
                 .get(SPECULAR_COLOR_KEY)
                 .map(|_| 1.)
                 .unwrap_or(1.0),
+            reflectivity: None,
+            wind_sway: None,
+            wind_flutter: None,
+            wind_height: None,
+            transparency_group: None,
+            transparency_sort_bias: None,
+            clearcoat: None,
+            clearcoat_roughness: None,
+            transmission: None,
+            texcoord_transform: None,
+            imposter: None,
 
             // TODO: Each FBX texture knows its sampler modes, but Ambient's
             // current material model assumes a single sampler for all textures
@@ -19,7 +19,7 @@ use ambient_native_std::{
     mesh::{flip_winding, generate_tangents, MeshBuilder},
     shapes::AABB,
 };
-use ambient_renderer::materials::pbr_material::PbrMaterialDesc;
+use ambient_renderer::materials::pbr_material::{PbrMaterialDesc, TexcoordTransform};
 use anyhow::Context;
 use glam::{uvec4, Mat4, Quat, Vec2, Vec3, Vec4, Vec4Swizzles};
 use gltf::animation::util::ReadOutputs;
@@ -270,6 +270,7 @@ pub async fn import(
     let mut materials = Vec::new();
     for (index, mat) in import.document.materials().enumerate() {
         let pbr = mat.pbr_metallic_roughness();
+        let extensions = MaterialExtensions::new(&mat);
 
         let mat_def = PbrMaterialDesc {
             name: mat.name().map(|x| x.to_string()),
@@ -277,11 +278,35 @@ pub async fn import(
             base_color_factor: Some(glam::Vec4::from_slice(
                 &mat.pbr_metallic_roughness().base_color_factor(),
             )),
-            emissive_factor: Some(glam::Vec3::from_slice(&mat.emissive_factor()).extend(0.)),
-            transparent: Some(mat.alpha_mode() == gltf::material::AlphaMode::Blend),
-            alpha_cutoff: mat.alpha_cutoff(),
+            emissive_factor: Some(
+                (glam::Vec3::from_slice(&mat.emissive_factor()) * extensions.emissive_strength)
+                    .extend(0.),
+            ),
+            // Transmission is drawn by blending with what's behind the material
+            transparent: Some(
+                mat.alpha_mode() == gltf::material::AlphaMode::Blend
+                    || extensions.transmission > 0.,
+            ),
+            alpha_cutoff: if extensions.transmission > 0.
+                && mat.alpha_mode() != gltf::material::AlphaMode::Mask
+            {
+                Some(0.)
+            } else {
+                mat.alpha_cutoff()
+            },
             metallic_factor: pbr.metallic_factor(),
             roughness_factor: pbr.roughness_factor(),
+            reflectivity: None,
+            wind_sway: None,
+            wind_flutter: None,
+            wind_height: None,
+            transparency_group: None,
+            transparency_sort_bias: None,
+            clearcoat: extensions.clearcoat,
+            clearcoat_roughness: extensions.clearcoat_roughness,
+            transmission: Some(extensions.transmission).filter(|x| *x > 0.),
+            texcoord_transform: extensions.texcoord_transform,
+            imposter: None,
             base_color: pbr
                 .base_color_texture()
                 .and_then(|x| images.get(x.texture().index()))
@@ -404,3 +429,84 @@ pub async fn import(
         .insert(ModelCrate::MAIN, Model(world))
         .path)
 }
+
+/// The properties of the material extensions of a glTF material which are mapped onto
+/// [PbrMaterialDesc]
+struct MaterialExtensions {
+    /// `KHR_materials_emissive_strength`
+    emissive_strength: f32,
+    /// `KHR_materials_clearcoat`, which the gltf crate doesn't parse
+    clearcoat: Option<f32>,
+    clearcoat_roughness: Option<f32>,
+    /// `KHR_materials_transmission`
+    transmission: f32,
+    /// `KHR_texture_transform`
+    texcoord_transform: Option<TexcoordTransform>,
+}
+impl MaterialExtensions {
+    fn new(mat: &gltf::Material) -> Self {
+        let name = mat.name().unwrap_or_default();
+        let pbr = mat.pbr_metallic_roughness();
+
+        let clearcoat = mat
+            .extensions()
+            .and_then(|extensions| extensions.get("KHR_materials_clearcoat"));
+        let clearcoat_factor = |key: &str| {
+            clearcoat
+                .and_then(|clearcoat| clearcoat.get(key))
+                .and_then(|value| value.as_f64())
+                .map(|value| value as f32)
+        };
+        if clearcoat.map_or(false, |clearcoat| {
+            [
+                "clearcoatTexture",
+                "clearcoatRoughnessTexture",
+                "clearcoatNormalTexture",
+            ]
+            .iter()
+            .any(|key| clearcoat.get(key).is_some())
+        }) {
+            tracing::warn!("The clear coat textures of the material {name:?} aren't supported");
+        }
+
+        let transmission = mat.transmission();
+        if transmission.as_ref().map_or(false, |transmission| {
+            transmission.transmission_texture().is_some()
+        }) {
+            tracing::warn!("The transmission texture of the material {name:?} isn't supported");
+        }
+
+        // All the textures of a material share their texture coordinates, so the transform of the
+        // base color texture is used for all of them
+        let texture_transform = pbr
+            .base_color_texture()
+            .and_then(|info| info.texture_transform())
+            .or_else(|| {
+                pbr.metallic_roughness_texture()
+                    .and_then(|info| info.texture_transform())
+            });
+        if texture_transform
+            .as_ref()
+            .map_or(false, |transform| transform.tex_coord().is_some())
+        {
+            tracing::warn!(
+                "The texture coordinate set of the texture transform of the material {name:?} \
+                 isn't supported"
+            );
+        }
+
+        Self {
+            emissive_strength: mat.emissive_strength().unwrap_or(1.),
+            clearcoat: clearcoat.map(|_| clearcoat_factor("clearcoatFactor").unwrap_or(0.)),
+            clearcoat_roughness: clearcoat
+                .map(|_| clearcoat_factor("clearcoatRoughnessFactor").unwrap_or(0.)),
+            transmission: transmission
+                .map_or(0., |transmission| transmission.transmission_factor()),
+            texcoord_transform: texture_transform.map(|transform| TexcoordTransform {
+                offset: Vec2::from(transform.offset()),
+                rotation: transform.rotation(),
+                scale: Vec2::from(transform.scale()),
+            }),
+        }
+    }
+}
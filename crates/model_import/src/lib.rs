@@ -21,7 +21,9 @@ pub(crate) mod animation_bind_id;
 pub mod assimp;
 pub mod fbx;
 pub mod gltf;
+pub mod lods;
 pub mod model_crate;
+pub mod retarget;
 
 pub type TextureResolver =
     Arc<dyn Fn(String) -> futures::future::BoxFuture<'static, Option<RgbaImage>> + Sync + Send>;
@@ -208,7 +210,7 @@ impl ModelImportTransform {
                 model_crate.create_prefab_from_model();
             }
             ModelImportTransform::CreateColliderFromModel => {
-                model_crate.create_collider_from_model(assets, false, true)?;
+                model_crate.create_collider_from_model(assets, false, true, None)?;
             }
             ModelImportTransform::CreateCharacterCollider => {
                 model_crate.create_character_collider(None, None);
@@ -0,0 +1,280 @@
+//! Baking of octahedral imposters: the meshes of a node are rendered on the cpu from each view of
+//! the grid of [imposter_frame_direction], into a texture of their base color and one of their
+//! normals, in the space of each view.
+
+use ambient_native_std::{asset_url::AssetUrl, mesh::Mesh, shapes::Sphere};
+use ambient_renderer::{
+    imposter_frame_basis, imposter_frame_direction, materials::pbr_material::PbrMaterialDesc,
+};
+use glam::{uvec2, vec2, vec3, Vec2, Vec3, Vec4};
+use image::{Rgba, RgbaImage};
+use itertools::Itertools;
+
+use crate::model_crate::ModelCrate;
+
+/// How many pixels the colors of each view are spread past its edges, so that the texture
+/// filtering at the edges doesn't blend in the background
+const DILATION: usize = 2;
+
+/// The textures of an octahedral imposter, with `frames` by `frames` views of `resolution` pixels
+pub struct BakedImposter {
+    /// The base color of the meshes, transparent where there are none
+    pub base_color: RgbaImage,
+    /// The normals of the meshes in the space of each view: right, up and towards the view
+    pub normals: RgbaImage,
+}
+
+/// Renders the `primitives` of a node, which fit in `bounds`, from `frames` by `frames` views
+pub fn bake_imposter(
+    model_crate: &ModelCrate,
+    primitives: &[(&Mesh, Option<&PbrMaterialDesc>)],
+    bounds: Sphere,
+    frames: u32,
+    resolution: u32,
+) -> BakedImposter {
+    let size = frames * resolution;
+    let mut base_color = RgbaImage::new(size, size);
+    let mut normals = RgbaImage::new(size, size);
+    let surfaces = primitives
+        .iter()
+        .map(|(mesh, material)| Surface::new(model_crate, mesh, *material))
+        .collect_vec();
+
+    for (x, y) in (0..frames).cartesian_product(0..frames) {
+        let mut view = View::new(
+            bounds,
+            imposter_frame_direction(uvec2(x, y), frames),
+            resolution as usize,
+        );
+        for surface in &surfaces {
+            view.draw(surface);
+        }
+        view.dilate();
+        for (i, (color, normal)) in view.colors.iter().zip(&view.normals).enumerate() {
+            let px = x * resolution + (i % resolution as usize) as u32;
+            let py = y * resolution + (i / resolution as usize) as u32;
+            base_color.put_pixel(px, py, Rgba(*color));
+            normals.put_pixel(px, py, Rgba(*normal));
+        }
+    }
+    BakedImposter {
+        base_color,
+        normals,
+    }
+}
+
+/// A mesh with the parts of its material which show in the base color
+struct Surface<'a> {
+    mesh: &'a Mesh,
+    base_color_factor: Vec4,
+    base_color: Option<&'a RgbaImage>,
+    opacity: Option<&'a RgbaImage>,
+    texcoord_transform: [Vec4; 2],
+    alpha_cutoff: f32,
+}
+impl<'a> Surface<'a> {
+    fn new(
+        model_crate: &'a ModelCrate,
+        mesh: &'a Mesh,
+        material: Option<&'a PbrMaterialDesc>,
+    ) -> Self {
+        // Only the images of the crate can be read; the others leave the color to the factor
+        let image = |url: Option<&AssetUrl>| match url {
+            Some(AssetUrl::Relative(path)) => model_crate.images.get_by_path(path.as_str()),
+            _ => None,
+        };
+        Self {
+            mesh,
+            base_color_factor: material
+                .and_then(|material| material.base_color_factor)
+                .unwrap_or(Vec4::ONE),
+            base_color: image(material.and_then(|material| material.base_color.as_ref())),
+            opacity: image(material.and_then(|material| material.opacity.as_ref())),
+            texcoord_transform: material
+                .and_then(|material| material.texcoord_transform)
+                .unwrap_or_default()
+                .rows(),
+            // Like the renderer
+            alpha_cutoff: material
+                .and_then(|material| material.alpha_cutoff)
+                .unwrap_or(0.9),
+        }
+    }
+
+    fn color(&self, texcoord: Vec2) -> Vec4 {
+        let texcoord = texcoord.extend(1.);
+        let texcoord = vec2(
+            self.texcoord_transform[0].truncate().dot(texcoord),
+            self.texcoord_transform[1].truncate().dot(texcoord),
+        );
+        let mut color = self.base_color_factor;
+        if let Some(image) = self.base_color {
+            color *= sample(image, texcoord);
+        }
+        if let Some(image) = self.opacity {
+            color.w *= sample(image, texcoord).x;
+        }
+        color
+    }
+}
+
+/// The nearest pixel of `image` at `texcoord`, repeating the image
+fn sample(image: &RgbaImage, texcoord: Vec2) -> Vec4 {
+    let (width, height) = image.dimensions();
+    let x = ((texcoord.x.rem_euclid(1.) * width as f32) as u32).min(width - 1);
+    let y = ((texcoord.y.rem_euclid(1.) * height as f32) as u32).min(height - 1);
+    Vec4::from(image.get_pixel(x, y).0.map(|value| value as f32 / 255.))
+}
+
+/// A view of the meshes from one direction, with an orthographic projection fitting their bounds
+struct View {
+    bounds: Sphere,
+    /// The right, up and backward directions of the view
+    basis: [Vec3; 3],
+    resolution: usize,
+    /// How far each pixel is towards the view, from the center of the bounds
+    depths: Vec<f32>,
+    colors: Vec<[u8; 4]>,
+    normals: Vec<[u8; 4]>,
+}
+impl View {
+    fn new(bounds: Sphere, direction: Vec3, resolution: usize) -> Self {
+        let pixels = resolution * resolution;
+        Self {
+            bounds,
+            basis: imposter_frame_basis(direction),
+            resolution,
+            depths: vec![f32::NEG_INFINITY; pixels],
+            colors: vec![[0; 4]; pixels],
+            normals: vec![[128, 128, 255, 255]; pixels],
+        }
+    }
+
+    /// The position of `position` in pixels, with the top of the view at the top of the image,
+    /// and its depth
+    fn project(&self, position: Vec3) -> Vec3 {
+        let [right, up, backward] = self.basis;
+        let offset = (position - self.bounds.center) / (2. * self.bounds.radius);
+        let resolution = self.resolution as f32;
+        vec3(
+            (offset.dot(right) + 0.5) * resolution,
+            (0.5 - offset.dot(up)) * resolution,
+            offset.dot(backward),
+        )
+    }
+
+    fn draw(&mut self, surface: &Surface) {
+        let mesh = surface.mesh;
+        let positions = mesh.positions();
+        let projected = positions.iter().map(|&p| self.project(p)).collect_vec();
+        let texcoords = mesh.texcoords(0);
+        let normals = mesh.normals();
+        let max = (self.resolution - 1) as f32;
+
+        for indices in mesh.indices().chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| indices[i] as usize);
+            let [pa, pb, pc] = [a, b, c].map(|i| projected[i]);
+            let area = edge(pa.truncate(), pb.truncate(), pc.truncate());
+            if area.abs() < 1e-9 {
+                continue;
+            }
+            let face_normal = (positions[b] - positions[a])
+                .cross(positions[c] - positions[a])
+                .normalize_or_zero();
+
+            let min_corner = pa.min(pb).min(pc).truncate().floor().max(Vec2::ZERO);
+            let max_corner = pa.max(pb).max(pc).truncate().ceil().min(Vec2::splat(max));
+            for y in min_corner.y as usize..=max_corner.y as usize {
+                for x in min_corner.x as usize..=max_corner.x as usize {
+                    let point = vec2(x as f32 + 0.5, y as f32 + 0.5);
+                    // Dividing by the signed area draws both windings, so back faces show too
+                    let weights = vec3(
+                        edge(pb.truncate(), pc.truncate(), point),
+                        edge(pc.truncate(), pa.truncate(), point),
+                        edge(pa.truncate(), pb.truncate(), point),
+                    ) / area;
+                    if weights.min_element() < 0. {
+                        continue;
+                    }
+                    let pixel = y * self.resolution + x;
+                    let depth = weights.dot(vec3(pa.z, pb.z, pc.z));
+                    if depth <= self.depths[pixel] {
+                        continue;
+                    }
+                    let texcoord = if texcoords.is_empty() {
+                        Vec2::ZERO
+                    } else {
+                        texcoords[a] * weights.x
+                            + texcoords[b] * weights.y
+                            + texcoords[c] * weights.z
+                    };
+                    let color = surface.color(texcoord);
+                    if color.w < surface.alpha_cutoff {
+                        continue;
+                    }
+                    let mut normal = if normals.is_empty() {
+                        face_normal
+                    } else {
+                        (normals[a] * weights.x + normals[b] * weights.y + normals[c] * weights.z)
+                            .normalize_or_zero()
+                    };
+                    let [right, up, backward] = self.basis;
+                    if normal.dot(backward) < 0. {
+                        normal = -normal;
+                    }
+
+                    self.depths[pixel] = depth;
+                    self.colors[pixel] = to_rgba(color.truncate().extend(1.));
+                    self.normals[pixel] = to_rgba(
+                        (vec3(normal.dot(right), normal.dot(up), normal.dot(backward)) * 0.5 + 0.5)
+                            .extend(1.),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Spreads the colors and normals of the drawn pixels onto the empty pixels around them,
+    /// leaving them transparent
+    fn dilate(&mut self) {
+        let resolution = self.resolution;
+        let mut filled = self.colors.iter().map(|color| color[3] > 0).collect_vec();
+        for _ in 0..DILATION {
+            let mut next = filled.clone();
+            for (x, y) in (0..resolution).cartesian_product(0..resolution) {
+                let pixel = y * resolution + x;
+                if filled[pixel] {
+                    continue;
+                }
+                let neighbor = [
+                    (x > 0, pixel.wrapping_sub(1)),
+                    (x + 1 < resolution, pixel + 1),
+                    (y > 0, pixel.wrapping_sub(resolution)),
+                    (y + 1 < resolution, pixel + resolution),
+                ]
+                .into_iter()
+                .find(|&(inside, neighbor)| inside && filled[neighbor])
+                .map(|(_, neighbor)| neighbor);
+                if let Some(neighbor) = neighbor {
+                    let [r, g, b, _] = self.colors[neighbor];
+                    self.colors[pixel] = [r, g, b, 0];
+                    self.normals[pixel] = self.normals[neighbor];
+                    next[pixel] = true;
+                }
+            }
+            filled = next;
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b - a).perp_dot(p - a)
+}
+
+fn to_rgba(value: Vec4) -> [u8; 4] {
+    (value.clamp(Vec4::ZERO, Vec4::ONE) * 255.)
+        .round()
+        .to_array()
+        .map(|value| value as u8)
+}
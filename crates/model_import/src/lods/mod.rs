@@ -0,0 +1,250 @@
+//! Levels of detail generated at import time. The meshes of each node of a model are simplified
+//! into a chain of levels, which can end with an octahedral imposter, and the renderer picks one of
+//! them by the size of the node on the screen, like authored levels of detail.
+
+use ambient_ecs::{query, EntityId};
+use ambient_model::{model_skin_ix, pbr_renderer_primitives_from_url, PbrRenderPrimitiveFromUrl};
+use ambient_native_std::{
+    mesh::{Mesh, MeshBuilder},
+    shapes::{Sphere, AABB},
+};
+use ambient_pipeline_types::models::{ModelImposter, ModelLodGeneration};
+use ambient_renderer::{
+    lod::{gpu_lod, lod_cutoffs, LodCutoffs, MAX_LOD_LEVELS},
+    materials::pbr_material::{ImposterParams, PbrMaterialDesc},
+};
+use anyhow::Context;
+use glam::{vec2, Vec3};
+use itertools::Itertools;
+
+use crate::{dotdot_path, model_crate::ModelCrate};
+
+mod imposter;
+mod simplify;
+
+pub use imposter::{bake_imposter, BakedImposter};
+pub use simplify::simplify_mesh;
+
+/// The minimum screen sizes of `count` levels of detail, spread evenly from the full screen down to
+/// 4% of it
+pub fn default_lod_cutoffs(count: usize) -> Vec<f32> {
+    let default_min_screen_size = 0.04; // i.e. 4%
+    let lod_step = (1. / default_min_screen_size).powf(1. / (count - 1) as f32);
+    (0..count).map(|i| 1. / lod_step.powi(i as i32)).collect()
+}
+
+/// Generates the levels of detail of the nodes of the model, as configured by `settings`. The
+/// nodes which already have levels of detail are left as is.
+pub fn generate_lods(
+    model_crate: &mut ModelCrate,
+    settings: &ModelLodGeneration,
+) -> anyhow::Result<()> {
+    let ratios = settings.ratios();
+    let level_count = 1 + ratios.len() + settings.imposter.is_some() as usize;
+    anyhow::ensure!(
+        level_count <= MAX_LOD_LEVELS,
+        "Can't generate {level_count} levels of detail, the most is {MAX_LOD_LEVELS}"
+    );
+    let cutoffs = match &settings.screen_sizes {
+        Some(screen_sizes) => {
+            anyhow::ensure!(
+                screen_sizes.len() == level_count,
+                "Expected a screen size for each of the {level_count} levels of detail, got {}",
+                screen_sizes.len()
+            );
+            screen_sizes.clone()
+        }
+        None => default_lod_cutoffs(level_count),
+    };
+
+    let nodes =
+        query(pbr_renderer_primitives_from_url()).collect_cloned(model_crate.model_world(), None);
+    for (index, (id, primitives)) in nodes.into_iter().enumerate() {
+        if primitives.is_empty() || primitives.iter().any(|primitive| primitive.lod > 0) {
+            continue;
+        }
+        generate_node_lods(
+            model_crate,
+            id,
+            &format!("lod_{index}"),
+            primitives,
+            &ratios,
+            settings,
+            cutoffs.clone(),
+        )?;
+    }
+    Ok(())
+}
+
+fn generate_node_lods(
+    model_crate: &mut ModelCrate,
+    id: EntityId,
+    name: &str,
+    primitives: Vec<PbrRenderPrimitiveFromUrl>,
+    ratios: &[f32],
+    settings: &ModelLodGeneration,
+    mut cutoffs: Vec<f32>,
+) -> anyhow::Result<()> {
+    let meshes = primitives
+        .iter()
+        .map(|primitive| {
+            let mesh_id = model_crate
+                .meshes
+                .loc
+                .id_from_path(primitive.mesh.path())
+                .with_context(|| format!("Invalid mesh url {}", primitive.mesh))?;
+            let mesh = model_crate
+                .meshes
+                .content
+                .get(&mesh_id)
+                .with_context(|| format!("Mesh {mesh_id} not found"))?
+                .clone();
+            Ok((mesh_id, mesh))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let bounds = meshes
+        .iter()
+        .map(|(_, mesh)| mesh.aabb())
+        .collect::<AABB>()
+        .to_sphere();
+    let max_error = settings.max_error() * bounds.radius;
+
+    // Each level is simplified from the previous one, which is faster and keeps them consistent
+    let mut lods = primitives.clone();
+    let mut previous = primitives
+        .iter()
+        .zip(&meshes)
+        .map(|(primitive, (_, mesh))| (primitive.mesh.clone(), mesh.clone()))
+        .collect_vec();
+    for (i, ratio) in ratios.iter().enumerate() {
+        let level = i + 1;
+        for ((primitive, (mesh_id, mesh)), previous) in
+            primitives.iter().zip(&meshes).zip(&mut previous)
+        {
+            let target = (mesh.indices().len() / 3) as f32 * ratio;
+            let simplified = simplify_mesh(&previous.1, target.ceil() as usize, max_error)?;
+            // A level which couldn't be simplified further shows the previous mesh again
+            if simplified.index_count() < previous.1.index_count() {
+                let path = model_crate
+                    .meshes
+                    .insert(format!("{mesh_id}_lod{level}"), simplified.clone())
+                    .path;
+                *previous = (dotdot_path(path).into(), simplified);
+            }
+            lods.push(PbrRenderPrimitiveFromUrl {
+                mesh: previous.0.clone(),
+                material: primitive.material.clone(),
+                lod: level,
+            });
+        }
+    }
+
+    if let Some(imposter) = &settings.imposter {
+        if model_crate.model_world().has_component(id, model_skin_ix()) {
+            // The last mesh stands in for the imposter instead
+            cutoffs.remove(cutoffs.len() - 2);
+        } else {
+            let meshes = meshes.into_iter().map(|(_, mesh)| mesh).collect_vec();
+            lods.push(add_imposter(
+                model_crate,
+                name,
+                &primitives,
+                &meshes,
+                bounds,
+                imposter,
+                ratios.len() + 1,
+            )?);
+        }
+    }
+
+    let world = model_crate.model_world_mut();
+    world.add_component(id, pbr_renderer_primitives_from_url(), lods)?;
+    world.add_component(id, lod_cutoffs(), LodCutoffs::new(&cutoffs))?;
+    world.add_component(id, gpu_lod(), ())?;
+    Ok(())
+}
+
+/// Bakes the imposter of the `primitives` of a node, and returns its primitive at `lod`
+fn add_imposter(
+    model_crate: &mut ModelCrate,
+    name: &str,
+    primitives: &[PbrRenderPrimitiveFromUrl],
+    meshes: &[Mesh],
+    bounds: Sphere,
+    settings: &ModelImposter,
+    lod: usize,
+) -> anyhow::Result<PbrRenderPrimitiveFromUrl> {
+    let frames = settings.frames();
+    let baked = {
+        let sources = meshes
+            .iter()
+            .zip(primitives)
+            .map(|(mesh, primitive)| {
+                let material = primitive
+                    .material
+                    .as_ref()
+                    .and_then(|url| model_crate.materials.get_by_path(url.path()));
+                (mesh, material)
+            })
+            .collect_vec();
+        bake_imposter(model_crate, &sources, bounds, frames, settings.resolution())
+    };
+
+    let base_color = model_crate
+        .images
+        .insert(format!("{name}_imposter_base_color"), baked.base_color)
+        .path;
+    let normals = model_crate
+        .images
+        .insert(format!("{name}_imposter_normals"), baked.normals)
+        .path;
+    let material = model_crate
+        .materials
+        .insert(
+            format!("{name}_imposter"),
+            PbrMaterialDesc {
+                name: Some(format!("{name}_imposter")),
+                base_color: Some(dotdot_path(base_color).into()),
+                normalmap: Some(dotdot_path(normals).into()),
+                alpha_cutoff: Some(0.5),
+                double_sided: Some(true),
+                metallic_factor: 0.,
+                imposter: Some(ImposterParams {
+                    frames,
+                    center: bounds.center,
+                    radius: bounds.radius,
+                }),
+                ..Default::default()
+            },
+        )
+        .path;
+    let mesh = model_crate
+        .meshes
+        .insert(format!("{name}_imposter"), imposter_quad(bounds)?)
+        .path;
+    Ok(PbrRenderPrimitiveFromUrl {
+        mesh: dotdot_path(mesh).into(),
+        material: Some(dotdot_path(material).into()),
+        lod,
+    })
+}
+
+/// The quad of an imposter, covering `bounds`. The shader turns it towards the camera
+fn imposter_quad(bounds: Sphere) -> anyhow::Result<Mesh> {
+    let texcoords = vec![vec2(0., 0.), vec2(1., 0.), vec2(1., 1.), vec2(0., 1.)];
+    MeshBuilder {
+        positions: texcoords
+            .iter()
+            .map(|texcoord| {
+                let corner = vec2(texcoord.x - 0.5, 0.5 - texcoord.y) * 2. * bounds.radius;
+                bounds.center + corner.extend(0.)
+            })
+            .collect(),
+        normals: vec![Vec3::Z; 4],
+        tangents: vec![Vec3::X; 4],
+        texcoords: vec![texcoords],
+        indices: vec![0, 2, 1, 0, 3, 2],
+        ..Default::default()
+    }
+    .build()
+}
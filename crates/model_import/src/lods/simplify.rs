@@ -0,0 +1,409 @@
+//! Simplification of meshes by collapsing their edges, for the generated levels of detail.
+//!
+//! The cost of a collapse is the quadric error metric of Garland and Heckbert: the mean squared
+//! distance to the planes of the triangles of the original mesh around the collapsed vertices.
+//! Edges are collapsed onto one of their vertices rather than an optimal position, so the
+//! simplified mesh keeps a subset of the vertices of the original, with their texture coordinates
+//! and skinning untouched. The vertices at the same position, like on either side of a seam of the
+//! texture coordinates, are collapsed together along the seam, so the seams stay closed.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    ops::{Add, AddAssign},
+};
+
+use ambient_native_std::mesh::{Mesh, MeshBuilder};
+use glam::DVec3;
+use itertools::Itertools;
+
+/// How much more moving the open edges of a mesh costs than moving its surface, so that the
+/// outline of holes and flat cards is kept
+const BOUNDARY_WEIGHT: f64 = 10.;
+
+/// Simplifies `mesh` down to `target_triangles`, without moving its surface further than
+/// `max_error` from where it was. The mesh keeps more triangles if it can't be simplified further.
+pub fn simplify_mesh(mesh: &Mesh, target_triangles: usize, max_error: f32) -> anyhow::Result<Mesh> {
+    let mut simplifier = Simplifier::new(mesh);
+    let max_cost = (max_error as f64).powi(2);
+    while simplifier.triangle_count > target_triangles {
+        let Some(collapse) = simplifier.heap.pop() else {
+            break;
+        };
+        if collapse.cost > max_cost {
+            break;
+        }
+        let Collapse { from, to, .. } = collapse;
+        if simplifier.removed[from as usize]
+            || simplifier.removed[to as usize]
+            || simplifier.versions[from as usize] != collapse.from_version
+            || simplifier.versions[to as usize] != collapse.to_version
+        {
+            continue;
+        }
+        simplifier.collapse(from, to);
+    }
+    simplifier.build(mesh)
+}
+
+/// The sum of the squared distances to planes, weighted by the areas of their triangles
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric {
+    /// The upper half of the symmetric 4x4 matrix: xx, xy, xz, xw, yy, yz, yw, zz, zw, ww
+    matrix: [f64; 10],
+    area: f64,
+}
+impl Quadric {
+    fn from_plane(normal: DVec3, point: DVec3, weight: f64) -> Self {
+        let [a, b, c, d] = normal
+            .extend(-normal.dot(point))
+            .to_array()
+            .map(|value| value * weight.sqrt());
+        Self {
+            matrix: [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ],
+            area: 0.,
+        }
+    }
+    /// The mean squared distance of `point` to the planes
+    fn error(&self, point: DVec3) -> f64 {
+        let [xx, xy, xz, xw, yy, yz, yw, zz, zw, ww] = self.matrix;
+        let DVec3 { x, y, z } = point;
+        let error = xx * x * x
+            + yy * y * y
+            + zz * z * z
+            + 2. * (xy * x * y + xz * x * z + yz * y * z + xw * x + yw * y + zw * z)
+            + ww;
+        error.max(0.) / self.area.max(f64::EPSILON)
+    }
+}
+impl AddAssign for Quadric {
+    fn add_assign(&mut self, other: Self) {
+        for (value, other) in self.matrix.iter_mut().zip(other.matrix) {
+            *value += other;
+        }
+        self.area += other.area;
+    }
+}
+impl Add for Quadric {
+    type Output = Self;
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+/// Collapsing the vertices at one position onto those at another one
+#[derive(Debug)]
+struct Collapse {
+    cost: f64,
+    from: u32,
+    to: u32,
+    from_version: u32,
+    to_version: u32,
+}
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Collapse {}
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Collapse {
+    // The heap pops the cheapest collapse first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// The state of the simplification. The vertices of the mesh are welded by position into groups,
+/// which are what gets collapsed
+struct Simplifier {
+    /// The group of each vertex
+    groups: Vec<u32>,
+    positions: Vec<DVec3>,
+    quadrics: Vec<Quadric>,
+    removed: Vec<bool>,
+    /// Bumped whenever a group changes, so that the collapses queued before are skipped
+    versions: Vec<u32>,
+    triangles: Vec<[u32; 3]>,
+    alive: Vec<bool>,
+    /// The triangles around each group. May hold dead triangles, and triangles twice
+    group_triangles: Vec<Vec<u32>>,
+    triangle_count: usize,
+    heap: BinaryHeap<Collapse>,
+}
+impl Simplifier {
+    fn new(mesh: &Mesh) -> Self {
+        let mut group_of_position = HashMap::new();
+        let mut positions = Vec::new();
+        let groups = mesh
+            .positions()
+            .iter()
+            .map(|position| {
+                *group_of_position
+                    .entry(position.to_array().map(f32::to_bits))
+                    .or_insert_with(|| {
+                        positions.push(position.as_dvec3());
+                        (positions.len() - 1) as u32
+                    })
+            })
+            .collect_vec();
+
+        let group_count = positions.len();
+        let mut simplifier = Self {
+            groups,
+            positions,
+            quadrics: vec![Quadric::default(); group_count],
+            removed: vec![false; group_count],
+            versions: vec![0; group_count],
+            triangles: Vec::new(),
+            alive: Vec::new(),
+            group_triangles: vec![Vec::new(); group_count],
+            triangle_count: 0,
+            heap: BinaryHeap::new(),
+        };
+
+        // The number of triangles on each edge, with the normal of one of them
+        let mut edges = HashMap::<(u32, u32), (u32, DVec3)>::new();
+        for indices in mesh.indices().chunks_exact(3) {
+            let triangle = [indices[0], indices[1], indices[2]];
+            let [a, b, c] = triangle.map(|vertex| simplifier.groups[vertex as usize]);
+            if a == b || b == c || c == a {
+                continue;
+            }
+            let index = simplifier.triangles.len() as u32;
+            simplifier.triangles.push(triangle);
+            simplifier.alive.push(true);
+            simplifier.triangle_count += 1;
+
+            let [pa, pb, pc] = [a, b, c].map(|group| simplifier.positions[group as usize]);
+            let cross = (pb - pa).cross(pc - pa);
+            let area = cross.length() / 2.;
+            let normal = cross.normalize_or_zero();
+            let mut quadric = Quadric::from_plane(normal, pa, area);
+            quadric.area = area;
+            for group in [a, b, c] {
+                simplifier.quadrics[group as usize] += quadric;
+                simplifier.group_triangles[group as usize].push(index);
+            }
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                edges
+                    .entry((from.min(to), from.max(to)))
+                    .or_insert((0, normal))
+                    .0 += 1;
+            }
+        }
+
+        // The open edges are held in place by planes perpendicular to their triangle
+        for (&(a, b), &(count, normal)) in &edges {
+            if count != 1 {
+                continue;
+            }
+            let [pa, pb] = [a, b].map(|group| simplifier.positions[group as usize]);
+            let edge = pb - pa;
+            let quadric = Quadric::from_plane(
+                edge.cross(normal).normalize_or_zero(),
+                pa,
+                BOUNDARY_WEIGHT * edge.length_squared(),
+            );
+            for group in [a, b] {
+                simplifier.quadrics[group as usize] += quadric;
+            }
+        }
+
+        for &(a, b) in edges.keys() {
+            simplifier.queue(a, b);
+            simplifier.queue(b, a);
+        }
+        simplifier
+    }
+
+    fn queue(&mut self, from: u32, to: u32) {
+        let quadric = self.quadrics[from as usize] + self.quadrics[to as usize];
+        self.heap.push(Collapse {
+            cost: quadric.error(self.positions[to as usize]),
+            from,
+            to,
+            from_version: self.versions[from as usize],
+            to_version: self.versions[to as usize],
+        });
+    }
+
+    fn alive_triangles(&self, group: u32) -> impl Iterator<Item = u32> + '_ {
+        self.group_triangles[group as usize]
+            .iter()
+            .copied()
+            .filter(|&triangle| self.alive[triangle as usize])
+    }
+
+    fn neighbors(&self, group: u32) -> HashSet<u32> {
+        self.alive_triangles(group)
+            .flat_map(|triangle| self.triangles[triangle as usize])
+            .map(|vertex| self.groups[vertex as usize])
+            .filter(|&neighbor| neighbor != group)
+            .collect()
+    }
+
+    /// Collapses the group `from` onto the group `to`, unless it would fold the surface over itself
+    fn collapse(&mut self, from: u32, to: u32) {
+        let group_of = |vertex: u32| self.groups[vertex as usize];
+
+        // Each vertex is moved to a vertex of the other group it shares an edge with, so that
+        // the attributes on either side of a seam stay apart
+        let mut targets = HashMap::new();
+        for triangle in self.alive_triangles(from) {
+            let triangle = self.triangles[triangle as usize];
+            let Some(&target) = triangle.iter().find(|&&vertex| group_of(vertex) == to) else {
+                continue;
+            };
+            for &vertex in &triangle {
+                if group_of(vertex) == from {
+                    targets.entry(vertex).or_insert(target);
+                }
+            }
+        }
+        let used_vertices = self
+            .alive_triangles(from)
+            .flat_map(|triangle| self.triangles[triangle as usize])
+            .filter(|&vertex| group_of(vertex) == from)
+            .collect::<HashSet<_>>();
+        if used_vertices
+            .iter()
+            .any(|vertex| !targets.contains_key(vertex))
+        {
+            return;
+        }
+
+        // The only neighbors of both groups must be across the triangles of the edge, or the
+        // collapse would pinch the surface
+        let shared_neighbors = self
+            .neighbors(from)
+            .intersection(&self.neighbors(to))
+            .copied()
+            .collect::<HashSet<_>>();
+        let opposite = self
+            .alive_triangles(from)
+            .map(|triangle| self.triangles[triangle as usize].map(group_of))
+            .filter(|groups| groups.contains(&to))
+            .flat_map(|groups| {
+                groups
+                    .into_iter()
+                    .filter(|&group| group != from && group != to)
+            })
+            .collect::<HashSet<_>>();
+        if shared_neighbors != opposite {
+            return;
+        }
+
+        // The triangles which remain mustn't flip over
+        let position = |group: u32| {
+            if group == from {
+                self.positions[to as usize]
+            } else {
+                self.positions[group as usize]
+            }
+        };
+        for triangle in self.alive_triangles(from) {
+            let groups = self.triangles[triangle as usize].map(group_of);
+            if groups.contains(&to) {
+                continue;
+            }
+            let [a, b, c] = groups.map(|group| self.positions[group as usize]);
+            let [na, nb, nc] = groups.map(position);
+            if (b - a).cross(c - a).dot((nb - na).cross(nc - na)) <= 0. {
+                return;
+            }
+        }
+
+        for triangle in self.group_triangles[from as usize].clone() {
+            if !self.alive[triangle as usize] {
+                continue;
+            }
+            let vertices = &mut self.triangles[triangle as usize];
+            for vertex in vertices.iter_mut() {
+                if let Some(&target) = targets.get(vertex) {
+                    *vertex = target;
+                }
+            }
+            let [a, b, c] = vertices.map(|vertex| self.groups[vertex as usize]);
+            if a == b || b == c || c == a {
+                self.alive[triangle as usize] = false;
+                self.triangle_count -= 1;
+            } else {
+                self.group_triangles[to as usize].push(triangle);
+            }
+        }
+        let quadric = self.quadrics[from as usize];
+        self.quadrics[to as usize] += quadric;
+        self.removed[from as usize] = true;
+        self.group_triangles[from as usize].clear();
+        self.versions[to as usize] += 1;
+
+        let alive = &self.alive;
+        let triangles = &mut self.group_triangles[to as usize];
+        triangles.retain(|&triangle| alive[triangle as usize]);
+        triangles.sort_unstable();
+        triangles.dedup();
+        for neighbor in self.neighbors(to) {
+            self.queue(to, neighbor);
+            self.queue(neighbor, to);
+        }
+    }
+
+    /// The simplified mesh, with only the vertices which are still used
+    fn build(&self, mesh: &Mesh) -> anyhow::Result<Mesh> {
+        let mut remap = HashMap::new();
+        let mut vertices = Vec::new();
+        let indices = self
+            .triangles
+            .iter()
+            .zip(&self.alive)
+            .filter(|(_, alive)| **alive)
+            .flat_map(|(triangle, _)| *triangle)
+            .map(|vertex| {
+                *remap.entry(vertex).or_insert_with(|| {
+                    vertices.push(vertex as usize);
+                    (vertices.len() - 1) as u32
+                })
+            })
+            .collect_vec();
+
+        fn pick<T: Copy>(values: &[T], vertices: &[usize]) -> Vec<T> {
+            if values.is_empty() {
+                Vec::new()
+            } else {
+                vertices.iter().map(|&vertex| values[vertex]).collect()
+            }
+        }
+        MeshBuilder {
+            positions: pick(mesh.positions(), &vertices),
+            colors: pick(mesh.colors(), &vertices),
+            normals: pick(mesh.normals(), &vertices),
+            tangents: pick(mesh.tangents(), &vertices),
+            texcoords: (0..)
+                .map(|set| mesh.texcoords(set))
+                .take_while(|texcoords| !texcoords.is_empty())
+                .map(|texcoords| pick(texcoords, &vertices))
+                .collect(),
+            joint_indices: pick(mesh.joint_indices(), &vertices),
+            joint_weights: pick(mesh.joint_weights(), &vertices),
+            indices,
+        }
+        .build()
+    }
+}
@@ -17,7 +17,7 @@ use ambient_model::{
 };
 use ambient_native_std::{
     asset_cache::{AssetCache, SyncAssetKeyExt},
-    asset_url::AbsAssetUrl,
+    asset_url::{AbsAssetUrl, AssetUrl},
     download_asset::AssetsCacheDir,
     mesh::Mesh,
     shapes::AABB,
@@ -27,6 +27,8 @@ use ambient_physics::{
         character_controller_height, character_controller_radius, collider, ColliderDef,
         ColliderFromUrls,
     },
+    collider_max_concavity,
+    convex_decomposition::{decompose, ConvexDecompositionParams, ConvexDecompositionStats},
     mesh::PhysxGeometryFromUrl,
     physx::PhysicsKey,
 };
@@ -46,7 +48,10 @@ use physxx::{
 };
 use relative_path::RelativePathBuf;
 
-use crate::{dotdot_path, material_filter_matches, MaterialFilter, TextureResolver};
+use crate::{
+    dotdot_path, lods::default_lod_cutoffs, material_filter_matches, MaterialFilter,
+    TextureResolver,
+};
 
 #[derive(Debug, Clone)]
 pub struct AssetLoc {
@@ -121,6 +126,8 @@ pub struct ModelCrate {
     pub meshes: AssetMap<Mesh>,
     pub animations: AssetMap<AnimationClip>,
     pub images: AssetMap<image::RgbaImage>,
+    /// The images compressed by [Self::compress_images]
+    pub compressed_images: AssetMap<Vec<u8>>,
     pub materials: AssetMap<PbrMaterialDesc>,
     pub px_triangle_meshes: AssetMap<Vec<u8>>,
     pub px_convex_meshes: AssetMap<Vec<u8>>,
@@ -138,6 +145,7 @@ impl ModelCrate {
                 v.write_to(&mut data, ImageOutputFormat::Png).unwrap();
                 data.into_inner()
             }),
+            compressed_images: AssetMap::new("images", "basis", |v| v.clone()),
             materials: AssetMap::new("materials", "json", |v| serde_json::to_vec(v).unwrap()),
             px_triangle_meshes: AssetMap::new("px_triangle_meshes", "pxtm", |v| v.clone()),
             px_convex_meshes: AssetMap::new("px_convex_meshes", "pxcm", |v| v.clone()),
@@ -198,6 +206,7 @@ impl ModelCrate {
             self.meshes.to_items().into_iter(),
             self.animations.to_items().into_iter(),
             self.images.to_items().into_iter(),
+            self.compressed_images.to_items().into_iter(),
             self.materials.to_items().into_iter(),
             self.px_triangle_meshes.to_items().into_iter(),
             self.px_convex_meshes.to_items().into_iter(),
@@ -298,13 +307,7 @@ impl ModelCrate {
         Ok(())
     }
     pub fn merge_mesh_lods(&mut self, cutoffs: Option<Vec<f32>>, lods: Vec<ModelNodeRef>) {
-        let default_min_screen_size = 0.04; // i.e. 4%
-        let lod_step = (1. / default_min_screen_size).powf(1. / (lods.len() - 1) as f32);
-        let cutoffs = cutoffs.unwrap_or_else(|| {
-            (0..lods.len())
-                .map(|i| 1. / lod_step.powi(i as i32))
-                .collect_vec()
-        });
+        let cutoffs = cutoffs.unwrap_or_else(|| default_lod_cutoffs(lods.len()));
 
         let lod_0_node = lods[0].get_node_id();
         let lod_0_world = lods[0].world();
@@ -386,7 +389,11 @@ impl ModelCrate {
                 let material = primitive.material.as_ref().and_then(|mat_url| {
                     let mat_id = lod.model.materials.loc.id_from_path(mat_url.path())?;
                     let lod_mat = lod.model.materials.content.get(&mat_id)?;
-                    Some(self.materials.insert(i.to_string(), lod_mat.clone()).path)
+                    Some(
+                        self.materials
+                            .insert(format!("{i}_{mat_id}"), lod_mat.clone())
+                            .path,
+                    )
                 });
                 root.get_mut(pbr_renderer_primitives_from_url())
                     .unwrap()
@@ -417,6 +424,58 @@ impl ModelCrate {
                 .collect(),
         )
     }
+    /// The nodes of the model which are authored levels of detail, from the most detailed to the least.
+    ///
+    /// These are the first nodes down the hierarchy whose names end with `LOD0`, `LOD1`, and so on.
+    pub fn authored_mesh_lods(&self) -> Vec<EntityId> {
+        let world = self.model_world();
+        let mut nodes = world.resource_opt(children()).cloned().unwrap_or_default();
+        loop {
+            let mut lods = nodes
+                .iter()
+                .filter_map(|&id| {
+                    let name = world.get_ref(id, name()).ok()?.to_ascii_uppercase();
+                    let (_, level) = name.rsplit_once("LOD")?;
+                    Some((level.parse::<usize>().ok()?, id))
+                })
+                .collect_vec();
+            if !lods.is_empty() {
+                lods.sort_by_key(|(level, _)| *level);
+                return lods.into_iter().map(|(_, id)| id).collect();
+            }
+            // Exporters often wrap the whole model in a single root node
+            match nodes.as_slice() {
+                [root] => {
+                    nodes = world
+                        .get_ref(*root, children())
+                        .cloned()
+                        .unwrap_or_default()
+                }
+                _ => return Vec::new(),
+            }
+        }
+    }
+    /// Creates a model crate with the `lods` nodes of this one merged into a single model
+    pub fn merge_authored_mesh_lods(
+        &self,
+        lods: Vec<EntityId>,
+        cutoffs: Option<Vec<f32>>,
+    ) -> ModelCrate {
+        let mut merged = ModelCrate::new();
+        merged.merge_mesh_lods(
+            cutoffs,
+            lods.into_iter()
+                .map(|id| ModelNodeRef {
+                    model: self,
+                    root: Some(id),
+                })
+                .collect(),
+        );
+        // The materials still refer to the images of this crate
+        merged.images.content = self.images.content.clone();
+        merged.compressed_images.content = self.compressed_images.content.clone();
+        merged
+    }
     pub fn set_all_material(&mut self, material: PbrMaterialDesc) {
         self.materials.content.clear();
         let mat_path = dotdot_path(self.materials.insert("main".to_string(), material).path);
@@ -562,6 +621,58 @@ impl ModelCrate {
             cap_texture_size(image, max_size);
         }
     }
+    /// Replaces the images used by the materials with the files `compress` compresses them to,
+    /// for the map of the material they're used as, like `base_color`. The images it returns
+    /// `None` for, and the images used as different maps, stay as they are.
+    pub fn compress_images(
+        &mut self,
+        compress: impl Fn(&str, &RgbaImage) -> anyhow::Result<Option<Vec<u8>>>,
+    ) -> anyhow::Result<()> {
+        let mut maps = HashMap::<String, Option<&'static str>>::new();
+        for material in self.materials.content.values_mut() {
+            // The runtime puts a separate opacity map into the base color, which it can't do
+            // with a compressed texture
+            let has_opacity = material.opacity.is_some();
+            for (map, url) in material_texture_maps(material) {
+                let Some(id) = url
+                    .as_ref()
+                    .and_then(|url| self.images.loc.id_from_path(url.path()))
+                else {
+                    continue;
+                };
+                let map = Some(map).filter(|_| !has_opacity);
+                let entry = maps.entry(id).or_insert(map);
+                if *entry != map {
+                    *entry = None;
+                }
+            }
+        }
+
+        for (id, map) in maps {
+            let (Some(map), Some(image)) = (map, self.images.content.get(&id)) else {
+                continue;
+            };
+            if let Some(data) = compress(map, image)? {
+                self.images.content.remove(&id);
+                self.compressed_images.insert(id, data);
+            }
+        }
+
+        for material in self.materials.content.values_mut() {
+            for (_, url) in material_texture_maps(material) {
+                let Some(id) = url
+                    .as_ref()
+                    .and_then(|url| self.images.loc.id_from_path(url.path()))
+                else {
+                    continue;
+                };
+                if self.compressed_images.content.contains_key(&id) {
+                    *url = Some(dotdot_path(self.compressed_images.loc.path(id)).into());
+                }
+            }
+        }
+        Ok(())
+    }
     pub fn update_transforms(&mut self) {
         TransformSystem::new().run(self.model_world_mut(), &FrameEvent);
     }
@@ -604,11 +715,15 @@ impl ModelCrate {
             .add_component(object, character_controller_height(), height.unwrap_or(2.0))
             .unwrap();
     }
+    /// Splits the convex version of the collider into convex hulls when `convex_decomposition` is
+    /// given, and records the largest concavity left in them in the `collider_max_concavity` of
+    /// the prefab
     pub fn create_collider_from_model(
         &mut self,
         assets: &AssetCache,
         flip_normals: bool,
         reverse_indices: bool,
+        convex_decomposition: Option<ConvexDecompositionParams>,
     ) -> anyhow::Result<()> {
         self.update_transforms();
         let physics = PhysicsKey.get(assets);
@@ -635,10 +750,26 @@ impl ModelCrate {
                 false
             }
         };
-        let create_convex_mesh = |asset_crate: &mut ModelCrate,
-                                  id: &str,
-                                  scale_signum: Vec3|
-         -> Option<RelativePathBuf> {
+        let cook_convex_mesh = |points: Vec<Vec3>, indices: Option<Vec<u32>>| -> Option<Vec<u8>> {
+            let desc = PxConvexMeshDesc {
+                points,
+                indices,
+                vertex_limit: None,
+                flags: Some(PxConvexFlag::COMPUTE_CONVEX),
+            };
+            let stream = PxDefaultMemoryOutputStream::new();
+            let mut res = physxx::PxConvexMeshCookingResult::Success;
+            if !physics.cooking.cook_convex_mesh(&desc, &stream, &mut res) {
+                tracing::error!("Failed to cook convex mesh: {:?}", res);
+                return None;
+            }
+            Some(stream.get_data())
+        };
+        let mut decomposition_stats = ConvexDecompositionStats::default();
+        let mut create_convex_meshes = |asset_crate: &mut ModelCrate,
+                                        id: &str,
+                                        scale_signum: Vec3|
+         -> Vec<RelativePathBuf> {
             // Physx doesn't support negative scaling on Convex meshes, so we need to generate a mesh with the right
             // scale signum first, and then scale that with the absolute scale
             let to_sign = |v| if v >= 0. { "p" } else { "n" }.to_string();
@@ -649,33 +780,45 @@ impl ModelCrate {
                 to_sign(scale_signum.z)
             );
             if asset_crate.px_convex_meshes.content.contains_key(&full_id) {
-                return Some(asset_crate.px_convex_meshes.loc.path(&full_id));
+                return vec![asset_crate.px_convex_meshes.loc.path(&full_id)];
+            }
+            let hull_ids = |count: usize| (0..count).map(|i| format!("{full_id}_hull{i}"));
+            let cached_hulls = hull_ids(usize::MAX)
+                .take_while(|hull_id| asset_crate.px_convex_meshes.content.contains_key(hull_id))
+                .map(|hull_id| asset_crate.px_convex_meshes.loc.path(&hull_id))
+                .collect_vec();
+            if !cached_hulls.is_empty() {
+                return cached_hulls;
             }
             let mesh = asset_crate.meshes.content.get(id).unwrap();
+            // Apply the correct mirroring according to the base scale
+            let points = mesh
+                .positions()
+                .iter()
+                .map(|&p| p * scale_signum)
+                .collect_vec();
 
-            let desc = PxConvexMeshDesc {
-                // Apply the correct mirroring according to the base scale
-                points: mesh
-                    .positions()
-                    .iter()
-                    .map(|&p| p * scale_signum)
-                    .collect_vec(),
-                indices: Some(mesh.indices().to_vec()),
-                vertex_limit: None,
-                flags: Some(PxConvexFlag::COMPUTE_CONVEX),
-            };
-            let stream = PxDefaultMemoryOutputStream::new();
-            let mut res = physxx::PxConvexMeshCookingResult::Success;
-            if !physics.cooking.cook_convex_mesh(&desc, &stream, &mut res) {
-                tracing::error!("Failed to cook convex mesh: {:?}", res);
-                return None;
+            if let Some(params) = &convex_decomposition {
+                let decomposition = decompose(&points, mesh.indices(), params);
+                decomposition_stats.hull_count += decomposition.stats.hull_count;
+                decomposition_stats.max_concavity = decomposition_stats
+                    .max_concavity
+                    .max(decomposition.stats.max_concavity);
+                if decomposition.hulls.len() > 1 {
+                    return hull_ids(decomposition.hulls.len())
+                        .zip(decomposition.hulls)
+                        .filter_map(|(hull_id, hull)| {
+                            let data = cook_convex_mesh(hull, None)?;
+                            Some(asset_crate.px_convex_meshes.insert(hull_id, data).path)
+                        })
+                        .collect();
+                }
             }
-            Some(
-                asset_crate
-                    .px_convex_meshes
-                    .insert(full_id, stream.get_data())
-                    .path,
-            )
+
+            cook_convex_mesh(points, Some(mesh.indices().to_vec()))
+                .map(|data| asset_crate.px_convex_meshes.insert(full_id, data).path)
+                .into_iter()
+                .collect()
         };
         let mut convex = Vec::new();
         let mut triangle = Vec::new();
@@ -685,6 +828,10 @@ impl ModelCrate {
             query(pbr_renderer_primitives_from_url()).collect_cloned(world, None)
         };
         for (id, prims) in entities {
+            let prims = prims
+                .into_iter()
+                .filter(|x| !self.is_imposter(x))
+                .collect_vec();
             let ltw = self
                 .model_world()
                 .get(id, local_to_world())
@@ -700,16 +847,16 @@ impl ModelCrate {
                     let (scale, rot, pos) = transform.to_scale_rotation_translation();
                     let mesh_id = self.meshes.loc.id_from_path(primitive.mesh.path()).unwrap();
                     if create_triangle_mesh(self, &mesh_id) {
-                        if let Some(convex_path) =
-                            create_convex_mesh(self, &mesh_id, scale.signum())
-                        {
-                            let convex_path = dotdot_path(convex_path);
+                        let convex_paths = create_convex_meshes(self, &mesh_id, scale.signum());
+                        if !convex_paths.is_empty() {
+                            for convex_path in convex_paths {
+                                convex.push((
+                                    Mat4::from_scale_rotation_translation(scale.abs(), rot, pos),
+                                    PhysxGeometryFromUrl(dotdot_path(convex_path).into()),
+                                ));
+                            }
                             let triangle_path =
                                 dotdot_path(self.px_triangle_meshes.loc.path(mesh_id));
-                            convex.push((
-                                Mat4::from_scale_rotation_translation(scale.abs(), rot, pos),
-                                PhysxGeometryFromUrl(convex_path.into()),
-                            ));
                             triangle.push((transform, PhysxGeometryFromUrl(triangle_path.into())));
                         }
                     }
@@ -724,23 +871,93 @@ impl ModelCrate {
             },
         );
         let prefab = self.prefab_world_mut();
+        let object = prefab.resource(children())[0];
         prefab
             .add_component(
-                prefab.resource(children())[0],
+                object,
                 collider(),
                 ColliderDef::Asset {
                     collider: dotdot_path(obj_collider.path).into(),
                 },
             )
             .unwrap();
+        if convex_decomposition.is_some() {
+            prefab
+                .add_component(
+                    object,
+                    collider_max_concavity(),
+                    decomposition_stats.max_concavity,
+                )
+                .unwrap();
+        }
         Ok(())
     }
+    /// Whether `primitive` is the quad of an octahedral imposter, which only looks like the meshes it
+    /// stands in for, so colliders and navmeshes skip it
+    pub fn is_imposter(&self, primitive: &PbrRenderPrimitiveFromUrl) -> bool {
+        primitive
+            .material
+            .as_ref()
+            .and_then(|url| self.materials.get_by_path(url.path()))
+            .map_or(false, |material| material.imposter.is_some())
+    }
+    /// The triangles of the meshes of the model in world space, counter-clockwise when seen from
+    /// their front. Like the colliders, only the lowest level of detail of each node is used.
+    pub fn world_triangles(&mut self) -> Vec<[Vec3; 3]> {
+        self.update_transforms();
+        let world_transform = self.model().get_transform().unwrap_or_default();
+        let world = self.model_world();
+        let mut triangles = Vec::new();
+        for (id, prims) in query(pbr_renderer_primitives_from_url()).collect_cloned(world, None) {
+            let prims = prims
+                .into_iter()
+                .filter(|x| !self.is_imposter(x))
+                .collect_vec();
+            let Some(max_lod) = prims.iter().map(|x| x.lod).max() else {
+                continue;
+            };
+            let transform = world_transform
+                * world.get(id, local_to_world()).unwrap_or_default()
+                * world.get(id, mesh_to_local()).unwrap_or_default();
+            // A mirroring transform turns the triangles inside out
+            let flip = transform.determinant() < 0.;
+            for primitive in prims.into_iter().filter(|x| x.lod == max_lod) {
+                let Some(mesh) = self
+                    .meshes
+                    .loc
+                    .id_from_path(primitive.mesh.path())
+                    .and_then(|id| self.meshes.content.get(&id))
+                else {
+                    continue;
+                };
+                let positions = mesh.positions();
+                for indices in mesh.indices().chunks_exact(3) {
+                    let [a, b, c] = [0, 1, 2]
+                        .map(|i| transform.transform_point3(positions[indices[i] as usize]));
+                    triangles.push(if flip { [a, c, b] } else { [a, b, c] });
+                }
+            }
+        }
+        triangles
+    }
 }
 pub struct AssetItem {
     pub path: RelativePathBuf,
     pub data: Arc<Vec<u8>>,
 }
 
+/// The texture maps of `material`, with their names
+fn material_texture_maps(
+    material: &mut PbrMaterialDesc,
+) -> [(&'static str, &mut Option<AssetUrl>); 4] {
+    [
+        ("base_color", &mut material.base_color),
+        ("opacity", &mut material.opacity),
+        ("normalmap", &mut material.normalmap),
+        ("metallic_roughness", &mut material.metallic_roughness),
+    ]
+}
+
 pub fn cap_texture_size(image: &mut RgbaImage, max_size: u32) {
     if image.width() > max_size || image.height() > max_size {
         let (width, height) = if image.width() >= image.height() {
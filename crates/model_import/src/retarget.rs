@@ -0,0 +1,313 @@
+//! Retargeting of the animations authored on one skeleton onto another skeleton, with different
+//! proportions.
+//!
+//! The rotations of the bones are carried over as rotations relative to their rest pose in world
+//! space, so that a bone that is bent forward in the source skeleton is bent forward in the target
+//! skeleton, regardless of how the bones are oriented in either skeleton. Only the top-level
+//! animated bones (i.e. the hips) keep their translations, scaled by the ratio of their heights;
+//! the other bones keep the lengths of the target skeleton.
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use ambient_animation::{AnimationClip, AnimationOutputs, AnimationTarget, AnimationTrack};
+use ambient_core::{
+    hierarchy::parent,
+    transform::{local_to_world, rotation, translation},
+};
+use ambient_ecs::{generated::animation::components::bind_id, EntityId};
+use ambient_pipeline_types::models::SkeletonMap;
+use glam::{Mat4, Quat, Vec3};
+use itertools::Itertools;
+
+use crate::model_crate::ModelCrate;
+
+/// Retargets the animations of `source` onto the skeleton of `target`, using `map` to match their
+/// bones.
+///
+/// Fails if a bone of the map isn't in its skeleton, or if an animated bone of `source` has no
+/// counterpart in `target` and isn't ignored by the map.
+pub fn retarget_animations(
+    source: &mut ModelCrate,
+    target: &mut ModelCrate,
+    map: &SkeletonMap,
+) -> anyhow::Result<()> {
+    source.update_transforms();
+    target.update_transforms();
+
+    let animated = source
+        .animations
+        .content
+        .values()
+        .flat_map(|clip| {
+            clip.tracks
+                .iter()
+                .filter_map(|track| track.target.bind_id())
+        })
+        .map(|id| id.to_string())
+        .collect::<BTreeSet<_>>();
+
+    let mut missing_source = BTreeSet::new();
+    let mut missing_target = BTreeSet::new();
+    let mut pairs = HashMap::new();
+    for (source_bone, target_bone) in &map.bones {
+        let source_bone_id = find_bone(source, source_bone);
+        let target_bone_id = find_bone(target, target_bone);
+        if source_bone_id.is_none() {
+            missing_source.insert(source_bone.clone());
+        }
+        if target_bone_id.is_none() {
+            missing_target.insert(target_bone.clone());
+        }
+        if let (Some(source_bone_id), Some(target_bone_id)) = (source_bone_id, target_bone_id) {
+            pairs.insert(source_bone_id.1, (source_bone_id.0, target_bone_id));
+        }
+    }
+    let mut ignored = HashSet::new();
+    for bone in &map.ignore {
+        match find_bone(source, bone) {
+            Some((_, id)) => {
+                ignored.insert(id);
+            }
+            None => {
+                missing_source.insert(bone.clone());
+            }
+        }
+    }
+    let mut unmapped = Vec::new();
+    for id in &animated {
+        if pairs.contains_key(id) || ignored.contains(id) {
+            continue;
+        }
+        match (find_bone(source, id), find_bone(target, id)) {
+            (Some((source_bone, _)), Some(target_bone)) => {
+                pairs.insert(id.clone(), (source_bone, target_bone));
+            }
+            _ => unmapped.push(id.clone()),
+        }
+    }
+
+    let mut errors = Vec::new();
+    if !missing_source.is_empty() {
+        errors.push(format!(
+            "bones not found in the source skeleton: {}",
+            missing_source.iter().join(", ")
+        ));
+    }
+    if !missing_target.is_empty() {
+        errors.push(format!(
+            "bones not found in the target skeleton: {}",
+            missing_target.iter().join(", ")
+        ));
+    }
+    if !unmapped.is_empty() {
+        errors.push(format!(
+            "animated bones with no counterpart in the target skeleton (map or ignore them): {}",
+            unmapped.iter().join(", ")
+        ));
+    }
+    if !errors.is_empty() {
+        anyhow::bail!("Failed to map the skeletons:\n  {}", errors.join("\n  "));
+    }
+
+    let bones = pairs
+        .into_iter()
+        .map(|(id, (source_bone, (target_bone, target_id)))| {
+            let bone = RetargetedBone::new(source, source_bone, target, target_bone, target_id);
+            (id, bone)
+        })
+        .collect::<HashMap<_, _>>();
+    for clip in source.animations.content.values_mut() {
+        *clip = retarget_clip(clip, &bones, &ignored);
+    }
+    Ok(())
+}
+
+/// Finds a bone by its bind id, or by its name, and returns it with its bind id
+fn find_bone(model_crate: &ModelCrate, name: &str) -> Option<(EntityId, String)> {
+    let model = model_crate.model();
+    let id = model
+        .get_entity_id_by_bind_id(name)
+        .or_else(|| model.get_entity_id_by_name(name))?;
+    let bind_id = model_crate.model_world().get_ref(id, bind_id()).ok()?;
+    Some((id, bind_id.clone()))
+}
+
+/// How the animation of a bone of the source skeleton is carried over to a bone of the target
+/// skeleton
+struct RetargetedBone {
+    target_bind_id: String,
+    /// The bind ids of the ancestors of the source bone
+    source_ancestors: Vec<String>,
+    /// A local rotation `r` of the source bone becomes `rotation_before * r * rotation_after`
+    rotation_before: Quat,
+    rotation_after: Quat,
+    source_translation: Vec3,
+    target_translation: Vec3,
+    /// Maps an offset from the rest translation of the source bone to one of the target bone
+    translation_transform: Mat4,
+}
+impl RetargetedBone {
+    fn new(
+        source: &ModelCrate,
+        source_bone: EntityId,
+        target: &ModelCrate,
+        target_bone: EntityId,
+        target_bind_id: String,
+    ) -> Self {
+        let source_rest = RestPose::new(source, source_bone);
+        let target_rest = RestPose::new(target, target_bone);
+
+        let source_parent_rotation = rotation_of(source_rest.parent_world);
+        let target_parent_rotation = rotation_of(target_rest.parent_world);
+        let rotation_before = target_parent_rotation.inverse() * source_parent_rotation;
+        let rotation_after =
+            rotation_of(source_rest.world).inverse() * rotation_of(target_rest.world);
+
+        // The heights of the bones above the ground tell how much longer the legs of the target are
+        let source_height = source_rest.world.w_axis.z;
+        let target_height = target_rest.world.w_axis.z;
+        let scale = if source_height.abs() > 1e-4 && target_height / source_height > 0. {
+            target_height / source_height
+        } else {
+            1.
+        };
+        let translation_transform = target_rest.parent_world.inverse()
+            * Mat4::from_scale(Vec3::splat(scale))
+            * source_rest.parent_world;
+
+        let world = source.model_world();
+        let mut source_ancestors = Vec::new();
+        let mut ancestor = world.get(source_bone, parent()).ok();
+        while let Some(id) = ancestor {
+            if let Ok(ancestor_bind_id) = world.get_ref(id, bind_id()) {
+                source_ancestors.push(ancestor_bind_id.clone());
+            }
+            ancestor = world.get(id, parent()).ok();
+        }
+
+        Self {
+            target_bind_id,
+            source_ancestors,
+            rotation_before,
+            rotation_after,
+            source_translation: source_rest.translation,
+            target_translation: target_rest.translation,
+            translation_transform,
+        }
+    }
+}
+
+/// The rest pose of a bone, with the transform of its model applied
+struct RestPose {
+    world: Mat4,
+    parent_world: Mat4,
+    translation: Vec3,
+}
+impl RestPose {
+    fn new(model_crate: &ModelCrate, bone: EntityId) -> Self {
+        let world = model_crate.model_world();
+        let model_transform = model_crate.model().get_transform().unwrap_or_default();
+        let parent_world = match world.get(bone, parent()) {
+            Ok(parent_id) => {
+                model_transform * world.get(parent_id, local_to_world()).unwrap_or_default()
+            }
+            Err(_) => model_transform,
+        };
+        Self {
+            world: model_transform * world.get(bone, local_to_world()).unwrap_or_default(),
+            parent_world,
+            translation: world.get(bone, translation()).unwrap_or_default(),
+        }
+    }
+}
+
+fn rotation_of(transform: Mat4) -> Quat {
+    transform.to_scale_rotation_translation().1
+}
+
+fn retarget_clip(
+    clip: &AnimationClip,
+    bones: &HashMap<String, RetargetedBone>,
+    ignored: &HashSet<String>,
+) -> AnimationClip {
+    // The rotations of the bones have to be whole to be retargeted, so the tracks of the separate
+    // fields are merged first
+    let (field_tracks, mut tracks): (Vec<_>, Vec<_>) = clip
+        .tracks
+        .iter()
+        .cloned()
+        .partition(|track| track.outputs.field().is_some());
+    if !field_tracks.is_empty() {
+        let mut merged = AnimationClip {
+            tracks: field_tracks,
+            ..clip.clone()
+        };
+        merged.merge_field_tracks();
+        tracks.extend(merged.tracks);
+    }
+
+    let animated = tracks
+        .iter()
+        .filter_map(|track| track.target.bind_id())
+        .map(|id| id.to_string())
+        .collect::<HashSet<_>>();
+    let tracks = tracks
+        .into_iter()
+        .filter_map(|track| {
+            let Some(id) = track.target.bind_id() else {
+                return Some(track);
+            };
+            if ignored.contains(id) {
+                return None;
+            }
+            let bone = bones.get(id)?;
+            let outputs = match &track.outputs {
+                AnimationOutputs::Quat { component, data }
+                    if track.outputs.component() == rotation() =>
+                {
+                    AnimationOutputs::Quat {
+                        component: *component,
+                        data: data
+                            .iter()
+                            .map(|r| (bone.rotation_before * *r * bone.rotation_after).normalize())
+                            .collect(),
+                    }
+                }
+                AnimationOutputs::Vec3 { component, data }
+                    if track.outputs.component() == translation() =>
+                {
+                    // Bones below an animated bone keep the translations of the target skeleton,
+                    // so that its proportions are kept
+                    if bone
+                        .source_ancestors
+                        .iter()
+                        .any(|ancestor| animated.contains(ancestor))
+                    {
+                        return None;
+                    }
+                    AnimationOutputs::Vec3 {
+                        component: *component,
+                        data: data
+                            .iter()
+                            .map(|t| {
+                                bone.target_translation
+                                    + bone
+                                        .translation_transform
+                                        .transform_vector3(*t - bone.source_translation)
+                            })
+                            .collect(),
+                    }
+                }
+                outputs => outputs.clone(),
+            };
+            Some(AnimationTrack {
+                target: AnimationTarget::BinderId(bone.target_bind_id.clone()),
+                inputs: track.inputs,
+                outputs,
+            })
+        })
+        .collect();
+    AnimationClip {
+        tracks,
+        ..clip.clone()
+    }
+}
@@ -17,6 +17,7 @@ use url::Url;
 
 use crate::{
     asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    bundle::read_bundled_file,
     download_asset::{download, AssetsCacheDir},
     Cb,
 };
@@ -255,7 +256,9 @@ impl AbsAssetUrl {
         self.to_download_url_with_base(&content_url)
     }
     pub async fn download_bytes(&self, assets: &AssetCache) -> anyhow::Result<Vec<u8>> {
-        if let Some(path) = self.to_file_path()? {
+        if let Some(content) = read_bundled_file(assets, self).await? {
+            Ok(content)
+        } else if let Some(path) = self.to_file_path()? {
             Ok(ambient_sys::fs::read(path)
                 .await
                 .context(format!("Failed to read file at: {:}", self.0))?)
@@ -270,7 +273,10 @@ impl AbsAssetUrl {
         }
     }
     pub async fn download_string(&self, assets: &AssetCache) -> anyhow::Result<String> {
-        if let Some(path) = self.to_file_path()? {
+        if let Some(content) = read_bundled_file(assets, self).await? {
+            Ok(String::from_utf8(content)
+                .context(format!("File at {:} is not valid UTF-8", self.0))?)
+        } else if let Some(path) = self.to_file_path()? {
             Ok(ambient_sys::fs::read_to_string(path)
                 .await
                 .context(format!("Failed to read file at: {:}", self.0))?)
@@ -287,10 +293,18 @@ impl AbsAssetUrl {
         &self,
         assets: &AssetCache,
     ) -> anyhow::Result<T> {
-        if let Some(path) = self.to_file_path()? {
-            let content: Vec<u8> = ambient_sys::fs::read(path)
-                .await
-                .context(format!("Failed to read file at: {:}", self.0))?;
+        let content = if let Some(content) = read_bundled_file(assets, self).await? {
+            Some(content)
+        } else if let Some(path) = self.to_file_path()? {
+            Some(
+                ambient_sys::fs::read(path)
+                    .await
+                    .context(format!("Failed to read file at: {:}", self.0))?,
+            )
+        } else {
+            None
+        };
+        if let Some(content) = content {
             let de = &mut serde_json::de::Deserializer::from_slice(&content);
             let res = serde_path_to_error::deserialize(de)?;
             Ok(res)
@@ -0,0 +1,436 @@
+//! Bundles, which pack built packages (their bytecode, processed assets and manifests) into a
+//! single signed archive.
+//!
+//! The files of the bundle at `https://example.com/game.ambient-bundle` are read from the URLs
+//! below it, i.e. `https://example.com/game.ambient-bundle/<package id>/ambient.toml`. The bundle
+//! is downloaded, and its signature verified, the first time one of its files is read.
+//!
+//! The public key a bundle is signed with is in the bundle itself, so the signature alone only
+//! shows that the bundle wasn't corrupted: anyone can sign a modified bundle with their own key.
+//! Bundles are only loaded when their key is one of the [TrustedBundleKeysKey], so that they are
+//! known to come from their author.
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    asset_cache::{
+        AssetCache, AssetKeepalive, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt,
+    },
+    asset_url::{AbsAssetUrl, ContentBaseUrlKey, ASSETS_PROTOCOL_SCHEME},
+    download_asset::{download, AssetResult},
+};
+
+pub const BUNDLE_EXTENSION: &str = "ambient-bundle";
+
+const MAGIC: &[u8; 4] = b"ABDL";
+const VERSION: u32 = 1;
+const PUBLIC_KEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+#[derive(Error, Debug)]
+pub enum BundleError {
+    #[error("Not a bundle, or a bundle of an unsupported version")]
+    InvalidHeader,
+    #[error("The bundle ends unexpectedly")]
+    UnexpectedEnd,
+    #[error("Failed to read the index of the bundle")]
+    InvalidIndex(#[from] bincode::Error),
+    #[error("The bundle contains a file outside of it: {0:?}")]
+    InvalidPath(String),
+    #[error("The signature of the bundle doesn't match its contents")]
+    InvalidSignature,
+    #[error("Invalid signing key: {0}")]
+    InvalidKey(String),
+    #[error("The bundle is signed with {0}, which is not a trusted key")]
+    UntrustedKey(String),
+}
+
+/// The public keys of the signers whose bundles are loaded. Without any, no bundle is loaded.
+///
+/// Clients trust the keys of the server they join, as the server is where the bundles come from
+#[derive(Debug, Clone)]
+pub struct TrustedBundleKeysKey;
+impl SyncAssetKey<Arc<Vec<Vec<u8>>>> for TrustedBundleKeysKey {
+    fn load(&self, _assets: AssetCache) -> Arc<Vec<Vec<u8>>> {
+        Default::default()
+    }
+}
+
+/// Parses a public key in hexadecimal, as it is printed when a bundle is signed
+pub fn parse_public_key(hex: &str) -> Result<Vec<u8>, BundleError> {
+    data_encoding::HEXLOWER_PERMISSIVE
+        .decode(hex.trim().as_bytes())
+        .ok()
+        .filter(|key| key.len() == PUBLIC_KEY_LEN)
+        .ok_or_else(|| BundleError::InvalidKey(format!("{hex:?} is not a public key")))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleIndex {
+    main_package: String,
+    main_package_name: String,
+    files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleFile {
+    path: String,
+    offset: u64,
+    len: u64,
+}
+
+/// The Ed25519 key bundles are signed with
+pub struct BundleSigningKey(Ed25519KeyPair);
+impl BundleSigningKey {
+    /// Generates a new key, and returns it with its PKCS#8 document, to store it
+    pub fn generate() -> Result<(Self, Vec<u8>), BundleError> {
+        let document = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new())
+            .map_err(|_| BundleError::InvalidKey("failed to generate a key".to_string()))?;
+        let key = Self::from_pkcs8(document.as_ref())?;
+        Ok((key, document.as_ref().to_vec()))
+    }
+    pub fn from_pkcs8(document: &[u8]) -> Result<Self, BundleError> {
+        Ed25519KeyPair::from_pkcs8(document)
+            .map(Self)
+            .map_err(|err| BundleError::InvalidKey(err.to_string()))
+    }
+    pub fn public_key(&self) -> &[u8] {
+        self.0.public_key().as_ref()
+    }
+}
+impl std::fmt::Debug for BundleSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BundleSigningKey")
+            .field(&data_encoding::HEXLOWER.encode(self.public_key()))
+            .finish()
+    }
+}
+
+/// A bundle whose signature has been verified
+///
+/// It is laid out as the magic `ABDL`, the version, the length of the index and the index, followed
+/// by the contents of the files, the public key and the signature of everything before it.
+pub struct Bundle {
+    index: BundleIndex,
+    files: HashMap<String, Range<usize>>,
+    data: Vec<u8>,
+    hash: String,
+}
+impl Bundle {
+    /// Packs `files`, keyed by their paths relative to the root of the bundle, into a bundle signed
+    /// with `key`
+    pub fn write(
+        main_package: &str,
+        main_package_name: &str,
+        files: impl IntoIterator<Item = (String, Vec<u8>)>,
+        key: &BundleSigningKey,
+    ) -> Result<Vec<u8>, BundleError> {
+        let mut index = BundleIndex {
+            main_package: main_package.to_string(),
+            main_package_name: main_package_name.to_string(),
+            files: Vec::new(),
+        };
+        let mut contents = Vec::new();
+        for (path, data) in files {
+            check_path(&path)?;
+            index.files.push(BundleFile {
+                path,
+                offset: contents.len() as u64,
+                len: data.len() as u64,
+            });
+            contents.extend(data);
+        }
+        let index = bincode::serialize(&index)?;
+
+        let mut data =
+            Vec::with_capacity(12 + index.len() + contents.len() + PUBLIC_KEY_LEN + SIGNATURE_LEN);
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&VERSION.to_le_bytes());
+        data.extend_from_slice(&(index.len() as u32).to_le_bytes());
+        data.extend(index);
+        data.extend(contents);
+        data.extend_from_slice(key.public_key());
+        let signature = key.0.sign(&data);
+        data.extend_from_slice(signature.as_ref());
+        Ok(data)
+    }
+
+    /// Reads a bundle, and verifies that its signature matches its contents, and that it was
+    /// signed with one of the `trusted_keys`
+    pub fn parse(data: Vec<u8>, trusted_keys: &[Vec<u8>]) -> Result<Self, BundleError> {
+        if data.len() < 12 || &data[0..4] != MAGIC {
+            return Err(BundleError::InvalidHeader);
+        }
+        let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        if version != VERSION {
+            return Err(BundleError::InvalidHeader);
+        }
+        let index_len = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
+        let contents_end = data
+            .len()
+            .checked_sub(PUBLIC_KEY_LEN + SIGNATURE_LEN)
+            .ok_or(BundleError::UnexpectedEnd)?;
+        let contents_start = index_len
+            .checked_add(12)
+            .filter(|start| *start <= contents_end)
+            .ok_or(BundleError::UnexpectedEnd)?;
+
+        let signed_end = contents_end + PUBLIC_KEY_LEN;
+        let public_key = &data[contents_end..signed_end];
+        UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(&data[..signed_end], &data[signed_end..])
+            .map_err(|_| BundleError::InvalidSignature)?;
+        if !trusted_keys.iter().any(|key| key == public_key) {
+            return Err(BundleError::UntrustedKey(
+                data_encoding::HEXLOWER.encode(public_key),
+            ));
+        }
+
+        let index: BundleIndex = bincode::deserialize(&data[12..contents_start])?;
+        let mut files = HashMap::new();
+        for file in &index.files {
+            check_path(&file.path)?;
+            let range = usize::try_from(file.offset)
+                .ok()
+                .and_then(|offset| contents_start.checked_add(offset))
+                .and_then(|start| Some(start..start.checked_add(file.len.try_into().ok()?)?))
+                .filter(|range| range.end <= contents_end)
+                .ok_or(BundleError::UnexpectedEnd)?;
+            files.insert(file.path.clone(), range);
+        }
+
+        let hash = ring::digest::digest(&ring::digest::SHA256, &data);
+        Ok(Self {
+            index,
+            files,
+            hash: data_encoding::HEXLOWER.encode(hash.as_ref()),
+            data,
+        })
+    }
+
+    /// The directory of the main package in the bundle
+    pub fn main_package(&self) -> &str {
+        &self.index.main_package
+    }
+    pub fn main_package_name(&self) -> &str {
+        &self.index.main_package_name
+    }
+    /// The public key the bundle was signed with
+    pub fn public_key(&self) -> &[u8] {
+        let end = self.data.len() - SIGNATURE_LEN;
+        &self.data[end - PUBLIC_KEY_LEN..end]
+    }
+    /// The SHA-256 hash of the bundle, in hexadecimal
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.index.files.iter().map(|file| file.path.as_str())
+    }
+    pub fn file(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(path).map(|range| &self.data[range.clone()])
+    }
+
+    /// Writes the files of the bundle to `dir`
+    #[cfg(not(target_os = "unknown"))]
+    pub fn unpack(&self, dir: &std::path::Path) -> std::io::Result<()> {
+        for (path, range) in &self.files {
+            let path = dir.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, &self.data[range.clone()])?;
+        }
+        Ok(())
+    }
+}
+impl std::fmt::Debug for Bundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bundle")
+            .field("main_package", &self.index.main_package)
+            .field("files", &self.index.files.len())
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
+/// Only relative paths that stay inside the bundle are allowed
+fn check_path(path: &str) -> Result<(), BundleError> {
+    if path.is_empty()
+        || path.starts_with('/')
+        || path.contains('\\')
+        || path
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        return Err(BundleError::InvalidPath(path.to_string()));
+    }
+    Ok(())
+}
+
+/// Downloads a bundle and verifies its signature, against the [TrustedBundleKeysKey]
+#[derive(Debug, Clone)]
+pub struct BundleFromUrl(pub AbsAssetUrl);
+#[async_trait]
+impl AsyncAssetKey<AssetResult<Arc<Bundle>>> for BundleFromUrl {
+    fn keepalive(&self) -> AssetKeepalive {
+        AssetKeepalive::Forever
+    }
+    async fn load(self, assets: AssetCache) -> AssetResult<Arc<Bundle>> {
+        let data = if let Some(path) = self.0.to_file_path()? {
+            ambient_sys::fs::read(path)
+                .await
+                .with_context(|| format!("Failed to read bundle at: {}", self.0))?
+        } else {
+            download(&assets, self.0 .0.clone(), |resp| async {
+                Ok(resp.bytes().await?)
+            })
+            .await?
+            .to_vec()
+        };
+        let bundle = Bundle::parse(data, &TrustedBundleKeysKey.get(&assets))
+            .with_context(|| format!("Invalid bundle {}", self.0))?;
+        Ok(Arc::new(bundle))
+    }
+}
+
+/// The bundles that were loaded some other way than from their URL
+#[derive(Debug, Clone)]
+struct BundleMountsKey;
+impl SyncAssetKey<Arc<Mutex<HashMap<AbsAssetUrl, Arc<Bundle>>>>> for BundleMountsKey {
+    fn load(&self, _assets: AssetCache) -> Arc<Mutex<HashMap<AbsAssetUrl, Arc<Bundle>>>> {
+        Default::default()
+    }
+}
+
+/// Reads the files below `url` from `bundle`, instead of downloading it from `url`
+pub fn mount_bundle(assets: &AssetCache, url: AbsAssetUrl, bundle: Arc<Bundle>) {
+    BundleMountsKey
+        .get(assets)
+        .lock()
+        .unwrap()
+        .insert(url.as_file(), bundle);
+}
+
+/// Splits the url of a file in a bundle into the url of the bundle and the path of the file in it
+pub fn split_bundle_url(url: &AbsAssetUrl) -> Option<(AbsAssetUrl, String)> {
+    let path = url.decoded_path();
+    let marker = format!(".{BUNDLE_EXTENSION}/");
+    let end = path.as_str().find(&marker)? + marker.len();
+    let file = &path.as_str()[end..];
+    if file.is_empty() {
+        return None;
+    }
+    let mut bundle_url = url.clone();
+    bundle_url.set_path(&path.as_str()[..end - 1]);
+    bundle_url.0.set_query(None);
+    bundle_url.0.set_fragment(None);
+    Some((bundle_url, file.to_string()))
+}
+
+/// Reads `url` from its bundle, if it is in one
+pub async fn read_bundled_file(
+    assets: &AssetCache,
+    url: &AbsAssetUrl,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let url = if url.0.scheme() == ASSETS_PROTOCOL_SCHEME {
+        if !ContentBaseUrlKey.exists(assets) {
+            return Ok(None);
+        }
+        url.to_download_url(assets)?
+    } else {
+        url.clone()
+    };
+    let Some((bundle_url, path)) = split_bundle_url(&url) else {
+        return Ok(None);
+    };
+
+    let mounted = BundleMountsKey
+        .get(assets)
+        .lock()
+        .unwrap()
+        .get(&bundle_url)
+        .cloned();
+    let bundle = match mounted {
+        Some(bundle) => bundle,
+        None => BundleFromUrl(bundle_url.clone()).get(assets).await?,
+    };
+    let file = bundle
+        .file(&path)
+        .with_context(|| format!("There is no file {path:?} in the bundle {bundle_url}"))?;
+    Ok(Some(file.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let (key, _) = BundleSigningKey::generate().unwrap();
+        let files = vec![
+            ("game/ambient.toml".to_string(), b"[package]".to_vec()),
+            ("game/assets/a.bin".to_string(), vec![1, 2, 3]),
+        ];
+        let data = Bundle::write("game", "Game", files, &key).unwrap();
+        let trusted_keys = [key.public_key().to_vec()];
+
+        let bundle = Bundle::parse(data.clone(), &trusted_keys).unwrap();
+        assert_eq!(bundle.main_package(), "game");
+        assert_eq!(bundle.public_key(), key.public_key());
+        assert_eq!(bundle.file("game/assets/a.bin"), Some(&[1, 2, 3][..]));
+        assert_eq!(bundle.file("game/missing"), None);
+
+        let mut tampered = data;
+        let last_content_byte = tampered.len() - PUBLIC_KEY_LEN - SIGNATURE_LEN - 1;
+        tampered[last_content_byte] ^= 1;
+        assert!(matches!(
+            Bundle::parse(tampered, &trusted_keys),
+            Err(BundleError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_bundle_trusted_keys() {
+        let (key, _) = BundleSigningKey::generate().unwrap();
+        let (other_key, _) = BundleSigningKey::generate().unwrap();
+        let files = vec![("game/ambient.toml".to_string(), b"[package]".to_vec())];
+        let data = Bundle::write("game", "Game", files, &key).unwrap();
+
+        assert!(Bundle::parse(data.clone(), &[key.public_key().to_vec()]).is_ok());
+        assert!(matches!(
+            Bundle::parse(data.clone(), &[other_key.public_key().to_vec()]),
+            Err(BundleError::UntrustedKey(_))
+        ));
+        // Without any trusted key, no bundle is trusted
+        assert!(matches!(
+            Bundle::parse(data, &[]),
+            Err(BundleError::UntrustedKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_bundle_url() {
+        let url: AbsAssetUrl = "https://example.com/game.ambient-bundle/game/ambient.toml"
+            .parse()
+            .unwrap();
+        let (bundle_url, path) = split_bundle_url(&url).unwrap();
+        assert_eq!(
+            bundle_url.to_string(),
+            "https://example.com/game.ambient-bundle"
+        );
+        assert_eq!(path, "game/ambient.toml");
+
+        let url: AbsAssetUrl = "https://example.com/game/ambient.toml".parse().unwrap();
+        assert!(split_bundle_url(&url).is_none());
+    }
+}
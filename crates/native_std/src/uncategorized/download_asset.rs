@@ -3,6 +3,7 @@ use std::{marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 use crate::{
     asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
     asset_url::AbsAssetUrl,
+    bundle::read_bundled_file,
     mesh::Mesh,
     RUNTIME_USER_AGENT,
 };
@@ -161,6 +162,9 @@ pub async fn download_uncached_bytes(
     assets: &AssetCache,
     url: AbsAssetUrl,
 ) -> AssetResult<Vec<u8>> {
+    if let Some(content) = read_bundled_file(assets, &url).await? {
+        return Ok(content);
+    }
     if let Some(path) = url.to_file_path()? {
         return Ok(ambient_sys::fs::read(path)
             .await
@@ -237,6 +241,15 @@ impl AsyncAssetKey<AssetResult<Arc<PathBuf>>> for BytesFromUrlCachedPath {
 
         let path = self.url.absolute_cache_path(&assets);
         if !path.exists() {
+            if let Some(content) = read_bundled_file(&assets, &self.url).await? {
+                let dir = path.parent().context("Invalid asset cache path")?;
+                std::fs::create_dir_all(dir)
+                    .context(format!("Failed to create asset dir: {dir:?}"))?;
+                std::fs::write(&path, content)
+                    .context(format!("Failed to write file: {path:?}"))?;
+                return Ok(Arc::new(path));
+            }
+
             use tokio::io::AsyncWriteExt;
             let mut dir = path.clone();
             dir.pop();
@@ -227,4 +227,132 @@ impl Mesh {
         flip_winding(&mut self.indices);
         self
     }
+
+    /// Overwrites the vertices starting at `offset`, extending the mesh if they go past its end.
+    ///
+    /// Attributes which are left empty are kept as they are, and padded with zeroes when the mesh
+    /// is extended.
+    pub fn write_vertices(
+        &mut self,
+        offset: usize,
+        positions: &[Vec3],
+        normals: &[Vec3],
+        tangents: &[Vec3],
+        texcoords: &[Vec2],
+    ) -> anyhow::Result<()> {
+        let count = positions
+            .len()
+            .max(normals.len())
+            .max(tangents.len())
+            .max(texcoords.len());
+        ensure!(
+            [
+                positions.len(),
+                normals.len(),
+                tangents.len(),
+                texcoords.len()
+            ]
+            .iter()
+            .all(|&len| len == 0 || len == count),
+            "All the written attributes must have the same length"
+        );
+        ensure!(
+            offset <= self.positions.len(),
+            "Cannot write vertices at {offset}, past the end of the mesh ({} vertices)",
+            self.positions.len()
+        );
+
+        let len = self.positions.len().max(offset + count);
+        fn write<T: Copy + Default>(dst: &mut Vec<T>, offset: usize, src: &[T], len: usize) {
+            if dst.is_empty() && src.is_empty() {
+                return;
+            }
+            dst.resize(len, T::default());
+            dst[offset..offset + src.len()].copy_from_slice(src);
+        }
+        write(&mut self.positions, offset, positions, len);
+        write(&mut self.normals, offset, normals, len);
+        write(&mut self.tangents, offset, tangents, len);
+        if self.texcoords.is_empty() && !texcoords.is_empty() {
+            self.texcoords.push(Vec::new());
+        }
+        if let Some(set) = self.texcoords.first_mut() {
+            write(set, offset, texcoords, len);
+        }
+        // The other attributes are not written, but still need to cover every vertex
+        for set in self.texcoords.iter_mut().skip(1) {
+            set.resize(len, Vec2::ZERO);
+        }
+        if !self.colors.is_empty() {
+            self.colors.resize(len, Vec4::ZERO);
+        }
+        if !self.joint_indices.is_empty() {
+            self.joint_indices.resize(len, UVec4::ZERO);
+        }
+        if !self.joint_weights.is_empty() {
+            self.joint_weights.resize(len, Vec4::ZERO);
+        }
+
+        self.aabb = AABB::new_invalid();
+        for &position in &self.positions {
+            self.aabb.take_point(position);
+        }
+        Ok(())
+    }
+
+    /// Overwrites the indices starting at `offset`, extending the mesh if they go past its end
+    pub fn write_indices(&mut self, offset: usize, indices: &[u32]) -> anyhow::Result<()> {
+        ensure!(
+            offset <= self.indices.len(),
+            "Cannot write indices at {offset}, past the end of the mesh ({} indices)",
+            self.indices.len()
+        );
+        ensure!(
+            indices.iter().all(|&i| (i as usize) < self.positions.len()),
+            "Indices must refer to one of the {} vertices",
+            self.positions.len()
+        );
+
+        let len = self.indices.len().max(offset + indices.len());
+        self.indices.resize(len, 0);
+        self.indices[offset..offset + indices.len()].copy_from_slice(indices);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_vertices() {
+        let mut mesh = MeshBuilder {
+            positions: vec![Vec3::ZERO, Vec3::X, Vec3::Y],
+            normals: vec![Vec3::Z; 3],
+            indices: vec![0, 1, 2],
+            ..MeshBuilder::default()
+        }
+        .build()
+        .unwrap();
+
+        mesh.write_vertices(2, &[Vec3::ONE, Vec3::NEG_ONE], &[], &[], &[])
+            .unwrap();
+        assert_eq!(
+            mesh.positions(),
+            [Vec3::ZERO, Vec3::X, Vec3::ONE, Vec3::NEG_ONE]
+        );
+        assert_eq!(mesh.normals(), [Vec3::Z, Vec3::Z, Vec3::Z, Vec3::ZERO]);
+        assert!(mesh.tangents().is_empty());
+        assert_eq!(mesh.aabb().min, Vec3::NEG_ONE);
+
+        assert!(mesh.write_vertices(5, &[Vec3::ONE], &[], &[], &[]).is_err());
+        assert!(mesh.write_vertices(0, &[Vec3::ONE], &[], &[], &[]).is_ok());
+        assert!(mesh
+            .write_vertices(0, &[Vec3::ONE], &[Vec3::Z; 2], &[], &[])
+            .is_err());
+
+        mesh.write_indices(3, &[1, 2, 3]).unwrap();
+        assert_eq!(mesh.indices(), [0, 1, 2, 1, 2, 3]);
+        assert!(mesh.write_indices(0, &[4]).is_err());
+    }
 }
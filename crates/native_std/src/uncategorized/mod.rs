@@ -3,6 +3,7 @@ pub use ambient_color as color;
 pub use ambient_math as math;
 pub mod asset_url;
 pub mod barc;
+pub mod bundle;
 pub mod disk_cache;
 pub mod download_asset;
 pub mod encode;
@@ -0,0 +1,231 @@
+//! Authentication of the players connecting to the server.
+//!
+//! A client can present a token along with its user id when it connects. If the server has an
+//! [Authenticator] in its [AuthenticatorKey], the token is verified with it before the player is
+//! spawned, and the identity it vouches for is added to the player entity, where the packages can
+//! use it. Connections it refuses are told why, and closed.
+//!
+//! Without an authenticator, every user id is accepted as is, and no identity is added.
+//!
+//! The user ids in the [BansKey] of the server are refused either way.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ambient_ecs::{
+    generated::player::components::{
+        identity_issuer, identity_name, identity_roles, identity_subject,
+    },
+    Entity, EntityId, World,
+};
+use ambient_native_std::asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_os = "unknown"))]
+mod token;
+#[cfg(not(target_os = "unknown"))]
+pub use token::*;
+
+/// How long past the expiry of a token it is still accepted, to allow for clock drift
+const LEEWAY_SECS: u64 = 60;
+
+/// Verifies the tokens presented by connecting clients
+pub trait Authenticator: Send + Sync + std::fmt::Debug {
+    /// Verifies the `token` presented by the client connecting as `user_id`, and returns the
+    /// identity it vouches for
+    fn authenticate(&self, user_id: &str, token: Option<&str>) -> Result<Identity, AuthError>;
+}
+
+/// The authenticator of the server
+#[derive(Debug, Clone)]
+pub struct AuthenticatorKey;
+impl SyncAssetKey<Arc<dyn Authenticator>> for AuthenticatorKey {}
+
+/// The user ids banned from the server, with the reason they were banned for
+#[derive(Debug, Clone)]
+pub struct BansKey;
+impl SyncAssetKey<Arc<Mutex<HashMap<String, String>>>> for BansKey {
+    fn load(&self, _assets: AssetCache) -> Arc<Mutex<HashMap<String, String>>> {
+        Default::default()
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    #[error("No token was presented")]
+    MissingToken,
+    #[error("Malformed token: {0}")]
+    Malformed(String),
+    #[error("Unsupported token algorithm {0:?}")]
+    UnsupportedAlgorithm(String),
+    #[error("Invalid token signature")]
+    InvalidSignature,
+    #[error("The token has expired")]
+    Expired,
+    #[error("The token is not valid yet")]
+    NotYetValid,
+    #[error("The token was issued by {0:?}, which is not trusted")]
+    UntrustedIssuer(Option<String>),
+    #[error("The token is not intended for this server")]
+    WrongAudience,
+    #[error("The token was issued to {subject:?}, but the client connected as {user_id:?}")]
+    SubjectMismatch { subject: String, user_id: String },
+    #[error("{0}")]
+    Rejected(String),
+    #[error("Banned from the server: {0}")]
+    Banned(String),
+}
+
+/// The identity of a player, as verified by the [Authenticator] of the server
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Identity {
+    /// Who the player is. The same as their user id
+    pub subject: String,
+    pub name: Option<String>,
+    /// Who vouched for the player
+    pub issuer: Option<String>,
+    pub roles: Vec<String>,
+}
+
+impl Identity {
+    /// The components of the identity on the player entity
+    pub fn to_entity(&self) -> Entity {
+        let mut entity = Entity::new()
+            .with(identity_subject(), self.subject.clone())
+            .with(identity_roles(), self.roles.clone());
+        if let Some(name) = &self.name {
+            entity.set(identity_name(), name.clone());
+        }
+        if let Some(issuer) = &self.issuer {
+            entity.set(identity_issuer(), issuer.clone());
+        }
+        entity
+    }
+
+    /// Reads the identity from the components of a player entity
+    pub fn from_entity(entity: &Entity) -> Option<Self> {
+        Some(Self {
+            subject: entity.get_cloned(identity_subject())?,
+            name: entity.get_cloned(identity_name()),
+            issuer: entity.get_cloned(identity_issuer()),
+            roles: entity.get_cloned(identity_roles()).unwrap_or_default(),
+        })
+    }
+
+    /// Removes the components of an identity from the player entity `id`
+    pub fn remove_from(world: &mut World, id: EntityId) {
+        let _ = world.remove_components(
+            id,
+            vec![
+                identity_subject().desc(),
+                identity_name().desc(),
+                identity_issuer().desc(),
+                identity_roles().desc(),
+            ],
+        );
+    }
+}
+
+/// The claims of a token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TokenClaims {
+    /// The user id of the player
+    pub sub: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Audience>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+    /// Seconds since the unix epoch after which the token is no longer valid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    /// Seconds since the unix epoch before which the token is not valid yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Audience::One(aud) => aud == audience,
+            Audience::Many(auds) => auds.iter().any(|aud| aud == audience),
+        }
+    }
+}
+
+/// The requirements a token must meet besides its signature
+#[derive(Debug, Clone, Default)]
+pub struct Validation {
+    /// Only accept tokens issued by this issuer
+    pub issuer: Option<String>,
+    /// Only accept tokens intended for this audience
+    pub audience: Option<String>,
+}
+
+impl Validation {
+    /// Checks `claims` against the requirements, the current time, and the user id the client
+    /// connected as, and returns the identity they vouch for
+    pub fn validate(&self, user_id: &str, claims: TokenClaims) -> Result<Identity, AuthError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if claims.exp.is_some_and(|exp| now > exp + LEEWAY_SECS) {
+            return Err(AuthError::Expired);
+        }
+        if claims.nbf.is_some_and(|nbf| now + LEEWAY_SECS < nbf) {
+            return Err(AuthError::NotYetValid);
+        }
+        if self.issuer.is_some() && claims.iss != self.issuer {
+            return Err(AuthError::UntrustedIssuer(claims.iss));
+        }
+        if let Some(audience) = &self.audience {
+            if !claims.aud.is_some_and(|aud| aud.contains(audience)) {
+                return Err(AuthError::WrongAudience);
+            }
+        }
+        if claims.sub != user_id {
+            return Err(AuthError::SubjectMismatch {
+                subject: claims.sub,
+                user_id: user_id.to_owned(),
+            });
+        }
+
+        Ok(Identity {
+            subject: claims.sub,
+            name: claims.name,
+            issuer: claims.iss,
+            roles: claims.roles,
+        })
+    }
+}
+
+/// Authenticates the client connecting as `user_id` with the authenticator of the server, if any
+pub(crate) fn authenticate(
+    assets: &AssetCache,
+    user_id: &str,
+    token: Option<&str>,
+) -> Result<Option<Identity>, AuthError> {
+    if let Some(reason) = BansKey.get(assets).lock().get(user_id) {
+        return Err(AuthError::Banned(reason.clone()));
+    }
+
+    match AuthenticatorKey.try_get(assets) {
+        Some(authenticator) => authenticator.authenticate(user_id, token).map(Some),
+        None => Ok(None),
+    }
+}
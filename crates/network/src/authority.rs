@@ -0,0 +1,232 @@
+//! Client authority over entities.
+//!
+//! The client of the player named by an entity's `authority` sends the changes it makes to the
+//! networked components of that entity to the server, which applies them and replicates them to
+//! the other players. For as long as the player has authority, the server ignores the changes
+//! other clients send for the entity, and the owner ignores the changes the server sends it.
+//!
+//! Client to server: `[sequence: u32][changes: Vec<(EntityId, Entity)>]`, over a unistream.
+//!
+//! Each payload holds the latest values of the components changed since the previous one, and
+//! the unistreams may arrive out of order. The server keeps the sequence of the last value it
+//! applied for each component, so that a late payload only applies the components that haven't
+//! been changed by a newer one since.
+
+use std::{collections::HashMap, sync::Arc};
+
+use ambient_core::{
+    player::{get_by_user_id, local_user_id},
+    runtime,
+};
+use ambient_ecs::{
+    components,
+    generated::network::components::{authority, is_remote_entity},
+    query, ComponentDesc, DynSystem, Entity, EntityId, FnSystem, Networked, SystemGroup, World,
+    WorldChange,
+};
+use ambient_native_std::asset_cache::AssetCache;
+use anyhow::Context;
+use bytes::{BufMut, Bytes, BytesMut};
+use parking_lot::Mutex;
+use tokio::io::AsyncReadExt;
+
+use crate::{
+    bytes_ext::BufExt,
+    client::client_state,
+    log_network_result,
+    server::{self, SharedServerState},
+    unwrap_log_network_err, DynRecv, AUTHORITY_UNISTREAM_ID,
+};
+
+components!("network::authority", {
+    /// The sequence of the changes the last value of each component of the entities the player has
+    /// authority over was applied from, so that older values arriving late are ignored
+    player_authority_sequences: HashMap<(EntityId, ComponentDesc), u32>,
+});
+
+const MAX_CHANGES_LENGTH: u64 = 16 * 1024 * 1024;
+
+/// Applies the changes the clients send for the entities they have authority over
+pub fn register_server_uni_stream_handler(handlers: &mut server::UniStreamHandlers) {
+    handlers.insert(
+        AUTHORITY_UNISTREAM_ID,
+        ("server_authority_uni_stream", Arc::new(on_server_unistream)),
+    );
+}
+
+fn on_server_unistream(
+    state: SharedServerState,
+    _assets: AssetCache,
+    user_id: &str,
+    mut recv: DynRecv,
+) {
+    let runtime = {
+        let mut state = state.lock();
+        let Some(world) = state.get_player_world_mut(user_id) else {
+            tracing::warn!("Failed to find player world for {user_id} when processing authority");
+            return;
+        };
+        world.resource(runtime()).clone()
+    };
+
+    let user_id = user_id.to_owned();
+    runtime.spawn(async move {
+        let mut data = Vec::new();
+        unwrap_log_network_err!(recv
+            .as_mut()
+            .take(MAX_CHANGES_LENGTH)
+            .read_to_end(&mut data)
+            .await
+            .context("Failed to read authority unistream"));
+
+        let mut state = state.lock();
+        let Some(world) = state.get_player_world_mut(&user_id) else {
+            return;
+        };
+        log_network_result!(apply_changes(world, &user_id, data.into()));
+    });
+}
+
+fn apply_changes(world: &mut World, user_id: &str, mut data: Bytes) -> anyhow::Result<()> {
+    let sequence = data.try_get_u32()?;
+    let changes: Vec<(EntityId, Entity)> = bincode::deserialize(&data)?;
+
+    let player = get_by_user_id(world, user_id).context("No player entity")?;
+    let mut sequences = world
+        .get_mut(player, player_authority_sequences())
+        .map(std::mem::take)
+        .unwrap_or_default();
+    let owns = |id| {
+        world
+            .get_ref(id, authority())
+            .is_ok_and(|owner| owner == user_id)
+    };
+    sequences.retain(|(id, _), _| owns(*id));
+
+    let mut applied = Vec::new();
+    for (id, mut entity) in changes {
+        if !owns(id) {
+            continue;
+        }
+        entity.filter(&|desc| {
+            is_sent(desc)
+                && world.has_component(id, desc)
+                && sequences
+                    .get(&(id, desc))
+                    .map_or(true, |last| *last < sequence)
+        });
+        for desc in entity.components() {
+            sequences.insert((id, desc), sequence);
+        }
+        if !entity.is_empty() {
+            applied.push((id, entity));
+        }
+    }
+    world.add_component(player, player_authority_sequences(), sequences)?;
+
+    for (id, entity) in applied {
+        world.set_components(id, entity)?;
+    }
+    Ok(())
+}
+
+/// Whether the changes to `desc` are sent by the owner of an entity
+fn is_sent(desc: ComponentDesc) -> bool {
+    desc.has_attribute::<Networked>()
+        && desc != authority().desc()
+        && desc != is_remote_entity().desc()
+}
+
+/// Whether the local player has authority over `id`
+pub fn has_local_authority(world: &World, id: EntityId) -> bool {
+    match (
+        world.resource_opt(local_user_id()),
+        world.get_ref(id, authority()),
+    ) {
+        (Some(user_id), Ok(owner)) => user_id == owner,
+        _ => false,
+    }
+}
+
+/// Removes the changes the server has made to the entities the local player has authority over
+/// from `changes`, except for `authority` itself, so that they don't undo the player's own
+pub(crate) fn strip_owned(world: &World, changes: &mut Vec<WorldChange>) {
+    changes.retain_mut(|change| match change {
+        WorldChange::SetComponents(id, entity) if has_local_authority(world, *id) => {
+            entity.filter(&|desc| desc == authority().desc());
+            !entity.is_empty()
+        }
+        _ => true,
+    });
+}
+
+/// Sends the changes made to the entities the local player has authority over to the server
+pub fn client_systems() -> SystemGroup {
+    SystemGroup::new("network/authority", vec![send_owned_changes_system()])
+}
+
+#[derive(Default)]
+struct SentChanges {
+    sequence: u32,
+    /// The content versions of the components of every owned entity when they were last sent
+    versions: HashMap<EntityId, HashMap<ComponentDesc, u64>>,
+}
+
+fn send_owned_changes_system() -> DynSystem {
+    let sent = Mutex::new(SentChanges::default());
+    Box::new(FnSystem::new(move |world, _| {
+        let mut sent = sent.lock();
+        let Some(user_id) = world.resource_opt(local_user_id()) else {
+            return;
+        };
+        let owned = query(authority())
+            .iter(world, None)
+            .filter(|(_, owner)| *owner == user_id)
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        sent.versions.retain(|id, _| owned.contains(id));
+
+        let mut changes = Vec::new();
+        for id in owned {
+            let versions = sent.versions.entry(id).or_default();
+            let mut entity = Entity::new();
+            for desc in world.get_components(id).unwrap_or_default() {
+                if !is_sent(desc) {
+                    continue;
+                }
+                let Ok(version) = world.get_component_content_version(id, desc.index()) else {
+                    continue;
+                };
+                if versions.insert(desc, version) != Some(version) {
+                    entity.set_entry(world.get_entry(id, desc).unwrap());
+                }
+            }
+            if !entity.is_empty() {
+                changes.push((id, entity));
+            }
+        }
+        if changes.is_empty() {
+            return;
+        }
+
+        let Some(transport) = world
+            .resource_opt(client_state())
+            .and_then(|state| state.as_ref())
+            .map(|state| state.transport.clone())
+        else {
+            return;
+        };
+        sent.sequence = sent.sequence.wrapping_add(1);
+        let mut payload = BytesMut::new();
+        payload.put_u32(sent.sequence);
+        payload.extend_from_slice(&bincode::serialize(&changes).unwrap());
+
+        world.resource(runtime()).spawn(async move {
+            log_network_result!(
+                transport
+                    .request_uni(AUTHORITY_UNISTREAM_ID, payload.freeze())
+                    .await
+            );
+        });
+    }))
+}
@@ -0,0 +1,195 @@
+use std::{
+    sync::{
+        atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use ambient_ecs::{
+    components,
+    generated::network::components::{
+        bandwidth_budget, bandwidth_in, bandwidth_out, packet_loss, round_trip_time,
+        send_queue_depth,
+    },
+    query, Component, ComponentDesc, ComponentValue, EntityId, FnSystem, Replicated,
+    ReplicationPriority, SystemGroup, World,
+};
+use ambient_sys::time::Instant;
+
+use crate::{
+    client::TransportStats,
+    server::{player_entity_stream, player_transport},
+};
+
+components!("network::bandwidth", {
+    /// The budget the diffs sent to the player are held to, shared with its connection
+    player_send_budget: Arc<SendBudget>,
+    /// The statistics of the player's connection when its bandwidth was last measured
+    player_bandwidth_sample: BandwidthSample,
+});
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The send budget of a connection, set from `bandwidth_budget` on the server and spent by the
+/// task sending the diffs to the player
+#[derive(Debug, Default)]
+pub struct SendBudget {
+    /// Bytes per second, or zero for no limit
+    rate: AtomicU32,
+    /// The bytes of diffs sent so far
+    sent: AtomicU64,
+    /// The entities whose changes are currently held back
+    held: AtomicUsize,
+}
+impl SendBudget {
+    pub(crate) fn set_held(&self, held: usize) {
+        self.held.store(held, Ordering::Relaxed);
+    }
+}
+
+/// A token bucket holding a connection to its [SendBudget], with a burst of up to one second
+/// of the budget
+#[derive(Debug)]
+pub(crate) struct SendAllowance {
+    budget: Arc<SendBudget>,
+    tokens: f32,
+    refilled: Instant,
+}
+impl SendAllowance {
+    pub fn new(budget: Arc<SendBudget>) -> Self {
+        Self {
+            budget,
+            tokens: 0.0,
+            refilled: Instant::now(),
+        }
+    }
+
+    /// The lowest priority of the changes that may be sent now. High priority changes are
+    /// always sent, even when the budget is spent.
+    pub fn admitted(&mut self) -> ReplicationPriority {
+        let rate = self.budget.rate.load(Ordering::Relaxed) as f32;
+        if rate == 0.0 {
+            return ReplicationPriority::Low;
+        }
+
+        let now = Instant::now();
+        self.tokens =
+            (self.tokens + now.duration_since(self.refilled).as_secs_f32() * rate).min(rate);
+        self.refilled = now;
+
+        if self.tokens >= rate / 2.0 {
+            ReplicationPriority::Low
+        } else if self.tokens > 0.0 {
+            ReplicationPriority::Normal
+        } else {
+            ReplicationPriority::High
+        }
+    }
+
+    pub fn spend(&mut self, bytes: usize) {
+        self.budget.sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        if self.budget.rate.load(Ordering::Relaxed) > 0 {
+            self.tokens -= bytes as f32;
+        }
+    }
+}
+
+/// The priority changes to `desc` are sent with when the budget is spent
+pub(crate) fn priority(desc: ComponentDesc) -> ReplicationPriority {
+    desc.attribute::<Replicated>()
+        .map_or(ReplicationPriority::Normal, |r| r.priority)
+}
+
+#[derive(Debug, Clone)]
+pub struct BandwidthSample {
+    time: Instant,
+    transport: Option<TransportStats>,
+    sent: u64,
+}
+
+/// Passes `bandwidth_budget` on to the connections of the players, and keeps the bandwidth
+/// statistics on the player entities up to date
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "network/bandwidth",
+        vec![
+            query(player_send_budget()).to_system(|q, world, qs, _| {
+                let default = world.resource_opt(bandwidth_budget()).copied();
+                for (id, budget) in q.collect_cloned(world, qs) {
+                    let rate = world.get(id, bandwidth_budget()).ok().or(default);
+                    budget
+                        .rate
+                        .store(rate.unwrap_or_default(), Ordering::Relaxed);
+
+                    let queued = world
+                        .get_ref(id, player_entity_stream())
+                        .map_or(0, |stream| stream.len())
+                        + budget.held.load(Ordering::Relaxed);
+                    set(world, id, send_queue_depth(), queued as u32);
+                }
+            }),
+            Box::new(FnSystem::new(|world, _| {
+                let now = Instant::now();
+                let players = query((player_transport(), player_send_budget()))
+                    .iter(world, None)
+                    .map(|(id, (transport, budget))| {
+                        let sample = BandwidthSample {
+                            time: now,
+                            transport: transport.stats(),
+                            sent: budget.sent.load(Ordering::Relaxed),
+                        };
+                        (id, sample)
+                    })
+                    .collect::<Vec<_>>();
+
+                for (id, sample) in players {
+                    let Ok(last) = world.get_ref(id, player_bandwidth_sample()) else {
+                        world
+                            .add_component(id, player_bandwidth_sample(), sample)
+                            .unwrap();
+                        continue;
+                    };
+                    let elapsed = sample.time.duration_since(last.time);
+                    if elapsed < SAMPLE_INTERVAL {
+                        continue;
+                    }
+                    let last = last.clone();
+                    let per_second = |bytes: u64| bytes as f32 / elapsed.as_secs_f32();
+
+                    match (&last.transport, &sample.transport) {
+                        (Some(last), Some(stats)) => {
+                            let received = stats.bytes_received.saturating_sub(last.bytes_received);
+                            let sent = stats.bytes_sent.saturating_sub(last.bytes_sent);
+                            let packets = stats.packets_sent.saturating_sub(last.packets_sent);
+                            let lost = stats.packets_lost.saturating_sub(last.packets_lost);
+                            set(world, id, bandwidth_in(), per_second(received));
+                            set(world, id, bandwidth_out(), per_second(sent));
+                            set(world, id, round_trip_time(), stats.rtt);
+                            if packets > 0 {
+                                set(world, id, packet_loss(), lost as f32 / packets as f32);
+                            }
+                        }
+                        // Without statistics from the transport, only the diffs are counted
+                        _ => {
+                            let sent = sample.sent.saturating_sub(last.sent);
+                            set(world, id, bandwidth_out(), per_second(sent));
+                        }
+                    }
+                    world.set(id, player_bandwidth_sample(), sample).unwrap();
+                }
+            })),
+        ],
+    )
+}
+
+fn set<T: ComponentValue + PartialEq>(
+    world: &mut World,
+    id: EntityId,
+    component: Component<T>,
+    value: T,
+) {
+    if world.set_if_changed(id, component, value.clone()).is_err() {
+        world.add_component(id, component, value).unwrap();
+    }
+}
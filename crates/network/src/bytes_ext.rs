@@ -29,7 +29,7 @@ pub trait BufExt: Buf {
         self.try_get()
     }
 
-    fn try_get_u128(&mut self) -> Result<u64, UnexpectedEnd> {
+    fn try_get_u128(&mut self) -> Result<u128, UnexpectedEnd> {
         self.try_get()
     }
 
@@ -60,5 +60,6 @@ impl_decode_u!(u8, 1, get_u8);
 impl_decode_u!(u16, 2, get_u16);
 impl_decode_u!(u32, 4, get_u32);
 impl_decode_u!(u64, 8, get_u64);
+impl_decode_u!(u128, 16, get_u128);
 
 impl<B> BufExt for B where B: Buf {}
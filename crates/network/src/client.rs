@@ -17,6 +17,7 @@ use std::{
     fmt::{Debug, Display},
     future::Future,
     sync::Arc,
+    time::Duration,
 };
 
 use crate::{
@@ -44,9 +45,9 @@ pub type PlatformSendStream = quinn::SendStream;
 pub type PlatformRecvStream = quinn::RecvStream;
 
 #[cfg(target_os = "unknown")]
-pub type PlatformSendStream = crate::webtransport::SendStream;
+pub type PlatformSendStream = crate::web::connection::SendStream;
 #[cfg(target_os = "unknown")]
-pub type PlatformRecvStream = crate::webtransport::RecvStream;
+pub type PlatformRecvStream = crate::web::connection::RecvStream;
 
 type BiStreamHandler = Arc<
     dyn Fn(
@@ -78,6 +79,20 @@ pub trait NetworkTransport: 'static + Send + Sync {
     /// Performs a unidirectional request without waiting for a response.
     fn request_uni(&self, id: u32, data: Bytes) -> BoxFuture<Result<(), NetworkError>>;
     fn send_datagram(&self, id: u32, data: Bytes) -> BoxFuture<Result<(), NetworkError>>;
+    /// Returns the statistics of the underlying connection, if the transport keeps them.
+    fn stats(&self) -> Option<TransportStats> {
+        None
+    }
+}
+
+/// The totals of a connection since it was opened
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransportStats {
+    pub rtt: Duration,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_lost: u64,
 }
 
 pub(crate) enum Control {
@@ -68,9 +68,13 @@ impl ClientGameState {
         let systems = SystemGroup::new(
             "game",
             vec![
+                Box::new(crate::interpolation::interpolation_systems()),
                 Box::new(client_systems),
+                Box::new(crate::authority::client_systems()),
                 Box::new(world_instance_systems(true)),
                 Box::new(audio_systems()),
+                #[cfg(not(target_os = "unknown"))]
+                Box::new(crate::voice::voice_systems()),
             ],
         );
         let mut renderer = Renderer::new(
@@ -79,6 +83,8 @@ impl ClientGameState {
             RendererConfig {
                 scene: main_scene(),
                 shadows: true,
+                planar_reflections: true,
+                frame_stats: true,
                 ..Default::default()
             },
         );
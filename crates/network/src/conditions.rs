@@ -0,0 +1,153 @@
+//! Simulation of poor network conditions, for development.
+//!
+//! The `simulated_*` resources of a world degrade what its side of the connection receives:
+//! messages, datagrams and, on the client, the diffs of the world are held back by the simulated
+//! latency and jitter, and datagrams are dropped or duplicated at random. As they are resources,
+//! the conditions can be changed at any time, from a module or the debugger.
+
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use ambient_ecs::{
+    generated::network::components::{
+        simulated_duplication, simulated_jitter, simulated_latency, simulated_packet_loss,
+    },
+    World,
+};
+use ambient_sys::time::{sleep_label, sleep_until_label, Instant, Sleep};
+use futures::{ready, stream::Fuse, Future, Stream, StreamExt};
+use pin_project::pin_project;
+
+/// The network conditions simulated by one side of the connection
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetworkConditions {
+    pub latency: Duration,
+    pub jitter: Duration,
+    pub loss: f32,
+    pub duplication: f32,
+}
+
+impl NetworkConditions {
+    /// The conditions simulated by `world`, if any
+    pub fn of(world: &World) -> Option<Self> {
+        let conditions = Self {
+            latency: resource(world, simulated_latency()),
+            jitter: resource(world, simulated_jitter()),
+            loss: resource(world, simulated_packet_loss()),
+            duplication: resource(world, simulated_duplication()),
+        };
+        (conditions != Self::default()).then_some(conditions)
+    }
+
+    /// A delay of the latency, plus up to the jitter
+    pub fn delay(&self) -> Duration {
+        self.latency + self.jitter.mul_f32(rand::random())
+    }
+
+    /// The number of copies of a datagram which arrive: none when it is lost, and two when it is
+    /// duplicated
+    pub fn datagram_copies(&self) -> usize {
+        if rand::random::<f32>() < self.loss {
+            0
+        } else if rand::random::<f32>() < self.duplication {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+fn resource<T: Copy + Default + ambient_ecs::ComponentValue>(
+    world: &World,
+    component: ambient_ecs::Component<T>,
+) -> T {
+    world.resource_opt(component).copied().unwrap_or_default()
+}
+
+/// Waits for the delay of `conditions`, if any
+pub(crate) async fn hold_back(conditions: Option<NetworkConditions>) {
+    if let Some(conditions) = conditions {
+        sleep_label(conditions.delay(), "simulated_latency").await;
+    }
+}
+
+/// Holds back each item of a stream by a delay sampled when it arrives, keeping them in order
+#[pin_project]
+pub struct Delayed<S: Stream, F> {
+    #[pin]
+    inner: Fuse<S>,
+    delay: F,
+    queue: VecDeque<(Instant, S::Item)>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S: Stream, F: FnMut() -> Duration> Delayed<S, F> {
+    pub fn new(inner: S, delay: F) -> Self {
+        Self {
+            inner: inner.fuse(),
+            delay,
+            queue: VecDeque::new(),
+            sleep: None,
+        }
+    }
+}
+
+impl<S: Stream, F: FnMut() -> Duration> Stream for Delayed<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        while let Poll::Ready(Some(item)) = this.inner.as_mut().poll_next(cx) {
+            let now = Instant::now();
+            let last = this.queue.back().map_or(now, |&(release, _)| release);
+            this.queue
+                .push_back(((now + (this.delay)()).max(last), item));
+        }
+
+        let Some(&(release, _)) = this.queue.front() else {
+            return if this.inner.is_done() {
+                Poll::Ready(None)
+            } else {
+                Poll::Pending
+            };
+        };
+
+        if release > Instant::now() {
+            let sleep = this
+                .sleep
+                .get_or_insert_with(|| Box::pin(sleep_until_label(release, "simulated_latency")));
+            if sleep.deadline() != release {
+                sleep.as_mut().reset(release);
+            }
+            ready!(sleep.as_mut().poll(cx));
+        }
+
+        *this.sleep = None;
+        Poll::Ready(this.queue.pop_front().map(|(_, item)| item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn delayed_items_stay_in_order() {
+        let mut delays = [30, 0, 10].into_iter().map(Duration::from_millis);
+        let start = Instant::now();
+
+        let items = Delayed::new(stream::iter([1, 2, 3]), move || delays.next().unwrap())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(items, [1, 2, 3]);
+        assert!(Instant::now().duration_since(start) >= Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,279 @@
+//! Delta compression of `SetComponents` changes, see `DiffSerializer`.
+use std::{borrow::Cow, collections::HashMap};
+
+use ambient_ecs::{ComponentDesc, ComponentEntry, Entity, EntityId, Replicated, WorldChange};
+use glam::{Quat, Vec2, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+
+/// The value of a component made of floats, which can be sent as a change from a previous value
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Lanes {
+    F32(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Quat(Quat),
+}
+impl Lanes {
+    fn of(entry: &ComponentEntry) -> Option<Self> {
+        entry
+            .try_downcast_ref::<f32>()
+            .map(|&v| Self::F32(v))
+            .or_else(|| entry.try_downcast_ref::<Vec2>().map(|&v| Self::Vec2(v)))
+            .or_else(|| entry.try_downcast_ref::<Vec3>().map(|&v| Self::Vec3(v)))
+            .or_else(|| entry.try_downcast_ref::<Vec4>().map(|&v| Self::Vec4(v)))
+            .or_else(|| entry.try_downcast_ref::<Quat>().map(|&v| Self::Quat(v)))
+    }
+
+    fn as_slice(&self) -> &[f32] {
+        match self {
+            Self::F32(v) => std::slice::from_ref(v),
+            Self::Vec2(v) => &v.as_ref()[..],
+            Self::Vec3(v) => &v.as_ref()[..],
+            Self::Vec4(v) => &v.as_ref()[..],
+            Self::Quat(v) => &v.as_ref()[..],
+        }
+    }
+
+    /// Returns a value of the same type with the lanes returned by `f`
+    fn map(&self, mut f: impl FnMut(usize, f32) -> f32) -> Self {
+        let mut lanes = [0.0; 4];
+        for (i, &v) in self.as_slice().iter().enumerate() {
+            lanes[i] = f(i, v);
+        }
+        let [x, y, z, w] = lanes;
+        match self {
+            Self::F32(_) => Self::F32(x),
+            Self::Vec2(_) => Self::Vec2(Vec2::new(x, y)),
+            Self::Vec3(_) => Self::Vec3(Vec3::new(x, y, z)),
+            Self::Vec4(_) => Self::Vec4(Vec4::new(x, y, z, w)),
+            Self::Quat(_) => Self::Quat(Quat::from_xyzw(x, y, z, w)),
+        }
+    }
+
+    fn into_entry(self, desc: ComponentDesc) -> ComponentEntry {
+        match self {
+            Self::F32(v) => ComponentEntry::from_raw_parts(desc, v),
+            Self::Vec2(v) => ComponentEntry::from_raw_parts(desc, v),
+            Self::Vec3(v) => ComponentEntry::from_raw_parts(desc, v),
+            Self::Vec4(v) => ComponentEntry::from_raw_parts(desc, v),
+            Self::Quat(v) => ComponentEntry::from_raw_parts(desc, v),
+        }
+    }
+}
+
+/// The change of a component from the value the other side last received
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) enum ComponentDelta {
+    /// The exact values of the lanes in `mask` which changed
+    Lanes { mask: u8, values: Vec<f32> },
+    /// The change of every lane, in steps of the component's quantization
+    Quantized(Vec<i32>),
+}
+impl ComponentDelta {
+    /// Returns the delta from `base` to `value`, or `None` if nothing changed, along with the
+    /// value the other side will have once it is applied
+    fn encode(base: Lanes, value: Lanes, quantization: Option<f32>) -> (Option<Self>, Lanes) {
+        match quantization {
+            Some(step) => {
+                let steps = value
+                    .as_slice()
+                    .iter()
+                    .zip(base.as_slice())
+                    .map(|(&v, &b)| quantize(v, step).wrapping_sub(quantize(b, step)))
+                    .collect::<Vec<_>>();
+                if steps.iter().all(|&s| s == 0) {
+                    return (None, base);
+                }
+                let delta = Self::Quantized(steps);
+                let value = delta.apply(base, quantization).unwrap();
+                (Some(delta), value)
+            }
+            None => {
+                let mut mask = 0;
+                let mut values = Vec::new();
+                for (i, (&v, &b)) in value.as_slice().iter().zip(base.as_slice()).enumerate() {
+                    if v.to_bits() != b.to_bits() {
+                        mask |= 1 << i;
+                        values.push(v);
+                    }
+                }
+                let delta = (mask != 0).then_some(Self::Lanes { mask, values });
+                (delta, value)
+            }
+        }
+    }
+
+    fn apply(&self, base: Lanes, quantization: Option<f32>) -> Option<Lanes> {
+        match (self, quantization) {
+            (Self::Lanes { mask, values }, _) => {
+                let mut values = values.iter();
+                let value = base.map(|i, b| match mask & (1 << i) {
+                    0 => b,
+                    _ => values.next().copied().unwrap_or(b),
+                });
+                Some(value)
+            }
+            (Self::Quantized(steps), Some(step)) => {
+                if steps.len() != base.as_slice().len() {
+                    return None;
+                }
+                Some(base.map(|i, b| quantize(b, step).wrapping_add(steps[i]) as f32 * step))
+            }
+            (Self::Quantized(_), None) => None,
+        }
+    }
+}
+
+fn quantize(value: f32, step: f32) -> i32 {
+    (value / step).round() as i32
+}
+
+fn quantization(desc: ComponentDesc) -> Option<f32> {
+    desc.attribute::<Replicated>()
+        .and_then(|r| r.quantization)
+        .filter(|&step| step > 0.0)
+}
+
+/// A change as it is sent, which either carries the full values of the components, or only how
+/// they changed
+#[derive(Clone, Debug)]
+pub(crate) enum EncodedChange<'a> {
+    Change(Cow<'a, WorldChange>),
+    Deltas(EntityId, Vec<(u32, ComponentDelta)>),
+}
+
+/// The float components of every entity, as the other side of the stream last received them.
+///
+/// The diffs are sent over a reliable and ordered stream, so everything the sender has sent is
+/// known to have been received before the next diff is, which makes it the acknowledged state to
+/// send deltas against.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DeltaBaselines {
+    entities: HashMap<EntityId, HashMap<u32, Lanes>>,
+}
+impl DeltaBaselines {
+    fn baseline(&self, id: EntityId, index: u32) -> Option<Lanes> {
+        self.entities.get(&id)?.get(&index).copied()
+    }
+
+    /// Records the full values in `change`
+    fn observe(&mut self, change: &WorldChange) {
+        match change {
+            WorldChange::Spawn(id, entity)
+            | WorldChange::AddComponents(id, entity)
+            | WorldChange::SetComponents(id, entity) => {
+                let lanes = entity
+                    .iter()
+                    .filter_map(|entry| Some((entry.desc().index(), Lanes::of(entry)?)))
+                    .collect::<Vec<_>>();
+                if !lanes.is_empty() {
+                    self.entities.entry(*id).or_default().extend(lanes);
+                }
+            }
+            WorldChange::Despawn(id) => {
+                self.entities.remove(id);
+            }
+            WorldChange::RemoveComponents(id, descs) => {
+                if let Some(baselines) = self.entities.get_mut(id) {
+                    for desc in descs {
+                        baselines.remove(&desc.index());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces the float components of `SetComponents` changes that the other side has a
+    /// baseline of with their deltas
+    pub fn encode<'a>(&mut self, changes: &'a [WorldChange]) -> Vec<EncodedChange<'a>> {
+        let mut encoded = Vec::with_capacity(changes.len());
+        for change in changes {
+            let WorldChange::SetComponents(id, entity) = change else {
+                self.observe(change);
+                encoded.push(EncodedChange::Change(Cow::Borrowed(change)));
+                continue;
+            };
+
+            let mut full = Entity::new();
+            let mut deltas = Vec::new();
+            for entry in entity.iter() {
+                let index = entry.desc().index();
+                let Some(value) = Lanes::of(entry) else {
+                    full.set_entry(entry.clone());
+                    continue;
+                };
+                let base = self
+                    .baseline(*id, index)
+                    .filter(|base| std::mem::discriminant(base) == std::mem::discriminant(&value));
+                let value = match base {
+                    Some(base) => {
+                        let (delta, value) =
+                            ComponentDelta::encode(base, value, quantization(entry.desc()));
+                        deltas.extend(delta.map(|delta| (index, delta)));
+                        value
+                    }
+                    None => {
+                        full.set_entry(entry.clone());
+                        value
+                    }
+                };
+                self.entities.entry(*id).or_default().insert(index, value);
+            }
+
+            if full.len() == entity.len() {
+                encoded.push(EncodedChange::Change(Cow::Borrowed(change)));
+                continue;
+            }
+            if !full.is_empty() {
+                encoded.push(EncodedChange::Change(Cow::Owned(
+                    WorldChange::SetComponents(*id, full),
+                )));
+            }
+            if !deltas.is_empty() {
+                encoded.push(EncodedChange::Deltas(*id, deltas));
+            }
+        }
+        encoded
+    }
+
+    /// Turns received changes back into a `WorldChange` each, applying the deltas to the
+    /// baselines
+    pub fn decode(
+        &mut self,
+        changes: Vec<DecodedChange>,
+        component: impl Fn(u32) -> Result<ComponentDesc, String>,
+    ) -> Result<Vec<WorldChange>, String> {
+        changes
+            .into_iter()
+            .map(|change| match change {
+                DecodedChange::Change(change) => {
+                    self.observe(&change);
+                    Ok(change)
+                }
+                DecodedChange::Deltas(id, deltas) => {
+                    let mut entity = Entity::new();
+                    for (index, delta) in deltas {
+                        let desc = component(index)?;
+                        let value = self
+                            .baseline(id, index)
+                            .and_then(|base| delta.apply(base, quantization(desc)))
+                            .ok_or_else(|| {
+                                format!("No baseline to apply the delta of {desc:?} on {id} to")
+                            })?;
+                        self.entities.entry(id).or_default().insert(index, value);
+                        entity.set_entry(value.into_entry(desc));
+                    }
+                    Ok(WorldChange::SetComponents(id, entity))
+                }
+            })
+            .collect()
+    }
+}
+
+/// A received change, whose deltas haven't been applied yet
+#[derive(Debug)]
+pub(crate) enum DecodedChange {
+    Change(WorldChange),
+    Deltas(EntityId, Vec<(u32, ComponentDelta)>),
+}
@@ -1,6 +1,7 @@
 //! Utilities for `WorldDiff` serialization like `WorldDiffDeduplicator` or `DiffSerializer`.
 use std::collections::HashMap;
 
+use crate::delta::{ComponentDelta, DecodedChange, DeltaBaselines, EncodedChange};
 use ambient_ecs::{
     with_component_registry, ComponentDesc, ComponentEntry, ComponentRegistry, Entity, EntityId,
     External, ExternalComponentDesc, Serializable, WorldChange, WorldDiff,
@@ -91,6 +92,7 @@ enum WorldChangeTag {
     AddComponents = 2,
     RemoveComponents = 3,
     SetComponents = 4,
+    SetComponentDeltas = 5,
 }
 impl TryFrom<u8> for WorldChangeTag {
     type Error = ();
@@ -102,6 +104,7 @@ impl TryFrom<u8> for WorldChangeTag {
             2 => Ok(Self::AddComponents),
             3 => Ok(Self::RemoveComponents),
             4 => Ok(Self::SetComponents),
+            5 => Ok(Self::SetComponentDeltas),
             _ => Err(()),
         }
     }
@@ -125,6 +128,7 @@ impl<'a> From<&NetworkedWorldChange<'a>> for WorldChangeTag {
             NetworkedWorldChange::AddComponents(_, _) => WorldChangeTag::AddComponents,
             NetworkedWorldChange::RemoveComponents(_, _) => WorldChangeTag::RemoveComponents,
             NetworkedWorldChange::SetComponents(_, _) => WorldChangeTag::SetComponents,
+            NetworkedWorldChange::SetComponentDeltas(_, _) => WorldChangeTag::SetComponentDeltas,
         }
     }
 }
@@ -239,10 +243,16 @@ impl From<&ComponentDesc> for UnknownComponent {
 /// 3. Change specific data:
 ///     - `Entity` (for `Spawn`, `AddComponents`, `SetComponents`)
 ///     - collection of `ComponentDesc` (for `RemoveComponents`)
+///     - collection of `(u32, ComponentDelta)` (for `SetComponentDeltas`)
 ///     - `0` byte filler (for `Despawn`)
 ///
 /// `Entity` is serialized as a collection of `ComponentEntry`.
 ///
+/// Components of type `f32`, `Vec2`, `Vec3`, `Vec4` and `Quat` that the other side has received before are sent in
+/// `SetComponentDeltas` instead of `SetComponents`, with only their lanes that changed, or the change of every lane
+/// in steps of their `Replicated::quantization` if they have one. Both sides keep the last value sent of each of
+/// these components as the baseline to apply the deltas to.
+///
 /// `ComponentEntry` and `ComponentDesc` are serialized differently compared to the textual representation. Instead of
 /// serializing the full component path, `DiffSerializer` serializes only the internal component index (`u32`).
 ///
@@ -281,6 +291,7 @@ impl From<&ComponentDesc> for UnknownComponent {
 #[derive(Clone, Default)]
 pub struct DiffSerializer {
     known_component_paths: HashMap<u32, String>,
+    baselines: DeltaBaselines,
 }
 
 impl std::fmt::Debug for DiffSerializer {
@@ -310,10 +321,8 @@ impl DiffSerializer {
     pub fn serialize_external_components(&mut self) -> Result<Bytes, bincode::Error> {
         // get all external components that we haven't seen before
         let unknown_components = self.collect_all_unknown_external_components();
-        // create dummy diff
-        let diff = Default::default();
-        // serialize everything
-        self.serialize_parts(unknown_components, &NetworkedWorldDiff(&diff))
+        // serialize everything with an empty diff
+        self.serialize_parts(unknown_components, &NetworkedWorldDiff(&[]))
     }
 
     fn collect_all_unknown_external_components(&self) -> HashMap<u32, UnknownComponent> {
@@ -329,8 +338,10 @@ impl DiffSerializer {
     pub fn serialize(&mut self, diff: &WorldDiff) -> Result<Bytes, bincode::Error> {
         // get all component that we haven't seen before
         let unknown_components = self.collect_unknown_components(diff.changes.iter());
+        // send only how the float components changed, where the other side knows their value
+        let changes = self.baselines.encode(&diff.changes);
         // serialize everything
-        self.serialize_parts(unknown_components, &NetworkedWorldDiff(diff))
+        self.serialize_parts(unknown_components, &NetworkedWorldDiff(&changes))
     }
 
     fn collect_unknown_components<'a, I>(&self, changes: I) -> HashMap<u32, UnknownComponent>
@@ -382,7 +393,16 @@ impl DiffSerializer {
                 .map(|(k, v)| (k, v.into_path())),
         );
         // deserialize the actual changes
-        deserializer.deserialize_seq(NetworkedChangesVisitor::from(&*self))
+        let changes = deserializer.deserialize_seq(NetworkedChangesVisitor::from(&*self))?;
+        // and apply the deltas to the values we last received
+        let known_component_paths = &self.known_component_paths;
+        let changes = self
+            .baselines
+            .decode(changes, |index| {
+                lookup_component(known_component_paths, index)
+            })
+            .map_err(<bincode::Error as serde::de::Error>::custom)?;
+        Ok(WorldDiff { changes })
     }
 }
 
@@ -398,7 +418,7 @@ impl<'a> From<&'a DiffSerializer> for NetworkedChangesVisitor<'a> {
     }
 }
 impl<'a, 'de> serde::de::Visitor<'de> for NetworkedChangesVisitor<'a> {
-    type Value = WorldDiff;
+    type Value = Vec<DecodedChange>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         formatter.write_str("struct WorldDiff")
@@ -416,7 +436,7 @@ impl<'a, 'de> serde::de::Visitor<'de> for NetworkedChangesVisitor<'a> {
         while let Some(change) = seq.next_element_seed(NetworkedChangeVisitor::from(self))? {
             changes.push(change)
         }
-        Ok(WorldDiff { changes })
+        Ok(changes)
     }
 }
 
@@ -425,7 +445,7 @@ struct NetworkedChangeVisitor<'a> {
     known_component_paths: &'a HashMap<u32, String>,
 }
 impl<'a, 'de> serde::de::Visitor<'de> for NetworkedChangeVisitor<'a> {
-    type Value = WorldChange;
+    type Value = DecodedChange;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         formatter.write_str("enum WorldChange")
@@ -438,7 +458,8 @@ impl<'a, 'de> serde::de::Visitor<'de> for NetworkedChangeVisitor<'a> {
         // WorldChange is encoded as a tuple of 3 elements:
         // 1. tag
         // 2. id
-        // 3. change specific data (either Entity, Vec<ComponentDesc> or 0u8 for Despawn)
+        // 3. change specific data (either Entity, Vec<ComponentDesc>, Vec<(u32, ComponentDelta)>
+        //    or 0u8 for Despawn)
 
         let tag = seq
             .next_element_seed(WorldChangeTagVisitor)?
@@ -447,7 +468,7 @@ impl<'a, 'de> serde::de::Visitor<'de> for NetworkedChangeVisitor<'a> {
             .next_element_seed(NetworkedEntityIdVisitor)?
             .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
 
-        Ok(match tag {
+        Ok(DecodedChange::Change(match tag {
             WorldChangeTag::Spawn => {
                 let entity = seq
                     .next_element_seed(NetworkedEntityVisitor::from(self))?
@@ -479,11 +500,17 @@ impl<'a, 'de> serde::de::Visitor<'de> for NetworkedChangeVisitor<'a> {
                     .ok_or_else(|| serde::de::Error::invalid_length(2, &self))?;
                 WorldChange::SetComponents(id, entity)
             }
-        })
+            WorldChangeTag::SetComponentDeltas => {
+                let deltas = seq
+                    .next_element::<Vec<(u32, ComponentDelta)>>()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(2, &self))?;
+                return Ok(DecodedChange::Deltas(id, deltas));
+            }
+        }))
     }
 }
 impl<'a, 'de> serde::de::DeserializeSeed<'de> for NetworkedChangeVisitor<'a> {
-    type Value = WorldChange;
+    type Value = DecodedChange;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
@@ -622,21 +649,7 @@ impl<'a, 'de> serde::de::Visitor<'de> for NetworkedComponentDescVisitor<'a> {
     where
         E: serde::de::Error,
     {
-        match self.known_component_paths.get(&v) {
-            Some(path) => {
-                let component = with_component_registry(|r| r.get_by_path(path));
-                match component {
-                    Some(desc) => Ok(desc),
-                    None => Err(serde::de::Error::custom(format!(
-                        "Encountered unknown networked component: {v} {path:?}"
-                    ))),
-                }
-            }
-            None => Err(serde::de::Error::custom(format!(
-                "Unknown component index {}",
-                v
-            ))),
-        }
+        lookup_component(self.known_component_paths, v).map_err(serde::de::Error::custom)
     }
 }
 impl<'a, 'de> serde::de::DeserializeSeed<'de> for NetworkedComponentDescVisitor<'a> {
@@ -691,15 +704,26 @@ impl<'a, 'de> serde::de::DeserializeSeed<'de> for NetworkedComponentEntryVisitor
     }
 }
 
+fn lookup_component(
+    known_component_paths: &HashMap<u32, String>,
+    index: u32,
+) -> Result<ComponentDesc, String> {
+    match known_component_paths.get(&index) {
+        Some(path) => with_component_registry(|r| r.get_by_path(path))
+            .ok_or_else(|| format!("Encountered unknown networked component: {index} {path:?}")),
+        None => Err(format!("Unknown component index {}", index)),
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
-struct NetworkedWorldDiff<'a>(&'a WorldDiff);
+struct NetworkedWorldDiff<'a>(&'a [EncodedChange<'a>]);
 impl<'a> serde::Serialize for NetworkedWorldDiff<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(self.0.changes.len()))?;
-        for change in self.0.changes.iter() {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for change in self.0.iter() {
             seq.serialize_element(&NetworkedWorldChange::from(change))?;
         }
         seq.end()
@@ -713,6 +737,7 @@ enum NetworkedWorldChange<'a> {
     AddComponents(u128, NetworkedEntity<'a>),
     RemoveComponents(u128, Vec<NetworkedComponentDesc>),
     SetComponents(u128, NetworkedEntity<'a>),
+    SetComponentDeltas(u128, &'a [(u32, ComponentDelta)]),
 }
 impl<'a> NetworkedWorldChange<'a> {
     fn id(&self) -> u128 {
@@ -721,13 +746,16 @@ impl<'a> NetworkedWorldChange<'a> {
             | NetworkedWorldChange::Despawn(id)
             | NetworkedWorldChange::AddComponents(id, _)
             | NetworkedWorldChange::RemoveComponents(id, _)
-            | NetworkedWorldChange::SetComponents(id, _) => *id,
+            | NetworkedWorldChange::SetComponents(id, _)
+            | NetworkedWorldChange::SetComponentDeltas(id, _) => *id,
         }
     }
 
     fn entity(&self) -> Option<NetworkedEntity<'a>> {
         match self {
-            NetworkedWorldChange::Despawn(_) | NetworkedWorldChange::RemoveComponents(_, _) => None,
+            NetworkedWorldChange::Despawn(_)
+            | NetworkedWorldChange::RemoveComponents(_, _)
+            | NetworkedWorldChange::SetComponentDeltas(_, _) => None,
             NetworkedWorldChange::Spawn(_, e)
             | NetworkedWorldChange::AddComponents(_, e)
             | NetworkedWorldChange::SetComponents(_, e) => Some(*e),
@@ -755,6 +783,14 @@ impl<'a> From<&'a WorldChange> for NetworkedWorldChange<'a> {
         }
     }
 }
+impl<'a> From<&'a EncodedChange<'a>> for NetworkedWorldChange<'a> {
+    fn from(value: &'a EncodedChange<'a>) -> Self {
+        match value {
+            EncodedChange::Change(change) => (&**change).into(),
+            EncodedChange::Deltas(id, deltas) => Self::SetComponentDeltas(id.0, deltas),
+        }
+    }
+}
 impl<'a> serde::Serialize for NetworkedWorldChange<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -763,7 +799,8 @@ impl<'a> serde::Serialize for NetworkedWorldChange<'a> {
         // WorldChange is encoded as a tuple of 3 elements:
         // 1. tag
         // 2. id
-        // 3. change specific data (either Entity, Vec<ComponentDesc> or 0u8 for Despawn)
+        // 3. change specific data (either Entity, Vec<ComponentDesc>, Vec<(u32, ComponentDelta)>
+        //    or 0u8 for Despawn)
         let mut seq = serializer.serialize_tuple(3)?;
         seq.serialize_element(&WorldChangeTag::from(self))?;
         seq.serialize_element(&self.id())?;
@@ -771,6 +808,8 @@ impl<'a> serde::Serialize for NetworkedWorldChange<'a> {
             seq.serialize_element(&e)?;
         } else if let NetworkedWorldChange::RemoveComponents(_, components) = self {
             seq.serialize_element(components)?;
+        } else if let NetworkedWorldChange::SetComponentDeltas(_, deltas) = self {
+            seq.serialize_element(deltas)?;
         } else if let NetworkedWorldChange::Despawn(_) = self {
             seq.serialize_element(&0u8)?;
         } else {
@@ -854,7 +893,8 @@ impl_from_kcp!(
 
 #[cfg(test)]
 mod tests {
-    use ambient_ecs::components;
+    use ambient_ecs::{components, Replicated, ReplicationPriority};
+    use glam::Vec3;
 
     use super::*;
 
@@ -865,6 +905,10 @@ mod tests {
         float: f32,
         @[Serializable]
         counter: usize,
+        @[Serializable]
+        position: Vec3,
+        @[Serializable, Replicated[Replicated { rate: None, priority: ReplicationPriority::Normal, quantization: Some(0.5) }]]
+        quantized_position: Vec3,
     });
 
     fn assert_same_diffs<'a, 'b, A, B>(a: A, b: B)
@@ -1033,4 +1077,92 @@ mod tests {
             assert_eq!(WorldChangeTag::try_from(tag as u8).unwrap(), tag);
         }
     }
+
+    #[test]
+    fn set_components_are_delta_compressed() {
+        // Arrange
+        init_components();
+        let id = EntityId::new();
+        let spawn = WorldDiff {
+            changes: vec![WorldChange::Spawn(
+                id,
+                Entity::new()
+                    .with(text(), "foo".to_string())
+                    .with(position(), Vec3::new(1.0, 2.0, 3.0)),
+            )],
+        };
+        let set = WorldDiff {
+            changes: vec![WorldChange::SetComponents(
+                id,
+                Entity::new()
+                    .with(text(), "bar".to_string())
+                    .with(position(), Vec3::new(1.0, 2.5, 3.0)),
+            )],
+        };
+        let mut serializer = DiffSerializer::default();
+        let mut deserializer = DiffSerializer::default();
+
+        // Act
+        deserializer
+            .deserialize(serializer.serialize(&spawn).unwrap())
+            .unwrap();
+        let message = serializer.serialize(&set).unwrap();
+        let full_message = DiffSerializer::default().serialize(&set).unwrap();
+        let received = deserializer.deserialize(message.clone()).unwrap();
+
+        // Assert
+        let mut received_entity = Entity::new();
+        for change in &received.changes {
+            let WorldChange::SetComponents(received_id, entity) = change else {
+                panic!("Unexpected change {change:?}");
+            };
+            assert_eq!(*received_id, id);
+            received_entity.merge(entity.clone());
+        }
+        assert_eq!(received_entity.get_ref(text()).unwrap(), "bar");
+        assert_eq!(
+            received_entity.get(position()),
+            Some(Vec3::new(1.0, 2.5, 3.0))
+        );
+        assert!(message.len() < full_message.len());
+    }
+
+    #[test]
+    fn quantized_components_are_rounded_to_steps() {
+        // Arrange
+        init_components();
+        let id = EntityId::new();
+        let diff = |value: Vec3, spawn: bool| {
+            let entity = Entity::new().with(quantized_position(), value);
+            WorldDiff {
+                changes: vec![if spawn {
+                    WorldChange::Spawn(id, entity)
+                } else {
+                    WorldChange::SetComponents(id, entity)
+                }],
+            }
+        };
+        let mut serializer = DiffSerializer::default();
+        let mut deserializer = DiffSerializer::default();
+        let mut send = |diff: WorldDiff| {
+            deserializer
+                .deserialize(serializer.serialize(&diff).unwrap())
+                .unwrap()
+        };
+
+        // Act
+        send(diff(Vec3::new(1.0, 1.0, 1.0), true));
+        let small_change = send(diff(Vec3::new(1.1, 1.0, 1.0), false));
+        let large_change = send(diff(Vec3::new(2.4, 1.0, -3.0), false));
+
+        // Assert
+        assert!(small_change.changes.is_empty());
+        let [WorldChange::SetComponents(_, entity)] = &large_change.changes[..] else {
+            panic!("Unexpected changes {large_change:?}");
+        };
+        assert_eq!(
+            entity.get(quantized_position()),
+            Some(Vec3::new(2.5, 1.0, -3.0))
+        );
+    }
 }
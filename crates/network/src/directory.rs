@@ -0,0 +1,201 @@
+//! Discovery of servers through a directory.
+//!
+//! A directory is an HTTP endpoint which servers register their [ServerListing] with, by `POST`ing
+//! it as JSON to `<endpoint>/servers` every [HEARTBEAT_INTERVAL]. A listing which is not refreshed
+//! within [LISTING_TTL] expires. Clients list the servers with a `GET` to the same URL.
+
+use std::{collections::HashMap, time::Duration};
+
+use ambient_core::asset_cache;
+use ambient_element::{
+    element_component, use_effect, use_state, Element, ElementComponentExt, Hooks,
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKeyExt},
+    download_asset::ReqwestClientKey,
+    Cb,
+};
+use ambient_sys::time::Instant;
+use ambient_ui_native::{
+    space_between_items, Button, FlowColumn, FlowRow, StylesExt, Text, Throbber, STREET,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// How often servers refresh their listing
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// How long a listing stays in the directory without being refreshed
+pub const LISTING_TTL: Duration = Duration::from_secs(45);
+
+/// A server, as listed in a directory
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ServerListing {
+    pub name: String,
+    /// The `host:port` of the QUIC interface clients join the server with
+    pub address: String,
+    /// The map being played, if the server tells
+    pub map: Option<String>,
+    pub player_count: usize,
+    /// The names of the packages running on the server
+    pub packages: Vec<String>,
+    /// The version of Ambient the server runs
+    pub version: String,
+}
+
+fn servers_url(endpoint: &str) -> String {
+    format!("{}/servers", endpoint.trim_end_matches('/'))
+}
+
+/// Registers or refreshes the listing of a server with the directory at `endpoint`
+pub async fn register(
+    assets: &AssetCache,
+    endpoint: &str,
+    listing: &ServerListing,
+) -> anyhow::Result<()> {
+    ReqwestClientKey
+        .get(assets)
+        .post(servers_url(endpoint))
+        .json(listing)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to register with the directory at {endpoint}"))?;
+
+    Ok(())
+}
+
+/// Lists the servers registered with the directory at `endpoint`
+pub async fn list_servers(
+    assets: &AssetCache,
+    endpoint: &str,
+) -> anyhow::Result<Vec<ServerListing>> {
+    let listings = ReqwestClientKey
+        .get(assets)
+        .get(servers_url(endpoint))
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to list the servers of the directory at {endpoint}"))?
+        .json()
+        .await?;
+
+    Ok(listings)
+}
+
+/// The listings held by a directory, keyed by the address of their server
+#[derive(Debug, Default)]
+pub struct Directory {
+    listings: HashMap<String, (ServerListing, Instant)>,
+}
+
+impl Directory {
+    pub fn register(&mut self, listing: ServerListing, now: Instant) {
+        self.listings
+            .insert(listing.address.clone(), (listing, now + LISTING_TTL));
+    }
+
+    /// The listings which have not expired by `now`, by name
+    pub fn listings(&mut self, now: Instant) -> Vec<ServerListing> {
+        self.listings.retain(|_, (_, expiry)| *expiry > now);
+
+        let mut listings: Vec<_> = self
+            .listings
+            .values()
+            .map(|(listing, _)| listing.clone())
+            .collect();
+        listings.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.address.cmp(&b.address)));
+        listings
+    }
+}
+
+/// Lists the servers of the directory at `endpoint`, with a button to join each of them
+#[element_component]
+pub fn ServerBrowser(
+    hooks: &mut Hooks,
+    endpoint: String,
+    on_join: Cb<dyn Fn(&mut ambient_ecs::World, ServerListing) + Sync + Send>,
+) -> Element {
+    let (listings, set_listings) = use_state(hooks, None);
+    let (refresh, set_refresh) = use_state(hooks, 0u32);
+
+    use_effect(hooks, (endpoint, refresh), move |world, (endpoint, _)| {
+        let assets = world.resource(asset_cache()).clone();
+        let endpoint = endpoint.clone();
+        set_listings(None);
+        let task = ambient_sys::task::spawn_local(move || async move {
+            let listings = list_servers(&assets, &endpoint)
+                .await
+                .map_err(|err| format!("{err:#}"));
+            set_listings(Some(listings));
+        });
+
+        move |_| task.abort()
+    });
+
+    let body = match listings {
+        None => Throbber.el(),
+        Some(Err(err)) => Text::el(err),
+        Some(Ok(listings)) if listings.is_empty() => Text::el("No servers found"),
+        Some(Ok(listings)) => FlowColumn::el(listings.into_iter().map(|listing| {
+            let on_join = on_join.clone();
+            let map = listing.map.as_deref().unwrap_or("-");
+            FlowRow::el([
+                Text::el(format!(
+                    "{} ({map}, {} players)",
+                    listing.name, listing.player_count
+                )),
+                Button::new("Join", move |world| on_join(world, listing.clone())).el(),
+            ])
+            .with(space_between_items(), STREET)
+        }))
+        .with(space_between_items(), STREET),
+    };
+
+    FlowColumn::el([
+        FlowRow::el([
+            Text::el("Servers").header_style(),
+            Button::new("Refresh", move |_| set_refresh(refresh + 1)).el(),
+        ])
+        .with(space_between_items(), STREET),
+        body,
+    ])
+    .with(space_between_items(), STREET)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(name: &str, address: &str) -> ServerListing {
+        ServerListing {
+            name: name.into(),
+            address: address.into(),
+            map: None,
+            player_count: 0,
+            packages: Vec::new(),
+            version: "0.3.2".into(),
+        }
+    }
+
+    #[test]
+    fn listings_expire_unless_refreshed() {
+        let start = Instant::now();
+        let mut directory = Directory::default();
+        directory.register(listing("b", "b:9000"), start);
+        directory.register(listing("a", "a:9000"), start);
+
+        let names = |directory: &mut Directory, now| {
+            directory
+                .listings(now)
+                .into_iter()
+                .map(|listing| listing.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&mut directory, start), ["a", "b"]);
+
+        let later = start + LISTING_TTL / 2;
+        directory.register(listing("a", "a:9000"), later);
+        assert_eq!(names(&mut directory, start + LISTING_TTL), ["a"]);
+        assert!(names(&mut directory, later + LISTING_TTL).is_empty());
+    }
+}
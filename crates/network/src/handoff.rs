@@ -0,0 +1,368 @@
+//! Handoff of entities, and of the players they belong to, from one server to another, for
+//! transitions between zones or shards.
+//!
+//! A server module sends a `HandoffRequest` to hand off an entity and its descendants. The server
+//! captures them as a [Handoff], along with the player entity if a player comes along, and
+//! `POST`s it as JSON to `<endpoint>/handoff` on the other server, presenting the
+//! [HandoffSecretKey] they share. The other server spawns the entities with the same IDs, and
+//! answers with the ticket the player joins it with. Only then does the first server despawn the
+//! entities, and redirect the client of the player to the other server.
+//!
+//! The entities spawned by a module stay attributed to the module of the same package and name on
+//! the other server, through the [SpawnOwnership] of each server.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use ambient_core::{
+    asset_cache, async_ecs::async_run, hierarchy::children, player::get_by_user_id,
+};
+use ambient_ecs::{
+    components,
+    generated::network::messages::{HandoffArrived, HandoffFailed, HandoffRequest},
+    read_messages, world_events, Entity, EntityId, FnSystem, Resource, SystemGroup, World,
+    WorldEventReader, WorldEventsExt,
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    download_asset::ReqwestClientKey,
+};
+use ambient_sys::time::Instant;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::server::redirect_player;
+#[cfg(not(target_os = "unknown"))]
+use crate::server::{ServerState, MAIN_INSTANCE_ID};
+
+components!("network::handoff", {
+    /// Tracks the entities spawned by the modules of the server
+    @[Resource]
+    spawn_ownership: Arc<dyn SpawnOwnership>,
+    /// The players handed off to this server which have yet to join it, by user ID
+    @[Resource]
+    handoff_arrivals: HashMap<String, Arrival>,
+});
+
+/// How long a player handed off to this server has to join it
+pub const ARRIVAL_TTL: Duration = Duration::from_secs(30);
+
+/// The secret servers present to each other to hand off entities. Without it, a server neither
+/// sends nor accepts handoffs
+#[derive(Debug, Clone)]
+pub struct HandoffSecretKey;
+impl SyncAssetKey<Arc<str>> for HandoffSecretKey {}
+
+/// The module which spawned an entity, by the ID of its package and its name
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleOwner {
+    pub package: String,
+    pub module: String,
+}
+
+/// Tracks which module spawned each entity, for the entities handed off to stay attributed to
+/// the same module on the other server
+pub trait SpawnOwnership: Send + Sync {
+    /// The module which spawned `id`, if any
+    fn owner(&self, world: &World, id: EntityId) -> Option<ModuleOwner>;
+    /// Forgets that `id` was spawned by a module
+    fn release(&self, world: &mut World, id: EntityId);
+    /// Attributes `id` to `owner`, if the module runs on this server
+    fn adopt(&self, world: &mut World, id: EntityId, owner: &ModuleOwner);
+}
+
+/// Entities handed off from one server to another
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Handoff {
+    pub root: EntityId,
+    /// The root and its descendants, except for the player entity
+    pub entities: Vec<(EntityId, Entity)>,
+    /// The modules which spawned some of the entities
+    pub owners: Vec<(EntityId, ModuleOwner)>,
+    pub player: Option<HandoffPlayer>,
+}
+
+/// The player coming along with a [Handoff]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HandoffPlayer {
+    pub user_id: String,
+    pub id: EntityId,
+    pub entity: Entity,
+}
+
+/// The answer of a server to a [Handoff]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HandoffReceipt {
+    /// The ticket the player joins the server with, if a player came along
+    pub ticket: Option<u128>,
+}
+
+/// A player handed off to this server, who joins it by presenting `ticket`
+#[derive(Debug, Clone)]
+pub struct Arrival {
+    pub ticket: u128,
+    /// The ID the player entity keeps
+    pub id: EntityId,
+    pub entity: Entity,
+    expires: Instant,
+}
+
+impl Handoff {
+    /// Captures `root` and its descendants, along with the player with `user_id` if any
+    pub fn capture(world: &World, root: EntityId, user_id: Option<&str>) -> anyhow::Result<Self> {
+        anyhow::ensure!(world.exists(root), "No such entity: {root}");
+
+        let player = match user_id {
+            Some(user_id) => {
+                let id = get_by_user_id(world, user_id)
+                    .with_context(|| format!("No such player: {user_id:?}"))?;
+                Some(HandoffPlayer {
+                    user_id: user_id.to_owned(),
+                    id,
+                    entity: world.clone_entity(id)?.serializable(),
+                })
+            }
+            None => None,
+        };
+
+        let ownership = world.resource_opt(spawn_ownership());
+        let mut entities = Vec::new();
+        let mut owners = Vec::new();
+        let mut pending = vec![root];
+        while let Some(id) = pending.pop() {
+            if let Ok(children) = world.get_ref(id, children()) {
+                pending.extend(children.iter().copied());
+            }
+            if player.as_ref().is_some_and(|player| player.id == id) {
+                continue;
+            }
+
+            entities.push((id, world.clone_entity(id)?.serializable()));
+            if let Some(owner) = ownership.and_then(|ownership| ownership.owner(world, id)) {
+                owners.push((id, owner));
+            }
+        }
+
+        Ok(Self {
+            root,
+            entities,
+            owners,
+            player,
+        })
+    }
+
+    /// Despawns the entities which were captured, once they have been handed off. The player
+    /// entity is despawned when the player is redirected
+    pub fn release(&self, world: &mut World) {
+        let ownership = world.resource_opt(spawn_ownership()).cloned();
+        for (id, _) in &self.entities {
+            if let Some(ownership) = &ownership {
+                ownership.release(world, *id);
+            }
+            world.despawn(*id);
+        }
+    }
+
+    /// Spawns the entities into `world` with the IDs they had, and expects the player, if any
+    pub fn arrive(self, world: &mut World) -> anyhow::Result<HandoffReceipt> {
+        if let Some((id, _)) = self.entities.iter().find(|(id, _)| world.exists(*id)) {
+            anyhow::bail!("The entity {id} already exists on this server");
+        }
+
+        for (id, entity) in self.entities {
+            world.spawn_with_id(id, entity);
+        }
+
+        if let Some(ownership) = world.resource_opt(spawn_ownership()).cloned() {
+            for (id, owner) in &self.owners {
+                ownership.adopt(world, *id, owner);
+            }
+        }
+
+        let ticket = self.player.as_ref().map(|_| Uuid::new_v4().as_u128());
+        let arriving = self.player.as_ref().map(|player| player.user_id.clone());
+        if let (Some(player), Some(ticket)) = (self.player, ticket) {
+            let now = Instant::now();
+            if world.resource_opt(handoff_arrivals()).is_none() {
+                world.add_resource(handoff_arrivals(), HashMap::new());
+            }
+
+            let arrivals = world.resource_mut(handoff_arrivals());
+            arrivals.retain(|_, arrival| arrival.expires > now);
+            arrivals.insert(
+                player.user_id,
+                Arrival {
+                    ticket,
+                    id: player.id,
+                    entity: player.entity,
+                    expires: now + ARRIVAL_TTL,
+                },
+            );
+        }
+
+        world
+            .resource_mut(world_events())
+            .add_message(HandoffArrived::new(self.root, arriving.unwrap_or_default()));
+
+        Ok(HandoffReceipt { ticket })
+    }
+}
+
+/// Takes the arrival of the player with `user_id`, if it joins with the `ticket` it was handed
+/// off with
+pub(crate) fn take_arrival(world: &mut World, user_id: &str, ticket: u128) -> Option<Arrival> {
+    let arrivals = world.resource_mut_opt(handoff_arrivals())?;
+    let arrival = arrivals.remove(user_id)?;
+    if arrival.ticket != ticket || arrival.expires < Instant::now() {
+        arrivals.insert(user_id.to_owned(), arrival);
+        return None;
+    }
+
+    Some(arrival)
+}
+
+fn handoff_url(endpoint: &str) -> String {
+    format!("{}/handoff", endpoint.trim_end_matches('/'))
+}
+
+/// Sends `handoff` to the server whose HTTP interface is at `endpoint`
+pub async fn send(
+    assets: &AssetCache,
+    endpoint: &str,
+    handoff: &Handoff,
+) -> anyhow::Result<HandoffReceipt> {
+    let secret = HandoffSecretKey
+        .try_get(assets)
+        .context("No handoff secret was set for this server")?;
+
+    let receipt = ReqwestClientKey
+        .get(assets)
+        .post(handoff_url(endpoint))
+        .bearer_auth(&*secret)
+        .json(handoff)
+        .send()
+        .await?
+        .error_for_status()
+        .with_context(|| format!("Failed to hand off to the server at {endpoint}"))?
+        .json()
+        .await?;
+
+    Ok(receipt)
+}
+
+/// Accepts a handoff sent to this server with `secret`, spawning its entities into the main
+/// instance
+#[cfg(not(target_os = "unknown"))]
+pub fn accept(
+    state: &mut ServerState,
+    secret: Option<&str>,
+    handoff: Handoff,
+) -> anyhow::Result<HandoffReceipt> {
+    let expected = HandoffSecretKey
+        .try_get(&state.assets)
+        .context("This server does not accept handoffs")?;
+    // Compared in constant time, so that the secret can't be guessed from how long it takes
+    let matches = secret.is_some_and(|secret| {
+        ring::constant_time::verify_slices_are_equal(secret.as_bytes(), expected.as_bytes()).is_ok()
+    });
+    anyhow::ensure!(matches, "The handoff secret does not match");
+
+    let instance = state
+        .instances
+        .get_mut(MAIN_INSTANCE_ID)
+        .context("No main instance")?;
+    handoff.arrive(&mut instance.world)
+}
+
+/// Hands off the entities the server modules ask for with `HandoffRequest`s
+pub fn server_systems() -> SystemGroup {
+    let mut reader = WorldEventReader::new();
+    SystemGroup::new(
+        "network/handoff",
+        vec![Box::new(FnSystem::new(move |world, _| {
+            for request in
+                read_messages::<HandoffRequest>(&mut reader, world.resource(world_events()))
+            {
+                start(world, request);
+            }
+        }))],
+    )
+}
+
+fn start(world: &mut World, request: HandoffRequest) {
+    let HandoffRequest {
+        root,
+        user_id,
+        endpoint,
+        address,
+    } = request;
+
+    let user_id = (!user_id.is_empty()).then_some(user_id);
+    let handoff = match Handoff::capture(world, root, user_id.as_deref()) {
+        Ok(handoff) => handoff,
+        Err(err) => return fail(world, root, err),
+    };
+
+    let assets = world.resource(asset_cache()).clone();
+    let async_run = world.resource(async_run()).clone();
+    ambient_sys::task::spawn(async move {
+        let result = send(&assets, &endpoint, &handoff).await;
+        async_run.run(move |world| match result {
+            Ok(receipt) => complete(world, handoff, &address, receipt),
+            Err(err) => fail(world, root, err),
+        });
+    });
+}
+
+fn complete(world: &mut World, handoff: Handoff, address: &str, receipt: HandoffReceipt) {
+    tracing::debug!(root = %handoff.root, entities = handoff.entities.len(), "Handed off");
+    handoff.release(world);
+
+    if let (Some(player), Some(ticket)) = (&handoff.player, receipt.ticket) {
+        match get_by_user_id(world, &player.user_id) {
+            Some(id) => redirect_player(world, id, address, ticket),
+            None => {
+                tracing::warn!(user_id = %player.user_id, "The player left during the handoff")
+            }
+        }
+    }
+}
+
+fn fail(world: &mut World, root: EntityId, err: anyhow::Error) {
+    tracing::warn!(%root, "Failed to hand off: {err:?}");
+    world
+        .resource_mut(world_events())
+        .add_message(HandoffFailed::new(root, format!("{err:#}")));
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_ecs::{generated::hierarchy::components::parent, WorldContext};
+
+    use super::*;
+
+    #[test]
+    fn handoff_keeps_the_hierarchy() {
+        ambient_ecs::init_components();
+        init_components();
+
+        let mut source = World::new("source", WorldContext::Server);
+        let root = Entity::new().spawn(&mut source);
+        let child = Entity::new().with(parent(), root).spawn(&mut source);
+        source.add_component(root, children(), vec![child]).unwrap();
+
+        let handoff = Handoff::capture(&source, root, None).unwrap();
+        handoff.release(&mut source);
+        assert!(!source.exists(root) && !source.exists(child));
+
+        let handoff: Handoff =
+            serde_json::from_str(&serde_json::to_string(&handoff).unwrap()).unwrap();
+        let mut target = World::new("target", WorldContext::Server);
+        target.add_resource(world_events(), Default::default());
+        let receipt = handoff.arrive(&mut target).unwrap();
+
+        assert!(receipt.ticket.is_none());
+        assert_eq!(target.get_ref(root, children()).unwrap(), &[child]);
+        assert_eq!(target.get(child, parent()).unwrap(), root);
+    }
+}
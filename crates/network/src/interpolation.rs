@@ -0,0 +1,243 @@
+use std::{collections::VecDeque, time::Duration};
+
+use ambient_ecs::{
+    components,
+    generated::{
+        network::components::{
+            interpolation_delay, max_extrapolation, predicted, server_time, shown_server_time,
+        },
+        transform::components::{rotation, scale, translation},
+    },
+    query, Entity, EntityId, FnSystem, Resource, SystemGroup, World, WorldChange,
+};
+use ambient_sys::time::Instant;
+use glam::{Quat, Vec3};
+
+use crate::{authority::has_local_authority, ServerWorldExt};
+
+components!("network::interpolation", {
+    /// The transforms the server has recently sent for this remote entity, oldest first
+    snapshots: Snapshots,
+    /// The latest `server_time` received, and when it was received
+    @[Resource]
+    server_clock: (Duration, Instant),
+});
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_EXTRAPOLATION: Duration = Duration::from_millis(250);
+
+/// The networked transform of an entity, as far as the server has sent it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Transform {
+    translation: Option<Vec3>,
+    rotation: Option<Quat>,
+    scale: Option<Vec3>,
+}
+impl Transform {
+    fn of(entity: &Entity) -> Self {
+        Self {
+            translation: entity.get(translation()),
+            rotation: entity.get(rotation()),
+            scale: entity.get(scale()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns this transform with the parts `other` has replaced
+    fn merge(self, other: Self) -> Self {
+        Self {
+            translation: other.translation.or(self.translation),
+            rotation: other.rotation.or(self.rotation),
+            scale: other.scale.or(self.scale),
+        }
+    }
+
+    /// Interpolates towards `other`, or extrapolates past it if `t` is greater than 1
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            translation: lerp_with(self.translation, other.translation, |a, b| a.lerp(b, t)),
+            rotation: lerp_with(self.rotation, other.rotation, |a, b| a.slerp(b, t)),
+            scale: lerp_with(self.scale, other.scale, |a, b| a.lerp(b, t)),
+        }
+    }
+}
+
+fn lerp_with<T: Copy>(a: Option<T>, b: Option<T>, f: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(f(a, b)),
+        _ => b,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Snapshot {
+    received: Instant,
+    transform: Transform,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Snapshots(VecDeque<Snapshot>);
+impl Snapshots {
+    /// Returns the transform to show `delay` after the snapshots were received, dropping the
+    /// snapshots that are no longer needed
+    fn sample(&mut self, now: Instant, delay: Duration, max_extrapolation: Duration) -> Transform {
+        let shown = |snapshot: &Snapshot| snapshot.received + delay;
+        let next = self.0.iter().position(|snapshot| shown(snapshot) > now);
+        let (from, to, t) = match next {
+            // Nothing is due yet
+            Some(0) => return self.0[0].transform,
+            Some(i) => {
+                self.0.drain(..i - 1);
+                let (from, to) = (self.0[0], self.0[1]);
+                let t = now.duration_since(shown(&from)).as_secs_f32()
+                    / to.received.duration_since(from.received).as_secs_f32();
+                (from, to, t)
+            }
+            // Every snapshot is due, so keep moving with the velocity of the last two
+            None => {
+                let len = self.0.len();
+                self.0.drain(..len.saturating_sub(2));
+                let [from, to] = match self.0.make_contiguous() {
+                    [from, to] => [*from, *to],
+                    [.., last] => return last.transform,
+                    [] => return Transform::default(),
+                };
+                let late = now.duration_since(shown(&to)).min(max_extrapolation);
+                let interval = to.received.duration_since(from.received);
+                let t = 1.0 + late.as_secs_f32() / interval.as_secs_f32();
+                (from, to, t)
+            }
+        };
+        if t.is_finite() {
+            from.transform.lerp(to.transform, t)
+        } else {
+            to.transform
+        }
+    }
+}
+
+/// Returns the transforms the server has set in `changes`, which must be recorded with [record]
+/// once they have been applied
+pub(crate) fn received_transforms(changes: &[WorldChange]) -> Vec<(EntityId, Transform)> {
+    changes
+        .iter()
+        .filter_map(|change| match change {
+            WorldChange::Spawn(id, entity)
+            | WorldChange::AddComponents(id, entity)
+            | WorldChange::SetComponents(id, entity) => Some((*id, Transform::of(entity))),
+            _ => None,
+        })
+        .filter(|(_, transform)| !transform.is_empty())
+        .collect()
+}
+
+/// Adds the transforms the server has sent to the snapshots of their entities. Entities that are
+/// `predicted`, or that the local player has authority over, are simulated locally instead, and
+/// are not interpolated.
+pub(crate) fn record(world: &mut World, transforms: Vec<(EntityId, Transform)>) {
+    let received = Instant::now();
+    if let Some(&time) = world.synced_resource(server_time()) {
+        if world
+            .resource_opt(server_clock())
+            .map_or(true, |&(last, _)| last != time)
+        {
+            world.add_resource(server_clock(), (time, received));
+        }
+    }
+
+    if delay(world).is_zero() {
+        return;
+    }
+    for (id, transform) in transforms {
+        if !world.exists(id) {
+            continue;
+        }
+        if world.has_component(id, predicted()) {
+            world.remove_component(id, snapshots()).ok();
+            continue;
+        }
+        match world.get_mut(id, snapshots()) {
+            Ok(snapshots) => {
+                let last = snapshots.0.back().map(|s| s.transform).unwrap_or_default();
+                snapshots.0.push_back(Snapshot {
+                    received,
+                    transform: last.merge(transform),
+                });
+            }
+            Err(_) => {
+                // The entity hasn't been interpolated yet, so it is still where the server put it
+                let current = Transform {
+                    translation: world.get(id, translation()).ok(),
+                    rotation: world.get(id, rotation()).ok(),
+                    scale: world.get(id, scale()).ok(),
+                };
+                let snapshot = Snapshot {
+                    received,
+                    transform: current.merge(transform),
+                };
+                world
+                    .add_component(id, snapshots(), Snapshots(VecDeque::from([snapshot])))
+                    .ok();
+            }
+        }
+    }
+}
+
+fn delay(world: &World) -> Duration {
+    world
+        .resource_opt(interpolation_delay())
+        .copied()
+        .unwrap_or(DEFAULT_DELAY)
+}
+
+/// Shows remote entities `interpolation_delay` behind the server, interpolating their transforms
+/// between the snapshots the server sent, and keeps `shown_server_time` up to date
+pub fn interpolation_systems() -> SystemGroup {
+    SystemGroup::new(
+        "interpolation",
+        vec![
+            Box::new(FnSystem::new(|world, _| {
+                let Some(&(time, received)) = world.resource_opt(server_clock()) else {
+                    return;
+                };
+                let shown = (time + received.elapsed()).saturating_sub(delay(world));
+                world.add_resource(shown_server_time(), shown);
+            })),
+            query(snapshots()).to_system(|q, world, qs, _| {
+                let delay = delay(world);
+                let max_extrapolation = world
+                    .resource_opt(max_extrapolation())
+                    .copied()
+                    .unwrap_or(DEFAULT_MAX_EXTRAPOLATION);
+                let now = Instant::now();
+
+                for (id, _) in q.collect_cloned(world, qs) {
+                    if delay.is_zero()
+                        || world.has_component(id, predicted())
+                        || has_local_authority(world, id)
+                    {
+                        world.remove_component(id, snapshots()).ok();
+                        continue;
+                    }
+                    let transform = world.get_mut(id, snapshots()).unwrap().sample(
+                        now,
+                        delay,
+                        max_extrapolation,
+                    );
+                    if let Some(value) = transform.translation {
+                        world.set_if_changed(id, translation(), value).ok();
+                    }
+                    if let Some(value) = transform.rotation {
+                        world.set_if_changed(id, rotation(), value).ok();
+                    }
+                    if let Some(value) = transform.scale {
+                        world.set_if_changed(id, scale(), value).ok();
+                    }
+                }
+            }),
+        ],
+    )
+}
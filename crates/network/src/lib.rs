@@ -16,17 +16,36 @@ pub use ambient_ecs::generated::network::components::{
 
 pub type AsyncMutex<T> = tokio::sync::Mutex<T>;
 
+pub mod auth;
+pub mod authority;
+pub mod bandwidth;
 pub mod bytes_ext;
 pub mod client;
 pub mod client_game_state;
 pub mod codec;
+pub mod conditions;
+mod delta;
 pub mod diff_serialization;
+pub mod directory;
+pub mod handoff;
 pub mod hooks;
+pub mod interpolation;
+pub mod mux;
+mod prediction;
 pub mod proto;
+pub mod rate_limit;
+#[cfg(not(target_os = "unknown"))]
+pub mod rcon;
+mod relevancy;
+pub mod replay;
+mod replication;
 pub mod rpc;
 pub mod serialization;
 pub mod server;
+pub mod session;
 pub mod stream;
+#[cfg(not(target_os = "unknown"))]
+pub mod voice;
 
 #[cfg(not(target_os = "unknown"))]
 pub mod native;
@@ -47,13 +66,27 @@ pub const WASM_UNISTREAM_ID: u32 = 11;
 
 pub const PLAYER_INPUT_DATAGRAM_ID: u32 = 12;
 pub const WASM_DATAGRAM_ID: u32 = 13;
+pub const VOICE_DATAGRAM_ID: u32 = 14;
+pub const WASM_SEQUENCED_DATAGRAM_ID: u32 = 15;
+pub const WASM_ORDERED_UNISTREAM_ID: u32 = 16;
+pub const AUTHORITY_UNISTREAM_ID: u32 = 17;
 
 const MAX_FRAME_SIZE: usize = 1024 * 1024 * 1024;
 
 pub fn init_all_components() {
+    authority::init_components();
+    bandwidth::init_components();
     client::init_components();
+    handoff::init_components();
+    replay::init_components();
     server::init_components();
     client_game_state::init_components();
+    interpolation::init_components();
+    rate_limit::init_components();
+    #[cfg(not(target_os = "unknown"))]
+    rcon::init_components();
+    #[cfg(not(target_os = "unknown"))]
+    voice::init_components();
 }
 
 pub trait ServerWorldExt {
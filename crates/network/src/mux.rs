@@ -0,0 +1,671 @@
+//! Streams and datagrams multiplexed over a single message based connection.
+//!
+//! Carries the same protocol as the QUIC and WebTransport connections over a WebSocket, for
+//! browsers which don't support WebTransport. The owner of the socket feeds every message it
+//! receives to [MuxConnection::handle_frame], and sends every message it gets from the outgoing
+//! channel.
+//!
+//! Every message is a frame of `[kind: u8][stream: u32][payload]`, except for datagrams, which
+//! are `[kind: u8][payload]`. The streams opened by the client have even ids, and the streams
+//! opened by the server odd ones.
+//!
+//! Unlike QUIC, the streams are not flow controlled individually, and the datagrams are
+//! delivered reliably and in order with everything else. Instead, the data buffered for each
+//! stream and the number of open streams are capped, and a peer which goes over either is in
+//! breach of the protocol. Streams are opened with increasing ids, and datagrams are held to the
+//! size of QUIC datagrams.
+//!
+//! On the sending side, the stream data and datagrams waiting to be sent are capped: writes to
+//! the streams wait for room, and datagrams are dropped.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{future::BoxFuture, ready};
+use parking_lot::Mutex;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf},
+    sync::{
+        mpsc::{
+            channel, error::TrySendError, unbounded_channel, Receiver, Sender, UnboundedReceiver,
+            UnboundedSender,
+        },
+        AcquireError, OwnedSemaphorePermit, Semaphore,
+    },
+};
+
+use crate::{
+    bytes_ext::{BufExt, UnexpectedEnd},
+    client::NetworkTransport,
+    AsyncMutex, NetworkError, MAX_FRAME_SIZE,
+};
+
+/// The largest payload sent in a single data frame
+const MAX_CHUNK_SIZE: usize = 64 * 1024;
+/// The most data frames buffered for an incoming stream before they are read, i.e. 16 MiB
+const MAX_BUFFERED_CHUNKS: usize = 256;
+/// The most incoming streams open at once, including the ones waiting to be accepted
+const MAX_OPEN_STREAMS: usize = 128;
+/// The most datagrams buffered before they are read. Datagrams over it are dropped
+const MAX_BUFFERED_DATAGRAMS: usize = 1024;
+/// The largest datagram, as QUIC datagrams can't be larger than the smallest MTU it supports
+pub const MAX_DATAGRAM_SIZE: usize = 1200;
+/// The largest frame, which is a data frame with a full chunk
+pub const MAX_MUX_FRAME_SIZE: usize = 5 + MAX_CHUNK_SIZE;
+/// The most bytes of stream data and datagrams waiting to be sent
+const MAX_QUEUED_BYTES: usize = 4 * 1024 * 1024;
+
+/// Which end of the connection this is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MuxSide {
+    Client,
+    Server,
+}
+
+impl MuxSide {
+    fn id_parity(self) -> u32 {
+        match self {
+            MuxSide::Client => 0,
+            MuxSide::Server => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    OpenUni(u32),
+    OpenBi(u32),
+    Data(u32, Bytes),
+    Finish(u32),
+    Datagram(Bytes),
+}
+
+impl Frame {
+    const OPEN_UNI: u8 = 0;
+    const OPEN_BI: u8 = 1;
+    const DATA: u8 = 2;
+    const FINISH: u8 = 3;
+    const DATAGRAM: u8 = 4;
+
+    pub fn encode(&self) -> Bytes {
+        let mut buf = BytesMut::new();
+        match self {
+            Frame::OpenUni(id) => {
+                buf.put_u8(Self::OPEN_UNI);
+                buf.put_u32(*id);
+            }
+            Frame::OpenBi(id) => {
+                buf.put_u8(Self::OPEN_BI);
+                buf.put_u32(*id);
+            }
+            Frame::Data(id, data) => {
+                buf.reserve(5 + data.len());
+                buf.put_u8(Self::DATA);
+                buf.put_u32(*id);
+                buf.put_slice(data);
+            }
+            Frame::Finish(id) => {
+                buf.put_u8(Self::FINISH);
+                buf.put_u32(*id);
+            }
+            Frame::Datagram(data) => {
+                buf.reserve(1 + data.len());
+                buf.put_u8(Self::DATAGRAM);
+                buf.put_slice(data);
+            }
+        }
+        buf.freeze()
+    }
+
+    pub fn decode(mut data: Bytes) -> Result<Self, MuxError> {
+        let frame = match data.try_get_u8()? {
+            Self::OPEN_UNI => Frame::OpenUni(data.try_get_u32()?),
+            Self::OPEN_BI => Frame::OpenBi(data.try_get_u32()?),
+            Self::DATA => {
+                let id = data.try_get_u32()?;
+                Frame::Data(id, data)
+            }
+            Self::FINISH => Frame::Finish(data.try_get_u32()?),
+            Self::DATAGRAM => Frame::Datagram(data),
+            kind => return Err(MuxError::UnknownFrame(kind)),
+        };
+
+        Ok(frame)
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum MuxError {
+    #[error(transparent)]
+    UnexpectedEnd(#[from] UnexpectedEnd),
+    #[error("Unknown frame kind {0}")]
+    UnknownFrame(u8),
+    #[error("The peer opened stream {0}, which has the id of a local stream or of a stream it opened before")]
+    InvalidStream(u32),
+    #[error("The peer opened more than {MAX_OPEN_STREAMS} streams")]
+    TooManyStreams,
+    #[error("The peer sent a chunk of {0} bytes, larger than {MAX_CHUNK_SIZE}")]
+    ChunkTooLarge(usize),
+    #[error("The peer sent more data on stream {0} than is buffered before it is read")]
+    StreamOverflow(u32),
+    #[error("The peer sent a datagram of {0} bytes, larger than {MAX_DATAGRAM_SIZE}")]
+    DatagramTooLarge(usize),
+}
+
+/// The channels of incoming streams and datagrams, dropped when the connection closes
+struct Incoming {
+    streams: HashMap<u32, Sender<Bytes>>,
+    /// The id of the last stream opened by the peer, which the next ones must be greater than
+    last_stream: Option<u32>,
+    uni: Sender<MuxRecvStream>,
+    bi: Sender<(MuxSendStream, MuxRecvStream)>,
+    datagrams: Sender<Bytes>,
+}
+
+struct Inner {
+    side: MuxSide,
+    next_id: AtomicU32,
+    outgoing: Outgoing,
+    incoming: Mutex<Option<Incoming>>,
+    uni: AsyncMutex<Receiver<MuxRecvStream>>,
+    bi: AsyncMutex<Receiver<(MuxSendStream, MuxRecvStream)>>,
+    datagrams: AsyncMutex<Receiver<Bytes>>,
+}
+
+/// A connection of multiplexed streams and datagrams
+#[derive(Clone)]
+pub struct MuxConnection {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for MuxConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MuxConnection")
+            .field("side", &self.inner.side)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The frames to send to the peer, with the bytes of data they take from the queue
+#[derive(Clone)]
+struct Outgoing {
+    frames: UnboundedSender<(Bytes, usize)>,
+    room: Arc<Semaphore>,
+}
+
+impl Outgoing {
+    /// Queues a frame which doesn't count against the queued bytes
+    fn send(&self, frame: Frame) -> Result<(), NetworkError> {
+        self.frames
+            .send((frame.encode(), 0))
+            .map_err(|_| NetworkError::ConnectionClosed)
+    }
+
+    /// Queues a frame of `len` bytes of data, for which room has been taken from the queue
+    fn send_taken(&self, frame: Frame, len: usize) -> Result<(), NetworkError> {
+        self.frames.send((frame.encode(), len)).map_err(|_| {
+            self.room.add_permits(len);
+            NetworkError::ConnectionClosed
+        })
+    }
+}
+
+/// The frames to send to the peer, in order
+pub struct MuxOutgoing {
+    frames: UnboundedReceiver<(Bytes, usize)>,
+    room: Arc<Semaphore>,
+}
+
+impl MuxOutgoing {
+    pub async fn recv(&mut self) -> Option<Bytes> {
+        let (frame, len) = self.frames.recv().await?;
+        self.room.add_permits(len);
+        Some(frame)
+    }
+}
+
+impl MuxConnection {
+    /// Creates a connection, and the channel of the frames to send to the peer
+    pub fn new(side: MuxSide) -> (Self, MuxOutgoing) {
+        let (frames, frames_rx) = unbounded_channel();
+        let room = Arc::new(Semaphore::new(MAX_QUEUED_BYTES));
+        let outgoing = Outgoing {
+            frames,
+            room: room.clone(),
+        };
+        let outgoing_rx = MuxOutgoing {
+            frames: frames_rx,
+            room,
+        };
+        let (uni_tx, uni_rx) = channel(MAX_OPEN_STREAMS);
+        let (bi_tx, bi_rx) = channel(MAX_OPEN_STREAMS);
+        let (datagrams_tx, datagrams_rx) = channel(MAX_BUFFERED_DATAGRAMS);
+
+        let inner = Inner {
+            side,
+            next_id: AtomicU32::new(0),
+            outgoing,
+            incoming: Mutex::new(Some(Incoming {
+                streams: HashMap::new(),
+                last_stream: None,
+                uni: uni_tx,
+                bi: bi_tx,
+                datagrams: datagrams_tx,
+            })),
+            uni: AsyncMutex::new(uni_rx),
+            bi: AsyncMutex::new(bi_rx),
+            datagrams: AsyncMutex::new(datagrams_rx),
+        };
+
+        (
+            Self {
+                inner: Arc::new(inner),
+            },
+            outgoing_rx,
+        )
+    }
+
+    /// Handles a frame received from the peer
+    pub fn handle_frame(&self, frame: Bytes) -> Result<(), MuxError> {
+        let frame = Frame::decode(frame)?;
+
+        let mut incoming = self.inner.incoming.lock();
+        let Some(incoming) = &mut *incoming else {
+            return Ok(());
+        };
+
+        match frame {
+            Frame::OpenUni(id) | Frame::OpenBi(id)
+                if id % 2 == self.inner.side.id_parity()
+                    || incoming.last_stream.is_some_and(|last| id <= last) =>
+            {
+                return Err(MuxError::InvalidStream(id));
+            }
+            Frame::OpenUni(_) | Frame::OpenBi(_) if incoming.streams.len() >= MAX_OPEN_STREAMS => {
+                return Err(MuxError::TooManyStreams);
+            }
+            Frame::OpenUni(id) => {
+                incoming.last_stream = Some(id);
+                let recv = self.recv_stream(incoming, id);
+                if let Err(TrySendError::Full(_)) = incoming.uni.try_send(recv) {
+                    return Err(MuxError::TooManyStreams);
+                }
+            }
+            Frame::OpenBi(id) => {
+                incoming.last_stream = Some(id);
+                let recv = self.recv_stream(incoming, id);
+                if let Err(TrySendError::Full(_)) =
+                    incoming.bi.try_send((self.send_stream(id), recv))
+                {
+                    return Err(MuxError::TooManyStreams);
+                }
+            }
+            Frame::Data(_, data) if data.len() > MAX_CHUNK_SIZE => {
+                return Err(MuxError::ChunkTooLarge(data.len()));
+            }
+            Frame::Data(id, data) => {
+                if let Some(stream) = incoming.streams.get(&id) {
+                    match stream.try_send(data) {
+                        Ok(()) => {}
+                        Err(TrySendError::Full(_)) => return Err(MuxError::StreamOverflow(id)),
+                        // The stream is no longer read
+                        Err(TrySendError::Closed(_)) => {
+                            incoming.streams.remove(&id);
+                        }
+                    }
+                }
+            }
+            Frame::Finish(id) => {
+                incoming.streams.remove(&id);
+            }
+            Frame::Datagram(data) if data.len() > MAX_DATAGRAM_SIZE => {
+                return Err(MuxError::DatagramTooLarge(data.len()));
+            }
+            Frame::Datagram(data) => {
+                incoming.datagrams.try_send(data).ok();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Closes the connection.
+    ///
+    /// Incoming streams end, and accepting streams or datagrams fails once the already received
+    /// ones have been taken.
+    pub fn close(&self) {
+        self.inner.incoming.lock().take();
+    }
+
+    pub fn open_uni(&self) -> Result<MuxSendStream, NetworkError> {
+        let id = self.next_id();
+        self.inner.outgoing.send(Frame::OpenUni(id))?;
+        Ok(self.send_stream(id))
+    }
+
+    pub fn open_bi(&self) -> Result<(MuxSendStream, MuxRecvStream), NetworkError> {
+        let id = self.next_id();
+
+        let recv = {
+            let mut incoming = self.inner.incoming.lock();
+            let incoming = incoming.as_mut().ok_or(NetworkError::ConnectionClosed)?;
+            self.recv_stream(incoming, id)
+        };
+
+        self.inner.outgoing.send(Frame::OpenBi(id))?;
+        Ok((self.send_stream(id), recv))
+    }
+
+    pub async fn accept_uni(&self) -> Result<MuxRecvStream, NetworkError> {
+        self.inner
+            .uni
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(NetworkError::ConnectionClosed)
+    }
+
+    pub async fn accept_bi(&self) -> Result<(MuxSendStream, MuxRecvStream), NetworkError> {
+        self.inner
+            .bi
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(NetworkError::ConnectionClosed)
+    }
+
+    pub async fn read_datagram(&self) -> Result<Bytes, NetworkError> {
+        self.inner
+            .datagrams
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(NetworkError::ConnectionClosed)
+    }
+
+    /// Sends a datagram, unless too much is already waiting to be sent
+    pub fn send_datagram(&self, data: Bytes) -> Result<(), NetworkError> {
+        if data.len() > MAX_DATAGRAM_SIZE {
+            return Err(NetworkError::FrameTooLarge);
+        }
+
+        let outgoing = &self.inner.outgoing;
+        match outgoing.room.try_acquire_many(data.len() as u32) {
+            Ok(permit) => {
+                permit.forget();
+                let len = data.len();
+                outgoing.send_taken(Frame::Datagram(data), len)
+            }
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn next_id(&self) -> u32 {
+        let index = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        index.wrapping_mul(2) | self.inner.side.id_parity()
+    }
+
+    fn send_stream(&self, id: u32) -> MuxSendStream {
+        MuxSendStream {
+            id,
+            outgoing: self.inner.outgoing.clone(),
+            room: None,
+            finished: false,
+        }
+    }
+
+    fn recv_stream(&self, incoming: &mut Incoming, id: u32) -> MuxRecvStream {
+        let (tx, rx) = channel(MAX_BUFFERED_CHUNKS);
+        incoming.streams.insert(id, tx);
+        MuxRecvStream {
+            buf: Bytes::new(),
+            chunks: rx,
+        }
+    }
+}
+
+impl NetworkTransport for MuxConnection {
+    fn request_bi(&self, id: u32, data: Bytes) -> BoxFuture<Result<Bytes, NetworkError>> {
+        Box::pin(async move {
+            let (mut send, recv) = self.open_bi()?;
+
+            send.write_u32(id).await?;
+            send.write_all(&data).await?;
+
+            drop(send);
+
+            let mut buf = Vec::new();
+
+            let read = recv
+                .take(MAX_FRAME_SIZE as u64 + 1)
+                .read_to_end(&mut buf)
+                .await?;
+            if read > MAX_FRAME_SIZE {
+                return Err(NetworkError::FrameTooLarge);
+            }
+
+            Ok(buf.into())
+        })
+    }
+
+    fn request_uni(&self, id: u32, data: Bytes) -> BoxFuture<Result<(), NetworkError>> {
+        Box::pin(async move {
+            let mut send = self.open_uni()?;
+
+            send.write_u32(id).await?;
+            send.write_all(&data).await?;
+
+            Ok(())
+        })
+    }
+
+    fn send_datagram(&self, id: u32, data: Bytes) -> BoxFuture<Result<(), NetworkError>> {
+        let mut bytes = BytesMut::with_capacity(4 + data.len());
+        bytes.put_u32(id);
+        bytes.put(data);
+
+        let res = MuxConnection::send_datagram(self, bytes.freeze());
+
+        Box::pin(futures::future::ready(res))
+    }
+}
+
+type AcquireRoom = Pin<Box<dyn Future<Output = Result<OwnedSemaphorePermit, AcquireError>> + Send>>;
+
+/// The sending half of a multiplexed stream
+///
+/// Finishes the stream when dropped
+pub struct MuxSendStream {
+    id: u32,
+    outgoing: Outgoing,
+    /// Waits for room in the queue for the next chunk, of the given length
+    room: Option<(usize, AcquireRoom)>,
+    finished: bool,
+}
+
+impl MuxSendStream {
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.finished {
+            self.finished = true;
+            self.outgoing
+                .send(Frame::Finish(self.id))
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for MuxSendStream {
+    fn drop(&mut self) {
+        self.finish().ok();
+    }
+}
+
+impl AsyncWrite for MuxSendStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.finished {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        let this = &mut *self;
+        let (taken, room) = this.room.get_or_insert_with(|| {
+            let len = buf.len().min(MAX_CHUNK_SIZE);
+            let room = this.outgoing.room.clone().acquire_many_owned(len as u32);
+            (len, Box::pin(room))
+        });
+        let permit = ready!(room.as_mut().poll(cx));
+        let taken = *taken;
+        this.room = None;
+        let Ok(permit) = permit else {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        };
+        permit.forget();
+
+        // The buffer may have shrunk while waiting
+        let len = taken.min(buf.len());
+        this.outgoing.room.add_permits(taken - len);
+        let frame = Frame::Data(this.id, Bytes::copy_from_slice(&buf[..len]));
+
+        match this.outgoing.send_taken(frame, len) {
+            Ok(()) => Poll::Ready(Ok(len)),
+            Err(_) => Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.finish())
+    }
+}
+
+/// The receiving half of a multiplexed stream
+pub struct MuxRecvStream {
+    buf: Bytes,
+    chunks: Receiver<Bytes>,
+}
+
+impl AsyncRead for MuxRecvStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while !self.buf.has_remaining() {
+            match ready!(self.chunks.poll_recv(cx)) {
+                Some(chunk) => self.buf = chunk,
+                // Finished
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+
+        let len = buf.remaining().min(self.buf.len());
+        buf.put_slice(&self.buf[..len]);
+        self.buf.advance(len);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frame_roundtrip() {
+        for frame in [
+            Frame::OpenUni(4),
+            Frame::OpenBi(7),
+            Frame::Data(4, Bytes::from_static(b"hello")),
+            Frame::Finish(4),
+            Frame::Datagram(Bytes::from_static(b"datagram")),
+        ] {
+            assert_eq!(Frame::decode(frame.encode()).unwrap(), frame);
+        }
+    }
+
+    #[test]
+    fn truncated_frame() {
+        assert!(Frame::decode(Bytes::from_static(&[Frame::DATA, 0, 0])).is_err());
+        assert!(Frame::decode(Bytes::from_static(&[42])).is_err());
+    }
+
+    #[test]
+    fn incoming_streams_are_capped() {
+        let (conn, _outgoing) = MuxConnection::new(MuxSide::Server);
+        for i in 0..MAX_OPEN_STREAMS as u32 {
+            conn.handle_frame(Frame::OpenUni(i * 2).encode()).unwrap();
+        }
+        let id = MAX_OPEN_STREAMS as u32 * 2;
+        assert!(matches!(
+            conn.handle_frame(Frame::OpenUni(id).encode()),
+            Err(MuxError::TooManyStreams)
+        ));
+    }
+
+    #[test]
+    fn closed_streams_are_not_reopened() {
+        let (conn, _outgoing) = MuxConnection::new(MuxSide::Server);
+        conn.handle_frame(Frame::OpenUni(2).encode()).unwrap();
+        conn.handle_frame(Frame::Finish(2).encode()).unwrap();
+        for id in [0, 2] {
+            assert!(matches!(
+                conn.handle_frame(Frame::OpenBi(id).encode()),
+                Err(MuxError::InvalidStream(_))
+            ));
+        }
+        conn.handle_frame(Frame::OpenBi(4).encode()).unwrap();
+    }
+
+    #[test]
+    fn datagrams_are_capped() {
+        let (conn, _outgoing) = MuxConnection::new(MuxSide::Server);
+        let datagram = Bytes::from(vec![0; MAX_DATAGRAM_SIZE + 1]);
+        assert!(matches!(
+            conn.handle_frame(Frame::Datagram(datagram.clone()).encode()),
+            Err(MuxError::DatagramTooLarge(_))
+        ));
+        assert!(conn.send_datagram(datagram).is_err());
+
+        // Datagrams are dropped rather than queued once the queue is full
+        let datagram = Bytes::from(vec![0; MAX_DATAGRAM_SIZE]);
+        for _ in 0..MAX_QUEUED_BYTES / MAX_DATAGRAM_SIZE + 1 {
+            conn.send_datagram(datagram.clone()).unwrap();
+        }
+        assert!(conn.inner.outgoing.room.available_permits() < MAX_DATAGRAM_SIZE);
+    }
+
+    #[test]
+    fn unread_stream_data_is_capped() {
+        let (conn, _outgoing) = MuxConnection::new(MuxSide::Server);
+        conn.handle_frame(Frame::OpenUni(0).encode()).unwrap();
+        let data = Frame::Data(0, Bytes::from_static(b"data")).encode();
+        for _ in 0..MAX_BUFFERED_CHUNKS {
+            conn.handle_frame(data.clone()).unwrap();
+        }
+        assert!(matches!(
+            conn.handle_frame(data),
+            Err(MuxError::StreamOverflow(0))
+        ));
+    }
+}
@@ -1,12 +1,14 @@
 use crate::{
     client::{CleanupFunc, ClientState, Control, GameClientRenderTarget, LoadedFunc, NetworkStats},
     client_game_state::{game_screen_render_target, ClientGameState},
+    conditions::{Delayed, NetworkConditions},
     native::load_root_certs,
     proto::{
-        client::{ClientProtoState, SharedClientGameState},
-        ClientRequest,
+        client::{ClientProtoState, Redirected, Refused, SharedClientGameState},
+        ClientRequest, ServerPush,
     },
     server::RpcArgs,
+    session::ACKNOWLEDGE_INTERVAL,
     stream::{FramedRecvStream, FramedSendStream, RawFramedRecvStream},
     NetworkError,
 };
@@ -73,6 +75,8 @@ pub struct ClientView {
     pub server_addr: ResolvedAddr,
     pub cert: Option<Vec<u8>>,
     pub user_id: String,
+    /// The token vouching for `user_id`, for servers which authenticate their players
+    pub token: Option<String>,
     pub fail_on_version_mismatch: bool,
     pub systems_and_resources: Cb<dyn Fn() -> (SystemGroup, Entity) + Sync + Send>,
     pub on_loaded: LoadedFunc,
@@ -85,6 +89,7 @@ impl ElementComponent for ClientView {
         let Self {
             server_addr,
             user_id,
+            token,
             fail_on_version_mismatch,
             systems_and_resources,
             create_rpc_registry,
@@ -154,71 +159,87 @@ impl ElementComponent for ClientView {
             let local_resources = world_instance_resources(AppResources::from_world(ui_world))
                 .with(game_screen_render_target(), render_target.0.clone());
             let task = async move {
-                let conn = open_connection(server_addr.clone(), cert.map(Certificate))
-                    .await
-                    .with_context(|| {
-                        format!("Failed to connect to endpoint \"{server_addr:?}\"")
-                    })?;
-
                 handle_connection(
-                    conn.clone(),
+                    server_addr,
+                    cert.map(Certificate),
                     &assets,
                     user_id,
+                    token,
                     fail_on_version_mismatch,
-                    move |args| {
-                        let OnConnectionState {
-                            assets,
-                            user_id,
-                            main_package_name,
-                        } = args;
+                    {
+                        let set_client_state = set_client_state.clone();
+                        move |args| {
+                            let OnConnectionState {
+                                conn,
+                                assets,
+                                user_id,
+                                main_package_name,
+                            } = args;
+
+                            set_window_title(main_package_name.to_string());
+
+                            let (systems, resources) = systems_and_resources();
+                            let resources = local_resources
+                                .clone()
+                                .with(ambient_core::player::local_user_id(), user_id.into())
+                                .with_merge(resources);
+
+                            let game_state = ClientGameState::new(
+                                &gpu,
+                                assets.clone(),
+                                user_id.into(),
+                                systems,
+                                resources,
+                            );
 
-                        set_window_title(main_package_name.to_string());
+                            // Create a handle for the game client
+                            let client_state = ClientState::new(
+                                Arc::new(conn.clone()),
+                                Arc::new(create_rpc_registry()),
+                                Arc::new(Mutex::new(game_state)),
+                                user_id.into(),
+                            );
 
-                        let (systems, resources) = systems_and_resources();
-                        let resources = local_resources
-                            .clone()
-                            .with(ambient_core::player::local_user_id(), user_id.into())
-                            .with_merge(resources);
-
-                        let game_state = ClientGameState::new(
-                            &gpu,
-                            assets.clone(),
-                            user_id.into(),
-                            systems,
-                            resources,
-                        );
+                            let game_state = &client_state.game_state;
+                            let cleanup = {
+                                // Lock before setting
+                                let game_state = &mut game_state.lock();
 
-                        // Create a handle for the game client
-                        let client_state = ClientState::new(
-                            Arc::new(conn.clone()),
-                            Arc::new(create_rpc_registry()),
-                            Arc::new(Mutex::new(game_state)),
-                            user_id.into(),
-                        );
+                                // Updates the game client context in the Ui tree
+                                // Update the resources on the client side world to reflect the new connection
+                                // state
 
-                        let game_state = &client_state.game_state;
-                        let cleanup = {
-                            // Lock before setting
-                            let game_state = &mut game_state.lock();
+                                game_state.world.add_resource(
+                                    crate::client::client_state(),
+                                    Some(client_state.clone()),
+                                );
 
-                            // Updates the game client context in the Ui tree
-                            // Update the resources on the client side world to reflect the new connection
-                            // state
+                                (on_loaded)(&client_state, game_state)?
+                            };
 
-                            game_state.world.add_resource(
-                                crate::client::client_state(),
-                                Some(client_state.clone()),
-                            );
+                            // Set the client last so that the game state is initialized first
+                            set_client_state(Some(client_state.clone()));
 
-                            (on_loaded)(&client_state, game_state)?
+                            Ok((game_state.clone(), cleanup))
+                        }
+                    },
+                    move |conn, game_state| {
+                        // The game state carries over, only the connection changes
+                        let mut game_state = game_state.lock();
+                        let Some(mut client_state) = game_state
+                            .world
+                            .resource(crate::client::client_state())
+                            .clone()
+                        else {
+                            return;
                         };
-
-                        // Set the client last so that the game state is initialized first
-                        set_client_state(Some(client_state.clone()));
-
-                        Ok((game_state.clone(), cleanup))
+                        client_state.transport = Arc::new(conn.clone());
+                        game_state.world.add_resource(
+                            crate::client::client_state(),
+                            Some(client_state.clone()),
+                        );
+                        set_client_state(Some(client_state));
                     },
-                    // game_state,
                     control_rx,
                 )
                 .await?;
@@ -270,61 +291,111 @@ impl ElementComponent for ClientView {
 }
 
 struct OnConnectionState<'a> {
+    conn: &'a Connection,
     assets: &'a AssetCache,
     user_id: &'a str,
     main_package_name: &'a str,
 }
 
+/// The streams of a connection which the server has accepted
+struct Established {
+    client: ClientProtoState,
+    request_send: FramedSendStream<ClientRequest, quinn::SendStream>,
+    push_recv: FramedRecvStream<ServerPush, quinn::RecvStream>,
+    diff_stream: RawFramedRecvStream<quinn::RecvStream>,
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_connection(
-    conn: quinn::Connection,
+    mut server_addr: ResolvedAddr,
+    cert: Option<Certificate>,
     assets: &AssetCache,
     user_id: String,
+    token: Option<String>,
     fail_on_version_mismatch: bool,
     mut on_loaded: impl FnMut(OnConnectionState) -> anyhow::Result<(SharedClientGameState, CleanupFunc)>
         + Send
         + Sync,
+    mut on_resumed: impl FnMut(&Connection, &SharedClientGameState) + Send + Sync,
     control_rx: flume::Receiver<Control>,
 ) -> anyhow::Result<()> {
-    let mut request_send = FramedSendStream::new(conn.open_uni().await?);
-
-    // Accept the diff and stat stream
-    // Nothing is read from them until the connection has been accepted
-
-    // Send a connection request
-    tracing::info!("Attempting to connect using {user_id:?}");
+    let mut request = ClientRequest::connect(user_id.clone(), token.clone());
+    loop {
+        let result = connect_to(
+            server_addr,
+            cert.clone(),
+            assets,
+            &user_id,
+            token.clone(),
+            request,
+            fail_on_version_mismatch,
+            &mut on_loaded,
+            &mut on_resumed,
+            control_rx.clone(),
+        )
+        .await;
+
+        let err = match result {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
 
-    request_send
-        .send(ClientRequest::Connect(user_id.clone()))
-        .await?;
+        // A player handed off to another server joins it with a fresh game state
+        let Redirected { address, ticket } = err.downcast::<Redirected>()?;
+        tracing::info!(%address, "Redirected to another server");
 
-    let mut client = ClientProtoState::Pending(user_id.clone());
+        server_addr = ResolvedAddr::lookup_host(address).await?;
+        request = ClientRequest::Arrive {
+            user_id: user_id.clone(),
+            token: token.clone(),
+            ticket,
+        };
+    }
+}
 
-    let mut push_recv = FramedRecvStream::new(conn.accept_uni().await?);
+/// Connects to the server at `server_addr` with `request`, and runs the connection until it ends
+#[allow(clippy::too_many_arguments)]
+async fn connect_to(
+    server_addr: ResolvedAddr,
+    cert: Option<Certificate>,
+    assets: &AssetCache,
+    user_id: &str,
+    token: Option<String>,
+    request: ClientRequest,
+    fail_on_version_mismatch: bool,
+    mut on_loaded: impl FnMut(OnConnectionState) -> anyhow::Result<(SharedClientGameState, CleanupFunc)>
+        + Send
+        + Sync,
+    mut on_resumed: impl FnMut(&Connection, &SharedClientGameState) + Send + Sync,
+    control_rx: flume::Receiver<Control>,
+) -> anyhow::Result<()> {
+    let mut conn = open_connection(server_addr.clone(), cert.clone())
+        .await
+        .with_context(|| format!("Failed to connect to endpoint \"{server_addr:?}\""))?;
 
-    while client.is_pending() {
-        if let Some(frame) = push_recv.next().await {
-            client.process_push(assets, fail_on_version_mismatch, frame?)?;
-        }
-    }
+    // Send a connection request
+    tracing::info!("Attempting to connect using {user_id:?}");
 
-    assert!(ContentBaseUrlKey.exists(assets));
+    let Some(mut established) =
+        establish(&conn, assets, user_id, request, fail_on_version_mismatch).await?
+    else {
+        tracing::warn!("Connection failed or was denied");
+        return Ok(());
+    };
 
-    let main_package_name = if let ClientProtoState::Connected(connected) = &client {
+    let main_package_name = if let ClientProtoState::Connected(connected) = &established.client {
         connected.main_package_name.clone()
     } else {
-        tracing::warn!("Connection failed or was denied");
-        return Ok(());
+        unreachable!("The connection was established")
     };
 
     tracing::info!("Connection successfully established");
 
     // Create the game client
-
-    let mut diff_stream = RawFramedRecvStream::new(conn.accept_uni().await?);
-
     let (shared_client_state, cleanup) = on_loaded(OnConnectionState {
+        conn: &conn,
         assets,
-        user_id: &user_id,
+        user_id,
         main_package_name: &main_package_name,
     })?;
 
@@ -335,13 +406,154 @@ async fn handle_connection(
 
     scopeguard::defer!(on_disconnect());
 
+    let mut control_rx = control_rx.into_stream();
+
+    loop {
+        let result = run_connection(
+            &conn,
+            &mut established,
+            assets,
+            fail_on_version_mismatch,
+            &shared_client_state,
+            &mut control_rx,
+        )
+        .await;
+
+        let err = match result {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+
+        // Only a connection which dropped can be resumed
+        let ClientProtoState::Connected(connected) = &established.client else {
+            return Err(err);
+        };
+        let Some(session) = connected.session.filter(|_| conn.close_reason().is_some()) else {
+            return Err(err);
+        };
+        tracing::warn!("Connection lost, resuming the session: {err:?}");
+
+        let request = ClientRequest::Resume {
+            user_id: user_id.to_owned(),
+            token: token.clone(),
+            session: session.key,
+            received: connected.diffs_received(),
+        };
+        let deadline = ambient_sys::time::Instant::now() + session.grace;
+        (conn, established) = loop {
+            let attempt = async {
+                let conn = open_connection(server_addr.clone(), cert.clone()).await?;
+                let established = establish(
+                    &conn,
+                    assets,
+                    user_id,
+                    request.clone(),
+                    fail_on_version_mismatch,
+                )
+                .await?
+                .context("The server disconnected while resuming the session")?;
+                anyhow::Ok((conn, established))
+            };
+
+            match attempt.await {
+                Ok(resumed) => break resumed,
+                Err(err)
+                    if err.downcast_ref::<Refused>().is_none()
+                        && ambient_sys::time::Instant::now() < deadline =>
+                {
+                    tracing::warn!("Failed to resume the session, retrying: {err:?}");
+                    tokio::time::sleep(RESUME_RETRY_INTERVAL).await;
+                }
+                Err(err) => return Err(err.context("Failed to resume the session")),
+            }
+        };
+
+        tracing::info!("Session resumed");
+        on_resumed(&conn, &shared_client_state);
+    }
+}
+
+/// How long to wait between attempts to resume a session
+const RESUME_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sends `request` to the server, and waits for it to accept the connection.
+///
+/// Returns `None` if the server disconnected the client instead.
+async fn establish(
+    conn: &Connection,
+    assets: &AssetCache,
+    user_id: &str,
+    request: ClientRequest,
+    fail_on_version_mismatch: bool,
+) -> anyhow::Result<Option<Established>> {
+    let mut request_send = FramedSendStream::new(conn.open_uni().await?);
+
+    // Accept the diff and stat stream
+    // Nothing is read from them until the connection has been accepted
+    request_send.send(request).await?;
+
+    let mut client = ClientProtoState::Pending(user_id.to_owned());
+
+    let mut push_recv = FramedRecvStream::new(conn.accept_uni().await?);
+
+    while client.is_pending() {
+        if let Some(frame) = push_recv.next().await {
+            client.process_push(assets, fail_on_version_mismatch, frame?)?;
+        }
+    }
+
+    if !client.is_connected() {
+        return Ok(None);
+    }
+
+    assert!(ContentBaseUrlKey.exists(assets));
+
+    let diff_stream = tokio::select! {
+        stream = conn.accept_uni() => RawFramedRecvStream::new(stream?),
+        // The server can still refuse the connection before sending the world
+        Some(frame) = push_recv.next() => {
+            client.process_push(assets, fail_on_version_mismatch, frame?)?;
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(Established {
+        client,
+        request_send,
+        push_recv,
+        diff_stream,
+    }))
+}
+
+/// Runs the client on an established connection, until it disconnects
+async fn run_connection(
+    conn: &Connection,
+    established: &mut Established,
+    assets: &AssetCache,
+    fail_on_version_mismatch: bool,
+    shared_client_state: &SharedClientGameState,
+    control_rx: &mut flume::r#async::RecvStream<'static, Control>,
+) -> anyhow::Result<()> {
+    let Established {
+        client,
+        request_send,
+        push_recv,
+        diff_stream,
+    } = established;
+
+    // Diffs are held back by the simulated latency, if any, as they arrive on a stream of their own
+    let mut diff_stream = Delayed::new(diff_stream, || {
+        NetworkConditions::of(&shared_client_state.lock().world)
+            .map_or(Duration::ZERO, |conditions| conditions.delay())
+    });
+
     let stats_interval = 5;
     let mut stats_timer = tokio::time::interval(Duration::from_secs_f32(stats_interval as f32));
     let mut prev_stats = conn.stats();
 
-    let mut control_rx = control_rx.into_stream();
+    let mut acknowledge_timer = tokio::time::interval(ACKNOWLEDGE_INTERVAL);
 
-    while let ClientProtoState::Connected(connected) = &mut client {
+    while let ClientProtoState::Connected(connected) = &mut *client {
         tokio::select! {
             Some(frame) = push_recv.next() => {
                 client.process_push(assets, fail_on_version_mismatch, frame?)?;
@@ -349,7 +561,7 @@ async fn handle_connection(
             _ = stats_timer.tick() => {
                 let stats = conn.stats();
 
-                client.process_client_stats(&shared_client_state, NetworkStats {
+                client.process_client_stats(shared_client_state, NetworkStats {
                     latency_ms: conn.rtt().as_millis() as u64,
                     bytes_sent: (stats.udp_tx.bytes - prev_stats.udp_tx.bytes) / stats_interval,
                     bytes_received: (stats.udp_rx.bytes - prev_stats.udp_rx.bytes) / stats_interval,
@@ -359,6 +571,9 @@ async fn handle_connection(
 
                 prev_stats = stats;
             }
+            _ = acknowledge_timer.tick(), if connected.session.is_some() => {
+                request_send.send(ClientRequest::Acknowledge(connected.diffs_received())).await?;
+            }
 
            Some(control) = control_rx.next() => {
                 match control {
@@ -371,16 +586,16 @@ async fn handle_connection(
             }
 
             Ok(datagram) = conn.read_datagram() => {
-                connected.process_datagram(&shared_client_state, datagram)?;
+                connected.process_datagram(shared_client_state, datagram)?;
             }
             Ok((send, recv)) = conn.accept_bi() => {
-                connected.process_bi(&shared_client_state, send, recv);
+                connected.process_bi(shared_client_state, send, recv);
             }
             Ok(recv) = conn.accept_uni() => {
-                connected.process_uni(&shared_client_state, recv);
+                connected.process_uni(shared_client_state, recv);
             }
             Some(diff) = diff_stream.next() => {
-                connected.process_diff(&shared_client_state, diff?)?;
+                connected.process_diff(shared_client_state, diff?)?;
             }
         }
     }
@@ -4,7 +4,10 @@ use h3::quic::BidiStream;
 use h3_webtransport::server::WebTransportSession;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-use crate::{client::NetworkTransport, NetworkError, MAX_FRAME_SIZE};
+use crate::{
+    client::{NetworkTransport, TransportStats},
+    NetworkError, MAX_FRAME_SIZE,
+};
 
 impl NetworkTransport for WebTransportSession<h3_quinn::Connection, Bytes> {
     fn request_bi(&self, id: u32, data: Bytes) -> BoxFuture<Result<Bytes, NetworkError>> {
@@ -89,6 +92,17 @@ impl NetworkTransport for quinn::Connection {
 
         Box::pin(futures::future::ready(res))
     }
+
+    fn stats(&self) -> Option<TransportStats> {
+        let stats = quinn::Connection::stats(self);
+        Some(TransportStats {
+            rtt: self.rtt(),
+            bytes_sent: stats.udp_tx.bytes,
+            bytes_received: stats.udp_rx.bytes,
+            packets_sent: stats.path.sent_packets,
+            packets_lost: stats.path.lost_packets,
+        })
+    }
 }
 
 impl NetworkTransport for crate::native::client_connection::ConnectionKind {
@@ -127,4 +141,11 @@ impl NetworkTransport for crate::native::client_connection::ConnectionKind {
 
         Box::pin(futures::future::ready(res))
     }
+
+    fn stats(&self) -> Option<TransportStats> {
+        match self {
+            Self::Direct(conn) => NetworkTransport::stats(conn),
+            Self::Proxied(_) => None,
+        }
+    }
 }
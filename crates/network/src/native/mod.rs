@@ -1,10 +1,12 @@
 //! Contains native implementations of the network interface.
 //!
-//! This included quinn server+client and webtransport server using `h3`
+//! This included quinn server+client, webtransport server using `h3`, and the server of the
+//! WebSocket fallback
 pub mod client;
 pub mod client_connection;
 pub mod common;
 pub mod server;
+mod websocket;
 mod webtransport;
 
 #[cfg(feature = "tls-native-roots")]
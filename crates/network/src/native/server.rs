@@ -29,16 +29,18 @@ use tokio::time::{interval, MissedTickBehavior};
 use uuid::Uuid;
 
 use crate::{
+    mux::MuxConnection,
     native::{
-        client_connection::ConnectionKind, load_root_certs, webtransport::handle_h3_connection,
+        client_connection::ConnectionKind, load_root_certs, websocket::handle_websocket_connection,
+        webtransport::handle_h3_connection,
     },
     proto::{
-        server::{handle_diffs, ConnectionData, ServerProtoState},
+        server::{ConnectionData, ServerProtoState},
         ServerInfo, ServerPush,
     },
     server::{
-        server_stats, ForkingEvent, ProxySettings, ServerState, SharedServerState, ShutdownEvent,
-        WorldInstance, MAIN_INSTANCE_ID,
+        server_stats, ticks_per_broadcast, BroadcastIntervalKey, ForkingEvent, ProxySettings,
+        ServerState, SharedServerState, ShutdownEvent, WorldInstance, MAIN_INSTANCE_ID,
     },
     stream::{FramedRecvStream, FramedSendStream},
     ServerWorldExt,
@@ -56,6 +58,8 @@ pub struct GameServer {
     /// Shuts down the server if there are no players
     pub inactivity_shutdown: Option<Duration>,
     proxy_settings: Option<ProxySettings>,
+    websocket_tx: flume::Sender<MuxConnection>,
+    websocket_rx: flume::Receiver<MuxConnection>,
 }
 
 impl GameServer {
@@ -68,10 +72,13 @@ impl GameServer {
         let endpoint = create_server(server_addr, crypto)?;
 
         tracing::debug!("GameServer listening on port {}", server_addr.port());
+        let (websocket_tx, websocket_rx) = flume::unbounded();
         Ok(Self {
             endpoint,
             inactivity_shutdown,
             proxy_settings,
+            websocket_tx,
+            websocket_rx,
         })
     }
 
@@ -115,6 +122,7 @@ impl GameServer {
         let Self {
             endpoint,
             proxy_settings,
+            websocket_rx,
             ..
         } = self;
 
@@ -129,6 +137,7 @@ impl GameServer {
                     systems: create_server_systems(&mut world),
                     world,
                     world_stream: WorldStream::new(world_stream_filter.clone()),
+                    replication: Default::default(),
                 },
             )]
             .into_iter()
@@ -142,6 +151,8 @@ impl GameServer {
         let mut fps_counter = FpsCounter::new();
         let mut sim_interval = interval(FIXED_SERVER_TICK_TIME);
         sim_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let ticks_per_broadcast = ticks_per_broadcast(BroadcastIntervalKey.get(&assets));
+        let mut tick = 0u64;
 
         let mut inactivity_interval = interval(Duration::from_secs_f32(5.));
         let mut last_active = ambient_sys::time::Instant::now();
@@ -172,6 +183,10 @@ impl GameServer {
                     let fut = resolve_connection(conn, state.clone(), world_stream_filter.clone(), ServerBaseUrlKey.get(&assets));
                     tokio::spawn(async move {  log_result!(fut.await) });
                 }
+                Ok(conn) = websocket_rx.recv_async() => {
+                    let fut = handle_websocket_connection(conn, state.clone(), world_stream_filter.clone(), ServerBaseUrlKey.get(&assets));
+                    tokio::spawn(async move {  log_result!(fut.await) });
+                }
                 _ = sim_interval.tick() => {
                     fps_counter.frame_start();
                     let mut state = state.lock();
@@ -179,7 +194,10 @@ impl GameServer {
                         profiling::finish_frame!();
                         profiling::scope!("sim_tick");
                         state.step();
-                        state.broadcast_diffs();
+                        if tick % ticks_per_broadcast == 0 {
+                            state.broadcast_diffs();
+                        }
+                        tick += 1;
                         if let Some(sample) = fps_counter.frame_end() {
                             for instance in state.instances.values_mut() {
                                 let id = instance.world.synced_resource_entity().unwrap();
@@ -217,6 +235,13 @@ impl GameServer {
         state
     }
 
+    /// Returns the sender of the connections the server accepts over the WebSocket fallback
+    ///
+    /// The owner of each WebSocket drives its [MuxConnection]
+    pub fn websocket_acceptor(&self) -> flume::Sender<MuxConnection> {
+        self.websocket_tx.clone()
+    }
+
     /// Returns the local socket address of the endpoint
     pub fn local_addr(&self) -> SocketAddr {
         self.endpoint
@@ -297,8 +322,6 @@ async fn handle_quinn_connection(
         tracing::warn!("Client connected from remote address but server is using debug assets. This might involve uploading large files to the client.");
     }
 
-    let (diffs_tx, diffs_rx) = flume::unbounded();
-
     let server_info = ServerInfo::new(&mut state.lock(), content_base_url);
 
     let mut server = ServerProtoState::default();
@@ -309,32 +332,37 @@ async fn handle_quinn_connection(
     // Send who we are
     push_send.send(ServerPush::ServerInfo(server_info)).await?;
 
-    // Feed the channel senders to the connection data
-    //
-    // Once connected they will be added to the player entity
+    // Once connected, the connection will be added to the player entity
     let data = ConnectionData {
         conn: Arc::new(conn.clone()),
         state,
-        diff_tx: diffs_tx,
         connection_id: Uuid::new_v4(),
         world_stream_filter,
+        send_budget: Default::default(),
     };
 
     while server.is_pending_connection() {
         if let Some(frame) = request_recv.next().await {
-            server.process_control(&data, frame?)?;
+            if let Err(err) = server.process_control(&data, frame?) {
+                tracing::warn!("Refused connection: {err:#}");
+                push_send
+                    .send(ServerPush::Refused(format!("{err:#}")))
+                    .await?;
+                SinkExt::<ServerPush>::close(&mut push_send).await?;
+                return Ok(());
+            }
         }
     }
 
-    tokio::spawn(handle_diffs(
-        FramedSendStream::new(conn.open_uni().await?),
-        diffs_rx,
-    ));
+    if let ServerProtoState::Connected(connected) = &server {
+        let stream = FramedSendStream::new(conn.open_uni().await?);
+        tokio::spawn(connected.handle_diffs(&data, stream));
+    }
 
     let mut server = scopeguard::guard(server, |mut server| {
         if !server.is_disconnected() {
             tracing::info!("Connection closed abruptly from {server:?}");
-            server.process_disconnect(&data);
+            server.process_connection_lost(&data);
         }
     });
 
@@ -356,10 +384,14 @@ async fn handle_quinn_connection(
             }
             Some(msg) = connected.control_rx.next() => {
                 push_send.send(&msg).await?;
+                server.process_push(&data, &msg);
             }
         }
     }
 
+    // Deliver the last push, such as the reason the player was kicked, before the connection drops
+    SinkExt::<ServerPush>::close(&mut push_send).await.ok();
+
     tracing::info!("Client disconnected");
 
     Ok(())
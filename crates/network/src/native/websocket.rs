@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use ambient_ecs::WorldStreamFilter;
+use ambient_native_std::asset_url::AbsAssetUrl;
+use futures::{SinkExt, StreamExt};
+use uuid::Uuid;
+
+use crate::{
+    mux::MuxConnection,
+    proto::{self, server::ConnectionData, ServerInfo, ServerPush},
+    server::SharedServerState,
+    stream::{FramedRecvStream, FramedSendStream},
+};
+
+/// Serves a client connected over the WebSocket fallback, with the same protocol as
+/// [`super::webtransport::handle_h3_connection`]
+#[tracing::instrument(level = "info", skip_all)]
+pub(crate) async fn handle_websocket_connection(
+    conn: MuxConnection,
+    state: SharedServerState,
+    world_stream_filter: WorldStreamFilter,
+    content_base_url: AbsAssetUrl,
+) -> anyhow::Result<()> {
+    let server_info = ServerInfo::new(&mut state.lock(), content_base_url);
+
+    let mut server = proto::server::ServerProtoState::default();
+
+    let mut request_recv = FramedRecvStream::new(conn.accept_uni().await?);
+
+    let mut push_send = FramedSendStream::new(conn.open_uni()?);
+
+    // Send who we are
+    push_send.send(ServerPush::ServerInfo(server_info)).await?;
+
+    // Once connected, the connection will be added to the player entity
+    let data = ConnectionData {
+        conn: Arc::new(conn.clone()),
+        state,
+        connection_id: Uuid::new_v4(),
+        world_stream_filter,
+        send_budget: Default::default(),
+    };
+
+    while server.is_pending_connection() {
+        if let Some(frame) = request_recv.next().await {
+            if let Err(err) = server.process_control(&data, frame?) {
+                tracing::warn!("Refused connection: {err:#}");
+                push_send
+                    .send(ServerPush::Refused(format!("{err:#}")))
+                    .await?;
+                SinkExt::<ServerPush>::close(&mut push_send).await?;
+                return Ok(());
+            }
+        }
+    }
+
+    if let proto::server::ServerProtoState::Connected(connected) = &server {
+        let stream = FramedSendStream::new(conn.open_uni()?);
+        tokio::spawn(connected.handle_diffs(&data, stream));
+    }
+
+    let mut server = scopeguard::guard(server, |mut server| {
+        if !server.is_disconnected() {
+            tracing::info!("Connection closed abruptly from {server:?}");
+            server.process_connection_lost(&data);
+        }
+    });
+
+    // Before a connection has been established, only process the control stream
+    while let proto::server::ServerProtoState::Connected(connected) = &mut *server {
+        tokio::select! {
+            Some(frame) = request_recv.next() => {
+                server.process_control(&data, frame?)?;
+            }
+            stream = conn.accept_uni() => {
+                connected.process_uni(&data, stream?);
+            }
+            stream = conn.accept_bi() => {
+                let (send, recv) = stream?;
+                connected.process_bi(&data, send, recv);
+            }
+            datagram = conn.read_datagram() => {
+                connected.process_datagram(&data, datagram?)?;
+            }
+            Some(msg) = connected.control_rx.next() => {
+                push_send.send(&msg).await?;
+                server.process_push(&data, &msg);
+            }
+        }
+    }
+
+    // Deliver the last push, such as the reason the player was kicked, before the connection drops
+    SinkExt::<ServerPush>::close(&mut push_send).await.ok();
+
+    tracing::info!("Client disconnected");
+
+    Ok(())
+}
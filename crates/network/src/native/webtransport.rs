@@ -14,11 +14,7 @@ use http::Method;
 use uuid::Uuid;
 
 use crate::{
-    proto::{
-        self,
-        server::{handle_diffs, ConnectionData},
-        ServerInfo, ServerPush,
-    },
+    proto::{self, server::ConnectionData, ServerInfo, ServerPush},
     server::SharedServerState,
     stream::{FramedRecvStream, FramedSendStream},
     NetworkError,
@@ -86,7 +82,6 @@ async fn handle_webtransport_session(
     let conn = Arc::new(conn);
 
     let sid = conn.session_id();
-    let (diffs_tx, diffs_rx) = flume::unbounded();
 
     let server_info = ServerInfo::new(&mut state.lock(), content_base_url);
 
@@ -104,32 +99,37 @@ async fn handle_webtransport_session(
     // Send who we are
     push_send.send(ServerPush::ServerInfo(server_info)).await?;
 
-    // Feed the channel senders to the connection data
-    //
-    // Once connected they will be added to the player entity
+    // Once connected, the connection will be added to the player entity
     let data = ConnectionData {
         conn: conn.clone(),
         state,
-        diff_tx: diffs_tx,
         connection_id: Uuid::new_v4(),
         world_stream_filter,
+        send_budget: Default::default(),
     };
 
     while server.is_pending_connection() {
         if let Some(frame) = request_recv.next().await {
-            server.process_control(&data, frame?)?;
+            if let Err(err) = server.process_control(&data, frame?) {
+                tracing::warn!("Refused connection: {err:#}");
+                push_send
+                    .send(ServerPush::Refused(format!("{err:#}")))
+                    .await?;
+                SinkExt::<ServerPush>::close(&mut push_send).await?;
+                return Ok(());
+            }
         }
     }
 
-    tokio::spawn(handle_diffs(
-        FramedSendStream::new(conn.open_uni(sid).await?),
-        diffs_rx,
-    ));
+    if let proto::server::ServerProtoState::Connected(connected) = &server {
+        let stream = FramedSendStream::new(conn.open_uni(sid).await?);
+        tokio::spawn(connected.handle_diffs(&data, stream));
+    }
 
     let mut server = scopeguard::guard(server, |mut server| {
         if !server.is_disconnected() {
             tracing::info!("Connection closed abruptly from {server:?}");
-            server.process_disconnect(&data);
+            server.process_connection_lost(&data);
         }
     });
 
@@ -155,10 +155,14 @@ async fn handle_webtransport_session(
             }
             Some(msg) = connected.control_rx.next() => {
                 push_send.send(&msg).await?;
+                server.process_push(&data, &msg);
             }
         }
     }
 
+    // Deliver the last push, such as the reason the player was kicked, before the connection drops
+    SinkExt::<ServerPush>::close(&mut push_send).await.ok();
+
     tracing::info!("Client disconnected");
 
     Ok(())
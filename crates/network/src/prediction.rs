@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use ambient_ecs::{
+    generated::network::components::{last_processed_input, predicted, prediction_rewound},
+    query, Entity, EntityId, Networked, World, WorldChange,
+};
+
+/// The state of every `predicted` entity as the server last sent it, which the client rewinds
+/// the entities to before the guest replays the inputs the server hasn't processed yet
+#[derive(Debug, Default)]
+pub(crate) struct PredictionBaselines {
+    entities: HashMap<EntityId, Entity>,
+}
+
+impl PredictionBaselines {
+    /// Records the server's state of the predicted entities in `changes`, before they are
+    /// applied. Returns whether the predicted entities need to be rewound.
+    pub fn record(&mut self, changes: &[WorldChange]) -> bool {
+        let mut rewind = false;
+        for change in changes {
+            match change {
+                WorldChange::SetComponents(id, entity) | WorldChange::AddComponents(id, entity) => {
+                    rewind |= entity.contains(last_processed_input());
+                    if let Some(baseline) = self.entities.get_mut(id) {
+                        baseline.merge(entity.clone());
+                        rewind = true;
+                    }
+                }
+                WorldChange::RemoveComponents(id, descs) => {
+                    if let Some(baseline) = self.entities.get_mut(id) {
+                        for &desc in descs {
+                            baseline.remove_raw(desc);
+                        }
+                        rewind = true;
+                    }
+                }
+                WorldChange::Spawn(id, entity) => {
+                    rewind |= entity.contains(last_processed_input());
+                    if entity.contains(predicted()) {
+                        self.entities.insert(*id, entity.clone());
+                    }
+                }
+                WorldChange::Despawn(id) => {
+                    self.entities.remove(id);
+                }
+            }
+        }
+        rewind
+    }
+
+    /// Starts tracking the entities that have become predicted, stops tracking those that no
+    /// longer are, and rewinds every predicted entity to the server's state if `rewind` is set.
+    ///
+    /// The rewound entities get `prediction_rewound`, which tells the guest to replay its inputs.
+    pub fn reconcile(&mut self, world: &mut World, rewind: bool) {
+        self.entities
+            .retain(|&id, _| world.has_component(id, predicted()));
+        for (id, _) in query(()).incl(predicted()).iter(world, None) {
+            self.entities.entry(id).or_insert_with(|| {
+                let mut entity = world.clone_entity(id).unwrap_or_default();
+                entity.filter(&|desc| desc.has_attribute::<Networked>());
+                entity
+            });
+        }
+
+        if !rewind {
+            return;
+        }
+        for (&id, baseline) in &self.entities {
+            let entity = baseline.clone().with(prediction_rewound(), ());
+            if let Err(err) = world.add_components(id, entity) {
+                tracing::warn!("Failed to rewind predicted entity {id}: {err:?}");
+            }
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use ambient_ecs::{generated::network::components::is_remote_entity, Entity};
 use ambient_native_std::{
     asset_cache::{AssetCache, SyncAssetKeyExt},
     asset_url::ContentBaseUrlKey,
+    bundle::TrustedBundleKeysKey,
 };
 use anyhow::Context;
 use bytes::Bytes;
@@ -12,14 +13,17 @@ use tokio::io::AsyncReadExt;
 use tracing::{debug_span, Instrument};
 
 use crate::{
+    authority,
     bytes_ext::BufExt,
     client::{
         bi_stream_handlers, datagram_handlers, uni_stream_handlers, PlatformRecvStream,
         PlatformSendStream,
     },
     client_game_state::ClientGameState,
+    conditions::{self, NetworkConditions},
     diff_serialization::DiffSerializer,
-    log_task_result,
+    interpolation, log_task_result,
+    prediction::PredictionBaselines,
     proto::*,
 };
 
@@ -29,7 +33,37 @@ use crate::{
 #[derive(Debug)]
 pub(crate) struct ConnectedClient {
     diff_serializer: DiffSerializer,
+    prediction: PredictionBaselines,
     pub main_package_name: String,
+    /// The messages received on the diff stream of this connection
+    diffs_received: u64,
+    /// Set if the server lets the session be resumed after the connection drops
+    pub session: Option<ResumableSession>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ResumableSession {
+    pub key: u128,
+    /// How long after the connection drops the session can be resumed
+    pub grace: Duration,
+}
+
+/// The server refused the connection
+#[derive(thiserror::Error, Debug)]
+#[error("The server refused the connection: {0}")]
+pub struct Refused(pub String);
+
+/// The server kicked the player
+#[derive(thiserror::Error, Debug)]
+#[error("Kicked by the server: {0}")]
+pub struct Kicked(pub String);
+
+/// The server handed off the player to another server, which it joins with `ticket`
+#[derive(thiserror::Error, Debug)]
+#[error("Redirected to the server at {address}")]
+pub struct Redirected {
+    pub address: String,
+    pub ticket: u128,
 }
 
 #[derive(Debug)]
@@ -77,10 +111,14 @@ impl ClientProtoState {
 
                 tracing::debug!(content_base_url=?server_info.content_base_url, "Inserting content base url");
                 ContentBaseUrlKey.insert(assets, server_info.content_base_url.clone());
+                TrustedBundleKeysKey.insert(assets, Arc::new(server_info.trusted_bundle_keys));
 
                 *self = Self::Connected(ConnectedClient {
                     diff_serializer: Default::default(),
+                    prediction: Default::default(),
                     main_package_name: server_info.main_package_name,
+                    diffs_received: 0,
+                    session: None,
                 });
 
                 Ok(())
@@ -93,6 +131,28 @@ impl ClientProtoState {
                 self.process_disconnect();
                 Ok(())
             }
+            (ServerPush::Refused(reason), _) => {
+                self.process_disconnect();
+                Err(Refused(reason).into())
+            }
+            (ServerPush::Kicked(reason), _) => {
+                self.process_disconnect();
+                Err(Kicked(reason).into())
+            }
+            (ServerPush::Redirect { address, ticket }, _) => {
+                self.process_disconnect();
+                Err(Redirected { address, ticket }.into())
+            }
+            (ServerPush::Session { key, grace }, Self::Connected(_)) => {
+                if let Self::Connected(connected) = self {
+                    connected.session = Some(ResumableSession { key, grace });
+                }
+                Ok(())
+            }
+            (ServerPush::Session { .. }, _) => {
+                tracing::warn!("Received a session before connecting");
+                Ok(())
+            }
         }
     }
 
@@ -121,7 +181,6 @@ impl ClientProtoState {
     ///
     /// [`Connected`]: ClientProtoState::Connected
     #[must_use]
-    #[allow(dead_code)]
     pub(crate) fn is_connected(&self) -> bool {
         matches!(self, Self::Connected(..))
     }
@@ -133,13 +192,25 @@ impl ConnectedClient {
         state: &SharedClientGameState,
         diff: Bytes,
     ) -> anyhow::Result<()> {
-        let diff = self.diff_serializer.deserialize(diff)?;
+        let mut diff = self.diff_serializer.deserialize(diff)?;
+        self.diffs_received += 1;
         let mut gs = state.lock();
         tracing::trace!(diff=?diff.len(), "Applying diff");
+        authority::strip_owned(&gs.world, &mut diff.changes);
+        let rewind = self.prediction.record(&diff.changes);
+        let transforms = interpolation::received_transforms(&diff.changes);
         diff.apply(&mut gs.world, Entity::new().with(is_remote_entity(), ()));
+        self.prediction.reconcile(&mut gs.world, rewind);
+        interpolation::record(&mut gs.world, transforms);
         Ok(())
     }
 
+    /// The number of messages received on the diff stream of this connection, which the server
+    /// is acknowledged with
+    pub fn diffs_received(&self) -> u64 {
+        self.diffs_received
+    }
+
     /// Processes an incoming datagram
     pub fn process_datagram(
         &mut self,
@@ -159,8 +230,27 @@ impl ConnectedClient {
             .with_context(|| format!("No handler for datagram {id}"))?
             .clone();
 
-        let _span = debug_span!("handle_uni", name, id).entered();
-        handler(world, assets, data);
+        let Some(conditions) = NetworkConditions::of(world) else {
+            let _span = debug_span!("handle_uni", name, id).entered();
+            handler(world, assets, data);
+            return Ok(());
+        };
+
+        for _ in 0..conditions.datagram_copies() {
+            let (state, assets, handler, data) =
+                (state.clone(), assets.clone(), handler.clone(), data.clone());
+            let task = async move {
+                conditions::hold_back(Some(conditions)).await;
+                let _span = debug_span!("handle_uni", name, id).entered();
+                handler(&mut state.lock().world, assets, data);
+            };
+
+            let rt = ambient_sys::task::RuntimeHandle::current();
+            #[cfg(target_os = "unknown")]
+            rt.spawn_local(task);
+            #[cfg(not(target_os = "unknown"))]
+            rt.spawn(task);
+        }
 
         Ok(())
     }
@@ -173,6 +263,9 @@ impl ConnectedClient {
             async move {
                 let id = recv.read_u32().await?;
 
+                let conditions = NetworkConditions::of(&state.lock().world);
+                conditions::hold_back(conditions).await;
+
                 // The handler is returned to avoid holding the lock while the handler is running
                 let handler = {
                     let mut gs = state.lock();
@@ -219,6 +312,9 @@ impl ConnectedClient {
         let task = log_task_result(async move {
             let id = recv.read_u32().await?;
 
+            let conditions = NetworkConditions::of(&state.lock().world);
+            conditions::hold_back(conditions).await;
+
             // The handler is returned to avoid holding the lock while the handler is running
             let handler = {
                 let mut gs = state.lock();
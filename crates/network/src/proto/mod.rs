@@ -1,16 +1,48 @@
+use std::time::Duration;
+
 use ambient_core::main_package_name;
-use ambient_native_std::{ambient_version, asset_url::AbsAssetUrl};
+use ambient_native_std::{
+    ambient_version, asset_cache::SyncAssetKeyExt, asset_url::AbsAssetUrl,
+    bundle::TrustedBundleKeysKey,
+};
 
 pub mod client;
 pub mod server;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 /// Request sent by the client to the server
 pub enum ClientRequest {
     /// Connect to the server with the specified user id
     Connect(String),
     /// Client wants to disconnect
     Disconnect,
+    /// Connect to the server with the specified user id, and a token vouching for it
+    ConnectWithToken { user_id: String, token: String },
+    /// Resume the session of a connection that dropped, which had received `received` diffs
+    Resume {
+        user_id: String,
+        token: Option<String>,
+        session: u128,
+        received: u64,
+    },
+    /// The number of diffs received on this connection so far
+    Acknowledge(u64),
+    /// Join the server as `user_id`, whose player was handed off to it with `ticket`
+    Arrive {
+        user_id: String,
+        token: Option<String>,
+        ticket: u128,
+    },
+}
+
+impl ClientRequest {
+    /// The request to connect as `user_id`, presenting `token` if any
+    pub fn connect(user_id: String, token: Option<String>) -> Self {
+        match token {
+            Some(token) => Self::ConnectWithToken { user_id, token },
+            None => Self::Connect(user_id),
+        }
+    }
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -19,6 +51,20 @@ pub enum ServerPush {
     ServerInfo(ServerInfo),
     /// Graceful disconnect
     Disconnect,
+    /// The connection was refused, for the given reason
+    Refused(String),
+    /// The session can be resumed with this key for `grace` after the connection drops
+    Session {
+        key: u128,
+        grace: Duration,
+    },
+    /// The player was kicked by the server, for the given reason
+    Kicked(String),
+    /// The player was handed off to the server at `address`, which it joins with `ticket`
+    Redirect {
+        address: String,
+        ticket: u128,
+    },
 }
 
 /// Miscellaneous information about the server that needs to be sent to the client during the handshake.
@@ -35,6 +81,9 @@ pub struct ServerInfo {
     /// Defaults to the version of the crate.
     /// TODO: use semver
     pub version: String,
+
+    /// The public keys the bundles the server serves are signed with, which the client trusts
+    pub trusted_bundle_keys: Vec<Vec<u8>>,
 }
 
 impl ServerInfo {
@@ -49,6 +98,7 @@ impl ServerInfo {
             main_package_name: world.resource(main_package_name()).clone(),
             content_base_url,
             version: ambient_version().to_string(),
+            trusted_bundle_keys: TrustedBundleKeysKey.get(&state.assets).to_vec(),
         }
     }
 }
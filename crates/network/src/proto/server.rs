@@ -1,27 +1,40 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
 
 use ambient_core::player::get_by_user_id;
 use ambient_ecs::{
-    ComponentRegistry, Entity, EntityId, FrozenWorldDiff, WorldChange, WorldDiff, WorldStreamFilter,
+    ComponentRegistry, Entity, EntityId, ReplicationPriority, WorldChange, WorldDiff,
+    WorldStreamFilter,
 };
-use ambient_native_std::{fps_counter::FpsSample, log_result};
+use ambient_native_std::{asset_cache::SyncAssetKeyExt, fps_counter::FpsSample, log_result};
 use anyhow::Context;
 use bytes::Bytes;
-use futures::{future::OptionFuture, Stream, StreamExt};
+use futures::{future::OptionFuture, Future, Stream, StreamExt};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
 use tracing::{debug_span, Instrument};
 use uuid::Uuid;
 
 use crate::{
+    auth::{self, Identity},
+    authority::player_authority_sequences,
+    bandwidth::{self, SendAllowance, SendBudget},
     bytes_ext::BufExt,
     client::NetworkTransport,
+    conditions::{self, NetworkConditions},
     diff_serialization::{DiffSerializer, WorldDiffDeduplicator},
+    handoff::{self, Arrival},
     log_network_result, log_task_result,
     proto::ServerPush,
+    relevancy::Relevancy,
     server::{
-        bi_stream_handlers, create_player_entity_data, datagram_handlers, uni_stream_handlers,
+        bi_stream_handlers, create_player_entity_data, datagram_handlers,
+        player_replicated_entities, uni_stream_handlers,
     },
-    server::{SharedServerState, MAIN_INSTANCE_ID},
+    server::{ServerState, SharedServerState, MAIN_INSTANCE_ID},
+    session::{Session, SessionGraceKey},
     stream,
 };
 
@@ -49,6 +62,9 @@ pub struct ConnectedClient {
     /// Currently a random friendly_id generated by the client
     user_id: Arc<str>,
     pub control_rx: flume::r#async::RecvStream<'static, ServerPush>,
+    session: Arc<Session>,
+    /// The diffs the client received on its previous connection, if it resumed its session
+    resumed_from: Option<u64>,
 }
 
 impl std::fmt::Debug for ConnectedClient {
@@ -62,19 +78,17 @@ impl std::fmt::Debug for ConnectedClient {
 /// Holds information relevant for all states of a given connection to a client
 pub struct ConnectionData {
     pub(crate) state: SharedServerState,
-    pub(crate) diff_tx: flume::Sender<FrozenWorldDiff>,
     /// Unique identifier for this session
     /// Used to declare ownership of the player entity when multiple simultaneous connections are made or reconnected
     pub(crate) connection_id: Uuid,
     pub(crate) conn: Arc<dyn NetworkTransport>,
     pub(crate) world_stream_filter: WorldStreamFilter,
+    pub(crate) send_budget: Arc<SendBudget>,
 }
 
 impl std::fmt::Debug for ConnectionData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ConnectionData")
-            .field("diff_tx", &self.diff_tx)
-            .field("diff_tx", &self.diff_tx)
             .field("connection_id", &self.connection_id)
             .finish_non_exhaustive()
     }
@@ -85,6 +99,8 @@ pub struct Player {
     pub instance: String,
     control_tx: flume::Sender<ServerPush>,
     connection_id: Uuid,
+    /// The session of the player, unless it is local
+    session: Option<Arc<Session>>,
 }
 
 impl Player {
@@ -95,6 +111,7 @@ impl Player {
             instance: instance.into(),
             control_tx,
             connection_id: Uuid::new_v4(),
+            session: None,
         }
     }
 
@@ -118,14 +135,46 @@ impl ServerProtoState {
             }
             (ClientRequest::Connect(user_id), Self::PendingConnection) => {
                 // Connect the user
-                tracing::debug!("User connected");
-                self.process_connect(data, user_id);
-                Ok(())
+                self.process_connect(data, user_id, None, None)
+            }
+            (ClientRequest::ConnectWithToken { user_id, token }, Self::PendingConnection) => {
+                self.process_connect(data, user_id, Some(token), None)
             }
-            (ClientRequest::Connect(_), Self::Connected(_)) => {
+            (
+                ClientRequest::Arrive {
+                    user_id,
+                    token,
+                    ticket,
+                },
+                Self::PendingConnection,
+            ) => self.process_connect(data, user_id, token, Some(ticket)),
+            (
+                ClientRequest::Resume {
+                    user_id,
+                    token,
+                    session,
+                    received,
+                },
+                Self::PendingConnection,
+            ) => self.process_resume(data, user_id, token, session, received),
+            (
+                ClientRequest::Connect(_)
+                | ClientRequest::ConnectWithToken { .. }
+                | ClientRequest::Resume { .. }
+                | ClientRequest::Arrive { .. },
+                Self::Connected(_),
+            ) => {
                 tracing::warn!("Client already connected");
                 Ok(())
             }
+            (ClientRequest::Acknowledge(received), Self::Connected(connected)) => {
+                connected.session.acknowledge(data.connection_id, received);
+                Ok(())
+            }
+            (ClientRequest::Acknowledge(_), Self::PendingConnection) => {
+                tracing::warn!("Client acknowledged diffs before connecting");
+                Ok(())
+            }
             (ClientRequest::Disconnect, _) => {
                 self.process_disconnect(data);
                 Ok(())
@@ -133,18 +182,41 @@ impl ServerProtoState {
         }
     }
 
-    #[tracing::instrument(level = "debug")]
-    fn process_connect(&mut self, data: &ConnectionData, user_id: String) {
+    #[tracing::instrument(level = "debug", skip(token, ticket))]
+    fn process_connect(
+        &mut self,
+        data: &ConnectionData,
+        user_id: String,
+        token: Option<String>,
+        ticket: Option<u128>,
+    ) -> anyhow::Result<()> {
         let mut state = data.state.lock();
 
+        let identity = self.authenticate(&state, &user_id, token.as_deref())?;
+        let arrival = match ticket {
+            Some(ticket) => Some(self.arrive(&mut state, &user_id, ticket)?),
+            None => None,
+        };
+        tracing::debug!("User connected");
+
         let (control_tx, control_rx) = flume::unbounded();
 
+        let grace = SessionGraceKey.get(&state.assets);
+        let session = Session::new(data.connection_id, !grace.is_zero());
+        if !grace.is_zero() {
+            log_result!(control_tx.send(ServerPush::Session {
+                key: session.key,
+                grace
+            }));
+        }
+
         let old_player = state.players.insert(
             user_id.clone(),
             Player {
                 instance: MAIN_INSTANCE_ID.to_string(),
-                control_tx,
+                control_tx: control_tx.clone(),
                 connection_id: data.connection_id,
+                session: Some(session.clone()),
             },
         );
 
@@ -155,26 +227,56 @@ impl ServerProtoState {
         instance.broadcast_diffs();
         tracing::debug!("[{}] Creating init diff", user_id);
 
-        let diff = data.world_stream_filter.initial_diff(&instance.world);
+        // Only the entities that are relevant before the player has configured its replication
+        let relevant = Relevancy::new(&instance.world, &data.world_stream_filter)
+            .map(|relevancy| relevancy.relevant_to(&instance.world, None));
+        let diff = match &relevant {
+            Some(relevant) => {
+                Relevancy::initial_diff(&instance.world, &data.world_stream_filter, relevant)
+            }
+            None => data.world_stream_filter.initial_diff(&instance.world),
+        };
 
-        log_result!(data.diff_tx.send(diff.into()));
+        log_result!(session.diffs_tx.send(diff.into()));
         tracing::debug!("[{}] Init diff sent", user_id);
 
-        let entity_data = create_player_entity_data(
+        let mut entity_data = create_player_entity_data(
             data.conn.clone(),
             user_id.clone(),
-            data.diff_tx.clone(),
+            session.diffs_tx.clone(),
             data.connection_id,
+            data.send_budget.clone(),
+            control_tx,
         );
+        if let Some(relevant) = relevant {
+            entity_data.set(player_replicated_entities(), relevant);
+        }
+        if let Some(identity) = &identity {
+            entity_data.merge(identity.to_entity());
+        }
 
         if let Some(old_player) = old_player {
             old_player.control_tx.send(ServerPush::Disconnect).ok();
 
             let id = get_by_user_id(&instance.world, &user_id).unwrap();
 
+            // The new connection starts from the initial diff
+            let _ = instance
+                .world
+                .remove_component(id, player_replicated_entities());
+            let _ = instance
+                .world
+                .remove_component(id, player_authority_sequences());
+            Identity::remove_from(&mut instance.world, id);
             instance.world.add_components(id, entity_data).unwrap();
 
             tracing::debug!(user_id, ?id, "Player reconnected");
+        } else if let Some(Arrival { id, entity, .. }) = arrival {
+            // The player entity keeps the ID and the components it had on the other server
+            instance
+                .world
+                .spawn_with_id(id, entity.with_merge(entity_data));
+            tracing::debug!(user_id, ?id, "Player arrived from another server");
         } else {
             let id = instance.spawn_player(entity_data);
             tracing::debug!(user_id, ?id, "Player connected");
@@ -183,7 +285,120 @@ impl ServerProtoState {
         *self = Self::Connected(ConnectedClient {
             user_id: user_id.into(),
             control_rx: control_rx.into_stream(),
+            session,
+            resumed_from: None,
+        });
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(token, key))]
+    fn process_resume(
+        &mut self,
+        data: &ConnectionData,
+        user_id: String,
+        token: Option<String>,
+        key: u128,
+        received: u64,
+    ) -> anyhow::Result<()> {
+        let mut state = data.state.lock();
+
+        let identity = self.authenticate(&state, &user_id, token.as_deref())?;
+
+        let session = state
+            .players
+            .get(&user_id)
+            .and_then(|player| player.session.clone())
+            .filter(|session| session.key == key);
+        let Some(session) = session.filter(|session| session.resume(data.connection_id, received))
+        else {
+            *self = Self::Disconnected;
+            anyhow::bail!("The session of {user_id:?} can no longer be resumed");
+        };
+
+        let (control_tx, control_rx) = flume::unbounded();
+        log_result!(control_tx.send(ServerPush::Session {
+            key: session.key,
+            grace: SessionGraceKey.get(&state.assets),
+        }));
+
+        let old_player = state
+            .players
+            .get_mut(&user_id)
+            .context("The player of the session is gone")?;
+        let instance_id = old_player.instance.clone();
+        let old_player = std::mem::replace(
+            old_player,
+            Player {
+                instance: instance_id.clone(),
+                control_tx: control_tx.clone(),
+                connection_id: data.connection_id,
+                session: Some(session.clone()),
+            },
+        );
+        old_player.control_tx.send(ServerPush::Disconnect).ok();
+
+        let mut entity_data = create_player_entity_data(
+            data.conn.clone(),
+            user_id.clone(),
+            session.diffs_tx.clone(),
+            data.connection_id,
+            data.send_budget.clone(),
+            control_tx,
+        );
+        if let Some(identity) = &identity {
+            entity_data.merge(identity.to_entity());
+        }
+
+        // The player entity, and what has been replicated to it, are kept as they are
+        let instance = state
+            .instances
+            .get_mut(&instance_id)
+            .with_context(|| format!("The instance {instance_id:?} of the player is gone"))?;
+        let id = get_by_user_id(&instance.world, &user_id)
+            .context("The player entity of the session is gone")?;
+        Identity::remove_from(&mut instance.world, id);
+        instance.world.add_components(id, entity_data)?;
+
+        tracing::debug!(user_id, ?id, received, "Player resumed their session");
+
+        *self = Self::Connected(ConnectedClient {
+            user_id: user_id.into(),
+            control_rx: control_rx.into_stream(),
+            session,
+            resumed_from: Some(received),
         });
+
+        Ok(())
+    }
+
+    /// Authenticates the client connecting as `user_id` with the authenticator of the server, if
+    /// any, and disconnects it if it is refused
+    fn authenticate(
+        &mut self,
+        state: &ServerState,
+        user_id: &str,
+        token: Option<&str>,
+    ) -> anyhow::Result<Option<Identity>> {
+        auth::authenticate(&state.assets, user_id, token).or_else(|err| {
+            *self = Self::Disconnected;
+            Err(err).with_context(|| format!("Failed to authenticate {user_id:?}"))
+        })
+    }
+
+    /// Takes the arrival of the player handed off to this server with `ticket`, and disconnects the
+    /// client if it was not
+    fn arrive(
+        &mut self,
+        state: &mut ServerState,
+        user_id: &str,
+        ticket: u128,
+    ) -> anyhow::Result<Arrival> {
+        let instance = state.instances.get_mut(MAIN_INSTANCE_ID).unwrap();
+        handoff::take_arrival(&mut instance.world, user_id, ticket).ok_or_else(|| {
+            *self = Self::Disconnected;
+            anyhow::anyhow!("{user_id:?} was not handed off to this server")
+        })
     }
 
     #[tracing::instrument(level = "debug")]
@@ -216,6 +431,70 @@ impl ServerProtoState {
         *self = Self::Disconnected;
     }
 
+    /// Processes a push once it has been sent to the client
+    pub fn process_push(&mut self, data: &ConnectionData, push: &ServerPush) {
+        match push {
+            ServerPush::Kicked(reason) => {
+                tracing::info!(?reason, "Kicked the player");
+                self.process_disconnect(data);
+            }
+            ServerPush::Redirect { address, .. } => {
+                tracing::info!(?address, "Redirected the player to another server");
+                self.process_disconnect(data);
+            }
+            _ => {}
+        }
+    }
+
+    /// Processes a connection which dropped without a graceful disconnect.
+    ///
+    /// The player is kept for the grace period of the server, if any, during which the client can
+    /// resume its session.
+    #[tracing::instrument(level = "debug")]
+    pub fn process_connection_lost(&mut self, data: &ConnectionData) {
+        let grace = SessionGraceKey.get(&data.state.lock().assets);
+        let user_id = match self {
+            Self::Connected(ConnectedClient { user_id, .. }) if !grace.is_zero() => user_id.clone(),
+            _ => return self.process_disconnect(data),
+        };
+
+        let owned = data
+            .state
+            .lock()
+            .players
+            .get(&**user_id)
+            .is_some_and(|player| player.connection_id == data.connection_id);
+        if owned {
+            tracing::debug!(%user_id, ?grace, "Suspending the session of the player");
+
+            let state = data.state.clone();
+            let connection_id = data.connection_id;
+            ambient_sys::task::spawn(async move {
+                ambient_sys::time::sleep_label(grace, "session_grace").await;
+
+                let mut state = state.lock();
+                // Resuming the session hands the player over to another connection
+                if !state
+                    .players
+                    .get(&*user_id)
+                    .is_some_and(|player| player.connection_id == connection_id)
+                {
+                    return;
+                }
+
+                tracing::debug!(%user_id, "The session of the player expired");
+                let player = state.players.remove(&*user_id).unwrap();
+                if let Some(instance) = state.instances.get_mut(&player.instance) {
+                    instance.despawn_player(&user_id);
+                }
+            });
+        } else {
+            tracing::warn!("Lost ownership of player entity, ignoring disconnect");
+        }
+
+        *self = Self::Disconnected;
+    }
+
     /// Returns `true` if the server state is [`Connected`].
     ///
     /// [`Connected`]: ServerState::Connected
@@ -242,6 +521,25 @@ impl ServerProtoState {
 }
 
 impl ConnectedClient {
+    /// Sends the diffs of the session to the client over `stream`, until the connection drops or
+    /// the session is resumed by another connection
+    pub fn handle_diffs<S>(
+        &self,
+        data: &ConnectionData,
+        stream: stream::FramedSendStream<WorldDiff, S>,
+    ) -> impl Future<Output = ()>
+    where
+        S: Unpin + AsyncWrite,
+    {
+        handle_diffs(
+            stream,
+            self.session.clone(),
+            data.connection_id,
+            self.resumed_from,
+            data.send_budget.clone(),
+        )
+    }
+
     /// Processes an incoming datagram
     #[tracing::instrument(level = "debug", skip(data))]
     pub fn process_datagram(
@@ -251,11 +549,12 @@ impl ConnectedClient {
     ) -> anyhow::Result<()> {
         let id = payload.try_get_u32()?;
 
-        let ((name, handler), assets) = {
+        let ((name, handler), assets, conditions) = {
             let mut state = data.state.lock();
             let world = state
                 .get_player_world_mut(&self.user_id)
                 .context("Failed to get player world")?;
+            let conditions = NetworkConditions::of(world);
             (
                 world
                     .resource(datagram_handlers())
@@ -263,11 +562,25 @@ impl ConnectedClient {
                     .with_context(|| format!("No handler for datagram {id}"))?
                     .clone(),
                 state.assets.clone(),
+                conditions,
             )
         };
 
-        let _span = debug_span!("handle_datagram", name, id).entered();
-        handler(data.state.clone(), assets, &self.user_id, payload);
+        let Some(conditions) = conditions else {
+            let _span = debug_span!("handle_datagram", name, id).entered();
+            handler(data.state.clone(), assets, &self.user_id, payload);
+            return Ok(());
+        };
+
+        for _ in 0..conditions.datagram_copies() {
+            let (state, assets, handler) = (data.state.clone(), assets.clone(), handler.clone());
+            let (user_id, payload) = (self.user_id.clone(), payload.clone());
+            ambient_sys::task::spawn(async move {
+                conditions::hold_back(Some(conditions)).await;
+                let _span = debug_span!("handle_datagram", name, id).entered();
+                handler(state, assets, &user_id, payload);
+            });
+        }
 
         Ok(())
     }
@@ -284,11 +597,12 @@ impl ConnectedClient {
             log_task_result(async move {
                 let id = stream.read_u32().await?;
 
-                let ((name, handler), assets) = {
+                let ((name, handler), assets, conditions) = {
                     let mut state = state.lock();
                     let world = state
                         .get_player_world_mut(&user_id)
                         .context("Failed to get player world")?;
+                    let conditions = NetworkConditions::of(world);
                     (
                         world
                             .resource(uni_stream_handlers())
@@ -296,8 +610,10 @@ impl ConnectedClient {
                             .with_context(|| format!("No handler for unistream {id}"))?
                             .clone(),
                         state.assets.clone(),
+                        conditions,
                     )
                 };
+                conditions::hold_back(conditions).await;
 
                 debug_span!("handle_uni", name, id).in_scope(|| {
                     handler(state, assets, &user_id, Box::pin(stream));
@@ -322,11 +638,12 @@ impl ConnectedClient {
             log_task_result(async move {
                 let id = recv.read_u32().await?;
 
-                let ((name, handler), assets) = {
+                let ((name, handler), assets, conditions) = {
                     let mut state = state.lock();
                     let world = state
                         .get_player_world_mut(&user_id)
                         .context("Failed to get player world")?;
+                    let conditions = NetworkConditions::of(world);
                     (
                         world
                             .resource(bi_stream_handlers())
@@ -334,8 +651,10 @@ impl ConnectedClient {
                             .with_context(|| format!("No handler for bistream {id}"))?
                             .clone(),
                         state.assets.clone(),
+                        conditions,
                     )
                 };
+                conditions::hold_back(conditions).await;
 
                 debug_span!("handle_bi", name, id)
                     .in_scope(|| handler(state, assets, &user_id, Box::pin(send), Box::pin(recv)));
@@ -357,16 +676,31 @@ pub async fn handle_stats<S>(
     log_network_result!(stats.map(Ok).forward(stream).await);
 }
 
-/// Sends the world diffs over the network
-pub async fn handle_diffs<S>(
+/// How long changes held back by the send budget wait before they are reconsidered
+const HELD_CHANGES_RETRY: Duration = Duration::from_millis(50);
+
+/// Sends the world diffs of the session over the network
+async fn handle_diffs<S>(
     stream: stream::FramedSendStream<WorldDiff, S>,
-    diffs_rx: flume::Receiver<FrozenWorldDiff>,
+    session: Arc<Session>,
+    connection_id: Uuid,
+    resumed_from: Option<u64>,
+    send_budget: Arc<SendBudget>,
 ) where
     S: Unpin + AsyncWrite,
 {
+    let mut owner = session.subscribe_owner();
+    // Wait for the previous connection of the session to stop sending
+    let mut replication = session.replication.lock().await;
+    if !session.is_owned_by(connection_id) {
+        return;
+    }
+    let mut catch_up = resumed_from
+        .map(|received| replication.resume(received))
+        .filter(|diff| !diff.changes.is_empty());
+
     let (external_components_rx, external_components_trigger) =
         create_external_components_trigger();
-    external_components_trigger.as_ref()(); // make sure that external components are sent over
     ComponentRegistry::get_mut()
         .on_external_components_change
         .add(external_components_trigger.clone());
@@ -374,13 +708,13 @@ pub async fn handle_diffs<S>(
         ComponentRegistry::get_mut().on_external_components_change.remove(external_components_trigger);
     }
 
-    let mut deduplicator = WorldDiffDeduplicator::default();
     let mut serializer = DiffSerializer::default();
     #[cfg(debug_assertions)]
     let mut deserializer = DiffSerializer::default();
 
-    let mut needs_external_components = false;
-    let mut intermediate_diff = IntermediateWorldDiff::default();
+    // make sure that external components are sent over before anything else
+    let mut needs_external_components = true;
+    let mut allowance = SendAllowance::new(send_budget.clone());
 
     let mut stream = Some(stream);
     let mut sending_future = OptionFuture::default();
@@ -404,27 +738,64 @@ pub async fn handle_diffs<S>(
             _ = external_components_rx.recv_async() => {
                 needs_external_components = true;
             }
-            Ok(diff) = diffs_rx.recv_async() => {
-                intermediate_diff.merge_in(&diff);
+            Ok(diff) = session.diffs_rx.recv_async() => {
+                replication.intermediate_diff.merge_in(&diff);
+            }
+            // the session was resumed by another connection
+            _ = owner.changed() => {
+                break;
             }
+            // changes held back by the send budget are retried once it has refilled
+            _ = ambient_sys::time::sleep_label(HELD_CHANGES_RETRY, "held_world_diff"),
+                if stream.is_some()
+                    && (needs_external_components
+                        || catch_up.is_some()
+                        || !replication.intermediate_diff.is_empty()) => {}
         }
 
+        replication.forget_acknowledged(session.acknowledged());
+
         // check if we have anything to send
-        if needs_external_components || !intermediate_diff.is_empty() {
-            let Some(mut stream) = stream.take() else {
+        if needs_external_components
+            || catch_up.is_some()
+            || !replication.intermediate_diff.is_empty()
+        {
+            if stream.is_none() {
                 // stream is not available - we must be still in progress of sending the previous message
                 continue;
-            };
+            }
 
             // prepare the message to send
             let msg = if needs_external_components {
                 needs_external_components = false;
-                serializer.serialize_external_components().unwrap()
+                let msg = serializer.serialize_external_components().unwrap();
+                replication.record_sent(None, msg.len());
+                msg
+            } else if let Some(diff) = catch_up.take() {
+                // the diffs missed on the previous connection were deduplicated when first sent
+                let msg = serializer.serialize(&diff).unwrap();
+                tracing::debug!(
+                    changes = diff.changes.len(),
+                    bytes = msg.len(),
+                    "Catching up"
+                );
+                replication.record_sent(Some(diff), msg.len());
+                msg
             } else {
                 // take the merged diff, deduplicate and serialize
+                let Replication {
+                    deduplicator,
+                    intermediate_diff,
+                    ..
+                } = &mut *replication;
                 let input_diffs_count = intermediate_diff.diffs_merged;
                 let input_changes_count = intermediate_diff.changes_merged;
-                let mut diff = intermediate_diff.take();
+                let mut diff = intermediate_diff.take_admitted(allowance.admitted());
+                send_budget.set_held(intermediate_diff.set_changes.len());
+                if diff.changes.is_empty() {
+                    // everything was held back by the send budget
+                    continue;
+                }
                 let merged_changes_count = diff.changes.len();
                 deduplicator.deduplicate(&mut diff);
                 let msg = serializer.serialize(&diff).unwrap();
@@ -435,10 +806,18 @@ pub async fn handle_diffs<S>(
                     final_changes_count = diff.changes.len(),
                     bytes = msg.len(),
                 );
+                replication.record_sent(Some(diff), msg.len());
 
                 msg
             };
 
+            if std::mem::take(&mut replication.overflowed) {
+                tracing::warn!(
+                    "Too many world diffs are unacknowledged, the session can no longer be resumed"
+                );
+                session.forfeit();
+            }
+
             // make sure that what we've serialized is going to be deserialized correctly on the other side
             #[cfg(debug_assertions)]
             {
@@ -450,6 +829,9 @@ pub async fn handle_diffs<S>(
                 );
             }
 
+            allowance.spend(msg.len());
+
+            let mut stream = stream.take().unwrap();
             let span = tracing::debug_span!("send_world_diff");
             sending_future = Some(Box::pin(async move {
                 stream
@@ -473,6 +855,88 @@ fn create_external_components_trigger() -> (flume::Receiver<()>, Arc<dyn Fn() +
     )
 }
 
+/// The most diffs held on to until they are acknowledged, beyond which the session can no longer
+/// be resumed
+const MAX_UNACKNOWLEDGED_DIFFS: usize = 4096;
+/// The most bytes of diffs held on to until they are acknowledged
+const MAX_UNACKNOWLEDGED_BYTES: usize = 32 * 1024 * 1024;
+
+/// What has been replicated to the client of a session, which carries over to its next
+/// connection when the session is resumed
+#[derive(Debug)]
+pub(crate) struct Replication {
+    deduplicator: WorldDiffDeduplicator,
+    intermediate_diff: IntermediateWorldDiff,
+    /// Whether the diffs sent are held on to until acknowledged
+    resumable: bool,
+    /// The number of messages sent on the diff stream of the current connection
+    sent: u64,
+    /// The diffs sent on the current connection but not acknowledged yet, by message index, and
+    /// their size when sent
+    unacknowledged: VecDeque<(u64, WorldDiff, usize)>,
+    unacknowledged_bytes: usize,
+    /// Set when the unacknowledged diffs went over the limits and were dropped
+    overflowed: bool,
+}
+
+impl Replication {
+    pub(crate) fn new(resumable: bool) -> Self {
+        Self {
+            deduplicator: Default::default(),
+            intermediate_diff: Default::default(),
+            resumable,
+            sent: 0,
+            unacknowledged: Default::default(),
+            unacknowledged_bytes: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Records a message of `bytes` sent on the diff stream, and the diff it holds, if any.
+    ///
+    /// Once too many diffs are held on to, they are dropped and no more are held, as the client
+    /// could not catch up on them anyway.
+    fn record_sent(&mut self, diff: Option<WorldDiff>, bytes: usize) {
+        if let Some(diff) = diff.filter(|_| self.resumable) {
+            self.unacknowledged.push_back((self.sent, diff, bytes));
+            self.unacknowledged_bytes += bytes;
+
+            if self.unacknowledged.len() > MAX_UNACKNOWLEDGED_DIFFS
+                || self.unacknowledged_bytes > MAX_UNACKNOWLEDGED_BYTES
+            {
+                self.unacknowledged.clear();
+                self.unacknowledged_bytes = 0;
+                self.resumable = false;
+                self.overflowed = true;
+            }
+        }
+        self.sent += 1;
+    }
+
+    fn forget_acknowledged(&mut self, acknowledged: u64) {
+        while let Some((index, _, _)) = self.unacknowledged.front() {
+            if *index >= acknowledged {
+                break;
+            }
+            let (_, _, bytes) = self.unacknowledged.pop_front().unwrap();
+            self.unacknowledged_bytes -= bytes;
+        }
+    }
+
+    /// Starts over on a new connection, and returns the diffs that the client missed after the
+    /// `received` first ones on its previous connection, merged into one
+    fn resume(&mut self, received: u64) -> WorldDiff {
+        self.forget_acknowledged(received);
+        let mut missed = IntermediateWorldDiff::default();
+        for (_, diff, _) in self.unacknowledged.drain(..) {
+            missed.merge_in(&diff.changes);
+        }
+        self.unacknowledged_bytes = 0;
+        self.sent = 0;
+        missed.take_admitted(ReplicationPriority::Low)
+    }
+}
+
 #[derive(Debug, Default)]
 struct IntermediateWorldDiff {
     shape_changes: Vec<WorldChange>,
@@ -489,26 +953,124 @@ impl IntermediateWorldDiff {
     pub fn merge_in<'a>(&mut self, changes: impl IntoIterator<Item = &'a WorldChange> + 'a) {
         self.diffs_merged += 1;
         for change in changes.into_iter().cloned() {
-            if let WorldChange::SetComponents(id, entity) = change {
-                self.set_changes.entry(id).or_default().merge(entity);
-            } else {
-                self.shape_changes.push(change);
+            match change {
+                WorldChange::SetComponents(id, entity) => {
+                    self.set_changes.entry(id).or_default().merge(entity);
+                }
+                // Changes held back by the send budget must not outlive what they change
+                WorldChange::Despawn(id) => {
+                    self.set_changes.remove(&id);
+                    self.shape_changes.push(change);
+                }
+                WorldChange::RemoveComponents(id, ref components) => {
+                    if let Some(entity) = self.set_changes.get_mut(&id) {
+                        for &desc in components {
+                            entity.remove_raw(desc);
+                        }
+                    }
+                    self.shape_changes.push(change);
+                }
+                _ => self.shape_changes.push(change),
             }
             self.changes_merged += 1;
         }
     }
 
-    pub fn take(&mut self) -> WorldDiff {
+    /// Takes the shape changes, and the set changes with at least the `admitted` priority.
+    ///
+    /// The set changes with a lower priority are held back, and are replaced by any newer
+    /// values until they are taken.
+    pub fn take_admitted(&mut self, admitted: ReplicationPriority) -> WorldDiff {
         self.diffs_merged = 0;
         self.changes_merged = 0;
         let mut changes = Vec::new();
         std::mem::swap(&mut changes, &mut self.shape_changes);
         changes.reserve(self.set_changes.len());
-        changes.extend(
-            self.set_changes
-                .drain()
-                .map(|(id, entity)| WorldChange::SetComponents(id, entity)),
-        );
+
+        let mut held = HashMap::new();
+        for (id, mut entity) in self.set_changes.drain() {
+            if admitted > ReplicationPriority::Low {
+                let mut held_entity = Entity::new();
+                for desc in entity.components() {
+                    if bandwidth::priority(desc) < admitted {
+                        held_entity.set_entry(entity.remove_raw(desc).unwrap());
+                    }
+                }
+                if !held_entity.is_empty() {
+                    held.insert(id, held_entity);
+                }
+            }
+            if !entity.is_empty() {
+                changes.push(WorldChange::SetComponents(id, entity));
+            }
+        }
+        self.set_changes = held;
+
         WorldDiff { changes }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn despawn(id: EntityId) -> WorldDiff {
+        WorldDiff {
+            changes: vec![WorldChange::Despawn(id)],
+        }
+    }
+
+    #[test]
+    fn resume_catches_up_on_missed_diffs() {
+        let ids: Vec<_> = (0..4).map(|_| EntityId::new()).collect();
+        let mut replication = Replication::new(true);
+
+        // The external components, then one diff per entity
+        replication.record_sent(None, 0);
+        for &id in &ids {
+            replication.record_sent(Some(despawn(id)), 1);
+        }
+
+        replication.forget_acknowledged(2);
+        assert_eq!(replication.unacknowledged.len(), 3);
+
+        let missed = replication.resume(3);
+        assert!(matches!(
+            missed.changes[..],
+            [WorldChange::Despawn(a), WorldChange::Despawn(b)] if a == ids[2] && b == ids[3]
+        ));
+        assert_eq!(replication.sent, 0);
+        assert!(replication.unacknowledged.is_empty());
+    }
+
+    #[test]
+    fn diffs_are_only_held_for_resumable_sessions() {
+        let mut replication = Replication::new(false);
+        replication.record_sent(Some(despawn(EntityId::new())), 1);
+
+        assert_eq!(replication.sent, 1);
+        assert!(replication.unacknowledged.is_empty());
+    }
+
+    #[test]
+    fn too_many_unacknowledged_diffs_end_the_resume_state() {
+        let mut replication = Replication::new(true);
+        for _ in 0..MAX_UNACKNOWLEDGED_DIFFS {
+            replication.record_sent(Some(despawn(EntityId::new())), 1);
+        }
+        assert!(!replication.overflowed);
+
+        replication.record_sent(Some(despawn(EntityId::new())), 1);
+        assert!(replication.overflowed);
+        assert!(replication.unacknowledged.is_empty());
+        assert_eq!(replication.unacknowledged_bytes, 0);
+
+        // Nothing more is held on to
+        replication.record_sent(Some(despawn(EntityId::new())), 1);
+        assert!(replication.unacknowledged.is_empty());
+
+        let mut replication = Replication::new(true);
+        replication.record_sent(Some(despawn(EntityId::new())), MAX_UNACKNOWLEDGED_BYTES + 1);
+        assert!(replication.overflowed);
+    }
+}
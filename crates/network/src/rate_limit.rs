@@ -0,0 +1,198 @@
+use std::{collections::HashMap, time::Duration};
+
+use ambient_core::player::get_by_user_id;
+use ambient_ecs::{
+    components,
+    generated::network::components::{
+        message_flood_limit, message_rate_limit, messages_dropped, messages_received,
+    },
+    Component, EntityId, World,
+};
+use ambient_sys::time::Instant;
+
+use crate::server::kick_player;
+
+components!("network::rate_limit", {
+    /// The allowances of the messages of each type sent by the player
+    player_message_allowance: MessageAllowance,
+});
+
+const FLOOD_WINDOW: Duration = Duration::from_secs(1);
+/// The message types with a bucket of their own, beyond which the messages share the bucket of
+/// the unknown messages
+const MAX_BUCKETS: usize = 256;
+
+/// Token buckets holding the messages of each type sent by a player to a rate, with a burst of
+/// up to one second of messages.
+///
+/// The messages no module listens to share a single bucket, so that a player can't get around
+/// the limit by making up new names.
+#[derive(Debug, Clone)]
+pub struct MessageAllowance {
+    /// The buckets by message name, with `None` holding the unknown messages
+    buckets: HashMap<Option<String>, (f32, Instant)>,
+    /// The messages dropped since the start of the current flood window
+    dropped: u32,
+    window_start: Instant,
+}
+impl MessageAllowance {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            buckets: HashMap::new(),
+            dropped: 0,
+            window_start: now,
+        }
+    }
+
+    /// Returns whether a message of type `name` may go through, at `rate` messages per second.
+    /// `name` is `None` for the messages no module listens to
+    pub fn admit(&mut self, name: Option<&str>, rate: u32, now: Instant) -> bool {
+        let rate = rate as f32;
+        let refill = |tokens: f32, refilled: Instant| {
+            (tokens + now.duration_since(refilled).as_secs_f32() * rate).min(rate)
+        };
+
+        let mut key = name.map(str::to_owned);
+        if !self.buckets.contains_key(&key) && self.buckets.len() >= MAX_BUCKETS {
+            // A full bucket is the same as a new one, so it can go
+            self.buckets.retain(|key, (tokens, refilled)| {
+                key.is_none() || refill(*tokens, *refilled) < rate
+            });
+            if self.buckets.len() >= MAX_BUCKETS {
+                key = None;
+            }
+        }
+
+        let (tokens, refilled) = self.buckets.entry(key).or_insert((rate, now));
+        *tokens = refill(*tokens, *refilled);
+        *refilled = now;
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            return true;
+        }
+
+        if now.duration_since(self.window_start) >= FLOOD_WINDOW {
+            self.window_start = now;
+            self.dropped = 0;
+        }
+        self.dropped += 1;
+        false
+    }
+
+    /// The messages dropped within the current flood window
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+}
+
+/// Returns whether a message of type `name` sent by the player with `user_id` may be passed on
+/// to the server modules, according to its `message_rate_limit`. `known` is whether any module
+/// listens to the message; the unknown messages are limited together.
+///
+/// The player is kicked once it goes over its `message_flood_limit`.
+pub fn admit_message(world: &mut World, user_id: &str, name: &str, known: bool) -> bool {
+    let Some(id) = get_by_user_id(world, user_id) else {
+        return true;
+    };
+    let limit = |component| {
+        world
+            .get(id, component)
+            .ok()
+            .or_else(|| world.resource_opt(component).copied())
+            .filter(|limit| *limit > 0)
+    };
+    let rate = limit(message_rate_limit());
+    let flood_limit = limit(message_flood_limit());
+
+    let Some(rate) = rate else {
+        increment(world, id, messages_received());
+        return true;
+    };
+
+    let now = Instant::now();
+    if !world.has_component(id, player_message_allowance()) {
+        world
+            .add_component(id, player_message_allowance(), MessageAllowance::new(now))
+            .unwrap();
+    }
+    let allowance = world.get_mut(id, player_message_allowance()).unwrap();
+    let admitted = allowance.admit(known.then_some(name), rate, now);
+    let dropped = allowance.dropped();
+
+    if admitted {
+        increment(world, id, messages_received());
+        return true;
+    }
+
+    increment(world, id, messages_dropped());
+    if flood_limit.is_some_and(|flood_limit| dropped > flood_limit) {
+        tracing::warn!(%user_id, %name, "Kicking the player for flooding the server with messages");
+        kick_player(world, id, "Sent too many messages");
+    }
+    false
+}
+
+fn increment(world: &mut World, id: EntityId, component: Component<u64>) {
+    match world.get_mut(id, component) {
+        Ok(count) => *count += 1,
+        Err(_) => {
+            world.add_component(id, component, 1).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_limited_per_type() {
+        let start = Instant::now();
+        let mut allowance = MessageAllowance::new(start);
+
+        for _ in 0..3 {
+            assert!(allowance.admit(Some("move"), 3, start));
+        }
+        assert!(!allowance.admit(Some("move"), 3, start));
+        assert!(allowance.admit(Some("chat"), 3, start));
+        assert_eq!(allowance.dropped(), 1);
+
+        let later = start + Duration::from_millis(500);
+        assert!(allowance.admit(Some("move"), 3, later));
+        assert!(!allowance.admit(Some("move"), 3, later));
+        assert_eq!(allowance.dropped(), 2);
+
+        // The flood window starts over with the first drop after it ends
+        let next_window = start + FLOOD_WINDOW * 2;
+        for _ in 0..3 {
+            assert!(allowance.admit(Some("move"), 3, next_window));
+        }
+        assert!(!allowance.admit(Some("move"), 3, next_window));
+        assert_eq!(allowance.dropped(), 1);
+    }
+
+    #[test]
+    fn rotating_names_share_a_bucket() {
+        let now = Instant::now();
+        let mut allowance = MessageAllowance::new(now);
+
+        // Unknown messages are limited together, whatever their names
+        for _ in 0..3 {
+            assert!(allowance.admit(None, 3, now));
+        }
+        assert!(!allowance.admit(None, 3, now));
+
+        // Past the cap, new names fall back to the shared bucket too
+        for i in 0..MAX_BUCKETS {
+            allowance.admit(Some(&format!("message {i}")), 3, now);
+        }
+        assert!(!allowance.admit(Some("one more"), 3, now));
+        assert!(allowance.buckets.len() <= MAX_BUCKETS);
+
+        // Once their buckets have refilled, the names make room for new ones
+        let later = now + Duration::from_secs(1);
+        assert!(allowance.admit(Some("one more"), 3, later));
+        assert!(allowance.buckets.len() <= MAX_BUCKETS);
+    }
+}
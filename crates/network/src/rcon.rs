@@ -0,0 +1,504 @@
+//! Remote console for administering headless servers without restarting them.
+//!
+//! An admin `POST`s an [RconRequest] as JSON to `<endpoint>/rcon`, presenting the
+//! [RconSecretKey] of the server, and gets an [RconResponse] back. Besides the built-in requests,
+//! server modules can register admin commands with `RconRegisterCommand`. Running one sends an
+//! `RconCommand` to the modules, and the module which registered it answers with `RconOutput`,
+//! within [COMMAND_TIMEOUT]. A command belongs to the module which registered it until that module
+//! is unloaded; only its output is taken.
+//!
+//! Bans are kept in the [BansKey] of the server, and saved to the file in its [BansFileKey], if
+//! any, whenever they change.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use ambient_core::player::get_by_user_id;
+use ambient_ecs::{
+    components,
+    generated::{
+        network::messages::{RconCommand, RconOutput, RconRegisterCommand},
+        package::components::id as package_id,
+        wasm::components::{
+            is_module, is_module_on_server, module_enabled, module_name, package_ref,
+        },
+    },
+    query, world_events, EntityId, FnSystem, Message, Resource, SystemGroup, World,
+    WorldEventReader, WorldEventSource, WorldEventsExt,
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt},
+    download_asset::ReqwestClientKey,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::BansKey,
+    server::{kick_player, ServerState, SharedServerState, MAIN_INSTANCE_ID},
+};
+
+components!("network::rcon", {
+    /// The admin commands registered by the server modules, with the module and their description
+    @[Resource]
+    rcon_commands: BTreeMap<String, (EntityId, String)>,
+    /// The admin commands waiting for the output of a module, by request, with the module
+    @[Resource]
+    rcon_pending: HashMap<u32, (EntityId, flume::Sender<String>)>,
+});
+
+/// How long a module has to answer an admin command
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The secret admins present to the remote console. Without it, the server has no remote console
+#[derive(Debug, Clone)]
+pub struct RconSecretKey;
+impl SyncAssetKey<Arc<str>> for RconSecretKey {}
+
+/// The file the bans of the server are saved to
+#[derive(Debug, Clone)]
+pub struct BansFileKey;
+impl SyncAssetKey<PathBuf> for BansFileKey {}
+
+/// A request to the remote console of a server
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum RconRequest {
+    ListPlayers,
+    Kick {
+        user_id: String,
+        #[serde(default)]
+        reason: String,
+    },
+    /// Kicks the player if they are connected, and refuses them until they are unbanned
+    Ban {
+        user_id: String,
+        #[serde(default)]
+        reason: String,
+    },
+    Unban {
+        user_id: String,
+    },
+    ListModules,
+    SetModuleEnabled {
+        package: String,
+        module: String,
+        enabled: bool,
+    },
+    /// Lists the admin commands registered by the server modules
+    ListCommands,
+    /// Runs an admin command registered by a server module
+    Run {
+        name: String,
+        #[serde(default)]
+        args: String,
+    },
+}
+
+/// The answer of the remote console to an [RconRequest]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RconResponse {
+    Done,
+    Players(Vec<RconPlayer>),
+    Modules(Vec<RconModule>),
+    /// The admin commands, with their description
+    Commands(BTreeMap<String, String>),
+    /// The output of an admin command
+    Output(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RconPlayer {
+    pub user_id: String,
+    /// The world instance the player is in
+    pub instance: String,
+    pub entity: Option<EntityId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RconModule {
+    /// The ID of the package of the module
+    pub package: String,
+    pub name: String,
+    pub enabled: bool,
+    pub on_server: bool,
+}
+
+/// Checks the `secret` an admin presented against the one of the server
+pub fn authorize(assets: &AssetCache, secret: Option<&str>) -> anyhow::Result<()> {
+    let expected = RconSecretKey
+        .try_get(assets)
+        .context("This server has no remote console")?;
+    // Compared in constant time, so that the secret can't be guessed from how long it takes
+    let matches = secret.is_some_and(|secret| {
+        ring::constant_time::verify_slices_are_equal(secret.as_bytes(), expected.as_bytes()).is_ok()
+    });
+    anyhow::ensure!(matches, "The remote console secret does not match");
+    Ok(())
+}
+
+/// Loads the bans saved to `path` into the [BansKey], and saves them there from now on. A
+/// missing file has no bans
+pub fn load_bans(assets: &AssetCache, path: &Path) -> anyhow::Result<()> {
+    if path.exists() {
+        let bans: HashMap<String, String> = serde_json::from_slice(&std::fs::read(path)?)
+            .with_context(|| format!("Failed to parse the bans in {}", path.display()))?;
+        BansKey.get(assets).lock().extend(bans);
+    }
+    BansFileKey.insert(assets, path.to_owned());
+    Ok(())
+}
+
+fn save_bans(assets: &AssetCache, bans: &HashMap<String, String>) -> anyhow::Result<()> {
+    let Some(path) = BansFileKey.try_get(assets) else {
+        return Ok(());
+    };
+    std::fs::write(&path, serde_json::to_vec_pretty(bans)?)
+        .with_context(|| format!("Failed to save the bans to {}", path.display()))
+}
+
+/// Runs `request` on the server
+pub async fn execute(
+    state: &SharedServerState,
+    request: RconRequest,
+) -> anyhow::Result<RconResponse> {
+    let (request, output) = {
+        let mut state = state.lock();
+        match request {
+            RconRequest::Run { name, args } => {
+                let world = &mut state
+                    .instances
+                    .get_mut(MAIN_INSTANCE_ID)
+                    .context("No main instance")?
+                    .world;
+                run_command(world, name, args)?
+            }
+            request => return execute_builtin(&mut state, request),
+        }
+    };
+
+    let result = tokio::time::timeout(COMMAND_TIMEOUT, output.recv_async()).await;
+    if let Ok(Ok(output)) = result {
+        return Ok(RconResponse::Output(output));
+    }
+
+    if let Some(instance) = state.lock().instances.get_mut(MAIN_INSTANCE_ID) {
+        if let Some(pending) = instance.world.resource_mut_opt(rcon_pending()) {
+            pending.remove(&request);
+        }
+    }
+    anyhow::bail!("No module answered the command in time")
+}
+
+fn execute_builtin(state: &mut ServerState, request: RconRequest) -> anyhow::Result<RconResponse> {
+    match request {
+        RconRequest::ListPlayers => {
+            let players = state
+                .players
+                .iter()
+                .map(|(user_id, player)| RconPlayer {
+                    user_id: user_id.clone(),
+                    instance: player.instance.clone(),
+                    entity: state
+                        .get_player_world(user_id)
+                        .and_then(|world| get_by_user_id(world, user_id)),
+                })
+                .collect();
+            Ok(RconResponse::Players(players))
+        }
+        RconRequest::Kick { user_id, reason } => {
+            let world = state
+                .get_player_world(&user_id)
+                .with_context(|| format!("{user_id:?} is not connected"))?;
+            let id = get_by_user_id(world, &user_id)
+                .with_context(|| format!("{user_id:?} has no player entity"))?;
+            kick_player(world, id, reason);
+            Ok(RconResponse::Done)
+        }
+        RconRequest::Ban { user_id, reason } => {
+            {
+                let bans = BansKey.get(&state.assets);
+                let mut bans = bans.lock();
+                // Saved first, so that the ban isn't kept if it can't be saved
+                let mut banned = bans.clone();
+                banned.insert(user_id.clone(), reason.clone());
+                save_bans(&state.assets, &banned)?;
+                *bans = banned;
+            }
+            if let Some(world) = state.get_player_world(&user_id) {
+                if let Some(id) = get_by_user_id(world, &user_id) {
+                    kick_player(world, id, format!("Banned from the server: {reason}"));
+                }
+            }
+            Ok(RconResponse::Done)
+        }
+        RconRequest::Unban { user_id } => {
+            let bans = BansKey.get(&state.assets);
+            let mut bans = bans.lock();
+            let mut banned = bans.clone();
+            banned
+                .remove(&user_id)
+                .with_context(|| format!("{user_id:?} is not banned"))?;
+            save_bans(&state.assets, &banned)?;
+            *bans = banned;
+            Ok(RconResponse::Done)
+        }
+        RconRequest::ListModules => {
+            let world = main_world(state)?;
+            Ok(RconResponse::Modules(
+                modules(world)
+                    .into_iter()
+                    .map(|(_, module)| module)
+                    .collect(),
+            ))
+        }
+        RconRequest::SetModuleEnabled {
+            package,
+            module,
+            enabled,
+        } => {
+            let world = main_world(state)?;
+            let (id, _) = modules(world)
+                .into_iter()
+                .find(|(_, info)| info.package == package && info.name == module)
+                .with_context(|| format!("No module {module:?} in the package {package:?}"))?;
+            world.set(id, module_enabled(), enabled)?;
+            Ok(RconResponse::Done)
+        }
+        RconRequest::ListCommands => {
+            let world = main_world(state)?;
+            Ok(RconResponse::Commands(
+                world
+                    .resource_opt(rcon_commands())
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, (_, description))| (name.clone(), description.clone()))
+                    .collect(),
+            ))
+        }
+        RconRequest::Run { .. } => unreachable!("Commands are run asynchronously"),
+    }
+}
+
+fn main_world(state: &mut ServerState) -> anyhow::Result<&mut World> {
+    Ok(&mut state
+        .instances
+        .get_mut(MAIN_INSTANCE_ID)
+        .context("No main instance")?
+        .world)
+}
+
+fn modules(world: &World) -> Vec<(EntityId, RconModule)> {
+    query((module_name(), package_ref()))
+        .incl(is_module())
+        .iter(world, None)
+        .map(|(id, (name, package))| {
+            let module = RconModule {
+                package: world.get_cloned(*package, package_id()).unwrap_or_default(),
+                name: name.clone(),
+                enabled: world.get(id, module_enabled()).unwrap_or_default(),
+                on_server: world.has_component(id, is_module_on_server()),
+            };
+            (id, module)
+        })
+        .collect()
+}
+
+/// Sends the admin command `name` to the modules, and returns the request it was sent as, along
+/// with where its output arrives
+fn run_command(
+    world: &mut World,
+    name: String,
+    args: String,
+) -> anyhow::Result<(u32, flume::Receiver<String>)> {
+    static NEXT_REQUEST: AtomicU32 = AtomicU32::new(0);
+
+    let module = world
+        .resource_opt(rcon_commands())
+        .and_then(|commands| commands.get(&name))
+        .map(|(module, _)| *module)
+        .with_context(|| format!("No module registered the command {name:?}"))?;
+
+    let request = NEXT_REQUEST.fetch_add(1, Ordering::Relaxed);
+    let (output_tx, output_rx) = flume::bounded(1);
+    if world.resource_opt(rcon_pending()).is_none() {
+        world.add_resource(rcon_pending(), HashMap::new());
+    }
+    world
+        .resource_mut(rcon_pending())
+        .insert(request, (module, output_tx));
+    world
+        .resource_mut(world_events())
+        .add_message(RconCommand::new(request, name, args));
+
+    Ok((request, output_rx))
+}
+
+/// Forgets the admin commands of `module`, and its pending commands, once it is unloaded
+pub fn remove_module_commands(world: &mut World, module: EntityId) {
+    if let Some(commands) = world.resource_mut_opt(rcon_commands()) {
+        commands.retain(|_, (owner, _)| *owner != module);
+    }
+    if let Some(pending) = world.resource_mut_opt(rcon_pending()) {
+        pending.retain(|_, (owner, _)| *owner != module);
+    }
+}
+
+/// Returns the messages `M` sent by server modules since the last call, with their module
+fn read_module_messages<M: Message>(
+    reader: &mut WorldEventReader,
+    world: &World,
+) -> Vec<(EntityId, M)> {
+    reader
+        .iter(world.resource(world_events()))
+        .filter_map(|(_, (source, name, data))| match source {
+            WorldEventSource::Local(module) if *name == M::id() => {
+                Some((*module, M::deserialize_message(data).ok()?))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Registers the admin commands of the server modules, and passes on their output
+pub fn server_systems() -> SystemGroup {
+    let mut register_reader = WorldEventReader::new();
+    let mut output_reader = WorldEventReader::new();
+    SystemGroup::new(
+        "network/rcon",
+        vec![Box::new(FnSystem::new(move |world, _| {
+            let registered =
+                read_module_messages::<RconRegisterCommand>(&mut register_reader, world);
+            if !registered.is_empty() {
+                if world.resource_opt(rcon_commands()).is_none() {
+                    world.add_resource(rcon_commands(), BTreeMap::new());
+                }
+                let commands = world.resource_mut(rcon_commands());
+                for (module, RconRegisterCommand { name, description }) in registered {
+                    match commands.get(&name) {
+                        Some((owner, _)) if *owner != module => tracing::warn!(
+                            "Module {module} can't register the admin command {name:?}, which \
+                             belongs to module {owner}"
+                        ),
+                        _ => {
+                            commands.insert(name, (module, description));
+                        }
+                    }
+                }
+            }
+
+            let outputs = read_module_messages::<RconOutput>(&mut output_reader, world);
+            let Some(pending) = world.resource_mut_opt(rcon_pending()) else {
+                return;
+            };
+            for (module, RconOutput { request, output }) in outputs {
+                // Only the module the command was sent for can answer it
+                if pending
+                    .get(&request)
+                    .is_some_and(|(owner, _)| *owner == module)
+                {
+                    let (_, output_tx) = pending.remove(&request).unwrap();
+                    output_tx.send(output).ok();
+                }
+            }
+        }))],
+    )
+}
+
+/// Sends `request` to the remote console of the server whose HTTP interface is at `endpoint`
+pub async fn send(
+    assets: &AssetCache,
+    endpoint: &str,
+    secret: &str,
+    request: &RconRequest,
+) -> anyhow::Result<RconResponse> {
+    let response = ReqwestClientKey
+        .get(assets)
+        .post(format!("{}/rcon", endpoint.trim_end_matches('/')))
+        .bearer_auth(secret)
+        .json(request)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        anyhow::bail!("The remote console refused the request ({status}): {message}");
+    }
+
+    Ok(response.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use ambient_ecs::{Entity, FrameEvent, System, WorldContext};
+
+    use super::*;
+
+    fn send_from<M: Message>(world: &mut World, module: EntityId, message: M) {
+        world.resource_mut(world_events()).add_event((
+            WorldEventSource::Local(module),
+            M::id().to_string(),
+            message.serialize_message().unwrap(),
+        ));
+    }
+
+    #[test]
+    fn commands_belong_to_their_module() {
+        ambient_ecs::init_components();
+        init_components();
+
+        let mut world = World::new("rcon", WorldContext::Server);
+        world.add_resource(world_events(), Default::default());
+        let mut systems = server_systems();
+        let owner = Entity::new().spawn(&mut world);
+        let other = Entity::new().spawn(&mut world);
+
+        let register = |description: &str| RconRegisterCommand::new("greet", description);
+        send_from(&mut world, owner, register("Says hello"));
+        send_from(&mut world, other, register("Takes over"));
+        systems.run(&mut world, &FrameEvent);
+        assert_eq!(
+            world.resource(rcon_commands()).get("greet"),
+            Some(&(owner, "Says hello".to_string()))
+        );
+
+        let (request, output) =
+            run_command(&mut world, "greet".to_string(), String::new()).unwrap();
+        send_from(&mut world, other, RconOutput::new(request, "forged"));
+        systems.run(&mut world, &FrameEvent);
+        assert!(output.try_recv().is_err());
+
+        send_from(&mut world, owner, RconOutput::new(request, "hello"));
+        systems.run(&mut world, &FrameEvent);
+        assert_eq!(output.try_recv().unwrap(), "hello");
+
+        remove_module_commands(&mut world, owner);
+        assert!(run_command(&mut world, "greet".to_string(), String::new()).is_err());
+    }
+
+    #[test]
+    fn requests_are_tagged() {
+        let request: RconRequest =
+            serde_json::from_str(r#"{ "request": "kick", "user_id": "alice" }"#).unwrap();
+        assert_eq!(
+            request,
+            RconRequest::Kick {
+                user_id: "alice".to_string(),
+                reason: String::new(),
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&RconRequest::ListPlayers).unwrap(),
+            r#"{"request":"list_players"}"#
+        );
+    }
+}
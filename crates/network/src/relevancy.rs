@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+
+use ambient_core::player::is_player;
+use ambient_ecs::{
+    generated::{
+        hierarchy::components::parent,
+        network::components::{
+            always_replicated, interest_group, interest_groups, replication_center,
+            replication_radius,
+        },
+        transform::components::translation,
+    },
+    query, EntityId, World, WorldChange, WorldDiff, WorldStreamFilter,
+};
+use glam::Vec3;
+use itertools::Itertools;
+
+/// Hierarchies deeper than this are treated as a cycle
+const MAX_HIERARCHY_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Interest {
+    Always,
+    Group(String),
+    At(Vec3),
+}
+
+/// What each replicated entity is relevant to, which decides the players it is replicated to
+pub(crate) struct Relevancy {
+    interests: HashMap<EntityId, Interest>,
+}
+
+impl Relevancy {
+    /// Returns `None` if nothing in the world limits replication, so that every player gets
+    /// every entity
+    pub fn new(world: &World, filter: &WorldStreamFilter) -> Option<Self> {
+        let enabled = world.resource_opt(replication_radius()).is_some()
+            || query(())
+                .incl(is_player())
+                .iter(world, None)
+                .any(|(id, _)| {
+                    world.has_component(id, replication_radius())
+                        || world.has_component(id, interest_groups())
+                })
+            || query(interest_group()).iter(world, None).next().is_some();
+        if !enabled {
+            return None;
+        }
+
+        Some(Self {
+            interests: filter
+                .all_entities(world)
+                .map(|id| (id, interest(world, id)))
+                .collect(),
+        })
+    }
+
+    /// Returns the entities that are relevant to `player`, or to a player that hasn't been
+    /// spawned yet if it is `None`
+    pub fn relevant_to(&self, world: &World, player: Option<EntityId>) -> HashSet<EntityId> {
+        let radius = player
+            .and_then(|player| world.get(player, replication_radius()).ok())
+            .or_else(|| world.resource_opt(replication_radius()).copied());
+        let center = player.and_then(|player| world.get(player, replication_center()).ok());
+        let groups = player
+            .and_then(|player| world.get_ref(player, interest_groups()).ok())
+            .map_or(&[][..], |groups| groups.as_slice());
+
+        self.interests
+            .iter()
+            .filter(|(_, interest)| match interest {
+                Interest::Always => true,
+                Interest::Group(group) => groups.contains(group),
+                Interest::At(pos) => match (radius, center) {
+                    (None, _) => true,
+                    (Some(radius), Some(center)) => pos.distance_squared(center) <= radius * radius,
+                    (Some(_), None) => false,
+                },
+            })
+            .map(|(&id, _)| id)
+            .collect()
+    }
+
+    /// Spawns the `relevant` entities, for a player that has just connected
+    pub fn initial_diff(
+        world: &World,
+        filter: &WorldStreamFilter,
+        relevant: &HashSet<EntityId>,
+    ) -> WorldDiff {
+        WorldDiff {
+            changes: relevant
+                .iter()
+                .map(|&id| spawn(world, filter, id))
+                .collect_vec(),
+        }
+    }
+
+    /// Narrows the `diff` of this tick down to the entities that are `relevant` to a player, who
+    /// had the `known` entities last tick, or every entity if it is `None`.
+    ///
+    /// Entities which became relevant are spawned with all their components, and those which
+    /// stopped being relevant are despawned. Returns the diff along with the entities that
+    /// entered and left.
+    pub fn player_diff(
+        &self,
+        world: &World,
+        filter: &WorldStreamFilter,
+        diff: &WorldDiff,
+        known: Option<&HashSet<EntityId>>,
+        relevant: &HashSet<EntityId>,
+    ) -> (WorldDiff, Vec<EntityId>, Vec<EntityId>) {
+        let spawned = diff
+            .changes
+            .iter()
+            .filter_map(|change| match change {
+                WorldChange::Spawn(id, _) => Some(*id),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let knows = |id: &EntityId| match known {
+            Some(known) => known.contains(id),
+            None => self.interests.contains_key(id) && !spawned.contains(id),
+        };
+
+        let mut delivered = HashSet::new();
+        let mut changes = Vec::new();
+        for change in &diff.changes {
+            let keep = match change {
+                WorldChange::Spawn(id, _) => {
+                    let keep = relevant.contains(id);
+                    if keep {
+                        delivered.insert(*id);
+                    }
+                    keep
+                }
+                WorldChange::Despawn(id) => known.map_or(true, |known| known.contains(id)),
+                WorldChange::AddComponents(id, _)
+                | WorldChange::RemoveComponents(id, _)
+                | WorldChange::SetComponents(id, _) => {
+                    relevant.contains(id) && (knows(id) || delivered.contains(id))
+                }
+            };
+            if keep {
+                changes.push(change.clone());
+            }
+        }
+
+        let mut entered = delivered.into_iter().collect_vec();
+        for &id in relevant {
+            if !knows(&id) && !spawned.contains(&id) {
+                changes.push(spawn(world, filter, id));
+                entered.push(id);
+            }
+        }
+
+        let mut left = Vec::new();
+        let mut leave = |id: EntityId| {
+            if !relevant.contains(&id) && world.exists(id) {
+                changes.push(WorldChange::Despawn(id));
+                left.push(id);
+            }
+        };
+        match known {
+            Some(known) => known.iter().copied().for_each(&mut leave),
+            None => self
+                .interests
+                .keys()
+                .copied()
+                .filter(|id| !spawned.contains(id))
+                .for_each(&mut leave),
+        }
+
+        (WorldDiff { changes }, entered, left)
+    }
+}
+
+fn spawn(world: &World, filter: &WorldStreamFilter, id: EntityId) -> WorldChange {
+    WorldChange::Spawn(id, filter.read_entity_components(world, id).into())
+}
+
+/// Entities in a hierarchy are relevant wherever their root is
+fn interest(world: &World, mut id: EntityId) -> Interest {
+    for _ in 0..MAX_HIERARCHY_DEPTH {
+        match world.get(id, parent()) {
+            Ok(parent) if world.exists(parent) => id = parent,
+            _ => break,
+        }
+    }
+
+    if world.has_component(id, always_replicated()) || world.has_component(id, is_player()) {
+        Interest::Always
+    } else if let Ok(group) = world.get_cloned(id, interest_group()) {
+        Interest::Group(group)
+    } else if let Ok(pos) = world.get(id, translation()) {
+        Interest::At(pos)
+    } else {
+        Interest::Always
+    }
+}
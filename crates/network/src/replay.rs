@@ -0,0 +1,244 @@
+//! Replays of a server.
+//!
+//! A [ReplayRecorder] writes the state the server replicates to its clients, and the messages
+//! it exchanges with them, to a file as it goes. A [ReplayPlayer] reads that file back at the
+//! pace it was recorded, and applies it to another world, which can then be served to
+//! spectators, or inspected to debug a desync or check the outcome of a regression test.
+//!
+//! The file starts with [MAGIC] and the format version, followed by a `[length: u32][frame]`
+//! for every server tick in which anything was replicated. The first frame holds the whole
+//! replicated world.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use ambient_core::game_time;
+use ambient_ecs::{
+    components, generated::network::components::is_remote_entity, Entity, EntityId, FnSystem,
+    Resource, SystemGroup, World, WorldDiff, WorldStreamFilter,
+};
+use anyhow::Context;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+components!("network::replay", {
+    /// Records the replicated state and the networked messages of this world
+    @[Resource]
+    replay_recorder: Arc<Mutex<ReplayRecorder>>,
+    /// Plays a replay back into this world
+    @[Resource]
+    replay_player: Arc<Mutex<ReplayPlayer>>,
+});
+
+pub const MAGIC: &[u8; 8] = b"AMBRPLAY";
+const VERSION: u32 = 1;
+
+/// A message exchanged between the server and its clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayMessage {
+    /// Sent by the server to the client of `user_id`, or to every client
+    Sent {
+        user_id: Option<String>,
+        module_id: EntityId,
+        name: String,
+        data: Vec<u8>,
+    },
+    /// Sent by the client of `user_id` to the server
+    Received {
+        user_id: String,
+        module_id: EntityId,
+        name: String,
+        data: Vec<u8>,
+    },
+}
+
+/// Everything replicated during a server tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// The `game_time` of the server at the end of the tick
+    pub time: Duration,
+    pub diff: WorldDiff,
+    pub messages: Vec<ReplayMessage>,
+}
+
+#[derive(Debug)]
+pub struct ReplayRecorder {
+    file: BufWriter<File>,
+    started: bool,
+    messages: Vec<ReplayMessage>,
+}
+impl ReplayRecorder {
+    pub fn create(path: &Path) -> anyhow::Result<Self> {
+        let mut file = BufWriter::new(
+            File::create(path)
+                .with_context(|| format!("Failed to create replay {}", path.display()))?,
+        );
+        file.write_all(MAGIC)?;
+        file.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self {
+            file,
+            started: false,
+            messages: Vec::new(),
+        })
+    }
+
+    /// Writes a frame with `diff` and the messages recorded since the last frame.
+    ///
+    /// The first frame holds the whole state of `world` instead, as the clients get it when they
+    /// join.
+    pub fn record(
+        &mut self,
+        world: &World,
+        filter: &WorldStreamFilter,
+        diff: &WorldDiff,
+    ) -> anyhow::Result<()> {
+        let diff = if self.started {
+            diff.clone()
+        } else {
+            self.started = true;
+            filter.initial_diff(world)
+        };
+        if diff.is_empty() && self.messages.is_empty() {
+            return Ok(());
+        }
+
+        let frame = ReplayFrame {
+            time: world.resource_opt(game_time()).copied().unwrap_or_default(),
+            diff,
+            messages: std::mem::take(&mut self.messages),
+        };
+        let bytes = bincode::serialize(&frame)?;
+        self.file.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.file.write_all(&bytes)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// Records the message made by `message` in the replay of `world`, if it is being recorded
+pub fn record_message(world: &World, message: impl FnOnce() -> ReplayMessage) {
+    if let Some(recorder) = world.resource_opt(replay_recorder()) {
+        recorder.lock().messages.push(message());
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayPlayer {
+    file: BufReader<File>,
+    finished: bool,
+    /// The next frame, read but not yet due
+    next: Option<ReplayFrame>,
+    /// The time of the first frame, and the `game_time` of the world it was applied at
+    start: Option<(Duration, Duration)>,
+}
+impl ReplayPlayer {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let mut file = BufReader::new(
+            File::open(path)
+                .with_context(|| format!("Failed to open replay {}", path.display()))?,
+        );
+        let mut magic = [0; 8];
+        file.read_exact(&mut magic)?;
+        anyhow::ensure!(&magic == MAGIC, "{} is not a replay", path.display());
+        let mut version = [0; 4];
+        file.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        anyhow::ensure!(
+            version == VERSION,
+            "Unsupported replay version {version}, expected {VERSION}"
+        );
+
+        Ok(Self {
+            file,
+            finished: false,
+            next: None,
+            start: None,
+        })
+    }
+
+    /// Reads the next frame, or returns `None` at the end of the replay
+    pub fn next_frame(&mut self) -> anyhow::Result<Option<ReplayFrame>> {
+        if let Some(frame) = self.next.take() {
+            return Ok(Some(frame));
+        }
+        if self.finished {
+            return Ok(None);
+        }
+        let mut len = [0; 4];
+        if let Err(err) = self.file.read_exact(&mut len) {
+            self.finished = true;
+            return match err.kind() {
+                std::io::ErrorKind::UnexpectedEof => Ok(None),
+                _ => Err(err.into()),
+            };
+        }
+        let mut bytes = vec![0; u32::from_le_bytes(len) as usize];
+        self.file.read_exact(&mut bytes)?;
+        Ok(Some(bincode::deserialize(&bytes)?))
+    }
+
+    /// Applies the next frame to `world`, and returns it
+    pub fn apply_next(&mut self, world: &mut World) -> anyhow::Result<Option<ReplayFrame>> {
+        let Some(frame) = self.next_frame()? else {
+            return Ok(None);
+        };
+        frame
+            .diff
+            .clone()
+            .apply(world, Entity::new().with(is_remote_entity(), ()));
+        Ok(Some(frame))
+    }
+
+    /// Applies the frames that were recorded in the time that has passed in `world` since the
+    /// playback started, and returns them
+    pub fn advance(&mut self, world: &mut World) -> anyhow::Result<Vec<ReplayFrame>> {
+        let now = world.resource_opt(game_time()).copied().unwrap_or_default();
+        let mut applied = Vec::new();
+        while let Some(frame) = self.next_frame()? {
+            let (first, started) = *self.start.get_or_insert((frame.time, now));
+            if frame.time.saturating_sub(first) > now.saturating_sub(started) {
+                self.next = Some(frame);
+                break;
+            }
+            frame
+                .diff
+                .clone()
+                .apply(world, Entity::new().with(is_remote_entity(), ()));
+            applied.push(frame);
+        }
+        Ok(applied)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.next.is_none()
+    }
+}
+
+/// Plays the `replay_player` of the world back at the speed it was recorded
+pub fn playback_systems() -> SystemGroup {
+    SystemGroup::new(
+        "network/replay",
+        vec![Box::new(FnSystem::new(|world, _| {
+            let Some(player) = world.resource_opt(replay_player()).cloned() else {
+                return;
+            };
+            let mut player = player.lock();
+            if player.is_finished() {
+                return;
+            }
+            if let Err(err) = player.advance(world) {
+                tracing::error!("Failed to play replay back: {err:?}");
+                player.finished = true;
+                player.next = None;
+            }
+            if player.is_finished() {
+                tracing::info!("Replay finished");
+            }
+        }))],
+    )
+}
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, time::Duration};
+
+use ambient_core::game_time;
+use ambient_ecs::{
+    ComponentDesc, Entity, EntityId, Replicated, ReplicationPriority, World, WorldChange, WorldDiff,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Throttled {
+    /// The game time the component was last sent at
+    sent: Duration,
+    /// Whether it has changed since then
+    held: bool,
+}
+
+/// Holds back changes to components with a [Replicated] rate, so that each component of an
+/// entity is sent at most at that rate, always with its latest value
+#[derive(Debug, Default)]
+pub(crate) struct ReplicationThrottle {
+    entities: HashMap<EntityId, HashMap<ComponentDesc, Throttled>>,
+}
+
+impl ReplicationThrottle {
+    pub fn throttle(&mut self, world: &World, diff: WorldDiff) -> WorldDiff {
+        let now = world.resource_opt(game_time()).copied().unwrap_or_default();
+
+        let mut changes = Vec::with_capacity(diff.changes.len());
+        for change in diff.changes {
+            match change {
+                WorldChange::SetComponents(id, mut entity) => {
+                    let flush = entity.components().into_iter().any(|desc| {
+                        replicated(desc).map_or(false, |r| r.priority == ReplicationPriority::High)
+                    });
+                    let throttled = self.entities.entry(id).or_default();
+
+                    for desc in entity.components() {
+                        let Some(interval) = replicated(desc).and_then(|r| r.interval()) else {
+                            continue;
+                        };
+                        match throttled.get_mut(&desc) {
+                            Some(t) if !flush && now < t.sent + interval => {
+                                t.held = true;
+                                entity.remove_raw(desc);
+                            }
+                            _ => {
+                                throttled.insert(
+                                    desc,
+                                    Throttled {
+                                        sent: now,
+                                        held: false,
+                                    },
+                                );
+                            }
+                        }
+                    }
+
+                    // A high priority change brings the entity up to date
+                    if flush {
+                        for (&desc, t) in throttled.iter_mut().filter(|(_, t)| t.held) {
+                            if let Ok(entry) = world.get_entry(id, desc) {
+                                entity.set_entry(entry);
+                            }
+                            *t = Throttled {
+                                sent: now,
+                                held: false,
+                            };
+                        }
+                    }
+
+                    if !entity.is_empty() {
+                        changes.push(WorldChange::SetComponents(id, entity));
+                    }
+                }
+                WorldChange::Spawn(id, _) | WorldChange::Despawn(id) => {
+                    self.entities.remove(&id);
+                    changes.push(change);
+                }
+                WorldChange::AddComponents(id, ref entity) => {
+                    self.forget(id, entity.components());
+                    changes.push(change);
+                }
+                WorldChange::RemoveComponents(id, ref descs) => {
+                    self.forget(id, descs.iter().copied());
+                    changes.push(change);
+                }
+            }
+        }
+
+        // Send the latest value of the held back components whose interval has elapsed
+        for (&id, throttled) in &mut self.entities {
+            let mut entity = Entity::new();
+            for (&desc, t) in throttled.iter_mut().filter(|(_, t)| t.held) {
+                let elapsed = replicated(desc)
+                    .and_then(|r| r.interval())
+                    .map_or(true, |interval| now >= t.sent + interval);
+                if !elapsed {
+                    continue;
+                }
+                if let Ok(entry) = world.get_entry(id, desc) {
+                    entity.set_entry(entry);
+                }
+                *t = Throttled {
+                    sent: now,
+                    held: false,
+                };
+            }
+            if !entity.is_empty() {
+                changes.push(WorldChange::SetComponents(id, entity));
+            }
+        }
+        self.entities.retain(|_, throttled| !throttled.is_empty());
+
+        WorldDiff { changes }
+    }
+
+    /// Forgets the components of an entity, after their whole value has been sent or they have
+    /// been removed
+    fn forget(&mut self, id: EntityId, descs: impl IntoIterator<Item = ComponentDesc>) {
+        if let Some(throttled) = self.entities.get_mut(&id) {
+            for desc in descs {
+                throttled.remove(&desc);
+            }
+        }
+    }
+}
+
+fn replicated(desc: ComponentDesc) -> Option<Replicated> {
+    desc.attribute::<Replicated>().map(|r| *r)
+}
@@ -6,9 +6,11 @@ use ambient_rpc::RpcRegistry;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::Identity,
+    bandwidth::player_send_budget,
     server::{
-        create_player_entity_data, player_connection_id, player_entity_stream, player_transport,
-        ForkingEvent, RpcArgs as ServerRpcArgs, WorldInstance, MAIN_INSTANCE_ID,
+        create_player_entity_data, player_connection_id, player_control, player_entity_stream,
+        player_transport, ForkingEvent, RpcArgs as ServerRpcArgs, WorldInstance, MAIN_INSTANCE_ID,
     },
     ServerWorldExt,
 };
@@ -78,6 +80,7 @@ pub async fn rpc_fork_instance(
                 systems: (state.create_server_systems)(&mut world),
                 world,
                 world_stream: instance.world_stream.clone(),
+                replication: Default::default(),
             }
         };
         state.instances.insert(id.clone(), new_instance);
@@ -120,6 +123,9 @@ pub async fn rpc_join_instance(args: ServerRpcArgs, new_instance_id: String) {
     let entities_tx;
     let connection_id;
     let conn;
+    let send_budget;
+    let control_tx;
+    let identity;
 
     {
         let mut ed = instances
@@ -131,18 +137,27 @@ pub async fn rpc_join_instance(args: ServerRpcArgs, new_instance_id: String) {
 
         connection_id = ed.remove_self(player_connection_id()).unwrap();
         conn = ed.remove_self(player_transport()).unwrap();
+        send_budget = ed.remove_self(player_send_budget()).unwrap();
+        control_tx = ed.remove_self(player_control()).unwrap();
+        identity = Identity::from_entity(&ed);
     };
 
     // Borrow the new world mutably to spawn the player in with their old streams.
-    instances
-        .get_mut(&new_instance_id)
-        .unwrap()
-        .spawn_player(create_player_entity_data(
+    instances.get_mut(&new_instance_id).unwrap().spawn_player(
+        create_player_entity_data(
             conn,
             args.user_id.clone(),
             entities_tx.clone(),
             connection_id,
-        ));
+            send_budget,
+            control_tx,
+        )
+        .with_merge(
+            identity
+                .map(|identity| identity.to_entity())
+                .unwrap_or_default(),
+        ),
+    );
     state.players.get_mut(&args.user_id).unwrap().instance = new_instance_id.to_string();
 
     entities_tx.send(diff.into()).ok();
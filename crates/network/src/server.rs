@@ -1,25 +1,40 @@
-use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
-    client::NetworkTransport, proto::server::Player, DynRecv, DynSend, NetworkError,
-    RPC_BISTREAM_ID,
+    bandwidth::{player_send_budget, SendBudget},
+    client::NetworkTransport,
+    proto::{server::Player, ServerPush},
+    relevancy::Relevancy,
+    replay::replay_recorder,
+    replication::ReplicationThrottle,
+    DynRecv, DynSend, NetworkError, ServerWorldExt, RPC_BISTREAM_ID,
 };
 use ambient_core::{
-    app_start_time, name,
+    app_start_time, game_time, name,
     player::{get_by_user_id, is_player, user_id},
     FIXED_SERVER_TICK_TIME,
 };
 use ambient_ecs::{
-    components, dont_store, query, ArchetypeFilter, Entity, EntityId, FrameEvent, FrozenWorldDiff,
-    Networked, Resource, System, SystemGroup, World, WorldStream, WorldStreamFilter,
+    components, dont_store, generated::network::components::server_time, query, ArchetypeFilter,
+    Entity, EntityId, FrameEvent, FrozenWorldDiff, Networked, Resource, System, SystemGroup, World,
+    WorldStream, WorldStreamFilter,
 };
 use ambient_native_std::{
-    asset_cache::AssetCache, asset_url::AbsAssetUrl, fps_counter::FpsSample, log_result,
+    asset_cache::{AssetCache, SyncAssetKey},
+    asset_url::AbsAssetUrl,
+    fps_counter::FpsSample,
+    log_result,
 };
 use ambient_rpc::RpcRegistry;
 use ambient_sys::time::Instant;
 use bytes::Bytes;
 use flume::Sender;
+use itertools::Itertools;
 use parking_lot::Mutex;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use uuid::Uuid;
@@ -34,12 +49,39 @@ components!("network::server", {
 
     player_entity_stream: Sender<FrozenWorldDiff>,
     player_connection_id: Uuid,
+    /// The entities that are replicated to the player, while replication is limited by
+    /// `replication_radius` or `interest_group`
+    player_replicated_entities: HashSet<EntityId>,
+    /// The `(player, entered, left)` entities of every relevancy change since they were last sent
+    /// to the modules as `RelevancyChange` messages
+    @[Resource]
+    relevancy_changes: Vec<(EntityId, Vec<EntityId>, Vec<EntityId>)>,
     player_transport: Arc<dyn NetworkTransport>,
+    /// Pushes to the client of the player, through its connection handler
+    player_control: Sender<ServerPush>,
     // synced resource
     @[Networked]
     server_stats: FpsSample,
 });
 
+/// How often the server broadcasts the changes to the world to the players, rounded to a whole
+/// number of ticks. The changes of the ticks in between are coalesced into one diff. Defaults to
+/// every tick
+#[derive(Debug, Clone)]
+pub struct BroadcastIntervalKey;
+impl SyncAssetKey<Duration> for BroadcastIntervalKey {
+    fn load(&self, _assets: AssetCache) -> Duration {
+        FIXED_SERVER_TICK_TIME
+    }
+}
+
+/// The number of ticks between two broadcasts, for a broadcast `interval`
+pub fn ticks_per_broadcast(interval: Duration) -> u64 {
+    (interval.as_secs_f64() / FIXED_SERVER_TICK_TIME.as_secs_f64())
+        .round()
+        .max(1.0) as u64
+}
+
 pub type BiStreamHandler =
     Arc<dyn Fn(SharedServerState, AssetCache, &str, DynSend, DynRecv) + Sync + Send>;
 pub type UniStreamHandler = Arc<dyn Fn(SharedServerState, AssetCache, &str, DynRecv) + Sync + Send>;
@@ -62,6 +104,7 @@ pub struct WorldInstance {
     pub world: World,
     pub world_stream: WorldStream,
     pub systems: SystemGroup,
+    pub(crate) replication: ReplicationThrottle,
 }
 
 #[derive(Clone)]
@@ -80,6 +123,8 @@ pub fn create_player_entity_data(
     new_user_id: String,
     entities_tx: Sender<FrozenWorldDiff>,
     connection_id: Uuid,
+    send_budget: Arc<SendBudget>,
+    control_tx: Sender<ServerPush>,
 ) -> Entity {
     Entity::new()
         .with(name(), format!("Player {}", new_user_id))
@@ -88,9 +133,31 @@ pub fn create_player_entity_data(
         .with(player_transport(), transport)
         .with(player_entity_stream(), entities_tx)
         .with(player_connection_id(), connection_id)
+        .with(player_send_budget(), send_budget)
+        .with(player_control(), control_tx)
         .with(dont_store(), ())
 }
 
+/// Disconnects the client of the player, telling it why
+pub fn kick_player(world: &World, player_id: EntityId, reason: impl Into<String>) {
+    if let Ok(control_tx) = world.get_ref(player_id, player_control()) {
+        control_tx.send(ServerPush::Kicked(reason.into())).ok();
+    }
+}
+
+/// Sends the client of the player to the server at `address`, which it joins by presenting
+/// `ticket`
+pub fn redirect_player(world: &World, player_id: EntityId, address: &str, ticket: u128) {
+    if let Ok(control_tx) = world.get_ref(player_id, player_control()) {
+        control_tx
+            .send(ServerPush::Redirect {
+                address: address.to_owned(),
+                ticket,
+            })
+            .ok();
+    }
+}
+
 pub fn register_rpc_bi_stream_handler(
     handlers: &mut BiStreamHandlers,
     rpc_registry: RpcRegistry<RpcArgs>,
@@ -133,19 +200,90 @@ impl WorldInstance {
         ambient_core::hierarchy::despawn_recursive(&mut self.world, id)
     }
     pub fn broadcast_diffs(&mut self) {
-        let diff = self.world_stream.next_diff(&self.world);
-        if diff.is_empty() {
-            return;
+        self.stamp_server_time();
+        let diff = self
+            .replication
+            .throttle(&self.world, self.world_stream.next_diff(&self.world));
+        if let Some(recorder) = self.world.resource_opt(replay_recorder()) {
+            let result = recorder
+                .lock()
+                .record(&self.world, self.world_stream.filter(), &diff);
+            if let Err(err) = result {
+                tracing::error!("Failed to record replay: {err:?}");
+            }
         }
-        let diff: FrozenWorldDiff = diff.into();
+        let Some(relevancy) = Relevancy::new(&self.world, self.world_stream.filter()) else {
+            if diff.is_empty() {
+                return;
+            }
+            let diff: FrozenWorldDiff = diff.into();
+
+            profiling::scope!("Send MsgEntities");
+
+            for (_, (entity_stream,)) in query((player_entity_stream(),)).iter(&self.world, None) {
+                if let Err(err) = entity_stream.send(diff.clone()) {
+                    tracing::warn!("Failed to broadcast diff to player: {err:?}");
+                }
+            }
+            return;
+        };
 
-        profiling::scope!("Send MsgEntities");
+        profiling::scope!("Send relevant MsgEntities");
 
-        for (_, (entity_stream,)) in query((player_entity_stream(),)).iter(&self.world, None) {
-            if let Err(err) = entity_stream.send(diff.clone()) {
-                tracing::warn!("Failed to broadcast diff to player: {err:?}");
+        let players = query(player_entity_stream())
+            .iter(&self.world, None)
+            .map(|(id, stream)| (id, stream.clone()))
+            .collect_vec();
+        let mut changes = Vec::new();
+        for (player, entity_stream) in players {
+            let relevant = relevancy.relevant_to(&self.world, Some(player));
+            let (diff, entered, left) = relevancy.player_diff(
+                &self.world,
+                self.world_stream.filter(),
+                &diff,
+                self.world
+                    .get_ref(player, player_replicated_entities())
+                    .ok(),
+                &relevant,
+            );
+            self.world
+                .add_component(player, player_replicated_entities(), relevant)
+                .unwrap();
+
+            if !diff.is_empty() {
+                if let Err(err) = entity_stream.send(diff.into()) {
+                    tracing::warn!("Failed to send diff to player: {err:?}");
+                }
+            }
+            if !entered.is_empty() || !left.is_empty() {
+                changes.push((player, entered, left));
             }
         }
+
+        if !changes.is_empty() {
+            let mut pending = self
+                .world
+                .resource_opt(relevancy_changes())
+                .cloned()
+                .unwrap_or_default();
+            pending.extend(changes);
+            self.world.add_resource(relevancy_changes(), pending);
+        }
+    }
+    /// Records the time of the diff that is about to be sent on the synchronized resources, so
+    /// that the clients know which server time they are seeing
+    fn stamp_server_time(&mut self) {
+        let Some(id) = self.world.synced_resource_entity() else {
+            return;
+        };
+        let now = self
+            .world
+            .resource_opt(game_time())
+            .copied()
+            .unwrap_or_default();
+        if self.world.set_if_changed(id, server_time(), now).is_err() {
+            self.world.add_component(id, server_time(), now).ok();
+        }
     }
     pub fn player_count(&self) -> usize {
         query((is_player(),)).iter(&self.world, None).count()
@@ -191,6 +329,7 @@ impl ServerState {
                     world: World::new("main_server", ambient_ecs::WorldContext::Server),
                     world_stream: WorldStream::new(world_stream_filter),
                     systems: SystemGroup::new("", vec![]),
+                    replication: Default::default(),
                 },
             )]
             .into(),
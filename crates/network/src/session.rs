@@ -0,0 +1,119 @@
+//! Sessions of the players, which can outlive their connections.
+//!
+//! When the connection of a player drops without a graceful disconnect, and the server has a
+//! [SessionGraceKey] period, the player entity is kept in the world for that long, and the diffs
+//! meant for the player keep accumulating in its session. A client which reconnects in time
+//! resumes the session with its key, and only receives the diffs it missed, merged into one,
+//! instead of the whole world.
+//!
+//! To know what it missed, the client regularly acknowledges how many diffs it has received on
+//! the current connection. The server holds on to the diffs it sent until they are acknowledged,
+//! up to a limit past which the session can no longer be resumed.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use ambient_ecs::FrozenWorldDiff;
+use ambient_native_std::asset_cache::{AssetCache, SyncAssetKey};
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::{proto::server::Replication, AsyncMutex};
+
+/// How often the client acknowledges the diffs it has received
+pub const ACKNOWLEDGE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the session of a player whose connection dropped can be resumed. Sessions are not
+/// resumable when zero, which is the default
+#[derive(Debug, Clone)]
+pub struct SessionGraceKey;
+impl SyncAssetKey<Duration> for SessionGraceKey {
+    fn load(&self, _assets: AssetCache) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// The replication of the world to a player, across the connections of its client
+pub(crate) struct Session {
+    /// The key the client resumes the session with
+    pub(crate) key: u128,
+    pub(crate) diffs_tx: flume::Sender<FrozenWorldDiff>,
+    pub(crate) diffs_rx: flume::Receiver<FrozenWorldDiff>,
+    /// Held by the task sending the diffs to the current connection
+    pub(crate) replication: AsyncMutex<Replication>,
+    /// The connection the diffs are sent to
+    owner: watch::Sender<Uuid>,
+    /// The diffs received by the client on the current connection, as last acknowledged
+    acknowledged: AtomicU64,
+    /// Set once the server stopped holding on to the diffs of the session
+    forfeited: AtomicBool,
+}
+
+impl std::fmt::Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("owner", &*self.owner.borrow())
+            .field("acknowledged", &self.acknowledged)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Session {
+    pub(crate) fn new(connection_id: Uuid, resumable: bool) -> Arc<Self> {
+        let (diffs_tx, diffs_rx) = flume::unbounded();
+        Arc::new(Self {
+            key: Uuid::new_v4().as_u128(),
+            diffs_tx,
+            diffs_rx,
+            replication: AsyncMutex::new(Replication::new(resumable)),
+            owner: watch::channel(connection_id).0,
+            acknowledged: AtomicU64::new(0),
+            forfeited: AtomicBool::new(false),
+        })
+    }
+
+    pub(crate) fn is_owned_by(&self, connection_id: Uuid) -> bool {
+        *self.owner.borrow() == connection_id
+    }
+
+    /// Follows the connection the diffs are sent to
+    pub(crate) fn subscribe_owner(&self) -> watch::Receiver<Uuid> {
+        self.owner.subscribe()
+    }
+
+    /// Hands the session over to a new connection of the client, which has received `received`
+    /// diffs on its previous connection.
+    ///
+    /// Returns `false` if the client lost diffs the server no longer holds on to.
+    pub(crate) fn resume(&self, connection_id: Uuid, received: u64) -> bool {
+        if self.forfeited.load(Ordering::Relaxed)
+            || received < self.acknowledged.load(Ordering::Relaxed)
+        {
+            return false;
+        }
+
+        self.owner.send_replace(connection_id);
+        self.acknowledged.store(0, Ordering::Relaxed);
+        true
+    }
+
+    pub(crate) fn acknowledge(&self, connection_id: Uuid, received: u64) {
+        if self.is_owned_by(connection_id) {
+            self.acknowledged.fetch_max(received, Ordering::Relaxed);
+        }
+    }
+
+    /// Makes the session no longer resumable, so that the client has to join again
+    pub(crate) fn forfeit(&self) {
+        self.forfeited.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn acknowledged(&self) -> u64 {
+        self.acknowledged.load(Ordering::Relaxed)
+    }
+}
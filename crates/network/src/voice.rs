@@ -0,0 +1,286 @@
+//! Voice chat between the players of a server.
+//!
+//! Clients with the `voice_capture` resource send their microphone to the server as opus packets
+//! over unreliable datagrams. The server forwards them to the other players, which play them as a
+//! spatial sound following the speaking player.
+
+use std::sync::Arc;
+
+use ambient_core::{
+    camera::get_active_camera,
+    main_scene, name,
+    player::{get_by_user_id, local_user_id},
+    runtime,
+    transform::{get_world_position, local_to_world},
+};
+use ambient_ecs::{
+    components,
+    generated::audio::components::{
+        playing_sound, spatial_audio_emitter, spatial_audio_listener, voice_capture, voice_muted,
+    },
+    query, DynSystem, Entity, EntityId, FnSystem, SystemGroup, World,
+};
+use ambient_native_std::asset_cache::AssetCache;
+use ambient_world_audio::{
+    audio_buses, audio_emitter, audio_listener,
+    core::{
+        voice::{VoiceBuffer, VoiceDecoder, VoiceEncoder, VoiceStream},
+        AudioCapture, AudioEmitter, AudioListener, SendHandle, Source, VOICE_BUS,
+    },
+    hrtf_lib, reverb_sends, sound_bus, sound_id,
+    systems::{DEFAULT_ATTENUATION, Y_UP_LHS},
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use itertools::Itertools;
+use parking_lot::Mutex;
+
+use crate::{
+    bytes_ext::BufExt,
+    client::{self, client_state},
+    log_network_result,
+    server::{self, player_transport, SharedServerState},
+    VOICE_DATAGRAM_ID,
+};
+
+components!("network::voice", {
+    /// The player whose voice this entity plays
+    voice_speaker: EntityId,
+    /// Decodes the voice packets of `voice_speaker` into the sound this entity plays
+    voice_decoder: Arc<Mutex<VoiceDecoder>>,
+});
+
+/// Forwards the voice of a player to the other players in the same world.
+///
+/// Client to server: `[sequence: u32][opus packet]`.
+/// Server to client: `[speaker: u128][sequence: u32][opus packet]`.
+pub fn register_server_datagram_handler(handlers: &mut server::DatagramHandlers) {
+    handlers.insert(
+        VOICE_DATAGRAM_ID,
+        ("server_voice_datagram", Arc::new(on_server_datagram)),
+    );
+}
+
+/// Plays the voices forwarded by the server
+pub fn register_client_datagram_handler(handlers: &mut client::DatagramHandlers) {
+    handlers.insert(
+        VOICE_DATAGRAM_ID,
+        ("client_voice_datagram", Arc::new(on_client_datagram)),
+    );
+}
+
+fn on_server_datagram(state: SharedServerState, _assets: AssetCache, user_id: &str, data: Bytes) {
+    let mut state = state.lock();
+    let Some(world) = state.get_player_world_mut(user_id) else {
+        tracing::warn!("Failed to find player world for {user_id} when processing voice");
+        return;
+    };
+    let Some(speaker) = get_by_user_id(world, user_id) else {
+        return;
+    };
+    if world.has_component(speaker, voice_muted()) {
+        return;
+    }
+
+    let mut payload = BytesMut::new();
+    payload.put_u128(speaker.0);
+    payload.extend_from_slice(&data);
+    let payload = payload.freeze();
+
+    let runtime = world.resource(runtime());
+    for (id, transport) in query(player_transport()).iter(world, None) {
+        if id == speaker {
+            continue;
+        }
+        let transport = transport.clone();
+        let payload = payload.clone();
+        runtime.spawn(async move {
+            log_network_result!(transport.send_datagram(VOICE_DATAGRAM_ID, payload).await);
+        });
+    }
+}
+
+fn on_client_datagram(world: &mut World, _assets: AssetCache, data: Bytes) {
+    log_network_result!(play_voice(world, data));
+}
+
+fn play_voice(world: &mut World, mut data: Bytes) -> anyhow::Result<()> {
+    let speaker = EntityId(data.try_get_u128()?);
+    let sequence = data.try_get_u32()?;
+    if !world.exists(speaker) || world.has_component(speaker, voice_muted()) {
+        return Ok(());
+    }
+
+    let existing = query(voice_speaker())
+        .iter(world, None)
+        .find(|(_, &id)| id == speaker)
+        .map(|(id, _)| id);
+    let decoder = match existing {
+        Some(id) => world.get_cloned(id, voice_decoder())?,
+        None => match spawn_voice(world, speaker)? {
+            Some(decoder) => decoder,
+            None => return Ok(()),
+        },
+    };
+    decoder.lock().decode(sequence, &data)?;
+    Ok(())
+}
+
+/// Spawns the entity playing the voice of `speaker`, unless audio is disabled
+fn spawn_voice(
+    world: &mut World,
+    speaker: EntityId,
+) -> anyhow::Result<Option<Arc<Mutex<VoiceDecoder>>>> {
+    let Some(bus) = world
+        .resource_opt(audio_buses())
+        .map(|buses| buses.get_or_create(VOICE_BUS))
+    else {
+        return Ok(None);
+    };
+    let Some(camera) = local_camera(world) else {
+        return Ok(None);
+    };
+
+    let buffer = VoiceBuffer::new();
+    let decoder = Arc::new(Mutex::new(VoiceDecoder::new(buffer.clone())?));
+    let listener = Arc::new(Mutex::new(AudioListener::new(
+        Y_UP_LHS * world.get(camera, local_to_world()).unwrap_or_default(),
+        glam::Vec3::X * 0.3,
+    )));
+    let emitter = Arc::new(Mutex::new(AudioEmitter {
+        amplitude: 1.0,
+        attenuation: DEFAULT_ATTENUATION,
+        pos: get_world_position(world, speaker).unwrap_or_default(),
+    }));
+    let sends = SendHandle::default();
+
+    let source = VoiceStream::new(buffer)
+        .spatial(
+            world.resource(hrtf_lib()),
+            listener.clone(),
+            emitter.clone(),
+        )
+        .effect_send(sends.clone());
+    let sound = bus.play(source);
+
+    Entity::new()
+        .with(name(), format!("Voice of {speaker}"))
+        .with(playing_sound(), ())
+        .with(audio_emitter(), emitter)
+        .with(audio_listener(), listener)
+        .with(spatial_audio_emitter(), speaker)
+        .with(spatial_audio_listener(), camera)
+        .with(reverb_sends(), sends)
+        .with(sound_id(), sound.id)
+        .with(sound_bus(), bus)
+        .with(voice_speaker(), speaker)
+        .with(voice_decoder(), decoder.clone())
+        .spawn(world);
+
+    Ok(Some(decoder))
+}
+
+fn local_camera(world: &World) -> Option<EntityId> {
+    let user_id = world.resource_opt(local_user_id())?;
+    get_active_camera(world, main_scene(), Some(user_id))
+}
+
+/// The microphone capture of the local player while `voice_capture` is set
+struct VoiceTransmitter {
+    capture: AudioCapture,
+    encoder: VoiceEncoder,
+    sequence: u32,
+}
+
+impl VoiceTransmitter {
+    fn new() -> Result<Self, ambient_world_audio::core::Error> {
+        let capture = AudioCapture::new()?;
+        let encoder = VoiceEncoder::new(capture.sample_rate())?;
+        Ok(Self {
+            capture,
+            encoder,
+            sequence: 0,
+        })
+    }
+}
+
+pub fn voice_systems() -> SystemGroup {
+    SystemGroup::new(
+        "voice",
+        vec![
+            voice_capture_system(),
+            // Stops the voices of players who left or were muted, and keeps them heard from the
+            // active camera
+            query(voice_speaker()).to_system(|q, world, qs, _| {
+                let camera = local_camera(world);
+                for (id, speaker) in q.collect_cloned(world, qs) {
+                    if !world.exists(speaker) || world.has_component(speaker, voice_muted()) {
+                        world.despawn(id);
+                    } else if let Some(camera) = camera {
+                        world
+                            .add_component(id, spatial_audio_listener(), camera)
+                            .unwrap();
+                    }
+                }
+            }),
+        ],
+    )
+}
+
+/// Captures and sends the voice of the local player while the `voice_capture` resource is set
+fn voice_capture_system() -> DynSystem {
+    let transmitter = Mutex::new(None::<VoiceTransmitter>);
+    Box::new(FnSystem::new(move |world, _| {
+        let mut transmitter = transmitter.lock();
+        if !world.has_component(world.resource_entity(), voice_capture()) {
+            *transmitter = None;
+            return;
+        }
+        let Some(transport) = world
+            .resource_opt(client_state())
+            .and_then(|state| state.as_ref())
+            .map(|state| state.transport.clone())
+        else {
+            return;
+        };
+
+        if transmitter.is_none() {
+            match VoiceTransmitter::new() {
+                Ok(new) => *transmitter = Some(new),
+                Err(err) => {
+                    tracing::warn!("Failed to start capturing voice: {err}");
+                    world
+                        .remove_component(world.resource_entity(), voice_capture())
+                        .unwrap();
+                    return;
+                }
+            }
+        }
+        let transmitter = transmitter.as_mut().unwrap();
+
+        let frames = transmitter.capture.drain().collect_vec();
+        let packets = match transmitter.encoder.push(frames) {
+            Ok(packets) => packets,
+            Err(err) => {
+                tracing::warn!("Failed to encode voice: {err}");
+                return;
+            }
+        };
+
+        let runtime = world.resource(runtime());
+        for packet in packets {
+            let mut payload = BytesMut::new();
+            payload.put_u32(transmitter.sequence);
+            payload.extend_from_slice(&packet);
+            transmitter.sequence = transmitter.sequence.wrapping_add(1);
+
+            let transport = transport.clone();
+            runtime.spawn(async move {
+                log_network_result!(
+                    transport
+                        .send_datagram(VOICE_DATAGRAM_ID, payload.freeze())
+                        .await
+                );
+            });
+        }
+    }))
+}
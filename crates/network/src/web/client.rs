@@ -26,15 +26,19 @@ use url::Url;
 use crate::{
     client::{CleanupFunc, ClientState, Control, GameClientRenderTarget, LoadedFunc},
     client_game_state::{game_screen_render_target, ClientGameState},
+    conditions::{Delayed, NetworkConditions},
     log_network_result,
     proto::{
-        client::{ClientProtoState, SharedClientGameState},
-        ClientRequest,
+        client::{ClientProtoState, Refused, SharedClientGameState},
+        ClientRequest, ServerPush,
     },
     server::RpcArgs,
+    session::ACKNOWLEDGE_INTERVAL,
     stream::{FramedRecvStream, FramedSendStream, RawFramedRecvStream},
-    web::WebTransportProxy,
-    webtransport::Connection,
+    web::{
+        connection::{Connection, RecvStream, SendStream},
+        WebTransportProxy,
+    },
     NetworkError,
 };
 
@@ -47,7 +51,11 @@ const MAX_ACCUMMULATED_FRAME_DELAY: u32 = 3;
 pub struct GameClientView {
     /// The url to connect to
     pub url: String,
+    /// The url of the WebSocket fallback, used when WebTransport is not available
+    pub websocket_url: Option<String>,
     pub user_id: String,
+    /// The token vouching for `user_id`, for servers which authenticate their players
+    pub token: Option<String>,
     pub fail_on_version_mismatch: bool,
     pub systems_and_resources: Cb<dyn Fn() -> (SystemGroup, Entity) + Sync + Send>,
     /// Invoked when the game client is loaded
@@ -63,7 +71,9 @@ impl ElementComponent for GameClientView {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self {
             url,
+            websocket_url,
             user_id,
+            token,
             fail_on_version_mismatch,
             systems_and_resources,
             on_loaded,
@@ -109,21 +119,17 @@ impl ElementComponent for GameClientView {
                     url = resolve_hosted_server(&assets, url).await?;
                 }
 
-                let conn = Connection::connect(&url.as_str()).await.with_context(|| {
-                    format!("Failed to establish a WebTransport session for \"{url}\"")
-                })?;
-
-                tracing::debug!("Established WebTransport session");
-
                 let (proxy_tx, proxy_rx) = flume::bounded(32);
 
                 let mut proxy_tx = Some(WebTransportProxy::new(proxy_tx));
                 // Create a handle for the game client
 
                 handle_connection(
-                    conn,
+                    url,
+                    websocket_url,
                     &assets,
                     user_id,
+                    token,
                     fail_on_version_mismatch,
                     move |assets, user_id| {
                         let (systems, resources) = systems_and_resources();
@@ -365,10 +371,21 @@ fn run_game_logic(
     });
 }
 
+/// The streams of a connection which the server has accepted
+struct Established {
+    client: ClientProtoState,
+    request_send: FramedSendStream<ClientRequest, SendStream>,
+    push_recv: FramedRecvStream<ServerPush, RecvStream>,
+    diff_stream: RawFramedRecvStream<RecvStream>,
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_connection(
-    mut conn: Connection,
+    url: Url,
+    websocket_url: Option<String>,
     assets: &AssetCache,
     user_id: String,
+    token: Option<String>,
     fail_on_version_mismatch: bool,
     mut on_loaded: impl FnMut(&AssetCache, &str) -> anyhow::Result<(SharedClientGameState, CleanupFunc)>,
     control_rx: flume::Receiver<Control>,
@@ -376,19 +393,128 @@ async fn handle_connection(
 ) -> anyhow::Result<()> {
     let runtime = RuntimeKey.get(&assets);
 
-    let mut request_send = FramedSendStream::new(conn.open_uni().await?);
-
-    // Accept the diff and stat stream
-    // Nothing is read from them until the connection has been accepted
+    let (url, websocket_url) = (&url, websocket_url.as_deref());
+    let connect = move || async move {
+        let conn = Connection::connect(url.as_str(), websocket_url)
+            .await
+            .with_context(|| format!("Failed to establish a session for \"{url}\""))?;
+        tracing::debug!("Established session");
+        anyhow::Ok(conn)
+    };
+    let mut conn = connect().await?;
 
     // Send a connection request
     tracing::debug!("Attempting to connect using {user_id:?}");
 
-    request_send
-        .send(ClientRequest::Connect(user_id.clone()))
-        .await?;
+    let request = ClientRequest::connect(user_id.clone(), token.clone());
+    let Some(mut established) =
+        establish(&conn, assets, &user_id, request, fail_on_version_mismatch).await?
+    else {
+        tracing::warn!("Connection failed or was denied");
+        return Ok(());
+    };
+
+    let (shared_client_state, cleanup) = on_loaded(&assets, &user_id)?;
+    let on_disconnect = move || cleanup();
+
+    scopeguard::defer!(on_disconnect());
+
+    let mut control_rx = control_rx.into_stream();
+    let mut proxy_rx = proxy_rx.into_stream();
+
+    tracing::info!("Client connected");
+
+    loop {
+        let result = run_connection(
+            &mut conn,
+            &mut established,
+            assets,
+            &runtime,
+            fail_on_version_mismatch,
+            &shared_client_state,
+            &mut control_rx,
+            &mut proxy_rx,
+        )
+        .await;
+
+        let err = match result {
+            Ok(()) => break,
+            Err(err) => err,
+        };
+
+        // Only a connection which dropped can be resumed
+        let ClientProtoState::Connected(connected) = &established.client else {
+            return Err(err);
+        };
+        let Some(session) = connected
+            .session
+            .filter(|_| err.downcast_ref::<NetworkError>().is_some())
+        else {
+            return Err(err);
+        };
+        tracing::warn!("Connection lost, resuming the session: {err:?}");
+
+        let request = ClientRequest::Resume {
+            user_id: user_id.clone(),
+            token: token.clone(),
+            session: session.key,
+            received: connected.diffs_received(),
+        };
+        let deadline = Instant::now() + session.grace;
+        (conn, established) = loop {
+            let attempt = async {
+                let conn = connect().await?;
+                let established = establish(
+                    &conn,
+                    assets,
+                    &user_id,
+                    request.clone(),
+                    fail_on_version_mismatch,
+                )
+                .await?
+                .context("The server disconnected while resuming the session")?;
+                anyhow::Ok((conn, established))
+            };
+
+            match attempt.await {
+                Ok(resumed) => break resumed,
+                Err(err)
+                    if err.downcast_ref::<Refused>().is_none() && Instant::now() < deadline =>
+                {
+                    tracing::warn!("Failed to resume the session, retrying: {err:?}");
+                    ambient_sys::time::sleep_label(RESUME_RETRY_INTERVAL, "resume_session").await;
+                }
+                Err(err) => return Err(err.context("Failed to resume the session")),
+            }
+        };
+
+        tracing::info!("Session resumed");
+    }
+
+    tracing::debug!("Client entered disconnected state");
+    Ok(())
+}
+
+/// How long to wait between attempts to resume a session
+const RESUME_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sends `request` to the server, and waits for it to accept the connection.
+///
+/// Returns `None` if the server disconnected the client instead.
+async fn establish(
+    conn: &Connection,
+    assets: &AssetCache,
+    user_id: &str,
+    request: ClientRequest,
+    fail_on_version_mismatch: bool,
+) -> anyhow::Result<Option<Established>> {
+    let mut request_send = FramedSendStream::new(conn.open_uni().await?);
+
+    // Accept the diff and stat stream
+    // Nothing is read from them until the connection has been accepted
+    request_send.send(request).await?;
 
-    let mut client = ClientProtoState::Pending(user_id.clone());
+    let mut client = ClientProtoState::Pending(user_id.to_owned());
 
     let mut push_recv = FramedRecvStream::new(
         conn.accept_uni()
@@ -399,39 +525,72 @@ async fn handle_connection(
     while client.is_pending() {
         tracing::info!("Waiting for server to accept connection and send server info");
         if let Some(frame) = push_recv.next().await {
-            client.process_push(&assets, fail_on_version_mismatch, frame?)?;
+            client.process_push(assets, fail_on_version_mismatch, frame?)?;
         }
     }
 
     if !client.is_connected() {
-        tracing::warn!("Connection failed or was denied");
-        return Ok(());
+        return Ok(None);
     }
 
-    let mut diff_stream = RawFramedRecvStream::new(
-        conn.accept_uni()
-            .await
-            .ok_or(NetworkError::ConnectionClosed)??,
-    );
-
-    let (shared_client_state, cleanup) = on_loaded(&assets, &user_id)?;
-    let on_disconnect = move || cleanup();
-
-    scopeguard::defer!(on_disconnect());
+    let diff_stream = tokio::select! {
+        stream = conn.accept_uni() => RawFramedRecvStream::new(
+            stream.ok_or(NetworkError::ConnectionClosed)??,
+        ),
+        // The server can still refuse the connection before sending the world
+        Some(frame) = push_recv.next() => {
+            client.process_push(assets, fail_on_version_mismatch, frame?)?;
+            return Ok(None);
+        }
+    };
+
+    Ok(Some(Established {
+        client,
+        request_send,
+        push_recv,
+        diff_stream,
+    }))
+}
 
-    let mut control_rx = control_rx.into_stream();
-    let mut proxy_rx = proxy_rx.into_stream();
+/// Runs the client on an established connection, until it disconnects
+#[allow(clippy::too_many_arguments)]
+async fn run_connection(
+    conn: &mut Connection,
+    established: &mut Established,
+    assets: &AssetCache,
+    runtime: &RuntimeHandle,
+    fail_on_version_mismatch: bool,
+    shared_client_state: &SharedClientGameState,
+    control_rx: &mut flume::r#async::RecvStream<'static, Control>,
+    proxy_rx: &mut flume::r#async::RecvStream<'static, ProxyMessage>,
+) -> anyhow::Result<()> {
+    let Established {
+        client,
+        request_send,
+        push_recv,
+        diff_stream,
+    } = established;
+
+    // Diffs are held back by the simulated latency, if any, as they arrive on a stream of their own
+    let mut diff_stream = Delayed::new(diff_stream, || {
+        NetworkConditions::of(&shared_client_state.lock().world)
+            .map_or(Duration::ZERO, |conditions| conditions.delay())
+    });
 
-    tracing::info!("Client connected");
+    let mut acknowledge_timer = ambient_sys::time::interval(ACKNOWLEDGE_INTERVAL);
 
-    while let ClientProtoState::Connected(connected) = &mut client {
+    while let ClientProtoState::Connected(connected) = &mut *client {
         tokio::select! {
             Some(frame) = push_recv.next() => {
-                client.process_push(&assets, fail_on_version_mismatch, frame?)?;
+                client.process_push(assets, fail_on_version_mismatch, frame?)?;
             }
 
             Some(message) = proxy_rx.next() => {
-                handle_request(&mut conn, &runtime, message).await?;
+                handle_request(conn, runtime, message).await?;
+            }
+
+            _ = acknowledge_timer.tick(), if connected.session.is_some() => {
+                request_send.send(ClientRequest::Acknowledge(connected.diffs_received())).await?;
             }
 
             Some(control) = control_rx.next() => {
@@ -445,24 +604,23 @@ async fn handle_connection(
             }
 
             Some(Ok(datagram)) = conn.read_datagram() => {
-                connected.process_datagram(&shared_client_state, datagram)?;
+                connected.process_datagram(shared_client_state, datagram)?;
             }
 
             Some(Ok((send, recv))) = conn.accept_bi() => {
-                connected.process_bi(&shared_client_state, send, recv);
+                connected.process_bi(shared_client_state, send, recv);
             }
 
             Some(Ok(recv)) = conn.accept_uni() => {
-                connected.process_uni(&shared_client_state, recv);
+                connected.process_uni(shared_client_state, recv);
             }
 
             Some(diff) = diff_stream.next() => {
-                connected.process_diff(&shared_client_state, diff?)?;
+                connected.process_diff(shared_client_state, diff?)?;
             }
         }
     }
 
-    tracing::debug!("Client entered disconnected state");
     Ok(())
 }
 
@@ -0,0 +1,204 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{
+    future::{self, Either},
+    Future,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{
+    mux::{MuxRecvStream, MuxSendStream},
+    webtransport, NetworkError,
+};
+
+use super::websocket::WebSocketConnection;
+
+/// Connection to the server from the browser that can be either WebTransport or the WebSocket
+/// fallback
+pub enum Connection {
+    WebTransport(webtransport::Connection),
+    WebSocket(WebSocketConnection),
+}
+
+impl From<webtransport::Connection> for Connection {
+    fn from(value: webtransport::Connection) -> Self {
+        Self::WebTransport(value)
+    }
+}
+
+impl From<WebSocketConnection> for Connection {
+    fn from(value: WebSocketConnection) -> Self {
+        Self::WebSocket(value)
+    }
+}
+
+impl Connection {
+    /// Connects to `url` with WebTransport, or to `websocket_url` if WebTransport is not supported
+    /// by the browser or fails to connect
+    pub async fn connect(url: &str, websocket_url: Option<&str>) -> anyhow::Result<Self> {
+        let err = match webtransport::Connection::connect(url).await {
+            Ok(conn) => return Ok(conn.into()),
+            Err(err) => err,
+        };
+
+        let Some(websocket_url) = websocket_url else {
+            return Err(err);
+        };
+
+        tracing::warn!("Falling back to WebSocket at {websocket_url}: {err:?}");
+        Ok(WebSocketConnection::connect(websocket_url).await?.into())
+    }
+
+    pub async fn open_uni(&self) -> Result<SendStream, NetworkError> {
+        match self {
+            Connection::WebTransport(conn) => Ok(conn.open_uni().await?.into()),
+            Connection::WebSocket(conn) => Ok(conn.mux().open_uni()?.into()),
+        }
+    }
+
+    pub async fn open_bi(&self) -> Result<(SendStream, RecvStream), NetworkError> {
+        match self {
+            Connection::WebTransport(conn) => {
+                let (send, recv) = conn.open_bi().await?;
+                Ok((send.into(), recv.into()))
+            }
+            Connection::WebSocket(conn) => {
+                let (send, recv) = conn.mux().open_bi()?;
+                Ok((send.into(), recv.into()))
+            }
+        }
+    }
+
+    pub async fn accept_uni(&self) -> Option<Result<RecvStream, NetworkError>> {
+        match self {
+            Connection::WebTransport(conn) => Some(
+                conn.accept_uni()
+                    .await?
+                    .map(Into::into)
+                    .map_err(|err| io::Error::from(err).into()),
+            ),
+            Connection::WebSocket(conn) => {
+                conn.mux().accept_uni().await.map(Into::into).ok().map(Ok)
+            }
+        }
+    }
+
+    pub async fn accept_bi(&self) -> Option<Result<(SendStream, RecvStream), NetworkError>> {
+        match self {
+            Connection::WebTransport(conn) => Some(
+                conn.accept_bi()
+                    .await?
+                    .map(|(send, recv)| (send.into(), recv.into()))
+                    .map_err(|err| io::Error::from(err).into()),
+            ),
+            Connection::WebSocket(conn) => conn
+                .mux()
+                .accept_bi()
+                .await
+                .map(|(send, recv)| (send.into(), recv.into()))
+                .ok()
+                .map(Ok),
+        }
+    }
+
+    pub async fn read_datagram(&self) -> Option<Result<Bytes, NetworkError>> {
+        match self {
+            Connection::WebTransport(conn) => Some(
+                conn.read_datagram()
+                    .await?
+                    .map_err(|err| io::Error::from(err).into()),
+            ),
+            Connection::WebSocket(conn) => conn.mux().read_datagram().await.ok().map(Ok),
+        }
+    }
+
+    pub fn send_datagram(&self, data: &[u8]) -> impl Future<Output = Result<(), NetworkError>> {
+        match self {
+            Connection::WebTransport(conn) => Either::Left(conn.send_datagram(data)),
+            Connection::WebSocket(conn) => Either::Right(future::ready(
+                conn.mux().send_datagram(Bytes::copy_from_slice(data)),
+            )),
+        }
+    }
+}
+
+/// The sending half of a stream of either connection
+pub enum SendStream {
+    WebTransport(webtransport::SendStream),
+    WebSocket(MuxSendStream),
+}
+
+impl From<webtransport::SendStream> for SendStream {
+    fn from(value: webtransport::SendStream) -> Self {
+        Self::WebTransport(value)
+    }
+}
+
+impl From<MuxSendStream> for SendStream {
+    fn from(value: MuxSendStream) -> Self {
+        Self::WebSocket(value)
+    }
+}
+
+impl AsyncWrite for SendStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            SendStream::WebTransport(stream) => Pin::new(stream).poll_write(cx, buf),
+            SendStream::WebSocket(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SendStream::WebTransport(stream) => Pin::new(stream).poll_flush(cx),
+            SendStream::WebSocket(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            SendStream::WebTransport(stream) => Pin::new(stream).poll_shutdown(cx),
+            SendStream::WebSocket(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// The receiving half of a stream of either connection
+pub enum RecvStream {
+    WebTransport(webtransport::RecvStream),
+    WebSocket(MuxRecvStream),
+}
+
+impl From<webtransport::RecvStream> for RecvStream {
+    fn from(value: webtransport::RecvStream) -> Self {
+        Self::WebTransport(value)
+    }
+}
+
+impl From<MuxRecvStream> for RecvStream {
+    fn from(value: MuxRecvStream) -> Self {
+        Self::WebSocket(value)
+    }
+}
+
+impl AsyncRead for RecvStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            RecvStream::WebTransport(stream) => Pin::new(stream).poll_read(cx, buf),
+            RecvStream::WebSocket(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod client;
+pub mod connection;
+mod websocket;
 
 use bytes::Bytes;
 use flume::Sender;
@@ -0,0 +1,98 @@
+use anyhow::anyhow;
+use js_sys::{ArrayBuffer, Uint8Array};
+use tokio::sync::oneshot;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
+
+use crate::mux::{MuxConnection, MuxSide};
+
+/// A connection to the WebSocket fallback of the server, multiplexing streams and datagrams
+///
+/// Disconnects when dropped
+pub struct WebSocketConnection {
+    socket: WebSocket,
+    conn: MuxConnection,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+}
+
+impl Drop for WebSocketConnection {
+    fn drop(&mut self) {
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+        self.conn.close();
+    }
+}
+
+impl WebSocketConnection {
+    /// Open a connection to `url`
+    pub async fn connect(url: &str) -> anyhow::Result<Self> {
+        let socket =
+            WebSocket::new(url).map_err(|e| anyhow!("Failed to connect to game server. {e:?}"))?;
+        socket.set_binary_type(BinaryType::Arraybuffer);
+
+        let (open_tx, open_rx) = oneshot::channel();
+        let mut open_tx = Some(open_tx);
+        let on_open = Closure::<dyn FnMut(Event)>::new(move |event: Event| {
+            if let Some(tx) = open_tx.take() {
+                tx.send(event.type_() == "open").ok();
+            }
+        });
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onerror(Some(on_open.as_ref().unchecked_ref()));
+
+        let opened = open_rx.await.unwrap_or(false);
+        socket.set_onopen(None);
+        socket.set_onerror(None);
+        if !opened {
+            anyhow::bail!("Failed to connect to game server");
+        }
+
+        let (conn, mut outgoing) = MuxConnection::new(MuxSide::Client);
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new({
+            let conn = conn.clone();
+            let socket = socket.clone();
+            move |event: MessageEvent| {
+                let Ok(data) = event.data().dyn_into::<ArrayBuffer>() else {
+                    return;
+                };
+                if let Err(err) = conn.handle_frame(Uint8Array::new(&data).to_vec().into()) {
+                    tracing::error!("Closing WebSocket connection: {err}");
+                    let _ = socket.close();
+                    conn.close();
+                }
+            }
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        let on_close = Closure::<dyn FnMut(CloseEvent)>::new({
+            let conn = conn.clone();
+            move |_: CloseEvent| conn.close()
+        });
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        wasm_bindgen_futures::spawn_local({
+            let socket = socket.clone();
+            async move {
+                while let Some(frame) = outgoing.recv().await {
+                    if socket.send_with_u8_array(&frame).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            socket,
+            conn,
+            _on_message: on_message,
+            _on_close: on_close,
+        })
+    }
+
+    pub fn mux(&self) -> &MuxConnection {
+        &self.conn
+    }
+}
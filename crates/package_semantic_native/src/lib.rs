@@ -10,11 +10,11 @@ use ambient_ecs::{
     components, generated::app::components::name as app_name,
     generated::wasm::components::module_enabled, query, ComponentRegistry, Entity, EntityId,
     ExternalComponentAttributes, ExternalComponentDesc, Networked, PrimitiveComponentType,
-    Resource, SystemGroup, World,
+    Replicated, ReplicationPriority, Resource, SystemGroup, World,
 };
 use ambient_native_std::asset_url::AbsAssetUrl;
 use ambient_network::ServerWorldExt;
-use ambient_package::PackageContent;
+use ambient_package::{ComponentReplication, PackageContent};
 use ambient_package_semantic::{
     Item, ItemId, ItemSource, LocalOrRemote, Package, PrimitiveType, RetrievableFile, Semantic,
     TypeInner,
@@ -401,9 +401,12 @@ fn all_defined_components(semantic: &Semantic) -> anyhow::Result<Vec<ExternalCom
                     })],
                     name: component.name.clone(),
                     description: component.description.clone(),
-                    attributes: ExternalComponentAttributes::from_iter(
-                        attributes.iter().map(|s| s.as_str()),
-                    ),
+                    attributes: ExternalComponentAttributes {
+                        replicated: component.replication.map(replicated),
+                        ..ExternalComponentAttributes::from_iter(
+                            attributes.iter().map(|s| s.as_str()),
+                        )
+                    },
                 });
             }
             Ok(())
@@ -412,6 +415,18 @@ fn all_defined_components(semantic: &Semantic) -> anyhow::Result<Vec<ExternalCom
     Ok(components)
 }
 
+fn replicated(replication: ComponentReplication) -> Replicated {
+    Replicated {
+        rate: replication.rate,
+        priority: match replication.priority {
+            ambient_package::ReplicationPriority::Low => ReplicationPriority::Low,
+            ambient_package::ReplicationPriority::Normal => ReplicationPriority::Normal,
+            ambient_package::ReplicationPriority::High => ReplicationPriority::High,
+        },
+        quantization: replication.quantization,
+    }
+}
+
 fn primitive_type_to_primitive_component_type(pt: PrimitiveType) -> PrimitiveComponentType {
     macro_rules! convert {
         ($(($value:ident, $_type:ty)),*) => {
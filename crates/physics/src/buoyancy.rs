@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use ambient_core::transform::get_world_position;
+use ambient_ecs::{components, query, EntityId, World};
+use glam::Vec3;
+use physxx::{PxActor, PxForceMode, PxRigidBody};
+
+use crate::{get_physics_timestep, physx::rigid_dynamic, splashes, thread::StepHook, GRAVITY};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+components!("physics", {
+    /// The buoyancy volume this body is currently in.
+    in_buoyancy_volume: EntityId,
+});
+
+struct BuoyancyVolume {
+    id: EntityId,
+    min: Vec3,
+    max: Vec3,
+    density: f32,
+    drag: f32,
+}
+
+/// Returns a hook that pushes the dynamic bodies inside buoyancy volumes up in proportion to how
+/// deep they are submerged, and slows them down by the volume's drag. Bodies entering or leaving a
+/// volume are recorded in [splashes].
+///
+/// Forces are cleared after each simulation step, so they are added again before every step.
+pub(crate) fn buoyancy_hook(world: &World) -> Option<StepHook> {
+    let volumes = query(buoyancy_volume())
+        .iter(world, None)
+        .map(|(id, size)| {
+            let center = get_world_position(world, id).unwrap_or_default();
+            BuoyancyVolume {
+                id,
+                min: center - *size / 2.,
+                max: center + *size / 2.,
+                density: world.get(id, buoyancy_density()).unwrap_or(1.),
+                drag: world.get(id, buoyancy_drag()).unwrap_or(1.),
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut inside: HashMap<EntityId, EntityId> = query(in_buoyancy_volume())
+        .iter(world, None)
+        .map(|(id, volume)| (id, *volume))
+        .collect();
+    if volumes.is_empty() && inside.is_empty() {
+        return None;
+    }
+
+    let bodies = query(rigid_dynamic())
+        .excl(kinematic())
+        .iter(world, None)
+        .map(|(id, body)| {
+            let body_density = world.get(id, density()).unwrap_or(1.).max(f32::EPSILON);
+            (id, *body, body_density)
+        })
+        .collect::<Vec<_>>();
+    let events = world.resource(splashes()).clone();
+    let dt = get_physics_timestep(world);
+
+    Some(Box::new(move |_, _| {
+        for (id, body, body_density) in &bodies {
+            let (min, max) = body.get_world_bounds(0.);
+            let submerged = volumes.iter().find_map(|volume| {
+                let overlaps = min.cmplt(volume.max).all() && max.cmpgt(volume.min).all();
+                // Treat the body as a box, and submerge it from the bottom up
+                let fraction = ((volume.max.z - min.z) / (max.z - min.z).max(f32::EPSILON)).min(1.);
+                (overlaps && fraction > 0.).then_some((volume, fraction))
+            });
+
+            let current = submerged.map(|(volume, _)| volume.id);
+            let previous = match current {
+                Some(volume) => inside.insert(*id, volume),
+                None => inside.remove(id),
+            };
+            if previous != current {
+                let mut events = events.lock();
+                events.extend(previous.map(|volume| (volume, *id, false)));
+                events.extend(current.map(|volume| (volume, *id, true)));
+            }
+
+            let Some((volume, fraction)) = submerged else {
+                continue;
+            };
+            // The displaced fluid weighs `fraction * density / body_density` times as much as the body
+            let lift = Vec3::Z * GRAVITY * fraction * volume.density / body_density;
+            let damping = (volume.drag * fraction).max(0.);
+            let drag = -body.get_linear_velocity() * damping;
+            body.add_force(lift + drag, Some(PxForceMode::Acceleration), Some(true));
+            body.set_angular_velocity(
+                body.get_angular_velocity() * (1. - damping * dt).max(0.),
+                false,
+            );
+        }
+    }))
+}
+
+/// Updates `in_buoyancy_volume` with the splashes of the last frame.
+pub(crate) fn apply_splashes(world: &mut World) {
+    let events = world.resource(splashes()).lock().clone();
+    for (volume, body, entered) in events {
+        if !world.exists(body) {
+            continue;
+        }
+        if entered {
+            world
+                .add_component(body, in_buoyancy_volume(), volume)
+                .unwrap();
+        } else if world.get(body, in_buoyancy_volume()).ok() == Some(volume) {
+            world.remove_component(body, in_buoyancy_volume()).unwrap();
+        }
+    }
+}
@@ -14,24 +14,25 @@ use ambient_model::model_from_url;
 use ambient_native_std::{
     asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, ColliderAssetType, TypedAssetUrl},
-    download_asset::{AssetError, JsonFromUrl},
+    download_asset::{AssetError, BytesFromUrl, JsonFromUrl},
     events::EventDispatcher,
 };
 use anyhow::Context;
 use async_trait::async_trait;
 use futures::future::try_join_all;
-use glam::{vec3, Mat4, Quat, Vec3};
+use glam::{uvec2, vec3, Mat4, Quat, UVec2, Vec3};
 use itertools::Itertools;
 use physxx::{
     AsPxActor, AsPxRigidActor, PxActor, PxActorFlag, PxBase, PxBoxGeometry, PxControllerDesc,
-    PxControllerShapeDesc, PxConvexMeshGeometry, PxGeometry, PxMaterial, PxMeshScale,
-    PxPlaneGeometry, PxRigidActor, PxRigidBody, PxRigidBodyFlag, PxRigidDynamicRef,
-    PxRigidStaticRef, PxShape, PxShapeFlag, PxSphereGeometry, PxTransform, PxTriangleMeshGeometry,
-    PxUserData,
+    PxControllerShapeDesc, PxConvexMeshGeometry, PxGeometry, PxHeightFieldDesc,
+    PxHeightFieldGeometry, PxMaterial, PxMeshScale, PxPlaneGeometry, PxQuantizedHeightFieldSamples,
+    PxRigidActor, PxRigidBody, PxRigidBodyFlag, PxRigidDynamicRef, PxRigidStaticRef, PxShape,
+    PxShapeFlag, PxSphereGeometry, PxTransform, PxTriangleMeshGeometry, PxUserData,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    helpers::{update_ccd_flags, update_mass_properties},
     main_controller_manager,
     mesh::{PhysxGeometry, PhysxGeometryFromUrl},
     physx::{character_controller, physics, physics_shape, rigid_actor, Physics},
@@ -141,6 +142,36 @@ pub fn server_systems() -> SystemGroup {
                         .unwrap();
                 }
             }),
+            query((
+                heightfield_collider().changed(),
+                heightfield_collider_resolution().changed(),
+            ))
+            .to_system(|q, world, qs, _| {
+                for (id, (heights, resolution)) in changed_or_missing(q, world, qs, collider()) {
+                    world
+                        .add_component(
+                            id,
+                            collider(),
+                            ColliderDef::Heightfield {
+                                heights,
+                                resolution,
+                            },
+                        )
+                        .unwrap();
+                }
+            }),
+            query(heightfield_collider_from_url().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in changed_or_missing(q, world, qs, collider()) {
+                    match AbsAssetUrl::from_str(&url) {
+                        Ok(url) => world
+                            .add_component(id, collider(), ColliderDef::HeightfieldFromUrl { url })
+                            .unwrap(),
+                        Err(err) => {
+                            tracing::warn!("Failed to load heightfield from {}: {:?}", url, err)
+                        }
+                    }
+                }
+            }),
             query(collider_from_url().changed()).to_system(|q, world, qs, _| {
                 for (id, url) in changed_or_missing(q, world, qs, collider()) {
                     tracing::debug!("Loading collider: {url:#?}");
@@ -292,6 +323,10 @@ pub fn server_systems() -> SystemGroup {
                                                 id,
                                                 Entity::new()
                                                     .with(collider_shapes(), shapes)
+                                                    .with(
+                                                        collider_convex_hulls(),
+                                                        convex.len() as u32,
+                                                    )
                                                     .with(collider_shapes_convex(), convex)
                                                     .with(collider_loaded(), ()),
                                             )
@@ -323,13 +358,22 @@ pub fn server_systems() -> SystemGroup {
                         let collider_type = world
                             .get(id, collider_type())
                             .unwrap_or(ColliderType::Static);
+                        let is_dynamic = collider_type == ColliderType::Dynamic;
+                        let is_kinematic = world.has_component(id, kinematic());
+                        // Checked before the old actor is removed, so that it is kept as is
+                        if is_dynamic && !is_kinematic && !force_static && shapes_convex.is_empty()
+                        {
+                            tracing::warn!(
+                                "Entity {} has a collider that can't be dynamic, such as a heightfield; make it kinematic or static instead",
+                                id
+                            );
+                            return;
+                        }
                         if let Ok(actor) = world.get(id, rigid_actor()) {
                             if let Some(scene) = actor.get_scene() {
                                 scene.remove_actor(&actor, false);
                             }
                         }
-                        let is_dynamic = collider_type == ColliderType::Dynamic;
-                        let is_kinematic = world.has_component(id, kinematic());
                         let actor = if is_dynamic && !force_static {
                             let body = PxRigidDynamicRef::new(
                                 physics.physics,
@@ -352,7 +396,11 @@ pub fn server_systems() -> SystemGroup {
                         };
                         if let Some(actor) = actor.to_rigid_body() {
                             actor.set_rigid_body_flag(PxRigidBodyFlag::KINEMATIC, is_kinematic);
-                            actor.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_CCD, !is_kinematic);
+                            update_ccd_flags(
+                                &actor,
+                                is_kinematic,
+                                world.get(id, ccd_enabled()).unwrap_or(!is_kinematic),
+                            );
                         }
                         actor
                             .as_actor()
@@ -383,26 +431,6 @@ pub fn server_systems() -> SystemGroup {
                             }
                             shape.update_user_data::<PxShapeUserData>(&|ud| ud.entity = id);
                         }
-                        if let Some(actor) = actor.to_rigid_dynamic() {
-                            if !actor
-                                .get_rigid_body_flags()
-                                .contains(PxRigidBodyFlag::KINEMATIC)
-                            {
-                                let densities = actor
-                                    .get_shapes()
-                                    .iter()
-                                    .map(|shape| {
-                                        shape.get_user_data::<PxShapeUserData>().unwrap().density
-                                    })
-                                    .collect_vec();
-                                actor.update_mass_and_inertia(densities, None, None);
-                                world.add_component(id, mass(), actor.get_mass()).unwrap();
-                            } else {
-                                world.remove_component(id, mass()).ok();
-                            }
-                        } else {
-                            world.remove_component(id, mass()).ok();
-                        }
                         let first_shape = shapes[0].clone();
                         world
                             .add_components(
@@ -412,6 +440,7 @@ pub fn server_systems() -> SystemGroup {
                                     .with(rigid_actor(), actor),
                             )
                             .unwrap();
+                        update_mass_properties(world, id);
                         actor.set_actor_flag(PxActorFlag::VISUALIZATION, false);
                         if collider_type != ColliderType::Dynamic
                             && collider_type != ColliderType::Static
@@ -460,6 +489,13 @@ pub enum ColliderDef {
         center: Vec3,
     },
     Plane,
+    Heightfield {
+        heights: Vec<f32>,
+        resolution: UVec2,
+    },
+    HeightfieldFromUrl {
+        url: AbsAssetUrl,
+    },
 }
 
 type ColliderSpawner = Box<dyn Fn(&Physics, Vec3) -> (Vec<PxShape>, Vec<PxShape>) + Sync + Send>;
@@ -547,6 +583,29 @@ impl ColliderDef {
                 });
                 (vec![shape.clone()], vec![shape])
             })),
+            ColliderDef::Heightfield {
+                heights,
+                resolution,
+            } => {
+                validate_heightfield(&heights, resolution)?;
+                Ok(Box::new(move |physics, scale| {
+                    let shape =
+                        heightfield_shape(physics, &material, &heights, resolution, scale, density);
+                    // Heightfields can't be used for dynamic actors, so there is no convex version
+                    (vec![shape], Vec::new())
+                }))
+            }
+            ColliderDef::HeightfieldFromUrl { url } => {
+                let bytes = BytesFromUrl::new(url.clone(), true).get(&assets).await?;
+                let (heights, resolution) = heightfield_from_image(&bytes)
+                    .with_context(|| format!("Failed to load heightmap from {url}"))?;
+                validate_heightfield(&heights, resolution)?;
+                Ok(Box::new(move |physics, scale| {
+                    let shape =
+                        heightfield_shape(physics, &material, &heights, resolution, scale, density);
+                    (vec![shape], Vec::new())
+                }))
+            }
             ColliderDef::Asset { collider } => {
                 let collider = collider.unwrap_abs();
                 let collider_from_urls: Arc<ColliderFromUrls> =
@@ -566,6 +625,76 @@ impl ColliderDef {
     }
 }
 
+fn validate_heightfield(heights: &[f32], resolution: UVec2) -> anyhow::Result<()> {
+    if resolution.x < 2 || resolution.y < 2 {
+        anyhow::bail!("Heightfield resolution has to be at least 2x2. resolution={resolution}");
+    }
+    if heights.len() != (resolution.x * resolution.y) as usize {
+        anyhow::bail!(
+            "Heightfield has {} heights, but its resolution {resolution} needs {}",
+            heights.len(),
+            resolution.x * resolution.y
+        );
+    }
+    if heights.iter().any(|height| !height.is_finite()) {
+        anyhow::bail!("Heightfield heights have to be finite");
+    }
+    Ok(())
+}
+
+/// Reads the heights of a heightfield from a grayscale image, where black is 0 and white is 1.
+fn heightfield_from_image(bytes: &[u8]) -> anyhow::Result<(Vec<f32>, UVec2)> {
+    let image = image::load_from_memory(bytes)?.into_luma16();
+    let heights = image
+        .pixels()
+        .map(|pixel| pixel.0[0] as f32 / u16::MAX as f32)
+        .collect();
+    Ok((heights, uvec2(image.width(), image.height())))
+}
+
+fn heightfield_shape(
+    physics: &Physics,
+    material: &PxMaterial,
+    heights: &[f32],
+    resolution: UVec2,
+    scale: Vec3,
+    density: f32,
+) -> PxShape {
+    // PhysX lays out heightfield rows along its local X and columns along its local Z, with
+    // heights along Y. We rotate Y to Z, which points the columns along -Y, so the columns are
+    // flipped and offset to put the sample at (x, y) at (x, y, height) in the entity's space.
+    let (rows, columns) = (resolution.x, resolution.y);
+    let samples = (0..rows)
+        .flat_map(|row| {
+            (0..columns).map(move |column| heights[((columns - 1 - column) * rows + row) as usize])
+        })
+        .collect_vec();
+    let quantized = PxQuantizedHeightFieldSamples::new_from_f32_array(&samples);
+    let desc = PxHeightFieldDesc::new(rows, columns, &quantized.samples);
+    let mut height_field = physics.cooking.create_height_field(&physics.physics, &desc);
+    let geometry = PxHeightFieldGeometry::new(
+        &mut height_field,
+        quantized.height_scale * scale.z.abs(),
+        scale.x.abs(),
+        scale.y.abs(),
+    );
+    let shape = PxShape::new(physics.physics, &geometry, &[material], Some(true), None);
+
+    let rotation = Quat::from_rotation_x(PI / 2.);
+    let offset = vec3(0., (columns - 1) as f32, quantized.min_height);
+    shape.set_local_pose(&PxTransform::new(offset * scale, rotation));
+    shape.set_user_data(PxShapeUserData {
+        entity: EntityId::null(),
+        density,
+        base_pose: Mat4::from_scale_rotation_translation(
+            vec3(1., 1., quantized.height_scale),
+            rotation,
+            offset,
+        ),
+    });
+    shape
+}
+
 impl Default for ColliderDef {
     fn default() -> Self {
         Self::Sphere {
@@ -0,0 +1,185 @@
+//! Contact filters: the packages with `contact_filter` decide whether the entities with
+//! `contact_filtered` collide with the entities they touch.
+//!
+//! The filter shader runs on the physics thread, so it can't ask the packages. Instead, it looks
+//! the pair up in the answers of the packages, kept in the [ContactFilterCache] of the scene; pairs
+//! without an answer collide, and are recorded so that the packages can be asked about them after
+//! the step. Once they answer, PhysX is made to re-filter the pair, and finds the answer.
+//!
+//! As the filter shader only gets the filter data of the shapes, the cache of their scene is found
+//! through the slot stored in the high bits of `word3`, and the entities through the key stored in
+//! `word2`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, OnceLock, Weak},
+};
+
+use ambient_ecs::{EntityId, World};
+use parking_lot::{Mutex, RwLock};
+use physxx::PxFilterData;
+
+use crate::{contact_filter_cache, helpers::get_shapes, main_physics_scene};
+
+/// Set in `word3` of the filter data of the shapes of entities with `contact_filtered`.
+pub(crate) const CONTACT_FILTERED: u32 = 1;
+/// The bits of `word3` below the slot of the cache of the scene
+const SLOT_SHIFT: u32 = 16;
+
+/// The caches of the scenes, by slot
+#[derive(Default)]
+struct Slots {
+    next: u16,
+    caches: HashMap<u16, Weak<ContactFilterCache>>,
+}
+
+fn slots() -> &'static RwLock<Slots> {
+    static SLOTS: OnceLock<RwLock<Slots>> = OnceLock::new();
+    SLOTS.get_or_init(Default::default)
+}
+
+#[derive(Default)]
+struct Answers {
+    next_key: u32,
+    /// The key of each entity with a physics shape.
+    keys: HashMap<EntityId, u32>,
+    entities: HashMap<u32, EntityId>,
+    /// The answers of the packages, by pair of keys, smallest first.
+    answers: HashMap<(u32, u32), bool>,
+}
+
+/// The answers of the packages for the pairs of entities of a scene, and the pairs it found
+/// without an answer
+pub struct ContactFilterCache {
+    slot: u16,
+    answers: RwLock<Answers>,
+    /// The pairs found by the filter shader without an answer.
+    pending: Mutex<HashSet<(u32, u32)>>,
+}
+
+impl ContactFilterCache {
+    pub fn new() -> Arc<Self> {
+        let mut slots = slots().write();
+        // 0 is left for shapes without a cache
+        let mut slot = slots.next.wrapping_add(1).max(1);
+        while slots.caches.contains_key(&slot) {
+            slot = slot.wrapping_add(1).max(1);
+        }
+        slots.next = slot;
+
+        let cache = Arc::new(Self {
+            slot,
+            answers: Default::default(),
+            pending: Default::default(),
+        });
+        slots.caches.insert(slot, Arc::downgrade(&cache));
+        cache
+    }
+
+    /// Returns the key of `id`, which is stored in `word2` of the filter data of its shapes
+    fn key(&self, id: EntityId) -> u32 {
+        let mut answers = self.answers.write();
+        if let Some(key) = answers.keys.get(&id) {
+            return *key;
+        }
+        // 0 is left for shapes without a key
+        answers.next_key += 1;
+        let key = answers.next_key;
+        answers.keys.insert(id, key);
+        answers.entities.insert(key, id);
+        key
+    }
+}
+
+impl Drop for ContactFilterCache {
+    fn drop(&mut self) {
+        slots().write().caches.remove(&self.slot);
+    }
+}
+
+fn pair(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+/// Returns the `word2` and `word3` of the filter data of the shapes of `id`.
+pub(crate) fn contact_filter_words(world: &World, id: EntityId, filtered: bool) -> (u32, u32) {
+    let Some(cache) = world.resource_opt(contact_filter_cache()) else {
+        return (0, 0);
+    };
+    let flags = if filtered { CONTACT_FILTERED } else { 0 };
+    (cache.key(id), (cache.slot as u32) << SLOT_SHIFT | flags)
+}
+
+/// Forgets `id`, once it has no physics shape anymore.
+pub(crate) fn remove_contact_filter_entity(world: &World, id: EntityId) {
+    let Some(cache) = world.resource_opt(contact_filter_cache()) else {
+        return;
+    };
+    let mut answers = cache.answers.write();
+    let Some(key) = answers.keys.remove(&id) else {
+        return;
+    };
+    answers.entities.remove(&key);
+    answers.answers.retain(|(a, b), _| *a != key && *b != key);
+    drop(answers);
+    cache.pending.lock().retain(|(a, b)| *a != key && *b != key);
+}
+
+/// Returns whether two shapes may collide according to the contact filters. Called by the filter
+/// shader, on the physics thread.
+pub(crate) fn contact_filter_allows(a: PxFilterData, b: PxFilterData) -> bool {
+    if (a.word3 | b.word3) & CONTACT_FILTERED == 0 || a.word2 == 0 || b.word2 == 0 {
+        return true;
+    }
+    let slot = (a.word3 >> SLOT_SHIFT) as u16;
+    let Some(cache) = slots().read().caches.get(&slot).and_then(Weak::upgrade) else {
+        return true;
+    };
+
+    let pair = pair(a.word2, b.word2);
+    if let Some(answer) = cache.answers.read().answers.get(&pair) {
+        return *answer;
+    }
+    cache.pending.lock().insert(pair);
+    true
+}
+
+/// Takes the pairs of entities of the scene of `world` the packages with `contact_filter` haven't
+/// answered for yet, which were found since the last call.
+pub fn take_pending_contact_filters(world: &World) -> Vec<(EntityId, EntityId)> {
+    let Some(cache) = world.resource_opt(contact_filter_cache()) else {
+        return vec![];
+    };
+    let pending = std::mem::take(&mut *cache.pending.lock());
+    let answers = cache.answers.read();
+    pending
+        .into_iter()
+        .filter_map(|(a, b)| Some((*answers.entities.get(&a)?, *answers.entities.get(&b)?)))
+        .collect()
+}
+
+/// Records whether `a` and `b` collide, and makes PhysX filter them again if that changed.
+///
+/// Entities without a physics shape are ignored.
+pub fn set_contact_filter(world: &World, a: EntityId, b: EntityId, collide: bool) {
+    let Some(cache) = world.resource_opt(contact_filter_cache()) else {
+        return;
+    };
+    {
+        let mut answers = cache.answers.write();
+        let (Some(&key_a), Some(&key_b)) = (answers.keys.get(&a), answers.keys.get(&b)) else {
+            return;
+        };
+        let pair = pair(key_a, key_b);
+        cache.pending.lock().remove(&pair);
+        if answers.answers.insert(pair, collide) == Some(collide) {
+            return;
+        }
+    }
+    let scene = world.resource(main_physics_scene());
+    for id in [a, b] {
+        if let Some(actor) = get_shapes(world, id).find_map(|shape| shape.get_actor()) {
+            scene.reset_filtering(&actor);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Approximate convex decomposition of triangle meshes.
+//!
+//! PhysX can only simulate convex shapes on dynamic actors, so a concave mesh used as a dynamic
+//! collider would otherwise be replaced by its convex hull. Instead, we recursively split the mesh
+//! in two along the longest axis of its bounds until every part is close enough to convex (or we
+//! run out of hulls), and then use the convex hull of each part.
+
+use glam::Vec3;
+use itertools::Itertools;
+
+/// The maximum number of triangles and vertices looked at when measuring concavity.
+const CONCAVITY_SAMPLES: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvexDecompositionParams {
+    /// The maximum number of convex hulls to split the mesh into.
+    pub max_hulls: u32,
+    /// Parts with a concavity below this, relative to the size of the part, are not split further.
+    pub max_concavity: f32,
+}
+impl Default for ConvexDecompositionParams {
+    fn default() -> Self {
+        Self {
+            max_hulls: 16,
+            max_concavity: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConvexDecompositionStats {
+    pub hull_count: u32,
+    /// The largest concavity left in any of the hulls, relative to the size of the hull.
+    pub max_concavity: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConvexDecomposition {
+    /// The points of each part; the convex hull of these points is the hull of the part.
+    pub hulls: Vec<Vec<Vec3>>,
+    pub stats: ConvexDecompositionStats,
+}
+
+pub fn decompose(
+    positions: &[Vec3],
+    indices: &[u32],
+    params: &ConvexDecompositionParams,
+) -> ConvexDecomposition {
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|tri| {
+            [
+                positions[tri[0] as usize],
+                positions[tri[1] as usize],
+                positions[tri[2] as usize],
+            ]
+        })
+        .collect_vec();
+
+    let mut parts = vec![Part::new(&triangles, (0..triangles.len()).collect())];
+    while parts.len() < params.max_hulls.max(1) as usize {
+        let (index, part) = parts
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.concavity.total_cmp(&b.1.concavity))
+            .unwrap();
+        if part.concavity <= params.max_concavity {
+            break;
+        }
+        match part.split(&triangles) {
+            Some((a, b)) => {
+                parts[index] = a;
+                parts.push(b);
+            }
+            // This part can't be split any further, so stop trying
+            None => parts[index].concavity = 0.,
+        }
+    }
+
+    let stats = ConvexDecompositionStats {
+        hull_count: parts.len() as u32,
+        max_concavity: parts.iter().map(|part| part.concavity).fold(0., f32::max),
+    };
+    let hulls = parts
+        .into_iter()
+        .map(|part| {
+            part.triangles
+                .iter()
+                .flat_map(|&tri| triangles[tri])
+                .collect_vec()
+        })
+        .collect();
+    ConvexDecomposition { hulls, stats }
+}
+
+struct Part {
+    triangles: Vec<usize>,
+    concavity: f32,
+}
+impl Part {
+    fn new(triangles: &[[Vec3; 3]], indices: Vec<usize>) -> Self {
+        let concavity = concavity(triangles, &indices);
+        Self {
+            triangles: indices,
+            concavity,
+        }
+    }
+
+    /// Splits the part in two at the median triangle along the longest axis of its bounds.
+    fn split(&self, triangles: &[[Vec3; 3]]) -> Option<(Self, Self)> {
+        let (min, max) = bounds(triangles, &self.triangles);
+        let extent = max - min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let centroid = |tri: usize| triangles[tri].iter().sum::<Vec3>()[axis] / 3.;
+
+        let mut sorted = self.triangles.clone();
+        sorted.sort_by(|&a, &b| centroid(a).total_cmp(&centroid(b)));
+        let right = sorted.split_off(sorted.len() / 2);
+        if sorted.is_empty() || right.is_empty() {
+            return None;
+        }
+        Some((Self::new(triangles, sorted), Self::new(triangles, right)))
+    }
+}
+
+fn bounds(triangles: &[[Vec3; 3]], indices: &[usize]) -> (Vec3, Vec3) {
+    indices
+        .iter()
+        .flat_map(|&tri| triangles[tri])
+        .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), p| {
+            (min.min(p), max.max(p))
+        })
+}
+
+/// Measures how far the part is from being convex, relative to its size.
+///
+/// In a convex mesh, every vertex is on the same side of the plane of every triangle. For each
+/// triangle, we take the furthest distance of any vertex on either side of its plane, and use the
+/// smaller of the two; looking at both sides makes this independent of the winding of the mesh.
+fn concavity(triangles: &[[Vec3; 3]], indices: &[usize]) -> f32 {
+    if indices.is_empty() {
+        return 0.;
+    }
+    let (min, max) = bounds(triangles, indices);
+    let size = (max - min).length();
+    if size <= f32::EPSILON {
+        return 0.;
+    }
+
+    let stride = (indices.len() / CONCAVITY_SAMPLES).max(1);
+    let vertices = indices
+        .iter()
+        .step_by(stride)
+        .flat_map(|&tri| triangles[tri])
+        .collect_vec();
+    indices
+        .iter()
+        .step_by(stride)
+        .filter_map(|&tri| {
+            let [a, b, c] = triangles[tri];
+            let normal = (b - a).cross(c - a).try_normalize()?;
+            let (front, back) = vertices.iter().fold((0f32, 0f32), |(front, back), v| {
+                let distance = (*v - a).dot(normal);
+                (front.max(distance), back.max(-distance))
+            });
+            Some(front.min(back))
+        })
+        .fold(0., f32::max)
+        / size
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{vec3, Vec3};
+
+    use super::{decompose, ConvexDecompositionParams};
+
+    /// Adds the triangles of an axis-aligned box.
+    fn cuboid(min: Vec3, max: Vec3, positions: &mut Vec<Vec3>, indices: &mut Vec<u32>) {
+        let base = positions.len() as u32;
+        for i in 0..8 {
+            positions.push(vec3(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            ));
+        }
+        let faces = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        for [a, b, c, d] in faces {
+            indices.extend([a, b, c, a, c, d].map(|i| base + i));
+        }
+    }
+
+    #[test]
+    fn convex_mesh_is_not_split() {
+        let (mut positions, mut indices) = (Vec::new(), Vec::new());
+        cuboid(Vec3::ZERO, Vec3::ONE, &mut positions, &mut indices);
+
+        let result = decompose(&positions, &indices, &ConvexDecompositionParams::default());
+        assert_eq!(result.stats.hull_count, 1);
+        assert_eq!(result.stats.max_concavity, 0.);
+    }
+
+    #[test]
+    fn concave_mesh_is_split() {
+        // Two boxes far apart, like the ends of a dumbbell
+        let (mut positions, mut indices) = (Vec::new(), Vec::new());
+        cuboid(Vec3::ZERO, Vec3::ONE, &mut positions, &mut indices);
+        cuboid(
+            vec3(0., 0., 5.),
+            vec3(1., 1., 6.),
+            &mut positions,
+            &mut indices,
+        );
+
+        let result = decompose(&positions, &indices, &ConvexDecompositionParams::default());
+        assert_eq!(result.stats.hull_count, 2);
+        assert!(result.stats.max_concavity <= 0.05);
+        for hull in &result.hulls {
+            let min_z = hull.iter().map(|p| p.z).fold(f32::INFINITY, f32::min);
+            let max_z = hull.iter().map(|p| p.z).fold(f32::NEG_INFINITY, f32::max);
+            assert_eq!(max_z - min_z, 1.);
+        }
+    }
+
+    #[test]
+    fn hull_count_is_limited() {
+        let (mut positions, mut indices) = (Vec::new(), Vec::new());
+        for i in 0..8 {
+            let z = i as f32 * 3.;
+            cuboid(
+                vec3(0., 0., z),
+                vec3(1., 1., z + 1.),
+                &mut positions,
+                &mut indices,
+            );
+        }
+
+        let params = ConvexDecompositionParams {
+            max_hulls: 4,
+            ..Default::default()
+        };
+        let result = decompose(&positions, &indices, &params);
+        assert_eq!(result.stats.hull_count, 4);
+        assert_eq!(result.hulls.len(), 4);
+    }
+}
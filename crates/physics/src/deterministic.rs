@@ -0,0 +1,94 @@
+//! Deterministic physics, for lockstep multiplayer and replay verification.
+//!
+//! When enabled with [DeterministicPhysicsKey], the main scene is created with PhysX's enhanced
+//! determinism, every dynamic body uses the same solver iteration counts, and the simulation
+//! advances by exactly one fixed step per server frame. Given the same inputs in the same frames,
+//! the simulation then produces the same results, regardless of how long each frame took.
+
+use ambient_ecs::{query, SystemGroup, World};
+use ambient_native_std::asset_cache::{AssetCache, SyncAssetKey};
+
+use crate::{physics_deterministic, physx::rigid_dynamic};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+// These match the PhysX defaults for rigid dynamics.
+const SOLVER_POSITION_ITERATIONS: u32 = 4;
+const SOLVER_VELOCITY_ITERATIONS: u32 = 1;
+
+/// Whether the server runs physics in deterministic mode. Must be set before the server
+/// resources are created.
+#[derive(Debug, Clone)]
+pub struct DeterministicPhysicsKey;
+impl SyncAssetKey<bool> for DeterministicPhysicsKey {
+    fn load(&self, _assets: AssetCache) -> bool {
+        false
+    }
+}
+
+pub fn is_deterministic(world: &World) -> bool {
+    world
+        .resource_opt(physics_deterministic())
+        .copied()
+        .unwrap_or(false)
+}
+
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/deterministic",
+        vec![
+            query(rigid_dynamic())
+                .spawned()
+                .to_system(|q, world, qs, _| {
+                    if !is_deterministic(world) {
+                        return;
+                    }
+                    for (_, body) in q.iter(world, qs) {
+                        body.set_solver_iteration_counts(
+                            SOLVER_POSITION_ITERATIONS,
+                            SOLVER_VELOCITY_ITERATIONS,
+                        );
+                    }
+                }),
+            // Reject the features that can't be simulated deterministically
+            query(physics_timestep())
+                .changed()
+                .to_system(|q, world, qs, _| {
+                    if !is_deterministic(world) {
+                        return;
+                    }
+                    for _ in q.iter(world, qs) {
+                        tracing::warn!(
+                            "physics_timestep is ignored in deterministic mode; the server tick time is used instead"
+                        );
+                    }
+                }),
+            query(())
+                .incl(collider_from_url())
+                .spawned()
+                .to_system(|q, world, qs, _| {
+                    if !is_deterministic(world) {
+                        return;
+                    }
+                    for (id, _) in q.iter(world, qs) {
+                        tracing::warn!(
+                            "Entity {id} uses collider_from_url, which loads asynchronously and is not deterministic"
+                        );
+                    }
+                }),
+            query(())
+                .incl(heightfield_collider_from_url())
+                .spawned()
+                .to_system(|q, world, qs, _| {
+                    if !is_deterministic(world) {
+                        return;
+                    }
+                    for (id, _) in q.iter(world, qs) {
+                        tracing::warn!(
+                            "Entity {id} uses heightfield_collider_from_url, which loads asynchronously and is not deterministic"
+                        );
+                    }
+                }),
+        ],
+    )
+}
@@ -0,0 +1,48 @@
+use ambient_core::transform::get_world_position;
+use ambient_ecs::{query, World};
+use glam::Vec3;
+
+use crate::{helpers::PhysicsObjectCollection, main_physics_scene, thread::StepHook};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+/// Returns a hook that applies every force field to the dynamic bodies inside it.
+///
+/// Forces are cleared after each simulation step, so they are added again before every step.
+pub(crate) fn force_field_hook(world: &World) -> Option<StepHook> {
+    let fields = query(force_field_radius())
+        .iter(world, None)
+        .map(|(id, radius)| {
+            (
+                get_world_position(world, id).unwrap_or_default(),
+                *radius,
+                world.get(id, force_field_wind()).unwrap_or_default(),
+                world.get(id, force_field_attractor()).unwrap_or_default(),
+                world.has_component(id, force_field_falloff()),
+            )
+        })
+        .filter(|(_, radius, wind, attractor, _)| {
+            *radius > 0. && (*wind != Vec3::ZERO || *attractor != 0.)
+        })
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return None;
+    }
+
+    let scene = *world.resource(main_physics_scene());
+    Some(Box::new(move |_, _| {
+        for &(center, radius, wind, attractor, falloff) in &fields {
+            PhysicsObjectCollection::from_scene_radius(&scene, center, radius).add_acceleration(
+                |pos| {
+                    let delta = center - pos;
+                    let strength = if falloff {
+                        (1. - delta.length() / radius).max(0.)
+                    } else {
+                        1.
+                    };
+                    (wind + delta.normalize_or_zero() * attractor) * strength
+                },
+            );
+        }
+    }))
+}
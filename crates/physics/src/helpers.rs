@@ -4,24 +4,25 @@ use ambient_core::{
     transform::{get_world_position, rotation, translation},
     FIXED_SERVER_TICK_TIME,
 };
-use ambient_ecs::{query, ECSError, EntityId, World};
+use ambient_ecs::{query, ECSError, Entity, EntityId, World};
 use anyhow::{bail, Context};
 use glam::{vec3, Mat4, Vec3};
 use itertools::Itertools;
 use physxx::{
-    AsPxActor, AsPxRigidActor, PxActor, PxActorRef, PxActorTypeFlag, PxBase, PxBoxGeometry,
-    PxConvexMeshGeometry, PxForceMode, PxJoint, PxMeshScale, PxOverlapCallback, PxPhysicsRef,
-    PxQueryFilterData, PxQueryFlag, PxRevoluteJointRef, PxRigidActor, PxRigidActorRef, PxRigidBody,
-    PxRigidBodyFlag, PxRigidDynamicRef, PxRigidStaticRef, PxSceneRef, PxShape, PxSphereGeometry,
-    PxTransform, PxTriangleMeshGeometry, PxUserData,
+    AsPxActor, AsPxJoint, AsPxRigidActor, PxActor, PxActorRef, PxActorTypeFlag, PxBase,
+    PxBoxGeometry, PxConvexMeshGeometry, PxFixedJointRef, PxForceMode, PxHeightFieldGeometry,
+    PxJoint, PxMeshScale, PxOverlapCallback, PxPhysicsRef, PxQueryFilterData, PxQueryFlag,
+    PxRevoluteJointRef, PxRigidActor, PxRigidActorRef, PxRigidBody, PxRigidBodyFlag,
+    PxRigidDynamicRef, PxRigidStaticRef, PxSceneRef, PxShape, PxSphereGeometry, PxTransform,
+    PxTriangleMeshGeometry, PxUserData,
 };
 
 use crate::{
-    collider::{collider_shapes_convex, collider_type, kinematic},
-    main_physics_scene,
+    collider::{ccd_enabled, collider_shapes_convex, collider_type, kinematic},
+    inertia, main_physics_scene, mass,
     physx::{
-        physics, physics_controlled, physics_shape, revolute_joint, rigid_actor, rigid_dynamic,
-        rigid_static,
+        fixed_joint, physics, physics_controlled, physics_shape, revolute_joint, rigid_actor,
+        rigid_dynamic, rigid_static,
     },
     unit_mass, unit_velocity, ColliderScene, PxActorUserData, PxShapeUserData,
 };
@@ -56,10 +57,11 @@ pub fn convert_rigid_static_dynamic(world: &mut World, id: EntityId, to_dynamic:
     scene.remove_actor(&old_actor, true);
     let physics = world.resource(physics());
     let is_kinematic = world.has_component(id, kinematic());
+    let ccd_enabled = world.get(id, ccd_enabled()).unwrap_or(!is_kinematic);
     let new_actor = if to_dynamic {
         let actor = PxRigidDynamicRef::new(physics.physics, &old_actor.get_global_pose());
         actor.set_rigid_body_flag(PxRigidBodyFlag::KINEMATIC, is_kinematic);
-        actor.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_CCD, !is_kinematic);
+        update_ccd_flags(&actor, is_kinematic, ccd_enabled);
         actor.as_rigid_actor()
     } else {
         PxRigidStaticRef::new(physics.physics, &old_actor.get_global_pose()).as_rigid_actor()
@@ -99,6 +101,48 @@ pub fn convert_rigid_static_dynamic(world: &mut World, id: EntityId, to_dynamic:
     scene.add_actor(&new_actor);
 }
 
+/// Computes the mass and inertia of the dynamic body of `id` from the volume and `density` of its
+/// shapes, and writes them to its `mass` and `inertia`. Static and kinematic bodies have neither.
+pub fn update_mass_properties(world: &mut World, id: EntityId) {
+    let body = get_actor(world, id)
+        .and_then(|x| x.to_rigid_dynamic())
+        .filter(|body| {
+            !body
+                .get_rigid_body_flags()
+                .contains(PxRigidBodyFlag::KINEMATIC)
+        });
+    let Some(body) = body else {
+        world.remove_component(id, mass()).ok();
+        world.remove_component(id, inertia()).ok();
+        return;
+    };
+    let densities = body
+        .get_shapes()
+        .iter()
+        .map(|shape| shape.get_user_data::<PxShapeUserData>().unwrap().density)
+        .collect_vec();
+    body.update_mass_and_inertia(densities, None, None);
+    world
+        .add_components(
+            id,
+            Entity::new()
+                .with(mass(), body.get_mass())
+                .with(inertia(), body.get_mass_space_inertia_tensor()),
+        )
+        .unwrap();
+}
+
+/// Updates the continuous collision detection flags of a rigid body.
+///
+/// Kinematic bodies can't use sweep-based CCD, so they use speculative CCD instead.
+pub fn update_ccd_flags(body: &impl PxRigidBody, is_kinematic: bool, ccd_enabled: bool) {
+    body.set_rigid_body_flag(PxRigidBodyFlag::ENABLE_CCD, ccd_enabled && !is_kinematic);
+    body.set_rigid_body_flag(
+        PxRigidBodyFlag::ENABLE_SPECULATIVE_CCD,
+        ccd_enabled && is_kinematic,
+    );
+}
+
 pub fn update_physics_controlled(world: &mut World, actor: PxRigidActorRef) {
     let is_physics_controlled = match actor.to_rigid_dynamic() {
         Some(body) => !body
@@ -221,6 +265,23 @@ pub fn scale_shape(shape: PxShape, scale: Vec3) {
                 "scale_shape: Invalid triangle geometry"
             );
         }
+    } else if let Some(geo) = geo.as_height_field() {
+        // The base scale of a heightfield only contains its quantization height scale
+        let new_geo = PxHeightFieldGeometry::new(
+            &mut geo.height_field(),
+            base_scale.z * scale.z.abs(),
+            scale.x.abs(),
+            scale.y.abs(),
+        );
+        if new_geo.is_valid() {
+            shape.set_geometry(&new_geo);
+        } else {
+            tracing::error!(
+                ?scale,
+                ?base_scale,
+                "scale_shape: Invalid heightfield geometry"
+            );
+        }
     } else if let Some(_geo) = geo.as_sphere() {
         let new_geo = PxSphereGeometry::new((size).x);
         shape.set_geometry(&new_geo);
@@ -284,11 +345,49 @@ pub fn create_revolute_joint(
         actor1,
         &PxTransform::new(pos1, rot1),
     );
+    crate::joints::update_break_thresholds(world, joint.as_joint());
     world.add_component(id0, revolute_joint(), joint).ok();
     world.add_component(id1, revolute_joint(), joint).ok();
     Ok(())
 }
 
+pub fn create_fixed_joint(
+    world: &mut World,
+    id0: EntityId,
+    transform0: Mat4,
+    id1: EntityId,
+    transform1: Mat4,
+) -> anyhow::Result<()> {
+    let actor0 = get_actor(world, id0).and_then(|x| x.to_rigid_actor());
+    let actor1 = get_actor(world, id1).and_then(|x| x.to_rigid_actor());
+    if actor0.is_none() && actor1.is_none() {
+        anyhow::bail!("Neither entity has a rigid actor");
+    }
+    if !actor0
+        .map(|x| x.to_rigid_dynamic().is_some())
+        .unwrap_or_default()
+        && !actor1
+            .map(|x| x.to_rigid_dynamic().is_some())
+            .unwrap_or_default()
+    {
+        anyhow::bail!("At least one actor has to be dynamic");
+    }
+    let (_, rot0, pos0) = transform0.to_scale_rotation_translation();
+    let (_, rot1, pos1) = transform1.to_scale_rotation_translation();
+
+    let joint = PxFixedJointRef::new(
+        PxPhysicsRef::get(),
+        actor0,
+        &PxTransform::new(pos0, rot0),
+        actor1,
+        &PxTransform::new(pos1, rot1),
+    );
+    crate::joints::update_break_thresholds(world, joint.as_joint());
+    world.add_component(id0, fixed_joint(), joint).ok();
+    world.add_component(id1, fixed_joint(), joint).ok();
+    Ok(())
+}
+
 pub fn get_entity_revolute_joint(world: &World, id: EntityId) -> Option<PxRevoluteJointRef> {
     if let Ok(joint) = world.get(id, revolute_joint()) {
         return Some(joint);
@@ -530,7 +629,24 @@ impl PhysicsObjectCollection {
         Self { actors, units }
     }
     pub fn from_radius(world: &World, position: Vec3, radius: f32) -> Self {
-        let scene = world.resource(main_physics_scene());
+        let mut res =
+            Self::from_scene_radius(world.resource(main_physics_scene()), position, radius);
+        res.units = query((translation(),))
+            .incl(unit_velocity())
+            .iter(world, None)
+            .filter_map(|(id, (&pos,))| {
+                if (pos - position).length() <= radius {
+                    Some(id)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        res
+    }
+    /// Like [Self::from_radius], but only collects the dynamic actors in `scene`, so it doesn't need
+    /// access to the world.
+    pub fn from_scene_radius(scene: &PxSceneRef, position: Vec3, radius: f32) -> Self {
         let mut hit_call = PxOverlapCallback::new(1000);
         let mut filter_data = PxQueryFilterData::new();
         filter_data.set_flags(PxQueryFlag::DYNAMIC);
@@ -545,17 +661,6 @@ impl PhysicsObjectCollection {
             let actors: HashSet<_> = hit_call.touches().iter().map(|hit| hit.actor).collect();
             res.actors = actors.into_iter().collect();
         }
-        res.units = query((translation(),))
-            .incl(unit_velocity())
-            .iter(world, None)
-            .filter_map(|(id, (&pos,))| {
-                if (pos - position).length() <= radius {
-                    Some(id)
-                } else {
-                    None
-                }
-            })
-            .collect();
         res
     }
     pub fn add_force(&self, world: &mut World, get_force: impl Fn(Vec3) -> Vec3) {
@@ -581,6 +686,21 @@ impl PhysicsObjectCollection {
                 a * FIXED_SERVER_TICK_TIME.as_secs_f32();
         }
     }
+    /// Accelerates the dynamic actors in the collection, regardless of their mass. Units are not
+    /// affected.
+    pub fn add_acceleration(&self, get_acceleration: impl Fn(Vec3) -> Vec3) {
+        for actor in &self.actors {
+            if let Some(actor) = actor.to_rigid_dynamic() {
+                if !actor
+                    .get_rigid_body_flags()
+                    .contains(PxRigidBodyFlag::KINEMATIC)
+                {
+                    let acceleration = get_acceleration(actor.get_global_pose().translation());
+                    actor.add_force(acceleration, Some(PxForceMode::Acceleration), Some(true));
+                }
+            }
+        }
+    }
     pub fn add_radial_impulse(
         &self,
         world: &mut World,
@@ -644,6 +764,35 @@ pub fn add_force_at_position(
     Ok(())
 }
 
+/// Puts a dynamic entity to sleep. It stays asleep until something touches it, or it is woken up.
+pub fn put_to_sleep(world: &World, id: EntityId) -> anyhow::Result<()> {
+    let shape = world.get_ref(id, physics_shape())?;
+    let actor = shape.get_actor().context("No actor for shape")?;
+    let actor = actor.to_rigid_dynamic().context("Not a rigid dynamic")?;
+    if actor
+        .get_rigid_body_flags()
+        .contains(PxRigidBodyFlag::KINEMATIC)
+    {
+        bail!("Can't put a kinematic actor to sleep");
+    }
+    actor.put_to_sleep();
+    Ok(())
+}
+
+pub fn wake_up(world: &World, id: EntityId) -> anyhow::Result<()> {
+    let shape = world.get_ref(id, physics_shape())?;
+    let actor = shape.get_actor().context("No actor for shape")?;
+    let actor = actor.to_rigid_dynamic().context("Not a rigid dynamic")?;
+    if actor
+        .get_rigid_body_flags()
+        .contains(PxRigidBodyFlag::KINEMATIC)
+    {
+        bail!("Can't wake up a kinematic actor");
+    }
+    actor.wake_up();
+    Ok(())
+}
+
 pub fn get_velocity_at_position(
     world: &World,
     id: EntityId,
@@ -10,7 +10,8 @@ use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use physxx::{
     PxConvexFlag, PxConvexMesh, PxConvexMeshDesc, PxConvexMeshGeometry, PxOverlapCallback,
-    PxQueryFilterData, PxRaycastCallback, PxRigidActor, PxShape, PxTransform, PxUserData,
+    PxQueryFilterData, PxRaycastCallback, PxRigidActor, PxSceneRef, PxShape, PxSphereGeometry,
+    PxTransform, PxUserData,
 };
 use serde::{Deserialize, Serialize};
 
@@ -83,7 +84,7 @@ pub fn raycast(world: &World, ray: Ray) -> Vec<(EntityId, f32)> {
         .collect_vec()
 }
 
-fn raycast_px(world: &World, ray: Ray) -> Vec<(PxShape, f32)> {
+pub(crate) fn raycast_px(world: &World, ray: Ray) -> Vec<(PxShape, f32)> {
     (0..3)
         .flat_map(|i| {
             raycast_collider_type_px(world, ColliderScene::from_usize(i), ray).into_iter()
@@ -124,6 +125,99 @@ pub fn raycast_collider_type_px(
     Vec::new()
 }
 
+/// Casts every ray in `rays`, and returns the closest hit of each ray.
+///
+/// This is equivalent to calling [raycast_first] for each ray, but the scenes and query buffers
+/// are shared between all of the rays.
+pub fn raycast_first_batch(world: &World, rays: &[Ray]) -> Vec<Option<(EntityId, f32)>> {
+    let scenes = collider_scenes(world);
+    let filter_data = PxQueryFilterData::new();
+    let mut hit = PxRaycastCallback::new(0);
+    rays.iter()
+        .map(|ray| {
+            scenes
+                .iter()
+                .filter_map(|scene| {
+                    if !scene.raycast(ray.origin, ray.dir, f32::MAX, &mut hit, None, &filter_data) {
+                        return None;
+                    }
+                    let block = hit.block()?;
+                    let ud = block.shape?.get_user_data::<PxShapeUserData>()?;
+                    Some((ud.entity, block.distance))
+                })
+                .min_by_key(|(_, dist)| OrderedFloat(*dist))
+        })
+        .collect()
+}
+
+/// Casts every ray in `rays`, and returns all of the hits of each ray, sorted by distance.
+///
+/// This is equivalent to calling [raycast] for each ray, but the scenes and query buffers are
+/// shared between all of the rays.
+pub fn raycast_batch(world: &World, rays: &[Ray]) -> Vec<Vec<(EntityId, f32)>> {
+    let scenes = collider_scenes(world);
+    let filter_data = PxQueryFilterData::new();
+    let mut hit = PxRaycastCallback::new(100);
+    rays.iter()
+        .map(|ray| {
+            scenes
+                .iter()
+                .flat_map(|scene| {
+                    if !scene.raycast(ray.origin, ray.dir, f32::MAX, &mut hit, None, &filter_data) {
+                        return Vec::new();
+                    }
+                    hit.touches()
+                        .into_iter()
+                        .filter_map(|hit| {
+                            let ud = hit.shape?.get_user_data::<PxShapeUserData>()?;
+                            Some((ud.entity, hit.distance))
+                        })
+                        .collect_vec()
+                })
+                .sorted_by_key(|(_, dist)| OrderedFloat(*dist))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the entities with a collider overlapping each sphere in `spheres`, given as
+/// `(center, radius)`.
+pub fn overlap_sphere_batch(world: &World, spheres: &[(Vec3, f32)]) -> Vec<Vec<EntityId>> {
+    let scene = world.resource(main_physics_scene());
+    let filter_data = PxQueryFilterData::new();
+    let mut hit_call = PxOverlapCallback::new(1000);
+    spheres
+        .iter()
+        .map(|&(center, radius)| {
+            let sphere = PxSphereGeometry::new(radius);
+            if !scene.overlap(
+                &sphere,
+                PxTransform::from_translation(center),
+                &mut hit_call,
+                &filter_data,
+            ) {
+                return Vec::new();
+            }
+            hit_call
+                .touches()
+                .into_iter()
+                .filter_map(|hit| {
+                    hit.shape
+                        .get_user_data::<PxShapeUserData>()
+                        .map(|ud| ud.entity)
+                })
+                .unique()
+                .collect()
+        })
+        .collect()
+}
+
+fn collider_scenes(world: &World) -> Vec<PxSceneRef> {
+    (0..3)
+        .map(|i| ColliderScene::from_usize(i).get_scene(world))
+        .collect()
+}
+
 pub fn intersect_frustum(world: &World, frustum_corners: &[Vec3; 8]) -> Vec<EntityId> {
     let mut hit_call = PxOverlapCallback::new(1000);
     let filter_data = PxQueryFilterData::new();
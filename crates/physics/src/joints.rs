@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use ambient_ecs::{query, EntityId, FnSystem, SystemGroup, World};
+use itertools::Itertools;
+use physxx::{
+    AsPxJoint, PxBase, PxConstraintFlags, PxJoint, PxJointRef, PxRigidActor, PxRigidActorRef,
+    PxUserData,
+};
+
+use crate::{
+    broken_joints,
+    helpers::get_actor,
+    physx::{fixed_joint, revolute_joint},
+    PxShapeUserData,
+};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+/// Returns the entity of `actor`, or the null entity if the joint is attached to the world.
+fn actor_entity(actor: Option<PxRigidActorRef>) -> EntityId {
+    actor
+        .and_then(|actor| {
+            actor
+                .borrow_shapes()
+                .first()
+                .and_then(|shape| shape.get_user_data::<PxShapeUserData>())
+                .map(|ud| ud.entity)
+        })
+        .unwrap_or_else(EntityId::null)
+}
+
+/// Returns the joints attached to the actor of `id`.
+fn entity_joints(world: &World, id: EntityId) -> Vec<PxJointRef> {
+    get_actor(world, id)
+        .and_then(|actor| actor.to_rigid_actor())
+        .map(|actor| {
+            actor
+                .get_constraints()
+                .into_iter()
+                .filter_map(|constraint| constraint.get_external_reference().to_joint())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Sets the break force and torque of `joint` to the lowest `joint_break_force` and
+/// `joint_break_torque` of the two entities it connects.
+pub(crate) fn update_break_thresholds(world: &World, joint: PxJointRef) {
+    let (actor0, actor1) = joint.get_actors();
+    let ids = [actor_entity(actor0), actor_entity(actor1)];
+    let threshold = |component| {
+        ids.iter()
+            .filter_map(|id| world.get(*id, component).ok())
+            .fold(f32::MAX, f32::min)
+    };
+    joint.set_break_force(
+        threshold(joint_break_force()),
+        threshold(joint_break_torque()),
+    );
+}
+
+/// Applies `joint_break_force` and `joint_break_torque` to the joints of the entities, and releases
+/// the joints that broke. The entities of each broken joint are recorded in [broken_joints].
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/joints",
+        vec![
+            query(joint_break_force())
+                .changed()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        for joint in entity_joints(world, id) {
+                            update_break_thresholds(world, joint);
+                        }
+                    }
+                }),
+            query(joint_break_torque())
+                .changed()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        for joint in entity_joints(world, id) {
+                            update_break_thresholds(world, joint);
+                        }
+                    }
+                }),
+            // Only the joints of entities with a break threshold can break
+            Box::new(FnSystem::new(|world, _| {
+                let ids = query(())
+                    .incl(joint_break_force())
+                    .iter(world, None)
+                    .chain(query(()).incl(joint_break_torque()).iter(world, None))
+                    .map(|(id, _)| id)
+                    .collect_vec();
+                *world.resource_mut(broken_joints()) = release_broken_joints(world, ids);
+            })),
+        ],
+    )
+}
+
+fn release_broken_joints(world: &mut World, ids: Vec<EntityId>) -> Vec<(EntityId, EntityId)> {
+    let mut seen = HashSet::new();
+    let mut broken = Vec::new();
+    for id in ids {
+        for joint in entity_joints(world, id) {
+            if !seen.insert(joint.0 as usize)
+                || !joint
+                    .get_constraint_flags()
+                    .contains(PxConstraintFlags::BROKEN)
+            {
+                continue;
+            }
+            let (actor0, actor1) = joint.get_actors();
+            let entities = (actor_entity(actor0), actor_entity(actor1));
+            for entity in [entities.0, entities.1] {
+                if world
+                    .get(entity, revolute_joint())
+                    .map_or(false, |x| x.as_joint().0 == joint.0)
+                {
+                    world.remove_component(entity, revolute_joint()).ok();
+                }
+                if world
+                    .get(entity, fixed_joint())
+                    .map_or(false, |x| x.as_joint().0 == joint.0)
+                {
+                    world.remove_component(entity, fixed_joint()).ok();
+                }
+            }
+            joint.release();
+            broken.push(entities);
+        }
+    }
+    broken
+}
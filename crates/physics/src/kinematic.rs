@@ -0,0 +1,46 @@
+use ambient_ecs::{query, World};
+use physxx::{PxRigidActor, PxTransform};
+
+use crate::{physx::rigid_dynamic, thread::StepHook};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+/// Returns a hook that moves kinematic bodies towards their `kinematic_target_translation` and
+/// `kinematic_target_rotation`, so that they reach them at the end of the last step of the frame.
+///
+/// Kinematic targets only last for a single step, so they are set again before every step.
+pub(crate) fn kinematic_target_hook(world: &World) -> Option<StepHook> {
+    let targets = query(rigid_dynamic())
+        .incl(kinematic())
+        .iter(world, None)
+        .filter_map(|(id, body)| {
+            let target_translation = world.get(id, kinematic_target_translation()).ok();
+            let target_rotation = world.get(id, kinematic_target_rotation()).ok();
+            (target_translation.is_some() || target_rotation.is_some()).then_some((
+                *body,
+                target_translation,
+                target_rotation,
+            ))
+        })
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return None;
+    }
+
+    Some(Box::new(move |step, steps| {
+        // The remaining distance is split evenly over the remaining steps
+        let fraction = 1. / steps.saturating_sub(step).max(1) as f32;
+        for (body, target_translation, target_rotation) in &targets {
+            let pose = body.get_global_pose();
+            let translation = match target_translation {
+                Some(target) => pose.translation().lerp(*target, fraction),
+                None => pose.translation(),
+            };
+            let rotation = match target_rotation {
+                Some(target) => pose.rotation().slerp(*target, fraction),
+                None => pose.rotation(),
+            };
+            body.set_kinematic_target(&PxTransform::new(translation, rotation));
+        }
+    }))
+}
@@ -0,0 +1,163 @@
+//! Rewinding the moving colliders to an earlier time, so that the server can check what a player
+//! hit in the world as the player saw it when they fired.
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Duration,
+};
+
+use ambient_core::game_time;
+use ambient_ecs::{components, EntityId, FnSystem, Resource, SystemGroup, World};
+use ambient_native_std::shapes::Ray;
+use glam::{Quat, Vec3};
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use physxx::{
+    PxActorTypeFlag, PxBase, PxHitFlags, PxRigidActor, PxSceneRef, PxShape, PxTransform, PxUserData,
+};
+
+use crate::{
+    intersection::raycast_px, lag_compensation_history, main_physics_scene, PxShapeUserData,
+};
+
+components!("physics", {
+    /// The poses of the shapes of the dynamic actors in the main physics scene at the end of each
+    /// recent frame, oldest first
+    @[Resource]
+    collider_history: ColliderHistory,
+});
+
+const DEFAULT_HISTORY: Duration = Duration::from_secs(1);
+
+/// A shape is identified by its entity and its index on its actor, as the shapes themselves may
+/// have been released since
+type ShapeKey = (EntityId, usize);
+
+#[derive(Debug, Clone, Default)]
+pub struct ColliderHistory(VecDeque<(Duration, HashMap<ShapeKey, (Vec3, Quat)>)>);
+impl ColliderHistory {
+    /// Returns the poses of the shapes at `time`, interpolated between the frames around it, or
+    /// `None` if `time` is not in the past
+    fn at(&self, time: Duration) -> Option<HashMap<ShapeKey, PxTransform>> {
+        let next = self.0.iter().position(|(frame, _)| *frame > time)?;
+        let (to_time, to) = &self.0[next];
+        let poses = match next.checked_sub(1).map(|i| &self.0[i]) {
+            Some((from_time, from)) => {
+                let t = (time - *from_time).as_secs_f32() / (*to_time - *from_time).as_secs_f32();
+                from.iter()
+                    .map(|(&key, &(pos, rot))| match to.get(&key) {
+                        Some(&(to_pos, to_rot)) => {
+                            (key, (pos.lerp(to_pos, t), rot.slerp(to_rot, t)))
+                        }
+                        None => (key, (pos, rot)),
+                    })
+                    .collect::<HashMap<_, _>>()
+            }
+            // Older than the history, so use the oldest poses there are
+            None => to.clone(),
+        };
+        Some(
+            poses
+                .into_iter()
+                .map(|(key, (pos, rot))| (key, PxTransform::new(pos, rot)))
+                .collect(),
+        )
+    }
+}
+
+/// The shapes of the dynamic actors in `scene` which belong to an entity, with their current pose
+fn moving_shapes(scene: PxSceneRef) -> Vec<(ShapeKey, PxShape, PxTransform)> {
+    scene
+        .get_actors(PxActorTypeFlag::RIGID_DYNAMIC)
+        .into_iter()
+        .filter_map(|actor| actor.to_rigid_actor())
+        .flat_map(|actor| {
+            actor
+                .borrow_shapes()
+                .into_iter()
+                .enumerate()
+                .filter_map(move |(index, shape)| {
+                    let entity = shape.get_user_data::<PxShapeUserData>()?.entity;
+                    let pose = shape.get_global_pose(actor);
+                    Some(((entity, index), shape, pose))
+                })
+        })
+        .collect()
+}
+
+/// Records the poses of the moving colliders at the end of every frame, for [lag_compensation_history]
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/lag_compensation",
+        vec![Box::new(FnSystem::new(|world, _| {
+            let now = *world.resource(game_time());
+            let keep = world
+                .resource_opt(lag_compensation_history())
+                .copied()
+                .unwrap_or(DEFAULT_HISTORY);
+            let poses = moving_shapes(*world.resource(main_physics_scene()))
+                .into_iter()
+                .map(|(key, _, pose)| (key, (pose.translation(), pose.rotation())))
+                .collect();
+
+            let Some(history) = world.resource_mut_opt(collider_history()) else {
+                return;
+            };
+            history.0.push_back((now, poses));
+            while matches!(history.0.front(), Some((frame, _)) if *frame + keep < now) {
+                history.0.pop_front();
+            }
+        }))],
+    )
+}
+
+/// Like [raycast](crate::intersection::raycast), but with the moving colliders of the main
+/// physics scene where they were at the game time `time`, as recorded in the collider history.
+///
+/// Colliders which didn't exist at `time` are not hit. If `time` is older than the history, the
+/// oldest poses are used, and if it is not in the past, this is the same as a regular raycast.
+pub fn raycast_at(world: &World, time: Duration, ray: Ray) -> Vec<(EntityId, f32)> {
+    let Some(poses) = world
+        .resource_opt(collider_history())
+        .and_then(|history| history.at(time))
+    else {
+        return crate::intersection::raycast(world, ray);
+    };
+
+    let moving = moving_shapes(*world.resource(main_physics_scene()));
+    let moved = moving
+        .iter()
+        .map(|(_, shape, _)| shape.0)
+        .collect::<HashSet<_>>();
+
+    let mut hits = raycast_px(world, ray)
+        .into_iter()
+        .filter(|(shape, _)| !moved.contains(&shape.0))
+        .filter_map(|(shape, dist)| {
+            shape
+                .get_user_data::<PxShapeUserData>()
+                .map(|ud| (ud.entity, dist))
+        })
+        .collect_vec();
+    for (key, shape, _) in &moving {
+        let Some(pose) = poses.get(key) else {
+            continue;
+        };
+        let hit = physxx::raycast(
+            ray.origin,
+            ray.dir,
+            &shape.get_geometry(),
+            pose,
+            f32::MAX,
+            PxHitFlags::DEFAULT,
+            1,
+        );
+        hits.extend(hit.first().map(|hit| (key.0, hit.distance)));
+    }
+    hits.sort_by_key(|(_, dist)| OrderedFloat(*dist));
+    hits
+}
+
+/// Like [raycast_at], but only returns the closest hit
+pub fn raycast_first_at(world: &World, time: Duration, ray: Ray) -> Option<(EntityId, f32)> {
+    raycast_at(world, time, ray).into_iter().next()
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use ambient_core::{asset_cache, FIXED_SERVER_TICK_TIME};
+use ambient_core::{asset_cache, delta_time, FIXED_SERVER_TICK_TIME};
 use ambient_ecs::{
     components, query, Debuggable, DynSystem, Entity, EntityId, FnSystem, Resource, SystemGroup,
     World,
@@ -8,29 +8,45 @@ use ambient_ecs::{
 use ambient_native_std::asset_cache::{AssetCache, SyncAssetKey, SyncAssetKeyExt};
 use ambient_network::server::{ForkingEvent, ShutdownEvent};
 use collider::{collider_shapes, collider_shapes_convex};
+use contact_filter::ContactFilterCache;
+use deterministic::DeterministicPhysicsKey;
 use glam::{vec3, Mat4, Vec3};
 use helpers::release_px_scene;
 use parking_lot::Mutex;
 use physx::{
     actor_aggregate, articulation_cache, articulation_link, articulation_reduce_coordinate,
-    character_controller, fixed_joint, physics_shape, revolute_joint, rigid_actor, rigid_dynamic,
-    rigid_static,
+    character_controller, fixed_joint, physics_material, physics_shape, revolute_joint,
+    rigid_actor, rigid_dynamic, rigid_static,
 };
 use physxx::{
-    AsPxActor, PxContactPairHeader, PxContactPoint, PxControllerManagerRef, PxMaterial,
-    PxPvdSceneFlag, PxRigidActor, PxSceneDesc, PxSceneFlags, PxSceneRef, PxSimulationEventCallback,
-    PxUserData,
+    AsPxActor, PxContactPairHeader, PxContactPoint, PxControllerManagerRef, PxFilterData,
+    PxMaterial, PxPvdSceneFlag, PxRigidActor, PxSceneDesc, PxSceneFlags, PxSceneRef,
+    PxSimulationEventCallback, PxUserData,
 };
+use ragdoll::ragdoll_bones;
 use serde::{Deserialize, Serialize};
+use thread::{PhysicsPoses, PhysicsThread, StepJob};
 
 use crate::physx::PhysicsKey;
 
+pub mod buoyancy;
 pub mod collider;
+pub mod contact_filter;
+pub mod convex_decomposition;
+pub mod deterministic;
+pub mod force_field;
 pub mod helpers;
 pub mod intersection;
+pub mod joints;
+pub mod kinematic;
+pub mod lag_compensation;
 pub mod mesh;
 pub mod physx;
+pub mod ragdoll;
 pub mod rc_asset;
+pub mod sleep;
+pub mod thread;
+pub mod vehicle;
 pub mod visualization;
 
 pub use ambient_ecs::generated::physics::components::*;
@@ -48,11 +64,47 @@ components!("physics", {
     wood_physics_material: PxMaterial,
     @[Debuggable, Resource]
     collisions: Arc<Mutex<Vec<(EntityId, EntityId, Vec<Vec3>, Vec<Vec3>)>>>,
+    /// How far the physics simulation is ahead of the server's frame time, in seconds.
+    @[Debuggable, Resource]
+    physics_time_ahead: f32,
+    /// Whether a simulation step was started this frame, and needs to be fetched.
+    @[Debuggable, Resource]
+    physics_simulation_pending: bool,
+    @[Resource]
+    physics_thread: Arc<PhysicsThread>,
+    /// The answers of the contact filters for the main scene; see [contact_filter].
+    @[Resource]
+    contact_filter_cache: Arc<ContactFilterCache>,
+    /// The poses of the physics-controlled bodies, as of the last fetched simulation.
+    @[Resource]
+    physics_poses: Arc<PhysicsPoses>,
+    /// Whether physics is simulated deterministically; see [deterministic].
+    @[Debuggable, Resource]
+    physics_deterministic: bool,
+    /// The `(volume, body, entered)` of every body that entered or left a buoyancy volume this frame.
+    @[Debuggable, Resource]
+    splashes: Arc<Mutex<Vec<(EntityId, EntityId, bool)>>>,
+    /// The compression of the wheels of every vehicle after the last step of this frame.
+    @[Debuggable, Resource]
+    vehicle_compressions: Arc<Mutex<Vec<(EntityId, Vec<f32>)>>>,
+    /// The dynamic entities that fell asleep this frame.
+    @[Debuggable, Resource]
+    slept_bodies: Vec<EntityId>,
+    /// The dynamic entities that woke up this frame.
+    @[Debuggable, Resource]
+    woken_bodies: Vec<EntityId>,
+    /// The entities connected by each joint that broke this frame. Either is null if the joint was
+    /// attached to the world.
+    @[Debuggable, Resource]
+    broken_joints: Vec<(EntityId, EntityId)>,
 });
 pub fn init_all_components() {
     init_components();
+    buoyancy::init_components();
     physx::init_components();
     collider::init_components();
+    lag_compensation::init_components();
+    ragdoll::init_components();
     visualization::init_components();
 }
 
@@ -65,6 +117,10 @@ pub fn create_server_resources(assets: &AssetCache, server_resources: &mut Entit
     main_scene_desc.set_cpu_dispatcher(&physics.dispatcher);
     main_scene_desc.set_gravity(vec3(0., 0., -GRAVITY));
     main_scene_desc.update_flags(|flags| flags | PxSceneFlags::ENABLE_CCD);
+    let deterministic = DeterministicPhysicsKey.get(assets);
+    if deterministic {
+        main_scene_desc.update_flags(|flags| flags | PxSceneFlags::ENABLE_ENHANCED_DETERMINISM);
+    }
     main_scene_desc.set_filter_shader(main_physx_scene_filter_shader, true);
     let collisions = Arc::new(Mutex::new(Vec::new()));
     {
@@ -101,6 +157,17 @@ pub fn create_server_resources(assets: &AssetCache, server_resources: &mut Entit
     let main_scene = PxSceneRef::new(&physics.physics, &main_scene_desc);
     server_resources.set(self::collisions(), collisions);
     server_resources.set(self::collider_loads(), vec![]);
+    server_resources.set(self::physics_time_ahead(), 0.);
+    server_resources.set(self::physics_simulation_pending(), false);
+    server_resources.set(self::physics_thread(), Arc::new(PhysicsThread::new()));
+    server_resources.set(self::physics_poses(), Default::default());
+    server_resources.set(self::splashes(), Default::default());
+    server_resources.set(self::vehicle_compressions(), Default::default());
+    server_resources.set(self::slept_bodies(), vec![]);
+    server_resources.set(self::woken_bodies(), vec![]);
+    server_resources.set(self::broken_joints(), vec![]);
+    server_resources.set(lag_compensation::collider_history(), Default::default());
+    server_resources.set(self::physics_deterministic(), deterministic);
 
     main_scene.get_scene_pvd_client().set_scene_pvd_flags(
         PxPvdSceneFlag::TRANSMIT_CONSTRAINTS
@@ -119,6 +186,7 @@ pub fn create_server_resources(assets: &AssetCache, server_resources: &mut Entit
     let trigger_areas = PxSceneRef::new(&physics.physics, &trigger_areas_desc);
 
     server_resources.set(self::main_physics_scene(), main_scene);
+    server_resources.set(self::contact_filter_cache(), ContactFilterCache::new());
     server_resources.set(crate::picking_scene(), picking_scene);
     server_resources.set(crate::trigger_areas_scene(), trigger_areas);
     server_resources.set(self::main_controller_manager(), main_controller_manager);
@@ -191,9 +259,21 @@ impl SyncAssetKey<PxMaterial> for PxWoodMaterialKey {
     }
 }
 
+/// Returns whether two shapes should collide, according to their collision groups.
+///
+/// The groups of a shape are stored in `word0` of its simulation filter data, and the groups it
+/// ignores in `word1`.
+pub fn collision_groups_interact(a: PxFilterData, b: PxFilterData) -> bool {
+    a.word0 & b.word1 == 0 && b.word0 & a.word1 == 0
+}
+
 unsafe extern "C" fn main_physx_scene_filter_shader(
     info: *mut physxx::sys::FilterShaderCallbackInfo,
 ) -> u16 {
+    let (a, b) = ((*info).filterData0.into(), (*info).filterData1.into());
+    if !collision_groups_interact(a, b) || !contact_filter::contact_filter_allows(a, b) {
+        return physxx::sys::PxFilterFlag::eSUPPRESS as u16;
+    }
     (*(*info).pairFlags).mBits |= (physxx::sys::PxPairFlag::eSOLVE_CONTACT
         | physxx::sys::PxPairFlag::eDETECT_DISCRETE_CONTACT
         | physxx::sys::PxPairFlag::eDETECT_CCD_CONTACT
@@ -234,41 +314,123 @@ pub fn server_systems() -> SystemGroup {
                     }
                 }),
             Box::new(collider::server_systems()),
+            Box::new(ragdoll::server_systems()),
+            Box::new(joints::server_systems()),
+            Box::new(sleep::server_systems()),
+            Box::new(deterministic::server_systems()),
             Box::new(visualization::server_systems()),
+            Box::new(lag_compensation::server_systems()),
         ],
     )
 }
 
-/// Starts the physx simulation step concurrently.
+/// The maximum number of physics steps taken in a single frame. If the simulation falls further
+/// behind than this, the remaining time is dropped so that it can catch up.
+const MAX_PHYSICS_STEPS_PER_FRAME: u32 = 8;
+
+/// Returns the fixed time step used for the physics simulation.
+pub fn get_physics_timestep(world: &World) -> f32 {
+    if deterministic::is_deterministic(world) {
+        return FIXED_SERVER_TICK_TIME.as_secs_f32();
+    }
+    world
+        .get(world.resource_entity(), physics_timestep())
+        .ok()
+        .filter(|timestep| *timestep > 0.)
+        .unwrap_or(FIXED_SERVER_TICK_TIME.as_secs_f32())
+}
+
+/// Returns how far the current frame is between the last two physics steps, from 0 (the
+/// previous step) to 1 (the latest step).
+pub fn get_physics_interpolation_alpha(world: &World) -> f32 {
+    let ahead = *world.resource(physics_time_ahead());
+    (1. - ahead / get_physics_timestep(world)).clamp(0., 1.)
+}
+
+/// Hands the physics steps needed to catch up with the frame time to the physics thread, which
+/// runs them with a fixed time step while the rest of the frame continues.
 ///
 /// Results will be available after [`fetch_simulation_system`]
 pub fn run_simulation_system() -> DynSystem {
     Box::new(FnSystem::new(|world, _| {
         profiling::scope!("run_simulation_system");
-        let scene = world.resource(main_physics_scene());
-        scene.simulate(FIXED_SERVER_TICK_TIME.as_secs_f32());
+        // Collisions and splashes are reported during each step, so clear them before the first one
+        world.resource(collisions()).lock().clear();
+        world.resource(splashes()).lock().clear();
+
+        let timestep = get_physics_timestep(world);
+        let mut ahead = *world.resource(physics_time_ahead()) - *world.resource(delta_time());
+        let mut steps = 0;
+        if deterministic::is_deterministic(world) {
+            // Step exactly once per frame, so that the results don't depend on the frame time
+            ahead = 0.;
+            steps = 1;
+        }
+        while ahead < 0. && steps < MAX_PHYSICS_STEPS_PER_FRAME {
+            ahead += timestep;
+            steps += 1;
+        }
+        *world.resource_mut(physics_time_ahead()) = ahead.max(0.);
+        *world.resource_mut(physics_simulation_pending()) = steps > 0;
+        if steps == 0 {
+            return;
+        }
+
+        let hooks = [
+            force_field::force_field_hook(world),
+            buoyancy::buoyancy_hook(world),
+            kinematic::kinematic_target_hook(world),
+            vehicle::vehicle_hook(world),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        // Sleeping bodies don't move, so there's no need to read them back
+        let bodies = query(rigid_dynamic())
+            .incl(physics_controlled())
+            .excl(sleeping())
+            .iter(world, None)
+            .map(|(id, body)| (id, *body))
+            .collect();
+        world.resource(physics_thread()).start(StepJob {
+            scene: *world.resource(main_physics_scene()),
+            timestep,
+            steps,
+            hooks,
+            bodies,
+        });
     }))
 }
 
-/// Ensures the physx simulation data is available.
+/// Waits for the physics thread to finish the steps started by [`run_simulation_system`], and
+/// makes their results available. PhysX may only be accessed after this.
 ///
 /// Must only be called once per [`run_simulation_system`]
 pub fn fetch_simulation_system() -> DynSystem {
     Box::new(FnSystem::new(|world, _| {
         profiling::scope!("fetch_simulation_system");
 
-        world.resource(collisions()).lock().clear();
         world.resource_mut(collider_loads()).clear();
-        let scene = world.resource(main_physics_scene());
-        // Ensure the previous simulation has completed
-        scene.fetch_results(true);
+        wait_for_simulation(world);
     }))
 }
 
+fn wait_for_simulation(world: &mut World) {
+    if !*world.resource(physics_simulation_pending()) {
+        return;
+    }
+    let poses = world.resource(physics_thread()).wait();
+    *world.resource_mut(physics_poses()) = Arc::new(poses);
+    *world.resource_mut(physics_simulation_pending()) = false;
+    buoyancy::apply_splashes(world);
+    vehicle::apply_compressions(world);
+}
+
 pub fn on_forking_systems() -> SystemGroup<ForkingEvent> {
     SystemGroup::new(
         "physics/on_forking_systems",
         vec![Box::new(FnSystem::new(|world, _| {
+            wait_for_simulation(world);
             let mut ed = Entity::new();
             create_server_resources(world.resource(asset_cache()), &mut ed);
             world.add_components(world.resource_entity(), ed).unwrap();
@@ -317,12 +479,21 @@ pub fn on_forking_systems() -> SystemGroup<ForkingEvent> {
             {
                 world.remove_component(id, articulation_cache()).unwrap();
             }
+            for (id, _) in query(()).incl(ragdoll_bones()).collect_cloned(world, None) {
+                world.remove_component(id, ragdoll_bones()).unwrap();
+            }
             for (id, _) in query(())
                 .incl(character_controller())
                 .collect_cloned(world, None)
             {
                 world.remove_component(id, character_controller()).unwrap();
             }
+            for (id, _) in query(())
+                .incl(physics_material())
+                .collect_cloned(world, None)
+            {
+                world.remove_component(id, physics_material()).unwrap();
+            }
             for (id, _) in query(())
                 .incl(collider_shapes())
                 .collect_cloned(world, None)
@@ -344,7 +515,7 @@ pub fn on_shutdown_systems() -> SystemGroup<ShutdownEvent> {
     SystemGroup::new(
         "physics/on_shutdown_systems",
         vec![Box::new(FnSystem::new(|world, _| {
-            world.resource(main_physics_scene()).fetch_results(true);
+            wait_for_simulation(world);
             release_px_scene(*world.resource(main_physics_scene()));
             release_px_scene(*world.resource(picking_scene()));
             release_px_scene(*world.resource(trigger_areas_scene()));
@@ -5,14 +5,20 @@ use ambient_core::{
     transform::{local_to_parent, local_to_world, rotation, scale, translation},
 };
 use ambient_ecs::{
-    components, ensure_has_component, query, FnSystem, QueryState, Resource, SystemGroup,
+    components, ensure_has_component, generated::physics::types::CombineMode, query, Component,
+    ComponentValue, DynSystem, EntityId, FnSystem, QueryState, Resource, SystemGroup, World,
 };
 use ambient_native_std::asset_cache::SyncAssetKey;
 use glam::{EulerRot, Quat, Vec3};
 use parking_lot::Mutex;
 use physxx::{articulation_reduced_coordinate::*, *};
 
-use crate::helpers::{get_shapes, scale_shape};
+use crate::{
+    contact_filter::{contact_filter_words, remove_contact_filter_entity},
+    get_physics_interpolation_alpha,
+    helpers::{get_shapes, scale_shape, update_ccd_flags, update_mass_properties},
+    main_physics_scene, physics_poses, wood_physics_material,
+};
 
 pub use ambient_ecs::generated::physics::components::*;
 
@@ -30,6 +36,7 @@ components!("physics", {
     articulation_link: PxArticulationLinkRef,
     articulation_cache: Option<PxArticulationCacheRef>,
     character_controller: PxControllerRef,
+    physics_material: PxMaterial,
 });
 
 #[derive(Debug)]
@@ -89,6 +96,36 @@ fn quat_changed(old: Quat, new: Quat) -> bool {
     !old.abs_diff_eq(new, 0.001)
 }
 
+/// Returns the pose of a body as read back by the physics thread, interpolated between the last two
+/// physics steps so that its transform moves smoothly even when physics isn't stepped every frame.
+///
+/// Bodies that weren't read back (like bodies created this frame) use their `current` pose.
+fn interpolated_pose(
+    world: &World,
+    id: EntityId,
+    alpha: f32,
+    current: impl FnOnce() -> (Vec3, Quat),
+) -> (Vec3, Quat) {
+    world
+        .resource_opt(physics_poses())
+        .and_then(|poses| poses.interpolated(id, alpha))
+        .unwrap_or_else(current)
+}
+
+/// Forgets the poses read back for the bodies of `ids`, which were just moved from the ECS, so that
+/// they aren't moved back to them if physics isn't stepped this frame
+fn forget_poses(world: &mut World, ids: Vec<EntityId>) {
+    if ids.is_empty() {
+        return;
+    }
+    if let Some(poses) = world.resource_mut_opt(physics_poses()) {
+        let poses = Arc::make_mut(poses);
+        for id in ids {
+            poses.forget(id);
+        }
+    }
+}
+
 /// Syncs physx to the ECS
 pub fn sync_ecs_physics() -> SystemGroup {
     let mut new_positions = Vec::new();
@@ -156,7 +193,9 @@ pub fn sync_ecs_physics() -> SystemGroup {
                 move |q, world, _, _| {
                     // Read back any changes that have happened during the frame to physx
                     let mut qs = translation_rotation_qs.lock();
+                    let mut moved = Vec::new();
                     for (id, (&pos, &rot)) in q.iter(world, Some(&mut *qs)) {
+                        moved.push(id);
                         let is_kinematic = world.has_component(id, kinematic());
 
                         if let Ok(body) = world.get(id, rigid_dynamic()) {
@@ -189,13 +228,16 @@ pub fn sync_ecs_physics() -> SystemGroup {
                             }
                         }
                     }
+                    forget_poses(world, moved);
                 }
             }),
             hiearchy_transform_q.to_system({
                 let hiearchy_transform_qs = hiearchy_transform_qs.clone();
                 move |q, world, _, _| {
                     let mut qs = hiearchy_transform_qs.lock();
+                    let mut moved = Vec::new();
                     for (id, &localworld) in q.iter(world, Some(&mut *qs)) {
+                        moved.push(id);
                         let is_kinematic = world.has_component(id, kinematic());
                         let (_scale, rot, pos) = localworld.to_scale_rotation_translation();
                         if let Ok(body) = world.get(id, rigid_dynamic()) {
@@ -229,6 +271,7 @@ pub fn sync_ecs_physics() -> SystemGroup {
                             }
                         }
                     }
+                    forget_poses(world, moved);
                 }
             }),
             translation_character_q.to_system({
@@ -244,10 +287,12 @@ pub fn sync_ecs_physics() -> SystemGroup {
                 let scale_qs = scale_qs.clone();
                 move |q, world, _, _| {
                     let mut qs = scale_qs.lock();
-                    for (id, &scl) in q.iter(world, Some(&mut *qs)) {
+                    for (id, scl) in q.collect_cloned(world, Some(&mut *qs)) {
                         for shape in get_shapes(world, id) {
                             scale_shape(shape, scl);
                         }
+                        // The volume of the shapes changed, so their mass did too
+                        update_mass_properties(world, id);
                     }
                 }
             }),
@@ -318,14 +363,60 @@ pub fn sync_ecs_physics() -> SystemGroup {
                         }
                     }
                 }),
-            // Sync PhysX changes to ECS.
+            query(ccd_enabled().changed()).to_system(|q, world, qs, _| {
+                for (id, &enabled) in q.iter(world, qs) {
+                    if let Ok(body) = world.get(id, rigid_dynamic()) {
+                        update_ccd_flags(&body, world.has_component(id, kinematic()), enabled);
+                    }
+                }
+            }),
+            query(physics_shape().changed())
+                .optional_changed(collision_groups())
+                .optional_changed(collision_ignored_groups())
+                .optional_changed(contact_filtered())
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        update_collision_filter(world, id);
+                    }
+                }),
+            collision_filter_removed(collision_groups()),
+            collision_filter_removed(collision_ignored_groups()),
+            collision_filter_removed(contact_filtered()),
+            query(())
+                .incl(physics_shape())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.iter(world, qs) {
+                        remove_contact_filter_entity(world, id);
+                    }
+                }),
+            query(physics_shape().changed())
+                .optional_changed(friction())
+                .optional_changed(friction_combine_mode())
+                .optional_changed(restitution())
+                .optional_changed(restitution_combine_mode())
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        update_physics_material(world, id);
+                    }
+                }),
+            // Removing a material component isn't a change, so the shapes fall back to the
+            // default for it when it is removed
+            physics_material_removed(friction()),
+            physics_material_removed(friction_combine_mode()),
+            physics_material_removed(restitution()),
+            physics_material_removed(restitution_combine_mode()),
+            // Sync PhysX changes to ECS. Sleeping entities don't move, so they can be skipped.
             query((rigid_dynamic(), translation(), rotation()))
                 .incl(physics_controlled())
+                .excl(sleeping())
                 .to_system(|q, world, qs, _| {
+                    let alpha = get_physics_interpolation_alpha(world);
                     for (id, (rigid_dynamic, pos, rot)) in q.collect_cloned(world, qs) {
-                        let pose = rigid_dynamic.get_global_pose();
-                        let new_pos = pose.translation();
-                        let new_rot = pose.rotation();
+                        let (new_pos, new_rot) = interpolated_pose(world, id, alpha, || {
+                            let pose = rigid_dynamic.get_global_pose();
+                            (pose.translation(), pose.rotation())
+                        });
                         if vec3_changed(pos, new_pos) {
                             world.set(id, translation(), new_pos).unwrap();
                         }
@@ -337,10 +428,12 @@ pub fn sync_ecs_physics() -> SystemGroup {
             query((rigid_actor(), translation(), rotation()))
                 .incl(physics_controlled())
                 .to_system(|q, world, qs, _| {
+                    let alpha = get_physics_interpolation_alpha(world);
                     for (id, (rigid_actor, pos, rot)) in q.collect_cloned(world, qs) {
-                        let pose = rigid_actor.get_global_pose();
-                        let new_pos = pose.translation();
-                        let new_rot = pose.rotation();
+                        let (new_pos, new_rot) = interpolated_pose(world, id, alpha, || {
+                            let pose = rigid_actor.get_global_pose();
+                            (pose.translation(), pose.rotation())
+                        });
                         if vec3_changed(pos, new_pos) {
                             world.set(id, translation(), new_pos).unwrap();
                         }
@@ -351,12 +444,16 @@ pub fn sync_ecs_physics() -> SystemGroup {
                 }),
             query((physics_shape(), translation(), rotation()))
                 .incl(physics_controlled())
+                .excl(sleeping())
                 .to_system(move |q, world, qs, _| {
+                    let alpha = get_physics_interpolation_alpha(world);
                     for (id, (shape, pos, rot)) in q.iter(world, qs) {
-                        let actor = shape.get_actor().unwrap();
-                        let global_pose = actor.get_global_pose().to_mat4();
-
-                        let (_, new_rot, new_pos) = (global_pose).to_scale_rotation_translation();
+                        let (new_pos, new_rot) = interpolated_pose(world, id, alpha, || {
+                            let actor = shape.get_actor().unwrap();
+                            let global_pose = actor.get_global_pose().to_mat4();
+                            let (_, rot, pos) = global_pose.to_scale_rotation_translation();
+                            (pos, rot)
+                        });
                         if vec3_changed(*pos, new_pos) {
                             new_positions.push((id, new_pos));
                         }
@@ -441,3 +538,131 @@ pub fn sync_ecs_physics() -> SystemGroup {
         ],
     )
 }
+
+/// Applies the collision groups and contact filtering of an entity to all of its shapes, and makes
+/// PhysX re-filter the pairs they are part of. PhysX caches the result of the filter shader for
+/// each pair until then.
+fn update_collision_filter(world: &World, id: EntityId) {
+    let (word2, word3) =
+        contact_filter_words(world, id, world.has_component(id, contact_filtered()));
+    let filter_data = PxFilterData {
+        word0: world.get(id, collision_groups()).unwrap_or_default(),
+        word1: world
+            .get(id, collision_ignored_groups())
+            .unwrap_or_default(),
+        word2,
+        word3,
+    };
+    let scene = world.resource(main_physics_scene());
+    for shape in get_shapes(world, id) {
+        if shape.get_simulation_filter_data() == filter_data {
+            continue;
+        }
+        shape.set_simulation_filter_data(&filter_data);
+        if let Some(actor) = shape.get_actor() {
+            scene.reset_filtering(&actor);
+        }
+    }
+}
+
+/// Applies the physics material components of an entity to all of its shapes.
+///
+/// Entities without any material components share the default wood material.
+fn update_physics_material(world: &mut World, id: EntityId) {
+    let friction = world.get(id, friction()).ok();
+    let friction_combine_mode = world.get(id, friction_combine_mode()).ok();
+    let restitution = world.get(id, restitution()).ok();
+    let restitution_combine_mode = world.get(id, restitution_combine_mode()).ok();
+
+    if friction.is_none()
+        && friction_combine_mode.is_none()
+        && restitution.is_none()
+        && restitution_combine_mode.is_none()
+    {
+        if world.has_component(id, physics_material()) {
+            world.remove_component(id, physics_material()).unwrap();
+            let material = world.resource(wood_physics_material()).clone();
+            for shape in get_shapes(world, id) {
+                shape.set_materials(&[&material]);
+            }
+        }
+        return;
+    }
+
+    let material = match world.get_ref(id, physics_material()) {
+        Ok(material) => material.clone(),
+        Err(_) => {
+            let wood = world.resource(wood_physics_material());
+            let material = PxMaterial::new(
+                world.resource(physics()).physics,
+                wood.get_static_friction(),
+                wood.get_dynamic_friction(),
+                wood.get_restitution(),
+            );
+            world
+                .add_component(id, physics_material(), material.clone())
+                .unwrap();
+            material
+        }
+    };
+    // The properties without a component take the default, as one may have just been removed
+    let wood = world.resource(wood_physics_material());
+    match friction {
+        Some(friction) => {
+            material.set_static_friction(friction);
+            material.set_dynamic_friction(friction);
+        }
+        None => {
+            material.set_static_friction(wood.get_static_friction());
+            material.set_dynamic_friction(wood.get_dynamic_friction());
+        }
+    }
+    material.set_friction_combine_mode(
+        friction_combine_mode.map_or_else(|| wood.get_friction_combine_mode(), px_combine_mode),
+    );
+    material.set_restitution(restitution.unwrap_or_else(|| wood.get_restitution()));
+    material.set_restitution_combine_mode(
+        restitution_combine_mode
+            .map_or_else(|| wood.get_restitution_combine_mode(), px_combine_mode),
+    );
+    for shape in get_shapes(world, id) {
+        shape.set_materials(&[&material]);
+    }
+}
+
+/// Updates the collision filter of the entities `component` is removed from
+fn collision_filter_removed<T: ComponentValue>(component: Component<T>) -> DynSystem {
+    query(())
+        .incl(component)
+        .despawned()
+        .to_system(|q, world, qs, _| {
+            for (id, _) in q.collect_cloned(world, qs) {
+                if world.exists(id) {
+                    update_collision_filter(world, id);
+                }
+            }
+        })
+}
+
+/// Updates the physics material of the entities `component` is removed from
+fn physics_material_removed<T: ComponentValue>(component: Component<T>) -> DynSystem {
+    query(())
+        .incl(component)
+        .despawned()
+        .to_system(|q, world, qs, _| {
+            for (id, _) in q.collect_cloned(world, qs) {
+                if world.exists(id) {
+                    update_physics_material(world, id);
+                }
+            }
+        })
+}
+
+fn px_combine_mode(mode: CombineMode) -> PxCombineMode {
+    match mode {
+        CombineMode::Average => PxCombineMode::Average,
+        CombineMode::Min => PxCombineMode::Min,
+        CombineMode::Multiply => PxCombineMode::Multiply,
+        CombineMode::Max => PxCombineMode::Max,
+    }
+}
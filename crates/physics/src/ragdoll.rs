@@ -0,0 +1,283 @@
+//! Ragdolls, built from the bones of an animated model.
+//!
+//! Each bone in the model's `animation_binder` becomes a link in a reduced-coordinate articulation,
+//! jointed to its parent bone. After each step, the simulated poses are written back to the local
+//! transforms of the bones, blended with the animated pose by `ragdoll_blend`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    f32::consts::PI,
+};
+
+use ambient_core::{
+    hierarchy::parent,
+    transform::{get_world_transform, rotation, translation},
+};
+use ambient_ecs::{components, query, Entity, EntityId, SystemGroup, World};
+use ambient_model::animation_binder;
+use glam::{Mat4, Quat, Vec3};
+use itertools::Itertools;
+use physxx::{
+    articulation_reduced_coordinate::PxArticulationRef, PxArticulationAxis,
+    PxArticulationJointBase, PxArticulationJointType, PxArticulationLinkRef, PxArticulationMotion,
+    PxCapsuleGeometry, PxGeometry, PxMaterial, PxRigidActor, PxRigidBody, PxShape,
+    PxSphereGeometry, PxTransform, PxUserData,
+};
+
+use crate::{
+    main_physics_scene,
+    physx::{articulation_link, articulation_reduce_coordinate, physics, Physics},
+    wood_physics_material, PxShapeUserData,
+};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+components!("physics", {
+    /// The bones of a ragdoll, with every bone listed after its parent.
+    ragdoll_bones: Vec<EntityId>,
+});
+
+/// PhysX articulations can't have more links than this.
+const MAX_RAGDOLL_LINKS: usize = 64;
+/// How far each joint can bend away from the pose the ragdoll was created in, in radians.
+const JOINT_LIMIT: f32 = PI / 4.;
+/// The radius of the sphere used for bones without any children.
+const LEAF_BONE_RADIUS: f32 = 0.05;
+
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/ragdoll",
+        vec![
+            query((ragdoll(), animation_binder()))
+                .spawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, (_, binder)) in q.collect_cloned(world, qs) {
+                        let bones = binder.into_values().collect_vec();
+                        create_ragdoll(world, id, &bones);
+                    }
+                }),
+            query((ragdoll(), articulation_reduce_coordinate(), ragdoll_bones()))
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, (_, articulation, bones)) in q.collect_cloned(world, qs) {
+                        release_ragdoll(world, id, articulation, &bones);
+                    }
+                }),
+            query((ragdoll_bones(), articulation_reduce_coordinate())).to_system(
+                |q, world, qs, _| {
+                    for (id, (bones, _)) in q.collect_cloned(world, qs) {
+                        pose_bones(world, id, &bones);
+                    }
+                },
+            ),
+        ],
+    )
+}
+
+/// Returns the largest tree of bones, with every bone after its parent.
+///
+/// An articulation has a single root, so bones that aren't part of the main skeleton (like the
+/// nodes of attached meshes) are left out.
+fn ragdoll_bone_order(world: &World, bones: &[EntityId]) -> Vec<EntityId> {
+    let bone_set: HashSet<EntityId> = bones.iter().copied().collect();
+    let bone_parent = |bone: EntityId| {
+        world
+            .get(bone, parent())
+            .ok()
+            .filter(|parent| bone_set.contains(parent))
+    };
+
+    let mut trees: HashMap<EntityId, Vec<(usize, EntityId)>> = HashMap::new();
+    for &bone in bones {
+        let (mut root, mut depth) = (bone, 0);
+        while let Some(parent) = bone_parent(root) {
+            root = parent;
+            depth += 1;
+        }
+        trees.entry(root).or_default().push((depth, bone));
+    }
+    let Some(mut tree) = trees.into_values().max_by_key(|tree| tree.len()) else {
+        return Vec::new();
+    };
+    tree.sort();
+    tree.into_iter()
+        .map(|(_, bone)| bone)
+        .take(MAX_RAGDOLL_LINKS)
+        .collect()
+}
+
+fn create_ragdoll(world: &mut World, id: EntityId, bones: &[EntityId]) {
+    let bones = ragdoll_bone_order(world, bones);
+    if bones.is_empty() {
+        tracing::warn!("Ragdoll {id} has no bones");
+        return;
+    }
+    let physics = world.resource(physics()).clone();
+    let material = world.resource(wood_physics_material()).clone();
+    let link_density = world.get(id, density()).unwrap_or(1.);
+
+    let poses = bones
+        .iter()
+        .map(|bone| {
+            let (_, rot, pos) = get_world_transform(world, *bone)
+                .unwrap_or_default()
+                .to_scale_rotation_translation();
+            (pos, rot)
+        })
+        .collect_vec();
+    let parents = bones
+        .iter()
+        .map(|bone| {
+            world
+                .get(*bone, parent())
+                .ok()
+                .and_then(|parent| bones.iter().position(|b| *b == parent))
+        })
+        .collect_vec();
+
+    let articulation = PxArticulationRef::new(&physics.physics);
+    let mut links: Vec<PxArticulationLinkRef> = Vec::with_capacity(bones.len());
+    for (index, &bone) in bones.iter().enumerate() {
+        let (pos, rot) = poses[index];
+        let parent = parents[index];
+        let link = PxArticulationLinkRef::new(
+            &articulation,
+            parent.map(|parent| &links[parent]),
+            &PxTransform::new(pos, rot),
+        );
+
+        let children = (0..bones.len())
+            .filter(|child| parents[*child] == Some(index))
+            .map(|child| rot.inverse() * (poses[child].0 - pos))
+            .collect_vec();
+        let shape = bone_shape(&physics, &material, &children);
+        shape.set_user_data(PxShapeUserData {
+            entity: bone,
+            density: link_density,
+            base_pose: Mat4::IDENTITY,
+        });
+        link.attach_shape(&shape);
+        link.update_mass_and_inertia(vec![link_density], None, None);
+
+        if let Some(parent) = parent {
+            let (parent_pos, parent_rot) = poses[parent];
+            let mut joint = link.get_inbound_joint().to_reduced_coordinate();
+            joint.set_joint_type(PxArticulationJointType::Spherical);
+            for axis in [
+                PxArticulationAxis::Twist,
+                PxArticulationAxis::Swing1,
+                PxArticulationAxis::Swing2,
+            ] {
+                joint.set_motion(axis, PxArticulationMotion::Limited);
+                joint.set_limit(axis, -JOINT_LIMIT, JOINT_LIMIT);
+            }
+            joint.set_parent_pose(&PxTransform::new(
+                parent_rot.inverse() * (pos - parent_pos),
+                parent_rot.inverse() * rot,
+            ));
+            joint.set_child_pose(&PxTransform::identity());
+        }
+
+        world.add_component(bone, articulation_link(), link).ok();
+        links.push(link);
+    }
+
+    world
+        .resource(main_physics_scene())
+        .add_articulation(&articulation);
+    world
+        .add_components(
+            id,
+            Entity::new()
+                .with(articulation_reduce_coordinate(), articulation)
+                .with(ragdoll_bones(), bones),
+        )
+        .unwrap();
+}
+
+/// Creates a capsule reaching from the bone to the average position of its children (in the bone's
+/// local space), or a small sphere if it doesn't have any.
+fn bone_shape(physics: &Physics, material: &PxMaterial, children: &[Vec3]) -> PxShape {
+    let target = children.iter().sum::<Vec3>() / children.len().max(1) as f32;
+    let length = target.length();
+    let radius = (length * 0.2).clamp(0.02, 0.2);
+    let half_height = length / 2. - radius;
+
+    let (geometry, pose): (Box<dyn PxGeometry>, PxTransform) = if half_height > 0. {
+        (
+            Box::new(PxCapsuleGeometry::new(radius, half_height)),
+            // Capsules extend along their local X axis
+            PxTransform::new(
+                target / 2.,
+                Quat::from_rotation_arc(Vec3::X, target / length),
+            ),
+        )
+    } else {
+        (
+            Box::new(PxSphereGeometry::new(LEAF_BONE_RADIUS.max(length / 2.))),
+            PxTransform::from_translation(target / 2.),
+        )
+    };
+    let shape = PxShape::new(physics.physics, &*geometry, &[material], Some(true), None);
+    shape.set_local_pose(&pose);
+    shape
+}
+
+/// Writes the simulated poses of the links to the local transforms of the bones.
+fn pose_bones(world: &mut World, id: EntityId, bones: &[EntityId]) {
+    let blend = world.get(id, ragdoll_blend()).unwrap_or(1.).clamp(0., 1.);
+    if blend <= 0. {
+        return;
+    }
+
+    let mut simulated: HashMap<EntityId, Mat4> = HashMap::new();
+    for &bone in bones {
+        let (Ok(link), Ok(bone_parent)) = (
+            world.get(bone, articulation_link()),
+            world.get(bone, parent()),
+        ) else {
+            continue;
+        };
+        let (bone_scale, _, _) = get_world_transform(world, bone)
+            .unwrap_or_default()
+            .to_scale_rotation_translation();
+        let pose = link.get_global_pose();
+        let transform =
+            Mat4::from_scale_rotation_translation(bone_scale, pose.rotation(), pose.translation());
+        let parent_transform = match simulated.get(&bone_parent) {
+            Some(transform) => *transform,
+            None => get_world_transform(world, bone_parent).unwrap_or_default(),
+        };
+        simulated.insert(bone, transform);
+
+        let (_, local_rot, local_pos) =
+            (parent_transform.inverse() * transform).to_scale_rotation_translation();
+        let animated_rot = world.get(bone, rotation()).unwrap_or_default();
+        let animated_pos = world.get(bone, translation()).unwrap_or_default();
+        world
+            .set(bone, rotation(), animated_rot.slerp(local_rot, blend))
+            .ok();
+        world
+            .set(bone, translation(), animated_pos.lerp(local_pos, blend))
+            .ok();
+    }
+}
+
+fn release_ragdoll(
+    world: &mut World,
+    id: EntityId,
+    mut articulation: PxArticulationRef,
+    bones: &[EntityId],
+) {
+    world
+        .resource(main_physics_scene())
+        .remove_articulation(&articulation, true);
+    articulation.release();
+    for &bone in bones {
+        world.remove_component(bone, articulation_link()).ok();
+    }
+    world
+        .remove_component(id, articulation_reduce_coordinate())
+        .ok();
+    world.remove_component(id, ragdoll_bones()).ok();
+}
@@ -0,0 +1,45 @@
+use ambient_ecs::{query, SystemGroup};
+
+use crate::{physx::rigid_dynamic, slept_bodies, woken_bodies};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+/// Applies `sleep_threshold`, and keeps `sleeping` up to date with PhysX. The entities that fell
+/// asleep or woke up this frame are recorded in [slept_bodies] and [woken_bodies].
+pub fn server_systems() -> SystemGroup {
+    SystemGroup::new(
+        "physics/sleep",
+        vec![
+            query(rigid_dynamic().changed())
+                .optional_changed(sleep_threshold())
+                .to_system(|q, world, qs, _| {
+                    for (id, body) in q.iter(world, qs) {
+                        if let Ok(threshold) = world.get(id, sleep_threshold()) {
+                            body.set_sleep_threshold(threshold);
+                        }
+                    }
+                }),
+            query(rigid_dynamic())
+                .excl(kinematic())
+                .to_system(|q, world, qs, _| {
+                    let mut slept = Vec::new();
+                    let mut woken = Vec::new();
+                    for (id, body) in q.collect_cloned(world, qs) {
+                        match (body.is_sleeping(), world.has_component(id, sleeping())) {
+                            (true, false) => {
+                                world.add_component(id, sleeping(), ()).unwrap();
+                                slept.push(id);
+                            }
+                            (false, true) => {
+                                world.remove_component(id, sleeping()).unwrap();
+                                woken.push(id);
+                            }
+                            _ => {}
+                        }
+                    }
+                    *world.resource_mut(slept_bodies()) = slept;
+                    *world.resource_mut(woken_bodies()) = woken;
+                }),
+        ],
+    )
+}
@@ -0,0 +1,148 @@
+//! Runs the physics simulation on a dedicated thread.
+//!
+//! [run_simulation_system](crate::run_simulation_system) hands the steps of a frame to the physics
+//! thread, and [fetch_simulation_system](crate::fetch_simulation_system) waits for them to finish.
+//! PhysX must not be accessed in between, so anything that has to happen before each step is done
+//! by a [StepHook], and the poses of the bodies are read back on the physics thread into a
+//! [PhysicsPoses] buffer, which the ECS is then synced from. The poses of bodies moved from outside
+//! of the simulation are forgotten, so that a frame without a step doesn't move them back.
+
+use std::{
+    collections::HashMap,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use ambient_ecs::EntityId;
+use glam::{Quat, Vec3};
+use parking_lot::Mutex;
+use physxx::{PxRigidActor, PxRigidDynamicRef, PxSceneRef};
+
+/// Called on the physics thread before each step, with the index of the step and the number of
+/// steps in the frame.
+pub(crate) type StepHook = Box<dyn FnMut(u32, u32) + Send>;
+
+pub(crate) struct StepJob {
+    pub scene: PxSceneRef,
+    pub timestep: f32,
+    pub steps: u32,
+    pub hooks: Vec<StepHook>,
+    /// The bodies to read back the poses of.
+    pub bodies: Vec<(EntityId, PxRigidDynamicRef)>,
+}
+
+/// The poses of the physics-controlled bodies before and after the last step of a frame.
+#[derive(Debug, Default, Clone)]
+pub struct PhysicsPoses {
+    previous: HashMap<EntityId, (Vec3, Quat)>,
+    current: HashMap<EntityId, (Vec3, Quat)>,
+}
+impl PhysicsPoses {
+    /// Returns the pose of `id` interpolated between the last two steps, from 0 (the previous step)
+    /// to 1 (the latest step), or `None` if it wasn't read back.
+    pub fn interpolated(&self, id: EntityId, alpha: f32) -> Option<(Vec3, Quat)> {
+        let (pos, rot) = *self.current.get(&id)?;
+        Some(match self.previous.get(&id) {
+            Some((prev_pos, prev_rot)) if alpha < 1. => {
+                (prev_pos.lerp(pos, alpha), prev_rot.slerp(rot, alpha))
+            }
+            _ => (pos, rot),
+        })
+    }
+    /// Forgets the poses of `id`, as its body was moved since they were read back
+    pub(crate) fn forget(&mut self, id: EntityId) {
+        self.previous.remove(&id);
+        self.current.remove(&id);
+    }
+}
+
+pub struct PhysicsThread {
+    jobs: Mutex<Sender<StepJob>>,
+    results: Mutex<Receiver<PhysicsPoses>>,
+}
+impl PhysicsThread {
+    /// Spawns the physics thread. It stops when this is dropped.
+    pub fn new() -> Self {
+        let (jobs, job_receiver) = channel::<StepJob>();
+        let (result_sender, results) = channel();
+        std::thread::Builder::new()
+            .name("physics".to_string())
+            .spawn(move || {
+                for job in job_receiver {
+                    if result_sender.send(run_job(job)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("Failed to spawn the physics thread");
+        Self {
+            jobs: Mutex::new(jobs),
+            results: Mutex::new(results),
+        }
+    }
+    pub(crate) fn start(&self, job: StepJob) {
+        self.jobs
+            .lock()
+            .send(job)
+            .expect("The physics thread has stopped");
+    }
+    /// Blocks until the job given to [Self::start] has finished.
+    pub(crate) fn wait(&self) -> PhysicsPoses {
+        self.results
+            .lock()
+            .recv()
+            .expect("The physics thread has stopped")
+    }
+}
+impl Default for PhysicsThread {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn run_job(mut job: StepJob) -> PhysicsPoses {
+    profiling::scope!("physics_thread_job");
+    let read_poses = |bodies: &[(EntityId, PxRigidDynamicRef)]| {
+        bodies
+            .iter()
+            .map(|(id, body)| {
+                let pose = body.get_global_pose();
+                (*id, (pose.translation(), pose.rotation()))
+            })
+            .collect::<HashMap<_, _>>()
+    };
+
+    let mut previous = HashMap::new();
+    for step in 0..job.steps {
+        if step + 1 == job.steps {
+            previous = read_poses(&job.bodies);
+        }
+        for hook in &mut job.hooks {
+            hook(step, job.steps);
+        }
+        job.scene.simulate(job.timestep);
+        job.scene.fetch_results(true);
+    }
+    PhysicsPoses {
+        previous,
+        current: read_poses(&job.bodies),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forgotten_poses_are_not_interpolated() {
+        let (moved, other) = (EntityId::new(), EntityId::new());
+        let pose = (Vec3::X, Quat::IDENTITY);
+        let mut poses = PhysicsPoses {
+            previous: [(moved, pose), (other, pose)].into(),
+            current: [(moved, pose), (other, pose)].into(),
+        };
+
+        poses.forget(moved);
+        assert_eq!(poses.interpolated(moved, 0.5), None);
+        assert_eq!(poses.interpolated(other, 0.5), Some(pose));
+    }
+}
@@ -0,0 +1,285 @@
+use ambient_ecs::{query, EntityId, World};
+use ambient_native_std::math::interpolate;
+use glam::{Quat, Vec2, Vec3};
+use physxx::{
+    PxForceMode, PxQueryFilterData, PxQueryFlag, PxRaycastCallback, PxRigidActor, PxRigidBody,
+    PxRigidDynamicRef, PxSceneRef,
+};
+
+use crate::{
+    get_physics_timestep, main_physics_scene, physx::rigid_dynamic, thread::StepHook,
+    vehicle_compressions,
+};
+
+pub use ambient_ecs::generated::physics::components::*;
+
+// These match the suggested defaults in the schema.
+const DEFAULT_WHEEL_RADIUS: f32 = 0.4;
+const DEFAULT_SUSPENSION_LENGTH: f32 = 0.5;
+const DEFAULT_SUSPENSION_STIFFNESS: f32 = 30000.;
+const DEFAULT_SUSPENSION_DAMPING: f32 = 3000.;
+const DEFAULT_ENGINE_FORCE: f32 = 6000.;
+const DEFAULT_BRAKE_FORCE: f32 = 8000.;
+const DEFAULT_MAX_STEER_ANGLE: f32 = 0.6;
+const DEFAULT_TIRE_GRIP: f32 = 1.5;
+
+/// Returns a hook that simulates all entities with `vehicle_wheel_offsets` as raycast vehicles.
+///
+/// Each wheel is a ray cast down from its suspension mount; the suspension, tire and engine forces
+/// are then applied to the chassis at the contact point. Forces are cleared after each simulation
+/// step, so they are added again before every step, which keeps the vehicles independent of the
+/// frame rate. The compressions of the last step are recorded in [vehicle_compressions].
+pub(crate) fn vehicle_hook(world: &World) -> Option<StepHook> {
+    let vehicles = query((vehicle_wheel_offsets(), rigid_dynamic()))
+        .excl(kinematic())
+        .iter(world, None)
+        .map(|(id, (offsets, body))| {
+            (
+                id,
+                *body,
+                offsets.clone(),
+                VehicleParams::from_world(world, id),
+            )
+        })
+        .collect::<Vec<_>>();
+    if vehicles.is_empty() {
+        return None;
+    }
+
+    let scene = *world.resource(main_physics_scene());
+    let dt = get_physics_timestep(world);
+    let compressions = world.resource(vehicle_compressions()).clone();
+    Some(Box::new(move |step, steps| {
+        let last_step = step + 1 == steps;
+        if last_step {
+            compressions.lock().clear();
+        }
+        for (id, body, offsets, params) in &vehicles {
+            let wheels = simulate_vehicle(scene, dt, *body, offsets, params);
+            if last_step {
+                compressions.lock().push((*id, wheels));
+            }
+        }
+    }))
+}
+
+/// Writes the compressions recorded by [vehicle_hook] to `vehicle_wheel_compressions`.
+pub(crate) fn apply_compressions(world: &mut World) {
+    let compressions = std::mem::take(&mut *world.resource(vehicle_compressions()).lock());
+    for (id, wheels) in compressions {
+        if world.exists(id) {
+            world
+                .add_component(id, vehicle_wheel_compressions(), wheels)
+                .unwrap();
+        }
+    }
+}
+
+struct VehicleParams {
+    wheel_radius: f32,
+    suspension_length: f32,
+    suspension_stiffness: f32,
+    suspension_damping: f32,
+    engine_force: f32,
+    engine_curve: Vec<Vec2>,
+    brake_force: f32,
+    max_steer_angle: f32,
+    tire_grip: f32,
+    steered_wheels: Option<Vec<u32>>,
+    driven_wheels: Option<Vec<u32>>,
+    throttle: f32,
+    steer: f32,
+    brake: f32,
+}
+impl VehicleParams {
+    fn from_world(world: &World, id: EntityId) -> Self {
+        Self {
+            wheel_radius: world
+                .get(id, vehicle_wheel_radius())
+                .unwrap_or(DEFAULT_WHEEL_RADIUS),
+            suspension_length: world
+                .get(id, vehicle_suspension_length())
+                .unwrap_or(DEFAULT_SUSPENSION_LENGTH),
+            suspension_stiffness: world
+                .get(id, vehicle_suspension_stiffness())
+                .unwrap_or(DEFAULT_SUSPENSION_STIFFNESS),
+            suspension_damping: world
+                .get(id, vehicle_suspension_damping())
+                .unwrap_or(DEFAULT_SUSPENSION_DAMPING),
+            engine_force: world
+                .get(id, vehicle_engine_force())
+                .unwrap_or(DEFAULT_ENGINE_FORCE),
+            engine_curve: world
+                .get_cloned(id, vehicle_engine_curve())
+                .unwrap_or_default(),
+            brake_force: world
+                .get(id, vehicle_brake_force())
+                .unwrap_or(DEFAULT_BRAKE_FORCE),
+            max_steer_angle: world
+                .get(id, vehicle_max_steer_angle())
+                .unwrap_or(DEFAULT_MAX_STEER_ANGLE),
+            tire_grip: world
+                .get(id, vehicle_tire_grip())
+                .unwrap_or(DEFAULT_TIRE_GRIP),
+            steered_wheels: world.get_cloned(id, vehicle_steered_wheels()).ok(),
+            driven_wheels: world.get_cloned(id, vehicle_driven_wheels()).ok(),
+            throttle: world
+                .get(id, vehicle_throttle())
+                .unwrap_or_default()
+                .clamp(-1., 1.),
+            steer: world
+                .get(id, vehicle_steer())
+                .unwrap_or_default()
+                .clamp(-1., 1.),
+            brake: world
+                .get(id, vehicle_brake())
+                .unwrap_or_default()
+                .clamp(0., 1.),
+        }
+    }
+    fn is_steered(&self, wheel: usize) -> bool {
+        self.steered_wheels
+            .as_ref()
+            .map(|wheels| wheels.contains(&(wheel as u32)))
+            .unwrap_or(false)
+    }
+    fn is_driven(&self, wheel: usize) -> bool {
+        self.driven_wheels
+            .as_ref()
+            .map(|wheels| wheels.contains(&(wheel as u32)))
+            .unwrap_or(true)
+    }
+}
+
+/// Applies the wheel forces for one step of `dt`, and returns the compression of each wheel.
+fn simulate_vehicle(
+    scene: PxSceneRef,
+    dt: f32,
+    body: PxRigidDynamicRef,
+    offsets: &[Vec3],
+    params: &VehicleParams,
+) -> Vec<f32> {
+    if offsets.is_empty() {
+        return Vec::new();
+    }
+
+    let pose = body.get_global_pose();
+    let (position, rotation) = (pose.translation(), pose.rotation());
+    let up = rotation * Vec3::Z;
+    let forward = rotation * -Vec3::Y;
+    let mass_per_wheel = body.get_mass() / offsets.len() as f32;
+
+    let driven_count = (0..offsets.len()).filter(|i| params.is_driven(*i)).count();
+    let forward_speed = body.get_linear_velocity().dot(forward);
+    let engine_force = params.throttle
+        * params.engine_force
+        * sample_engine_curve(&params.engine_curve, forward_speed.abs())
+        / driven_count.max(1) as f32;
+    let brake_force = params.brake * params.brake_force / offsets.len() as f32;
+    let steer_rotation = Quat::from_axis_angle(up, -params.steer * params.max_steer_angle);
+
+    let mut filter_data = PxQueryFilterData::new();
+    filter_data.set_flags(PxQueryFlag::STATIC | PxQueryFlag::DYNAMIC | PxQueryFlag::NO_BLOCK);
+    let ray_length = params.suspension_length + params.wheel_radius;
+
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(index, offset)| {
+            let mount = position + rotation * *offset;
+
+            let mut hit_call = PxRaycastCallback::new(16);
+            if !scene.raycast(mount, -up, ray_length, &mut hit_call, None, &filter_data) {
+                return 0.;
+            }
+            // The ray starts inside the chassis, so ignore any hits against ourselves.
+            let Some(hit) = hit_call
+                .touches()
+                .into_iter()
+                .filter(|hit| hit.actor != Some(body.as_rigid_actor()))
+                .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            else {
+                return 0.;
+            };
+
+            let compression = (ray_length - hit.distance).clamp(0., params.suspension_length);
+            let contact_velocity = body.get_velocity_at_pos(hit.position);
+
+            // Suspension: a damped spring pushing the chassis away from the ground.
+            let suspension = (params.suspension_stiffness * compression
+                - params.suspension_damping * contact_velocity.dot(up))
+            .max(0.);
+            let max_tire_force = suspension * params.tire_grip;
+
+            let wheel_forward = if params.is_steered(index) {
+                steer_rotation * forward
+            } else {
+                forward
+            };
+            let wheel_forward =
+                (wheel_forward - hit.normal * wheel_forward.dot(hit.normal)).normalize_or_zero();
+            let wheel_right = wheel_forward.cross(hit.normal).normalize_or_zero();
+
+            // Lateral grip: cancel out the sideways velocity of the contact point.
+            let lateral_speed = contact_velocity.dot(wheel_right);
+            let lateral =
+                (-lateral_speed * mass_per_wheel / dt).clamp(-max_tire_force, max_tire_force);
+
+            // Longitudinal: engine and brakes.
+            let longitudinal_speed = contact_velocity.dot(wheel_forward);
+            let mut longitudinal = if params.is_driven(index) {
+                engine_force
+            } else {
+                0.
+            };
+            let max_brake = longitudinal_speed.abs() * mass_per_wheel / dt;
+            longitudinal -= longitudinal_speed.signum() * brake_force.min(max_brake);
+            let longitudinal = longitudinal.clamp(-max_tire_force, max_tire_force);
+
+            let force =
+                hit.normal * suspension + wheel_right * lateral + wheel_forward * longitudinal;
+            body.add_force_at_pos(force, hit.position, Some(PxForceMode::Force), Some(true));
+
+            compression / params.suspension_length
+        })
+        .collect()
+}
+
+/// Samples an engine curve made of `(speed, multiplier)` points at the given speed.
+fn sample_engine_curve(curve: &[Vec2], speed: f32) -> f32 {
+    let (Some(first), Some(last)) = (curve.first(), curve.last()) else {
+        return 1.;
+    };
+    if speed <= first.x {
+        return first.y;
+    }
+    if speed >= last.x {
+        return last.y;
+    }
+    curve
+        .windows(2)
+        .find(|points| speed <= points[1].x)
+        .map(|points| interpolate(speed, points[0].x, points[1].x, points[0].y, points[1].y))
+        .unwrap_or(last.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec2;
+
+    use super::sample_engine_curve;
+
+    #[test]
+    fn engine_curve_defaults_to_full_force() {
+        assert_eq!(sample_engine_curve(&[], 10.), 1.);
+    }
+
+    #[test]
+    fn engine_curve_is_clamped_and_interpolated() {
+        let curve = [vec2(0., 1.), vec2(10., 0.5), vec2(30., 0.)];
+        assert_eq!(sample_engine_curve(&curve, -1.), 1.);
+        assert_eq!(sample_engine_curve(&curve, 5.), 0.75);
+        assert_eq!(sample_engine_curve(&curve, 20.), 0.25);
+        assert_eq!(sample_engine_curve(&curve, 40.), 0.);
+    }
+}
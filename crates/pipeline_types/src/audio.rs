@@ -5,8 +5,15 @@ use crate::is_false;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct AudioPipeline {
-    /// Whether or not the audio should be converted to Ogg Vorbis.
+    /// Whether or not wav files should be converted to Ogg Vorbis. mp3 and flac files are always
+    /// converted, as Ogg Vorbis is the format that plays best at runtime.
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
     pub convert: bool,
+    /// Normalizes the loudness of the audio to this integrated loudness, in LUFS, like `-16.0` for
+    /// music and dialogue or `-23.0` for broadcast levels. The audio is converted to Ogg Vorbis,
+    /// and its gain is limited so that its peaks stay below -1 dBFS.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<f32>,
 }
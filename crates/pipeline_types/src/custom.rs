@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomPipeline {
+    /// The name of the asset processor to import the files with, either one declared in the
+    /// `[build.asset-processors]` section of the package's `ambient.toml`, or one registered by
+    /// the application building the package.
+    pub processor: String,
+    /// Settings passed as is to the asset processor.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "toml::Table::is_empty")]
+    pub config: toml::Table,
+}
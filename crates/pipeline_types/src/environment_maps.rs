@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnvironmentMapsPipeline {
+    /// The width, in pixels, of the environment maps, whose height is half of it. Larger images
+    /// are downscaled to it. Defaults to 512.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+}
@@ -1,11 +1,23 @@
 pub mod audio;
+pub mod custom;
+pub mod environment_maps;
 pub mod materials;
 pub mod models;
+pub mod navmeshes;
+pub mod string_tables;
+pub mod texture_atlases;
+pub mod textures;
 pub use audio::AudioPipeline;
+pub use custom::CustomPipeline;
+pub use environment_maps::EnvironmentMapsPipeline;
 pub use materials::{MaterialsImporter, MaterialsPipeline, PipelinePbrMaterial};
-pub use models::{Collider, ModelImporter, ModelsPipeline};
+pub use models::{Collider, ModelImporter, ModelLods, ModelsPipeline};
+pub use navmeshes::NavmeshesPipeline;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+pub use string_tables::StringTablesPipeline;
+pub use texture_atlases::TextureAtlasesPipeline;
+pub use textures::TextureCompression;
 
 fn is_false(value: &bool) -> bool {
     !*value
@@ -53,6 +65,21 @@ pub enum PipelineProcessor {
     /// The audio asset pipeline.
     /// Will import supported audio file formats and produce Ogg Vorbis or WAV files to be used by the runtime.
     Audio(AudioPipeline),
+    /// The environment maps asset pipeline.
+    /// Will prefilter HDR images (`.hdr` or `.exr`) into environment maps, which light a scene and can be shown as its sky.
+    EnvironmentMaps(EnvironmentMapsPipeline),
+    /// The texture atlases asset pipeline.
+    /// Will pack images into a single texture atlas, whose images can be shown by UI elements and nine-sliced.
+    TextureAtlases(TextureAtlasesPipeline),
+    /// The string tables asset pipeline.
+    /// Will merge TOML string tables, one for each locale, into the strings the runtime translates text with.
+    StringTables(StringTablesPipeline),
+    /// The navmeshes asset pipeline.
+    /// Will bake the models of a level into a single navigation mesh, which agents find paths on at runtime.
+    Navmeshes(NavmeshesPipeline),
+    /// A pipeline run by a user-supplied asset processor.
+    /// Will import each file with the processor, to support formats the other pipelines don't, like voxel files or tilemaps.
+    Custom(CustomPipeline),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,4 +114,9 @@ pub struct Pipeline {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub categories: Vec<Vec<String>>,
+    /// Compress the textures of the materials output by this pipeline, to make them smaller to
+    /// download and in GPU memory. Only used by the `Models` and `Materials` pipelines.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub texture_compression: Option<TextureCompression>,
 }
@@ -65,6 +65,25 @@ pub struct PipelinePbrMaterial {
     pub metallic_factor: Option<f32>,
     /// Roughness map multiplier. Defaults to `1.0`
     pub roughness_factor: Option<f32>,
+    /// How strongly this material reflects its surroundings, between 0 and 1, with the
+    /// `ssr` post-processing effect or a planar reflection probe. Rough materials reflect less
+    /// regardless. Defaults to `1.0`
+    pub reflectivity: Option<f32>,
+    /// How far the top of meshes with this material sways with the wind, in meters per m/s of
+    /// wind. Use it for foliage, along with `wind_flutter` and `wind_height`. Defaults to `0.0`
+    pub wind_sway: Option<f32>,
+    /// How far the vertices of meshes with this material flutter with the wind, in meters per m/s
+    /// of wind, like leaves. Defaults to `0.0`
+    pub wind_flutter: Option<f32>,
+    /// The height above their origin at which meshes with this material bend fully with the
+    /// wind; their base stays in place. Defaults to `1.0`
+    pub wind_height: Option<f32>,
+    /// When transparent meshes with this material are drawn: lower groups are drawn first.
+    /// Defaults to `0`
+    pub transparency_group: Option<i32>,
+    /// How many meters transparent meshes with this material are moved towards the camera when
+    /// they are sorted, to draw them over the transparent meshes around them. Defaults to `0.0`
+    pub transparency_sort_bias: Option<f32>,
 
     // Non-PBR properties that get translated to PBR.
     /// The non-PBR specular map of this material. If specified, it will be translated to a PBR equivalent.
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{is_default, is_false, is_true, materials::PipelinePbrMaterial, true_value};
@@ -61,6 +63,135 @@ pub struct ModelsPipeline {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub transforms: Vec<ModelTransform>,
+    /// If specified, the levels of detail authored in each model are merged into a single model,
+    /// which shows one of them at a time depending on its size on the screen.
+    ///
+    /// The levels are the top-level nodes of the model whose names end with `LOD0`, `LOD1`, and so on.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lods: Option<ModelLods>,
+    /// If specified, the animations of each model are retargeted onto the skeleton of another model,
+    /// so that they can be played on a character with different proportions.
+    ///
+    /// Only supported by the regular importer.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retarget: Option<ModelRetarget>,
+    /// If specified, levels of detail are generated for each model by simplifying its meshes, and
+    /// shown depending on its size on the screen, like authored ones.
+    ///
+    /// The nodes which already have authored levels of detail merged with `lods` are left as is.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generate_lods: Option<ModelLodGeneration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ModelLods {
+    /// The minimum screen size of each level of detail, from the most detailed level to the least.
+    /// The screen size is the fraction of the height of the screen covered by the model.
+    ///
+    /// Below the last size, the model is not rendered at all. If not specified, the sizes are spread
+    /// evenly from the full screen down to 4% of it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_sizes: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ModelLodGeneration {
+    /// The fraction of the triangles of the model kept by each generated level of detail, from the
+    /// most detailed to the least. The model itself is the first level, and isn't listed.
+    ///
+    /// If not specified, each level keeps half of the triangles of the previous one, for three levels.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratios: Option<Vec<f32>>,
+    /// The furthest the surface of a generated level of detail may stray from the model, as a
+    /// fraction of the radius of its bounds. The simplification of a level stops there even if it
+    /// keeps more triangles than its ratio, so that detailed shapes keep their silhouette.
+    ///
+    /// If not specified, it is 5% of the radius.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_error: Option<f32>,
+    /// The minimum screen size of each level of detail, including the model itself and the
+    /// imposter, like the `screen_sizes` of [`ModelLods`].
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_sizes: Option<Vec<f32>>,
+    /// If specified, an octahedral imposter is baked as the last level of detail: a quad showing the
+    /// model from the closest of many directions, rendered to a texture at build time.
+    ///
+    /// Skinned meshes don't get an imposter, as it couldn't follow their animations.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imposter: Option<ModelImposter>,
+}
+impl ModelLodGeneration {
+    pub fn ratios(&self) -> Vec<f32> {
+        self.ratios
+            .clone()
+            .unwrap_or_else(|| vec![0.5, 0.25, 0.125])
+    }
+    pub fn max_error(&self) -> f32 {
+        self.max_error.unwrap_or(0.05)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ModelImposter {
+    /// The number of views along each side of the grid of views of the model, of at least 2.
+    /// Defaults to 8, for 64 views.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames: Option<u32>,
+    /// The width and height of each view, in pixels. Defaults to 128.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<u32>,
+}
+impl ModelImposter {
+    pub fn frames(&self) -> u32 {
+        self.frames.unwrap_or(8).max(2)
+    }
+    pub fn resolution(&self) -> u32 {
+        self.resolution.unwrap_or(128).max(1)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelRetarget {
+    /// The path to the model whose skeleton the animations are retargeted onto, relative to the pipeline.
+    pub model: String,
+    /// The path to a [`SkeletonMap`] TOML file, relative to the pipeline. If not specified, the bones
+    /// of the two skeletons are matched by name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skeleton_map: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+/// How the bones of the skeleton an animation was authored on map to the bones of another skeleton.
+///
+/// Bones are referred to by their name in the model, or by their normalized name (i.e. `LeftUpLeg`
+/// for `mixamorig:LeftUpLeg`).
+pub struct SkeletonMap {
+    /// The bones of the target skeleton, keyed by the bones of the source skeleton. Animated bones
+    /// that aren't listed are matched with the bone of the same name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub bones: HashMap<String, String>,
+    /// The animated bones of the source skeleton that have no counterpart in the target skeleton.
+    /// Their animation is dropped.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,6 +245,12 @@ pub enum Collider {
         #[serde(default = "true_value")]
         #[serde(skip_serializing_if = "is_true")]
         reverse_indices: bool,
+        /// The maximum number of convex hulls each mesh is split into when the collider is dynamic.
+        /// Dynamic colliders have to be convex, so concave meshes are decomposed into several convex hulls
+        /// to keep their shape. Set this to 1 to use a single convex hull. Defaults to 16.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_convex_hulls: Option<u32>,
     },
     /// Use a cylindrical character collider.
     Character {
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+/// Bakes the models of a level into a navigation mesh, which covers the surfaces an agent can
+/// walk on, for finding paths at runtime.
+pub struct NavmeshesPipeline {
+    /// The name of the navmesh, which is written as `<name>.navmesh`. Defaults to `navmesh`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The radius of the agent, in meters. The navmesh is kept this far from walls and ledges.
+    /// Defaults to `0.4`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_radius: Option<f32>,
+    /// The height of the agent, in meters. Surfaces with less room above them are left out.
+    /// Defaults to `1.8`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_height: Option<f32>,
+    /// The steepest slope the agent can walk up, in degrees. Defaults to `45`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_slope: Option<f32>,
+    /// The highest step the agent can climb, like a stair, in meters. Defaults to `0.4`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_climb: Option<f32>,
+    /// The size of the cells the level is voxelized into, in meters. Smaller cells follow the
+    /// level more closely, but take longer to bake. Defaults to half of `agent_radius`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cell_size: Option<f32>,
+}
+impl NavmeshesPipeline {
+    pub const DEFAULT_AGENT_RADIUS: f32 = 0.4;
+    pub const DEFAULT_AGENT_HEIGHT: f32 = 1.8;
+    pub const DEFAULT_MAX_SLOPE: f32 = 45.;
+    pub const DEFAULT_MAX_CLIMB: f32 = 0.4;
+
+    /// The settings the navmesh is baked with
+    pub fn settings(&self) -> NavmeshSettings {
+        let agent_radius = self.agent_radius.unwrap_or(Self::DEFAULT_AGENT_RADIUS);
+        NavmeshSettings {
+            agent_radius,
+            agent_height: self.agent_height.unwrap_or(Self::DEFAULT_AGENT_HEIGHT),
+            max_slope: self.max_slope.unwrap_or(Self::DEFAULT_MAX_SLOPE),
+            max_climb: self.max_climb.unwrap_or(Self::DEFAULT_MAX_CLIMB),
+            cell_size: self.cell_size.unwrap_or(agent_radius / 2.),
+        }
+    }
+}
+
+/// How a navmesh is baked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavmeshSettings {
+    pub agent_radius: f32,
+    pub agent_height: f32,
+    /// In degrees
+    pub max_slope: f32,
+    pub max_climb: f32,
+    pub cell_size: f32,
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use glam::Vec4;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TextureAtlasesPipeline {
+    /// The name of the atlas, which is written as `<name>.atlas.json` and `<name>.atlas.png`.
+    /// Defaults to `atlas`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The pixels left between the images, which are filled with their edges so they don't bleed
+    /// into each other when filtered. Defaults to 2.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<u32>,
+    /// The largest width and height of each page of the atlas, in pixels; the images that don't
+    /// fit in one page are spread over several. Defaults to 4096.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<u32>,
+    /// The nine-slice insets of images, as top, right, bottom and left in pixels, by the name of
+    /// the image (its file name without the extension).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub slices: HashMap<String, Vec4>,
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::is_default;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+/// Compresses the textures of the materials to the Basis Universal format, which the runtime
+/// transcodes to a compressed format the GPU supports, like BC7 or ASTC.
+///
+/// Compressed textures are much smaller to download, and use much less GPU memory.
+pub struct TextureCompression {
+    /// The codec to compress the textures with. Defaults to `Etc1s`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
+    pub codec: TextureCodec,
+    /// The quality of the textures, between 0 (the smallest files) and 1 (the sharpest textures).
+    /// Defaults to `0.5`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f32>,
+    /// Settings for specific textures, which take precedence over the ones above.
+    /// The first override matching a texture is used.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<TextureCompressionOverride>,
+}
+impl TextureCompression {
+    pub const DEFAULT_QUALITY: f32 = 0.5;
+
+    /// The settings for a texture, where `matches` tells whether the glob pattern of an override
+    /// matches it; `None` if the texture should be left uncompressed
+    pub fn settings(&self, matches: impl Fn(&str) -> bool) -> Option<TextureCompressionSettings> {
+        let default = TextureCompressionSettings {
+            codec: self.codec,
+            quality: self.quality.unwrap_or(Self::DEFAULT_QUALITY),
+        };
+        match self
+            .overrides
+            .iter()
+            .find(|texture| matches(&texture.textures))
+        {
+            Some(texture) if texture.uncompressed => None,
+            Some(texture) => Some(TextureCompressionSettings {
+                codec: texture.codec.unwrap_or(default.codec),
+                quality: texture.quality.unwrap_or(default.quality),
+            }),
+            None => Some(default),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TextureCodec {
+    /// Small files of a lower quality, which suit most color textures.
+    #[default]
+    Etc1s,
+    /// Larger files of a much higher quality, which suit normal maps and detailed textures.
+    Uastc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+/// The compression settings of the textures matching `textures`.
+pub struct TextureCompressionOverride {
+    /// A glob pattern matched against the path of the source image of a texture, relative to the pipeline,
+    /// and against the map of the material it is used as: `base_color`, `opacity`, `normalmap` or `metallic_roughness`.
+    pub textures: String,
+    /// The codec to compress these textures with.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<TextureCodec>,
+    /// The quality of these textures, between 0 and 1.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f32>,
+    /// Leaves these textures uncompressed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "crate::is_false")]
+    pub uncompressed: bool,
+}
+
+/// How a single texture is compressed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureCompressionSettings {
+    pub codec: TextureCodec,
+    /// Between 0 and 1
+    pub quality: f32,
+}
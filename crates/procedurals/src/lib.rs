@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::Range, sync::Arc};
 
 use ambient_core::{
     asset_cache,
@@ -11,8 +11,11 @@ use ambient_ecs::{
     generated::procedurals::components::{procedural_material, procedural_mesh},
     query, Entity, Resource, SystemGroup,
 };
-use ambient_gpu::{mesh_buffer::GpuMesh, texture::TextureView};
-use ambient_native_std::{cb, mesh::Mesh};
+use ambient_gpu::{
+    mesh_buffer::{GpuMesh, MeshBufferKey},
+    texture::TextureView,
+};
+use ambient_native_std::{asset_cache::SyncAssetKeyExt, cb, mesh::Mesh};
 use ambient_renderer::{
     gpu_primitives_lod, gpu_primitives_mesh,
     pbr_material::{get_pbr_shader, PbrMaterial, PbrMaterialConfig},
@@ -65,6 +68,43 @@ pub fn client_systems() -> SystemGroup {
                         world.add_component_if_required(id, mesh_to_world(), Default::default());
                 }
             }),
+            query(procedural_mesh()).to_system(|query, world, query_state, _| {
+                let updates =
+                    std::mem::take(&mut world.resource_mut(procedural_storage()).mesh_updates);
+                if updates.is_empty() {
+                    return;
+                }
+                let assets = world.resource(asset_cache()).clone();
+                let gpu = world.resource(gpu()).clone();
+                for (id, mesh_handle) in query.collect_cloned(world, query_state) {
+                    let Some(update) = updates.get(&mesh_handle) else {
+                        continue;
+                    };
+                    let Ok(gpu_mesh) = world.get(id, ambient_core::mesh()) else {
+                        continue;
+                    };
+                    let (new_gpu_mesh, mesh_aabb) = {
+                        let storage = world.resource(procedural_storage());
+                        let mesh = storage.meshes.get(mesh_handle);
+                        // Write in place when possible, and fall back to a new GPU mesh when the
+                        // mesh changed size
+                        let written = MeshBufferKey.get(&assets).lock().write(
+                            &gpu,
+                            &gpu_mesh,
+                            mesh,
+                            update.vertices.clone().unwrap_or_default(),
+                            update.indices.clone().unwrap_or_default(),
+                        );
+                        let new_gpu_mesh =
+                            (!written).then(|| GpuMesh::from_mesh(&gpu, &assets, mesh));
+                        (new_gpu_mesh, mesh.aabb())
+                    };
+                    if let Some(gpu_mesh) = new_gpu_mesh {
+                        world.set(id, ambient_core::mesh(), gpu_mesh).unwrap();
+                    }
+                    world.set(id, local_bounding_aabb(), mesh_aabb).unwrap();
+                }
+            }),
             query(procedural_material().changed()).to_system(|query, world, query_state, _| {
                 let assets = world.resource(asset_cache()).clone();
                 let gpu = world.resource(gpu()).clone();
@@ -120,6 +160,12 @@ where
             .unwrap_or_else(|| panic!("Procedural resource {handle} must exist"))
     }
 
+    pub fn get_mut(&mut self, handle: Handle) -> &mut Resource {
+        self.0
+            .get_mut(&handle)
+            .unwrap_or_else(|| panic!("Procedural resource {handle} must exist"))
+    }
+
     pub fn remove(&mut self, handle: Handle) -> Resource {
         self.0
             .remove(&handle)
@@ -133,12 +179,20 @@ impl<Handle, Resource> Default for ProceduralMap<Handle, Resource> {
     }
 }
 
+/// The vertices and indices of a procedural mesh which were written since they were last uploaded
+#[derive(Clone, Debug, Default)]
+pub struct ProceduralMeshUpdate {
+    pub vertices: Option<Range<usize>>,
+    pub indices: Option<Range<usize>>,
+}
+
 #[derive(Clone)]
 pub struct ProceduralStorage {
     pub meshes: ProceduralMap<ProceduralMeshHandle, ProceduralMesh>,
     pub textures: ProceduralMap<ProceduralTextureHandle, ProceduralTexture>,
     pub samplers: ProceduralMap<ProceduralSamplerHandle, ProceduralSampler>,
     pub materials: ProceduralMap<ProceduralMaterialHandle, ProceduralMaterial>,
+    pub mesh_updates: HashMap<ProceduralMeshHandle, ProceduralMeshUpdate>,
 }
 
 impl ProceduralStorage {
@@ -149,7 +203,27 @@ impl ProceduralStorage {
             textures: Default::default(),
             samplers: Default::default(),
             materials: Default::default(),
+            mesh_updates: Default::default(),
+        }
+    }
+
+    /// Marks parts of a procedural mesh as written, so that they are uploaded to the entities
+    /// using it
+    pub fn mesh_written(
+        &mut self,
+        handle: ProceduralMeshHandle,
+        vertices: Option<Range<usize>>,
+        indices: Option<Range<usize>>,
+    ) {
+        fn merge(a: &mut Option<Range<usize>>, b: Option<Range<usize>>) {
+            *a = match (a.take(), b) {
+                (Some(a), Some(b)) => Some(a.start.min(b.start)..a.end.max(b.end)),
+                (a, b) => a.or(b),
+            };
         }
+        let update = self.mesh_updates.entry(handle).or_default();
+        merge(&mut update.vertices, vertices);
+        merge(&mut update.indices, indices);
     }
 }
 
@@ -0,0 +1,296 @@
+use std::{str::FromStr, sync::Arc};
+
+use ambient_core::{asset_cache, async_ecs::async_run, runtime, window::window_scale_factor};
+use ambient_ecs::{
+    components, generated::text::types::FontStyle, query, DynSystem, EntityId, World,
+};
+use ambient_gpu::{gpu::GpuKey, texture::Texture, texture_loaders::Rgba8ImageFromUrl};
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+};
+use ambient_renderer::{material, SharedMaterial};
+use ambient_shared_types::canvas::{CanvasCommand, CanvasDrawing};
+use ambient_text::{FontDef, FontFamily};
+use glam::{vec2, UVec2, Vec2, Vec4};
+use glyph_brush::ab_glyph::{point, Font, FontArc, ScaleFont};
+use tiny_skia::{
+    Color, FillRule, FilterQuality, IntSize, Paint, PathBuilder, Pixmap, PixmapPaint,
+    PremultipliedColorU8, Stroke, Transform,
+};
+
+use crate::{
+    background_color, border_color, border_radius, border_thickness, rect, rect_material_params,
+    RectMaterial,
+};
+
+components!("rect", {
+    /// The drawing shown by a rect, which replaces its background image.
+    canvas_drawing: Arc<CanvasDrawing>,
+});
+
+/// Draws `drawing` onto the rect `id`, replacing its previous drawing.
+///
+/// The drawing is rasterized in the background, so it appears once its images and fonts have
+/// loaded. Drawing the same thing again is free, so this can be called every frame.
+pub fn draw(world: &mut World, id: EntityId, drawing: CanvasDrawing) {
+    let unchanged = world
+        .get_ref(id, canvas_drawing())
+        .map_or(false, |current| **current == drawing);
+    if !unchanged {
+        world
+            .add_component(id, canvas_drawing(), Arc::new(drawing))
+            .ok();
+    }
+}
+
+pub(crate) fn canvas_system() -> DynSystem {
+    query(canvas_drawing())
+        .incl(rect())
+        .optional_changed(canvas_drawing())
+        .optional_changed(background_color())
+        .optional_changed(border_color())
+        .optional_changed(border_radius())
+        .optional_changed(border_thickness())
+        .to_system(|q, world, qs, _| {
+            let runtime = world.resource(runtime()).clone();
+            let scale_factor = world
+                .resource_opt(window_scale_factor())
+                .cloned()
+                .unwrap_or(1.) as f32;
+            for (id, drawing) in q.collect_cloned(world, qs) {
+                let assets = world.resource(asset_cache()).clone();
+                let async_run = world.resource(async_run()).clone();
+                // Canvases are transparent unless they're given a background color
+                let params = rect_material_params(world, id, Vec4::ZERO);
+                runtime.spawn(async move {
+                    let (size, pixels) = rasterize(&assets, &drawing, scale_factor).await;
+                    async_run.run(move |world| {
+                        // A newer drawing may have been submitted while this one was rasterized
+                        let current = world
+                            .get_ref(id, canvas_drawing())
+                            .map_or(false, |current| Arc::ptr_eq(current, &drawing));
+                        if !current {
+                            return;
+                        }
+                        let gpu = GpuKey.get(&assets);
+                        let texture = Texture::new_with_data(
+                            &gpu,
+                            &wgpu::TextureDescriptor {
+                                label: Some("Canvas"),
+                                size: wgpu::Extent3d {
+                                    width: size.x,
+                                    height: size.y,
+                                    depth_or_array_layers: 1,
+                                },
+                                mip_level_count: 1,
+                                sample_count: 1,
+                                dimension: wgpu::TextureDimension::D2,
+                                format: wgpu::TextureFormat::Rgba8Unorm,
+                                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                                    | wgpu::TextureUsages::COPY_DST,
+                                view_formats: &[],
+                            },
+                            &pixels,
+                        );
+                        let mat = RectMaterial::new(&gpu, &assets, params, &texture);
+                        world
+                            .add_component(id, material(), SharedMaterial(Arc::new(mat)))
+                            .ok();
+                    });
+                });
+            }
+        })
+}
+
+/// Rasterizes `drawing` at `scale_factor` pixels per logical pixel, returning the size of the
+/// image and its pixels as straight alpha RGBA.
+async fn rasterize(
+    assets: &AssetCache,
+    drawing: &CanvasDrawing,
+    scale_factor: f32,
+) -> (UVec2, Vec<u8>) {
+    let size = (drawing.size.as_vec2() * scale_factor)
+        .ceil()
+        .as_uvec2()
+        .max(UVec2::ONE);
+    let mut pixmap = Pixmap::new(size.x, size.y).unwrap();
+    let transform = Transform::from_scale(scale_factor, scale_factor);
+    let mut path = PathBuilder::new();
+
+    for command in &drawing.commands {
+        match command {
+            CanvasCommand::BeginPath => path = PathBuilder::new(),
+            CanvasCommand::MoveTo(p) => path.move_to(p.x, p.y),
+            CanvasCommand::LineTo(p) => path.line_to(p.x, p.y),
+            CanvasCommand::QuadTo { control, point } => {
+                path.quad_to(control.x, control.y, point.x, point.y)
+            }
+            CanvasCommand::CubicTo {
+                control1,
+                control2,
+                point,
+            } => path.cubic_to(
+                control1.x, control1.y, control2.x, control2.y, point.x, point.y,
+            ),
+            CanvasCommand::ClosePath => path.close(),
+            CanvasCommand::Fill(color) => {
+                if let Some(path) = path.clone().finish() {
+                    pixmap.fill_path(&path, &paint(*color), FillRule::Winding, transform, None);
+                }
+            }
+            CanvasCommand::Stroke { color, width } => {
+                if let Some(path) = path.clone().finish() {
+                    let stroke = Stroke {
+                        width: *width,
+                        ..Default::default()
+                    };
+                    pixmap.stroke_path(&path, &paint(*color), &stroke, transform, None);
+                }
+            }
+            CanvasCommand::Image {
+                url,
+                position,
+                size,
+            } => {
+                let Some(image) = load_image(assets, url).await else {
+                    continue;
+                };
+                let image_size = vec2(image.width() as f32, image.height() as f32);
+                let scale = *size / image_size;
+                pixmap.draw_pixmap(
+                    0,
+                    0,
+                    image.as_ref(),
+                    &PixmapPaint {
+                        quality: FilterQuality::Bilinear,
+                        ..Default::default()
+                    },
+                    transform
+                        .pre_translate(position.x, position.y)
+                        .pre_scale(scale.x, scale.y),
+                    None,
+                );
+            }
+            CanvasCommand::Text {
+                text,
+                position,
+                size,
+                color,
+            } => {
+                let font = FontDef(FontFamily::Default, FontStyle::Regular)
+                    .get(assets)
+                    .await;
+                draw_text(
+                    &mut pixmap,
+                    &font,
+                    text,
+                    *position * scale_factor,
+                    *size * scale_factor,
+                    *color,
+                );
+            }
+        }
+    }
+
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    (size, pixels)
+}
+
+fn paint(color: Vec4) -> Paint<'static> {
+    let mut paint = Paint::default();
+    let color = color.clamp(Vec4::ZERO, Vec4::ONE);
+    paint.set_color(Color::from_rgba(color.x, color.y, color.z, color.w).unwrap_or(Color::BLACK));
+    paint.anti_alias = true;
+    paint
+}
+
+async fn load_image(assets: &AssetCache, url: &str) -> Option<Pixmap> {
+    let url = match AbsAssetUrl::from_str(url) {
+        Ok(url) => url,
+        Err(err) => {
+            tracing::warn!("Failed to load canvas image at url {}: {:?}", url, err);
+            return None;
+        }
+    };
+    let image = match (Rgba8ImageFromUrl { url: url.clone() }).get(assets).await {
+        Ok(image) => image,
+        Err(err) => {
+            tracing::warn!("Failed to load canvas image at url {}: {:?}", url, err);
+            return None;
+        }
+    };
+
+    // tiny-skia works with premultiplied alpha
+    let mut data = image.as_raw().clone();
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u16;
+        for channel in &mut pixel[..3] {
+            *channel = (*channel as u16 * alpha / 255) as u8;
+        }
+    }
+    Pixmap::from_vec(data, IntSize::from_wh(image.width(), image.height())?)
+}
+
+/// Draws a line of text with its top left corner at `position`, in physical pixels.
+fn draw_text(
+    pixmap: &mut Pixmap,
+    font: &FontArc,
+    text: &str,
+    position: Vec2,
+    size: f32,
+    color: Vec4,
+) {
+    let scaled = font.as_scaled(size);
+    let color = color.clamp(Vec4::ZERO, Vec4::ONE);
+    let (width, height) = (pixmap.width() as i32, pixmap.height() as i32);
+
+    let mut caret = point(position.x, position.y + scaled.ascent());
+    let mut previous = None;
+    for c in text.chars() {
+        let glyph_id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret.x += scaled.kern(previous, glyph_id);
+        }
+        previous = Some(glyph_id);
+        let glyph = glyph_id.with_scale_and_position(size, caret);
+        caret.x += scaled.h_advance(glyph_id);
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        let pixels = pixmap.pixels_mut();
+        outlined.draw(|x, y, coverage| {
+            let x = bounds.min.x as i32 + x as i32;
+            let y = bounds.min.y as i32 + y as i32;
+            if x < 0 || y < 0 || x >= width || y >= height {
+                return;
+            }
+            let pixel = &mut pixels[(y * width + x) as usize];
+            *pixel = blend(*pixel, color, coverage);
+        });
+    }
+}
+
+/// Blends `color` over `pixel` with `coverage`.
+fn blend(pixel: PremultipliedColorU8, color: Vec4, coverage: f32) -> PremultipliedColorU8 {
+    let alpha = color.w * coverage.clamp(0., 1.);
+    let over = |src: f32, dst: u8| src * alpha * 255. + dst as f32 * (1. - alpha);
+    let a = over(1., pixel.alpha()).round().min(255.) as u8;
+    let channel = |src: f32, dst: u8| over(src, dst).round().min(a as f32) as u8;
+    PremultipliedColorU8::from_rgba(
+        channel(color.x, pixel.red()),
+        channel(color.y, pixel.green()),
+        channel(color.z, pixel.blue()),
+        a,
+    )
+    .unwrap_or(pixel)
+}
@@ -9,7 +9,8 @@ use ambient_core::{
     ui_scene,
 };
 use ambient_ecs::{
-    ensure_has_component, ensure_has_component_with_default, query, Entity, SystemGroup,
+    ensure_has_component, ensure_has_component_with_default, query, Entity, EntityId, SystemGroup,
+    World,
 };
 use ambient_gpu::{
     gpu::{Gpu, GpuKey},
@@ -31,17 +32,24 @@ use ambient_native_std::{
     friendly_id, include_file,
 };
 use ambient_renderer::{
-    gpu_primitives_lod, gpu_primitives_mesh, material, primitives, renderer_shader, Material,
-    MaterialShader, RendererConfig, RendererShader, SharedMaterial, StandardShaderKey,
+    gpu_primitives_lod, gpu_primitives_mesh, material, primitives, renderer_shader,
+    texture_atlas::{is_texture_atlas_entry, TextureAtlasImageFromUrl},
+    Material, MaterialShader, RendererConfig, RendererShader, SharedMaterial, StandardShaderKey,
     MATERIAL_BIND_GROUP,
 };
+use anyhow::Context;
 use async_trait::async_trait;
 use glam::{uvec4, vec4, Quat, UVec3, UVec4, Vec3, Vec3Swizzles, Vec4};
 use wgpu::{BindGroup, BindGroupLayoutEntry, Extent3d};
 
+use crate::canvas::canvas_drawing;
+
+pub mod canvas;
+
 pub use ambient_ecs::generated::rect::components::{
-    background_color, background_url, border_color, border_radius, border_thickness, line_from,
-    line_to, line_width, pixel_line_from, pixel_line_to, rect, size_from_background_image,
+    background_color, background_region, background_slice, background_url, border_color,
+    border_radius, border_thickness, line_from, line_to, line_width, pixel_line_from,
+    pixel_line_to, rect, size_from_background_image,
 };
 
 #[repr(C)]
@@ -177,8 +185,11 @@ pub fn systems() -> SystemGroup {
                 }),
             query(())
                 .incl(rect())
+                .excl(canvas_drawing())
                 .optional_changed(background_color())
                 .optional_changed(background_url())
+                .optional_changed(background_region())
+                .optional_changed(background_slice())
                 .optional_changed(border_color())
                 .optional_changed(border_radius())
                 .optional_changed(border_thickness())
@@ -188,20 +199,7 @@ pub fn systems() -> SystemGroup {
                         let assets = world.resource(asset_cache()).clone();
                         let async_run = world.resource(async_run()).clone();
                         let mat_key = RectMaterialKey {
-                            params: RectMaterialParams {
-                                background_color: world
-                                    .get(id, background_color())
-                                    .unwrap_or(Color::WHITE.into()),
-                                border_color: world
-                                    .get(id, border_color())
-                                    .unwrap_or(Color::WHITE.into()),
-                                border_radius: world
-                                    .get(id, border_radius())
-                                    .unwrap_or_default()
-                                    .into(),
-                                border_thickness: world.get(id, border_thickness()).unwrap_or(0.),
-                                _padding: Default::default(),
-                            },
+                            params: rect_material_params(world, id, Color::WHITE.into()),
                             background: world.get_cloned(id, background_url()).ok(),
                         };
                         let resize = world.has_component(id, size_from_background_image());
@@ -210,6 +208,10 @@ pub fn systems() -> SystemGroup {
                             match mat {
                                 Ok(mat) => {
                                     async_run.run(move |world| {
+                                        // The rect may have become a canvas while this loaded
+                                        if world.has_component(id, canvas_drawing()) {
+                                            return;
+                                        }
                                         world
                                             .add_component(
                                                 id,
@@ -246,10 +248,31 @@ pub fn systems() -> SystemGroup {
                         });
                     }
                 }),
+            canvas::canvas_system(),
         ],
     )
 }
 
+/// The material parameters of the rect `id`, with `default_background` if it doesn't have a
+/// `background_color`.
+fn rect_material_params(
+    world: &World,
+    id: EntityId,
+    default_background: Vec4,
+) -> RectMaterialParams {
+    RectMaterialParams {
+        background_color: world
+            .get(id, background_color())
+            .unwrap_or(default_background),
+        border_color: world.get(id, border_color()).unwrap_or(Color::WHITE.into()),
+        border_radius: world.get(id, border_radius()).unwrap_or_default().into(),
+        border_thickness: world.get(id, border_thickness()).unwrap_or(0.),
+        _padding: Default::default(),
+        background_region: world.get(id, background_region()).unwrap_or_default(),
+        background_slice: world.get(id, background_slice()).unwrap_or_default(),
+    }
+}
+
 #[derive(Debug)]
 pub struct RectMaterialShaderKey;
 impl SyncAssetKey<Arc<MaterialShader>> for RectMaterialShaderKey {
@@ -304,6 +327,8 @@ pub fn get_rect_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<Rend
         material_shader: RectMaterialShaderKey.get(assets),
         lit: false,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
     }
     .get(assets)
 }
@@ -320,21 +345,10 @@ impl AsyncAssetKey<AssetResult<Arc<RectMaterial>>> for RectMaterialKey {
         let error_color = PixelTextureKey {
             colors: vec![uvec4(255, 0, 0, 255)],
         };
+        let mut params = self.params;
         let background = match self.background {
-            Some(url) => match AbsAssetUrl::from_str(&url) {
-                Ok(url) => {
-                    let tex = TextureFromUrl {
-                        url: url.clone(),
-                        format: wgpu::TextureFormat::Rgba8Unorm,
-                    };
-                    match tex.get(&assets).await {
-                        Ok(texture) => texture,
-                        Err(err) => {
-                            tracing::warn!("Failed to load image at url {}: {:?}", url, err);
-                            error_color.get(&assets)
-                        }
-                    }
-                }
+            Some(url) => match load_background(&assets, &url, &mut params).await {
+                Ok(texture) => texture,
                 Err(err) => {
                     tracing::warn!("Failed to load image at url {}: {:?}", url, err);
                     error_color.get(&assets)
@@ -348,12 +362,36 @@ impl AsyncAssetKey<AssetResult<Arc<RectMaterial>>> for RectMaterialKey {
         Ok(Arc::new(RectMaterial::new(
             &gpu,
             &assets,
-            self.params,
+            params,
             &background,
         )))
     }
 }
 
+/// Loads the background image at `url`. Images of texture atlases show their region of the atlas
+/// image, and are nine-sliced with their slice unless the rect has a `background_slice` of its own.
+async fn load_background(
+    assets: &AssetCache,
+    url: &str,
+    params: &mut RectMaterialParams,
+) -> AssetResult<Arc<Texture>> {
+    let mut url = AbsAssetUrl::from_str(url).context("Invalid url")?;
+    if is_texture_atlas_entry(&url) {
+        let image = TextureAtlasImageFromUrl(url).get(assets).await?;
+        params.background_region = image.entry.region;
+        if params.background_slice == Vec4::ZERO {
+            params.background_slice = image.entry.slice.unwrap_or_default();
+        }
+        url = image.url.clone();
+    }
+    TextureFromUrl {
+        url,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+    }
+    .get(assets)
+    .await
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct RectMaterialParams {
@@ -362,6 +400,11 @@ pub struct RectMaterialParams {
     pub border_radius: Corners,
     pub border_thickness: f32,
     pub _padding: UVec3,
+    /// The region of the background shown, as x, y, width and height in pixels; all zeros shows
+    /// the whole background
+    pub background_region: Vec4,
+    /// The nine-slice insets of the background, as top, right, bottom and left in pixels
+    pub background_slice: Vec4,
 }
 
 pub struct RectMaterial {
@@ -378,6 +421,7 @@ impl RectMaterial {
         background: &Texture,
     ) -> Self {
         let layout = get_rect_layout().get(assets);
+        let region = params.background_region;
 
         let buffer = TypedBuffer::new_init(
             gpu,
@@ -412,7 +456,16 @@ impl RectMaterial {
                 label: Some("RectMaterial.bind_group"),
             }),
             transparent: Some(true),
-            background_size: background.size,
+            // Rects sized from their background are sized from the region they show
+            background_size: if region.z > 0. && region.w > 0. {
+                Extent3d {
+                    width: region.z as u32,
+                    height: region.w as u32,
+                    depth_or_array_layers: 1,
+                }
+            } else {
+                background.size
+            },
         }
     }
 }
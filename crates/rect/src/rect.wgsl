@@ -4,6 +4,8 @@ struct RectMaterialParams {
     border_color: vec4<f32>,
     border_radius: vec4<f32>,
     border_thickness: f32,
+    background_region: vec4<f32>,
+    background_slice: vec4<f32>,
 }
 @group(MATERIAL_BIND_GROUP)
 @binding(0)
@@ -28,6 +30,36 @@ fn get_corner_from_tc(tc: vec2<f32>) -> u32 {
     return corner;
 }
 
+// Maps a position along one axis of the rect to a position in the region of the background, in
+// pixels. The insets at either end keep their size, and the rest stretches.
+fn slice_axis(p: f32, size: f32, start: f32, end: f32, length: f32) -> f32 {
+    // Shrink the insets evenly when the rect is too small to fit them
+    let k = min(1., size / max(start + end, 0.0001));
+    if p < start * k {
+        return p / k;
+    }
+    if p > size - end * k {
+        return length - (size - p) / k;
+    }
+    let t = (p - start * k) / max(size - (start + end) * k, 0.0001);
+    return start + t * (length - start - end);
+}
+
+fn get_background_texcoord(texcoord: vec2<f32>, size: vec2<f32>) -> vec2<f32> {
+    let texture_size = vec2<f32>(textureDimensions(background));
+    var region = rect_params.background_region;
+    if region.z <= 0. || region.w <= 0. {
+        region = vec4(0., 0., texture_size);
+    }
+    let slice = rect_params.background_slice;
+    let p = texcoord * size;
+    let offset = vec2(
+        slice_axis(p.x, size.x, slice.w, slice.y, region.z),
+        slice_axis(p.y, size.y, slice.x, slice.z, region.w),
+    );
+    return (region.xy + offset) / texture_size;
+}
+
 fn get_material(in: MaterialInput) -> MaterialOutput {
     var out: MaterialOutput;
     out.roughness = 0.4;
@@ -40,7 +72,7 @@ fn get_material(in: MaterialInput) -> MaterialOutput {
 
     let entity_color = get_entity_color_or(in.entity_loc, vec4<f32>(1., 1., 1., 1.));
     let border_color = rect_params.border_color * entity_color;
-    let image = textureSample(background, background_sampler, in.texcoord);
+    let image = textureSample(background, background_sampler, get_background_texcoord(in.texcoord, size));
     let back_color = vec4(mix(rect_params.background_color.rgb, image.rgb, image.a), image.a + rect_params.background_color.a);
     var color = back_color * entity_color;
     if max(p.x, p.y) <= border_radius {
@@ -106,6 +106,8 @@ async fn init(app: &mut App) {
                 material_shader: CustomMaterialShaderKey.get(assets),
                 lit: true,
                 shadow_cascades: config.shadow_cascades,
+                wind: false,
+                imposter: false,
             }
             .get(assets)
         }),
@@ -89,8 +89,13 @@ async fn init(app: &mut App) {
 
     for i in 0..5 {
         let conf = RendererConfig::default();
-        let shadow_cam =
-            demo_cam.create_snapping_shadow_camera(sun_direction, i, 5, conf.shadow_map_resolution);
+        let shadow_cam = demo_cam.create_snapping_shadow_camera(
+            sun_direction,
+            i,
+            5,
+            conf.shadow_map_resolution,
+            0.,
+        );
         Cube.el()
             .remove(translation())
             .remove(scale())
@@ -110,7 +115,7 @@ async fn init(app: &mut App) {
 
         for point in &demo_cam
             .to_shadows_far_bound()
-            .world_space_frustum_points_for_shadow_cascade(i, 5)
+            .world_space_frustum_points_for_shadow_cascade(i, 5, 0.)
         {
             Cube.el()
                 .with(translation(), *point)
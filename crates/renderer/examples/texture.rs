@@ -44,6 +44,8 @@ async fn init(app: &mut App) {
             transparent: None,
             double_sided: None,
             depth_write_enabled: None,
+            transparency_group: None,
+            transparency_sort_bias: None,
         },
     ));
 
@@ -320,6 +320,10 @@ impl RendererCollect {
             ],
         });
 
+        // The visible primitives are packed at the start of each material, and the slots after them
+        // must not keep the commands of a previous frame
+        encoder.clear_buffer(output.commands.buffer(), 0, None);
+
         {
             let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Collect"),
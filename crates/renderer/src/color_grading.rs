@@ -0,0 +1,191 @@
+//! The lookup tables of the `color_grading` post-processing effect, which remap the colors of the
+//! image of a camera to give it a mood.
+
+use std::{str::FromStr, sync::Arc};
+
+use ambient_core::{asset_cache, async_ecs::async_run, runtime};
+use ambient_ecs::{components, query, Component, EntityId, SystemGroup, World};
+use ambient_gpu::{
+    gpu::{Gpu, GpuKey},
+    texture::{Texture, TextureView},
+    texture_loaders::Rgba8ImageFromUrl,
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::AssetError,
+};
+use async_trait::async_trait;
+
+pub use ambient_ecs::generated::rendering::components::{
+    color_grading_intensity, color_grading_lut_blend, color_grading_lut_blend_from_url,
+    color_grading_lut_from_url,
+};
+
+components!("rendering", {
+    /// The `color_grading_lut_from_url` of a camera, once loaded
+    color_grading_lut: Arc<ColorGradingLut>,
+    /// The `color_grading_lut_blend_from_url` of a camera, once loaded
+    color_grading_blend_lut: Arc<ColorGradingLut>,
+});
+
+/// A 3D lookup table of colors
+#[derive(Debug)]
+pub struct ColorGradingLut {
+    pub view: TextureView,
+    /// The number of texels along each side
+    pub size: u32,
+}
+impl ColorGradingLut {
+    /// A table of `size` texels along each side laid out as in `color_grading_lut_from_url`:
+    /// `size` slices of `size` x `size` texels side by side, with blue going from one to the next
+    pub fn from_strip(gpu: &Gpu, size: u32, texels: &[u8]) -> Self {
+        // Within a slice, the rows of the strip are apart by the width of all slices
+        let mut data = Vec::with_capacity(texels.len());
+        for blue in 0..size {
+            for green in 0..size {
+                let start = ((green * size * size + blue * size) * 4) as usize;
+                data.extend_from_slice(&texels[start..start + size as usize * 4]);
+            }
+        }
+        let texture = Texture::new_with_data(
+            gpu,
+            &wgpu::TextureDescriptor {
+                label: Some("ColorGradingLut"),
+                size: wgpu::Extent3d {
+                    width: size,
+                    height: size,
+                    depth_or_array_layers: size,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D3,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            &data,
+        );
+        Self {
+            view: Arc::new(texture).create_view(&Default::default()),
+            size,
+        }
+    }
+
+    /// The table which leaves every color as it is
+    pub fn identity(gpu: &Gpu) -> Self {
+        let mut texels = Vec::new();
+        for green in [0, 255] {
+            for blue in [0, 255] {
+                for red in [0, 255] {
+                    texels.extend_from_slice(&[red, green, blue, 255]);
+                }
+            }
+        }
+        Self::from_strip(gpu, 2, &texels)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColorGradingLutFromUrl(pub AbsAssetUrl);
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<ColorGradingLut>, AssetError>> for ColorGradingLutFromUrl {
+    async fn load(self, assets: AssetCache) -> Result<Arc<ColorGradingLut>, AssetError> {
+        let image = Rgba8ImageFromUrl { url: self.0 }.get(&assets).await?;
+        let size = image.height();
+        if size < 2 || image.width() != size * size {
+            return Err(anyhow::anyhow!(
+                "A color grading lookup table of {size} slices must be {} pixels wide, but is {}",
+                size * size,
+                image.width()
+            )
+            .into());
+        }
+        let gpu = GpuKey.get(&assets);
+        Ok(Arc::new(ColorGradingLut::from_strip(
+            &gpu,
+            size,
+            image.as_raw(),
+        )))
+    }
+}
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "color_grading",
+        vec![
+            query(color_grading_lut_from_url().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in q.collect_cloned(world, qs) {
+                    load_lut(
+                        world,
+                        id,
+                        url,
+                        color_grading_lut_from_url(),
+                        color_grading_lut(),
+                    );
+                }
+            }),
+            query(color_grading_lut_blend_from_url().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in q.collect_cloned(world, qs) {
+                    load_lut(
+                        world,
+                        id,
+                        url,
+                        color_grading_lut_blend_from_url(),
+                        color_grading_blend_lut(),
+                    );
+                }
+            }),
+            query(color_grading_lut_from_url())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        world.remove_component(id, color_grading_lut()).ok();
+                    }
+                }),
+            query(color_grading_lut_blend_from_url())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        world.remove_component(id, color_grading_blend_lut()).ok();
+                    }
+                }),
+        ],
+    )
+}
+
+fn load_lut(
+    world: &World,
+    id: EntityId,
+    url: String,
+    url_component: Component<String>,
+    lut_component: Component<Arc<ColorGradingLut>>,
+) {
+    let abs_url = match AbsAssetUrl::from_str(&url) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!("Failed to parse color grading lookup table url: {:?}", err);
+            return;
+        }
+    };
+    let assets = world.resource(asset_cache()).clone();
+    let async_run = world.resource(async_run()).clone();
+    world.resource(runtime()).spawn(async move {
+        match ColorGradingLutFromUrl(abs_url).get(&assets).await {
+            Err(err) => {
+                tracing::warn!("Failed to load color grading lookup table: {:?}", err);
+            }
+            Ok(lut) => {
+                async_run.run(move |world| {
+                    // The table may have been changed or removed while it loaded
+                    if world
+                        .get_ref(id, url_component)
+                        .is_ok_and(|value| *value == url)
+                    {
+                        world.add_component(id, lut_component, lut).unwrap();
+                    }
+                });
+            }
+        }
+    });
+}
@@ -1,10 +1,6 @@
 use std::{f32::INFINITY, sync::Arc};
 
-use ambient_core::{
-    bounding::world_bounding_sphere,
-    camera::{shadow_cameras_from_world, Camera},
-    player::local_user_id,
-};
+use ambient_core::{bounding::world_bounding_sphere, camera::Camera, player::local_user_id};
 use ambient_ecs::{ArchetypeFilter, World};
 use ambient_gpu::{
     gpu::Gpu,
@@ -20,7 +16,7 @@ use ambient_native_std::{
 use glam::{Mat4, UVec3, Vec2, Vec3, Vec3Swizzles, Vec4};
 use wgpu::{BindGroupLayout, BindGroupLayoutEntry, BindingType, BufferBindingType, ShaderStages};
 
-use crate::{get_sun_light_direction, RendererConfig};
+use crate::{HiZ, Lights, RendererConfig, MAX_SHADOW_LIGHTS};
 
 gpu_components! {
     world_bounding_sphere() => renderer_cameras_visible: GpuComponentFormat::Mat4,
@@ -73,6 +69,14 @@ struct CullingParams {
     pub shadow_cameras: [CullCamera; MAX_SHADOW_CASCADES as usize],
     pub lod_cutoff_scaling: f32,
     pub _padding: UVec3,
+    /// The position and range of the lights with shadow maps
+    pub light_shadows: [Vec4; MAX_SHADOW_LIGHTS as usize],
+    /// The projection view the depth pyramid was rendered with
+    pub occlusion_projection_view: Mat4,
+    pub occlusion_size: Vec2,
+    pub occlusion_mips: u32,
+    /// Zero when the objects should not be tested against the depth pyramid
+    pub occlusion: u32,
 }
 
 pub struct Culling {
@@ -85,16 +89,28 @@ pub struct Culling {
 fn get_culling_layout() -> BindGroupDesc<'static> {
     BindGroupDesc {
         label: CULLING_BIND_GROUP.into(),
-        entries: vec![BindGroupLayoutEntry {
-            binding: 0,
-            visibility: ShaderStages::COMPUTE,
-            ty: BindingType::Buffer {
-                ty: BufferBindingType::Uniform,
-                has_dynamic_offset: false,
-                min_binding_size: None,
+        entries: vec![
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::COMPUTE,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
             },
-            count: None,
-        }],
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::COMPUTE,
+                ty: BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+        ],
     }
 }
 
@@ -110,6 +126,10 @@ impl Culling {
                 "MAX_SHADOW_CASCADES",
                 MAX_SHADOW_CASCADES,
             ))
+            .with_ident(ShaderIdent::constant(
+                "MAX_SHADOW_LIGHTS",
+                MAX_SHADOW_LIGHTS,
+            ))
             .with_binding_desc(get_culling_layout());
 
         Self {
@@ -138,7 +158,15 @@ impl Culling {
     }
 
     #[profiling::function]
-    pub fn run<'a>(&mut self, gpu: &Gpu, encoder: &'a mut wgpu::CommandEncoder, world: &World) {
+    pub fn run<'a>(
+        &mut self,
+        gpu: &Gpu,
+        encoder: &'a mut wgpu::CommandEncoder,
+        world: &World,
+        shadow_cameras: &[Camera],
+        lights: &Lights,
+        hi_z: &HiZ,
+    ) {
         let main_camera = if let Some(camera) = Camera::get_active(
             world,
             self.config.scene,
@@ -152,22 +180,20 @@ impl Culling {
 
         let mut params = CullingParams {
             lod_cutoff_scaling: self.config.lod_cutoff_scaling,
-            main_camera: main_camera.into(),
+            main_camera: main_camera.clone().into(),
             ..Default::default()
         };
-        if self.config.shadow_cascades > 0 {
-            let shadow_cameras = shadow_cameras_from_world(
-                world,
-                self.config.shadow_cascades,
-                self.config.shadow_map_resolution,
-                get_sun_light_direction(world, self.config.scene),
-                self.config.scene,
-                world.resource_opt(local_user_id()),
-            );
-            #[allow(clippy::needless_range_loop)]
-            for i in 0..(self.config.shadow_cascades as usize) {
-                params.shadow_cameras[i] = shadow_cameras[i].clone().into();
-            }
+        for (i, camera) in shadow_cameras.iter().enumerate() {
+            params.shadow_cameras[i] = camera.clone().into();
+        }
+        for (i, light) in lights.shadowed() {
+            params.light_shadows[i as usize] = light.position.extend(light.range);
+        }
+        if let Some(hi_z) = hi_z.params(&main_camera) {
+            params.occlusion_projection_view = hi_z.projection_view;
+            params.occlusion_size = hi_z.size;
+            params.occlusion_mips = hi_z.mips;
+            params.occlusion = 1;
         }
 
         self.params.fill(gpu, &[params], |_| {});
@@ -175,10 +201,16 @@ impl Culling {
         let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: None,
             layout: &self.layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: self.params.buffer().as_entire_binding(),
-            }],
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.params.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(hi_z.view()),
+                },
+            ],
         });
 
         self.updater
@@ -23,12 +23,23 @@ struct Params {
     main_camera: Camera,
     shadow_cameras: array<Camera, MAX_SHADOW_CASCADES>,
     lod_cutoff_scaling: f32,
+    // The position and range of the lights with shadow maps
+    light_shadows: array<vec4<f32>, MAX_SHADOW_LIGHTS>,
+    // The projection view the depth pyramid was rendered with
+    occlusion_projection_view: mat4x4<f32>,
+    occlusion_size: vec2<f32>,
+    occlusion_mips: u32,
+    occlusion: u32,
 };
 
 @group(LODDING_BIND_GROUP)
 @binding(0)
 var<uniform> params: Params;
 
+@group(LODDING_BIND_GROUP)
+@binding(1)
+var depth_pyramid: texture_2d<f32>;
+
 struct CameraCullResult {
     fully_contained: bool,
     inside: bool,
@@ -72,13 +83,64 @@ fn get_lod(entity_loc: vec2<u32>) -> u32 {
     return 0u;
 }
 
+// Small objects can be culled from the main camera by their screen size, like the lods are picked
+fn is_too_small(entity_loc: vec2<u32>, bounding_sphere: vec4<f32>) -> bool {
+    if !has_entity_lod_cull_screen_size(entity_loc) {
+        return false;
+    }
+    let dist = length(params.main_camera.position.xyz - bounding_sphere.xyz);
+    let screen_size = bounding_sphere.w * params.main_camera.cot_fov_2 / dist;
+    return screen_size < get_entity_lod_cull_screen_size(entity_loc).x * params.lod_cutoff_scaling;
+}
+
+// Tests the bounds of the sphere against the depth pyramid of the last frame. The depth is
+// reversed, so the object is hidden if its nearest point is farther than everything drawn over it
+fn is_occluded(bounding_sphere: vec4<f32>) -> bool {
+    if params.occlusion == 0u {
+        return false;
+    }
+    var uv_min = vec2<f32>(1.);
+    var uv_max = vec2<f32>(0.);
+    var depth = 0.;
+    for (var i = 0u; i < 8u; i = i + 1u) {
+        let corner = vec3<f32>(f32(i & 1u), f32((i >> 1u) & 1u), f32((i >> 2u) & 1u)) * 2. - 1.;
+        let clip = params.occlusion_projection_view * vec4<f32>(bounding_sphere.xyz + corner * bounding_sphere.w, 1.);
+        // Part of the object is behind the camera
+        if clip.w <= 0. {
+            return false;
+        }
+        let ndc = clip.xyz / clip.w;
+        let uv = vec2<f32>(ndc.x, -ndc.y) * 0.5 + 0.5;
+        uv_min = min(uv_min, uv);
+        uv_max = max(uv_max, uv);
+        depth = max(depth, ndc.z);
+    }
+    uv_min = clamp(uv_min, vec2<f32>(0.), vec2<f32>(1.));
+    uv_max = clamp(uv_max, vec2<f32>(0.), vec2<f32>(1.));
+
+    // The level where the bounds cover about two texels on each side
+    let extent = (uv_max - uv_min) * params.occlusion_size;
+    let mip = min(u32(ceil(log2(max(max(extent.x, extent.y), 1.)))), params.occlusion_mips - 1u);
+    let size = vec2<i32>(textureDimensions(depth_pyramid, i32(mip)));
+    // Grown by half a texel, as the odd rows and columns are folded into the last texel of a level
+    let start = clamp(vec2<i32>(floor(uv_min * vec2<f32>(size) - 0.5)), vec2<i32>(0), size - 1);
+    let end = clamp(vec2<i32>(floor(uv_max * vec2<f32>(size) + 0.5)), vec2<i32>(0), size - 1);
+    var occluder = 1.;
+    for (var y = start.y; y <= end.y; y = y + 1) {
+        for (var x = start.x; x <= end.x; x = x + 1) {
+            occluder = min(occluder, textureLoad(depth_pyramid, vec2<i32>(x, y), i32(mip)).x);
+        }
+    }
+    return depth < occluder;
+}
+
 fn update(entity_loc: vec2<u32>) {
     if has_entity_gpu_lod(entity_loc) {
         set_entity_gpu_lod(entity_loc, vec4<f32>(f32(get_lod(entity_loc)), 0.0, 0.0, 0.0));
     }
     var cameras: mat4x4<f32>;
     let bounding_sphere = get_entity_world_bounding_sphere(entity_loc);
-    cameras[0][0] = f32(cull_camera(params.main_camera, bounding_sphere).inside);
+    cameras[0][0] = f32(cull_camera(params.main_camera, bounding_sphere).inside && !is_too_small(entity_loc, bounding_sphere) && !is_occluded(bounding_sphere));
 
     for (var i = 1u; i <= SHADOW_CASCADESu; i = i + 1u) {
         let a = i >> 2u;
@@ -105,5 +167,12 @@ fn update(entity_loc: vec2<u32>) {
             break;
         }
     }
+    // The shadow maps of a light come right after the cascades, and are culled by the light range
+    for (var i = 0u; i < MAX_SHADOW_LIGHTSu; i = i + 1u) {
+        let light = params.light_shadows[i];
+        let camera = SHADOW_CASCADESu + 1u + i;
+        let visible = light.w > 0. && distance(light.xyz, bounding_sphere.xyz) < light.w + bounding_sphere.w;
+        cameras[camera >> 2u][camera & 3u] = f32(visible);
+    }
     set_entity_renderer_cameras_visible(entity_loc, cameras);
 }
@@ -0,0 +1,197 @@
+//! Environment maps: HDR images of the surroundings of a scene, which light it with image-based
+//! lighting and are shown as its sky.
+//!
+//! The `EnvironmentMaps` asset pipeline prefilters them ahead of time into an equirectangular
+//! texture, whose mip levels are blurred for increasing roughness, and the spherical harmonics of
+//! their diffuse irradiance, so the renderer only has to sample them.
+
+use std::{str::FromStr, sync::Arc};
+
+use ambient_core::{asset_cache, async_ecs::async_run, main_scene, runtime};
+use ambient_ecs::{components, query, Component, EntityId, SystemGroup, World};
+use ambient_gpu::{
+    gpu::GpuKey,
+    texture::{Texture, TextureView},
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::{AssetError, BytesFromUrl, JsonFromUrl},
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+pub use ambient_ecs::generated::rendering::components::{
+    environment_map_from_url, environment_map_intensity,
+};
+
+components!("rendering", {
+    /// The `environment_map_from_url` of an entity, once loaded
+    environment_map: Arc<EnvironmentMap>,
+});
+
+/// The number of spherical harmonics coefficients of the irradiance of an environment map
+pub const ENVIRONMENT_MAP_SH_COEFFICIENTS: usize = 9;
+
+/// A prefiltered environment map, as written by the asset pipeline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EnvironmentMapDesc {
+    /// The size of the first level, whose width is twice its height
+    pub width: u32,
+    pub height: u32,
+    /// The mip levels, prefiltered for a roughness going from 0 at the first to 1 at the last
+    pub levels: u32,
+    /// The second order spherical harmonics of the diffuse irradiance, divided by pi, so that
+    /// multiplying them with the albedo gives the diffuse light of a surface
+    pub irradiance: [Vec3; ENVIRONMENT_MAP_SH_COEFFICIENTS],
+    /// The path of the texels of all levels, relative to the description. They are packed as
+    /// `Rgb9e5Ufloat`, one level after the other
+    pub texels: String,
+}
+impl EnvironmentMapDesc {
+    pub fn level_size(&self, level: u32) -> (u32, u32) {
+        ((self.width >> level).max(1), (self.height >> level).max(1))
+    }
+    pub fn texel_count(&self) -> usize {
+        (0..self.levels)
+            .map(|level| {
+                let (width, height) = self.level_size(level);
+                width as usize * height as usize
+            })
+            .sum()
+    }
+}
+
+/// Packs a linear HDR color in the shared exponent format of `Rgb9e5Ufloat`
+pub fn pack_rgb9e5(color: Vec3) -> u32 {
+    const MANTISSA_BITS: i32 = 9;
+    const EXPONENT_BIAS: i32 = 15;
+    const MAX: f32 = 65408.;
+
+    let color = color.clamp(Vec3::ZERO, Vec3::splat(MAX));
+    let max = color.max_element();
+    let mut exponent = max.log2().floor().max(-EXPONENT_BIAS as f32 - 1.) as i32 + 1;
+    if (max / 2f32.powi(exponent - MANTISSA_BITS) + 0.5).floor() as i32 == 1 << MANTISSA_BITS {
+        exponent += 1;
+    }
+    let scale = 2f32.powi(exponent - MANTISSA_BITS);
+    let [r, g, b] = (color / scale + 0.5).floor().to_array().map(|x| x as u32);
+    r | (g << 9) | (b << 18) | (((exponent + EXPONENT_BIAS) as u32) << 27)
+}
+
+#[derive(Debug)]
+pub struct EnvironmentMap {
+    pub view: TextureView,
+    pub levels: u32,
+    pub irradiance: [Vec3; ENVIRONMENT_MAP_SH_COEFFICIENTS],
+}
+
+#[derive(Debug, Clone)]
+pub struct EnvironmentMapFromUrl(pub AbsAssetUrl);
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<EnvironmentMap>, AssetError>> for EnvironmentMapFromUrl {
+    async fn load(self, assets: AssetCache) -> Result<Arc<EnvironmentMap>, AssetError> {
+        let desc = JsonFromUrl::<EnvironmentMapDesc>::new(self.0.clone(), true)
+            .get(&assets)
+            .await?;
+        let texels_url = self.0.resolve(&desc.texels).context("Invalid texels url")?;
+        let texels = BytesFromUrl::new(texels_url, true).get(&assets).await?;
+        if desc.levels == 0 || texels.len() != desc.texel_count() * 4 {
+            return Err(anyhow::anyhow!(
+                "The texels of the environment map do not match its description"
+            )
+            .into());
+        }
+
+        let gpu = GpuKey.get(&assets);
+        let texture = Arc::new(Texture::new_with_data(
+            &gpu,
+            &wgpu::TextureDescriptor {
+                label: Some("EnvironmentMap"),
+                size: wgpu::Extent3d {
+                    width: desc.width,
+                    height: desc.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: desc.levels,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgb9e5Ufloat,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            &texels,
+        ));
+        Ok(Arc::new(EnvironmentMap {
+            view: texture.create_view(&Default::default()),
+            levels: desc.levels,
+            irradiance: desc.irradiance,
+        }))
+    }
+}
+
+/// The loaded environment map lighting `scene`, and the entity it belongs to
+pub fn get_active_environment_map(
+    world: &World,
+    scene: Component<()>,
+) -> Option<(EntityId, Arc<EnvironmentMap>)> {
+    query((scene, environment_map()))
+        .iter(world, None)
+        .next()
+        .map(|(id, (_, map))| (id, map.clone()))
+}
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "environment_maps",
+        vec![
+            query(environment_map_from_url().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in q.collect_cloned(world, qs) {
+                    load_environment_map(world, id, url);
+                }
+            }),
+            query(environment_map_from_url())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        world.remove_component(id, environment_map()).ok();
+                    }
+                }),
+        ],
+    )
+}
+
+fn load_environment_map(world: &World, id: EntityId, url: String) {
+    let abs_url = match AbsAssetUrl::from_str(&url) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!("Failed to parse environment_map_from_url url: {:?}", err);
+            return;
+        }
+    };
+    let assets = world.resource(asset_cache()).clone();
+    let async_run = world.resource(async_run()).clone();
+    world.resource(runtime()).spawn(async move {
+        match EnvironmentMapFromUrl(abs_url).get(&assets).await {
+            Err(err) => {
+                tracing::warn!("Failed to load environment map: {:?}", err);
+            }
+            Ok(map) => {
+                async_run.run(move |world| {
+                    // The environment map may have been changed or removed while it loaded
+                    if world
+                        .get_ref(id, environment_map_from_url())
+                        .is_ok_and(|value| *value == url)
+                    {
+                        world.add_component(id, environment_map(), map).unwrap();
+                        if !world.has_component(id, main_scene()) {
+                            world.add_component(id, main_scene(), ()).unwrap();
+                        }
+                    }
+                });
+            }
+        }
+    });
+}
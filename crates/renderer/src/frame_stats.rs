@@ -0,0 +1,194 @@
+//! Statistics of the frames of a renderer: the time the GPU spends on each of its passes, what
+//! it draws and the GPU memory in use, kept in the `frame_stats` resource of the world it renders.
+
+use std::{
+    ops::{AddAssign, Mul},
+    sync::Arc,
+    time::Duration,
+};
+
+use ambient_ecs::{components, Debuggable, Resource};
+use ambient_gpu::gpu::Gpu;
+use parking_lot::Mutex;
+
+use crate::PostSubmitFunc;
+
+/// The most passes a frame is split into
+const MAX_TIMESTAMPS: u32 = 32;
+/// The most frames whose timestamps are waiting to be read back at the same time
+const MAX_READBACKS: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrawStats {
+    pub draw_calls: u64,
+    /// The triangles of everything drawn, before the culling on the GPU leaves some out
+    pub triangles: u64,
+}
+impl AddAssign for DrawStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.draw_calls += rhs.draw_calls;
+        self.triangles += rhs.triangles;
+    }
+}
+impl Mul<u64> for DrawStats {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        Self {
+            draw_calls: self.draw_calls * rhs,
+            triangles: self.triangles * rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    /// The GPU time of each pass, in the order they ran. It is measured a few frames late, and is
+    /// empty when the GPU does not support timestamps
+    pub passes: Vec<(String, Duration)>,
+    pub draws: DrawStats,
+    /// The bytes of the textures allocated by the engine
+    pub texture_bytes: u64,
+    /// The bytes of the buffers allocated by the engine, including the meshes
+    pub buffer_bytes: u64,
+}
+impl FrameStats {
+    pub fn gpu_time(&self) -> Duration {
+        self.passes.iter().map(|(_, time)| *time).sum()
+    }
+}
+
+components!("rendering", {
+    /// The statistics of the last frame of the renderer with `frame_stats` in its config
+    @[Debuggable, Resource]
+    frame_stats: FrameStats,
+});
+
+/// Measures the GPU time between the points of a frame it is told about, with timestamps written
+/// between the passes. Nothing is measured when it is disabled, or without the `TIMESTAMP_QUERY`
+/// feature.
+pub(crate) struct GpuTimer {
+    /// The timestamps, and the buffer they are resolved to
+    queries: Option<(wgpu::QuerySet, wgpu::Buffer)>,
+    /// The pass ending at each timestamp of this frame, after the one starting it
+    labels: Vec<&'static str>,
+    readbacks: usize,
+    free_readbacks: Arc<Mutex<Vec<Arc<wgpu::Buffer>>>>,
+    passes: Arc<Mutex<Vec<(String, Duration)>>>,
+}
+impl GpuTimer {
+    pub fn new(gpu: &Gpu, enabled: bool) -> Self {
+        let supported = gpu
+            .device
+            .features()
+            .contains(wgpu::Features::TIMESTAMP_QUERY);
+        let queries = (enabled && supported).then(|| {
+            (
+                gpu.device.create_query_set(&wgpu::QuerySetDescriptor {
+                    label: Some("GpuTimer.query_set"),
+                    ty: wgpu::QueryType::Timestamp,
+                    count: MAX_TIMESTAMPS,
+                }),
+                gpu.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("GpuTimer.resolve_buffer"),
+                    size: MAX_TIMESTAMPS as u64 * std::mem::size_of::<u64>() as u64,
+                    usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                    mapped_at_creation: false,
+                }),
+            )
+        });
+        Self {
+            queries,
+            labels: Vec::new(),
+            readbacks: 0,
+            free_readbacks: Default::default(),
+            passes: Default::default(),
+        }
+    }
+
+    pub fn start(&mut self, gpu: &Gpu, encoder: &mut wgpu::CommandEncoder) {
+        if self.queries.is_none() {
+            return;
+        }
+        // The timestamps of the previous frames are read back without waiting for the GPU
+        if !gpu.will_be_polled {
+            gpu.device.poll(wgpu::Maintain::Poll);
+        }
+        self.labels.clear();
+        self.mark(encoder, "");
+    }
+
+    /// Ends the pass `label`, which started at the previous mark
+    pub fn mark(&mut self, encoder: &mut wgpu::CommandEncoder, label: &'static str) {
+        if let Some((query_set, _)) = &self.queries {
+            if self.labels.len() < MAX_TIMESTAMPS as usize {
+                encoder.write_timestamp(query_set, self.labels.len() as u32);
+                self.labels.push(label);
+            }
+        }
+    }
+
+    pub fn finish(
+        &mut self,
+        gpu: &Gpu,
+        encoder: &mut wgpu::CommandEncoder,
+        post_submit: &mut Vec<PostSubmitFunc>,
+    ) {
+        let Some((query_set, resolve_buffer)) = &self.queries else {
+            return;
+        };
+        let count = self.labels.len() as u32;
+        if count < 2 {
+            return;
+        }
+        let readback = match self.free_readbacks.lock().pop() {
+            Some(readback) => readback,
+            None if self.readbacks < MAX_READBACKS => {
+                self.readbacks += 1;
+                Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("GpuTimer.readback"),
+                    size: resolve_buffer.size(),
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }))
+            }
+            // The GPU is too far behind, so this frame is left out
+            None => return,
+        };
+        let size = count as u64 * std::mem::size_of::<u64>() as u64;
+        encoder.resolve_query_set(query_set, 0..count, resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(resolve_buffer, 0, &readback, 0, size);
+
+        let labels = self.labels[1..].to_vec();
+        let period = gpu.queue.get_timestamp_period() as f64;
+        let free_readbacks = self.free_readbacks.clone();
+        let passes = self.passes.clone();
+        post_submit.push(Box::new(move || {
+            let mapped = readback.clone();
+            mapped
+                .slice(..size)
+                .map_async(wgpu::MapMode::Read, move |res| {
+                    if res.is_ok() {
+                        let timestamps: Vec<u64> =
+                            bytemuck::cast_slice(&readback.slice(..size).get_mapped_range())
+                                .to_vec();
+                        readback.unmap();
+                        *passes.lock() = labels
+                            .iter()
+                            .zip(timestamps.windows(2))
+                            .map(|(label, ticks)| {
+                                let nanos = ticks[1].saturating_sub(ticks[0]) as f64 * period;
+                                (label.to_string(), Duration::from_nanos(nanos as u64))
+                            })
+                            .collect();
+                    }
+                    free_readbacks.lock().push(readback);
+                });
+        }));
+    }
+
+    /// The passes of the last frame read back
+    pub fn passes(&self) -> Vec<(String, Duration)> {
+        self.passes.lock().clone()
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use ambient_core::{
-    camera::{far, fog, get_active_camera, projection_view},
+    camera::{far, fog, get_active_camera, projection_view, Camera},
     player::local_user_id,
     transform::{get_world_position, get_world_rotation, local_to_world},
 };
@@ -12,17 +12,22 @@ use ambient_gpu::{
     sampler::SamplerKey,
     shader_module::{BindGroupDesc, DEPTH_FORMAT},
     texture::{Texture, TextureView},
+    typed_buffer::TypedBuffer,
 };
 use ambient_native_std::asset_cache::{AssetCache, SyncAssetKeyExt};
 use glam::{vec3, Mat4, UVec2, Vec3, Vec4};
 use wgpu::{BindGroup, BindGroupLayout, Buffer, Sampler};
 
 use super::{
-    fog_color, get_active_sun, light_ambient, light_diffuse, RenderTarget, ShadowCameraData,
+    fog_color, get_active_sun, light_ambient, light_diffuse, LightClusters, LightData, Lights,
+    RenderTarget, ShadowCameraData, LIGHT_SHADOW_FACES, MAX_SHADOW_LIGHTS,
 };
 use crate::{
-    fog_density, fog_height_falloff, skinning::SkinsBufferKey, GLOBALS_BIND_GROUP,
-    MESH_BASE_BINDING, MESH_METADATA_BINDING, MESH_SKIN_BINDING, SKINS_BINDING,
+    environment_map_intensity, fog_density, fog_height_falloff, get_active_environment_map,
+    get_active_wind, get_wind_time, skinning::SkinsBufferKey, EnvironmentMap,
+    DEFAULT_WIND_VELOCITY, ENVIRONMENT_MAP_SH_COEFFICIENTS, GLOBALS_BIND_GROUP,
+    GLOBALS_BIND_GROUP_SIZE, MESH_BASE_BINDING, MESH_METADATA_BINDING, MESH_SKIN_BINDING,
+    SKINS_BINDING,
 };
 
 #[repr(C)]
@@ -53,6 +58,27 @@ pub(crate) struct GlobalParams {
     pub fog_height_falloff: f32,
     pub fog_density: f32,
     pub debug_params: ShaderDebugParams,
+    pub light_cluster_near: f32,
+    pub light_cluster_far: f32,
+    pub light_count: u32,
+    /// The depth offset used when comparing against the shadow cascades
+    pub shadow_bias: f32,
+    pub planar_reflection_projection_view: Mat4,
+    /// The plane of the rendered planar reflection probe, or zero without one
+    pub planar_reflection_plane: Vec4,
+    /// Fragments behind this plane are discarded, unless it is zero
+    pub clip_plane: Vec4,
+    /// The diffuse irradiance of the environment map, see `EnvironmentMapDesc::irradiance`
+    pub environment_irradiance: [Vec4; ENVIRONMENT_MAP_SH_COEFFICIENTS],
+    pub environment_intensity: f32,
+    /// The mip levels of the environment map, or zero without one
+    pub environment_levels: u32,
+    pub environment_padding: [u32; 2],
+    /// The velocity of the wind in xyz, and the strength of its gusts in w
+    pub wind: Vec4,
+    /// See `get_wind_time`. The shadow passes use it too, so the shadows sway along
+    pub wind_time: f32,
+    pub wind_padding: [u32; 3],
 }
 
 impl Default for GlobalParams {
@@ -73,6 +99,20 @@ impl Default for GlobalParams {
             fog_height_falloff: 0.5,
             fog_density: 0.5,
             debug_params: Default::default(),
+            light_cluster_near: 0.1,
+            light_cluster_far: 1000.,
+            light_count: 0,
+            shadow_bias: 0.0001,
+            planar_reflection_projection_view: Default::default(),
+            planar_reflection_plane: Vec4::ZERO,
+            clip_plane: Vec4::ZERO,
+            environment_irradiance: [Vec4::ZERO; ENVIRONMENT_MAP_SH_COEFFICIENTS],
+            environment_intensity: 1.,
+            environment_levels: 0,
+            environment_padding: Default::default(),
+            wind: DEFAULT_WIND_VELOCITY.extend(0.5),
+            wind_time: 0.,
+            wind_padding: Default::default(),
         }
     }
 }
@@ -156,6 +196,62 @@ pub fn globals_layout() -> BindGroupDesc<'static> {
                 },
                 count: None,
             },
+            wgpu::BindGroupLayoutEntry {
+                binding: 8,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 9,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 10,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2Array,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 11,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 12,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 13,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
         ],
         label: GLOBALS_BIND_GROUP.into(),
     }
@@ -166,6 +262,9 @@ pub(crate) struct ForwardGlobals {
     shadow_cameras_buffer: wgpu::Buffer,
     shadow_sampler: wgpu::Sampler,
     dummy_shadow_texture: TextureView,
+    lights_buffer: TypedBuffer<LightData>,
+    light_clusters_buffer: TypedBuffer<u32>,
+    environment_map: Option<Arc<EnvironmentMap>>,
     pub(crate) params: GlobalParams,
     scene: Component<()>,
     start_time: ambient_sys::time::Instant,
@@ -189,9 +288,22 @@ impl ForwardGlobals {
         let shadow_cameras_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("ForwardGlobals.shadow_cameras_buffer"),
             usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            size: shadow_cascades as u64 * std::mem::size_of::<ShadowCameraData>() as u64,
+            size: (shadow_cascades + MAX_SHADOW_LIGHTS * LIGHT_SHADOW_FACES) as u64
+                * std::mem::size_of::<ShadowCameraData>() as u64,
             mapped_at_creation: false,
         });
+        let lights_buffer = TypedBuffer::new(
+            gpu,
+            Some("ForwardGlobals.lights_buffer"),
+            1,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        let light_clusters_buffer = TypedBuffer::new(
+            gpu,
+            Some("ForwardGlobals.light_clusters_buffer"),
+            1,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
 
         let shadow_sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("shadow"),
@@ -217,6 +329,9 @@ impl ForwardGlobals {
                     ..Default::default()
                 },
             ),
+            lights_buffer,
+            light_clusters_buffer,
+            environment_map: None,
             params,
             scene,
             start_time: ambient_sys::time::Instant::now(),
@@ -229,7 +344,9 @@ impl ForwardGlobals {
         gpu: &Gpu,
         assets: &AssetCache,
         shadow_texture: Option<&TextureView>,
+        light_shadow_texture: Option<&TextureView>,
         solids_frame: &RenderTarget,
+        planar_reflection: Option<&TextureView>,
         mesh_buffer: &MeshBuffer,
     ) -> BindGroup {
         let skins = SkinsBufferKey.get(assets);
@@ -278,19 +395,54 @@ impl ForwardGlobals {
                     ),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + MESH_METADATA_BINDING,
+                    binding: 8,
+                    resource: self.lights_buffer.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 9,
+                    resource: self.light_clusters_buffer.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 10,
+                    resource: wgpu::BindingResource::TextureView(
+                        light_shadow_texture.unwrap_or(&self.dummy_shadow_texture),
+                    ),
+                },
+                // Only sampled when a probe was rendered
+                wgpu::BindGroupEntry {
+                    binding: 11,
+                    resource: wgpu::BindingResource::TextureView(
+                        planar_reflection.unwrap_or(&solids_frame.color_buffer_view),
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 12,
+                    resource: wgpu::BindingResource::Sampler(
+                        &SamplerKey::LINEAR_REPEAT.get(assets),
+                    ),
+                },
+                // Only sampled when the scene has an environment map
+                wgpu::BindGroupEntry {
+                    binding: 13,
+                    resource: wgpu::BindingResource::TextureView(match &self.environment_map {
+                        Some(map) => &map.view,
+                        None => &solids_frame.color_buffer_view,
+                    }),
+                },
+                wgpu::BindGroupEntry {
+                    binding: GLOBALS_BIND_GROUP_SIZE + MESH_METADATA_BINDING,
                     resource: mesh_buffer.metadata_buffer.buffer().as_entire_binding(),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + MESH_BASE_BINDING,
+                    binding: GLOBALS_BIND_GROUP_SIZE + MESH_BASE_BINDING,
                     resource: mesh_buffer.base_buffer.buffer().as_entire_binding(),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + MESH_SKIN_BINDING,
+                    binding: GLOBALS_BIND_GROUP_SIZE + MESH_SKIN_BINDING,
                     resource: mesh_buffer.skinned_buffer.buffer().as_entire_binding(),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + SKINS_BINDING,
+                    binding: GLOBALS_BIND_GROUP_SIZE + SKINS_BINDING,
                     resource: skins.buffer.buffer().as_entire_binding(),
                 },
             ],
@@ -299,8 +451,15 @@ impl ForwardGlobals {
     }
 
     #[tracing::instrument(level = "debug", skip_all, fields(scene = ?self.scene, user = ?world.resource_opt(local_user_id())))]
-    pub fn update(&mut self, gpu: &Gpu, world: &World, shadow_cameras: &[ShadowCameraData]) {
+    pub fn update(
+        &mut self,
+        gpu: &Gpu,
+        world: &World,
+        shadow_cameras: &[ShadowCameraData],
+        lights: &Lights,
+    ) {
         let p = &mut self.params;
+        let mut light_clusters = None;
         if let Some(id) = get_active_camera(world, self.scene, world.resource_opt(local_user_id()))
         {
             p.projection_view = world.get(id, projection_view()).unwrap_or_default();
@@ -313,9 +472,32 @@ impl ForwardGlobals {
             p.camera_far = world.get(id, far()).unwrap_or(1e3);
             p.fog = world.has_component(id, fog()) as i32;
             p.forward_camera_position = p.camera_position;
+            if !lights.lights.is_empty() {
+                light_clusters = Camera::from_world(world, id)
+                    .map(|camera| LightClusters::new(&camera, &lights.lights));
+            }
             tracing::trace!(world = world.name(), "found active camera",);
         }
 
+        if let Some(clusters) = light_clusters {
+            let light_data = lights
+                .lights
+                .iter()
+                .map(LightData::from)
+                .collect::<Vec<_>>();
+            p.light_cluster_near = clusters.near;
+            p.light_cluster_far = clusters.far;
+            p.light_count = light_data.len() as u32;
+            self.lights_buffer.fill(gpu, &light_data, |_| {});
+            self.light_clusters_buffer.fill(gpu, &clusters.data, |_| {});
+        } else {
+            // Nothing reads the buffers without lights, but they still need to be bound
+            p.light_count = 0;
+            self.lights_buffer
+                .fill(gpu, &[LightData::default()], |_| {});
+            self.light_clusters_buffer.fill(gpu, &[0, 0], |_| {});
+        }
+
         if let Some(sun) = get_active_sun(world, self.scene) {
             fn update<T, U>(out: &mut T, input: Result<U, ECSError>, mapper: impl Fn(U) -> T) {
                 if let Ok(value) = input {
@@ -342,6 +524,21 @@ impl ForwardGlobals {
             );
             update(&mut p.fog_density, world.get(sun, fog_density()), |v| v);
         }
+
+        self.environment_map = match get_active_environment_map(world, self.scene) {
+            Some((id, map)) => {
+                p.environment_irradiance = map.irradiance.map(|v| v.extend(0.));
+                p.environment_intensity = world.get(id, environment_map_intensity()).unwrap_or(1.);
+                p.environment_levels = map.levels;
+                Some(map)
+            }
+            None => {
+                p.environment_levels = 0;
+                None
+            }
+        };
+        p.wind = get_active_wind(world, self.scene);
+        p.wind_time = get_wind_time(world);
         self.params.time = ambient_sys::time::Instant::now()
             .duration_since(self.start_time)
             .as_secs_f32();
@@ -381,6 +578,7 @@ pub struct ShadowAndUIGlobals {
     shadow_sampler: Sampler,
     shadow_view: TextureView,
     dummy_prev_frame: RenderTarget,
+    dummy_lights_buffer: wgpu::Buffer,
     buffer: wgpu::Buffer,
     bind_group: Option<BindGroup>,
 }
@@ -412,6 +610,14 @@ impl ShadowAndUIGlobals {
             ..Default::default()
         });
 
+        // Only used to satisfy the layout; lights are never shaded in these passes
+        let dummy_lights_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("ShadowGlobals.dummy_lights_buffer"),
+            usage: wgpu::BufferUsages::STORAGE,
+            size: std::mem::size_of::<LightData>() as u64,
+            mapped_at_creation: false,
+        });
+
         let shadow_texture = create_dummy_shadow_texture(gpu);
         let dummy_prev_frame = RenderTarget::new(gpu, UVec2::ONE, None);
         let shadow_view = shadow_texture.create_view(&wgpu::TextureViewDescriptor {
@@ -426,6 +632,7 @@ impl ShadowAndUIGlobals {
             shadow_sampler,
             shadow_view,
             dummy_prev_frame,
+            dummy_lights_buffer,
             bind_group: None,
         }
     }
@@ -485,19 +692,49 @@ impl ShadowAndUIGlobals {
                     ),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + MESH_METADATA_BINDING,
+                    binding: 8,
+                    resource: self.dummy_lights_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 9,
+                    resource: self.dummy_lights_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 10,
+                    resource: wgpu::BindingResource::TextureView(&self.shadow_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 11,
+                    resource: wgpu::BindingResource::TextureView(
+                        &self.dummy_prev_frame.color_buffer_view,
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 12,
+                    resource: wgpu::BindingResource::Sampler(
+                        &SamplerKey::LINEAR_REPEAT.get(assets),
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 13,
+                    resource: wgpu::BindingResource::TextureView(
+                        &self.dummy_prev_frame.color_buffer_view,
+                    ),
+                },
+                wgpu::BindGroupEntry {
+                    binding: GLOBALS_BIND_GROUP_SIZE + MESH_METADATA_BINDING,
                     resource: mesh_buffer.metadata_buffer.as_binding(),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + MESH_BASE_BINDING,
+                    binding: GLOBALS_BIND_GROUP_SIZE + MESH_BASE_BINDING,
                     resource: mesh_buffer.base_buffer.front().as_binding(),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + MESH_SKIN_BINDING,
+                    binding: GLOBALS_BIND_GROUP_SIZE + MESH_SKIN_BINDING,
                     resource: mesh_buffer.skinned_buffer.front().as_binding(),
                 },
                 wgpu::BindGroupEntry {
-                    binding: 8 + SKINS_BINDING,
+                    binding: GLOBALS_BIND_GROUP_SIZE + SKINS_BINDING,
                     resource: skins.buffer.as_binding(),
                 },
             ],
@@ -514,6 +751,8 @@ impl ShadowAndUIGlobals {
                 .inverse()
                 .project_point3(-Vec3::Z)
                 .extend(1.),
+            wind: get_active_wind(world, scene),
+            wind_time: get_wind_time(world),
             ..Default::default()
         };
         if let Some(id) = get_active_camera(world, scene, world.resource_opt(local_user_id())) {
@@ -22,6 +22,30 @@ struct ForwardGlobalParams {
     debug_metallic_roughness: f32,
     debug_normals: f32,
     debug_shading: f32,
+    debug_padding: f32,
+
+    light_cluster_near: f32,
+    light_cluster_far: f32,
+    light_count: u32,
+    shadow_bias: f32,
+
+    planar_reflection_projection_view: mat4x4<f32>,
+    planar_reflection_plane: vec4<f32>,
+    clip_plane: vec4<f32>,
+
+    environment_irradiance: array<vec4<f32>, 9>,
+    environment_intensity: f32,
+    // Zero when there is no environment map
+    environment_levels: u32,
+    environment_padding0: u32,
+    environment_padding1: u32,
+
+    // xyz: velocity, w: gust strength
+    wind: vec4<f32>,
+    wind_time: f32,
+    wind_padding0: u32,
+    wind_padding1: u32,
+    wind_padding2: u32,
 };
 
 struct ShadowCamera {
@@ -61,13 +85,51 @@ var solids_screen_depth: texture_depth_2d;
 @binding(7)
 var solids_screen_normal_quat: texture_2d<f32>;
 
+struct Light {
+    position: vec3<f32>,
+    range: f32,
+    color: vec3<f32>,
+    // 0 for point lights, 1 for spot lights
+    kind: u32,
+    direction: vec3<f32>,
+    cos_cone: f32,
+    shadow: i32,
+    cos_inner_cone: f32,
+};
+
+@group(GLOBALS_BIND_GROUP)
+@binding(8)
+var<storage> lights: array<Light>;
+
+// An (offset, count) pair for each cluster, followed by the light indices they point to
+@group(GLOBALS_BIND_GROUP)
+@binding(9)
+var<storage> light_clusters: array<u32>;
+
+@group(GLOBALS_BIND_GROUP)
+@binding(10)
+var light_shadow_texture: texture_depth_2d_array;
+
+@group(GLOBALS_BIND_GROUP)
+@binding(11)
+var planar_reflection_texture: texture_2d<f32>;
+
+@group(GLOBALS_BIND_GROUP)
+@binding(12)
+var environment_sampler: sampler;
+
+// An equirectangular image, whose mip levels are prefiltered for increasing roughness
+@group(GLOBALS_BIND_GROUP)
+@binding(13)
+var environment_texture: texture_2d<f32>;
+
 fn inside(v: vec3<f32>) -> bool {
     return v.x > -1. && v.x < 1. && v.y > -1. && v.y < 1. && v.z > 0. && v.z < 1.;
 }
 
 fn fetch_shadow_cascade(cascade: i32, homogeneous_coords: vec3<f32>) -> f32 {
     let light_local = homogeneous_coords.xy * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
-    return textureSampleCompareLevel(shadow_texture, shadow_sampler, light_local, cascade, homogeneous_coords.z + 0.0001);
+    return textureSampleCompareLevel(shadow_texture, shadow_sampler, light_local, cascade, homogeneous_coords.z + global_params.shadow_bias);
 }
 
 fn get_shadow_cascade(world_position: vec4<f32>) -> i32 {
@@ -94,6 +156,61 @@ fn fetch_shadow(light_angle: f32, world_position: vec4<f32>) -> f32 {
     return 1.;
 }
 
+fn fetch_light_shadow(light: Light, world_position: vec4<f32>) -> f32 {
+    if light.shadow < 0 {
+        return 1.;
+    }
+    // Point lights have a shadow map for each side of a cube, in the order +X, -X, +Y, -Y, +Z, -Z
+    var face = 0;
+    if light.kind == 0u {
+        let d = world_position.xyz - light.position;
+        let a = abs(d);
+        if a.x >= a.y && a.x >= a.z {
+            face = select(1, 0, d.x > 0.);
+        } else if a.y >= a.z {
+            face = select(3, 2, d.y > 0.);
+        } else {
+            face = select(5, 4, d.z > 0.);
+        }
+    }
+    let layer = light.shadow * LIGHT_SHADOW_FACES + face;
+    let cam = shadow_cameras.cameras[SHADOW_CASCADES + layer].viewproj * world_position;
+    let p = cam.xyz / cam.w;
+    if !inside(p) {
+        return 1.;
+    }
+    let light_local = p.xy * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
+    // The depth is not linear, so the bias is relative to the distance from the light
+    return textureSampleCompareLevel(light_shadow_texture, shadow_sampler, light_local, layer, p.z * 1.005);
+}
+
+fn get_light_cluster(world_position: vec4<f32>) -> u32 {
+    let clip = global_params.projection_view * world_position;
+    let tiles = vec2<i32>(LIGHT_CLUSTERS_X, LIGHT_CLUSTERS_Y);
+    let tile = clamp(vec2<i32>((clip.xy / clip.w * 0.5 + 0.5) * vec2<f32>(tiles)), vec2<i32>(0), tiles - 1);
+
+    // The depth slices are spread exponentially between the near and far of the clusters
+    let near = global_params.light_cluster_near;
+    let far = global_params.light_cluster_far;
+    let depth = dot(world_position.xyz - global_params.camera_position.xyz, normalize(global_params.camera_forward));
+    let slice = clamp(i32(floor(log(max(depth, near) / near) / log(far / near) * f32(LIGHT_CLUSTERS_Z))), 0, LIGHT_CLUSTERS_Z - 1);
+
+    return u32(tile.x + tile.y * tiles.x + slice * tiles.x * tiles.y);
+}
+
+fn punctual_light_attenuation(light: Light, world_position: vec3<f32>) -> f32 {
+    let to_light = light.position - world_position;
+    let distance = length(to_light);
+    // Inverse square falloff, windowed to reach zero at the range of the light
+    let window = clamp(1. - pow(distance / light.range, 4.), 0., 1.);
+    var attenuation = window * window / max(distance * distance, 0.0001);
+    if light.kind == 1u {
+        let cos_angle = dot(-to_light / distance, light.direction);
+        attenuation *= smoothstep(light.cos_cone, light.cos_inner_cone, cos_angle);
+    }
+    return attenuation;
+}
+
 fn screen_pixel_to_uv(pixel_position: vec2<f32>, screen_size: vec2<f32>) -> vec2<f32> {
     return pixel_position / screen_size;
 }
@@ -159,11 +276,17 @@ struct MaterialOutput {
             normal: vec3<f32>,
             metallic: f32,
             roughness: f32,
+            reflectivity: f32,
+            // How strongly a clear coat on top of the surface reflects light; zero for none
+            clearcoat: f32,
+            clearcoat_roughness: f32,
         };
 
 struct MainFsOut {
             @location(0) color: vec4<f32>,
             @location(1) normal: vec4<f32>,
+            // The world space normal, and how strongly the surface reflects its surroundings
+            @location(2) material: vec4<f32>,
         }
 
 fn apply_fog(color: vec3<f32>, camera_pos: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
@@ -228,14 +351,8 @@ fn geometry_smith(normal: vec3<f32>, v: vec3<f32>, l: vec3<f32>, roughness: f32)
           geometry_schlick_ggx(ndotv, k) * geometry_schlick_ggx(ndotl, k);
 }
 
-fn shading(material: MaterialOutput, world_position: vec4<f32>) -> vec4<f32> {
-    if global_params.debug_shading > 0.0 {
-        return vec4(material.base_color.rgb, material.opacity);
-    }
-
-    let v = normalize(global_params.camera_position.xyz - world_position.xyz);
-
-    let l = normalize(global_params.sun_direction.xyz);
+// The light reflected towards `v` by a surface lit from the direction `l`
+fn brdf(material: MaterialOutput, v: vec3<f32>, l: vec3<f32>) -> vec3<f32> {
     let h = normalize(v + l);
 
     let albedo = material.base_color.rgb;
@@ -292,13 +409,130 @@ fn shading(material: MaterialOutput, world_position: vec4<f32>) -> vec4<f32> {
     // Cook-torrance specular reflection
     let specular = ks * (ndf * g * f) / denom;
 
+    if material.clearcoat <= 0.0 {
+        return (lambert + specular) * ndotl;
+    }
+    // The clear coat reflects the light it doesn't let through to the surface below
+    let coat = clearcoat_fresnel(material, max(dot(h, v), 0.0));
+    let coat_specular = distribution_ggx(normal, h, material.clearcoat_roughness)
+        * geometry_smith(normal, v, l, material.clearcoat_roughness) * coat / denom;
+
+    return ((lambert + specular) * (1.0 - coat) + coat_specular) * ndotl;
+}
+
+// The share of the light reflected by the clear coat of a material
+fn clearcoat_fresnel(material: MaterialOutput, cos_theta: f32) -> f32 {
+    return fresnel(cos_theta, vec3<f32>(0.04)).x * material.clearcoat;
+}
+
+// Section: Image-based lighting
+
+fn has_environment_map() -> bool {
+    return global_params.environment_levels > 0u;
+}
+
+// The equirectangular texture coordinates of a direction, with +Z at the top
+fn environment_uv(dir: vec3<f32>) -> vec2<f32> {
+    let d = normalize(dir);
+    return vec2<f32>(atan2(d.y, d.x) / (2. * PI) + 0.5, acos(clamp(d.z, -1., 1.)) / PI);
+}
+
+// The light coming from the direction `dir` of the environment map, blurred for `roughness`
+fn get_environment_color(dir: vec3<f32>, roughness: f32) -> vec3<f32> {
+    let level = roughness * f32(global_params.environment_levels - 1u);
+    let color = textureSampleLevel(environment_texture, environment_sampler, environment_uv(dir), level).rgb;
+    return color * global_params.environment_intensity;
+}
+
+// The diffuse irradiance of the environment map around `normal`, divided by pi
+fn get_environment_irradiance(normal: vec3<f32>) -> vec3<f32> {
+    let n = normalize(normal);
+    let sh = global_params.environment_irradiance;
+    let irradiance = sh[0].rgb * 0.282095
+        + sh[1].rgb * 0.488603 * n.y
+        + sh[2].rgb * 0.488603 * n.z
+        + sh[3].rgb * 0.488603 * n.x
+        + sh[4].rgb * 1.092548 * n.x * n.y
+        + sh[5].rgb * 1.092548 * n.y * n.z
+        + sh[6].rgb * 0.315392 * (3. * n.z * n.z - 1.)
+        + sh[7].rgb * 1.092548 * n.x * n.z
+        + sh[8].rgb * 0.546274 * (n.x * n.x - n.y * n.y);
+    return max(irradiance, vec3<f32>(0.)) * global_params.environment_intensity;
+}
+
+// The light of the environment map reflected towards `v`, with the analytic approximation of
+// the split-sum BRDF from "Physically Based Shading on Mobile" by Brian Karis
+fn image_based_lighting(material: MaterialOutput, v: vec3<f32>) -> vec3<f32> {
+    let normal = material.normal;
+    let roughness = material.roughness;
+    let ndotv = max(dot(normal, v), 0.0);
+    let f0 = mix(vec3<f32>(0.04), material.base_color.rgb, material.metallic);
+
+    let c0 = vec4<f32>(-1., -0.0275, -0.572, 0.022);
+    let c1 = vec4<f32>(1., 0.0425, 1.04, -0.04);
+    let r = roughness * c0 + c1;
+    let a004 = min(r.x * r.x, exp2(-9.28 * ndotv)) * r.x + r.y;
+    let ab = vec2<f32>(-1.04, 1.04) * a004 + r.zw;
+    let specular_weight = f0 * ab.x + ab.y;
+
+    let kd = (vec3<f32>(1.0) - specular_weight) * (1.0 - material.metallic);
+    let diffuse = kd * material.base_color.rgb * get_environment_irradiance(normal);
+    let specular = specular_weight * get_environment_color(reflect(-v, normal), roughness);
+    if material.clearcoat <= 0.0 {
+        return diffuse + specular;
+    }
+    let coat = clearcoat_fresnel(material, ndotv);
+    let coat_specular = coat * get_environment_color(reflect(-v, normal), material.clearcoat_roughness);
+    return (diffuse + specular) * (1.0 - coat) + coat_specular;
+}
+
+fn punctual_lighting(material: MaterialOutput, v: vec3<f32>, world_position: vec4<f32>) -> vec3<f32> {
+    var lum = vec3<f32>(0.);
+    if global_params.light_count == 0u {
+        return lum;
+    }
+    let cluster = get_light_cluster(world_position);
+    let offset = light_clusters[cluster * 2u];
+    let count = light_clusters[cluster * 2u + 1u];
+    for (var i = 0u; i < count; i = i + 1u) {
+        let light = lights[light_clusters[offset + i]];
+        let attenuation = punctual_light_attenuation(light, world_position.xyz);
+        if attenuation <= 0. {
+            continue;
+        }
+        let l = normalize(light.position - world_position.xyz);
+        lum += brdf(material, v, l) * light.color * attenuation * fetch_light_shadow(light, world_position);
+    }
+    return lum;
+}
+
+fn shading(material: MaterialOutput, world_position: vec4<f32>) -> vec4<f32> {
+    if global_params.debug_shading > 0.0 {
+        return vec4(material.base_color.rgb, material.opacity);
+    }
+
+    let v = normalize(global_params.camera_position.xyz - world_position.xyz);
+
+    let l = normalize(global_params.sun_direction.xyz);
+
+    let albedo = material.base_color.rgb;
+
+    let metallic = material.metallic;
+    let roughness = material.roughness;
+    let normal = material.normal;
+
+    let ndotl = max(dot(normal, l), 0.0);
+
     let radiance = global_params.sun_diffuse.rgb;
 
     let in_shadow = fetch_shadow(ndotl, world_position);
 
-    let direct = (lambert + specular) * radiance * ndotl * in_shadow;
+    let direct = brdf(material, v, l) * radiance * in_shadow + punctual_lighting(material, v, world_position);
 
-    let indirect = albedo * global_params.sun_ambient.rgb;
+    var indirect = albedo * global_params.sun_ambient.rgb;
+    if has_environment_map() {
+        indirect = image_based_lighting(material, v);
+    }
 
     let lum = direct + indirect;
 
@@ -319,6 +553,47 @@ fn shading(material: MaterialOutput, world_position: vec4<f32>) -> vec4<f32> {
     return vec4<f32>(color, material.opacity);
 }
 
+// How strongly a surface reflects its surroundings towards the camera
+fn get_reflection_strength(material: MaterialOutput, world_position: vec4<f32>) -> f32 {
+    let v = normalize(global_params.camera_position.xyz - world_position.xyz);
+    let smoothness = 1. - material.roughness;
+    let f0 = mix(0.04, 1., material.metallic);
+    let f = f0 + (1. - f0) * pow(clamp(1. - dot(material.normal, v), 0., 1.), 5.);
+    let coat_smoothness = 1. - material.clearcoat_roughness;
+    let coat = clearcoat_fresnel(material, dot(material.normal, v)) * coat_smoothness * coat_smoothness;
+    return clamp(max(material.reflectivity * smoothness * smoothness * f, coat), 0., 1.);
+}
+
+// The image of the planar reflection probe at a point on its plane, with an alpha of zero away
+// from the plane, or when no probe was rendered
+fn get_planar_reflection(world_position: vec3<f32>, offset: vec2<f32>) -> vec4<f32> {
+    let plane = global_params.planar_reflection_plane;
+    if dot(plane.xyz, plane.xyz) == 0. {
+        return vec4<f32>(0.);
+    }
+    let distance = abs(dot(plane.xyz, world_position) + plane.w);
+    let ndc = project_point(global_params.planar_reflection_projection_view, world_position);
+    let uv = screen_ndc_to_uv(ndc) + offset;
+    let color = textureSampleLevel(planar_reflection_texture, default_sampler, uv, 0.).rgb;
+    return vec4<f32>(color, 1. - smoothstep(0.05, 0.2, distance));
+}
+
+// Finishes the forward pass of a surface: discards it behind the clip plane, reflects the planar
+// reflection probe it lies on, and leaves its other reflections to the `ssr` post-processing effect
+fn forward_out(material: MaterialOutput, color: vec4<f32>, world_position: vec4<f32>, normal_quat: vec4<f32>) -> MainFsOut {
+    let clip_plane = global_params.clip_plane;
+    if dot(clip_plane.xyz, world_position.xyz) + clip_plane.w < 0. {
+        discard;
+    }
+
+    var reflection = get_reflection_strength(material, world_position);
+    let planar = get_planar_reflection(world_position.xyz, vec2<f32>(0.));
+    let reflected = vec4<f32>(mix(color.rgb, planar.rgb, reflection * planar.a), color.a);
+    // What the probe already reflects is not reflected again from the screen
+    reflection *= 1. - planar.a;
+    return MainFsOut(reflected, normal_quat, vec4<f32>(material.normal, reflection));
+}
+
 struct FSOutput {
             @location(0) color: vec4<f32>,
             @location(1) outline: vec4<f32>,
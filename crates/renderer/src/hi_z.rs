@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use ambient_core::camera::Camera;
+use ambient_gpu::{
+    gpu::Gpu,
+    shader_module::{BindGroupDesc, GraphicsPipeline, GraphicsPipelineInfo, Shader, ShaderModule},
+    texture::{Texture, TextureView},
+};
+use ambient_native_std::{asset_cache::AssetCache, include_file};
+use glam::{vec2, Mat4, Vec2};
+use wgpu::{BindGroupLayout, BindGroupLayoutEntry, BindingType, ShaderStages};
+
+const HI_Z_BIND_GROUP: &str = "HI_Z_BIND_GROUP";
+const HI_Z_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+
+/// The previous frame is not used for occlusion culling when the camera moved further than this
+/// since, as too much of what it sees now was not visible then
+const MAX_CAMERA_MOVE: f32 = 2.;
+/// Same as above, for the cosine of the angle the camera turned
+const MIN_CAMERA_TURN_COS: f32 = 0.97;
+
+fn get_hi_z_layout() -> BindGroupDesc<'static> {
+    let texture = |binding, sample_type| BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::FRAGMENT,
+        ty: BindingType::Texture {
+            sample_type,
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    };
+    BindGroupDesc {
+        entries: vec![
+            texture(0, wgpu::TextureSampleType::Depth),
+            texture(1, wgpu::TextureSampleType::Float { filterable: false }),
+        ],
+        label: HI_Z_BIND_GROUP.into(),
+    }
+}
+
+/// What the occlusion culling needs to know about the pyramid
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HiZParams {
+    /// The projection view of the camera the pyramid was built for
+    pub projection_view: Mat4,
+    /// The size of the first level of the pyramid
+    pub size: Vec2,
+    pub mips: u32,
+}
+
+/// A hierarchical depth buffer of the last rendered frame.
+///
+/// Each level is half the size of the one above, and each texel holds the farthest depth of the
+/// texels it covers, so the culling can tell if an object is hidden behind what was drawn in that
+/// frame with a handful of reads.
+pub struct HiZ {
+    enabled: bool,
+    layout: Arc<BindGroupLayout>,
+    from_depth: GraphicsPipeline,
+    downsample: GraphicsPipeline,
+    /// Bound in place of the previous level to the first pass, and to the culling when there is no
+    /// pyramid
+    dummy: TextureView,
+    pyramid: Option<(TextureView, Camera)>,
+}
+
+impl HiZ {
+    pub fn new(gpu: &Gpu, assets: &AssetCache, enabled: bool) -> Self {
+        let shader = Shader::new(
+            assets,
+            "HiZ",
+            &[HI_Z_BIND_GROUP],
+            &ShaderModule::new("hi_z", include_file!("hi_z.wgsl"))
+                .with_binding_desc(get_hi_z_layout()),
+        )
+        .unwrap();
+
+        let pipeline = |fs_main| {
+            shader.to_pipeline(
+                gpu,
+                GraphicsPipelineInfo {
+                    fs_main,
+                    targets: &[Some(HI_Z_FORMAT.into())],
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let dummy = Arc::new(Self::create_texture(
+            gpu,
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            1,
+        ));
+
+        Self {
+            enabled,
+            layout: shader.layouts()[0].clone(),
+            from_depth: pipeline("fs_depth"),
+            downsample: pipeline("fs_downsample"),
+            dummy: dummy.create_view(&Default::default()),
+            pyramid: None,
+        }
+    }
+
+    fn create_texture(gpu: &Gpu, size: wgpu::Extent3d, mip_level_count: u32) -> Texture {
+        Texture::new(
+            gpu,
+            &wgpu::TextureDescriptor {
+                label: Some("HiZ"),
+                size,
+                mip_level_count,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: HI_Z_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        )
+    }
+
+    /// Rebuilds the pyramid from the depth buffer of a frame rendered by `camera`
+    pub fn build(
+        &mut self,
+        gpu: &Gpu,
+        encoder: &mut wgpu::CommandEncoder,
+        depth: &wgpu::TextureView,
+        depth_size: wgpu::Extent3d,
+        camera: &Camera,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        profiling::scope!("HiZ");
+
+        let size = wgpu::Extent3d {
+            width: (depth_size.width / 2).max(1),
+            height: (depth_size.height / 2).max(1),
+            depth_or_array_layers: 1,
+        };
+        let texture = match &self.pyramid {
+            Some((view, _)) if view.texture.size == size => view.texture.clone(),
+            _ => {
+                let mips = size.width.max(size.height).ilog2() + 1;
+                Arc::new(Self::create_texture(gpu, size, mips))
+            }
+        };
+        let levels = (0..texture.mip_level_count)
+            .map(|mip| {
+                texture.create_view(&wgpu::TextureViewDescriptor {
+                    base_mip_level: mip,
+                    mip_level_count: Some(1),
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for (i, level) in levels.iter().enumerate() {
+            let (pipeline, source) = if i == 0 {
+                (&self.from_depth, &*self.dummy)
+            } else {
+                (&self.downsample, &*levels[i - 1])
+            };
+            let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(depth),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(source),
+                    },
+                ],
+                label: Some("HiZ.bind_group"),
+            });
+
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("HiZ"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: level,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            rpass.set_pipeline(pipeline.pipeline());
+            rpass.set_bind_group(0, &bind_group, &[]);
+            rpass.draw(0..4, 0..1);
+        }
+
+        self.pyramid = Some((texture.create_view(&Default::default()), camera.clone()));
+    }
+
+    /// The pyramid, or an empty texture if there is none
+    pub(crate) fn view(&self) -> &wgpu::TextureView {
+        match &self.pyramid {
+            Some((view, _)) => view,
+            None => &self.dummy,
+        }
+    }
+
+    /// The parameters to cull the objects seen by `camera` against the pyramid, if it can be used
+    pub(crate) fn params(&self, camera: &Camera) -> Option<HiZParams> {
+        let (view, built_for) = self.pyramid.as_ref()?;
+        let moved = camera.position().distance(built_for.position()) > MAX_CAMERA_MOVE;
+        let turned = camera.forward().dot(built_for.forward()) < MIN_CAMERA_TURN_COS;
+        if moved || turned {
+            return None;
+        }
+        Some(HiZParams {
+            projection_view: built_for.projection_view(),
+            size: vec2(
+                view.texture.size.width as f32,
+                view.texture.size.height as f32,
+            ),
+            mips: view.texture.mip_level_count,
+        })
+    }
+}
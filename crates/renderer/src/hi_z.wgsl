@@ -0,0 +1,64 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    out.position = vec4<f32>(
+        f32(x) * 4.0 - 1.0,
+        1.0 - f32(y) * 4.0,
+        0.0,
+        1.0
+    );
+    return out;
+}
+
+@group(HI_Z_BIND_GROUP)
+@binding(0)
+var depth_texture: texture_depth_2d;
+
+@group(HI_Z_BIND_GROUP)
+@binding(1)
+var source_texture: texture_2d<f32>;
+
+// The texels of the source level covered by a texel of the level being written, as an exclusive
+// range. Each level is half the size of the one above rounded down, so the last texel of a level
+// also covers the odd row or column left over
+fn source_end(pixel: vec2<i32>, source_size: vec2<i32>) -> vec2<i32> {
+    let size = max(source_size / 2, vec2<i32>(1));
+    return select(pixel * 2 + 2, source_size, pixel == size - 1);
+}
+
+// The depth is reversed, so the farthest depth is the smallest one
+@fragment
+fn fs_depth(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let source_size = vec2<i32>(textureDimensions(depth_texture));
+    let start = min(pixel * 2, source_size - 1);
+    let end = source_end(pixel, source_size);
+    var depth = 1.;
+    for (var y = start.y; y < end.y; y = y + 1) {
+        for (var x = start.x; x < end.x; x = x + 1) {
+            depth = min(depth, textureLoad(depth_texture, vec2<i32>(x, y), 0));
+        }
+    }
+    return vec4<f32>(depth, 0., 0., 0.);
+}
+
+@fragment
+fn fs_downsample(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(in.position.xy);
+    let source_size = vec2<i32>(textureDimensions(source_texture));
+    let start = min(pixel * 2, source_size - 1);
+    let end = source_end(pixel, source_size);
+    var depth = 1.;
+    for (var y = start.y; y < end.y; y = y + 1) {
+        for (var x = start.x; x < end.x; x = x + 1) {
+            depth = min(depth, textureLoad(source_texture, vec2<i32>(x, y), 0).x);
+        }
+    }
+    return vec4<f32>(depth, 0., 0., 0.);
+}
@@ -0,0 +1,58 @@
+//! Octahedral imposters: a quad standing in for a model far away, showing it from the closest of
+//! many directions it was rendered from at build time.
+//!
+//! The views are laid out on a grid of `frames` by `frames` in a single texture. Each cell of the
+//! grid looks at the model from a direction on an octahedron unfolded onto the grid, like
+//! octahedral normal encoding, so the views cover every direction evenly. The materials of
+//! imposters have [crate::materials::pbr_material::ImposterParams], and are drawn with a standard
+//! shader which turns their quad towards the camera in the vertex shader.
+
+use std::sync::Arc;
+
+use ambient_gpu::shader_module::ShaderModule;
+use ambient_native_std::include_file;
+use glam::{vec3, UVec2, Vec3};
+
+/// The module defining `apply_imposter` for the standard shader. With `enabled`, it turns the quad
+/// of the mesh into the view of the `get_material_imposter` of the material shader, otherwise it
+/// leaves the vertices as is.
+pub fn get_imposter_module(enabled: bool) -> Arc<ShaderModule> {
+    Arc::new(if enabled {
+        ShaderModule::new("imposter", include_file!("imposter.wgsl"))
+    } else {
+        ShaderModule::new(
+            "imposter",
+            "fn apply_imposter(world: ModelToWorld, entity_loc: vec2<u32>) -> ModelToWorld { return world; }",
+        )
+    })
+}
+
+/// The direction, from the center of the model, which the view at `frame` of a grid of `frames`
+/// by `frames` looks from. Matches `imposter_frame_direction` in the shader.
+pub fn imposter_frame_direction(frame: UVec2, frames: u32) -> Vec3 {
+    let p = frame.as_vec2() / (frames - 1) as f32 * 2. - 1.;
+    let z = 1. - p.x.abs() - p.y.abs();
+    let direction = if z < 0. {
+        let sign = |value: f32| if value >= 0. { 1. } else { -1. };
+        vec3(
+            (1. - p.y.abs()) * sign(p.x),
+            (1. - p.x.abs()) * sign(p.y),
+            z,
+        )
+    } else {
+        p.extend(z)
+    };
+    direction.normalize()
+}
+
+/// The right, up and backward directions of the view looking from `direction`. Matches
+/// `imposter_frame_basis` in the shader.
+pub fn imposter_frame_basis(direction: Vec3) -> [Vec3; 3] {
+    let reference = if direction.z.abs() > 0.999 {
+        Vec3::Y
+    } else {
+        Vec3::Z
+    };
+    let right = reference.cross(direction).normalize();
+    [right, direction.cross(right), direction]
+}
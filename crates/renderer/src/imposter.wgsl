@@ -0,0 +1,62 @@
+// Turns the quad of an octahedral imposter to face the view of its atlas closest to the camera,
+// and maps the view onto it. The material shader provides its imposter parameters with
+// `get_material_imposter`:
+// x: the number of views along each side of the atlas
+// And the bounding sphere of the baked meshes, in mesh space, with `get_material_imposter_sphere`.
+
+fn imposter_sign(value: vec2<f32>) -> vec2<f32> {
+    return select(vec2<f32>(-1.), vec2<f32>(1.), value >= vec2<f32>(0.));
+}
+
+// The view of the grid looking from `direction`, with the octahedron unfolded onto the grid
+fn imposter_frame(direction: vec3<f32>, frames: f32) -> vec2<f32> {
+    var p = direction.xy / (abs(direction.x) + abs(direction.y) + abs(direction.z));
+    if direction.z < 0. {
+        p = (1. - abs(p.yx)) * imposter_sign(p);
+    }
+    return clamp(round((p * 0.5 + 0.5) * (frames - 1.)), vec2<f32>(0.), vec2<f32>(frames - 1.));
+}
+
+fn imposter_frame_direction(frame: vec2<f32>, frames: f32) -> vec3<f32> {
+    let p = frame / (frames - 1.) * 2. - 1.;
+    var direction = vec3<f32>(p, 1. - abs(p.x) - abs(p.y));
+    if direction.z < 0. {
+        direction = vec3<f32>((1. - abs(p.yx)) * imposter_sign(p), direction.z);
+    }
+    return normalize(direction);
+}
+
+// The right, up and backward directions of the view looking from `direction`
+fn imposter_frame_basis(direction: vec3<f32>) -> mat3x3<f32> {
+    var reference = vec3<f32>(0., 0., 1.);
+    if abs(direction.z) > 0.999 {
+        reference = vec3<f32>(0., 1., 0.);
+    }
+    let right = normalize(cross(reference, direction));
+    return mat3x3<f32>(right, cross(direction, right), direction);
+}
+
+fn apply_imposter(world: ModelToWorld, entity_loc: vec2<u32>) -> ModelToWorld {
+    let frames = get_material_imposter().x;
+    let sphere = get_material_imposter_sphere();
+    let mesh_to_world = get_entity_mesh_to_world(entity_loc);
+    let scale = vec3<f32>(length(mesh_to_world[0].xyz), length(mesh_to_world[1].xyz), length(mesh_to_world[2].xyz));
+    let rotation = mat3x3<f32>(mesh_to_world[0].xyz / scale.x, mesh_to_world[1].xyz / scale.y, mesh_to_world[2].xyz / scale.z);
+
+    // The view is picked by the direction of the camera in mesh space
+    let center = mesh_to_world * vec4<f32>(sphere.xyz, 1.);
+    let to_camera = transpose(rotation) * (global_params.camera_position.xyz - center.xyz) / scale;
+    let frame = imposter_frame(normalize(to_camera), frames);
+    let basis = imposter_frame_basis(imposter_frame_direction(frame, frames));
+
+    // The top of the view is at the top of the quad
+    let corner = vec2<f32>(world.texcoord.x - 0.5, 0.5 - world.texcoord.y) * 2. * sphere.w;
+    var out = world;
+    out.local = vec4<f32>(sphere.xyz + basis[0] * corner.x + basis[1] * corner.y, 1.);
+    out.pos = mesh_to_world * out.local;
+    // The normals are baked in the space of the view, like a tangent space
+    out.normal = normalize(rotation * (basis[2] / scale));
+    out.tangent = normalize(rotation * (basis[0] * scale));
+    out.texcoord = (frame + world.texcoord) / frames;
+    return out;
+}
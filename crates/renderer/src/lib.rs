@@ -10,11 +10,12 @@ use ambient_core::{
     asset_cache, async_ecs::async_run, mesh, runtime, transform::get_world_rotation,
 };
 use ambient_ecs::{
-    components, copy_component_recursive, query_mut, Debuggable, Entity, EntityId, Resource,
-    SystemGroup, World,
+    components, copy_component_recursive, generated::package::messages::PackageLoadSuccess,
+    query_mut, read_messages, world_events, Debuggable, Entity, EntityId, FnSystem, Resource,
+    SystemGroup, World, WorldEventReader,
 };
 use ambient_gpu::{
-    gpu::Gpu,
+    gpu::{Gpu, GpuKey},
     mesh_buffer::GpuMesh,
     shader_module::{BindGroupDesc, Shader, ShaderIdent, ShaderModule},
     wgsl_utils::wgsl_interpolate,
@@ -30,40 +31,66 @@ use serde::{Deserialize, Serialize};
 
 pub mod bind_groups;
 mod collect;
+mod color_grading;
 mod culling;
+mod environment_map;
+mod frame_stats;
 mod globals;
+mod hi_z;
+mod imposter;
+mod lights;
 pub mod lod;
 pub mod materials;
+mod order_independent_transparency;
 mod outlines;
 mod overlay_renderer;
+mod particles;
+mod planar_reflections;
+mod post_processing;
 mod renderer;
 mod shaders;
 mod shadow_renderer;
 pub mod skinning;
+mod sprites;
 mod target;
+pub mod texture_atlas;
+pub mod texture_streaming;
 mod transparent_renderer;
 mod tree_renderer;
+mod wind;
 use ambient_ecs::{query, Component};
 pub use collect::*;
+pub use color_grading::*;
 pub use culling::*;
+pub use environment_map::*;
+pub use frame_stats::*;
 pub use globals::*;
+pub use hi_z::*;
+pub use imposter::*;
+pub use lights::*;
 use materials::pbr_material::PbrMaterialFromUrl;
 pub use materials::*;
+pub use order_independent_transparency::*;
 use ordered_float::OrderedFloat;
 pub use outlines::*;
+pub use particles::*;
+pub use post_processing::*;
 pub use renderer::*;
 pub use shaders::*;
 pub use shadow_renderer::*;
+pub use sprites::*;
 pub use target::*;
 pub use transparent_renderer::*;
 pub use tree_renderer::*;
+pub use wind::*;
 
 pub const MAX_PRIMITIVE_COUNT: usize = 16;
 
 pub use ambient_ecs::generated::rendering::components::{
-    cast_shadows, color, double_sided, fog_color, fog_density, fog_height_falloff, light_ambient,
-    light_diffuse, overlay, pbr_material_from_url, scissors, scissors_recursive, sun,
-    transparency_group,
+    cast_shadows, color, custom_material_from_url, double_sided, fog_color, fog_density,
+    fog_height_falloff, light_ambient, light_diffuse, overlay, pbr_material_from_url,
+    planar_reflection, scissors, scissors_recursive, sun, sun_shadows, texture_streaming_priority,
+    transparency_group, transparency_sort_bias,
 };
 
 components!("rendering", {
@@ -91,12 +118,20 @@ gpu_components! {
 pub fn init_all_components() {
     init_components();
     init_gpu_components();
+    outlines::init_components();
     outlines::init_gpu_components();
     culling::init_gpu_components();
+    color_grading::init_components();
+    environment_map::init_components();
+    frame_stats::init_components();
     lod::init_components();
     lod::init_gpu_components();
     skinning::init_components();
     skinning::init_gpu_components();
+    shadow_renderer::init_components();
+    sprites::init_components();
+    sprites::init_gpu_components();
+    texture_streaming::init_components();
 }
 
 pub fn systems() -> SystemGroup {
@@ -130,10 +165,7 @@ pub fn systems() -> SystemGroup {
                                         .add_components(
                                             id,
                                             Entity::new()
-                                                .with(
-                                                    renderer_shader(),
-                                                    cb(pbr_material::get_pbr_shader),
-                                                )
+                                                .with(renderer_shader(), mat.shader())
                                                 .with(material(), mat.into()),
                                         )
                                         .ok();
@@ -143,6 +175,33 @@ pub fn systems() -> SystemGroup {
                     });
                 }
             }),
+            query(custom_material_from_url().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in q.collect_cloned(world, qs) {
+                    load_custom_material(world, id, url);
+                }
+            }),
+            {
+                let mut reader = WorldEventReader::new();
+                Box::new(FnSystem::new(move |world, _| {
+                    // Reload the custom materials of the packages which were reloaded
+                    let packages = read_messages::<PackageLoadSuccess>(
+                        &mut reader,
+                        world.resource(world_events()),
+                    );
+                    if packages.is_empty() {
+                        return;
+                    }
+                    let bases = packages
+                        .iter()
+                        .map(|package| package.url.rsplit_once('/').map_or("", |(base, _)| base))
+                        .collect::<Vec<_>>();
+                    for (id, url) in query(custom_material_from_url()).collect_cloned(world, None) {
+                        if bases.iter().any(|base| url.starts_with(base)) {
+                            load_custom_material(world, id, url);
+                        }
+                    }
+                }))
+            },
             query_mut(
                 (primitives(),),
                 (
@@ -179,11 +238,57 @@ pub fn systems() -> SystemGroup {
                     }
                 }
             }),
+            Box::new(color_grading::systems()),
+            Box::new(environment_map::systems()),
             Box::new(outlines::systems()),
+            Box::new(post_processing::systems()),
+            Box::new(sprites::systems()),
+            Box::new(texture_streaming::systems()),
         ],
     )
 }
 
+fn load_custom_material(world: &World, id: EntityId, url: String) {
+    let url = match AbsAssetUrl::from_str(&url) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!("Failed to parse custom_material_from_url url: {:?}", err);
+            return;
+        }
+    };
+    let assets = world.resource(asset_cache()).clone();
+    let async_run = world.resource(async_run()).clone();
+    world.resource(runtime()).spawn(async move {
+        let gpu = GpuKey.get(&assets);
+        match custom_material::load_custom_material(&gpu, &assets, &url).await {
+            Err(err) => {
+                tracing::warn!("Failed to load custom material from url: {:?}", err);
+            }
+            Ok((shader, mat)) => {
+                async_run.run(move |world| {
+                    world
+                        .add_components(
+                            id,
+                            Entity::new()
+                                .with(
+                                    renderer_shader(),
+                                    cb(move |assets, config| {
+                                        custom_material::get_custom_shader(
+                                            assets,
+                                            config,
+                                            shader.clone(),
+                                        )
+                                    }),
+                                )
+                                .with(material(), mat.into()),
+                        )
+                        .ok();
+                });
+            }
+        }
+    });
+}
+
 pub fn gpu_world_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
     SystemGroup::new(
         "renderer/gpu_world_update",
@@ -208,7 +313,8 @@ pub fn gpu_world_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
                 gpu_components::gpu_primitives_lod(),
             )),
             Box::new(lod::gpu_world_system(gpu.clone())),
-            Box::new(skinning::gpu_world_systems(gpu)),
+            Box::new(skinning::gpu_world_systems(gpu.clone())),
+            Box::new(sprites::gpu_world_systems(gpu)),
         ],
     )
 }
@@ -387,6 +493,13 @@ pub fn get_globals_module(_assets: &AssetCache, shadow_cascades: u32) -> Arc<Sha
     Arc::new(
         ShaderModule::new("globals", include_file!("globals.wgsl"))
             .with_ident(ShaderIdent::constant("SHADOW_CASCADES", shadow_cascades))
+            .with_ident(ShaderIdent::constant(
+                "LIGHT_SHADOW_FACES",
+                LIGHT_SHADOW_FACES,
+            ))
+            .with_ident(ShaderIdent::constant("LIGHT_CLUSTERS_X", LIGHT_CLUSTERS.x))
+            .with_ident(ShaderIdent::constant("LIGHT_CLUSTERS_Y", LIGHT_CLUSTERS.y))
+            .with_ident(ShaderIdent::constant("LIGHT_CLUSTERS_Z", LIGHT_CLUSTERS.z))
             .with_binding_desc(globals_layout()),
     )
 }
@@ -439,6 +552,11 @@ pub trait Material: Debug + Sync + Send + as_any::AsAny {
     fn transparency_group(&self) -> Option<i32> {
         None
     }
+    /// How many meters transparent primitives with this material are moved towards the camera
+    /// when they are sorted
+    fn transparency_sort_bias(&self) -> Option<f32> {
+        None
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -0,0 +1,281 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use ambient_core::{
+    camera::{Camera, Projection},
+    transform::{get_world_position, get_world_rotation},
+};
+use ambient_ecs::{
+    generated::rendering::components::{
+        light_color, light_cone_angle, light_intensity, light_range, light_shadows, point_light,
+        spot_light,
+    },
+    query, Component, EntityId, World,
+};
+use glam::{uvec3, vec2, Mat4, UVec3, Vec3};
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+
+/// The number of lights which can have shadow maps at the same time
+pub const MAX_SHADOW_LIGHTS: u32 = 4;
+/// Each shadowed light is given 6 layers of the light shadow texture, one for every side of a cube
+pub const LIGHT_SHADOW_FACES: u32 = 6;
+pub const LIGHT_SHADOW_MAP_RESOLUTION: u32 = 512;
+
+/// The number of clusters the view is split into, in screen tiles and depth slices
+pub const LIGHT_CLUSTERS: UVec3 = uvec3(16, 9, 24);
+/// The depth slices are spread exponentially up to this distance; the last one extends to infinity
+const LIGHT_CLUSTERS_MAX_FAR: f32 = 1000.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LightKind {
+    Point = 0,
+    Spot = 1,
+}
+
+#[derive(Debug, Clone)]
+pub struct Light {
+    pub id: EntityId,
+    pub kind: LightKind,
+    pub position: Vec3,
+    pub direction: Vec3,
+    /// The color, scaled by the intensity
+    pub color: Vec3,
+    pub range: f32,
+    pub cone_angle: f32,
+    pub casts_shadows: bool,
+    /// The index of the shadow map of this light, if it got one this frame
+    pub shadow: Option<u32>,
+}
+
+impl Light {
+    fn from_world(world: &World, id: EntityId, kind: LightKind) -> Option<Self> {
+        let position = get_world_position(world, id).ok()?;
+        let rotation = get_world_rotation(world, id).unwrap_or_default();
+        let color = world.get(id, light_color()).unwrap_or(Vec3::ONE)
+            * world.get(id, light_intensity()).unwrap_or(1.);
+        Some(Self {
+            id,
+            kind,
+            position,
+            direction: rotation.mul_vec3(Vec3::Z),
+            color,
+            range: world.get(id, light_range()).unwrap_or(10.).max(0.01),
+            cone_angle: world
+                .get(id, light_cone_angle())
+                .unwrap_or(FRAC_PI_4)
+                .clamp(0.01, FRAC_PI_2 - 0.01),
+            casts_shadows: world.has_component(id, light_shadows()),
+            shadow: None,
+        })
+    }
+
+    /// The cameras rendering the shadow map of this light; one for a spot light, and one for each
+    /// side of a cube for a point light
+    pub fn shadow_cameras(&self) -> Vec<Camera> {
+        let camera = |direction: Vec3, fovy: f32| {
+            let up = if direction.z.abs() > 0.99 {
+                Vec3::Y
+            } else {
+                Vec3::Z
+            };
+            Camera {
+                projection: Projection::Perspective {
+                    fovy,
+                    aspect_ratio: 1.,
+                    near: (self.range * 0.001).max(0.01),
+                    far: self.range,
+                },
+                view: Mat4::look_at_lh(self.position, self.position + direction, up),
+                shadows_far: self.range,
+            }
+        };
+        match self.kind {
+            LightKind::Spot => vec![camera(self.direction, self.cone_angle * 2.)],
+            // The order of the faces must match `fetch_light_shadow` in globals.wgsl
+            LightKind::Point => [
+                Vec3::X,
+                Vec3::NEG_X,
+                Vec3::Y,
+                Vec3::NEG_Y,
+                Vec3::Z,
+                Vec3::NEG_Z,
+            ]
+            .into_iter()
+            .map(|direction| camera(direction, FRAC_PI_2))
+            .collect(),
+        }
+    }
+}
+
+/// The point and spot lights of a scene, collected once per frame
+#[derive(Debug, Clone, Default)]
+pub struct Lights {
+    pub lights: Vec<Light>,
+}
+
+impl Lights {
+    pub fn from_world(
+        world: &World,
+        scene: Component<()>,
+        camera_position: Vec3,
+        shadows: bool,
+    ) -> Self {
+        let points = query((scene, point_light()))
+            .excl(spot_light())
+            .iter(world, None)
+            .filter_map(|(id, _)| Light::from_world(world, id, LightKind::Point));
+        let spots = query((scene, spot_light()))
+            .iter(world, None)
+            .filter_map(|(id, _)| Light::from_world(world, id, LightKind::Spot));
+        let mut lights = points.chain(spots).collect_vec();
+
+        // The lights closest to the camera get the shadow maps
+        if shadows {
+            lights
+                .iter_mut()
+                .filter(|light| light.casts_shadows)
+                .sorted_by_key(|light| {
+                    OrderedFloat(light.position.distance_squared(camera_position))
+                })
+                .take(MAX_SHADOW_LIGHTS as usize)
+                .enumerate()
+                .for_each(|(i, light)| light.shadow = Some(i as u32));
+        }
+
+        Self { lights }
+    }
+
+    /// The lights which got a shadow map, with the index of it
+    pub fn shadowed(&self) -> impl Iterator<Item = (u32, &Light)> {
+        self.lights
+            .iter()
+            .filter_map(|light| Some((light.shadow?, light)))
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct LightData {
+    pub position: Vec3,
+    pub range: f32,
+    pub color: Vec3,
+    pub kind: u32,
+    pub direction: Vec3,
+    pub cos_cone: f32,
+    pub shadow: i32,
+    pub cos_inner_cone: f32,
+    _padding: [f32; 2],
+}
+
+impl From<&Light> for LightData {
+    fn from(light: &Light) -> Self {
+        Self {
+            position: light.position,
+            range: light.range,
+            color: light.color,
+            kind: light.kind as u32,
+            direction: light.direction,
+            cos_cone: light.cone_angle.cos(),
+            shadow: light.shadow.map(|x| x as i32).unwrap_or(-1),
+            // The edge of the cone is softened over its outer tenth
+            cos_inner_cone: (light.cone_angle * 0.9).cos(),
+            _padding: Default::default(),
+        }
+    }
+}
+
+pub(crate) struct LightClusters {
+    pub near: f32,
+    pub far: f32,
+    /// An `(offset, count)` pair for each cluster, followed by the light indices they point to
+    pub data: Vec<u32>,
+}
+
+impl LightClusters {
+    pub fn new(camera: &Camera, lights: &[Light]) -> Self {
+        let near = camera.projection.near().max(0.01);
+        let far = camera
+            .projection
+            .far()
+            .unwrap_or(LIGHT_CLUSTERS_MAX_FAR)
+            .clamp(near * 2., LIGHT_CLUSTERS_MAX_FAR);
+        let cluster_count = (LIGHT_CLUSTERS.x * LIGHT_CLUSTERS.y * LIGHT_CLUSTERS.z) as usize;
+
+        // Must match `get_light_cluster` in globals.wgsl
+        let slice = |depth: f32| {
+            ((depth.max(near) / near).ln() / (far / near).ln() * LIGHT_CLUSTERS.z as f32)
+                .floor()
+                .clamp(0., LIGHT_CLUSTERS.z as f32 - 1.) as u32
+        };
+
+        // Two view space points on the ray through every tile corner. Using two points rather
+        // than the origin makes this work for orthographic projections too
+        let inv_projection = camera.projection.matrix().inverse();
+        let ray = |x: f32, y: f32| {
+            let ndc = vec2(
+                x / LIGHT_CLUSTERS.x as f32 * 2. - 1.,
+                y / LIGHT_CLUSTERS.y as f32 * 2. - 1.,
+            );
+            (
+                inv_projection.project_point3(ndc.extend(1.)),
+                inv_projection.project_point3(ndc.extend(0.5)),
+            )
+        };
+
+        // The planes bounding each tile, pointing inwards
+        let tiles = (0..LIGHT_CLUSTERS.y)
+            .flat_map(|y| (0..LIGHT_CLUSTERS.x).map(move |x| (x as f32, y as f32)))
+            .map(|(x, y)| {
+                let corners = [
+                    ray(x, y),
+                    ray(x + 1., y),
+                    ray(x + 1., y + 1.),
+                    ray(x, y + 1.),
+                ];
+                let center = ray(x + 0.5, y + 0.5).1;
+                let mut planes = [(Vec3::ZERO, 0.); 4];
+                for (i, plane) in planes.iter_mut().enumerate() {
+                    let (a0, a1) = corners[i];
+                    let (b0, _) = corners[(i + 1) % 4];
+                    let mut normal = (a1 - a0).cross(b0 - a0).normalize_or_zero();
+                    if normal.dot(center - a0) < 0. {
+                        normal = -normal;
+                    }
+                    *plane = (normal, -normal.dot(a0));
+                }
+                planes
+            })
+            .collect_vec();
+
+        let mut clusters = vec![Vec::new(); cluster_count];
+        for (index, light) in lights.iter().enumerate() {
+            let center = camera.view.transform_point3(light.position);
+            if center.z + light.range < 0. {
+                continue;
+            }
+            let slices = slice(center.z - light.range)..=slice(center.z + light.range);
+            for (tile, planes) in tiles.iter().enumerate() {
+                if planes
+                    .iter()
+                    .any(|(normal, distance)| normal.dot(center) + distance < -light.range)
+                {
+                    continue;
+                }
+                for z in slices.clone() {
+                    let cluster = tile + (z * LIGHT_CLUSTERS.x * LIGHT_CLUSTERS.y) as usize;
+                    clusters[cluster].push(index as u32);
+                }
+            }
+        }
+
+        let mut data = vec![0; cluster_count * 2];
+        for (i, cluster) in clusters.iter().enumerate() {
+            data[i * 2] = data.len() as u32;
+            data[i * 2 + 1] = cluster.len() as u32;
+            data.extend_from_slice(cluster);
+        }
+
+        Self { near, far, data }
+    }
+}
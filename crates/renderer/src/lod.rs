@@ -1,7 +1,8 @@
 use ambient_core::{
     bounding::world_bounding_sphere,
     camera::{fovy, get_active_camera},
-    hierarchy::children,
+    delta_time,
+    hierarchy::{children, parent},
     main_scene,
     player::local_user_id,
     transform::translation,
@@ -9,15 +10,21 @@ use ambient_core::{
 use ambient_ecs::{components, query, ECSError, EntityId, Networked, Store, SystemGroup, World};
 use ambient_gpu_ecs::{
     gpu_components, ComponentToGpuSystem, GpuComponentFormat, GpuWorldSyncEvent,
+    MappedComponentToGpuSystem,
 };
+use ambient_native_std::shapes::Sphere;
 use bytemuck::{Pod, Zeroable};
-use glam::Vec3;
+use glam::{vec4, Vec3, Vec4};
 use serde::{Deserialize, Serialize};
 
 use crate::primitives;
 use ambient_gpu::gpu::Gpu;
 use std::sync::Arc;
 
+pub use ambient_ecs::generated::rendering::components::{
+    lod_crossfade_duration, lod_cull_screen_size, lod_group, lod_screen_sizes,
+};
+
 /// Maximum number of LOD levels
 pub const MAX_LOD_LEVELS: usize = 16;
 #[repr(transparent)]
@@ -43,6 +50,14 @@ impl LodCutoffs {
     }
 }
 
+/// A crossfade of a `lod_group` from a previous level to its current one
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LodCrossfade {
+    pub from: usize,
+    /// From 0 to 1
+    pub progress: f32,
+}
+
 components!("rendering", {
     @[Networked, Store]
     lod_cutoffs: LodCutoffs,
@@ -56,11 +71,28 @@ components!("rendering", {
     /// Stores the computed current lod-level as calculated from the lod cutoffs
     @[Networked, Store]
     gpu_lod: (),
+    lod_crossfade: LodCrossfade,
+    /// The fraction of the pixels of a primitive entity which are drawn while its `lod_group`
+    /// crossfades; the previous level draws the pixels the current one doesn't
+    lod_fade: Vec4,
 });
 gpu_components! {
     lod_cutoffs(), gpu_lod() => lod_cutoffs: GpuComponentFormat::Mat4,
     // [lod, 0, 0, 0]
     gpu_lod() => gpu_lod: GpuComponentFormat::Vec4,
+    // [screen size, 0, 0, 0]
+    lod_cull_screen_size() => lod_cull_screen_size: GpuComponentFormat::Vec4,
+    // [progress, fading in, 0, 0]
+    lod_fade() => lod_fade: GpuComponentFormat::Vec4,
+}
+
+/// The position of the main camera and the cotangent of half its vertical field of view, which
+/// turns a bounding sphere into its size on the screen
+fn main_camera_lod_params(world: &World) -> Option<(Vec3, f32)> {
+    let main_camera = get_active_camera(world, main_scene(), world.resource_opt(local_user_id()))?;
+    let camera_pos = world.get(main_camera, translation()).unwrap_or(Vec3::ZERO);
+    let main_camera_fov = world.get(main_camera, fovy()).ok()?;
+    Some((camera_pos, 1. / (main_camera_fov / 2.).tan()))
 }
 
 pub fn lod_system() -> SystemGroup {
@@ -69,17 +101,8 @@ pub fn lod_system() -> SystemGroup {
         vec![
             query((lod_cutoffs(), cpu_lod(), world_bounding_sphere())).to_system(
                 |q, world, qs, _| {
-                    if let Some(main_camera) =
-                        get_active_camera(world, main_scene(), world.resource_opt(local_user_id()))
+                    if let Some((camera_pos, main_camera_cot_fov_2)) = main_camera_lod_params(world)
                     {
-                        let camera_pos =
-                            world.get(main_camera, translation()).unwrap_or(Vec3::ZERO);
-                        let main_camera_fov = match world.get(main_camera, fovy()) {
-                            Ok(val) => val,
-                            Err(_) => return,
-                        };
-                        let main_camera_cot_fov_2 = 1. / (main_camera_fov / 2.).tan();
-
                         // let frame = world.resource(frame_index());
                         // let count = q.query.iter(world, None).count();
                         // let chunk_size = (count / 100).max(1);
@@ -126,19 +149,189 @@ pub fn lod_system() -> SystemGroup {
                     }
                 },
             ),
+            query((lod_group(), lod_screen_sizes())).to_system(|q, world, qs, _| {
+                let Some((camera_pos, cot_fov_2)) = main_camera_lod_params(world) else {
+                    return;
+                };
+                let dtime = *world.resource(delta_time());
+
+                let mut to_update = Vec::new();
+                for (id, (_, screen_sizes)) in q.iter(world, qs) {
+                    let Some(bounding_sphere) = lod_group_bounding_sphere(world, id) else {
+                        continue;
+                    };
+                    let dist = (camera_pos - bounding_sphere.center).length();
+                    let screen_size = bounding_sphere.radius * cot_fov_2 / dist;
+                    let level = screen_sizes
+                        .iter()
+                        .position(|x| screen_size >= *x)
+                        .unwrap_or(screen_sizes.len());
+                    to_update.push((id, level));
+                }
+                for (id, level) in to_update {
+                    update_lod_group(world, id, level, dtime).unwrap();
+                }
+            }),
+            // The levels of a group may be loaded after it picked one, so hide them if they're not it
+            query(primitives().changed()).to_system(|q, world, qs, _| {
+                let mut to_update = Vec::new();
+                for (id, _) in q.iter(world, qs) {
+                    let Some((group, level)) = get_lod_group_level(world, id) else {
+                        continue;
+                    };
+                    let Ok(current) = world.get(group, cpu_lod()) else {
+                        continue;
+                    };
+                    let visible = level == current
+                        || world
+                            .get(group, lod_crossfade())
+                            .map_or(false, |crossfade| crossfade.from == level);
+                    if world.get(id, cpu_lod_visible()).unwrap_or(true) != visible {
+                        to_update.push((id, visible));
+                    }
+                }
+                for (id, visible) in to_update {
+                    world.add_component(id, cpu_lod_visible(), visible).unwrap();
+                }
+            }),
         ],
     )
 }
 
+/// The bounding sphere of a `lod_group`, or the first one found in its most detailed level
+fn lod_group_bounding_sphere(world: &World, id: EntityId) -> Option<Sphere> {
+    fn find(world: &World, id: EntityId) -> Option<Sphere> {
+        if let Ok(sphere) = world.get(id, world_bounding_sphere()) {
+            return Some(sphere);
+        }
+        let children = world.get_ref(id, children()).ok()?;
+        children.iter().find_map(|&child| find(world, child))
+    }
+    if let Ok(sphere) = world.get(id, world_bounding_sphere()) {
+        return Some(sphere);
+    }
+    find(world, *world.get_ref(id, children()).ok()?.first()?)
+}
+
+/// The `lod_group` an entity is part of, and the level it's in
+fn get_lod_group_level(world: &World, id: EntityId) -> Option<(EntityId, usize)> {
+    let mut child = id;
+    while let Ok(group) = world.get(child, parent()) {
+        if world.has_component(group, lod_group()) {
+            let level = world
+                .get_ref(group, children())
+                .ok()?
+                .iter()
+                .position(|&x| x == child)?;
+            return Some((group, level));
+        }
+        child = group;
+    }
+    None
+}
+
+/// Switches a `lod_group` to `level`, crossfading from the previous one if it has a
+/// `lod_crossfade_duration`
+fn update_lod_group(
+    world: &mut World,
+    id: EntityId,
+    level: usize,
+    dtime: f32,
+) -> Result<(), ECSError> {
+    let current = world.get(id, cpu_lod()).ok();
+    let mut crossfade = world.get(id, lod_crossfade()).ok();
+    if current == Some(level) && crossfade.is_none() {
+        return Ok(());
+    }
+
+    let levels = world.get_ref(id, children()).cloned().unwrap_or_default();
+    let set_visible = |world: &mut World, level: usize, value: bool| match levels.get(level) {
+        Some(&level) => set_lod_visible_recursive(world, level, value),
+        None => Ok(()),
+    };
+    let set_fade = |world: &mut World, level: usize, value: Option<Vec4>| match levels.get(level) {
+        Some(&level) => set_lod_fade_recursive(world, level, value),
+        None => Ok(()),
+    };
+
+    let Some(current) = current else {
+        // The first time the group is seen, it shows its level right away
+        for i in 0..levels.len() {
+            set_visible(world, i, i == level)?;
+        }
+        return world.add_component(id, cpu_lod(), level);
+    };
+
+    let duration = world.get(id, lod_crossfade_duration()).unwrap_or_default();
+    if level != current {
+        // A crossfade which hasn't finished yet is cut short
+        if let Some(crossfade) = crossfade.take() {
+            set_visible(world, crossfade.from, false)?;
+            set_fade(world, crossfade.from, None)?;
+            set_fade(world, current, None)?;
+        }
+        world.set(id, cpu_lod(), level)?;
+        set_visible(world, level, true)?;
+        if duration > 0. {
+            crossfade = Some(LodCrossfade {
+                from: current,
+                progress: 0.,
+            });
+        } else {
+            set_visible(world, current, false)?;
+        }
+    }
+
+    match crossfade {
+        Some(mut crossfade) => {
+            crossfade.progress += if duration > 0. { dtime / duration } else { 1. };
+            if crossfade.progress >= 1. {
+                set_visible(world, crossfade.from, false)?;
+                set_fade(world, crossfade.from, None)?;
+                set_fade(world, level, None)?;
+                world.remove_component(id, lod_crossfade())
+            } else {
+                set_fade(
+                    world,
+                    crossfade.from,
+                    Some(vec4(crossfade.progress, 0., 0., 0.)),
+                )?;
+                set_fade(world, level, Some(vec4(crossfade.progress, 1., 0., 0.)))?;
+                if world.has_component(id, lod_crossfade()) {
+                    world.set(id, lod_crossfade(), crossfade).map(|_| ())
+                } else {
+                    world.add_component(id, lod_crossfade(), crossfade)
+                }
+            }
+        }
+        None => world.remove_component(id, lod_crossfade()),
+    }
+}
+
 pub fn gpu_world_system(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
     SystemGroup::new(
         "lod/gpu_world",
-        vec![Box::new(ComponentToGpuSystem::new(
-            gpu,
-            GpuComponentFormat::Mat4,
-            lod_cutoffs(),
-            gpu_components::lod_cutoffs(),
-        ))],
+        vec![
+            Box::new(ComponentToGpuSystem::new(
+                gpu.clone(),
+                GpuComponentFormat::Mat4,
+                lod_cutoffs(),
+                gpu_components::lod_cutoffs(),
+            )),
+            Box::new(MappedComponentToGpuSystem::new(
+                gpu.clone(),
+                GpuComponentFormat::Vec4,
+                lod_cull_screen_size(),
+                gpu_components::lod_cull_screen_size(),
+                Box::new(|_, _, &size| vec4(size, 0., 0., 0.)),
+            )),
+            Box::new(ComponentToGpuSystem::new(
+                gpu,
+                GpuComponentFormat::Vec4,
+                lod_fade(),
+                gpu_components::lod_fade(),
+            )),
+        ],
     )
 }
 
@@ -148,7 +341,11 @@ pub fn set_lod_visible_recursive(
     value: bool,
 ) -> Result<(), ECSError> {
     if world.has_component(id, primitives()) {
-        world.set(id, cpu_lod_visible(), value)?;
+        if world.has_component(id, cpu_lod_visible()) {
+            world.set(id, cpu_lod_visible(), value)?;
+        } else {
+            world.add_component(id, cpu_lod_visible(), value)?;
+        }
     }
     let cs = world
         .get_ref(id, children())
@@ -159,3 +356,27 @@ pub fn set_lod_visible_recursive(
     }
     Ok(())
 }
+
+fn set_lod_fade_recursive(
+    world: &mut World,
+    id: EntityId,
+    value: Option<Vec4>,
+) -> Result<(), ECSError> {
+    if world.has_component(id, primitives()) {
+        match value {
+            Some(value) if world.has_component(id, lod_fade()) => {
+                world.set(id, lod_fade(), value)?;
+            }
+            Some(value) => world.add_component(id, lod_fade(), value)?,
+            None => world.remove_component(id, lod_fade())?,
+        }
+    }
+    let cs = world
+        .get_ref(id, children())
+        .map(|cs| cs.clone())
+        .unwrap_or_default();
+    for c in cs {
+        set_lod_fade_recursive(world, c, value)?;
+    }
+    Ok(())
+}
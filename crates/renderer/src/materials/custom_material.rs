@@ -0,0 +1,346 @@
+//! Materials shipped by packages.
+//!
+//! A custom material is a JSON file describing a WGSL snippet and a typed parameter block:
+//!
+//! ```json
+//! {
+//!     "name": "Glow",
+//!     "shader": "glow.wgsl",
+//!     "params": [
+//!         { "name": "tint", "type": "Vec4", "value": [1.0, 0.2, 0.2, 1.0] },
+//!         { "name": "intensity", "type": "F32", "value": 4.0 }
+//!     ]
+//! }
+//! ```
+//!
+//! The parameter types are the primitive types of the package manifest. The snippet implements
+//! `get_material(in: MaterialInput) -> MaterialOutput` like the built-in materials, and reads its
+//! parameters from `material_params`, which is generated from the parameter block and compiled
+//! into the standard PBR pipeline with it.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::Write,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use ambient_ecs::PrimitiveComponentType;
+use ambient_gpu::{
+    gpu::Gpu,
+    shader_module::{BindGroupDesc, ShaderModule},
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::download_uncached_bytes,
+    friendly_id,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use wgpu::{util::DeviceExt, BindGroup};
+
+use super::super::{Material, MaterialShader, RendererShader, MATERIAL_BIND_GROUP};
+use crate::{RendererConfig, StandardShaderKey};
+
+fn get_material_layout() -> BindGroupDesc<'static> {
+    BindGroupDesc {
+        entries: vec![wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }],
+        label: MATERIAL_BIND_GROUP.into(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomMaterialDesc {
+    pub name: Option<String>,
+    /// The WGSL snippet, relative to the material
+    pub shader: String,
+    #[serde(default)]
+    pub params: Vec<CustomMaterialParam>,
+    pub transparent: Option<bool>,
+    pub double_sided: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomMaterialParam {
+    pub name: String,
+    /// A primitive type of the package manifest, e.g. `F32` or `Vec4`
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub value: CustomMaterialValue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CustomMaterialValue {
+    Bool(bool),
+    Scalar(f64),
+    Vector(Vec<f64>),
+}
+
+/// How a parameter type is laid out in a WGSL uniform buffer
+#[derive(Debug, Clone, Copy)]
+struct ParamLayout {
+    wgsl: &'static str,
+    scalar: Scalar,
+    components: usize,
+    align: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scalar {
+    F32,
+    I32,
+    U32,
+}
+
+impl ParamLayout {
+    fn of(ty: PrimitiveComponentType) -> Option<Self> {
+        use PrimitiveComponentType as T;
+        let (wgsl, scalar, components, align) = match ty {
+            // Booleans are not host-shareable in WGSL
+            T::Bool => ("u32", Scalar::U32, 1, 4),
+            T::F32 => ("f32", Scalar::F32, 1, 4),
+            T::I32 => ("i32", Scalar::I32, 1, 4),
+            T::U32 => ("u32", Scalar::U32, 1, 4),
+            T::Vec2 => ("vec2<f32>", Scalar::F32, 2, 8),
+            T::Vec3 => ("vec3<f32>", Scalar::F32, 3, 16),
+            T::Vec4 | T::Quat => ("vec4<f32>", Scalar::F32, 4, 16),
+            T::IVec2 => ("vec2<i32>", Scalar::I32, 2, 8),
+            T::IVec3 => ("vec3<i32>", Scalar::I32, 3, 16),
+            T::IVec4 => ("vec4<i32>", Scalar::I32, 4, 16),
+            T::UVec2 => ("vec2<u32>", Scalar::U32, 2, 8),
+            T::UVec3 => ("vec3<u32>", Scalar::U32, 3, 16),
+            T::UVec4 => ("vec4<u32>", Scalar::U32, 4, 16),
+            T::Mat4 => ("mat4x4<f32>", Scalar::F32, 16, 16),
+            _ => return None,
+        };
+        Some(Self {
+            wgsl,
+            scalar,
+            components,
+            align,
+        })
+    }
+}
+
+fn align_to(offset: usize, align: usize) -> usize {
+    (offset + align - 1) / align * align
+}
+
+/// The parameter block of a custom material: its WGSL declaration, and its contents
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomMaterialParams {
+    pub wgsl: String,
+    pub data: Vec<u8>,
+}
+
+impl CustomMaterialParams {
+    /// Validates `params` against their declared types, and lays them out for a uniform buffer
+    pub fn new(params: &[CustomMaterialParam]) -> anyhow::Result<Self> {
+        let mut fields = String::new();
+        let mut data = Vec::new();
+        for param in params {
+            anyhow::ensure!(
+                !param.name.is_empty()
+                    && param
+                        .name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !param.name.starts_with(|c: char| c.is_ascii_digit()),
+                "{:?} is not a valid parameter name",
+                param.name
+            );
+            let ty = PrimitiveComponentType::try_from(param.type_.as_str())
+                .map_err(|err| anyhow::anyhow!("{}: {err}: {:?}", param.name, param.type_))?;
+            let layout = ParamLayout::of(ty).with_context(|| {
+                format!(
+                    "{}: {:?} can not be used in a material parameter block",
+                    param.name, param.type_
+                )
+            })?;
+
+            let values = match (&param.value, ty) {
+                (CustomMaterialValue::Bool(value), PrimitiveComponentType::Bool) => {
+                    vec![if *value { 1. } else { 0. }]
+                }
+                (CustomMaterialValue::Scalar(value), _) if layout.components == 1 => vec![*value],
+                (CustomMaterialValue::Vector(values), _) if values.len() == layout.components => {
+                    values.clone()
+                }
+                (value, _) => {
+                    anyhow::bail!("{}: {value:?} is not a valid {}", param.name, param.type_)
+                }
+            };
+
+            data.resize(align_to(data.len(), layout.align), 0);
+            for value in values {
+                let bytes = match layout.scalar {
+                    Scalar::F32 => (value as f32).to_le_bytes(),
+                    Scalar::I32 if value.fract() == 0. => (value as i32).to_le_bytes(),
+                    Scalar::U32 if value.fract() == 0. && value >= 0. => {
+                        (value as u32).to_le_bytes()
+                    }
+                    _ => anyhow::bail!("{}: {value} is not a valid {}", param.name, param.type_),
+                };
+                data.extend_from_slice(&bytes);
+            }
+            writeln!(fields, "    {}: {},", param.name, layout.wgsl).unwrap();
+        }
+
+        // WGSL structs can't be empty
+        if params.is_empty() {
+            fields.push_str("    _padding: u32,\n");
+        }
+        // Uniform buffers are sized in multiples of 16 bytes
+        data.resize(align_to(data.len().max(1), 16), 0);
+
+        Ok(Self {
+            wgsl: format!(
+                "struct CustomMaterialParams {{\n{fields}}};\n\n\
+                 @group(MATERIAL_BIND_GROUP)\n\
+                 @binding(0)\n\
+                 var<uniform> material_params: CustomMaterialParams;\n"
+            ),
+            data,
+        })
+    }
+}
+
+/// Downloads the material at `url` and its shader, bypassing the caches so that they can be
+/// reloaded on the fly
+pub async fn load_custom_material(
+    gpu: &Gpu,
+    assets: &AssetCache,
+    url: &AbsAssetUrl,
+) -> anyhow::Result<(Arc<MaterialShader>, Arc<CustomMaterial>)> {
+    let desc = download_uncached_bytes(assets, url.clone()).await?;
+    let desc: CustomMaterialDesc =
+        serde_json::from_slice(&desc).with_context(|| format!("Invalid material {url}"))?;
+    let params = CustomMaterialParams::new(&desc.params)
+        .with_context(|| format!("Invalid parameters in the material {url}"))?;
+
+    let shader_url = url.resolve(&desc.shader)?;
+    let snippet = download_uncached_bytes(assets, shader_url.clone()).await?;
+    let snippet = String::from_utf8(snippet)
+        .with_context(|| format!("The shader {shader_url} is not valid UTF-8"))?;
+
+    let source = format!("{}\n{snippet}", params.wgsl);
+    // Identifies the shader by its source, so that a reloaded shader gets a new pipeline
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    let shader = Arc::new(MaterialShader {
+        id: format!("custom_material_shader_{url}_{:x}", hasher.finish()),
+        shader: Arc::new(
+            ShaderModule::new(format!("CustomMaterial({url})"), source)
+                .with_binding_desc(get_material_layout()),
+        ),
+    });
+
+    let material = CustomMaterial::new(
+        gpu,
+        assets,
+        desc.name.unwrap_or_else(|| url.to_string()),
+        params,
+        desc.transparent,
+        desc.double_sided,
+    );
+    Ok((shader, Arc::new(material)))
+}
+
+pub fn get_custom_shader(
+    assets: &AssetCache,
+    config: &RendererConfig,
+    material_shader: Arc<MaterialShader>,
+) -> Arc<RendererShader> {
+    StandardShaderKey {
+        material_shader,
+        lit: true,
+        shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
+    }
+    .get(assets)
+}
+
+pub struct CustomMaterial {
+    id: String,
+    name: String,
+    transparent: Option<bool>,
+    double_sided: Option<bool>,
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl CustomMaterial {
+    pub fn new(
+        gpu: &Gpu,
+        assets: &AssetCache,
+        name: String,
+        params: CustomMaterialParams,
+        transparent: Option<bool>,
+        double_sided: Option<bool>,
+    ) -> Self {
+        let layout = get_material_layout().get(assets);
+
+        let buffer = gpu
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("CustomMaterial.buffer"),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                contents: &params.data,
+            });
+
+        Self {
+            id: friendly_id(),
+            bind_group: gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(buffer.as_entire_buffer_binding()),
+                }],
+                label: Some("CustomMaterial.bind_group"),
+            }),
+            buffer,
+            name,
+            transparent,
+            double_sided,
+        }
+    }
+}
+
+impl std::fmt::Debug for CustomMaterial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomMaterial")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl Material for CustomMaterial {
+    fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn transparent(&self) -> Option<bool> {
+        self.transparent
+    }
+    fn double_sided(&self) -> Option<bool> {
+        self.double_sided
+    }
+}
@@ -56,6 +56,8 @@ impl SyncAssetKey<Arc<RendererShader>> for FlatShaderKey {
             material_shader: FlatMaterialShaderKey.get(&assets),
             lit: self.lit,
             shadow_cascades: self.shadow_cascades,
+            wind: false,
+            imposter: false,
         }
         .get(&assets)
     }
@@ -66,6 +68,8 @@ pub fn get_flat_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<Rend
         material_shader: FlatMaterialShaderKey.get(assets),
         lit: true,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
     }
     .get(assets)
 }
@@ -75,6 +79,8 @@ pub fn get_flat_shader_unlit(assets: &AssetCache, config: &RendererConfig) -> Ar
         material_shader: FlatMaterialShaderKey.get(assets),
         lit: false,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
     }
     .get(assets)
 }
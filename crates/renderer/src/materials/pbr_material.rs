@@ -1,26 +1,31 @@
 use std::sync::Arc;
 
 use ambient_gpu::{
+    basis::is_basis_url,
     gpu::{Gpu, GpuKey},
     sampler::SamplerKey,
     shader_module::{BindGroupDesc, ShaderModule},
     std_assets::{DefaultNormalMapViewKey, PixelTextureViewKey},
     texture::{Texture, TextureView},
-    texture_loaders::{SplitTextureFromUrl, TextureFromUrl},
+    texture_loaders::{Rgba8ImageFromUrl, Rgba8ImageKey, SplitImageFromUrl},
 };
 use ambient_native_std::{
     asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
     asset_url::{AbsAssetUrl, AssetUrl},
+    cb,
     download_asset::{AssetError, JsonFromUrl},
     friendly_id, include_file,
 };
 use async_trait::async_trait;
-use glam::{uvec4, Vec4};
+use glam::{uvec4, vec4, Vec2, Vec3, Vec4};
 use serde::{Deserialize, Serialize};
 use wgpu::{util::DeviceExt, BindGroup};
 
 use super::super::{Material, MaterialShader, RendererShader, MATERIAL_BIND_GROUP};
-use crate::{RendererConfig, StandardShaderKey};
+use crate::{
+    texture_streaming::{StreamedSource, StreamedTextures},
+    RendererConfig, RendererShaderProducer, StandardShaderKey,
+};
 
 fn get_material_layout() -> BindGroupDesc<'static> {
     BindGroupDesc {
@@ -28,7 +33,8 @@ fn get_material_layout() -> BindGroupDesc<'static> {
         entries: vec![
             wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::FRAGMENT,
+                // The vertex shader reads the wind parameters
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
@@ -95,6 +101,35 @@ pub fn get_pbr_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<Rende
         material_shader: PbrMaterialShaderKey.get(assets),
         lit: true,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
+    }
+    .get(assets)
+}
+
+/// The PBR shader of materials which sway with the wind, see [PbrMaterialParams::has_wind]
+pub fn get_pbr_wind_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<RendererShader> {
+    StandardShaderKey {
+        material_shader: PbrMaterialShaderKey.get(assets),
+        lit: true,
+        shadow_cascades: config.shadow_cascades,
+        wind: true,
+        imposter: false,
+    }
+    .get(assets)
+}
+
+/// The PBR shader of the materials of octahedral imposters, see [PbrMaterialParams::has_imposter]
+pub fn get_pbr_imposter_shader(
+    assets: &AssetCache,
+    config: &RendererConfig,
+) -> Arc<RendererShader> {
+    StandardShaderKey {
+        material_shader: PbrMaterialShaderKey.get(assets),
+        lit: true,
+        shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: true,
     }
     .get(assets)
 }
@@ -104,6 +139,8 @@ pub fn get_pbr_shader_unlit(assets: &AssetCache, config: &RendererConfig) -> Arc
         material_shader: PbrMaterialShaderKey.get(assets),
         lit: false,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
     }
     .get(assets)
 }
@@ -116,7 +153,38 @@ pub struct PbrMaterialParams {
     pub alpha_cutoff: f32,
     pub metallic_factor: f32,
     pub roughness_factor: f32,
-    pub _padding: u32,
+    /// How strongly the material reflects its surroundings, scaled down as it gets rougher
+    pub reflectivity: f32,
+    /// How far the top of the mesh sways, in meters per m/s of wind
+    pub wind_sway: f32,
+    /// How far the vertices of the mesh flutter, in meters per m/s of wind
+    pub wind_flutter: f32,
+    /// The height above the origin of the mesh at which it bends fully with the wind
+    pub wind_height: f32,
+    /// The number of views along each side of the atlas of an octahedral imposter, or 0
+    pub imposter_frames: f32,
+    /// How strongly the clear coat on top of the material reflects light
+    pub clearcoat: f32,
+    pub clearcoat_roughness: f32,
+    /// How much light passes through the material
+    pub transmission: f32,
+    pub transmission_padding: f32,
+    /// The rows of the affine transform of the texture coordinates, see [TexcoordTransform]
+    pub texcoord_transform: [Vec4; 2],
+    /// The bounding sphere of the meshes baked into an octahedral imposter, in mesh space
+    pub imposter_sphere: Vec4,
+}
+impl PbrMaterialParams {
+    /// Whether the material sways with the wind, and needs to be drawn with
+    /// [get_pbr_wind_shader]
+    pub fn has_wind(&self) -> bool {
+        self.wind_sway > 0. || self.wind_flutter > 0.
+    }
+    /// Whether the material is the one of an octahedral imposter, and needs to be drawn with
+    /// [get_pbr_imposter_shader]
+    pub fn has_imposter(&self) -> bool {
+        self.imposter_frames > 0.
+    }
 }
 
 impl Default for PbrMaterialParams {
@@ -127,10 +195,58 @@ impl Default for PbrMaterialParams {
             alpha_cutoff: 0.5,
             metallic_factor: 1.0,
             roughness_factor: 1.0,
-            _padding: Default::default(),
+            reflectivity: 1.0,
+            wind_sway: 0.0,
+            wind_flutter: 0.0,
+            wind_height: 1.0,
+            imposter_frames: 0.0,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.0,
+            transmission: 0.0,
+            transmission_padding: 0.0,
+            texcoord_transform: TexcoordTransform::default().rows(),
+            imposter_sphere: Vec4::ZERO,
+        }
+    }
+}
+
+/// Offsets, rotates and scales the texture coordinates of a material, like the
+/// `KHR_texture_transform` glTF extension
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TexcoordTransform {
+    pub offset: Vec2,
+    /// Counter-clockwise, in radians
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+impl TexcoordTransform {
+    /// The rows of the 2x3 matrix which scales, then rotates, then offsets the texture coordinates
+    pub fn rows(&self) -> [Vec4; 2] {
+        let (sin, cos) = self.rotation.sin_cos();
+        [
+            vec4(cos * self.scale.x, sin * self.scale.y, self.offset.x, 0.),
+            vec4(-sin * self.scale.x, cos * self.scale.y, self.offset.y, 0.),
+        ]
+    }
+}
+impl Default for TexcoordTransform {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            rotation: 0.,
+            scale: Vec2::ONE,
         }
     }
 }
+/// The octahedral imposter a material is drawn as, see [crate::imposter]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ImposterParams {
+    /// The number of views along each side of the grid of views in the textures
+    pub frames: u32,
+    /// The center of the bounding sphere of the baked meshes, in mesh space
+    pub center: Vec3,
+    pub radius: f32,
+}
 #[derive(Clone, Debug)]
 pub struct PbrMaterialConfig {
     pub source: String,
@@ -144,6 +260,8 @@ pub struct PbrMaterialConfig {
     pub transparent: Option<bool>,
     pub double_sided: Option<bool>,
     pub depth_write_enabled: Option<bool>,
+    pub transparency_group: Option<i32>,
+    pub transparency_sort_bias: Option<f32>,
 }
 
 pub struct PbrMaterial {
@@ -151,6 +269,8 @@ pub struct PbrMaterial {
     pub config: PbrMaterialConfig,
     buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
+    streamed: Option<Arc<StreamedTextures>>,
+    resident_mip: u32,
 }
 
 impl PbrMaterial {
@@ -197,8 +317,51 @@ impl PbrMaterial {
             }),
             buffer,
             config,
+            streamed: None,
+            resident_mip: 0,
+        }
+    }
+    /// A material whose textures are the mip `resident_mip` of `streamed`, which the texture
+    /// streaming swaps for more or less detailed ones as needed
+    pub fn new_streamed(
+        gpu: &Gpu,
+        assets: &AssetCache,
+        config: PbrMaterialConfig,
+        streamed: Arc<StreamedTextures>,
+        resident_mip: u32,
+    ) -> Self {
+        Self {
+            streamed: Some(streamed),
+            resident_mip,
+            ..Self::new(gpu, assets, config)
+        }
+    }
+    /// A copy of this streamed material with other mips of its textures, in the order of
+    /// [StreamedTextures::images]
+    pub fn with_mip(
+        &self,
+        gpu: &Gpu,
+        assets: &AssetCache,
+        views: [Option<Arc<TextureView>>; 3],
+        resident_mip: u32,
+    ) -> Self {
+        let mut config = self.config.clone();
+        let [base_color, normalmap, metallic_roughness] = views;
+        config.base_color = base_color.unwrap_or(config.base_color);
+        config.normalmap = normalmap.unwrap_or(config.normalmap);
+        config.metallic_roughness = metallic_roughness.unwrap_or(config.metallic_roughness);
+        Self {
+            streamed: self.streamed.clone(),
+            resident_mip,
+            ..Self::new(gpu, assets, config)
         }
     }
+    pub fn streamed(&self) -> Option<&Arc<StreamedTextures>> {
+        self.streamed.as_ref()
+    }
+    pub fn resident_mip(&self) -> u32 {
+        self.resident_mip
+    }
     pub fn base_color_from_file(gpu: &Gpu, assets: &AssetCache, url: &str) -> Self {
         let texture = Arc::new(
             Arc::new(Texture::from_file(
@@ -222,6 +385,8 @@ impl PbrMaterial {
                 transparent: None,
                 double_sided: None,
                 depth_write_enabled: None,
+                transparency_group: None,
+                transparency_sort_bias: None,
             },
         )
     }
@@ -229,6 +394,16 @@ impl PbrMaterial {
         gpu.queue
             .write_buffer(&self.buffer, 0, bytemuck::cast_slice(&[self.config.params]));
     }
+    /// The shader this material is drawn with
+    pub fn shader(&self) -> RendererShaderProducer {
+        if self.config.params.has_imposter() {
+            cb(get_pbr_imposter_shader)
+        } else if self.config.params.has_wind() {
+            cb(get_pbr_wind_shader)
+        } else {
+            cb(get_pbr_shader)
+        }
+    }
     pub fn gpu_size(&self) -> u64 {
         self.config.base_color.texture.size_in_bytes
             + self.config.normalmap.texture.size_in_bytes
@@ -273,6 +448,12 @@ impl Material for PbrMaterial {
     fn depth_write_enabled(&self) -> Option<bool> {
         self.config.depth_write_enabled
     }
+    fn transparency_group(&self) -> Option<i32> {
+        self.config.transparency_group
+    }
+    fn transparency_sort_bias(&self) -> Option<f32> {
+        self.config.transparency_sort_bias
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -320,6 +501,32 @@ pub struct PbrMaterialDesc {
     pub double_sided: Option<bool>,
     pub metallic_factor: f32,
     pub roughness_factor: f32,
+    /// How strongly the material reflects its surroundings, from 0 to 1. Defaults to 1, which
+    /// leaves it to the roughness
+    pub reflectivity: Option<f32>,
+    /// How far the top of the mesh sways with the wind, in meters per m/s. Defaults to 0
+    pub wind_sway: Option<f32>,
+    /// How far the vertices of the mesh flutter with the wind, in meters per m/s. Defaults to 0
+    pub wind_flutter: Option<f32>,
+    /// The height above its origin at which the mesh bends fully with the wind. Defaults to 1
+    pub wind_height: Option<f32>,
+    /// When transparent primitives with this material are drawn; see `transparency_group`
+    pub transparency_group: Option<i32>,
+    /// How many meters transparent primitives with this material are moved towards the camera
+    /// when they are sorted; see `transparency_sort_bias`
+    pub transparency_sort_bias: Option<f32>,
+    /// How strongly a clear coat on top of the material, like varnish, reflects light, from 0 to
+    /// 1. Defaults to 0
+    pub clearcoat: Option<f32>,
+    /// The roughness of the clear coat. Defaults to 0
+    pub clearcoat_roughness: Option<f32>,
+    /// How much light passes through the material, from 0 to 1, like glass. Materials with
+    /// transmission should be transparent. Defaults to 0
+    pub transmission: Option<f32>,
+    /// The transform of the texture coordinates of all the textures. Defaults to none
+    pub texcoord_transform: Option<TexcoordTransform>,
+    /// Draws the material as an octahedral imposter, whose textures hold its views. Defaults to none
+    pub imposter: Option<ImposterParams>,
     pub sampler: Option<SamplerKey>,
 }
 
@@ -340,6 +547,17 @@ impl Default for PbrMaterialDesc {
             sampler: None,
             metallic_factor: 1.0,
             roughness_factor: 1.0,
+            reflectivity: None,
+            wind_sway: None,
+            wind_flutter: None,
+            wind_height: None,
+            transparency_group: None,
+            transparency_sort_bias: None,
+            clearcoat: None,
+            clearcoat_roughness: None,
+            transmission: None,
+            texcoord_transform: None,
+            imposter: None,
         }
     }
 }
@@ -379,6 +597,17 @@ impl PbrMaterialDesc {
             sampler: self.sampler,
             metallic_factor: self.metallic_factor,
             roughness_factor: self.roughness_factor,
+            reflectivity: self.reflectivity,
+            wind_sway: self.wind_sway,
+            wind_flutter: self.wind_flutter,
+            wind_height: self.wind_height,
+            transparency_group: self.transparency_group,
+            transparency_sort_bias: self.transparency_sort_bias,
+            clearcoat: self.clearcoat,
+            clearcoat_roughness: self.clearcoat_roughness,
+            transmission: self.transmission,
+            texcoord_transform: self.texcoord_transform,
+            imposter: self.imposter,
         })
     }
 
@@ -412,6 +641,17 @@ impl PbrMaterialDesc {
             sampler: self.sampler,
             metallic_factor: self.metallic_factor,
             roughness_factor: self.roughness_factor,
+            reflectivity: self.reflectivity,
+            wind_sway: self.wind_sway,
+            wind_flutter: self.wind_flutter,
+            wind_height: self.wind_height,
+            transparency_group: self.transparency_group,
+            transparency_sort_bias: self.transparency_sort_bias,
+            clearcoat: self.clearcoat,
+            clearcoat_roughness: self.clearcoat_roughness,
+            transmission: self.transmission,
+            texcoord_transform: self.texcoord_transform,
+            imposter: self.imposter,
         }
     }
 }
@@ -419,59 +659,43 @@ impl PbrMaterialDesc {
 #[async_trait]
 impl AsyncAssetKey<Result<Arc<PbrMaterial>, AssetError>> for PbrMaterialDesc {
     async fn load(self, assets: AssetCache) -> Result<Arc<PbrMaterial>, AssetError> {
-        let color = if let (Some(opacity), Some(albedo)) = (&self.opacity, &self.base_color) {
-            Some(
-                SplitTextureFromUrl {
-                    color: albedo.clone().unwrap_abs(),
-                    alpha: opacity.clone().unwrap_abs(),
-                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                }
-                .get(&assets)
-                .await?,
-            )
-        } else if let Some(albedo) = &self.base_color {
-            Some(
-                TextureFromUrl {
-                    url: albedo.clone().unwrap_abs(),
-                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        let image = |url: &Option<AssetUrl>| {
+            url.as_ref().map(|url| {
+                let url = url.clone().unwrap_abs();
+                if is_basis_url(&url) {
+                    StreamedSource::Basis(url)
+                } else {
+                    StreamedSource::Image(Arc::new(Rgba8ImageFromUrl { url }) as Rgba8ImageKey)
                 }
-                .get(&assets)
-                .await?,
-            )
-        } else {
-            None
-        };
-        let color_view = match color {
-            Some(color) => Arc::new(color.create_view(&wgpu::TextureViewDescriptor::default())),
-            None => PixelTextureViewKey::white().get(&assets),
+            })
         };
-        let normalmap = if let Some(normalmap) = &self.normalmap {
-            Arc::new(
-                TextureFromUrl {
-                    url: normalmap.clone().unwrap_abs(),
-                    format: wgpu::TextureFormat::Rgba8Unorm,
-                }
-                .get(&assets)
-                .await?
-                .create_view(&Default::default()),
-            )
+        let color = if let (Some(opacity), Some(albedo)) = (&self.opacity, &self.base_color) {
+            Some(StreamedSource::Image(Arc::new(SplitImageFromUrl {
+                color: albedo.clone().unwrap_abs(),
+                alpha: opacity.clone().unwrap_abs(),
+            })))
         } else {
-            DefaultNormalMapViewKey.get(&assets)
+            image(&self.base_color)
         };
 
-        let metallic_roughness = if let Some(metallic_roughness) = self.metallic_roughness {
-            Arc::new(
-                TextureFromUrl {
-                    url: metallic_roughness.clone().unwrap_abs(),
-                    format: wgpu::TextureFormat::Rgba8Unorm,
-                }
-                .get(&assets)
-                .await?
-                .create_view(&Default::default()),
-            )
-        } else {
-            default_metallic_roughness(&assets)
-        };
+        // The textures start at a low resolution, and get more detailed once they are seen
+        // up close
+        let streamed = StreamedTextures::load(
+            &assets,
+            [
+                color.map(|image| (image, wgpu::TextureFormat::Rgba8UnormSrgb)),
+                image(&self.normalmap).map(|image| (image, wgpu::TextureFormat::Rgba8Unorm)),
+                image(&self.metallic_roughness)
+                    .map(|image| (image, wgpu::TextureFormat::Rgba8Unorm)),
+            ],
+        )
+        .await?;
+        let [color_view, normalmap, metallic_roughness] =
+            streamed.load_views(&assets, streamed.base_mip).await?;
+        let color_view = color_view.unwrap_or_else(|| PixelTextureViewKey::white().get(&assets));
+        let normalmap = normalmap.unwrap_or_else(|| DefaultNormalMapViewKey.get(&assets));
+        let metallic_roughness =
+            metallic_roughness.unwrap_or_else(|| default_metallic_roughness(&assets));
 
         let sampler = if let Some(sampler) = self.sampler {
             sampler.get(&assets)
@@ -485,7 +709,19 @@ impl AsyncAssetKey<Result<Arc<PbrMaterial>, AssetError>> for PbrMaterialDesc {
             alpha_cutoff: self.alpha_cutoff.unwrap_or(0.9),
             metallic_factor: self.metallic_factor,
             roughness_factor: self.roughness_factor,
-            _padding: Default::default(),
+            reflectivity: self.reflectivity.unwrap_or(1.0),
+            wind_sway: self.wind_sway.unwrap_or(0.0),
+            wind_flutter: self.wind_flutter.unwrap_or(0.0),
+            wind_height: self.wind_height.unwrap_or(1.0),
+            imposter_frames: self.imposter.map_or(0.0, |imposter| imposter.frames as f32),
+            clearcoat: self.clearcoat.unwrap_or(0.0),
+            clearcoat_roughness: self.clearcoat_roughness.unwrap_or(0.0),
+            transmission: self.transmission.unwrap_or(0.0),
+            transmission_padding: 0.0,
+            texcoord_transform: self.texcoord_transform.unwrap_or_default().rows(),
+            imposter_sphere: self.imposter.map_or(Vec4::ZERO, |imposter| {
+                imposter.center.extend(imposter.radius)
+            }),
         };
 
         let name = self
@@ -493,21 +729,25 @@ impl AsyncAssetKey<Result<Arc<PbrMaterial>, AssetError>> for PbrMaterialDesc {
             .or(self.base_color.map(|x| x.to_string()))
             .unwrap_or_default();
         let gpu = GpuKey.get(&assets);
-        Ok(Arc::new(PbrMaterial::new(
-            &gpu,
-            &assets,
-            PbrMaterialConfig {
-                source: self.source.unwrap_or_default(),
-                name,
-                params,
-                base_color: color_view.clone(),
-                normalmap,
-                metallic_roughness,
-                sampler,
-                transparent: self.transparent,
-                double_sided: self.double_sided,
-                depth_write_enabled: None,
-            },
-        )))
+        let config = PbrMaterialConfig {
+            source: self.source.unwrap_or_default(),
+            name,
+            params,
+            base_color: color_view.clone(),
+            normalmap,
+            metallic_roughness,
+            sampler,
+            transparent: self.transparent,
+            double_sided: self.double_sided,
+            depth_write_enabled: None,
+            transparency_group: self.transparency_group,
+            transparency_sort_bias: self.transparency_sort_bias,
+        };
+        Ok(Arc::new(if streamed.size > 0 {
+            let base_mip = streamed.base_mip;
+            PbrMaterial::new_streamed(&gpu, &assets, config, Arc::new(streamed), base_mip)
+        } else {
+            PbrMaterial::new(&gpu, &assets, config)
+        }))
     }
 }
@@ -4,6 +4,17 @@ struct PbrMaterialParams {
     alpha_cutoff: f32,
     metallic: f32,
     roughness: f32,
+    reflectivity: f32,
+    wind_sway: f32,
+    wind_flutter: f32,
+    wind_height: f32,
+    imposter_frames: f32,
+    clearcoat: f32,
+    clearcoat_roughness: f32,
+    transmission: f32,
+    transmission_padding: f32,
+    texcoord_transform: array<vec4<f32>, 2>,
+    imposter_sphere: vec4<f32>,
 };
 
 @group(MATERIAL_BIND_GROUP)
@@ -26,22 +37,40 @@ var normal_texture: texture_2d<f32>;
 @binding(4)
 var metallic_roughness: texture_2d<f32>;
 
+fn get_material_wind() -> vec4<f32> {
+    return vec4<f32>(pbr_params.wind_sway, pbr_params.wind_flutter, pbr_params.wind_height, 0.);
+}
+
+fn get_material_imposter() -> vec4<f32> {
+    return vec4<f32>(pbr_params.imposter_frames, 0., 0., 0.);
+}
+
+fn get_material_imposter_sphere() -> vec4<f32> {
+    return pbr_params.imposter_sphere;
+}
+
 fn get_material(in: MaterialInput) -> MaterialOutput {
     var out: MaterialOutput;
-    let base_color_texture_sample = textureSample(base_color_texture, base_color_sampler, in.texcoord);
-    let mr = textureSample(metallic_roughness, base_color_sampler, in.texcoord);
+    let uv = vec3<f32>(in.texcoord, 1.);
+    let texcoord = vec2<f32>(dot(pbr_params.texcoord_transform[0].xyz, uv), dot(pbr_params.texcoord_transform[1].xyz, uv));
+    let base_color_texture_sample = textureSample(base_color_texture, base_color_sampler, texcoord);
+    let mr = textureSample(metallic_roughness, base_color_sampler, texcoord);
     let color = base_color_texture_sample * pbr_params.base_color_factor * get_entity_color_or(in.entity_loc, vec4<f32>(1., 1., 1., 1.));
-    out.opacity = color.a;
+    // The light passing through the material shows what's behind it, like blending does
+    out.opacity = color.a * (1. - pbr_params.transmission * (1. - mr.r * pbr_params.metallic));
 
     out.metallic = mr.r * pbr_params.metallic;
     out.roughness = max(mr.g * pbr_params.roughness, 0.1);
+    out.reflectivity = pbr_params.reflectivity;
+    out.clearcoat = pbr_params.clearcoat;
+    out.clearcoat_roughness = max(pbr_params.clearcoat_roughness, 0.05);
 
     out.alpha_cutoff = pbr_params.alpha_cutoff;
     out.base_color = color.rgb;
     out.emissive_factor = pbr_params.emissive_factor.rgb;
     out.shading = 1.;
 
-    let normal = textureSample(normal_texture, base_color_sampler, in.texcoord).xyz * 2. - 1.;
+    let normal = textureSample(normal_texture, base_color_sampler, texcoord).xyz * 2. - 1.;
     out.normal = in.normal_matrix * normal;
     return out;
 }
@@ -0,0 +1,213 @@
+//! Order-independent transparency, enabled with the `order_independent_transparency` render
+//! setting.
+//!
+//! Instead of being sorted and blended over each other, the transparent primitives are drawn
+//! twice: once adding up their colors weighted by their opacity, and once multiplying together
+//! how much of the background they let through. Compositing both gives the same image whatever
+//! the order they were drawn in, so intersecting and layered transparent objects don't pop as the
+//! camera moves. Where several layers overlap, their colors are averaged rather than stacked,
+//! which is a close approximation as long as they are similar in opacity.
+
+use std::sync::Arc;
+
+use ambient_gpu::{
+    gpu::Gpu,
+    shader_module::{BindGroupDesc, GraphicsPipeline, GraphicsPipelineInfo, Shader, ShaderModule},
+    texture::{Texture, TextureView},
+};
+use ambient_native_std::{asset_cache::AssetCache, include_file};
+use wgpu::{BindGroupLayout, BindGroupLayoutEntry, BindingType, ShaderStages};
+
+use crate::RendererTarget;
+
+const ORDER_INDEPENDENT_TRANSPARENCY_BIND_GROUP: &str = "ORDER_INDEPENDENT_TRANSPARENCY_BIND_GROUP";
+
+/// The sum of the colors of the transparent layers, premultiplied by their opacity, and of their
+/// opacities
+pub const OIT_ACCUMULATION_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+/// How much of the background is seen through all the transparent layers
+pub const OIT_REVEALAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R16Float;
+
+/// The target the transparent primitives are first drawn to
+pub fn get_oit_accumulation_target() -> wgpu::ColorTargetState {
+    wgpu::ColorTargetState {
+        format: OIT_ACCUMULATION_FORMAT,
+        blend: Some(wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+        }),
+        write_mask: wgpu::ColorWrites::ALL,
+    }
+}
+
+/// The target the transparent primitives are then drawn to
+pub fn get_oit_revealage_target() -> wgpu::ColorTargetState {
+    let component = wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    };
+    wgpu::ColorTargetState {
+        format: OIT_REVEALAGE_FORMAT,
+        blend: Some(wgpu::BlendState {
+            color: component,
+            alpha: component,
+        }),
+        write_mask: wgpu::ColorWrites::ALL,
+    }
+}
+
+fn get_order_independent_transparency_layout() -> BindGroupDesc<'static> {
+    let texture = |binding| BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::FRAGMENT,
+        ty: BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    };
+    BindGroupDesc {
+        entries: vec![texture(0), texture(1)],
+        label: ORDER_INDEPENDENT_TRANSPARENCY_BIND_GROUP.into(),
+    }
+}
+
+/// The accumulation and revealage images, sized after the target
+struct Buffers {
+    size: wgpu::Extent3d,
+    accumulation: TextureView,
+    revealage: TextureView,
+}
+
+impl Buffers {
+    fn new(gpu: &Gpu, size: wgpu::Extent3d) -> Self {
+        let create_view = |label, format| {
+            Arc::new(Texture::new(
+                gpu,
+                &wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            ))
+            .create_view(&Default::default())
+        };
+        Self {
+            size,
+            accumulation: create_view("OIT.accumulation", OIT_ACCUMULATION_FORMAT),
+            revealage: create_view("OIT.revealage", OIT_REVEALAGE_FORMAT),
+        }
+    }
+}
+
+pub struct OrderIndependentTransparency {
+    layout: Arc<BindGroupLayout>,
+    composite: GraphicsPipeline,
+    buffers: Option<Buffers>,
+}
+
+impl OrderIndependentTransparency {
+    pub fn new(gpu: &Gpu, assets: &AssetCache) -> Self {
+        let shader = Shader::new(
+            assets,
+            "OrderIndependentTransparency",
+            &[ORDER_INDEPENDENT_TRANSPARENCY_BIND_GROUP],
+            &ShaderModule::new(
+                "order_independent_transparency",
+                include_file!("order_independent_transparency.wgsl"),
+            )
+            .with_binding_desc(get_order_independent_transparency_layout()),
+        )
+        .unwrap();
+
+        Self {
+            layout: shader.layouts()[0].clone(),
+            composite: shader.to_pipeline(
+                gpu,
+                GraphicsPipelineInfo {
+                    fs_main: "fs_composite",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: gpu.swapchain_format(),
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+            ),
+            buffers: None,
+        }
+    }
+
+    /// Recreates the images if the target is now of another `size`
+    pub fn resize(&mut self, gpu: &Gpu, size: wgpu::Extent3d) {
+        if self.buffers.as_ref().map(|buffers| buffers.size) != Some(size) {
+            self.buffers = Some(Buffers::new(gpu, size));
+        }
+    }
+
+    /// The accumulation and revealage images the primitives are drawn to, once sized with
+    /// [Self::resize]
+    pub fn views(&self) -> (&TextureView, &TextureView) {
+        let buffers = self.buffers.as_ref().expect("Not resized");
+        (&buffers.accumulation, &buffers.revealage)
+    }
+
+    /// Blends the accumulated transparent layers over the target
+    pub fn composite(
+        &self,
+        gpu: &Gpu,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &RendererTarget,
+    ) {
+        let Some(buffers) = &self.buffers else {
+            return;
+        };
+        let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&buffers.accumulation),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&buffers.revealage),
+                },
+            ],
+            label: Some("OrderIndependentTransparency.bind_group"),
+        });
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("OrderIndependentTransparency.composite"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target.color(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: None,
+        });
+        rpass.set_pipeline(self.composite.pipeline());
+        rpass.set_bind_group(0, &bind_group, &[]);
+        rpass.draw(0..4, 0..1);
+    }
+}
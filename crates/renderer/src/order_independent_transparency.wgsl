@@ -0,0 +1,33 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32(i32(vertex_index) / 2);
+    let y = f32(i32(vertex_index) & 1);
+    out.position = vec4<f32>(x * 4. - 1., 1. - y * 4., 0., 1.);
+    return out;
+}
+
+@group(ORDER_INDEPENDENT_TRANSPARENCY_BIND_GROUP)
+@binding(0)
+var accumulation_texture: texture_2d<f32>;
+
+@group(ORDER_INDEPENDENT_TRANSPARENCY_BIND_GROUP)
+@binding(1)
+var revealage_texture: texture_2d<f32>;
+
+@fragment
+fn fs_composite(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(in.position.xy);
+    let accumulation = textureLoad(accumulation_texture, coords, 0);
+    // Nothing transparent was drawn here
+    if accumulation.a < 0.00001 {
+        discard;
+    }
+    let revealage = textureLoad(revealage_texture, coords, 0).r;
+    // The average color of the layers, weighted by their opacity, covering what they don't reveal
+    return vec4<f32>(accumulation.rgb / accumulation.a, 1. - revealage);
+}
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use ambient_ecs::{copy_component_recursive, ArchetypeFilter, Component, SystemGroup, World};
+use ambient_ecs::{
+    add_component_recursive, components, copy_component_recursive, query, ArchetypeFilter,
+    Component, SystemGroup, World,
+};
 use ambient_gpu::{
     gpu::Gpu,
     mesh_buffer::MeshBuffer,
@@ -15,6 +18,7 @@ use ambient_native_std::{
     include_file,
 };
 use ambient_settings::{RenderMode, SettingsKey};
+use glam::Vec4;
 use wgpu::{BindGroupLayoutEntry, BindingType, PrimitiveTopology, ShaderStages};
 
 use super::{
@@ -23,10 +27,18 @@ use super::{
 };
 use crate::{bind_groups::BindGroups, PostSubmitFunc, RendererConfig};
 
-pub use ambient_ecs::generated::rendering::components::{outline, outline_recursive};
+pub use ambient_ecs::generated::rendering::components::{
+    outline, outline_recursive, outline_thickness,
+};
+
+components!("rendering", {
+    /// The color of the `outline` of an entity, with its thickness in pixels divided by 255 as
+    /// the alpha, so that it fits in the alpha of the outlines texture
+    outline_style: Vec4,
+});
 
 gpu_components! {
-    outline() => outline: GpuComponentFormat::Vec4,
+    outline_style() => outline: GpuComponentFormat::Vec4,
 }
 
 pub struct OutlinesConfig {
@@ -105,7 +117,7 @@ impl Outlines {
                     renderer_config,
                     targets: vec![Some(wgpu::ColorTargetState {
                         format: Outlines::FORMAT,
-                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        blend: None,
                         write_mask: wgpu::ColorWrites::all(),
                     })],
                     filter: ArchetypeFilter::new().incl(config.scene).incl(outline()),
@@ -243,7 +255,42 @@ impl Outlines {
 }
 
 pub fn systems() -> SystemGroup {
-    copy_component_recursive("outlines", outline_recursive(), outline())
+    SystemGroup::new(
+        "outlines",
+        vec![
+            Box::new(copy_component_recursive(
+                "outlines/recursive",
+                outline_recursive(),
+                outline(),
+            )),
+            query(outline_thickness())
+                .incl(outline_recursive())
+                .to_system(|q, world, qs, _| {
+                    for (id, thickness) in q.collect_cloned(world, qs) {
+                        add_component_recursive(world, id, outline_thickness(), thickness);
+                    }
+                }),
+            query(outline().changed())
+                .optional_changed(outline_thickness())
+                .to_system(|q, world, qs, _| {
+                    for (id, color) in q.collect_cloned(world, qs) {
+                        let thickness = world.get(id, outline_thickness()).unwrap_or(3.);
+                        let style = color
+                            .truncate()
+                            .extend(thickness.round().clamp(1., 255.) / 255.);
+                        world.add_component(id, outline_style(), style).unwrap();
+                    }
+                }),
+            query(())
+                .incl(outline())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for id in q.collect_ids(world, qs) {
+                        world.remove_component(id, outline_style()).ok();
+                    }
+                }),
+        ],
+    )
 }
 
 pub fn gpu_world_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
@@ -252,7 +299,7 @@ pub fn gpu_world_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
         vec![Box::new(ComponentToGpuSystem::new(
             gpu,
             GpuComponentFormat::Vec4,
-            outline(),
+            outline_style(),
             gpu_components::outline(),
         ))],
     )
@@ -30,12 +30,13 @@ var r_color: texture_2d<f32>;
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let p = vec2<i32>(in.tex_coords * vec2<f32>(textureDimensions(r_color)));
     let center_color = textureLoad(r_color, p, 0);
-    let center = center_color.a > 0.5;
-    let size = 3;
-    let top = textureLoad(r_color, p + vec2<i32>(0, -size), 0).a > 0.5;
-    let bottom = textureLoad(r_color, p + vec2<i32>(0, size), 0).a > 0.5;
-    let left = textureLoad(r_color, p + vec2<i32>(-size, 0), 0).a > 0.5;
-    let right = textureLoad(r_color, p + vec2<i32>(size, 0), 0).a > 0.5;
+    // The alpha holds the thickness of the outline in pixels, divided by 255
+    let size = i32(round(center_color.a * 255.));
+    let center = size > 0;
+    let top = textureLoad(r_color, p + vec2<i32>(0, -size), 0).a > 0.;
+    let bottom = textureLoad(r_color, p + vec2<i32>(0, size), 0).a > 0.;
+    let left = textureLoad(r_color, p + vec2<i32>(-size, 0), 0).a > 0.;
+    let right = textureLoad(r_color, p + vec2<i32>(size, 0), 0).a > 0.;
     if !(center && (!top || !bottom || !left || !right)) {
         discard;
     }
@@ -0,0 +1,440 @@
+use std::{collections::HashMap, sync::Arc};
+
+use ambient_core::{
+    camera::{get_active_camera, projection_view},
+    delta_time,
+    player::local_user_id,
+    transform::local_to_world,
+};
+use ambient_ecs::{
+    query, read_messages, world_events, Component, EntityId, World, WorldEventReader,
+};
+use ambient_gpu::{
+    gpu::Gpu,
+    shader_module::{
+        BindGroupDesc, ComputePipeline, GraphicsPipeline, GraphicsPipelineInfo, Shader,
+        ShaderIdent, ShaderModule, DEPTH_FORMAT,
+    },
+};
+use ambient_native_std::{asset_cache::AssetCache, include_file};
+use glam::{Mat4, Vec3, Vec4};
+use wgpu::{BindGroupLayout, BindGroupLayoutEntry, BindingType, BufferBindingType, ShaderStages};
+
+use crate::RendererTarget;
+
+pub use ambient_ecs::generated::rendering::{
+    components::{
+        particle_acceleration, particle_collision, particle_color_over_life, particle_emitter,
+        particle_lifetime, particle_max_count, particle_restitution, particle_size_over_life,
+        particle_spawn_rate, particle_speed_over_life, particle_velocity, particle_velocity_spread,
+    },
+    messages::ParticleBurst,
+};
+
+const PARTICLES_SIMULATE_BIND_GROUP: &str = "PARTICLES_SIMULATE_BIND_GROUP";
+const PARTICLES_BIND_GROUP: &str = "PARTICLES_BIND_GROUP";
+const PARTICLES_WORKGROUP_SIZE: u32 = 64;
+const MAX_CURVE_KEYS: usize = 8;
+/// The size of a particle on the GPU: a position, an age, a velocity and a lifetime
+const PARTICLE_SIZE: u64 = 32;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct EmitterParams {
+    projection_view: Mat4,
+    inv_projection_view: Mat4,
+    origin: Vec3,
+    spawn_start: u32,
+    velocity: Vec3,
+    spawn_count: u32,
+    acceleration: Vec3,
+    max_count: u32,
+    camera_right: Vec3,
+    lifetime: f32,
+    camera_up: Vec3,
+    velocity_spread: f32,
+    rotation: Vec4,
+    dt: f32,
+    seed: u32,
+    collision: u32,
+    restitution: f32,
+    color_count: u32,
+    size_count: u32,
+    speed_count: u32,
+    _padding: u32,
+    colors: [Vec4; MAX_CURVE_KEYS],
+    sizes: [f32; MAX_CURVE_KEYS],
+    speeds: [f32; MAX_CURVE_KEYS],
+}
+
+fn uniform_entry(binding: u32, visibility: ShaderStages) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn particles_entry(
+    binding: u32,
+    visibility: ShaderStages,
+    read_only: bool,
+) -> BindGroupLayoutEntry {
+    BindGroupLayoutEntry {
+        binding,
+        visibility,
+        ty: BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn get_simulate_layout() -> BindGroupDesc<'static> {
+    BindGroupDesc {
+        entries: vec![
+            uniform_entry(0, ShaderStages::COMPUTE),
+            particles_entry(1, ShaderStages::COMPUTE, false),
+            BindGroupLayoutEntry {
+                binding: 2,
+                visibility: ShaderStages::COMPUTE,
+                ty: BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+        ],
+        label: PARTICLES_SIMULATE_BIND_GROUP.into(),
+    }
+}
+
+fn get_particles_layout() -> BindGroupDesc<'static> {
+    BindGroupDesc {
+        entries: vec![
+            uniform_entry(0, ShaderStages::VERTEX),
+            particles_entry(1, ShaderStages::VERTEX, true),
+        ],
+        label: PARTICLES_BIND_GROUP.into(),
+    }
+}
+
+fn get_particles_common_module() -> Arc<ShaderModule> {
+    Arc::new(
+        ShaderModule::new("particles_common", include_file!("particles_common.wgsl"))
+            .with_ident(ShaderIdent::constant(
+                "MAX_CURVE_KEYS",
+                MAX_CURVE_KEYS as u32,
+            ))
+            .with_ident(ShaderIdent::constant(
+                "MAX_CURVE_VEC4S",
+                MAX_CURVE_KEYS as u32 / 4,
+            )),
+    )
+}
+
+/// The GPU side of an emitter
+struct Emitter {
+    max_count: u32,
+    params: wgpu::Buffer,
+    particles: wgpu::Buffer,
+    render_bind_group: wgpu::BindGroup,
+    /// The slot the next spawned particle goes to
+    next: u32,
+    /// The fraction of a particle left to spawn
+    accumulator: f32,
+}
+
+impl Emitter {
+    fn new(gpu: &Gpu, layout: &BindGroupLayout, max_count: u32) -> Self {
+        let params = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particles.params"),
+            size: std::mem::size_of::<EmitterParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        // Zeroed particles are dead, as their age is not below their lifetime
+        let particles = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particles.particles"),
+            size: max_count as u64 * PARTICLE_SIZE,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        let render_bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: particles.as_entire_binding(),
+                },
+            ],
+            label: Some("Particles.render_bind_group"),
+        });
+        Self {
+            max_count,
+            params,
+            particles,
+            render_bind_group,
+            next: 0,
+            accumulator: 0.,
+        }
+    }
+}
+
+/// Simulates and draws the particles of the emitters of a scene
+pub struct Particles {
+    scene: Component<()>,
+    simulate_layout: Arc<BindGroupLayout>,
+    render_layout: Arc<BindGroupLayout>,
+    simulate: ComputePipeline,
+    render: GraphicsPipeline,
+    emitters: HashMap<EntityId, Emitter>,
+    bursts: WorldEventReader,
+    frame: u32,
+}
+
+impl Particles {
+    pub fn new(gpu: &Gpu, assets: &AssetCache, scene: Component<()>) -> Self {
+        let simulate = Shader::new(
+            assets,
+            "Particles.simulate",
+            &[PARTICLES_SIMULATE_BIND_GROUP],
+            &ShaderModule::new(
+                "particles_simulate",
+                include_file!("particles_simulate.wgsl"),
+            )
+            .with_ident(ShaderIdent::constant(
+                "PARTICLES_WORKGROUP_SIZE",
+                PARTICLES_WORKGROUP_SIZE,
+            ))
+            .with_binding_desc(get_simulate_layout())
+            .with_dependency(get_particles_common_module()),
+        )
+        .unwrap();
+
+        let render = Shader::new(
+            assets,
+            "Particles",
+            &[PARTICLES_BIND_GROUP],
+            &ShaderModule::new("particles", include_file!("particles.wgsl"))
+                .with_binding_desc(get_particles_layout())
+                .with_dependency(get_particles_common_module()),
+        )
+        .unwrap();
+
+        Self {
+            scene,
+            simulate_layout: simulate.layouts()[0].clone(),
+            render_layout: render.layouts()[0].clone(),
+            simulate: simulate.to_compute_pipeline(gpu, "main"),
+            render: render.to_pipeline(
+                gpu,
+                GraphicsPipelineInfo {
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: gpu.swapchain_format(),
+                        blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    // Particles are tested against the scene, but don't occlude each other
+                    depth: Some(wgpu::DepthStencilState {
+                        format: DEPTH_FORMAT,
+                        depth_write_enabled: false,
+                        depth_compare: wgpu::CompareFunction::Greater,
+                        stencil: wgpu::StencilState::default(),
+                        bias: wgpu::DepthBiasState::default(),
+                    }),
+                    ..Default::default()
+                },
+            ),
+            emitters: HashMap::new(),
+            bursts: WorldEventReader::new(),
+            frame: 0,
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        gpu: &Gpu,
+        world: &World,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &RendererTarget,
+    ) {
+        let mut bursts = HashMap::<EntityId, u32>::new();
+        for burst in
+            read_messages::<ParticleBurst>(&mut self.bursts, world.resource(world_events()))
+        {
+            *bursts.entry(burst.emitter).or_default() += burst.count;
+        }
+
+        let Some(camera) =
+            get_active_camera(world, self.scene, world.resource_opt(local_user_id()))
+        else {
+            return;
+        };
+        let projection_view = world.get(camera, projection_view()).unwrap_or_default();
+        let camera_transform = world.get(camera, local_to_world()).unwrap_or_default();
+        let dt = *world.resource(delta_time());
+
+        let emitters = query((particle_emitter(), local_to_world()))
+            .incl(self.scene)
+            .iter(world, None)
+            .map(|(id, (_, transform))| (id, *transform))
+            .collect::<Vec<_>>();
+        self.emitters
+            .retain(|id, _| emitters.iter().any(|(emitter, _)| emitter == id));
+        if emitters.is_empty() {
+            return;
+        }
+
+        profiling::scope!("Particles");
+        self.frame = self.frame.wrapping_add(1);
+        let mut draws = Vec::with_capacity(emitters.len());
+        for (id, transform) in emitters {
+            let max_count = world.get(id, particle_max_count()).unwrap_or(1000).max(1);
+            let emitter = self
+                .emitters
+                .entry(id)
+                .and_modify(|emitter| {
+                    if emitter.max_count != max_count {
+                        *emitter = Emitter::new(gpu, &self.render_layout, max_count);
+                    }
+                })
+                .or_insert_with(|| Emitter::new(gpu, &self.render_layout, max_count));
+
+            emitter.accumulator += world.get(id, particle_spawn_rate()).unwrap_or(10.) * dt;
+            let spawn_count = (emitter.accumulator.floor() as u32
+                + bursts.get(&id).copied().unwrap_or_default())
+            .min(max_count);
+            emitter.accumulator = emitter.accumulator.fract();
+            let spawn_start = emitter.next;
+            emitter.next = (emitter.next + spawn_count) % max_count;
+
+            let (_, rotation, origin) = transform.to_scale_rotation_translation();
+            let colors = world
+                .get_ref(id, particle_color_over_life())
+                .map(|keys| keys.as_slice())
+                .unwrap_or_default();
+            let sizes = world
+                .get_ref(id, particle_size_over_life())
+                .map(|keys| keys.as_slice())
+                .unwrap_or_default();
+            let speeds = world
+                .get_ref(id, particle_speed_over_life())
+                .map(|keys| keys.as_slice())
+                .unwrap_or_default();
+            fn curve<T: Copy + Default>(keys: &[T]) -> ([T; MAX_CURVE_KEYS], u32) {
+                let mut curve = [T::default(); MAX_CURVE_KEYS];
+                let count = keys.len().min(MAX_CURVE_KEYS);
+                curve[..count].copy_from_slice(&keys[..count]);
+                (curve, count as u32)
+            }
+            let (colors, color_count) = curve(colors);
+            let (sizes, size_count) = curve(sizes);
+            let (speeds, speed_count) = curve(speeds);
+
+            let params = EmitterParams {
+                projection_view,
+                inv_projection_view: projection_view.inverse(),
+                origin,
+                spawn_start,
+                velocity: world.get(id, particle_velocity()).unwrap_or(Vec3::Z),
+                spawn_count,
+                acceleration: world.get(id, particle_acceleration()).unwrap_or_default(),
+                max_count,
+                camera_right: camera_transform
+                    .transform_vector3(Vec3::X)
+                    .normalize_or_zero(),
+                lifetime: world.get(id, particle_lifetime()).unwrap_or(2.),
+                camera_up: camera_transform
+                    .transform_vector3(Vec3::Y)
+                    .normalize_or_zero(),
+                velocity_spread: world.get(id, particle_velocity_spread()).unwrap_or(0.5),
+                rotation: Vec4::from(rotation),
+                dt,
+                seed: self.frame ^ (id.0 as u32),
+                collision: world.get(id, particle_collision()).unwrap_or_default() as u32,
+                restitution: world.get(id, particle_restitution()).unwrap_or(0.5),
+                color_count,
+                size_count,
+                speed_count,
+                _padding: 0,
+                colors,
+                sizes,
+                speeds,
+            };
+            gpu.queue
+                .write_buffer(&emitter.params, 0, bytemuck::bytes_of(&params));
+
+            let simulate_bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.simulate_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: emitter.params.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: emitter.particles.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(target.depth()),
+                    },
+                ],
+                label: Some("Particles.simulate_bind_group"),
+            });
+            {
+                let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("Particles.simulate"),
+                });
+                cpass.set_pipeline(self.simulate.pipeline());
+                cpass.set_bind_group(0, &simulate_bind_group, &[]);
+                cpass.dispatch_workgroups(
+                    (max_count + PARTICLES_WORKGROUP_SIZE - 1) / PARTICLES_WORKGROUP_SIZE,
+                    1,
+                    1,
+                );
+            }
+            draws.push(id);
+        }
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Particles"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target.color(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: target.depth_stencil(),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+        });
+        rpass.set_pipeline(self.render.pipeline());
+        for id in draws {
+            let emitter = &self.emitters[&id];
+            rpass.set_bind_group(0, &emitter.render_bind_group, &[]);
+            rpass.draw(0..4, 0..emitter.max_count);
+        }
+    }
+}
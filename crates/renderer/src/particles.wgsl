@@ -0,0 +1,42 @@
+@group(PARTICLES_BIND_GROUP)
+@binding(0)
+var<uniform> params: EmitterParams;
+
+@group(PARTICLES_BIND_GROUP)
+@binding(1)
+var<storage, read> particles: array<Particle>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) corner: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, @builtin(instance_index) instance_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let particle = particles[instance_index];
+    let corner = vec2<f32>(f32(vertex_index / 2u), f32(vertex_index & 1u)) * 2. - 1.;
+    out.corner = corner;
+
+    // Dead particles collapse to a point, and are not rasterized
+    var size = 0.;
+    if particle.age < particle.lifetime {
+        let life = particle.age / particle.lifetime;
+        size = particle_size(life);
+        out.color = particle_color(life);
+    }
+
+    let position = particle.position + (params.camera_right * corner.x + params.camera_up * corner.y) * size * 0.5;
+    out.position = params.projection_view * vec4<f32>(position, 1.);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let alpha = in.color.a * (1. - smoothstep(0.5, 1., length(in.corner)));
+    if alpha <= 0. {
+        discard;
+    }
+    return vec4<f32>(in.color.rgb * alpha, alpha);
+}
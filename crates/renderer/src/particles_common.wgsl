@@ -0,0 +1,71 @@
+struct Particle {
+    position: vec3<f32>,
+    age: f32,
+    velocity: vec3<f32>,
+    lifetime: f32,
+};
+
+struct EmitterParams {
+    projection_view: mat4x4<f32>,
+    inv_projection_view: mat4x4<f32>,
+    origin: vec3<f32>,
+    spawn_start: u32,
+    velocity: vec3<f32>,
+    spawn_count: u32,
+    acceleration: vec3<f32>,
+    max_count: u32,
+    camera_right: vec3<f32>,
+    lifetime: f32,
+    camera_up: vec3<f32>,
+    velocity_spread: f32,
+    rotation: vec4<f32>,
+    dt: f32,
+    seed: u32,
+    collision: u32,
+    restitution: f32,
+    color_count: u32,
+    size_count: u32,
+    speed_count: u32,
+    _padding: u32,
+    colors: array<vec4<f32>, MAX_CURVE_KEYS>,
+    // Scalar curves are packed four keys to a vector, as uniform arrays are aligned to 16 bytes
+    sizes: array<vec4<f32>, MAX_CURVE_VEC4S>,
+    speeds: array<vec4<f32>, MAX_CURVE_VEC4S>,
+};
+
+// The curves are read from `params`, which is declared by the shaders using this module
+
+fn curve_position(count: u32, life: f32) -> vec3<f32> {
+    let t = clamp(life, 0., 1.) * f32(count - 1u);
+    let a = u32(floor(t));
+    let b = min(a + 1u, count - 1u);
+    return vec3<f32>(f32(a), f32(b), fract(t));
+}
+
+fn particle_speed(life: f32) -> f32 {
+    if params.speed_count == 0u {
+        return 1.;
+    }
+    let p = curve_position(params.speed_count, life);
+    let a = u32(p.x);
+    let b = u32(p.y);
+    return mix(params.speeds[a / 4u][a % 4u], params.speeds[b / 4u][b % 4u], p.z);
+}
+
+fn particle_size(life: f32) -> f32 {
+    if params.size_count == 0u {
+        return 0.1;
+    }
+    let p = curve_position(params.size_count, life);
+    let a = u32(p.x);
+    let b = u32(p.y);
+    return mix(params.sizes[a / 4u][a % 4u], params.sizes[b / 4u][b % 4u], p.z);
+}
+
+fn particle_color(life: f32) -> vec4<f32> {
+    if params.color_count == 0u {
+        return vec4<f32>(1.);
+    }
+    let p = curve_position(params.color_count, life);
+    return mix(params.colors[u32(p.x)], params.colors[u32(p.y)], p.z);
+}
@@ -0,0 +1,114 @@
+@group(PARTICLES_SIMULATE_BIND_GROUP)
+@binding(0)
+var<uniform> params: EmitterParams;
+
+@group(PARTICLES_SIMULATE_BIND_GROUP)
+@binding(1)
+var<storage, read_write> particles: array<Particle>;
+
+@group(PARTICLES_SIMULATE_BIND_GROUP)
+@binding(2)
+var depth_texture: texture_depth_2d;
+
+fn hash(value: u32) -> u32 {
+    var x = value;
+    x ^= x >> 16u;
+    x *= 0x7feb352du;
+    x ^= x >> 15u;
+    x *= 0x846ca68bu;
+    x ^= x >> 16u;
+    return x;
+}
+
+fn random(state: ptr<function, u32>) -> f32 {
+    *state = hash(*state);
+    return f32(*state) / 4294967295.;
+}
+
+fn random_in_sphere(state: ptr<function, u32>) -> vec3<f32> {
+    let z = random(state) * 2. - 1.;
+    let angle = random(state) * 6.28318530;
+    let r = sqrt(1. - z * z);
+    return vec3<f32>(r * cos(angle), r * sin(angle), z) * pow(random(state), 1. / 3.);
+}
+
+fn quat_rotate(q: vec4<f32>, v: vec3<f32>) -> vec3<f32> {
+    let t = 2. * cross(q.xyz, v);
+    return v + q.w * t + cross(q.xyz, t);
+}
+
+fn world_position_at(pixel: vec2<i32>, size: vec2<i32>) -> vec3<f32> {
+    let p = clamp(pixel, vec2<i32>(0), size - 1);
+    let depth = textureLoad(depth_texture, p, 0);
+    let uv = (vec2<f32>(p) + 0.5) / vec2<f32>(size);
+    let ndc = vec4<f32>(uv.x * 2. - 1., 1. - uv.y * 2., depth, 1.);
+    let position = params.inv_projection_view * ndc;
+    return position.xyz / position.w;
+}
+
+/// Bounces the particle off the surface visible at its position, if it went behind it
+fn collide(particle: ptr<function, Particle>) {
+    let clip = params.projection_view * vec4<f32>((*particle).position, 1.);
+    if clip.w <= 0. {
+        return;
+    }
+    let ndc = clip.xyz / clip.w;
+    if any(abs(ndc.xy) > vec2<f32>(1.)) {
+        return;
+    }
+
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    let pixel = vec2<i32>(uv * vec2<f32>(size));
+    let depth = textureLoad(depth_texture, clamp(pixel, vec2<i32>(0), size - 1), 0);
+    // The depth is reversed: the particle is in front of the surface, or nothing was drawn here
+    if ndc.z >= depth || depth <= 0. {
+        return;
+    }
+
+    let surface = world_position_at(pixel, size);
+    // Only collide with surfaces the particle just went through, not with the ones hiding it
+    let thickness = length((*particle).velocity) * params.dt * 2. + 0.1;
+    if distance(surface, (*particle).position) > thickness {
+        return;
+    }
+
+    let dx = world_position_at(pixel + vec2<i32>(1, 0), size) - surface;
+    let dy = world_position_at(pixel + vec2<i32>(0, 1), size) - surface;
+    var normal = normalize(cross(dy, dx));
+    if dot(normal, (*particle).velocity) > 0. {
+        normal = -normal;
+    }
+    (*particle).position = surface + normal * 0.01;
+    (*particle).velocity = reflect((*particle).velocity, normal) * params.restitution;
+}
+
+@compute
+@workgroup_size(PARTICLES_WORKGROUP_SIZE)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= params.max_count {
+        return;
+    }
+
+    var particle = particles[index];
+    // The particles are spawned into a ring buffer, replacing the oldest ones
+    let slot = (index + params.max_count - params.spawn_start) % params.max_count;
+    if slot < params.spawn_count {
+        var state = hash(index ^ hash(params.seed));
+        particle.position = params.origin;
+        particle.velocity = quat_rotate(params.rotation, params.velocity) + random_in_sphere(&state) * params.velocity_spread;
+        particle.age = 0.;
+        particle.lifetime = params.lifetime;
+    } else if particle.age < particle.lifetime {
+        particle.age += params.dt;
+        let life = particle.age / particle.lifetime;
+        let speed = particle_speed(life);
+        particle.velocity += params.acceleration * params.dt;
+        particle.position += particle.velocity * speed * params.dt;
+        if params.collision != 0u {
+            collide(&particle);
+        }
+    }
+    particles[index] = particle;
+}
@@ -0,0 +1,163 @@
+//! Planar reflection probes, which render the scene mirrored across their plane for the
+//! reflective surfaces lying on it, like water and mirrors.
+
+use ambient_core::{
+    camera::{
+        active_camera, far, fog, fovy, get_active_camera, near, orthographic_rect, projection,
+        projection_view,
+    },
+    player::local_user_id,
+    transform::{get_world_position, inv_local_to_world, local_to_world},
+};
+use ambient_ecs::{query, Entity, EntityId, World};
+use ambient_gpu::{gpu::Gpu, texture::TextureView};
+use ambient_native_std::{asset_cache::AssetCache, color::Color};
+use glam::{uvec2, vec3, Mat4, Vec3, Vec4};
+
+use crate::{
+    planar_reflection, PostSubmitFunc, RenderTarget, Renderer, RendererConfig, RendererTarget,
+};
+
+/// The planar reflection rendered for a frame
+pub(crate) struct PlanarReflection {
+    /// The plane of the probe, as its normal and its distance to the origin
+    pub plane: Vec4,
+    /// Projects points of the world to where their reflection is in `view`
+    pub projection_view: Mat4,
+    pub view: TextureView,
+}
+
+/// Renders the closest planar reflection probe facing the camera, at half the resolution of the
+/// scene
+pub(crate) struct PlanarReflections {
+    renderer: Box<Renderer>,
+    target: Option<RenderTarget>,
+    /// The mirrored camera, which is only part of the scene while the reflection is rendered
+    camera: Option<EntityId>,
+}
+
+impl PlanarReflections {
+    pub fn new(gpu: &Gpu, assets: &AssetCache, config: RendererConfig) -> Self {
+        Self {
+            renderer: Box::new(Renderer::new(
+                gpu,
+                assets,
+                RendererConfig {
+                    shadows: false,
+                    planar_reflections: false,
+                    // The reflections are measured as a pass of the renderer owning them
+                    frame_stats: false,
+                    ..config
+                },
+            )),
+            target: None,
+            camera: None,
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        gpu: &Gpu,
+        world: &mut World,
+        encoder: &mut wgpu::CommandEncoder,
+        post_submit: &mut Vec<PostSubmitFunc>,
+        target: &RendererTarget,
+    ) -> Option<PlanarReflection> {
+        let scene = self.renderer.config.scene;
+        let main_camera = get_active_camera(world, scene, world.resource_opt(local_user_id()))?;
+        let camera_position = get_world_position(world, main_camera).ok()?.extend(1.);
+
+        let plane = query((planar_reflection(), local_to_world()))
+            .iter(world, None)
+            .map(|(_, (_, probe_to_world))| {
+                let normal = probe_to_world.transform_vector3(Vec3::Z).normalize();
+                normal.extend(-normal.dot(probe_to_world.w_axis.truncate()))
+            })
+            // Probes only reflect what is in front of them
+            .filter(|plane| plane.dot(camera_position) > 0.)
+            .min_by(|a, b| a.dot(camera_position).total_cmp(&b.dot(camera_position)))?;
+
+        // Mirroring the view flips the winding of the triangles, so the image is also flipped
+        // horizontally, which keeps it the view of a regular camera that is culled as usual
+        let view = world.get(main_camera, inv_local_to_world()).ok()?;
+        let mirrored_view = Mat4::from_scale(vec3(-1., 1., 1.)) * view * reflection(plane);
+        let camera_projection = world.get(main_camera, projection()).ok()?;
+        let mirrored_projection_view = camera_projection * mirrored_view;
+
+        let mut camera_data = Entity::new()
+            .with(local_to_world(), mirrored_view.inverse())
+            .with(inv_local_to_world(), mirrored_view)
+            .with(projection(), camera_projection)
+            .with(projection_view(), mirrored_projection_view)
+            .with(active_camera(), f32::MAX)
+            .with(scene, ());
+        for component in [fovy(), near(), far()] {
+            if let Ok(value) = world.get(main_camera, component) {
+                camera_data.set(component, value);
+            }
+        }
+        if let Ok(rect) = world.get(main_camera, orthographic_rect()) {
+            camera_data.set(orthographic_rect(), rect);
+        }
+        let camera = match self.camera {
+            Some(camera) if world.exists(camera) => {
+                world.add_components(camera, camera_data).unwrap();
+                camera
+            }
+            _ => {
+                let camera = camera_data.spawn(world);
+                self.camera = Some(camera);
+                camera
+            }
+        };
+        if world.has_component(main_camera, fog()) {
+            world.add_component(camera, fog(), ()).unwrap();
+        } else {
+            world.remove_component(camera, fog()).ok();
+        }
+
+        let size = target.size();
+        let size = uvec2((size.width / 2).max(1), (size.height / 2).max(1));
+        if self.target.as_ref().map(|target| {
+            uvec2(
+                target.color_buffer.size.width,
+                target.color_buffer.size.height,
+            )
+        }) != Some(size)
+        {
+            self.target = Some(RenderTarget::new(gpu, size, None));
+        }
+        let reflection_target = self.target.as_ref().unwrap();
+
+        // Everything behind the mirror is hidden by it, including the surface of the mirror itself
+        self.renderer.clip_plane = plane.truncate().extend(plane.w - 0.01);
+        self.renderer.render(
+            gpu,
+            world,
+            encoder,
+            post_submit,
+            RendererTarget::Target(reflection_target),
+            Some(Color::rgba(0., 0., 0., 1.)),
+        );
+        world.remove_component(camera, scene).unwrap();
+
+        Some(PlanarReflection {
+            plane,
+            projection_view: mirrored_projection_view,
+            view: reflection_target
+                .color_buffer
+                .create_view(&Default::default()),
+        })
+    }
+}
+
+/// Mirrors points across `plane`
+fn reflection(plane: Vec4) -> Mat4 {
+    let normal = plane.truncate();
+    Mat4::from_cols(
+        (Vec3::X - 2. * normal.x * normal).extend(0.),
+        (Vec3::Y - 2. * normal.y * normal).extend(0.),
+        (Vec3::Z - 2. * normal.z * normal).extend(0.),
+        (-2. * plane.w * normal).extend(1.),
+    )
+}
@@ -0,0 +1,510 @@
+use std::{str::FromStr, sync::Arc};
+
+use ambient_core::{
+    camera::get_active_camera, camera::projection, player::local_user_id,
+    transform::inv_local_to_world,
+};
+use ambient_ecs::{query, Component, SystemGroup, World};
+use ambient_gpu::{
+    gpu::Gpu,
+    sampler::SamplerKey,
+    shader_module::{BindGroupDesc, GraphicsPipeline, GraphicsPipelineInfo, Shader, ShaderModule},
+    texture::Texture,
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, SyncAssetKeyExt},
+    include_file,
+};
+use glam::{vec2, Mat4, Vec2};
+use wgpu::{util::DeviceExt, BindGroupLayout, BindGroupLayoutEntry, BindingType, ShaderStages};
+
+use crate::{
+    color_grading_blend_lut, color_grading_intensity, color_grading_lut, color_grading_lut_blend,
+    ColorGradingLut, RendererTarget,
+};
+
+pub use ambient_ecs::generated::rendering::{
+    components::{
+        bloom_intensity, bloom_threshold, exposure, post_processing, ssao_intensity, ssao_radius,
+        ssr_intensity, ssr_max_distance, tonemapping, vignette_intensity, vignette_radius,
+    },
+    types::Tonemapping,
+};
+
+const POST_PROCESSING_BIND_GROUP: &str = "POST_PROCESSING_BIND_GROUP";
+
+/// An effect of the post-processing chain of a camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostProcessEffect {
+    Ssao,
+    Ssr,
+    Bloom,
+    Tonemapping,
+    ColorGrading,
+    Vignette,
+}
+
+impl FromStr for PostProcessEffect {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ssao" => Ok(Self::Ssao),
+            "ssr" => Ok(Self::Ssr),
+            "bloom" => Ok(Self::Bloom),
+            "tonemapping" => Ok(Self::Tonemapping),
+            "color_grading" => Ok(Self::ColorGrading),
+            "vignette" => Ok(Self::Vignette),
+            _ => anyhow::bail!("Unknown post-processing effect {s:?}"),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct PostProcessingParams {
+    projection: Mat4,
+    inv_projection: Mat4,
+    view: Mat4,
+    texel_size: Vec2,
+    direction: Vec2,
+    threshold: f32,
+    intensity: f32,
+    radius: f32,
+    exposure: f32,
+    tonemapping: u32,
+    blend: f32,
+    _padding: [u32; 2],
+}
+
+fn get_post_processing_layout() -> BindGroupDesc<'static> {
+    let texture = |binding, sample_type, view_dimension| BindGroupLayoutEntry {
+        binding,
+        visibility: ShaderStages::FRAGMENT,
+        ty: BindingType::Texture {
+            sample_type,
+            view_dimension,
+            multisampled: false,
+        },
+        count: None,
+    };
+    let float = wgpu::TextureSampleType::Float { filterable: true };
+    let d2 = wgpu::TextureViewDimension::D2;
+    BindGroupDesc {
+        entries: vec![
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            texture(2, float, d2),
+            texture(3, wgpu::TextureSampleType::Depth, d2),
+            texture(4, float, d2),
+            texture(5, float, d2),
+            texture(6, float, wgpu::TextureViewDimension::D3),
+            texture(7, float, wgpu::TextureViewDimension::D3),
+        ],
+        label: POST_PROCESSING_BIND_GROUP.into(),
+    }
+}
+
+struct Pipelines {
+    ssao: GraphicsPipeline,
+    ssr: GraphicsPipeline,
+    bloom_extract: GraphicsPipeline,
+    blur: GraphicsPipeline,
+    bloom_composite: GraphicsPipeline,
+    tonemapping: GraphicsPipeline,
+    color_grading: GraphicsPipeline,
+    vignette: GraphicsPipeline,
+}
+
+/// Intermediate images of the chain, sized after the target
+struct Buffers {
+    size: wgpu::Extent3d,
+    /// Full resolution, to ping-pong between effects
+    frames: [Arc<Texture>; 2],
+    /// Half resolution, to blur the bloom
+    bloom: [Arc<Texture>; 2],
+}
+
+impl Buffers {
+    fn new(gpu: &Gpu, size: wgpu::Extent3d) -> Self {
+        let half = wgpu::Extent3d {
+            width: (size.width / 2).max(1),
+            height: (size.height / 2).max(1),
+            depth_or_array_layers: 1,
+        };
+        Self {
+            size,
+            frames: [
+                Self::create_texture(gpu, "PostProcessing.frame", size),
+                Self::create_texture(gpu, "PostProcessing.frame", size),
+            ],
+            bloom: [
+                Self::create_texture(gpu, "PostProcessing.bloom", half),
+                Self::create_texture(gpu, "PostProcessing.bloom", half),
+            ],
+        }
+    }
+
+    fn create_texture(gpu: &Gpu, label: &str, size: wgpu::Extent3d) -> Arc<Texture> {
+        Arc::new(Texture::new(
+            gpu,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: gpu.swapchain_format(),
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        ))
+    }
+}
+
+/// Applies the post-processing chain of the active camera to the rendered image
+pub struct PostProcessing {
+    scene: Component<()>,
+    layout: Arc<BindGroupLayout>,
+    sampler: Arc<wgpu::Sampler>,
+    pipelines: Pipelines,
+    buffers: Option<Buffers>,
+    /// Bound in place of the lookup tables of cameras without any
+    identity_lut: Arc<ColorGradingLut>,
+}
+
+impl PostProcessing {
+    pub fn new(gpu: &Gpu, assets: &AssetCache, scene: Component<()>) -> Self {
+        let shader = Shader::new(
+            assets,
+            "PostProcessing",
+            &[POST_PROCESSING_BIND_GROUP],
+            &ShaderModule::new("post_processing", include_file!("post_processing.wgsl"))
+                .with_binding_desc(get_post_processing_layout()),
+        )
+        .unwrap();
+
+        let pipeline = |fs_main| {
+            shader.to_pipeline(
+                gpu,
+                GraphicsPipelineInfo {
+                    fs_main,
+                    targets: &[Some(gpu.swapchain_format().into())],
+                    topology: wgpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+            )
+        };
+
+        Self {
+            scene,
+            layout: shader.layouts()[0].clone(),
+            sampler: SamplerKey::LINEAR_CLAMP_TO_EDGE.get(assets),
+            pipelines: Pipelines {
+                ssao: pipeline("fs_ssao"),
+                ssr: pipeline("fs_ssr"),
+                bloom_extract: pipeline("fs_bloom_extract"),
+                blur: pipeline("fs_blur"),
+                bloom_composite: pipeline("fs_bloom_composite"),
+                tonemapping: pipeline("fs_tonemapping"),
+                color_grading: pipeline("fs_color_grading"),
+                vignette: pipeline("fs_vignette"),
+            },
+            buffers: None,
+            identity_lut: Arc::new(ColorGradingLut::identity(gpu)),
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        gpu: &Gpu,
+        world: &World,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &RendererTarget,
+    ) {
+        // The chain reads from a copy of the image, which needs the texture behind it
+        let RendererTarget::Target(render_target) = target else {
+            return;
+        };
+        let Some(camera) =
+            get_active_camera(world, self.scene, world.resource_opt(local_user_id()))
+        else {
+            return;
+        };
+        let Ok(effects) = world.get_ref(camera, post_processing()) else {
+            return;
+        };
+        let effects = effects
+            .iter()
+            .filter_map(|effect| effect.parse::<PostProcessEffect>().ok())
+            .collect::<Vec<_>>();
+        if effects.is_empty() {
+            return;
+        }
+
+        profiling::scope!("PostProcessing");
+        let size = target.size();
+        if self.buffers.as_ref().map(|buffers| buffers.size) != Some(size) {
+            self.buffers = Some(Buffers::new(gpu, size));
+        }
+        let buffers = self.buffers.as_ref().unwrap();
+        let frames = buffers
+            .frames
+            .iter()
+            .map(|frame| frame.create_view(&Default::default()))
+            .collect::<Vec<_>>();
+        let bloom = buffers
+            .bloom
+            .iter()
+            .map(|bloom| bloom.create_view(&Default::default()))
+            .collect::<Vec<_>>();
+
+        encoder.copy_texture_to_texture(
+            render_target.color_buffer.handle.as_image_copy(),
+            buffers.frames[0].handle.as_image_copy(),
+            size,
+        );
+
+        let projection = world.get(camera, projection()).unwrap_or_default();
+        let base = PostProcessingParams {
+            projection,
+            inv_projection: projection.inverse(),
+            view: world.get(camera, inv_local_to_world()).unwrap_or_default(),
+            texel_size: vec2(1. / size.width as f32, 1. / size.height as f32),
+            direction: Vec2::ZERO,
+            threshold: 0.,
+            intensity: 0.,
+            radius: 0.,
+            exposure: 0.,
+            tonemapping: 0,
+            blend: 0.,
+            _padding: Default::default(),
+        };
+        let get = |component, default| world.get(camera, component).unwrap_or(default);
+        let lut = world
+            .get_cloned(camera, color_grading_lut())
+            .unwrap_or_else(|_| self.identity_lut.clone());
+        let blend_lut = world
+            .get_cloned(camera, color_grading_blend_lut())
+            .unwrap_or_else(|_| lut.clone());
+
+        let pass = |encoder: &mut wgpu::CommandEncoder,
+                    pipeline: &GraphicsPipeline,
+                    params: PostProcessingParams,
+                    input: &wgpu::TextureView,
+                    bloom: &wgpu::TextureView,
+                    output: &wgpu::TextureView| {
+            let buffer = gpu
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("PostProcessing.params"),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                    contents: bytemuck::bytes_of(&params),
+                });
+            let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(input),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(target.depth()),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(bloom),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: wgpu::BindingResource::TextureView(target.material()),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: wgpu::BindingResource::TextureView(&lut.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(&blend_lut.view),
+                    },
+                ],
+                label: Some("PostProcessing.bind_group"),
+            });
+
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("PostProcessing"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: output,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            rpass.set_pipeline(pipeline.pipeline());
+            rpass.set_bind_group(0, &bind_group, &[]);
+            rpass.draw(0..4, 0..1);
+        };
+
+        // Each effect reads the previous frame and writes the next, and the last one writes to the target
+        let mut current = 0;
+        for (i, effect) in effects.iter().enumerate() {
+            let input = &frames[current];
+            let output = if i + 1 == effects.len() {
+                target.color()
+            } else {
+                &frames[1 - current]
+            };
+
+            match effect {
+                PostProcessEffect::Ssao => pass(
+                    encoder,
+                    &self.pipelines.ssao,
+                    PostProcessingParams {
+                        radius: get(ssao_radius(), 0.5),
+                        intensity: get(ssao_intensity(), 1.),
+                        ..base
+                    },
+                    input,
+                    input,
+                    output,
+                ),
+                PostProcessEffect::Ssr => pass(
+                    encoder,
+                    &self.pipelines.ssr,
+                    PostProcessingParams {
+                        radius: get(ssr_max_distance(), 20.),
+                        intensity: get(ssr_intensity(), 1.),
+                        ..base
+                    },
+                    input,
+                    input,
+                    output,
+                ),
+                PostProcessEffect::Bloom => {
+                    let half_texel = vec2(
+                        1. / buffers.bloom[0].size.width as f32,
+                        1. / buffers.bloom[0].size.height as f32,
+                    );
+                    pass(
+                        encoder,
+                        &self.pipelines.bloom_extract,
+                        PostProcessingParams {
+                            threshold: get(bloom_threshold(), 0.8),
+                            ..base
+                        },
+                        input,
+                        input,
+                        &bloom[0],
+                    );
+                    for (direction, from, to) in [(Vec2::X, 0, 1), (Vec2::Y, 1, 0)] {
+                        pass(
+                            encoder,
+                            &self.pipelines.blur,
+                            PostProcessingParams {
+                                texel_size: half_texel,
+                                direction,
+                                ..base
+                            },
+                            &bloom[from],
+                            &bloom[from],
+                            &bloom[to],
+                        );
+                    }
+                    pass(
+                        encoder,
+                        &self.pipelines.bloom_composite,
+                        PostProcessingParams {
+                            intensity: get(bloom_intensity(), 0.5),
+                            ..base
+                        },
+                        input,
+                        &bloom[0],
+                        output,
+                    );
+                }
+                PostProcessEffect::Tonemapping => pass(
+                    encoder,
+                    &self.pipelines.tonemapping,
+                    PostProcessingParams {
+                        exposure: get(exposure(), 0.),
+                        tonemapping: world.get(camera, tonemapping()).unwrap_or_default() as u32,
+                        ..base
+                    },
+                    input,
+                    input,
+                    output,
+                ),
+                PostProcessEffect::ColorGrading => pass(
+                    encoder,
+                    &self.pipelines.color_grading,
+                    PostProcessingParams {
+                        intensity: get(color_grading_intensity(), 1.),
+                        blend: get(color_grading_lut_blend(), 0.),
+                        ..base
+                    },
+                    input,
+                    input,
+                    output,
+                ),
+                PostProcessEffect::Vignette => pass(
+                    encoder,
+                    &self.pipelines.vignette,
+                    PostProcessingParams {
+                        intensity: get(vignette_intensity(), 0.3),
+                        radius: get(vignette_radius(), 0.75),
+                        ..base
+                    },
+                    input,
+                    input,
+                    output,
+                ),
+            }
+            current = 1 - current;
+        }
+    }
+}
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "post_processing",
+        vec![
+            query(post_processing().changed()).to_system(|q, world, qs, _| {
+                for (id, effects) in q.iter(world, qs) {
+                    for effect in effects {
+                        if let Err(err) = effect.parse::<PostProcessEffect>() {
+                            tracing::warn!("Ignoring post-processing effect of {id}: {err}");
+                        }
+                    }
+                }
+            }),
+        ],
+    )
+}
@@ -0,0 +1,286 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    let tc = vec2<f32>(
+        f32(x) * 2.0,
+        f32(y) * 2.0
+    );
+    out.position = vec4<f32>(
+        tc.x * 2.0 - 1.0,
+        1.0 - tc.y * 2.0,
+        0.0,
+        1.0
+    );
+    out.tex_coords = tc;
+    return out;
+}
+
+struct PostProcessingParams {
+    projection: mat4x4<f32>,
+    inv_projection: mat4x4<f32>,
+    view: mat4x4<f32>,
+    texel_size: vec2<f32>,
+    direction: vec2<f32>,
+    threshold: f32,
+    intensity: f32,
+    radius: f32,
+    exposure: f32,
+    tonemapping: u32,
+    blend: f32,
+    _padding0: u32,
+    _padding1: u32,
+};
+
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(0)
+var<uniform> params: PostProcessingParams;
+
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(1)
+var input_sampler: sampler;
+
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(2)
+var input_texture: texture_2d<f32>;
+
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(3)
+var depth_texture: texture_depth_2d;
+
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(4)
+var bloom_texture: texture_2d<f32>;
+
+// The world space normal of each pixel, and how strongly it reflects its surroundings
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(5)
+var material_texture: texture_2d<f32>;
+
+// The lookup tables of the color grading, blended from the first to the second
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(6)
+var color_grading_lut: texture_3d<f32>;
+
+@group(POST_PROCESSING_BIND_GROUP)
+@binding(7)
+var color_grading_blend_lut: texture_3d<f32>;
+
+fn luminance(color: vec3<f32>) -> f32 {
+    return dot(color, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+fn view_position(uv: vec2<f32>, depth: f32) -> vec3<f32> {
+    let ndc = vec4<f32>(uv.x * 2. - 1., 1. - uv.y * 2., depth, 1.);
+    let position = params.inv_projection * ndc;
+    return position.xyz / position.w;
+}
+
+fn load_depth(uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let p = clamp(vec2<i32>(uv * vec2<f32>(size)), vec2<i32>(0), size - 1);
+    return textureLoad(depth_texture, p, 0);
+}
+
+@fragment
+fn fs_ssao(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords);
+    let depth = load_depth(in.tex_coords);
+    // The depth is reversed, so nothing was drawn here
+    if depth <= 0. {
+        return color;
+    }
+
+    let center = view_position(in.tex_coords, depth);
+    // Rotate the kernel per pixel to trade banding for noise
+    let noise = fract(52.9829189 * fract(dot(in.position.xy, vec2<f32>(0.06711056, 0.00583715))));
+    let samples = 16;
+    var occlusion = 0.;
+    for (var i = 0; i < samples; i++) {
+        let t = (f32(i) + 0.5) / f32(samples);
+        let angle = f32(i) * 2.39996323 + noise * 6.28318530;
+        let offset = vec2<f32>(cos(angle), sin(angle)) * sqrt(t) * params.radius;
+
+        let sample_clip = params.projection * vec4<f32>(center.xy + offset, center.z, 1.);
+        let sample_ndc = sample_clip.xy / sample_clip.w;
+        let sample_uv = vec2<f32>(sample_ndc.x * 0.5 + 0.5, 0.5 - sample_ndc.y * 0.5);
+        let sample_depth = load_depth(sample_uv);
+        if sample_depth <= 0. {
+            continue;
+        }
+
+        let occluder = view_position(sample_uv, sample_depth);
+        let difference = center.z - occluder.z;
+        // Only count occluders in front of the center, and fade out the ones much further away
+        let range = smoothstep(0., 1., params.radius / max(abs(difference), 0.0001));
+        occlusion += select(0., range, difference > 0.02 * params.radius);
+    }
+
+    let ambient = 1. - params.intensity * occlusion / f32(samples);
+    return vec4<f32>(color.rgb * clamp(ambient, 0., 1.), color.a);
+}
+
+fn to_uv(view_position: vec3<f32>) -> vec2<f32> {
+    let clip = params.projection * vec4<f32>(view_position, 1.);
+    let ndc = clip.xy / clip.w;
+    return vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+}
+
+@fragment
+fn fs_ssr(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords);
+    let material = textureSampleLevel(material_texture, input_sampler, in.tex_coords, 0.);
+    let depth = load_depth(in.tex_coords);
+    if depth <= 0. || material.a <= 0.001 {
+        return color;
+    }
+
+    let origin = view_position(in.tex_coords, depth);
+    let normal = normalize((params.view * vec4<f32>(material.xyz, 0.)).xyz);
+    let direction = reflect(normalize(origin), normal);
+    // Rays towards the camera mostly hit the back of what is on screen, which isn't drawn
+    if direction.z < -0.5 {
+        return color;
+    }
+
+    let steps = 48;
+    let step = direction * params.radius / f32(steps);
+    let thickness = max(length(step) * 2., 0.05);
+    // Start each ray a bit further along per pixel to trade banding for noise
+    let noise = fract(52.9829189 * fract(dot(in.position.xy, vec2<f32>(0.06711056, 0.00583715))));
+    var position = origin + step * noise;
+    var hit = -1.;
+    var hit_uv = vec2<f32>(0.);
+    for (var i = 0; i < steps; i++) {
+        position += step;
+        if position.z <= 0. {
+            break;
+        }
+        let uv = to_uv(position);
+        if any(uv < vec2<f32>(0.)) || any(uv > vec2<f32>(1.)) {
+            break;
+        }
+        let scene_depth = load_depth(uv);
+        if scene_depth <= 0. {
+            continue;
+        }
+        let behind = position.z - view_position(uv, scene_depth).z;
+        if behind > 0. && behind < thickness {
+            // Narrow the hit down between the last two steps
+            var before = position - step;
+            var after = position;
+            for (var j = 0; j < 4; j++) {
+                let middle = (before + after) * 0.5;
+                let middle_uv = to_uv(middle);
+                if middle.z > view_position(middle_uv, load_depth(middle_uv)).z {
+                    after = middle;
+                } else {
+                    before = middle;
+                }
+            }
+            hit = f32(i) / f32(steps);
+            hit_uv = to_uv(after);
+            break;
+        }
+    }
+    if hit < 0. {
+        return color;
+    }
+
+    // Fade out the reflections of rays leaving the screen or reaching their max distance
+    let edge = min(min(hit_uv.x, 1. - hit_uv.x), min(hit_uv.y, 1. - hit_uv.y));
+    let fade = smoothstep(0., 0.1, edge) * (1. - smoothstep(0.75, 1., hit));
+    let reflected = textureSampleLevel(input_texture, input_sampler, hit_uv, 0.).rgb;
+    let strength = clamp(material.a * params.intensity * fade, 0., 1.);
+    return vec4<f32>(mix(color.rgb, reflected, strength), color.a);
+}
+
+@fragment
+fn fs_bloom_extract(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords).rgb;
+    let brightness = luminance(color);
+    let contribution = max(brightness - params.threshold, 0.) / max(brightness, 0.0001);
+    return vec4<f32>(color * contribution, 1.);
+}
+
+@fragment
+fn fs_blur(in: VertexOutput) -> @location(0) vec4<f32> {
+    var weights = array<f32, 5>(0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+    let step = params.direction * params.texel_size;
+    var color = textureSample(input_texture, input_sampler, in.tex_coords).rgb * weights[0];
+    for (var i = 1; i < 5; i++) {
+        let offset = step * f32(i);
+        color += textureSample(input_texture, input_sampler, in.tex_coords + offset).rgb * weights[i];
+        color += textureSample(input_texture, input_sampler, in.tex_coords - offset).rgb * weights[i];
+    }
+    return vec4<f32>(color, 1.);
+}
+
+@fragment
+fn fs_bloom_composite(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords);
+    let bloom = textureSample(bloom_texture, input_sampler, in.tex_coords).rgb;
+    return vec4<f32>(color.rgb + bloom * params.intensity, color.a);
+}
+
+fn aces(x: vec3<f32>) -> vec3<f32> {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), vec3<f32>(0.), vec3<f32>(1.));
+}
+
+@fragment
+fn fs_tonemapping(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords);
+    let exposed = color.rgb * exp2(params.exposure);
+    var mapped: vec3<f32>;
+    switch params.tonemapping {
+        case 0u: {
+            mapped = aces(exposed);
+        }
+        case 1u: {
+            mapped = exposed / (1. + luminance(exposed));
+        }
+        default: {
+            mapped = clamp(exposed, vec3<f32>(0.), vec3<f32>(1.));
+        }
+    }
+    return vec4<f32>(mapped, color.a);
+}
+
+fn sample_lut(lut: texture_3d<f32>, color: vec3<f32>) -> vec3<f32> {
+    // Sample between the centers of the first and last texels, so the ends of the range map exactly
+    let size = f32(textureDimensions(lut).x);
+    let uvw = color * (size - 1.) / size + 0.5 / size;
+    return textureSampleLevel(lut, input_sampler, uvw, 0.).rgb;
+}
+
+@fragment
+fn fs_color_grading(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords);
+    let original = clamp(color.rgb, vec3<f32>(0.), vec3<f32>(1.));
+    let graded = mix(
+        sample_lut(color_grading_lut, original),
+        sample_lut(color_grading_blend_lut, original),
+        clamp(params.blend, 0., 1.)
+    );
+    return vec4<f32>(mix(color.rgb, graded, params.intensity), color.a);
+}
+
+@fragment
+fn fs_vignette(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.tex_coords);
+    // The distance from the center, relative to the half diagonal
+    let distance = length(in.tex_coords * 2. - 1.) / sqrt(2.);
+    let darkening = params.intensity * smoothstep(params.radius, 1., distance);
+    return vec4<f32>(color.rgb * (1. - darkening), color.a);
+}
@@ -1,12 +1,14 @@
 use super::{
     overlay_renderer::{OverlayConfig, OverlayRenderer},
     shadow_renderer::ShadowsRenderer,
-    Culling, FSMain, ForwardGlobals, Outlines, OutlinesConfig, RenderTarget, RendererCollect,
-    RendererCollectState, TransparentRenderer, TransparentRendererConfig, TreeRenderer,
-    TreeRendererConfig,
+    Culling, FSMain, ForwardGlobals, HiZ, Lights, Outlines, OutlinesConfig, Particles,
+    PostProcessing, RenderTarget, RendererCollect, RendererCollectState, TransparentRenderer,
+    TransparentRendererConfig, TreeRenderer, TreeRendererConfig, MATERIAL_FORMAT,
 };
 use crate::{
-    bind_groups::BindGroups, get_common_layout, globals_layout, to_linear_format, ShaderDebugParams,
+    bind_groups::BindGroups, frame_stats, get_common_layout, get_shadow_settings, globals_layout,
+    planar_reflections::PlanarReflections, to_linear_format, DrawStats, FrameStats, GpuTimer,
+    ShaderDebugParams,
 };
 use ambient_core::{asset_cache, camera::*, gpu, player::local_user_id, ui_scene};
 use ambient_ecs::{ArchetypeFilter, Component, World};
@@ -14,7 +16,10 @@ use ambient_gpu::mesh_buffer::MeshBufferKey;
 use ambient_gpu::{
     gpu::{Gpu, GpuKey},
     mesh_buffer::MeshBuffer,
+    multi_buffer::MultiBuffer,
     shader_module::BindGroupDesc,
+    texture::Texture,
+    typed_buffer::TypedBuffer,
 };
 use ambient_gpu_ecs::gpu_world;
 use ambient_native_std::{
@@ -22,7 +27,7 @@ use ambient_native_std::{
     color::Color,
 };
 use ambient_settings::{RenderMode, SettingsKey};
-use glam::uvec2;
+use glam::{uvec2, Vec4};
 use std::sync::Arc;
 use tracing::debug_span;
 use wgpu::{BindGroupLayout, BindGroupLayoutEntry, TextureView};
@@ -30,7 +35,7 @@ use wgpu::{BindGroupLayout, BindGroupLayoutEntry, TextureView};
 pub const GLOBALS_BIND_GROUP: &str = "GLOBALS_BIND_GROUP";
 pub const MATERIAL_BIND_GROUP: &str = "MATERIAL_BIND_GROUP";
 pub const PRIMITIVES_BIND_GROUP: &str = "PRIMITIVES_BIND_GROUP";
-pub const GLOBALS_BIND_GROUP_SIZE: u32 = 8;
+pub const GLOBALS_BIND_GROUP_SIZE: u32 = 14;
 
 pub const MESH_METADATA_BINDING: u32 = 0;
 pub const MESH_BASE_BINDING: u32 = 1;
@@ -85,9 +90,15 @@ pub struct RendererConfig {
     pub scene: Component<()>,
     pub forward: bool,
     pub shadows: bool,
+    /// Replaced by the shadow settings for renderers with shadows
     pub shadow_map_resolution: u32,
+    /// Replaced by the shadow settings for renderers with shadows
     pub shadow_cascades: u32,
     pub lod_cutoff_scaling: f32,
+    /// Renders the `planar_reflection` probes of the scene before the scene itself
+    pub planar_reflections: bool,
+    /// Measures every frame, and keeps the statistics in the `frame_stats` resource of the world
+    pub frame_stats: bool,
 }
 
 impl Default for RendererConfig {
@@ -99,6 +110,8 @@ impl Default for RendererConfig {
             shadow_map_resolution: 1024,
             shadow_cascades: 5,
             lod_cutoff_scaling: 1.,
+            planar_reflections: false,
+            frame_stats: false,
         }
     }
 }
@@ -109,6 +122,7 @@ pub enum RendererTarget<'a> {
         color: &'a TextureView,
         depth: &'a TextureView,
         normals: &'a TextureView,
+        material: &'a TextureView,
         size: wgpu::Extent3d,
     },
 }
@@ -142,6 +156,13 @@ impl<'a> RendererTarget<'a> {
         }
     }
 
+    pub fn material(&self) -> &'a TextureView {
+        match self {
+            RendererTarget::Target(target) => &target.material_buffer_view,
+            RendererTarget::Direct { material, .. } => material,
+        }
+    }
+
     pub fn size(&self) -> wgpu::Extent3d {
         match self {
             RendererTarget::Target(target) => target.color_buffer.size,
@@ -171,6 +192,7 @@ pub struct Renderer {
     mesh_meta_layout: Arc<BindGroupLayout>,
 
     culling: Culling,
+    hi_z: HiZ,
     pub shadows: Option<ShadowsRenderer>,
     forward_globals: ForwardGlobals,
     forward_collect_state: RendererCollectState,
@@ -179,6 +201,13 @@ pub struct Renderer {
     transparent: TransparentRenderer,
     solids_frame: RenderTarget,
     outlines: Outlines,
+    particles: Particles,
+    post_processing: PostProcessing,
+    planar_reflections: Option<PlanarReflections>,
+    timer: GpuTimer,
+    /// Geometry behind this plane is not drawn, which the planar reflections use to only show
+    /// what is in front of their mirror
+    pub(crate) clip_plane: Vec4,
     pub post_forward: Option<Box<dyn SubRenderer>>,
     pub post_transparent: Option<Box<dyn SubRenderer>>,
 }
@@ -186,6 +215,12 @@ pub struct Renderer {
 impl Renderer {
     pub fn new(gpu: &Gpu, assets: &AssetCache, config: RendererConfig) -> Self {
         let renderer_resources = RendererResourcesKey.get(assets);
+        let settings = SettingsKey.get(assets).render;
+        let config = if config.shadows {
+            config.with_shadow_settings(&settings.shadows)
+        } else {
+            config
+        };
 
         // Need atleast one for array<Camera, SIZE> to be valid
         let shadow_cascades = config.shadow_cascades;
@@ -201,12 +236,21 @@ impl Renderer {
             None
         };
 
-        let settings = SettingsKey.get(assets).render;
-
         let normals_format = to_linear_format(gpu.swapchain_format()).into();
+        let render_mode = settings
+            .render_mode
+            .unwrap_or_else(RenderMode::instrinsic_render_mode);
 
         Self {
             culling: Culling::new(gpu, assets, config.clone()),
+            // Direct rendering draws everything regardless of the culling
+            hi_z: HiZ::new(
+                gpu,
+                assets,
+                config.forward
+                    && render_mode != RenderMode::Direct
+                    && settings.occlusion_culling.unwrap_or(true),
+            ),
             forward_globals: ForwardGlobals::new(
                 gpu,
                 renderer_resources.globals_layout.clone(),
@@ -242,6 +286,7 @@ impl Renderer {
                                 write_mask: wgpu::ColorWrites::COLOR,
                             }),
                             Some(normals_format),
+                            Some(MATERIAL_FORMAT.into()),
                         ],
                         filter: ArchetypeFilter::new().incl(config.scene),
                         renderer_resources: renderer_resources.clone(),
@@ -250,9 +295,7 @@ impl Renderer {
                         depth_stencil: true,
                         cull_mode: Some(wgpu::Face::Back),
                         depth_bias: Default::default(),
-                        render_mode: settings
-                            .render_mode
-                            .unwrap_or_else(RenderMode::instrinsic_render_mode),
+                        render_mode,
                         software_culling: settings.software_culling,
                     },
                 ))
@@ -261,6 +304,7 @@ impl Renderer {
             },
             transparent: TransparentRenderer::new(
                 gpu,
+                assets,
                 TransparentRendererConfig {
                     renderer_config: config.clone(),
                     targets: vec![Some(wgpu::ColorTargetState {
@@ -293,6 +337,15 @@ impl Renderer {
                 },
                 config.clone(),
             ),
+            particles: Particles::new(gpu, assets, config.scene),
+            post_processing: PostProcessing::new(gpu, assets, config.scene),
+            planar_reflections: if config.planar_reflections {
+                Some(PlanarReflections::new(gpu, assets, config.clone()))
+            } else {
+                None
+            },
+            timer: GpuTimer::new(gpu, config.frame_stats),
+            clip_plane: Vec4::ZERO,
             mesh_meta_layout: renderer_resources.mesh_meta_layout,
             config,
             shader_debug_params: Default::default(),
@@ -301,6 +354,16 @@ impl Renderer {
         }
     }
 
+    /// Rebuilds the renderer for `config`, keeping what was set on it from the outside
+    fn reconfigure(&mut self, gpu: &Gpu, assets: &AssetCache, config: RendererConfig) {
+        let mut renderer = Self::new(gpu, assets, config);
+        renderer.shader_debug_params = self.shader_debug_params;
+        renderer.clip_plane = self.clip_plane;
+        renderer.post_forward = self.post_forward.take();
+        renderer.post_transparent = self.post_transparent.take();
+        *self = renderer;
+    }
+
     pub fn render(
         &mut self,
         gpu: &Gpu,
@@ -313,6 +376,19 @@ impl Renderer {
         let _span = debug_span!("Renderer.render", label = world.name()).entered();
         profiling::scope!("Renderer.render", world.name());
 
+        // The shadow maps and the shaders reading them are built for a resolution and number of
+        // cascades, so the renderer is rebuilt when those change
+        let shadow_settings = self.config.shadows.then(|| get_shadow_settings(world));
+        if let Some(settings) = &shadow_settings {
+            let config = self.config.clone().with_shadow_settings(settings);
+            if config.shadow_map_resolution != self.config.shadow_map_resolution
+                || config.shadow_cascades != self.config.shadow_cascades
+            {
+                self.reconfigure(gpu, &world.resource(asset_cache()).clone(), config);
+            }
+        }
+        self.timer.start(gpu, encoder);
+
         if let RendererTarget::Target(target) = &target {
             if self.solids_frame.color_buffer.size != target.color_buffer.size {
                 self.solids_frame = RenderTarget::new(
@@ -330,6 +406,13 @@ impl Renderer {
             }
         }
 
+        // The probes are rendered with renderers of their own, before the scene reflects them
+        let planar_reflection = self
+            .planar_reflections
+            .as_mut()
+            .and_then(|reflections| reflections.render(gpu, world, encoder, post_submit, &target));
+        self.timer.mark(encoder, "Planar reflections");
+
         let assets = world.resource(asset_cache()).clone();
         let mesh_buffer_h = MeshBufferKey.get(&assets);
         let mesh_buffer = mesh_buffer_h.lock();
@@ -352,9 +435,24 @@ impl Renderer {
             world.resource_opt(local_user_id()),
         )
         .unwrap_or_default();
+        let lights = Lights::from_world(
+            world,
+            self.config.scene,
+            main_camera.position(),
+            self.shadows.is_some(),
+        );
+        if let (Some(shadows), Some(settings)) = (&mut self.shadows, &shadow_settings) {
+            shadows.update(gpu, &assets, world, &lights, settings);
+        }
         {
             profiling::scope!("Update");
-            self.culling.run(gpu, encoder, world);
+            let shadow_cameras = self
+                .shadows
+                .as_ref()
+                .map(|shadows| shadows.cascade_cameras())
+                .unwrap_or_default();
+            self.culling
+                .run(gpu, encoder, world, &shadow_cameras, &lights, &self.hi_z);
 
             self.forward_collect_state.set_camera(gpu, 0);
             self.overlays.update(gpu, &assets, world);
@@ -374,20 +472,26 @@ impl Renderer {
                 );
             }
 
-            self.transparent.update(
-                gpu,
-                &assets,
-                world,
-                &mesh_buffer,
-                main_camera.projection_view(),
-            );
-        }
-
-        if let Some(shadows) = &mut self.shadows {
-            shadows.update(gpu, &assets, world);
+            self.transparent
+                .update(gpu, &assets, world, &mesh_buffer, &main_camera);
         }
+        self.timer.mark(encoder, "Culling");
 
         self.forward_globals.params.debug_params = self.shader_debug_params;
+        self.forward_globals.params.clip_plane = self.clip_plane;
+        if let Some(settings) = &shadow_settings {
+            self.forward_globals.params.shadow_bias = settings.bias;
+        }
+        self.forward_globals.params.planar_reflection_plane = planar_reflection
+            .as_ref()
+            .map(|reflection| reflection.plane)
+            .unwrap_or_default();
+        self.forward_globals
+            .params
+            .planar_reflection_projection_view = planar_reflection
+            .as_ref()
+            .map(|reflection| reflection.projection_view)
+            .unwrap_or_default();
         // tracing::debug!("Updating forward globals");
         self.forward_globals.update(
             gpu,
@@ -397,13 +501,18 @@ impl Renderer {
                 .as_ref()
                 .map(|x| x.get_cameras())
                 .unwrap_or_default(),
+            &lights,
         );
 
         let forward_globals_bind_group = self.forward_globals.create_bind_group(
             gpu,
             &assets,
             self.shadows.as_ref().map(|x| &x.shadow_view),
+            self.shadows.as_ref().map(|x| &x.light_shadow_view),
             &self.solids_frame,
+            planar_reflection
+                .as_ref()
+                .map(|reflection| &reflection.view),
             &mesh_buffer,
         );
 
@@ -424,6 +533,7 @@ impl Renderer {
                 post_submit,
             );
         }
+        self.timer.mark(encoder, "Shadows");
 
         {
             profiling::scope!("Forward");
@@ -455,6 +565,19 @@ impl Renderer {
                             store: true,
                         },
                     }),
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: target.material(),
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            // Nothing reflects where nothing was drawn
+                            load: if clear.is_some() {
+                                wgpu::LoadOp::Clear(Color::rgba(0., 0., 0., 0.).into())
+                            } else {
+                                wgpu::LoadOp::Load
+                            },
+                            store: true,
+                        },
+                    }),
                 ],
                 depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                     view: target.depth_stencil(),
@@ -492,6 +615,14 @@ impl Renderer {
                 drop(render_pass);
             }
         }
+        self.timer.mark(encoder, "Forward");
+
+        // The next frame is culled against the solids of this one
+        if let RendererTarget::Target(_) = &target {
+            self.hi_z
+                .build(gpu, encoder, target.depth(), target.size(), &main_camera);
+        }
+        self.timer.mark(encoder, "Hi-Z");
 
         if let Some(post_forward) = &mut self.post_forward {
             post_forward.render(
@@ -525,8 +656,19 @@ impl Renderer {
                 target.normals_quat_buffer.size,
             );
         }
+        self.timer.mark(encoder, "Overlays");
 
-        {
+        if self.transparent.is_order_independent() {
+            profiling::scope!("Transparent");
+            self.transparent.render_order_independent(
+                gpu,
+                world,
+                encoder,
+                &target,
+                &bind_groups,
+                &mesh_buffer,
+            );
+        } else {
             profiling::scope!("Transparent");
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Transparent"),
@@ -561,6 +703,12 @@ impl Renderer {
                 drop(render_pass);
             }
         }
+        self.timer.mark(encoder, "Transparent");
+
+        self.particles.render(gpu, world, encoder, &target);
+        self.timer.mark(encoder, "Particles");
+        self.post_processing.render(gpu, world, encoder, &target);
+        self.timer.mark(encoder, "Post-processing");
 
         if let Some(post_transparent) = &mut self.post_transparent {
             post_transparent.render(
@@ -584,6 +732,29 @@ impl Renderer {
             &bind_groups,
             &mesh_buffer,
         );
+        self.timer.mark(encoder, "Outlines");
+
+        if self.config.frame_stats {
+            self.timer.finish(gpu, encoder, post_submit);
+            let mut draws = DrawStats::default();
+            if let Some(forward) = &self.forward {
+                draws += forward.draw_stats(world, &mesh_buffer);
+            }
+            if let Some(shadows) = &self.shadows {
+                draws += shadows.draw_stats(world, &mesh_buffer);
+            }
+            draws += self.transparent.draw_stats(world);
+            world.add_resource(
+                frame_stats(),
+                FrameStats {
+                    passes: self.timer.passes(),
+                    draws,
+                    texture_bytes: Texture::total_bytes_used(),
+                    buffer_bytes: TypedBuffer::<u8>::total_bytes_used()
+                        + MultiBuffer::total_bytes_used() as u64,
+                },
+            );
+        }
     }
 
     pub fn dump_to_tmp_file(&self) {
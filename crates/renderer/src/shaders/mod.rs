@@ -7,7 +7,10 @@ use ambient_native_std::{
     include_file,
 };
 
-use crate::{GLOBALS_BIND_GROUP, MATERIAL_BIND_GROUP, PRIMITIVES_BIND_GROUP};
+use crate::{
+    get_imposter_module, get_wind_module, GLOBALS_BIND_GROUP, MATERIAL_BIND_GROUP,
+    PRIMITIVES_BIND_GROUP,
+};
 
 use super::{get_forward_modules, MaterialShader, RendererShader};
 
@@ -15,6 +18,11 @@ pub struct StandardShaderKey {
     pub material_shader: Arc<MaterialShader>,
     pub lit: bool,
     pub shadow_cascades: u32,
+    /// Whether the vertices sway with the wind, with the `get_material_wind` of the material shader
+    pub wind: bool,
+    /// Whether the mesh is the quad of an octahedral imposter, turned with the
+    /// `get_material_imposter` of the material shader
+    pub imposter: bool,
 }
 
 impl std::fmt::Debug for StandardShaderKey {
@@ -22,13 +30,18 @@ impl std::fmt::Debug for StandardShaderKey {
         f.debug_struct("StandardShaderKey")
             .field("material_shader", &self.material_shader.id)
             .field("lit", &self.lit)
+            .field("wind", &self.wind)
+            .field("imposter", &self.imposter)
             .finish()
     }
 }
 
 impl SyncAssetKey<Arc<RendererShader>> for StandardShaderKey {
     fn load(&self, assets: AssetCache) -> Arc<RendererShader> {
-        let id = format!("standard_shader_{}_{}", self.material_shader.id, self.lit);
+        let id = format!(
+            "standard_shader_{}_{}_{}_{}",
+            self.material_shader.id, self.lit, self.wind, self.imposter
+        );
         let shader = Shader::new(
             &assets,
             id.clone(),
@@ -40,7 +53,9 @@ impl SyncAssetKey<Arc<RendererShader>> for StandardShaderKey {
             ],
             &ShaderModule::new("standard_material", include_file!("standard.wgsl"))
                 .with_dependencies(get_forward_modules(&assets, self.shadow_cascades))
-                .with_dependency(self.material_shader.shader.clone()),
+                .with_dependency(self.material_shader.shader.clone())
+                .with_dependency(get_wind_module(self.wind))
+                .with_dependency(get_imposter_module(self.imposter)),
         )
         .unwrap();
 
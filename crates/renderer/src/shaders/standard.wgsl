@@ -18,7 +18,8 @@ fn vs_main(@builtin(instance_index) instance_index: u32, @builtin(vertex_index)
     let entity_loc = primitive.xy;
     let mesh_index = get_entity_primitive_mesh(entity_loc, primitive.z);
 
-    let world = model_to_world(entity_loc, mesh_index, vertex_index);
+    var world = apply_imposter(model_to_world(entity_loc, mesh_index, vertex_index), entity_loc);
+    world.pos = apply_wind(world.pos, get_entity_mesh_to_world(entity_loc)[3].xyz);
     out.instance_index = instance_index;
     out.texcoord = world.texcoord;
 
@@ -51,11 +52,21 @@ fn get_material_in(in: VertexOutput, is_front: bool) -> MaterialInput {
     return material_in;
 }
 
+// While a lod group crossfades, its two levels draw complementary parts of a dither pattern
+fn is_lod_faded(instance_index: u32, position: vec4<f32>) -> bool {
+    let entity_loc = primitives.data[instance_index].xy;
+    let fade = get_entity_lod_fade_or(entity_loc, vec4<f32>(1., 1., 0., 0.));
+    let pixel = vec2<u32>(position.xy) & vec2<u32>(3u);
+    var bayer = array<f32, 16>(0., 8., 2., 10., 12., 4., 14., 6., 3., 11., 1., 9., 15., 7., 13., 5.);
+    let threshold = (bayer[pixel.y * 4u + pixel.x] + 0.5) / 16.;
+    return (threshold < fade.x) != (fade.y > 0.5);
+}
+
 @fragment
 fn fs_shadow_main(in: VertexOutput, @builtin(front_facing) is_front: bool) {
     var material = get_material(get_material_in(in, is_front));
 
-    if material.opacity < material.alpha_cutoff {
+    if material.opacity < material.alpha_cutoff || is_lod_faded(in.instance_index, in.position) {
         discard;
     }
 }
@@ -70,7 +81,7 @@ fn fs_forward_lit_main(in: VertexOutput, @builtin(front_facing) is_front: bool)
     let material_in = get_material_in(in, is_front);
     var material = get_material(material_in);
 
-    if material.opacity < material.alpha_cutoff {
+    if material.opacity < material.alpha_cutoff || is_lod_faded(in.instance_index, in.position) {
         discard;
     }
 
@@ -80,8 +91,10 @@ fn fs_forward_lit_main(in: VertexOutput, @builtin(front_facing) is_front: bool)
 
     material.normal = normalize(material.normal);
 
-    return MainFsOut(
+    return forward_out(
+        material,
         shading(material, in.world_position),
+        in.world_position,
         quat_from_mat3(material_in.normal_matrix)
     );
 }
@@ -91,12 +104,14 @@ fn fs_forward_unlit_main(in: VertexOutput, @builtin(front_facing) is_front: bool
     let material_in = get_material_in(in, is_front);
     var material = get_material(material_in);
 
-    if material.opacity < material.alpha_cutoff {
+    if material.opacity < material.alpha_cutoff || is_lod_faded(in.instance_index, in.position) {
         discard;
     }
 
-    return MainFsOut(
+    return forward_out(
+        material,
         vec4<f32>(material.base_color, material.opacity),
+        in.world_position,
         quat_from_mat3(material_in.normal_matrix)
     );
 }
@@ -105,7 +120,7 @@ fn fs_forward_unlit_main(in: VertexOutput, @builtin(front_facing) is_front: bool
 fn fs_outlines_main(in: VertexOutput, @builtin(front_facing) is_front: bool) -> @location(0) vec4<f32> {
     var material = get_material(get_material_in(in, is_front));
 
-    if material.opacity < material.alpha_cutoff {
+    if material.opacity < material.alpha_cutoff || is_lod_faded(in.instance_index, in.position) {
         discard;
     }
     return get_outline(in.instance_index);
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
-use ambient_core::{camera::Camera, main_scene, player::local_user_id, transform::*};
-use ambient_ecs::{ArchetypeFilter, World};
+use ambient_core::{asset_cache, camera::Camera, main_scene, player::local_user_id, transform::*};
+use ambient_ecs::{
+    components, generated::rendering::components::sun_shadows, ArchetypeFilter, Debuggable,
+    Resource, World,
+};
 use ambient_gpu::{
     gpu::Gpu,
     mesh_buffer::MeshBuffer,
@@ -13,21 +16,62 @@ use ambient_settings::{RenderMode, SettingsKey};
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 use itertools::Itertools;
-use smallvec::SmallVec;
 use wgpu::DepthBiasState;
 
 use super::{
-    cast_shadows, get_active_sun, FSMain, RendererCollectState, RendererResources,
-    ShadowAndUIGlobals, TreeRenderer, TreeRendererConfig, MAX_SHADOW_CASCADES,
+    cast_shadows, get_active_sun, FSMain, Lights, RendererCollectState, RendererResources,
+    ShadowAndUIGlobals, TreeRenderer, TreeRendererConfig, LIGHT_SHADOW_FACES,
+    LIGHT_SHADOW_MAP_RESOLUTION, MAX_SHADOW_LIGHTS,
+};
+use crate::{
+    bind_groups::BindGroups, default_sun_direction, DrawStats, PostSubmitFunc, RendererConfig,
+    MAX_SHADOW_CASCADES,
 };
-use crate::{bind_groups::BindGroups, default_sun_direction, PostSubmitFunc, RendererConfig};
+
+pub use ambient_settings::ShadowSettings;
+
+components!("rendering", {
+    /// Overrides the shadow settings of the settings file for the renderers of this world, which
+    /// lets the quality of the shadows be changed at runtime
+    @[Debuggable, Resource]
+    shadow_settings: ShadowSettings,
+});
+
+/// The shadow settings of a world: its `shadow_settings`, or else those of the settings file
+pub fn get_shadow_settings(world: &World) -> ShadowSettings {
+    world
+        .resource_opt(shadow_settings())
+        .cloned()
+        .unwrap_or_else(|| {
+            SettingsKey
+                .get(world.resource(asset_cache()))
+                .render
+                .shadows
+        })
+}
+
+impl RendererConfig {
+    /// Uses the shadow map resolution and number of cascades of `settings`
+    pub fn with_shadow_settings(self, settings: &ShadowSettings) -> Self {
+        Self {
+            shadow_map_resolution: settings.resolution.clamp(128, 8192),
+            shadow_cascades: settings.cascades.clamp(1, MAX_SHADOW_CASCADES),
+            ..self
+        }
+    }
+}
 
 pub struct ShadowsRenderer {
     renderer: TreeRenderer,
     cascades: Vec<ShadowCascade>,
+    light_shadows: Vec<LightShadow>,
+    /// If the sun casts shadows this frame; the cascades are only cleared otherwise
+    sun_shadows: bool,
     pub shadow_texture: Arc<Texture>,
     config: RendererConfig,
     pub shadow_view: TextureView,
+    pub light_shadow_texture: Arc<Texture>,
+    pub light_shadow_view: TextureView,
 }
 
 impl std::fmt::Debug for ShadowsRenderer {
@@ -71,6 +115,30 @@ impl ShadowsRenderer {
             ..Default::default()
         });
 
+        let light_shadow_texture = Arc::new(Texture::new(
+            gpu,
+            &wgpu::TextureDescriptor {
+                label: Some("Renderer.light_shadow_texture"),
+                size: wgpu::Extent3d {
+                    width: LIGHT_SHADOW_MAP_RESOLUTION,
+                    height: LIGHT_SHADOW_MAP_RESOLUTION,
+                    depth_or_array_layers: MAX_SHADOW_LIGHTS * LIGHT_SHADOW_FACES,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: DEPTH_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        ));
+
+        let light_shadow_view = light_shadow_texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+
         let settings = SettingsKey.get(assets).render;
 
         Self {
@@ -119,25 +187,76 @@ impl ShadowsRenderer {
                     collect_state: RendererCollectState::new(gpu),
                 })
                 .collect_vec(),
+            light_shadows: (0..MAX_SHADOW_LIGHTS)
+                .map(|i| LightShadow {
+                    faces: (0..LIGHT_SHADOW_FACES)
+                        .map(|face| LightShadowFace {
+                            target: light_shadow_texture.create_view(
+                                &wgpu::TextureViewDescriptor {
+                                    label: Some("Renderer.light_shadow_target_views"),
+                                    format: None,
+                                    dimension: Some(wgpu::TextureViewDimension::D2),
+                                    aspect: wgpu::TextureAspect::All,
+                                    base_mip_level: 0,
+                                    mip_level_count: None,
+                                    base_array_layer: i * LIGHT_SHADOW_FACES + face,
+                                    array_layer_count: Some(1),
+                                },
+                            ),
+                            globals: ShadowAndUIGlobals::new(
+                                gpu,
+                                renderer_resources.globals_layout.clone(),
+                            ),
+                            camera: Camera::default(),
+                        })
+                        .collect_vec(),
+                    active_faces: 0,
+                    collect_state: RendererCollectState::new(gpu),
+                })
+                .collect_vec(),
+            sun_shadows: true,
             shadow_texture,
             shadow_view,
+            light_shadow_texture,
+            light_shadow_view,
             config,
         }
     }
-    pub fn get_cameras(&self) -> SmallVec<[ShadowCameraData; MAX_SHADOW_CASCADES as usize]> {
-        self.cascades.iter().map(|v| (&v.camera).into()).collect()
+    /// The cameras of the cascades, followed by the cameras of every face of the light shadow maps
+    pub fn get_cameras(&self) -> Vec<ShadowCameraData> {
+        let cascades = self.cascades.iter().map(|v| (&v.camera).into());
+        let lights = self
+            .light_shadows
+            .iter()
+            .flat_map(|light| &light.faces)
+            .map(|face| (&face.camera).into());
+        cascades.chain(lights).collect()
+    }
+    /// The cameras of the cascades
+    pub fn cascade_cameras(&self) -> Vec<Camera> {
+        self.cascades.iter().map(|v| v.camera.clone()).collect()
     }
     pub fn n_cascades(&self) -> usize {
         self.cascades.len()
     }
 
     #[profiling::function]
-    pub fn update(&mut self, gpu: &Gpu, assets: &AssetCache, world: &mut World) {
-        let main_camera =
+    pub fn update(
+        &mut self,
+        gpu: &Gpu,
+        assets: &AssetCache,
+        world: &mut World,
+        lights: &Lights,
+        settings: &ShadowSettings,
+    ) {
+        let mut main_camera =
             Camera::get_active(world, main_scene(), world.resource_opt(local_user_id()))
                 .unwrap_or_default();
+        main_camera.shadows_far = main_camera.shadows_far.min(settings.distance.max(1.));
 
-        let sun_direction = if let Some(sun) = get_active_sun(world, main_scene()) {
+        let sun = get_active_sun(world, main_scene());
+        self.sun_shadows = sun.map_or(true, |sun| world.get(sun, sun_shadows()).unwrap_or(true));
+        let sun_direction = if let Some(sun) = sun {
             get_world_rotation(world, sun).unwrap().mul_vec3(Vec3::X)
         } else {
             default_sun_direction()
@@ -152,6 +271,7 @@ impl ShadowsRenderer {
                 i as u32,
                 self.config.shadow_cascades,
                 self.config.shadow_map_resolution,
+                settings.cascade_split,
             );
             cascade
                 .globals
@@ -159,6 +279,25 @@ impl ShadowsRenderer {
             cascade.camera = new_camera;
             cascade.collect_state.set_camera(gpu, i as u32 + 1);
         }
+
+        for light_shadow in &mut self.light_shadows {
+            light_shadow.active_faces = 0;
+        }
+        for (i, light) in lights.shadowed() {
+            profiling::scope!("Light shadow update");
+            let light_shadow = &mut self.light_shadows[i as usize];
+            let cameras = light.shadow_cameras();
+            light_shadow.active_faces = cameras.len();
+            for (face, camera) in light_shadow.faces.iter_mut().zip(cameras) {
+                face.globals
+                    .update(gpu, world, main_scene(), camera.projection_view());
+                face.camera = camera;
+            }
+            // The light shadows are culled right after the cascades
+            light_shadow
+                .collect_state
+                .set_camera(gpu, self.config.shadow_cascades + 1 + i);
+        }
     }
 
     pub fn stats(&self) -> String {
@@ -168,6 +307,22 @@ impl ShadowsRenderer {
         format!("shadow: {shadow_entities}/{shadow_nodes}")
     }
 
+    /// What [Self::render] draws, which is everything in the shadows once for each cascade and
+    /// face it renders
+    pub fn draw_stats(&self, world: &World, mesh_buffer: &MeshBuffer) -> DrawStats {
+        let cascades = if self.sun_shadows {
+            self.cascades.len()
+        } else {
+            0
+        };
+        let faces: usize = self
+            .light_shadows
+            .iter()
+            .map(|light_shadow| light_shadow.active_faces)
+            .sum();
+        self.renderer.draw_stats(world, mesh_buffer) * (cascades + faces) as u64
+    }
+
     pub fn dump(&self, f: &mut dyn std::io::Write) {
         // for (i, shadow) in self.cascades.iter().enumerate() {
         writeln!(f, "  shadow").ok();
@@ -190,6 +345,22 @@ impl ShadowsRenderer {
     ) {
         for cascade in self.cascades.iter_mut() {
             profiling::scope!("Shadow dynamic render");
+            if !self.sun_shadows {
+                // Cleared cascades leave everything lit
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Shadow cascade"),
+                    color_attachments: &[],
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: &cascade.dynamic_target,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(0.0),
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    }),
+                });
+                continue;
+            }
             self.renderer.run_collect(
                 gpu,
                 world,
@@ -238,6 +409,59 @@ impl ShadowsRenderer {
                 drop(render_pass);
             }
         }
+
+        for light_shadow in self.light_shadows.iter_mut() {
+            if light_shadow.active_faces == 0 {
+                continue;
+            }
+            profiling::scope!("Light shadow render");
+            // All the faces of a light share the culling by its range
+            self.renderer.run_collect(
+                gpu,
+                world,
+                assets,
+                encoder,
+                post_submit,
+                bind_groups.mesh_meta,
+                bind_groups.entities,
+                &mut light_shadow.collect_state,
+                mesh_buffer,
+            );
+            for face in light_shadow.faces[..light_shadow.active_faces].iter_mut() {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Light shadow"),
+                    color_attachments: &[],
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: &face.target,
+                        depth_ops: Some(wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(0.0),
+                            store: true,
+                        }),
+                        stencil_ops: None,
+                    }),
+                });
+
+                let globals = face.globals.create_bind_group(gpu, assets, mesh_buffer);
+
+                render_pass.set_index_buffer(
+                    mesh_buffer.index_buffer.buffer().slice(..),
+                    wgpu::IndexFormat::Uint32,
+                );
+
+                self.renderer.render(
+                    gpu,
+                    world,
+                    mesh_buffer,
+                    &mut render_pass,
+                    &light_shadow.collect_state,
+                    &BindGroups {
+                        globals,
+                        ..*bind_groups
+                    },
+                    face.target.texture.size,
+                );
+            }
+        }
     }
 }
 
@@ -248,6 +472,19 @@ struct ShadowCascade {
     collect_state: RendererCollectState,
 }
 
+struct LightShadow {
+    faces: Vec<LightShadowFace>,
+    /// The number of faces in use this frame; zero when no light got this shadow map
+    active_faces: usize,
+    collect_state: RendererCollectState,
+}
+
+struct LightShadowFace {
+    target: TextureView,
+    globals: ShadowAndUIGlobals,
+    camera: Camera,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
 #[repr(C)]
 pub struct ShadowCameraData {
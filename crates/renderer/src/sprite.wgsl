@@ -0,0 +1,116 @@
+struct SpriteParams {
+    billboard: u32,
+    padding0: u32,
+    padding1: u32,
+    padding2: u32,
+};
+
+@group(MATERIAL_BIND_GROUP)
+@binding(0)
+var<uniform> sprite_params: SpriteParams;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(1)
+var sprite_sampler: sampler;
+
+@group(MATERIAL_BIND_GROUP)
+@binding(2)
+var sprite_texture: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) texcoord: vec2<f32>,
+    @location(1) world_position: vec4<f32>,
+    @location(2) @interpolate(flat) instance_index: u32,
+    @location(3) world_right: vec3<f32>,
+    @location(4) world_up: vec3<f32>,
+};
+
+const BILLBOARD_SPHERICAL: u32 = 0u;
+const BILLBOARD_CYLINDRICAL: u32 = 1u;
+
+@vertex
+fn vs_main(@builtin(instance_index) instance_index: u32, @builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let primitive = primitives.data[instance_index];
+    let entity_loc = primitive.xy;
+    let mesh_index = get_entity_primitive_mesh(entity_loc, primitive.z);
+    let corner = get_mesh_base(mesh_index, vertex_index).position.xy;
+
+    // The quad keeps the size and position it is given, but not always its orientation
+    let mesh_to_world = get_entity_mesh_to_world(entity_loc);
+    var right = mesh_to_world[0].xyz;
+    var up = mesh_to_world[1].xyz;
+    // The first two rows of the view hold the directions of the camera
+    let view = global_params.projection_view;
+    let camera_right = normalize(vec3<f32>(view[0].x, view[1].x, view[2].x));
+    let camera_up = normalize(vec3<f32>(view[0].y, view[1].y, view[2].y));
+    if sprite_params.billboard == BILLBOARD_SPHERICAL {
+        right = camera_right * length(right);
+        up = camera_up * length(up);
+    } else if sprite_params.billboard == BILLBOARD_CYLINDRICAL {
+        right = normalize(vec3<f32>(camera_right.xy, 0.)) * length(right);
+        up = vec3<f32>(0., 0., 1.) * length(up);
+    }
+
+    // The top of the region is at the top of the quad
+    let uv = vec2<f32>(corner.x + 0.5, 0.5 - corner.y);
+    let region = get_entity_sprite_uv_or(entity_loc, vec4<f32>(0., 0., 1., 1.));
+    out.texcoord = region.xy + uv * region.zw;
+    out.world_position = vec4<f32>(mesh_to_world[3].xyz + right * corner.x + up * corner.y, 1.);
+    out.instance_index = instance_index;
+    out.world_right = normalize(right);
+    out.world_up = normalize(up);
+    out.position = global_params.projection_view * out.world_position;
+    return out;
+}
+
+fn get_sprite_color(in: VertexOutput) -> vec4<f32> {
+    let entity_loc = primitives.data[in.instance_index].xy;
+    return textureSample(sprite_texture, sprite_sampler, in.texcoord) * get_entity_color_or(entity_loc, vec4<f32>(1., 1., 1., 1.));
+}
+
+@fragment
+fn fs_shadow_main(in: VertexOutput) {
+    if get_sprite_color(in).a < 0.5 {
+        discard;
+    }
+}
+
+@fragment
+fn fs_forward_main(in: VertexOutput) -> MainFsOut {
+    let color = get_sprite_color(in);
+    if color.a < 0.01 {
+        discard;
+    }
+
+    // Sprites are unlit, and show their texture as is
+    let normal = cross(in.world_right, in.world_up);
+    var material: MaterialOutput;
+    material.base_color = color.rgb;
+    material.emissive_factor = vec3<f32>(0., 0., 0.);
+    material.opacity = color.a;
+    material.alpha_cutoff = 0.01;
+    material.shading = 0.;
+    material.normal = normal;
+    material.metallic = 0.;
+    material.roughness = 1.;
+    material.reflectivity = 0.;
+
+    return forward_out(
+        material,
+        color,
+        in.world_position,
+        quat_from_mat3(mat3x3<f32>(in.world_right, in.world_up, normal))
+    );
+}
+
+@fragment
+fn fs_outlines_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    if get_sprite_color(in).a < 0.5 {
+        discard;
+    }
+    let entity_loc = primitives.data[in.instance_index].xy;
+    return get_entity_outline_or(entity_loc, vec4<f32>(0., 0., 0., 0.));
+}
@@ -0,0 +1,417 @@
+//! Sprites, which draw a region of a texture on a quad turning to face the camera.
+//!
+//! The quads are turned on the gpu, from wherever their entity is placed, so sprites can be
+//! parented to other entities, like the health bars above characters. The region of the texture
+//! each sprite shows is read on the gpu too, so the sprites showing the same texture, like the
+//! images of a texture atlas, share a material and are drawn together.
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Weak},
+};
+
+use ambient_core::{
+    asset_cache,
+    async_ecs::async_run,
+    bounding::{local_bounding_aabb, world_bounding_aabb, world_bounding_sphere},
+    main_scene, mesh, runtime,
+    transform::{local_to_world, mesh_to_local, mesh_to_world},
+};
+use ambient_ecs::{components, query, Entity, EntityId, SystemGroup, World};
+use ambient_gpu::{
+    gpu::{Gpu, GpuKey},
+    sampler::SamplerKey,
+    shader_module::{BindGroupDesc, Shader, ShaderModule},
+    texture::Texture,
+    texture_loaders::TextureFromUrl,
+};
+use ambient_gpu_ecs::{
+    gpu_components, ComponentToGpuSystem, GpuComponentFormat, GpuWorldSyncEvent,
+    ENTITIES_BIND_GROUP,
+};
+use ambient_meshes::UnitQuadMeshKey;
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt, SyncAssetKey, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    cb,
+    download_asset::AssetError,
+    friendly_id, include_file,
+    shapes::AABB,
+};
+use glam::{vec4, Mat4, Vec3, Vec4, Vec4Swizzles};
+use parking_lot::Mutex;
+use wgpu::{util::DeviceExt, BindGroup};
+
+use crate::{
+    color, get_forward_modules, gpu_primitives_lod, gpu_primitives_mesh, material, primitives,
+    renderer_shader,
+    texture_atlas::{is_texture_atlas_entry, TextureAtlasImageFromUrl},
+    Material, RendererConfig, RendererShader, GLOBALS_BIND_GROUP, MATERIAL_BIND_GROUP,
+    PRIMITIVES_BIND_GROUP,
+};
+
+pub use ambient_ecs::generated::rendering::{
+    components::{sprite, sprite_billboard, sprite_pixels_per_unit, sprite_region},
+    types::SpriteBillboard,
+};
+
+components!("rendering", {
+    /// The texture of the `sprite` of an entity, once loaded
+    sprite_texture: Arc<Texture>,
+    /// The region of the texture of the `sprite` of an entity covered by its image, when it is an
+    /// image of a texture atlas
+    sprite_atlas_region: Vec4,
+    /// The region of the texture of the `sprite` of an entity, as the offset and size of its
+    /// texture coordinates
+    sprite_uv: Vec4,
+});
+
+gpu_components! {
+    sprite_uv() => sprite_uv: GpuComponentFormat::Vec4,
+}
+
+fn get_sprite_layout() -> BindGroupDesc<'static> {
+    BindGroupDesc {
+        entries: vec![
+            // sprite_params
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            // sprite_sampler
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            // sprite_texture
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+        ],
+        label: MATERIAL_BIND_GROUP.into(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpriteShaderKey {
+    pub shadow_cascades: u32,
+}
+impl SyncAssetKey<Arc<RendererShader>> for SpriteShaderKey {
+    fn load(&self, assets: AssetCache) -> Arc<RendererShader> {
+        let shader = Shader::new(
+            &assets,
+            "sprite",
+            &[
+                GLOBALS_BIND_GROUP,
+                ENTITIES_BIND_GROUP,
+                PRIMITIVES_BIND_GROUP,
+                MATERIAL_BIND_GROUP,
+            ],
+            &ShaderModule::new("Sprite", include_file!("sprite.wgsl"))
+                .with_binding_desc(get_sprite_layout())
+                .with_dependencies(get_forward_modules(&assets, self.shadow_cascades)),
+        )
+        .unwrap();
+
+        // Sprites are blended, so the transparent renderer draws them back to front
+        Arc::new(RendererShader {
+            shader,
+            id: "sprite".to_string(),
+            vs_main: "vs_main".to_string(),
+            fs_forward_main: "fs_forward_main".to_string(),
+            fs_shadow_main: "fs_shadow_main".to_string(),
+            fs_outline_main: "fs_outlines_main".to_string(),
+            transparent: true,
+            double_sided: true,
+            depth_write_enabled: false,
+            transparency_group: 0,
+        })
+    }
+}
+
+pub fn get_sprite_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<RendererShader> {
+    SpriteShaderKey {
+        shadow_cascades: config.shadow_cascades,
+    }
+    .get(assets)
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct SpriteParams {
+    billboard: u32,
+    _padding: [u32; 3],
+}
+
+/// The material of the sprites showing a texture, each of which shows its `sprite_uv` region of it
+#[derive(Debug)]
+pub struct SpriteMaterial {
+    id: String,
+    _buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+impl SpriteMaterial {
+    pub fn new(
+        gpu: &Gpu,
+        assets: &AssetCache,
+        texture: &Texture,
+        billboard: SpriteBillboard,
+    ) -> Self {
+        let layout = get_sprite_layout().get(assets);
+        let params = SpriteParams {
+            billboard: billboard as u32,
+            _padding: Default::default(),
+        };
+        let buffer = gpu
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("SpriteMaterial.buffer"),
+                usage: wgpu::BufferUsages::UNIFORM,
+                contents: bytemuck::cast_slice(&[params]),
+            });
+        let view = texture.create_view(&Default::default());
+        let sampler = SamplerKey::LINEAR_CLAMP_TO_EDGE.get(assets);
+
+        Self {
+            id: friendly_id(),
+            bind_group: gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::Buffer(buffer.as_entire_buffer_binding()),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                ],
+                label: Some("SpriteMaterial.bind_group"),
+            }),
+            _buffer: buffer,
+        }
+    }
+}
+impl Material for SpriteMaterial {
+    fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// The materials of the sprites, by the id of their texture and their billboard
+#[derive(Debug)]
+struct SpriteMaterialsKey;
+impl SyncAssetKey<Arc<Mutex<HashMap<(u32, u32), Weak<SpriteMaterial>>>>> for SpriteMaterialsKey {
+    fn load(&self, _assets: AssetCache) -> Arc<Mutex<HashMap<(u32, u32), Weak<SpriteMaterial>>>> {
+        Default::default()
+    }
+}
+
+/// Returns the material shared by the sprites showing `texture` with `billboard`
+fn get_sprite_material(
+    gpu: &Gpu,
+    assets: &AssetCache,
+    texture: &Texture,
+    billboard: SpriteBillboard,
+) -> Arc<SpriteMaterial> {
+    let materials = SpriteMaterialsKey.get(assets);
+    let mut materials = materials.lock();
+    let key = (texture.id, billboard as u32);
+    if let Some(material) = materials.get(&key).and_then(Weak::upgrade) {
+        return material;
+    }
+
+    let material = Arc::new(SpriteMaterial::new(gpu, assets, texture, billboard));
+    materials.retain(|_, material| material.strong_count() > 0);
+    materials.insert(key, Arc::downgrade(&material));
+    material
+}
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "sprites",
+        vec![
+            query(sprite().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in q.collect_cloned(world, qs) {
+                    load_sprite_texture(world, id, url);
+                }
+            }),
+            query((sprite(), sprite_texture().changed()))
+                .optional_changed(sprite_region())
+                .optional_changed(sprite_atlas_region())
+                .optional_changed(sprite_pixels_per_unit())
+                .optional_changed(sprite_billboard())
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        update_sprite(world, id);
+                    }
+                }),
+            query(sprite()).despawned().to_system(|q, world, qs, _| {
+                for (id, _) in q.collect_cloned(world, qs) {
+                    world
+                        .remove_components(
+                            id,
+                            vec![
+                                sprite_texture().desc(),
+                                sprite_atlas_region().desc(),
+                                sprite_uv().desc(),
+                                primitives().desc(),
+                                renderer_shader().desc(),
+                                material().desc(),
+                                mesh().desc(),
+                                mesh_to_local().desc(),
+                            ],
+                        )
+                        .ok();
+                }
+            }),
+        ],
+    )
+}
+
+pub fn gpu_world_systems(gpu: Arc<Gpu>) -> SystemGroup<GpuWorldSyncEvent> {
+    SystemGroup::new(
+        "sprites/gpu_world_update",
+        vec![Box::new(ComponentToGpuSystem::new(
+            gpu,
+            GpuComponentFormat::Vec4,
+            sprite_uv(),
+            gpu_components::sprite_uv(),
+        ))],
+    )
+}
+
+fn load_sprite_texture(world: &World, id: EntityId, url: String) {
+    let abs_url = match AbsAssetUrl::from_str(&url) {
+        Ok(value) => value,
+        Err(err) => {
+            tracing::warn!("Failed to parse sprite url: {:?}", err);
+            return;
+        }
+    };
+    let assets = world.resource(asset_cache()).clone();
+    let async_run = world.resource(async_run()).clone();
+    world.resource(runtime()).spawn(async move {
+        let texture = async {
+            // The images of texture atlases are regions of their atlas image
+            let (texture_url, atlas_region) = if is_texture_atlas_entry(&abs_url) {
+                let image = TextureAtlasImageFromUrl(abs_url).get(&assets).await?;
+                (image.url.clone(), Some(image.entry.region))
+            } else {
+                (abs_url, None)
+            };
+            let texture = TextureFromUrl {
+                url: texture_url,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            }
+            .get(&assets)
+            .await?;
+            Ok::<_, AssetError>((texture, atlas_region))
+        }
+        .await;
+        match texture {
+            Err(err) => {
+                tracing::warn!("Failed to load sprite texture: {:?}", err);
+            }
+            Ok((texture, atlas_region)) => {
+                async_run.run(move |world| {
+                    // The sprite may have been changed or removed while its texture loaded
+                    if world.get_ref(id, sprite()).is_ok_and(|value| *value == url) {
+                        if let Some(region) = atlas_region {
+                            world
+                                .add_component(id, sprite_atlas_region(), region)
+                                .unwrap();
+                        } else if world.has_component(id, sprite_atlas_region()) {
+                            world.remove_component(id, sprite_atlas_region()).unwrap();
+                        }
+                        world.add_component(id, sprite_texture(), texture).unwrap();
+                    }
+                });
+            }
+        }
+    });
+}
+
+/// Sizes the quad of a sprite, and gives it a material showing its region of the texture
+fn update_sprite(world: &mut World, id: EntityId) {
+    let assets = world.resource(asset_cache()).clone();
+    let gpu = GpuKey.get(&assets);
+    let texture = world.get_ref(id, sprite_texture()).unwrap().clone();
+    let (width, height) = (texture.size.width as f32, texture.size.height as f32);
+    let region = world
+        .get(id, sprite_region())
+        .or_else(|_| world.get(id, sprite_atlas_region()))
+        .unwrap_or(vec4(0., 0., width, height));
+    let pixels_per_unit = world
+        .get(id, sprite_pixels_per_unit())
+        .unwrap_or(100.)
+        .max(0.001);
+    let billboard = world.get(id, sprite_billboard()).unwrap_or_default();
+    let size = region.zw() / pixels_per_unit;
+
+    // Billboards may turn in any direction around their center
+    let aabb = match billboard {
+        SpriteBillboard::Fixed => AABB {
+            min: (-size / 2.).extend(0.),
+            max: (size / 2.).extend(0.),
+        },
+        _ => AABB {
+            min: Vec3::splat(-size.length() / 2.),
+            max: Vec3::splat(size.length() / 2.),
+        },
+    };
+    let sprite_material = get_sprite_material(&gpu, &assets, &texture, billboard);
+
+    let mut data = Entity::new()
+        .with(mesh(), UnitQuadMeshKey.get(&assets))
+        .with(renderer_shader(), cb(get_sprite_shader))
+        .with(material(), sprite_material.into())
+        .with(sprite_uv(), region / vec4(width, height, width, height))
+        .with(mesh_to_local(), Mat4::from_scale(size.extend(1.)))
+        .with(local_bounding_aabb(), aabb);
+    if !world.has_component(id, primitives()) {
+        let local_to_world = world.get(id, local_to_world()).unwrap_or_default();
+        let world_box = aabb.transform(&local_to_world);
+        data.set(primitives(), vec![]);
+        data.set(gpu_primitives_mesh(), Default::default());
+        data.set(gpu_primitives_lod(), Default::default());
+        data.set(local_to_world(), local_to_world);
+        data.set(
+            mesh_to_world(),
+            local_to_world * Mat4::from_scale(size.extend(1.)),
+        );
+        data.set(world_bounding_aabb(), world_box.to_aabb());
+        data.set(world_bounding_sphere(), world_box.to_sphere());
+    }
+    if !world.has_component(id, color()) {
+        data.set(color(), Vec4::ONE);
+    }
+    if !world.has_component(id, main_scene()) {
+        data.set(main_scene(), ());
+    }
+    world.add_components(id, data).unwrap();
+}
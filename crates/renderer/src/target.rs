@@ -24,6 +24,10 @@ pub(crate) fn to_linear_format(format: TextureFormat) -> TextureFormat {
     }
 }
 
+/// The format of the material buffer, which holds the world space normal of each pixel, and how
+/// strongly it reflects its surroundings
+pub const MATERIAL_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
 #[derive(Debug)]
 pub struct RenderTarget {
     pub depth_buffer: Arc<Texture>,
@@ -33,6 +37,8 @@ pub struct RenderTarget {
     pub color_buffer_view: TextureView,
     pub normals_quat_buffer: Arc<Texture>,
     pub normals_quat_buffer_view: TextureView,
+    pub material_buffer: Arc<Texture>,
+    pub material_buffer_view: TextureView,
 }
 impl RenderTarget {
     pub fn new(gpu: &Gpu, size: UVec2, usage: Option<wgpu::TextureUsages>) -> Self {
@@ -93,6 +99,23 @@ impl RenderTarget {
                 view_formats: &[],
             },
         ));
+        let material_buffer = Arc::new(Texture::new(
+            gpu,
+            &wgpu::TextureDescriptor {
+                label: Some("RenderTarget.material_buffer"),
+                size: wgpu::Extent3d {
+                    width: sc_desc.width,
+                    height: sc_desc.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: MATERIAL_FORMAT,
+                usage,
+                view_formats: &[],
+            },
+        ));
         Self {
             depth_buffer_view: depth_buffer.create_view(&TextureViewDescriptor {
                 aspect: wgpu::TextureAspect::DepthOnly,
@@ -106,6 +129,8 @@ impl RenderTarget {
             color_buffer,
             normals_quat_buffer_view: normals_buffer.create_view(&Default::default()),
             normals_quat_buffer: normals_buffer,
+            material_buffer_view: material_buffer.create_view(&Default::default()),
+            material_buffer,
         }
     }
 }
@@ -0,0 +1,98 @@
+//! Texture atlases: many small images packed into a single texture, so UI skins and sprites can
+//! all be drawn from it.
+//!
+//! The `TextureAtlases` asset pipeline writes an atlas as one or more images, its pages, and a
+//! `.atlas.json` description of where each image ended up. An image of an atlas is referred to by
+//! the url of the description, with the name of the image as its fragment, like
+//! `ui.atlas.json#button`.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKey, AsyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::{AssetError, JsonFromUrl},
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use glam::Vec4;
+use serde::{Deserialize, Serialize};
+
+/// A texture atlas, as written by the asset pipeline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextureAtlasDesc {
+    /// The atlas images; the images are spread over several pages if they don't fit in one
+    pub pages: Vec<TextureAtlasPage>,
+    /// The images packed into the atlas, by name
+    pub entries: BTreeMap<String, TextureAtlasEntry>,
+}
+
+/// An atlas image of a [TextureAtlasDesc]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextureAtlasPage {
+    /// The path of the atlas image, relative to the description
+    pub image: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// An image packed into a [TextureAtlasDesc]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TextureAtlasEntry {
+    /// The index of the page the image is on
+    #[serde(default)]
+    pub page: usize,
+    /// The region of the atlas image covered by the image, as x, y, width and height in pixels
+    pub region: Vec4,
+    /// The same region, as the offset and size of its texture coordinates
+    pub uv: Vec4,
+    /// The nine-slice insets of the image, as top, right, bottom and left in pixels
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slice: Option<Vec4>,
+}
+
+/// Returns true if `url` refers to an image of a texture atlas, like `ui.atlas.json#button`
+pub fn is_texture_atlas_entry(url: &AbsAssetUrl) -> bool {
+    url.0.path().ends_with(".atlas.json") && url.0.fragment().is_some()
+}
+
+/// An image of a texture atlas, resolved to the atlas image it's in
+#[derive(Debug, Clone)]
+pub struct TextureAtlasImage {
+    /// The url of the atlas image
+    pub url: AbsAssetUrl,
+    pub entry: TextureAtlasEntry,
+}
+
+/// Resolves a url like `ui.atlas.json#button` to the image it refers to
+#[derive(Debug, Clone)]
+pub struct TextureAtlasImageFromUrl(pub AbsAssetUrl);
+#[async_trait]
+impl AsyncAssetKey<Result<Arc<TextureAtlasImage>, AssetError>> for TextureAtlasImageFromUrl {
+    async fn load(self, assets: AssetCache) -> Result<Arc<TextureAtlasImage>, AssetError> {
+        let mut desc_url = self.0;
+        let name = desc_url
+            .0
+            .fragment()
+            .context("Texture atlas urls need the name of an image as their fragment")?
+            .to_string();
+        desc_url.0.set_fragment(None);
+
+        let desc = JsonFromUrl::<TextureAtlasDesc>::new(desc_url.clone(), true)
+            .get(&assets)
+            .await?;
+        let entry = *desc
+            .entries
+            .get(&name)
+            .with_context(|| format!("The texture atlas {desc_url} has no image named {name}"))?;
+        let page = desc
+            .pages
+            .get(entry.page)
+            .with_context(|| format!("The texture atlas {desc_url} has no page {}", entry.page))?;
+        let url = desc_url
+            .resolve(&page.image)
+            .context("Invalid atlas image url")?;
+        Ok(Arc::new(TextureAtlasImage { url, entry }))
+    }
+}
@@ -0,0 +1,459 @@
+//! Streaming of the textures of PBR materials.
+//!
+//! Materials are loaded with their textures at a low resolution, and the mips they need to look
+//! sharp from the camera are uploaded, and dropped again, as it moves. The textures of a material
+//! are streamed together, by swapping the material of the entities using it for one with more or
+//! fewer mips, while the total stays within the `texture_memory_budget` setting.
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use ambient_core::{
+    asset_cache,
+    bounding::world_bounding_sphere,
+    camera::{get_active_camera, projection},
+    gpu, main_scene,
+    player::local_user_id,
+    runtime,
+    transform::get_world_position,
+    window::window_physical_size,
+};
+use ambient_ecs::{
+    components, query, Debuggable, EntityId, FnSystem, Resource, SystemGroup, World,
+};
+use ambient_gpu::{
+    basis::{BasisFileFromUrl, TextureMipFromBasis, TranscodeFormat},
+    gpu::GpuKey,
+    texture::TextureView,
+    texture_loaders::{Rgba8ImageKey, TextureMipFromRgba8Image},
+};
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt, SyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    download_asset::AssetError,
+    friendly_id,
+};
+use ambient_settings::SettingsKey;
+use as_any::Downcast;
+use glam::{uvec2, UVec2};
+use parking_lot::Mutex;
+
+use crate::{
+    materials::pbr_material::PbrMaterial, primitives, texture_streaming_priority, SharedMaterial,
+};
+
+/// The largest side, in pixels, of the textures of a material when they are at their lowest
+/// resolution, which always stays resident
+pub const BASE_TEXTURE_SIZE: u32 = 256;
+/// The number of materials whose textures can be loading at once
+const MAX_LOADING: usize = 4;
+const DEFAULT_BUDGET_MIB: u64 = 1024;
+
+/// The memory used by the streamed textures
+#[derive(Debug, Clone, Default)]
+pub struct TextureStreamingStats {
+    /// The number of streamed materials used in the scene
+    pub materials: usize,
+    /// The number of materials whose textures are being loaded
+    pub loading: usize,
+    pub resident_bytes: u64,
+    /// The memory the textures would use if they all had the detail they need
+    pub wanted_bytes: u64,
+    pub budget_bytes: u64,
+}
+impl fmt::Display for TextureStreamingStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MIB: u64 = 1024 * 1024;
+        write!(
+            f,
+            "textures: {}/{} MiB (wants {} MiB, {} loading)",
+            self.resident_bytes / MIB,
+            self.budget_bytes / MIB,
+            self.wanted_bytes / MIB,
+            self.loading
+        )
+    }
+}
+
+components!("rendering", {
+    @[Debuggable, Resource]
+    texture_streaming_stats: TextureStreamingStats,
+});
+
+/// Where the mips of a streamed texture come from
+#[derive(Debug, Clone)]
+pub enum StreamedSource {
+    /// An image, which is decoded again whenever a mip of it is uploaded
+    Image(Rgba8ImageKey),
+    /// A texture compressed by the asset pipeline, whose mips are transcoded when they are
+    /// uploaded
+    Basis(AbsAssetUrl),
+}
+
+/// A streamed texture
+#[derive(Debug)]
+pub struct StreamedImage {
+    pub source: StreamedSource,
+    pub format: wgpu::TextureFormat,
+    pub size: UVec2,
+    /// The memory used by a texel of the texture on the GPU
+    pub bytes_per_texel: u64,
+}
+
+/// The textures of a material, which are streamed together. A mip of them is the mip of their
+/// largest texture, and the smaller textures are scaled down with it.
+#[derive(Debug)]
+pub struct StreamedTextures {
+    pub id: String,
+    /// The base color, normal map and metallic roughness textures
+    pub images: [Option<StreamedImage>; 3],
+    /// The largest side of the largest texture, in pixels
+    pub size: u32,
+    /// The mip at which the textures fit in [BASE_TEXTURE_SIZE]
+    pub base_mip: u32,
+}
+impl StreamedTextures {
+    pub async fn load(
+        assets: &AssetCache,
+        images: [Option<(StreamedSource, wgpu::TextureFormat)>; 3],
+    ) -> Result<Self, AssetError> {
+        let mut streamed = [None, None, None];
+        for (out, image) in streamed.iter_mut().zip(images) {
+            if let Some((source, format)) = image {
+                let (size, bytes_per_texel) = match &source {
+                    StreamedSource::Image(image) => {
+                        let image = image.get(assets).await?;
+                        (uvec2(image.width(), image.height()), 4)
+                    }
+                    StreamedSource::Basis(url) => {
+                        let file = BasisFileFromUrl { url: url.clone() }.get(assets).await?;
+                        let gpu = GpuKey.get(assets);
+                        (
+                            file.mips[0],
+                            TranscodeFormat::for_gpu(&gpu).bytes_per_texel(),
+                        )
+                    }
+                };
+                *out = Some(StreamedImage {
+                    source,
+                    format,
+                    size,
+                    bytes_per_texel,
+                });
+            }
+        }
+        let size = streamed
+            .iter()
+            .flatten()
+            .map(|image| image.size.max_element())
+            .max()
+            .unwrap_or(0);
+        let mut base_mip = 0;
+        while size >> base_mip > BASE_TEXTURE_SIZE {
+            base_mip += 1;
+        }
+        Ok(Self {
+            id: friendly_id(),
+            images: streamed,
+            size,
+            base_mip,
+        })
+    }
+
+    /// The mip of `image` for the mip `mip` of all of the textures
+    fn image_mip(&self, image: &StreamedImage, mip: u32) -> u32 {
+        mip.saturating_sub(self.size.ilog2() - image.size.max_element().max(1).ilog2())
+    }
+
+    /// The memory the textures use at `mip`, with their smaller mips
+    pub fn bytes(&self, mip: u32) -> u64 {
+        self.images
+            .iter()
+            .flatten()
+            .map(|image| {
+                let mip = self.image_mip(image, mip);
+                let size = (image.size >> mip).max(UVec2::ONE);
+                size.x as u64 * size.y as u64 * image.bytes_per_texel * 4 / 3
+            })
+            .sum()
+    }
+
+    /// The mip at which the textures have about one texel per pixel, for a material covering
+    /// `pixels` pixels across the screen
+    pub fn wanted_mip(&self, pixels: f32) -> u32 {
+        if pixels >= self.size as f32 {
+            0
+        } else {
+            ((self.size as f32 / pixels.max(1.)).log2() as u32).min(self.base_mip)
+        }
+    }
+
+    /// Uploads the textures at `mip`, in the order of [Self::images]
+    pub async fn load_views(
+        &self,
+        assets: &AssetCache,
+        mip: u32,
+    ) -> Result<[Option<Arc<TextureView>>; 3], AssetError> {
+        let mut views = [None, None, None];
+        for (view, image) in views.iter_mut().zip(&self.images) {
+            if let Some(image) = image {
+                let mip = self.image_mip(image, mip);
+                let texture = match &image.source {
+                    StreamedSource::Image(source) => {
+                        TextureMipFromRgba8Image {
+                            image: source.clone(),
+                            format: image.format,
+                            mip,
+                        }
+                        .get(assets)
+                        .await?
+                    }
+                    StreamedSource::Basis(url) => {
+                        TextureMipFromBasis {
+                            url: url.clone(),
+                            srgb: image.format.is_srgb(),
+                            mip,
+                        }
+                        .get(assets)
+                        .await?
+                    }
+                };
+                *view = Some(Arc::new(texture.create_view(&Default::default())));
+            }
+        }
+        Ok(views)
+    }
+}
+
+struct StreamedMaterial {
+    textures: Arc<StreamedTextures>,
+    /// The material the other mips are made from
+    base: SharedMaterial,
+    /// The material the entities are given
+    current: SharedMaterial,
+    resident_mip: u32,
+    loading: Option<u32>,
+    /// Set if the textures failed to load, in which case they stay at their current mip
+    failed: bool,
+}
+
+#[derive(Default)]
+struct TextureStreamer {
+    materials: HashMap<String, StreamedMaterial>,
+}
+
+/// The detail the entities using a streamed material need
+struct Wanted {
+    mip: u32,
+    /// The priority of the entities, scaled by the pixels they cover
+    score: f32,
+}
+
+pub fn systems() -> SystemGroup {
+    let streamer = Arc::new(Mutex::new(TextureStreamer::default()));
+    SystemGroup::new(
+        "renderer/texture_streaming",
+        vec![Box::new(FnSystem::new(move |world, _| {
+            update(world, &streamer);
+        }))],
+    )
+}
+
+fn update(world: &mut World, streamer: &Arc<Mutex<TextureStreamer>>) {
+    let camera = get_active_camera(world, main_scene(), world.resource_opt(local_user_id()))
+        .and_then(|id| {
+            Some((
+                get_world_position(world, id).ok()?,
+                world.get(id, projection()).ok()?,
+            ))
+        });
+    let screen_height = world
+        .resource_opt(window_physical_size())
+        .map_or(1080, |size| size.y) as f32;
+
+    let mut streamer_guard = streamer.lock();
+    let streamer_state = &mut *streamer_guard;
+    let mut wanted = HashMap::<String, Wanted>::new();
+    // The streamed primitives, and which material they use
+    let mut used = Vec::new();
+    for (id, primitives) in query(primitives()).incl(main_scene()).iter(world, None) {
+        let priority = world.get(id, texture_streaming_priority()).unwrap_or(1.);
+        // The diameter of the entity on the screen, in pixels
+        let pixels = match (camera, world.get(id, world_bounding_sphere())) {
+            (Some((position, projection)), Ok(sphere)) => {
+                sphere.radius * projection.y_axis.y * screen_height
+                    / position.distance(sphere.center).max(sphere.radius)
+            }
+            _ => f32::MAX,
+        };
+        for (index, primitive) in primitives.iter().enumerate() {
+            let Some(material) = (*primitive.material.0).downcast_ref::<PbrMaterial>() else {
+                continue;
+            };
+            let Some(textures) = material.streamed() else {
+                continue;
+            };
+            let mip = if priority > 0. {
+                textures.wanted_mip(pixels)
+            } else {
+                textures.base_mip
+            };
+            let entry = wanted.entry(textures.id.clone()).or_insert(Wanted {
+                mip: textures.base_mip,
+                score: 0.,
+            });
+            entry.mip = entry.mip.min(mip);
+            entry.score = entry.score.max(priority * pixels.min(textures.size as f32));
+
+            streamer_state
+                .materials
+                .entry(textures.id.clone())
+                .or_insert_with(|| StreamedMaterial {
+                    textures: textures.clone(),
+                    base: primitive.material.clone(),
+                    current: primitive.material.clone(),
+                    resident_mip: material.resident_mip(),
+                    loading: None,
+                    failed: false,
+                });
+            used.push((id, index, textures.id.clone(), material.id().to_string()));
+        }
+    }
+    // Nothing uses the other materials anymore, so their mips can be freed
+    streamer_state
+        .materials
+        .retain(|id, _| wanted.contains_key(id));
+
+    // The materials covering the most of the screen get their detail first
+    let budget = SettingsKey
+        .get(world.resource(asset_cache()))
+        .render
+        .texture_memory_budget
+        .unwrap_or(DEFAULT_BUDGET_MIB)
+        * 1024
+        * 1024;
+    let mut order = wanted.iter().collect::<Vec<_>>();
+    order.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+    let mut total = streamer_state
+        .materials
+        .values()
+        .map(|streamed| streamed.textures.bytes(streamed.textures.base_mip))
+        .sum::<u64>();
+    let mut targets = HashMap::new();
+    for (id, wanted) in order {
+        let textures = &streamer_state.materials[id].textures;
+        let base = textures.bytes(textures.base_mip);
+        let mut mip = wanted.mip;
+        while mip < textures.base_mip && total + textures.bytes(mip) - base > budget {
+            mip += 1;
+        }
+        total += textures.bytes(mip) - base;
+        targets.insert(id.clone(), mip);
+    }
+
+    let assets = world.resource(asset_cache()).clone();
+    let mut loading = streamer_state
+        .materials
+        .values()
+        .filter(|streamed| streamed.loading.is_some())
+        .count();
+    for (id, streamed) in streamer_state.materials.iter_mut() {
+        let target = targets[id];
+        if target == streamed.resident_mip || streamed.loading.is_some() {
+            continue;
+        }
+        let base = streamed.base.borrow_downcast::<PbrMaterial>();
+        if target == base.resident_mip() {
+            streamed.current = streamed.base.clone();
+            streamed.resident_mip = target;
+            continue;
+        }
+        if streamed.failed || loading >= MAX_LOADING {
+            continue;
+        }
+        loading += 1;
+        streamed.loading = Some(target);
+
+        let id = id.clone();
+        let textures = streamed.textures.clone();
+        let base = streamed.base.clone();
+        let streamer = streamer.clone();
+        let assets = assets.clone();
+        let gpu = world.resource(gpu()).clone();
+        world.resource(runtime()).spawn(async move {
+            let material = match textures.load_views(&assets, target).await {
+                Ok(views) => Some(SharedMaterial::new(
+                    base.borrow_downcast::<PbrMaterial>()
+                        .with_mip(&gpu, &assets, views, target),
+                )),
+                Err(err) => {
+                    tracing::warn!("Failed to stream textures: {:?}", err);
+                    None
+                }
+            };
+            let mut streamer = streamer.lock();
+            if let Some(streamed) = streamer.materials.get_mut(&id) {
+                streamed.loading = None;
+                match material {
+                    Some(material) => {
+                        streamed.current = material;
+                        streamed.resident_mip = target;
+                    }
+                    None => streamed.failed = true,
+                }
+            }
+        });
+    }
+
+    world.add_resource(
+        texture_streaming_stats(),
+        TextureStreamingStats {
+            materials: streamer_state.materials.len(),
+            loading,
+            resident_bytes: streamer_state
+                .materials
+                .values()
+                .map(|streamed| streamed.textures.bytes(streamed.resident_mip))
+                .sum(),
+            wanted_bytes: wanted
+                .iter()
+                .map(|(id, wanted)| streamer_state.materials[id].textures.bytes(wanted.mip))
+                .sum(),
+            budget_bytes: budget,
+        },
+    );
+
+    // Give the entities the materials with the mips which are now resident
+    let mut outdated = HashMap::<EntityId, Vec<(usize, SharedMaterial)>>::new();
+    for (id, index, textures, material) in used {
+        let current = &streamer_state.materials[&textures].current;
+        if current.id() != material {
+            outdated
+                .entry(id)
+                .or_default()
+                .push((index, current.clone()));
+        }
+    }
+    drop(streamer_guard);
+    for (id, materials) in outdated {
+        swap_materials(world, id, materials);
+    }
+}
+
+fn swap_materials(world: &mut World, id: EntityId, materials: Vec<(usize, SharedMaterial)>) {
+    let Ok(mut entity_primitives) = world.get_cloned(id, primitives()) else {
+        return;
+    };
+    for (index, material) in materials {
+        // Entities with a `material` rebuild their primitives from it when it changes
+        if world
+            .get_ref(id, crate::material())
+            .is_ok_and(|entity_material| {
+                entity_material.id() == entity_primitives[index].material.id()
+            })
+        {
+            world.set(id, crate::material(), material.clone()).unwrap();
+        }
+        entity_primitives[index].material = material;
+    }
+    world.set(id, primitives(), entity_primitives).unwrap();
+}
@@ -1,6 +1,6 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
 
-use ambient_core::transform::local_to_world;
+use ambient_core::{camera::Camera, transform::local_to_world};
 use ambient_ecs::{query, ArchetypeFilter, EntityId, QueryState, World};
 use ambient_gpu::{
     gpu::Gpu,
@@ -10,7 +10,7 @@ use ambient_gpu::{
 };
 use ambient_native_std::asset_cache::AssetCache;
 use ambient_settings::RenderSettings;
-use glam::{Mat4, UVec4, Vec3};
+use glam::{UVec4, Vec3};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 
@@ -19,8 +19,9 @@ use super::{
     SharedMaterial,
 };
 use crate::{
-    bind_groups::BindGroups, is_transparent, scissors, set_scissors_safe, transparency_group,
-    RendererConfig,
+    bind_groups::BindGroups, get_oit_accumulation_target, get_oit_revealage_target, is_transparent,
+    scissors, set_scissors_safe, transparency_group, transparency_sort_bias, DrawStats,
+    OrderIndependentTransparency, RendererConfig, RendererTarget,
 };
 
 pub struct TransparentRendererConfig {
@@ -41,12 +42,14 @@ pub struct TransparentRenderer {
 
     gpu_primitives: TypedBuffer<UVec4>,
     primitives_bind_group: wgpu::BindGroup,
+    /// With the `order_independent_transparency` setting, blends the primitives without sorting
+    order_independent: Option<OrderIndependentTransparency>,
 
     spawn_qs: QueryState,
     despawn_qs: QueryState,
 }
 impl TransparentRenderer {
-    pub fn new(gpu: &Gpu, config: TransparentRendererConfig) -> Self {
+    pub fn new(gpu: &Gpu, assets: &AssetCache, config: TransparentRendererConfig) -> Self {
         let gpu_primitives = TypedBuffer::new(
             gpu,
             Some("TransparentRenderer.primitives"),
@@ -67,6 +70,10 @@ impl TransparentRenderer {
                 gpu_primitives.buffer(),
             ),
             gpu_primitives,
+            order_independent: config
+                .settings
+                .order_independent_transparency
+                .then(|| OrderIndependentTransparency::new(gpu, assets)),
             config: Arc::new(config),
 
             spawn_qs: QueryState::new(),
@@ -80,7 +87,7 @@ impl TransparentRenderer {
         assets: &AssetCache,
         world: &mut World,
         mesh_buffer: &MeshBuffer,
-        camera_projection_view: Mat4,
+        camera: &Camera,
     ) {
         let mut spawn_qs = std::mem::replace(&mut self.spawn_qs, QueryState::new());
         let mut despawn_qs = std::mem::replace(&mut self.despawn_qs, QueryState::new());
@@ -132,6 +139,10 @@ impl TransparentRenderer {
                                 .transparency_group()
                                 .unwrap_or(primitive_shader.transparency_group),
                         ),
+                        transparency_sort_bias: primitive
+                            .material
+                            .transparency_sort_bias()
+                            .unwrap_or_default(),
                     });
                 }
             }
@@ -158,12 +169,15 @@ impl TransparentRenderer {
             let mesh = &primitives[entry.primitive_index].mesh;
             entry.mesh_metadata = *mesh_buffer.get_mesh_metadata(mesh);
         }
-        // TODO: Sort entities by distance to camera
-        self.primitives.sort_by_key(|x| {
+        // Back to front within each group, by the depth of their origin along the view. The bias
+        // is read every frame so that it can be animated
+        self.primitives.sort_by_cached_key(|x| {
             let ltw = world.get(x.id, local_to_world()).unwrap();
-            let transf = camera_projection_view * ltw;
-            let point = transf.project_point3(Vec3::ZERO);
-            (x.transparency_group, OrderedFloat(point.z))
+            let depth = (camera.view * ltw).transform_point3(Vec3::ZERO).z;
+            let bias = world
+                .get(x.id, transparency_sort_bias())
+                .unwrap_or(x.transparency_sort_bias);
+            (x.transparency_group, Reverse(OrderedFloat(depth - bias)))
         });
 
         if self.gpu_primitives.set_len(gpu, self.primitives.len()) {
@@ -190,6 +204,12 @@ impl TransparentRenderer {
             .retain(|x| !(x.id == id && x.primitive_index == primitive_index));
     }
 
+    /// Whether the primitives are drawn with [Self::render_order_independent], rather than
+    /// [Self::render]
+    pub fn is_order_independent(&self) -> bool {
+        self.order_independent.is_some()
+    }
+
     #[profiling::function]
     pub fn render<'a>(
         &'a self,
@@ -197,10 +217,99 @@ impl TransparentRenderer {
         render_pass: &mut wgpu::RenderPass<'a>,
         bind_groups: &BindGroups<'a>,
         render_target_size: wgpu::Extent3d,
+    ) {
+        self.draw(
+            world,
+            render_pass,
+            bind_groups,
+            render_target_size,
+            |shader| &shader.pipeline,
+        );
+    }
+
+    /// Draws the primitives to the accumulation and revealage images of the order-independent
+    /// transparency, and blends them over the target
+    #[profiling::function]
+    pub fn render_order_independent(
+        &mut self,
+        gpu: &Gpu,
+        world: &World,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &RendererTarget,
+        bind_groups: &BindGroups,
+        mesh_buffer: &MeshBuffer,
+    ) {
+        let Some(order_independent) = &mut self.order_independent else {
+            return;
+        };
+        let size = target.size();
+        order_independent.resize(gpu, size);
+        let Some(order_independent) = &self.order_independent else {
+            return;
+        };
+        let (accumulation, revealage) = order_independent.views();
+
+        for (label, view, clear, is_revealage) in [
+            (
+                "Transparent.accumulation",
+                accumulation,
+                wgpu::Color::TRANSPARENT,
+                false,
+            ),
+            ("Transparent.revealage", revealage, wgpu::Color::WHITE, true),
+        ] {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some(label),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: target.depth_stencil(),
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+            render_pass.set_index_buffer(
+                mesh_buffer.index_buffer.buffer().slice(..),
+                wgpu::IndexFormat::Uint32,
+            );
+            self.draw(world, &mut render_pass, bind_groups, size, |shader| {
+                if is_revealage {
+                    shader.revealage_pipeline.as_ref().unwrap()
+                } else {
+                    &shader.pipeline
+                }
+            });
+        }
+
+        order_independent.composite(gpu, encoder, target);
+    }
+
+    fn draw<'a>(
+        &'a self,
+        world: &World,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        bind_groups: &BindGroups<'a>,
+        render_target_size: wgpu::Extent3d,
+        pipeline: impl Fn(&'a ShaderNode) -> &'a GraphicsPipeline,
     ) {
         let mut is_bound = false;
         // TODO: keep track of the state to avoid state switches (same pipeline multiple times etc.)
-        for (i, entry) in self.primitives.iter().enumerate() {
+        let mut next = 0;
+        while next < self.primitives.len() {
+            let i = next;
+            let entry = &self.primitives[i];
+            let batch_len = self.batch_len(world, i);
+            next += batch_len;
+
             let bind_groups = [
                 bind_groups.globals,
                 bind_groups.entities,
@@ -214,7 +323,7 @@ impl TransparentRenderer {
             }
             let metadata = &entry.mesh_metadata;
             if metadata.index_count > 0 {
-                render_pass.set_pipeline(entry.shader.pipeline.pipeline());
+                render_pass.set_pipeline(pipeline(&*entry.shader).pipeline());
                 render_pass.set_bind_group(
                     bind_groups.len() as _,
                     entry.material.bind_group(),
@@ -233,12 +342,32 @@ impl TransparentRenderer {
                 render_pass.draw_indexed(
                     metadata.index_offset..(metadata.index_offset + metadata.index_count),
                     0,
-                    (i as u32)..((i + 1) as u32),
+                    (i as u32)..((i + batch_len) as u32),
                 );
             }
         }
     }
 
+    /// How many primitives, from `start` on, are drawn by a single draw call. Consecutive
+    /// primitives with the same shader, material and mesh, like the sprites showing the images of
+    /// a texture atlas, are drawn as instances of the same draw call, which keeps them in order.
+    fn batch_len(&self, world: &World, start: usize) -> usize {
+        let first = &self.primitives[start];
+        if world.has_component(first.id, scissors()) {
+            return 1;
+        }
+        1 + self.primitives[start + 1..]
+            .iter()
+            .take_while(|entry| {
+                Arc::ptr_eq(&entry.shader, &first.shader)
+                    && entry.material.id() == first.material.id()
+                    && entry.mesh_metadata.index_offset == first.mesh_metadata.index_offset
+                    && entry.mesh_metadata.index_count == first.mesh_metadata.index_count
+                    && !world.has_component(entry.id, scissors())
+            })
+            .count()
+    }
+
     fn create_primitives_bind_group(
         gpu: &Gpu,
         layout: &wgpu::BindGroupLayout,
@@ -253,6 +382,21 @@ impl TransparentRenderer {
             label: Some("InstanceDataBuffer.bind_group"),
         })
     }
+    /// What [Self::render] draws
+    pub fn draw_stats(&self, world: &World) -> DrawStats {
+        let mut stats = DrawStats::default();
+        let mut next = 0;
+        while next < self.primitives.len() {
+            let entry = &self.primitives[next];
+            let batch_len = self.batch_len(world, next);
+            next += batch_len;
+            if entry.mesh_metadata.index_count > 0 {
+                stats.draw_calls += 1;
+                stats.triangles += entry.mesh_metadata.index_count as u64 / 3 * batch_len as u64;
+            }
+        }
+        stats
+    }
     pub fn n_entities(&self) -> usize {
         self.primitives.len()
     }
@@ -267,9 +411,14 @@ struct TransparentPrimitive {
     material: SharedMaterial,
     mesh_metadata: MeshMetadata,
     transparency_group: i32,
+    /// The bias of the material, used if the entity has no `transparency_sort_bias`
+    transparency_sort_bias: f32,
 }
 struct ShaderNode {
+    /// Draws to the target, or to the accumulation image of the order-independent transparency
     pipeline: GraphicsPipeline,
+    /// Draws to the revealage image of the order-independent transparency
+    revealage_pipeline: Option<GraphicsPipeline>,
 }
 impl ShaderNode {
     pub fn new(
@@ -279,28 +428,42 @@ impl ShaderNode {
         double_sided: bool,
         depth_write_enabled: bool,
     ) -> Self {
-        let pipeline = shader.shader.to_pipeline(
-            gpu,
-            GraphicsPipelineInfo {
-                vs_main: &shader.vs_main,
-                fs_main: shader.get_fs_main_name(config.fs_main),
-                depth: Some(wgpu::DepthStencilState {
-                    format: DEPTH_FORMAT,
-                    depth_write_enabled,
-                    depth_compare: wgpu::CompareFunction::Greater,
-                    stencil: wgpu::StencilState::default(),
-                    bias: wgpu::DepthBiasState::default(),
-                }),
-                targets: &config.targets,
-                cull_mode: if double_sided {
-                    None
-                } else {
-                    Some(wgpu::Face::Back)
+        let order_independent = config.settings.order_independent_transparency;
+        let pipeline = |targets: &[Option<wgpu::ColorTargetState>]| {
+            shader.shader.to_pipeline(
+                gpu,
+                GraphicsPipelineInfo {
+                    vs_main: &shader.vs_main,
+                    fs_main: shader.get_fs_main_name(config.fs_main),
+                    depth: Some(wgpu::DepthStencilState {
+                        format: DEPTH_FORMAT,
+                        // The layers behind others still need to be blended in
+                        depth_write_enabled: depth_write_enabled && !order_independent,
+                        depth_compare: wgpu::CompareFunction::Greater,
+                        stencil: wgpu::StencilState::default(),
+                        bias: wgpu::DepthBiasState::default(),
+                    }),
+                    targets,
+                    cull_mode: if double_sided {
+                        None
+                    } else {
+                        Some(wgpu::Face::Back)
+                    },
+                    ..Default::default()
                 },
-                ..Default::default()
-            },
-        );
+            )
+        };
 
-        Self { pipeline }
+        if order_independent {
+            Self {
+                pipeline: pipeline(&[Some(get_oit_accumulation_target())]),
+                revealage_pipeline: Some(pipeline(&[Some(get_oit_revealage_target())])),
+            }
+        } else {
+            Self {
+                pipeline: pipeline(&config.targets),
+                revealage_pipeline: None,
+            }
+        }
     }
 }
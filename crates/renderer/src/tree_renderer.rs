@@ -24,7 +24,7 @@ use super::{
 };
 use crate::{
     bind_groups::BindGroups, is_transparent, scissors, set_scissors_safe, DrawIndexedIndirect,
-    PostSubmitFunc, RendererConfig,
+    DrawStats, PostSubmitFunc, RendererConfig,
 };
 
 #[repr(C)]
@@ -508,8 +508,6 @@ impl TreeRenderer {
             return; // Nothing to render
         };
 
-        let count_state = collect_state.counts_cpu.lock();
-
         let mut is_bound = false;
 
         for node in self.tree.values() {
@@ -568,21 +566,19 @@ impl TreeRenderer {
                         );
                     }
                 } else if self.config.render_mode == RenderMode::Indirect {
-                    // If none, a new material has been added, but the async buffer read has
-                    // not yet finished and we are still using the previous frame's material
-                    // counts.
-                    let count = count_state
-                        .counts()
-                        .get(mat.material_index as usize)
-                        .copied()
-                        .unwrap_or(0);
-
-                    // NOTE: this issues 1 draw call *for every single visible primitive* in the scene
-                    tracing::trace!(?count, ?offset, "draw node primitives");
-                    for i in 0..count {
+                    // The commands of the culled primitives are left empty by the collect pass, so
+                    // every slot can be drawn without waiting for the counts to be read back
+                    //
+                    // NOTE: this issues 1 draw call *for every single primitive* in the scene
+                    tracing::trace!(
+                        count = mat.primitives.len(),
+                        ?offset,
+                        "draw node primitives"
+                    );
+                    for i in 0..mat.primitives.len() as u64 {
                         render_pass.draw_indexed_indirect(
                             collect_state.commands.buffer(),
-                            (offset + i as u64) * std::mem::size_of::<DrawIndexedIndirect>() as u64,
+                            (offset + i) * std::mem::size_of::<DrawIndexedIndirect>() as u64,
                         );
                     }
                 } else if self.config.render_mode == RenderMode::Direct {
@@ -603,6 +599,29 @@ impl TreeRenderer {
         }
     }
 
+    /// What [Self::render] draws, in as many draw calls as the render mode issues
+    pub fn draw_stats(&self, world: &World, mesh_buffer: &MeshBuffer) -> DrawStats {
+        let mut stats = DrawStats::default();
+        for mat in self.tree.values().flat_map(|node| node.tree.values()) {
+            stats.draw_calls += if self.config.render_mode == RenderMode::MultiIndirect {
+                1
+            } else {
+                mat.primitives.len() as u64
+            };
+            for &(id, primitive_index) in &mat.primitives {
+                if let Some(primitive) = world
+                    .get_ref(id, primitives())
+                    .ok()
+                    .and_then(|primitives| primitives.get(primitive_index))
+                {
+                    stats.triangles +=
+                        mesh_buffer.get_mesh_metadata(&primitive.mesh).index_count as u64 / 3;
+                }
+            }
+        }
+        stats
+    }
+
     pub fn n_entities(&self) -> usize {
         self.tree.values().fold(0, |p, n| p + n.n_entities())
     }
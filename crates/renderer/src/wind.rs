@@ -0,0 +1,51 @@
+//! Wind: bends the vegetation of a scene in the vertex shader, so forests don't look frozen.
+//!
+//! The wind of a scene is set with `wind_velocity` on one of its entities, and each material
+//! chooses how strongly it reacts to it; see the `wind_sway`, `wind_flutter` and `wind_height`
+//! of [crate::materials::pbr_material::PbrMaterialDesc].
+
+use std::sync::Arc;
+
+use ambient_core::game_time;
+use ambient_ecs::{query, Component, World};
+use ambient_gpu::shader_module::ShaderModule;
+use ambient_native_std::include_file;
+use glam::{Vec3, Vec4};
+
+pub use ambient_ecs::generated::rendering::components::{wind_gust_strength, wind_velocity};
+
+/// The wind blowing over scenes without a `wind_velocity`
+pub const DEFAULT_WIND_VELOCITY: Vec3 = Vec3::X;
+
+/// The wind of `scene`: its velocity in xyz, and the strength of its gusts in w
+pub fn get_active_wind(world: &World, scene: Component<()>) -> Vec4 {
+    query((scene, wind_velocity()))
+        .iter(world, None)
+        .next()
+        .map(|(id, (_, velocity))| {
+            velocity.extend(world.get(id, wind_gust_strength()).unwrap_or(0.5))
+        })
+        .unwrap_or(DEFAULT_WIND_VELOCITY.extend(0.5))
+}
+
+/// The time the wind animates with, in seconds. It follows the `game_time`, so that every client
+/// sees the same gusts
+pub fn get_wind_time(world: &World) -> f32 {
+    world
+        .resource_opt(game_time())
+        .map(|time| time.as_secs_f32())
+        .unwrap_or_default()
+}
+
+/// The module defining `apply_wind` for the standard shader. With `enabled`, it displaces the
+/// vertices with the `get_material_wind` of the material shader, otherwise it leaves them as is.
+pub fn get_wind_module(enabled: bool) -> Arc<ShaderModule> {
+    Arc::new(if enabled {
+        ShaderModule::new("wind", include_file!("wind.wgsl"))
+    } else {
+        ShaderModule::new(
+            "wind",
+            "fn apply_wind(world_position: vec4<f32>, origin: vec3<f32>) -> vec4<f32> { return world_position; }",
+        )
+    })
+}
@@ -0,0 +1,34 @@
+// Bends the vertices of vegetation with the wind. The material shader provides its wind
+// parameters with `get_material_wind`:
+// x: how far its top sways, in meters per m/s of wind
+// y: how far its leaves flutter, in meters per m/s of wind
+// z: the height above its origin at which it bends fully, in meters
+fn apply_wind(world_position: vec4<f32>, origin: vec3<f32>) -> vec4<f32> {
+    let params = get_material_wind();
+    let wind = global_params.wind;
+    let time = global_params.wind_time;
+    let speed = length(wind.xyz);
+    if speed < 0.0001 || (params.x <= 0. && params.y <= 0.) {
+        return world_position;
+    }
+    let direction = wind.xyz / speed;
+    let position = world_position.xyz / world_position.w;
+
+    // The ground stays in place, and the top bends the most
+    let height = clamp((position.z - origin.z) / max(params.z, 0.01), 0., 1.);
+    let bend = height * height;
+
+    // Gusts travel across the scene with the wind, so neighbouring plants bend together
+    let travel = dot(origin, direction) - time * speed;
+    let gust = 1. + wind.w * (0.6 * sin(travel * 0.2) + 0.4 * sin(travel * 0.53 + 1.7));
+    // Every plant also sways back and forth at its own phase
+    let phase = dot(origin, vec3<f32>(0.37, 0.71, 0.13));
+    let sway = speed * gust * (1. + 0.25 * sin(time * 1.9 + phase));
+    var offset = direction * params.x * bend * sway;
+
+    // The leaves flutter much faster, and differently at each vertex
+    let flutter_phase = dot(position, vec3<f32>(3.1, 2.7, 1.9));
+    offset += vec3<f32>(0.5, 0.5, 1.) * params.y * height * speed * gust * sin(time * 11. + flutter_phase);
+
+    return vec4<f32>(position + offset, 1.);
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// The volumes of the built-in audio buses, from 0.0 to 1.0
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub voice_volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            voice_volume: 1.0,
+            muted: false,
+        }
+    }
+}
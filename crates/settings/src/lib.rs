@@ -7,6 +7,9 @@ use std::path::PathBuf;
 #[cfg(not(target_os = "unknown"))]
 use anyhow::Context;
 
+mod audio;
+pub use audio::*;
+
 mod render;
 pub use render::*;
 
@@ -18,6 +21,8 @@ pub struct Settings {
     #[serde(default)]
     pub general: GeneralSettings,
     pub render: RenderSettings,
+    #[serde(default)]
+    pub audio: AudioSettings,
 }
 
 #[cfg(not(target_os = "unknown"))]
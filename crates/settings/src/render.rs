@@ -11,6 +11,18 @@ pub struct RenderSettings {
     pub render_mode: Option<RenderMode>,
     #[serde(default)]
     pub software_culling: bool,
+    #[serde(default)]
+    /// If `None`, objects hidden behind others are culled whenever the render mode draws the culled set
+    pub occlusion_culling: Option<bool>,
+    #[serde(default)]
+    /// The GPU memory, in MiB, that streamed textures may use. If `None`, 1024 MiB are used
+    pub texture_memory_budget: Option<u64>,
+    #[serde(default)]
+    /// Blends transparent objects with weighted order-independent transparency instead of
+    /// sorting them, so that overlapping transparent objects don't pop as the camera turns
+    pub order_independent_transparency: bool,
+    #[serde(default)]
+    pub shadows: ShadowSettings,
 }
 
 impl RenderSettings {
@@ -23,6 +35,35 @@ impl RenderSettings {
     }
 }
 
+/// The quality of the shadows of the sun, which can also be changed at runtime with the
+/// `shadow_settings` resource of the renderer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ShadowSettings {
+    /// The width and height, in pixels, of the shadow map of each cascade
+    pub resolution: u32,
+    /// The number of cascades the view is split into, from 1 to 6
+    pub cascades: u32,
+    /// How the cascades are spread up to the shadow distance, from 0 for logarithmic, which
+    /// keeps the shadows close to the camera sharpest, to 1 for linear
+    pub cascade_split: f32,
+    /// The depth offset used when comparing against the shadow maps, which hides shadow acne
+    pub bias: f32,
+    /// The distance from the camera, in meters, up to which shadows are drawn
+    pub distance: f32,
+}
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 1024,
+            cascades: 5,
+            cascade_split: 0.,
+            bias: 0.0001,
+            distance: 2000.,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RenderMode {
     MultiIndirect,
@@ -32,14 +73,13 @@ pub enum RenderMode {
 
 impl RenderMode {
     pub const fn instrinsic_render_mode() -> Self {
-        // All backends are now using Direct rendering due to a one frame lag flickering bug with culling in the indirect rendering path.
+        // The draw commands are built from the culling on the GPU in the same frame for the
+        // indirect modes, so only the visible objects are drawn without a round trip to the CPU
         cfg_if::cfg_if! {
             if #[cfg(any(target_os = "windows", target_os = "linux"))] {
-                //Self::MultiIndirect
-                Self::Direct
+                Self::MultiIndirect
             } else if #[cfg(target_os = "macos")] {
-                //Self::Indirect
-                Self::Direct
+                Self::Indirect
             } else if #[cfg(target_os = "unknown")] {
                 // Chrome uses DirectX12 which does not correctly implement `INDIRECT_FIRST_INSTANCE` which causes the wrong instance index to be passed to indirect draws.
                 // This in turn causes a dispatch of X vertices to unconditionally use instance/entity 0, and therefore the wrong mesh for the dispatch count.
@@ -142,6 +142,11 @@ fn fs_forward_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // See: <https://www.w3.org/TR/WGSL/#texturesamplelevel>
     let depth: f32 = 1.0 - textureSample(solids_screen_depth, default_sampler, in.uv);
 
+    // An environment map of the scene replaces the atmosphere
+    if has_environment_map() {
+        return vec4<f32>(get_environment_color(dir, 0.), 1.0);
+    }
+
     var color = get_sky_color(depth, global_params.camera_position.xyz, dir);
 
     return vec4<f32>(color, 1.0);
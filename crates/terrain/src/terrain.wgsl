@@ -306,8 +306,10 @@ fn fs_forward_main(in: VertexOutput) -> MainFsOut {
 
     let x = mat3_from_quat(quat_from_mat3(normal_mat)) * vec3<f32>(0., 0., 1.);
 
-    return MainFsOut(
+    return forward_out(
+        material,
         shading(material, in.world_position),
+        in.world_position,
         quat_from_mat3(normal_mat)
     );
 }
@@ -33,6 +33,7 @@ use parking_lot::Mutex;
 
 use crate::text_material::{get_text_shader, TextMaterial};
 
+pub mod localization;
 mod text_material;
 
 pub use ambient_ecs::generated::text::components::{font_family, font_size, font_style, text};
@@ -108,8 +109,9 @@ impl Default for FontFamily {
     }
 }
 
+/// A font of a [FontFamily] in a [FontStyle], which loads as a [FontArc].
 #[derive(Debug, Clone)]
-struct FontDef(FontFamily, FontStyle);
+pub struct FontDef(pub FontFamily, pub FontStyle);
 
 #[async_trait]
 impl AsyncAssetKey<Arc<FontArc>> for FontDef {
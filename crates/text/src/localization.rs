@@ -0,0 +1,153 @@
+//! Translates text into the current `locale`, with the string tables loaded by
+//! `string_tables_url`.
+//!
+//! The strings of the current locale are merged from all the string tables and published as the
+//! `translation_keys` and `translation_strings` resources, so guests can look them up too. Entities
+//! with a `translation_key` get their `text` set to its string.
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use ambient_core::{asset_cache, async_ecs::async_run, runtime};
+pub use ambient_ecs::generated::text::components::{
+    locale, string_tables_url, translation_args, translation_key, translation_keys,
+    translation_revision, translation_strings,
+};
+use ambient_ecs::{components, query, EntityId, FnSystem, Resource, SystemGroup, World};
+use ambient_native_std::{
+    asset_cache::AsyncAssetKeyExt, asset_url::AbsAssetUrl, download_asset::JsonFromUrl,
+};
+use ambient_shared_types::localization::{
+    normalize_locale, parse_args, StringTables, Translations, DEFAULT_LOCALE,
+};
+
+use crate::text;
+
+components!("text", {
+    string_tables: Arc<StringTables>,
+    @[Resource]
+    translations: Arc<Translations>,
+});
+
+/// Shared by all worlds, so a revision identifies the strings it was published with
+static NEXT_REVISION: AtomicU32 = AtomicU32::new(1);
+
+pub fn systems() -> SystemGroup {
+    SystemGroup::new(
+        "ui/text/localization",
+        vec![
+            Box::new(FnSystem::new(|world, _| {
+                if world.resource_opt(locale()).is_none() {
+                    world.add_resource(locale(), system_locale());
+                }
+            })),
+            query(string_tables_url().changed()).to_system(|q, world, qs, _| {
+                for (id, url) in q.collect_cloned(world, qs) {
+                    let url = match AbsAssetUrl::from_str(&url) {
+                        Ok(url) => url,
+                        Err(err) => {
+                            tracing::warn!("Invalid string tables url {url}: {err:?}");
+                            continue;
+                        }
+                    };
+                    let assets = world.resource(asset_cache()).clone();
+                    let async_run = world.resource(async_run()).clone();
+                    world.resource(runtime()).spawn(async move {
+                        let tables = JsonFromUrl::<StringTables>::new(url.clone(), true)
+                            .get(&assets)
+                            .await;
+                        async_run.run(move |world| match tables {
+                            Ok(tables) => {
+                                world.add_component(id, string_tables(), tables).ok();
+                            }
+                            Err(err) => {
+                                tracing::warn!("Failed to load the string tables {url}: {err:?}")
+                            }
+                        });
+                    });
+                }
+            }),
+            query(locale().changed()).to_system(|q, world, qs, _| {
+                if !q.collect_cloned(world, qs).is_empty() {
+                    update_translations(world);
+                }
+            }),
+            query(string_tables().changed()).to_system(|q, world, qs, _| {
+                if !q.collect_cloned(world, qs).is_empty() {
+                    update_translations(world);
+                }
+            }),
+            query(())
+                .incl(string_tables())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    if !q.collect_cloned(world, qs).is_empty() {
+                        update_translations(world);
+                    }
+                }),
+            query(translation_key().changed())
+                .optional_changed(translation_args())
+                .to_system(|q, world, qs, _| {
+                    for (id, _) in q.collect_cloned(world, qs) {
+                        translate(world, id);
+                    }
+                }),
+        ],
+    )
+}
+
+/// The locale of the system, from the environment; [DEFAULT_LOCALE] if there's none
+fn system_locale() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+        .map(|locale| normalize_locale(&locale))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// Merges the strings of the current locale from all the string tables, publishes them, and
+/// translates all the texts again
+fn update_translations(world: &mut World) {
+    let locale = world
+        .resource_opt(locale())
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+    let tables = query(string_tables())
+        .iter(world, None)
+        .map(|(_, tables)| tables.clone())
+        .collect::<Vec<_>>();
+    let strings = StringTables::merge(tables.iter().map(|tables| &**tables), &locale);
+
+    let (keys, values): (Vec<_>, Vec<_>) = strings
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .unzip();
+    world.add_resource(translation_keys(), keys);
+    world.add_resource(translation_strings(), values);
+    world.add_resource(
+        translation_revision(),
+        NEXT_REVISION.fetch_add(1, Ordering::Relaxed),
+    );
+    world.add_resource(translations(), Arc::new(Translations::new(locale, strings)));
+
+    for (id, _) in query(translation_key()).collect_cloned(world, None) {
+        translate(world, id);
+    }
+}
+
+/// Sets the `text` of `id` to the string of its `translation_key`
+fn translate(world: &mut World, id: EntityId) {
+    let Ok(key) = world.get_cloned(id, translation_key()) else {
+        return;
+    };
+    let args = world.get_cloned(id, translation_args()).unwrap_or_default();
+    let value = match world.resource_opt(translations()) {
+        Some(translations) => translations.get(&key, &parse_args(&args)),
+        None => key,
+    };
+    world.add_component(id, text(), value).ok();
+}
@@ -59,6 +59,8 @@ pub fn get_text_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<Rend
         material_shader: TextMaterialShaderKey.get(assets),
         lit: false,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
     }
     .get(assets)
 }
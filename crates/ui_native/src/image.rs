@@ -53,6 +53,8 @@ impl ElementComponent for Image {
                         transparent: None,
                         double_sided: None,
                         depth_write_enabled: None,
+                        transparency_group: None,
+                        transparency_sort_bias: None,
                     },
                 ))
             })
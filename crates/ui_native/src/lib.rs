@@ -53,8 +53,10 @@ use ambient_shared_types::MouseButton;
 use element::{use_ref_with, use_runtime_message, use_spawn};
 
 pub fn init_all_components() {
-    layout::init_gpu_components();
+    layout::init_all_components();
     ambient_text::init_components();
+    ambient_text::localization::init_components();
+    rect::canvas::init_components();
 }
 
 pub fn systems() -> SystemGroup {
@@ -63,6 +65,7 @@ pub fn systems() -> SystemGroup {
         vec![
             Box::new(rect::systems()),
             Box::new(ambient_text::systems(true)),
+            Box::new(ambient_text::localization::systems()),
             Box::new(layout::layout_systems()),
         ],
     )
@@ -222,3 +222,107 @@ impl wit::ambient_package::Host for Bindings {
         shared::implementation::package::get_entity_for_package_id(self.world(), package_id)
     }
 }
+
+impl wit::animation::Host for Bindings {
+    fn get_bone(
+        &mut self,
+        entity: wit::types::EntityId,
+        bone: String,
+    ) -> anyhow::Result<Option<wit::types::EntityId>> {
+        shared::implementation::animation::get_bone(self.world(), entity, bone)
+    }
+}
+
+impl wit::debug_draw::Host for Bindings {
+    fn line(
+        &mut self,
+        start: wit::types::Vec3,
+        end: wit::types::Vec3,
+        color: wit::types::Vec3,
+        duration: wit::types::Duration,
+    ) -> anyhow::Result<()> {
+        shared::implementation::debug_draw::line(self.world_mut(), start, end, color, duration)
+    }
+
+    fn wire_sphere(
+        &mut self,
+        center: wit::types::Vec3,
+        radius: f32,
+        color: wit::types::Vec3,
+        duration: wit::types::Duration,
+    ) -> anyhow::Result<()> {
+        shared::implementation::debug_draw::wire_sphere(
+            self.world_mut(),
+            center,
+            radius,
+            color,
+            duration,
+        )
+    }
+
+    fn wire_box(
+        &mut self,
+        center: wit::types::Vec3,
+        half_extents: wit::types::Vec3,
+        rotation: wit::types::Quat,
+        color: wit::types::Vec3,
+        duration: wit::types::Duration,
+    ) -> anyhow::Result<()> {
+        shared::implementation::debug_draw::wire_box(
+            self.world_mut(),
+            center,
+            half_extents,
+            rotation,
+            color,
+            duration,
+        )
+    }
+
+    fn text(
+        &mut self,
+        position: wit::types::Vec3,
+        text: String,
+        size: f32,
+        color: wit::types::Vec3,
+        duration: wit::types::Duration,
+    ) -> anyhow::Result<()> {
+        shared::implementation::debug_draw::text(
+            self.world_mut(),
+            position,
+            text,
+            size,
+            color,
+            duration,
+        )
+    }
+}
+
+impl wit::terrain::Host for Bindings {
+    fn raise(
+        &mut self,
+        entity: wit::types::EntityId,
+        center: wit::types::Vec2,
+        radius: f32,
+        amount: f32,
+    ) -> anyhow::Result<()> {
+        shared::implementation::terrain::raise(self.world_mut(), entity, center, radius, amount)
+    }
+
+    fn paint(
+        &mut self,
+        entity: wit::types::EntityId,
+        center: wit::types::Vec2,
+        radius: f32,
+        layer: u32,
+        strength: f32,
+    ) -> anyhow::Result<()> {
+        shared::implementation::terrain::paint(
+            self.world_mut(),
+            entity,
+            center,
+            radius,
+            layer,
+            strength,
+        )
+    }
+}
@@ -14,23 +14,24 @@ use ambient_core::{
 use ambient_ecs::generated::input::messages::ClipboardGet;
 use ambient_gpu::texture::Texture;
 use ambient_input::{player_prev_raw_input, player_raw_input};
-use ambient_native_std::mesh::MeshBuilder;
+use ambient_native_std::mesh::{Mesh, MeshBuilder};
 use ambient_network::client::client_state;
 use ambient_procedurals::{
     new_material_handle, new_mesh_handle, new_sampler_handle, new_texture_handle,
     procedural_storage,
 };
 use ambient_renderer::pbr_material::{PbrMaterialConfig, PbrMaterialParams};
+use ambient_shared_types::canvas::{CanvasCommand, CanvasDrawing};
 
 use anyhow::Context;
-use glam::Vec4;
+use glam::{Vec2, Vec3, Vec4};
 use wgpu::TextureViewDescriptor;
 use winit::window::CursorGrabMode;
 
 use super::Bindings;
 use crate::shared::{
     conversion::{FromBindgen, IntoBindgen},
-    implementation::message,
+    implementation::message::{self, Delivery},
     message::{MessageExt, Target},
     wit,
 };
@@ -49,35 +50,38 @@ impl wit::client_message::Host for Bindings {
         let module_id = self.id;
         let world = self.world_mut();
 
-        match target {
-            WitTarget::ServerUnreliable | WitTarget::ServerReliable => {
-                let connection = world
-                    .resource(client_state())
-                    .as_ref()
-                    .context("no game client")?
-                    .transport
-                    .clone();
-
-                message::send_networked(
+        let delivery = match target {
+            WitTarget::ServerUnreliable => Delivery::Unreliable,
+            WitTarget::ServerReliable => Delivery::ReliableUnordered,
+            WitTarget::Server(reliability) => reliability.from_bindgen(),
+            WitTarget::LocalBroadcast(include_self) => {
+                return message::send_local(
                     world,
-                    connection,
                     module_id,
-                    &name,
-                    &data,
-                    matches!(target, WitTarget::ServerReliable),
-                )
+                    Target::All { include_self },
+                    name,
+                    data,
+                );
             }
-            WitTarget::LocalBroadcast(include_self) => {
-                message::send_local(world, module_id, Target::All { include_self }, name, data)
+            WitTarget::Local(id) => {
+                return message::send_local(
+                    world,
+                    module_id,
+                    Target::PackageOrModule(id.from_bindgen()),
+                    name,
+                    data,
+                );
             }
-            WitTarget::Local(id) => message::send_local(
-                world,
-                module_id,
-                Target::PackageOrModule(id.from_bindgen()),
-                name,
-                data,
-            ),
-        }
+        };
+
+        let connection = world
+            .resource(client_state())
+            .as_ref()
+            .context("no game client")?
+            .transport
+            .clone();
+
+        message::send_networked(world, connection, None, module_id, &name, &data, delivery)
     }
 }
 impl wit::client_player::Host for Bindings {
@@ -240,6 +244,20 @@ impl wit::client_window::Host for Bindings {
             .send(WindowCtl::SetFullscreen(fullscreen))?;
         Ok(())
     }
+
+    fn set_ime_allowed(&mut self, allowed: bool) -> anyhow::Result<()> {
+        self.world_mut()
+            .resource(window_ctl())
+            .send(WindowCtl::SetImeAllowed(allowed))?;
+        Ok(())
+    }
+
+    fn set_ime_position(&mut self, position: wit::types::Vec2) -> anyhow::Result<()> {
+        self.world_mut()
+            .resource(window_ctl())
+            .send(WindowCtl::SetImePosition(position.from_bindgen()))?;
+        Ok(())
+    }
 }
 
 impl wit::client_mesh::Host for Bindings {
@@ -247,26 +265,7 @@ impl wit::client_mesh::Host for Bindings {
         &mut self,
         desc: wit::client_mesh::Descriptor,
     ) -> anyhow::Result<wit::client_mesh::Handle> {
-        let wit::client_mesh::Descriptor { vertices, indices } = desc;
-        let mut positions = Vec::with_capacity(vertices.len());
-        let mut normals = Vec::with_capacity(vertices.len());
-        let mut tangents = Vec::with_capacity(vertices.len());
-        let mut texcoords = Vec::with_capacity(vertices.len());
-        for v in &vertices {
-            positions.push(v.position.from_bindgen());
-            normals.push(v.normal.from_bindgen());
-            tangents.push(v.tangent.from_bindgen());
-            texcoords.push(v.texcoord0.from_bindgen());
-        }
-        let mesh = MeshBuilder {
-            positions,
-            normals,
-            tangents,
-            texcoords: vec![texcoords],
-            indices,
-            ..MeshBuilder::default()
-        }
-        .build()?;
+        let mesh = mesh_from_descriptor(desc)?;
 
         let world = self.world_mut();
         let storage = world.resource_mut(procedural_storage());
@@ -277,10 +276,115 @@ impl wit::client_mesh::Host for Bindings {
     fn destroy(&mut self, handle: wit::client_mesh::Handle) -> anyhow::Result<()> {
         let world = self.world_mut();
         let storage = world.resource_mut(procedural_storage());
-        storage.meshes.remove(handle.from_bindgen());
+        let handle = handle.from_bindgen();
+        storage.meshes.remove(handle);
+        storage.mesh_updates.remove(&handle);
         Ok(())
     }
+    fn update(
+        &mut self,
+        handle: wit::client_mesh::Handle,
+        desc: wit::client_mesh::Descriptor,
+    ) -> anyhow::Result<()> {
+        let mesh = mesh_from_descriptor(desc)?;
+        let vertices = 0..mesh.positions().len();
+        let indices = 0..mesh.indices().len();
+
+        let world = self.world_mut();
+        let storage = world.resource_mut(procedural_storage());
+        let handle = handle.from_bindgen();
+        *storage.meshes.get_mut(handle) = mesh;
+        storage.mesh_written(handle, Some(vertices), Some(indices));
+        Ok(())
+    }
+    fn update_vertices(
+        &mut self,
+        handle: wit::client_mesh::Handle,
+        offset: u32,
+        vertices: Vec<wit::client_mesh::Vertex>,
+    ) -> anyhow::Result<()> {
+        let (positions, normals, tangents, texcoords) = vertex_attributes(&vertices);
+        let offset = offset as usize;
+
+        let world = self.world_mut();
+        let storage = world.resource_mut(procedural_storage());
+        let handle = handle.from_bindgen();
+        storage
+            .meshes
+            .get_mut(handle)
+            .write_vertices(offset, &positions, &normals, &tangents, &texcoords)?;
+        storage.mesh_written(handle, Some(offset..offset + vertices.len()), None);
+        Ok(())
+    }
+    fn update_positions(
+        &mut self,
+        handle: wit::client_mesh::Handle,
+        offset: u32,
+        positions: Vec<wit::types::Vec3>,
+    ) -> anyhow::Result<()> {
+        let positions = positions.from_bindgen();
+        let offset = offset as usize;
+
+        let world = self.world_mut();
+        let storage = world.resource_mut(procedural_storage());
+        let handle = handle.from_bindgen();
+        storage
+            .meshes
+            .get_mut(handle)
+            .write_vertices(offset, &positions, &[], &[], &[])?;
+        storage.mesh_written(handle, Some(offset..offset + positions.len()), None);
+        Ok(())
+    }
+    fn update_indices(
+        &mut self,
+        handle: wit::client_mesh::Handle,
+        offset: u32,
+        indices: Vec<u32>,
+    ) -> anyhow::Result<()> {
+        let offset = offset as usize;
+
+        let world = self.world_mut();
+        let storage = world.resource_mut(procedural_storage());
+        let handle = handle.from_bindgen();
+        storage
+            .meshes
+            .get_mut(handle)
+            .write_indices(offset, &indices)?;
+        storage.mesh_written(handle, None, Some(offset..offset + indices.len()));
+        Ok(())
+    }
+}
+
+fn vertex_attributes(
+    vertices: &[wit::client_mesh::Vertex],
+) -> (Vec<Vec3>, Vec<Vec3>, Vec<Vec3>, Vec<Vec2>) {
+    let mut positions = Vec::with_capacity(vertices.len());
+    let mut normals = Vec::with_capacity(vertices.len());
+    let mut tangents = Vec::with_capacity(vertices.len());
+    let mut texcoords = Vec::with_capacity(vertices.len());
+    for v in vertices {
+        positions.push(v.position.from_bindgen());
+        normals.push(v.normal.from_bindgen());
+        tangents.push(v.tangent.from_bindgen());
+        texcoords.push(v.texcoord0.from_bindgen());
+    }
+    (positions, normals, tangents, texcoords)
+}
+
+fn mesh_from_descriptor(desc: wit::client_mesh::Descriptor) -> anyhow::Result<Mesh> {
+    let wit::client_mesh::Descriptor { vertices, indices } = desc;
+    let (positions, normals, tangents, texcoords) = vertex_attributes(&vertices);
+    MeshBuilder {
+        positions,
+        normals,
+        tangents,
+        texcoords: vec![texcoords],
+        indices,
+        ..MeshBuilder::default()
+    }
+    .build()
 }
+
 impl wit::client_texture::Host for Bindings {
     fn create2d(
         &mut self,
@@ -376,6 +480,8 @@ impl wit::client_material::Host for Bindings {
             transparent: Some(desc.transparent),
             double_sided: None,
             depth_write_enabled: None,
+            transparency_group: None,
+            transparency_sort_bias: None,
         };
         let material_handle = new_material_handle();
         storage.materials.insert(material_handle, material);
@@ -388,3 +494,105 @@ impl wit::client_material::Host for Bindings {
         Ok(())
     }
 }
+
+impl wit::client_canvas::Host for Bindings {
+    fn begin(
+        &mut self,
+        entity: wit::types::EntityId,
+        size: wit::types::Uvec2,
+    ) -> anyhow::Result<()> {
+        self.canvas = Some((
+            entity.from_bindgen(),
+            CanvasDrawing::new(size.from_bindgen()),
+        ));
+        Ok(())
+    }
+    fn begin_path(&mut self) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::BeginPath)
+    }
+    fn move_to(&mut self, point: wit::types::Vec2) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::MoveTo(point.from_bindgen()))
+    }
+    fn line_to(&mut self, point: wit::types::Vec2) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::LineTo(point.from_bindgen()))
+    }
+    fn quad_to(
+        &mut self,
+        control: wit::types::Vec2,
+        point: wit::types::Vec2,
+    ) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::QuadTo {
+            control: control.from_bindgen(),
+            point: point.from_bindgen(),
+        })
+    }
+    fn cubic_to(
+        &mut self,
+        control1: wit::types::Vec2,
+        control2: wit::types::Vec2,
+        point: wit::types::Vec2,
+    ) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::CubicTo {
+            control1: control1.from_bindgen(),
+            control2: control2.from_bindgen(),
+            point: point.from_bindgen(),
+        })
+    }
+    fn close_path(&mut self) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::ClosePath)
+    }
+    fn fill(&mut self, color: wit::types::Vec4) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::Fill(color.from_bindgen()))
+    }
+    fn stroke(&mut self, color: wit::types::Vec4, width: f32) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::Stroke {
+            color: color.from_bindgen(),
+            width,
+        })
+    }
+    fn image(
+        &mut self,
+        url: String,
+        position: wit::types::Vec2,
+        size: wit::types::Vec2,
+    ) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::Image {
+            url,
+            position: position.from_bindgen(),
+            size: size.from_bindgen(),
+        })
+    }
+    fn text(
+        &mut self,
+        text: String,
+        position: wit::types::Vec2,
+        size: f32,
+        color: wit::types::Vec4,
+    ) -> anyhow::Result<()> {
+        self.push_canvas_command(CanvasCommand::Text {
+            text,
+            position: position.from_bindgen(),
+            size,
+            color: color.from_bindgen(),
+        })
+    }
+    fn end(&mut self) -> anyhow::Result<()> {
+        let (entity, drawing) = self
+            .canvas
+            .take()
+            .context("No canvas drawing has been begun")?;
+        ambient_rect::canvas::draw(self.world_mut(), entity, drawing);
+        Ok(())
+    }
+}
+
+impl Bindings {
+    fn push_canvas_command(&mut self, command: CanvasCommand) -> anyhow::Result<()> {
+        let (_, drawing) = self
+            .canvas
+            .as_mut()
+            .context("No canvas drawing has been begun")?;
+        drawing.push(command);
+        Ok(())
+    }
+}
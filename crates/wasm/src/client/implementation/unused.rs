@@ -71,6 +71,14 @@ impl wit::server_physics::Host for Bindings {
         unsupported()
     }
 
+    fn put_to_sleep(&mut self, _entity: wit::types::EntityId) -> anyhow::Result<()> {
+        unsupported()
+    }
+
+    fn wake_up(&mut self, _entity: wit::types::EntityId) -> anyhow::Result<()> {
+        unsupported()
+    }
+
     fn start_motor(&mut self, _entity: wit::types::EntityId, _velocity: f32) -> anyhow::Result<()> {
         unsupported()
     }
@@ -89,6 +97,16 @@ impl wit::server_physics::Host for Bindings {
         unsupported()
     }
 
+    fn create_fixed_joint(
+        &mut self,
+        _entity0: wit::types::EntityId,
+        _transform0: wit::types::Mat4,
+        _entity1: wit::types::EntityId,
+        _transform1: wit::types::Mat4,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+
     fn raycast_first(
         &mut self,
         _origin: wit::types::Vec3,
@@ -105,6 +123,48 @@ impl wit::server_physics::Host for Bindings {
         unsupported()
     }
 
+    fn raycast_first_batch(
+        &mut self,
+        _origins: Vec<wit::types::Vec3>,
+        _directions: Vec<wit::types::Vec3>,
+    ) -> anyhow::Result<Vec<Option<(wit::types::EntityId, f32)>>> {
+        unsupported()
+    }
+
+    fn raycast_batch(
+        &mut self,
+        _origins: Vec<wit::types::Vec3>,
+        _directions: Vec<wit::types::Vec3>,
+    ) -> anyhow::Result<Vec<Vec<(wit::types::EntityId, f32)>>> {
+        unsupported()
+    }
+
+    fn raycast_first_at(
+        &mut self,
+        _time: wit::types::Duration,
+        _origin: wit::types::Vec3,
+        _direction: wit::types::Vec3,
+    ) -> anyhow::Result<Option<(wit::types::EntityId, f32)>> {
+        unsupported()
+    }
+
+    fn raycast_at(
+        &mut self,
+        _time: wit::types::Duration,
+        _origin: wit::types::Vec3,
+        _direction: wit::types::Vec3,
+    ) -> anyhow::Result<Vec<(wit::types::EntityId, f32)>> {
+        unsupported()
+    }
+
+    fn overlap_sphere_batch(
+        &mut self,
+        _centers: Vec<wit::types::Vec3>,
+        _radii: Vec<f32>,
+    ) -> anyhow::Result<Vec<Vec<wit::types::EntityId>>> {
+        unsupported()
+    }
+
     fn move_character(
         &mut self,
         _entity: wit::types::EntityId,
@@ -130,6 +190,15 @@ impl wit::server_physics::Host for Bindings {
     ) -> anyhow::Result<()> {
         unsupported()
     }
+
+    fn set_contact_filter(
+        &mut self,
+        _first: wit::types::EntityId,
+        _second: wit::types::EntityId,
+        _collide: bool,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
 }
 impl wit::server_message::Host for Bindings {
     fn send(
@@ -1,6 +1,7 @@
 use crate::shared;
 use ambient_ecs::{EntityId, SystemGroup, World};
 use ambient_native_std::asset_cache::AssetCache;
+use ambient_shared_types::canvas::CanvasDrawing;
 use std::sync::Arc;
 
 mod implementation;
@@ -19,6 +20,7 @@ pub fn initialize(
             base: Default::default(),
             world_ref: Default::default(),
             id,
+            canvas: None,
         },
         None,
     )?;
@@ -36,6 +38,8 @@ struct Bindings {
     base: shared::bindings::BindingsBase,
     world_ref: shared::bindings::WorldRef,
     id: EntityId,
+    /// The canvas drawing being built between `client_canvas::begin` and `client_canvas::end`.
+    canvas: Option<(EntityId, CanvasDrawing)>,
 }
 
 impl Bindings {
@@ -7,7 +7,7 @@ use ambient_network::{
         PlatformSendStream,
     },
     log_network_result, unwrap_log_network_err, WASM_BISTREAM_ID, WASM_DATAGRAM_ID,
-    WASM_UNISTREAM_ID,
+    WASM_ORDERED_UNISTREAM_ID, WASM_SEQUENCED_DATAGRAM_ID, WASM_UNISTREAM_ID,
 };
 
 use ambient_sys::task::PlatformBoxFuture;
@@ -16,7 +16,10 @@ use bytes::Bytes;
 
 use std::{pin::Pin, sync::Arc};
 
-use crate::shared::implementation::message::{self, process_network_message, read_unistream};
+use crate::shared::implementation::message::{
+    self, process_network_message, process_ordered_network_message, read_ordered_unistream,
+    read_unistream,
+};
 
 pub fn initialize(world: &mut World) {
     world.resource_mut(datagram_handlers()).insert(
@@ -24,6 +27,14 @@ pub fn initialize(world: &mut World) {
         ("client_wasm_datagram", Arc::new(on_datagram)),
     );
 
+    world.resource_mut(datagram_handlers()).insert(
+        WASM_SEQUENCED_DATAGRAM_ID,
+        (
+            "client_wasm_sequenced_datagram",
+            Arc::new(on_sequenced_datagram),
+        ),
+    );
+
     world.resource_mut(bi_stream_handlers()).insert(
         WASM_BISTREAM_ID,
         ("client_wasm_bi_stream", Arc::new(on_bistream)),
@@ -33,10 +44,22 @@ pub fn initialize(world: &mut World) {
         WASM_UNISTREAM_ID,
         ("client_wasm_uni_stream", Arc::new(on_unistream)),
     );
+
+    world.resource_mut(uni_stream_handlers()).insert(
+        WASM_ORDERED_UNISTREAM_ID,
+        (
+            "client_wasm_ordered_uni_stream",
+            Arc::new(on_ordered_unistream),
+        ),
+    );
 }
 
 fn on_datagram(world: &mut World, _asset_cache: AssetCache, bytes: Bytes) {
-    log_network_result!(message::on_datagram(world, None, bytes));
+    log_network_result!(message::on_datagram(world, None, bytes, false));
+}
+
+fn on_sequenced_datagram(world: &mut World, _asset_cache: AssetCache, bytes: Bytes) {
+    log_network_result!(message::on_datagram(world, None, bytes, true));
 }
 
 fn on_bistream(
@@ -72,3 +95,29 @@ fn on_unistream(
         });
     })
 }
+
+fn on_ordered_unistream(
+    world: &mut World,
+    _asset_cache: AssetCache,
+    mut recv_stream: PlatformRecvStream,
+) -> PlatformBoxFuture<'static, ()> {
+    // Reads an incoming unistream and dispatches to WASM once the ones before it have been
+    let async_run = world.resource(async_run()).clone();
+    PlatformBoxFuture::new(async move {
+        let (sequence, remote_module_id, name, data) =
+            unwrap_log_network_err!(read_ordered_unistream(Pin::new(&mut recv_stream))
+                .await
+                .context("Failed to read ordered unistream"));
+
+        async_run.run(move |world| {
+            log_network_result!(process_ordered_network_message(
+                world,
+                None,
+                sequence,
+                remote_module_id,
+                name,
+                data
+            ));
+        });
+    })
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use ambient_ecs::{query, EntityId, World};
+use ambient_network::handoff::{spawn_ownership, ModuleOwner, SpawnOwnership};
+use ambient_package_semantic_native::id as package_id;
+
+use crate::shared::{module_name, module_state, package_ref, ModuleState, ModuleStateBehavior};
+
+pub fn initialize(world: &mut World) {
+    world.add_resource(spawn_ownership(), Arc::new(ModuleSpawnOwnership));
+}
+
+/// Tracks the entities spawned by the modules with the entities each module spawned
+struct ModuleSpawnOwnership;
+
+impl ModuleSpawnOwnership {
+    /// The loaded modules, along with their owner
+    fn modules(world: &World) -> Vec<(ModuleState, ModuleOwner)> {
+        query((module_state(), module_name(), package_ref()))
+            .iter(world, None)
+            .filter_map(|(_, (state, module, package))| {
+                let package = world.get_ref(*package, package_id()).ok()?;
+                Some((
+                    state.clone(),
+                    ModuleOwner {
+                        package: package.clone(),
+                        module: module.clone(),
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+impl SpawnOwnership for ModuleSpawnOwnership {
+    fn owner(&self, world: &World, id: EntityId) -> Option<ModuleOwner> {
+        Self::modules(world)
+            .into_iter()
+            .find(|(state, _)| state.has_spawned_entity(id))
+            .map(|(_, owner)| owner)
+    }
+
+    fn release(&self, world: &mut World, id: EntityId) {
+        for (mut state, _) in Self::modules(world) {
+            state.set_spawned_entity(id, false);
+        }
+    }
+
+    fn adopt(&self, world: &mut World, id: EntityId, owner: &ModuleOwner) {
+        let module = Self::modules(world)
+            .into_iter()
+            .find(|(_, module)| module == owner);
+        match module {
+            Some((mut state, _)) => state.set_spawned_entity(id, true),
+            None => tracing::warn!(
+                ?owner,
+                "No module on this server to attribute the handed off entity {id} to"
+            ),
+        }
+    }
+}
@@ -12,14 +12,17 @@ use ambient_core::{
 };
 use ambient_ecs::{generated::messages::HttpResponse, query, EntityId, World};
 use ambient_native_std::asset_url::AbsAssetUrl;
-use ambient_network::server::player_transport;
+use ambient_network::{
+    replay::{self, ReplayMessage},
+    server::player_transport,
+};
 
 use super::super::Bindings;
 
 use crate::shared::{
     self,
     conversion::FromBindgen,
-    implementation::message,
+    implementation::message::{self, Delivery},
     message::{MessageExt, Target},
 };
 
@@ -40,40 +43,48 @@ impl shared::wit::server_message::Host for Bindings {
         let module_id = self.id;
         let world = self.world_mut();
 
-        match target {
-            WitTarget::ClientBroadcastUnreliable => {
-                send_networked(world, None, module_id, name, data, false)
-            }
-            WitTarget::ClientBroadcastReliable => {
-                send_networked(world, None, module_id, name, data, true)
-            }
-            WitTarget::ClientTargetedUnreliable(user_id) => {
-                send_networked(world, Some(user_id), module_id, name, data, false)
-            }
+        let (target_user_id, delivery) = match target {
+            WitTarget::ClientBroadcastUnreliable => (None, Delivery::Unreliable),
+            WitTarget::ClientBroadcastReliable => (None, Delivery::ReliableUnordered),
+            WitTarget::ClientTargetedUnreliable(user_id) => (Some(user_id), Delivery::Unreliable),
             WitTarget::ClientTargetedReliable(user_id) => {
-                send_networked(world, Some(user_id), module_id, name, data, true)
+                (Some(user_id), Delivery::ReliableUnordered)
+            }
+            WitTarget::ClientBroadcast(reliability) => (None, reliability.from_bindgen()),
+            WitTarget::ClientTargeted((user_id, reliability)) => {
+                (Some(user_id), reliability.from_bindgen())
             }
             WitTarget::LocalBroadcast(include_self) => {
-                message::send_local(world, module_id, Target::All { include_self }, name, data)
+                return message::send_local(
+                    world,
+                    module_id,
+                    Target::All { include_self },
+                    name,
+                    data,
+                );
             }
-            WitTarget::Local(id) => message::send_local(
-                world,
-                module_id,
-                Target::PackageOrModule(id.from_bindgen()),
-                name,
-                data,
-            ),
-        }
+            WitTarget::Local(id) => {
+                return message::send_local(
+                    world,
+                    module_id,
+                    Target::PackageOrModule(id.from_bindgen()),
+                    name,
+                    data,
+                );
+            }
+        };
+
+        send_networked(world, target_user_id, module_id, name, data, delivery)
     }
 }
 
 fn send_networked(
-    world: &World,
+    world: &mut World,
     target_user_id: Option<String>,
     module_id: EntityId,
     name: String,
     data: Vec<u8>,
-    reliable: bool,
+    delivery: Delivery,
 ) -> wasm_bridge::Result<()> {
     let connections: Vec<_> = query((user_id(), player_transport()))
         .incl(is_player())
@@ -84,11 +95,18 @@ fn send_networked(
                 .map(|target_uid| target_uid == *uid)
                 .unwrap_or(true)
         })
-        .map(|(_, (_, connection))| connection.clone())
+        .map(|(_, (uid, connection))| (uid.clone(), connection.clone()))
         .collect();
 
-    for conn in connections {
-        message::send_networked(world, conn, module_id, &name, &data, reliable)?;
+    replay::record_message(world, || ReplayMessage::Sent {
+        user_id: target_user_id.clone(),
+        module_id,
+        name: name.clone(),
+        data: data.clone(),
+    });
+
+    for (uid, conn) in connections {
+        message::send_networked(world, conn, Some(&uid), module_id, &name, &data, delivery)?;
     }
 
     Ok(())
@@ -126,6 +126,16 @@ impl shared::wit::server_physics::Host for Bindings {
         Ok(())
     }
 
+    fn put_to_sleep(&mut self, entity: wit::types::EntityId) -> anyhow::Result<()> {
+        let _ = ambient_physics::helpers::put_to_sleep(self.world(), entity.from_bindgen());
+        Ok(())
+    }
+
+    fn wake_up(&mut self, entity: wit::types::EntityId) -> anyhow::Result<()> {
+        let _ = ambient_physics::helpers::wake_up(self.world(), entity.from_bindgen());
+        Ok(())
+    }
+
     fn start_motor(&mut self, entity: wit::types::EntityId, velocity: f32) -> anyhow::Result<()> {
         let joint = ambient_physics::helpers::get_entity_revolute_joint(
             self.world_mut(),
@@ -165,6 +175,22 @@ impl shared::wit::server_physics::Host for Bindings {
         )
     }
 
+    fn create_fixed_joint(
+        &mut self,
+        entity0: wit::types::EntityId,
+        transform0: wit::types::Mat4,
+        entity1: wit::types::EntityId,
+        transform1: wit::types::Mat4,
+    ) -> anyhow::Result<()> {
+        ambient_physics::helpers::create_fixed_joint(
+            self.world_mut(),
+            entity0.from_bindgen(),
+            transform0.from_bindgen(),
+            entity1.from_bindgen(),
+            transform1.from_bindgen(),
+        )
+    }
+
     fn raycast_first(
         &mut self,
         origin: wit::types::Vec3,
@@ -197,6 +223,95 @@ impl shared::wit::server_physics::Host for Bindings {
         Ok(result)
     }
 
+    fn raycast_first_batch(
+        &mut self,
+        origins: Vec<wit::types::Vec3>,
+        directions: Vec<wit::types::Vec3>,
+    ) -> anyhow::Result<Vec<Option<(wit::types::EntityId, f32)>>> {
+        let rays = get_raycast_batch(origins, directions)?;
+        let result = ambient_physics::intersection::raycast_first_batch(self.world(), &rays)
+            .into_iter()
+            .map(|hit| hit.map(|t| (t.0.into_bindgen(), t.1.into_bindgen())))
+            .collect();
+
+        Ok(result)
+    }
+
+    fn raycast_batch(
+        &mut self,
+        origins: Vec<wit::types::Vec3>,
+        directions: Vec<wit::types::Vec3>,
+    ) -> anyhow::Result<Vec<Vec<(wit::types::EntityId, f32)>>> {
+        let rays = get_raycast_batch(origins, directions)?;
+        let result = ambient_physics::intersection::raycast_batch(self.world(), &rays)
+            .into_iter()
+            .map(|hits| {
+                hits.into_iter()
+                    .map(|t| (t.0.into_bindgen(), t.1.into_bindgen()))
+                    .collect()
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    fn raycast_first_at(
+        &mut self,
+        time: wit::types::Duration,
+        origin: wit::types::Vec3,
+        direction: wit::types::Vec3,
+    ) -> anyhow::Result<Option<(wit::types::EntityId, f32)>> {
+        let direction = get_raycast_direction(direction)?;
+        let result = ambient_physics::lag_compensation::raycast_first_at(
+            self.world(),
+            time.from_bindgen(),
+            Ray::new(origin.from_bindgen(), direction),
+        )
+        .map(|t| (t.0.into_bindgen(), t.1.into_bindgen()));
+
+        Ok(result)
+    }
+
+    fn raycast_at(
+        &mut self,
+        time: wit::types::Duration,
+        origin: wit::types::Vec3,
+        direction: wit::types::Vec3,
+    ) -> anyhow::Result<Vec<(wit::types::EntityId, f32)>> {
+        let direction = get_raycast_direction(direction)?;
+        let result = ambient_physics::lag_compensation::raycast_at(
+            self.world(),
+            time.from_bindgen(),
+            Ray::new(origin.from_bindgen(), direction),
+        )
+        .into_iter()
+        .map(|t| (t.0.into_bindgen(), t.1.into_bindgen()))
+        .collect();
+
+        Ok(result)
+    }
+
+    fn overlap_sphere_batch(
+        &mut self,
+        centers: Vec<wit::types::Vec3>,
+        radii: Vec<f32>,
+    ) -> anyhow::Result<Vec<Vec<wit::types::EntityId>>> {
+        if centers.len() != radii.len() {
+            anyhow::bail!("Overlap batch must have the same number of centers and radii");
+        }
+        let spheres = centers
+            .into_iter()
+            .map(|center| center.from_bindgen())
+            .zip(radii)
+            .collect::<Vec<_>>();
+        let result = ambient_physics::intersection::overlap_sphere_batch(self.world(), &spheres)
+            .into_iter()
+            .map(|ids| ids.into_bindgen())
+            .collect();
+
+        Ok(result)
+    }
+
     fn move_character(
         &mut self,
         entity: wit::types::EntityId,
@@ -251,6 +366,41 @@ impl shared::wit::server_physics::Host for Bindings {
             .set_foot_position(position.from_bindgen().as_dvec3());
         Ok(())
     }
+
+    fn set_contact_filter(
+        &mut self,
+        first: wit::types::EntityId,
+        second: wit::types::EntityId,
+        collide: bool,
+    ) -> anyhow::Result<()> {
+        ambient_physics::contact_filter::set_contact_filter(
+            self.world(),
+            first.from_bindgen(),
+            second.from_bindgen(),
+            collide,
+        );
+        Ok(())
+    }
+}
+
+/// Returns an error if the batch is mismatched, or any direction is non-normalized.
+fn get_raycast_batch(
+    origins: Vec<wit::types::Vec3>,
+    directions: Vec<wit::types::Vec3>,
+) -> anyhow::Result<Vec<Ray>> {
+    if origins.len() != directions.len() {
+        anyhow::bail!("Raycast batch must have the same number of origins and directions");
+    }
+    origins
+        .into_iter()
+        .zip(directions)
+        .map(|(origin, direction)| {
+            Ok(Ray::new(
+                origin.from_bindgen(),
+                get_raycast_direction(direction)?,
+            ))
+        })
+        .collect()
 }
 
 /// Returns an error if the direction is non-normalized.
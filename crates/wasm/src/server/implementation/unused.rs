@@ -85,6 +85,14 @@ impl wit::client_window::Host for Bindings {
     fn set_fullscreen(&mut self, _fullscreen: bool) -> anyhow::Result<()> {
         unsupported()
     }
+
+    fn set_ime_allowed(&mut self, _allowed: bool) -> anyhow::Result<()> {
+        unsupported()
+    }
+
+    fn set_ime_position(&mut self, _position: wit::types::Vec2) -> anyhow::Result<()> {
+        unsupported()
+    }
 }
 
 impl wit::client_mesh::Host for Bindings {
@@ -97,6 +105,37 @@ impl wit::client_mesh::Host for Bindings {
     fn destroy(&mut self, _handle: wit::client_mesh::Handle) -> anyhow::Result<()> {
         unsupported()
     }
+    fn update(
+        &mut self,
+        _handle: wit::client_mesh::Handle,
+        _desc: wit::client_mesh::Descriptor,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn update_vertices(
+        &mut self,
+        _handle: wit::client_mesh::Handle,
+        _offset: u32,
+        _vertices: Vec<wit::client_mesh::Vertex>,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn update_positions(
+        &mut self,
+        _handle: wit::client_mesh::Handle,
+        _offset: u32,
+        _positions: Vec<wit::types::Vec3>,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn update_indices(
+        &mut self,
+        _handle: wit::client_mesh::Handle,
+        _offset: u32,
+        _indices: Vec<u32>,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
 }
 
 impl wit::client_texture::Host for Bindings {
@@ -134,3 +173,66 @@ impl wit::client_material::Host for Bindings {
         unsupported()
     }
 }
+
+impl wit::client_canvas::Host for Bindings {
+    fn begin(
+        &mut self,
+        _entity: wit::types::EntityId,
+        _size: wit::types::Uvec2,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn begin_path(&mut self) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn move_to(&mut self, _point: wit::types::Vec2) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn line_to(&mut self, _point: wit::types::Vec2) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn quad_to(
+        &mut self,
+        _control: wit::types::Vec2,
+        _point: wit::types::Vec2,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn cubic_to(
+        &mut self,
+        _control1: wit::types::Vec2,
+        _control2: wit::types::Vec2,
+        _point: wit::types::Vec2,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn close_path(&mut self) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn fill(&mut self, _color: wit::types::Vec4) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn stroke(&mut self, _color: wit::types::Vec4, _width: f32) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn image(
+        &mut self,
+        _url: String,
+        _position: wit::types::Vec2,
+        _size: wit::types::Vec2,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn text(
+        &mut self,
+        _text: String,
+        _position: wit::types::Vec2,
+        _size: f32,
+        _color: wit::types::Vec4,
+    ) -> anyhow::Result<()> {
+        unsupported()
+    }
+    fn end(&mut self) -> anyhow::Result<()> {
+        unsupported()
+    }
+}
@@ -1,9 +1,17 @@
 use crate::shared::{self, message::MessageExt};
-use ambient_ecs::{generated::messages, query, EntityId, FnSystem, SystemGroup, World};
+use ambient_ecs::{
+    generated::{
+        messages,
+        physics::components::{collision_event_groups, collision_groups, contact_filter},
+    },
+    query, EntityId, FnSystem, SystemGroup, World,
+};
 use ambient_native_std::asset_cache::AssetCache;
 use ambient_network::server::{ForkingEvent, ShutdownEvent};
+use ambient_package_semantic_native::is_package;
 use std::{path::PathBuf, sync::Arc};
 
+mod handoff;
 mod implementation;
 mod network;
 
@@ -26,6 +34,7 @@ pub fn initialize(
     )?;
 
     network::initialize(world);
+    handoff::initialize(world);
 
     Ok(())
 }
@@ -41,8 +50,106 @@ pub fn systems() -> SystemGroup {
                     Some(collisions) => collisions.lock().clone(),
                     None => return,
                 };
+                if collisions.is_empty() {
+                    return;
+                }
+
+                // Packages can opt into only receiving collisions for some collision groups
+                let packages = query(()).incl(is_package()).collect_ids(world, None);
+                let package_filters = packages
+                    .into_iter()
+                    .map(|id| (id, world.get(id, collision_event_groups()).ok()))
+                    .collect::<Vec<_>>();
+                let filtered = package_filters.iter().any(|(_, groups)| groups.is_some());
+
                 for (a, b, positions, normals) in collisions.into_iter() {
-                    messages::Collision::new(vec![a, b], positions, normals)
+                    let message = messages::Collision::new(vec![a, b], positions, normals);
+                    if !filtered {
+                        message.run(world, None).unwrap();
+                        continue;
+                    }
+
+                    let groups = world.get(a, collision_groups()).unwrap_or_default()
+                        | world.get(b, collision_groups()).unwrap_or_default();
+                    for (package, package_groups) in &package_filters {
+                        if package_groups
+                            .map_or(true, |package_groups| package_groups & groups != 0)
+                        {
+                            message.clone().run(world, Some(*package)).unwrap();
+                        }
+                    }
+                }
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module splash event");
+                let splashes = match world.resource_opt(ambient_physics::splashes()) {
+                    Some(splashes) => splashes.lock().clone(),
+                    None => return,
+                };
+
+                for (volume, body, entered) in splashes {
+                    messages::Splash::new(volume, body, entered)
+                        .run(world, None)
+                        .unwrap();
+                }
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module contact filter event");
+                // The pairs are taken even without filters, so that they don't pile up
+                let pairs = ambient_physics::contact_filter::take_pending_contact_filters(world);
+                if pairs.is_empty() {
+                    return;
+                }
+
+                let filters = query(())
+                    .incl(is_package())
+                    .incl(contact_filter())
+                    .collect_ids(world, None);
+                let (first, second) = pairs.into_iter().unzip();
+                let message = messages::ContactFilter::new(first, second);
+                for package in &filters {
+                    message.clone().run(world, Some(*package)).unwrap();
+                }
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module relevancy events");
+                let Some(changes) =
+                    world.resource_mut_opt(ambient_network::server::relevancy_changes())
+                else {
+                    return;
+                };
+                for (player, entered, left) in std::mem::take(changes) {
+                    messages::RelevancyChange::new(player, entered, left)
+                        .run(world, None)
+                        .unwrap();
+                }
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module sleep events");
+                let (Some(slept), Some(woken)) = (
+                    world.resource_opt(ambient_physics::slept_bodies()),
+                    world.resource_opt(ambient_physics::woken_bodies()),
+                ) else {
+                    return;
+                };
+                let (slept, woken) = (slept.clone(), woken.clone());
+
+                if !slept.is_empty() {
+                    messages::BodySleep::new(slept).run(world, None).unwrap();
+                }
+                if !woken.is_empty() {
+                    messages::BodyWake::new(woken).run(world, None).unwrap();
+                }
+            })),
+            Box::new(FnSystem::new(move |world, _| {
+                profiling::scope!("WASM module joint broken events");
+                let broken_joints = match world.resource_opt(ambient_physics::broken_joints()) {
+                    Some(broken_joints) => broken_joints.clone(),
+                    None => return,
+                };
+
+                for (entity0, entity1) in broken_joints {
+                    messages::JointBroken::new(entity0, entity1)
                         .run(world, None)
                         .unwrap();
                 }
@@ -5,7 +5,7 @@ use ambient_network::{
     log_network_result,
     server::{bi_stream_handlers, datagram_handlers, uni_stream_handlers, SharedServerState},
     unwrap_log_network_err, DynRecv, DynSend, WASM_BISTREAM_ID, WASM_DATAGRAM_ID,
-    WASM_UNISTREAM_ID,
+    WASM_ORDERED_UNISTREAM_ID, WASM_SEQUENCED_DATAGRAM_ID, WASM_UNISTREAM_ID,
 };
 
 use anyhow::Context;
@@ -13,7 +13,10 @@ use bytes::Bytes;
 
 use std::sync::Arc;
 
-use crate::shared::implementation::message::{self, process_network_message, read_unistream};
+use crate::shared::implementation::message::{
+    self, process_network_message, process_ordered_network_message, read_ordered_unistream,
+    read_unistream,
+};
 
 pub fn initialize(world: &mut World) {
     world.resource_mut(datagram_handlers()).insert(
@@ -21,6 +24,14 @@ pub fn initialize(world: &mut World) {
         ("server_wasm_datagram", Arc::new(on_datagram)),
     );
 
+    world.resource_mut(datagram_handlers()).insert(
+        WASM_SEQUENCED_DATAGRAM_ID,
+        (
+            "server_wasm_sequenced_datagram",
+            Arc::new(on_sequenced_datagram),
+        ),
+    );
+
     world.resource_mut(bi_stream_handlers()).insert(
         WASM_BISTREAM_ID,
         ("server_wasm_bi_stream", Arc::new(on_bistream)),
@@ -30,6 +41,14 @@ pub fn initialize(world: &mut World) {
         WASM_UNISTREAM_ID,
         ("server_wasm_uni_stream", Arc::new(on_unistream)),
     );
+
+    world.resource_mut(uni_stream_handlers()).insert(
+        WASM_ORDERED_UNISTREAM_ID,
+        (
+            "server_wasm_ordered_uni_stream",
+            Arc::new(on_ordered_unistream),
+        ),
+    );
 }
 
 #[allow(clippy::ptr_arg)]
@@ -40,7 +59,33 @@ fn on_datagram(state: SharedServerState, _asset_cache: AssetCache, user_id: &str
         return;
     };
 
-    log_network_result!(message::on_datagram(world, Some(user_id.to_owned()), bytes));
+    log_network_result!(message::on_datagram(
+        world,
+        Some(user_id.to_owned()),
+        bytes,
+        false
+    ));
+}
+
+#[allow(clippy::ptr_arg)]
+fn on_sequenced_datagram(
+    state: SharedServerState,
+    _asset_cache: AssetCache,
+    user_id: &str,
+    bytes: Bytes,
+) {
+    let mut state = state.lock();
+    let Some(world) = state.get_player_world_mut(user_id) else {
+        tracing::warn!("Failed to find player world for {user_id} when processing datagram");
+        return;
+    };
+
+    log_network_result!(message::on_datagram(
+        world,
+        Some(user_id.to_owned()),
+        bytes,
+        true
+    ));
 }
 
 #[allow(clippy::ptr_arg)]
@@ -87,3 +132,38 @@ fn on_unistream(
         });
     });
 }
+
+#[allow(clippy::ptr_arg)]
+fn on_ordered_unistream(
+    state: SharedServerState,
+    _asset_cache: AssetCache,
+    user_id: &str,
+    mut recv_stream: DynRecv,
+) {
+    let mut state = state.lock();
+    let Some(world) = state.get_player_world_mut(user_id) else {
+        tracing::warn!("Failed to find player world for {user_id} when processing unistream");
+        return;
+    };
+
+    // Reads an incoming unistream and dispatches to WASM once the ones before it have been
+    let async_run = world.resource(async_run()).clone();
+    let user_id = user_id.to_owned();
+    world.resource(runtime()).spawn(async move {
+        let (sequence, remote_module_id, name, data) =
+            unwrap_log_network_err!(read_ordered_unistream(recv_stream.as_mut())
+                .await
+                .context("Failed to read ordered unistream"));
+
+        async_run.run(move |world| {
+            log_network_result!(process_ordered_network_message(
+                world,
+                Some(user_id),
+                sequence,
+                remote_module_id,
+                name,
+                data
+            ));
+        });
+    });
+}
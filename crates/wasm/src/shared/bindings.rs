@@ -24,6 +24,9 @@ pub trait BindingsBound:
     + super::wit::message::Host
     + super::wit::player::Host
     + super::wit::ambient_package::Host
+    + super::wit::debug_draw::Host
+    + super::wit::terrain::Host
+    + super::wit::animation::Host
     // Client
     + super::wit::client_message::Host
     + super::wit::client_player::Host
@@ -35,6 +38,7 @@ pub trait BindingsBound:
     + super::wit::client_texture::Host
     + super::wit::client_sampler::Host
     + super::wit::client_material::Host
+    + super::wit::client_canvas::Host
     // Server
     + super::wit::server_asset::Host
     + super::wit::server_message::Host
@@ -0,0 +1,14 @@
+use ambient_ecs::World;
+
+use crate::shared::{
+    conversion::{FromBindgen, IntoBindgen},
+    wit,
+};
+
+pub fn get_bone(
+    world: &World,
+    entity: wit::types::EntityId,
+    bone: String,
+) -> anyhow::Result<Option<wit::types::EntityId>> {
+    Ok(ambient_animation::find_bone(world, entity.from_bindgen(), &bone).into_bindgen())
+}
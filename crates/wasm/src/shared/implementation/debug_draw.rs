@@ -0,0 +1,93 @@
+use ambient_core::game_time;
+use ambient_ecs::World;
+use ambient_gizmos::{debug_draw, DebugShape};
+
+use crate::shared::{conversion::FromBindgen, wit};
+
+pub fn line(
+    world: &mut World,
+    start: wit::types::Vec3,
+    end: wit::types::Vec3,
+    color: wit::types::Vec3,
+    duration: wit::types::Duration,
+) -> anyhow::Result<()> {
+    draw(
+        world,
+        DebugShape::Line {
+            start: start.from_bindgen(),
+            end: end.from_bindgen(),
+            color: color.from_bindgen(),
+        },
+        duration,
+    )
+}
+
+pub fn wire_sphere(
+    world: &mut World,
+    center: wit::types::Vec3,
+    radius: f32,
+    color: wit::types::Vec3,
+    duration: wit::types::Duration,
+) -> anyhow::Result<()> {
+    draw(
+        world,
+        DebugShape::WireSphere {
+            center: center.from_bindgen(),
+            radius,
+            color: color.from_bindgen(),
+        },
+        duration,
+    )
+}
+
+pub fn wire_box(
+    world: &mut World,
+    center: wit::types::Vec3,
+    half_extents: wit::types::Vec3,
+    rotation: wit::types::Quat,
+    color: wit::types::Vec3,
+    duration: wit::types::Duration,
+) -> anyhow::Result<()> {
+    draw(
+        world,
+        DebugShape::WireBox {
+            center: center.from_bindgen(),
+            half_extents: half_extents.from_bindgen(),
+            rotation: rotation.from_bindgen(),
+            color: color.from_bindgen(),
+        },
+        duration,
+    )
+}
+
+pub fn text(
+    world: &mut World,
+    position: wit::types::Vec3,
+    text: String,
+    size: f32,
+    color: wit::types::Vec3,
+    duration: wit::types::Duration,
+) -> anyhow::Result<()> {
+    draw(
+        world,
+        DebugShape::Text {
+            position: position.from_bindgen(),
+            text,
+            size,
+            color: color.from_bindgen(),
+        },
+        duration,
+    )
+}
+
+fn draw(
+    world: &mut World,
+    shape: DebugShape,
+    duration: wit::types::Duration,
+) -> anyhow::Result<()> {
+    let time = *world.resource(game_time());
+    world
+        .resource_mut(debug_draw())
+        .draw(shape, time, duration.from_bindgen());
+    Ok(())
+}
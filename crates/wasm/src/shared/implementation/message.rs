@@ -1,22 +1,157 @@
 use ambient_core::runtime;
-use ambient_ecs::{generated::wasm::components::package_ref, EntityId, World};
+use ambient_ecs::{generated::wasm::components::package_ref, query, EntityId, World};
 use ambient_network::{
-    client::NetworkTransport, log_network_result, WASM_DATAGRAM_ID, WASM_UNISTREAM_ID,
+    client::NetworkTransport,
+    log_network_result, rate_limit,
+    replay::{self, ReplayMessage},
+    server::kick_player,
+    WASM_DATAGRAM_ID, WASM_ORDERED_UNISTREAM_ID, WASM_SEQUENCED_DATAGRAM_ID, WASM_UNISTREAM_ID,
 };
 
 use anyhow::Context;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     io::{Cursor, Read},
     pin::Pin,
     sync::Arc,
 };
 
-use crate::shared::message::Target;
+use crate::shared::{
+    conversion::FromBindgen,
+    message::{message_channel, Target},
+    wit,
+};
 
 pub const MAX_STREAM_LENGTH: usize = 10 * 1024 * 1024;
+/// The most ordered messages held back for one peer, waiting for the ones sent before them
+pub const MAX_HELD_BACK_MESSAGES: usize = 1024;
+/// The most bytes of ordered messages held back for one peer, enough for a few of the largest
+pub const MAX_HELD_BACK_BYTES: usize = 32 * 1024 * 1024;
+
+/// How a message is sent over the network
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    /// As a datagram, which may be lost or arrive out of order
+    Unreliable,
+    /// As a datagram, which is dropped if a newer one of the same message has already arrived
+    UnreliableSequenced,
+    /// On a stream of its own
+    ReliableUnordered,
+    /// On a stream of its own, which is held back until the ordered messages sent before it have
+    /// arrived
+    ReliableOrdered,
+}
+impl FromBindgen for wit::message::Reliability {
+    type Item = Delivery;
+
+    fn from_bindgen(self) -> Self::Item {
+        match self {
+            Self::ReliableOrdered => Delivery::ReliableOrdered,
+            Self::ReliableUnordered => Delivery::ReliableUnordered,
+            Self::UnreliableSequenced => Delivery::UnreliableSequenced,
+        }
+    }
+}
+
+type NetworkMessage = (EntityId, String, Vec<u8>);
+
+/// The sequence numbers of the messages sent to and received from one peer: the server on the
+/// client, and each player on the server
+#[derive(Debug, Clone, Default)]
+pub struct MessageChannel {
+    next_ordered: u64,
+    expected_ordered: u64,
+    /// Ordered messages that arrived before some of the ones sent before them. The ones dropped by
+    /// the rate limit are kept as `None`, so that the messages after them aren't held back forever
+    held_back: BTreeMap<u64, Option<NetworkMessage>>,
+    held_back_bytes: usize,
+    last_sent_sequenced: HashMap<(EntityId, String), u64>,
+    last_received_sequenced: HashMap<(EntityId, String), u64>,
+}
+impl MessageChannel {
+    /// Returns the ordered messages that can be delivered now that `message` has arrived, or an
+    /// error if too many messages are held back.
+    fn receive_ordered(
+        &mut self,
+        sequence: u64,
+        message: Option<NetworkMessage>,
+    ) -> Result<Vec<NetworkMessage>, HeldBackOverflow> {
+        if sequence < self.expected_ordered {
+            return Ok(vec![]);
+        }
+        let bytes = message_bytes(&message);
+        if sequence != self.expected_ordered
+            && (self.held_back.len() >= MAX_HELD_BACK_MESSAGES
+                || self.held_back_bytes + bytes > MAX_HELD_BACK_BYTES)
+        {
+            return Err(HeldBackOverflow);
+        }
+        self.held_back_bytes += bytes;
+        if let Some(replaced) = self.held_back.insert(sequence, message) {
+            self.held_back_bytes -= message_bytes(&replaced);
+        }
+        Ok(self.take_ready())
+    }
+
+    /// Gives up on the ordered messages that haven't arrived before the first held back one, and
+    /// returns the messages that can be delivered from there
+    fn skip_ahead(&mut self) -> Vec<NetworkMessage> {
+        if let Some((&sequence, _)) = self.held_back.first_key_value() {
+            self.expected_ordered = sequence;
+        }
+        self.take_ready()
+    }
+
+    fn take_ready(&mut self) -> Vec<NetworkMessage> {
+        let mut ready = vec![];
+        while let Some(message) = self.held_back.remove(&self.expected_ordered) {
+            self.held_back_bytes -= message_bytes(&message);
+            ready.extend(message);
+            self.expected_ordered += 1;
+        }
+        ready
+    }
+
+    /// Returns whether a sequenced message is newer than the last one of its kind
+    fn receive_sequenced(&mut self, package_id: EntityId, name: &str, sequence: u64) -> bool {
+        let last = self
+            .last_received_sequenced
+            .entry((package_id, name.to_owned()))
+            .or_default();
+        if sequence <= *last {
+            return false;
+        }
+        *last = sequence;
+        true
+    }
+}
+
+/// Too many ordered messages are held back for a peer
+#[derive(Debug)]
+struct HeldBackOverflow;
+
+/// The bytes a held back message takes
+fn message_bytes(message: &Option<NetworkMessage>) -> usize {
+    message
+        .as_ref()
+        .map_or(0, |(_, name, data)| name.len() + data.len())
+}
+
+/// The channel of the peer with `user_id`, or of the server if there is none
+fn channel<'a>(world: &'a mut World, user_id: Option<&str>) -> Option<&'a mut MessageChannel> {
+    let id = match user_id {
+        Some(user_id) => ambient_core::player::get_by_user_id(world, user_id)?,
+        None => world.resource_entity(),
+    };
+    if !world.has_component(id, message_channel()) {
+        world
+            .add_component(id, message_channel(), MessageChannel::default())
+            .ok()?;
+    }
+    world.get_mut(id, message_channel()).ok()
+}
 
 pub fn subscribe(subscribed_events: &mut HashSet<String>, name: String) -> anyhow::Result<()> {
     subscribed_events.insert(name);
@@ -47,8 +182,14 @@ impl DatagramLatencyStat {
     }
 }
 
-/// Reads an incoming datagram and dispatches to WASM
-pub fn on_datagram(world: &mut World, user_id: Option<String>, bytes: Bytes) -> anyhow::Result<()> {
+/// Reads an incoming datagram and dispatches to WASM. Sequenced datagrams are dropped if a newer
+/// one of the same message has already been dispatched.
+pub fn on_datagram(
+    world: &mut World,
+    user_id: Option<String>,
+    bytes: Bytes,
+    sequenced: bool,
+) -> anyhow::Result<()> {
     use byteorder::ReadBytesExt;
 
     let mut cursor = Cursor::new(&bytes);
@@ -75,6 +216,12 @@ pub fn on_datagram(world: &mut World, user_id: Option<String>, bytes: Bytes) ->
         }
     }
 
+    let sequence = if sequenced {
+        Some(cursor.read_u64::<byteorder::BigEndian>()?)
+    } else {
+        None
+    };
+
     let package_id = cursor.read_u128::<byteorder::BigEndian>()?;
     let package_id = EntityId(package_id);
 
@@ -86,6 +233,13 @@ pub fn on_datagram(world: &mut World, user_id: Option<String>, bytes: Bytes) ->
     let position = cursor.position();
     let data = &bytes[usize::try_from(position)?..];
 
+    if let Some(sequence) = sequence {
+        let channel = channel(world, user_id.as_deref()).context("No channel for the sender")?;
+        if !channel.receive_sequenced(package_id, &name, sequence) {
+            return Ok(());
+        }
+    }
+
     process_network_message(world, user_id, package_id, name, data.to_vec())?;
 
     Ok(())
@@ -118,6 +272,60 @@ pub async fn read_unistream<R: ?Sized + tokio::io::AsyncRead>(
     Ok((package_id, name, data))
 }
 
+/// Reads an incoming ordered unistream, which is [read_unistream] preceded by its sequence number
+pub async fn read_ordered_unistream<R: ?Sized + tokio::io::AsyncRead>(
+    mut recv_stream: Pin<&mut R>,
+) -> anyhow::Result<(u64, EntityId, String, Vec<u8>)> {
+    use tokio::io::AsyncReadExt;
+
+    let sequence = recv_stream.read_u64().await?;
+    let (package_id, name, data) = read_unistream(recv_stream).await?;
+
+    Ok((sequence, package_id, name, data))
+}
+
+/// Dispatches an ordered message once the ordered messages sent before it have been dispatched.
+///
+/// The message counts against the rate limit of the player when it arrives, rather than when it
+/// is dispatched. If too many messages are held back, the player is kicked; the server is trusted
+/// instead, and the client gives up on the messages that haven't arrived.
+pub fn process_ordered_network_message(
+    world: &mut World,
+    user_id: Option<String>,
+    sequence: u64,
+    package_id: EntityId,
+    name: String,
+    data: Vec<u8>,
+) -> anyhow::Result<()> {
+    let message =
+        admit_network_message(world, user_id.as_deref(), &name).then_some((package_id, name, data));
+    let channel = channel(world, user_id.as_deref()).context("No channel for the sender")?;
+    let ready = match channel.receive_ordered(sequence, message) {
+        Ok(ready) => ready,
+        Err(HeldBackOverflow) => match &user_id {
+            Some(user_id) => {
+                tracing::warn!(%user_id, "Kicking the player for sending too many out of order messages");
+                if let Some(id) = ambient_core::player::get_by_user_id(world, user_id) {
+                    kick_player(world, id, "Sent too many out of order messages");
+                }
+                return Ok(());
+            }
+            None => {
+                tracing::warn!(
+                    "Too many ordered messages are held back, skipping the missing ones"
+                );
+                channel.skip_ahead()
+            }
+        },
+    };
+
+    for (package_id, name, data) in ready {
+        dispatch_network_message(world, user_id.clone(), package_id, name, data);
+    }
+
+    Ok(())
+}
+
 pub fn process_network_message(
     world: &mut World,
     user_id: Option<String>,
@@ -125,8 +333,44 @@ pub fn process_network_message(
     name: String,
     data: Vec<u8>,
 ) -> anyhow::Result<()> {
+    if admit_network_message(world, user_id.as_deref(), &name) {
+        dispatch_network_message(world, user_id, package_id, name, data);
+    }
+
+    Ok(())
+}
+
+/// Returns whether a message from the player with `user_id` is within its rate limit. Messages
+/// from the server are always admitted
+fn admit_network_message(world: &mut World, user_id: Option<&str>, name: &str) -> bool {
+    use crate::shared::{module_state, ModuleStateBehavior};
+
+    user_id.map_or(true, |user_id| {
+        let known = query(module_state())
+            .iter(world, None)
+            .any(|(_, state)| state.supports_message(name));
+        rate_limit::admit_message(world, user_id, name, known)
+    })
+}
+
+fn dispatch_network_message(
+    world: &mut World,
+    user_id: Option<String>,
+    package_id: EntityId,
+    name: String,
+    data: Vec<u8>,
+) {
     use crate::shared::message;
 
+    if let Some(user_id) = &user_id {
+        replay::record_message(world, || ReplayMessage::Received {
+            user_id: user_id.clone(),
+            module_id: package_id,
+            name: name.clone(),
+            data: data.clone(),
+        });
+    }
+
     message::send(
         world,
         Target::PackageOrModule(package_id),
@@ -137,8 +381,6 @@ pub fn process_network_message(
         name,
         data,
     );
-
-    Ok(())
 }
 
 /// Sends a message to another module on this side
@@ -162,28 +404,49 @@ pub fn send_local(
     Ok(())
 }
 
-/// Sends a message over the network for the specified module
+/// Sends a message over the network for the specified module to the peer with `user_id`, or to
+/// the server if there is none
 pub fn send_networked(
-    world: &World,
+    world: &mut World,
     transport: Arc<dyn NetworkTransport>,
+    user_id: Option<&str>,
     module_id: EntityId,
     name: &str,
     data: &[u8],
-    reliable: bool,
+    delivery: Delivery,
 ) -> anyhow::Result<()> {
     let package_id = world.get(module_id, package_ref())?;
 
-    if reliable {
-        send_unistream(world, transport, package_id, name, data);
-        Ok(())
-    } else {
-        send_datagram(world, transport, package_id, name, data)
+    match delivery {
+        Delivery::Unreliable => send_datagram(world, transport, None, package_id, name, data),
+        Delivery::UnreliableSequenced => {
+            let sequence = channel(world, user_id)
+                .context("No channel for the recipient")?
+                .last_sent_sequenced
+                .entry((package_id, name.to_owned()))
+                .or_default();
+            *sequence += 1;
+            let sequence = *sequence;
+            send_datagram(world, transport, Some(sequence), package_id, name, data)
+        }
+        Delivery::ReliableUnordered => {
+            send_unistream(world, transport, None, package_id, name, data);
+            Ok(())
+        }
+        Delivery::ReliableOrdered => {
+            let channel = channel(world, user_id).context("No channel for the recipient")?;
+            let sequence = channel.next_ordered;
+            channel.next_ordered += 1;
+            send_unistream(world, transport, Some(sequence), package_id, name, data);
+            Ok(())
+        }
     }
 }
 
 fn send_datagram(
     world: &World,
     transport: Arc<dyn NetworkTransport>,
+    sequence: Option<u64>,
     package_id: EntityId,
     name: &str,
     data: &[u8],
@@ -193,6 +456,9 @@ fn send_datagram(
     #[cfg(feature = "debug-local-datagram-latency")]
     payload.put_f64(DatagramLatencyStat::now().as_secs_f64());
 
+    if let Some(sequence) = sequence {
+        payload.put_u64(sequence);
+    }
     payload.put_u128(package_id.0);
 
     payload.put_u32(name.len().try_into()?);
@@ -201,11 +467,12 @@ fn send_datagram(
     payload.extend_from_slice(data);
 
     world.resource(runtime()).spawn(async move {
-        log_network_result!(
-            transport
-                .send_datagram(WASM_DATAGRAM_ID, payload.freeze())
-                .await
-        );
+        let id = if sequence.is_some() {
+            WASM_SEQUENCED_DATAGRAM_ID
+        } else {
+            WASM_DATAGRAM_ID
+        };
+        log_network_result!(transport.send_datagram(id, payload.freeze()).await);
     });
 
     Ok(())
@@ -214,6 +481,7 @@ fn send_datagram(
 fn send_unistream(
     world: &World,
     transport: Arc<dyn NetworkTransport>,
+    sequence: Option<u64>,
     package_id: EntityId,
     name: &str,
     data: &[u8],
@@ -223,6 +491,9 @@ fn send_unistream(
 
     world.resource(runtime()).spawn(async move {
         let mut payload = BytesMut::new();
+        if let Some(sequence) = sequence {
+            payload.put_u64(sequence);
+        }
         payload.put_u128(package_id.0);
 
         payload.put_u32(name.len().try_into()?);
@@ -230,10 +501,46 @@ fn send_unistream(
 
         payload.put(&data[..]);
 
-        transport
-            .request_uni(WASM_UNISTREAM_ID, payload.freeze())
-            .await?;
+        let id = if sequence.is_some() {
+            WASM_ORDERED_UNISTREAM_ID
+        } else {
+            WASM_UNISTREAM_ID
+        };
+        transport.request_uni(id, payload.freeze()).await?;
 
         anyhow::Ok(())
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(bytes: usize) -> Option<NetworkMessage> {
+        Some((EntityId::null(), String::new(), vec![0; bytes]))
+    }
+
+    #[test]
+    fn held_back_messages_are_capped_by_size() {
+        let mut channel = MessageChannel::default();
+
+        // Message 0 is missing, so the others are held back
+        for sequence in 1..4 {
+            let ready = channel
+                .receive_ordered(sequence, message(MAX_STREAM_LENGTH))
+                .unwrap();
+            assert!(ready.is_empty());
+        }
+        assert!(channel
+            .receive_ordered(4, message(MAX_STREAM_LENGTH))
+            .is_err());
+
+        // The expected message always goes through, along with the held back ones
+        let ready = channel.receive_ordered(0, message(1)).unwrap();
+        assert_eq!(ready.len(), 4);
+        assert_eq!(channel.held_back_bytes, 0);
+        assert!(channel
+            .receive_ordered(5, message(MAX_STREAM_LENGTH))
+            .is_ok());
+    }
+}
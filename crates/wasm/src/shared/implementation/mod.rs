@@ -1,9 +1,12 @@
+pub mod animation;
 pub mod asset;
 pub mod component;
+pub mod debug_draw;
 pub mod entity;
 pub mod message;
 pub mod package;
 pub mod player;
+pub mod terrain;
 
 pub fn unsupported<T>() -> anyhow::Result<T> {
     anyhow::bail!("This function is not supported on this side of the API. Please report this if you were able to access this function.")
@@ -0,0 +1,37 @@
+use ambient_ecs::World;
+
+use crate::shared::{conversion::FromBindgen, wit};
+
+pub fn raise(
+    world: &mut World,
+    entity: wit::types::EntityId,
+    center: wit::types::Vec2,
+    radius: f32,
+    amount: f32,
+) -> anyhow::Result<()> {
+    ambient_heightmap_terrain::raise(
+        world,
+        entity.from_bindgen(),
+        center.from_bindgen(),
+        radius,
+        amount,
+    )
+}
+
+pub fn paint(
+    world: &mut World,
+    entity: wit::types::EntityId,
+    center: wit::types::Vec2,
+    radius: f32,
+    layer: u32,
+    strength: f32,
+) -> anyhow::Result<()> {
+    ambient_heightmap_terrain::paint(
+        world,
+        entity.from_bindgen(),
+        center.from_bindgen(),
+        radius,
+        layer,
+        strength,
+    )
+}
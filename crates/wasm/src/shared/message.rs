@@ -6,9 +6,14 @@ use ambient_ecs::{
 };
 use ambient_package_semantic_native::{client_modules, is_package, server_modules};
 
+use super::implementation::message::MessageChannel;
+
 components!("wasm::message", {
     @[Debuggable, Resource]
     pending_messages: Vec<SerializedMessage>,
+    /// The sequence numbers of the messages sent to and received from a peer, on its player
+    /// entity on the server and on the resource entity on the client
+    message_channel: MessageChannel,
 });
 
 #[derive(Clone, PartialEq, Debug)]
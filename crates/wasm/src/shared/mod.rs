@@ -12,12 +12,16 @@ pub mod wit;
 
 pub use ambient_ecs::generated::wasm::components::*;
 use ambient_sys::task::PlatformBoxFuture;
-pub use internal::{messenger, module_bytecode, module_errors, module_state, module_state_maker};
+pub use internal::{
+    messenger, module_bytecode, module_cpu_time, module_errors, module_state, module_state_maker,
+};
 pub use module::*;
 use tracing::{Instrument, Span};
 
 use std::{path::Path, str::FromStr, sync::Arc};
 
+use ambient_sys::time::Instant;
+
 use ambient_core::{asset_cache, async_ecs::async_run, hierarchy::despawn_recursive, runtime};
 use ambient_ecs::{
     dont_despawn_on_unload, generated::messages, query, world_events, EntityId, FnSystem, Message,
@@ -34,7 +38,7 @@ use itertools::Itertools;
 use wasi_cap_std_sync::Dir;
 
 mod internal {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use ambient_ecs::{
         components, Debuggable, Description, EntityId, Networked, Resource, Store, World,
@@ -48,6 +52,8 @@ mod internal {
         module_bytecode: ModuleBytecode,
         @[Networked, Store, Debuggable]
         module_errors: ModuleErrors,
+        @[Debuggable, Description["The time spent running the module so far."]]
+        module_cpu_time: Duration,
 
         @[Resource, Description["Used to signal messages from the WASM host/runtime."]]
         messenger: Arc<dyn Fn(&World, EntityId, MessageType, &str) + Send + Sync>,
@@ -410,8 +416,16 @@ fn run(
         return;
     }
 
+    let start = Instant::now();
     let result =
         run_and_catch_panics(|| state.run(world, message_source, message_name, message_data));
+    let elapsed = start.elapsed();
+    match world.get_mut(id, module_cpu_time()) {
+        Ok(cpu_time) => *cpu_time += elapsed,
+        Err(_) => {
+            world.add_component(id, module_cpu_time(), elapsed).ok();
+        }
+    }
 
     if let Err(message) = result {
         update_errors(world, &[(id, message)]);
@@ -446,6 +460,8 @@ pub(crate) fn unload(world: &mut World, module_id: EntityId, reason: &str) {
     }
 
     world.remove_component(module_id, module_state()).unwrap();
+    #[cfg(not(target_os = "unknown"))]
+    ambient_network::rcon::remove_module_commands(world, module_id);
 
     for id in spawned_entities {
         if !world.has_component(id, dont_despawn_on_unload()) {
@@ -118,6 +118,8 @@ pub trait ModuleStateBehavior: Send + Sync {
         message_data: &[u8],
     ) -> anyhow::Result<()>;
     fn drain_spawned_entities(&mut self) -> HashSet<EntityId>;
+    fn has_spawned_entity(&self, id: EntityId) -> bool;
+    fn set_spawned_entity(&mut self, id: EntityId, spawned: bool);
     fn listen_to_message(&mut self, event_name: String);
     fn supports_message(&self, event_name: &str) -> bool;
 }
@@ -186,6 +188,14 @@ impl ModuleStateBehavior for ModuleState {
         self.inner.write().drain_spawned_entities()
     }
 
+    fn has_spawned_entity(&self, id: EntityId) -> bool {
+        self.inner.read().has_spawned_entity(id)
+    }
+
+    fn set_spawned_entity(&mut self, id: EntityId, spawned: bool) {
+        self.inner.write().set_spawned_entity(id, spawned)
+    }
+
     fn listen_to_message(&mut self, message_name: String) {
         self.inner.write().listen_to_message(message_name)
     }
@@ -379,6 +389,24 @@ impl<Bindings: BindingsBound> ModuleStateBehavior for InstanceState<Bindings> {
         std::mem::take(&mut self.store.data_mut().bindings.base_mut().spawned_entities)
     }
 
+    fn has_spawned_entity(&self, id: EntityId) -> bool {
+        self.store
+            .data()
+            .bindings
+            .base()
+            .spawned_entities
+            .contains(&id)
+    }
+
+    fn set_spawned_entity(&mut self, id: EntityId, spawned: bool) {
+        let spawned_entities = &mut self.store.data_mut().bindings.base_mut().spawned_entities;
+        if spawned {
+            spawned_entities.insert(id);
+        } else {
+            spawned_entities.remove(&id);
+        }
+    }
+
     fn listen_to_message(&mut self, event_name: String) {
         self.store
             .data_mut()
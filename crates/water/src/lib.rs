@@ -118,6 +118,8 @@ pub fn get_water_shader(assets: &AssetCache, config: &RendererConfig) -> Arc<Ren
         material_shader: WaterMaterialShaderKey.get(assets),
         lit: true,
         shadow_cascades: config.shadow_cascades,
+        wind: false,
+        imposter: false,
     }
     .get(assets)
 }
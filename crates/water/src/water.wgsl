@@ -62,7 +62,13 @@ fn get_material(in: MaterialInput) -> MaterialOutput {
     let normal = normalize(normal_t * 2. - 1.);
 
     let screen_ray_dir = normalize(in.world_position.xyz - global_params.camera_position.xyz);
-    let reflection_color = screen_space_reflections(in.world_position.xyz, screen_ray_dir, normal, screen_size, in.position.xy);
+    // A planar reflection probe on the water also reflects what is not on screen
+    let planar_reflection = get_planar_reflection(in.world_position.xyz, normal.xy * 0.02);
+    let reflection_color = mix(
+        screen_space_reflections(in.world_position.xyz, screen_ray_dir, normal, screen_size, in.position.xy),
+        planar_reflection.rgb,
+        planar_reflection.a
+    );
 
     let screen_tc = screen_pixel_to_uv(in.position.xy, screen_size);
     let screen_ndc = screen_uv_to_ndc(screen_tc);
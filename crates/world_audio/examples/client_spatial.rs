@@ -17,7 +17,7 @@ use ambient_primitives::Cube;
 use ambient_renderer::{cast_shadows, color};
 use ambient_ui_native::World;
 use ambient_world_audio::{
-    audio_emitter, audio_listener, audio_mixer, play_sound_on_entity, systems::setup_audio,
+    audio_buses, audio_emitter, audio_listener, play_sound_on_entity, systems::setup_audio,
 };
 use glam::{vec3, vec4, Mat4, Vec3};
 use parking_lot::Mutex;
@@ -106,7 +106,7 @@ async fn main() {
         .run(|app, _| {
             setup_audio(&mut app.world).unwrap();
             app.world
-                .add_resource(audio_mixer(), stream.mixer().clone());
+                .add_resource(audio_buses(), stream.buses().clone());
             init(app)
         })
         .await;
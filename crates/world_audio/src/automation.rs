@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use ambient_audio::AutomatedParam;
+use ambient_ecs::{generated::audio::components::*, query, Component, DynSystem, SystemGroup};
+use glam::Vec2;
+
+/// Starts the fades requested on playing sounds. The ramps themselves run on the mixer thread,
+/// see [AutomatedParam].
+pub fn automation_systems() -> SystemGroup {
+    SystemGroup::new(
+        "audio/automation",
+        vec![
+            fade_system(amplitude_fade(), amplitude(), crate::amplitude_arc()),
+            fade_system(pitch_fade(), pitch(), crate::pitch_param()),
+            fade_system(onepole_lpf_fade(), onepole_lpf(), crate::onepole_arc()),
+        ],
+    )
+}
+
+/// Ramps `param` as requested by `fade`, once the sound has loaded, and sets `value` to the
+/// target so that it isn't set back once the fade has finished
+fn fade_system(
+    fade: Component<Vec2>,
+    value: Component<f32>,
+    param: Component<AutomatedParam>,
+) -> DynSystem {
+    query((playing_sound(), fade)).to_system(move |q, world, qs, _| {
+        for (id, (_, fade_to)) in q.collect_cloned(world, qs) {
+            let Ok(automated) = world.get_cloned(id, param) else {
+                continue;
+            };
+            // Fade from the last value that was set, unless a fade is already in progress
+            if !automated.is_ramping() {
+                if let Ok(current) = world.get(id, value) {
+                    automated.set(current);
+                }
+            }
+            automated.ramp_to(fade_to.x, Duration::from_secs_f32(fade_to.y.max(0.0)));
+
+            world.add_component(id, value, fade_to.x).unwrap();
+            world.remove_component(id, fade).unwrap();
+        }
+    })
+}
@@ -0,0 +1,86 @@
+use ambient_core::{
+    delta_time,
+    transform::{get_world_position, local_to_world},
+};
+use ambient_ecs::{
+    generated::{audio::components::*, physics::components::linear_velocity},
+    query, DynSystem, EntityId, World,
+};
+use glam::Vec3;
+
+use crate::{doppler_positions, playback_control};
+
+/// In meters per second
+const SPEED_OF_SOUND: f32 = 343.0;
+/// The emitter and listener speeds are clamped to this fraction of the speed of sound, so that the
+/// shift stays finite
+const MAX_SPEED: f32 = 0.5 * SPEED_OF_SOUND;
+/// How quickly the shift follows changes in velocity, in seconds; velocities from transform deltas
+/// are noisy
+const SMOOTHING: f32 = 0.1;
+
+/// Returns the pitch multiplier of a sound at `to_emitter` from the listener, with the emitter
+/// and listener moving at `emitter_velocity` and `listener_velocity`.
+pub fn get_doppler_shift(
+    to_emitter: Vec3,
+    emitter_velocity: Vec3,
+    listener_velocity: Vec3,
+    doppler_factor: f32,
+) -> f32 {
+    let dir = to_emitter.normalize_or_zero();
+    let listener_speed = (listener_velocity.dot(dir) * doppler_factor).clamp(-MAX_SPEED, MAX_SPEED);
+    let emitter_speed = (-emitter_velocity.dot(dir) * doppler_factor).clamp(-MAX_SPEED, MAX_SPEED);
+    (SPEED_OF_SOUND + listener_speed) / (SPEED_OF_SOUND - emitter_speed)
+}
+
+/// Updates the `doppler_shift` of the playing spatial sounds from the velocities of their emitter
+/// and listener. The `linear_velocity` of an entity is used when it has one, and otherwise the
+/// velocity is estimated from how far it moved since the last frame.
+pub fn doppler_system() -> DynSystem {
+    query((spatial_audio_emitter(), spatial_audio_listener()))
+        .incl(playback_control())
+        .to_system(|q, world, qs, _| {
+            let dt = *world.resource(delta_time());
+            for (id, (emitter, listener)) in q.collect_cloned(world, qs) {
+                let listener = crate::systems::active_listener(world, listener);
+                let (Ok(emitter_pos), Ok(listener_ltw)) = (
+                    get_world_position(world, emitter),
+                    world.get(listener, local_to_world()),
+                ) else {
+                    continue;
+                };
+                let listener_pos = listener_ltw.w_axis.truncate();
+
+                let previous = world.get(id, doppler_positions()).ok();
+                world
+                    .add_component(id, doppler_positions(), (emitter_pos, listener_pos))
+                    .unwrap();
+                let Some((previous_emitter_pos, previous_listener_pos)) = previous else {
+                    continue;
+                };
+                if dt <= 0.0 {
+                    continue;
+                }
+
+                let velocity = |id: EntityId, pos: Vec3, previous: Vec3| {
+                    world
+                        .get(id, linear_velocity())
+                        .unwrap_or((pos - previous) / dt)
+                };
+                let target = get_doppler_shift(
+                    emitter_pos - listener_pos,
+                    velocity(emitter, emitter_pos, previous_emitter_pos),
+                    velocity(listener, listener_pos, previous_listener_pos),
+                    world.get(id, doppler_factor()).unwrap_or(1.0),
+                );
+
+                let shift = smooth(world, id, target, dt);
+                world.add_component(id, doppler_shift(), shift).unwrap();
+            }
+        })
+}
+
+fn smooth(world: &World, id: EntityId, target: f32, dt: f32) -> f32 {
+    let current = world.get(id, doppler_shift()).unwrap_or(target);
+    current + (target - current) * (1.0 - (-dt / SMOOTHING).exp())
+}
@@ -1,8 +1,15 @@
+mod automation;
+pub mod doppler;
 mod error;
 mod events;
 mod graph;
+mod metering;
+#[cfg(not(target_os = "unknown"))]
+pub mod occlusion;
+mod reverb;
 mod sounds;
 pub mod systems;
+mod virtualization;
 pub use ambient_audio as core;
 pub use error::*;
 pub use events::*;
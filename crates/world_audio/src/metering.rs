@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use ambient_audio::{AudioBus, BusTap, LoudnessMeter};
+use ambient_ecs::{generated::audio::components::*, FnSystem, SystemGroup, World};
+
+use crate::{audio_buses, audio_recorder, loudness_meter};
+
+pub fn metering_systems() -> SystemGroup {
+    SystemGroup::new(
+        "audio/metering",
+        vec![
+            // Taps the master bus with a meter while `audio_metering` is set, and publishes its
+            // measurements
+            Box::new(FnSystem::new(|world, _| {
+                let Some(master) = master_bus(world) else {
+                    return;
+                };
+                let enabled = world.resource_opt(audio_metering()).is_some();
+                match (enabled, world.resource_opt(loudness_meter()).cloned()) {
+                    (true, None) => {
+                        let meter = Arc::new(LoudnessMeter::new());
+                        master.add_tap(meter.clone());
+                        world.add_resource(loudness_meter(), meter);
+                    }
+                    (true, Some(meter)) => {
+                        let loudness = meter.loudness();
+                        world.add_resource(audio_rms(), loudness.rms);
+                        world.add_resource(audio_peak(), loudness.peak);
+                        world.add_resource(audio_momentary_loudness(), loudness.momentary);
+                        world.add_resource(audio_integrated_loudness(), loudness.integrated);
+                    }
+                    (false, Some(meter)) => {
+                        master.remove_tap(&(meter as Arc<dyn BusTap>));
+                        let r = world.resource_entity();
+                        world.remove_component(r, loudness_meter()).unwrap();
+                        for measurement in [
+                            audio_rms(),
+                            audio_peak(),
+                            audio_momentary_loudness(),
+                            audio_integrated_loudness(),
+                        ] {
+                            let _ = world.remove_component(r, measurement);
+                        }
+                    }
+                    (false, None) => {}
+                }
+            })),
+            // Records the master bus to the file named by `audio_recording`
+            Box::new(FnSystem::new(|world, _| {
+                let Some(master) = master_bus(world) else {
+                    return;
+                };
+                let name = world.resource_opt(audio_recording()).cloned();
+                let recorder = world.resource_opt(audio_recorder()).cloned();
+                if name.as_ref() == recorder.as_ref().map(|(name, _)| name) {
+                    return;
+                }
+
+                if let Some((_, recorder)) = recorder {
+                    // The file is finished once the last reference to the recorder is dropped
+                    master.remove_tap(&recorder);
+                    world
+                        .remove_component(world.resource_entity(), audio_recorder())
+                        .unwrap();
+                }
+                if let Some(name) = name {
+                    let tap = start_recording(&master, &name);
+                    world.add_resource(audio_recorder(), (name, tap));
+                }
+            })),
+        ],
+    )
+}
+
+fn master_bus(world: &World) -> Option<AudioBus> {
+    Some(world.resource_opt(audio_buses())?.master().clone())
+}
+
+/// Starts recording `master` to `recordings/<name>.wav`. Returns the tap which records, or a
+/// silent one if the recording couldn't be started, so that it isn't attempted every frame.
+#[cfg(not(target_os = "unknown"))]
+fn start_recording(master: &AudioBus, name: &str) -> Arc<dyn BusTap> {
+    use std::path::Path;
+
+    use ambient_audio::Source;
+
+    let file_name = Path::new(name).file_name().unwrap_or("recording".as_ref());
+    let path = Path::new("recordings")
+        .join(file_name)
+        .with_extension("wav");
+    let recorder = std::fs::create_dir_all("recordings")
+        .map_err(|err| ambient_audio::Error::Io(err, path.clone()))
+        .and_then(|_| ambient_audio::WavRecorder::new(&path, master.sample_rate()));
+    match recorder {
+        Ok(recorder) => {
+            tracing::info!("Recording audio to {path:?}");
+            let recorder: Arc<dyn BusTap> = Arc::new(recorder);
+            master.add_tap(recorder.clone());
+            recorder
+        }
+        Err(err) => {
+            tracing::error!("Failed to record audio to {path:?}: {err}");
+            Arc::new(SilentTap)
+        }
+    }
+}
+
+#[cfg(target_os = "unknown")]
+fn start_recording(_: &AudioBus, _: &str) -> Arc<dyn BusTap> {
+    tracing::warn!("Recording audio is not supported on the web");
+    Arc::new(SilentTap)
+}
+
+/// Stands in for a recording which couldn't be started
+#[derive(Debug)]
+struct SilentTap;
+
+impl BusTap for SilentTap {
+    fn process(&self, _: &[ambient_audio::Frame], _: ambient_audio::SampleRate) {}
+}
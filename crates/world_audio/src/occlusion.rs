@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use ambient_core::transform::{get_world_position, local_to_world};
+use ambient_ecs::{generated::audio::components::*, query, DynSystem, EntityId, FnSystem, World};
+use ambient_native_std::shapes::Ray;
+use ambient_physics::{intersection::raycast_collider_type, main_physics_scene, ColliderScene};
+use ambient_sys::time::Instant;
+use itertools::Itertools;
+
+use crate::{audio_emitter, occlusion_cutoff_arc, systems::UNOCCLUDED_CUTOFF};
+
+/// How often the path from the listener to each spatial sound is checked
+const UPDATE_INTERVAL: Duration = Duration::from_millis(200);
+/// The gain of a fully occluded sound
+const OCCLUDED_GAIN: f32 = 0.25;
+/// The low-pass cutoff of a fully occluded sound
+const OCCLUDED_CUTOFF: f32 = 600.0;
+/// Hits this close to either end of the path are ignored, so that the colliders the listener and
+/// emitter are inside of don't block the sound
+const END_MARGIN: f32 = 0.1;
+
+/// Casts a ray from the listener to the emitter of each playing spatial sound a few times per
+/// second, and muffles and attenuates the sound the more colliders are in the way.
+///
+/// This only does anything in worlds with a physics scene.
+pub fn occlusion_system() -> DynSystem {
+    let mut last_update: Option<Instant> = None;
+    Box::new(FnSystem::new(move |world, _| {
+        if world.resource_opt(main_physics_scene()).is_none() {
+            return;
+        }
+        let now = Instant::now();
+        if last_update.map_or(false, |last| now - last < UPDATE_INTERVAL) {
+            return;
+        }
+        last_update = Some(now);
+
+        let sounds = query((spatial_audio_emitter(), spatial_audio_listener()))
+            .incl(audio_emitter())
+            .incl(occlusion_cutoff_arc())
+            .iter(world, None)
+            .map(|(id, (&emitter, &listener))| {
+                (
+                    id,
+                    emitter,
+                    crate::systems::active_listener(world, listener),
+                )
+            })
+            .collect_vec();
+        for (id, emitter, listener) in sounds {
+            let Some(occlusion) = get_occlusion(world, emitter, listener) else {
+                continue;
+            };
+
+            *world.get_ref(id, occlusion_cutoff_arc()).unwrap().lock() =
+                UNOCCLUDED_CUTOFF * (OCCLUDED_CUTOFF / UNOCCLUDED_CUTOFF).powf(occlusion);
+            world.get_ref(id, audio_emitter()).unwrap().lock().amplitude =
+                1.0 + (OCCLUDED_GAIN - 1.0) * occlusion;
+            world
+                .add_component(id, audio_occlusion(), occlusion)
+                .unwrap();
+        }
+    }))
+}
+
+/// Returns how much the path from `listener` to `emitter` is blocked, from 0 to 1. Each collider
+/// in the way blocks half of the remaining sound.
+fn get_occlusion(world: &World, emitter: EntityId, listener: EntityId) -> Option<f32> {
+    let from = world
+        .get(listener, local_to_world())
+        .ok()?
+        .w_axis
+        .truncate();
+    let to = get_world_position(world, emitter).ok()?;
+    let distance = from.distance(to);
+    if distance <= 2.0 * END_MARGIN {
+        return Some(0.0);
+    }
+
+    let ray = Ray::new(from, (to - from) / distance);
+    let blockers = raycast_collider_type(world, ColliderScene::Physics, ray)
+        .into_iter()
+        .filter(|&(id, hit)| {
+            id != emitter && id != listener && hit > END_MARGIN && hit < distance - END_MARGIN
+        })
+        .map(|(id, _)| id)
+        .unique()
+        .count();
+
+    Some(1.0 - 0.5f32.powi(blockers as i32))
+}
@@ -0,0 +1,143 @@
+use ambient_audio::{BusEffect, SendChannel, SendLevels, SendReturn, Source, SFX_BUS};
+use ambient_core::transform::{get_world_position, local_to_world, translation};
+use ambient_ecs::{generated::audio::components::*, query, EntityId, SystemGroup, World};
+use glam::{Mat4, Vec3};
+
+use crate::{audio_buses, reverb_sends, reverb_zone_bus, reverb_zone_send};
+
+/// The shape and send levels of a reverb zone
+struct Zone {
+    channel: SendChannel,
+    world_to_local: Mat4,
+    position: Vec3,
+    radius: Option<f32>,
+    extents: Option<Vec3>,
+    fade: f32,
+    wet: f32,
+    dry: f32,
+}
+
+impl Zone {
+    fn get(world: &World, id: EntityId, channel: SendChannel) -> Self {
+        let ltw = world.get(id, local_to_world()).unwrap_or_else(|_| {
+            Mat4::from_translation(world.get(id, translation()).unwrap_or_default())
+        });
+        Self {
+            channel,
+            world_to_local: ltw.inverse(),
+            position: ltw.w_axis.truncate(),
+            radius: world.get(id, reverb_zone_radius()).ok(),
+            extents: world.get(id, reverb_zone_extents()).ok(),
+            fade: world.get(id, reverb_zone_fade()).unwrap_or(1.0),
+            wet: world.get(id, reverb_wet()).unwrap_or(0.5),
+            dry: world.get(id, reverb_dry()).unwrap_or(1.0),
+        }
+    }
+
+    /// How much a sound at `pos` is inside the zone, from 0 outside of the fade distance to 1
+    fn weight(&self, pos: Vec3) -> f32 {
+        let distance = if let Some(radius) = self.radius {
+            (pos.distance(self.position) - radius).max(0.0)
+        } else if let Some(extents) = self.extents {
+            let local = self.world_to_local.transform_point3(pos);
+            (local.abs() - extents).max(Vec3::ZERO).length()
+        } else {
+            return 0.0;
+        };
+
+        if self.fade > 0.0 {
+            (1.0 - distance / self.fade).max(0.0)
+        } else if distance > 0.0 {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+fn zone_bus_name(id: EntityId) -> String {
+    format!("reverb_zone/{id}")
+}
+
+pub fn reverb_systems() -> SystemGroup {
+    SystemGroup::new(
+        "audio/reverb",
+        vec![
+            // Creates and configures the bus that plays the reverb of each zone
+            query(reverb_zone()).to_system(|q, world, qs, _| {
+                let Some(buses) = world.resource_opt(audio_buses()).cloned() else {
+                    return;
+                };
+                for (id, _) in q.collect_cloned(world, qs) {
+                    let bus = match world.get_cloned(id, reverb_zone_bus()) {
+                        Ok(bus) => bus,
+                        Err(_) => {
+                            let bus = buses.get_or_create(&zone_bus_name(id));
+                            let channel = SendChannel::new();
+                            bus.play(SendReturn::new(channel.clone(), bus.sample_rate()));
+                            world
+                                .add_component(id, reverb_zone_send(), channel)
+                                .unwrap();
+                            world
+                                .add_component(id, reverb_zone_bus(), bus.clone())
+                                .unwrap();
+                            bus
+                        }
+                    };
+
+                    let parent = world
+                        .get_ref(id, audio_bus())
+                        .map_or(SFX_BUS, |name| name.as_str());
+                    let parent = buses.get_or_create(parent);
+                    if let Err(err) = buses.set_parent(&bus, &parent) {
+                        tracing::warn!("Failed to set the bus of reverb zone {id}: {err}");
+                    }
+                    bus.set_effects(&[BusEffect::Reverb {
+                        room_size: world.get(id, reverb_room_size()).unwrap_or(0.5),
+                        damping: world.get(id, reverb_damping()).unwrap_or(0.5),
+                        wet: 1.0,
+                    }]);
+                }
+            }),
+            query(reverb_zone_bus())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    let Some(buses) = world.resource_opt(audio_buses()) else {
+                        return;
+                    };
+                    for (_, bus) in q.iter(world, qs) {
+                        buses.remove(bus.name());
+                    }
+                }),
+            // Blends the sends of each spatial sound between the zones its emitter is in
+            query((spatial_audio_emitter(), reverb_sends())).to_system(|q, world, qs, _| {
+                let zones = query(reverb_zone_send())
+                    .incl(reverb_zone())
+                    .iter(world, None)
+                    .map(|(id, channel)| Zone::get(world, id, channel.clone()))
+                    .collect::<Vec<_>>();
+
+                for (_, (&emitter, sends)) in q.iter(world, qs) {
+                    let Ok(pos) = get_world_position(world, emitter) else {
+                        continue;
+                    };
+
+                    let mut levels = SendLevels::default();
+                    let mut strongest = 0.0;
+                    for zone in &zones {
+                        let weight = zone.weight(pos);
+                        if weight <= 0.0 {
+                            continue;
+                        }
+                        levels.sends.push((zone.channel.clone(), zone.wet * weight));
+                        if weight > strongest {
+                            strongest = weight;
+                            levels.dry = 1.0 + (zone.dry - 1.0) * weight;
+                        }
+                    }
+                    *sends.lock() = levels;
+                }
+            }),
+        ],
+    )
+}
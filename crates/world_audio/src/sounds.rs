@@ -3,9 +3,14 @@ use std::sync::Arc;
 use ambient_audio::{
     hrtf::HrtfLib,
     track::{Track, TrackDecodeStream},
-    Attenuation, AudioEmitter, AudioListener, AudioMixer, Sound, SoundId, Source, Spatial,
+    Attenuation, AudioBus, AudioBuses, AudioEmitter, AudioListener, AutomatedParam, BusTap,
+    LoudnessMeter, PlaybackHandle, SendChannel, SendHandle, Sound, SoundId, Source, Spatial,
+    VirtualHandle, SFX_BUS,
+};
+use ambient_ecs::{
+    components, generated::audio::components::active_audio_listener, query, Debuggable,
+    Description, EntityId, Name, Networked, Resource, Store, World,
 };
-use ambient_ecs::{components, query, EntityId, Resource, World};
 use ambient_element::ElementComponentExt;
 use ambient_native_std::{asset_url::AbsAssetUrl, cb, Cb};
 use ambient_ui_native::{
@@ -14,7 +19,7 @@ use ambient_ui_native::{
 };
 use anyhow::Context;
 use derive_more::{Deref, DerefMut, From, Into};
-use glam::{vec2, vec4};
+use glam::{vec2, vec4, Vec3};
 use itertools::Itertools;
 use parking_lot::Mutex;
 
@@ -28,12 +33,41 @@ components!("audio", {
     @[Resource]
     audio_sender: Arc<flume::Sender<AudioMessage>>,
     @[Resource]
-    audio_mixer: AudioMixer,
-    amplitude_arc: Arc<Mutex<f32>>,
+    audio_buses: AudioBuses,
+    amplitude_arc: AutomatedParam,
     panning_arc: Arc<Mutex<f32>>,
-    onepole_arc: Arc<Mutex<f32>>,
+    onepole_arc: AutomatedParam,
+    /// The pitch of a playing sound, multiplied with its playback speed
+    pitch_param: AutomatedParam,
     looping_arc: Arc<Mutex<bool>>,
+    playback_control: PlaybackHandle,
     sound_id: SoundId,
+    /// The bus that the sound with `sound_id` plays on
+    sound_bus: AudioBus,
+    /// The low-pass cutoff applied to a spatial sound by occlusion
+    occlusion_cutoff_arc: Arc<Mutex<f32>>,
+    /// The positions of the emitter and listener of a spatial sound last frame
+    doppler_positions: (Vec3, Vec3),
+    /// How much a spatial sound is sent to the reverb zones it is in
+    reverb_sends: SendHandle,
+    /// The bus which plays the reverb of a reverb zone
+    reverb_zone_bus: AudioBus,
+    /// The channel that spatial sounds send to the reverb of a reverb zone through
+    reverb_zone_send: SendChannel,
+    /// Set while a spatial sound is virtualized
+    virtual_handle: VirtualHandle,
+    /// Measures the master bus while `audio_metering` is set
+    @[Resource]
+    loudness_meter: Arc<LoudnessMeter>,
+    /// Records the master bus while `audio_recording` is set, along with the recording's name
+    @[Resource]
+    audio_recorder: (String, Arc<dyn BusTap>),
+    @[
+        Debuggable, Networked, Store,
+        Name["Audio attenuation"],
+        Description["How the spatial sounds emitted from this entity fall off with distance. Overrides `spatial_audio_attenuation`, and can be edited while they play."]
+    ]
+    audio_attenuation: AttenuationEditorVisual,
 });
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -76,8 +110,8 @@ pub enum AudioControl {
     HighPass(Arc<Mutex<f32>>, Arc<Mutex<f32>>),
 }
 
-/// TODO: hook this into the Attenuation inside ambient_audio
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, DerefMut, Deref, From, Into)]
+/// An [Attenuation] with an editor that plots its falloff curve. Stored in `audio_attenuation`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, DerefMut, Deref, From, Into)]
 pub struct AttenuationEditorVisual(Attenuation);
 
 impl Editor for AttenuationEditorVisual {
@@ -118,18 +152,22 @@ impl Editor for AttenuationEditorVisual {
     }
 }
 
+/// Returns the listener that sounds played with [play_sound_on_entity] are heard from: the
+/// `active_audio_listener` if it has an `audio_listener`, and otherwise the listener with the
+/// lowest id, so that worlds with several listeners, like split-screen, keep working.
 pub fn get_audio_listener(world: &World) -> anyhow::Result<&Arc<Mutex<AudioListener>>> {
-    let (_, listener) = query(audio_listener())
+    let active = world
+        .resource_opt(active_audio_listener())
+        .and_then(|&id| world.get_ref(id, audio_listener()).ok());
+    if let Some(listener) = active {
+        return Ok(listener);
+    }
+
+    query(audio_listener())
         .iter(world, None)
-        .exactly_one()
-        .map_err(|v| {
-            anyhow::anyhow!(
-                "Incorrect number of listeners in world. Additional: {:?}",
-                v.count()
-            )
-        })?;
-
-    Ok(listener)
+        .min_by_key(|(id, _)| *id)
+        .map(|(_, listener)| listener)
+        .context("No audio listener in world")
 }
 
 /// Makes a sound source emit from the entity
@@ -139,12 +177,15 @@ pub fn play_sound_on_entity<S: 'static + Source>(
     source: S,
 ) -> anyhow::Result<Sound> {
     let hrtf_lib = world.resource(hrtf_lib());
-    let mixer = world.resource(audio_mixer());
+    let bus = world
+        .resource(audio_buses())
+        .get(SFX_BUS)
+        .context("No sfx bus")?;
     let emitter = world
         .get_ref(id, audio_emitter())
         .context("No audio emitter on entity")?;
 
     let listener = get_audio_listener(world)?;
 
-    Ok(mixer.play(source.spatial(hrtf_lib, listener.clone(), emitter.clone())))
+    Ok(bus.play(source.spatial(hrtf_lib, listener.clone(), emitter.clone())))
 }
@@ -1,21 +1,29 @@
 use std::{io::Cursor, sync::Arc};
 
 use crate::{audio_emitter, audio_listener, hrtf_lib};
-use ambient_audio::{hrtf::HrtfLib, AudioFromUrl, Source};
+use ambient_audio::{
+    hrtf::HrtfLib, track::Track, AudioBus, AudioFromUrl, AudioStreamFromUrl, AutomatedParam,
+    BusEffect, Playback, PlaybackHandle, SendHandle, Sound, Source, VirtualHandle, Virtualize,
+    MASTER_BUS, SFX_BUS,
+};
 use ambient_audio::{Attenuation, AudioEmitter, AudioListener};
 use ambient_core::{
     asset_cache,
     async_ecs::async_run,
     runtime,
-    transform::{local_to_world, translation},
+    transform::{get_world_position, local_to_world},
 };
-use ambient_ecs::EntityId;
 use ambient_ecs::{
-    generated::audio::components::*, generated::hierarchy::components::children, query,
+    generated::audio::components::*, generated::hierarchy::components::children, query, FnSystem,
     SystemGroup, World,
 };
-use ambient_native_std::{asset_cache::AsyncAssetKeyExt, asset_url::AbsAssetUrl, unwrap_log_warn};
-use glam::{vec4, Mat4};
+use ambient_ecs::{Entity, EntityId};
+use ambient_native_std::{
+    asset_cache::{AssetCache, AsyncAssetKeyExt},
+    asset_url::AbsAssetUrl,
+    unwrap_log_warn,
+};
+use glam::{vec4, Mat4, Vec3};
 use parking_lot::Mutex;
 use std::str::FromStr;
 
@@ -56,7 +64,7 @@ pub fn audio_systems() -> SystemGroup {
                 for (_, (emitter, ltw)) in q.iter(world, qs) {
                     // check if mute_audio is set
                     let r = world.resource_entity();
-                    if !world.has_component(r, crate::audio_mixer()) {
+                    if !world.has_component(r, crate::audio_buses()) {
                         continue;
                     }
                     let (_, _, pos) = ltw.to_scale_rotation_translation();
@@ -70,7 +78,7 @@ pub fn audio_systems() -> SystemGroup {
                     for (_, (listener, &ltw)) in q.iter(world, qs) {
                         // check if mute_audio is set
                         let r = world.resource_entity();
-                        if !world.has_component(r, crate::audio_mixer()) {
+                        if !world.has_component(r, crate::audio_buses()) {
                             continue;
                         }
                         let mut listener = listener.lock();
@@ -78,22 +86,137 @@ pub fn audio_systems() -> SystemGroup {
                     }
                 },
             ),
+            // Follows the emitters and listeners of playing spatial sounds
+            query((audio_emitter(), spatial_audio_emitter())).to_system(|q, world, qs, _| {
+                for (_, (emitter, &emitter_id)) in q.iter(world, qs) {
+                    if let Ok(pos) = get_world_position(world, emitter_id) {
+                        emitter.lock().pos = pos;
+                    }
+                }
+            }),
+            query((audio_listener(), spatial_audio_listener())).to_system(|q, world, qs, _| {
+                for (_, (listener, &listener_id)) in q.iter(world, qs) {
+                    let listener_id = active_listener(world, listener_id);
+                    if let Ok(ltw) = world.get(listener_id, local_to_world()) {
+                        listener.lock().transform = Y_UP_LHS * ltw;
+                    }
+                }
+            }),
+            query((audio_emitter(), spatial_audio_attenuation().changed())).to_system(
+                |q, world, qs, _| {
+                    for (_, (emitter, &coefficients)) in q.iter(world, qs) {
+                        emitter.lock().attenuation = attenuation_from_coefficients(coefficients);
+                    }
+                },
+            ),
+            // Applies the `audio_attenuation` of emitters, or of the entity a spatial sound is
+            // emitted from, so that it can be tuned while the sounds play
+            query(audio_emitter()).to_system(|q, world, qs, _| {
+                for (id, emitter) in q.iter(world, qs) {
+                    let attenuation = world.get(id, crate::audio_attenuation()).or_else(|_| {
+                        let emitter_id = world.get(id, spatial_audio_emitter())?;
+                        world.get(emitter_id, crate::audio_attenuation())
+                    });
+                    if let Ok(attenuation) = attenuation {
+                        emitter.lock().attenuation = *attenuation;
+                    }
+                }
+            }),
+            crate::doppler::doppler_system(),
+            crate::virtualization::virtualization_system(),
+            // Applies the controls of playing sounds, and despawns them once they have finished
+            query((playing_sound(), crate::playback_control())).to_system(|q, world, qs, _| {
+                for (id, (_, control)) in q.collect_cloned(world, qs) {
+                    let seek = world.get(id, seek_to()).ok();
+                    let (position, finished) = {
+                        let mut control = control.lock();
+                        control.paused = world.has_component(id, paused());
+                        // The pitch is multiplied in by the `pitch_param` of the playback
+                        control.speed = world.get(id, doppler_shift()).unwrap_or(1.0);
+                        control.loop_start = world.get(id, loop_start()).unwrap_or(0);
+                        control.loop_end = world.get(id, loop_end()).ok();
+                        if seek.is_some() {
+                            control.seek = seek;
+                        }
+                        (control.position, control.finished)
+                    };
+                    if finished {
+                        world.despawn(id);
+                        continue;
+                    }
+                    if seek.is_some() {
+                        world.remove_component(id, seek_to()).unwrap();
+                    }
+                    world
+                        .add_component(id, playback_position(), position)
+                        .unwrap();
+                }
+            }),
+            // Stops the sounds whose playing entity was despawned
+            query((crate::sound_id(), crate::sound_bus()))
+                .incl(playing_sound())
+                .despawned()
+                .to_system(|q, world, qs, _| {
+                    for (_, (&id, bus)) in q.iter(world, qs) {
+                        bus.stop(id);
+                    }
+                }),
+            // Configures the buses from the entities describing them
+            query(bus_name()).to_system(|q, world, qs, _| {
+                let Some(buses) = world.resource_opt(crate::audio_buses()) else {
+                    return;
+                };
+                for (id, name) in q.iter(world, qs) {
+                    let bus = buses.get_or_create(name);
+                    if name != MASTER_BUS {
+                        let parent = world
+                            .get_ref(id, bus_parent())
+                            .map_or(MASTER_BUS, |parent| parent.as_str());
+                        let parent = buses.get_or_create(parent);
+                        if let Err(err) = buses.set_parent(&bus, &parent) {
+                            tracing::warn!("Failed to set the parent of bus {name:?}: {err}");
+                        }
+                    }
+                    bus.set_gain(world.get(id, bus_gain()).unwrap_or(1.0));
+                    bus.set_muted(world.has_component(id, bus_muted()));
+                    bus.set_effects(&bus_effects(world, id));
+                }
+            }),
+            // Resets the buses whose configuring entity was despawned
+            query(bus_name()).despawned().to_system(|q, world, qs, _| {
+                let Some(buses) = world.resource_opt(crate::audio_buses()) else {
+                    return;
+                };
+                for (_, name) in q.iter(world, qs) {
+                    if let Some(bus) = buses.get(name) {
+                        bus.set_gain(1.0);
+                        bus.set_muted(false);
+                        bus.set_effects(&[]);
+                    }
+                }
+            }),
             query(stop_now()).to_system(|q, world, qs, _| {
                 for (playing_entity, _) in q.collect_cloned(world, qs) {
                     // check if mute_audio is set
                     let r = world.resource_entity();
-                    if !world.has_component(r, crate::audio_mixer()) {
+                    if !world.has_component(r, crate::audio_buses()) {
                         continue;
                     }
 
-                    let mixer = world.resource(crate::audio_mixer());
-                    let Ok(id) = world.get(playing_entity, crate::sound_id()) else {
+                    let (Ok(id), Ok(bus)) = (
+                        world.get(playing_entity, crate::sound_id()),
+                        world.get_cloned(playing_entity, crate::sound_bus()),
+                    ) else {
                         tracing::error!(
                             "No sound id component on playing entity; cannot stop audio."
                         );
                         continue;
                     };
-                    mixer.stop(id);
+                    bus.stop(id);
+                    if world.has_component(playing_entity, playing_sound()) {
+                        world.despawn(playing_entity);
+                        continue;
+                    }
                     // stopping an emitter is different
                     if world.has_component(playing_entity, audio_emitter()) {
                         let _ = world.remove_component(playing_entity, audio_emitter());
@@ -112,11 +235,14 @@ pub fn audio_systems() -> SystemGroup {
                 for (playing_entity, (_, amp)) in q.collect_cloned(world, qs) {
                     // check if mute_audio is set
                     let r = world.resource_entity();
-                    if !world.has_component(r, crate::audio_mixer()) {
+                    if !world.has_component(r, crate::audio_buses()) {
                         continue;
                     }
-                    if let Ok(amp_arc) = world.get_mut(playing_entity, crate::amplitude_arc()) {
-                        *amp_arc.lock() = amp;
+                    if let Ok(amp_param) = world.get_ref(playing_entity, crate::amplitude_arc()) {
+                        // A fade owns the value until it has finished
+                        if !amp_param.is_ramping() {
+                            amp_param.set(amp);
+                        }
                     }
                 }
             }),
@@ -124,7 +250,7 @@ pub fn audio_systems() -> SystemGroup {
                 for (playing_entity, (_, pan)) in q.collect_cloned(world, qs) {
                     // check if mute_audio is set
                     let r = world.resource_entity();
-                    if !world.has_component(r, crate::audio_mixer()) {
+                    if !world.has_component(r, crate::audio_buses()) {
                         continue;
                     }
                     if let Ok(pan_arc) = world.get_mut(playing_entity, crate::panning_arc()) {
@@ -136,19 +262,29 @@ pub fn audio_systems() -> SystemGroup {
                 for (playing_entity, (_, freq)) in q.collect_cloned(world, qs) {
                     // check if mute_audio is set
                     let r = world.resource_entity();
-                    if !world.has_component(r, crate::audio_mixer()) {
+                    if !world.has_component(r, crate::audio_buses()) {
                         continue;
                     }
-                    if let Ok(freq_arc) = world.get_mut(playing_entity, crate::onepole_arc()) {
-                        *freq_arc.lock() = freq;
+                    if let Ok(freq_param) = world.get_ref(playing_entity, crate::onepole_arc()) {
+                        if !freq_param.is_ramping() {
+                            freq_param.set(freq);
+                        }
+                    }
+                }
+            }),
+            query(crate::pitch_param()).to_system(|q, world, qs, _| {
+                for (playing_entity, pitch_param) in q.iter(world, qs) {
+                    if !pitch_param.is_ramping() {
+                        pitch_param.set(world.get(playing_entity, pitch()).unwrap_or(1.0));
                     }
                 }
             }),
+            Box::new(crate::automation::automation_systems()),
             query((is_audio_player(), play_now(), audio_url())).to_system(|q, world, qs, _| {
                 for (player, (_, _, url)) in q.collect_cloned(world, qs) {
                     // check if mute_audio is set
                     let r = world.resource_entity();
-                    if !world.has_component(r, crate::audio_mixer()) {
+                    if !world.has_component(r, crate::audio_buses()) {
                         continue;
                     }
 
@@ -156,6 +292,9 @@ pub fn audio_systems() -> SystemGroup {
                     let pan = world.get(player, panning()).unwrap_or(0.0);
                     let freq = world.get(player, onepole_lpf()).unwrap_or(20000.0);
                     let looping = world.get(player, looping()).unwrap_or(false);
+                    let streaming = world.has_component(player, streaming());
+                    let loop_points = loop_points(world, player);
+                    let bus = player_bus(world, player);
 
                     world.remove_component(player, play_now()).unwrap();
 
@@ -169,14 +308,7 @@ pub fn audio_systems() -> SystemGroup {
                     };
 
                     runtime.spawn(async move {
-                        let track =
-                            unwrap_log_warn!(AudioFromUrl { url: url.clone() }.get(&assets).await);
-                        let id_arc = Arc::new(Mutex::new(None));
-                        let id_arc_clone = id_arc.clone();
-                        let count_arc = Arc::new(Mutex::new(None));
-                        let count_arc_clone = count_arc.clone();
-                        let sr_arc = Arc::new(Mutex::new(None));
-                        let sr_arc_clone = sr_arc.clone();
+                        let track = unwrap_log_warn!(load_track(&assets, url, streaming).await);
                         async_run.run(move |world| {
                             let Some(id) = world
                                 .get_ref(player, children())
@@ -189,115 +321,309 @@ pub fn audio_systems() -> SystemGroup {
                                 );
                                 return;
                             };
-                            id_arc.lock().replace(id);
-
-                            let mut t: Box<dyn Source> = if looping {
-                                Box::new(track.decode().repeat())
-                            } else {
-                                let decoded = track.decode();
-                                let count = decoded.sample_count().unwrap();
-                                let sr = decoded.sample_rate();
-                                *count_arc.lock() = Some(count);
-                                *sr_arc.lock() = Some(sr);
-                                Box::new(decoded)
-                            };
-                            let a = Arc::new(Mutex::new(amp));
+                            if !world.exists(id) {
+                                return;
+                            }
+
+                            let decoded = track.decode();
+                            let sample_rate = decoded.sample_rate();
+                            let duration = decoded
+                                .sample_count()
+                                .map(|count| count as f32 / sample_rate as f32);
+                            let control = PlaybackHandle::default();
+                            let pitch_param = AutomatedParam::new(
+                                world.get(id, pitch()).unwrap_or(1.0),
+                                sample_rate,
+                            );
+                            let mut t: Box<dyn Source> = Box::new(
+                                Playback::new(decoded, control.clone(), looping)
+                                    .with_pitch(pitch_param.clone()),
+                            );
+                            let a = AutomatedParam::new(amp, sample_rate);
                             t = t.gain(a.clone());
                             let p = Arc::new(Mutex::new(pan));
                             t = t.pan(p.clone());
-                            let f = Arc::new(Mutex::new(freq));
+                            let f = AutomatedParam::new(freq, sample_rate);
                             t = t.onepole(f.clone());
 
-                            let id = id_arc.lock().unwrap();
                             let _ = world.add_component(id, crate::amplitude_arc(), a);
                             let _ = world.add_component(id, crate::panning_arc(), p);
                             let _ = world.add_component(id, crate::onepole_arc(), f);
+                            let _ = world.add_component(id, crate::pitch_param(), pitch_param);
+                            let _ = world.add_component(id, crate::playback_control(), control);
+                            if let Some(duration) = duration {
+                                let _ = world.add_component(id, playback_duration(), duration);
+                            }
 
-                            let mixer = world.resource(crate::audio_mixer());
-                            let sound = mixer.play(t);
+                            let sound = play_on_bus(world, id, &bus, t);
 
                             let _ = world.add_component(id, crate::sound_id(), sound.id);
+                            let _ = world.add_component(id, crate::sound_bus(), bus);
+                            let _ =
+                                world.add_components(id, sound_loop_points(loop_points, &track));
                         });
-
-                        let count = *count_arc_clone.lock();
-                        let sr = *sr_arc_clone.lock();
-                        if let Some((count, sr)) = count.zip(sr) {
-                            let dur = count as f32 / sr as f32 * 1.001;
-                            ambient_sys::time::sleep_label(
-                                std::time::Duration::from_secs_f32(dur),
-                                "audio",
-                            )
-                            .await;
-                            async_run.run(move |world| {
-                                if let Some(id) = id_arc_clone.lock().take() {
-                                    world.despawn(id);
-                                }
-                            });
-                        };
                     });
                 }
             }),
+            // Publishes the audio clock, which sounds are scheduled against
+            Box::new(FnSystem::new(|world, _| {
+                if let Some(buses) = world.resource_opt(crate::audio_buses()) {
+                    let time = buses.clock().time();
+                    world.add_resource(audio_time(), time);
+                }
+            })),
+            Box::new(crate::reverb::reverb_systems()),
+            Box::new(crate::metering::metering_systems()),
+            Box::new(occlusion_systems()),
         ],
     )
 }
 
+#[cfg(not(target_os = "unknown"))]
+fn occlusion_systems() -> SystemGroup {
+    SystemGroup::new(
+        "audio/occlusion",
+        vec![crate::occlusion::occlusion_system()],
+    )
+}
+
+/// There is no physics on the web, so sounds are never occluded
+#[cfg(target_os = "unknown")]
+fn occlusion_systems() -> SystemGroup {
+    SystemGroup::new("audio/occlusion", vec![])
+}
+
+/// The low-pass cutoff of spatial sounds which aren't occluded
+pub(crate) const UNOCCLUDED_CUTOFF: f32 = 20000.0;
+
+/// The attenuation used by spatial sounds without a `spatial_audio_attenuation`.
+pub const DEFAULT_ATTENUATION: Attenuation = Attenuation::InversePoly {
+    quad: 0.1,
+    lin: 0.0,
+    constant: 1.0,
+};
+
+fn attenuation_from_coefficients(coefficients: Vec3) -> Attenuation {
+    Attenuation::InversePoly {
+        constant: coefficients.x,
+        lin: coefficients.y,
+        quad: coefficients.z,
+    }
+}
+
+/// Returns the entity that spatial sounds with the `spatial_audio_listener` `listener` are heard
+/// from, which is the `active_audio_listener` if there is one
+pub(crate) fn active_listener(world: &World, listener: EntityId) -> EntityId {
+    world
+        .resource_opt(active_audio_listener())
+        .copied()
+        .filter(|&active| world.exists(active))
+        .unwrap_or(listener)
+}
+
+/// Plays the `source` of the playing sound `id` on `bus`, from its `start_at_audio_time` if it
+/// has one
+fn play_on_bus<S: Source + 'static>(
+    world: &World,
+    id: EntityId,
+    bus: &AudioBus,
+    source: S,
+) -> Sound {
+    match world.get(id, start_at_audio_time()) {
+        Ok(time) => {
+            let clock = world.resource(crate::audio_buses()).clock();
+            bus.play_at(source, clock, clock.frame_at(time))
+        }
+        Err(_) => bus.play(source),
+    }
+}
+
+/// Returns the bus that `player` plays on, creating it if it doesn't exist yet.
+fn player_bus(world: &World, player: EntityId) -> AudioBus {
+    let name = world
+        .get_ref(player, audio_bus())
+        .map_or(SFX_BUS, |name| name.as_str());
+    world.resource(crate::audio_buses()).get_or_create(name)
+}
+
+fn bus_effects(world: &World, id: EntityId) -> Vec<BusEffect> {
+    [
+        world.get(id, bus_low_pass()).ok().map(BusEffect::LowPass),
+        world.get(id, bus_high_pass()).ok().map(BusEffect::HighPass),
+        world
+            .get(id, reverb_wet())
+            .ok()
+            .map(|wet| BusEffect::Reverb {
+                room_size: world.get(id, reverb_room_size()).unwrap_or(0.5),
+                damping: world.get(id, reverb_damping()).unwrap_or(0.5),
+                wet,
+            }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// The loop points of a player, which are copied to the sounds it plays
+fn loop_points(world: &World, player: EntityId) -> Entity {
+    let mut res = Entity::new();
+    if let Ok(start) = world.get(player, loop_start()) {
+        res.set(loop_start(), start);
+    }
+    if let Ok(end) = world.get(player, loop_end()) {
+        res.set(loop_end(), end);
+    }
+    res
+}
+
+/// The loop points of a sound: the ones of its player if it has any, or else the ones in the
+/// metadata of its track
+fn sound_loop_points(player_loop_points: Entity, track: &Track) -> Entity {
+    if !player_loop_points.is_empty() {
+        return player_loop_points;
+    }
+    let mut res = Entity::new();
+    if let Some(points) = track.loop_points() {
+        res.set(loop_start(), points.start);
+        if let Some(end) = points.end {
+            res.set(loop_end(), end);
+        }
+    }
+    res
+}
+
+async fn load_track(
+    assets: &AssetCache,
+    url: AbsAssetUrl,
+    streaming: bool,
+) -> Result<Arc<Track>, Arc<ambient_audio::Error>> {
+    if streaming {
+        AudioStreamFromUrl { url }.get(assets).await
+    } else {
+        AudioFromUrl { url }.get(assets).await
+    }
+}
+
 fn process_player(world: &mut World, player: EntityId, url: &str) {
     // check if mute_audio is set
     let r = world.resource_entity();
-    if !world.has_component(r, crate::audio_mixer()) {
+    if !world.has_component(r, crate::audio_buses()) {
         return;
     }
 
     let amp = world.get(player, amplitude()).unwrap_or(1.0);
     let looping = world.get(player, looping()).unwrap_or(false);
+    let attenuation = world
+        .get(player, spatial_audio_attenuation())
+        .map(attenuation_from_coefficients)
+        .unwrap_or(DEFAULT_ATTENUATION);
+    let doppler = world.get(player, doppler_factor()).ok();
+    let priority = world.get(player, audio_priority()).ok();
+    let streaming = world.has_component(player, streaming());
+    let loop_points = loop_points(world, player);
+    let bus = player_bus(world, player);
     world.remove_component(player, play_now()).unwrap();
 
+    // The guest spawns the playing sound entity as the last child of the player
+    let Some(id) = world
+        .get_ref(player, children())
+        .ok()
+        .and_then(|c| c.last())
+        .copied()
+    else {
+        tracing::error!("No children component on spatial audio player; cannot play audio.");
+        return;
+    };
+
     let assets = world.resource(asset_cache()).clone();
     let runtime = world.resource(runtime()).clone();
     let async_run = world.resource(async_run()).clone();
     let url = unwrap_log_warn!(AbsAssetUrl::from_str(url).and_then(|u| u.to_download_url(&assets)));
 
     runtime.spawn(async move {
-        let track = unwrap_log_warn!(AudioFromUrl { url: url.clone() }.get(&assets).await);
+        let track = unwrap_log_warn!(load_track(&assets, url, streaming).await);
         async_run.run(move |world| {
+            if !world.exists(id) {
+                return;
+            }
             let Ok(listener_id) = world.get(player, spatial_audio_listener()) else {
                 return;
             };
             let Ok(emitter_id) = world.get(player, spatial_audio_emitter()) else {
                 return;
             };
-            let Ok(listener_transform) = world.get(listener_id, local_to_world()) else {
+            let Ok(listener_transform) =
+                world.get(active_listener(world, listener_id), local_to_world())
+            else {
                 return;
             };
 
-            let pos_emitter = world.get(emitter_id, translation()).unwrap();
-
             let listener = Arc::new(Mutex::new(AudioListener::new(
-                listener_transform,
+                Y_UP_LHS * listener_transform,
                 glam::Vec3::X * 0.3,
             )));
             let emitter = Arc::new(Mutex::new(AudioEmitter {
-                amplitude: amp,
-                attenuation: Attenuation::InversePoly {
-                    quad: 0.1,
-                    lin: 0.0,
-                    constant: 1.0,
-                },
-                pos: pos_emitter,
+                amplitude: 1.0,
+                attenuation,
+                pos: get_world_position(world, emitter_id).unwrap_or_default(),
             }));
-            let _ = world.add_component(emitter_id, audio_emitter(), emitter.clone());
-            let _ = world.add_component(listener_id, audio_listener(), listener.clone());
+            let cutoff_arc = Arc::new(Mutex::new(UNOCCLUDED_CUTOFF));
+            let control = PlaybackHandle::default();
+            let sends = SendHandle::default();
+            let virtual_handle = VirtualHandle::default();
 
+            let decoded = track.decode();
+            let sample_rate = decoded.sample_rate();
+            let duration = decoded
+                .sample_count()
+                .map(|count| count as f32 / sample_rate as f32);
+            let amp_param = AutomatedParam::new(amp, sample_rate);
+            let pitch_param =
+                AutomatedParam::new(world.get(id, pitch()).unwrap_or(1.0), sample_rate);
             let hrtf_lib = world.resource(hrtf_lib());
+            let source = Playback::new(decoded, control.clone(), looping)
+                .with_pitch(pitch_param.clone())
+                .gain(amp_param.clone())
+                .onepole(cutoff_arc.clone())
+                .spatial(hrtf_lib, listener.clone(), emitter.clone())
+                .effect_send(sends.clone());
+            let sound = play_on_bus(
+                world,
+                id,
+                &bus,
+                Virtualize::new(source, virtual_handle.clone(), control.clone()),
+            );
 
-            let mixer = world.resource(crate::audio_mixer());
-            let source: Box<dyn Source> = if looping {
-                Box::new(track.decode().repeat().spatial(hrtf_lib, listener, emitter))
-            } else {
-                Box::new(track.decode().spatial(hrtf_lib, listener, emitter))
-            };
-            let sound = mixer.play(source);
-            let _ = world.add_component(emitter_id, crate::sound_id(), sound.id);
+            world
+                .add_components(
+                    id,
+                    Entity::new()
+                        .with(audio_emitter(), emitter)
+                        .with(audio_listener(), listener)
+                        .with(spatial_audio_emitter(), emitter_id)
+                        .with(spatial_audio_listener(), listener_id)
+                        .with(crate::amplitude_arc(), amp_param)
+                        .with(crate::pitch_param(), pitch_param)
+                        .with(crate::occlusion_cutoff_arc(), cutoff_arc)
+                        .with(crate::playback_control(), control)
+                        .with(crate::reverb_sends(), sends)
+                        .with(crate::virtual_handle(), virtual_handle)
+                        .with(crate::sound_id(), sound.id)
+                        .with(crate::sound_bus(), bus)
+                        .with_merge(sound_loop_points(loop_points, &track)),
+                )
+                .unwrap();
+            if let Some(duration) = duration {
+                world
+                    .add_component(id, playback_duration(), duration)
+                    .unwrap();
+            }
+            if let Some(factor) = doppler {
+                world.add_component(id, doppler_factor(), factor).unwrap();
+            }
+            if let Some(priority) = priority {
+                world.add_component(id, audio_priority(), priority).unwrap();
+            }
         });
     });
 }
@@ -0,0 +1,48 @@
+use ambient_ecs::{generated::audio::components::*, query, DynSystem};
+use itertools::Itertools;
+
+use crate::{amplitude_arc, audio_emitter, audio_listener, virtual_handle};
+
+/// The number of spatial sounds played at once without a `max_audible_sounds` resource
+const DEFAULT_MAX_AUDIBLE_SOUNDS: u32 = 32;
+/// Sounds quieter than this at their listener (-60 dB) are virtualized even below the limit
+const INAUDIBLE_GAIN: f32 = 0.001;
+
+/// Virtualizes the spatial sounds that aren't among the `max_audible_sounds` loudest, ordered by
+/// `audio_priority` first, or that are too quiet to be heard. Virtualized sounds aren't processed
+/// by the mixer, but keep advancing, and are revived where they would have been once they are
+/// audible again.
+pub fn virtualization_system() -> DynSystem {
+    query((virtual_handle(), audio_emitter(), audio_listener())).to_system(|q, world, qs, _| {
+        let max_audible = world
+            .resource_opt(max_audible_sounds())
+            .copied()
+            .unwrap_or(DEFAULT_MAX_AUDIBLE_SOUNDS) as usize;
+
+        let sounds = q
+            .iter(world, qs)
+            .map(|(id, (handle, emitter, listener))| {
+                let amplitude = world
+                    .get_ref(id, amplitude_arc())
+                    .map_or(1.0, |a| a.value());
+                let gain = amplitude * emitter.lock().gain_at(&listener.lock());
+                let priority = world.get(id, audio_priority()).unwrap_or(0.0);
+                (id, handle.clone(), priority, gain)
+            })
+            .sorted_by(|a, b| b.2.total_cmp(&a.2).then(b.3.total_cmp(&a.3)))
+            .collect_vec();
+
+        for (i, (id, handle, _, gain)) in sounds.into_iter().enumerate() {
+            let is_virtual = i >= max_audible || gain < INAUDIBLE_GAIN;
+            *handle.lock() = is_virtual;
+            if is_virtual == world.has_component(id, virtualized()) {
+                continue;
+            }
+            if is_virtual {
+                world.add_component(id, virtualized(), ()).unwrap();
+            } else {
+                world.remove_component(id, virtualized()).unwrap();
+            }
+        }
+    })
+}
@@ -40,6 +40,10 @@ export type Pipeline = {
       flip_normals?: boolean,
       /// Whether or not the indices should be reversed for each triangle. On by default.
       reverse_indices?: boolean,
+      /// The maximum number of convex hulls each mesh is split into when the collider is dynamic.
+      /// Dynamic colliders have to be convex, so concave meshes are decomposed into several convex hulls
+      /// to keep their shape. Set this to 1 to use a single convex hull. Defaults to 16.
+      max_convex_hulls?: u32,
     } | {
       /// Use a cylindrical character collider.
       type: "Character",
@@ -187,6 +191,48 @@ export type Pipeline = {
       /// Re-center this mesh such that the root is located at the origin.
       type: "Center",
     })[],
+    /// If specified, the animations of each model are retargeted onto the skeleton of another model,
+    /// so that they can be played on a character with different proportions.
+    ///
+    /// Only supported by the regular importer.
+    retarget?: {
+      /// The path to the model whose skeleton the animations are retargeted onto, relative to the pipeline.
+      model: string,
+      /// The path to a skeleton map TOML file, relative to the pipeline. If not specified, the bones
+      /// of the two skeletons are matched by name.
+      skeleton_map?: string,
+    },
+    /// If specified, levels of detail are generated for each model by simplifying its meshes, and
+    /// shown depending on its size on the screen, like authored ones.
+    ///
+    /// The nodes which already have authored levels of detail merged with `lods` are left as is.
+    generate_lods?: {
+      /// The fraction of the triangles of the model kept by each generated level of detail, from the
+      /// most detailed to the least. The model itself is the first level, and isn't listed.
+      ///
+      /// If not specified, each level keeps half of the triangles of the previous one, for three levels.
+      ratios?: f32[],
+      /// The furthest the surface of a generated level of detail may stray from the model, as a
+      /// fraction of the radius of its bounds. The simplification of a level stops there even if it
+      /// keeps more triangles than its ratio, so that detailed shapes keep their silhouette.
+      ///
+      /// If not specified, it is 5% of the radius.
+      max_error?: f32,
+      /// The minimum screen size of each level of detail, including the model itself and the
+      /// imposter, like the `screen_sizes` of `lods`.
+      screen_sizes?: f32[],
+      /// If specified, an octahedral imposter is baked as the last level of detail: a quad showing the
+      /// model from the closest of many directions, rendered to a texture at build time.
+      ///
+      /// Skinned meshes don't get an imposter, as it couldn't follow their animations.
+      imposter?: {
+        /// The number of views along each side of the grid of views of the model, of at least 2.
+        /// Defaults to 8, for 64 views.
+        frames?: u32,
+        /// The width and height of each view, in pixels. Defaults to 128.
+        resolution?: u32,
+      },
+    },
   } | {
     /// The materials asset pipeline.
     /// Will import specific materials without needing to be part of a model.
@@ -240,6 +286,56 @@ export type Pipeline = {
     type: "Audio",
     /// Whether or not the audio should be converted to Ogg Vorbis.
     convert?: boolean,
+    /// Normalizes the loudness of the audio to this integrated loudness, in LUFS, converting it to Ogg Vorbis.
+    loudness?: f32,
+  } | {
+    /// The environment maps asset pipeline.
+    /// Will prefilter HDR images (`.hdr` or `.exr`) into environment maps, which light a scene and can be shown as its sky.
+    type: "EnvironmentMaps",
+    /// The width, in pixels, of the environment maps, whose height is half of it. Defaults to 512.
+    size?: u32,
+  } | {
+    /// The texture atlases asset pipeline.
+    /// Will pack images into a single texture atlas, whose images can be shown by UI elements and nine-sliced.
+    type: "TextureAtlases",
+    /// The name of the atlas, which is written as `<name>.atlas.json` and `<name>.atlas.png`. Defaults to `atlas`.
+    name?: string,
+    /// The pixels left between the images, which are filled with their edges. Defaults to 2.
+    padding?: u32,
+    /// The largest width and height of each page of the atlas, in pixels; the images that don't fit in one page are spread over several. Defaults to 4096.
+    max_size?: u32,
+    /// The nine-slice insets of images, as top, right, bottom and left in pixels, by the name of the image.
+    slices?: {[name: string]: Vec4},
+  } | {
+    /// The string tables asset pipeline.
+    /// Will merge TOML string tables, one for each locale, into the strings the runtime translates text with.
+    type: "StringTables",
+    /// The name of the string tables, which are written as `<name>.strings.json`. Defaults to `locales`.
+    name?: string,
+  } | {
+    /// The navmeshes asset pipeline.
+    /// Will bake the models of a level into a single navigation mesh, which agents find paths on at runtime.
+    type: "Navmeshes",
+    /// The name of the navmesh, which is written as `<name>.navmesh`. Defaults to `navmesh`.
+    name?: string,
+    /// The radius of the agent, in meters. Defaults to 0.4.
+    agent_radius?: f32,
+    /// The height of the agent, in meters. Defaults to 1.8.
+    agent_height?: f32,
+    /// The steepest slope the agent can walk up, in degrees. Defaults to 45.
+    max_slope?: f32,
+    /// The highest step the agent can climb, in meters. Defaults to 0.4.
+    max_climb?: f32,
+    /// The size of the cells the level is voxelized into, in meters. Defaults to half of `agent_radius`.
+    cell_size?: f32,
+  } | {
+    /// A pipeline run by a user-supplied asset processor.
+    /// Will import each file with the processor, to support formats the other pipelines don't, like voxel files or tilemaps.
+    type: "Custom",
+    /// The name of the asset processor to import the files with, declared in the `[build.asset-processors]` section of `ambient.toml`.
+    processor: string,
+    /// Settings passed as is to the asset processor.
+    config?: {[key: string]: any},
   },
   /// Filter the sources used to feed this pipeline.
   /// This is a list of glob patterns for accepted files.
@@ -1,15 +1,22 @@
 use crate::{
     core::{
         animation::components::{
-            apply_base_pose, bind_id, bind_ids, blend, clip_duration, freeze_at_percentage,
-            freeze_at_time, is_animation_player, looping, mask_bind_ids, mask_weights,
-            play_clip_from_url, retarget_animation_scaled, retarget_model_from_url, start_time,
+            self, animation_graph_from_url, animation_graph_parameter_values,
+            animation_graph_parameters, animation_graph_states, apply_base_pose, attach_to_model,
+            bind_id, bind_ids, blend, clip_duration, freeze_at_percentage, freeze_at_time,
+            is_animation_player, looping, mask_bind_ids, mask_weights, play_clip_from_url,
+            retarget_animation_scaled, retarget_model_from_url, start_time,
         },
         app::components::name,
         hierarchy::components::{children, parent},
+        transform::components::local_to_world,
     },
     entity,
-    prelude::{epoch_time, Entity, EntityId},
+    internal::{
+        conversion::{FromBindgen, IntoBindgen},
+        wit,
+    },
+    prelude::{epoch_time, Entity, EntityId, Mat4},
 };
 use std::time::Duration;
 
@@ -224,6 +231,61 @@ impl AsRef<AnimationNodeRef> for PlayClipFromUrlNodeRef {
     }
 }
 
+/// Animation graph node.
+/// This is an animation node which can be plugged into an animation player or other animation nodes.
+///
+/// It plays a graph of layered state machines, whose transitions are driven by parameters. When
+/// the states of the graph reach their events, an `AnimationEvent` message is sent.
+///
+/// This is just a reference to an entity which lives in the ecs. You need to call `despawn` to
+/// remove it.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationGraphNodeRef(pub AnimationNodeRef);
+impl AnimationGraphNodeRef {
+    /// Create a new node playing the graph at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        let node = Entity::new()
+            .with(animation_graph_from_url(), url.into())
+            .with(name(), "Animation graph".to_string())
+            .spawn();
+        Self(AnimationNodeRef(node))
+    }
+    /// Use an existing node
+    pub fn from_entity(entity: EntityId) -> Self {
+        Self(AnimationNodeRef::from_entity(entity))
+    }
+    /// Set a parameter of the graph. The transitions whose conditions now hold are taken on the next frame.
+    pub fn set_parameter(&self, parameter: &str, value: f32) {
+        let mut parameters =
+            entity::get_component(self.0 .0, animation_graph_parameters()).unwrap_or_default();
+        let mut values = entity::get_component(self.0 .0, animation_graph_parameter_values())
+            .unwrap_or_default();
+        values.resize(parameters.len(), 0.);
+        match parameters.iter().position(|x| x == parameter) {
+            Some(index) => values[index] = value,
+            None => {
+                parameters.push(parameter.to_string());
+                values.push(value);
+            }
+        }
+        entity::add_component(self.0 .0, animation_graph_parameters(), parameters);
+        entity::add_component(self.0 .0, animation_graph_parameter_values(), values);
+    }
+    /// Set a boolean parameter of the graph, which is 1 when true and 0 when false.
+    pub fn set_bool_parameter(&self, parameter: &str, value: bool) {
+        self.set_parameter(parameter, if value { 1. } else { 0. });
+    }
+    /// Returns the current state of each layer of the graph, or None if the graph hasn't been loaded yet
+    pub fn peek_states(&self) -> Option<Vec<String>> {
+        entity::get_component(self.0 .0, animation_graph_states())
+    }
+}
+impl AsRef<AnimationNodeRef> for AnimationGraphNodeRef {
+    fn as_ref(&self) -> &AnimationNodeRef {
+        &self.0
+    }
+}
+
 /// Blend animation node.
 /// This is an animation node which can be plugged into an animation player or other animation nodes.
 ///
@@ -345,6 +407,39 @@ pub fn get_bone_by_bind_id(entity: EntityId, bind_id: &BindId) -> Option<EntityI
     None
 }
 
+/// Get the bone or socket of the model on `entity` with the bind id or node name `bone`; for
+/// example "RightHand".
+///
+/// The bones of a model only exist once it is loaded, and only where it is spawned: on the client.
+pub fn get_bone(entity: EntityId, bone: &str) -> Option<EntityId> {
+    wit::animation::get_bone(entity.into_bindgen(), bone).from_bindgen()
+}
+
+/// Get the world transform of the bone or socket of the model on `entity` with the bind id or
+/// node name `bone`; see [get_bone].
+pub fn get_bone_transform(entity: EntityId, bone: &str) -> Option<Mat4> {
+    entity::get_component(get_bone(entity, bone)?, local_to_world())
+}
+
+/// Attach `entity` to the bone or socket `bone` of the model on `model`, such as a weapon to a
+/// hand. It follows the bone every frame, offset by its `local_to_parent`, even if the model is
+/// reloaded.
+///
+/// Attaching on the server attaches the entity for every player.
+pub fn attach_to_bone(entity: EntityId, model: EntityId, bone: &str) {
+    entity::add_components(
+        entity,
+        Entity::new()
+            .with(attach_to_model(), model)
+            .with(components::attach_to_bone(), bone.to_string()),
+    );
+}
+
+/// Detach `entity` from the bone it was attached to with [attach_to_bone].
+pub fn detach_from_bone(entity: EntityId) {
+    entity::remove_components(entity, &[&attach_to_model(), &components::attach_to_bone()]);
+}
+
 /// Valid bind ids
 #[derive(Debug, Clone)]
 pub enum BindId {
@@ -1,3 +1,5 @@
+use crate::{core::camera::components::camera_shake_trauma, entity, global::EntityId};
+
 #[cfg(feature = "client")]
 mod client {
     use crate::{
@@ -75,3 +77,13 @@ mod client {
 }
 #[cfg(feature = "client")]
 pub use client::*;
+
+/// Shakes `camera` by adding `trauma` to its `camera_shake_trauma`, which is capped at 1.
+///
+/// The shake grows with the square of the trauma, so small hits barely move the camera while
+/// explosions shake it hard.
+pub fn add_shake_trauma(camera: EntityId, trauma: f32) {
+    entity::mutate_component_with_default(camera, camera_shake_trauma(), trauma.min(1.), |value| {
+        *value = (*value + trauma).min(1.)
+    });
+}
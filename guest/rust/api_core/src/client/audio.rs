@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{cell::Cell, time::Duration};
 
 use crate::{
     core::{
@@ -6,10 +6,16 @@ use crate::{
         audio::components::*,
         ecs::components::remove_at_game_time,
         hierarchy::components::{children, parent, unmanaged_children},
-        transform::components::translation,
+        transform::components::{local_to_world, translation},
     },
+    ecs::{query, Component, GeneralQuery},
     entity,
-    prelude::{game_time, Entity, EntityId, Vec3},
+    prelude::{game_time, Entity, EntityId, Vec2, Vec3},
+};
+use once_cell::sync::Lazy;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    thread_rng, Rng,
 };
 
 /// stop the audio on the given entity
@@ -21,6 +27,200 @@ pub fn stop(entity: EntityId) {
     }
 }
 
+/// Pause the playing sound `entity`. It can be resumed with [resume].
+pub fn pause(entity: EntityId) {
+    entity::add_component(entity, paused(), ());
+}
+
+/// Resume the playing sound `entity` after it was paused with [pause].
+pub fn resume(entity: EntityId) {
+    entity::remove_component(entity, paused());
+}
+
+/// Returns whether the playing sound `entity` is paused.
+pub fn is_paused(entity: EntityId) -> bool {
+    entity::has_component(entity, paused())
+}
+
+/// Jump to `position` in the playing sound `entity`.
+pub fn seek(entity: EntityId, position: Duration) {
+    entity::add_component(entity, seek_to(), position.as_secs_f32());
+}
+
+/// Set the playback speed of the playing sound `entity`, which also shifts its pitch. 1.0 is the
+/// original speed.
+pub fn set_pitch(entity: EntityId, pitch: f32) {
+    entity::add_component(entity, self::pitch(), pitch);
+}
+
+/// Fade the amplitude of the playing sound `entity` to `target` over `duration`. The fade runs on
+/// the audio thread, so it is smooth regardless of the frame rate.
+pub fn fade_to(entity: EntityId, target: f32, duration: Duration) {
+    entity::add_component(
+        entity,
+        amplitude_fade(),
+        Vec2::new(target, duration.as_secs_f32()),
+    );
+}
+
+/// Ramp the pitch of the playing sound `entity` to `target` over `duration`, see [set_pitch].
+pub fn ramp_pitch_to(entity: EntityId, target: f32, duration: Duration) {
+    entity::add_component(
+        entity,
+        pitch_fade(),
+        Vec2::new(target, duration.as_secs_f32()),
+    );
+}
+
+/// Ramp the cutoff of the one-pole low-pass filter of the playing sound `entity` to `target` Hz
+/// over `duration`. Only sounds played with an [AudioPlayer] have this filter.
+pub fn ramp_cutoff_to(entity: EntityId, target: f32, duration: Duration) {
+    entity::add_component(
+        entity,
+        onepole_lpf_fade(),
+        Vec2::new(target, duration.as_secs_f32()),
+    );
+}
+
+/// Crossfade from the playing sound `from` to the playing sound `to`, like between two music
+/// tracks. `from` fades out and is stopped once it is silent, while `to` fades in from silence to
+/// full amplitude.
+pub fn crossfade(from: EntityId, to: EntityId, duration: Duration) {
+    fade_to(from, 0.0, duration);
+    entity::add_component(from, remove_at_game_time(), game_time() + duration);
+
+    entity::add_component(to, amplitude(), 0.0);
+    fade_to(to, 1.0, duration);
+}
+
+/// Set the frames the audio player or playing sound `entity` loops between when looping. With an
+/// `end` of `None`, it loops at the end of the audio.
+pub fn set_loop_points(entity: EntityId, start: u64, end: Option<u64>) {
+    entity::add_component(entity, loop_start(), start);
+    match end {
+        Some(end) => entity::add_component(entity, loop_end(), end),
+        None => entity::remove_component(entity, loop_end()),
+    }
+}
+
+fn set_streaming(player: EntityId, streaming: bool) {
+    if streaming {
+        entity::add_component(player, self::streaming(), ());
+    } else {
+        entity::remove_component(player, self::streaming());
+    }
+}
+
+/// Returns the position of the playing sound `entity`, or `None` if it hasn't started yet.
+pub fn position(entity: EntityId) -> Option<Duration> {
+    entity::get_component(entity, playback_position()).map(Duration::from_secs_f32)
+}
+
+/// Returns the duration of the playing sound `entity`, or `None` if it hasn't loaded yet.
+pub fn duration(entity: EntityId) -> Option<Duration> {
+    entity::get_component(entity, playback_duration()).map(Duration::from_secs_f32)
+}
+
+/// Hear every spatial sound from `listener` instead of the listener it was played with, or go
+/// back to their own listeners with `None`. Use this to switch cameras in spectator modes.
+pub fn set_active_listener(listener: Option<EntityId>) {
+    match listener {
+        Some(listener) => {
+            entity::add_component(entity::resources(), active_audio_listener(), listener)
+        }
+        None => entity::remove_component(entity::resources(), active_audio_listener()),
+    }
+}
+
+/// Set the maximum number of spatial sounds that are played at once. The quietest sounds, and
+/// those with the lowest priority, are virtualized beyond this.
+pub fn set_max_audible_sounds(max: u32) {
+    entity::add_component(entity::resources(), max_audible_sounds(), max);
+}
+
+/// Returns whether the playing sound `entity` is virtualized, so that it isn't heard until it is
+/// among the loudest sounds again.
+pub fn is_virtualized(entity: EntityId) -> bool {
+    entity::has_component(entity, virtualized())
+}
+
+/// Returns the time of the audio clock, in seconds of audio output, or `None` if there is no audio
+/// output. Schedule sounds against it with [AudioPlayer::play_at], which unlike playing them from
+/// a frame callback starts them on the exact sample.
+pub fn time() -> Option<f64> {
+    entity::get_component(entity::resources(), audio_time())
+}
+
+/// The loudness of the master bus, see [get_loudness]. Silence measures as negative infinity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Loudness {
+    /// The RMS level of the last 400 ms, in dBFS
+    pub rms: f32,
+    /// The highest sample of the last 400 ms, in dBFS
+    pub peak: f32,
+    /// The loudness of the last 400 ms, in LUFS
+    pub momentary: f32,
+    /// The loudness since metering was enabled, in LUFS
+    pub integrated: f32,
+}
+
+/// Start or stop measuring the loudness of the master bus, which is then returned by
+/// [get_loudness]. Enabling it again restarts the integrated loudness.
+pub fn set_metering(enabled: bool) {
+    if enabled {
+        entity::add_component(entity::resources(), audio_metering(), ());
+    } else {
+        entity::remove_component(entity::resources(), audio_metering());
+    }
+}
+
+/// Returns the loudness of the master bus, or `None` until metering has been enabled with
+/// [set_metering] and the first measurement is in.
+pub fn get_loudness() -> Option<Loudness> {
+    let r = entity::resources();
+    Some(Loudness {
+        rms: entity::get_component(r, audio_rms())?,
+        peak: entity::get_component(r, audio_peak())?,
+        momentary: entity::get_component(r, audio_momentary_loudness())?,
+        integrated: entity::get_component(r, audio_integrated_loudness())?,
+    })
+}
+
+/// Start recording the master bus to `recordings/<name>.wav` in the working directory of the
+/// client, replacing any recording in progress. Not supported on the web.
+pub fn start_recording(name: impl Into<String>) {
+    entity::add_component(entity::resources(), audio_recording(), name.into());
+}
+
+/// Stop the recording started with [start_recording], and finish its file
+pub fn stop_recording() {
+    entity::remove_component(entity::resources(), audio_recording());
+}
+
+/// Start or stop sending the microphone to the other players as voice chat.
+pub fn set_voice_capture(capture: bool) {
+    if capture {
+        entity::add_component(entity::resources(), voice_capture(), ());
+    } else {
+        entity::remove_component(entity::resources(), voice_capture());
+    }
+}
+
+/// Returns whether the microphone is being sent as voice chat. This becomes `false` if capturing
+/// failed, like when there is no microphone.
+pub fn is_voice_capturing() -> bool {
+    entity::has_component(entity::resources(), voice_capture())
+}
+
+/// Mute or unmute the voice chat of the player entity `player` for this client.
+pub fn set_voice_muted(player: EntityId, muted: bool) {
+    if muted {
+        entity::add_component(player, voice_muted(), ());
+    } else {
+        entity::remove_component(player, voice_muted());
+    }
+}
+
 /// play spatial audio
 #[derive(Debug, Clone)]
 pub struct SpatialAudioPlayer {
@@ -39,6 +239,8 @@ impl SpatialAudioPlayer {
         let player = Entity::new()
             .with(is_spatial_audio_player(), ())
             .with(name(), "Spatial audio player".to_string())
+            .with(children(), vec![])
+            .with(unmanaged_children(), ())
             .spawn();
         Self { player }
     }
@@ -59,10 +261,73 @@ impl SpatialAudioPlayer {
         entity::add_component(self.player, looping(), val);
     }
 
-    pub fn play_sound_on_entity(&self, url: impl Into<String>, emitter: EntityId) {
+    /// Set the frames the sounds loop between. See [set_loop_points].
+    pub fn set_loop_points(&self, start: u64, end: Option<u64>) {
+        set_loop_points(self.player, start, end);
+    }
+
+    /// Decode the sounds from disk as they play instead of loading them into memory first. Use
+    /// this for long audio like music.
+    pub fn set_streaming(&self, streaming: bool) {
+        set_streaming(self.player, streaming);
+    }
+
+    /// Set the bus the sounds play on, like `music`, `sfx` or `voice`. Defaults to `sfx`.
+    pub fn set_bus(&self, bus: impl Into<String>) {
+        entity::add_component(self.player, audio_bus(), bus.into());
+    }
+
+    /// Set how the volume falls off with the distance `d` to the listener, as
+    /// `1 / (constant + linear * d + quadratic * d²)`.
+    pub fn set_attenuation(&self, constant: f32, linear: f32, quadratic: f32) {
+        entity::add_component(
+            self.player,
+            spatial_audio_attenuation(),
+            Vec3::new(constant, linear, quadratic),
+        );
+    }
+
+    /// Set how strongly the pitch shifts as the emitter and listener move towards or away from
+    /// each other. 1.0 is physically accurate, and 0.0 disables the doppler effect.
+    pub fn set_doppler_factor(&self, factor: f32) {
+        entity::add_component(self.player, doppler_factor(), factor);
+    }
+
+    /// Set the priority of the sounds this player plays. When too many spatial sounds are playing,
+    /// those with a lower priority are virtualized first.
+    pub fn set_priority(&self, priority: f32) {
+        entity::add_component(self.player, audio_priority(), priority);
+    }
+
+    /// Play the sound from `emitter`, following it as it moves. This will generate a new entity
+    /// that represents the playing sound; add `amplitude` or `spatial_audio_attenuation` to it to
+    /// change the sound while it plays, or pass it to [stop] to stop it.
+    pub fn play_sound_on_entity(&self, url: impl Into<String>, emitter: EntityId) -> EntityId {
         entity::add_component(self.player, spatial_audio_emitter(), emitter);
         entity::add_component(self.player, audio_url(), url.into());
         entity::add_component(self.player, play_now(), ());
+        let id = Entity::new()
+            .with(playing_sound(), ())
+            .with(name(), "Playing sound".to_string())
+            .with(parent(), self.player)
+            .spawn();
+        entity::mutate_component(self.player, children(), |val| {
+            val.push(id);
+        });
+        id
+    }
+
+    /// Like [Self::play_sound_on_entity], but starts the sound on the exact sample at `time` of
+    /// the audio clock, see [time].
+    pub fn play_sound_on_entity_at(
+        &self,
+        url: impl Into<String>,
+        emitter: EntityId,
+        time: f64,
+    ) -> EntityId {
+        let id = self.play_sound_on_entity(url, emitter);
+        entity::add_component(id, start_at_audio_time(), time);
+        id
     }
 }
 impl SpatialAudioPlayer {
@@ -119,6 +384,22 @@ impl AudioPlayer {
         entity::add_component(self.entity, looping(), val);
     }
 
+    /// Set the frames the sound loops between. See [set_loop_points].
+    pub fn set_loop_points(&self, start: u64, end: Option<u64>) {
+        set_loop_points(self.entity, start, end);
+    }
+
+    /// Decode the sound from disk as it plays instead of loading it into memory first. Use this
+    /// for long audio like music.
+    pub fn set_streaming(&self, streaming: bool) {
+        set_streaming(self.entity, streaming);
+    }
+
+    /// Set the bus the sounds play on, like `music`, `sfx` or `voice`. Defaults to `sfx`.
+    pub fn set_bus(&self, bus: impl Into<String>) {
+        entity::add_component(self.entity, audio_bus(), bus.into());
+    }
+
     /// Add a simple onepole lowpass filter to the sound with one param: roll off frequency
     pub fn add_one_pole_lpf(&self, rolloff_freq: f32) {
         entity::add_component(self.entity, onepole_lpf(), rolloff_freq);
@@ -146,4 +427,325 @@ impl AudioPlayer {
         });
         id
     }
+
+    /// Play the sound from the exact sample at `time` of the audio clock, see [time]. Schedule
+    /// sounds a little ahead, as they first need to load.
+    pub fn play_at(&self, url: String, time: f64) -> EntityId {
+        let id = self.play(url);
+        entity::add_component(id, start_at_audio_time(), time);
+        id
+    }
+}
+
+/// A set of variations of a sound, like footsteps or impacts. Every time it is played, one of the
+/// clips is picked at random by its weight, with a random pitch and volume, so that repeated
+/// sounds don't all sound the same. The same clip is not picked twice in a row unless it is the
+/// only one with a weight.
+#[derive(Debug, Clone)]
+pub struct SoundContainer {
+    clips: Vec<(String, f32)>,
+    pitch: (f32, f32),
+    volume: (f32, f32),
+    last: Cell<Option<usize>>,
+}
+
+impl Default for SoundContainer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoundContainer {
+    /// Create an empty container, without any pitch or volume variation.
+    pub fn new() -> Self {
+        Self {
+            clips: Vec::new(),
+            pitch: (1.0, 1.0),
+            volume: (1.0, 1.0),
+            last: Cell::new(None),
+        }
+    }
+
+    /// Add the clip at `url`, which is picked with a probability relative to `weight`. A clip with a
+    /// zero weight is never picked.
+    pub fn with_clip(mut self, url: impl Into<String>, weight: f32) -> Self {
+        self.clips.push((url.into(), weight.max(0.0)));
+        self
+    }
+
+    /// Play the clips at a random speed between `min` and `max`, which also shifts their pitch.
+    pub fn with_pitch(mut self, min: f32, max: f32) -> Self {
+        self.pitch = (min, max);
+        self
+    }
+
+    /// Multiply the amplitude of the clips by a random value between `min` and `max`.
+    pub fn with_volume(mut self, min: f32, max: f32) -> Self {
+        self.volume = (min, max);
+        self
+    }
+
+    /// Play a variation with `player`. Returns the playing sound, or `None` if the container has
+    /// no clips with a weight.
+    pub fn play(&self, player: &AudioPlayer) -> Option<EntityId> {
+        let url = self.pick()?;
+        let amplitude = entity::get_component(player.entity, self::amplitude()).unwrap_or(1.0);
+        let id = player.play(url);
+        self.vary(id, amplitude);
+        Some(id)
+    }
+
+    /// Play a variation with `player` from `emitter`, see [SpatialAudioPlayer::play_sound_on_entity].
+    /// Returns the playing sound, or `None` if the container has no clips with a weight.
+    pub fn play_on_entity(
+        &self,
+        player: &SpatialAudioPlayer,
+        emitter: EntityId,
+    ) -> Option<EntityId> {
+        let url = self.pick()?;
+        let amplitude = entity::get_component(player.player, self::amplitude()).unwrap_or(1.0);
+        let id = player.play_sound_on_entity(url, emitter);
+        self.vary(id, amplitude);
+        Some(id)
+    }
+
+    fn pick(&self) -> Option<String> {
+        let weights = self
+            .clips
+            .iter()
+            .map(|(_, weight)| *weight)
+            .collect::<Vec<_>>();
+        let index = pick_index(&weights, self.last.get(), &mut thread_rng())?;
+        self.last.set(Some(index));
+        Some(self.clips[index].0.clone())
+    }
+
+    fn vary(&self, sound: EntityId, amplitude: f32) {
+        set_pitch(sound, random_between(self.pitch));
+        entity::add_component(
+            sound,
+            self::amplitude(),
+            amplitude * random_between(self.volume),
+        );
+    }
+}
+
+/// Picks an index of `weights` by its weight, other than `last` unless it's the only one with a
+/// weight. Returns `None` if all the weights are zero.
+fn pick_index(weights: &[f32], last: Option<usize>, rng: &mut impl Rng) -> Option<usize> {
+    let mut others = weights.to_vec();
+    if let Some(weight) = last.and_then(|last| others.get_mut(last)) {
+        *weight = 0.0;
+    }
+    WeightedIndex::new(&others)
+        .or_else(|_| WeightedIndex::new(weights))
+        .ok()
+        .map(|dist| dist.sample(rng))
+}
+
+fn random_between((min, max): (f32, f32)) -> f32 {
+    if min < max {
+        thread_rng().gen_range(min..=max)
+    } else {
+        min
+    }
+}
+
+/// A mixer bus. Every sound plays on a bus, and the buses are mixed into their parent bus, up to
+/// the `master` bus which is played on the speakers. The built-in buses are `master`, and its
+/// children `music`, `sfx` and `voice`.
+#[derive(Debug, Clone)]
+pub struct AudioBus {
+    /// The entity that configures the bus
+    pub entity: EntityId,
+}
+
+impl AudioBus {
+    /// The name of the master bus
+    pub const MASTER: &'static str = "master";
+    /// The name of the music bus
+    pub const MUSIC: &'static str = "music";
+    /// The name of the sound effects bus
+    pub const SFX: &'static str = "sfx";
+    /// The name of the voice bus
+    pub const VOICE: &'static str = "voice";
+
+    /// Get the bus `name`, creating it as a child of the master bus if it does not exist yet.
+    pub fn get(name: impl Into<String>) -> Self {
+        static QUERY: Lazy<GeneralQuery<Component<String>>> =
+            Lazy::new(|| query(bus_name()).build());
+
+        let name = name.into();
+        if let Some((entity, _)) = QUERY.evaluate().into_iter().find(|(_, n)| *n == name) {
+            return Self { entity };
+        }
+
+        let entity = Entity::new()
+            .with(self::name(), format!("Audio bus {name}"))
+            .with(bus_name(), name)
+            .spawn();
+        Self { entity }
+    }
+
+    /// Mix this bus into the bus `parent` instead of the master bus.
+    pub fn set_parent(&self, parent: impl Into<String>) {
+        entity::add_component(self.entity, bus_parent(), parent.into());
+    }
+
+    /// Set the gain of the bus; 0.0 is silent, 1.0 is unchanged.
+    pub fn set_gain(&self, gain: f32) {
+        entity::add_component(self.entity, bus_gain(), gain);
+    }
+
+    /// Mute or unmute the bus. The sounds on a muted bus keep playing silently.
+    pub fn set_muted(&self, muted: bool) {
+        if muted {
+            entity::add_component(self.entity, bus_muted(), ());
+        } else {
+            entity::remove_component(self.entity, bus_muted());
+        }
+    }
+
+    /// Filter out the frequencies of the bus above `cutoff`, or remove the filter with `None`.
+    pub fn set_low_pass(&self, cutoff: Option<f32>) {
+        match cutoff {
+            Some(cutoff) => entity::add_component(self.entity, bus_low_pass(), cutoff),
+            None => entity::remove_component(self.entity, bus_low_pass()),
+        }
+    }
+
+    /// Filter out the frequencies of the bus below `cutoff`, or remove the filter with `None`.
+    pub fn set_high_pass(&self, cutoff: Option<f32>) {
+        match cutoff {
+            Some(cutoff) => entity::add_component(self.entity, bus_high_pass(), cutoff),
+            None => entity::remove_component(self.entity, bus_high_pass()),
+        }
+    }
+
+    /// Add a reverb to everything that plays on the bus, or remove it with `None`.
+    pub fn set_reverb(&self, reverb: Option<Reverb>) {
+        match reverb {
+            Some(reverb) => set_reverb(self.entity, reverb),
+            None => {
+                entity::remove_component(self.entity, reverb_wet());
+                entity::remove_component(self.entity, reverb_room_size());
+                entity::remove_component(self.entity, reverb_damping());
+            }
+        }
+    }
+}
+
+/// The parameters of a reverb
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reverb {
+    /// How long the reverb rings, from 0 to 1
+    pub room_size: f32,
+    /// How quickly the high frequencies of the reverb die out, from 0 to 1
+    pub damping: f32,
+    /// How much of the sound goes through the reverb
+    pub wet: f32,
+}
+
+impl Default for Reverb {
+    fn default() -> Self {
+        Self {
+            room_size: 0.5,
+            damping: 0.5,
+            wet: 0.5,
+        }
+    }
+}
+
+fn set_reverb(entity: EntityId, reverb: Reverb) {
+    entity::add_component(entity, reverb_room_size(), reverb.room_size);
+    entity::add_component(entity, reverb_damping(), reverb.damping);
+    entity::add_component(entity, reverb_wet(), reverb.wet);
+}
+
+/// A volume with a reverb. Spatial sounds whose emitter is inside it send some of their sound
+/// to the reverb, so that sounds indoors and outdoors can sound different.
+#[derive(Debug, Clone)]
+pub struct ReverbZone {
+    /// The entity of the zone, which can be moved with its `translation` and `rotation`
+    pub entity: EntityId,
+}
+
+impl ReverbZone {
+    fn new(position: Vec3) -> Self {
+        let entity = Entity::new()
+            .with(name(), "Reverb zone".to_string())
+            .with(reverb_zone(), ())
+            .with(translation(), position)
+            .with(local_to_world(), Default::default())
+            .spawn();
+        Self { entity }
+    }
+
+    /// Create a spherical zone around `position`
+    pub fn sphere(position: Vec3, radius: f32) -> Self {
+        let zone = Self::new(position);
+        entity::add_component(zone.entity, reverb_zone_radius(), radius);
+        zone
+    }
+
+    /// Create a box-shaped zone around `position`, extending `half_extents` in each direction
+    pub fn cuboid(position: Vec3, half_extents: Vec3) -> Self {
+        let zone = Self::new(position);
+        entity::add_component(zone.entity, reverb_zone_extents(), half_extents);
+        zone
+    }
+
+    /// Set the reverb of the zone. `wet` is how much of the sound of the emitters inside it is
+    /// sent to the reverb.
+    pub fn set_reverb(&self, reverb: Reverb) {
+        set_reverb(self.entity, reverb);
+    }
+
+    /// Set the gain of the direct sound of the emitters inside the zone. Defaults to 1.0.
+    pub fn set_dry(&self, dry: f32) {
+        entity::add_component(self.entity, reverb_dry(), dry);
+    }
+
+    /// Set the distance outside of the zone over which the reverb fades out. Defaults to 1.0.
+    pub fn set_fade(&self, fade: f32) {
+        entity::add_component(self.entity, reverb_zone_fade(), fade);
+    }
+
+    /// Set the bus the reverb plays on. Defaults to `sfx`.
+    pub fn set_bus(&self, bus: impl Into<String>) {
+        entity::add_component(self.entity, audio_bus(), bus.into());
+    }
+
+    /// Remove the zone
+    pub fn despawn(self) {
+        entity::despawn(self.entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn pick_index_follows_the_weights() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let index = pick_index(&[1.0, 0.0, 2.0], None, &mut rng).unwrap();
+            assert_ne!(index, 1);
+        }
+        // The last clip isn't picked again, unless it's the only one with a weight
+        assert_eq!(pick_index(&[1.0, 0.0, 2.0], Some(2), &mut rng), Some(0));
+        assert_eq!(pick_index(&[0.0, 3.0], Some(1), &mut rng), Some(1));
+        assert_eq!(pick_index(&[2.0], Some(0), &mut rng), Some(0));
+    }
+
+    #[test]
+    fn pick_index_with_all_weights_zero_picks_nothing() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(pick_index(&[0.0, 0.0], None, &mut rng), None);
+        assert_eq!(pick_index(&[0.0, 0.0], Some(1), &mut rng), None);
+        assert_eq!(pick_index(&[], None, &mut rng), None);
+    }
 }
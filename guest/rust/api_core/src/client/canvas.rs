@@ -0,0 +1,54 @@
+pub use ambient_shared_types::canvas::{CanvasCommand, CanvasDrawing};
+
+use crate::{
+    global::EntityId,
+    internal::{conversion::IntoBindgen, wit},
+};
+
+/// Draws `drawing` onto the rect `entity`, replacing its previous drawing.
+///
+/// The entity should be a rect, like the entity of a `Rectangle` element; its size is not changed
+/// to match the drawing. The drawing is rasterized on the host, and only when it has changed, so
+/// this can be called every frame for animated drawings.
+///
+/// The drawing is shown over the `background_color` of the rect, which is transparent if the
+/// rect doesn't have one, and clipped to its `border_radius`.
+pub fn draw(entity: EntityId, drawing: &CanvasDrawing) {
+    use wit::client_canvas as canvas;
+
+    canvas::begin(entity.into_bindgen(), drawing.size.into_bindgen());
+    for command in &drawing.commands {
+        match command {
+            CanvasCommand::BeginPath => canvas::begin_path(),
+            CanvasCommand::MoveTo(point) => canvas::move_to(point.into_bindgen()),
+            CanvasCommand::LineTo(point) => canvas::line_to(point.into_bindgen()),
+            CanvasCommand::QuadTo { control, point } => {
+                canvas::quad_to(control.into_bindgen(), point.into_bindgen())
+            }
+            CanvasCommand::CubicTo {
+                control1,
+                control2,
+                point,
+            } => canvas::cubic_to(
+                control1.into_bindgen(),
+                control2.into_bindgen(),
+                point.into_bindgen(),
+            ),
+            CanvasCommand::ClosePath => canvas::close_path(),
+            CanvasCommand::Fill(color) => canvas::fill(color.into_bindgen()),
+            CanvasCommand::Stroke { color, width } => canvas::stroke(color.into_bindgen(), *width),
+            CanvasCommand::Image {
+                url,
+                position,
+                size,
+            } => canvas::image(url, position.into_bindgen(), size.into_bindgen()),
+            CanvasCommand::Text {
+                text,
+                position,
+                size,
+                color,
+            } => canvas::text(text, position.into_bindgen(), *size, color.into_bindgen()),
+        }
+    }
+    canvas::end();
+}
@@ -46,3 +46,42 @@ pub fn create(desc: &Descriptor) -> ProceduralMeshHandle {
 pub fn destroy(handle: ProceduralMeshHandle) {
     wit::client_mesh::destroy(handle.into_bindgen());
 }
+
+/// Replaces the vertices and indices of the mesh `handle` with the ones in `desc`.
+///
+/// Every entity using the mesh is updated.
+pub fn update(handle: ProceduralMeshHandle, desc: &Descriptor) {
+    wit::client_mesh::update(handle.into_bindgen(), &desc.into_bindgen());
+}
+
+/// Overwrites the vertices of the mesh `handle`, starting at `offset`.
+///
+/// Vertices past the end of the mesh are appended to it. When the vertex count stays the same,
+/// only the written vertices are uploaded to the GPU, which makes this suitable for updating
+/// parts of a large mesh every frame.
+pub fn update_vertices(handle: ProceduralMeshHandle, offset: u32, vertices: &[Vertex]) {
+    let vertices = vertices
+        .iter()
+        .map(|v| v.into_bindgen())
+        .collect::<Vec<_>>();
+    wit::client_mesh::update_vertices(handle.into_bindgen(), offset, &vertices);
+}
+
+/// Overwrites the positions of the vertices of the mesh `handle`, starting at `offset`,
+/// keeping their other attributes.
+///
+/// See [update_vertices] for how the vertices are written.
+pub fn update_positions(handle: ProceduralMeshHandle, offset: u32, positions: &[Vec3]) {
+    let positions = positions
+        .iter()
+        .map(|p| p.into_bindgen())
+        .collect::<Vec<_>>();
+    wit::client_mesh::update_positions(handle.into_bindgen(), offset, &positions);
+}
+
+/// Overwrites the indices of the mesh `handle`, starting at `offset`.
+///
+/// Indices past the end of the mesh are appended to it, and must all refer to existing vertices.
+pub fn update_indices(handle: ProceduralMeshHandle, offset: u32, indices: &[u32]) {
+    wit::client_mesh::update_indices(handle.into_bindgen(), offset, indices);
+}
@@ -21,3 +21,6 @@ pub mod sampler;
 
 /// **\[Client-only\]** Procedural material generation.
 pub mod material;
+
+/// **\[Client-only\]** 2D drawing onto UI rects, for custom minimaps, graphs and gauges.
+pub mod canvas;
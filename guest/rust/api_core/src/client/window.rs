@@ -1,6 +1,21 @@
-use crate::internal::wit;
+use glam::Vec2;
+
+use crate::internal::{conversion::IntoBindgen, wit};
 
 /// Request that the window enters or exits fullscreen mode.
 pub fn set_fullscreen(fullscreen: bool) {
     wit::client_window::set_fullscreen(fullscreen)
 }
+
+/// Set whether text can be composed with the operating system's input method editor (IME).
+///
+/// While it is allowed, the text being composed is sent as `WindowImeComposition` messages, and
+/// the composed text as `WindowKeyboardCharacter` messages.
+pub fn set_ime_allowed(allowed: bool) {
+    wit::client_window::set_ime_allowed(allowed)
+}
+
+/// Set where the input method editor's candidate window should be, in logical pixels.
+pub fn set_ime_position(position: Vec2) {
+    wit::client_window::set_ime_position(position.into_bindgen())
+}
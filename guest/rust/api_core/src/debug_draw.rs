@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use crate::{
+    global::{Quat, Vec3},
+    internal::{conversion::IntoBindgen, wit},
+};
+
+/// Draws a line from `start` to `end` in `color`.
+///
+/// The line is drawn for `duration`; a zero `duration` draws it for a single frame, so that it
+/// can be drawn again every frame.
+///
+/// Shapes drawn on the server are drawn for every player, while those drawn on the client are only
+/// drawn for the local player.
+pub fn line(start: Vec3, end: Vec3, color: Vec3, duration: Duration) {
+    wit::debug_draw::line(
+        start.into_bindgen(),
+        end.into_bindgen(),
+        color.into_bindgen(),
+        duration.into_bindgen(),
+    )
+}
+
+/// Draws the outline of a sphere at `center` with `radius` in `color`, for `duration`.
+///
+/// See [line] for how the `duration` is used.
+pub fn wire_sphere(center: Vec3, radius: f32, color: Vec3, duration: Duration) {
+    wit::debug_draw::wire_sphere(
+        center.into_bindgen(),
+        radius,
+        color.into_bindgen(),
+        duration.into_bindgen(),
+    )
+}
+
+/// Draws the edges of a box at `center` with `half_extents` and `rotation` in `color`, for
+/// `duration`.
+///
+/// See [line] for how the `duration` is used.
+pub fn wire_box(center: Vec3, half_extents: Vec3, rotation: Quat, color: Vec3, duration: Duration) {
+    wit::debug_draw::wire_box(
+        center.into_bindgen(),
+        half_extents.into_bindgen(),
+        rotation.into_bindgen(),
+        color.into_bindgen(),
+        duration.into_bindgen(),
+    )
+}
+
+/// Draws `text` centered above `position`, facing the camera, in `color` for `duration`.
+///
+/// `size` is the height of a line of text. Only ASCII letters, digits and common punctuation are
+/// supported, and letters are drawn in uppercase.
+///
+/// See [line] for how the `duration` is used.
+pub fn text(position: Vec3, text: &str, size: f32, color: Vec3, duration: Duration) {
+    wit::debug_draw::text(
+        position.into_bindgen(),
+        text,
+        size,
+        color.into_bindgen(),
+        duration.into_bindgen(),
+    )
+}
@@ -396,6 +396,98 @@ pub mod ambient {
             wit_import(wit_bindgen::rt::as_i64(t1_0), wit_bindgen::rt::as_i64(t1_1));
           }
         }
+        #[allow(clippy::all)]
+        pub fn update(handle: Handle,desc: &Descriptor,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let Handle{ ulid:ulid0, } = handle;
+            let (t1_0, t1_1, ) = ulid0;
+            let Descriptor{ vertices:vertices2, indices:indices2, } = desc;
+            let vec3 = vertices2;
+            let ptr3 = vec3.as_ptr() as i32;
+            let len3 = vec3.len() as i32;
+            let vec4 = indices2;
+            let ptr4 = vec4.as_ptr() as i32;
+            let len4 = vec4.len() as i32;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-mesh")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "update")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-mesh_update")]
+              fn wit_import(
+              _: i64, _: i64, _: i32, _: i32, _: i32, _: i32, );
+            }
+            wit_import(wit_bindgen::rt::as_i64(t1_0), wit_bindgen::rt::as_i64(t1_1), ptr3, len3, ptr4, len4);
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn update_vertices(handle: Handle,offset: u32,vertices: &[Vertex],){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let Handle{ ulid:ulid0, } = handle;
+            let (t1_0, t1_1, ) = ulid0;
+            let vec2 = vertices;
+            let ptr2 = vec2.as_ptr() as i32;
+            let len2 = vec2.len() as i32;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-mesh")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "update-vertices")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-mesh_update-vertices")]
+              fn wit_import(
+              _: i64, _: i64, _: i32, _: i32, _: i32, );
+            }
+            wit_import(wit_bindgen::rt::as_i64(t1_0), wit_bindgen::rt::as_i64(t1_1), wit_bindgen::rt::as_i32(offset), ptr2, len2);
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn update_positions(handle: Handle,offset: u32,positions: &[Vec3],){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let Handle{ ulid:ulid0, } = handle;
+            let (t1_0, t1_1, ) = ulid0;
+            let vec2 = positions;
+            let ptr2 = vec2.as_ptr() as i32;
+            let len2 = vec2.len() as i32;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-mesh")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "update-positions")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-mesh_update-positions")]
+              fn wit_import(
+              _: i64, _: i64, _: i32, _: i32, _: i32, );
+            }
+            wit_import(wit_bindgen::rt::as_i64(t1_0), wit_bindgen::rt::as_i64(t1_1), wit_bindgen::rt::as_i32(offset), ptr2, len2);
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn update_indices(handle: Handle,offset: u32,indices: &[u32],){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let Handle{ ulid:ulid0, } = handle;
+            let (t1_0, t1_1, ) = ulid0;
+            let vec2 = indices;
+            let ptr2 = vec2.as_ptr() as i32;
+            let len2 = vec2.len() as i32;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-mesh")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "update-indices")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-mesh_update-indices")]
+              fn wit_import(
+              _: i64, _: i64, _: i32, _: i32, _: i32, );
+            }
+            wit_import(wit_bindgen::rt::as_i64(t1_0), wit_bindgen::rt::as_i64(t1_1), wit_bindgen::rt::as_i32(offset), ptr2, len2);
+          }
+        }
         
       }
       
@@ -1046,6 +1138,246 @@ pub mod ambient {
       }
       
       
+      #[allow(clippy::all)]
+      pub mod client_canvas {
+        #[used]
+        #[doc(hidden)]
+        #[cfg(target_arch = "wasm32")]
+        static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
+        
+        pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
+        pub type Uvec2 = super::super::super::ambient::bindings::types::Uvec2;
+        pub type Vec2 = super::super::super::ambient::bindings::types::Vec2;
+        pub type Vec4 = super::super::super::ambient::bindings::types::Vec4;
+        #[allow(clippy::all)]
+        pub fn begin(entity: EntityId,size: Uvec2,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = entity;
+            let super::super::super::ambient::bindings::types::Uvec2{ x:x1, y:y1, } = size;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "begin")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_begin")]
+              fn wit_import(
+              _: i64, _: i64, _: i32, _: i32, );
+            }
+            wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10), wit_bindgen::rt::as_i32(x1), wit_bindgen::rt::as_i32(y1));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn begin_path(){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "begin-path")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_begin-path")]
+              fn wit_import(
+              );
+            }
+            wit_import();
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn move_to(point: Vec2,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::Vec2{ x:x0, y:y0, } = point;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "move-to")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_move-to")]
+              fn wit_import(
+              _: f32, _: f32, );
+            }
+            wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn line_to(point: Vec2,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::Vec2{ x:x0, y:y0, } = point;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "line-to")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_line-to")]
+              fn wit_import(
+              _: f32, _: f32, );
+            }
+            wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn quad_to(control: Vec2,point: Vec2,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::Vec2{ x:x0, y:y0, } = control;
+            let super::super::super::ambient::bindings::types::Vec2{ x:x1, y:y1, } = point;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "quad-to")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_quad-to")]
+              fn wit_import(
+              _: f32, _: f32, _: f32, _: f32, );
+            }
+            wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn cubic_to(control1: Vec2,control2: Vec2,point: Vec2,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::Vec2{ x:x0, y:y0, } = control1;
+            let super::super::super::ambient::bindings::types::Vec2{ x:x1, y:y1, } = control2;
+            let super::super::super::ambient::bindings::types::Vec2{ x:x2, y:y2, } = point;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "cubic-to")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_cubic-to")]
+              fn wit_import(
+              _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, );
+            }
+            wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn close_path(){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "close-path")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_close-path")]
+              fn wit_import(
+              );
+            }
+            wit_import();
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn fill(color: Vec4,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::Vec4{ x:x0, y:y0, z:z0, w:w0, } = color;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "fill")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_fill")]
+              fn wit_import(
+              _: f32, _: f32, _: f32, _: f32, );
+            }
+            wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(z0), wit_bindgen::rt::as_f32(w0));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn stroke(color: Vec4,width: f32,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let super::super::super::ambient::bindings::types::Vec4{ x:x0, y:y0, z:z0, w:w0, } = color;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "stroke")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_stroke")]
+              fn wit_import(
+              _: f32, _: f32, _: f32, _: f32, _: f32, );
+            }
+            wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(z0), wit_bindgen::rt::as_f32(w0), wit_bindgen::rt::as_f32(width));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn image(url: &str,position: Vec2,size: Vec2,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let vec0 = url;
+            let ptr0 = vec0.as_ptr() as i32;
+            let len0 = vec0.len() as i32;
+            let super::super::super::ambient::bindings::types::Vec2{ x:x1, y:y1, } = position;
+            let super::super::super::ambient::bindings::types::Vec2{ x:x2, y:y2, } = size;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "image")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_image")]
+              fn wit_import(
+              _: i32, _: i32, _: f32, _: f32, _: f32, _: f32, );
+            }
+            wit_import(ptr0, len0, wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn text(text: &str,position: Vec2,size: f32,color: Vec4,){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            let vec0 = text;
+            let ptr0 = vec0.as_ptr() as i32;
+            let len0 = vec0.len() as i32;
+            let super::super::super::ambient::bindings::types::Vec2{ x:x1, y:y1, } = position;
+            let super::super::super::ambient::bindings::types::Vec4{ x:x3, y:y3, z:z3, w:w3, } = color;
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "text")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_text")]
+              fn wit_import(
+              _: i32, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, );
+            }
+            wit_import(ptr0, len0, wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(size), wit_bindgen::rt::as_f32(x3), wit_bindgen::rt::as_f32(y3), wit_bindgen::rt::as_f32(z3), wit_bindgen::rt::as_f32(w3));
+          }
+        }
+        #[allow(clippy::all)]
+        pub fn end(){
+          
+          #[allow(unused_imports)]
+          use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+          unsafe {
+            
+            #[link(wasm_import_module = "ambient:bindings/client-canvas")]
+            extern "C" {
+              #[cfg_attr(target_arch = "wasm32", link_name = "end")]
+              #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-canvas_end")]
+              fn wit_import(
+              );
+            }
+            wit_import();
+          }
+        }
+        
+      }
+      
+      
       #[allow(clippy::all)]
       pub mod component {
         #[used]
@@ -11626,6 +11958,28 @@ pub mod ambient {
                                   #[cfg(target_arch = "wasm32")]
                                   static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
                                   
+                                  #[repr(u8)]
+                                  #[derive(Clone, Copy, PartialEq, Eq)]
+                                  pub enum Reliability {
+                                    ReliableOrdered,
+                                    ReliableUnordered,
+                                    UnreliableSequenced,
+                                  }
+                                  impl ::core::fmt::Debug for Reliability {
+                                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                                      match self {
+                                        Reliability::ReliableOrdered => {
+                                          f.debug_tuple("Reliability::ReliableOrdered").finish()
+                                        }
+                                        Reliability::ReliableUnordered => {
+                                          f.debug_tuple("Reliability::ReliableUnordered").finish()
+                                        }
+                                        Reliability::UnreliableSequenced => {
+                                          f.debug_tuple("Reliability::UnreliableSequenced").finish()
+                                        }
+                                      }
+                                    }
+                                  }
                                   #[allow(clippy::all)]
                                   pub fn subscribe(name: &str,){
                                     
@@ -11695,7 +12049,7 @@ pub mod ambient {
                                 
                                 
                                 #[allow(clippy::all)]
-                                pub mod ambient_package {
+                                pub mod animation {
                                   #[used]
                                   #[doc(hidden)]
                                   #[cfg(target_arch = "wasm32")]
@@ -11703,7 +12057,7 @@ pub mod ambient {
                                   
                                   pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
                                   #[allow(clippy::all)]
-                                  pub fn get_entity_for_package_id(package_id: &str,) -> Option<EntityId>{
+                                  pub fn get_bone(entity: EntityId,bone: &str,) -> Option<EntityId>{
                                     
                                     #[allow(unused_imports)]
                                     use wit_bindgen::rt::{alloc, vec::Vec, string::String};
@@ -11712,21 +12066,22 @@ pub mod ambient {
                                       #[repr(align(8))]
                                       struct RetArea([u8; 24]);
                                       let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
-                                      let vec0 = package_id;
-                                      let ptr0 = vec0.as_ptr() as i32;
-                                      let len0 = vec0.len() as i32;
-                                      let ptr1 = ret_area.as_mut_ptr() as i32;
-                                      #[link(wasm_import_module = "ambient:bindings/ambient-package")]
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = entity;
+                                      let vec1 = bone;
+                                      let ptr1 = vec1.as_ptr() as i32;
+                                      let len1 = vec1.len() as i32;
+                                      let ptr2 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/animation")]
                                       extern "C" {
-                                        #[cfg_attr(target_arch = "wasm32", link_name = "get-entity-for-package-id")]
-                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/ambient-package_get-entity-for-package-id")]
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "get-bone")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/animation_get-bone")]
                                         fn wit_import(
-                                        _: i32, _: i32, _: i32, );
+                                        _: i64, _: i64, _: i32, _: i32, _: i32, );
                                       }
-                                      wit_import(ptr0, len0, ptr1);
-                                      match i32::from(*((ptr1 + 0) as *const u8)) {
+                                      wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10), ptr1, len1, ptr2);
+                                      match i32::from(*((ptr2 + 0) as *const u8)) {
                                         0 => None,
-                                        1 => Some(super::super::super::ambient::bindings::types::EntityId{id0:*((ptr1 + 8) as *const i64) as u64, id1:*((ptr1 + 16) as *const i64) as u64, }),
+                                        1 => Some(super::super::super::ambient::bindings::types::EntityId{id0:*((ptr2 + 8) as *const i64) as u64, id1:*((ptr2 + 16) as *const i64) as u64, }),
                                         #[cfg(not(debug_assertions))]
                                         _ => ::core::hint::unreachable_unchecked(),
                                         #[cfg(debug_assertions)]
@@ -11739,23 +12094,220 @@ pub mod ambient {
                                 
                                 
                                 #[allow(clippy::all)]
-                                pub mod client_message {
+                                pub mod ambient_package {
                                   #[used]
                                   #[doc(hidden)]
                                   #[cfg(target_arch = "wasm32")]
                                   static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
                                   
                                   pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
-                                  #[derive(Clone, Copy)]
-                                  pub enum Target{
-                                    ServerUnreliable,
-                                    ServerReliable,
-                                    LocalBroadcast(bool),
-                                    Local(EntityId),
-                                  }
-                                  impl ::core::fmt::Debug for Target {
-                                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                                      match self {
+                                  #[allow(clippy::all)]
+                                  pub fn get_entity_for_package_id(package_id: &str,) -> Option<EntityId>{
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(8))]
+                                      struct RetArea([u8; 24]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let vec0 = package_id;
+                                      let ptr0 = vec0.as_ptr() as i32;
+                                      let len0 = vec0.len() as i32;
+                                      let ptr1 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/ambient-package")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "get-entity-for-package-id")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/ambient-package_get-entity-for-package-id")]
+                                        fn wit_import(
+                                        _: i32, _: i32, _: i32, );
+                                      }
+                                      wit_import(ptr0, len0, ptr1);
+                                      match i32::from(*((ptr1 + 0) as *const u8)) {
+                                        0 => None,
+                                        1 => Some(super::super::super::ambient::bindings::types::EntityId{id0:*((ptr1 + 8) as *const i64) as u64, id1:*((ptr1 + 16) as *const i64) as u64, }),
+                                        #[cfg(not(debug_assertions))]
+                                        _ => ::core::hint::unreachable_unchecked(),
+                                        #[cfg(debug_assertions)]
+                                        _ => panic!("invalid enum discriminant"),
+                                      }
+                                    }
+                                  }
+                                  
+                                }
+                                
+                                
+                                #[allow(clippy::all)]
+                                pub mod debug_draw {
+                                  #[used]
+                                  #[doc(hidden)]
+                                  #[cfg(target_arch = "wasm32")]
+                                  static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
+                                  
+                                  pub type Vec3 = super::super::super::ambient::bindings::types::Vec3;
+                                  pub type Quat = super::super::super::ambient::bindings::types::Quat;
+                                  pub type Duration = super::super::super::ambient::bindings::types::Duration;
+                                  #[allow(clippy::all)]
+                                  pub fn line(start: Vec3,end: Vec3,color: Vec3,duration: Duration,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x0, y:y0, z:z0, } = start;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x1, y:y1, z:z1, } = end;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x2, y:y2, z:z2, } = color;
+                                      let super::super::super::ambient::bindings::types::Duration{ seconds:seconds3, nanoseconds:nanoseconds3, } = duration;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/debug-draw")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "line")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/debug-draw_line")]
+                                        fn wit_import(
+                                        _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: i64, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(z0), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(z1), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2), wit_bindgen::rt::as_f32(z2), wit_bindgen::rt::as_i64(seconds3), wit_bindgen::rt::as_i32(nanoseconds3));
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn wire_sphere(center: Vec3,radius: f32,color: Vec3,duration: Duration,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x0, y:y0, z:z0, } = center;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x1, y:y1, z:z1, } = color;
+                                      let super::super::super::ambient::bindings::types::Duration{ seconds:seconds2, nanoseconds:nanoseconds2, } = duration;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/debug-draw")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "wire-sphere")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/debug-draw_wire-sphere")]
+                                        fn wit_import(
+                                        _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: i64, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(z0), wit_bindgen::rt::as_f32(radius), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(z1), wit_bindgen::rt::as_i64(seconds2), wit_bindgen::rt::as_i32(nanoseconds2));
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn wire_box(center: Vec3,half_extents: Vec3,rotation: Quat,color: Vec3,duration: Duration,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x0, y:y0, z:z0, } = center;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x1, y:y1, z:z1, } = half_extents;
+                                      let super::super::super::ambient::bindings::types::Quat{ x:x2, y:y2, z:z2, w:w2, } = rotation;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x3, y:y3, z:z3, } = color;
+                                      let super::super::super::ambient::bindings::types::Duration{ seconds:seconds4, nanoseconds:nanoseconds4, } = duration;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/debug-draw")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "wire-box")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/debug-draw_wire-box")]
+                                        fn wit_import(
+                                        _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: i64, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(z0), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(z1), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2), wit_bindgen::rt::as_f32(z2), wit_bindgen::rt::as_f32(w2), wit_bindgen::rt::as_f32(x3), wit_bindgen::rt::as_f32(y3), wit_bindgen::rt::as_f32(z3), wit_bindgen::rt::as_i64(seconds4), wit_bindgen::rt::as_i32(nanoseconds4));
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn text(position: Vec3,text: &str,size: f32,color: Vec3,duration: Duration,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x0, y:y0, z:z0, } = position;
+                                      let vec1 = text;
+                                      let ptr1 = vec1.as_ptr() as i32;
+                                      let len1 = vec1.len() as i32;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x2, y:y2, z:z2, } = color;
+                                      let super::super::super::ambient::bindings::types::Duration{ seconds:seconds3, nanoseconds:nanoseconds3, } = duration;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/debug-draw")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "text")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/debug-draw_text")]
+                                        fn wit_import(
+                                        _: f32, _: f32, _: f32, _: i32, _: i32, _: f32, _: f32, _: f32, _: f32, _: i64, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0), wit_bindgen::rt::as_f32(z0), ptr1, len1, wit_bindgen::rt::as_f32(size), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2), wit_bindgen::rt::as_f32(z2), wit_bindgen::rt::as_i64(seconds3), wit_bindgen::rt::as_i32(nanoseconds3));
+                                    }
+                                  }
+                                  
+                                }
+                                
+                                
+                                #[allow(clippy::all)]
+                                pub mod terrain {
+                                  #[used]
+                                  #[doc(hidden)]
+                                  #[cfg(target_arch = "wasm32")]
+                                  static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
+                                  
+                                  pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
+                                  pub type Vec2 = super::super::super::ambient::bindings::types::Vec2;
+                                  #[allow(clippy::all)]
+                                  pub fn raise(entity: EntityId,center: Vec2,radius: f32,amount: f32,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = entity;
+                                      let super::super::super::ambient::bindings::types::Vec2{ x:x1, y:y1, } = center;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/terrain")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "raise")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/terrain_raise")]
+                                        fn wit_import(
+                                        _: i64, _: i64, _: f32, _: f32, _: f32, _: f32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(radius), wit_bindgen::rt::as_f32(amount));
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn paint(entity: EntityId,center: Vec2,radius: f32,layer: u32,strength: f32,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = entity;
+                                      let super::super::super::ambient::bindings::types::Vec2{ x:x1, y:y1, } = center;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/terrain")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "paint")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/terrain_paint")]
+                                        fn wit_import(
+                                        _: i64, _: i64, _: f32, _: f32, _: f32, _: i32, _: f32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(radius), wit_bindgen::rt::as_i32(layer), wit_bindgen::rt::as_f32(strength));
+                                    }
+                                  }
+                                  
+                                }
+                                
+                                
+                                #[allow(clippy::all)]
+                                pub mod client_message {
+                                  #[used]
+                                  #[doc(hidden)]
+                                  #[cfg(target_arch = "wasm32")]
+                                  static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
+                                  
+                                  pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
+                                  pub type Reliability = super::super::super::ambient::bindings::message::Reliability;
+                                  #[derive(Clone, Copy)]
+                                  pub enum Target{
+                                    ServerUnreliable,
+                                    ServerReliable,
+                                    LocalBroadcast(bool),
+                                    Local(EntityId),
+                                    Server(Reliability),
+                                  }
+                                  impl ::core::fmt::Debug for Target {
+                                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                                      match self {
                                         Target::ServerUnreliable => {
                                           f.debug_tuple("Target::ServerUnreliable").finish()
                                         }
@@ -11768,6 +12320,9 @@ pub mod ambient {
                                         Target::Local(e) => {
                                           f.debug_tuple("Target::Local").field(e).finish()
                                         }
+                                        Target::Server(e) => {
+                                          f.debug_tuple("Target::Server").field(e).finish()
+                                        }
                                       }
                                     }
                                   }
@@ -11790,6 +12345,7 @@ pub mod ambient {
                                           
                                           (3i32, wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10))
                                         },
+                                        Target::Server(e) => (4i32, i64::from(e.clone() as i32), 0i64),
                                       };
                                       let vec2 = name;
                                       let ptr2 = vec2.as_ptr() as i32;
@@ -13485,6 +14041,7 @@ pub mod ambient {
                                   #[cfg(target_arch = "wasm32")]
                                   static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
                                   
+                                  pub type Vec2 = super::super::super::ambient::bindings::types::Vec2;
                                   #[allow(clippy::all)]
                                   pub fn set_fullscreen(fullscreen: bool,){
                                     
@@ -13502,6 +14059,41 @@ pub mod ambient {
                                       wit_import(match fullscreen { true => 1, false => 0 });
                                     }
                                   }
+                                  #[allow(clippy::all)]
+                                  pub fn set_ime_allowed(allowed: bool,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/client-window")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "set-ime-allowed")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-window_set-ime-allowed")]
+                                        fn wit_import(
+                                        _: i32, );
+                                      }
+                                      wit_import(match allowed { true => 1, false => 0 });
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn set_ime_position(position: Vec2,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::Vec2{ x:x0, y:y0, } = position;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/client-window")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "set-ime-position")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/client-window_set-ime-position")]
+                                        fn wit_import(
+                                        _: f32, _: f32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_f32(x0), wit_bindgen::rt::as_f32(y0));
+                                    }
+                                  }
                                   
                                 }
                                 
@@ -13527,6 +14119,7 @@ pub mod ambient {
                                   pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
                                   pub type Vec3 = super::super::super::ambient::bindings::types::Vec3;
                                   pub type Mat4 = super::super::super::ambient::bindings::types::Mat4;
+                                  pub type Duration = super::super::super::ambient::bindings::types::Duration;
                                   #[repr(C)]
                                   #[derive(Copy, Clone)]
                                   pub struct CharacterCollision {
@@ -13719,6 +14312,42 @@ pub mod ambient {
                                     }
                                   }
                                   #[allow(clippy::all)]
+                                  pub fn put_to_sleep(entity: EntityId,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = entity;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "put-to-sleep")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_put-to-sleep")]
+                                        fn wit_import(
+                                        _: i64, _: i64, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10));
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn wake_up(entity: EntityId,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = entity;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "wake-up")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_wake-up")]
+                                        fn wit_import(
+                                        _: i64, _: i64, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10));
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
                                   pub fn start_motor(entity: EntityId,velocity: f32,){
                                     
                                     #[allow(unused_imports)]
@@ -13824,6 +14453,75 @@ pub mod ambient {
                                     }
                                   }
                                   #[allow(clippy::all)]
+                                  pub fn create_fixed_joint(actor0: EntityId,transform0: Mat4,actor1: EntityId,transform1: Mat4,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(8))]
+                                      struct RetArea([u8; 160]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let ptr0 = ret_area.as_mut_ptr() as i32;let super::super::super::ambient::bindings::types::EntityId{ id0:id01, id1:id11, } = actor0;
+                                      *((ptr0 + 0) as *mut i64) = wit_bindgen::rt::as_i64(id01);
+                                      *((ptr0 + 8) as *mut i64) = wit_bindgen::rt::as_i64(id11);
+                                      let super::super::super::ambient::bindings::types::Mat4{ x:x2, y:y2, z:z2, w:w2, } = transform0;
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x3, y:y3, z:z3, w:w3, } = x2;
+                                      *((ptr0 + 16) as *mut f32) = wit_bindgen::rt::as_f32(x3);
+                                      *((ptr0 + 20) as *mut f32) = wit_bindgen::rt::as_f32(y3);
+                                      *((ptr0 + 24) as *mut f32) = wit_bindgen::rt::as_f32(z3);
+                                      *((ptr0 + 28) as *mut f32) = wit_bindgen::rt::as_f32(w3);
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x4, y:y4, z:z4, w:w4, } = y2;
+                                      *((ptr0 + 32) as *mut f32) = wit_bindgen::rt::as_f32(x4);
+                                      *((ptr0 + 36) as *mut f32) = wit_bindgen::rt::as_f32(y4);
+                                      *((ptr0 + 40) as *mut f32) = wit_bindgen::rt::as_f32(z4);
+                                      *((ptr0 + 44) as *mut f32) = wit_bindgen::rt::as_f32(w4);
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x5, y:y5, z:z5, w:w5, } = z2;
+                                      *((ptr0 + 48) as *mut f32) = wit_bindgen::rt::as_f32(x5);
+                                      *((ptr0 + 52) as *mut f32) = wit_bindgen::rt::as_f32(y5);
+                                      *((ptr0 + 56) as *mut f32) = wit_bindgen::rt::as_f32(z5);
+                                      *((ptr0 + 60) as *mut f32) = wit_bindgen::rt::as_f32(w5);
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x6, y:y6, z:z6, w:w6, } = w2;
+                                      *((ptr0 + 64) as *mut f32) = wit_bindgen::rt::as_f32(x6);
+                                      *((ptr0 + 68) as *mut f32) = wit_bindgen::rt::as_f32(y6);
+                                      *((ptr0 + 72) as *mut f32) = wit_bindgen::rt::as_f32(z6);
+                                      *((ptr0 + 76) as *mut f32) = wit_bindgen::rt::as_f32(w6);
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id07, id1:id17, } = actor1;
+                                      *((ptr0 + 80) as *mut i64) = wit_bindgen::rt::as_i64(id07);
+                                      *((ptr0 + 88) as *mut i64) = wit_bindgen::rt::as_i64(id17);
+                                      let super::super::super::ambient::bindings::types::Mat4{ x:x8, y:y8, z:z8, w:w8, } = transform1;
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x9, y:y9, z:z9, w:w9, } = x8;
+                                      *((ptr0 + 96) as *mut f32) = wit_bindgen::rt::as_f32(x9);
+                                      *((ptr0 + 100) as *mut f32) = wit_bindgen::rt::as_f32(y9);
+                                      *((ptr0 + 104) as *mut f32) = wit_bindgen::rt::as_f32(z9);
+                                      *((ptr0 + 108) as *mut f32) = wit_bindgen::rt::as_f32(w9);
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x10, y:y10, z:z10, w:w10, } = y8;
+                                      *((ptr0 + 112) as *mut f32) = wit_bindgen::rt::as_f32(x10);
+                                      *((ptr0 + 116) as *mut f32) = wit_bindgen::rt::as_f32(y10);
+                                      *((ptr0 + 120) as *mut f32) = wit_bindgen::rt::as_f32(z10);
+                                      *((ptr0 + 124) as *mut f32) = wit_bindgen::rt::as_f32(w10);
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x11, y:y11, z:z11, w:w11, } = z8;
+                                      *((ptr0 + 128) as *mut f32) = wit_bindgen::rt::as_f32(x11);
+                                      *((ptr0 + 132) as *mut f32) = wit_bindgen::rt::as_f32(y11);
+                                      *((ptr0 + 136) as *mut f32) = wit_bindgen::rt::as_f32(z11);
+                                      *((ptr0 + 140) as *mut f32) = wit_bindgen::rt::as_f32(w11);
+                                      let super::super::super::ambient::bindings::types::Vec4{ x:x12, y:y12, z:z12, w:w12, } = w8;
+                                      *((ptr0 + 144) as *mut f32) = wit_bindgen::rt::as_f32(x12);
+                                      *((ptr0 + 148) as *mut f32) = wit_bindgen::rt::as_f32(y12);
+                                      *((ptr0 + 152) as *mut f32) = wit_bindgen::rt::as_f32(z12);
+                                      *((ptr0 + 156) as *mut f32) = wit_bindgen::rt::as_f32(w12);
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "create-fixed-joint")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_create-fixed-joint")]
+                                        fn wit_import(
+                                        _: i32, );
+                                      }
+                                      wit_import(ptr0);
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
                                   pub fn raycast_first(origin: Vec3,direction: Vec3,) -> Option<(EntityId,f32,)>{
                                     
                                     #[allow(unused_imports)]
@@ -13879,6 +14577,191 @@ pub mod ambient {
                                       Vec::from_raw_parts(*((ptr2 + 0) as *const i32) as *mut _, len3, len3)
                                     }
                                   }
+                                  #[allow(clippy::all)]
+                                  pub fn raycast_first_batch(origins: &[Vec3],directions: &[Vec3],) -> wit_bindgen::rt::vec::Vec::<Option<(EntityId,f32,)>>{
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(4))]
+                                      struct RetArea([u8; 8]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let vec0 = origins;
+                                      let ptr0 = vec0.as_ptr() as i32;
+                                      let len0 = vec0.len() as i32;
+                                      let vec1 = directions;
+                                      let ptr1 = vec1.as_ptr() as i32;
+                                      let len1 = vec1.len() as i32;
+                                      let ptr2 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "raycast-first-batch")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_raycast-first-batch")]
+                                        fn wit_import(
+                                        _: i32, _: i32, _: i32, _: i32, _: i32, );
+                                      }
+                                      wit_import(ptr0, len0, ptr1, len1, ptr2);
+                                      let base3 = *((ptr2 + 0) as *const i32);
+                                      let len3 = *((ptr2 + 4) as *const i32);
+                                      let mut result3 = Vec::with_capacity(len3 as usize);
+                                      for i in 0..len3 {
+                                        let base = base3 + i *32;
+                                        result3.push(match i32::from(*((base + 0) as *const u8)) {
+                                          0 => None,
+                                          1 => Some((super::super::super::ambient::bindings::types::EntityId{id0:*((base + 8) as *const i64) as u64, id1:*((base + 16) as *const i64) as u64, }, *((base + 24) as *const f32))),
+                                          #[cfg(not(debug_assertions))]
+                                          _ => ::core::hint::unreachable_unchecked(),
+                                          #[cfg(debug_assertions)]
+                                          _ => panic!("invalid enum discriminant"),
+                                        });
+                                      }
+                                      wit_bindgen::rt::dealloc(base3, (len3 as usize) * 32, 8);
+                                      
+                                      result3
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn raycast_batch(origins: &[Vec3],directions: &[Vec3],) -> wit_bindgen::rt::vec::Vec::<wit_bindgen::rt::vec::Vec::<(EntityId,f32,)>>{
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(4))]
+                                      struct RetArea([u8; 8]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let vec0 = origins;
+                                      let ptr0 = vec0.as_ptr() as i32;
+                                      let len0 = vec0.len() as i32;
+                                      let vec1 = directions;
+                                      let ptr1 = vec1.as_ptr() as i32;
+                                      let len1 = vec1.len() as i32;
+                                      let ptr2 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "raycast-batch")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_raycast-batch")]
+                                        fn wit_import(
+                                        _: i32, _: i32, _: i32, _: i32, _: i32, );
+                                      }
+                                      wit_import(ptr0, len0, ptr1, len1, ptr2);
+                                      let base4 = *((ptr2 + 0) as *const i32);
+                                      let len4 = *((ptr2 + 4) as *const i32);
+                                      let mut result4 = Vec::with_capacity(len4 as usize);
+                                      for i in 0..len4 {
+                                        let base = base4 + i *8;
+                                        result4.push({
+                                          let len3 = *((base + 4) as *const i32) as usize;
+                                          
+                                          Vec::from_raw_parts(*((base + 0) as *const i32) as *mut _, len3, len3)
+                                        });
+                                      }
+                                      wit_bindgen::rt::dealloc(base4, (len4 as usize) * 8, 4);
+                                      
+                                      result4
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn raycast_first_at(time: Duration,origin: Vec3,direction: Vec3,) -> Option<(EntityId,f32,)>{
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(8))]
+                                      struct RetArea([u8; 32]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let super::super::super::ambient::bindings::types::Duration{ seconds:seconds0, nanoseconds:nanoseconds0, } = time;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x1, y:y1, z:z1, } = origin;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x2, y:y2, z:z2, } = direction;
+                                      let ptr3 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "raycast-first-at")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_raycast-first-at")]
+                                        fn wit_import(
+                                        _: i64, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(seconds0), wit_bindgen::rt::as_i32(nanoseconds0), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(z1), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2), wit_bindgen::rt::as_f32(z2), ptr3);
+                                      match i32::from(*((ptr3 + 0) as *const u8)) {
+                                        0 => None,
+                                        1 => Some((super::super::super::ambient::bindings::types::EntityId{id0:*((ptr3 + 8) as *const i64) as u64, id1:*((ptr3 + 16) as *const i64) as u64, }, *((ptr3 + 24) as *const f32))),
+                                        #[cfg(not(debug_assertions))]
+                                        _ => ::core::hint::unreachable_unchecked(),
+                                        #[cfg(debug_assertions)]
+                                        _ => panic!("invalid enum discriminant"),
+                                      }
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn raycast_at(time: Duration,origin: Vec3,direction: Vec3,) -> wit_bindgen::rt::vec::Vec::<(EntityId,f32,)>{
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(4))]
+                                      struct RetArea([u8; 8]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let super::super::super::ambient::bindings::types::Duration{ seconds:seconds0, nanoseconds:nanoseconds0, } = time;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x1, y:y1, z:z1, } = origin;
+                                      let super::super::super::ambient::bindings::types::Vec3{ x:x2, y:y2, z:z2, } = direction;
+                                      let ptr3 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "raycast-at")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_raycast-at")]
+                                        fn wit_import(
+                                        _: i64, _: i32, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(seconds0), wit_bindgen::rt::as_i32(nanoseconds0), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(z1), wit_bindgen::rt::as_f32(x2), wit_bindgen::rt::as_f32(y2), wit_bindgen::rt::as_f32(z2), ptr3);
+                                      let len4 = *((ptr3 + 4) as *const i32) as usize;
+                                      Vec::from_raw_parts(*((ptr3 + 0) as *const i32) as *mut _, len4, len4)
+                                    }
+                                  }
+                                  #[allow(clippy::all)]
+                                  pub fn overlap_sphere_batch(centers: &[Vec3],radii: &[f32],) -> wit_bindgen::rt::vec::Vec::<wit_bindgen::rt::vec::Vec::<EntityId>>{
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      
+                                      #[repr(align(4))]
+                                      struct RetArea([u8; 8]);
+                                      let mut ret_area = ::core::mem::MaybeUninit::<RetArea>::uninit();
+                                      let vec0 = centers;
+                                      let ptr0 = vec0.as_ptr() as i32;
+                                      let len0 = vec0.len() as i32;
+                                      let vec1 = radii;
+                                      let ptr1 = vec1.as_ptr() as i32;
+                                      let len1 = vec1.len() as i32;
+                                      let ptr2 = ret_area.as_mut_ptr() as i32;
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "overlap-sphere-batch")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_overlap-sphere-batch")]
+                                        fn wit_import(
+                                        _: i32, _: i32, _: i32, _: i32, _: i32, );
+                                      }
+                                      wit_import(ptr0, len0, ptr1, len1, ptr2);
+                                      let base4 = *((ptr2 + 0) as *const i32);
+                                      let len4 = *((ptr2 + 4) as *const i32);
+                                      let mut result4 = Vec::with_capacity(len4 as usize);
+                                      for i in 0..len4 {
+                                        let base = base4 + i *8;
+                                        result4.push({
+                                          let len3 = *((base + 4) as *const i32) as usize;
+                                          
+                                          Vec::from_raw_parts(*((base + 0) as *const i32) as *mut _, len3, len3)
+                                        });
+                                      }
+                                      wit_bindgen::rt::dealloc(base4, (len4 as usize) * 8, 4);
+                                      
+                                      result4
+                                    }
+                                  }
+
                                   #[allow(clippy::all)]
                                   pub fn move_character(entity: EntityId,displacement: Vec3,min_dist: f32,elapsed_time: f32,) -> CharacterCollision{
                                     
@@ -13974,6 +14857,25 @@ pub mod ambient {
                                       wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10), wit_bindgen::rt::as_f32(x1), wit_bindgen::rt::as_f32(y1), wit_bindgen::rt::as_f32(z1));
                                     }
                                   }
+                                  #[allow(clippy::all)]
+                                  pub fn set_contact_filter(first: EntityId,second: EntityId,collide: bool,){
+                                    
+                                    #[allow(unused_imports)]
+                                    use wit_bindgen::rt::{alloc, vec::Vec, string::String};
+                                    unsafe {
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id00, id1:id10, } = first;
+                                      let super::super::super::ambient::bindings::types::EntityId{ id0:id01, id1:id11, } = second;
+                                      
+                                      #[link(wasm_import_module = "ambient:bindings/server-physics")]
+                                      extern "C" {
+                                        #[cfg_attr(target_arch = "wasm32", link_name = "set-contact-filter")]
+                                        #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-physics_set-contact-filter")]
+                                        fn wit_import(
+                                        _: i64, _: i64, _: i64, _: i64, _: i32, );
+                                      }
+                                      wit_import(wit_bindgen::rt::as_i64(id00), wit_bindgen::rt::as_i64(id10), wit_bindgen::rt::as_i64(id01), wit_bindgen::rt::as_i64(id11), match collide { true => 1, false => 0 });
+                                    }
+                                  }
                                   
                                 }
                                 
@@ -13986,6 +14888,7 @@ pub mod ambient {
                                   static __FORCE_SECTION_REF: fn() = super::super::super::__link_section;
                                   
                                   pub type EntityId = super::super::super::ambient::bindings::types::EntityId;
+                                  pub type Reliability = super::super::super::ambient::bindings::message::Reliability;
                                   #[derive(Clone)]
                                   pub enum Target{
                                     ClientBroadcastUnreliable,
@@ -13994,6 +14897,8 @@ pub mod ambient {
                                     ClientTargetedReliable(wit_bindgen::rt::string::String),
                                     LocalBroadcast(bool),
                                     Local(EntityId),
+                                    ClientBroadcast(Reliability),
+                                    ClientTargeted((wit_bindgen::rt::string::String,Reliability,)),
                                   }
                                   impl ::core::fmt::Debug for Target {
                                     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
@@ -14016,6 +14921,12 @@ pub mod ambient {
                                         Target::Local(e) => {
                                           f.debug_tuple("Target::Local").field(e).finish()
                                         }
+                                        Target::ClientBroadcast(e) => {
+                                          f.debug_tuple("Target::ClientBroadcast").field(e).finish()
+                                        }
+                                        Target::ClientTargeted(e) => {
+                                          f.debug_tuple("Target::ClientTargeted").field(e).finish()
+                                        }
                                       }
                                     }
                                   }
@@ -14025,49 +14936,58 @@ pub mod ambient {
                                     #[allow(unused_imports)]
                                     use wit_bindgen::rt::{alloc, vec::Vec, string::String};
                                     unsafe {
-                                      let (result3_0,result3_1,result3_2,) = match target_id {
+                                      let (result4_0,result4_1,result4_2,result4_3,) = match target_id {
                                         Target::ClientBroadcastUnreliable=> {
-                                          (0i32, 0i64, 0i64)
+                                          (0i32, 0i64, 0i64, 0i32)
                                         }
                                         Target::ClientBroadcastReliable=> {
-                                          (1i32, 0i64, 0i64)
+                                          (1i32, 0i64, 0i64, 0i32)
                                         }
                                         Target::ClientTargetedUnreliable(e) => {
                                           let vec0 = e;
                                           let ptr0 = vec0.as_ptr() as i32;
                                           let len0 = vec0.len() as i32;
                                           
-                                          (2i32, i64::from(ptr0), i64::from(len0))
+                                          (2i32, i64::from(ptr0), i64::from(len0), 0i32)
                                         },
                                         Target::ClientTargetedReliable(e) => {
                                           let vec1 = e;
                                           let ptr1 = vec1.as_ptr() as i32;
                                           let len1 = vec1.len() as i32;
                                           
-                                          (3i32, i64::from(ptr1), i64::from(len1))
+                                          (3i32, i64::from(ptr1), i64::from(len1), 0i32)
                                         },
-                                        Target::LocalBroadcast(e) => (4i32, i64::from(match e { true => 1, false => 0 }), 0i64),
+                                        Target::LocalBroadcast(e) => (4i32, i64::from(match e { true => 1, false => 0 }), 0i64, 0i32),
                                         Target::Local(e) => {
                                           let super::super::super::ambient::bindings::types::EntityId{ id0:id02, id1:id12, } = e;
                                           
-                                          (5i32, wit_bindgen::rt::as_i64(id02), wit_bindgen::rt::as_i64(id12))
+                                          (5i32, wit_bindgen::rt::as_i64(id02), wit_bindgen::rt::as_i64(id12), 0i32)
+                                        },
+                                        Target::ClientBroadcast(e) => (6i32, i64::from(e.clone() as i32), 0i64, 0i32),
+                                        Target::ClientTargeted(e) => {
+                                          let (t3_0, t3_1, ) = e;
+                                          let vec3 = t3_0;
+                                          let ptr3 = vec3.as_ptr() as i32;
+                                          let len3 = vec3.len() as i32;
+                                          
+                                          (7i32, i64::from(ptr3), i64::from(len3), t3_1.clone() as i32)
                                         },
                                       };
-                                      let vec4 = name;
-                                      let ptr4 = vec4.as_ptr() as i32;
-                                      let len4 = vec4.len() as i32;
-                                      let vec5 = data;
+                                      let vec5 = name;
                                       let ptr5 = vec5.as_ptr() as i32;
                                       let len5 = vec5.len() as i32;
+                                      let vec6 = data;
+                                      let ptr6 = vec6.as_ptr() as i32;
+                                      let len6 = vec6.len() as i32;
                                       
                                       #[link(wasm_import_module = "ambient:bindings/server-message")]
                                       extern "C" {
                                         #[cfg_attr(target_arch = "wasm32", link_name = "send")]
                                         #[cfg_attr(not(target_arch = "wasm32"), link_name = "ambient:bindings/server-message_send")]
                                         fn wit_import(
-                                        _: i32, _: i64, _: i64, _: i32, _: i32, _: i32, _: i32, );
+                                        _: i32, _: i64, _: i64, _: i32, _: i32, _: i32, _: i32, _: i32, );
                                       }
-                                      wit_import(result3_0, result3_1, result3_2, ptr4, len4, ptr5, len5);
+                                      wit_import(result4_0, result4_1, result4_2, result4_3, ptr5, len5, ptr6, len6);
                                     }
                                   }
                                   
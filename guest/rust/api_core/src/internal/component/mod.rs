@@ -18,6 +18,11 @@ pub(crate) use ambient_shared_types::ComponentIndex;
 pub trait UntypedComponent {
     #[doc(hidden)]
     fn index(&self) -> ComponentIndex;
+    /// The fully qualified path of this component, like `ambient_core::transform::translation`.
+    fn path(&self) -> String {
+        let index = self.index();
+        wit::component::get_id(index).unwrap_or_else(|| format!("unknown component {index}"))
+    }
 }
 
 /// A component (piece of entity data). See [entity::get_component](crate::entity::get_component) and [entity::set_component](crate::entity::set_component).
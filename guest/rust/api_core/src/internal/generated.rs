@@ -151,6 +151,103 @@ mod raw {
                 pub fn bind_ids() -> Component<Vec<String>> {
                     *BIND_IDS
                 }
+                static ANIMATION_GRAPH_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::animation::animation_graph_from_url")
+                });
+                #[doc = "**Animation graph from URL**: Make this entity an 'animation graph' node. The value is the URL to the graph we'd like to play: a JSON file of layered state machines, whose states play clips and whose transitions blend between them.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn animation_graph_from_url() -> Component<String> {
+                    *ANIMATION_GRAPH_FROM_URL
+                }
+                static ANIMATION_GRAPH_PARAMETERS: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::animation::animation_graph_parameters")
+                });
+                #[doc = "**Animation graph parameters**: The names of the parameters set on this animation graph node, which drive the transitions of its state machines.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn animation_graph_parameters() -> Component<Vec<String>> {
+                    *ANIMATION_GRAPH_PARAMETERS
+                }
+                static ANIMATION_GRAPH_PARAMETER_VALUES: Lazy<Component<Vec<f32>>> =
+                    Lazy::new(|| {
+                        __internal_get_component(
+                            "ambient_core::animation::animation_graph_parameter_values",
+                        )
+                    });
+                #[doc = "**Animation graph parameter values**: Values for each parameter in `animation_graph_parameters`. Booleans are 0 or 1.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn animation_graph_parameter_values() -> Component<Vec<f32>> {
+                    *ANIMATION_GRAPH_PARAMETER_VALUES
+                }
+                static ANIMATION_GRAPH_STATES: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::animation::animation_graph_states")
+                });
+                #[doc = "**Animation graph states**: The current state of each layer of this animation graph node.\n\n*Attributes*: Debuggable"]
+                pub fn animation_graph_states() -> Component<Vec<String>> {
+                    *ANIMATION_GRAPH_STATES
+                }
+                static ATTACH_TO_MODEL: Lazy<Component<EntityId>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::animation::attach_to_model")
+                });
+                #[doc = "**Attach to model**: Attaches this entity to a bone of the model of the given entity, such as a weapon to a hand or a hat to a head. The bone is picked with `attach_to_bone`.\n\nOnce the model is loaded, this entity follows the bone every frame, offset by its `local_to_parent`; add `reset_scale` to ignore the scale of the bone.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn attach_to_model() -> Component<EntityId> {
+                    *ATTACH_TO_MODEL
+                }
+                static ATTACH_TO_BONE: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::animation::attach_to_bone")
+                });
+                #[doc = "**Attach to bone**: The bone or socket of the model in `attach_to_model` to attach this entity to: either the bind id of a bone (for example `RightHand`), or the name of a node of the model.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn attach_to_bone() -> Component<String> {
+                    *ATTACH_TO_BONE
+                }
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{
+                    message::{
+                        Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                        RuntimeMessage,
+                    },
+                    prelude::*,
+                };
+                #[derive(Clone, Debug)]
+                #[doc = "**AnimationEvent**: An event of a state of an animation graph was reached: `name` is the event, `state` the state playing it, and `node` the animation graph node."]
+                pub struct AnimationEvent {
+                    pub node: EntityId,
+                    pub state: String,
+                    pub name: String,
+                }
+                impl AnimationEvent {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        node: impl Into<EntityId>,
+                        state: impl Into<String>,
+                        name: impl Into<String>,
+                    ) -> Self {
+                        Self {
+                            node: node.into(),
+                            state: state.into(),
+                            name: name.into(),
+                        }
+                    }
+                }
+                impl Message for AnimationEvent {
+                    fn id() -> &'static str {
+                        "ambient_core::animation::AnimationEvent"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.node.serialize_message_part(&mut output)?;
+                        self.state.serialize_message_part(&mut output)?;
+                        self.name.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            node: EntityId::deserialize_message_part(&mut input)?,
+                            state: String::deserialize_message_part(&mut input)?,
+                            name: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for AnimationEvent {}
             }
         }
         pub mod app {
@@ -316,12 +413,55 @@ mod raw {
                 pub fn spatial_audio_listener() -> Component<EntityId> {
                     *SPATIAL_AUDIO_LISTENER
                 }
+                static SPATIAL_AUDIO_ATTENUATION: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::spatial_audio_attenuation")
+                });
+                #[doc = "**Spatial audio attenuation**: How the volume of a spatial sound falls off with the distance `d` to the listener, as the `(constant, linear, quadratic)` coefficients of `1 / (constant + linear * d + quadratic * d²)`.\n\nCan be attached to a spatial audio player, or to one of its playing sounds to change it while it plays.\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn spatial_audio_attenuation() -> Component<Vec3> {
+                    *SPATIAL_AUDIO_ATTENUATION
+                }
+                static AUDIO_OCCLUSION: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_occlusion"));
+                #[doc = "**Audio occlusion**: How much the path from the listener to a playing spatial sound is blocked by colliders, from 0.0 (clear) to 1.0 (fully blocked).\n\nOccluded sounds are muffled and quieter. Updated a few times per second, in worlds with physics.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_occlusion() -> Component<f32> {
+                    *AUDIO_OCCLUSION
+                }
+                static DOPPLER_FACTOR: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::doppler_factor"));
+                #[doc = "**Doppler factor**: How strongly the pitch of a spatial sound shifts as its emitter and listener move towards or away from each other. 1.0 is physically accurate, and 0.0 disables the doppler effect. Defaults to 1.0.\n\nCan be attached to a spatial audio player, or to one of its playing sounds to change it while it plays.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn doppler_factor() -> Component<f32> {
+                    *DOPPLER_FACTOR
+                }
+                static DOPPLER_SHIFT: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::doppler_shift"));
+                #[doc = "**Doppler shift**: The pitch multiplier applied to a playing spatial sound by the doppler effect. Updated every frame.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn doppler_shift() -> Component<f32> {
+                    *DOPPLER_SHIFT
+                }
                 static LOOPING: Lazy<Component<bool>> =
                     Lazy::new(|| __internal_get_component("ambient_core::audio::looping"));
                 #[doc = "**Looping**: Whether or not the audio should loop.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
                 pub fn looping() -> Component<bool> {
                     *LOOPING
                 }
+                static LOOP_START: Lazy<Component<u64>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::loop_start"));
+                #[doc = "**Loop start**: The frame a looping sound jumps back to when it reaches `loop_end` or the end of the audio. Defaults to 0, or to the loop start in the metadata of the audio if the player has neither `loop_start` nor `loop_end`.\n\nCan be attached to an audio player, or to one of its playing sounds to change it while it plays.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn loop_start() -> Component<u64> {
+                    *LOOP_START
+                }
+                static LOOP_END: Lazy<Component<u64>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::loop_end"));
+                #[doc = "**Loop end**: The frame at which a looping sound jumps back to `loop_start`. If not attached, defaults to the loop end in the metadata of the audio if the player has neither `loop_start` nor `loop_end`, or else loops at the end of the audio.\n\nCan be attached to an audio player, or to one of its playing sounds to change it while it plays.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn loop_end() -> Component<u64> {
+                    *LOOP_END
+                }
+                static STREAMING: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::streaming"));
+                #[doc = "**Streaming**: If attached to an audio player, its sounds are decoded from disk as they play instead of being loaded into memory first.\n\nUse this for long audio like music. Streaming is not supported on the web, where this has no effect.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn streaming() -> Component<()> {
+                    *STREAMING
+                }
                 static ONEPOLE_LPF: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::audio::onepole_lpf"));
                 #[doc = "**One pole low pass filter**: With this component, the audio will be filtered with a one pole low pass filter.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
@@ -334,6 +474,237 @@ mod raw {
                 pub fn playing_sound() -> Component<()> {
                     *PLAYING_SOUND
                 }
+                static PAUSED: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::paused"));
+                #[doc = "**Paused**: While attached to a playing sound, the sound is paused.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn paused() -> Component<()> {
+                    *PAUSED
+                }
+                static PITCH: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::pitch"));
+                #[doc = "**Pitch**: The playback speed of a playing sound, which also shifts its pitch. 1.0 is the original speed, 2.0 is twice as fast and an octave higher.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn pitch() -> Component<f32> {
+                    *PITCH
+                }
+                static SEEK_TO: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::seek_to"));
+                #[doc = "**Seek to**: Add this to a playing sound to jump to this position, in seconds.\n\nIt is removed once the sound has jumped.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn seek_to() -> Component<f32> {
+                    *SEEK_TO
+                }
+                static PLAYBACK_POSITION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::playback_position")
+                });
+                #[doc = "**Playback position**: The position of a playing sound, in seconds. Updated every frame.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn playback_position() -> Component<f32> {
+                    *PLAYBACK_POSITION
+                }
+                static PLAYBACK_DURATION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::playback_duration")
+                });
+                #[doc = "**Playback duration**: The duration of a playing sound, in seconds, once it has loaded.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn playback_duration() -> Component<f32> {
+                    *PLAYBACK_DURATION
+                }
+                static AUDIO_BUS: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_bus"));
+                #[doc = "**Audio bus**: The name of the mixer bus an audio player plays its sounds on, like `music`, `sfx` or `voice`.\n\nDefaults to `sfx`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_bus() -> Component<String> {
+                    *AUDIO_BUS
+                }
+                static BUS_NAME: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::bus_name"));
+                #[doc = "**Bus name**: The entity configures the mixer bus with this name, creating it if it does not exist.\n\nThe built-in buses are `master`, `music`, `sfx` and `voice`; all other buses are children of `master` unless they have a `bus_parent`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn bus_name() -> Component<String> {
+                    *BUS_NAME
+                }
+                static BUS_PARENT: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::bus_parent"));
+                #[doc = "**Bus parent**: The name of the bus that the bus configured by this entity is mixed into. Defaults to `master`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn bus_parent() -> Component<String> {
+                    *BUS_PARENT
+                }
+                static BUS_GAIN: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::bus_gain"));
+                #[doc = "**Bus gain**: The gain of the bus configured by this entity. Defaults to 1.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn bus_gain() -> Component<f32> {
+                    *BUS_GAIN
+                }
+                static BUS_MUTED: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::bus_muted"));
+                #[doc = "**Bus muted**: While attached, the bus configured by this entity is muted. Its sounds keep playing silently.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn bus_muted() -> Component<()> {
+                    *BUS_MUTED
+                }
+                static BUS_LOW_PASS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::bus_low_pass"));
+                #[doc = "**Bus low-pass filter**: The cutoff frequency of a one pole low-pass filter applied to the bus configured by this entity.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn bus_low_pass() -> Component<f32> {
+                    *BUS_LOW_PASS
+                }
+                static BUS_HIGH_PASS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::bus_high_pass"));
+                #[doc = "**Bus high-pass filter**: The cutoff frequency of a one pole high-pass filter applied to the bus configured by this entity.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn bus_high_pass() -> Component<f32> {
+                    *BUS_HIGH_PASS
+                }
+                static REVERB_ZONE: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::reverb_zone"));
+                #[doc = "**Reverb zone**: If attached, this entity is a reverb zone. Spatial sounds whose emitter is inside it send some of their sound to its reverb.\n\nIts shape is set with `reverb_zone_radius` or `reverb_zone_extents`, around its `local_to_world`. The reverb plays on the bus in `audio_bus`, which defaults to `sfx`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_zone() -> Component<()> {
+                    *REVERB_ZONE
+                }
+                static REVERB_ZONE_RADIUS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::reverb_zone_radius")
+                });
+                #[doc = "**Reverb zone radius**: The radius of a spherical reverb zone.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_zone_radius() -> Component<f32> {
+                    *REVERB_ZONE_RADIUS
+                }
+                static REVERB_ZONE_EXTENTS: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::reverb_zone_extents")
+                });
+                #[doc = "**Reverb zone extents**: The half extents of a box-shaped reverb zone, in its local space.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_zone_extents() -> Component<Vec3> {
+                    *REVERB_ZONE_EXTENTS
+                }
+                static REVERB_ZONE_FADE: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::reverb_zone_fade"));
+                #[doc = "**Reverb zone fade**: The distance outside of a reverb zone over which its reverb fades out, so that moving in and out of it is smooth. Defaults to 1.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_zone_fade() -> Component<f32> {
+                    *REVERB_ZONE_FADE
+                }
+                static REVERB_ROOM_SIZE: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::reverb_room_size"));
+                #[doc = "**Reverb room size**: How long the reverb of a reverb zone or bus rings, from 0 to 1. Defaults to 0.5.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_room_size() -> Component<f32> {
+                    *REVERB_ROOM_SIZE
+                }
+                static REVERB_DAMPING: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::reverb_damping"));
+                #[doc = "**Reverb damping**: How quickly the high frequencies of the reverb of a reverb zone or bus die out, from 0 to 1. Defaults to 0.5.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_damping() -> Component<f32> {
+                    *REVERB_DAMPING
+                }
+                static REVERB_WET: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::reverb_wet"));
+                #[doc = "**Reverb wet**: On a reverb zone, how much of the sound of the emitters inside it is sent to its reverb. Defaults to 0.5.\n\nOn a bus entity, adds a reverb to the bus which mixes in this much of the reverberated sound.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_wet() -> Component<f32> {
+                    *REVERB_WET
+                }
+                static REVERB_DRY: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::reverb_dry"));
+                #[doc = "**Reverb dry**: The gain of the direct sound of spatial sounds whose emitter is inside this reverb zone. Defaults to 1.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn reverb_dry() -> Component<f32> {
+                    *REVERB_DRY
+                }
+                static VOICE_CAPTURE: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::voice_capture"));
+                #[doc = "**Voice capture**: While this resource is attached on a client, the microphone is captured and sent to the other players as voice chat.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn voice_capture() -> Component<()> {
+                    *VOICE_CAPTURE
+                }
+                static VOICE_MUTED: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::voice_muted"));
+                #[doc = "**Voice muted**: If attached to a player entity on the server, the voice of the player is not sent to anyone.\n\nIf attached to a player entity on a client, the voice of the player is not played for that client.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn voice_muted() -> Component<()> {
+                    *VOICE_MUTED
+                }
+                static AUDIO_PRIORITY: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_priority"));
+                #[doc = "**Audio priority**: The priority of a spatial audio player, or of one of its playing sounds. When more spatial sounds play than `max_audible_sounds`, those with the lowest priority are virtualized first, and the quietest among sounds of equal priority. Defaults to 0.0.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_priority() -> Component<f32> {
+                    *AUDIO_PRIORITY
+                }
+                static MAX_AUDIBLE_SOUNDS: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::max_audible_sounds")
+                });
+                #[doc = "**Max audible sounds**: The maximum number of spatial sounds that are played at once on this client. The others are virtualized: they keep their position, but aren't processed until they are among the loudest again. Defaults to 32.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn max_audible_sounds() -> Component<u32> {
+                    *MAX_AUDIBLE_SOUNDS
+                }
+                static VIRTUALIZED: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::virtualized"));
+                #[doc = "**Virtualized**: Attached by the engine to spatial playing sounds which are currently virtualized, as they are too quiet or too many sounds are playing.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn virtualized() -> Component<()> {
+                    *VIRTUALIZED
+                }
+                static AMPLITUDE_FADE: Lazy<Component<Vec2>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::amplitude_fade"));
+                #[doc = "**Amplitude fade**: Attach to a playing sound to ramp its `amplitude` to `x` over `y` seconds. The ramp happens on the audio thread, and `amplitude` is set to `x` once it has started. Removed when the ramp starts.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn amplitude_fade() -> Component<Vec2> {
+                    *AMPLITUDE_FADE
+                }
+                static PITCH_FADE: Lazy<Component<Vec2>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::pitch_fade"));
+                #[doc = "**Pitch fade**: Attach to a playing sound to ramp its `pitch` to `x` over `y` seconds, see `amplitude_fade`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn pitch_fade() -> Component<Vec2> {
+                    *PITCH_FADE
+                }
+                static ONEPOLE_LPF_FADE: Lazy<Component<Vec2>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::onepole_lpf_fade"));
+                #[doc = "**One-pole low-pass filter fade**: Attach to a playing sound of an audio player to ramp the cutoff of its `onepole_lpf` to `x` Hz over `y` seconds, see `amplitude_fade`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn onepole_lpf_fade() -> Component<Vec2> {
+                    *ONEPOLE_LPF_FADE
+                }
+                static ACTIVE_AUDIO_LISTENER: Lazy<Component<EntityId>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::active_audio_listener")
+                });
+                #[doc = "**Active audio listener**: If attached as a resource, every spatial sound is heard from this entity instead of its `spatial_audio_listener`. Switch it at runtime to change the camera sounds are heard from, like in spectator modes.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn active_audio_listener() -> Component<EntityId> {
+                    *ACTIVE_AUDIO_LISTENER
+                }
+                static AUDIO_METERING: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_metering"));
+                #[doc = "**Audio metering**: If attached as a resource, the loudness of the master bus is measured, and updated every 100 ms in `audio_rms`, `audio_peak`, `audio_momentary_loudness` and `audio_integrated_loudness`.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_metering() -> Component<()> {
+                    *AUDIO_METERING
+                }
+                static AUDIO_RMS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_rms"));
+                #[doc = "**Audio RMS**: The RMS level of the last 400 ms of the master bus, in dBFS. Negative infinity for silence. Measured while `audio_metering` is attached.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_rms() -> Component<f32> {
+                    *AUDIO_RMS
+                }
+                static AUDIO_PEAK: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_peak"));
+                #[doc = "**Audio peak**: The highest sample of the last 400 ms of the master bus, in dBFS. Negative infinity for silence. Measured while `audio_metering` is attached.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_peak() -> Component<f32> {
+                    *AUDIO_PEAK
+                }
+                static AUDIO_MOMENTARY_LOUDNESS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::audio_momentary_loudness")
+                });
+                #[doc = "**Audio momentary loudness**: The loudness of the last 400 ms of the master bus, in LUFS. Measured while `audio_metering` is attached.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_momentary_loudness() -> Component<f32> {
+                    *AUDIO_MOMENTARY_LOUDNESS
+                }
+                static AUDIO_INTEGRATED_LOUDNESS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::audio_integrated_loudness")
+                });
+                #[doc = "**Audio integrated loudness**: The loudness of the master bus since `audio_metering` was attached, in LUFS. Silent parts are left out.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_integrated_loudness() -> Component<f32> {
+                    *AUDIO_INTEGRATED_LOUDNESS
+                }
+                static AUDIO_RECORDING: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_recording"));
+                #[doc = "**Audio recording**: If attached as a resource, the master bus is recorded to `recordings/<name>.wav` in the working directory until it is removed. Only the file name of the value is used. Not supported on the web.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_recording() -> Component<String> {
+                    *AUDIO_RECORDING
+                }
+                static AUDIO_TIME: Lazy<Component<f64>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::audio::audio_time"));
+                #[doc = "**Audio time**: The time of the audio clock of this client, in seconds of audio output since it started. Updated every frame; sounds scheduled with `start_at_audio_time` should be a little ahead of it.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
+                pub fn audio_time() -> Component<f64> {
+                    *AUDIO_TIME
+                }
+                static START_AT_AUDIO_TIME: Lazy<Component<f64>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::audio::start_at_audio_time")
+                });
+                #[doc = "**Start at audio time**: Starts this playing sound on the exact sample at this `audio_time`, rather than as soon as it has loaded. Sounds scheduled in the past start right away. Must be attached when the sound is played.\n\n\n\n*Attributes*: Debuggable"]
+                pub fn start_at_audio_time() -> Component<f64> {
+                    *START_AT_AUDIO_TIME
+                }
                 static AMPLITUDE: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::audio::amplitude"));
                 #[doc = "**Amplitude**: The amplitude of the audio.\n\n\n\n*Attributes*: MaybeResource, Debuggable"]
@@ -501,6 +872,82 @@ mod raw {
                 pub fn shadows_far() -> Component<f32> {
                     *SHADOWS_FAR
                 }
+                static CAMERA_FOLLOW: Lazy<Component<EntityId>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::camera::camera_follow"));
+                #[doc = "**Camera follow**: If attached, this camera follows the given entity on the client: it stays at `camera_follow_offset` from it, and looks at it if the camera has a `lookat_target`. It catches up with the entity smoothly, see `camera_follow_damping` and `camera_follow_look_ahead`. This replaces the `translation` and `lookat_target` of the camera every frame.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn camera_follow() -> Component<EntityId> {
+                    *CAMERA_FOLLOW
+                }
+                static CAMERA_FOLLOW_OFFSET: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_follow_offset")
+                });
+                #[doc = "**Camera follow offset**: The position of a camera with `camera_follow` relative to the entity it follows, in world space. Defaults to zero if not attached.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn camera_follow_offset() -> Component<Vec3> {
+                    *CAMERA_FOLLOW_OFFSET
+                }
+                static CAMERA_FOLLOW_DAMPING: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_follow_damping")
+                });
+                #[doc = "**Camera follow damping**: Roughly how long, in seconds, a camera with `camera_follow` takes to catch up with the entity it follows. Zero keeps it exactly in place. Defaults to 0.2 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.2"]
+                pub fn camera_follow_damping() -> Component<f32> {
+                    *CAMERA_FOLLOW_DAMPING
+                }
+                static CAMERA_FOLLOW_LOOK_AHEAD: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_follow_look_ahead")
+                });
+                #[doc = "**Camera follow look-ahead**: How far ahead of the entity it follows a camera with `camera_follow` aims, in seconds of the entity's velocity, so that players see where they are going. Defaults to 0 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn camera_follow_look_ahead() -> Component<f32> {
+                    *CAMERA_FOLLOW_LOOK_AHEAD
+                }
+                static CAMERA_FOVY_TARGET: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_fovy_target")
+                });
+                #[doc = "**Camera field of view Y target**: If attached, the `fovy` of this camera moves smoothly towards this value, in radians, over `camera_fovy_transition_time`. For a FOV kick, set `fovy` directly and let it ease back to the target.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn camera_fovy_target() -> Component<f32> {
+                    *CAMERA_FOVY_TARGET
+                }
+                static CAMERA_FOVY_TRANSITION_TIME: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_fovy_transition_time")
+                });
+                #[doc = "**Camera field of view Y transition time**: Roughly how long, in seconds, the `fovy` of a camera takes to reach its `camera_fovy_target`. Defaults to 0.25 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.25"]
+                pub fn camera_fovy_transition_time() -> Component<f32> {
+                    *CAMERA_FOVY_TRANSITION_TIME
+                }
+                static CAMERA_SHAKE_TRAUMA: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_shake_trauma")
+                });
+                #[doc = "**Camera shake trauma**: If attached, this camera shakes on the client. The trauma goes from 0 to 1, and the shake grows with its square; add to it on hits and explosions. It decays by `camera_shake_decay` every second.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn camera_shake_trauma() -> Component<f32> {
+                    *CAMERA_SHAKE_TRAUMA
+                }
+                static CAMERA_SHAKE_DECAY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_shake_decay")
+                });
+                #[doc = "**Camera shake decay**: How much `camera_shake_trauma` the camera loses every second. Defaults to 1 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn camera_shake_decay() -> Component<f32> {
+                    *CAMERA_SHAKE_DECAY
+                }
+                static CAMERA_SHAKE_MAX_ANGLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_shake_max_angle")
+                });
+                #[doc = "**Camera shake max angle**: How far a camera with a `camera_shake_trauma` of 1 turns around each of its axes, in radians. Defaults to 0.1 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.1"]
+                pub fn camera_shake_max_angle() -> Component<f32> {
+                    *CAMERA_SHAKE_MAX_ANGLE
+                }
+                static CAMERA_SHAKE_MAX_OFFSET: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_shake_max_offset")
+                });
+                #[doc = "**Camera shake max offset**: How far a camera with a `camera_shake_trauma` of 1 moves along each of its axes, in meters. Defaults to 0 if not attached, as turning the camera is usually enough.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn camera_shake_max_offset() -> Component<f32> {
+                    *CAMERA_SHAKE_MAX_OFFSET
+                }
+                static CAMERA_SHAKE_FREQUENCY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::camera::camera_shake_frequency")
+                });
+                #[doc = "**Camera shake frequency**: How fast a camera with a `camera_shake_trauma` shakes, in changes of direction per second. Defaults to 15 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 15.0"]
+                pub fn camera_shake_frequency() -> Component<f32> {
+                    *CAMERA_SHAKE_FREQUENCY
+                }
             }
             #[doc = r" Auto-generated concept definitions. Concepts are collections of components that describe some form of gameplay concept."]
             #[doc = r""]
@@ -1957,7 +2404,8 @@ mod raw {
             pub mod messages {
                 use crate::{
                     message::{
-                        Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                        Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                        RuntimeMessage,
                     },
                     prelude::*,
                 };
@@ -2167,6 +2615,86 @@ mod raw {
                 pub fn gpu_ui_size() -> Component<Vec4> {
                     *GPU_UI_SIZE
                 }
+                static FLEX_WRAP: Lazy<Component<bool>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_wrap"));
+                #[doc = "**Flex wrap**: Whether the children of a `Flex` layout wrap onto new lines when they don't fit along its `orientation`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: false"]
+                pub fn flex_wrap() -> Component<bool> {
+                    *FLEX_WRAP
+                }
+                static JUSTIFY_CONTENT: Lazy<
+                    Component<crate::ambient_core::layout::types::Justify>,
+                > = Lazy::new(|| __internal_get_component("ambient_core::layout::justify_content"));
+                #[doc = "**Justify content**: How a `Flex` layout distributes the space left along its `orientation` between its children.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn justify_content() -> Component<crate::ambient_core::layout::types::Justify> {
+                    *JUSTIFY_CONTENT
+                }
+                static ALIGN_ITEMS: Lazy<Component<crate::ambient_core::layout::types::FlexAlign>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::align_items"));
+                #[doc = "**Align items**: How a `Flex` layout aligns its children across its `orientation`, within their line.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn align_items() -> Component<crate::ambient_core::layout::types::FlexAlign> {
+                    *ALIGN_ITEMS
+                }
+                static ALIGN_SELF: Lazy<Component<crate::ambient_core::layout::types::FlexAlign>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::align_self"));
+                #[doc = "**Align self**: How this child of a `Flex` layout is aligned across its `orientation`, overriding the layout's `align_items`.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn align_self() -> Component<crate::ambient_core::layout::types::FlexAlign> {
+                    *ALIGN_SELF
+                }
+                static FLEX_GROW: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_grow"));
+                #[doc = "**Flex grow**: How much of the space left along a `Flex` layout's `orientation` this child takes, relative to the other children.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn flex_grow() -> Component<f32> {
+                    *FLEX_GROW
+                }
+                static FLEX_SHRINK: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_shrink"));
+                #[doc = "**Flex shrink**: How much this child of a `Flex` layout shrinks, relative to the other children, when they don't fit along its `orientation`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn flex_shrink() -> Component<f32> {
+                    *FLEX_SHRINK
+                }
+                static FLEX_BASIS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::flex_basis"));
+                #[doc = "**Flex basis**: The size of this child of a `Flex` layout along its `orientation`, before growing or shrinking.\n\nDefaults to the child's own size, like `auto` in CSS. The layout keeps the size a growing or shrinking child had before it was resized as its basis, until the child is resized by something else.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn flex_basis() -> Component<f32> {
+                    *FLEX_BASIS
+                }
+                static GRID_COLUMNS: Lazy<Component<Vec<f32>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_columns"));
+                #[doc = "**Grid columns**: The sizes of the columns of a `Grid` layout.\n\nPositive sizes are in pixels, 0 fits the column to its children, and negative sizes are shares of the space left: `[200., -1., -2.]` is a 200 pixel column followed by two columns sharing the rest 1:2.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_columns() -> Component<Vec<f32>> {
+                    *GRID_COLUMNS
+                }
+                static GRID_ROWS: Lazy<Component<Vec<f32>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_rows"));
+                #[doc = "**Grid rows**: The sizes of the rows of a `Grid` layout, like `grid_columns`.\n\nRows past these are fitted to their children.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_rows() -> Component<Vec<f32>> {
+                    *GRID_ROWS
+                }
+                static GRID_COLUMN: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_column"));
+                #[doc = "**Grid column**: The column of a `Grid` layout this child is placed in, from 0. Children without a `grid_column` and `grid_row` fill the next free cells, row by row.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_column() -> Component<u32> {
+                    *GRID_COLUMN
+                }
+                static GRID_ROW: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_row"));
+                #[doc = "**Grid row**: The row of a `Grid` layout this child is placed in, from 0.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn grid_row() -> Component<u32> {
+                    *GRID_ROW
+                }
+                static GRID_COLUMN_SPAN: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::layout::grid_column_span")
+                });
+                #[doc = "**Grid column span**: The number of columns of a `Grid` layout this child covers.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1"]
+                pub fn grid_column_span() -> Component<u32> {
+                    *GRID_COLUMN_SPAN
+                }
+                static GRID_ROW_SPAN: Lazy<Component<u32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::layout::grid_row_span"));
+                #[doc = "**Grid row span**: The number of rows of a `Grid` layout this child covers.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1"]
+                pub fn grid_row_span() -> Component<u32> {
+                    *GRID_ROW_SPAN
+                }
             }
             #[doc = r" Auto-generated type definitions."]
             pub mod types {
@@ -2495,6 +3023,10 @@ mod raw {
                     Bookcase,
                     #[doc = "Width to children."]
                     WidthToChildren,
+                    #[doc = "Flexbox layout: children grow, shrink, wrap and are aligned along its orientation."]
+                    Flex,
+                    #[doc = "Grid layout: children are placed in columns and rows."]
+                    Grid,
                 }
                 impl crate::ecs::EnumComponent for Layout {
                     fn to_u32(&self) -> u32 {
@@ -2503,6 +3035,8 @@ mod raw {
                             Self::Dock => Layout::Dock as u32,
                             Self::Bookcase => Layout::Bookcase as u32,
                             Self::WidthToChildren => Layout::WidthToChildren as u32,
+                            Self::Flex => Layout::Flex as u32,
+                            Self::Grid => Layout::Grid as u32,
                         }
                     }
                     fn from_u32(value: u32) -> Option<Self> {
@@ -2518,6 +3052,12 @@ mod raw {
                         if value == Layout::WidthToChildren as u32 {
                             return Some(Self::WidthToChildren);
                         }
+                        if value == Layout::Flex as u32 {
+                            return Some(Self::Flex);
+                        }
+                        if value == Layout::Grid as u32 {
+                            return Some(Self::Grid);
+                        }
                         None
                     }
                 }
@@ -2553,6 +3093,178 @@ mod raw {
                             .ok_or(MessageSerdeError::InvalidValue)
                     }
                 }
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**Justify**: How the space left along a layout is distributed."]
+                pub enum Justify {
+                    #[default]
+                    #[doc = "The children are packed at the beginning."]
+                    Begin,
+                    #[doc = "The children are packed in the center."]
+                    Center,
+                    #[doc = "The children are packed at the end."]
+                    End,
+                    #[doc = "The space is shared between the children, with none before the first or after the last."]
+                    SpaceBetween,
+                    #[doc = "The space is shared around each child, so the ends get half as much as between two children."]
+                    SpaceAround,
+                    #[doc = "The space is shared evenly between the children and the ends."]
+                    SpaceEvenly,
+                }
+                impl crate::ecs::EnumComponent for Justify {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Begin => Justify::Begin as u32,
+                            Self::Center => Justify::Center as u32,
+                            Self::End => Justify::End as u32,
+                            Self::SpaceBetween => Justify::SpaceBetween as u32,
+                            Self::SpaceAround => Justify::SpaceAround as u32,
+                            Self::SpaceEvenly => Justify::SpaceEvenly as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == Justify::Begin as u32 {
+                            return Some(Self::Begin);
+                        }
+                        if value == Justify::Center as u32 {
+                            return Some(Self::Center);
+                        }
+                        if value == Justify::End as u32 {
+                            return Some(Self::End);
+                        }
+                        if value == Justify::SpaceBetween as u32 {
+                            return Some(Self::SpaceBetween);
+                        }
+                        if value == Justify::SpaceAround as u32 {
+                            return Some(Self::SpaceAround);
+                        }
+                        if value == Justify::SpaceEvenly as u32 {
+                            return Some(Self::SpaceEvenly);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for Justify {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for Justify {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**FlexAlign**: How a child is aligned across a `Flex` layout."]
+                pub enum FlexAlign {
+                    #[default]
+                    #[doc = "Begin"]
+                    Begin,
+                    #[doc = "Center"]
+                    Center,
+                    #[doc = "End"]
+                    End,
+                    #[doc = "The child is stretched to the size of its line."]
+                    Stretch,
+                }
+                impl crate::ecs::EnumComponent for FlexAlign {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Begin => FlexAlign::Begin as u32,
+                            Self::Center => FlexAlign::Center as u32,
+                            Self::End => FlexAlign::End as u32,
+                            Self::Stretch => FlexAlign::Stretch as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == FlexAlign::Begin as u32 {
+                            return Some(Self::Begin);
+                        }
+                        if value == FlexAlign::Center as u32 {
+                            return Some(Self::Center);
+                        }
+                        if value == FlexAlign::End as u32 {
+                            return Some(Self::End);
+                        }
+                        if value == FlexAlign::Stretch as u32 {
+                            return Some(Self::Stretch);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for FlexAlign {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for FlexAlign {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod model {
@@ -2618,48 +3330,460 @@ mod raw {
                 pub fn no_sync() -> Component<()> {
                     *NO_SYNC
                 }
-            }
-        }
-        pub mod package {
-            #[doc = r" Auto-generated component definitions."]
-            pub mod components {
-                use crate::{
-                    ecs::{Component, __internal_get_component},
-                    once_cell::sync::Lazy,
-                    prelude::*,
-                };
-                static MAIN_PACKAGE_ID: Lazy<Component<EntityId>> = Lazy::new(|| {
-                    __internal_get_component("ambient_core::package::main_package_id")
+                static REPLICATION_RADIUS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::replication_radius")
                 });
-                #[doc = "**Main Package ID**: The ID of the main package.\n\n*Attributes*: Debuggable, Networked"]
-                pub fn main_package_id() -> Component<EntityId> {
-                    *MAIN_PACKAGE_ID
+                #[doc = "**Replication radius**: On a player entity, or as a resource for every player: only entities within this distance of the player's `replication_center` are replicated to them, while those that aren't positioned are always replicated. Entities that are children are replicated along with their root.\n\nUntil the player has a `replication_center`, positioned entities are not replicated to them at all.\n\n\n\n*Attributes*: Debuggable, MaybeResource"]
+                pub fn replication_radius() -> Component<f32> {
+                    *REPLICATION_RADIUS
                 }
-                static IS_PACKAGE: Lazy<Component<()>> =
-                    Lazy::new(|| __internal_get_component("ambient_core::package::is_package"));
-                #[doc = "**Is Package**: Whether or not this entity is a package.\n\n*Attributes*: Debuggable, Networked"]
-                pub fn is_package() -> Component<()> {
-                    *IS_PACKAGE
+                static REPLICATION_CENTER: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::replication_center")
+                });
+                #[doc = "**Replication center**: The position around which entities are replicated to this player, see `replication_radius`. Usually kept at the position of the player's character or camera.\n\n\n\n*Attributes*: Debuggable"]
+                pub fn replication_center() -> Component<Vec3> {
+                    *REPLICATION_CENTER
                 }
-                static ENABLED: Lazy<Component<bool>> =
-                    Lazy::new(|| __internal_get_component("ambient_core::package::enabled"));
-                #[doc = "**Enabled**: Whether or not this package is enabled.\n\n*Attributes*: Debuggable, Networked"]
-                pub fn enabled() -> Component<bool> {
-                    *ENABLED
+                static ALWAYS_REPLICATED: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::always_replicated")
+                });
+                #[doc = "**Always replicated**: If attached, this entity is replicated to every player regardless of their `replication_radius`.\n\n\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn always_replicated() -> Component<()> {
+                    *ALWAYS_REPLICATED
+                }
+                static INTEREST_GROUP: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::interest_group"));
+                #[doc = "**Interest group**: If attached, this entity is only replicated to the players whose `interest_groups` contain this group, regardless of their `replication_radius`. Useful for team-only entities or per-room state.\n\n\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn interest_group() -> Component<String> {
+                    *INTEREST_GROUP
+                }
+                static INTEREST_GROUPS: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::interest_groups")
+                });
+                #[doc = "**Interest groups**: The `interest_group`s whose entities are replicated to this player.\n\n\n\n*Attributes*: Debuggable"]
+                pub fn interest_groups() -> Component<Vec<String>> {
+                    *INTEREST_GROUPS
+                }
+                static PREDICTED: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::predicted"));
+                #[doc = "**Predicted**: If attached, clients simulate this entity ahead of the server with the inputs they send through a `Predictor`.\n\nWhenever the server's state of a predicted entity, or the player's `last_processed_input`, arrives, the client rewinds every predicted entity to the server's state, marks it with `prediction_rewound`, and replays the inputs the server hasn't processed yet.\n\n\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn predicted() -> Component<()> {
+                    *PREDICTED
+                }
+                static LAST_PROCESSED_INPUT: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::last_processed_input")
+                });
+                #[doc = "**Last processed input**: On a player entity: the sequence number of the last predicted input of that player the server has simulated. Inputs up to it are no longer replayed by the client.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn last_processed_input() -> Component<u32> {
+                    *LAST_PROCESSED_INPUT
                 }
-                static ID: Lazy<Component<String>> =
-                    Lazy::new(|| __internal_get_component("ambient_core::package::id"));
-                #[doc = "**ID**: The ID of the package.\n\n*Attributes*: Debuggable, Networked"]
-                pub fn id() -> Component<String> {
-                    *ID
+                static PREDICTION_REWOUND: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::prediction_rewound")
+                });
+                #[doc = "**Prediction rewound**: Added on the client to the `predicted` entities which have just been rewound to the server's state, until the pending inputs have been replayed on top of them.\n\n\n\n*Attributes*: Debuggable"]
+                pub fn prediction_rewound() -> Component<()> {
+                    *PREDICTION_REWOUND
                 }
-                static NAME: Lazy<Component<String>> =
-                    Lazy::new(|| __internal_get_component("ambient_core::package::name"));
-                #[doc = "**Name**: The name of the package.\n\n*Attributes*: Debuggable, Networked"]
-                pub fn name() -> Component<String> {
-                    *NAME
+                static INTERPOLATION_DELAY: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::interpolation_delay")
+                });
+                #[doc = "**Interpolation delay**: On the client: how far behind the server the transforms of remote entities are shown, so that they can be interpolated between the snapshots the server sends instead of jumping on every update. Defaults to 100 ms if not set; zero disables interpolation.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn interpolation_delay() -> Component<Duration> {
+                    *INTERPOLATION_DELAY
                 }
-                static VERSION: Lazy<Component<String>> =
+                static MAX_EXTRAPOLATION: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::max_extrapolation")
+                });
+                #[doc = "**Max extrapolation**: On the client: how long remote entities keep moving with the velocity of their last snapshots when the next one is late, before they stop. Defaults to 250 ms if not set.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn max_extrapolation() -> Component<Duration> {
+                    *MAX_EXTRAPOLATION
+                }
+                static SERVER_TIME: Lazy<Component<Duration>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::server_time"));
+                #[doc = "**Server time**: On the synchronized resources: the server's `game_time` when it sent the latest update to the clients.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn server_time() -> Component<Duration> {
+                    *SERVER_TIME
+                }
+                static SHOWN_SERVER_TIME: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::shown_server_time")
+                });
+                #[doc = "**Shown server time**: On the client: the `server_time` the remote entities are currently shown at, taking `interpolation_delay` into account. Send it to the server along with a shot, so that the server can check what the player saw with `raycast_at`.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn shown_server_time() -> Component<Duration> {
+                    *SHOWN_SERVER_TIME
+                }
+                static BANDWIDTH_BUDGET: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::bandwidth_budget")
+                });
+                #[doc = "**Bandwidth budget**: On the server: the bytes per second the updates of the world sent to a player may take, on the player entity or as a resource for every player. Once the budget is spent, changes to low priority components are held back first, then those to normal priority components, and only the latest value is sent when there is room again. Zero or not set means no limit.\n\n\n\n*Attributes*: Debuggable, MaybeResource"]
+                pub fn bandwidth_budget() -> Component<u32> {
+                    *BANDWIDTH_BUDGET
+                }
+                static BANDWIDTH_IN: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::bandwidth_in"));
+                #[doc = "**Bandwidth in**: On a player entity: the bytes per second the server has received from the player over the last second.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn bandwidth_in() -> Component<f32> {
+                    *BANDWIDTH_IN
+                }
+                static BANDWIDTH_OUT: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::bandwidth_out"));
+                #[doc = "**Bandwidth out**: On a player entity: the bytes per second the server has sent to the player over the last second.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn bandwidth_out() -> Component<f32> {
+                    *BANDWIDTH_OUT
+                }
+                static ROUND_TRIP_TIME: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::round_trip_time")
+                });
+                #[doc = "**Round trip time**: On a player entity: the time it takes for a packet to reach the player and be acknowledged, as estimated by the connection.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn round_trip_time() -> Component<Duration> {
+                    *ROUND_TRIP_TIME
+                }
+                static PACKET_LOSS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::packet_loss"));
+                #[doc = "**Packet loss**: On a player entity: the fraction of the packets sent to the player over the last second which were lost, from 0 to 1.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn packet_loss() -> Component<f32> {
+                    *PACKET_LOSS
+                }
+                static SEND_QUEUE_DEPTH: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::send_queue_depth")
+                });
+                #[doc = "**Send queue depth**: On a player entity: the number of world updates waiting to be sent to the player, including the entities whose changes are held back by `bandwidth_budget`.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn send_queue_depth() -> Component<u32> {
+                    *SEND_QUEUE_DEPTH
+                }
+                static AUTHORITY: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::network::authority"));
+                #[doc = "**Authority**: The user id of the player with authority over this entity. Their client simulates the entity and sends the changes it makes to its networked components to the server, which applies them and replicates them to the other players.\n\n\n\nChanges to the entity sent by any other client are ignored, and the owner ignores the changes the server sends it, so the server should take authority back (by removing this component) before changing the entity itself.\n\n\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn authority() -> Component<String> {
+                    *AUTHORITY
+                }
+                static MESSAGE_RATE_LIMIT: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::message_rate_limit")
+                });
+                #[doc = "**Message rate limit**: On the server: the messages of each type per second a player may send to the server modules, on the player entity or as a resource for every player. Bursts of up to one second of messages are allowed. Messages over the limit are dropped before they reach the modules. Zero or not set means no limit.\n\n\n\n*Attributes*: Debuggable, MaybeResource"]
+                pub fn message_rate_limit() -> Component<u32> {
+                    *MESSAGE_RATE_LIMIT
+                }
+                static MESSAGE_FLOOD_LIMIT: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::message_flood_limit")
+                });
+                #[doc = "**Message flood limit**: On the server: the messages over `message_rate_limit` a player may send within a second before being kicked, on the player entity or as a resource for every player. Zero or not set means players are never kicked, only throttled.\n\n\n\n*Attributes*: Debuggable, MaybeResource"]
+                pub fn message_flood_limit() -> Component<u32> {
+                    *MESSAGE_FLOOD_LIMIT
+                }
+                static MESSAGES_RECEIVED: Lazy<Component<u64>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::messages_received")
+                });
+                #[doc = "**Messages received**: On a player entity: the number of messages the player has sent to the server modules which were passed on to them.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn messages_received() -> Component<u64> {
+                    *MESSAGES_RECEIVED
+                }
+                static MESSAGES_DROPPED: Lazy<Component<u64>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::messages_dropped")
+                });
+                #[doc = "**Messages dropped**: On a player entity: the number of messages the player has sent to the server modules which were dropped for going over `message_rate_limit`.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn messages_dropped() -> Component<u64> {
+                    *MESSAGES_DROPPED
+                }
+                static SIMULATED_LATENCY: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::simulated_latency")
+                });
+                #[doc = "**Simulated latency**: For development: holds back what this side of the connection receives (messages, datagrams and, on the client, the updates of the world) by this long, to simulate a slow network. Not set means no simulated latency.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn simulated_latency() -> Component<Duration> {
+                    *SIMULATED_LATENCY
+                }
+                static SIMULATED_JITTER: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::simulated_jitter")
+                });
+                #[doc = "**Simulated jitter**: For development: holds back what this side of the connection receives by up to this long on top of `simulated_latency`, at random. Datagrams and messages may arrive out of order as a result, while the updates of the world stay in order.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn simulated_jitter() -> Component<Duration> {
+                    *SIMULATED_JITTER
+                }
+                static SIMULATED_PACKET_LOSS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::simulated_packet_loss")
+                });
+                #[doc = "**Simulated packet loss**: For development: the fraction of the datagrams this side of the connection receives which are dropped, from 0 to 1.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn simulated_packet_loss() -> Component<f32> {
+                    *SIMULATED_PACKET_LOSS
+                }
+                static SIMULATED_DUPLICATION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::network::simulated_duplication")
+                });
+                #[doc = "**Simulated duplication**: For development: the fraction of the datagrams this side of the connection receives which arrive twice, from 0 to 1.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn simulated_duplication() -> Component<f32> {
+                    *SIMULATED_DUPLICATION
+                }
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{
+                    message::{
+                        Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                        RuntimeMessage,
+                    },
+                    prelude::*,
+                };
+                #[derive(Clone, Debug)]
+                #[doc = "**HandoffRequest**: Sent by a server module: hands `root` and its descendants off to the server whose HTTP interface is at `endpoint`, to keep simulating them there. If `user_id` is not empty, that player comes along: the player entity moves with its components, and its client is redirected to `address`, the QUIC address of the other server."]
+                pub struct HandoffRequest {
+                    pub root: EntityId,
+                    pub user_id: String,
+                    pub endpoint: String,
+                    pub address: String,
+                }
+                impl HandoffRequest {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        root: impl Into<EntityId>,
+                        user_id: impl Into<String>,
+                        endpoint: impl Into<String>,
+                        address: impl Into<String>,
+                    ) -> Self {
+                        Self {
+                            root: root.into(),
+                            user_id: user_id.into(),
+                            endpoint: endpoint.into(),
+                            address: address.into(),
+                        }
+                    }
+                }
+                impl Message for HandoffRequest {
+                    fn id() -> &'static str {
+                        "ambient_core::network::HandoffRequest"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.root.serialize_message_part(&mut output)?;
+                        self.user_id.serialize_message_part(&mut output)?;
+                        self.endpoint.serialize_message_part(&mut output)?;
+                        self.address.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            root: EntityId::deserialize_message_part(&mut input)?,
+                            user_id: String::deserialize_message_part(&mut input)?,
+                            endpoint: String::deserialize_message_part(&mut input)?,
+                            address: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for HandoffRequest {}
+                #[derive(Clone, Debug)]
+                #[doc = "**HandoffArrived**: Entities were handed off to this server: `root` and its descendants, which keep their IDs. If `user_id` is not empty, that player is on its way, and joins once its client has been redirected."]
+                pub struct HandoffArrived {
+                    pub root: EntityId,
+                    pub user_id: String,
+                }
+                impl HandoffArrived {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(root: impl Into<EntityId>, user_id: impl Into<String>) -> Self {
+                        Self {
+                            root: root.into(),
+                            user_id: user_id.into(),
+                        }
+                    }
+                }
+                impl Message for HandoffArrived {
+                    fn id() -> &'static str {
+                        "ambient_core::network::HandoffArrived"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.root.serialize_message_part(&mut output)?;
+                        self.user_id.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            root: EntityId::deserialize_message_part(&mut input)?,
+                            user_id: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for HandoffArrived {}
+                #[derive(Clone, Debug)]
+                #[doc = "**HandoffFailed**: The handoff of `root` to another server failed, for the given reason. The entities are left as they were on this server."]
+                pub struct HandoffFailed {
+                    pub root: EntityId,
+                    pub reason: String,
+                }
+                impl HandoffFailed {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(root: impl Into<EntityId>, reason: impl Into<String>) -> Self {
+                        Self {
+                            root: root.into(),
+                            reason: reason.into(),
+                        }
+                    }
+                }
+                impl Message for HandoffFailed {
+                    fn id() -> &'static str {
+                        "ambient_core::network::HandoffFailed"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.root.serialize_message_part(&mut output)?;
+                        self.reason.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            root: EntityId::deserialize_message_part(&mut input)?,
+                            reason: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for HandoffFailed {}
+                #[derive(Clone, Debug)]
+                #[doc = "**RconRegisterCommand**: Sent by a server module: registers `name` as an admin command of the remote console, described by `description`. When an admin runs it, the module receives an `RconCommand`."]
+                pub struct RconRegisterCommand {
+                    pub name: String,
+                    pub description: String,
+                }
+                impl RconRegisterCommand {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+                        Self {
+                            name: name.into(),
+                            description: description.into(),
+                        }
+                    }
+                }
+                impl Message for RconRegisterCommand {
+                    fn id() -> &'static str {
+                        "ambient_core::network::RconRegisterCommand"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.name.serialize_message_part(&mut output)?;
+                        self.description.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            name: String::deserialize_message_part(&mut input)?,
+                            description: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for RconRegisterCommand {}
+                #[derive(Clone, Debug)]
+                #[doc = "**RconCommand**: An admin ran the remote console command `name` with `args`. The module which registered it answers with an `RconOutput` for the same `request`."]
+                pub struct RconCommand {
+                    pub request: u32,
+                    pub name: String,
+                    pub args: String,
+                }
+                impl RconCommand {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        request: impl Into<u32>,
+                        name: impl Into<String>,
+                        args: impl Into<String>,
+                    ) -> Self {
+                        Self {
+                            request: request.into(),
+                            name: name.into(),
+                            args: args.into(),
+                        }
+                    }
+                }
+                impl Message for RconCommand {
+                    fn id() -> &'static str {
+                        "ambient_core::network::RconCommand"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.request.serialize_message_part(&mut output)?;
+                        self.name.serialize_message_part(&mut output)?;
+                        self.args.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            request: u32::deserialize_message_part(&mut input)?,
+                            name: String::deserialize_message_part(&mut input)?,
+                            args: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl RuntimeMessage for RconCommand {}
+                #[derive(Clone, Debug)]
+                #[doc = "**RconOutput**: Sent by a server module: the output of the remote console command it ran for `request`, which is shown to the admin."]
+                pub struct RconOutput {
+                    pub request: u32,
+                    pub output: String,
+                }
+                impl RconOutput {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(request: impl Into<u32>, output: impl Into<String>) -> Self {
+                        Self {
+                            request: request.into(),
+                            output: output.into(),
+                        }
+                    }
+                }
+                impl Message for RconOutput {
+                    fn id() -> &'static str {
+                        "ambient_core::network::RconOutput"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.request.serialize_message_part(&mut output)?;
+                        self.output.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            request: u32::deserialize_message_part(&mut input)?,
+                            output: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for RconOutput {}
+            }
+        }
+        pub mod package {
+            #[doc = r" Auto-generated component definitions."]
+            pub mod components {
+                use crate::{
+                    ecs::{Component, __internal_get_component},
+                    once_cell::sync::Lazy,
+                    prelude::*,
+                };
+                static MAIN_PACKAGE_ID: Lazy<Component<EntityId>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::package::main_package_id")
+                });
+                #[doc = "**Main Package ID**: The ID of the main package.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn main_package_id() -> Component<EntityId> {
+                    *MAIN_PACKAGE_ID
+                }
+                static IS_PACKAGE: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::package::is_package"));
+                #[doc = "**Is Package**: Whether or not this entity is a package.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn is_package() -> Component<()> {
+                    *IS_PACKAGE
+                }
+                static ENABLED: Lazy<Component<bool>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::package::enabled"));
+                #[doc = "**Enabled**: Whether or not this package is enabled.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn enabled() -> Component<bool> {
+                    *ENABLED
+                }
+                static ID: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::package::id"));
+                #[doc = "**ID**: The ID of the package.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn id() -> Component<String> {
+                    *ID
+                }
+                static NAME: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::package::name"));
+                #[doc = "**Name**: The name of the package.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn name() -> Component<String> {
+                    *NAME
+                }
+                static VERSION: Lazy<Component<String>> =
                     Lazy::new(|| __internal_get_component("ambient_core::package::version"));
                 #[doc = "**Version**: The version of the package.\n\n*Attributes*: Debuggable, Networked"]
                 pub fn version() -> Component<String> {
@@ -2973,7 +4097,8 @@ mod raw {
             pub mod messages {
                 use crate::{
                     message::{
-                        Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                        Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                        RuntimeMessage,
                     },
                     prelude::*,
                 };
@@ -3144,12 +4269,38 @@ mod raw {
                 pub fn angular_velocity() -> Component<Vec3> {
                     *ANGULAR_VELOCITY
                 }
+                static BUOYANCY_DENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::buoyancy_density")
+                });
+                #[doc = "**Buoyancy density**: The density of the fluid in this `buoyancy_volume`, relative to the `density` of the bodies in it.\n\nBodies with a lower density than the fluid float, and bodies with a higher density sink.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn buoyancy_density() -> Component<f32> {
+                    *BUOYANCY_DENSITY
+                }
+                static BUOYANCY_DRAG: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::buoyancy_drag"));
+                #[doc = "**Buoyancy drag**: How strongly the fluid in this `buoyancy_volume` slows down the bodies moving through it, as the fraction of their velocity lost per second.\n\nThis is scaled by how much of each body is submerged.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn buoyancy_drag() -> Component<f32> {
+                    *BUOYANCY_DRAG
+                }
+                static BUOYANCY_VOLUME: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::buoyancy_volume")
+                });
+                #[doc = "**Buoyancy volume**: If attached, this entity is a volume of fluid that pushes dynamic bodies up, in proportion to how deep they are submerged.\n\n`x, y, z` is the size of the box, centered on this entity's position and aligned with the world axes; its top is the surface of the fluid.\n\nA `Splash` message is sent when a body enters or leaves the volume.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn buoyancy_volume() -> Component<Vec3> {
+                    *BUOYANCY_VOLUME
+                }
                 static CUBE_COLLIDER: Lazy<Component<Vec3>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::cube_collider"));
                 #[doc = "**Cube collider**: If attached, this entity will have a cube physics collider.\n\n`x, y, z` is the size of the cube.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn cube_collider() -> Component<Vec3> {
                     *CUBE_COLLIDER
                 }
+                static CCD_ENABLED: Lazy<Component<bool>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ccd_enabled"));
+                #[doc = "**CCD enabled**: Whether continuous collision detection (CCD) is enabled for this dynamic entity. CCD stops fast-moving entities, like projectiles and vehicles, from passing through thin colliders, at the cost of some performance.\n\nIf this component is not attached, CCD is enabled for dynamic entities and disabled for kinematic entities. Kinematic entities use speculative CCD when it is enabled.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn ccd_enabled() -> Component<bool> {
+                    *CCD_ENABLED
+                }
                 static CHARACTER_CONTROLLER_HEIGHT: Lazy<Component<f32>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::physics::character_controller_height")
                 });
@@ -3164,6 +4315,13 @@ mod raw {
                 pub fn character_controller_radius() -> Component<f32> {
                     *CHARACTER_CONTROLLER_RADIUS
                 }
+                static COLLIDER_CONVEX_HULLS: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::collider_convex_hulls")
+                });
+                #[doc = "**Collider convex hulls**: The number of convex hulls in the convex version of this entity's collider, which is used when the entity is dynamic. This is attached when the collider is loaded.\n\nConcave models are split into several convex hulls by the asset pipeline when their collider is dynamic; see `max_convex_hulls` in the pipeline's `FromModel` collider.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn collider_convex_hulls() -> Component<u32> {
+                    *COLLIDER_CONVEX_HULLS
+                }
                 static COLLIDER_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::physics::collider_from_url")
                 });
@@ -3184,6 +4342,47 @@ mod raw {
                 pub fn collider_loads() -> Component<Vec<EntityId>> {
                     *COLLIDER_LOADS
                 }
+                static COLLIDER_MAX_CONCAVITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::collider_max_concavity")
+                });
+                #[doc = "**Collider max concavity**: The largest concavity left in the convex hulls of this entity's collider, relative to the size of each hull, when the asset pipeline split its model into convex hulls. It is part of the prefabs of the models with a dynamic `FromModel` collider, and can be read once the collider has loaded, along with `collider_convex_hulls`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn collider_max_concavity() -> Component<f32> {
+                    *COLLIDER_MAX_CONCAVITY
+                }
+                static COLLISION_EVENT_GROUPS: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::collision_event_groups")
+                });
+                #[doc = "**Collision event groups**: If attached to a package, its modules will only receive `Collision` messages for collisions involving at least one entity in one of these collision groups (a bitmask, see `collision_groups`).\n\nPackages without this component receive every `Collision` message.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn collision_event_groups() -> Component<u32> {
+                    *COLLISION_EVENT_GROUPS
+                }
+                static COLLISION_GROUPS: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::collision_groups")
+                });
+                #[doc = "**Collision groups**: A bitmask of the collision groups this entity belongs to. Entities don't collide with entities that ignore one of their groups (see `collision_ignored_groups`).\n\nIf not attached, the entity is not in any group.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn collision_groups() -> Component<u32> {
+                    *COLLISION_GROUPS
+                }
+                static COLLISION_IGNORED_GROUPS: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::collision_ignored_groups")
+                });
+                #[doc = "**Collision ignored groups**: A bitmask of the collision groups this entity does not collide with (see `collision_groups`).\n\nThis can be used for team-based collisions, or for one-way platforms, by ignoring the platforms' group while moving up through them. If not attached, the entity collides with every group.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn collision_ignored_groups() -> Component<u32> {
+                    *COLLISION_IGNORED_GROUPS
+                }
+                static CONTACT_FILTER: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::contact_filter"));
+                #[doc = "**Contact filter**: If attached to a package, its modules decide whether the entities with `contact_filtered` collide with the entities they touch.\n\nThey are sent a `ContactFilter` message for each new pair of touching entities, and answer with `physics::set_contact_filter`. The answers are cached, so each pair is only asked about once.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn contact_filter() -> Component<()> {
+                    *CONTACT_FILTER
+                }
+                static CONTACT_FILTERED: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::contact_filtered")
+                });
+                #[doc = "**Contact filtered**: If attached, whether this entity collides with the entities it touches is decided by the packages with `contact_filter`.\n\nIt collides with an entity until they have answered for it.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn contact_filtered() -> Component<()> {
+                    *CONTACT_FILTERED
+                }
                 static CONTACT_OFFSET: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::contact_offset"));
                 #[doc = "**Contact offset**: Contact offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's contact offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -3192,7 +4391,7 @@ mod raw {
                 }
                 static DENSITY: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::density"));
-                #[doc = "**Density**: The density of this entity.\n\nThis is used to update the `mass` when the entity is rescaled.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                #[doc = "**Density**: The density of this entity, in kg/m³.\n\nThe `mass` and `inertia` of dynamic entities are computed from the volume of their collider and this density, and are updated when the entity is rescaled.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
                 pub fn density() -> Component<f32> {
                     *DENSITY
                 }
@@ -3202,12 +4401,120 @@ mod raw {
                 pub fn dynamic() -> Component<bool> {
                     *DYNAMIC
                 }
+                static FORCE_FIELD_ATTRACTOR: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::force_field_attractor")
+                });
+                #[doc = "**Force field attractor**: If attached along with `force_field_radius`, dynamic bodies within the radius are accelerated towards this entity's position by this amount (in m/s²) every physics step, regardless of their mass.\n\nNegative values push bodies away instead, which can be used for explosions and repulsors.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn force_field_attractor() -> Component<f32> {
+                    *FORCE_FIELD_ATTRACTOR
+                }
+                static FORCE_FIELD_FALLOFF: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::force_field_falloff")
+                });
+                #[doc = "**Force field falloff**: If attached to a force field, its strength falls off linearly from the full strength at its center to zero at `force_field_radius`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn force_field_falloff() -> Component<()> {
+                    *FORCE_FIELD_FALLOFF
+                }
+                static FORCE_FIELD_RADIUS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::force_field_radius")
+                });
+                #[doc = "**Force field radius**: The radius of the sphere around this entity's position in which its force field affects dynamic bodies.\n\nThe force field itself is defined by `force_field_wind` and/or `force_field_attractor`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn force_field_radius() -> Component<f32> {
+                    *FORCE_FIELD_RADIUS
+                }
+                static FORCE_FIELD_WIND: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::force_field_wind")
+                });
+                #[doc = "**Force field wind**: If attached along with `force_field_radius`, dynamic bodies within the radius are accelerated by this vector (in world space, in m/s²) every physics step, regardless of their mass.\n\nThis can be used for wind, currents and conveyor-like volumes.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn force_field_wind() -> Component<Vec3> {
+                    *FORCE_FIELD_WIND
+                }
+                static FRICTION: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::friction"));
+                #[doc = "**Friction**: The friction coefficient of this entity's collider, used for both static and dynamic friction.\n\nUpdating this component will update the physics material of each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn friction() -> Component<f32> {
+                    *FRICTION
+                }
+                static FRICTION_COMBINE_MODE: Lazy<
+                    Component<crate::ambient_core::physics::types::CombineMode>,
+                > = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::friction_combine_mode")
+                });
+                #[doc = "**Friction combine mode**: How the friction of this entity's collider is combined with the friction of the collider it is touching.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn friction_combine_mode(
+                ) -> Component<crate::ambient_core::physics::types::CombineMode> {
+                    *FRICTION_COMBINE_MODE
+                }
+                static HEIGHTFIELD_COLLIDER: Lazy<Component<Vec<f32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::heightfield_collider")
+                });
+                #[doc = "**Heightfield collider**: If attached along with `heightfield_collider_resolution`, this entity will have a heightfield physics collider. This is much cheaper than building large terrains out of many colliders or a triangle mesh.\n\nThe value contains the height of each sample, row by row: the sample at `(x, y)` is at index `y * resolution.x + x`, and is placed at `(x, y, height)` in the entity's local space. Use `scale` to change the spacing between samples and the height.\n\nHeightfield colliders can't be used with dynamic entities, unless they are also kinematic.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn heightfield_collider() -> Component<Vec<f32>> {
+                    *HEIGHTFIELD_COLLIDER
+                }
+                static HEIGHTFIELD_COLLIDER_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::heightfield_collider_from_url")
+                });
+                #[doc = "**Heightfield collider from URL**: If attached, this entity will have a heightfield physics collider loaded from the grayscale heightmap image at this URL.\n\nEach pixel becomes a sample, with black being a height of 0 and white being a height of 1; the image's top-left pixel is at the local origin, with rows going along +Y. Use `scale` to change the spacing between samples and the height.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn heightfield_collider_from_url() -> Component<String> {
+                    *HEIGHTFIELD_COLLIDER_FROM_URL
+                }
+                static HEIGHTFIELD_COLLIDER_RESOLUTION: Lazy<Component<UVec2>> = Lazy::new(|| {
+                    __internal_get_component(
+                        "ambient_core::physics::heightfield_collider_resolution",
+                    )
+                });
+                #[doc = "**Heightfield collider resolution**: The number of samples along the X and Y axes of this entity's `heightfield_collider`. Each axis needs at least 2 samples.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn heightfield_collider_resolution() -> Component<UVec2> {
+                    *HEIGHTFIELD_COLLIDER_RESOLUTION
+                }
+                static INERTIA: Lazy<Component<Vec3>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::inertia"));
+                #[doc = "**Inertia**: The moment of inertia (in kg·m²) of this dynamic entity around each of its principal axes.\n\nLike `mass`, this is computed from the collider and `density` of the entity.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn inertia() -> Component<Vec3> {
+                    *INERTIA
+                }
+                static JOINT_BREAK_FORCE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::joint_break_force")
+                });
+                #[doc = "**Joint break force**: The linear force (in newtons) above which the joints attached to this entity break.\n\nIf both entities of a joint have a break force, the lowest one is used. If not attached, joints are unbreakable. A `JointBroken` message is sent when joints break.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn joint_break_force() -> Component<f32> {
+                    *JOINT_BREAK_FORCE
+                }
+                static JOINT_BREAK_TORQUE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::joint_break_torque")
+                });
+                #[doc = "**Joint break torque**: The torque (in newton-meters) above which the joints attached to this entity break.\n\nIf both entities of a joint have a break torque, the lowest one is used. If not attached, joints are unbreakable. A `JointBroken` message is sent when joints break.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn joint_break_torque() -> Component<f32> {
+                    *JOINT_BREAK_TORQUE
+                }
                 static KINEMATIC: Lazy<Component<()>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::kinematic"));
                 #[doc = "**Kinematic**: If attached, and this entity is dynamic, this entity will also be kinematic (i.e. unable to be affected by other entities motion). Otherwise, it will receive forces normally.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn kinematic() -> Component<()> {
                     *KINEMATIC
                 }
+                static KINEMATIC_TARGET_ROTATION: Lazy<Component<Quat>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::kinematic_target_rotation")
+                });
+                #[doc = "**Kinematic target rotation**: If attached to a kinematic entity, it will be rotated to this rotation over the physics steps of the next frame, pushing dynamic bodies out of its way.\n\nUnlike setting `rotation` directly, the body gets an angular velocity while it moves, so the bodies it pushes (or carries) move along with it. Add `physics_controlled` to have `rotation` follow the body.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn kinematic_target_rotation() -> Component<Quat> {
+                    *KINEMATIC_TARGET_ROTATION
+                }
+                static KINEMATIC_TARGET_TRANSLATION: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::kinematic_target_translation")
+                });
+                #[doc = "**Kinematic target translation**: If attached to a kinematic entity, it will be moved to this position over the physics steps of the next frame, pushing dynamic bodies out of its way. This can be used for moving platforms, doors and elevators.\n\nUnlike setting `translation` directly, the body gets a velocity while it moves, so the bodies it pushes (or carries) move along with it. Add `physics_controlled` to have `translation` follow the body.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn kinematic_target_translation() -> Component<Vec3> {
+                    *KINEMATIC_TARGET_TRANSLATION
+                }
+                static LAG_COMPENSATION_HISTORY: Lazy<Component<Duration>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::lag_compensation_history")
+                });
+                #[doc = "**Lag compensation history**: How long the server keeps the poses of the moving colliders for `raycast_at`, which limits how far back a player's shot can be rewound. Defaults to 1 second if not set.\n\n\n\n*Attributes*: Debuggable, Resource"]
+                pub fn lag_compensation_history() -> Component<Duration> {
+                    *LAG_COMPENSATION_HISTORY
+                }
                 static LINEAR_VELOCITY: Lazy<Component<Vec3>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::physics::linear_velocity")
                 });
@@ -3224,7 +4531,7 @@ mod raw {
                 }
                 static MASS: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::mass"));
-                #[doc = "**Mass**: The mass of this entity, measured in kilograms.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                #[doc = "**Mass**: The mass of this entity, measured in kilograms.\n\nThis is computed from the collider and `density` of dynamic entities; to make an entity heavier or lighter, change its `density`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
                 pub fn mass() -> Component<f32> {
                     *MASS
                 }
@@ -3235,18 +4542,66 @@ mod raw {
                 pub fn physics_controlled() -> Component<()> {
                     *PHYSICS_CONTROLLED
                 }
+                static PHYSICS_TIMESTEP: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::physics_timestep")
+                });
+                #[doc = "**Physics timestep**: The fixed time step (in seconds) that the physics simulation is advanced by.\n\nPhysics is stepped as many times as needed to keep up with the server's frame time, independently of the frame rate, and the transforms of physics-controlled entities are interpolated between the last two steps. Forces added to an entity only act on the next step.\n\nIf not attached, the server tick time is used.\n\n*Attributes*: Debuggable, Networked, Resource, Store"]
+                pub fn physics_timestep() -> Component<f32> {
+                    *PHYSICS_TIMESTEP
+                }
                 static PLANE_COLLIDER: Lazy<Component<()>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::plane_collider"));
                 #[doc = "**Plane collider**: If attached, this entity will have a plane physics collider. A plane is an infinite, flat surface. If you need a bounded flat surface, consider using a cube collider instead.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn plane_collider() -> Component<()> {
                     *PLANE_COLLIDER
                 }
+                static RAGDOLL: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ragdoll"));
+                #[doc = "**Ragdoll**: If attached to a model with an animation binder (a character), its bones will be simulated as a ragdoll: each bone becomes a link in a physics articulation, with a capsule shape reaching to its child bones and a limited spherical joint to its parent bone.\n\nRemoving this component releases the articulation and hands the bones back to the animation.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn ragdoll() -> Component<()> {
+                    *RAGDOLL
+                }
+                static RAGDOLL_BLEND: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::ragdoll_blend"));
+                #[doc = "**Ragdoll blend**: How much of a ragdoll's pose comes from the physics simulation, between 0 and 1. At 0 the bones follow the animation, and at 1 they follow the simulation.\n\nIf not attached, the simulation is used.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn ragdoll_blend() -> Component<f32> {
+                    *RAGDOLL_BLEND
+                }
                 static REST_OFFSET: Lazy<Component<f32>> =
                     Lazy::new(|| __internal_get_component("ambient_core::physics::rest_offset"));
                 #[doc = "**Rest offset**: Rest offset (in meters) of this entity in the physics scene.\n\nUpdating this component will update the entity's rest offset for each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn rest_offset() -> Component<f32> {
                     *REST_OFFSET
                 }
+                static RESTITUTION: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::restitution"));
+                #[doc = "**Restitution**: The restitution (bounciness) of this entity's collider. 0 will not bounce at all, while 1 will bounce without losing energy.\n\nUpdating this component will update the physics material of each attached shape in the physics scene.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.6"]
+                pub fn restitution() -> Component<f32> {
+                    *RESTITUTION
+                }
+                static RESTITUTION_COMBINE_MODE: Lazy<
+                    Component<crate::ambient_core::physics::types::CombineMode>,
+                > = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::restitution_combine_mode")
+                });
+                #[doc = "**Restitution combine mode**: How the restitution of this entity's collider is combined with the restitution of the collider it is touching.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn restitution_combine_mode(
+                ) -> Component<crate::ambient_core::physics::types::CombineMode> {
+                    *RESTITUTION_COMBINE_MODE
+                }
+                static SLEEP_THRESHOLD: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::sleep_threshold")
+                });
+                #[doc = "**Sleep threshold**: The mass-normalized kinetic energy (in m²/s²) below which this dynamic entity may fall asleep. Sleeping entities are not simulated until something wakes them up, which saves a lot of time in scenes with many resting objects.\n\nIf not attached, the PhysX default is used.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn sleep_threshold() -> Component<f32> {
+                    *SLEEP_THRESHOLD
+                }
+                static SLEEPING: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::sleeping"));
+                #[doc = "**Sleeping**: Attached to dynamic entities while they are asleep.\n\nA `BodySleep` message is sent when entities fall asleep, and a `BodyWake` message when they wake up.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn sleeping() -> Component<()> {
+                    *SLEEPING
+                }
                 static SPHERE_COLLIDER: Lazy<Component<f32>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::physics::sphere_collider")
                 });
@@ -3272,20 +4627,130 @@ mod raw {
                 pub fn unit_yaw() -> Component<f32> {
                     *UNIT_YAW
                 }
-                static VISUALIZE_COLLIDER: Lazy<Component<()>> = Lazy::new(|| {
-                    __internal_get_component("ambient_core::physics::visualize_collider")
+                static VEHICLE_BRAKE: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::vehicle_brake"));
+                #[doc = "**Vehicle brake**: The brake input of this vehicle, from 0 (no braking) to 1 (full braking).\n\nSet this from your gameplay code to control the vehicle.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_brake() -> Component<f32> {
+                    *VEHICLE_BRAKE
+                }
+                static VEHICLE_BRAKE_FORCE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_brake_force")
                 });
-                #[doc = "**Visualize collider**: If attached, the collider will be rendered.\n\n\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n\n\n\n*Attributes*: Debuggable, Networked"]
-                pub fn visualize_collider() -> Component<()> {
-                    *VISUALIZE_COLLIDER
+                #[doc = "**Vehicle brake force**: The total force (in newtons) applied by the brakes of this vehicle at full braking, spread across all wheels.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 8000.0"]
+                pub fn vehicle_brake_force() -> Component<f32> {
+                    *VEHICLE_BRAKE_FORCE
                 }
-            }
-            #[doc = r" Auto-generated concept definitions. Concepts are collections of components that describe some form of gameplay concept."]
-            #[doc = r""]
-            #[doc = r" They do not have any runtime representation outside of the components that compose them."]
-            pub mod concepts {
-                use crate::{
-                    global::serde::{self, Deserialize, Serialize},
+                static VEHICLE_DRIVEN_WHEELS: Lazy<Component<Vec<u32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_driven_wheels")
+                });
+                #[doc = "**Vehicle driven wheels**: The indices (into `vehicle_wheel_offsets`) of the wheels that are driven by the engine.\n\nIf not attached, all wheels are driven.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_driven_wheels() -> Component<Vec<u32>> {
+                    *VEHICLE_DRIVEN_WHEELS
+                }
+                static VEHICLE_ENGINE_CURVE: Lazy<Component<Vec<Vec2>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_engine_curve")
+                });
+                #[doc = "**Vehicle engine curve**: The engine curve of this vehicle. Each point is `(forward speed in meters/second, engine force multiplier)`, sorted by speed; the multiplier is linearly interpolated between points, and clamped to the first and last points.\n\nIf not attached, the full engine force is available at all speeds.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_engine_curve() -> Component<Vec<Vec2>> {
+                    *VEHICLE_ENGINE_CURVE
+                }
+                static VEHICLE_ENGINE_FORCE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_engine_force")
+                });
+                #[doc = "**Vehicle engine force**: The total force (in newtons) the engine of this vehicle applies at full throttle, spread across the driven wheels.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 6000.0"]
+                pub fn vehicle_engine_force() -> Component<f32> {
+                    *VEHICLE_ENGINE_FORCE
+                }
+                static VEHICLE_MAX_STEER_ANGLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_max_steer_angle")
+                });
+                #[doc = "**Vehicle max steer angle**: The maximum angle (in radians) that the steered wheels of this vehicle can turn.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.6"]
+                pub fn vehicle_max_steer_angle() -> Component<f32> {
+                    *VEHICLE_MAX_STEER_ANGLE
+                }
+                static VEHICLE_STEER: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::physics::vehicle_steer"));
+                #[doc = "**Vehicle steer**: The steering input of this vehicle, from -1 (full left) to 1 (full right).\n\nSet this from your gameplay code to control the vehicle.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_steer() -> Component<f32> {
+                    *VEHICLE_STEER
+                }
+                static VEHICLE_STEERED_WHEELS: Lazy<Component<Vec<u32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_steered_wheels")
+                });
+                #[doc = "**Vehicle steered wheels**: The indices (into `vehicle_wheel_offsets`) of the wheels that turn when steering.\n\nIf not attached, the vehicle can't steer.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_steered_wheels() -> Component<Vec<u32>> {
+                    *VEHICLE_STEERED_WHEELS
+                }
+                static VEHICLE_SUSPENSION_DAMPING: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_suspension_damping")
+                });
+                #[doc = "**Vehicle suspension damping**: The damping (in newton-seconds/meter) of each wheel's suspension on this vehicle.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 3000.0"]
+                pub fn vehicle_suspension_damping() -> Component<f32> {
+                    *VEHICLE_SUSPENSION_DAMPING
+                }
+                static VEHICLE_SUSPENSION_LENGTH: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_suspension_length")
+                });
+                #[doc = "**Vehicle suspension length**: The length (in meters) of each wheel's suspension on this vehicle when fully extended.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn vehicle_suspension_length() -> Component<f32> {
+                    *VEHICLE_SUSPENSION_LENGTH
+                }
+                static VEHICLE_SUSPENSION_STIFFNESS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_suspension_stiffness")
+                });
+                #[doc = "**Vehicle suspension stiffness**: The stiffness (in newtons/meter) of each wheel's suspension on this vehicle.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 30000.0"]
+                pub fn vehicle_suspension_stiffness() -> Component<f32> {
+                    *VEHICLE_SUSPENSION_STIFFNESS
+                }
+                static VEHICLE_THROTTLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_throttle")
+                });
+                #[doc = "**Vehicle throttle**: The throttle input of this vehicle, from -1 (full reverse) to 1 (full forward).\n\nSet this from your gameplay code to control the vehicle.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_throttle() -> Component<f32> {
+                    *VEHICLE_THROTTLE
+                }
+                static VEHICLE_TIRE_GRIP: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_tire_grip")
+                });
+                #[doc = "**Vehicle tire grip**: The friction coefficient between the tires of this vehicle and the ground. This limits how much force each wheel can apply sideways, and from the engine and brakes.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.5"]
+                pub fn vehicle_tire_grip() -> Component<f32> {
+                    *VEHICLE_TIRE_GRIP
+                }
+                static VEHICLE_WHEEL_COMPRESSIONS: Lazy<Component<Vec<f32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_wheel_compressions")
+                });
+                #[doc = "**Vehicle wheel compressions**: The current compression of each wheel's suspension on this vehicle, from 0 (fully extended, or not touching the ground) to 1 (fully compressed).\n\nThis is updated by the physics simulation, and can be used to position the wheel models.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_wheel_compressions() -> Component<Vec<f32>> {
+                    *VEHICLE_WHEEL_COMPRESSIONS
+                }
+                static VEHICLE_WHEEL_OFFSETS: Lazy<Component<Vec<Vec3>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_wheel_offsets")
+                });
+                #[doc = "**Vehicle wheel offsets**: If attached to a dynamic entity, it will be simulated as a raycast vehicle with a wheel at each offset. The offsets are in the entity's local space, and are where each wheel's suspension is mounted to the chassis.\n\nThe vehicle's forward direction is its local -Y axis, and its up direction is its local +Z axis.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn vehicle_wheel_offsets() -> Component<Vec<Vec3>> {
+                    *VEHICLE_WHEEL_OFFSETS
+                }
+                static VEHICLE_WHEEL_RADIUS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::vehicle_wheel_radius")
+                });
+                #[doc = "**Vehicle wheel radius**: The radius (in meters) of each wheel on this vehicle.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.4"]
+                pub fn vehicle_wheel_radius() -> Component<f32> {
+                    *VEHICLE_WHEEL_RADIUS
+                }
+                static VISUALIZE_COLLIDER: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::physics::visualize_collider")
+                });
+                #[doc = "**Visualize collider**: If attached, the collider will be rendered.\n\n\n\n**Note**: this will continuously overwrite the `local_gizmos` component.\n\n\n\n*Attributes*: Debuggable, Networked"]
+                pub fn visualize_collider() -> Component<()> {
+                    *VISUALIZE_COLLIDER
+                }
+            }
+            #[doc = r" Auto-generated concept definitions. Concepts are collections of components that describe some form of gameplay concept."]
+            #[doc = r""]
+            #[doc = r" They do not have any runtime representation outside of the components that compose them."]
+            pub mod concepts {
+                use crate::{
+                    global::serde::{self, Deserialize, Serialize},
                     prelude::*,
                 };
                 #[doc = "**Character Controller**: A capsule character controller. The capsule is defined as a position, a vertical height, and a radius. The height is the distance between the two sphere centers at the end of the capsule.\n\n**Note**: The character controller is not affected by gravity; you will have to manually simulate gravity by moving the character down in accordance with a vertical velocity you maintain. See the `character_movement` package for an example.\n\n**Required**:\n- `character_controller_height`: The height of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_radius`, it will be given a physical character collider.\n- `character_controller_radius`: The radius of the physics character controller attached to this entity.\nIf an entity has both this and a `character_controller_height`, it will be given a physical character collider.\n- `physics_controlled`: If attached, this entity will be controlled by physics.\nNote that this requires the entity to have a collider."]
@@ -3357,6 +4822,90 @@ mod raw {
                     }
                 }
             }
+            #[doc = r" Auto-generated type definitions."]
+            pub mod types {
+                use crate::{global::serde, message::*};
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**CombineMode**: How a physics material property is combined between two touching colliders."]
+                pub enum CombineMode {
+                    #[default]
+                    #[doc = "The average of the two values."]
+                    Average,
+                    #[doc = "The smaller of the two values."]
+                    Min,
+                    #[doc = "The product of the two values."]
+                    Multiply,
+                    #[doc = "The larger of the two values."]
+                    Max,
+                }
+                impl crate::ecs::EnumComponent for CombineMode {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Average => CombineMode::Average as u32,
+                            Self::Min => CombineMode::Min as u32,
+                            Self::Multiply => CombineMode::Multiply as u32,
+                            Self::Max => CombineMode::Max as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == CombineMode::Average as u32 {
+                            return Some(Self::Average);
+                        }
+                        if value == CombineMode::Min as u32 {
+                            return Some(Self::Min);
+                        }
+                        if value == CombineMode::Multiply as u32 {
+                            return Some(Self::Multiply);
+                        }
+                        if value == CombineMode::Max as u32 {
+                            return Some(Self::Max);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for CombineMode {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for CombineMode {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
+            }
         }
         pub mod player {
             #[doc = r" Auto-generated component definitions."]
@@ -3384,6 +4933,31 @@ mod raw {
                 pub fn user_id() -> Component<String> {
                     *USER_ID
                 }
+                static IDENTITY_SUBJECT: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::player::identity_subject")
+                });
+                #[doc = "**Identity subject**: On a player entity: who the player is, as verified by the server from the token the player's client presented when connecting. This is the same as their `user_id`.\n\nAbsent if the server does not authenticate its players.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn identity_subject() -> Component<String> {
+                    *IDENTITY_SUBJECT
+                }
+                static IDENTITY_NAME: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::player::identity_name"));
+                #[doc = "**Identity name**: On a player entity: the display name of the player, as verified by the server from the token the player's client presented when connecting.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn identity_name() -> Component<String> {
+                    *IDENTITY_NAME
+                }
+                static IDENTITY_ISSUER: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::player::identity_issuer"));
+                #[doc = "**Identity issuer**: On a player entity: who issued the token the player's client presented when connecting, and vouched for their identity.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn identity_issuer() -> Component<String> {
+                    *IDENTITY_ISSUER
+                }
+                static IDENTITY_ROLES: Lazy<Component<Vec<String>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::player::identity_roles"));
+                #[doc = "**Identity roles**: On a player entity: the roles of the player, as verified by the server from the token the player's client presented when connecting.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn identity_roles() -> Component<Vec<String>> {
+                    *IDENTITY_ROLES
+                }
             }
         }
         pub mod prefab {
@@ -4027,6 +5601,18 @@ mod raw {
                 pub fn background_url() -> Component<String> {
                     *BACKGROUND_URL
                 }
+                static BACKGROUND_REGION: Lazy<Component<Vec4>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rect::background_region"));
+                #[doc = "**Background region**: The region of the `background_url` image this rect shows, as the x and y of its top left corner followed by its width and height, in pixels.\n\nUse it to show one image out of an atlas. If not attached, the whole image is shown.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn background_region() -> Component<Vec4> {
+                    *BACKGROUND_REGION
+                }
+                static BACKGROUND_SLICE: Lazy<Component<Vec4>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rect::background_slice"));
+                #[doc = "**Background slice**: Nine-slice insets of the background image of this rect, in pixels: `x` = top, `y` = right, `z` = bottom, `w` = left.\n\nThe corners keep their size, the edges only stretch along their length, and the center stretches to fill the rect, so skinned buttons and panels can be resized without stretching their borders.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn background_slice() -> Component<Vec4> {
+                    *BACKGROUND_SLICE
+                }
                 static BORDER_COLOR: Lazy<Component<Vec4>> =
                     Lazy::new(|| __internal_get_component("ambient_core::rect::border_color"));
                 #[doc = "**Border color**: Border color of an entity with a `rect` component.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -4164,7 +5750,7 @@ mod raw {
                 }
                 static OUTLINE: Lazy<Component<Vec4>> =
                     Lazy::new(|| __internal_get_component("ambient_core::rendering::outline"));
-                #[doc = "**Outline**: If attached, this entity will be rendered with an outline with the color specified.\n\n*Attributes*: Debuggable, Networked, Store"]
+                #[doc = "**Outline**: If attached, this entity will be rendered with an outline with the color specified, as thick as `outline_thickness`.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn outline() -> Component<Vec4> {
                     *OUTLINE
                 }
@@ -4188,6 +5774,13 @@ mod raw {
                 pub fn pbr_material_from_url() -> Component<String> {
                     *PBR_MATERIAL_FROM_URL
                 }
+                static CUSTOM_MATERIAL_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::custom_material_from_url")
+                });
+                #[doc = "**Custom material from URL**: Load a custom material (a WGSL shader snippet with a typed parameter block) from the URL and attach it to this entity.\n\nThe material is reloaded whenever this component is set, or its package is reloaded.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn custom_material_from_url() -> Component<String> {
+                    *CUSTOM_MATERIAL_FROM_URL
+                }
                 static SKY: Lazy<Component<()>> =
                     Lazy::new(|| __internal_get_component("ambient_core::rendering::sky"));
                 #[doc = "**Sky**: Add a realistic skybox to the scene.\n\n*Attributes*: Debuggable, Networked, Store"]
@@ -4203,7 +5796,7 @@ mod raw {
                 static TRANSPARENCY_GROUP: Lazy<Component<i32>> = Lazy::new(|| {
                     __internal_get_component("ambient_core::rendering::transparency_group")
                 });
-                #[doc = "**Transparency group**: Controls when this transparent object will be rendered. Transparent objects are sorted by `(transparency_group, z-depth)`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                #[doc = "**Transparency group**: Controls when this transparent object will be rendered. Transparent objects are sorted by `(transparency_group, depth)`, drawing lower groups first and, within a group, the farthest objects first. The depth can be offset with `transparency_sort_bias`. If not attached, the group of its material is used.\n\n*Attributes*: Debuggable, Networked, Store"]
                 pub fn transparency_group() -> Component<i32> {
                     *TRANSPARENCY_GROUP
                 }
@@ -4279,6 +5872,606 @@ mod raw {
                 pub fn world_bounding_sphere_radius() -> Component<f32> {
                     *WORLD_BOUNDING_SPHERE_RADIUS
                 }
+                static POST_PROCESSING: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::post_processing")
+                });
+                #[doc = "**Post-processing**: The post-processing effects applied to the image of this camera, in order.\n\nThe supported effects are `ssao`, `ssr`, `bloom`, `tonemapping`, `color_grading` and `vignette`; `color_grading` expects colors between 0 and 1, so it should come after `tonemapping`. Their parameters are read from the other post-processing components of the camera.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn post_processing() -> Component<Vec<String>> {
+                    *POST_PROCESSING
+                }
+                static SSAO_RADIUS: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::ssao_radius"));
+                #[doc = "**SSAO radius**: The radius, in meters, within which geometry occludes ambient light when the `ssao` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn ssao_radius() -> Component<f32> {
+                    *SSAO_RADIUS
+                }
+                static SSAO_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::ssao_intensity")
+                });
+                #[doc = "**SSAO intensity**: How much the `ssao` post-processing effect darkens occluded areas, from 0 to 1.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn ssao_intensity() -> Component<f32> {
+                    *SSAO_INTENSITY
+                }
+                static BLOOM_THRESHOLD: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::bloom_threshold")
+                });
+                #[doc = "**Bloom threshold**: The luminance above which pixels bleed into their surroundings when the `bloom` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.8"]
+                pub fn bloom_threshold() -> Component<f32> {
+                    *BLOOM_THRESHOLD
+                }
+                static BLOOM_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::bloom_intensity")
+                });
+                #[doc = "**Bloom intensity**: How strongly bright pixels bleed into their surroundings when the `bloom` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn bloom_intensity() -> Component<f32> {
+                    *BLOOM_INTENSITY
+                }
+                static TONEMAPPING: Lazy<
+                    Component<crate::ambient_core::rendering::types::Tonemapping>,
+                > = Lazy::new(|| __internal_get_component("ambient_core::rendering::tonemapping"));
+                #[doc = "**Tonemapping**: The operator used to map the colors of the image when the `tonemapping` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn tonemapping() -> Component<crate::ambient_core::rendering::types::Tonemapping>
+                {
+                    *TONEMAPPING
+                }
+                static EXPOSURE: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::exposure"));
+                #[doc = "**Exposure**: The exposure, in stops, the image is brightened or darkened by before tonemapping when the `tonemapping` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn exposure() -> Component<f32> {
+                    *EXPOSURE
+                }
+                static VIGNETTE_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::vignette_intensity")
+                });
+                #[doc = "**Vignette intensity**: How much the `vignette` post-processing effect darkens the corners of the image, from 0 to 1.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.3"]
+                pub fn vignette_intensity() -> Component<f32> {
+                    *VIGNETTE_INTENSITY
+                }
+                static VIGNETTE_RADIUS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::vignette_radius")
+                });
+                #[doc = "**Vignette radius**: The distance from the center of the image, relative to its half diagonal, at which the `vignette` post-processing effect starts darkening it.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.75"]
+                pub fn vignette_radius() -> Component<f32> {
+                    *VIGNETTE_RADIUS
+                }
+                static PARTICLE_EMITTER: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_emitter")
+                });
+                #[doc = "**Particle emitter**: If attached, this entity emits particles from its position, simulated on the GPU.\n\nThe particles are spawned continuously at `particle_spawn_rate`, and in bursts with the `ParticleBurst` message.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn particle_emitter() -> Component<()> {
+                    *PARTICLE_EMITTER
+                }
+                static PARTICLE_SPAWN_RATE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_spawn_rate")
+                });
+                #[doc = "**Particle spawn rate**: The number of particles spawned per second by this emitter.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 10.0"]
+                pub fn particle_spawn_rate() -> Component<f32> {
+                    *PARTICLE_SPAWN_RATE
+                }
+                static PARTICLE_MAX_COUNT: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_max_count")
+                });
+                #[doc = "**Particle max count**: The maximum number of particles of this emitter alive at once. When it is reached, the oldest particles are replaced.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1000"]
+                pub fn particle_max_count() -> Component<u32> {
+                    *PARTICLE_MAX_COUNT
+                }
+                static PARTICLE_LIFETIME: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_lifetime")
+                });
+                #[doc = "**Particle lifetime**: The time, in seconds, a particle of this emitter lives for.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 2.0"]
+                pub fn particle_lifetime() -> Component<f32> {
+                    *PARTICLE_LIFETIME
+                }
+                static PARTICLE_VELOCITY: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_velocity")
+                });
+                #[doc = "**Particle velocity**: The velocity particles are spawned with, in the space of the emitter.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: [0.0, 0.0, 1.0]"]
+                pub fn particle_velocity() -> Component<Vec3> {
+                    *PARTICLE_VELOCITY
+                }
+                static PARTICLE_VELOCITY_SPREAD: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_velocity_spread")
+                });
+                #[doc = "**Particle velocity spread**: The maximum random velocity added to each spawned particle, in any direction.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn particle_velocity_spread() -> Component<f32> {
+                    *PARTICLE_VELOCITY_SPREAD
+                }
+                static PARTICLE_ACCELERATION: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_acceleration")
+                });
+                #[doc = "**Particle acceleration**: The acceleration applied to the particles, in world space. Use it for gravity or wind.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: [0.0, 0.0, 0.0]"]
+                pub fn particle_acceleration() -> Component<Vec3> {
+                    *PARTICLE_ACCELERATION
+                }
+                static PARTICLE_SPEED_OVER_LIFE: Lazy<Component<Vec<f32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_speed_over_life")
+                });
+                #[doc = "**Particle speed over life**: A curve scaling the speed of the particles over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn particle_speed_over_life() -> Component<Vec<f32>> {
+                    *PARTICLE_SPEED_OVER_LIFE
+                }
+                static PARTICLE_COLOR_OVER_LIFE: Lazy<Component<Vec<Vec4>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_color_over_life")
+                });
+                #[doc = "**Particle color over life**: A curve of the color of the particles over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn particle_color_over_life() -> Component<Vec<Vec4>> {
+                    *PARTICLE_COLOR_OVER_LIFE
+                }
+                static PARTICLE_SIZE_OVER_LIFE: Lazy<Component<Vec<f32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_size_over_life")
+                });
+                #[doc = "**Particle size over life**: A curve of the size of the particles, in meters, over their life. The keys are spread evenly from birth to death, and interpolated linearly. At most 8 keys are used.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn particle_size_over_life() -> Component<Vec<f32>> {
+                    *PARTICLE_SIZE_OVER_LIFE
+                }
+                static PARTICLE_COLLISION: Lazy<Component<bool>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_collision")
+                });
+                #[doc = "**Particle collision**: If true, the particles of this emitter bounce off the visible surfaces of the scene, using the depth buffer.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: false"]
+                pub fn particle_collision() -> Component<bool> {
+                    *PARTICLE_COLLISION
+                }
+                static PARTICLE_RESTITUTION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::particle_restitution")
+                });
+                #[doc = "**Particle restitution**: The fraction of their velocity the particles keep when they bounce off a surface.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn particle_restitution() -> Component<f32> {
+                    *PARTICLE_RESTITUTION
+                }
+                static POINT_LIGHT: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::point_light"));
+                #[doc = "**Point light**: If attached, this entity is a point light, which lights its surroundings in all directions from its world position.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn point_light() -> Component<()> {
+                    *POINT_LIGHT
+                }
+                static SPOT_LIGHT: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::spot_light"));
+                #[doc = "**Spot light**: If attached, this entity is a spot light, which lights a cone along its local Z axis from its world position.\n\nThe angle of the cone is set with `light_cone_angle`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn spot_light() -> Component<()> {
+                    *SPOT_LIGHT
+                }
+                static LIGHT_COLOR: Lazy<Component<Vec3>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::light_color"));
+                #[doc = "**Light color**: The color of a `point_light` or `spot_light`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: [1.0, 1.0, 1.0]"]
+                pub fn light_color() -> Component<Vec3> {
+                    *LIGHT_COLOR
+                }
+                static LIGHT_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::light_intensity")
+                });
+                #[doc = "**Light intensity**: The intensity of a `point_light` or `spot_light`, which scales its `light_color`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn light_intensity() -> Component<f32> {
+                    *LIGHT_INTENSITY
+                }
+                static LIGHT_RANGE: Lazy<Component<f32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::light_range"));
+                #[doc = "**Light range**: The distance, in meters, beyond which a `point_light` or `spot_light` has no effect. The light fades out smoothly towards it.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 10.0"]
+                pub fn light_range() -> Component<f32> {
+                    *LIGHT_RANGE
+                }
+                static LIGHT_CONE_ANGLE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::light_cone_angle")
+                });
+                #[doc = "**Light cone angle**: The angle, in radians, between the axis of a `spot_light` and the edge of its cone.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.7853982"]
+                pub fn light_cone_angle() -> Component<f32> {
+                    *LIGHT_CONE_ANGLE
+                }
+                static LIGHT_SHADOWS: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::light_shadows")
+                });
+                #[doc = "**Light shadows**: If attached to a `point_light` or `spot_light`, it will cast shadows.\n\nOnly the closest few lights to the camera with this component get shadow maps each frame.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn light_shadows() -> Component<()> {
+                    *LIGHT_SHADOWS
+                }
+                static LOD_GROUP: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::lod_group"));
+                #[doc = "**LOD group**: If attached, the children of this entity are its levels of detail, from the most detailed to the least.\n\nOnly the level matching the size of this entity on the screen is shown, as picked with `lod_screen_sizes`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn lod_group() -> Component<()> {
+                    *LOD_GROUP
+                }
+                static LOD_SCREEN_SIZES: Lazy<Component<Vec<f32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::lod_screen_sizes")
+                });
+                #[doc = "**LOD screen sizes**: The minimum screen size of each level of a `lod_group`, from the most detailed level to the least.\n\nThe screen size is the fraction of the height of the screen covered by the bounding sphere of the group. Below the last size, no level is shown, which culls small objects.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn lod_screen_sizes() -> Component<Vec<f32>> {
+                    *LOD_SCREEN_SIZES
+                }
+                static LOD_CROSSFADE_DURATION: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::lod_crossfade_duration")
+                });
+                #[doc = "**LOD crossfade duration**: The time, in seconds, over which a `lod_group` crossfades between two levels of detail. If zero, the levels are switched instantly.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn lod_crossfade_duration() -> Component<f32> {
+                    *LOD_CROSSFADE_DURATION
+                }
+                static LOD_CULL_SCREEN_SIZE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::lod_cull_screen_size")
+                });
+                #[doc = "**LOD cull screen size**: If attached, this entity is not rendered by the main camera when the bounding sphere covers less than this fraction of the height of the screen.\n\nThis is cheaper than a `lod_group` for culling small objects with a single level of detail.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn lod_cull_screen_size() -> Component<f32> {
+                    *LOD_CULL_SCREEN_SIZE
+                }
+                static TERRAIN: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::terrain"));
+                #[doc = "**Terrain**: If attached along with `terrain_heights` and `terrain_resolution`, this entity is rendered as a heightmap terrain, and gets a matching heightfield collider.\n\nThe terrain covers the samples from the local origin along +X and +Y, with more detail close to the camera. Use `scale` to change the spacing between samples and the height.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn terrain() -> Component<()> {
+                    *TERRAIN
+                }
+                static TERRAIN_HEIGHTS: Lazy<Component<Vec<f32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::terrain_heights")
+                });
+                #[doc = "**Terrain heights**: The height of each sample of a `terrain`, row by row: the sample at `(x, y)` is at index `y * resolution.x + x`, and is placed at `(x, y, height)` in the entity's local space.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn terrain_heights() -> Component<Vec<f32>> {
+                    *TERRAIN_HEIGHTS
+                }
+                static TERRAIN_RESOLUTION: Lazy<Component<UVec2>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::terrain_resolution")
+                });
+                #[doc = "**Terrain resolution**: The number of samples along the X and Y axes of a `terrain`. Each axis needs at least 2 samples.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn terrain_resolution() -> Component<UVec2> {
+                    *TERRAIN_RESOLUTION
+                }
+                static TERRAIN_SPLATMAP: Lazy<Component<Vec<u32>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::terrain_splatmap")
+                });
+                #[doc = "**Terrain splatmap**: The weights of the four material layers of a `terrain` at each sample, in the same order as `terrain_heights`. Each weight is a byte, with the first layer in the lowest byte; the layers are blended in proportion to their weights.\n\nIf not attached, the first layer covers the whole terrain.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn terrain_splatmap() -> Component<Vec<u32>> {
+                    *TERRAIN_SPLATMAP
+                }
+                static TERRAIN_LAYER_COLORS: Lazy<Component<Vec<Vec3>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::terrain_layer_colors")
+                });
+                #[doc = "**Terrain layer colors**: The color of each of the four material layers of a `terrain`, multiplied with its texture. Layers without a color are white.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn terrain_layer_colors() -> Component<Vec<Vec3>> {
+                    *TERRAIN_LAYER_COLORS
+                }
+                static TERRAIN_LAYER_TEXTURES: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::terrain_layer_textures")
+                });
+                #[doc = "**Terrain layer textures**: The URL of the base color texture of each of the four material layers of a `terrain`. Layers without a texture, or with an empty URL, only use their color.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn terrain_layer_textures() -> Component<Vec<String>> {
+                    *TERRAIN_LAYER_TEXTURES
+                }
+                static TERRAIN_LAYER_SCALE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::terrain_layer_scale")
+                });
+                #[doc = "**Terrain layer scale**: The size, in meters, of one repetition of the layer textures of a `terrain`.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 4.0"]
+                pub fn terrain_layer_scale() -> Component<f32> {
+                    *TERRAIN_LAYER_SCALE
+                }
+                static OUTLINE_THICKNESS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::outline_thickness")
+                });
+                #[doc = "**Outline thickness**: The thickness, in pixels, of the `outline` of this entity, between 1 and 255. Defaults to 3.\n\nWhen attached along with `outline_recursive`, it also applies to all of the children of this entity.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 3.0"]
+                pub fn outline_thickness() -> Component<f32> {
+                    *OUTLINE_THICKNESS
+                }
+                static SSR_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::ssr_intensity")
+                });
+                #[doc = "**SSR intensity**: How strongly surfaces reflect their surroundings when the `ssr` post-processing effect is applied, from 0 to 1.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn ssr_intensity() -> Component<f32> {
+                    *SSR_INTENSITY
+                }
+                static SSR_MAX_DISTANCE: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::ssr_max_distance")
+                });
+                #[doc = "**SSR max distance**: How far, in meters, reflected rays are followed when the `ssr` post-processing effect is applied.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 20.0"]
+                pub fn ssr_max_distance() -> Component<f32> {
+                    *SSR_MAX_DISTANCE
+                }
+                static PLANAR_REFLECTION: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::planar_reflection")
+                });
+                #[doc = "**Planar reflection**: Makes this entity a planar reflection probe: the scene is rendered mirrored across the XY plane of this entity, and reflective surfaces lying on that plane, like water and mirrors, reflect that image instead of what is visible on screen.\n\nOnly the closest probe facing the camera is rendered.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn planar_reflection() -> Component<()> {
+                    *PLANAR_REFLECTION
+                }
+                static TEXTURE_STREAMING_PRIORITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::texture_streaming_priority")
+                });
+                #[doc = "**Texture streaming priority**: How important the textures of this entity are when the streamed textures exceed their GPU memory budget: the textures of the entities with the highest priority, scaled by how large they are on the screen, are given the most detail first. Defaults to 1.\n\nAt 0, its textures stay at their lowest resolution.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn texture_streaming_priority() -> Component<f32> {
+                    *TEXTURE_STREAMING_PRIORITY
+                }
+                static SPRITE: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::sprite"));
+                #[doc = "**Sprite**: If attached, this entity is rendered as a sprite: a flat quad showing the texture at this URL, or the image of a texture atlas at this URL (like `sprites.atlas.json#coin`), blended with what is behind it.\n\nThe quad is centered on the entity, and sized from `sprite_region` and `sprite_pixels_per_unit`. It faces the camera as set by `sprite_billboard`, and is tinted by `color`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn sprite() -> Component<String> {
+                    *SPRITE
+                }
+                static SPRITE_REGION: Lazy<Component<Vec4>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::sprite_region")
+                });
+                #[doc = "**Sprite region**: The region of the texture of the `sprite` this entity shows, as the x and y of its top left corner followed by its width and height, in pixels.\n\nUse it to pick one image out of an atlas. If not attached, the whole texture, or the image of the texture atlas, is shown.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn sprite_region() -> Component<Vec4> {
+                    *SPRITE_REGION
+                }
+                static SPRITE_PIXELS_PER_UNIT: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::sprite_pixels_per_unit")
+                });
+                #[doc = "**Sprite pixels per unit**: How many pixels of the texture of the `sprite` cover one meter of its quad.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 100.0"]
+                pub fn sprite_pixels_per_unit() -> Component<f32> {
+                    *SPRITE_PIXELS_PER_UNIT
+                }
+                static SPRITE_BILLBOARD: Lazy<
+                    Component<crate::ambient_core::rendering::types::SpriteBillboard>,
+                > = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::sprite_billboard")
+                });
+                #[doc = "**Sprite billboard**: How the quad of the `sprite` of this entity turns to face the camera. If not attached, it fully faces the camera.\n\n*Attributes*: Debuggable, Networked, Store, Enum"]
+                pub fn sprite_billboard(
+                ) -> Component<crate::ambient_core::rendering::types::SpriteBillboard>
+                {
+                    *SPRITE_BILLBOARD
+                }
+                static SUN_SHADOWS: Lazy<Component<bool>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::rendering::sun_shadows"));
+                #[doc = "**Sun shadows**: If attached to a `sun`, controls whether it casts shadows. Turning them off skips rendering the shadow cascades, which is cheaper. If not attached, the sun casts shadows.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn sun_shadows() -> Component<bool> {
+                    *SUN_SHADOWS
+                }
+                static ENVIRONMENT_MAP_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::environment_map_from_url")
+                });
+                #[doc = "**Environment map from URL**: If attached, the scene is lit by the environment map at this URL: an HDR image of its surroundings, prefiltered by the `EnvironmentMaps` asset pipeline. It gives the scene diffuse and specular image-based lighting in place of the `light_ambient` of the sun, and is shown as the sky by the `sky` of the scene.\n\nIf several entities have one, the first found is used.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn environment_map_from_url() -> Component<String> {
+                    *ENVIRONMENT_MAP_FROM_URL
+                }
+                static ENVIRONMENT_MAP_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::environment_map_intensity")
+                });
+                #[doc = "**Environment map intensity**: How strongly the `environment_map_from_url` of this entity lights the scene, and how bright it is as the sky.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn environment_map_intensity() -> Component<f32> {
+                    *ENVIRONMENT_MAP_INTENSITY
+                }
+                static WIND_VELOCITY: Lazy<Component<Vec3>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::wind_velocity")
+                });
+                #[doc = "**Wind velocity**: The direction and speed of the wind blowing over the scene of this entity, in meters per second. Materials with wind parameters, like foliage, sway with it. Only one wind is used per scene; without one, a gentle breeze of 1 m/s blows along +X.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn wind_velocity() -> Component<Vec3> {
+                    *WIND_VELOCITY
+                }
+                static WIND_GUST_STRENGTH: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::wind_gust_strength")
+                });
+                #[doc = "**Wind gust strength**: If attached next to `wind_velocity`, how much the wind varies in gusts travelling across the scene, from 0 (a steady wind) to 1. Defaults to 0.5 if not attached.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.5"]
+                pub fn wind_gust_strength() -> Component<f32> {
+                    *WIND_GUST_STRENGTH
+                }
+                static COLOR_GRADING_LUT_FROM_URL: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::color_grading_lut_from_url")
+                });
+                #[doc = "**Color grading LUT from URL**: The 3D lookup table the `color_grading` post-processing effect of this camera remaps colors with, as an image of N slices of N x N pixels side by side (N² pixels wide and N pixels high). Red increases to the right within each slice, green downwards, and blue from one slice to the next.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn color_grading_lut_from_url() -> Component<String> {
+                    *COLOR_GRADING_LUT_FROM_URL
+                }
+                static COLOR_GRADING_LUT_BLEND_FROM_URL: Lazy<Component<String>> =
+                    Lazy::new(|| {
+                        __internal_get_component(
+                            "ambient_core::rendering::color_grading_lut_blend_from_url",
+                        )
+                    });
+                #[doc = "**Color grading LUT blend from URL**: A second lookup table for the `color_grading` post-processing effect of this camera, laid out like `color_grading_lut_from_url`. The colors are remapped with a mix of both tables, set by `color_grading_lut_blend`.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn color_grading_lut_blend_from_url() -> Component<String> {
+                    *COLOR_GRADING_LUT_BLEND_FROM_URL
+                }
+                static COLOR_GRADING_LUT_BLEND: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::color_grading_lut_blend")
+                });
+                #[doc = "**Color grading LUT blend**: How much of the `color_grading_lut_blend_from_url` table of this camera is used, from 0 (only `color_grading_lut_from_url`) to 1 (only `color_grading_lut_blend_from_url`). Animate it to transition between moods.\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 0.0"]
+                pub fn color_grading_lut_blend() -> Component<f32> {
+                    *COLOR_GRADING_LUT_BLEND
+                }
+                static COLOR_GRADING_INTENSITY: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::color_grading_intensity")
+                });
+                #[doc = "**Color grading intensity**: How strongly the `color_grading` post-processing effect of this camera is applied, from 0 (the original colors) to 1 (the colors of the lookup tables).\n\n*Attributes*: Debuggable, Networked, Store\n\n*Suggested Default*: 1.0"]
+                pub fn color_grading_intensity() -> Component<f32> {
+                    *COLOR_GRADING_INTENSITY
+                }
+                static TRANSPARENCY_SORT_BIAS: Lazy<Component<f32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::rendering::transparency_sort_bias")
+                });
+                #[doc = "**Transparency sort bias**: Moves this transparent object towards the camera by this many meters when sorting it against the other transparent objects of its `transparency_group`, so that it is drawn over them. Negative values move it away. Use it to keep layered effects, like particles inside glass, in a stable order from every angle. If not attached, the bias of its material is used.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn transparency_sort_bias() -> Component<f32> {
+                    *TRANSPARENCY_SORT_BIAS
+                }
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{
+                    message::{
+                        Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                        RuntimeMessage,
+                    },
+                    prelude::*,
+                };
+                #[derive(Clone, Debug)]
+                #[doc = "**ParticleBurst**: Sent by a client module: spawns `count` particles at once from the particle emitter `emitter`, for explosions and impacts."]
+                pub struct ParticleBurst {
+                    pub emitter: EntityId,
+                    pub count: u32,
+                }
+                impl ParticleBurst {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(emitter: impl Into<EntityId>, count: impl Into<u32>) -> Self {
+                        Self {
+                            emitter: emitter.into(),
+                            count: count.into(),
+                        }
+                    }
+                }
+                impl Message for ParticleBurst {
+                    fn id() -> &'static str {
+                        "ambient_core::rendering::ParticleBurst"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.emitter.serialize_message_part(&mut output)?;
+                        self.count.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            emitter: EntityId::deserialize_message_part(&mut input)?,
+                            count: u32::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for ParticleBurst {}
+            }
+            #[doc = r" Auto-generated type definitions."]
+            pub mod types {
+                use crate::{global::serde, message::*};
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**Tonemapping**: An operator mapping the colors of the rendered image to the colors of the screen."]
+                pub enum Tonemapping {
+                    #[default]
+                    #[doc = "The ACES filmic curve, which keeps contrast and desaturates highlights."]
+                    Aces,
+                    #[doc = "The Reinhard curve, which compresses highlights smoothly."]
+                    Reinhard,
+                    #[doc = "Only applies the exposure, clamping the colors."]
+                    Linear,
+                }
+                impl crate::ecs::EnumComponent for Tonemapping {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Aces => Tonemapping::Aces as u32,
+                            Self::Reinhard => Tonemapping::Reinhard as u32,
+                            Self::Linear => Tonemapping::Linear as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == Tonemapping::Aces as u32 {
+                            return Some(Self::Aces);
+                        }
+                        if value == Tonemapping::Reinhard as u32 {
+                            return Some(Self::Reinhard);
+                        }
+                        if value == Tonemapping::Linear as u32 {
+                            return Some(Self::Linear);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for Tonemapping {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for Tonemapping {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**SpriteBillboard**: How a sprite turns to face the camera."]
+                pub enum SpriteBillboard {
+                    #[default]
+                    #[doc = "The sprite always fully faces the camera, like a health bar."]
+                    Spherical,
+                    #[doc = "The sprite only turns around the world Z axis, staying upright, like a tree."]
+                    Cylindrical,
+                    #[doc = "The sprite does not turn, and lies on the XY plane of the entity."]
+                    Fixed,
+                }
+                impl crate::ecs::EnumComponent for SpriteBillboard {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Spherical => SpriteBillboard::Spherical as u32,
+                            Self::Cylindrical => SpriteBillboard::Cylindrical as u32,
+                            Self::Fixed => SpriteBillboard::Fixed as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == SpriteBillboard::Spherical as u32 {
+                            return Some(Self::Spherical);
+                        }
+                        if value == SpriteBillboard::Cylindrical as u32 {
+                            return Some(Self::Cylindrical);
+                        }
+                        if value == SpriteBillboard::Fixed as u32 {
+                            return Some(Self::Fixed);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for SpriteBillboard {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for SpriteBillboard {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod text {
@@ -4313,6 +6506,50 @@ mod raw {
                 pub fn text() -> Component<String> {
                     *TEXT
                 }
+                static LOCALE: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::text::locale"));
+                #[doc = "**Locale**: The locale that text is translated to, like `en` or `fr-CA`. The runtime sets it to the locale of the system; set it to switch the locale at runtime.\n\n*Attributes*: Debuggable, Resource"]
+                pub fn locale() -> Component<String> {
+                    *LOCALE
+                }
+                static STRING_TABLES_URL: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::text::string_tables_url"));
+                #[doc = "**String tables URL**: Loads the string tables at this URL, as written by the `StringTables` asset pipeline, and uses their strings to translate text.\n\n*Attributes*: Debuggable"]
+                pub fn string_tables_url() -> Component<String> {
+                    *STRING_TABLES_URL
+                }
+                static TRANSLATION_ARGS: Lazy<Component<Vec<String>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::text::translation_args"));
+                #[doc = "**Translation arguments**: The arguments of the `translation_key` string, as `name=value` pairs. A `count` argument also picks the plural form of the string.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn translation_args() -> Component<Vec<String>> {
+                    *TRANSLATION_ARGS
+                }
+                static TRANSLATION_KEY: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::text::translation_key"));
+                #[doc = "**Translation key**: Sets `text` to the string with this key in the current locale, and updates it when the locale changes.\n\n*Attributes*: Debuggable, Networked, Store"]
+                pub fn translation_key() -> Component<String> {
+                    *TRANSLATION_KEY
+                }
+                static TRANSLATION_KEYS: Lazy<Component<Vec<String>>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::text::translation_keys"));
+                #[doc = "**Translation keys**: The keys of the strings of the current locale, from all the loaded string tables. The string of each key is at the same index of `translation_strings`.\n\n*Attributes*: Debuggable, Resource"]
+                pub fn translation_keys() -> Component<Vec<String>> {
+                    *TRANSLATION_KEYS
+                }
+                static TRANSLATION_REVISION: Lazy<Component<u32>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::text::translation_revision")
+                });
+                #[doc = "**Translation revision**: Changes whenever the strings of the current locale do, so translations can be cached.\n\n*Attributes*: Debuggable, Resource"]
+                pub fn translation_revision() -> Component<u32> {
+                    *TRANSLATION_REVISION
+                }
+                static TRANSLATION_STRINGS: Lazy<Component<Vec<String>>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::text::translation_strings")
+                });
+                #[doc = "**Translation strings**: The strings of the current locale, for each of the `translation_keys`.\n\n*Attributes*: Debuggable, Resource"]
+                pub fn translation_strings() -> Component<Vec<String>> {
+                    *TRANSLATION_STRINGS
+                }
             }
             #[doc = r" Auto-generated type definitions."]
             pub mod types {
@@ -4653,76 +6890,479 @@ mod raw {
                         required: <Self::Required as ComponentsTuple>::Data,
                     ) -> Self {
                         Self {
-                            local_to_world: required.0,
-                            optional: Default::default(),
+                            local_to_world: required.0,
+                            optional: Default::default(),
+                        }
+                    }
+                }
+            }
+        }
+        pub mod ui {
+            #[doc = r" Auto-generated component definitions."]
+            pub mod components {
+                use crate::{
+                    ecs::{Component, __internal_get_component},
+                    once_cell::sync::Lazy,
+                    prelude::*,
+                };
+                static FOCUS: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::focus"));
+                #[doc = "**Focus**: Currently focused object.\n\n*Attributes*: Debuggable, Networked, Resource"]
+                pub fn focus() -> Component<String> {
+                    *FOCUS
+                }
+                static FOCUSABLE: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::focusable"));
+                #[doc = "**Focus**: This entity can be focused. The value is the focus id.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn focusable() -> Component<String> {
+                    *FOCUSABLE
+                }
+                static FOCUS_ORDER: Lazy<Component<i32>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::focus_order"));
+                #[doc = "**Focus order**: The position of this focusable entity in the order the focus moves in with Tab; lower values come first.\n\nEntities with the same order, or without one, are ordered by their position on the screen: top to bottom, then left to right.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn focus_order() -> Component<i32> {
+                    *FOCUS_ORDER
+                }
+                static FOCUS_CAPTURES_NAVIGATION: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::ui::focus_captures_navigation")
+                });
+                #[doc = "**Focus captures navigation**: While this entity is focused, directional `FocusNavigate` messages and the activation keys don't move the focus or send `FocusActivated`.\n\nThe entity can handle the `FocusNavigate` messages itself, for example to move a cursor. Tab still moves the focus.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn focus_captures_navigation() -> Component<()> {
+                    *FOCUS_CAPTURES_NAVIGATION
+                }
+                static FOCUS_VISIBLE: Lazy<Component<bool>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::focus_visible"));
+                #[doc = "**Focus visible**: Whether the focus was moved with `FocusNavigate`, rather than by clicking.\n\nFocused elements only show that they are focused, and are only activated by the activation keys, when it was.\n\n*Attributes*: Debuggable, Networked, Resource"]
+                pub fn focus_visible() -> Component<bool> {
+                    *FOCUS_VISIBLE
+                }
+                static ACCESSIBILITY_ROLE: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::accessibility_role"));
+                #[doc = "**Accessibility role**: The role of this entity for assistive technology, like screen readers: `alert`, `button`, `checkbox`, `dialog`, `group`, `heading`, `image`, `link`, `list`, `list_item`, `menu`, `menu_item`, `progress_bar`, `radio_button`, `slider`, `status`, `tab`, `tab_list`, `text` or `text_input`. Entities with a role, and entities with `text`, are part of the accessibility tree.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn accessibility_role() -> Component<String> {
+                    *ACCESSIBILITY_ROLE
+                }
+                static ACCESSIBILITY_LABEL: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::accessibility_label"));
+                #[doc = "**Accessibility label**: The name that assistive technology reads for this entity. Defaults to its `text`, or to the text of its children for buttons and other roles that are named by their content.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn accessibility_label() -> Component<String> {
+                    *ACCESSIBILITY_LABEL
+                }
+                static ACCESSIBILITY_DESCRIPTION: Lazy<Component<String>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::ui::accessibility_description")
+                });
+                #[doc = "**Accessibility description**: A longer description that assistive technology reads after the `accessibility_label` of this entity, like the tooltip of a button.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn accessibility_description() -> Component<String> {
+                    *ACCESSIBILITY_DESCRIPTION
+                }
+                static ACCESSIBILITY_VALUE: Lazy<Component<String>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::accessibility_value"));
+                #[doc = "**Accessibility value**: The value that assistive technology reads for this entity, like the text of a text input or the position of a slider. Checkboxes and radio buttons are checked when it is `true`.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn accessibility_value() -> Component<String> {
+                    *ACCESSIBILITY_VALUE
+                }
+                static ACCESSIBILITY_HIDDEN: Lazy<Component<()>> = Lazy::new(|| {
+                    __internal_get_component("ambient_core::ui::accessibility_hidden")
+                });
+                #[doc = "**Accessibility hidden**: Hides this entity and its children from assistive technology, for decorations and other elements that aren't meaningful.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn accessibility_hidden() -> Component<()> {
+                    *ACCESSIBILITY_HIDDEN
+                }
+                static ACCESSIBILITY_LIVE: Lazy<Component<()>> =
+                    Lazy::new(|| __internal_get_component("ambient_core::ui::accessibility_live"));
+                #[doc = "**Accessibility live**: Assistive technology announces the changes of this entity and its children, without it being focused. Use it for status and HUD text, like a score or a timer.\n\n*Attributes*: Debuggable, Networked"]
+                pub fn accessibility_live() -> Component<()> {
+                    *ACCESSIBILITY_LIVE
+                }
+            }
+            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
+            #[doc = r" and with other modules."]
+            pub mod messages {
+                use crate::{
+                    message::{
+                        Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                        RuntimeMessage,
+                    },
+                    prelude::*,
+                };
+                #[derive(Clone, Debug)]
+                #[doc = "**FocusChanged**: Focus has been updated"]
+                pub struct FocusChanged {
+                    pub from_external: bool,
+                    pub focus: String,
+                }
+                impl FocusChanged {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(from_external: impl Into<bool>, focus: impl Into<String>) -> Self {
+                        Self {
+                            from_external: from_external.into(),
+                            focus: focus.into(),
+                        }
+                    }
+                }
+                impl Message for FocusChanged {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::FocusChanged"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.from_external.serialize_message_part(&mut output)?;
+                        self.focus.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            from_external: bool::deserialize_message_part(&mut input)?,
+                            focus: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for FocusChanged {}
+                #[derive(Clone, Debug)]
+                #[doc = "**FocusNavigate**: Moves the focus: to the nearest focusable entity in `direction` on the screen, where +y is down, or by `step` entities in the focus order if `direction` is zero. Sent by the runtime for the Tab and arrow keys; packages can send it to navigate with other inputs, like gamepads."]
+                pub struct FocusNavigate {
+                    pub direction: Vec2,
+                    pub step: i32,
+                }
+                impl FocusNavigate {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(direction: impl Into<Vec2>, step: impl Into<i32>) -> Self {
+                        Self {
+                            direction: direction.into(),
+                            step: step.into(),
+                        }
+                    }
+                }
+                impl Message for FocusNavigate {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::FocusNavigate"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.direction.serialize_message_part(&mut output)?;
+                        self.step.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            direction: Vec2::deserialize_message_part(&mut input)?,
+                            step: i32::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for FocusNavigate {}
+                #[derive(Clone, Debug)]
+                #[doc = "**FocusActivated**: The focused entity was activated, like a click. Sent by the runtime for the Enter and Space keys; packages can send it to activate with other inputs, like gamepads."]
+                pub struct FocusActivated {
+                    pub focus: String,
+                }
+                impl FocusActivated {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(focus: impl Into<String>) -> Self {
+                        Self {
+                            focus: focus.into(),
+                        }
+                    }
+                }
+                impl Message for FocusActivated {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::FocusActivated"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.focus.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            focus: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for FocusActivated {}
+                #[derive(Clone, Debug)]
+                #[doc = "**NotificationShow**: Shows a notification in the corner of the screen. Notifications with a higher `priority` are shown first, and each is hidden after `timeout` seconds, or when dismissed if it's zero. A notification with the same non-empty `id` as a shown one replaces it. Each of the `actions` is a button, which sends `NotificationAction` and dismisses the notification. Sent by the runtime for errors of modules; packages can send it to notify the player."]
+                pub struct NotificationShow {
+                    pub id: String,
+                    pub title: String,
+                    pub body: String,
+                    pub level: crate::ambient_core::ui::types::NotificationLevel,
+                    pub priority: i32,
+                    pub timeout: f32,
+                    pub actions: Vec<String>,
+                }
+                impl NotificationShow {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        id: impl Into<String>,
+                        title: impl Into<String>,
+                        body: impl Into<String>,
+                        level: impl Into<crate::ambient_core::ui::types::NotificationLevel>,
+                        priority: impl Into<i32>,
+                        timeout: impl Into<f32>,
+                        actions: impl Into<Vec<String>>,
+                    ) -> Self {
+                        Self {
+                            id: id.into(),
+                            title: title.into(),
+                            body: body.into(),
+                            level: level.into(),
+                            priority: priority.into(),
+                            timeout: timeout.into(),
+                            actions: actions.into(),
+                        }
+                    }
+                }
+                impl Message for NotificationShow {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::NotificationShow"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.title.serialize_message_part(&mut output)?;
+                        self.body.serialize_message_part(&mut output)?;
+                        self.level.serialize_message_part(&mut output)?;
+                        self.priority.serialize_message_part(&mut output)?;
+                        self.timeout.serialize_message_part(&mut output)?;
+                        self.actions.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            title: String::deserialize_message_part(&mut input)?,
+                            body: String::deserialize_message_part(&mut input)?,
+                            level: crate::ambient_core::ui::types::NotificationLevel::deserialize_message_part(&mut input)?,
+                            priority: i32::deserialize_message_part(&mut input)?,
+                            timeout: f32::deserialize_message_part(&mut input)?,
+                            actions: Vec::<String>::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for NotificationShow {}
+                #[derive(Clone, Debug)]
+                #[doc = "**NotificationDismiss**: Hides the notification with `id`."]
+                pub struct NotificationDismiss {
+                    pub id: String,
+                }
+                impl NotificationDismiss {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(id: impl Into<String>) -> Self {
+                        Self { id: id.into() }
+                    }
+                }
+                impl Message for NotificationDismiss {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::NotificationDismiss"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for NotificationDismiss {}
+                #[derive(Clone, Debug)]
+                #[doc = "**NotificationAction**: One of the `actions` of the notification with `id` was clicked. Sent by the runtime."]
+                pub struct NotificationAction {
+                    pub id: String,
+                    pub action: String,
+                }
+                impl NotificationAction {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(id: impl Into<String>, action: impl Into<String>) -> Self {
+                        Self {
+                            id: id.into(),
+                            action: action.into(),
+                        }
+                    }
+                }
+                impl Message for NotificationAction {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::NotificationAction"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.action.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            action: String::deserialize_message_part(&mut input)?,
+                        })
+                    }
+                }
+                impl ModuleMessage for NotificationAction {}
+                #[derive(Clone, Debug)]
+                #[doc = "**ModalShow**: Shows a modal dialog, which blocks the UI until one of its `actions` is clicked, which sends `ModalResponse`. Dialogs are shown one at a time, in the order they were sent, and one without actions has an `Ok` action."]
+                pub struct ModalShow {
+                    pub id: String,
+                    pub title: String,
+                    pub body: String,
+                    pub actions: Vec<String>,
+                }
+                impl ModalShow {
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn new(
+                        id: impl Into<String>,
+                        title: impl Into<String>,
+                        body: impl Into<String>,
+                        actions: impl Into<Vec<String>>,
+                    ) -> Self {
+                        Self {
+                            id: id.into(),
+                            title: title.into(),
+                            body: body.into(),
+                            actions: actions.into(),
                         }
                     }
                 }
-            }
-        }
-        pub mod ui {
-            #[doc = r" Auto-generated component definitions."]
-            pub mod components {
-                use crate::{
-                    ecs::{Component, __internal_get_component},
-                    once_cell::sync::Lazy,
-                    prelude::*,
-                };
-                static FOCUS: Lazy<Component<String>> =
-                    Lazy::new(|| __internal_get_component("ambient_core::ui::focus"));
-                #[doc = "**Focus**: Currently focused object.\n\n*Attributes*: Debuggable, Networked, Resource"]
-                pub fn focus() -> Component<String> {
-                    *FOCUS
-                }
-                static FOCUSABLE: Lazy<Component<String>> =
-                    Lazy::new(|| __internal_get_component("ambient_core::ui::focusable"));
-                #[doc = "**Focus**: This entity can be focused. The value is the focus id.\n\n*Attributes*: Debuggable, Networked"]
-                pub fn focusable() -> Component<String> {
-                    *FOCUSABLE
+                impl Message for ModalShow {
+                    fn id() -> &'static str {
+                        "ambient_core::ui::ModalShow"
+                    }
+                    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                        let mut output = vec![];
+                        self.id.serialize_message_part(&mut output)?;
+                        self.title.serialize_message_part(&mut output)?;
+                        self.body.serialize_message_part(&mut output)?;
+                        self.actions.serialize_message_part(&mut output)?;
+                        Ok(output)
+                    }
+                    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                        Ok(Self {
+                            id: String::deserialize_message_part(&mut input)?,
+                            title: String::deserialize_message_part(&mut input)?,
+                            body: String::deserialize_message_part(&mut input)?,
+                            actions: Vec::<String>::deserialize_message_part(&mut input)?,
+                        })
+                    }
                 }
-            }
-            #[doc = r" Auto-generated message definitions. Messages are used to communicate with the runtime, the other side of the network,"]
-            #[doc = r" and with other modules."]
-            pub mod messages {
-                use crate::{
-                    message::{
-                        Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
-                    },
-                    prelude::*,
-                };
+                impl ModuleMessage for ModalShow {}
                 #[derive(Clone, Debug)]
-                #[doc = "**FocusChanged**: Focus has been updated"]
-                pub struct FocusChanged {
-                    pub from_external: bool,
-                    pub focus: String,
+                #[doc = "**ModalResponse**: One of the `actions` of the modal dialog with `id` was clicked. Sent by the runtime."]
+                pub struct ModalResponse {
+                    pub id: String,
+                    pub action: String,
                 }
-                impl FocusChanged {
+                impl ModalResponse {
                     #[allow(clippy::too_many_arguments)]
-                    pub fn new(from_external: impl Into<bool>, focus: impl Into<String>) -> Self {
+                    pub fn new(id: impl Into<String>, action: impl Into<String>) -> Self {
                         Self {
-                            from_external: from_external.into(),
-                            focus: focus.into(),
+                            id: id.into(),
+                            action: action.into(),
                         }
                     }
                 }
-                impl Message for FocusChanged {
+                impl Message for ModalResponse {
                     fn id() -> &'static str {
-                        "ambient_core::ui::FocusChanged"
+                        "ambient_core::ui::ModalResponse"
                     }
                     fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
                         let mut output = vec![];
-                        self.from_external.serialize_message_part(&mut output)?;
-                        self.focus.serialize_message_part(&mut output)?;
+                        self.id.serialize_message_part(&mut output)?;
+                        self.action.serialize_message_part(&mut output)?;
                         Ok(output)
                     }
                     fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
                         Ok(Self {
-                            from_external: bool::deserialize_message_part(&mut input)?,
-                            focus: String::deserialize_message_part(&mut input)?,
+                            id: String::deserialize_message_part(&mut input)?,
+                            action: String::deserialize_message_part(&mut input)?,
                         })
                     }
                 }
-                impl ModuleMessage for FocusChanged {}
+                impl ModuleMessage for ModalResponse {}
+            }
+            pub mod types {
+                use crate::{global::serde, message::*};
+                #[derive(
+                    Copy,
+                    Clone,
+                    Debug,
+                    PartialEq,
+                    Eq,
+                    serde :: Serialize,
+                    serde :: Deserialize,
+                    Default,
+                )]
+                #[serde(crate = "self::serde")]
+                #[doc = "**NotificationLevel**: How important a notification is, which determines its color."]
+                pub enum NotificationLevel {
+                    #[default]
+                    #[doc = "Information, like a hint or a status change."]
+                    Info,
+                    #[doc = "Something finished successfully."]
+                    Success,
+                    #[doc = "Something might be wrong."]
+                    Warning,
+                    #[doc = "Something failed."]
+                    Error,
+                }
+                impl crate::ecs::EnumComponent for NotificationLevel {
+                    fn to_u32(&self) -> u32 {
+                        match self {
+                            Self::Info => NotificationLevel::Info as u32,
+                            Self::Success => NotificationLevel::Success as u32,
+                            Self::Warning => NotificationLevel::Warning as u32,
+                            Self::Error => NotificationLevel::Error as u32,
+                        }
+                    }
+                    fn from_u32(value: u32) -> Option<Self> {
+                        if value == NotificationLevel::Info as u32 {
+                            return Some(Self::Info);
+                        }
+                        if value == NotificationLevel::Success as u32 {
+                            return Some(Self::Success);
+                        }
+                        if value == NotificationLevel::Warning as u32 {
+                            return Some(Self::Warning);
+                        }
+                        if value == NotificationLevel::Error as u32 {
+                            return Some(Self::Error);
+                        }
+                        None
+                    }
+                }
+                impl crate::ecs::SupportedValue for NotificationLevel {
+                    fn from_result(result: crate::ecs::WitComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_result(result).and_then(Self::from_u32)
+                    }
+                    fn into_result(self) -> crate::ecs::WitComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_result()
+                    }
+                    fn from_value(value: crate::ecs::ComponentValue) -> Option<Self> {
+                        use crate::ecs::EnumComponent;
+                        u32::from_value(value).and_then(Self::from_u32)
+                    }
+                    fn into_value(self) -> crate::ecs::ComponentValue {
+                        use crate::ecs::EnumComponent;
+                        self.to_u32().into_value()
+                    }
+                }
+                impl MessageSerde for NotificationLevel {
+                    fn serialize_message_part(
+                        &self,
+                        output: &mut Vec<u8>,
+                    ) -> Result<(), MessageSerdeError> {
+                        crate::ecs::EnumComponent::to_u32(self).serialize_message_part(output)
+                    }
+                    fn deserialize_message_part(
+                        input: &mut dyn std::io::Read,
+                    ) -> Result<Self, MessageSerdeError> {
+                        crate::ecs::EnumComponent::from_u32(u32::deserialize_message_part(input)?)
+                            .ok_or(MessageSerdeError::InvalidValue)
+                    }
+                }
             }
         }
         pub mod wasm {
@@ -4946,7 +7586,8 @@ mod raw {
         pub mod messages {
             use crate::{
                 message::{
-                    Message, MessageSerde, MessageSerdeError, ModuleMessage, RuntimeMessage,
+                    Message, MessageSerde, MessageSerdeError, ModuleMessage, Reliability,
+                    RuntimeMessage,
                 },
                 prelude::*,
             };
@@ -5018,6 +7659,170 @@ mod raw {
             }
             impl RuntimeMessage for Collision {}
             #[derive(Clone, Debug)]
+            #[doc = "**ContactFilter**: Sent to the packages with `contact_filter` for the pairs of touching entities that haven't been decided yet, where at least one has `contact_filtered`. `first[i]` and `second[i]` are a pair; answer with `physics::set_contact_filter`."]
+            pub struct ContactFilter {
+                pub first: Vec<EntityId>,
+                pub second: Vec<EntityId>,
+            }
+            impl ContactFilter {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    first: impl Into<Vec<EntityId>>,
+                    second: impl Into<Vec<EntityId>>,
+                ) -> Self {
+                    Self {
+                        first: first.into(),
+                        second: second.into(),
+                    }
+                }
+            }
+            impl Message for ContactFilter {
+                fn id() -> &'static str {
+                    "ambient_core::ContactFilter"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.first.serialize_message_part(&mut output)?;
+                    self.second.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        first: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                        second: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for ContactFilter {}
+            #[derive(Clone, Debug)]
+            #[doc = "**Splash**: Sent when a dynamic `body` enters or leaves the buoyancy `volume` (see `buoyancy_volume`)."]
+            pub struct Splash {
+                pub volume: EntityId,
+                pub body: EntityId,
+                pub entered: bool,
+            }
+            impl Splash {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    volume: impl Into<EntityId>,
+                    body: impl Into<EntityId>,
+                    entered: impl Into<bool>,
+                ) -> Self {
+                    Self {
+                        volume: volume.into(),
+                        body: body.into(),
+                        entered: entered.into(),
+                    }
+                }
+            }
+            impl Message for Splash {
+                fn id() -> &'static str {
+                    "ambient_core::Splash"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.volume.serialize_message_part(&mut output)?;
+                    self.body.serialize_message_part(&mut output)?;
+                    self.entered.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        volume: EntityId::deserialize_message_part(&mut input)?,
+                        body: EntityId::deserialize_message_part(&mut input)?,
+                        entered: bool::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for Splash {}
+            #[derive(Clone, Debug)]
+            #[doc = "**BodySleep**: Sent when dynamic entities fall asleep (see `sleeping`)."]
+            pub struct BodySleep {
+                pub ids: Vec<EntityId>,
+            }
+            impl BodySleep {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(ids: impl Into<Vec<EntityId>>) -> Self {
+                    Self { ids: ids.into() }
+                }
+            }
+            impl Message for BodySleep {
+                fn id() -> &'static str {
+                    "ambient_core::BodySleep"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.ids.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        ids: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for BodySleep {}
+            #[derive(Clone, Debug)]
+            #[doc = "**BodyWake**: Sent when sleeping dynamic entities wake up (see `sleeping`)."]
+            pub struct BodyWake {
+                pub ids: Vec<EntityId>,
+            }
+            impl BodyWake {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(ids: impl Into<Vec<EntityId>>) -> Self {
+                    Self { ids: ids.into() }
+                }
+            }
+            impl Message for BodyWake {
+                fn id() -> &'static str {
+                    "ambient_core::BodyWake"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.ids.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        ids: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for BodyWake {}
+            #[derive(Clone, Debug)]
+            #[doc = "**JointBroken**: Sent when the joint between `entity0` and `entity1` breaks (see `joint_break_force` and `joint_break_torque`). Either entity is null if the joint was attached to the world."]
+            pub struct JointBroken {
+                pub entity0: EntityId,
+                pub entity1: EntityId,
+            }
+            impl JointBroken {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(entity0: impl Into<EntityId>, entity1: impl Into<EntityId>) -> Self {
+                    Self {
+                        entity0: entity0.into(),
+                        entity1: entity1.into(),
+                    }
+                }
+            }
+            impl Message for JointBroken {
+                fn id() -> &'static str {
+                    "ambient_core::JointBroken"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.entity0.serialize_message_part(&mut output)?;
+                    self.entity1.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        entity0: EntityId::deserialize_message_part(&mut input)?,
+                        entity1: EntityId::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for JointBroken {}
+            #[derive(Clone, Debug)]
             #[doc = "**ColliderLoads**: Sent when colliders load."]
             pub struct ColliderLoads {
                 pub ids: Vec<EntityId>,
@@ -5181,6 +7986,33 @@ mod raw {
             }
             impl RuntimeMessage for WindowKeyboardCharacter {}
             #[derive(Clone, Debug)]
+            #[doc = "**WindowImeComposition**: Sent when the text being composed with an input method editor changes. It is empty once the composition is committed, as `WindowKeyboardCharacter` messages, or cancelled."]
+            pub struct WindowImeComposition {
+                pub text: String,
+            }
+            impl WindowImeComposition {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(text: impl Into<String>) -> Self {
+                    Self { text: text.into() }
+                }
+            }
+            impl Message for WindowImeComposition {
+                fn id() -> &'static str {
+                    "ambient_core::WindowImeComposition"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.text.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        text: String::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for WindowImeComposition {}
+            #[derive(Clone, Debug)]
             #[doc = "**WindowKeyboardModifiersChange**: Sent when the window's keyboard modifiers change."]
             pub struct WindowKeyboardModifiersChange {
                 pub modifiers: u32,
@@ -5449,6 +8281,47 @@ mod raw {
                 }
             }
             impl RuntimeMessage for WasmRebuild {}
+            #[derive(Clone, Debug)]
+            #[doc = "**RelevancyChange**: Sent on the server when entities start or stop being replicated to `player` (see `replication_radius` and `interest_group`). Entities that were despawned are not in `left`."]
+            pub struct RelevancyChange {
+                pub player: EntityId,
+                pub entered: Vec<EntityId>,
+                pub left: Vec<EntityId>,
+            }
+            impl RelevancyChange {
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    player: impl Into<EntityId>,
+                    entered: impl Into<Vec<EntityId>>,
+                    left: impl Into<Vec<EntityId>>,
+                ) -> Self {
+                    Self {
+                        player: player.into(),
+                        entered: entered.into(),
+                        left: left.into(),
+                    }
+                }
+            }
+            impl Message for RelevancyChange {
+                fn id() -> &'static str {
+                    "ambient_core::RelevancyChange"
+                }
+                fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+                    let mut output = vec![];
+                    self.player.serialize_message_part(&mut output)?;
+                    self.entered.serialize_message_part(&mut output)?;
+                    self.left.serialize_message_part(&mut output)?;
+                    Ok(output)
+                }
+                fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+                    Ok(Self {
+                        player: EntityId::deserialize_message_part(&mut input)?,
+                        entered: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                        left: Vec::<EntityId>::deserialize_message_part(&mut input)?,
+                    })
+                }
+            }
+            impl RuntimeMessage for RelevancyChange {}
         }
     }
 }
@@ -19,6 +19,8 @@ pub mod server;
 pub mod asset;
 /// Helper functions for the camera.
 pub mod camera;
+/// Immediate-mode drawing of lines, shapes and text, to visualize what a package is doing.
+pub mod debug_draw;
 /// ECS-related functionality not directly related to entities.
 pub mod ecs;
 /// Manipulation, creation, removal, search and more for entities.
@@ -29,6 +31,10 @@ pub mod global;
 pub mod message;
 /// Player-specific functionality.
 pub mod player;
+/// Client-side prediction of player inputs, reconciled with the server.
+pub mod prediction;
+/// Editing and querying of heightmap terrains.
+pub mod terrain;
 
 /// Helpful imports that almost all Ambient packages will use.
 pub mod prelude;
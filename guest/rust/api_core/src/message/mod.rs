@@ -126,6 +126,13 @@ pub enum Target {
     /// relay the message to other packages on the server.
     #[cfg(feature = "client")]
     ServerReliable,
+    /// A transmission to the server, delivered as the [Reliability] of the message says.
+    ///
+    /// Note that this message will only be received by the corresponding package
+    /// on the server, and not by any other packages. You will need to explicitly
+    /// relay the message to other packages on the server.
+    #[cfg(feature = "client")]
+    Server,
 
     // Server
     /// An unreliable transmission to all clients.
@@ -180,16 +187,48 @@ pub enum Target {
         /// The user to send to.
         String,
     ),
+    /// A transmission to all clients, delivered as the [Reliability] of the message says.
+    ///
+    /// Note that this message will only be received by the corresponding package
+    /// on the client, and not by any other packages. You will need to explicitly
+    /// relay the message to other packages on the client.
+    #[cfg(feature = "server")]
+    ClientBroadcast,
+    /// A transmission to a specific client, delivered as the [Reliability] of the message says.
+    ///
+    /// Note that this message will only be received by the corresponding package
+    /// on the client, and not by any other packages. You will need to explicitly
+    /// relay the message to other packages on the client.
+    #[cfg(feature = "server")]
+    ClientTargeted(
+        /// The user to send to.
+        String,
+    ),
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl IntoBindgen for Reliability {
+    type Item = wit::message::Reliability;
+
+    fn into_bindgen(self) -> Self::Item {
+        match self {
+            Reliability::ReliableOrdered => Self::Item::ReliableOrdered,
+            Reliability::ReliableUnordered => Self::Item::ReliableUnordered,
+            Reliability::UnreliableSequenced => Self::Item::UnreliableSequenced,
+        }
+    }
 }
 
 #[cfg(feature = "client")]
-impl IntoBindgen for Target {
+impl IntoBindgen for (Target, Reliability) {
     type Item = wit::client_message::Target;
 
     fn into_bindgen(self) -> Self::Item {
-        match self {
+        let (target, reliability) = self;
+        match target {
             Target::ServerUnreliable => Self::Item::ServerUnreliable,
             Target::ServerReliable => Self::Item::ServerReliable,
+            Target::Server => Self::Item::Server(reliability.into_bindgen()),
             Target::LocalBroadcast { include_self } => Self::Item::LocalBroadcast(include_self),
             Target::Local(id) => Self::Item::Local(id.into_bindgen()),
             #[cfg(feature = "server")]
@@ -199,11 +238,12 @@ impl IntoBindgen for Target {
 }
 
 #[cfg(feature = "server")]
-impl<'a> IntoBindgen for &'a Target {
+impl<'a> IntoBindgen for (&'a Target, Reliability) {
     type Item = wit::server_message::Target;
 
     fn into_bindgen(self) -> Self::Item {
-        match self {
+        let (target, reliability) = self;
+        match target {
             Target::ClientBroadcastUnreliable => Self::Item::ClientBroadcastUnreliable,
             Target::ClientBroadcastReliable => Self::Item::ClientBroadcastReliable,
             Target::ClientTargetedUnreliable(user_id) => {
@@ -212,6 +252,10 @@ impl<'a> IntoBindgen for &'a Target {
             Target::ClientTargetedReliable(user_id) => {
                 Self::Item::ClientTargetedReliable(user_id.clone())
             }
+            Target::ClientBroadcast => Self::Item::ClientBroadcast(reliability.into_bindgen()),
+            Target::ClientTargeted(user_id) => {
+                Self::Item::ClientTargeted((user_id.clone(), reliability.into_bindgen()))
+            }
             Target::LocalBroadcast { include_self } => Self::Item::LocalBroadcast(*include_self),
             Target::Local(id) => Self::Item::Local(id.into_bindgen()),
             #[cfg(feature = "client")]
@@ -224,13 +268,13 @@ impl<'a> IntoBindgen for &'a Target {
 pub fn send<T: Message>(target: Target, data: &T) {
     #[cfg(all(feature = "client", not(feature = "server")))]
     wit::client_message::send(
-        target.into_bindgen(),
+        (target, T::reliability()).into_bindgen(),
         T::id(),
         &data.serialize_message().unwrap(),
     );
     #[cfg(all(feature = "server", not(feature = "client")))]
     wit::server_message::send(
-        &(&target).into_bindgen(),
+        &(&target, T::reliability()).into_bindgen(),
         T::id(),
         &data.serialize_message().unwrap(),
     );
@@ -369,6 +413,18 @@ pub trait ModuleMessage: Message {
         self.send(Target::ServerReliable)
     }
 
+    #[cfg(feature = "client")]
+    /// Sends a message to the server, delivered as the [Reliability] of the message says.
+    ///
+    /// Note that this message will only be received by the corresponding package on the server,
+    /// and not by any other packages. You will need to explicitly relay the message to other
+    /// packages on the server.
+    ///
+    /// See [Target::Server] for details.
+    fn send_server(&self) {
+        self.send(Target::Server)
+    }
+
     #[cfg(feature = "server")]
     /// Sends an unreliable message to all clients.
     ///
@@ -417,6 +473,30 @@ pub trait ModuleMessage: Message {
         self.send(Target::ClientTargetedReliable(user_id))
     }
 
+    #[cfg(feature = "server")]
+    /// Sends a message to all clients, delivered as the [Reliability] of the message says.
+    ///
+    /// Note that this message will only be received by the corresponding package on the client,
+    /// and not by any other packages. You will need to explicitly relay the message to other
+    /// packages on the client.
+    ///
+    /// See [Target::ClientBroadcast] for details.
+    fn send_client_broadcast(&self) {
+        self.send(Target::ClientBroadcast)
+    }
+
+    #[cfg(feature = "server")]
+    /// Sends a message to a specific client, delivered as the [Reliability] of the message says.
+    ///
+    /// Note that this message will only be received by the corresponding package on the client,
+    /// and not by any other packages. You will need to explicitly relay the message to other
+    /// packages on the client.
+    ///
+    /// See [Target::ClientTargeted] for details.
+    fn send_client_targeted(&self, user_id: String) {
+        self.send(Target::ClientTargeted(user_id))
+    }
+
     /// Subscribes to this [Message]. Wrapper around [self::subscribe].
     fn subscribe<R: CallbackReturn>(
         callback: impl FnMut(MessageContext, Self) -> R + 'static,
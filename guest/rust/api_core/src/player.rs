@@ -13,3 +13,31 @@ pub fn get_by_user_id(user_id: &str) -> Option<EntityId> {
 pub fn get_local() -> EntityId {
     wit::client_player::get_local().from_bindgen()
 }
+
+/// Gives the player with `user_id` authority over `entity`, or takes it back to the server with
+/// `None`.
+///
+/// While a player has authority, their client simulates the entity and its changes to the
+/// entity's networked components are replicated to everyone else, while the changes the server
+/// makes are not sent to them.
+#[cfg(feature = "server")]
+pub fn transfer_authority(entity: EntityId, user_id: Option<&str>) {
+    use crate::{core::network::components::authority, entity};
+
+    match user_id {
+        Some(user_id) => entity::add_component(entity, authority(), user_id.to_string()),
+        None => entity::remove_component(entity, authority()),
+    }
+}
+
+/// Whether the local player has authority over `entity`, and so should simulate it.
+#[cfg(feature = "client")]
+pub fn has_authority(entity: EntityId) -> bool {
+    use crate::{
+        core::{network::components::authority, player::components::user_id},
+        entity,
+    };
+
+    let owner = entity::get_component(entity, authority());
+    owner.is_some() && owner == entity::get_component(get_local(), user_id())
+}
@@ -0,0 +1,236 @@
+#[cfg(any(feature = "client", feature = "server"))]
+use crate::message::{Message, MessageSerde, MessageSerdeError};
+
+/// An input a client has already simulated, as it is sent to the server
+#[cfg(any(feature = "client", feature = "server"))]
+#[derive(Clone, Debug)]
+struct PredictedInput {
+    /// The id of the message the input was serialized from
+    input_id: String,
+    sequence: u32,
+    delta_time: f32,
+    data: Vec<u8>,
+}
+#[cfg(any(feature = "client", feature = "server"))]
+impl Message for PredictedInput {
+    fn id() -> &'static str {
+        "ambient_core::prediction::PredictedInput"
+    }
+    fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError> {
+        let mut output = vec![];
+        self.input_id.serialize_message_part(&mut output)?;
+        self.sequence.serialize_message_part(&mut output)?;
+        self.delta_time.serialize_message_part(&mut output)?;
+        self.data.serialize_message_part(&mut output)?;
+        Ok(output)
+    }
+    fn deserialize_message(mut input: &[u8]) -> Result<Self, MessageSerdeError> {
+        Ok(Self {
+            input_id: String::deserialize_message_part(&mut input)?,
+            sequence: u32::deserialize_message_part(&mut input)?,
+            delta_time: f32::deserialize_message_part(&mut input)?,
+            data: Vec::<u8>::deserialize_message_part(&mut input)?,
+        })
+    }
+}
+
+/// The longest time an input can be simulated for, in seconds. Longer frames are clamped to it on
+/// both sides.
+#[cfg(any(feature = "client", feature = "server"))]
+pub const MAX_INPUT_DELTA_TIME: f32 = 0.1;
+
+/// How far ahead of the server the inputs of a player can be simulated, in seconds. The server
+/// simulates the inputs of each player for no more time than has passed, besides this allowance
+/// for inputs arriving in bursts, so that a client can't speed its player up by sending more
+/// inputs or claiming longer frames.
+#[cfg(any(feature = "client", feature = "server"))]
+pub const MAX_INPUT_TIME_AHEAD: f32 = 0.5;
+
+#[cfg(any(feature = "client", feature = "server"))]
+fn clamp_delta_time(delta_time: f32) -> f32 {
+    if delta_time.is_nan() {
+        return 0.;
+    }
+    delta_time.clamp(0., MAX_INPUT_DELTA_TIME)
+}
+
+/// Whether the input `sequence` was sent after the input `last`, as the sequence wraps around
+#[cfg(any(feature = "client", feature = "server"))]
+fn is_after(sequence: u32, last: u32) -> bool {
+    (sequence.wrapping_sub(last) as i32) > 0
+}
+
+#[cfg(feature = "client")]
+mod client {
+    use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+    use once_cell::sync::Lazy;
+
+    use super::{clamp_delta_time, is_after, PredictedInput};
+    use crate::{
+        core::{
+            messages::Frame,
+            network::components::{last_processed_input, prediction_rewound},
+        },
+        ecs::{query, Component, GeneralQuery},
+        entity,
+        global::{runtime::delta_time, EntityId},
+        message::{self, Message, RuntimeMessage, Target},
+        player,
+    };
+
+    type Step<I> = Box<dyn FnMut(EntityId, &I, f32)>;
+
+    struct State<I> {
+        step: Step<I>,
+        next_sequence: u32,
+        /// The inputs the server hasn't processed yet, with their sequence and delta time
+        pending: VecDeque<(u32, f32, I)>,
+    }
+    impl<I> State<I> {
+        /// Forgets the inputs the server has processed, and replays the others if the `predicted`
+        /// entities have been rewound to the server's state
+        fn reconcile(&mut self) {
+            static REWOUND: Lazy<GeneralQuery<Component<()>>> =
+                Lazy::new(|| query(prediction_rewound()).build());
+
+            let player = player::get_local();
+            if let Some(processed) = entity::get_component(player, last_processed_input()) {
+                while matches!(self.pending.front(), Some((sequence, _, _)) if !is_after(*sequence, processed))
+                {
+                    self.pending.pop_front();
+                }
+            }
+
+            let rewound = REWOUND.evaluate();
+            if rewound.is_empty() {
+                return;
+            }
+            for (id, _) in rewound {
+                entity::remove_component(id, prediction_rewound());
+            }
+            for (_, dt, input) in &self.pending {
+                (self.step)(player, input, *dt);
+            }
+        }
+    }
+
+    /// Simulates the inputs of the local player on the `predicted` entities as they are sent,
+    /// instead of waiting for the server to respond to them.
+    ///
+    /// The server simulates the same inputs with [on_input](super::on_input), and reports the
+    /// last one it has processed in the player's `last_processed_input`. Whenever the server's
+    /// state arrives, the predicted entities are rewound to it and the inputs the server hasn't
+    /// processed yet are replayed on top of it with the same `step`.
+    ///
+    /// `step` must only depend on the state of the entities and the input, so that it gives the
+    /// same result on both sides.
+    pub struct Predictor<I> {
+        state: Rc<RefCell<State<I>>>,
+    }
+    impl<I: Message + 'static> Predictor<I> {
+        /// Creates a predictor that simulates an input for the player with `step(player, input,
+        /// delta_time)`. The predictor keeps reconciling with the server every frame, even after
+        /// it has been dropped.
+        pub fn new(step: impl FnMut(EntityId, &I, f32) + 'static) -> Self {
+            let state = Rc::new(RefCell::new(State {
+                step: Box::new(step),
+                next_sequence: 1,
+                pending: VecDeque::new(),
+            }));
+            Frame::subscribe({
+                let state = state.clone();
+                move |_| state.borrow_mut().reconcile()
+            });
+            Self { state }
+        }
+
+        /// Sends `input` to the server, and simulates it right away with the length of this frame,
+        /// up to [MAX_INPUT_DELTA_TIME](super::MAX_INPUT_DELTA_TIME)
+        pub fn input(&self, input: I) {
+            let mut state = self.state.borrow_mut();
+            state.reconcile();
+
+            let sequence = state.next_sequence;
+            state.next_sequence = sequence.wrapping_add(1);
+            let dt = clamp_delta_time(delta_time());
+            message::send(
+                Target::ServerReliable,
+                &PredictedInput {
+                    input_id: I::id().to_string(),
+                    sequence,
+                    delta_time: dt,
+                    data: input.serialize_message().unwrap(),
+                },
+            );
+
+            (state.step)(player::get_local(), &input, dt);
+            state.pending.push_back((sequence, dt, input));
+        }
+
+        /// The number of inputs the server hasn't processed yet
+        pub fn pending(&self) -> usize {
+            self.state.borrow().pending.len()
+        }
+    }
+}
+#[cfg(feature = "client")]
+pub use client::*;
+
+#[cfg(feature = "server")]
+mod server {
+    use std::collections::HashMap;
+
+    use super::{clamp_delta_time, is_after, PredictedInput, MAX_INPUT_TIME_AHEAD};
+    use crate::{
+        core::network::components::last_processed_input,
+        entity,
+        global::{game_time, Duration, EntityId, OkEmpty},
+        message::{self, Listener, Message, MessageContext},
+    };
+
+    /// Simulates the inputs the clients send through a `Predictor` of `I` with `step(player,
+    /// input, delta_time)`, the same way as the clients did, and records the last input of every
+    /// player that was processed in its `last_processed_input`.
+    ///
+    /// The inputs are sent reliably but may arrive out of order, so an input older than the last
+    /// processed one is dropped. Its effect is lost, and the client is corrected when it
+    /// reconciles. The delta time of each input is clamped to
+    /// [MAX_INPUT_DELTA_TIME](super::MAX_INPUT_DELTA_TIME), and to the time that has passed since
+    /// the previous inputs of the player, up to [MAX_INPUT_TIME_AHEAD].
+    pub fn on_input<I: Message + 'static>(
+        mut step: impl FnMut(EntityId, &I, f32) + 'static,
+    ) -> Listener {
+        // The time each player's inputs can still be simulated for, and when it was last updated
+        let mut budgets = HashMap::<EntityId, (f32, Duration)>::new();
+        message::subscribe(move |ctx: MessageContext, msg: PredictedInput| {
+            if msg.input_id != I::id() {
+                return OkEmpty;
+            }
+            let Some(player) = ctx.client_entity_id() else {
+                return OkEmpty;
+            };
+            let last = entity::get_component(player, last_processed_input());
+            if last.is_some_and(|last| !is_after(msg.sequence, last)) {
+                return OkEmpty;
+            }
+            let input = I::deserialize_message(&msg.data)?;
+
+            let now = game_time();
+            if !budgets.contains_key(&player) {
+                budgets.retain(|id, _| entity::exists(*id));
+            }
+            let (budget, updated) = budgets.entry(player).or_insert((MAX_INPUT_TIME_AHEAD, now));
+            *budget = (*budget + (now - *updated).as_secs_f32()).min(MAX_INPUT_TIME_AHEAD);
+            *updated = now;
+            let dt = clamp_delta_time(msg.delta_time).min(*budget);
+            *budget -= dt;
+
+            step(player, &input, dt);
+            entity::add_component(player, last_processed_input(), msg.sequence);
+            OkEmpty
+        })
+    }
+}
+#[cfg(feature = "server")]
+pub use server::*;
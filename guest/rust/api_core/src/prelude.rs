@@ -1,5 +1,5 @@
 pub use crate::{
-    asset, camera,
+    asset, camera, debug_draw,
     ecs::{
         change_query, despawn_query, query, spawn_query, Component, ComponentsTuple, Concept,
         ConceptComponents, ConceptQuery, ConceptSuggested, Entity, QueryEvent,
@@ -8,7 +8,7 @@ pub use crate::{
     global::*,
     main, message,
     message::{Message, ModuleMessage, RuntimeMessage},
-    player,
+    player, terrain,
 };
 pub use anyhow::{anyhow, Context as AnyhowContext};
 pub use rand::prelude::*;
@@ -20,4 +20,4 @@ pub use crate::client::{
 };
 
 #[cfg(feature = "server")]
-pub use crate::server::{http, physics};
+pub use crate::server::{http, navmesh, physics};
@@ -3,3 +3,6 @@ pub mod physics;
 
 /// **\[Server-only\]** HTTP-related functionality, including sending requests and receiving responses.
 pub mod http;
+
+/// **\[Server-only\]** Navigation meshes, baked by the asset pipeline, and finding paths on them.
+pub mod navmesh;
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+pub use ambient_shared_types::navmesh::{Navmesh, NavmeshError, NavmeshPoint, NavmeshPolygon};
+
+use super::http::{self, HttpError};
+
+#[derive(Error, Debug)]
+/// Errors that can occur when loading a navmesh.
+pub enum LoadNavmeshError {
+    #[error("{0}")]
+    /// The navmesh could not be downloaded.
+    Http(#[from] HttpError),
+    #[error("Invalid navmesh: {0}")]
+    /// The file is not a navmesh.
+    Invalid(#[from] NavmeshError),
+}
+
+/// Loads the navmesh at `url`, as baked by the `Navmeshes` asset pipeline, to find paths on it
+/// with [Navmesh::find_path].
+///
+/// ```ignore
+/// let navmesh = navmesh::load(packages::this::assets::url("level.navmesh")).await?;
+/// let path = navmesh.find_path(start, end);
+/// ```
+pub async fn load(url: impl AsRef<str>) -> Result<Navmesh, LoadNavmeshError> {
+    Ok(Navmesh::from_bytes(&http::get(url).await?)?)
+}
@@ -1,11 +1,14 @@
 use crate::{
-    global::{EntityId, Vec3},
+    core::physics::components::{kinematic_target_rotation, kinematic_target_translation, ragdoll},
+    entity,
+    global::{EntityId, Quat, Vec3},
     internal::{
         conversion::{FromBindgen, IntoBindgen},
         wit,
     },
 };
 use glam::Mat4;
+use std::time::Duration;
 
 /// Applies a `force` (a [Vec3]) to the `entity` (an [EntityId]) specified.
 ///
@@ -118,6 +121,39 @@ pub fn freeze(entity: EntityId) {
     wit::server_physics::freeze(entity.into_bindgen())
 }
 
+/// Puts a dynamic `entity` to sleep, so that it is no longer simulated until something touches it or
+/// it is woken up with [wake_up]. Does nothing if the entity isn't dynamic.
+pub fn put_to_sleep(entity: EntityId) {
+    wit::server_physics::put_to_sleep(entity.into_bindgen())
+}
+
+/// Wakes up a sleeping dynamic `entity`. Does nothing if the entity isn't dynamic.
+pub fn wake_up(entity: EntityId) {
+    wit::server_physics::wake_up(entity.into_bindgen())
+}
+
+/// Moves the kinematic `entity` to `translation` and `rotation` over the next frame, pushing any
+/// dynamic bodies in its way. This should be used instead of setting the transform of moving
+/// platforms, doors and elevators, so that the bodies they push or carry move with them.
+pub fn set_kinematic_target(entity: EntityId, translation: Vec3, rotation: Quat) {
+    entity::add_component(entity, kinematic_target_translation(), translation);
+    entity::add_component(entity, kinematic_target_rotation(), rotation);
+}
+
+/// Turns the animated model `entity` into a ragdoll, so that its bones are simulated by physics.
+/// Does nothing if it was already a ragdoll.
+///
+/// The blend between the animation and the simulation can be controlled with the
+/// [ragdoll_blend](crate::core::physics::components::ragdoll_blend) component.
+pub fn ragdollify(entity: EntityId) {
+    entity::add_component(entity, ragdoll(), ());
+}
+
+/// Hands the bones of a ragdoll `entity` back to its animation. Does nothing if it wasn't a ragdoll.
+pub fn unragdollify(entity: EntityId) {
+    entity::remove_component(entity, ragdoll());
+}
+
 /// Starts a motor on `entity` with `velocity`. Does nothing if the motor has already been started.
 pub fn start_motor(entity: EntityId, velocity: f32) {
     wit::server_physics::start_motor(entity.into_bindgen(), velocity)
@@ -143,6 +179,23 @@ pub fn create_revolute_joint(
     )
 }
 
+/// Creates a fixed joint, which holds the two entities together. entity0 or entity1 can either be `EntityId::null()` to bind this to the world frame.
+///
+/// The joint breaks when the `joint_break_force` or `joint_break_torque` of either entity is exceeded.
+pub fn create_fixed_joint(
+    entity0: EntityId,
+    transform0: Mat4,
+    entity1: EntityId,
+    transform1: Mat4,
+) {
+    wit::server_physics::create_fixed_joint(
+        entity0.into_bindgen(),
+        transform0.into_bindgen(),
+        entity1.into_bindgen(),
+        transform1.into_bindgen(),
+    )
+}
+
 /// Where a [raycast] hit.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct RaycastHit {
@@ -169,6 +222,81 @@ pub fn raycast_first(origin: Vec3, direction: Vec3) -> Option<RaycastHit> {
     wit::server_physics::raycast_first(origin.into_bindgen(), direction.into_bindgen())
         .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
 }
+/// Casts a batch of rays, given as `(origin, direction)`, and returns the [RaycastHit]s along each ray.
+///
+/// This is equivalent to calling [raycast] for each ray, but is much faster when casting many rays
+/// at once. Every `direction` must be normalized.
+pub fn raycast_batch(rays: &[(Vec3, Vec3)]) -> Vec<Vec<RaycastHit>> {
+    let (origins, directions) = split_rays(rays);
+    wit::server_physics::raycast_batch(&origins, &directions)
+        .into_iter()
+        .zip(rays)
+        .map(|(hits, &(origin, direction))| {
+            hits.into_iter()
+                .map(|(entity, distance)| {
+                    raycast_result_to_hit(origin, direction, entity, distance)
+                })
+                .collect()
+        })
+        .collect()
+}
+/// Casts a batch of rays, given as `(origin, direction)`, and returns the first [RaycastHit] of each
+/// ray if it hits.
+///
+/// This is equivalent to calling [raycast_first] for each ray, but is much faster when casting many
+/// rays at once. Every `direction` must be normalized.
+pub fn raycast_first_batch(rays: &[(Vec3, Vec3)]) -> Vec<Option<RaycastHit>> {
+    let (origins, directions) = split_rays(rays);
+    wit::server_physics::raycast_first_batch(&origins, &directions)
+        .into_iter()
+        .zip(rays)
+        .map(|(hit, &(origin, direction))| {
+            hit.map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
+        })
+        .collect()
+}
+/// Casts a ray from `origin` in `direction` against the moving colliders where they were at the
+/// server `time`, and returns the [RaycastHit]s along the way.
+///
+/// This is used for lag compensation: have the client send the `shown_server_time` it saw when
+/// the player fired, so that the shot hits what the player aimed at, even though the targets have
+/// moved on since. The server keeps the poses for `lag_compensation_history`; older times use the
+/// oldest poses. `direction` must be normalized.
+pub fn raycast_at(time: Duration, origin: Vec3, direction: Vec3) -> Vec<RaycastHit> {
+    wit::server_physics::raycast_at(
+        time.into_bindgen(),
+        origin.into_bindgen(),
+        direction.into_bindgen(),
+    )
+    .into_iter()
+    .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
+    .collect()
+}
+/// Like [raycast_at], but only returns the first [RaycastHit] if it hits.
+pub fn raycast_first_at(time: Duration, origin: Vec3, direction: Vec3) -> Option<RaycastHit> {
+    wit::server_physics::raycast_first_at(
+        time.into_bindgen(),
+        origin.into_bindgen(),
+        direction.into_bindgen(),
+    )
+    .map(|(entity, distance)| raycast_result_to_hit(origin, direction, entity, distance))
+}
+fn split_rays(rays: &[(Vec3, Vec3)]) -> (Vec<wit::types::Vec3>, Vec<wit::types::Vec3>) {
+    rays.iter()
+        .map(|(origin, direction)| (origin.into_bindgen(), direction.into_bindgen()))
+        .unzip()
+}
+/// Returns the entities with colliders that overlap each sphere in a batch, given as `(center, radius)`.
+pub fn overlap_sphere_batch(spheres: &[(Vec3, f32)]) -> Vec<Vec<EntityId>> {
+    let (centers, radii): (Vec<_>, Vec<_>) = spheres
+        .iter()
+        .map(|(center, radius)| (center.into_bindgen(), *radius))
+        .unzip();
+    wit::server_physics::overlap_sphere_batch(&centers, &radii)
+        .into_iter()
+        .map(|ids| ids.from_bindgen())
+        .collect()
+}
 fn raycast_result_to_hit(
     origin: Vec3,
     direction: Vec3,
@@ -235,3 +363,12 @@ pub fn set_character_foot_position(entity: EntityId, position: Vec3) {
         position.into_bindgen(),
     );
 }
+
+/// Sets whether `first` and `second` collide, in answer to a
+/// [ContactFilter](crate::core::messages::ContactFilter) message.
+///
+/// Only packages with the `contact_filter` component receive these messages; until they answer,
+/// the pair collides.
+pub fn set_contact_filter(first: EntityId, second: EntityId, collide: bool) {
+    wit::server_physics::set_contact_filter(first.into_bindgen(), second.into_bindgen(), collide);
+}
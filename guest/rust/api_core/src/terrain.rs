@@ -0,0 +1,64 @@
+use crate::{
+    core::{
+        rendering::components::{terrain_heights, terrain_resolution},
+        transform::components::local_to_world,
+    },
+    entity,
+    global::{EntityId, Mat4, Vec2, Vec3Swizzles},
+    internal::{conversion::IntoBindgen, wit},
+};
+
+/// Raises the terrain `entity` by `amount` meters at `center`, smoothly falling off to nothing
+/// at `radius` meters from it. A negative `amount` lowers the terrain.
+///
+/// Edits made on the server are sent to every player, and update the terrain's collider.
+pub fn raise(entity: EntityId, center: Vec2, radius: f32, amount: f32) {
+    wit::terrain::raise(entity.into_bindgen(), center.into_bindgen(), radius, amount)
+}
+
+/// Lowers the terrain `entity` by `amount` meters at `center`; the opposite of [raise].
+pub fn lower(entity: EntityId, center: Vec2, radius: f32, amount: f32) {
+    raise(entity, center, radius, -amount)
+}
+
+/// Paints `layer` over the other layers of the terrain `entity` at `center`, with `strength`
+/// between 0 and 1, smoothly falling off to nothing at `radius` meters from it.
+pub fn paint(entity: EntityId, center: Vec2, radius: f32, layer: u32, strength: f32) {
+    wit::terrain::paint(
+        entity.into_bindgen(),
+        center.into_bindgen(),
+        radius,
+        layer,
+        strength,
+    )
+}
+
+/// The height, in meters, of the terrain `entity` at `position`, or `None` if it is not a valid
+/// terrain. Positions outside of the terrain get the height of its closest edge.
+pub fn height(entity: EntityId, position: Vec2) -> Option<f32> {
+    let resolution = entity::get_component(entity, terrain_resolution())?;
+    let heights = entity::get_component(entity, terrain_heights())?;
+    if resolution.x < 2
+        || resolution.y < 2
+        || heights.len() != (resolution.x * resolution.y) as usize
+    {
+        return None;
+    }
+    let terrain_to_world =
+        entity::get_component(entity, local_to_world()).unwrap_or(Mat4::IDENTITY);
+
+    let last = (resolution - 1).as_vec2();
+    let position = terrain_to_world
+        .inverse()
+        .transform_point3(position.extend(0.))
+        .xy()
+        .clamp(Vec2::ZERO, last);
+    let min = position.floor().min(last - 1.);
+    let t = position - min;
+    let min = min.as_uvec2();
+    let at = |x: u32, y: u32| heights[(y * resolution.x + x) as usize];
+    let height = (at(min.x, min.y) * (1. - t.x) + at(min.x + 1, min.y) * t.x) * (1. - t.y)
+        + (at(min.x, min.y + 1) * (1. - t.x) + at(min.x + 1, min.y + 1) * t.x) * t.y;
+
+    Some(terrain_to_world.transform_point3(position.extend(height)).z)
+}
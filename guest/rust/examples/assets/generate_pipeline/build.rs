@@ -15,6 +15,7 @@ fn main() {
             sources: vec!["*".to_string()],
             tags: vec![],
             categories: vec![],
+            texture_compression: None,
         }],
     }
     .save_to_file("assets/pipeline.toml")
@@ -4,7 +4,6 @@ use ambient_api::{
         animation::components::apply_animation_player,
         app::components::name,
         layout::components::space_between_items,
-        model::components::model_loaded,
         prefab::components::prefab_from_url,
         primitives::{components::quad, concepts::Sphere},
         rendering::components::color,
@@ -56,10 +55,8 @@ pub async fn main() {
 
     println!("Robot duration: {} sec", robot.clip_duration().await);
 
-    let _ = entity::wait_for_component(unit_id, model_loaded()).await;
-
-    // This demonstrates how to attach an entity to a bone
-    let left_foot = animation::get_bone_by_bind_id(unit_id, &BindId::LeftFoot).unwrap();
+    // This demonstrates how to attach an entity to a bone; it follows the bone once the model
+    // has loaded
     let ball = Entity::new()
         .with_merge(Sphere::suggested())
         .with(scale(), vec3(0.3, 0.3, 0.3))
@@ -67,7 +64,7 @@ pub async fn main() {
         .with(local_to_parent(), Default::default())
         .with(reset_scale(), ())
         .spawn();
-    entity::add_child(left_foot, ball);
+    animation::attach_to_bone(ball, unit_id, BindId::LeftFoot.as_str());
 
     let robot = PlayClipFromUrlNodeRef::new(assets::url(
         "Robot Hip Hop Dance.fbx/animations/mixamo.com.anim",
@@ -0,0 +1,37 @@
+use ambient_api::{core::layout::components::space_between_items, element::use_state, prelude::*};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (score, set_score) = use_state(hooks, 0);
+    let (volume, set_volume) = use_state(hooks, 0.5);
+
+    FlowColumn::el([
+        Text::el("Main menu").header_style(),
+        // Buttons are named by their text, and can be activated with a screen reader
+        Button::new("Score a point", move |_| set_score(score + 1)).el(),
+        // An icon has no text to read, so the button gets a label
+        Button::new(FontAwesomeIcon::el(0xf2f9, true), move |_| set_score(0))
+            .el()
+            .with_label("Reset the score"),
+        Slider::new(volume, move |value| set_volume(value)).el(),
+        // Announced whenever it changes, without being focused
+        FlowRow::el([
+            FontAwesomeIcon::el(0xf091, true),
+            Text::el(score.to_string()),
+        ])
+        .with(space_between_items(), STREET)
+        .with_role(AccessibilityRole::Status)
+        .with_label(format!("Score: {score}"))
+        .with_live_updates(),
+        Text::el("~ ~ ~").hidden_from_accessibility(),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
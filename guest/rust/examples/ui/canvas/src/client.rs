@@ -0,0 +1,92 @@
+use ambient_api::{
+    core::{layout::components::space_between_items, rect::components::border_radius},
+    element::{use_frame, use_state},
+    prelude::*,
+};
+use std::f32::consts::{PI, TAU};
+
+pub mod packages;
+
+const SAMPLES: usize = 120;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (samples, set_samples) = use_state(hooks, Vec::<f32>::new());
+    use_frame(hooks, {
+        let samples = samples.clone();
+        move |_| {
+            // A made up value to show, like the load of a server
+            let t = game_time().as_secs_f32();
+            let value = 0.5 + 0.3 * (t * 1.3).sin() + 0.15 * (t * 5.7).sin();
+
+            let mut samples = samples.clone();
+            samples.push(value.clamp(0., 1.));
+            if samples.len() > SAMPLES {
+                samples.remove(0);
+            }
+            set_samples(samples);
+        }
+    });
+
+    let value = samples.last().copied().unwrap_or_default();
+    FlowRow::el([
+        // The canvases are redrawn whenever the drawings change, which is every frame here
+        Canvas::el(gauge(value)),
+        Canvas::el(graph(&samples)).with(border_radius(), Vec4::ONE * 8.),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
+
+fn gauge(value: f32) -> CanvasDrawing {
+    let center = vec2(80., 90.);
+    let color = vec4(1., 1. - value, 0.2, 1.);
+
+    let mut drawing = CanvasDrawing::new(uvec2(160, 100));
+    drawing
+        .arc(center, 70., PI, TAU)
+        .stroke(vec4(1., 1., 1., 0.2), 12.)
+        .begin_path()
+        .arc(center, 70., PI, PI + PI * value)
+        .stroke(color, 12.)
+        .text(
+            format!("{:.0}%", value * 100.),
+            vec2(55., 55.),
+            24.,
+            Vec4::ONE,
+        );
+    drawing
+}
+
+fn graph(samples: &[f32]) -> CanvasDrawing {
+    let size = vec2(300., 100.);
+
+    let mut drawing = CanvasDrawing::new(size.as_uvec2());
+    drawing
+        .rect(Vec2::ZERO, size)
+        .fill(vec4(0., 0., 0., 0.5))
+        .begin_path();
+    for i in 1..4 {
+        let y = size.y * i as f32 / 4.;
+        drawing.line(vec2(0., y), vec2(size.x, y));
+    }
+    drawing.stroke(vec4(1., 1., 1., 0.1), 1.).begin_path();
+    for (i, sample) in samples.iter().enumerate() {
+        let point = vec2(
+            i as f32 / (SAMPLES - 1) as f32 * size.x,
+            (1. - sample) * size.y,
+        );
+        if i == 0 {
+            drawing.move_to(point);
+        } else {
+            drawing.line_to(point);
+        }
+    }
+    drawing.stroke(vec4(0.3, 0.8, 1., 1.), 2.);
+    drawing
+}
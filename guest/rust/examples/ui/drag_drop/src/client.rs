@@ -0,0 +1,77 @@
+use ambient_api::{
+    core::layout::components::{fit_horizontal, fit_vertical, height, space_between_items, width},
+    element::use_state,
+    prelude::*,
+};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+const SLOT_SIZE: f32 = 80.;
+
+fn slot(content: Element) -> Element {
+    FlowColumn::el([content])
+        .with(fit_horizontal(), Fit::None)
+        .with(fit_vertical(), Fit::None)
+        .with(width(), SLOT_SIZE)
+        .with(height(), SLOT_SIZE)
+        .with_background(vec4(0.1, 0.1, 0.1, 1.))
+        .with_padding_even(STREET)
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (items, set_items) = use_state(hooks, {
+        let mut items = vec![None; 8];
+        items[0] = Some("Sword");
+        items[1] = Some("Shield");
+        items[4] = Some("Potion");
+        items
+    });
+    // The trash is only shown while an item is dragged
+    let dragging = use_drag(hooks).is_some();
+
+    // The payload of an item is the index of its slot
+    let slots = (0..items.len())
+        .map(|index| {
+            let content = match items[index] {
+                Some(name) => DragSource::new(Text::el(name), DragPayload::new(index))
+                    .preview(Text::el(name).with_background(vec4(0., 0., 0., 0.8)))
+                    .el(),
+                None => Element::new(),
+            };
+            DropTarget::new(slot(content), {
+                to_owned![items, set_items];
+                move |_, &from: &usize| {
+                    let mut items = items.clone();
+                    items.swap(from, index);
+                    set_items(items);
+                }
+            })
+            .el()
+        })
+        .collect();
+
+    let trash = if dragging {
+        DropTarget::new(slot(Text::el("Trash")), move |_, &from: &usize| {
+            let mut items = items.clone();
+            items[from] = None;
+            set_items(items);
+        })
+        .el()
+    } else {
+        Element::new()
+    };
+
+    FlowColumn::el([
+        Text::el("Drag the items between the slots").header_style(),
+        FlowRow::el([FlowRow::el(slots).with(space_between_items(), 5.), trash])
+            .with(space_between_items(), STREET),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
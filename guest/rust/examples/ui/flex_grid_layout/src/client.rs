@@ -0,0 +1,94 @@
+use ambient_api::{
+    core::layout::components::{
+        align_items, docking, fit_horizontal, fit_vertical, flex_grow, flex_wrap, grid_column,
+        grid_column_span, grid_columns, grid_row, grid_row_span, grid_rows, justify_content,
+        space_between_items, width,
+    },
+    prelude::*,
+};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+/// A panel which fills its grid cell
+fn panel(title: &str, content: Element) -> Element {
+    FlowColumn::el([Text::el(title).header_style(), content])
+        .with(fit_horizontal(), Fit::Parent)
+        .with(fit_vertical(), Fit::Parent)
+        .with_background(vec4(0.1, 0.1, 0.1, 0.8))
+        .with_padding_even(STREET)
+        .with(space_between_items(), STREET)
+}
+
+#[element_component]
+fn App(_hooks: &mut Hooks) -> Element {
+    let top_bar = FlexRow::el([
+        Text::el("Flex and grid layout"),
+        // Pushes the buttons to the right
+        UIBase.el().with(flex_grow(), 1.),
+        Button::new("Settings", |_| {}).el(),
+        Button::new("Quit", |_| {}).el(),
+    ])
+    .with(docking(), Docking::Top)
+    .with(align_items(), FlexAlign::Center)
+    .with(space_between_items(), STREET)
+    .with_background(vec4(0., 0., 0., 0.8))
+    .with_padding_even(STREET);
+
+    let inventory = FlexRow::el(
+        (0..24)
+            .map(|i| {
+                Text::el(format!("Item {i}"))
+                    .with(width(), 60.)
+                    .with_background(vec4(0.2, 0.2, 0.2, 1.))
+                    .with_padding_even(5.)
+            })
+            .collect(),
+    )
+    .with(fit_horizontal(), Fit::Parent)
+    .with(flex_wrap(), true)
+    .with(justify_content(), Justify::SpaceEvenly)
+    .with(space_between_items(), 5.);
+
+    let hud = Grid::el([
+        panel("Map", Text::el("The sidebar is 200 pixels wide")).with(grid_row_span(), 2),
+        panel(
+            "Inventory",
+            Text::el("The rest is shared between the other columns"),
+        )
+        .with(grid_column_span(), 2),
+        panel("Quests", Text::el("This column takes twice as much")),
+        panel("Chat", Text::el("...")),
+        // The last row fits the bag, so it doesn't fill its cell
+        panel("Bag", inventory)
+            .with(fit_vertical(), Fit::Children)
+            .with(grid_column(), 0)
+            .with(grid_row(), 2)
+            .with(grid_column_span(), 3),
+    ])
+    .with(docking(), Docking::Fill)
+    .with(
+        grid_columns(),
+        grid_tracks([
+            GridTrack::Pixels(200.),
+            GridTrack::Fraction(2.),
+            GridTrack::Fraction(1.),
+        ]),
+    )
+    .with(
+        grid_rows(),
+        grid_tracks([
+            GridTrack::Fraction(1.),
+            GridTrack::Fraction(1.),
+            GridTrack::Auto,
+        ]),
+    )
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET);
+
+    WindowSized::el([top_bar, hud])
+}
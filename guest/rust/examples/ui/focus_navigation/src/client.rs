@@ -0,0 +1,59 @@
+use ambient_api::{
+    core::layout::components::space_between_items, element::use_state, prelude::*,
+    ui::use_keyboard_input,
+};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (last, set_last) = use_state(hooks, "Nothing yet".to_string());
+    let (name, set_name) = use_state(hooks, String::new());
+
+    // Stands in for a gamepad: the I, J, K and L keys navigate, and O activates
+    use_keyboard_input(hooks, |world, keycode, _, pressed| {
+        if !pressed {
+            return;
+        }
+        match keycode {
+            Some(VirtualKeyCode::I) => navigate_focus(world, -Vec2::Y),
+            Some(VirtualKeyCode::K) => navigate_focus(world, Vec2::Y),
+            Some(VirtualKeyCode::J) => navigate_focus(world, -Vec2::X),
+            Some(VirtualKeyCode::L) => navigate_focus(world, Vec2::X),
+            Some(VirtualKeyCode::O) => activate_focus(world),
+            _ => {}
+        }
+    });
+
+    let button = |label: &'static str| {
+        let set_last = set_last.clone();
+        Button::new(label, move |_| set_last(label.to_string())).el()
+    };
+
+    FlowColumn::el([
+        Text::el("Use Tab, the arrow keys and Enter, or I, J, K, L and O"),
+        // The menu is focused when it's shown, so it can be used right away
+        InitialFocus::el(
+            FlowColumn::el([
+                FlowRow::el([button("New game"), button("Continue")])
+                    .with(space_between_items(), STREET),
+                FlowRow::el([button("Settings"), button("Credits")])
+                    .with(space_between_items(), STREET),
+                // The arrow keys move the cursor of the text editor, but Tab leaves it
+                TextEditor::new(name, set_name)
+                    .placeholder(Some("Your name"))
+                    .el(),
+                button("Quit"),
+            ])
+            .with(space_between_items(), STREET),
+        ),
+        Text::el(format!("Last activated: {last}")),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
@@ -0,0 +1,33 @@
+use ambient_api::{
+    core::layout::components::{height, space_between_items, width},
+    prelude::*,
+};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(_hooks: &mut Hooks) -> Element {
+    FlowColumn::el([
+        Text::el("Ten thousand items, of which only the visible ones are created").header_style(),
+        ListView::el(
+            10_000,
+            30.,
+            cb(|index: usize| {
+                FlowRow::el([
+                    Text::el(format!("Server #{index}")).with(width(), 200.),
+                    Text::el(format!("{} players", index * 7 % 32)),
+                ])
+                .with(space_between_items(), STREET)
+            }),
+        )
+        .with(width(), 400.)
+        .with(height(), 300.),
+    ])
+    .with_padding_even(STREET)
+    .with(space_between_items(), STREET)
+}
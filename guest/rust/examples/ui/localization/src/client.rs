@@ -0,0 +1,37 @@
+use ambient_api::{core::layout::components::space_between_items, element::use_state, prelude::*};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    load_string_tables(
+        &mut World,
+        packages::this::assets::url("locales.strings.json"),
+    );
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (apples, set_apples) = use_state(hooks, 1);
+    // Rerenders this element when the locale changes, so the strings looked up with `tr!` follow it
+    let translations = use_translations(hooks);
+
+    FlowColumn::el([
+        // Translated by the runtime, which keeps it up to date by itself
+        Text::tr("title").header_style(),
+        Text::el(tr!(translations, "locale", locale = translations.locale)),
+        FlowRow::el(
+            ["en", "fr", "pl"]
+                .map(|locale| Button::new(locale, move |world| set_locale(world, locale)).el()),
+        )
+        .with(space_between_items(), STREET),
+        Button::new(tr!(translations, "shop.add"), move |_| {
+            set_apples(apples + 1)
+        })
+        .el(),
+        Text::tr_with("shop.apples", &[("count", &apples.to_string())]),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
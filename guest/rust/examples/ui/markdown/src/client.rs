@@ -0,0 +1,55 @@
+use ambient_api::{
+    core::layout::components::{space_between_items, width},
+    element::use_state,
+    prelude::*,
+};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+const HELP: &str = r#"# How to play
+
+Collect **all the gems** before the *timer* runs out. Gems give you more time, but
+**_golden gems_** give you twice as much.
+
+## Controls
+
+- Move with `W`, `A`, `S` and `D`
+- Jump with `Space`
+  - Jump again in the air to double jump
+- Open the map with `M`
+
+## Tips
+
+1. Look for gems behind waterfalls
+2. Read the [wiki](https://example.com/wiki) for the secret levels
+3. Join the [community](https://example.com/community) to share your times
+
+```
+Best time:   02:31
+Gems found:  48 / 50
+```
+"#;
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (clicked, set_clicked) = use_state(hooks, None::<String>);
+
+    FlowColumn::el([
+        // The text wraps to the width of the element
+        Markdown::new(HELP)
+            .on_link(move |_, url| set_clicked(Some(url)))
+            .el()
+            .with(width(), 400.),
+        Text::el(match clicked {
+            Some(url) => format!("Clicked {url}"),
+            None => "Click a link".to_string(),
+        }),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
@@ -0,0 +1,61 @@
+use ambient_api::{
+    core::{
+        layout::components::{fit_horizontal, space_between_items, width},
+        rect::components::size_from_background_image,
+    },
+    element::{use_frame, use_state},
+    prelude::*,
+    ui::ImageFromUrl,
+};
+
+pub mod packages;
+use packages::this::assets;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+/// The url of an image of the atlas built by the `TextureAtlases` pipeline
+fn atlas(name: &str) -> String {
+    format!("{}#{name}", assets::url("ui.atlas.json"))
+}
+
+fn skinned_button(label: &str, on_click: impl Fn(&mut World) + Sync + Send + 'static) -> Element {
+    // The button image has nine-slice insets in the atlas, so its borders don't stretch
+    Text::el(label)
+        .with_padding_even(STREET)
+        .with_background_image(atlas("button"))
+        .with_clickarea()
+        .on_mouse_up(move |world, _, _| on_click(world))
+        .el()
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (coins, set_coins) = use_state(hooks, 0);
+    let (panel_width, set_panel_width) = use_state(hooks, 300.);
+    use_frame(hooks, move |_| {
+        set_panel_width(300. + 150. * game_time().as_secs_f32().sin());
+    });
+
+    FlowColumn::el([
+        FlowRow::el([
+            ImageFromUrl { url: atlas("coin") }
+                .el()
+                .with(size_from_background_image(), ()),
+            Text::el(format!("{coins} coins")),
+        ])
+        .with(space_between_items(), STREET),
+        skinned_button("Collect a coin", move |_| set_coins(coins + 1)),
+        skinned_button("Collect all the coins in the world", move |_| {
+            set_coins(coins + 100)
+        }),
+    ])
+    .with(space_between_items(), STREET)
+    .with(fit_horizontal(), Fit::None)
+    .with(width(), panel_width)
+    .with_padding_even(24.)
+    .with_background_image(atlas("panel"))
+    .with_margin_even(STREET)
+}
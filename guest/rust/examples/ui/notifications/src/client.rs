@@ -0,0 +1,63 @@
+use ambient_api::{core::layout::components::space_between_items, element::use_state, prelude::*};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (saves, set_saves) = use_state(hooks, 0);
+    let (log, set_log) = use_state(hooks, String::new());
+
+    use_notification_action(hooks, "save".to_string(), {
+        let set_log = set_log.clone();
+        move |_, action| set_log(format!("Picked {action} on the save notification"))
+    });
+    use_modal_response(hooks, "quit".to_string(), move |_, action| {
+        set_log(format!("Picked {action} in the quit dialog"))
+    });
+
+    FlowColumn::el([
+        FlowRow::el([
+            Button::new("Info", |world| {
+                Notification::info("A player joined")
+                    .body("Say hi!")
+                    .show(world)
+            })
+            .el(),
+            Button::new("Save", move |world| {
+                set_saves(saves + 1);
+                // Shown with the same id each time, so it replaces the previous one
+                Notification::success(format!("Saved {} times", saves + 1))
+                    .action("Undo")
+                    .id("save")
+                    .show(world)
+            })
+            .el(),
+            Button::new("Warning", |world| {
+                // Shown until it's dismissed, ahead of the other notifications
+                Notification::new(NotificationLevel::Warning, "Low health")
+                    .body("Find a medkit")
+                    .priority(1)
+                    .timeout(0.)
+                    .show(world)
+            })
+            .el(),
+            Button::new("Quit", |world| {
+                Modal::new("quit", "Quit the game?")
+                    .body("Your progress is saved.")
+                    .action("Quit")
+                    .action("Cancel")
+                    .show(world)
+            })
+            .el(),
+        ])
+        .with(space_between_items(), STREET),
+        Text::el(log),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
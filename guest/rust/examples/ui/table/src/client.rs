@@ -0,0 +1,77 @@
+use ambient_api::{
+    core::layout::components::space_between_items,
+    element::{use_query, use_state},
+    prelude::*,
+};
+use packages::this::components::{deaths, kills, player_name};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    for (name, player_kills, player_deaths) in [
+        ("Alice", 12, 3),
+        ("Bob", 7, 7),
+        ("Charlie", 15, 10),
+        ("Dana", 4, 1),
+        ("Eve", 9, 12),
+    ] {
+        Entity::new()
+            .with(player_name(), name.to_string())
+            .with(kills(), player_kills)
+            .with(deaths(), player_deaths)
+            .spawn();
+    }
+
+    App.el().spawn_interactive();
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let players = use_query(hooks, (player_name(), kills(), deaths()));
+    let (selected, set_selected) = use_state(hooks, None::<EntityId>);
+
+    let columns = vec![
+        TableColumn::new("Name", 120.),
+        TableColumn::new("Kills", 60.),
+        TableColumn::new("Deaths", 60.),
+        TableColumn::new("K/D", 60.),
+        TableColumn::new("", 60.).sortable(false),
+    ];
+    let rows = players
+        .into_iter()
+        .map(|(id, (name, kills, deaths))| {
+            let ratio = kills as f32 / deaths.max(1) as f32;
+            vec![
+                name.into(),
+                kills.into(),
+                deaths.into(),
+                // Shown rounded, but sorted by the exact value
+                TableCell::new(Text::el(format!("{ratio:.2}")))
+                    .sort_key(SortKey::Number(ratio as f64)),
+                TableCell::new(
+                    Button::new("Edit", {
+                        let set_selected = set_selected.clone();
+                        move |_| set_selected(Some(id))
+                    })
+                    .style(ButtonStyle::Flat)
+                    .el(),
+                ),
+            ]
+        })
+        .collect();
+
+    // The property grid edits the components of the selected player, which updates the table
+    let properties = match selected {
+        Some(id) => PropertyGrid::el(vec![
+            Property::component(id, player_name()),
+            Property::component(id, kills()),
+            Property::component(id, deaths()),
+        ]),
+        None => Text::el("Click Edit to edit a player"),
+    };
+
+    FlowColumn::el([Table::new(columns, rows).sort_by(1, true).el(), properties])
+        .with(space_between_items(), STREET)
+        .with_padding_even(STREET)
+}
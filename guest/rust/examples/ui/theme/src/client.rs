@@ -0,0 +1,81 @@
+use ambient_api::{core::layout::components::space_between_items, element::use_state, prelude::*};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    // The theme of this package, used by all of its UI
+    Theme::set_current(dark_theme());
+
+    ThemeProvider::el(dark_theme(), App.el()).spawn_interactive();
+}
+
+fn dark_theme() -> Theme {
+    Theme {
+        primary_color: Color::hex("2E86DE").unwrap(),
+        secondary_color: Color::hex("10AC84").unwrap(),
+        ..Default::default()
+    }
+}
+
+fn light_theme() -> Theme {
+    Theme {
+        primary_color: Color::hex("C0392B").unwrap(),
+        secondary_color: Color::hex("F39C12").unwrap(),
+        app_background_color: Color::hex("ECF0F1").unwrap(),
+        cutout_color: Color::hex("D5DBDB").unwrap(),
+        tooltip_background_color: Color::rgba(1., 1., 1., 0.9),
+        text_color: Color::rgba(0.1, 0.1, 0.1, 1.),
+        body_text_color: Color::rgba(0.2, 0.2, 0.2, 1.),
+        button_text_color: Color::hex("2C3E50").unwrap(),
+        font_size: 14.,
+        spacing: 14.,
+        rounding: 8.,
+        ..Default::default()
+    }
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let theme = use_theme(hooks);
+    let set_theme = use_set_theme(hooks).unwrap();
+    let (value, set_value) = use_state(hooks, 50.);
+    let (name, set_name) = use_state(hooks, String::new());
+
+    FlowColumn::el([
+        Text::el("Theme").header_style(),
+        FlowRow::el([
+            Button::new("Dark", {
+                let set_theme = set_theme.clone();
+                move |_| set_theme(dark_theme())
+            })
+            .toggled(theme == dark_theme())
+            .el(),
+            Button::new("Light", move |_| set_theme(light_theme()))
+                .toggled(theme == light_theme())
+                .el(),
+        ])
+        .with(space_between_items(), theme.spacing),
+        Slider {
+            value,
+            on_change: Some(set_value),
+            min: 0.,
+            max: 100.,
+            width: 200.,
+            logarithmic: false,
+            round: Some(0),
+            suffix: Some("%"),
+        }
+        .el(),
+        TextEditor::new(name, set_name)
+            .placeholder(Some("Your name"))
+            .el(),
+        Button::new("Save", |_| {})
+            .style(ButtonStyle::Primary)
+            .tooltip("Nothing is saved in this example")
+            .el(),
+    ])
+    .with_background(theme.app_background_color.into())
+    .with(space_between_items(), theme.spacing)
+    .with_padding_even(theme.spacing)
+}
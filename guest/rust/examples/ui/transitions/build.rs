@@ -0,0 +1,3 @@
+fn main() {
+    ambient_package_projection::generate();
+}
@@ -0,0 +1,67 @@
+use ambient_api::{core::layout::components::space_between_items, element::use_state, prelude::*};
+
+pub mod packages;
+
+#[main]
+pub fn main() {
+    App.el().spawn_interactive();
+}
+
+/// A menu item which grows while it's hovered
+#[element_component]
+fn MenuItem(hooks: &mut Hooks, label: &'static str) -> Element {
+    let (hovered, set_hovered) = use_state(hooks, false);
+    let item = Text::el(label)
+        .with_background(vec4(0.2, 0.2, 0.2, 1.))
+        .with_padding_even(STREET);
+
+    Transition::new(item)
+        .enter(None)
+        .exit(None)
+        .state(TransitionState::default().scale(if hovered { 1.1 } else { 1. }))
+        .easing(Easing::EaseOutBack)
+        .el()
+        .with_clickarea()
+        .on_mouse_enter({
+            let set_hovered = set_hovered.clone();
+            move |_, _| set_hovered(true)
+        })
+        .on_mouse_leave(move |_, _| set_hovered(false))
+        .el()
+}
+
+#[element_component]
+fn App(hooks: &mut Hooks) -> Element {
+    let (open, set_open) = use_state(hooks, false);
+
+    // The menu slides in from the left, and fades out where it is
+    let menu = FlowColumn::el(
+        ["New game", "Continue", "Settings", "Quit"]
+            .into_iter()
+            .map(|label| MenuItem::el(label))
+            .collect(),
+    )
+    .with(space_between_items(), 5.)
+    .with_background(vec4(0., 0., 0., 0.8))
+    .with_padding_even(STREET);
+
+    FlowColumn::el([
+        Button::new(if open { "Close menu" } else { "Open menu" }, move |_| {
+            set_open(!open)
+        })
+        .el(),
+        Transition::new(menu)
+            .visible(open)
+            .enter(Some(
+                TransitionState::default()
+                    .opacity(0.)
+                    .translation(vec2(-50., 0.)),
+            ))
+            .exit(Some(TransitionState::default().opacity(0.)))
+            .duration(0.3)
+            .easing(Easing::EaseOutCubic)
+            .el(),
+    ])
+    .with(space_between_items(), STREET)
+    .with_padding_even(STREET)
+}
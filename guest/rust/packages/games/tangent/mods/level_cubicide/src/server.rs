@@ -4,7 +4,7 @@ use ambient_api::{
     core::{
         app::components::main_scene,
         messages::Frame,
-        physics::components::{cube_collider, dynamic, mass, physics_controlled, plane_collider},
+        physics::components::{cube_collider, density, dynamic, physics_controlled, plane_collider},
         primitives::{
             components::{cube, quad},
             concepts::Sphere,
@@ -255,9 +255,9 @@ fn handle_respawnables(
 }
 
 fn make_cube(pos: Vec3, size: Vec3, dynamic: bool, color: Vec4, rng: &mut dyn RngCore) -> EntityId {
-    const MASS_MULTIPLIER: f32 = 10.;
+    // The mass is computed from the size of the cube and this density
+    const DENSITY: f32 = 10.;
 
-    let volume = size.dot(Vec3::ONE);
     let pitch_amplitude: f32 = if dynamic { 5. } else { 60. };
     Entity::new()
         .with(cube(), ())
@@ -275,7 +275,7 @@ fn make_cube(pos: Vec3, size: Vec3, dynamic: bool, color: Vec4, rng: &mut dyn Rn
         .with(physics_controlled(), ())
         .with(cube_collider(), Vec3::ONE)
         .with(self::dynamic(), dynamic)
-        .with(mass(), volume * MASS_MULTIPLIER)
+        .with(density(), DENSITY)
         .spawn()
 }
 
@@ -255,6 +255,7 @@ pub trait PxRigidBody {
     fn set_angular_velocity(&self, value: Vec3, autoawake: bool);
     fn get_mass(&self) -> f32;
     fn set_mass(&self, mass: f32);
+    fn get_mass_space_inertia_tensor(&self) -> Vec3;
     fn get_velocity_at_pos(&self, pos: Vec3) -> Vec3;
     fn add_force(&self, force: Vec3, mode: Option<PxForceMode>, autowake: Option<bool>);
     fn add_force_at_pos(
@@ -334,6 +335,11 @@ impl<T: AsPxRigidBody + ?Sized> PxRigidBody for T {
     fn set_mass(&self, mass: f32) {
         unsafe { physx_sys::PxRigidBody_setMass_mut(self.as_rigid_body().0, mass) }
     }
+    fn get_mass_space_inertia_tensor(&self) -> Vec3 {
+        to_glam_vec3(&unsafe {
+            physx_sys::PxRigidBody_getMassSpaceInertiaTensor(self.as_rigid_body().0)
+        })
+    }
     fn get_velocity_at_pos(&self, pos: Vec3) -> Vec3 {
         unsafe {
             to_glam_vec3(&physx_sys::PxRigidBodyExt_getVelocityAtPos_mut(
@@ -449,11 +455,36 @@ impl PxRigidDynamicRef {
     pub fn wake_up(&self) {
         unsafe { physx_sys::PxRigidDynamic_wakeUp_mut(self.0) }
     }
+    pub fn put_to_sleep(&self) {
+        unsafe { physx_sys::PxRigidDynamic_putToSleep_mut(self.0) }
+    }
+    pub fn is_sleeping(&self) -> bool {
+        unsafe { physx_sys::PxRigidDynamic_isSleeping(self.0) }
+    }
+    pub fn set_sleep_threshold(&self, threshold: f32) {
+        unsafe { physx_sys::PxRigidDynamic_setSleepThreshold_mut(self.0, threshold) }
+    }
+    pub fn get_sleep_threshold(&self) -> f32 {
+        unsafe { physx_sys::PxRigidDynamic_getSleepThreshold(self.0) }
+    }
     pub fn set_kinematic_target(&self, destination: &PxTransform) {
         unsafe {
             physx_sys::PxRigidDynamic_setKinematicTarget_mut(self.0, &destination.0);
         }
     }
+    pub fn set_solver_iteration_counts(
+        &self,
+        min_position_iterations: u32,
+        min_velocity_iterations: u32,
+    ) {
+        unsafe {
+            physx_sys::PxRigidDynamic_setSolverIterationCounts_mut(
+                self.0,
+                min_position_iterations,
+                min_velocity_iterations,
+            )
+        }
+    }
 }
 impl AsPxBase for PxRigidDynamicRef {
     fn as_base(&self) -> PxBaseRef {
@@ -1,8 +1,8 @@
 use std::ptr::null_mut;
 
 use crate::{
-    AsPxActor, AsPxBase, AsPxRigidActor, AsPxRigidBody, PxActorRef, PxBaseRef, PxRigidActorRef,
-    PxRigidBodyRef, PxTransform,
+    articulation_reduced_coordinate::PxArticulationJointRef, AsPxActor, AsPxBase, AsPxRigidActor,
+    AsPxRigidBody, PxActorRef, PxBaseRef, PxRigidActorRef, PxRigidBodyRef, PxTransform,
 };
 
 pub trait AsArticulationBase {
@@ -90,6 +90,17 @@ impl<T: AsArticulationJointBase> PxArticulationJointBase for T {
     }
 }
 pub struct PxArticulationJointBaseRef(*mut physx_sys::PxArticulationJointBase);
+impl PxArticulationJointBaseRef {
+    /// Only valid for joints of a [PxArticulationRef](crate::articulation_reduced_coordinate::PxArticulationRef).
+    pub fn to_reduced_coordinate(&self) -> PxArticulationJointRef {
+        PxArticulationJointRef(self.0 as _)
+    }
+}
+impl AsArticulationJointBase for PxArticulationJointBaseRef {
+    fn as_articulation_joint_base_ptr(&self) -> *mut physx_sys::PxArticulationJointBase {
+        self.0
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct PxArticulationLinkRef(pub(crate) *mut physx_sys::PxArticulationLink);
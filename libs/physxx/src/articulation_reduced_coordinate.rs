@@ -49,7 +49,7 @@ unsafe impl Sync for PxArticulationRef {}
 unsafe impl Send for PxArticulationRef {}
 
 #[derive(Clone, Copy)]
-pub struct PxArticulationJointRef(*mut physx_sys::PxArticulationJointReducedCoordinate);
+pub struct PxArticulationJointRef(pub(crate) *mut physx_sys::PxArticulationJointReducedCoordinate);
 impl PxArticulationJointRef {
     pub fn set_joint_type(&mut self, joint_type: PxArticulationJointType) {
         unsafe {
@@ -223,6 +223,21 @@ impl PxHeightFieldGeometry {
             )
         })
     }
+    pub fn height_field(&self) -> PxHeightField {
+        PxHeightField::from_ptr(self.0.heightField)
+    }
+    pub fn height_scale(&self) -> f32 {
+        self.0.heightScale
+    }
+    pub fn row_scale(&self) -> f32 {
+        self.0.rowScale
+    }
+    pub fn column_scale(&self) -> f32 {
+        self.0.columnScale
+    }
+    pub fn is_valid(&self) -> bool {
+        unsafe { physx_sys::PxHeightFieldGeometry_isValid(&self.0 as _) }
+    }
 }
 impl PxGeometry for PxHeightFieldGeometry {
     fn as_geometry_ptr(&self) -> *const physx_sys::PxGeometry {
@@ -258,6 +273,16 @@ impl PxGeometryHolder {
             ))
         }
     }
+    pub fn as_height_field(&self) -> Option<PxHeightFieldGeometry> {
+        if self.get_type() != PxGeometryType::HEIGHTFIELD {
+            return None;
+        }
+        unsafe {
+            Some(PxHeightFieldGeometry(
+                *physx_sys::PxGeometryHolder_heightField(&self.0),
+            ))
+        }
+    }
     pub fn as_triangle_mesh(&self) -> Option<PxTriangleMeshGeometry> {
         if self.get_type() != PxGeometryType::TRIANGLEMESH {
             return None;
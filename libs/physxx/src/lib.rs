@@ -4,6 +4,9 @@ extern crate bitflags;
 extern crate num_derive;
 use std::ptr::null_mut;
 
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
 mod actor;
 pub mod articulation;
 pub mod articulation_reduced_coordinate;
@@ -141,6 +144,38 @@ impl PxMaterial {
             )
         })
     }
+    pub fn get_static_friction(&self) -> f32 {
+        unsafe { physx_sys::PxMaterial_getStaticFriction(self.0) }
+    }
+    pub fn set_static_friction(&self, coef: f32) {
+        unsafe { physx_sys::PxMaterial_setStaticFriction_mut(self.0, coef) }
+    }
+    pub fn get_dynamic_friction(&self) -> f32 {
+        unsafe { physx_sys::PxMaterial_getDynamicFriction(self.0) }
+    }
+    pub fn set_dynamic_friction(&self, coef: f32) {
+        unsafe { physx_sys::PxMaterial_setDynamicFriction_mut(self.0, coef) }
+    }
+    pub fn get_restitution(&self) -> f32 {
+        unsafe { physx_sys::PxMaterial_getRestitution(self.0) }
+    }
+    pub fn set_restitution(&self, restitution: f32) {
+        unsafe { physx_sys::PxMaterial_setRestitution_mut(self.0, restitution) }
+    }
+    pub fn get_friction_combine_mode(&self) -> PxCombineMode {
+        PxCombineMode::from_u32(unsafe { physx_sys::PxMaterial_getFrictionCombineMode(self.0) })
+            .unwrap()
+    }
+    pub fn set_friction_combine_mode(&self, mode: PxCombineMode) {
+        unsafe { physx_sys::PxMaterial_setFrictionCombineMode_mut(self.0, mode as u32) }
+    }
+    pub fn get_restitution_combine_mode(&self) -> PxCombineMode {
+        PxCombineMode::from_u32(unsafe { physx_sys::PxMaterial_getRestitutionCombineMode(self.0) })
+            .unwrap()
+    }
+    pub fn set_restitution_combine_mode(&self, mode: PxCombineMode) {
+        unsafe { physx_sys::PxMaterial_setRestitutionCombineMode_mut(self.0, mode as u32) }
+    }
     pub(crate) fn from_ptr(ptr: *mut physx_sys::PxMaterial) -> Self {
         let mut s = Self(ptr);
         s.acquire_reference();
@@ -168,6 +203,15 @@ impl Clone for PxMaterial {
 unsafe impl Sync for PxMaterial {}
 unsafe impl Send for PxMaterial {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
+#[repr(u32)]
+pub enum PxCombineMode {
+    Average = physx_sys::PxCombineMode::eAVERAGE,
+    Min = physx_sys::PxCombineMode::eMIN,
+    Multiply = physx_sys::PxCombineMode::eMULTIPLY,
+    Max = physx_sys::PxCombineMode::eMAX,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct PxAggregateRef(*mut physx_sys::PxAggregate);
 impl PxAggregateRef {
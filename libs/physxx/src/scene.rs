@@ -290,6 +290,12 @@ impl PxSceneRef {
         }
     }
 
+    /// Makes the scene re-run the filter shader for all pairs involving this actor, for instance
+    /// after the filter data of its shapes has changed.
+    pub fn reset_filtering(&self, actor: &dyn AsPxActor) -> bool {
+        unsafe { physx_sys::PxScene_resetFiltering_mut(self.0, actor.as_actor().0) }
+    }
+
     pub fn add_aggregate(&self, aggregate: &PxAggregateRef) {
         unsafe {
             physx_sys::PxScene_addAggregate_mut(self.0, aggregate.0);
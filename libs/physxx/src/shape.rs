@@ -21,6 +21,35 @@ impl Default for PxShapeFlag {
     }
 }
 
+/// Data passed to the filter shader for each pair of shapes that may collide.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PxFilterData {
+    pub word0: u32,
+    pub word1: u32,
+    pub word2: u32,
+    pub word3: u32,
+}
+impl From<physx_sys::PxFilterData> for PxFilterData {
+    fn from(data: physx_sys::PxFilterData) -> Self {
+        Self {
+            word0: data.word0,
+            word1: data.word1,
+            word2: data.word2,
+            word3: data.word3,
+        }
+    }
+}
+impl From<PxFilterData> for physx_sys::PxFilterData {
+    fn from(data: PxFilterData) -> Self {
+        Self {
+            word0: data.word0,
+            word1: data.word1,
+            word2: data.word2,
+            word3: data.word3,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct PxShape(pub *mut physx_sys::PxShape, pub usize);
 impl PxShape {
@@ -101,6 +130,35 @@ impl PxShape {
             physx_sys::PxShape_setFlags_mut(self.0, physx_sys::PxShapeFlags { mBits: flags.bits })
         }
     }
+    pub fn get_simulation_filter_data(&self) -> PxFilterData {
+        unsafe { physx_sys::PxShape_getSimulationFilterData(self.0) }.into()
+    }
+    pub fn set_simulation_filter_data(&self, data: &PxFilterData) {
+        let data: physx_sys::PxFilterData = (*data).into();
+        unsafe { physx_sys::PxShape_setSimulationFilterData_mut(self.0, &data) }
+    }
+    pub fn get_nb_materials(&self) -> u16 {
+        unsafe { physx_sys::PxShape_getNbMaterials(self.0) }
+    }
+    pub fn get_materials(&self) -> Vec<PxMaterial> {
+        let capacity = self.get_nb_materials();
+        let mut buffer: Vec<*mut physx_sys::PxMaterial> = Vec::with_capacity(capacity as usize);
+        unsafe {
+            let len =
+                physx_sys::PxShape_getMaterials(self.0, buffer.as_mut_ptr(), capacity as u32, 0);
+            buffer.set_len(len as usize);
+        }
+        buffer.into_iter().map(PxMaterial::from_ptr).collect()
+    }
+    pub fn set_materials(&self, materials: &[&PxMaterial]) {
+        let mats = materials
+            .iter()
+            .map(|x| x.0)
+            .collect::<Vec<*mut physx_sys::PxMaterial>>();
+        unsafe {
+            physx_sys::PxShape_setMaterials_mut(self.0, mats.as_ptr(), materials.len() as u16)
+        }
+    }
     pub fn get_contact_offset(&self) -> f32 {
         unsafe { physx_sys::PxShape_getContactOffset(self.0) }
     }
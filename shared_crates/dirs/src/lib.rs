@@ -15,6 +15,21 @@ pub fn deployment_cache_path(deployment: &str) -> PathBuf {
         .join(deployment)
 }
 
+/// Returns the path to the cache of the outputs of the asset pipelines, shared by all packages.
+pub fn build_cache_path() -> PathBuf {
+    project_dirs().cache_dir().join("build")
+}
+
+/// Returns the path the bundle with the given hash is unpacked to.
+pub fn bundle_cache_path(hash: &str) -> PathBuf {
+    project_dirs().cache_dir().join("bundles").join(hash)
+}
+
+/// Returns the path to the key bundles are signed with by default.
+pub fn bundle_key_path() -> PathBuf {
+    project_dirs().config_dir().join("bundle_key.pk8")
+}
+
 fn project_dirs() -> &'static ProjectDirs {
     const QUALIFIER: &str = "com";
     const ORGANIZATION: &str = "Ambient";
@@ -117,6 +117,15 @@ pub mod ecs {
         pub fn index(&self) -> u32 {
             self.0.index()
         }
+        pub fn path(&self) -> String {
+            self.0.path()
+        }
+        pub fn path_last(&self) -> String {
+            let path = self.path();
+            path.rsplit_once("::")
+                .map(|v| v.1.to_string())
+                .unwrap_or(path)
+        }
     }
     impl<T: 'static> From<Component<T>> for ComponentDesc {
         fn from(value: Component<T>) -> Self {
@@ -134,6 +143,18 @@ pub mod window {
         #[cfg(not(feature = "client"))]
         let _ = cursor;
     }
+    pub fn set_ime_allowed(_world: &crate::ecs::World, allowed: bool) {
+        #[cfg(feature = "client")]
+        super::api::client::window::set_ime_allowed(allowed);
+        #[cfg(not(feature = "client"))]
+        let _ = allowed;
+    }
+    pub fn set_ime_position(_world: &crate::ecs::World, position: glam::Vec2) {
+        #[cfg(feature = "client")]
+        super::api::client::window::set_ime_position(position);
+        #[cfg(not(feature = "client"))]
+        let _ = position;
+    }
     pub async fn get_clipboard() -> Option<String> {
         #[cfg(feature = "client")]
         return super::api::client::clipboard::get().await;
@@ -150,3 +171,16 @@ pub mod window {
         Ok(())
     }
 }
+
+pub mod canvas {
+    use ambient_shared_types::canvas::CanvasDrawing;
+
+    use crate::ecs::{EntityId, World};
+
+    pub fn draw(_world: &mut World, entity: EntityId, drawing: &CanvasDrawing) {
+        #[cfg(feature = "client")]
+        super::api::client::canvas::draw(entity, drawing);
+        #[cfg(not(feature = "client"))]
+        let _ = (entity, drawing);
+    }
+}
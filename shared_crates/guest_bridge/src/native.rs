@@ -44,6 +44,20 @@ pub mod window {
             .ok();
     }
 
+    pub fn set_ime_allowed(world: &World, allowed: bool) {
+        world
+            .resource(window_ctl())
+            .send(WindowCtl::SetImeAllowed(allowed))
+            .ok();
+    }
+
+    pub fn set_ime_position(world: &World, position: glam::Vec2) {
+        world
+            .resource(window_ctl())
+            .send(WindowCtl::SetImePosition(position))
+            .ok();
+    }
+
     pub async fn get_clipboard() -> Option<String> {
         ambient_sys::clipboard::get().await
     }
@@ -52,3 +66,12 @@ pub mod window {
         ambient_sys::clipboard::set(text).await
     }
 }
+
+pub mod canvas {
+    use ambient_ecs::{EntityId, World};
+    use ambient_shared_types::canvas::CanvasDrawing;
+
+    pub fn draw(world: &mut World, entity: EntityId, drawing: &CanvasDrawing) {
+        ambient_rect::canvas::draw(world, entity, drawing.clone());
+    }
+}
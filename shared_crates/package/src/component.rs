@@ -12,6 +12,30 @@ pub struct Component {
     pub attributes: Vec<ItemPathBuf>,
     #[serde(default)]
     pub default: Option<toml::Value>,
+    #[serde(default)]
+    pub replication: Option<ComponentReplication>,
+}
+
+/// Limits how often a networked component is sent to the clients, e.g.
+/// `replication = { rate = 10, priority = "Low" }`
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct ComponentReplication {
+    /// The maximum number of times per second the component is sent, in hertz
+    #[serde(default)]
+    pub rate: Option<f32>,
+    #[serde(default)]
+    pub priority: ReplicationPriority,
+    /// The step floating point values are rounded to when only their change is sent
+    #[serde(default)]
+    pub quantization: Option<f32>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub enum ReplicationPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
 }
 
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq, Serialize)]
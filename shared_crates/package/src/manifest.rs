@@ -228,6 +228,12 @@ impl Default for PackageContent {
 pub struct Build {
     #[serde(default)]
     pub rust: BuildRust,
+    /// The processors this package's asset pipelines can use, by name, to import formats the
+    /// built-in pipelines don't support.
+    #[serde(default)]
+    #[serde(rename = "asset-processors")]
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub asset_processors: IndexMap<SnakeCaseIdentifier, BuildAssetProcessor>,
 }
 
 #[derive(Deserialize, Clone, Debug, PartialEq, Serialize)]
@@ -243,6 +249,14 @@ impl Default for BuildRust {
     }
 }
 
+/// An asset processor run as an external command, which is sent a file to import and writes the
+/// assets imported from it.
+#[derive(Deserialize, Clone, Debug, PartialEq, Serialize)]
+pub struct BuildAssetProcessor {
+    /// The program to run and its arguments, run in the package's directory.
+    pub command: Vec<String>,
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Serialize)]
 pub struct Dependency {
     #[serde(default)]
@@ -265,9 +279,10 @@ mod tests {
     use indexmap::IndexMap;
 
     use crate::{
-        Build, BuildRust, Component, ComponentType, Components, Concept, ConceptValue,
-        ContainerType, Dependency, Enum, Identifier, ItemPathBuf, Manifest, ManifestParseError,
-        Package, PackageId, PascalCaseIdentifier, SnakeCaseIdentifier,
+        Build, BuildAssetProcessor, BuildRust, Component, ComponentReplication, ComponentType,
+        Components, Concept, ConceptValue, ContainerType, Dependency, Enum, Identifier,
+        ItemPathBuf, Manifest, ManifestParseError, Package, PackageId, PascalCaseIdentifier,
+        ReplicationPriority, SnakeCaseIdentifier,
     };
     use semver::Version;
 
@@ -357,7 +372,8 @@ mod tests {
                 build: Build {
                     rust: BuildRust {
                         feature_multibuild: vec!["client".to_string(), "server".to_string()]
-                    }
+                    },
+                    ..Default::default()
                 },
                 components: IndexMap::from_iter([(
                     ipb("cell"),
@@ -367,6 +383,7 @@ mod tests {
                         type_: ComponentType::Item(i("i32").into()),
                         attributes: vec![i("store").into()],
                         default: None,
+                        replication: None,
                     }
                 )]),
                 concepts: IndexMap::from_iter([(
@@ -389,6 +406,41 @@ mod tests {
         )
     }
 
+    #[test]
+    fn can_parse_component_replication() {
+        const TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Replication"
+        version = "0.0.1"
+
+        [components]
+        position = { type = "Vec3", attributes = ["Networked"], replication = { rate = 10, priority = "High", quantization = 0.01 } }
+        sparkle = { type = "F32", attributes = ["Networked"], replication = { priority = "Low" } }
+        health = { type = "F32", attributes = ["Networked"] }
+        "#;
+
+        let manifest = Manifest::parse(TOML).unwrap();
+        let replication = |id: &str| manifest.components[&ipb(id)].replication;
+        assert_eq!(
+            replication("position"),
+            Some(ComponentReplication {
+                rate: Some(10.0),
+                priority: ReplicationPriority::High,
+                quantization: Some(0.01)
+            })
+        );
+        assert_eq!(
+            replication("sparkle"),
+            Some(ComponentReplication {
+                rate: None,
+                priority: ReplicationPriority::Low,
+                quantization: None
+            })
+        );
+        assert_eq!(replication("health"), None);
+    }
+
     #[test]
     fn can_parse_rust_build_settings() {
         const TOML: &str = r#"
@@ -418,13 +470,42 @@ mod tests {
                 build: Build {
                     rust: BuildRust {
                         feature_multibuild: vec!["client".to_string()]
-                    }
+                    },
+                    ..Default::default()
                 },
                 ..Default::default()
             })
         )
     }
 
+    #[test]
+    fn can_parse_asset_processors() {
+        const TOML: &str = r#"
+        [package]
+        id = "lktsfudbjw2qikhyumt573ozxhadkiwm"
+        name = "Voxels"
+        version = "0.0.1"
+        content = { type = "Playable" }
+
+        [build.asset-processors]
+        voxels = { command = ["python", "tools/import_voxels.py"] }
+        "#;
+
+        let manifest = Manifest::parse(TOML).unwrap();
+        assert_eq!(
+            manifest.build,
+            Build {
+                asset_processors: IndexMap::from_iter([(
+                    sci("voxels"),
+                    BuildAssetProcessor {
+                        command: vec!["python".to_string(), "tools/import_voxels.py".to_string()]
+                    }
+                )]),
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn can_parse_concepts_with_documented_namespace_from_manifest() {
         use toml::Value;
@@ -469,7 +550,8 @@ mod tests {
                 build: Build {
                     rust: BuildRust {
                         feature_multibuild: vec!["client".to_string(), "server".to_string()]
-                    }
+                    },
+                    ..Default::default()
                 },
                 components: IndexMap::from_iter([
                     (
@@ -480,6 +562,7 @@ mod tests {
                             type_: ComponentType::Item(i("quat").into()),
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     ),
                     (
@@ -490,6 +573,7 @@ mod tests {
                             type_: ComponentType::Item(i("vec3").into()),
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     ),
                     (
@@ -500,6 +584,7 @@ mod tests {
                             type_: ComponentType::Item(i("empty").into()),
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     ),
                     (
@@ -510,6 +595,7 @@ mod tests {
                             type_: ComponentType::Item(i("vec3").into()),
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     ),
                 ]),
@@ -666,7 +752,8 @@ mod tests {
                 build: Build {
                     rust: BuildRust {
                         feature_multibuild: vec!["client".to_string(), "server".to_string()]
-                    }
+                    },
+                    ..Default::default()
                 },
                 components: IndexMap::from_iter([
                     (
@@ -677,6 +764,7 @@ mod tests {
                             type_: ComponentType::Item(i("I32").into()),
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     ),
                     (
@@ -690,6 +778,7 @@ mod tests {
                             },
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     ),
                     (
@@ -703,6 +792,7 @@ mod tests {
                             },
                             attributes: vec![],
                             default: None,
+                            replication: None,
                         }
                     )
                 ]),
@@ -12,6 +12,16 @@ pub struct Message {
     /// Only applicable to messages defined in the `ambient_core` schema. Intentionally undocumented.
     #[serde(default)]
     pub as_module_message: bool,
+    /// How the message is delivered when it is sent over the network without specifying how.
+    #[serde(default)]
+    pub reliability: Option<MessageReliability>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum MessageReliability {
+    ReliableOrdered,
+    ReliableUnordered,
+    UnreliableSequenced,
 }
 
 #[cfg(test)]
@@ -38,4 +48,22 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn can_parse_message_reliability() {
+        let t = r#"
+        reliability = "UnreliableSequenced"
+        [fields]
+        a = "Vec3"
+        "#;
+
+        let message: Message = toml::from_str(t).unwrap();
+        assert_eq!(
+            message.reliability,
+            Some(MessageReliability::UnreliableSequenced)
+        );
+
+        let message: Message = toml::from_str("[fields]").unwrap();
+        assert_eq!(message.reliability, None);
+    }
 }
@@ -88,10 +88,26 @@ pub fn generate(
                         .map(|s| make_path(s.as_str()))
                         .collect();
                     let description = component.description.to_owned().unwrap_or_default();
+                    let replicated = component.replication.map(|replication| {
+                        let option = |value: Option<f32>| match value {
+                            Some(value) => quote! { Some(#value) },
+                            None => quote! { None },
+                        };
+                        let rate = option(replication.rate);
+                        let quantization = option(replication.quantization);
+                        let priority = make_path(&format!("{:?}", replication.priority));
+                        quote! {
+                            crate::Replicated[crate::Replicated {
+                                rate: #rate,
+                                priority: crate::ReplicationPriority::#priority,
+                                quantization: #quantization,
+                            }],
+                        }
+                    });
 
                     Ok(quote! {
                         #[doc = #doc_comment]
-                        @[#(#attributes,)* Name[#name], Description[#description]]
+                        @[#(#attributes,)* #replicated Name[#name], Description[#description]]
                         #ident: #ty,
                     })
                 }
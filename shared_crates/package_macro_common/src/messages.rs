@@ -79,6 +79,15 @@ pub fn generate(
                     quote! { RuntimeMessage }
                 };
 
+            let reliability_impl = message.reliability.map(|reliability| {
+                let reliability = make_path(&format!("{reliability:?}"));
+                quote! {
+                    fn reliability() -> Reliability {
+                        Reliability::#reliability
+                    }
+                }
+            });
+
             let struct_definition = if message.fields.is_empty() {
                 quote! {
                     pub struct #struct_name;
@@ -120,6 +129,7 @@ pub fn generate(
                             #(#deserialize_fields,)*
                         })
                     }
+                    #reliability_impl
                 }
                 impl #message_impl for #struct_name {}
                 #default_impl
@@ -133,7 +143,7 @@ pub fn generate(
 
     let inner = match context {
         Context::Host => quote! {
-            use ambient_package_rt::message_serde::{Message, MessageSerde, MessageSerdeError, RuntimeMessage, ModuleMessage};
+            use ambient_package_rt::message_serde::{Message, MessageSerde, MessageSerdeError, Reliability, RuntimeMessage, ModuleMessage};
             use glam::{Vec2, Vec3, Vec4, UVec2, UVec3, UVec4, Mat4, Quat};
             use crate::{EntityId, Entity};
             #(#messages)*
@@ -142,7 +152,7 @@ pub fn generate(
         Context::GuestApi | Context::GuestUser => {
             let api_path = context.guest_api_path().unwrap();
             quote! {
-                use #api_path::{prelude::*, message::{Message, MessageSerde, MessageSerdeError, RuntimeMessage, ModuleMessage}};
+                use #api_path::{prelude::*, message::{Message, MessageSerde, MessageSerdeError, Reliability, RuntimeMessage, ModuleMessage}};
                 #(#messages)*
             }
         }
@@ -387,12 +387,31 @@ fn deserialize_array<T: MessageSerde>(input: &mut dyn Read) -> Result<Vec<T>, Me
     Ok(data)
 }
 
+/// How a message is delivered when it is sent to the other side of the network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Reliability {
+    /// Guaranteed to be received, after the ordered messages that were sent before it.
+    #[default]
+    ReliableOrdered,
+    /// Guaranteed to be received, but possibly before messages that were sent before it.
+    ReliableUnordered,
+    /// Not guaranteed to be received, and dropped if a newer message of the same type has
+    /// already been received. Must be below one kilobyte.
+    UnreliableSequenced,
+}
+
 /// Implemented on all types that can be de/serialized from/to a `Vec<u8>`.
 pub trait Message: Sized {
     /// The identifier of this message.
     #[doc(hidden)]
     fn id() -> &'static str;
 
+    /// How this message is delivered when it is sent to the other side of the network without
+    /// specifying how. Set with `reliability` in the manifest.
+    fn reliability() -> Reliability {
+        Reliability::default()
+    }
+
     /// Serialize this to a `Vec<u8>`.
     #[doc(hidden)]
     fn serialize_message(&self) -> Result<Vec<u8>, MessageSerdeError>;
@@ -1,4 +1,4 @@
-use ambient_package::ItemPathBuf;
+use ambient_package::{ComponentReplication, ItemPathBuf};
 use anyhow::Context as AnyhowContext;
 
 use crate::{
@@ -15,6 +15,7 @@ pub struct Component {
     pub type_: ResolvableItemId<Type>,
     pub attributes: Vec<ResolvableItemId<Attribute>>,
     pub default: Option<ResolvableValue>,
+    pub replication: Option<ComponentReplication>,
 
     resolved: bool,
 }
@@ -117,6 +118,7 @@ impl Component {
                 .default
                 .as_ref()
                 .map(|v| ResolvableValue::Unresolved(v.clone())),
+            replication: value.replication,
             resolved: false,
         }
     }
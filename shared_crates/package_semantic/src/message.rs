@@ -1,4 +1,4 @@
-use ambient_package::{ItemPathBuf, SnakeCaseIdentifier};
+use ambient_package::{ItemPathBuf, MessageReliability, SnakeCaseIdentifier};
 use anyhow::Context as AnyhowContext;
 use indexmap::IndexMap;
 
@@ -13,6 +13,7 @@ pub struct Message {
     pub description: Option<String>,
     pub fields: IndexMap<SnakeCaseIdentifier, ResolvableItemId<Type>>,
     pub as_module_message: bool,
+    pub reliability: Option<MessageReliability>,
 
     resolved: bool,
 }
@@ -84,6 +85,7 @@ impl Message {
                 .map(|(k, v)| (k.clone(), ResolvableItemId::Unresolved(v.clone())))
                 .collect(),
             as_module_message: value.as_module_message,
+            reliability: value.reliability,
             resolved: false,
         }
     }
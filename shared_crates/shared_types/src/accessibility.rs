@@ -0,0 +1,83 @@
+//! The roles that UI entities can have for assistive technology, like screen readers.
+//!
+//! A role is stored in the `accessibility_role` component as its snake case name, like
+//! `menu_item`, so that packages can set it without this type.
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// What an entity is to assistive technology, which decides how it's read and used.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumString, Display, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum AccessibilityRole {
+    /// An important message, which is announced as soon as it's shown.
+    Alert,
+    /// A button, which is activated.
+    Button,
+    /// A checkbox, which is toggled.
+    Checkbox,
+    /// A dialog, which is on top of the rest of the UI.
+    Dialog,
+    /// A group of related entities.
+    Group,
+    /// The heading of a section.
+    Heading,
+    /// An image; it should have an `accessibility_label`.
+    Image,
+    /// A link to another screen or page.
+    Link,
+    /// A list of `ListItem`s.
+    List,
+    /// An item of a `List`.
+    ListItem,
+    /// A menu of `MenuItem`s.
+    Menu,
+    /// An item of a `Menu`, which is activated.
+    MenuItem,
+    /// The progress of a task, like loading.
+    ProgressBar,
+    /// One of a group of options, which is picked.
+    RadioButton,
+    /// A value picked in a range.
+    Slider,
+    /// A status message, which is announced when the user isn't busy.
+    Status,
+    /// A tab of a `TabList`.
+    Tab,
+    /// A list of `Tab`s.
+    TabList,
+    /// Text.
+    Text,
+    /// An editable text field.
+    TextInput,
+}
+impl AccessibilityRole {
+    /// Whether an entity with this role is named by the text of its children, which aren't read
+    /// on their own, like the label of a button.
+    pub fn is_named_by_content(&self) -> bool {
+        matches!(
+            self,
+            Self::Button
+                | Self::Checkbox
+                | Self::Heading
+                | Self::Link
+                | Self::ListItem
+                | Self::MenuItem
+                | Self::RadioButton
+                | Self::Tab
+        )
+    }
+
+    /// Whether an entity with this role is activated like a button, when it's focusable.
+    pub fn is_activatable(&self) -> bool {
+        matches!(
+            self,
+            Self::Button
+                | Self::Checkbox
+                | Self::Link
+                | Self::ListItem
+                | Self::MenuItem
+                | Self::RadioButton
+                | Self::Tab
+        )
+    }
+}
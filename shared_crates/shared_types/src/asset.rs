@@ -29,4 +29,13 @@ pub enum AssetType {
     /// Represents a vorbis backed file
     VorbisTrack,
     SoundGraph,
+
+    /// An HDR environment map, prefiltered for image-based lighting
+    EnvironmentMap,
+    /// Images packed into a single texture, for the UI
+    TextureAtlas,
+    /// The strings of each locale, for translating text
+    StringTables,
+    /// A navigation mesh, for finding paths
+    Navmesh,
 }
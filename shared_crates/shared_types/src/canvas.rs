@@ -0,0 +1,206 @@
+//! Types for 2D drawings made of paths, images and text, which the runtime draws onto UI rects.
+use glam::{vec2, UVec2, Vec2, Vec4};
+
+/// A drawing command of a [CanvasDrawing].
+///
+/// Paths are built up with [CanvasCommand::MoveTo] and friends, and stay current until the next
+/// [CanvasCommand::BeginPath], so the same path can be both filled and stroked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasCommand {
+    /// Discards the current path.
+    BeginPath,
+    /// Starts a new sub-path at `point`.
+    MoveTo(Vec2),
+    /// Adds a line from the last point to `point`.
+    LineTo(Vec2),
+    /// Adds a quadratic curve from the last point to `point`.
+    QuadTo {
+        /// The control point of the curve.
+        control: Vec2,
+        /// The end of the curve.
+        point: Vec2,
+    },
+    /// Adds a cubic curve from the last point to `point`.
+    CubicTo {
+        /// The first control point of the curve.
+        control1: Vec2,
+        /// The second control point of the curve.
+        control2: Vec2,
+        /// The end of the curve.
+        point: Vec2,
+    },
+    /// Closes the current sub-path with a line back to its start.
+    ClosePath,
+    /// Fills the current path with a color.
+    Fill(Vec4),
+    /// Strokes the current path with a color.
+    Stroke {
+        /// The color of the stroke.
+        color: Vec4,
+        /// The width of the stroke, in pixels.
+        width: f32,
+    },
+    /// Draws the image at `url`, scaled to `size`.
+    Image {
+        /// The URL of the image.
+        url: String,
+        /// The top left corner of the image.
+        position: Vec2,
+        /// The size of the image, in pixels.
+        size: Vec2,
+    },
+    /// Draws a line of text in the default font.
+    Text {
+        /// The text to draw.
+        text: String,
+        /// The top left corner of the text.
+        position: Vec2,
+        /// The font size, in pixels.
+        size: f32,
+        /// The color of the text.
+        color: Vec4,
+    },
+}
+
+/// A 2D drawing, which is a list of [CanvasCommand]s drawn in order onto a surface of `size`
+/// pixels.
+///
+/// The origin is the top left corner of the surface, and colors are in sRGB like other UI colors.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CanvasDrawing {
+    /// The size of the surface, in pixels.
+    pub size: UVec2,
+    /// The commands to draw.
+    pub commands: Vec<CanvasCommand>,
+}
+impl CanvasDrawing {
+    /// Creates an empty drawing of `size` pixels.
+    pub fn new(size: UVec2) -> Self {
+        Self {
+            size,
+            commands: Vec::new(),
+        }
+    }
+    /// Adds a command to the drawing.
+    pub fn push(&mut self, command: CanvasCommand) -> &mut Self {
+        self.commands.push(command);
+        self
+    }
+    /// Discards the current path. See [CanvasCommand::BeginPath].
+    pub fn begin_path(&mut self) -> &mut Self {
+        self.push(CanvasCommand::BeginPath)
+    }
+    /// Starts a new sub-path at `point`. See [CanvasCommand::MoveTo].
+    pub fn move_to(&mut self, point: Vec2) -> &mut Self {
+        self.push(CanvasCommand::MoveTo(point))
+    }
+    /// Adds a line to `point`. See [CanvasCommand::LineTo].
+    pub fn line_to(&mut self, point: Vec2) -> &mut Self {
+        self.push(CanvasCommand::LineTo(point))
+    }
+    /// Adds a quadratic curve to `point`. See [CanvasCommand::QuadTo].
+    pub fn quad_to(&mut self, control: Vec2, point: Vec2) -> &mut Self {
+        self.push(CanvasCommand::QuadTo { control, point })
+    }
+    /// Adds a cubic curve to `point`. See [CanvasCommand::CubicTo].
+    pub fn cubic_to(&mut self, control1: Vec2, control2: Vec2, point: Vec2) -> &mut Self {
+        self.push(CanvasCommand::CubicTo {
+            control1,
+            control2,
+            point,
+        })
+    }
+    /// Closes the current sub-path. See [CanvasCommand::ClosePath].
+    pub fn close_path(&mut self) -> &mut Self {
+        self.push(CanvasCommand::ClosePath)
+    }
+    /// Fills the current path. See [CanvasCommand::Fill].
+    pub fn fill(&mut self, color: Vec4) -> &mut Self {
+        self.push(CanvasCommand::Fill(color))
+    }
+    /// Strokes the current path. See [CanvasCommand::Stroke].
+    pub fn stroke(&mut self, color: Vec4, width: f32) -> &mut Self {
+        self.push(CanvasCommand::Stroke { color, width })
+    }
+    /// Draws an image. See [CanvasCommand::Image].
+    pub fn image(&mut self, url: impl Into<String>, position: Vec2, size: Vec2) -> &mut Self {
+        self.push(CanvasCommand::Image {
+            url: url.into(),
+            position,
+            size,
+        })
+    }
+    /// Draws text. See [CanvasCommand::Text].
+    pub fn text(
+        &mut self,
+        text: impl Into<String>,
+        position: Vec2,
+        size: f32,
+        color: Vec4,
+    ) -> &mut Self {
+        self.push(CanvasCommand::Text {
+            text: text.into(),
+            position,
+            size,
+            color,
+        })
+    }
+
+    /// Adds a line from `from` to `to` as a new sub-path.
+    pub fn line(&mut self, from: Vec2, to: Vec2) -> &mut Self {
+        self.move_to(from).line_to(to)
+    }
+    /// Adds a rectangle as a new sub-path.
+    pub fn rect(&mut self, position: Vec2, size: Vec2) -> &mut Self {
+        self.move_to(position)
+            .line_to(position + vec2(size.x, 0.))
+            .line_to(position + size)
+            .line_to(position + vec2(0., size.y))
+            .close_path()
+    }
+    /// Adds a circle as a new sub-path.
+    pub fn circle(&mut self, center: Vec2, radius: f32) -> &mut Self {
+        self.move_to(center + vec2(radius, 0.))
+            .arc(center, radius, 0., std::f32::consts::TAU)
+            .close_path()
+    }
+    /// Adds an arc of a circle from angle `start` to `end`, in radians, clockwise from the
+    /// positive X axis.
+    ///
+    /// Continues the current sub-path with a line to the start of the arc, or starts a new one
+    /// if there is none.
+    pub fn arc(&mut self, center: Vec2, radius: f32, start: f32, end: f32) -> &mut Self {
+        let point = |angle: f32| center + Vec2::from_angle(angle) * radius;
+        let continues = matches!(
+            self.commands.last(),
+            Some(
+                CanvasCommand::MoveTo(_)
+                    | CanvasCommand::LineTo(_)
+                    | CanvasCommand::QuadTo { .. }
+                    | CanvasCommand::CubicTo { .. }
+            )
+        );
+        if continues {
+            self.line_to(point(start));
+        } else {
+            self.move_to(point(start));
+        }
+
+        // Approximate the arc with cubic curves of at most a quarter turn each
+        let segments = ((end - start).abs() / std::f32::consts::FRAC_PI_2)
+            .ceil()
+            .max(1.);
+        let step = (end - start) / segments;
+        let handle = 4. / 3. * (step / 4.).tan() * radius;
+        for i in 0..segments as usize {
+            let from = start + step * i as f32;
+            let to = from + step;
+            self.cubic_to(
+                point(from) + Vec2::from_angle(from).perp() * handle,
+                point(to) - Vec2::from_angle(to).perp() * handle,
+                point(to),
+            );
+        }
+        self
+    }
+}
@@ -4,7 +4,11 @@ pub use crate::winit::*;
 mod procedurals;
 pub use crate::procedurals::*;
 
+pub mod accessibility;
 pub mod asset;
+pub mod canvas;
+pub mod localization;
+pub mod navmesh;
 pub mod urls;
 
 pub type ComponentIndex = u32;
@@ -0,0 +1,315 @@
+//! Types for translating text: the string tables written by the asset pipeline, and the lookup
+//! and formatting of their strings, which the runtime and the UI share.
+//!
+//! A string is looked up by its key, like `menu.play`. Strings can have `{name}` arguments, and a
+//! string with plural forms has a key for each of its plural categories, like `items.one` and
+//! `items.other`; the form is picked by the `count` argument.
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// The locale used for the strings a locale doesn't have.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// The string tables of a package, as written by the `StringTables` asset pipeline.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringTables {
+    /// The strings of each locale, by key.
+    pub locales: BTreeMap<String, BTreeMap<String, String>>,
+}
+impl StringTables {
+    /// Merges the strings of `locale` from all of `tables`. Strings the locale doesn't have are
+    /// taken from its less specific locales, like `fr` for `fr-CA`, and then [DEFAULT_LOCALE].
+    pub fn merge<'a>(
+        tables: impl IntoIterator<Item = &'a StringTables> + Clone,
+        locale: &str,
+    ) -> HashMap<String, String> {
+        let mut strings = HashMap::new();
+        for fallback in locale_fallbacks(locale).iter().rev() {
+            for table in tables.clone() {
+                if let Some(table_strings) = table.locales.get(fallback) {
+                    strings.extend(
+                        table_strings
+                            .iter()
+                            .map(|(key, value)| (key.clone(), value.clone())),
+                    );
+                }
+            }
+        }
+        strings
+    }
+}
+
+/// Returns the locales whose strings are used for `locale`, most specific first: `fr-CA` uses
+/// `fr-CA`, then `fr`, then [DEFAULT_LOCALE]. Accepts POSIX locales, like `fr_CA.UTF-8`.
+pub fn locale_fallbacks(locale: &str) -> Vec<String> {
+    let locale = normalize_locale(locale);
+    let mut fallbacks = Vec::new();
+    let mut rest = locale.as_str();
+    while !rest.is_empty() {
+        fallbacks.push(rest.to_string());
+        rest = rest
+            .rsplit_once('-')
+            .map(|(rest, _)| rest)
+            .unwrap_or_default();
+    }
+    if !fallbacks.iter().any(|fallback| fallback == DEFAULT_LOCALE) {
+        fallbacks.push(DEFAULT_LOCALE.to_string());
+    }
+    fallbacks
+}
+
+/// Converts a locale to the form used by string tables, like `fr-CA`. POSIX locales, like
+/// `fr_CA.UTF-8`, lose their encoding, and the `C` and `POSIX` locales become [DEFAULT_LOCALE].
+pub fn normalize_locale(locale: &str) -> String {
+    let locale = locale.split(['.', '@']).next().unwrap_or_default().trim();
+    if locale.is_empty() || locale == "C" || locale == "POSIX" {
+        return DEFAULT_LOCALE.to_string();
+    }
+    locale.replace('_', "-")
+}
+
+/// The plural categories of the Unicode CLDR, which languages pick the form of a string with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    /// Used for zero in some languages, like Arabic.
+    Zero,
+    /// Used for one in most languages.
+    One,
+    /// Used for two in some languages, like Arabic.
+    Two,
+    /// Used for small numbers in some languages, like 2 to 4 in Polish.
+    Few,
+    /// Used for large numbers in some languages, like 5 to 21 in Russian.
+    Many,
+    /// Used for everything else; every string with plural forms has it.
+    Other,
+}
+impl PluralCategory {
+    /// All the categories.
+    pub const ALL: [PluralCategory; 6] = [
+        Self::Zero,
+        Self::One,
+        Self::Two,
+        Self::Few,
+        Self::Many,
+        Self::Other,
+    ];
+
+    /// The name of the category, which is the last part of the key of a plural form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Returns the plural category of `count` in the language of `locale`.
+///
+/// Covers the integer rules of the most common languages; other languages use the English rule.
+pub fn plural_category(locale: &str, count: i64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let locale = normalize_locale(locale);
+    let language = locale.split('-').next().unwrap_or_default();
+    let n = count.unsigned_abs();
+    let (n10, n100) = (n % 10, n % 100);
+    match language {
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" => Other,
+        "fr" | "pt" | "hi" => {
+            if n <= 1 {
+                One
+            } else {
+                Other
+            }
+        }
+        "ru" | "uk" | "be" => {
+            if n10 == 1 && n100 != 11 {
+                One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                Few
+            } else {
+                Many
+            }
+        }
+        "pl" => {
+            if n == 1 {
+                One
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                Few
+            } else {
+                Many
+            }
+        }
+        "cs" | "sk" => match n {
+            1 => One,
+            2..=4 => Few,
+            _ => Other,
+        },
+        "ar" => match (n, n100) {
+            (0, _) => Zero,
+            (1, _) => One,
+            (2, _) => Two,
+            (_, 3..=10) => Few,
+            (_, 11..=99) => Many,
+            _ => Other,
+        },
+        _ => {
+            if n == 1 {
+                One
+            } else {
+                Other
+            }
+        }
+    }
+}
+
+/// Replaces the `{name}` arguments of `template` with their values in `args`. Arguments without a
+/// value are left as they are, and `{{` and `}}` are replaced with `{` and `}`.
+pub fn format_string(template: &str, args: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            output.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        if rest.starts_with('}') {
+            output.push('}');
+            rest = &rest[1..];
+            continue;
+        }
+        let name = rest[1..].find('}').map(|end| &rest[1..end + 1]);
+        match name.and_then(|name| args.iter().find(|(arg, _)| *arg == name)) {
+            Some((name, value)) => {
+                output.push_str(value);
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                output.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// The strings of a locale, merged from all the loaded string tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Translations {
+    /// The locale of the strings.
+    pub locale: String,
+    /// The strings, by key.
+    pub strings: HashMap<String, String>,
+}
+impl Translations {
+    /// Creates translations for `locale` from its strings.
+    pub fn new(locale: impl Into<String>, strings: HashMap<String, String>) -> Self {
+        Self {
+            locale: locale.into(),
+            strings,
+        }
+    }
+
+    /// Returns the string with `key`, formatted with `args`. If there's a `count` argument, the
+    /// plural form of the string for it is used. Returns the key if there's no such string, so
+    /// missing strings stand out.
+    pub fn get(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let count = args
+            .iter()
+            .find(|(name, _)| *name == "count")
+            .and_then(|(_, count)| count.trim().parse::<i64>().ok());
+        let plural = count.and_then(|count| {
+            let category = plural_category(&self.locale, count);
+            self.strings
+                .get(&format!("{key}.{}", category.as_str()))
+                .or_else(|| self.strings.get(&format!("{key}.other")))
+        });
+        match plural.or_else(|| self.strings.get(key)) {
+            Some(template) => format_string(template, args),
+            None => key.to_string(),
+        }
+    }
+
+    /// Returns true if there's a string with `key`, or plural forms of it.
+    pub fn contains(&self, key: &str) -> bool {
+        self.strings.contains_key(key) || self.strings.contains_key(&format!("{key}.other"))
+    }
+}
+
+/// Parses arguments written as `name=value`, like the ones of the `translation_args` component.
+/// Arguments without a `=` have an empty value.
+pub fn parse_args(args: &[String]) -> Vec<(&str, &str)> {
+    args.iter()
+        .map(|arg| arg.split_once('=').unwrap_or((arg, "")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_fallbacks() {
+        assert_eq!(locale_fallbacks("fr_CA.UTF-8"), ["fr-CA", "fr", "en"]);
+        assert_eq!(locale_fallbacks("en-US"), ["en-US", "en"]);
+        assert_eq!(locale_fallbacks("C"), ["en"]);
+    }
+
+    #[test]
+    fn test_plural_categories() {
+        assert_eq!(plural_category("en", 1), PluralCategory::One);
+        assert_eq!(plural_category("en", 0), PluralCategory::Other);
+        assert_eq!(plural_category("fr", 0), PluralCategory::One);
+        assert_eq!(plural_category("ru", 21), PluralCategory::One);
+        assert_eq!(plural_category("ru", 12), PluralCategory::Many);
+        assert_eq!(plural_category("pl-PL", 22), PluralCategory::Few);
+        assert_eq!(plural_category("ja", 1), PluralCategory::Other);
+    }
+
+    #[test]
+    fn test_format_string() {
+        assert_eq!(
+            format_string(
+                "{name} has {count} {{coins}}",
+                &[("name", "Ada"), ("count", "3")]
+            ),
+            "Ada has 3 {coins}"
+        );
+        assert_eq!(format_string("{missing} }", &[]), "{missing} }");
+    }
+
+    #[test]
+    fn test_translations() {
+        let mut tables = StringTables::default();
+        tables.locales.insert(
+            "en".to_string(),
+            BTreeMap::from([
+                ("play".to_string(), "Play".to_string()),
+                ("quit".to_string(), "Quit".to_string()),
+                ("items.one".to_string(), "{count} item".to_string()),
+                ("items.other".to_string(), "{count} items".to_string()),
+            ]),
+        );
+        tables.locales.insert(
+            "fr".to_string(),
+            BTreeMap::from([("play".to_string(), "Jouer".to_string())]),
+        );
+
+        let translations = Translations::new("fr-CA", StringTables::merge([&tables], "fr-CA"));
+        assert_eq!(translations.get("play", &[]), "Jouer");
+        assert_eq!(translations.get("quit", &[]), "Quit");
+        assert_eq!(translations.get("items", &[("count", "0")]), "0 item");
+        assert_eq!(translations.get("items", &[("count", "2")]), "2 items");
+        assert_eq!(translations.get("missing", &[]), "missing");
+    }
+}
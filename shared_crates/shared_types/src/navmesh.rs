@@ -0,0 +1,481 @@
+//! Navigation meshes, as baked by the `Navmeshes` asset pipeline, and finding paths on them.
+//!
+//! A navmesh is made of convex polygons covering the surfaces an agent can walk on, with the
+//! walls and ledges already pushed back by the radius of the agent, so a path can be followed by
+//! the center of the agent. Up is `+Z`.
+use std::{cmp::Ordering, collections::BinaryHeap, fmt};
+
+use glam::{Vec3, Vec3Swizzles};
+
+/// A navigation mesh, as baked by the `Navmeshes` asset pipeline.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Navmesh {
+    pub vertices: Vec<Vec3>,
+    pub polygons: Vec<NavmeshPolygon>,
+}
+
+/// A convex polygon of a [Navmesh].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NavmeshPolygon {
+    /// The indices of the vertices of the polygon, counter-clockwise when seen from above.
+    pub vertices: Vec<u32>,
+    /// The polygon on the other side of each edge, if any. The edge `i` goes from the vertex `i`
+    /// to the vertex `i + 1`.
+    pub neighbours: Vec<Option<u32>>,
+}
+
+/// A point on a [Navmesh].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavmeshPoint {
+    /// The index of the polygon the point is on.
+    pub polygon: u32,
+    pub position: Vec3,
+}
+
+/// The error returned when a navmesh file can't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavmeshError {
+    /// The file isn't a navmesh, or was written by an unsupported version of the pipeline.
+    InvalidHeader,
+    /// The file ends before the end of the navmesh.
+    UnexpectedEnd,
+    /// A polygon references a vertex or a polygon that doesn't exist.
+    InvalidIndex,
+}
+impl fmt::Display for NavmeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "Not a navmesh, or an unsupported version"),
+            Self::UnexpectedEnd => write!(f, "The navmesh ends unexpectedly"),
+            Self::InvalidIndex => write!(f, "The navmesh has an invalid index"),
+        }
+    }
+}
+impl std::error::Error for NavmeshError {}
+
+impl Navmesh {
+    /// The extension of navmesh files.
+    pub const EXTENSION: &'static str = "navmesh";
+    const MAGIC: &'static [u8; 4] = b"ANAV";
+    const VERSION: u32 = 1;
+    const NO_NEIGHBOUR: u32 = u32::MAX;
+
+    /// Writes the navmesh to the binary format of navmesh files.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+            bytes.extend(value.to_le_bytes());
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend(Self::MAGIC);
+        write_u32(&mut bytes, Self::VERSION);
+        write_u32(&mut bytes, self.vertices.len() as u32);
+        for vertex in &self.vertices {
+            for value in vertex.to_array() {
+                bytes.extend(value.to_le_bytes());
+            }
+        }
+        write_u32(&mut bytes, self.polygons.len() as u32);
+        for polygon in &self.polygons {
+            write_u32(&mut bytes, polygon.vertices.len() as u32);
+            for &vertex in &polygon.vertices {
+                write_u32(&mut bytes, vertex);
+            }
+            for neighbour in &polygon.neighbours {
+                write_u32(&mut bytes, neighbour.unwrap_or(Self::NO_NEIGHBOUR));
+            }
+        }
+        bytes
+    }
+
+    /// Reads a navmesh from the binary format of navmesh files.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, NavmeshError> {
+        let mut rest = bytes;
+        let mut read_u32 = || -> Result<u32, NavmeshError> {
+            if rest.len() < 4 {
+                return Err(NavmeshError::UnexpectedEnd);
+            }
+            let (value, tail) = rest.split_at(4);
+            rest = tail;
+            Ok(u32::from_le_bytes(value.try_into().unwrap()))
+        };
+        if read_u32()?.to_le_bytes() != *Self::MAGIC || read_u32()? != Self::VERSION {
+            return Err(NavmeshError::InvalidHeader);
+        }
+
+        let vertex_count = read_u32()?;
+        let mut vertices = Vec::new();
+        for _ in 0..vertex_count {
+            let mut vertex = [0.; 3];
+            for value in &mut vertex {
+                *value = f32::from_bits(read_u32()?);
+            }
+            vertices.push(Vec3::from_array(vertex));
+        }
+
+        let polygon_count = read_u32()?;
+        let mut polygons = Vec::new();
+        for _ in 0..polygon_count {
+            let len = read_u32()?;
+            let vertices = (0..len)
+                .map(|_| read_u32())
+                .collect::<Result<Vec<_>, _>>()?;
+            let neighbours = (0..len)
+                .map(|_| {
+                    read_u32()
+                        .map(|neighbour| (neighbour != Self::NO_NEIGHBOUR).then_some(neighbour))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            polygons.push(NavmeshPolygon {
+                vertices,
+                neighbours,
+            });
+        }
+
+        let navmesh = Self { vertices, polygons };
+        let is_valid = navmesh.polygons.iter().all(|polygon| {
+            polygon.vertices.len() >= 3
+                && polygon
+                    .vertices
+                    .iter()
+                    .all(|&vertex| (vertex as usize) < navmesh.vertices.len())
+                && polygon
+                    .neighbours
+                    .iter()
+                    .flatten()
+                    .all(|&neighbour| (neighbour as usize) < navmesh.polygons.len())
+        });
+        if !is_valid {
+            return Err(NavmeshError::InvalidIndex);
+        }
+        Ok(navmesh)
+    }
+
+    /// The closest point to `point` on the navmesh, or `None` if the navmesh is empty.
+    pub fn closest_point(&self, point: Vec3) -> Option<NavmeshPoint> {
+        let mut closest: Option<(f32, NavmeshPoint)> = None;
+        for (index, polygon) in self.polygons.iter().enumerate() {
+            let position = self.closest_point_on_polygon(polygon, point);
+            let distance = position.distance_squared(point);
+            if closest.map_or(true, |(closest, _)| distance < closest) {
+                closest = Some((
+                    distance,
+                    NavmeshPoint {
+                        polygon: index as u32,
+                        position,
+                    },
+                ));
+            }
+        }
+        closest.map(|(_, point)| point)
+    }
+
+    /// The shortest path from `start` to `end` on the navmesh, as the points where it turns,
+    /// starting at the closest point to `start` on the navmesh and ending at the closest point
+    /// to `end`. Returns `None` if there is no path between them.
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let start = self.closest_point(start)?;
+        let end = self.closest_point(end)?;
+        let corridor = self.find_corridor(start, end)?;
+
+        // The portals are the edges between the polygons of the corridor, as their left and
+        // right vertices when walking through them
+        let mut portals = vec![(start.position, start.position)];
+        for pair in corridor.windows(2) {
+            let polygon = &self.polygons[pair[0] as usize];
+            let edge = polygon
+                .neighbours
+                .iter()
+                .position(|&neighbour| neighbour == Some(pair[1]))?;
+            let right = polygon.vertices[edge];
+            let left = polygon.vertices[(edge + 1) % polygon.vertices.len()];
+            portals.push((self.vertices[left as usize], self.vertices[right as usize]));
+        }
+        portals.push((end.position, end.position));
+        Some(string_pull(&portals))
+    }
+
+    /// The polygons an agent walks through from `start` to `end`, found with A*
+    fn find_corridor(&self, start: NavmeshPoint, end: NavmeshPoint) -> Option<Vec<u32>> {
+        #[derive(PartialEq)]
+        struct Node {
+            estimate: f32,
+            polygon: u32,
+        }
+        impl Eq for Node {}
+        impl Ord for Node {
+            fn cmp(&self, other: &Self) -> Ordering {
+                other.estimate.total_cmp(&self.estimate)
+            }
+        }
+        impl PartialOrd for Node {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        // Each polygon is entered at the middle of the edge it is entered through
+        let mut entries = vec![None::<(f32, Vec3, u32)>; self.polygons.len()];
+        entries[start.polygon as usize] = Some((0., start.position, start.polygon));
+        let mut open = BinaryHeap::new();
+        open.push(Node {
+            estimate: start.position.distance(end.position),
+            polygon: start.polygon,
+        });
+        let mut closed = vec![false; self.polygons.len()];
+        while let Some(Node { polygon, .. }) = open.pop() {
+            if polygon == end.polygon {
+                let mut corridor = vec![polygon];
+                let mut current = polygon;
+                while current != start.polygon {
+                    current = entries[current as usize].unwrap().2;
+                    corridor.push(current);
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            if std::mem::replace(&mut closed[polygon as usize], true) {
+                continue;
+            }
+
+            let (cost, position, _) = entries[polygon as usize].unwrap();
+            let data = &self.polygons[polygon as usize];
+            for (edge, neighbour) in data.neighbours.iter().enumerate() {
+                let Some(neighbour) = *neighbour else {
+                    continue;
+                };
+                if closed[neighbour as usize] {
+                    continue;
+                }
+                let a = self.vertices[data.vertices[edge] as usize];
+                let b = self.vertices[data.vertices[(edge + 1) % data.vertices.len()] as usize];
+                let entry = (a + b) / 2.;
+                let entry_cost = cost + position.distance(entry);
+                if entries[neighbour as usize].map_or(true, |(cost, _, _)| entry_cost < cost) {
+                    entries[neighbour as usize] = Some((entry_cost, entry, polygon));
+                    open.push(Node {
+                        estimate: entry_cost + entry.distance(end.position),
+                        polygon: neighbour,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn closest_point_on_polygon(&self, polygon: &NavmeshPolygon, point: Vec3) -> Vec3 {
+        let vertex = |index: usize| self.vertices[polygon.vertices[index] as usize];
+        (1..polygon.vertices.len() - 1)
+            .map(|i| closest_point_on_triangle(point, vertex(0), vertex(i), vertex(i + 1)))
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+            .unwrap_or(point)
+    }
+}
+
+/// The shortest path through `portals`, as pairs of left and right points, with the simple
+/// stupid funnel algorithm. The first and last portals are the start and the end of the path.
+fn string_pull(portals: &[(Vec3, Vec3)]) -> Vec<Vec3> {
+    // Positive when `c` is to the left of the line from `a` to `b`, when seen from above
+    fn cross(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+        (b.xy() - a.xy()).perp_dot(c.xy() - a.xy())
+    }
+    fn same(a: Vec3, b: Vec3) -> bool {
+        a.xy().distance_squared(b.xy()) < 1e-12
+    }
+
+    let (start, _) = portals[0];
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+    let mut i = 1;
+    while i < portals.len() {
+        let (portal_left, portal_right) = portals[i];
+
+        if cross(apex, right, portal_right) >= 0. {
+            if same(apex, right) || cross(apex, left, portal_right) < 0. {
+                right = portal_right;
+                right_index = i;
+            } else {
+                // The right side crossed over the left one, so the path turns at the left one
+                if path.last() != Some(&left) {
+                    path.push(left);
+                }
+                apex = left;
+                right = apex;
+                right_index = left_index;
+                i = left_index + 1;
+                continue;
+            }
+        }
+
+        if cross(apex, left, portal_left) <= 0. {
+            if same(apex, left) || cross(apex, right, portal_left) > 0. {
+                left = portal_left;
+                left_index = i;
+            } else {
+                if path.last() != Some(&right) {
+                    path.push(right);
+                }
+                apex = right;
+                left = apex;
+                left_index = right_index;
+                i = right_index + 1;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    let (end, _) = portals[portals.len() - 1];
+    if path.last().map_or(true, |&last| last != end) {
+        path.push(end);
+    }
+    path
+}
+
+/// The closest point to `p` on the triangle `abc`, from Real-Time Collision Detection
+fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0. && d2 <= 0. {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0. && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0. && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0. && (d4 - d3) >= 0. && (d5 - d6) >= 0. {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1. / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::vec3;
+
+    use super::*;
+
+    /// A U-shaped corridor of three squares, which goes east, north then west
+    fn u_shape() -> Navmesh {
+        let vertices = vec![
+            vec3(0., 0., 0.),
+            vec3(1., 0., 0.),
+            vec3(2., 0., 0.),
+            vec3(0., 1., 0.),
+            vec3(1., 1., 0.),
+            vec3(2., 1., 0.),
+            vec3(0., 2., 0.),
+            vec3(1., 2., 0.),
+            vec3(2., 2., 0.),
+        ];
+        let polygons = vec![
+            NavmeshPolygon {
+                vertices: vec![0, 1, 4, 3],
+                neighbours: vec![None, Some(1), None, None],
+            },
+            NavmeshPolygon {
+                vertices: vec![1, 2, 5, 8, 7, 4],
+                neighbours: vec![None, None, None, None, Some(2), Some(0)],
+            },
+            NavmeshPolygon {
+                vertices: vec![3, 4, 7, 6],
+                neighbours: vec![None, Some(1), None, None],
+            },
+        ];
+        Navmesh { vertices, polygons }
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let navmesh = u_shape();
+        let point = navmesh.closest_point(vec3(0.5, 0.5, 3.)).unwrap();
+        assert_eq!(point.polygon, 0);
+        assert_eq!(point.position, vec3(0.5, 0.5, 0.));
+
+        let point = navmesh.closest_point(vec3(-1., 1.5, 0.)).unwrap();
+        assert_eq!(point.polygon, 2);
+        assert_eq!(point.position, vec3(0., 1.5, 0.));
+    }
+
+    fn assert_path(path: Option<Vec<Vec3>>, expected: &[Vec3]) {
+        let path = path.expect("There is no path");
+        assert_eq!(path.len(), expected.len(), "{path:?}");
+        for (point, expected) in path.iter().zip(expected) {
+            assert!(point.abs_diff_eq(*expected, 1e-5), "{path:?}");
+        }
+    }
+
+    #[test]
+    fn test_find_path() {
+        let navmesh = u_shape();
+        assert_path(
+            navmesh.find_path(vec3(0.5, 0.5, 0.), vec3(0.5, 1.5, 0.)),
+            &[vec3(0.5, 0.5, 0.), vec3(1., 1., 0.), vec3(0.5, 1.5, 0.)],
+        );
+        assert_path(
+            navmesh.find_path(vec3(0.2, 0.5, 0.), vec3(0.8, 0.5, 0.)),
+            &[vec3(0.2, 0.5, 0.), vec3(0.8, 0.5, 0.)],
+        );
+        assert_path(
+            navmesh.find_path(vec3(0.5, 0.5, 0.), vec3(1.5, 0.9, 0.)),
+            &[vec3(0.5, 0.5, 0.), vec3(1.5, 0.9, 0.)],
+        );
+
+        let mut disconnected = u_shape();
+        disconnected.polygons[1].neighbours[4] = None;
+        disconnected.polygons[2].neighbours[1] = None;
+        assert_eq!(
+            disconnected.find_path(vec3(0.5, 0.5, 0.), vec3(0.5, 1.5, 0.)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let navmesh = u_shape();
+        assert_eq!(
+            Navmesh::from_bytes(&navmesh.to_bytes()),
+            Ok(navmesh.clone())
+        );
+        assert_eq!(
+            Navmesh::from_bytes(&navmesh.to_bytes()[..20]),
+            Err(NavmeshError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Navmesh::from_bytes(b"nope"),
+            Err(NavmeshError::InvalidHeader)
+        );
+    }
+}
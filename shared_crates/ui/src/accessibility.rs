@@ -0,0 +1,47 @@
+//! Describes the UI to assistive technology, like screen readers.
+//!
+//! The runtime builds an accessibility tree from the UI: entities with an [AccessibilityRole], and
+//! text, are read in the order of the element hierarchy. The built-in elements have roles already;
+//! [AccessibilityExt] sets them on custom elements.
+use ambient_element::Element;
+use ambient_guest_bridge::core::ui::components::{
+    accessibility_description, accessibility_hidden, accessibility_label, accessibility_live,
+    accessibility_role, accessibility_value,
+};
+pub use ambient_shared_types::accessibility::AccessibilityRole;
+
+/// Helpers for describing elements to assistive technology.
+pub trait AccessibilityExt {
+    /// Sets the role of this element, like [AccessibilityRole::Button].
+    fn with_role(self, role: AccessibilityRole) -> Self;
+    /// Sets the name that assistive technology reads for this element, instead of its text.
+    fn with_label(self, label: impl Into<String>) -> Self;
+    /// Sets a longer description, which is read after the label of this element.
+    fn with_description(self, description: impl Into<String>) -> Self;
+    /// Sets the value of this element, like the position of a slider.
+    fn with_value(self, value: impl Into<String>) -> Self;
+    /// Announces the changes of this element without it being focused, like a score in the HUD.
+    fn with_live_updates(self) -> Self;
+    /// Hides this element and its children from assistive technology, for decorations.
+    fn hidden_from_accessibility(self) -> Self;
+}
+impl AccessibilityExt for Element {
+    fn with_role(self, role: AccessibilityRole) -> Self {
+        self.with(accessibility_role(), role.to_string())
+    }
+    fn with_label(self, label: impl Into<String>) -> Self {
+        self.with(accessibility_label(), label.into())
+    }
+    fn with_description(self, description: impl Into<String>) -> Self {
+        self.with(accessibility_description(), description.into())
+    }
+    fn with_value(self, value: impl Into<String>) -> Self {
+        self.with(accessibility_value(), value.into())
+    }
+    fn with_live_updates(self) -> Self {
+        self.with(accessibility_live(), ())
+    }
+    fn hidden_from_accessibility(self) -> Self {
+        self.with(accessibility_hidden(), ())
+    }
+}
@@ -27,6 +27,7 @@ use ambient_guest_bridge::{
         rect::components::{border_color, border_radius, border_thickness},
         rendering::components::color,
         text::{components::font_style, types::FontStyle},
+        ui::components::focusable,
     },
     ecs::World,
     run_async,
@@ -37,11 +38,13 @@ use glam::*;
 use parking_lot::Mutex;
 
 use crate::{
-    default_theme::{cutout_color, primary_color, secondary_color},
+    accessibility::{AccessibilityExt, AccessibilityRole},
+    default_theme::{use_theme, Theme},
     dropdown::Tooltip,
     layout::{FlowColumn, FlowRow},
+    navigation::{use_focus_activated, use_focus_visible},
     text::Text,
-    use_keyboard_input, UIBase, UIElement, UIExt,
+    use_focus, use_keyboard_input, UIBase, UIElement, UIExt,
 };
 
 #[derive(Clone, Debug)]
@@ -91,11 +94,13 @@ impl ButtonStyle {
     #[allow(clippy::too_many_arguments)]
     fn create_container(
         &self,
+        theme: &Theme,
         is_pressed: bool,
         is_working: bool,
         disabled: bool,
         toggled: bool,
         hover: bool,
+        focused: bool,
         hotkey: Option<VirtualKeyCode>,
         hotkey_modifier: ModifiersState,
         tooltip: Option<Element>,
@@ -104,26 +109,26 @@ impl ButtonStyle {
         let background = match self {
             ButtonStyle::Regular | ButtonStyle::Card => {
                 if is_pressed {
-                    cutout_color().lighten(0.1)
+                    theme.cutout_color.lighten(0.1)
                 } else if disabled || is_working {
-                    cutout_color()
+                    theme.cutout_color
                 } else if toggled {
-                    primary_color()
+                    theme.primary_color
                 } else if hover && *self == ButtonStyle::Card {
-                    cutout_color().lighten(0.05)
+                    theme.cutout_color.lighten(0.05)
                 } else {
-                    cutout_color()
+                    theme.cutout_color
                 }
             }
             ButtonStyle::Primary => {
                 if is_pressed {
-                    secondary_color().lighten(0.2)
+                    theme.secondary_color.lighten(0.2)
                 } else if disabled || is_working {
-                    secondary_color().desaturate(-1.)
+                    theme.secondary_color.desaturate(-1.)
                 } else if hover || disabled || is_working {
-                    secondary_color().lighten(0.1)
+                    theme.secondary_color.lighten(0.1)
                 } else {
-                    secondary_color()
+                    theme.secondary_color
                 }
             }
             ButtonStyle::Flat | ButtonStyle::Inline => Color::rgba(1., 1., 1., 0.0),
@@ -137,14 +142,14 @@ impl ButtonStyle {
                     Color::rgba(0.3, 0.3, 0.3, 1.)
                 } else if toggled {
                     if *self == Self::Flat || *self == Self::Inline {
-                        primary_color()
+                        theme.primary_color
                     } else {
                         Color::rgba(1., 1., 1., 1.)
                     }
                 } else if hover {
                     Color::rgba(0.8, 0.8, 0.8, 1.)
                 } else {
-                    Color::hex("B3B3B3").unwrap()
+                    theme.button_text_color
                 }
                 .into(),
             ),
@@ -172,6 +177,8 @@ impl ButtonStyle {
                     .with(margin(), vec4(2., 0., 0., 0.)),
             ])
             .with_background(background.into())
+            .with(border_thickness(), if focused { 2. } else { 0. })
+            .with(border_color(), theme.primary_color.into())
         } else {
             let content = content.with(font_style(), FontStyle::Bold);
             let tooltip = if let Some(hotkey) = hotkey {
@@ -182,7 +189,10 @@ impl ButtonStyle {
                 };
                 let hotkey = Text::el(format!("[{modifier}{hotkey:?}]"));
                 if let Some(tooltip) = tooltip {
-                    Some(FlowColumn::el([tooltip, hotkey]).with(space_between_items(), 10.))
+                    Some(
+                        FlowColumn::el([tooltip, hotkey])
+                            .with(space_between_items(), theme.spacing),
+                    )
                 } else {
                     Some(hotkey)
                 }
@@ -213,13 +223,13 @@ impl ButtonStyle {
                 .with(
                     border_radius(),
                     match self {
-                        Self::Card => Vec4::ONE * 3.,
-                        Self::Flat => Vec4::ONE * 3.,
-                        _ => Vec4::ONE * 26. / 2.,
+                        Self::Card => Vec4::ONE * theme.small_rounding,
+                        Self::Flat => Vec4::ONE * theme.small_rounding,
+                        _ => Vec4::ONE * theme.button_rounding,
                     },
                 )
-                .with(border_thickness(), 0.)
-                .with(border_color(), Color::WHITE.into());
+                .with(border_thickness(), if focused { 2. } else { 0. })
+                .with(border_color(), theme.primary_color.into());
             if *self != Self::Flat {
                 el = el.with(min_height(), 26.);
             }
@@ -287,18 +297,42 @@ pub fn Button(
         }
     });
 
+    // Buttons can be focused and activated without a mouse; they only show their focus then
+    let (focused, _) = use_focus(hooks);
+    let focus_visible = use_focus_visible(hooks);
+    use_focus_activated(hooks, hooks.instance_id().to_string(), {
+        to_owned![on_invoked, set_is_working];
+        move |world| {
+            if !disabled {
+                on_invoked.invoke(world, set_is_working.clone());
+            }
+        }
+    });
+
+    let theme = use_theme(hooks);
     let content = style
         .create_container(
+            &theme,
             is_pressed,
             is_working,
             disabled,
             toggled,
             hover,
+            focused && focus_visible,
             hotkey,
             hotkey_modifier,
             tooltip,
             content,
         )
+        .with(
+            focusable(),
+            if disabled {
+                String::new()
+            } else {
+                hooks.instance_id().to_string()
+            },
+        )
+        .with_role(AccessibilityRole::Button)
         .with_clickarea()
         .on_mouse_enter({
             to_owned![set_hover];
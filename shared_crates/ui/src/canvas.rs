@@ -0,0 +1,53 @@
+//! Implements a [Canvas], which shows a 2D drawing of paths, images and text.
+use ambient_element::{
+    element_component, to_owned, use_frame, use_ref_with, Element, ElementComponentExt, Hooks,
+};
+use ambient_guest_bridge::{
+    canvas,
+    core::{
+        layout::components::{height, width},
+        rect::components::background_color,
+    },
+    ecs::EntityId,
+};
+pub use ambient_shared_types::canvas::{CanvasCommand, CanvasDrawing};
+use glam::Vec4;
+
+use crate::{with_rect, UIBase};
+
+#[element_component]
+/// Shows `drawing`, sized to the drawing.
+///
+/// The drawing is redrawn whenever the canvas is rendered with a different one, so animated
+/// drawings, like gauges and graphs of live values, can be rendered from state that changes each
+/// frame. Like other rects, the canvas can have a `background_color` behind the drawing, and a
+/// `border_radius` to clip it.
+pub fn Canvas(
+    hooks: &mut Hooks,
+    /// The drawing to show.
+    drawing: CanvasDrawing,
+) -> Element {
+    let id = use_ref_with(hooks, |_| None::<EntityId>);
+    let pending = use_ref_with(hooks, |_| None::<CanvasDrawing>);
+    let size = drawing.size.as_vec2();
+    *pending.lock() = Some(drawing);
+
+    // The drawing can only be submitted once the rect has been spawned
+    use_frame(hooks, {
+        to_owned![id];
+        move |world| {
+            let Some(id) = *id.lock() else {
+                return;
+            };
+            if let Some(drawing) = pending.lock().take() {
+                canvas::draw(world, id, &drawing);
+            }
+        }
+    });
+
+    with_rect(UIBase.el())
+        .with(width(), size.x)
+        .with(height(), size.y)
+        .with(background_color(), Vec4::ZERO)
+        .on_spawned(move |_, spawned, _| *id.lock() = Some(spawned))
+}
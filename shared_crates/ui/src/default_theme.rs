@@ -1,9 +1,19 @@
-//! Defines the default theme for the UI.
-use glam::{vec4, Vec4};
+//! Defines the theme for the UI, and the default theme.
+//!
+//! All built-in widgets read their colors, fonts, spacing and corner radii from a [Theme]. Each
+//! package has its own current theme, which can be replaced with [Theme::set_current]; parts of
+//! the UI can also be given a different theme with [ThemeProvider], which can be changed at runtime.
+use glam::Vec4;
+use parking_lot::RwLock;
 
-use crate::UIExt;
+use crate::{
+    accessibility::{AccessibilityExt, AccessibilityRole},
+    UIExt,
+};
 use ambient_color::Color;
-use ambient_element::Element;
+use ambient_element::{
+    consume_context, element_component, provide_context, use_effect, Element, Hooks, Setter,
+};
 use ambient_guest_bridge::core::{
     layout::{
         components::{align_vertical, space_between_items},
@@ -14,37 +24,175 @@ use ambient_guest_bridge::core::{
     text::components::font_size,
 };
 
-/// The primary color.
+/// The colors, fonts, spacing and corner radii used by the built-in widgets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// The primary color, used for toggled and active elements.
+    pub primary_color: Color,
+    /// The secondary color, used for primary buttons.
+    pub secondary_color: Color,
+    /// The color used for the background of the app.
+    pub app_background_color: Color,
+    /// The error color.
+    pub error_color: Color,
+    /// The color of things that went well, like successful notifications.
+    pub success_color: Color,
+    /// The color of things that might be wrong, like warning notifications.
+    pub warning_color: Color,
+    /// A color slightly darker than the app background, used for buttons and inputs.
+    pub cutout_color: Color,
+    /// The color used for tooltip and dropdown backgrounds.
+    pub tooltip_background_color: Color,
+    /// The color used for the background of windows.
+    pub panel_background_color: Color,
+    /// The color used for the background of floating panels.
+    pub floating_panel_background_color: Color,
+    /// The color used for headers and text which stands out.
+    pub text_color: Color,
+    /// The default color of text.
+    pub body_text_color: Color,
+    /// The color used for small text.
+    pub small_text_color: Color,
+    /// The color used for error text.
+    pub error_text_color: Color,
+    /// The color used for the text of buttons.
+    pub button_text_color: Color,
+    /// The color used for selected text.
+    pub selection_color: Color,
+    /// The default font family; see the `font_family` component.
+    pub font_family: String,
+    /// The default font size.
+    pub font_size: f32,
+    /// The font size used for small text.
+    pub small_font_size: f32,
+    /// The font size used for section headers.
+    pub section_font_size: f32,
+    /// The font size used for headers.
+    pub header_font_size: f32,
+    /// The default margin/padding.
+    pub spacing: f32,
+    /// The rounding of corners of small elements, like dropdowns.
+    pub small_rounding: f32,
+    /// The rounding of corners of panels.
+    pub rounding: f32,
+    /// The rounding of corners of buttons.
+    pub button_rounding: f32,
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary_color: Color::hex("DE0B5D").unwrap(),
+            secondary_color: Color::hex("ffac04").unwrap(),
+            app_background_color: Color::hex("1B1B1B").unwrap(),
+            error_color: Color::hex("750631").unwrap(),
+            success_color: Color::hex("1E6B3A").unwrap(),
+            warning_color: Color::hex("8A5A00").unwrap(),
+            cutout_color: Color::hex("151515").unwrap(),
+            tooltip_background_color: Color::rgba(0., 0., 0., 0.9),
+            panel_background_color: Color::rgba(0., 0., 0., 0.5),
+            floating_panel_background_color: *Color::hex("1D1C22").unwrap().set_a(0.9),
+            text_color: Color::rgba(0.9, 0.9, 0.9, 1.),
+            body_text_color: Color::rgba(0.6, 0.6, 0.6, 1.),
+            small_text_color: Color::rgba(0.5, 0.5, 0.5, 1.),
+            error_text_color: Color::rgba(1., 0.5, 0.5, 1.),
+            button_text_color: Color::hex("B3B3B3").unwrap(),
+            selection_color: Color::rgba(0.2, 0.4, 0.8, 0.8),
+            font_family: "Default".to_string(),
+            font_size: 12.,
+            small_font_size: 10.,
+            section_font_size: 16.,
+            header_font_size: 25.,
+            spacing: STREET,
+            small_rounding: SMALL_ROUNDING,
+            rounding: 5.,
+            button_rounding: 13.,
+        }
+    }
+}
+
+static CURRENT_THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+impl Theme {
+    /// The theme of this package; the default theme unless it was replaced with [Theme::set_current].
+    pub fn current() -> Theme {
+        CURRENT_THEME.read().clone().unwrap_or_default()
+    }
+    /// Replaces the theme of this package.
+    ///
+    /// Elements which are already rendered keep their style until they are rendered again, so
+    /// this is best called before spawning the UI. Use [ThemeProvider] to change the theme of a
+    /// UI which is already on screen.
+    pub fn set_current(theme: Theme) {
+        *CURRENT_THEME.write() = Some(theme);
+    }
+}
+
+/// Provides `theme` to `child` and all its descendants, instead of the theme of the package.
+///
+/// Use [use_set_theme] in a descendant to change it at runtime; every widget under this provider
+/// is rendered again with the new theme.
+#[element_component]
+pub fn ThemeProvider(
+    hooks: &mut Hooks,
+    /// The theme to provide.
+    theme: Theme,
+    /// The element which gets the theme.
+    child: Element,
+) -> Element {
+    let set_theme = provide_context(hooks, || theme.clone());
+    // Rendering the provider again with another theme replaces the one set at runtime
+    use_effect(hooks, theme, move |_, theme| {
+        set_theme(theme.clone());
+        |_| {}
+    });
+    child
+}
+
+/// The theme used by this element: the one from the closest [ThemeProvider] above it, or the
+/// theme of the package if there's none.
+pub fn use_theme(hooks: &mut Hooks) -> Theme {
+    consume_context::<Theme>(hooks)
+        .map(|(theme, _)| theme)
+        .unwrap_or_else(Theme::current)
+}
+
+/// Returns a setter for the theme of the closest [ThemeProvider] above this element, or `None`
+/// if there's none.
+pub fn use_set_theme(hooks: &mut Hooks) -> Option<Setter<Theme>> {
+    consume_context::<Theme>(hooks).map(|(_, set_theme)| set_theme)
+}
+
+/// The primary color of the current theme.
 pub fn primary_color() -> Color {
-    Color::hex("DE0B5D").unwrap()
+    Theme::current().primary_color
 }
-/// The secondary color.
+/// The secondary color of the current theme.
 pub fn secondary_color() -> Color {
-    Color::hex("ffac04").unwrap()
+    Theme::current().secondary_color
 }
-/// The color used for the background of the app.
+/// The color used for the background of the app in the current theme.
 pub fn app_background_color() -> Color {
-    Color::hex("1B1B1B").unwrap()
+    Theme::current().app_background_color
 }
-/// The error color.
+/// The error color of the current theme.
 pub fn error_color() -> Color {
-    Color::hex("750631").unwrap()
+    Theme::current().error_color
 }
 /// A color slightly darker than [app_background_color].
 pub fn cutout_color() -> Color {
-    Color::hex("151515").unwrap()
+    Theme::current().cutout_color
 }
-/// The color used for tooltip backgrounds.
+/// The color used for tooltip backgrounds in the current theme.
 pub fn tooltip_background_color() -> Color {
-    Color::rgba(0., 0., 0., 0.9)
+    Theme::current().tooltip_background_color
 }
 
-/// Default margin/padding.
+/// Default margin/padding of the default theme; see [Theme::spacing].
 pub const STREET: f32 = 10.;
-/// Default rounding of corners.
+/// Default rounding of corners of the default theme; see [Theme::small_rounding].
 pub const SMALL_ROUNDING: f32 = 3.;
 
-/// A trait that adds some default styles to elements.
+/// A trait that adds some default styles to elements, from the theme of the package.
 pub trait StylesExt {
     /// Apply the default style for a section header.
     fn section_style(self) -> Self;
@@ -63,33 +211,38 @@ pub trait StylesExt {
 }
 impl StylesExt for Element {
     fn section_style(self) -> Self {
-        self.with(font_size(), 16.)
-            .with(color(), vec4(0.9, 0.9, 0.9, 1.))
+        let theme = Theme::current();
+        self.with(font_size(), theme.section_font_size)
+            .with(color(), theme.text_color.into())
     }
     fn header_style(self) -> Self {
-        self.with(font_size(), 25.)
-            .with(color(), vec4(0.9, 0.9, 0.9, 1.))
+        let theme = Theme::current();
+        self.with(font_size(), theme.header_font_size)
+            .with(color(), theme.text_color.into())
+            .with_role(AccessibilityRole::Heading)
     }
     fn small_style(self) -> Self {
-        self.with(font_size(), 10.)
-            .with(color(), vec4(0.5, 0.5, 0.5, 1.))
+        let theme = Theme::current();
+        self.with(font_size(), theme.small_font_size)
+            .with(color(), theme.small_text_color.into())
     }
     fn error_text_style(self) -> Self {
-        self.with(color(), vec4(1., 0.5, 0.5, 1.))
+        self.with(color(), Theme::current().error_text_color.into())
     }
-    #[allow(clippy::clone_on_copy)]
     fn floating_panel(self) -> Self {
-        self.with_background(Color::hex("1D1C22").unwrap().set_a(0.9).clone().into())
-            .with(border_radius(), Vec4::ONE * 5.)
-            .with_padding_even(STREET)
+        let theme = Theme::current();
+        self.with_background(theme.floating_panel_background_color.into())
+            .with(border_radius(), Vec4::ONE * theme.rounding)
+            .with_padding_even(theme.spacing)
     }
     fn panel(self) -> Self {
         self.with_background(Color::rgba(1., 1., 1., 0.01).into())
-            .with(border_radius(), Vec4::ONE * 5.)
+            .with(border_radius(), Vec4::ONE * Theme::current().rounding)
     }
     fn keyboard(self) -> Self {
-        self.with(space_between_items(), STREET)
-            .with_padding_even(STREET)
+        let spacing = Theme::current().spacing;
+        self.with(space_between_items(), spacing)
+            .with_padding_even(spacing)
             .with(align_vertical(), Align::Center)
     }
 }
@@ -0,0 +1,385 @@
+//! Implements drag and drop: a [DragSource] can be dragged onto a [DropTarget], which receives its [DragPayload].
+//!
+//! Only one element is dragged at a time. Elements can react to the drag with [use_drag].
+use std::{
+    any::{type_name, Any},
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use ambient_cb::{cb, Cb};
+use ambient_element::{
+    element_component, to_owned, use_frame, use_ref_with, use_runtime_message, use_spawn,
+    use_state, Element, ElementComponentExt, Hooks,
+};
+use ambient_guest_bridge::{
+    core::{
+        app::components::cursor_position,
+        layout::components::{height, width},
+        messages,
+        rect::components::{border_color, border_thickness},
+        transform::components::{local_to_world, translation},
+    },
+    ecs::{EntityId, World},
+    window::set_cursor,
+};
+use ambient_shared_types::{CursorIcon, MouseButton};
+use glam::{vec2, Vec2, Vec3};
+use parking_lot::Mutex;
+
+use crate::{default_theme::use_theme, layout::FlowRow, UIExt};
+
+/// How far the mouse has to move while pressed, in pixels, before a drag starts
+const DRAG_THRESHOLD: f32 = 4.;
+/// The depth of the drag preview, in front of the rest of the UI
+const PREVIEW_Z: f32 = -0.9;
+
+/// The value carried by a [DragSource], which is given to the [DropTarget] it is dropped on.
+///
+/// It can hold any type; [DropTarget]s check its type with [DragPayload::get] or [DragPayload::is].
+#[derive(Clone)]
+pub struct DragPayload {
+    value: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+impl DragPayload {
+    /// Creates a payload holding `value`.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self {
+            value: Arc::new(value),
+            type_name: type_name::<T>(),
+        }
+    }
+    /// Returns the value, if it is a `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+    /// Returns whether the value is a `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.value.is::<T>()
+    }
+}
+impl Debug for DragPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DragPayload").field(&self.type_name).finish()
+    }
+}
+
+#[derive(Debug)]
+struct Drag {
+    id: u64,
+    payload: DragPayload,
+    /// Whether the mouse was released; the source ends the drag on the next frame
+    released: bool,
+    /// Whether a [DropTarget] took the payload
+    dropped: bool,
+}
+
+static DRAG: Mutex<Option<Drag>> = Mutex::new(None);
+static NEXT_DRAG_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The payload being dragged, if there is one.
+fn current_payload() -> Option<(u64, DragPayload)> {
+    DRAG.lock()
+        .as_ref()
+        .filter(|drag| !drag.released)
+        .map(|drag| (drag.id, drag.payload.clone()))
+}
+
+fn cursor(world: &World) -> Vec2 {
+    let position = world.resource(cursor_position());
+    vec2(position.x, position.y)
+}
+
+/// Whether the cursor is over the rectangle of the entity `id`.
+fn contains_cursor(world: &World, id: EntityId) -> bool {
+    let Ok(local_to_world) = world.get(id, local_to_world()) else {
+        return false;
+    };
+    let (_, _, position) = local_to_world.to_scale_rotation_translation();
+    let size = vec2(
+        world.get(id, width()).unwrap_or(0.),
+        world.get(id, height()).unwrap_or(0.),
+    );
+    let cursor = cursor(world) - position.truncate();
+    cursor.cmpge(Vec2::ZERO).all() && cursor.cmplt(size).all()
+}
+
+/// Returns the payload being dragged, if there is one.
+///
+/// The element is rendered again when a drag starts or ends, which can be used to show where
+/// the payload can be dropped.
+pub fn use_drag(hooks: &mut Hooks) -> Option<DragPayload> {
+    let (drag, set_drag) = use_state(hooks, None);
+    use_frame(hooks, {
+        let id = drag.as_ref().map(|(id, _)| *id);
+        move |_| {
+            let current = current_payload();
+            if current.as_ref().map(|(id, _)| *id) != id {
+                set_drag(current);
+            }
+        }
+    });
+    drag.map(|(_, payload)| payload)
+}
+
+#[element_component]
+/// An element which can be dragged onto a [DropTarget].
+///
+/// The drag starts when `inner` is pressed with the left mouse button and the mouse is moved a
+/// few pixels. While dragging, the `preview` follows the cursor.
+pub fn DragSource(
+    hooks: &mut Hooks,
+    /// The element to drag.
+    inner: Element,
+    /// The value given to the [DropTarget] this is dropped on.
+    payload: DragPayload,
+    /// The element shown under the cursor while dragging.
+    preview: Option<Element>,
+    /// The callback to invoke when the drag starts.
+    on_drag_start: Option<Cb<dyn Fn(&mut World) + Sync + Send>>,
+    /// The callback to invoke when the drag ends, with whether it was dropped on a [DropTarget].
+    on_drag_end: Option<Cb<dyn Fn(&mut World, bool) + Sync + Send>>,
+) -> Element {
+    let (dragging, set_dragging) = use_state(hooks, false);
+    // Where the mouse was pressed, and where that is relative to the source
+    let press = use_ref_with(hooks, |_| None::<(Vec2, Vec2)>);
+    let drag_id = use_ref_with(hooks, |_| None::<u64>);
+    let preview_id = use_ref_with(hooks, |_| None::<EntityId>);
+    let preview_position = use_ref_with(hooks, |_| Vec3::ZERO);
+
+    // A source which is removed while it's dragged ends its drag, without calling `on_drag_end`
+    use_spawn(hooks, {
+        to_owned![drag_id];
+        move |_| {
+            move |world| {
+                if let Some(id) = *drag_id.lock() {
+                    let mut current = DRAG.lock();
+                    if current.as_ref().map_or(false, |drag| drag.id == id) {
+                        *current = None;
+                    }
+                    set_cursor(world, CursorIcon::Default);
+                }
+            }
+        }
+    });
+
+    use_runtime_message::<messages::WindowMouseInput>(hooks, {
+        to_owned![press, drag_id];
+        move |_, event| {
+            if event.pressed || MouseButton::from(event.button) != MouseButton::Left {
+                return;
+            }
+            *press.lock() = None;
+            if let Some(id) = *drag_id.lock() {
+                // Drop targets take the payload on this same event, so the drag ends next frame
+                if let Some(drag) = DRAG.lock().as_mut().filter(|drag| drag.id == id) {
+                    drag.released = true;
+                }
+            }
+        }
+    });
+
+    use_frame(hooks, {
+        to_owned![press, drag_id, preview_id, preview_position, payload];
+        move |world| {
+            let cursor = cursor(world);
+            let id = *drag_id.lock();
+            if let Some(id) = id {
+                let mut current = DRAG.lock();
+                let ended = match current.as_ref() {
+                    Some(drag) if drag.id == id => drag.released.then_some(drag.dropped),
+                    _ => Some(false),
+                };
+                if let Some(dropped) = ended {
+                    if current.as_ref().map_or(false, |drag| drag.id == id) {
+                        *current = None;
+                    }
+                    drop(current);
+                    *drag_id.lock() = None;
+                    set_dragging(false);
+                    set_cursor(world, CursorIcon::Default);
+                    if let Some(on_drag_end) = &on_drag_end {
+                        on_drag_end(world, dropped);
+                    }
+                } else if let Some(press) = *press.lock() {
+                    let position = (cursor - press.1).extend(PREVIEW_Z);
+                    *preview_position.lock() = position;
+                    if let Some(preview_id) = *preview_id.lock() {
+                        world.set(preview_id, translation(), position).ok();
+                    }
+                }
+            } else if let Some((start, offset)) = *press.lock() {
+                if cursor.distance(start) < DRAG_THRESHOLD {
+                    return;
+                }
+                let mut current = DRAG.lock();
+                if current.is_some() {
+                    return;
+                }
+                let id = NEXT_DRAG_ID.fetch_add(1, Ordering::SeqCst);
+                *current = Some(Drag {
+                    id,
+                    payload: payload.clone(),
+                    released: false,
+                    dropped: false,
+                });
+                drop(current);
+                *drag_id.lock() = Some(id);
+                *preview_position.lock() = (cursor - offset).extend(PREVIEW_Z);
+                set_dragging(true);
+                set_cursor(world, CursorIcon::Grabbing);
+                if let Some(on_drag_start) = &on_drag_start {
+                    on_drag_start(world);
+                }
+            }
+        }
+    });
+
+    let inner = inner
+        .with_clickarea()
+        .on_mouse_down(move |world, id, button| {
+            if button != MouseButton::Left {
+                return;
+            }
+            let Ok(local_to_world) = world.get(id, local_to_world()) else {
+                return;
+            };
+            let (_, _, position) = local_to_world.to_scale_rotation_translation();
+            let cursor = cursor(world);
+            *press.lock() = Some((cursor, cursor - position.truncate()));
+        })
+        .el();
+
+    // The preview has no `local_to_parent`, so it is positioned on the screen and isn't laid out
+    let preview = match preview {
+        Some(preview) if dragging => Element::new()
+            .init_default(local_to_world())
+            .init(translation(), *preview_position.lock())
+            .children(vec![preview])
+            .on_spawned(move |_, id, _| *preview_id.lock() = Some(id)),
+        _ => Element::new(),
+    };
+    FlowRow::el([inner, preview])
+}
+impl DragSource {
+    /// Creates a new [DragSource] which drags `inner`, carrying `payload`.
+    pub fn new(inner: Element, payload: DragPayload) -> Self {
+        Self {
+            inner,
+            payload,
+            preview: None,
+            on_drag_start: None,
+            on_drag_end: None,
+        }
+    }
+    /// Sets the element shown under the cursor while dragging.
+    pub fn preview(mut self, preview: Element) -> Self {
+        self.preview = Some(preview);
+        self
+    }
+    /// Sets the callback to invoke when the drag starts.
+    pub fn on_drag_start(
+        mut self,
+        on_drag_start: impl Fn(&mut World) + Sync + Send + 'static,
+    ) -> Self {
+        self.on_drag_start = Some(cb(on_drag_start));
+        self
+    }
+    /// Sets the callback to invoke when the drag ends, with whether it was dropped on a [DropTarget].
+    pub fn on_drag_end(
+        mut self,
+        on_drag_end: impl Fn(&mut World, bool) + Sync + Send + 'static,
+    ) -> Self {
+        self.on_drag_end = Some(cb(on_drag_end));
+        self
+    }
+}
+
+#[element_component]
+/// An element which receives the [DragPayload] of the [DragSource]s dropped on it.
+///
+/// While a payload it accepts is dragged over it, it gets a border in the theme's primary color,
+/// if `inner` is a rect (for example, if it has a background). If drop targets overlap, the
+/// payload is only given to one of them.
+pub fn DropTarget(
+    hooks: &mut Hooks,
+    /// The element to drop onto.
+    inner: Element,
+    /// Returns whether a payload can be dropped here.
+    accepts: Cb<dyn Fn(&DragPayload) -> bool + Sync + Send>,
+    /// The callback to invoke with the payload dropped here.
+    on_drop: Cb<dyn Fn(&mut World, DragPayload) + Sync + Send>,
+) -> Element {
+    let theme = use_theme(hooks);
+    let (hovered, set_hovered) = use_state(hooks, false);
+    let id = use_ref_with(hooks, |_| None::<EntityId>);
+
+    use_frame(hooks, {
+        to_owned![id, accepts];
+        move |world| {
+            let next = match (*id.lock(), current_payload()) {
+                (Some(id), Some((_, payload))) => contains_cursor(world, id) && accepts(&payload),
+                _ => false,
+            };
+            if next != hovered {
+                set_hovered(next);
+            }
+        }
+    });
+
+    use_runtime_message::<messages::WindowMouseInput>(hooks, {
+        to_owned![id];
+        move |world, event| {
+            if event.pressed || MouseButton::from(event.button) != MouseButton::Left {
+                return;
+            }
+            let Some(id) = *id.lock() else {
+                return;
+            };
+            // The source may have seen the mouse being released already, so this looks at the
+            // drag until it ends
+            let Some((drag_id, payload)) = DRAG
+                .lock()
+                .as_ref()
+                .filter(|drag| !drag.dropped)
+                .map(|drag| (drag.id, drag.payload.clone()))
+            else {
+                return;
+            };
+            if !contains_cursor(world, id) || !accepts(&payload) {
+                return;
+            }
+            match DRAG.lock().as_mut() {
+                Some(drag) if drag.id == drag_id && !drag.dropped => drag.dropped = true,
+                _ => return,
+            }
+            on_drop(world, payload);
+        }
+    });
+
+    inner
+        .with(border_color(), theme.primary_color.into())
+        .with(border_thickness(), if hovered { 2. } else { 0. })
+        .on_spawned(move |_, new_id, _| *id.lock() = Some(new_id))
+}
+impl DropTarget {
+    /// Creates a new [DropTarget] which accepts payloads holding a `T`, and calls `on_drop` with them.
+    pub fn new<T: Any + Send + Sync>(
+        inner: Element,
+        on_drop: impl Fn(&mut World, &T) + Sync + Send + 'static,
+    ) -> Self {
+        Self {
+            inner,
+            accepts: cb(|payload: &DragPayload| payload.is::<T>()),
+            on_drop: cb(move |world, payload: DragPayload| {
+                if let Some(value) = payload.get::<T>() {
+                    on_drop(world, value);
+                }
+            }),
+        }
+    }
+}
@@ -8,11 +8,7 @@ use ambient_guest_bridge::core::{
 };
 use glam::{vec3, Vec4};
 
-use crate::{
-    default_theme::{tooltip_background_color, SMALL_ROUNDING, STREET},
-    layout::FlowColumn,
-    UIBase, UIExt,
-};
+use crate::{default_theme::use_theme, layout::FlowColumn, UIBase, UIExt};
 
 #[element_component]
 /// A dropdown element: shows the `dropdown` when `show` is specified.
@@ -47,14 +43,15 @@ pub fn Tooltip(
     tooltip: Element,
 ) -> Element {
     let (hover, set_hover) = use_state(hooks, false);
+    let theme = use_theme(hooks);
     Dropdown {
         content: inner,
         dropdown: FlowColumn(vec![tooltip])
             .el()
-            .with_padding_even(STREET)
-            .with_background(tooltip_background_color().into())
-            .with(border_radius(), Vec4::ONE * SMALL_ROUNDING)
-            .with(margin(), Vec4::X * STREET),
+            .with_padding_even(theme.spacing)
+            .with_background(theme.tooltip_background_color.into())
+            .with(border_radius(), Vec4::ONE * theme.small_rounding)
+            .with(margin(), Vec4::X * theme.spacing),
         show: hover,
     }
     .el()
@@ -24,8 +24,8 @@ use super::{Editor, EditorOpts};
 use crate::{
     button::{Button, ButtonStyle},
     default_theme::{
-        StylesExt, COLLECTION_ADD_ICON, COLLECTION_DELETE_ICON, MOVE_DOWN_ICON, MOVE_UP_ICON,
-        STREET,
+        use_theme, StylesExt, Theme, COLLECTION_ADD_ICON, COLLECTION_DELETE_ICON, MOVE_DOWN_ICON,
+        MOVE_UP_ICON,
     },
     dropdown::Dropdown,
     layout::{FlowColumn, FlowRow},
@@ -198,6 +198,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
     for MinimalListEditorWithItemEditor<T>
 {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let spacing = use_theme(hooks).spacing;
         let Self {
             value,
             on_change,
@@ -284,7 +285,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
                                             }
                                         })
                                         .el()
-                                        .with_margin_even(STREET)
+                                        .with_margin_even(spacing)
                                 })
                                 .collect(),
                         )
@@ -295,7 +296,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
                         show: add_action,
                     }
                     .el()
-                    .with(margin(), Vec4::X * STREET)
+                    .with(margin(), Vec4::X * spacing)
                 } else {
                     Button::new(add_title, {
                         to_owned![value, on_change];
@@ -335,6 +336,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
     for MinimalListEditorItem<T>
 {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let spacing = use_theme(hooks).spacing;
         let Self {
             value,
             on_change,
@@ -377,7 +379,7 @@ impl<T: std::fmt::Debug + Clone + Default + Sync + Send + 'static> ElementCompon
             set_focused(world, true);
         })
         .el()
-        .with(padding(), vec4(STREET, 0., STREET, 0.))
+        .with(padding(), vec4(spacing, 0., spacing, 0.))
         .with(fit_horizontal(), Fit::Parent)
     }
 }
@@ -551,7 +553,8 @@ where
     K: Hash + Eq + Send + Sync + Debug + 'static + Clone + Editor + Default,
     V: Send + Sync + Debug + 'static + Clone + Editor + Default,
 {
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let spacing = use_theme(hooks).spacing;
         let fields = self.value.iter().map(|(key, value)| {
             IndexMapEntryPart {
                 key: key.clone(),
@@ -581,7 +584,7 @@ where
         } else {
             FlowColumn(fields).el()
         }
-        .with(space_between_items(), STREET)
+        .with(space_between_items(), spacing)
     }
 }
 
@@ -599,7 +602,9 @@ where
             .into_iter()
             .map(|(k, v)| FlowColumn(vec![K::view(k, opts.clone()), V::view(v, opts.clone())]).el())
             .collect_vec();
-        FlowColumn(fields).el().with(space_between_items(), STREET)
+        FlowColumn(fields)
+            .el()
+            .with(space_between_items(), Theme::current().spacing)
     }
 }
 
@@ -616,7 +621,8 @@ where
     K: Hash + Eq + Clone + Debug + Send + Sync + 'static + Editor,
     V: Clone + Debug + Editor + Send + Sync + 'static,
 {
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let spacing = use_theme(hooks).spacing;
         let Self {
             key,
             value,
@@ -671,12 +677,12 @@ where
         FlowColumn(vec![
             FlowRow(vec![discard, key_editor])
                 .el()
-                .with(space_between_items(), STREET),
+                .with(space_between_items(), spacing),
             value_editor,
         ])
         .el()
         .panel()
-        .with(space_between_items(), STREET)
-        .with_padding_even(STREET)
+        .with(space_between_items(), spacing)
+        .with_padding_even(spacing)
     }
 }
@@ -12,8 +12,9 @@ use itertools::Itertools;
 
 use super::{ChangeCb, Editor, EditorOpts, TextEditor};
 use crate::{
+    accessibility::{AccessibilityExt, AccessibilityRole},
     button::{Button, ButtonStyle},
-    default_theme::STREET,
+    default_theme::use_theme,
     layout::{FlowColumn, FlowRow},
     text::{FontAwesomeIcon, Text},
     use_focus_for_instance_id,
@@ -229,6 +230,8 @@ impl ElementComponent for Checkbox {
         )
         .style(ButtonStyle::Flat)
         .el()
+        .with_role(AccessibilityRole::Checkbox)
+        .with_value(value.to_string())
     }
 }
 
@@ -269,10 +272,11 @@ impl EditorRow {
     }
 }
 impl ElementComponent for EditorRow {
-    fn render(self: Box<Self>, _hooks: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self { title, editor } = *self;
+        let spacing = use_theme(hooks).spacing;
         FlowRow(vec![
-            Text::el(title).with(margin(), vec4(0., STREET, 0., 0.)),
+            Text::el(title).with(margin(), vec4(0., spacing, 0., 0.)),
             editor,
         ])
         .el()
@@ -12,7 +12,7 @@ use ambient_guest_bridge::core::layout::{
 use super::{ChangeCb, Editor, EditorOpts};
 use crate::{
     button::{Button, ButtonStyle},
-    default_theme::{StylesExt, STREET},
+    default_theme::{use_theme, StylesExt},
     layout::{FlowColumn, FlowRow},
     screens::{DialogScreen, ScreenContainer},
     scroll_area::{ScrollArea, ScrollAreaSizing},
@@ -94,6 +94,7 @@ fn EditorScreen<T: Debug + Clone + Sync + Send + 'static + Editor>(
     opts: EditorOpts,
 ) -> Element {
     let (value, set_value) = use_state(hooks, value);
+    let spacing = use_theme(hooks).spacing;
     DialogScreen(ScrollArea::el(
         ScrollAreaSizing::FitParentWidth,
         FlowColumn::el([
@@ -113,9 +114,9 @@ fn EditorScreen<T: Debug + Clone + Sync + Send + 'static + Editor>(
             ])
             .el()
             .with(align_vertical(), Align::Center)
-            .with(space_between_items(), STREET),
+            .with(space_between_items(), spacing),
         ])
-        .with(space_between_items(), STREET),
+        .with(space_between_items(), spacing),
     ))
     .el()
 }
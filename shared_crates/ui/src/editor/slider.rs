@@ -22,7 +22,8 @@ use glam::{vec3, Vec4};
 
 use super::Editor;
 use crate::{
-    default_theme::{primary_color, STREET},
+    accessibility::{AccessibilityExt, AccessibilityRole},
+    default_theme::use_theme,
     editor::EditorOpts,
     layout::FlowRow,
     text::Text,
@@ -148,6 +149,7 @@ impl ElementComponent for Slider {
         };
 
         let dragging = use_ref_with(hooks, |_| false);
+        let theme = use_theme(hooks);
         use_runtime_message::<messages::WindowMouseInput>(hooks, {
             let dragging = dragging.clone();
             move |_, event| {
@@ -186,7 +188,7 @@ impl ElementComponent for Slider {
             .with(width(), slider_width)
             .with(height(), 2.)
             .with(translation(), vec3(0., (SLIDER_HEIGHT - 2.) / 2., 0.))
-            .with(background_color(), primary_color().into())
+            .with(background_color(), theme.primary_color.into())
             .on_spawned(move |_, id, _| *block_id.lock() = id);
 
         let thumb = {
@@ -194,7 +196,7 @@ impl ElementComponent for Slider {
                 .el()
                 .with(width(), THUMB_WIDTH)
                 .with(height(), SLIDER_HEIGHT)
-                .with_background(primary_color().into())
+                .with_background(theme.primary_color.into())
                 .with(border_radius(), Vec4::ONE * THUMB_WIDTH / 2.)
                 .with(translation(), vec3(block_left_offset, 0., -0.01))
                 .with_clickarea()
@@ -227,7 +229,9 @@ impl ElementComponent for Slider {
                 suffix.map(Text::el).unwrap_or_default(),
             ]),
         ])
-        .with(space_between_items(), STREET)
+        .with(space_between_items(), theme.spacing)
+        .with_role(AccessibilityRole::Slider)
+        .with_value(format!("{value}{}", suffix.unwrap_or_default()))
     }
 }
 
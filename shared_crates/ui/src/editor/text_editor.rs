@@ -1,10 +1,11 @@
+use std::ops::Range;
 #[cfg(feature = "guest")]
 use std::time::Instant;
 
 use ambient_cb::{cb, Cb};
 use ambient_element::{
-    element_component, to_owned, use_frame, use_ref_with, use_rerender_signal, use_runtime_message,
-    use_spawn, use_state, Element, ElementComponentExt, Hooks,
+    element_component, to_owned, use_effect, use_frame, use_ref_with, use_rerender_signal,
+    use_runtime_message, use_spawn, use_state, Element, ElementComponentExt, Hooks,
 };
 use ambient_guest_bridge::{
     core::{
@@ -14,22 +15,185 @@ use ambient_guest_bridge::{
         rendering::components::color,
         text::components::text,
         transform::components::translation,
-        ui::components::focusable,
+        ui::components::{focus_captures_navigation, focusable},
     },
-    window::set_cursor,
+    window::{set_cursor, set_ime_allowed, set_ime_position},
 };
-use ambient_shared_types::{CursorIcon, VirtualKeyCode};
+use ambient_shared_types::{CursorIcon, MouseButton, VirtualKeyCode};
 #[cfg(feature = "native")]
 use ambient_sys::time::Instant;
 use glam::*;
-use itertools::Itertools;
 
 use super::{Editor, EditorOpts};
 use crate::{
-    layout::FlowRow, text::Text, use_focus, use_keyboard_input, with_rect, Rectangle, UIBase, UIExt,
+    accessibility::{AccessibilityExt, AccessibilityRole},
+    default_theme::use_theme,
+    layout::{FlowRow, MeasureAbsolutePosition},
+    text::Text,
+    use_focus, use_keyboard_input, with_rect, Rectangle, UIBase, UIExt,
 };
 
+/// The most edits which can be undone
+const UNDO_LIMIT: usize = 100;
+/// The height of a line of text
+const LINE_HEIGHT: f32 = 13.;
+const COMPOSITION_COLOR: Vec4 = vec4(0.9, 0.8, 0.4, 1.);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    /// Characters typed one after the other, which are undone together
+    Typing,
+    Other,
+}
+
+/// The text being edited, its selection and the edits which can be undone
+#[derive(Debug)]
+struct EditState {
+    value: String,
+    /// The byte offset of the cursor in `value`
+    cursor: usize,
+    /// Where the selection started, if there is one. It ends at the cursor
+    anchor: Option<usize>,
+    undo: Vec<(String, usize)>,
+    redo: Vec<(String, usize)>,
+    last_edit: Option<EditKind>,
+}
+
+impl EditState {
+    fn new(value: String) -> Self {
+        Self {
+            cursor: value.len(),
+            value,
+            anchor: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: None,
+        }
+    }
+
+    /// Follows a value which was changed from outside of the editor
+    fn set_value(&mut self, value: String) {
+        let mut cursor = self.cursor.min(value.len());
+        while !value.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        self.value = value;
+        self.cursor = cursor;
+        self.anchor = None;
+        self.last_edit = None;
+    }
+
+    fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.cursor)?;
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.value[range])
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .chars()
+            .next_back()
+            .map_or(0, |c| self.cursor - c.len_utf8())
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    /// Moves the cursor, extending the selection from where the cursor was if `select` is set
+    fn move_to(&mut self, position: usize, select: bool) {
+        if !select {
+            self.anchor = None;
+        } else if self.anchor.is_none() {
+            self.anchor = Some(self.cursor);
+        }
+        self.cursor = position;
+        self.last_edit = None;
+    }
+
+    /// Starts selecting from `position`, as the mouse is pressed there
+    fn select_from(&mut self, position: usize) {
+        self.move_to(position, false);
+        self.anchor = Some(position);
+    }
+
+    fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.value.len();
+        self.last_edit = None;
+    }
+
+    fn push_undo(&mut self, kind: EditKind) {
+        if kind == EditKind::Typing && self.last_edit == Some(EditKind::Typing) {
+            return;
+        }
+        if self.undo.len() == UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push((self.value.clone(), self.cursor));
+        self.redo.clear();
+        self.last_edit = Some(kind);
+    }
+
+    /// Replaces the selection with `text`, or inserts it at the cursor if nothing is selected
+    fn replace_selection(&mut self, text: &str, kind: EditKind) {
+        self.push_undo(kind);
+        let range = self.selection().unwrap_or(self.cursor..self.cursor);
+        self.value.replace_range(range.clone(), text);
+        self.cursor = range.start + text.len();
+        self.anchor = None;
+    }
+
+    /// Deletes the selection, or `range` if nothing is selected. Returns whether anything was
+    /// deleted
+    fn delete(&mut self, range: Range<usize>) -> bool {
+        let range = self.selection().unwrap_or(range);
+        if range.is_empty() {
+            return false;
+        }
+        self.push_undo(EditKind::Other);
+        self.value.replace_range(range.clone(), "");
+        self.cursor = range.start;
+        self.anchor = None;
+        true
+    }
+
+    fn undo(&mut self) -> bool {
+        let Some((value, cursor)) = self.undo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.value, value);
+        self.redo.push((current, self.cursor));
+        self.cursor = cursor;
+        self.anchor = None;
+        self.last_edit = None;
+        true
+    }
+
+    fn redo(&mut self) -> bool {
+        let Some((value, cursor)) = self.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.value, value);
+        self.undo.push((current, self.cursor));
+        self.cursor = cursor;
+        self.anchor = None;
+        self.last_edit = None;
+        true
+    }
+}
+
 /// A text editor.
+///
+/// Text can be selected with the mouse or with shift and the arrow, `Home` and `End` keys, cut,
+/// copied and pasted through the clipboard, and edits can be undone and redone. While it is
+/// focused, text can also be composed with the operating system's input method editor.
 #[element_component]
 pub fn TextEditor(
     hooks: &mut Hooks,
@@ -47,17 +211,18 @@ pub fn TextEditor(
     auto_focus: bool,
 ) -> Element {
     let (focused, set_focused) = use_focus(hooks);
+    let theme = use_theme(hooks);
     let (command, set_command) = use_state(hooks, false);
-    let intermediate_value = use_ref_with(hooks, |_| value.clone());
-    let cursor_position = use_ref_with(hooks, |_| value.len());
+    let (composition, set_composition) = use_state(hooks, String::new());
+    let (position, set_position) = use_state(hooks, Vec3::ZERO);
+    let state = use_ref_with(hooks, |_| EditState::new(value.clone()));
+    let dragging = use_ref_with(hooks, |_| false);
     let rerender = use_rerender_signal(hooks);
     {
-        let mut inter = intermediate_value.lock();
-        if *inter != value {
-            let mut cp = cursor_position.lock();
-            *cp = cp.min(value.len());
+        let mut state = state.lock();
+        if state.value != value {
+            state.set_value(value.clone());
         }
-        *inter = value.clone();
     }
 
     use_spawn(hooks, {
@@ -74,140 +239,299 @@ pub fn TextEditor(
         }
     });
 
+    use_effect(hooks, focused, {
+        to_owned![set_composition];
+        move |world, &focused| {
+            if focused {
+                set_ime_allowed(world, true);
+            }
+            move |world| {
+                if focused {
+                    set_ime_allowed(world, false);
+                    set_composition(String::new());
+                }
+            }
+        }
+    });
+    use_effect(hooks, (focused, position), |world, &(focused, position)| {
+        // The candidate window goes under the text
+        if focused {
+            set_ime_position(world, position.truncate() + vec2(0., LINE_HEIGHT));
+        }
+        |_| {}
+    });
+    use_runtime_message::<messages::WindowImeComposition>(hooks, move |_world, event| {
+        if focused {
+            set_composition(event.text.clone());
+        }
+    });
+
     use_runtime_message::<messages::WindowKeyboardCharacter>(hooks, {
-        to_owned![intermediate_value, on_change, cursor_position];
+        to_owned![state, on_change];
         move |_world, event| {
-            let c = event.character.chars().next().unwrap();
             if command || !focused {
                 return;
             }
 
-            let mut value = intermediate_value.lock();
-            value.insert(*cursor_position.lock(), c);
-            *cursor_position.lock() += 1;
-            on_change.0(value.clone());
+            let mut state = state.lock();
+            state.replace_selection(&event.character, EditKind::Typing);
+            on_change.0(state.value.clone());
+        }
+    });
+    use_runtime_message::<messages::WindowMouseInput>(hooks, {
+        to_owned![dragging];
+        move |_world, event| {
+            if !event.pressed {
+                *dragging.lock() = false;
+            }
         }
     });
     use_keyboard_input(hooks, {
-        to_owned![intermediate_value, on_change, cursor_position];
-        move |world, keycode, _modifiers, pressed| {
+        to_owned![state, on_change, rerender];
+        move |world, keycode, modifiers, pressed| {
             if !focused {
                 return;
             }
-            if let Some(kc) = keycode {
-                match kc {
-                    VirtualKeyCode::LWin => {
-                        #[cfg(target_os = "macos")]
-                        set_command(pressed);
-                    }
-                    VirtualKeyCode::LControl => {
-                        #[cfg(not(target_os = "macos"))]
-                        set_command(pressed);
-                    }
-                    VirtualKeyCode::C if command && pressed => {
-                        let value = intermediate_value.lock().clone();
+            let Some(kc) = keycode else {
+                return;
+            };
+            match kc {
+                VirtualKeyCode::LWin => {
+                    #[cfg(target_os = "macos")]
+                    set_command(pressed);
+                }
+                VirtualKeyCode::LControl => {
+                    #[cfg(not(target_os = "macos"))]
+                    set_command(pressed);
+                }
+                _ => {}
+            }
+            if !pressed {
+                return;
+            }
+
+            let select = modifiers.shift();
+            let mut edit = state.lock();
+            match kc {
+                VirtualKeyCode::A if command => {
+                    edit.select_all();
+                    rerender();
+                }
+                VirtualKeyCode::C if command && !password => {
+                    // Without a selection, the whole value is copied
+                    let value = edit.selected_text().unwrap_or(&edit.value).to_string();
+                    ambient_guest_bridge::run_async_local(world, move || async move {
+                        let _ = ambient_guest_bridge::window::set_clipboard(&value).await;
+                    })
+                }
+                VirtualKeyCode::X if command && !password => {
+                    if let Some(value) = edit.selected_text().map(|text| text.to_string()) {
                         ambient_guest_bridge::run_async_local(world, move || async move {
                             let _ = ambient_guest_bridge::window::set_clipboard(&value).await;
-                        })
+                        });
+                        let cursor = edit.cursor;
+                        edit.delete(cursor..cursor);
+                        on_change.0(edit.value.clone());
                     }
-                    VirtualKeyCode::V if command && pressed => {
-                        let on_change = on_change.clone();
-                        let cursor_position = cursor_position.clone();
-                        let intermediate_value = intermediate_value.clone();
-                        ambient_guest_bridge::run_async_local(world, move || async move {
-                            if let Some(paste) = ambient_guest_bridge::window::get_clipboard().await
-                            {
-                                let mut value = intermediate_value.lock();
-                                value.insert_str(*cursor_position.lock(), &paste);
-                                *cursor_position.lock() += paste.len();
-                                on_change.0(value.clone());
-                            }
-                        })
-                    }
-                    VirtualKeyCode::Left => {
-                        if pressed && *cursor_position.lock() > 0 {
-                            *cursor_position.lock() -= 1;
-                            rerender();
+                }
+                VirtualKeyCode::V if command => {
+                    to_owned![on_change, state];
+                    ambient_guest_bridge::run_async_local(world, move || async move {
+                        if let Some(paste) = ambient_guest_bridge::window::get_clipboard().await {
+                            let mut state = state.lock();
+                            state.replace_selection(&paste, EditKind::Other);
+                            on_change.0(state.value.clone());
                         }
+                    })
+                }
+                VirtualKeyCode::Z if command => {
+                    let changed = if modifiers.shift() {
+                        edit.redo()
+                    } else {
+                        edit.undo()
+                    };
+                    if changed {
+                        on_change.0(edit.value.clone());
                     }
-                    VirtualKeyCode::Right => {
-                        if pressed && *cursor_position.lock() < intermediate_value.lock().len() {
-                            *cursor_position.lock() += 1;
-                            rerender();
-                        }
+                }
+                VirtualKeyCode::Y if command => {
+                    if edit.redo() {
+                        on_change.0(edit.value.clone());
                     }
-                    VirtualKeyCode::Back => {
-                        if pressed && *cursor_position.lock() > 0 {
-                            let mut value = intermediate_value.lock();
-                            value.remove(*cursor_position.lock() - 1);
-                            *cursor_position.lock() -= 1;
-                            on_change.0(value.clone());
-                        }
+                }
+                VirtualKeyCode::Left => {
+                    let position = match edit.selection() {
+                        Some(selection) if !select => selection.start,
+                        _ => edit.previous_boundary(),
+                    };
+                    edit.move_to(position, select);
+                    rerender();
+                }
+                VirtualKeyCode::Right => {
+                    let position = match edit.selection() {
+                        Some(selection) if !select => selection.end,
+                        _ => edit.next_boundary(),
+                    };
+                    edit.move_to(position, select);
+                    rerender();
+                }
+                VirtualKeyCode::Home => {
+                    edit.move_to(0, select);
+                    rerender();
+                }
+                VirtualKeyCode::End => {
+                    let end = edit.value.len();
+                    edit.move_to(end, select);
+                    rerender();
+                }
+                VirtualKeyCode::Back => {
+                    let range = edit.previous_boundary()..edit.cursor;
+                    if edit.delete(range) {
+                        on_change.0(edit.value.clone());
                     }
-                    VirtualKeyCode::Delete => {
-                        if pressed && *cursor_position.lock() < intermediate_value.lock().len() {
-                            let mut value = intermediate_value.lock();
-                            value.remove(*cursor_position.lock());
-                            on_change.0(value.clone());
-                        }
+                }
+                VirtualKeyCode::Delete => {
+                    let range = edit.cursor..edit.next_boundary();
+                    if edit.delete(range) {
+                        on_change.0(edit.value.clone());
                     }
-                    VirtualKeyCode::Return => {
-                        if pressed && !command {
-                            if let Some(on_submit) = on_submit.clone() {
-                                on_submit.0(intermediate_value.lock().clone());
-                            }
+                }
+                VirtualKeyCode::Return => {
+                    if !command {
+                        if let Some(on_submit) = on_submit.clone() {
+                            on_submit.0(edit.value.clone());
                         }
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
     });
-    let (cursor_left, cursor_right) = value.split_at(*cursor_position.lock());
-    let [a, b]: [Element; 2] = [cursor_left, cursor_right]
-        .iter()
-        .map(|value| {
-            Text.el()
-                .with(
-                    text(),
-                    if password {
-                        value.chars().map(|_| '*').collect()
-                    } else {
-                        value.to_string()
-                    },
-                )
-                .with(color(), vec4(0.9, 0.9, 0.9, 1.))
-        })
-        .collect_vec()
-        .try_into()
-        .unwrap();
 
-    with_rect(if focused {
-        if !cursor_left.is_empty() {
-            FlowRow::el([a, Cursor.el(), b])
-        } else {
-            FlowRow::el([Cursor.el(), b])
+    let (cursor, selection) = {
+        let state = state.lock();
+        (state.cursor, state.selection())
+    };
+    // Each character can be clicked to move the cursor before it, and dragged over to select it
+    let glyph = |offset: usize, c: char| {
+        let end = offset + c.len_utf8();
+        let el = Text
+            .el()
+            .with(text(), if password { '*' } else { c }.to_string())
+            .with(color(), theme.text_color.into());
+        let el = match &selection {
+            Some(selection) if focused && selection.contains(&offset) => {
+                FlowRow::el([el]).with_background(theme.selection_color.into())
+            }
+            _ => el,
+        };
+        el.with_clickarea()
+            .on_mouse_down({
+                to_owned![state, dragging, set_focused, rerender];
+                move |world, _, button| {
+                    if button == MouseButton::Left {
+                        set_focused(world, true);
+                        state.lock().select_from(offset);
+                        *dragging.lock() = true;
+                        rerender();
+                    }
+                }
+            })
+            .on_mouse_enter({
+                to_owned![state, dragging, rerender];
+                move |_, _| {
+                    if *dragging.lock() {
+                        let mut state = state.lock();
+                        let anchor = state.anchor.unwrap_or(offset);
+                        state.cursor = if offset < anchor { offset } else { end };
+                        rerender();
+                    }
+                }
+            })
+            .el()
+    };
+    let mut glyphs = value
+        .char_indices()
+        .map(|(offset, c)| glyph(offset, c))
+        .collect::<Vec<_>>();
+    if focused {
+        let index = value[..cursor].chars().count();
+        let mut at_cursor = vec![Cursor.el()];
+        if !composition.is_empty() {
+            at_cursor.insert(
+                0,
+                Text.el()
+                    .with(text(), composition)
+                    .with(color(), COMPOSITION_COLOR),
+            );
         }
-    } else if value.is_empty() && !focused && placeholder.is_some() {
+        glyphs.splice(index..index, at_cursor);
+    }
+    // Clicking after the text moves the cursor to its end
+    glyphs.push(
+        UIBase
+            .el()
+            .with(width(), 3.)
+            .with(height(), LINE_HEIGHT)
+            .with_clickarea()
+            .on_mouse_down({
+                to_owned![state, dragging, set_focused, rerender];
+                move |world, _, button| {
+                    if button == MouseButton::Left {
+                        set_focused(world, true);
+                        let mut state = state.lock();
+                        let end = state.value.len();
+                        state.select_from(end);
+                        *dragging.lock() = true;
+                        rerender();
+                    }
+                }
+            })
+            .on_mouse_enter({
+                to_owned![state, dragging, rerender];
+                move |_, _| {
+                    if *dragging.lock() {
+                        let mut state = state.lock();
+                        state.cursor = state.value.len();
+                        rerender();
+                    }
+                }
+            })
+            .el(),
+    );
+
+    let inner = if value.is_empty() && !focused && placeholder.is_some() {
         FlowRow::el([Text
             .el()
             .with(text(), placeholder.unwrap())
             .with(color(), vec4(1., 1., 1., 0.2))])
     } else {
-        FlowRow::el([a, b])
-    })
-    .with(min_width(), 3.)
-    .with(min_height(), 13.)
-    .with(background_color(), vec4(0., 0., 0., 0.5))
-    .with(focusable(), hooks.instance_id().to_string())
-    .with_clickarea()
-    .on_mouse_enter(|world, _| {
-        set_cursor(world, CursorIcon::Text);
-    })
-    .on_mouse_leave(|world, _| {
-        set_cursor(world, CursorIcon::Default);
-    })
-    .el()
+        FlowRow::el(glyphs)
+    };
+    MeasureAbsolutePosition::el(
+        with_rect(inner)
+            .with(min_width(), 3.)
+            .with(min_height(), LINE_HEIGHT)
+            .with(background_color(), vec4(0., 0., 0., 0.5))
+            .with(focusable(), hooks.instance_id().to_string())
+            // The arrow keys move the cursor instead of the focus
+            .with(focus_captures_navigation(), ())
+            .with_role(AccessibilityRole::TextInput)
+            .with_value(if password {
+                "*".repeat(value.chars().count())
+            } else {
+                value
+            })
+            .with_clickarea()
+            .on_mouse_enter(|world, _| {
+                set_cursor(world, CursorIcon::Text);
+            })
+            .on_mouse_leave(|world, _| {
+                set_cursor(world, CursorIcon::Default);
+            })
+            .el(),
+        set_position,
+    )
 }
 
 impl TextEditor {
@@ -257,7 +581,7 @@ fn CursorInner(hooks: &mut Hooks, render_time: Instant) -> Element {
         UIBase.el().children(vec![Rectangle
             .el()
             .with(width(), 2.)
-            .with(height(), 13.)
+            .with(height(), LINE_HEIGHT)
             .with(translation(), vec3(1., 0., 0.))])
     } else {
         Element::new()
@@ -10,9 +10,8 @@ use ambient_guest_bridge::core::{
     layout::components::space_between_items, rendering::components::color,
 };
 use ambient_time::parse_duration;
-use glam::vec4;
 
-use crate::{layout::FlowRow, text::Text};
+use crate::{default_theme::use_theme, layout::FlowRow, text::Text};
 
 use super::{ChangeCb, Editor, EditorOpts, TextEditor};
 
@@ -101,11 +100,12 @@ impl DurationEditor {
 }
 
 impl ElementComponent for DurationEditor {
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self {
             value: EditableDuration { input, dur, valid },
             on_change,
         } = *self;
+        let theme = use_theme(hooks);
         let input = TextEditor::new(
             input,
             cb(move |upd: String| on_change(EditableDuration::from(upd))),
@@ -116,14 +116,14 @@ impl ElementComponent for DurationEditor {
         if valid {
             FlowRow(vec![input, value])
                 .el()
-                .with(space_between_items(), 10.0)
+                .with(space_between_items(), theme.spacing)
         } else {
             FlowRow(vec![
                 input,
-                Text::el("invalid duration").with(color(), vec4(1.0, 0.0, 0.0, 1.0)),
+                Text::el("invalid duration").with(color(), theme.error_text_color.into()),
             ])
             .el()
-            .with(space_between_items(), 10.0)
+            .with(space_between_items(), theme.spacing)
         }
     }
 }
@@ -3,6 +3,8 @@
 //! The layout is roughly based on [Windows Forms](https://docs.microsoft.com/en-us/dotnet/desktop/winforms/controls/layout?view=netdesktop-6.0#container-flow-layout).
 //!
 //! There are two major layout components, [Dock] and [Flow] (which includes [FlowColumn] and [FlowRow]).
+//! For more complex arrangements, [Flex] (which includes [FlexColumn] and [FlexRow]) and [Grid] follow
+//! CSS flexbox and grid.
 use crate::{use_window_logical_resolution, UIBase, UIExt};
 use ambient_cb::Cb;
 use ambient_color::Color;
@@ -19,7 +21,7 @@ use itertools::Itertools;
 
 pub use ambient_guest_bridge::core::layout::{
     components::*,
-    types::{Align, Docking, Fit, Layout, Orientation},
+    types::{Align, Docking, Fit, FlexAlign, Justify, Layout, Orientation},
 };
 
 #[derive(Debug, Clone)]
@@ -136,6 +138,96 @@ impl ElementComponent for FlowRow {
     }
 }
 
+/// A flexbox layout, like CSS flexbox.
+///
+/// Its children are laid out along its `orientation`, wrapping onto new lines if `flex_wrap` is
+/// set. They grow to fill the space left with `flex_grow`, shrink when they don't fit with
+/// `flex_shrink`, and are spread out with `justify_content`. Across the orientation, they are
+/// aligned with `align_items` and `align_self`.
+#[derive(Debug, Clone)]
+pub struct Flex(pub Vec<Element>);
+define_el_function_for_vec_element_newtype!(Flex);
+impl ElementComponent for Flex {
+    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+        Element::from(UIBase)
+            .init(layout(), Layout::Flex)
+            .init_default(children())
+            .children(self.0)
+    }
+}
+
+/// A [FlexRow] is a [Flex] that is oriented horizontally. Its width has to be set, and its height
+/// fits its children.
+#[derive(Debug, Clone)]
+pub struct FlexRow(pub Vec<Element>);
+define_el_function_for_vec_element_newtype!(FlexRow);
+impl ElementComponent for FlexRow {
+    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+        Flex(self.0)
+            .el()
+            .with(orientation(), Orientation::Horizontal)
+            .with(fit_vertical(), Fit::Children)
+    }
+}
+
+/// A [FlexColumn] is a [Flex] that is oriented vertically. Its height has to be set, and its width
+/// fits its children.
+#[derive(Debug, Clone)]
+pub struct FlexColumn(pub Vec<Element>);
+define_el_function_for_vec_element_newtype!(FlexColumn);
+impl ElementComponent for FlexColumn {
+    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+        Flex(self.0)
+            .el()
+            .with(orientation(), Orientation::Vertical)
+            .with(fit_horizontal(), Fit::Children)
+    }
+}
+
+/// A grid layout, like CSS grid.
+///
+/// The sizes of its columns and rows are set with `grid_columns` and `grid_rows`, which can be
+/// built from [GridTrack]s with [grid_tracks]. Its children are placed in the next free cells, or
+/// in the one set with `grid_column` and `grid_row`, and can cover several with
+/// `grid_column_span` and `grid_row_span`. Children with `fit_horizontal` or `fit_vertical` set to
+/// [Fit::Parent] are sized to their cells.
+#[derive(Debug, Clone)]
+pub struct Grid(pub Vec<Element>);
+define_el_function_for_vec_element_newtype!(Grid);
+impl ElementComponent for Grid {
+    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+        Element::from(UIBase)
+            .init(layout(), Layout::Grid)
+            .init_default(children())
+            .children(self.0)
+    }
+}
+
+/// The size of a column or row of a [Grid].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridTrack {
+    /// A size in pixels.
+    Pixels(f32),
+    /// A share of the space left by the other tracks, relative to the other shares.
+    Fraction(f32),
+    /// Fits the children in the track.
+    Auto,
+}
+impl From<GridTrack> for f32 {
+    fn from(value: GridTrack) -> Self {
+        match value {
+            GridTrack::Pixels(pixels) => pixels.max(0.),
+            GridTrack::Fraction(fraction) => -fraction.max(0.),
+            GridTrack::Auto => 0.,
+        }
+    }
+}
+
+/// Converts [GridTrack]s to the sizes of `grid_columns` or `grid_rows`.
+pub fn grid_tracks(tracks: impl IntoIterator<Item = GridTrack>) -> Vec<f32> {
+    tracks.into_iter().map(f32::from).collect()
+}
+
 /// A [Centered] is a [Flow] that is oriented vertically and is centered.
 ///
 #[derive(Debug, Clone)]
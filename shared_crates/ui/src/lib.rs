@@ -40,7 +40,9 @@ use ambient_guest_bridge::{
         layout::components::{
             gpu_ui_size, height, margin, mesh_to_local_from_size, padding, width,
         },
-        rect::components::{background_color, background_url, rect},
+        rect::components::{
+            background_color, background_region, background_slice, background_url, rect,
+        },
         transform::components::{
             local_to_parent, local_to_world, mesh_to_local, mesh_to_world, scale, translation,
         },
@@ -52,20 +54,31 @@ use ambient_shared_types::{ModifiersState, VirtualKeyCode};
 use clickarea::ClickArea;
 use glam::{vec3, Mat4, UVec2, Vec3, Vec4};
 
+pub mod accessibility;
 pub mod button;
+pub mod canvas;
 pub mod clickarea;
 pub mod default_theme;
+pub mod drag_drop;
 pub mod dropdown;
 pub mod editor;
 pub mod layout;
+pub mod list_view;
+pub mod localization;
+pub mod markdown;
+pub mod navigation;
+pub mod notification;
 pub mod prelude;
 pub mod prompt;
+pub mod property_grid;
 pub mod screens;
 pub mod scroll_area;
 pub mod select;
+pub mod table;
 pub mod tabs;
 pub mod text;
 pub mod throbber;
+pub mod transition;
 pub mod window;
 
 /// A base element for all UI elements. It contains all the components needed for a UI element to work.
@@ -111,6 +124,9 @@ pub fn with_rect(element: Element) -> Element {
 }
 
 /// Show an image loaded from a url
+///
+/// The url can also refer to an image of a texture atlas built by the `TextureAtlases` asset
+/// pipeline, like `ui.atlas.json#button`.
 #[element_component]
 pub fn ImageFromUrl(
     _: &mut Hooks,
@@ -123,6 +139,25 @@ pub fn ImageFromUrl(
         .with(background_url(), url)
 }
 
+/// Show a region of an image loaded from a url, sized to the region. Use it to show the images of
+/// a hand-made atlas or sprite sheet.
+#[element_component]
+pub fn ImageRegion(
+    _: &mut Hooks,
+    /// Url to load the image from
+    url: String,
+    /// The region of the image to show, as x, y, width and height in pixels
+    region: Vec4,
+) -> Element {
+    Rectangle
+        .el()
+        .with(width(), region.z)
+        .with(height(), region.w)
+        .with(background_color(), Vec4::ZERO)
+        .with(background_url(), url)
+        .with(background_region(), region)
+}
+
 /// A simple UI line. Use components like `line_from`, `line_to`, `line_width`, `background_color`, `border_color`, `border_radius` and `border_thickness`
 /// to control its appearance.
 #[element_component]
@@ -189,6 +224,13 @@ pub trait UIExt {
     fn with_clickarea(self) -> ClickArea;
     /// Adds a background color to this element.
     fn with_background(self, color: Vec4) -> Self;
+    /// Adds a background image to this element, stretched to its size. Images of texture atlases
+    /// which have nine-slice insets are nine-sliced.
+    fn with_background_image(self, url: impl Into<String>) -> Self;
+    /// Adds a nine-sliced background image to this element. The corners of the image, as given by
+    /// the `slice` insets (top, right, bottom, left, in pixels), keep their size, so borders of
+    /// skinned buttons and panels don't stretch.
+    fn with_nine_slice(self, url: impl Into<String>, slice: Vec4) -> Self;
     /// Adds padding to all sides of this element.
     fn with_padding_even(self, padding: f32) -> Self;
     /// Adds margin to all sides of this element.
@@ -201,6 +243,15 @@ impl UIExt for Element {
     fn with_background(self, background: Vec4) -> Self {
         with_rect(self).with(background_color(), background)
     }
+    fn with_background_image(self, url: impl Into<String>) -> Self {
+        with_rect(self)
+            .with(background_color(), Vec4::ZERO)
+            .with(background_url(), url.into())
+    }
+    fn with_nine_slice(self, url: impl Into<String>, slice: Vec4) -> Self {
+        self.with_background_image(url)
+            .with(background_slice(), slice)
+    }
     fn with_padding_even(self, value: f32) -> Self {
         self.with(padding(), Vec4::ONE * value)
     }
@@ -0,0 +1,132 @@
+//! Defines a virtualized list view.
+use ambient_cb::Cb;
+use ambient_element::{
+    element_component, to_owned, use_frame, use_ref_with, use_runtime_message, use_state,
+    use_state_with, Element, ElementComponentExt, Hooks,
+};
+use ambient_guest_bridge::core::{
+    app::components::window_scale_factor,
+    hierarchy::components::children,
+    input::components::{is_mouse_over, mouse_pickable_max, mouse_pickable_min},
+    layout::components::{height, width},
+    messages,
+    rendering::components::scissors_recursive,
+    transform::components::translation,
+};
+use glam::{vec3, Vec2, Vec3};
+
+use crate::{
+    layout::{MeasureAbsolutePosition, MeasureSize},
+    scroll_area::{get_scissors, scroll_bar},
+    UIBase,
+};
+
+/// Creates the element of the item at an index of a [ListView]
+pub type ListViewItem = Cb<dyn Fn(usize) -> Element + Sync + Send>;
+
+/// The rows created past each end of the visible ones, so that scrolling doesn't show them late
+const OVERSCAN_ROWS: usize = 2;
+
+/// A vertical list of rows of the same height, scrolled with the mouse wheel.
+///
+/// Unlike a [ScrollArea](crate::scroll_area::ScrollArea), only the rows in view are created, so
+/// that lists of thousands of items stay responsive. Its size has to be set with `width` and
+/// `height`.
+#[element_component]
+pub fn ListView(
+    hooks: &mut Hooks,
+    /// The number of items in the list
+    item_count: usize,
+    /// The height of each row
+    item_height: f32,
+    /// Creates the element of the item at an index. It's only called for the rows in view, again
+    /// every time the list is rendered
+    item: ListViewItem,
+) -> Element {
+    let (scroll, set_scroll) = use_state(hooks, 0.);
+    let (ratio, _set_ratio) = use_state_with(hooks, |world| {
+        #[allow(clippy::clone_on_copy)]
+        let r = world.resource(window_scale_factor()).clone();
+        r as f32
+    });
+    let (outer_size, set_outer_size) = use_state(hooks, Vec2::ZERO);
+    let (canvas_offset, set_canvas_offset) = use_state(hooks, Vec3::ZERO);
+    let scroll_height = (item_count as f32 * item_height - outer_size.y).max(0.0);
+    let mouse_over_count = use_ref_with(hooks, |_| 0);
+    let id = use_ref_with(hooks, |_| None);
+
+    use_frame(hooks, {
+        to_owned![id, mouse_over_count, set_scroll];
+        move |world| {
+            if let Some(id) = *id.lock() {
+                let number = world.get(id, is_mouse_over()).unwrap_or(0);
+                *mouse_over_count.lock() = number;
+            }
+            // The list may have gotten shorter
+            if scroll < -scroll_height {
+                set_scroll(-scroll_height);
+            }
+        }
+    });
+    use_runtime_message::<messages::WindowMouseWheel>(hooks, {
+        to_owned![mouse_over_count];
+        move |_world, event| {
+            if *mouse_over_count.lock() == 0 {
+                return;
+            };
+            let delta = event.delta;
+            let scroll = scroll + if event.pixels { delta.y } else { delta.y * 20. };
+            set_scroll(scroll.clamp(-scroll_height, 0.0));
+        }
+    });
+
+    // The scroll moves the rows up, so the top of the view is this far down the list
+    let top = -scroll;
+    let (first, last) = if item_height > 0.0 {
+        (
+            ((top / item_height) as usize).saturating_sub(OVERSCAN_ROWS),
+            (((top + outer_size.y) / item_height).ceil() as usize + OVERSCAN_ROWS).min(item_count),
+        )
+    } else {
+        (0, 0)
+    };
+    let rows = (first..last)
+        .map(|index| {
+            UIBase::el()
+                .with(
+                    translation(),
+                    vec3(0., index as f32 * item_height - top, 0.),
+                )
+                .with(width(), outer_size.x)
+                .with(height(), item_height)
+                .init_default(children())
+                .children(vec![item(index)])
+                // Keeps the rows which stay in view as they are while scrolling
+                .key(index.to_string())
+        })
+        .collect();
+
+    MeasureSize::el(
+        MeasureAbsolutePosition::el(UIBase::el(), set_canvas_offset),
+        set_outer_size,
+    )
+    .on_spawned({
+        to_owned![id];
+        move |_world, canvas_id, _| {
+            *id.lock() = Some(canvas_id);
+        }
+    })
+    .init(mouse_pickable_min(), Vec3::ZERO)
+    .init(mouse_pickable_max(), Vec3::ZERO)
+    .init_default(children())
+    .children(vec![
+        UIBase::el()
+            .with(
+                scissors_recursive(),
+                get_scissors(canvas_offset, outer_size, ratio),
+            )
+            .init_default(children())
+            .children(rows),
+        scroll_bar(outer_size, scroll, scroll_height),
+    ])
+}
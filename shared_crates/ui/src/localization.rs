@@ -0,0 +1,106 @@
+//! Implements translating the UI into the current locale.
+//!
+//! Packages ship string tables built by the `StringTables` asset pipeline, and load them with
+//! [load_string_tables]. The runtime merges the strings of the current locale from all the loaded
+//! string tables, so a translation package can add a locale to other packages.
+//! [Text::tr](crate::text::Text::tr) shows a translated string that follows locale changes, and
+//! [tr!](crate::tr) looks strings up in code.
+use std::sync::{Arc, Mutex};
+
+use ambient_element::{to_owned, use_frame, use_ref_with, use_rerender_signal, Hooks};
+use ambient_guest_bridge::{
+    core::text::components::{
+        locale, string_tables_url, translation_keys, translation_revision, translation_strings,
+    },
+    ecs::{Entity, EntityId, World},
+};
+pub use ambient_shared_types::localization::{
+    format_string, plural_category, PluralCategory, Translations, DEFAULT_LOCALE,
+};
+
+/// Looks up the string with a key in [Translations], formatted with `name = value` arguments.
+/// A `count` argument also picks the plural form of the string.
+///
+/// ```ignore
+/// let translations = use_translations(hooks);
+/// tr!(translations, "menu.play");
+/// tr!(translations, "inventory.items", count = items.len());
+/// tr!(translations(world), "chat.joined", name = player_name);
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($translations:expr, $key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $translations.get($key, &[$((stringify!($name), $value.to_string().as_str())),*])
+    };
+}
+
+/// Loads the string tables at `url`, as written by the `StringTables` asset pipeline. Their
+/// strings are used for translations until the returned entity is despawned.
+pub fn load_string_tables(world: &mut World, url: impl Into<String>) -> EntityId {
+    world.spawn(Entity::new().with(string_tables_url(), url.into()))
+}
+
+/// Returns the current locale, like `en` or `fr-CA`.
+pub fn current_locale(world: &World) -> String {
+    world
+        .get_cloned(EntityId::resources(), locale())
+        .unwrap_or_else(|_| DEFAULT_LOCALE.to_string())
+}
+
+/// Switches to `value`, like `fr` or `pt-BR`, which translates all the text again.
+pub fn set_locale(world: &mut World, value: impl Into<String>) {
+    world
+        .add_component(EntityId::resources(), locale(), value.into())
+        .ok();
+}
+
+fn revision(world: &World) -> u32 {
+    world
+        .get(EntityId::resources(), translation_revision())
+        .unwrap_or_default()
+}
+
+/// Returns the strings of the current locale. They're cached until they change, so this is cheap
+/// to call.
+pub fn translations(world: &World) -> Arc<Translations> {
+    static CACHE: Mutex<Option<(u32, Arc<Translations>)>> = Mutex::new(None);
+
+    let revision = revision(world);
+    let mut cache = CACHE.lock().unwrap();
+    if let Some((cached, translations)) = &*cache {
+        if *cached == revision {
+            return translations.clone();
+        }
+    }
+    let keys = world
+        .get_cloned(EntityId::resources(), translation_keys())
+        .unwrap_or_default();
+    let strings = world
+        .get_cloned(EntityId::resources(), translation_strings())
+        .unwrap_or_default();
+    let translations = Arc::new(Translations::new(
+        current_locale(world),
+        keys.into_iter().zip(strings).collect(),
+    ));
+    *cache = Some((revision, translations.clone()));
+    translations
+}
+
+/// A hook that returns the strings of the current locale, and rerenders the element when they
+/// change, like when the locale is switched.
+pub fn use_translations(hooks: &mut Hooks) -> Arc<Translations> {
+    let rerender = use_rerender_signal(hooks);
+    let shown = use_ref_with(hooks, |world| revision(world));
+    use_frame(hooks, {
+        to_owned![shown];
+        move |world| {
+            let revision = revision(world);
+            let mut shown = shown.lock();
+            if *shown != revision {
+                *shown = revision;
+                rerender();
+            }
+        }
+    });
+    translations(hooks.world)
+}
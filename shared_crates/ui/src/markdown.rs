@@ -0,0 +1,587 @@
+//! Implements [Markdown], which renders a subset of Markdown as UI elements.
+//!
+//! The supported subset is headings, paragraphs, bulleted and numbered lists (which can be nested
+//! by indenting them), fenced code blocks, and within text: bold, italic, inline code, links and
+//! backslash escapes.
+use ambient_cb::{cb, Cb};
+use ambient_element::{element_component, Element, ElementComponentExt, Hooks};
+use ambient_guest_bridge::{
+    core::{
+        layout::{
+            components::{
+                fit_horizontal, flex_grow, flex_wrap, margin, padding, space_between_items,
+            },
+            types::Fit,
+        },
+        rendering::components::color,
+        text::{
+            components::{font_family, font_size, font_style},
+            types::FontStyle,
+        },
+    },
+    ecs::World,
+    window::set_cursor,
+};
+use ambient_shared_types::{CursorIcon, MouseButton};
+use glam::vec4;
+
+use crate::{
+    default_theme::{use_theme, Theme},
+    layout::{FlexRow, FlowColumn, FlowRow},
+    text::Text,
+    UIExt,
+};
+
+/// How a [Span] of text is shown
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+/// A run of text with the same [Style]
+#[derive(Debug, Clone, PartialEq)]
+struct Span {
+    text: String,
+    style: Style,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Heading(usize, Vec<Span>),
+    Paragraph(Vec<Span>),
+    ListItem {
+        depth: usize,
+        marker: String,
+        spans: Vec<Span>,
+    },
+    Code(Vec<String>),
+}
+
+#[derive(Default)]
+struct BlockParser {
+    blocks: Vec<Block>,
+    /// The text of the paragraph or list item being read
+    text: String,
+    /// The depth and marker of the list item being read, if it is one
+    item: Option<(usize, String)>,
+}
+impl BlockParser {
+    fn push_text(&mut self, line: &str) {
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        self.text.push_str(line.trim());
+    }
+    /// Ends the paragraph or list item being read
+    fn flush(&mut self) {
+        let text = std::mem::take(&mut self.text);
+        match self.item.take() {
+            Some((depth, marker)) => self.blocks.push(Block::ListItem {
+                depth,
+                marker,
+                spans: parse_inline(&text),
+            }),
+            None if !text.is_empty() => self.blocks.push(Block::Paragraph(parse_inline(&text))),
+            None => {}
+        }
+    }
+}
+
+fn parse_blocks(source: &str) -> Vec<Block> {
+    let mut parser = BlockParser::default();
+    let source = source.replace('\t', "    ");
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if trimmed.starts_with("```") {
+            parser.flush();
+            let code = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .map(|line| line.to_string())
+                .collect();
+            parser.blocks.push(Block::Code(code));
+        } else if trimmed.is_empty() {
+            parser.flush();
+        } else if let Some((level, title)) = heading(trimmed) {
+            parser.flush();
+            parser
+                .blocks
+                .push(Block::Heading(level, parse_inline(title)));
+        } else if let Some((marker, rest)) = list_marker(trimmed) {
+            parser.flush();
+            parser.item = Some((indent / 2, marker));
+            parser.push_text(rest);
+        } else {
+            // Other lines continue the paragraph or list item before them
+            parser.push_text(trimmed);
+        }
+    }
+    parser.flush();
+    parser.blocks
+}
+
+/// The level and title of a heading like `## Title`
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')))
+        .then(|| (level, rest.trim()))
+}
+
+/// The marker shown for a list item like `- Item` or `1. Item`, and the rest of the line
+fn list_marker(line: &str) -> Option<(String, &str)> {
+    if let Some(rest) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| line.strip_prefix(bullet))
+    {
+        return Some(("\u{2022}".to_string(), rest));
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let rest = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then(|| (line[..digits + 1].to_string(), rest))
+}
+
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    parse_inline_into(text, Style::default(), &mut spans);
+    spans
+}
+
+fn parse_inline_into(text: &str, mut style: Style, spans: &mut Vec<Span>) {
+    fn push(spans: &mut Vec<Span>, text: &mut String, style: &Style) {
+        if !text.is_empty() {
+            spans.push(Span {
+                text: std::mem::take(text),
+                style: style.clone(),
+            });
+        }
+    }
+
+    let mut current = String::new();
+    let mut previous = None;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        if c == '\\' {
+            if let Some(escaped) = after.chars().next() {
+                current.push(escaped);
+                previous = Some(escaped);
+                rest = &after[escaped.len_utf8()..];
+                continue;
+            }
+        } else if c == '`' {
+            if let Some(end) = after.find('`') {
+                push(spans, &mut current, &style);
+                spans.push(Span {
+                    text: after[..end].to_string(),
+                    style: Style {
+                        code: true,
+                        ..style.clone()
+                    },
+                });
+                previous = Some('`');
+                rest = &after[end + 1..];
+                continue;
+            }
+        } else if c == '[' {
+            if let Some((label, url, len)) = link(rest) {
+                push(spans, &mut current, &style);
+                let link_style = Style {
+                    link: Some(url.to_string()),
+                    ..style.clone()
+                };
+                parse_inline_into(label, link_style, spans);
+                previous = Some(')');
+                rest = &rest[len..];
+                continue;
+            }
+        } else if rest.starts_with("**") || rest.starts_with("__") {
+            let marker = &rest[..2];
+            // Markers which aren't closed are shown as they are
+            if style.bold || rest[2..].contains(marker) {
+                push(spans, &mut current, &style);
+                style.bold = !style.bold;
+            } else {
+                current.push_str(marker);
+            }
+            previous = Some(c);
+            rest = &rest[2..];
+            continue;
+        } else if c == '*' || c == '_' {
+            // `_` within a word, like in `snake_case`, isn't a marker
+            let in_word = c == '_'
+                && previous.map_or(false, char::is_alphanumeric)
+                && after.chars().next().map_or(false, char::is_alphanumeric);
+            if !in_word && (style.italic || after.contains(c)) {
+                push(spans, &mut current, &style);
+                style.italic = !style.italic;
+                previous = Some(c);
+                rest = after;
+                continue;
+            }
+        }
+        current.push(c);
+        previous = Some(c);
+        rest = after;
+    }
+    push(spans, &mut current, &style);
+}
+
+/// The label and url of a link like `[label](url)` at the start of `text`, and its length
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let close = text.find("](")?;
+    let after = &text[close + 2..];
+    let end = after.find(')')?;
+    Some((&text[1..close], &after[..end], close + 2 + end + 1))
+}
+
+/// Splits `spans` into words, made of the parts of the spans in them, so that lines can wrap
+/// between words. Inline code isn't split.
+fn words(spans: Vec<Span>) -> Vec<Vec<Span>> {
+    let mut words = Vec::new();
+    let mut word = Vec::new();
+    for span in spans {
+        if span.style.code {
+            word.push(span);
+            continue;
+        }
+        // The first part continues the word before it
+        for (i, part) in span.text.split(char::is_whitespace).enumerate() {
+            if i > 0 && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            if !part.is_empty() {
+                word.push(Span {
+                    text: part.to_string(),
+                    style: span.style.clone(),
+                });
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+type LinkCb = Cb<dyn Fn(&mut World, String) + Sync + Send>;
+
+/// Renders text which wraps to the width of its parent, with the words of `spans`
+fn text_block(
+    theme: &Theme,
+    spans: Vec<Span>,
+    size: f32,
+    bold: bool,
+    on_link: &Option<LinkCb>,
+) -> Element {
+    let words = words(spans).into_iter().map(|word| {
+        let mut parts = word
+            .into_iter()
+            .map(|span| span_element(theme, span, size, bold, on_link))
+            .collect::<Vec<_>>();
+        if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            FlowRow::el(parts)
+        }
+    });
+    FlexRow::el(words)
+        .with(fit_horizontal(), Fit::Parent)
+        .with(flex_wrap(), true)
+        .with(space_between_items(), (size * 0.3).round())
+}
+
+fn span_element(
+    theme: &Theme,
+    span: Span,
+    size: f32,
+    bold: bool,
+    on_link: &Option<LinkCb>,
+) -> Element {
+    let Span { text, style } = span;
+    let text_color = if style.link.is_some() {
+        theme.primary_color
+    } else if bold {
+        theme.text_color
+    } else {
+        theme.body_text_color
+    };
+    let text = Text::el(text)
+        .with(font_size(), size)
+        .with(
+            font_style(),
+            match (bold || style.bold, style.italic) {
+                (false, false) => FontStyle::Regular,
+                (true, false) => FontStyle::Bold,
+                (false, true) => FontStyle::Italic,
+                (true, true) => FontStyle::BoldItalic,
+            },
+        )
+        .with(color(), text_color.into());
+    let element = if style.code {
+        FlowRow::el([text.with(font_family(), "Code".to_string())])
+            .with_background(theme.cutout_color.into())
+            .with(padding(), vec4(0., 2., 0., 2.))
+    } else {
+        text
+    };
+    match (style.link, on_link) {
+        (Some(url), Some(on_link)) => {
+            let on_link = on_link.clone();
+            element
+                .with_clickarea()
+                .on_mouse_enter(|world, _| set_cursor(world, CursorIcon::Hand))
+                .on_mouse_leave(|world, _| set_cursor(world, CursorIcon::Default))
+                .on_mouse_up(move |world, _, button| {
+                    if button == MouseButton::Left {
+                        on_link(world, url.clone());
+                    }
+                })
+                .el()
+        }
+        _ => element,
+    }
+}
+
+#[element_component]
+/// Renders `source`, a subset of Markdown: `#` headings, paragraphs, lists starting with `-`, `*`,
+/// `+` or a number, fenced code blocks, `**bold**`, `*italic*`, `` `code` `` and `[links](url)`.
+///
+/// Text wraps to the width of the element, which has to be set with `width` or `fit_horizontal`.
+/// Clicking a link calls `on_link` with its url; without it, links can't be clicked.
+pub fn Markdown(
+    hooks: &mut Hooks,
+    /// The Markdown to render.
+    source: String,
+    /// The callback to invoke with the url of a link when it's clicked.
+    on_link: Option<Cb<dyn Fn(&mut World, String) + Sync + Send>>,
+) -> Element {
+    let theme = use_theme(hooks);
+    let blocks = parse_blocks(&source).into_iter().map(|block| match block {
+        Block::Heading(level, spans) => {
+            let size = match level {
+                1 => theme.header_font_size,
+                2 => theme.section_font_size,
+                _ => theme.font_size,
+            };
+            text_block(&theme, spans, size, true, &on_link)
+        }
+        Block::Paragraph(spans) => text_block(&theme, spans, theme.font_size, false, &on_link),
+        Block::ListItem {
+            depth,
+            marker,
+            spans,
+        } => FlexRow::el([
+            Text::el(marker).with(margin(), vec4(0., theme.spacing, 0., 0.)),
+            text_block(&theme, spans, theme.font_size, false, &on_link)
+                .with(fit_horizontal(), Fit::None)
+                .with(flex_grow(), 1.),
+        ])
+        .with(fit_horizontal(), Fit::Parent)
+        .with(
+            margin(),
+            vec4(0., 0., 0., theme.spacing * 2. * depth as f32),
+        ),
+        Block::Code(lines) => FlowColumn::el(lines.into_iter().map(|line| {
+            // Empty lines are kept as tall as the others
+            Text::el(if line.is_empty() {
+                " ".to_string()
+            } else {
+                line
+            })
+            .with(font_family(), "Code".to_string())
+            .with(color(), theme.text_color.into())
+        }))
+        .with(fit_horizontal(), Fit::Parent)
+        .with_background(theme.cutout_color.into())
+        .with_padding_even(theme.spacing),
+    });
+    FlowColumn::el(blocks)
+        .with(fit_horizontal(), Fit::None)
+        .with(space_between_items(), theme.spacing)
+}
+impl Markdown {
+    /// Creates a new [Markdown] element rendering `source`.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            on_link: None,
+        }
+    }
+    /// Sets the callback to invoke with the url of a link when it's clicked.
+    pub fn on_link(mut self, on_link: impl Fn(&mut World, String) + Sync + Send + 'static) -> Self {
+        self.on_link = Some(cb(on_link));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str, style: Style) -> Span {
+        Span {
+            text: text.to_string(),
+            style,
+        }
+    }
+    fn plain(text: &str) -> Span {
+        span(text, Style::default())
+    }
+    fn bold(italic: bool) -> Style {
+        Style {
+            bold: true,
+            italic,
+            ..Default::default()
+        }
+    }
+    fn italic() -> Style {
+        Style {
+            italic: true,
+            ..Default::default()
+        }
+    }
+    fn code(text: &str) -> Span {
+        span(
+            text,
+            Style {
+                code: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn emphasis_can_be_nested() {
+        assert_eq!(
+            parse_inline("**bold *both* bold**"),
+            [
+                span("bold ", bold(false)),
+                span("both", bold(true)),
+                span(" bold", bold(false)),
+            ]
+        );
+        assert_eq!(
+            parse_inline("_a **b** c_"),
+            [
+                span("a ", italic()),
+                span("b", bold(true)),
+                span(" c", italic()),
+            ]
+        );
+        // Markers which aren't closed, and `_` within words, are text
+        assert_eq!(parse_inline("2 ** 3 * 4"), [plain("2 ** 3 * 4")]);
+        assert_eq!(parse_inline("snake_case_name"), [plain("snake_case_name")]);
+        assert_eq!(parse_inline(r"\*not italic\*"), [plain("*not italic*")]);
+    }
+
+    #[test]
+    fn code_spans_are_not_parsed() {
+        assert_eq!(
+            parse_inline("use `a*b` and `**`"),
+            [plain("use "), code("a*b"), plain(" and "), code("**")]
+        );
+        assert_eq!(parse_inline("a `b"), [plain("a `b")]);
+    }
+
+    #[test]
+    fn links_keep_the_style_of_their_label() {
+        let link = |italic| Style {
+            italic,
+            link: Some("https://ambient.run/docs".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_inline("see [the *docs*](https://ambient.run/docs) now"),
+            [
+                plain("see "),
+                span("the ", link(false)),
+                span("docs", link(true)),
+                plain(" now"),
+            ]
+        );
+        assert_eq!(parse_inline("[a] (b)"), [plain("[a] (b)")]);
+    }
+
+    #[test]
+    fn blocks_are_split() {
+        let source = "# Title\n\nSome *text*\ncontinued\n\n- one\n  - nested\n2. two\n\n```\nlet x = 1;\n\n```\nafter";
+        assert_eq!(
+            parse_blocks(source),
+            [
+                Block::Heading(1, vec![plain("Title")]),
+                Block::Paragraph(vec![
+                    plain("Some "),
+                    span("text", italic()),
+                    plain(" continued"),
+                ]),
+                Block::ListItem {
+                    depth: 0,
+                    marker: "\u{2022}".to_string(),
+                    spans: vec![plain("one")],
+                },
+                Block::ListItem {
+                    depth: 1,
+                    marker: "\u{2022}".to_string(),
+                    spans: vec![plain("nested")],
+                },
+                Block::ListItem {
+                    depth: 0,
+                    marker: "2.".to_string(),
+                    spans: vec![plain("two")],
+                },
+                Block::Code(vec!["let x = 1;".to_string(), String::new()]),
+                Block::Paragraph(vec![plain("after")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_input_does_not_panic() {
+        let sources = [
+            "",
+            "*",
+            "**",
+            "_",
+            "`",
+            "\\",
+            "a\\",
+            "[",
+            "[a](",
+            "[](b)",
+            "[a](b",
+            "](",
+            "# ",
+            "#######",
+            "1.",
+            "1. ",
+            "- ",
+            "```",
+            "```\nunclosed",
+            "**a *b** c*",
+            "é*ü*",
+            "[é](ü",
+            "🙂**🙂",
+            "\t- x",
+            "  \n\n",
+            "***",
+            "*_*_",
+            "`**`**",
+        ];
+        for source in sources {
+            for block in parse_blocks(source) {
+                if let Block::Heading(_, spans)
+                | Block::Paragraph(spans)
+                | Block::ListItem { spans, .. } = block
+                {
+                    words(spans);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Implements navigating the UI without a mouse.
+//!
+//! The runtime moves the focus between `focusable` entities with the Tab and arrow keys, and sends
+//! `FocusActivated` for the Enter and Space keys. Other inputs, like gamepads, can do the same with
+//! [navigate_focus], [step_focus] and [activate_focus].
+use ambient_element::{
+    element_component, to_owned, use_frame, use_module_message, use_ref_with, use_rerender_signal,
+    Element, Hooks,
+};
+use ambient_guest_bridge::{
+    broadcast_local_message,
+    core::{
+        hierarchy::components::children,
+        ui::{
+            components::{focus, focus_order, focus_visible, focusable},
+            messages::{FocusActivated, FocusChanged, FocusNavigate},
+        },
+    },
+    ecs::{EntityId, World},
+};
+use glam::Vec2;
+
+use crate::{layout::FlowRow, set_focus};
+
+/// Moves the focus to the nearest focusable element in `direction` on the screen, where +y is
+/// down. Does nothing if nothing is focused.
+pub fn navigate_focus(world: &mut World, direction: Vec2) {
+    broadcast_local_message(world, FocusNavigate::new(direction, 0));
+}
+
+/// Moves the focus by `step` elements in the focus order, like Tab (1) and Shift+Tab (-1).
+pub fn step_focus(world: &mut World, step: i32) {
+    broadcast_local_message(world, FocusNavigate::new(Vec2::ZERO, step));
+}
+
+/// Activates the focused element, like a click.
+pub fn activate_focus(world: &mut World) {
+    let focus_id = world
+        .get_cloned(EntityId::resources(), focus())
+        .unwrap_or_default();
+    if !focus_id.is_empty() {
+        broadcast_local_message(world, FocusActivated::new(focus_id));
+    }
+}
+
+/// Focuses the element with the focus id `focus_id` as if it was navigated to, so that it shows
+/// that it is focused.
+pub fn navigate_focus_to(world: &mut World, focus_id: String) {
+    let visible = !focus_id.is_empty();
+    let was_visible = world
+        .get(EntityId::resources(), focus_visible())
+        .unwrap_or(false);
+    world
+        .set(EntityId::resources(), focus_visible(), visible)
+        .ok();
+    let current = world
+        .get_cloned(EntityId::resources(), focus())
+        .unwrap_or_default();
+    if current == focus_id && was_visible != visible {
+        // The focus doesn't change, but how it's shown does
+        broadcast_local_message(world, FocusChanged::new(false, focus_id));
+    } else {
+        set_focus(world, focus_id);
+    }
+}
+
+/// A hook that returns whether the focus was moved by navigating rather than by clicking.
+///
+/// Focused elements should only show that they are focused when it was.
+pub fn use_focus_visible(hooks: &mut Hooks) -> bool {
+    let rerender = use_rerender_signal(hooks);
+    use_module_message::<FocusChanged>(hooks, move |_, _, _| {
+        rerender();
+    });
+    hooks
+        .world
+        .get(EntityId::resources(), focus_visible())
+        .unwrap_or(false)
+}
+
+/// A hook which invokes `func` when the element with the focus id `focus_id` is activated.
+pub fn use_focus_activated(
+    hooks: &mut Hooks,
+    focus_id: String,
+    func: impl Fn(&mut World) + Sync + Send + 'static,
+) {
+    use_module_message::<FocusActivated>(hooks, move |world, _, event| {
+        if event.focus == focus_id {
+            func(world);
+        }
+    });
+}
+
+#[element_component]
+/// Focuses the first focusable element in `inner` when it's shown, as if it was navigated to.
+///
+/// The first element is the one with the lowest `focus_order`, then the first one in `inner`.
+/// Menus meant to be used with a gamepad or keyboard can use this, so that they can be navigated
+/// right away.
+pub fn InitialFocus(
+    hooks: &mut Hooks,
+    /// The element to focus an element of.
+    inner: Element,
+) -> Element {
+    let id = use_ref_with(hooks, |_| None::<EntityId>);
+    let done = use_ref_with(hooks, |_| false);
+    use_frame(hooks, {
+        to_owned![id];
+        move |world| {
+            let Some(id) = *id.lock() else {
+                return;
+            };
+            let mut done = done.lock();
+            if *done {
+                return;
+            }
+            *done = true;
+            if let Some(focus_id) = first_focusable(world, id) {
+                navigate_focus_to(world, focus_id);
+            }
+        }
+    });
+    FlowRow::el([inner]).on_spawned(move |_, spawned, _| *id.lock() = Some(spawned))
+}
+
+/// The focus id of the first focusable entity under `root`, in the focus order and then in the
+/// tree
+fn first_focusable(world: &World, root: EntityId) -> Option<String> {
+    let mut stack = vec![root];
+    let mut first: Option<(i32, String)> = None;
+    while let Some(id) = stack.pop() {
+        if let Ok(focus_id) = world.get_cloned(id, focusable()) {
+            let order = world.get(id, focus_order()).unwrap_or(0);
+            if !focus_id.is_empty() && first.as_ref().map_or(true, |(first, _)| order < *first) {
+                first = Some((order, focus_id));
+            }
+        }
+        if let Ok(children) = world.get_cloned(id, children()) {
+            stack.extend(children.into_iter().rev());
+        }
+    }
+    first.map(|(_, focus_id)| focus_id)
+}
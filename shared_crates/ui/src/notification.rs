@@ -0,0 +1,503 @@
+//! Implements notifications, which are shown in a corner of the screen for a while, and modal
+//! dialogs, which block the UI until one of their actions is picked.
+//!
+//! Both are shown by the [NotificationCenter], which the runtime spawns above the UI of all
+//! packages. It's driven by messages, so the runtime and packages alike can show them with
+//! [Notification::show] and [Modal::show], and react to the picked actions with
+//! [use_notification_action] and [use_modal_response].
+use std::collections::VecDeque;
+#[cfg(feature = "guest")]
+use std::time::Instant;
+
+use ambient_color::Color;
+use ambient_element::{
+    element_component, to_owned, use_frame, use_module_message, use_ref_with, use_rerender_signal,
+    Element, ElementComponentExt, Group, Hooks,
+};
+pub use ambient_guest_bridge::core::ui::{
+    messages::{
+        ModalResponse, ModalShow, NotificationAction, NotificationDismiss, NotificationShow,
+    },
+    types::NotificationLevel,
+};
+use ambient_guest_bridge::{
+    broadcast_local_message,
+    core::{
+        layout::{
+            components::{align_horizontal, fit_horizontal, space_between_items, width},
+            types::{Align, Fit},
+        },
+        rect::components::{border_color, border_thickness},
+        transform::components::translation,
+    },
+    ecs::World,
+};
+#[cfg(feature = "native")]
+use ambient_sys::time::Instant;
+use glam::vec3;
+
+use crate::{
+    accessibility::{AccessibilityExt, AccessibilityRole},
+    button::{Button, ButtonStyle},
+    default_theme::{use_theme, StylesExt, Theme},
+    layout::{Centered, FlowColumn, FlowRow, WindowSized},
+    text::Text,
+    UIExt,
+};
+
+/// How long notifications are shown for by default, in seconds.
+pub const DEFAULT_NOTIFICATION_TIMEOUT: f32 = 5.;
+/// How many notifications are shown at once; the others wait until there's room for them.
+pub const MAX_VISIBLE_NOTIFICATIONS: usize = 5;
+/// The width of notifications.
+const NOTIFICATION_WIDTH: f32 = 300.;
+/// The depth of the notifications and the dialogs, in front of the UI of packages.
+const NOTIFICATIONS_Z: f32 = -0.8;
+const MODAL_Z: f32 = -0.9;
+
+/// A notification, shown in a corner of the screen by the [NotificationCenter].
+///
+/// ```ignore
+/// Notification::warning("Low health")
+///     .body("Find a medkit")
+///     .action("Heal")
+///     .id("health")
+///     .show(world);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// Identifies the notification, for replacing and dismissing it, and for its actions.
+    pub id: String,
+    /// The title of the notification.
+    pub title: String,
+    /// The text below the title; can be empty.
+    pub body: String,
+    /// How important the notification is.
+    pub level: NotificationLevel,
+    /// Notifications with a higher priority are shown first.
+    pub priority: i32,
+    /// How long the notification is shown for, in seconds; zero shows it until it's dismissed.
+    pub timeout: f32,
+    /// The labels of the buttons of the notification.
+    pub actions: Vec<String>,
+}
+impl Notification {
+    /// Creates a notification with `title`, which is shown for [DEFAULT_NOTIFICATION_TIMEOUT]
+    /// seconds.
+    pub fn new(level: NotificationLevel, title: impl Into<String>) -> Self {
+        Self {
+            id: String::new(),
+            title: title.into(),
+            body: String::new(),
+            level,
+            priority: 0,
+            timeout: DEFAULT_NOTIFICATION_TIMEOUT,
+            actions: Vec::new(),
+        }
+    }
+    /// Creates an informational notification.
+    pub fn info(title: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Info, title)
+    }
+    /// Creates a notification that something finished successfully.
+    pub fn success(title: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Success, title)
+    }
+    /// Creates a notification that something might be wrong.
+    pub fn warning(title: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Warning, title)
+    }
+    /// Creates a notification that something failed.
+    pub fn error(title: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Error, title)
+    }
+    /// Sets the id of the notification. Showing a notification with the id of a shown one
+    /// replaces it, instead of showing both.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+    /// Sets the text below the title.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+    /// Sets the priority; notifications with a higher priority are shown first.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+    /// Sets how long the notification is shown for, in seconds; zero shows it until it's
+    /// dismissed.
+    pub fn timeout(mut self, timeout: f32) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    /// Adds a button labeled `action`. Clicking it dismisses the notification and sends
+    /// [NotificationAction], which can be handled with [use_notification_action].
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+    /// Shows the notification.
+    pub fn show(self, world: &mut World) {
+        broadcast_local_message(
+            world,
+            NotificationShow::new(
+                self.id,
+                self.title,
+                self.body,
+                self.level,
+                self.priority,
+                self.timeout,
+                self.actions,
+            ),
+        );
+    }
+    /// Hides the notification with `id`.
+    pub fn dismiss(world: &mut World, id: impl Into<String>) {
+        broadcast_local_message(world, NotificationDismiss::new(id));
+    }
+}
+
+/// A modal dialog, shown by the [NotificationCenter] in front of everything else. The UI can't be
+/// used until one of its actions is picked.
+#[derive(Debug, Clone)]
+pub struct Modal {
+    /// Identifies the dialog in its [ModalResponse].
+    pub id: String,
+    /// The title of the dialog.
+    pub title: String,
+    /// The text below the title; can be empty.
+    pub body: String,
+    /// The labels of the buttons of the dialog.
+    pub actions: Vec<String>,
+}
+impl Modal {
+    /// Creates a dialog with `title`, identified by `id`.
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            body: String::new(),
+            actions: Vec::new(),
+        }
+    }
+    /// Sets the text below the title.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+    /// Adds a button labeled `action`. Dialogs without actions have an `Ok` button.
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.actions.push(action.into());
+        self
+    }
+    /// Shows the dialog, after the dialogs that are already shown or waiting to be.
+    pub fn show(self, world: &mut World) {
+        broadcast_local_message(
+            world,
+            ModalShow::new(self.id, self.title, self.body, self.actions),
+        );
+    }
+}
+
+/// A hook which invokes `func` with the action when one of the actions of the notification with
+/// `id` is clicked.
+pub fn use_notification_action(
+    hooks: &mut Hooks,
+    id: String,
+    func: impl Fn(&mut World, &str) + Sync + Send + 'static,
+) {
+    use_module_message::<NotificationAction>(hooks, move |world, _, event| {
+        if event.id == id {
+            func(world, &event.action);
+        }
+    });
+}
+
+/// A hook which invokes `func` with the action when one of the actions of the modal dialog with
+/// `id` is clicked.
+pub fn use_modal_response(
+    hooks: &mut Hooks,
+    id: String,
+    func: impl Fn(&mut World, &str) + Sync + Send + 'static,
+) {
+    use_module_message::<ModalResponse>(hooks, move |world, _, event| {
+        if event.id == id {
+            func(world, &event.action);
+        }
+    });
+}
+
+#[derive(Debug, Clone)]
+struct QueuedNotification {
+    notification: NotificationShow,
+    /// How many times the same notification was shown
+    count: u32,
+    /// When the notification is hidden; set once it's visible
+    expires: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    notifications: Vec<QueuedNotification>,
+    modals: VecDeque<ModalShow>,
+}
+impl Queue {
+    fn show(&mut self, notification: NotificationShow) {
+        let existing = if notification.id.is_empty() {
+            None
+        } else {
+            self.notifications
+                .iter_mut()
+                .find(|queued| queued.notification.id == notification.id)
+        };
+        if let Some(queued) = existing {
+            let same = queued.notification.title == notification.title
+                && queued.notification.body == notification.body;
+            queued.count = if same { queued.count + 1 } else { 1 };
+            queued.notification = notification;
+            queued.expires = None;
+        } else {
+            self.notifications.push(QueuedNotification {
+                notification,
+                count: 1,
+                expires: None,
+            });
+        }
+        // Stable, so notifications with the same priority stay in the order they were shown in
+        self.notifications
+            .sort_by_key(|queued| std::cmp::Reverse(queued.notification.priority));
+    }
+    fn dismiss(&mut self, id: &str) {
+        self.notifications
+            .retain(|queued| queued.notification.id != id);
+    }
+    /// Hides the expired notifications, and starts the timeouts of the ones that became visible.
+    /// Returns true if anything changed.
+    fn update(&mut self, now: Instant) -> bool {
+        let count = self.notifications.len();
+        self.notifications
+            .retain(|queued| queued.expires.map_or(true, |expires| expires > now));
+        let mut changed = self.notifications.len() != count;
+        for queued in self
+            .notifications
+            .iter_mut()
+            .take(MAX_VISIBLE_NOTIFICATIONS)
+        {
+            let timeout = queued.notification.timeout;
+            if queued.expires.is_none() && timeout > 0. {
+                queued.expires = Some(now + std::time::Duration::from_secs_f32(timeout));
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+fn level_color(theme: &Theme, level: NotificationLevel) -> Color {
+    match level {
+        NotificationLevel::Info => theme.primary_color,
+        NotificationLevel::Success => theme.success_color,
+        NotificationLevel::Warning => theme.warning_color,
+        NotificationLevel::Error => theme.error_color,
+    }
+}
+
+#[element_component]
+/// Shows the notifications and modal dialogs sent with [NotificationShow] and [ModalShow].
+///
+/// The runtime spawns one for the client, so packages don't need to; they only send messages to
+/// it, through [Notification] and [Modal].
+pub fn NotificationCenter(hooks: &mut Hooks) -> Element {
+    let queue = use_ref_with(hooks, |_| Queue::default());
+    let rerender = use_rerender_signal(hooks);
+    use_module_message::<NotificationShow>(hooks, {
+        to_owned![queue, rerender];
+        move |_, _, event| {
+            queue.lock().show(event.clone());
+            rerender();
+        }
+    });
+    use_module_message::<NotificationDismiss>(hooks, {
+        to_owned![queue, rerender];
+        move |_, _, event| {
+            queue.lock().dismiss(&event.id);
+            rerender();
+        }
+    });
+    use_module_message::<ModalShow>(hooks, {
+        to_owned![queue, rerender];
+        move |_, _, event| {
+            queue.lock().modals.push_back(event.clone());
+            rerender();
+        }
+    });
+    use_frame(hooks, {
+        to_owned![queue, rerender];
+        move |_| {
+            if queue.lock().update(Instant::now()) {
+                rerender();
+            }
+        }
+    });
+
+    let theme = use_theme(hooks);
+    let (notifications, modal) = {
+        let queue = queue.lock();
+        (
+            queue
+                .notifications
+                .iter()
+                .take(MAX_VISIBLE_NOTIFICATIONS)
+                .cloned()
+                .collect::<Vec<_>>(),
+            queue.modals.front().cloned(),
+        )
+    };
+
+    let notifications = notifications
+        .into_iter()
+        .map(|queued| {
+            let id = queued.notification.id.clone();
+            let dismiss = {
+                to_owned![queue, rerender];
+                move |world: &mut World, action: Option<String>| {
+                    queue.lock().dismiss(&id);
+                    rerender();
+                    if let Some(action) = action {
+                        broadcast_local_message(world, NotificationAction::new(id.clone(), action));
+                    }
+                }
+            };
+            notification_view(&theme, queued, dismiss)
+        })
+        .collect::<Vec<_>>();
+
+    let modal = modal.map(|modal| {
+        let respond = {
+            to_owned![queue, rerender];
+            let id = modal.id.clone();
+            move |world: &mut World, action: String| {
+                queue.lock().modals.pop_front();
+                rerender();
+                broadcast_local_message(world, ModalResponse::new(id.clone(), action));
+            }
+        };
+        modal_view(modal, respond)
+    });
+
+    Group::el([
+        WindowSized::el([FlowColumn::el(notifications)
+            .with(align_horizontal(), Align::End)
+            .with(fit_horizontal(), Fit::None)
+            .with(space_between_items(), theme.spacing)
+            .with_padding_even(theme.spacing)])
+        .with(translation(), vec3(0., 0., NOTIFICATIONS_Z)),
+        modal
+            .map(|modal| modal.with(translation(), vec3(0., 0., MODAL_Z)))
+            .unwrap_or_default(),
+    ])
+}
+
+fn notification_view(
+    theme: &Theme,
+    queued: QueuedNotification,
+    dismiss: impl Fn(&mut World, Option<String>) + Clone + Sync + Send + 'static,
+) -> Element {
+    let QueuedNotification {
+        notification,
+        count,
+        ..
+    } = queued;
+    let title = if count > 1 {
+        format!("{} ({count})", notification.title)
+    } else {
+        notification.title
+    };
+
+    let buttons = notification
+        .actions
+        .into_iter()
+        .map(|action| {
+            let dismiss = dismiss.clone();
+            Button::new(action.clone(), move |world| {
+                dismiss(world, Some(action.clone()))
+            })
+            .style(ButtonStyle::Primary)
+            .el()
+        })
+        .chain([Button::new("Dismiss", move |world| dismiss(world, None))
+            .style(ButtonStyle::Flat)
+            .el()])
+        .collect::<Vec<_>>();
+
+    FlowColumn::el([
+        Text::el(title).section_style(),
+        if notification.body.is_empty() {
+            Element::new()
+        } else {
+            Text::el(notification.body)
+        },
+        FlowRow::el(buttons).with(space_between_items(), theme.spacing),
+    ])
+    .with(space_between_items(), theme.spacing)
+    .with(fit_horizontal(), Fit::None)
+    .with(width(), NOTIFICATION_WIDTH)
+    .floating_panel()
+    .with(
+        border_color(),
+        level_color(theme, notification.level).into(),
+    )
+    .with(border_thickness(), 2.)
+    .with_role(match notification.level {
+        NotificationLevel::Warning | NotificationLevel::Error => AccessibilityRole::Alert,
+        NotificationLevel::Info | NotificationLevel::Success => AccessibilityRole::Status,
+    })
+    // Clicks on the notification shouldn't go through to what's behind it
+    .with_clickarea()
+    .el()
+}
+
+fn modal_view(
+    modal: ModalShow,
+    respond: impl Fn(&mut World, String) + Clone + Sync + Send + 'static,
+) -> Element {
+    let actions = if modal.actions.is_empty() {
+        vec!["Ok".to_string()]
+    } else {
+        modal.actions
+    };
+    let buttons = actions
+        .into_iter()
+        .map(|action| {
+            let respond = respond.clone();
+            Button::new(action.clone(), move |world| respond(world, action.clone()))
+                .style(ButtonStyle::Primary)
+                .el()
+        })
+        .collect::<Vec<_>>();
+
+    let spacing = Theme::current().spacing;
+    let title = modal.title;
+    // The dimmed background takes the clicks, so the UI behind the dialog can't be used
+    WindowSized::el([Centered::el([FlowColumn::el([
+        Text::el(title.clone()).header_style(),
+        if modal.body.is_empty() {
+            Element::new()
+        } else {
+            Text::el(modal.body)
+        },
+        FlowRow::el(buttons).with(space_between_items(), spacing),
+    ])
+    .with(space_between_items(), spacing)
+    .floating_panel()
+    .with_role(AccessibilityRole::Dialog)
+    .with_label(title)
+    .with_clickarea()
+    .el()])])
+    .with_background(Color::rgba(0., 0., 0., 0.6).into())
+    .with_clickarea()
+    .el()
+}
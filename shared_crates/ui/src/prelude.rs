@@ -1,11 +1,14 @@
 //! A prelude for users of the crate. Imports all the most commonly used types and functions.
 
 pub use crate::{
-    button::*, clickarea::*, default_theme::*, dropdown::*, editor::*, layout::*, prompt::*,
-    screens::*, scroll_area::*, select::*, tabs::*, text::*, throbber::*, window::*, with_rect,
-    Line, Rectangle, UIBase, UIElement, UIExt,
+    accessibility::*, button::*, canvas::*, clickarea::*, default_theme::*, drag_drop::*,
+    dropdown::*, editor::*, layout::*, list_view::*, localization::*, markdown::*, navigation::*,
+    notification::*, prompt::*, property_grid::*, screens::*, scroll_area::*, select::*, table::*,
+    tabs::*, text::*, throbber::*, tr, transition::*, window::*, with_rect, Line, Rectangle,
+    UIBase, UIElement, UIExt,
 };
 pub use ambient_cb::{cb, Cb};
+pub use ambient_color::Color;
 pub use ambient_element::{
     self, element_component, to_owned, Element, ElementComponent, ElementComponentExt, ElementTree,
     Group, Hooks, Memo, Wrap,
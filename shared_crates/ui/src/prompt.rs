@@ -12,7 +12,7 @@ use ambient_guest_bridge::{
 
 use crate::{
     button::{Button, ButtonStyle},
-    default_theme::{StylesExt, STREET},
+    default_theme::{use_theme, StylesExt},
     editor::{Editor, TextEditor},
     layout::{FlowColumn, FlowRow},
     screens::DialogScreen,
@@ -25,7 +25,7 @@ use crate::{
 ///
 /// If neither `on_ok` nor `on_cancel` are specified, the user will not be able to dismiss the alert.
 pub fn Alert(
-    _hooks: &mut Hooks,
+    hooks: &mut Hooks,
     /// The title of the alert.
     title: String,
     /// The callback to set the screen to `None`.
@@ -35,6 +35,7 @@ pub fn Alert(
     /// The callback to run when the user clicks "Cancel".
     on_cancel: Option<Cb<dyn Fn(&mut World) + Sync + Send>>,
 ) -> Element {
+    let spacing = use_theme(hooks).spacing;
     DialogScreen(
         FlowColumn::el([
             Text::el(title).header_style(),
@@ -62,9 +63,9 @@ pub fn Alert(
                     Element::new()
                 },
             ])
-            .with(space_between_items(), STREET),
+            .with(space_between_items(), spacing),
         ])
-        .with(space_between_items(), STREET),
+        .with(space_between_items(), spacing),
     )
     .el()
 }
@@ -102,6 +103,7 @@ pub fn Prompt(
     on_cancel: Option<Cb<dyn Fn(&mut World) + Sync + Send>>,
 ) -> Element {
     let (value, set_value) = use_state(hooks, "".to_string());
+    let spacing = use_theme(hooks).spacing;
     DialogScreen(
         FlowColumn::el([
             Text::el(title).header_style(),
@@ -125,9 +127,9 @@ pub fn Prompt(
                 },
             ])
             .with(align_vertical(), Align::Center)
-            .with(space_between_items(), STREET),
+            .with(space_between_items(), spacing),
         ])
-        .with(space_between_items(), STREET),
+        .with(space_between_items(), spacing),
     )
     .el()
 }
@@ -194,6 +196,7 @@ pub fn EditorPrompt<T: Editor + std::fmt::Debug + Clone + Sync + Send + 'static>
     validator: Option<Cb<dyn Fn(&T) -> bool + Sync + Send>>,
 ) -> Element {
     let (value, set_value) = use_state(hooks, value);
+    let spacing = use_theme(hooks).spacing;
     DialogScreen(ScrollArea::el(
         ScrollAreaSizing::FitParentWidth,
         FlowColumn::el([
@@ -224,9 +227,9 @@ pub fn EditorPrompt<T: Editor + std::fmt::Debug + Clone + Sync + Send + 'static>
             ])
             .el()
             .with(align_vertical(), Align::Center)
-            .with(space_between_items(), STREET),
+            .with(space_between_items(), spacing),
         ])
-        .with(space_between_items(), STREET),
+        .with(space_between_items(), spacing),
     ))
     .el()
 }
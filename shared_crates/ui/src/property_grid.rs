@@ -0,0 +1,137 @@
+//! Implements a [PropertyGrid], which shows labelled editors in two columns, and [ComponentEditor],
+//! which edits a component of an entity.
+use std::fmt::Debug;
+
+use ambient_cb::cb;
+use ambient_element::{
+    element_component, to_owned, use_frame, use_ref_with, use_state_with, Element,
+    ElementComponent, ElementComponentExt, Hooks,
+};
+use ambient_guest_bridge::{
+    core::layout::{
+        components::{fit_horizontal, fit_vertical, grid_columns, space_between_items},
+        types::Fit,
+    },
+    ecs::{Component, ComponentDesc, ComponentValue, EntityId},
+};
+use convert_case::{Case, Casing};
+
+use crate::{
+    default_theme::{use_theme, StylesExt},
+    editor::{Editor, EditorOpts},
+    layout::{grid_tracks, Grid, GridTrack},
+    text::Text,
+};
+
+/// A labelled row of a [PropertyGrid].
+#[derive(Debug, Clone)]
+pub struct Property {
+    /// The label shown in the first column.
+    pub label: String,
+    /// The element shown in the second column, usually an editor.
+    pub editor: Element,
+}
+impl Property {
+    /// Creates a new property showing `editor`.
+    pub fn new(label: impl Into<String>, editor: Element) -> Self {
+        Self {
+            label: label.into(),
+            editor,
+        }
+    }
+    /// Creates a property which edits `component` of `entity` with a [ComponentEditor], labelled
+    /// with the name of the component.
+    pub fn component<T>(entity: EntityId, component: Component<T>) -> Self
+    where
+        T: Editor + ComponentValue + Clone + Debug + PartialEq + Sync + Send + 'static,
+    {
+        Self::new(
+            component_label(component),
+            ComponentEditor { entity, component }.el(),
+        )
+    }
+}
+
+/// The label of `component` in a [PropertyGrid], like "Space Between Items" for
+/// `space_between_items`.
+pub fn component_label<T: 'static>(component: Component<T>) -> String {
+    ComponentDesc::from(component)
+        .path_last()
+        .to_case(Case::Title)
+}
+
+#[element_component]
+/// Shows `properties` in two columns: their labels, and their editors.
+///
+/// The grid fits its children; with `fit_horizontal` set to [Fit::Parent], the editors fill the
+/// rest of its width instead.
+///
+/// Entity inspectors can show the components of an entity with [Property::component], and other
+/// UIs can show any element with [Property::new].
+pub fn PropertyGrid(
+    hooks: &mut Hooks,
+    /// The properties to show, in order.
+    properties: Vec<Property>,
+) -> Element {
+    let theme = use_theme(hooks);
+    Grid::el(
+        properties
+            .into_iter()
+            .flat_map(|property| [Text::el(property.label).small_style(), property.editor]),
+    )
+    .with(
+        grid_columns(),
+        grid_tracks([GridTrack::Auto, GridTrack::Fraction(1.)]),
+    )
+    .with(fit_horizontal(), Fit::Children)
+    .with(fit_vertical(), Fit::Children)
+    .with(space_between_items(), theme.spacing)
+}
+
+/// An editor for `component` of `entity`, which shows its current value and writes the edited
+/// value back to the entity.
+///
+/// Shows nothing if the entity doesn't have the component.
+#[derive(Debug, Clone)]
+pub struct ComponentEditor<T: 'static> {
+    /// The entity to edit.
+    pub entity: EntityId,
+    /// The component to edit.
+    pub component: Component<T>,
+}
+impl<T> ElementComponent for ComponentEditor<T>
+where
+    T: Editor + ComponentValue + Clone + Debug + PartialEq + Sync + Send + 'static,
+{
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let Self { entity, component } = *self;
+        let (value, set_value) =
+            use_state_with(hooks, |world| world.get_cloned(entity, component).ok());
+        // Editors don't get the world, so the edited value is written on the next frame
+        let edited = use_ref_with(hooks, |_| None::<T>);
+
+        use_frame(hooks, {
+            to_owned![value, edited];
+            move |world| {
+                if let Some(edited) = edited.lock().take() {
+                    world.set(entity, component, edited.clone()).ok();
+                    set_value(Some(edited));
+                    return;
+                }
+                // The value may be changed by something else
+                let current = world.get_cloned(entity, component).ok();
+                if current != value {
+                    set_value(current);
+                }
+            }
+        });
+
+        match value {
+            Some(value) => value.editor(
+                cb(move |value| *edited.lock() = Some(value)),
+                EditorOpts::default(),
+            ),
+            None => Element::new(),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Defines several kinds of window-sized screens.
 
 use crate::{
-    default_theme::app_background_color,
+    default_theme::use_theme,
     layout::{Dock, WindowSized},
     UIBase, UIExt,
 };
@@ -18,8 +18,8 @@ use glam::vec3;
 /// Contains an element with a background color and click area.
 pub struct ScreenContainer(pub Option<Element>);
 impl ElementComponent for ScreenContainer {
-    #[allow(clippy::clone_on_copy)]
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let background = *use_theme(hooks).app_background_color.set_a(0.99);
         if let Some(content) = self.0 {
             UIBase
                 .el()
@@ -28,7 +28,7 @@ impl ElementComponent for ScreenContainer {
                     .el()
                     .with(translation(), vec3(0., 0., 0.1))])
                 .el()
-                .with_background(app_background_color().set_a(0.99).clone().into())
+                .with_background(background.into())
                 .with_clickarea()
                 .el()])
         } else {
@@ -42,11 +42,11 @@ impl ElementComponent for ScreenContainer {
 pub struct PageScreen(pub Vec<Element>);
 define_el_function_for_vec_element_newtype!(PageScreen);
 impl ElementComponent for PageScreen {
-    #[allow(clippy::clone_on_copy)]
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let background = *use_theme(hooks).app_background_color.set_a(0.99);
         WindowSized(vec![Dock(self.0).el().with_padding_even(30.)])
             .el()
-            .with_background(app_background_color().set_a(0.99).clone().into())
+            .with_background(background.into())
             .with_clickarea()
             .el()
     }
@@ -57,11 +57,11 @@ impl ElementComponent for PageScreen {
 // TODO: isn't this just [PageScreen] with a single element?
 pub struct DialogScreen(pub Element);
 impl ElementComponent for DialogScreen {
-    #[allow(clippy::clone_on_copy)]
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
+        let background = *use_theme(hooks).app_background_color.set_a(0.99);
         WindowSized(vec![Dock(vec![self.0]).el().with_padding_even(30.)])
             .el()
-            .with_background(app_background_color().set_a(0.99).clone().into())
+            .with_background(background.into())
             .with_clickarea()
             .el()
     }
@@ -16,7 +16,7 @@ use ambient_guest_bridge::core::{
     rendering::components::scissors_recursive,
     transform::components::{local_to_parent, local_to_world, translation},
 };
-use glam::{uvec4, vec3, vec4, Vec2, Vec3, Vec4};
+use glam::{uvec4, vec3, vec4, UVec4, Vec2, Vec3, Vec4};
 
 use crate::{
     layout::{Flow, MeasureAbsolutePosition, MeasureSize},
@@ -56,8 +56,6 @@ pub fn ScrollArea(
         0.0
     };
     let mouse_over_count = use_ref_with(hooks, |_| 0);
-    let bar_height = outer_size.y / (outer_size.y + scroll_height) * outer_size.y;
-    let offset = scroll / scroll_height * (outer_size.y - bar_height);
     let id = use_ref_with(hooks, |_| None);
     let inner_flow_id = use_ref_with(hooks, |_| None);
     let (canvas_offset, set_canvas_offset) = use_state(hooks, Vec3::ZERO);
@@ -105,26 +103,10 @@ pub fn ScrollArea(
             {
                 let flow = Flow(vec![inner])
                     .el()
-                    .with(scissors_recursive(), {
-                        let (y, h) = if canvas_offset.y > 0.0 {
-                            (
-                                (canvas_offset.y * ratio) as u32,
-                                (outer_size.y * ratio) as u32,
-                            )
-                        } else {
-                            (0, ((outer_size.y + canvas_offset.y) * ratio) as u32)
-                        };
-
-                        let (x, w) = if canvas_offset.x > 0.0 {
-                            (
-                                (canvas_offset.x * ratio) as u32,
-                                (outer_size.x * ratio) as u32,
-                            )
-                        } else {
-                            (0, ((outer_size.x + canvas_offset.x) * ratio) as u32)
-                        };
-                        uvec4(x, y, w, h)
-                    })
+                    .with(
+                        scissors_recursive(),
+                        get_scissors(canvas_offset, outer_size, ratio),
+                    )
                     .on_spawned({
                         to_owned![inner_flow_id];
                         move |_world, flow_id, _| {
@@ -144,18 +126,7 @@ pub fn ScrollArea(
             },
             set_inner_size,
         ),
-        if scroll_height > 0.0 {
-            Rectangle::el()
-                .with(width(), 5.)
-                .with(height(), bar_height)
-                .with(border_radius(), Vec4::ONE * 4.0)
-                .with(background_color(), vec4(0.6, 0.6, 0.6, 1.0))
-                .with(local_to_parent(), Default::default())
-                .with(local_to_world(), Default::default())
-                .with(translation(), vec3(outer_size.x - 5.0, -offset, -0.1))
-        } else {
-            Element::new()
-        },
+        scroll_bar(outer_size, scroll, scroll_height),
     ]);
 
     match sizing {
@@ -163,3 +134,43 @@ pub fn ScrollArea(
         ScrollAreaSizing::FitParentWidth => canvas,
     }
 }
+
+/// The scroll bar of a scroll area of `outer_size`, scrolled by `scroll` out of `scroll_height`
+pub(crate) fn scroll_bar(outer_size: Vec2, scroll: f32, scroll_height: f32) -> Element {
+    if scroll_height <= 0.0 {
+        return Element::new();
+    }
+    let bar_height = outer_size.y / (outer_size.y + scroll_height) * outer_size.y;
+    let offset = scroll / scroll_height * (outer_size.y - bar_height);
+    Rectangle::el()
+        .with(width(), 5.)
+        .with(height(), bar_height)
+        .with(border_radius(), Vec4::ONE * 4.0)
+        .with(background_color(), vec4(0.6, 0.6, 0.6, 1.0))
+        .with(local_to_parent(), Default::default())
+        .with(local_to_world(), Default::default())
+        .with(translation(), vec3(outer_size.x - 5.0, -offset, -0.1))
+}
+
+/// The scissors, in physical pixels, which clip the content of a scroll area of `outer_size` at
+/// `canvas_offset` to it
+pub(crate) fn get_scissors(canvas_offset: Vec3, outer_size: Vec2, ratio: f32) -> UVec4 {
+    let (y, h) = if canvas_offset.y > 0.0 {
+        (
+            (canvas_offset.y * ratio) as u32,
+            (outer_size.y * ratio) as u32,
+        )
+    } else {
+        (0, ((outer_size.y + canvas_offset.y) * ratio) as u32)
+    };
+
+    let (x, w) = if canvas_offset.x > 0.0 {
+        (
+            (canvas_offset.x * ratio) as u32,
+            (outer_size.x * ratio) as u32,
+        )
+    } else {
+        (0, ((outer_size.x + canvas_offset.x) * ratio) as u32)
+    };
+    uvec4(x, y, w, h)
+}
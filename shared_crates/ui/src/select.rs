@@ -10,7 +10,7 @@ use glam::{vec4, Vec4};
 
 use crate::{
     button::{Button, ButtonStyle},
-    default_theme::{tooltip_background_color, SMALL_ROUNDING, STREET},
+    default_theme::use_theme,
     dropdown::Dropdown,
     layout::{FlowColumn, FlowRow},
     text::Text,
@@ -38,6 +38,7 @@ impl ElementComponent for DropdownSelect {
             inline,
         } = *self;
         let (show, set_show) = use_state(hooks, false);
+        let theme = use_theme(hooks);
         use_runtime_message::<messages::WindowMouseInput>(hooks, {
             to_owned![set_show];
             move |_world, event| {
@@ -77,7 +78,10 @@ impl ElementComponent for DropdownSelect {
                         })
                         .style(ButtonStyle::Card)
                         .el()
-                        .with(margin(), vec4(if i != 0 { STREET } else { 0. }, 0., 0., 0.))
+                        .with(
+                            margin(),
+                            vec4(if i != 0 { theme.spacing } else { 0. }, 0., 0., 0.),
+                        )
                     })
                     .collect(), //     vec![Bookcase(
                                 //     items
@@ -96,9 +100,9 @@ impl ElementComponent for DropdownSelect {
                                 // .set(orientation(), Orientation::Vertical)]
             )
             .el()
-            .with_padding_even(STREET)
-            .with(border_radius(), Vec4::ONE * SMALL_ROUNDING)
-            .with_background(tooltip_background_color().into()),
+            .with_padding_even(theme.spacing)
+            .with(border_radius(), Vec4::ONE * theme.small_rounding)
+            .with_background(theme.tooltip_background_color.into()),
             show,
         }
         .el()
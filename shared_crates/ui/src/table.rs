@@ -0,0 +1,350 @@
+//! Implements a [Table] whose columns can be sorted and resized, for lists like scoreboards.
+use std::cmp::Ordering;
+
+use ambient_element::{
+    element_component, to_owned, use_frame, use_ref_with, use_runtime_message, use_state,
+    use_state_with, Element, ElementComponentExt, Hooks,
+};
+use ambient_guest_bridge::{
+    core::{
+        app::components::cursor_position,
+        layout::{
+            components::{fit_horizontal, fit_vertical, height, padding, width},
+            types::Fit,
+        },
+        messages,
+    },
+    window::set_cursor,
+};
+use ambient_shared_types::{CursorIcon, MouseButton};
+use glam::{vec4, Vec4};
+
+use crate::{
+    default_theme::{use_theme, MOVE_DOWN_ICON, MOVE_UP_ICON},
+    layout::{FlowColumn, FlowRow},
+    text::Text,
+    UIBase, UIExt,
+};
+
+/// The narrowest a column can be resized to
+const MIN_COLUMN_WIDTH: f32 = 30.;
+/// The width of the handle at the right edge of a header, which resizes its column
+const RESIZE_HANDLE_WIDTH: f32 = 6.;
+
+/// A column of a [Table].
+#[derive(Debug, Clone)]
+pub struct TableColumn {
+    /// The title shown in the header.
+    pub title: String,
+    /// The initial width of the column, in pixels.
+    pub width: f32,
+    /// Whether the rows can be sorted by this column by clicking its header.
+    pub sortable: bool,
+}
+impl TableColumn {
+    /// Creates a new sortable column.
+    pub fn new(title: impl Into<String>, width: f32) -> Self {
+        Self {
+            title: title.into(),
+            width,
+            sortable: true,
+        }
+    }
+    /// Sets whether the rows can be sorted by this column.
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// The value a [TableCell] is sorted by.
+///
+/// Numbers are sorted before text, and cells without a key are sorted first.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub enum SortKey {
+    /// The cell isn't sorted by its value.
+    #[default]
+    None,
+    /// A number.
+    Number(f64),
+    /// Text, which is sorted case-insensitively.
+    Text(String),
+}
+
+/// A cell of a [Table]: the element shown, and the value it's sorted by.
+///
+/// Cells can be created from text and numbers, which are shown as [Text] and sorted by their
+/// value, or with [TableCell::new] for any element.
+#[derive(Debug, Clone)]
+pub struct TableCell {
+    /// The element shown in the cell.
+    pub element: Element,
+    /// The value the cell is sorted by.
+    pub sort_key: SortKey,
+}
+impl TableCell {
+    /// Creates a cell showing `element`, which isn't sorted by its value.
+    pub fn new(element: Element) -> Self {
+        Self {
+            element,
+            sort_key: SortKey::None,
+        }
+    }
+    /// Sets the value the cell is sorted by.
+    pub fn sort_key(mut self, sort_key: SortKey) -> Self {
+        self.sort_key = sort_key;
+        self
+    }
+}
+impl From<&str> for TableCell {
+    fn from(value: &str) -> Self {
+        value.to_string().into()
+    }
+}
+impl From<String> for TableCell {
+    fn from(value: String) -> Self {
+        Self::new(Text::el(value.clone())).sort_key(SortKey::Text(value.to_lowercase()))
+    }
+}
+impl From<f32> for TableCell {
+    fn from(value: f32) -> Self {
+        Self::new(Text::el(value.to_string())).sort_key(SortKey::Number(value as f64))
+    }
+}
+impl From<i32> for TableCell {
+    fn from(value: i32) -> Self {
+        Self::new(Text::el(value.to_string())).sort_key(SortKey::Number(value as f64))
+    }
+}
+impl From<u32> for TableCell {
+    fn from(value: u32) -> Self {
+        Self::new(Text::el(value.to_string())).sort_key(SortKey::Number(value as f64))
+    }
+}
+
+/// Sorts `rows` by the [SortKey] of their cell in the sorted column, as returned by `key`. Rows
+/// without that cell are sorted first.
+fn sort_rows<T>(rows: &mut [T], key: impl Fn(&T) -> Option<&SortKey>, descending: bool) {
+    // `sort_by` is stable, so equal rows keep their order
+    rows.sort_by(|a, b| {
+        let ordering = key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+#[element_component]
+/// A table of rows, with a header for each of its `columns`.
+///
+/// Clicking the header of a sortable column sorts the rows by it in ascending order, then in
+/// descending order, and then not at all. Dragging the right edge of a header resizes its column.
+/// Rows which compare equal keep their order.
+pub fn Table(
+    hooks: &mut Hooks,
+    /// The columns of the table.
+    columns: Vec<TableColumn>,
+    /// The rows of the table, with a cell for each column.
+    rows: Vec<Vec<TableCell>>,
+    /// The column the rows are sorted by at first, and whether they are sorted in descending order.
+    sort: Option<(usize, bool)>,
+) -> Element {
+    let theme = use_theme(hooks);
+    let (sort, set_sort) = use_state(hooks, sort);
+    let (widths, set_widths) = use_state_with(hooks, |_| {
+        columns
+            .iter()
+            .map(|column| column.width)
+            .collect::<Vec<_>>()
+    });
+    // The column being resized, where the cursor started and the width it had then
+    let resize = use_ref_with(hooks, |_| None::<(usize, f32, f32)>);
+
+    use_runtime_message::<messages::WindowMouseInput>(hooks, {
+        to_owned![resize];
+        move |world, event| {
+            if event.pressed || MouseButton::from(event.button) != MouseButton::Left {
+                return;
+            }
+            if resize.lock().take().is_some() {
+                set_cursor(world, CursorIcon::Default);
+            }
+        }
+    });
+    use_frame(hooks, {
+        to_owned![resize, widths, set_widths];
+        move |world| {
+            let Some((column, start, start_width)) = *resize.lock() else {
+                return;
+            };
+            let cursor = world.resource(cursor_position()).x;
+            let width = (start_width + cursor - start).max(MIN_COLUMN_WIDTH);
+            if widths.get(column) != Some(&width) {
+                let mut widths = widths.clone();
+                widths[column] = width;
+                set_widths(widths);
+            }
+        }
+    });
+
+    // Columns added after the table was created get their initial width
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| widths.get(i).copied().unwrap_or(column.width))
+        .collect::<Vec<_>>();
+    let cell = |inner: Element, cell_width: f32| {
+        FlowRow::el([inner])
+            .with(fit_horizontal(), Fit::None)
+            .with(width(), cell_width)
+            .with(padding(), vec4(0., theme.spacing, 0., theme.spacing))
+    };
+    let header_height = theme.font_size + theme.spacing * 2.;
+
+    let header = FlowRow::el(columns.iter().enumerate().map(|(i, column)| {
+        let arrow = match sort {
+            Some((column, descending)) if column == i => {
+                if descending {
+                    MOVE_DOWN_ICON
+                } else {
+                    MOVE_UP_ICON
+                }
+            }
+            _ => "",
+        };
+        let title = cell(
+            FlowRow::el([Text::el(column.title.clone()), Text::el(arrow)]),
+            widths[i] - RESIZE_HANDLE_WIDTH,
+        )
+        .with(fit_vertical(), Fit::None)
+        .with(height(), header_height)
+        .with(padding(), Vec4::splat(theme.spacing));
+        let title = if column.sortable {
+            let set_sort = set_sort.clone();
+            title
+                .with_clickarea()
+                .on_mouse_up(move |_, _, button| {
+                    if button != MouseButton::Left {
+                        return;
+                    }
+                    set_sort(match sort {
+                        Some((column, false)) if column == i => Some((i, true)),
+                        Some((column, true)) if column == i => None,
+                        _ => Some((i, false)),
+                    })
+                })
+                .el()
+        } else {
+            title
+        };
+        let handle = UIBase
+            .el()
+            .with(width(), RESIZE_HANDLE_WIDTH)
+            .with(height(), header_height)
+            .with_clickarea()
+            .on_mouse_enter(|world, _| set_cursor(world, CursorIcon::ColResize))
+            .on_mouse_leave({
+                to_owned![resize];
+                move |world, _| {
+                    if resize.lock().is_none() {
+                        set_cursor(world, CursorIcon::Default);
+                    }
+                }
+            })
+            .on_mouse_down({
+                to_owned![resize];
+                let start_width = widths[i];
+                move |world, _, button| {
+                    if button == MouseButton::Left {
+                        let cursor = world.resource(cursor_position()).x;
+                        *resize.lock() = Some((i, cursor, start_width));
+                    }
+                }
+            })
+            .el();
+        FlowRow::el([title, handle])
+    }))
+    .with_background(theme.cutout_color.into());
+
+    let mut rows = rows.into_iter().enumerate().collect::<Vec<_>>();
+    if let Some((column, descending)) = sort {
+        sort_rows(
+            &mut rows,
+            |(_, row)| row.get(column).map(|cell| &cell.sort_key),
+            descending,
+        );
+    }
+    let rows = rows.into_iter().enumerate().map(|(i, (key, row))| {
+        let row = FlowRow::el(
+            row.into_iter()
+                .zip(&widths)
+                .map(|(value, cell_width)| cell(value.element, *cell_width))
+                .collect::<Vec<_>>(),
+        )
+        .with(
+            padding(),
+            vec4(theme.spacing / 2., 0., theme.spacing / 2., 0.),
+        )
+        .key(key.to_string());
+        if i % 2 == 1 {
+            row.with_background(vec4(1., 1., 1., 0.03))
+        } else {
+            row
+        }
+    });
+
+    FlowColumn::el(std::iter::once(header).chain(rows).collect::<Vec<_>>())
+}
+impl Table {
+    /// Creates a new [Table] with `columns` and `rows`, which isn't sorted at first.
+    pub fn new(columns: Vec<TableColumn>, rows: Vec<Vec<TableCell>>) -> Self {
+        Self {
+            columns,
+            rows,
+            sort: None,
+        }
+    }
+    /// Sorts the rows by `column` at first, in descending order if `descending` is set.
+    pub fn sort_by(mut self, column: usize, descending: bool) -> Self {
+        self.sort = Some((column, descending));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(keys: &[Option<SortKey>], descending: bool) -> Vec<usize> {
+        let mut rows = keys.iter().enumerate().collect::<Vec<_>>();
+        sort_rows(&mut rows, |(_, key)| key.as_ref(), descending);
+        rows.into_iter().map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn mixed_keys_sort_missing_then_none_then_numbers_then_text() {
+        let keys = [
+            Some(SortKey::Text("b".to_string())),
+            Some(SortKey::Number(10.)),
+            None,
+            Some(SortKey::Text("a".to_string())),
+            Some(SortKey::None),
+            Some(SortKey::Number(2.)),
+        ];
+        assert_eq!(sorted(&keys, false), [2, 4, 5, 1, 3, 0]);
+        assert_eq!(sorted(&keys, true), [0, 3, 1, 5, 4, 2]);
+    }
+
+    #[test]
+    fn equal_rows_keep_their_order() {
+        let keys = [1., 0., 1., 0.].map(|n| Some(SortKey::Number(n)));
+        assert_eq!(sorted(&keys, false), [1, 3, 0, 2]);
+        assert_eq!(sorted(&keys, true), [0, 2, 1, 3]);
+
+        let keys = [SortKey::None, SortKey::None, SortKey::None].map(Some);
+        assert_eq!(sorted(&keys, false), [0, 1, 2]);
+        assert_eq!(sorted(&keys, true), [0, 1, 2]);
+    }
+}
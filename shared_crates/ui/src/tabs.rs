@@ -8,7 +8,7 @@ use glam::vec4;
 
 use crate::{
     button::{Button, ButtonStyle},
-    default_theme::STREET,
+    default_theme::use_theme,
     layout::{FlowColumn, FlowRow},
 };
 
@@ -25,12 +25,13 @@ pub struct TabBar<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'stati
 impl<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'static> ElementComponent
     for TabBar<T>
 {
-    fn render(self: Box<Self>, _: &mut Hooks) -> Element {
+    fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let Self {
             tabs,
             value,
             on_change,
         } = *self;
+        let spacing = use_theme(hooks).spacing;
         FlowRow(
             tabs.into_iter()
                 .map(|tab| {
@@ -41,7 +42,7 @@ impl<T: ToString + PartialEq + Clone + Debug + Sync + Send + 'static> ElementCom
                     .toggled(tab == value)
                     .style(ButtonStyle::Card)
                     .el()
-                    .with(padding(), vec4(0.0, STREET, 0.0, STREET))
+                    .with(padding(), vec4(0.0, spacing, 0.0, spacing))
                 })
                 .collect(),
         )
@@ -78,6 +79,7 @@ impl<T: ToString + PartialEq + Default + Clone + Debug + Sync + Send + 'static>
 {
     fn render(self: Box<Self>, hooks: &mut Hooks) -> Element {
         let (value, set_value) = use_state(hooks, T::default());
+        let spacing = use_theme(hooks).spacing;
         let selected_tab = self
             .tabs
             .iter()
@@ -95,6 +97,6 @@ impl<T: ToString + PartialEq + Default + Clone + Debug + Sync + Send + 'static>
             .el(),
             selected_tab().key(key),
         ])
-        .with(space_between_items(), STREET)
+        .with(space_between_items(), spacing)
     }
 }
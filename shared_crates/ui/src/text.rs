@@ -1,30 +1,31 @@
 //! Defines a text element.
 
-use crate::{UIBase, UIElement};
+use crate::{accessibility::AccessibilityExt, default_theme::use_theme, UIBase, UIElement};
 use ambient_element::{element_component, Element, ElementComponentExt, Hooks};
 use ambient_guest_bridge::core::{
     app::components::{main_scene, name, ui_scene},
     layout::components::{height, width},
     rendering::components::color,
-    text::components::{font_family, font_size, text},
+    text::components::{font_family, font_size, text, translation_args, translation_key},
     transform::components::{local_to_parent, local_to_world, mesh_to_local, mesh_to_world, scale},
 };
-use glam::{vec4, Mat4, Vec3};
+use glam::{Mat4, Vec3};
 
 /// A text element. Use the [text], [font_size], [font_family] and [color] components to set its state.
 #[element_component(without_el)]
-pub fn Text(_hooks: &mut Hooks) -> Element {
+pub fn Text(hooks: &mut Hooks) -> Element {
+    let theme = use_theme(hooks);
     UIBase
         .el()
         .init(width(), 1.)
         .init(height(), 1.)
         .init(mesh_to_local(), Mat4::IDENTITY)
-        .init(color(), vec4(0.6, 0.6, 0.6, 1.))
+        .init(color(), theme.body_text_color.into())
         .init(name(), "Text".to_string())
         .init(ui_scene(), ())
-        // .init_default(font_family())
+        .init(font_family(), theme.font_family)
         // .init_default(font_style())
-        .init(font_size(), 12.)
+        .init(font_size(), theme.font_size)
         .init(text(), "".to_string())
 }
 impl Text {
@@ -32,6 +33,21 @@ impl Text {
     pub fn el(value: impl Into<String>) -> Element {
         Text.el().with(text(), value.into())
     }
+    /// Creates a new text element with the string with `key` in the current locale, which is
+    /// translated again when the locale changes. See [localization](crate::localization).
+    pub fn tr(key: impl Into<String>) -> Element {
+        Text.el().with(translation_key(), key.into())
+    }
+    /// Like [Text::tr], with `name` and `value` arguments for the string. A `count` argument also
+    /// picks the plural form of the string.
+    pub fn tr_with(key: impl Into<String>, args: &[(&str, &str)]) -> Element {
+        Text::tr(key).with(
+            translation_args(),
+            args.iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect(),
+        )
+    }
 }
 impl From<&str> for UIElement {
     fn from(value: &str) -> Self {
@@ -58,15 +74,18 @@ pub fn FontAwesomeIcon(
     /// Whether the icon should be solid or not.
     solid: bool,
 ) -> Element {
-    Text::el(char::from_u32(icon).unwrap().to_string()).with(
-        font_family(),
-        if solid {
-            "FontAwesomeSolid"
-        } else {
-            "FontAwesome"
-        }
-        .to_string(),
-    )
+    Text::el(char::from_u32(icon).unwrap().to_string())
+        .with(
+            font_family(),
+            if solid {
+                "FontAwesomeSolid"
+            } else {
+                "FontAwesome"
+            }
+            .to_string(),
+        )
+        // Icons are private use characters, which would be read as gibberish
+        .hidden_from_accessibility()
 }
 
 #[element_component]
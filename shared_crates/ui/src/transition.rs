@@ -0,0 +1,428 @@
+//! Implements declarative transitions: a [Transition] animates the opacity, translation, scale and
+//! color of an element when it appears, disappears, or when its target [TransitionState] changes.
+//!
+//! The animation is driven by the element tree, so menus, tooltips and the like can animate
+//! without any per-frame code.
+use std::collections::HashMap;
+#[cfg(feature = "guest")]
+use std::time::Instant;
+
+use ambient_element::{
+    element_component, to_owned, use_frame, use_ref_with, use_rerender_signal, Element,
+    ElementComponentExt, Hooks,
+};
+use ambient_guest_bridge::{
+    core::{
+        hierarchy::components::children,
+        layout::{
+            components::{fit_horizontal, fit_vertical, height, width},
+            types::Fit,
+        },
+        rendering::components::color,
+        transform::components::{scale, translation},
+    },
+    ecs::{EntityId, World},
+};
+#[cfg(feature = "native")]
+use ambient_sys::time::Instant;
+use glam::{vec2, Vec2, Vec3, Vec4};
+
+use crate::UIBase;
+
+/// The depth of the animated element, relative to the [Transition]; the same as [UIBase]'s
+const Z: f32 = -0.001;
+
+/// An easing curve, which maps the progress of a transition to how far along its value is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Starts slow, then accelerates.
+    EaseInQuad,
+    /// Starts fast, then decelerates.
+    #[default]
+    EaseOutQuad,
+    /// Starts and ends slow.
+    EaseInOutQuad,
+    /// Like [Easing::EaseInQuad], but more pronounced.
+    EaseInCubic,
+    /// Like [Easing::EaseOutQuad], but more pronounced.
+    EaseOutCubic,
+    /// Like [Easing::EaseInOutQuad], but more pronounced.
+    EaseInOutCubic,
+    /// Overshoots the end a little, then settles back.
+    EaseOutBack,
+    /// Bounces against the end a few times.
+    EaseOutBounce,
+}
+impl Easing {
+    /// Maps `t`, from 0 to 1, through the curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => 1. - (1. - t) * (1. - t),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 {
+                    2. * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(2) / 2.
+                }
+            }
+            Easing::EaseInCubic => t * t * t,
+            Easing::EaseOutCubic => 1. - (1. - t).powi(3),
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+            Easing::EaseOutBack => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.;
+                1. + C3 * (t - 1.).powi(3) + C1 * (t - 1.).powi(2)
+            }
+            Easing::EaseOutBounce => {
+                const N1: f32 = 7.5625;
+                const D1: f32 = 2.75;
+                if t < 1. / D1 {
+                    N1 * t * t
+                } else if t < 2. / D1 {
+                    let t = t - 1.5 / D1;
+                    N1 * t * t + 0.75
+                } else if t < 2.5 / D1 {
+                    let t = t - 2.25 / D1;
+                    N1 * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D1;
+                    N1 * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+/// The animated properties of a [Transition].
+///
+/// The default state is fully opaque, unmoved and unscaled, with the element's own color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionState {
+    /// Multiplies the alpha of the element and all of its descendants.
+    pub opacity: f32,
+    /// Moves the element, in pixels, without affecting the layout.
+    pub translation: Vec2,
+    /// Scales the element around its center, without affecting the layout.
+    pub scale: f32,
+    /// Replaces the `color` of the element, which tints rects and colors text. Its own color is
+    /// kept if this is `None`.
+    pub color: Option<Vec4>,
+}
+impl Default for TransitionState {
+    fn default() -> Self {
+        Self {
+            opacity: 1.,
+            translation: Vec2::ZERO,
+            scale: 1.,
+            color: None,
+        }
+    }
+}
+impl TransitionState {
+    /// Sets the opacity.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+    /// Sets the translation.
+    pub fn translation(mut self, translation: Vec2) -> Self {
+        self.translation = translation;
+        self
+    }
+    /// Sets the scale.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+    /// Sets the color.
+    pub fn color(mut self, color: Vec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Interpolates between `self` and `other`. If only one of them has a color, that color is used.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            opacity: self.opacity + (other.opacity - self.opacity) * t,
+            translation: self.translation.lerp(other.translation, t),
+            scale: self.scale + (other.scale - self.scale) * t,
+            color: match (self.color, other.color) {
+                (Some(from), Some(to)) => Some(from.lerp(to, t)),
+                (from, to) => to.or(from),
+            },
+        }
+    }
+    /// Whether the element is shown as it is.
+    fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Animation {
+    from: TransitionState,
+    to: TransitionState,
+    start: Instant,
+    /// The last applied state
+    current: TransitionState,
+    /// Whether the element is removed when the animation ends
+    exiting: bool,
+    finished: bool,
+}
+impl Animation {
+    fn new(from: TransitionState, to: TransitionState, exiting: bool) -> Self {
+        Self {
+            from,
+            to,
+            start: Instant::now(),
+            current: from,
+            exiting,
+            finished: false,
+        }
+    }
+}
+
+#[element_component]
+/// Animates `inner` between [TransitionState]s.
+///
+/// When `inner` becomes visible, it animates from `enter` to `state`; when it's hidden, it
+/// animates to `exit` and is then removed. Changing `state` animates from the current state to the
+/// new one.
+///
+/// The transition doesn't affect the layout: `inner` is moved and scaled within the space of the
+/// [Transition] element, which fits `inner` by default. Layout components like `docking`, `margin`
+/// or `fit_horizontal` should be set on the [Transition] element rather than on `inner`; with
+/// [Fit::Parent] or [Fit::None], `inner` is resized to the [Transition] element instead.
+pub fn Transition(
+    hooks: &mut Hooks,
+    /// The element to animate.
+    inner: Element,
+    /// Whether `inner` is shown.
+    visible: bool,
+    /// The state of `inner` while it's visible.
+    state: TransitionState,
+    /// The state `inner` animates from when it appears. It appears immediately if this is `None`.
+    enter: Option<TransitionState>,
+    /// The state `inner` animates to before it's removed. It's removed immediately if this is `None`.
+    exit: Option<TransitionState>,
+    /// The duration of the animations, in seconds.
+    duration: f32,
+    /// The easing curve of the animations.
+    easing: Easing,
+) -> Element {
+    let rerender = use_rerender_signal(hooks);
+    // `None` while `inner` isn't shown
+    let animation = use_ref_with(hooks, |_| None::<Animation>);
+    let ids = use_ref_with(hooks, |_| (None::<EntityId>, None::<EntityId>));
+    // The color of each entity before it was faded, and the faded color
+    let colors = use_ref_with(hooks, |_| HashMap::<EntityId, (Vec4, Vec4)>::new());
+
+    // A change in the props is what starts an animation
+    {
+        let mut animation = animation.lock();
+        *animation = match *animation {
+            None if visible => Some(Animation::new(enter.unwrap_or(state), state, false)),
+            None => None,
+            Some(current) => match if visible { Some(state) } else { exit } {
+                Some(to) if current.to != to || current.exiting == visible => {
+                    Some(Animation::new(current.current, to, !visible))
+                }
+                Some(_) => Some(current),
+                None => None,
+            },
+        };
+    }
+
+    use_frame(hooks, {
+        to_owned![animation, ids, colors];
+        move |world| {
+            let (Some(wrapper), Some(content)) = *ids.lock() else {
+                return;
+            };
+            let mut guard = animation.lock();
+            let Some(current) = guard.as_mut() else {
+                return;
+            };
+            let size = sync_size(world, wrapper, content);
+
+            let t = if duration > 0. {
+                (current.start.elapsed().as_secs_f32() / duration).min(1.)
+            } else {
+                1.
+            };
+            if current.exiting && t >= 1. {
+                *guard = None;
+                drop(guard);
+                colors.lock().clear();
+                // The wrapper no longer has anything to fit
+                for (fit, size) in [(fit_horizontal(), width()), (fit_vertical(), height())] {
+                    if world.get(wrapper, fit).unwrap_or(Fit::Children) == Fit::Children {
+                        world.set(wrapper, size, 0.).ok();
+                    }
+                }
+                rerender();
+                return;
+            }
+            // A finished animation only has to be kept up if `inner` is shown differently
+            if current.finished && current.current.is_identity() {
+                return;
+            }
+            current.current = current.from.lerp(&current.to, easing.apply(t));
+            current.finished = t >= 1.;
+            let value = current.current;
+            drop(guard);
+
+            let offset = value.translation + size * (1. - value.scale) / 2.;
+            world.set(content, translation(), offset.extend(Z)).ok();
+            world
+                .set(content, scale(), vec2(value.scale, value.scale).extend(1.))
+                .ok();
+            apply_color(world, content, &value, &mut colors.lock());
+        }
+    });
+
+    let content = match *animation.lock() {
+        Some(current) => {
+            let value = current.current;
+            vec![UIBase
+                .el()
+                .init(scale(), vec2(value.scale, value.scale).extend(1.))
+                .init_default(children())
+                .children(vec![inner])
+                .on_spawned({
+                    to_owned![ids];
+                    move |_, id, _| ids.lock().1 = Some(id)
+                })]
+        }
+        None => Vec::new(),
+    };
+    UIBase
+        .el()
+        .init(fit_horizontal(), Fit::Children)
+        .init(fit_vertical(), Fit::Children)
+        .init_default(children())
+        .children(content)
+        .on_spawned(move |_, id, _| ids.lock().0 = Some(id))
+}
+impl Transition {
+    /// Creates a new [Transition] for `inner`, which fades in and out.
+    pub fn new(inner: Element) -> Self {
+        Self {
+            inner,
+            visible: true,
+            state: TransitionState::default(),
+            enter: Some(TransitionState::default().opacity(0.)),
+            exit: Some(TransitionState::default().opacity(0.)),
+            duration: 0.2,
+            easing: Easing::default(),
+        }
+    }
+    /// Sets whether `inner` is shown.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+    /// Sets the state of `inner` while it's visible.
+    pub fn state(mut self, state: TransitionState) -> Self {
+        self.state = state;
+        self
+    }
+    /// Sets the state `inner` animates from when it appears.
+    pub fn enter(mut self, enter: Option<TransitionState>) -> Self {
+        self.enter = enter;
+        self
+    }
+    /// Sets the state `inner` animates to before it's removed.
+    pub fn exit(mut self, exit: Option<TransitionState>) -> Self {
+        self.exit = exit;
+        self
+    }
+    /// Sets the duration of the animations, in seconds.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+    /// Sets the easing curve of the animations.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Fits the wrapper to its content, or the content to the wrapper on the axes it isn't fitted.
+/// Returns the size of the content.
+fn sync_size(world: &mut World, wrapper: EntityId, content: EntityId) -> Vec2 {
+    let inner = world
+        .get_cloned(content, children())
+        .ok()
+        .and_then(|children| children.first().copied());
+    let mut size = Vec2::ZERO;
+    for (axis, fit, component) in [
+        (0, fit_horizontal(), width()),
+        (1, fit_vertical(), height()),
+    ] {
+        let value = if world.get(wrapper, fit).unwrap_or(Fit::Children) == Fit::Children {
+            let value = inner
+                .and_then(|inner| world.get(inner, component).ok())
+                .unwrap_or(0.);
+            if world.get(wrapper, component).ok() != Some(value) {
+                world.set(wrapper, component, value).ok();
+            }
+            value
+        } else {
+            let value = world.get(wrapper, component).unwrap_or(0.);
+            if let Some(inner) = inner {
+                if world.get(inner, component).ok() != Some(value) {
+                    world.set(inner, component, value).ok();
+                }
+            }
+            value
+        };
+        world.set(content, component, value).ok();
+        size[axis] = value;
+    }
+    size
+}
+
+/// Fades the descendants of `content` by `value.opacity`, and replaces the color of its child.
+///
+/// The original colors are kept in `colors`; a color which was changed since it was faded, for
+/// example by a render of `inner`, becomes the new original.
+fn apply_color(
+    world: &mut World,
+    content: EntityId,
+    value: &TransitionState,
+    colors: &mut HashMap<EntityId, (Vec4, Vec4)>,
+) {
+    let roots = world.get_cloned(content, children()).unwrap_or_default();
+    let mut stack = roots.iter().map(|&id| (id, true)).collect::<Vec<_>>();
+    while let Some((id, is_root)) = stack.pop() {
+        if let Ok(children) = world.get_cloned(id, children()) {
+            stack.extend(children.into_iter().map(|id| (id, false)));
+        }
+        let current = world.get(id, color()).ok();
+        let original = match colors.get(&id) {
+            Some(&(original, faded)) if current == Some(faded) => original,
+            _ => current.unwrap_or(Vec4::ONE),
+        };
+        let base = match value.color {
+            Some(color) if is_root => color,
+            _ => original,
+        };
+        let faded = base * Vec3::ONE.extend(value.opacity);
+        if current != Some(faded) {
+            world.add_component(id, color(), faded).ok();
+        }
+        colors.insert(id, (original, faded));
+    }
+}
@@ -15,11 +15,12 @@ use ambient_guest_bridge::core::{
     ui::components::focusable,
 };
 use ambient_shared_types::MouseButton;
-use glam::{vec3, vec4, Vec2};
+use glam::{vec3, Vec2};
 
 use crate::{
     button::{Button, ButtonStyle},
     clickarea::MouseInput,
+    default_theme::{use_theme, Theme},
     layout::{FlowColumn, FlowRow},
     text::Text,
     with_rect, UIExt,
@@ -51,7 +52,7 @@ impl Default for WindowStyle {
                         .with(font_style(), FontStyle::Bold)
                         .with(font_size(), 14.),
                 ]))
-                .with_background(vec4(0.0, 0.0, 0.0, 0.5))
+                .with_background(Theme::current().panel_background_color.into())
                 .with(fit_horizontal(), Fit::Parent)
             }),
         }
@@ -98,6 +99,7 @@ pub fn Window(
     }
 
     let style = style.unwrap_or_default();
+    let theme = use_theme(hooks);
 
     let title = (style.title_bar)(title, close)
         .with(focusable(), hooks.instance_id().to_string())
@@ -110,7 +112,8 @@ pub fn Window(
         .el();
 
     (style.body)(
-        with_rect(FlowColumn::el([title, child])).with_background(vec4(0.0, 0.0, 0.0, 0.5)),
+        with_rect(FlowColumn::el([title, child]))
+            .with_background(theme.panel_background_color.into()),
     )
     .with(translation(), vec3(position.x, position.y, -0.001))
 }
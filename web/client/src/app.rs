@@ -14,6 +14,8 @@ pub fn MainApp(_hooks: &mut Hooks, server_url: String, settings: Settings) -> El
 
     GameClientView {
         url: server_url,
+        websocket_url: settings.websocket_url,
+        token: settings.token,
         user_id: settings
             .user_id
             .unwrap_or_else(ambient_client_shared::util::random_username),
@@ -39,6 +39,12 @@ pub struct Settings {
     user_id: Option<String>,
     #[serde(default)]
     debugger: bool,
+    /// The url of the WebSocket fallback of the server, for browsers without WebTransport
+    #[serde(default)]
+    websocket_url: Option<String>,
+    /// The token vouching for the user id, for servers which authenticate their players
+    #[serde(default)]
+    token: Option<String>,
 }
 
 fn default_filter() -> String {
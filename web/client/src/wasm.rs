@@ -1,5 +1,5 @@
 use ambient_core::asset_cache;
-// use ambient_audio::AudioMixer;
+// use ambient_audio::AudioBuses;
 use ambient_ecs::{EntityId, SystemGroup, World};
 use ambient_wasm::shared::{module_name, MessageType};
 
@@ -29,8 +29,8 @@ pub fn initialize(world: &mut World) -> anyhow::Result<()> {
     );
 
     // TODO: audio
-    // if let Some(mixer) = mixer {
-    //     world.add_resource(ambient_world_audio::audio_mixer(), mixer);
+    // if let Some(buses) = buses {
+    //     world.add_resource(ambient_world_audio::audio_buses(), buses);
     // }
 
     ambient_wasm::client::initialize(world, &assets, messenger)?;
@@ -7,6 +7,8 @@ declare global {
         audioStart: () => void;
         audioStop: () => void;
         setupAudio: () => void;
+        BASIS?: () => Promise<any>;
+        ambientBasis?: any;
     }
 }
 
@@ -62,6 +64,26 @@ async function setupAudio() {
 
 window.setupAudio = setupAudio;
 
+// Loads the Basis Universal transcoder, which transcodes the compressed textures of packages to a
+// format the GPU supports. Its files are copied to `public/basis` from the Basis Universal
+// repository.
+async function setupBasis() {
+    try {
+        await new Promise((resolve, reject) => {
+            const script = document.createElement("script");
+            script.src = "/basis/basis_transcoder.js";
+            script.onload = resolve;
+            script.onerror = reject;
+            document.head.appendChild(script);
+        });
+        const basis = await window.BASIS!();
+        basis.initializeBasis();
+        window.ambientBasis = basis;
+    } catch (e) {
+        console.warn("Failed to load the Basis Universal transcoder, compressed textures will not load:", e);
+    }
+}
+
 window.audioStart = () => {
     if (window.audio_ctx) {
         window.audio_ctx.resume();
@@ -96,6 +118,8 @@ import("ambient_web")
         const context = urlParams.get('context');
         const debuggerOn = urlParams.get('debugger') != null;
         const serverUrl = urlParams.get('server_url');
+        const websocketUrl = urlParams.get('websocket_url');
+        const token = urlParams.get('token');
         const maxPlayers = urlParams.get('max_players');
 
         let params = new URLSearchParams();
@@ -125,10 +149,13 @@ import("ambient_web")
             allowVersionMismatch: true,
             debugger: debuggerOn,
             userId: userId,
+            websocketUrl: websocketUrl,
+            token: token,
         };
 
         (async () => {
             try {
+                await setupBasis();
                 await ambient.start(target, url, settings)
             }
              catch (e) {